@@ -0,0 +1,49 @@
+//! Two-party AES-128 under half-gates garbled circuits
+//!
+//! Measures garbling, evaluation and the full protocol (including base OTs)
+//! per AES block, and prints the throughput report against the 1k AES/s target.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use mpc_api::garbled_circuits::{AesBlock, HalfGatesCircuit};
+use mpc_api::protocols::aes_gc::*;
+
+/// Benchmark garbling and evaluating one AES block
+fn bench_aes_garbling(c: &mut Criterion) {
+    let circuit = AesGcCircuit::builtin().unwrap();
+    let aes = circuit.aes();
+    let compiled = HalfGatesCircuit::compile(aes.circuit()).unwrap();
+    let key: AesBlock = rand::random();
+    let plaintext: AesBlock = rand::random();
+    let mut rng = rand::thread_rng();
+
+    let mut group = c.benchmark_group("aes_gc");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("garble", |b| {
+        b.iter(|| black_box(compiled.garble(&mut rng)));
+    });
+
+    let (garbled, encoding) = compiled.garble(&mut rng);
+    let labels = encoding.encode(&aes.encode_inputs(&key, &plaintext)).unwrap();
+    group.bench_function("evaluate", |b| {
+        b.iter(|| black_box(compiled.evaluate(black_box(&garbled), black_box(&labels)).unwrap()));
+    });
+
+    group.sample_size(10);
+    group.bench_function("two_party_protocol", |b| {
+        b.iter(|| black_box(execute_aes_gc(&circuit, &key, &plaintext).unwrap()));
+    });
+    group.finish();
+
+    let report = benchmark_aes_gc(&circuit, 200).unwrap();
+    println!(
+        "AES-GC: {:.0} AES/s garble+evaluate (target {:.0}, {}), {:.0} AES/s end to end, {} bytes garbled per block",
+        report.blocks_per_second,
+        AES_GC_TARGET_BLOCKS_PER_SECOND,
+        if report.meets_target() { "met" } else { "missed" },
+        report.end_to_end_blocks_per_second,
+        report.garbled_bytes_per_block,
+    );
+}
+
+criterion_group!(benches, bench_aes_garbling);
+criterion_main!(benches);
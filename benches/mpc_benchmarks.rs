@@ -4,7 +4,8 @@
 //! to help optimize the library and provide performance baselines.
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
-use mpc_api::*;
+use mpc_api::prelude::*;
+use mpc_api::secret_sharing::{field_add, field_mul, field_sub, NttDomain};
 
 /// Benchmark Shamir secret sharing operations
 fn bench_secret_sharing(c: &mut Criterion) {
@@ -282,6 +283,16 @@ fn bench_field_operations(c: &mut Criterion) {
         });
     });
     
+    let domain = NttDomain::new(10).unwrap();
+    let coefficients: Vec<u64> = (0..1024u64).map(|i| field_mul(i, b)).collect();
+    group.bench_function("ntt_1024", |bench| {
+        bench.iter(|| {
+            let mut values = coefficients.clone();
+            domain.forward(&mut values).unwrap();
+            black_box(values)
+        });
+    });
+    
     group.finish();
 }
 
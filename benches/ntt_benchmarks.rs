@@ -0,0 +1,89 @@
+//! Number-theoretic transform versus schoolbook polynomial arithmetic
+//!
+//! Compares polynomial multiplication at several sizes, Shamir share
+//! evaluation (Horner per point versus the subproduct tree) and
+//! BFV ring multiplication / ciphertext multiplication / modulus switching.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use mpc_api::homomorphic_encryption::{HomomorphicEncryption, MultiplicativelyHomomorphic, BFV};
+use mpc_api::homomorphic_encryption::bfv::BFVParameters;
+use mpc_api::secret_sharing::{ShamirSecretSharing, FIELD_PRIME};
+use mpc_api::utils::math::ntt::{multipoint_evaluate, polynomial_multiply_mod, schoolbook_multiply, NttPlan};
+use rand::Rng;
+
+fn random_poly(len: usize, modulus: u64) -> Vec<u64> {
+    let mut rng = rand::thread_rng();
+    (0..len).map(|_| rng.gen_range(0..modulus)).collect()
+}
+
+/// Benchmark linear polynomial multiplication
+fn bench_polynomial_multiply(c: &mut Criterion) {
+    let mut group = c.benchmark_group("polynomial_multiply");
+    for size in [64usize, 256, 1024, 4096] {
+        let a = random_poly(size, FIELD_PRIME);
+        let b = random_poly(size, FIELD_PRIME);
+        group.bench_with_input(BenchmarkId::new("ntt", size), &size, |bench, _| {
+            bench.iter(|| black_box(polynomial_multiply_mod(black_box(&a), black_box(&b), FIELD_PRIME).unwrap()));
+        });
+        if size <= 1024 {
+            group.bench_with_input(BenchmarkId::new("schoolbook", size), &size, |bench, _| {
+                bench.iter(|| black_box(schoolbook_multiply(black_box(&a), black_box(&b), FIELD_PRIME)));
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Benchmark multiplication in Z_q[x]/(x^n + 1)
+fn bench_ring_multiply(c: &mut Criterion) {
+    // first prime of the default BFV modulus chain
+    let modulus = BFVParameters::default().moduli[0];
+    let mut group = c.benchmark_group("ring_multiply");
+    for size in [256usize, 1024, 4096] {
+        let plan = NttPlan::new(modulus, size).unwrap();
+        let a = random_poly(size, modulus);
+        let b = random_poly(size, modulus);
+        group.bench_with_input(BenchmarkId::new("negacyclic_ntt", size), &size, |bench, _| {
+            bench.iter(|| black_box(plan.negacyclic_multiply(black_box(&a), black_box(&b)).unwrap()));
+        });
+        if size <= 1024 {
+            group.bench_with_input(BenchmarkId::new("schoolbook", size), &size, |bench, _| {
+                bench.iter(|| black_box(schoolbook_multiply(black_box(&a), black_box(&b), modulus)));
+            });
+        }
+    }
+    group.finish();
+
+    let (pk, _) = BFV::keygen().unwrap();
+    let c1 = BFV::encrypt(&pk, &3).unwrap();
+    let c2 = BFV::encrypt(&pk, &5).unwrap();
+    c.bench_function("bfv_multiply_ciphertexts", |bench| {
+        bench.iter(|| black_box(BFV::multiply_ciphertexts(&pk, black_box(&c1), black_box(&c2)).unwrap()));
+    });
+    c.bench_function("bfv_modulus_switch", |bench| {
+        bench.iter(|| black_box(BFV::modulus_switch(&pk, black_box(&c1)).unwrap()));
+    });
+}
+
+/// Benchmark evaluating a sharing polynomial (threshold = parties) at every party's point
+fn bench_shamir_evaluation(c: &mut Criterion) {
+    let scheme = ShamirSecretSharing::new();
+    let mut group = c.benchmark_group("shamir_evaluation");
+    group.sample_size(10);
+    for parties in [1024usize, 4096, 8192] {
+        let coefficients = random_poly(parties, FIELD_PRIME);
+        let xs: Vec<u64> = (1..=parties as u64).collect();
+        group.bench_with_input(BenchmarkId::new("horner", parties), &parties, |bench, _| {
+            bench.iter(|| {
+                black_box(xs.iter().map(|&x| scheme.evaluate_polynomial(&coefficients, x)).collect::<Vec<_>>())
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("subproduct_tree", parties), &parties, |bench, _| {
+            bench.iter(|| black_box(multipoint_evaluate(black_box(&coefficients), black_box(&xs), FIELD_PRIME).unwrap()));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_polynomial_multiply, bench_ring_multiply, bench_shamir_evaluation);
+criterion_main!(benches);
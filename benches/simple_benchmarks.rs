@@ -3,7 +3,8 @@
 //! Demonstrates basic performance testing for key MPC operations
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use mpc_api::*;
+use mpc_api::prelude::*;
+use mpc_api::secret_sharing::{field_add, field_mul};
 
 /// Benchmark basic secret sharing operations
 fn bench_basic_secret_sharing(c: &mut Criterion) {
@@ -91,7 +91,8 @@
 //! - Pedersen承诺依赖离散对数假设
 //! - Merkle树的安全性依赖底层哈希函数的抗碰撞性
 
-use mpc_api::{*, Result};
+use mpc_api::prelude::*;
+use mpc_api::secret_sharing::field_add;
 
 /// 1. 哈希承诺方案演示
 /// 
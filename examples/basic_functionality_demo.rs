@@ -78,7 +78,8 @@
 //! - **承诺方案**: O(1) 承诺和验证操作
 //! - **Merkle 树**: O(log n) 证明生成和验证
 
-use mpc_api::{*, Result};
+use mpc_api::prelude::*;
+use mpc_api::secret_sharing::{field_add, field_inv, field_mul, field_sub};
 use mpc_api::secret_sharing::FIELD_PRIME;
 
 /// 有限域运算基础演示
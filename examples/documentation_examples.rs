@@ -32,7 +32,8 @@
 //! of the MPC API. These examples are designed to be included in the library's
 //! documentation and serve as a reference for users.
 
-use mpc_api::*;
+use mpc_api::prelude::*;
+use mpc_api::secret_sharing::field_add;
 
 /// # Basic Secret Sharing Example
 /// 
@@ -45,7 +46,7 @@ use mpc_api::*;
 /// - The threshold property (any k shares can reconstruct, but k-1 cannot)
 /// 
 /// ```rust
-/// use mpc_api::*;
+/// use mpc_api::prelude::*;
 /// 
 /// # fn main() -> Result<()> {
 /// // Step 1: Define the secret and sharing parameters
@@ -100,7 +101,7 @@ fn basic_secret_sharing_example() -> Result<()> {
 /// - How to perform secure computations on shared data
 /// 
 /// ```rust
-/// use mpc_api::*;
+/// use mpc_api::prelude::*;
 /// 
 /// # fn main() -> Result<()> {
 /// // Two secret values that we want to add securely
@@ -181,7 +182,7 @@ fn homomorphic_operations_example() -> Result<()> {
 /// - Commitment verification
 /// 
 /// ```rust
-/// use mpc_api::*;
+/// use mpc_api::prelude::*;
 /// 
 /// # fn main() -> Result<()> {
 /// // Commit to a secret value
@@ -254,7 +255,7 @@ fn commitment_schemes_example() -> Result<()> {
 /// - How triples enable secure multiplication
 /// 
 /// ```rust
-/// use mpc_api::*;
+/// use mpc_api::prelude::*;
 /// 
 /// # fn main() -> Result<()> {
 /// // Setup parameters for Beaver triple generation
@@ -321,7 +322,7 @@ fn beaver_triples_example() -> Result<()> {
 /// - Security against tampering
 /// 
 /// ```rust
-/// use mpc_api::*;
+/// use mpc_api::prelude::*;
 /// 
 /// # fn main() -> Result<()> {
 /// // Generate a random HMAC key
@@ -380,7 +381,7 @@ fn message_authentication_example() -> Result<()> {
 /// - Real-world usage patterns
 /// 
 /// ```rust
-/// use mpc_api::*;
+/// use mpc_api::prelude::*;
 /// 
 /// # fn main() -> Result<()> {
 /// // Scenario: Three parties want to compute the sum of their private inputs
@@ -477,7 +478,7 @@ fn complete_mpc_protocol_example() -> Result<()> {
 /// - Defensive programming practices
 /// 
 /// ```rust
-/// use mpc_api::*;
+/// use mpc_api::prelude::*;
 /// 
 /// # fn main() -> Result<()> {
 /// // Example 1: Handle invalid threshold
@@ -36,8 +36,7 @@ use mpc_api::{
         protocol::NetworkMessage,
         common::{NetworkResult, NetworkError},
     },
-    secret_sharing::{ShamirSecretSharing, Share},
-    SecretSharing,
+    secret_sharing::{SecretSharing, ShamirSecretSharing, Share},
 };
 use base64::{Engine, engine::general_purpose};
 use serde::{Serialize, Deserialize};
@@ -30,7 +30,8 @@
 //! This file contains real-world examples demonstrating how to use the MPC API
 //! for common secure multi-party computation scenarios.
 
-use mpc_api::*;
+use mpc_api::prelude::*;
+use mpc_api::secret_sharing::{field_add, field_mul, field_sub};
 
 /// Example 1: Secure Average Salary Calculation
 /// 
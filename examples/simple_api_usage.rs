@@ -30,7 +30,8 @@
 //! 展示 MPC API 中实际可用功能的基本使用方法。
 //! 这些示例都是可以编译和运行的。
 
-use mpc_api::{*, Result};
+use mpc_api::prelude::*;
+use mpc_api::secret_sharing::{field_add, field_inv, field_mul, field_sub};
 
 /// 1. 哈希承诺演示
 pub fn hash_commitment_demo() -> Result<()> {
@@ -36,8 +36,7 @@ use mpc_api::{
         protocol::NetworkMessage,
         common::{NetworkResult, NetworkError},
     },
-    secret_sharing::ShamirSecretSharing,
-    SecretSharing,
+    secret_sharing::{SecretSharing, ShamirSecretSharing},
 };
 use serde::{Serialize, Deserialize};
 use std::{
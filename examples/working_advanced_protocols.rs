@@ -27,7 +27,8 @@
 //! 
 //! 展示MPC API中高级协议的实际使用方法，所有示例都可以编译和运行。
 
-use mpc_api::{*, Result};
+use mpc_api::prelude::*;
+use mpc_api::secret_sharing::field_add;
 
 /// 1. 哈希承诺方案演示
 pub mod hash_commitment_examples {
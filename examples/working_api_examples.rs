@@ -35,6 +35,7 @@
 use mpc_api::{
     secret_sharing::{ShamirSecretSharing, SecretSharing, AdditiveSecretSharing, AdditiveSecretSharingScheme, field_add, field_mul, field_sub, field_inv, FIELD_PRIME},
     beaver_triples::{TrustedPartyBeaverGenerator, BeaverTripleGenerator, secure_multiply, verify_triple_batch},
+    protocols::SealedBidAuction,
     Result
 };
 
@@ -352,22 +353,15 @@ pub mod application_examples {
             println!("{} 提交出价分享", bidders[i]);
         }
         
-        // 步骤2: 使用 Beaver 三元组进行比较（简化版）
-        // 在实际应用中，这里会使用更复杂的比较协议
-        
-        // 为了简化，我们重构所有出价进行比较
+        // 步骤2: 在分享上做安全比较，锦标赛求出最高出价和获胜者
+        // 出价不重构，只公开获胜者和成交价
         println!("\n拍卖结果计算中...");
         
-        let mut max_bid = 0u64;
-        let mut winner_index = 0;
-        
-        for (i, shares) in bid_shares.iter().enumerate() {
-            let bid = ShamirSecretSharing::reconstruct(&shares[0..threshold], threshold)?;
-            if bid > max_bid {
-                max_bid = bid;
-                winner_index = i;
-            }
-        }
+        let auction = SealedBidAuction::new(party_count, threshold, 16)?;
+        let mut generator = TrustedPartyBeaverGenerator::new(party_count, threshold, 0, None)?;
+        let outcome = auction.run(&bid_shares, true, &mut generator)?.result;
+        let winner_index = outcome.winner;
+        let max_bid = outcome.price.expect("price is revealed");
         
         println!("拍卖结果:");
         println!("获胜者: {}", bidders[winner_index]);
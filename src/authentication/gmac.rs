@@ -16,6 +16,7 @@
 //! - 密码学协议中的认证原语
 
 use crate::{MpcError, Result};
+use crate::utils::math::gf2k;
 use super::{MessageAuthenticationCode, UnforgeableMac, SecureMac};
 use rand::{Rng, thread_rng};
 use serde::{Deserialize, Serialize};
@@ -27,9 +28,6 @@ const GMAC_TAG_SIZE: usize = 16;
 /// GMAC 块大小（字节）- 128位
 const GMAC_BLOCK_SIZE: usize = 16;
 
-/// GF(2^128) 不可约多项式: x^128 + x^7 + x^2 + x + 1
-const GF128_POLYNOMIAL: u128 = 0x87;
-
 /// GMAC 密钥结构
 /// 
 /// 包含用于 GMAC 认证的密钥材料。
@@ -210,7 +208,7 @@ impl GMAC {
     
     /// 在 GF(2^128) 中进行乘法运算
     /// 
-    /// 实现伽罗瓦域 GF(2^128) 中的乘法运算，使用不可约多项式进行约简。
+    /// 实现伽罗瓦域 GF(2^128) 中的乘法运算，委托给 `utils::math::gf2k::gf128_mul`。
     /// 这是 GMAC 算法的核心运算。
     /// 
     /// # 参数
@@ -220,30 +218,7 @@ impl GMAC {
     /// # 返回值
     /// 返回 a * b 在 GF(2^128) 中的结果
     pub fn gf128_mul(a: u128, b: u128) -> u128 {
-        let mut result = 0u128;
-        let mut temp_a = a;
-        let mut temp_b = b;
-        
-        for _ in 0..128 {
-            if temp_b & 1 != 0 {
-                result ^= temp_a;
-            }
-            
-            let overflow = temp_a & (1u128 << 127) != 0;
-            temp_a <<= 1;
-            
-            if overflow {
-                temp_a ^= GF128_POLYNOMIAL;
-            }
-            
-            temp_b >>= 1;
-            
-            if temp_b == 0 {
-                break;
-            }
-        }
-        
-        result
+        gf2k::gf128_mul(a, b)
     }
     
     fn secure_compare(a: &[u8], b: &[u8]) -> bool {
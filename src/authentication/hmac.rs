@@ -25,6 +25,8 @@
 use crate::{MpcError, Result};
 // use crate::secret_sharing::FIELD_PRIME; // Unused import
 use super::{MessageAuthenticationCode, UnforgeableMac, SecureMac};
+use crate::utils::canonical_encode;
+use crate::utils::memory::SecretValue;
 use rand::{Rng, thread_rng};
 use sha2::{Sha256, Digest};
 use serde::{Deserialize, Serialize};
@@ -39,11 +41,11 @@ const HMAC_TAG_SIZE: usize = 32;
 /// HMAC 密钥结构
 /// 
 /// 封装了用于 HMAC 计算的密钥数据。密钥长度固定为 32 字节，
-/// 提供了足够的安全强度。
+/// 提供了足够的安全强度。密钥在释放时清零，序列化时不输出。
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HmacKey {
     /// 32 字节的密钥数据
-    pub key: [u8; HMAC_KEY_SIZE],
+    pub key: SecretValue<[u8; HMAC_KEY_SIZE]>,
 }
 
 /// HMAC 认证标签结构
@@ -80,7 +82,7 @@ impl MessageAuthenticationCode for HMAC {
         for i in 0..HMAC_KEY_SIZE {
             key[i] = rng.gen();
         }
-        HmacKey { key }
+        HmacKey { key: SecretValue::new(key) }
     }
     
     /// 为消息生成 HMAC 认证标签
@@ -96,7 +98,7 @@ impl MessageAuthenticationCode for HMAC {
     /// 
     /// 返回包含认证标签的 `HmacTag` 实例
     fn authenticate(key: &Self::Key, message: &Self::Message) -> Self::Tag {
-        let tag = Self::compute_hmac(&key.key, message);
+        let tag = Self::compute_hmac(&key.key[..], message);
         HmacTag { tag }
     }
     
@@ -208,7 +210,7 @@ impl HMAC {
     }
     
     pub fn verify_u64(key: &HmacKey, value: u64, tag: &HmacTag) -> bool {
-        let computed_tag = Self::compute_hmac_u64(&key.key, value);
+        let computed_tag = Self::compute_hmac_u64(&key.key[..], value);
         Self::secure_compare(&computed_tag, &tag.tag)
     }
     
@@ -234,19 +236,17 @@ impl HMAC {
     
     // HMAC for secret shares
     pub fn authenticate_share(key: &HmacKey, share_value: u64, share_index: usize) -> HmacTag {
-        let mut message = Vec::new();
-        message.extend_from_slice(&share_value.to_le_bytes());
-        message.extend_from_slice(&share_index.to_le_bytes());
-        
-        Self::authenticate(key, &message)
+        Self::authenticate(key, &Self::share_message(share_value, share_index))
     }
     
     pub fn verify_share(key: &HmacKey, share_value: u64, share_index: usize, tag: &HmacTag) -> bool {
-        let mut message = Vec::new();
-        message.extend_from_slice(&share_value.to_le_bytes());
-        message.extend_from_slice(&share_index.to_le_bytes());
-        
-        Self::verify(key, &message, tag)
+        Self::verify(key, &Self::share_message(share_value, share_index), tag)
+    }
+    
+    // Canonical encoding of a share, independent of the platform's usize width
+    fn share_message(share_value: u64, share_index: usize) -> Vec<u8> {
+        canonical_encode(&(share_value, share_index))
+            .expect("integer tuples always have a canonical encoding")
     }
     
     // HMAC-based key derivation
@@ -284,7 +284,7 @@ impl HMAC {
             }
         }
         
-        HmacKey { key: result }
+        HmacKey { key: SecretValue::new(result) }
     }
 }
 
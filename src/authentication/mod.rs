@@ -9,6 +9,7 @@
 //! - **Poly1305**: 高性能的一次性消息认证码，常与 ChaCha20 流密码配合使用
 //! - **GMAC**: 基于 Galois/Counter Mode 的消息认证码，提供认证加密
 //! - **CMAC**: 基于分组密码的消息认证码，使用 AES 作为底层分组密码
+//! - **Nonce 管理**: Poly1305/GMAC 的计数器 nonce 序列、重放检测和 SIV 抗误用认证加密
 //! 
 //! ## 安全特性
 //! 
@@ -33,11 +34,13 @@ pub mod hmac;
 pub mod poly1305;
 pub mod gmac;
 pub mod cmac;
+pub mod nonce;
 
 pub use hmac::*;
 pub use poly1305::*;
 pub use gmac::*;
 pub use cmac::*;
+pub use nonce::*;
 
 // use crate::{MpcError, Result}; // Unused imports
 use serde::{Deserialize, Serialize};
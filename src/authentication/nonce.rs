@@ -0,0 +1,285 @@
+//! # Nonce 管理 (Nonce Management)
+//!
+//! Poly1305 和 GMAC 都要求每个一次性密钥只认证一条消息：用同一个密钥认证两条
+//! 不同的消息，攻击者就可以解出密钥并伪造任意标签。直接调用 `authenticate` 时
+//! 没有任何机制阻止这种误用。本模块提供：
+//!
+//! - **计数器 nonce 序列**: `NonceSequence` 按 `前缀 || 计数器` 生成 nonce，
+//!   计数器可以持久化到 `StorageBackend`，进程重启后不会重复
+//! - **一次性密钥派生**: `NonceBasedMac` 由主密钥和 nonce 派生 Poly1305/GMAC 的
+//!   一次性密钥；`NonceManagedMac` 发送时自动取下一个 nonce，接收时拒绝重复的 nonce
+//! - **抗误用 AEAD**: `SivAead` 以 SIV 方式把 nonce、关联数据和明文一起计算合成 IV，
+//!   nonce 重复时只泄露两条消息是否完全相同
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::authentication::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let master_key = [7u8; 32];
+//! let mut sender = NonceManagedMac::<Poly1305>::new(&master_key, NonceSequence::new())?;
+//! let mut receiver = NonceManagedMac::<Poly1305>::new(&master_key, NonceSequence::new())?;
+//!
+//! let (nonce, tag) = sender.authenticate(b"round 1")?;
+//! assert!(receiver.verify(&nonce, b"round 1", &tag)?);
+//! // 同一个 nonce 不能被接受两次
+//! assert!(receiver.verify(&nonce, b"round 1", &tag).is_err());
+//!
+//! let aead = SivAead::new(&master_key)?;
+//! let sealed = aead.seal(&nonce, b"header", b"secret share");
+//! assert_eq!(aead.open(&nonce, b"header", &sealed)?, b"secret share");
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{MessageAuthenticationCode, GmacKey, Poly1305Key, GMAC, HMAC, Poly1305};
+use crate::storage::{load_value, store_value, StorageBackend};
+use crate::{MpcError, Result};
+use rand::{Rng, thread_rng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// nonce 长度（字节）
+pub const NONCE_SIZE: usize = 16;
+
+/// 保存 nonce 计数器的命名空间
+const NONCE_NAMESPACE: &str = "nonce-sequences";
+
+/// nonce
+pub type Nonce = [u8; NONCE_SIZE];
+
+/// 持久化的序列状态
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct SequenceState {
+    prefix: [u8; 8],
+    next: u64,
+}
+
+/// 计数器 nonce 序列
+///
+/// nonce 为 8 字节随机前缀加 8 字节大端计数器。不持久化的序列每次创建都使用新的
+/// 随机前缀；持久化的序列在返回 nonce 之前先把计数器写入存储。
+#[derive(Debug)]
+pub struct NonceSequence {
+    state: SequenceState,
+    storage: Option<(Arc<dyn StorageBackend>, Vec<u8>)>,
+}
+
+impl NonceSequence {
+    /// 创建只保存在内存中的序列
+    pub fn new() -> Self {
+        Self {
+            state: SequenceState { prefix: thread_rng().gen(), next: 0 },
+            storage: None,
+        }
+    }
+
+    /// 创建（或恢复）持久化的序列
+    ///
+    /// # 参数
+    /// - `backend`: 存储后端
+    /// - `key_id`: 密钥标识，每个密钥一个序列
+    pub fn persistent(backend: Arc<dyn StorageBackend>, key_id: &[u8]) -> Result<Self> {
+        let state = match load_value(backend.as_ref(), NONCE_NAMESPACE, key_id)? {
+            Some(state) => state,
+            None => {
+                let state = SequenceState { prefix: thread_rng().gen(), next: 0 };
+                store_value(backend.as_ref(), NONCE_NAMESPACE, key_id, &state)?;
+                state
+            }
+        };
+        Ok(Self {
+            state,
+            storage: Some((backend, key_id.to_vec())),
+        })
+    }
+
+    /// 已经发出的 nonce 数量
+    pub fn issued(&self) -> u64 {
+        self.state.next
+    }
+
+    /// 取下一个 nonce
+    pub fn next_nonce(&mut self) -> Result<Nonce> {
+        let counter = self.state.next;
+        let next = counter.checked_add(1)
+            .ok_or_else(|| MpcError::AuthenticationError("Nonce sequence exhausted".to_string()))?;
+        if let Some((backend, key_id)) = &self.storage {
+            store_value(backend.as_ref(), NONCE_NAMESPACE, key_id, &SequenceState { next, ..self.state })?;
+        }
+        self.state.next = next;
+
+        let mut nonce = [0u8; NONCE_SIZE];
+        nonce[..8].copy_from_slice(&self.state.prefix);
+        nonce[8..].copy_from_slice(&counter.to_be_bytes());
+        Ok(nonce)
+    }
+}
+
+impl Default for NonceSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 由主密钥和 nonce 派生一次性密钥的 MAC
+pub trait NonceBasedMac: MessageAuthenticationCode<Message = Vec<u8>> {
+    /// 派生 nonce 对应的一次性密钥
+    fn one_time_key(master_key: &[u8], nonce: &Nonce) -> Result<Self::Key>;
+}
+
+impl NonceBasedMac for Poly1305 {
+    fn one_time_key(master_key: &[u8], nonce: &Nonce) -> Result<Poly1305Key> {
+        let material = HMAC::derive_key(master_key, &[b"poly1305".as_slice(), nonce].concat(), 32);
+        Poly1305::generate_one_time_key(&material, nonce)
+    }
+}
+
+impl NonceBasedMac for GMAC {
+    fn one_time_key(master_key: &[u8], nonce: &Nonce) -> Result<GmacKey> {
+        // 哈希子密钥对同一主密钥固定，只有最终的掩码随 nonce 变化，与 GCM 相同
+        let mut h = [0u8; 16];
+        let mut k = [0u8; 16];
+        h.copy_from_slice(&HMAC::derive_key(master_key, b"gmac-hash-key", 16));
+        k.copy_from_slice(&HMAC::derive_key(master_key, &[b"gmac-mask".as_slice(), nonce].concat(), 16));
+        Ok(GmacKey { h, k })
+    }
+}
+
+/// 强制 nonce 唯一的 MAC
+///
+/// 发送方向用 `NonceSequence` 生成 nonce，或者通过 `authenticate_with_nonce`
+/// 使用外部 nonce（重复时返回错误）；接收方向记录已接受的 nonce，拒绝重放。
+#[derive(Debug)]
+pub struct NonceManagedMac<M: NonceBasedMac> {
+    master_key: Vec<u8>,
+    sequence: NonceSequence,
+    /// 本方已用于认证的 nonce
+    issued: HashSet<Nonce>,
+    /// 已通过验证的 nonce
+    accepted: HashSet<Nonce>,
+    _mac: PhantomData<M>,
+}
+
+impl<M: NonceBasedMac> NonceManagedMac<M> {
+    /// 创建 MAC
+    ///
+    /// # 参数
+    /// - `master_key`: 主密钥，至少 16 字节
+    /// - `sequence`: 发送方向使用的 nonce 序列
+    pub fn new(master_key: &[u8], sequence: NonceSequence) -> Result<Self> {
+        if master_key.len() < 16 {
+            return Err(MpcError::AuthenticationError("Master key must be at least 16 bytes".to_string()));
+        }
+        Ok(Self {
+            master_key: master_key.to_vec(),
+            sequence,
+            issued: HashSet::new(),
+            accepted: HashSet::new(),
+            _mac: PhantomData,
+        })
+    }
+
+    /// 用下一个 nonce 认证消息
+    pub fn authenticate(&mut self, message: &[u8]) -> Result<(Nonce, M::Tag)> {
+        let nonce = self.sequence.next_nonce()?;
+        let tag = self.authenticate_with_nonce(&nonce, message)?;
+        Ok((nonce, tag))
+    }
+
+    /// 用调用方提供的 nonce 认证消息，nonce 已经用过时返回错误
+    pub fn authenticate_with_nonce(&mut self, nonce: &Nonce, message: &[u8]) -> Result<M::Tag> {
+        if !self.issued.insert(*nonce) {
+            return Err(MpcError::AuthenticationError("Nonce reused for authentication".to_string()));
+        }
+        let key = M::one_time_key(&self.master_key, nonce)?;
+        Ok(M::authenticate(&key, &message.to_vec()))
+    }
+
+    /// 验证消息
+    ///
+    /// # 返回值
+    /// 标签有效时返回 `true` 并记录 nonce；nonce 已被接受过时返回错误
+    pub fn verify(&mut self, nonce: &Nonce, message: &[u8], tag: &M::Tag) -> Result<bool> {
+        if self.accepted.contains(nonce) {
+            return Err(MpcError::AuthenticationError("Nonce replayed".to_string()));
+        }
+        let key = M::one_time_key(&self.master_key, nonce)?;
+        let valid = M::verify(&key, &message.to_vec(), tag);
+        if valid {
+            self.accepted.insert(*nonce);
+        }
+        Ok(valid)
+    }
+}
+
+/// SIV 密文
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SivCiphertext {
+    /// 合成 IV，同时作为认证标签
+    pub siv: [u8; 16],
+    /// 密文
+    pub ciphertext: Vec<u8>,
+}
+
+/// SIV 方式的抗误用认证加密
+///
+/// 合成 IV = HMAC(K_mac, aad || nonce || 明文) 的前 16 字节，密文 = 明文 ⊕ PRF(K_enc, IV)。
+/// 即使 nonce 重复，只要 (aad, 明文) 不同，合成 IV 就不同；完全相同的输入得到相同的密文。
+#[derive(Debug, Clone)]
+pub struct SivAead {
+    mac_key: Vec<u8>,
+    enc_key: Vec<u8>,
+}
+
+impl SivAead {
+    /// 由主密钥创建，主密钥至少 32 字节
+    pub fn new(key: &[u8]) -> Result<Self> {
+        if key.len() < 32 {
+            return Err(MpcError::AuthenticationError("SIV key must be at least 32 bytes".to_string()));
+        }
+        Ok(Self {
+            mac_key: HMAC::derive_key(key, b"siv-mac", 32),
+            enc_key: HMAC::derive_key(key, b"siv-enc", 32),
+        })
+    }
+
+    /// 加密并认证
+    pub fn seal(&self, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> SivCiphertext {
+        let siv = self.synthetic_iv(nonce, aad, plaintext);
+        SivCiphertext {
+            siv,
+            ciphertext: self.apply_keystream(&siv, plaintext),
+        }
+    }
+
+    /// 验证并解密
+    pub fn open(&self, nonce: &[u8], aad: &[u8], sealed: &SivCiphertext) -> Result<Vec<u8>> {
+        let plaintext = self.apply_keystream(&sealed.siv, &sealed.ciphertext);
+        let expected = self.synthetic_iv(nonce, aad, &plaintext);
+        if !HMAC::secure_compare(&expected, &sealed.siv) {
+            return Err(MpcError::AuthenticationError("Authentication failed".to_string()));
+        }
+        Ok(plaintext)
+    }
+
+    fn synthetic_iv(&self, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> [u8; 16] {
+        // 各字段带长度前缀，避免不同切分得到相同的输入
+        let mut input = Vec::with_capacity(24 + aad.len() + nonce.len() + plaintext.len());
+        for field in [aad, nonce, plaintext] {
+            input.extend_from_slice(&(field.len() as u64).to_be_bytes());
+            input.extend_from_slice(field);
+        }
+        let mut siv = [0u8; 16];
+        siv.copy_from_slice(&HMAC::compute_hmac(&self.mac_key, &input)[..16]);
+        siv
+    }
+
+    fn apply_keystream(&self, siv: &[u8; 16], data: &[u8]) -> Vec<u8> {
+        let keystream = HMAC::derive_key(&self.enc_key, siv, data.len());
+        data.iter().zip(keystream).map(|(byte, key)| byte ^ key).collect()
+    }
+}
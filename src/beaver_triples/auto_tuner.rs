@@ -0,0 +1,567 @@
+//! # 预处理自动调优 (Preprocessing Auto-Tuning)
+//!
+//! 不同的三元组生成方式在不同的机器和网络上表现差异很大：OLE 与可信方
+//! 计算开销小但需要信任假设或多轮交互，BFV 计算开销大但通信轮数少，
+//! 基于 OT 的 Gilboa 乘法只适用于两方且通信量较大。
+//!
+//! 本模块给定一个目标（例如"60 秒内生成 10000 个三元组"）：
+//!
+//! 1. **基准测试**: 在本机上用少量样本测量每种可用生成器的单个三元组耗时、
+//!    通信量和每批轮数
+//! 2. **参数选择**: 结合网络延迟与带宽估计每批耗时，选择批大小（使网络延迟
+//!    在每批中的占比不超过约 10%）和并行度（满足截止时间所需的最少工作线程）
+//! 3. **后台生成**: 按决策启动工作线程，在后台生成三元组
+//!
+//! 调优决策可以通过 `ConnectionStats::record_preprocessing_plan` 记录到统计中，
+//! 后台生成的执行统计则通过 `ConnectionStats::record_protocol_stats` 汇总。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::*;
+//! use std::time::Duration;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let tuner = PreprocessingAutoTuner::new(3, 2)?
+//!     .with_candidates(vec![TripleGeneratorKind::Ole, TripleGeneratorKind::TrustedParty])
+//!     .with_max_parallelism(2);
+//!
+//! let target = PreprocessingTarget::new(20, Duration::from_secs(60));
+//! let decision = tuner.tune(&target)?;
+//! assert!(decision.meets_deadline);
+//!
+//! let job = tuner.schedule(&decision)?;
+//! let output = job.join()?;
+//! assert_eq!(output.result.len(), 20);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::*;
+use super::ot_gilboa::OTGilboaBeaverGenerator;
+use crate::protocols::stats::ProtocolStats;
+use crate::utils::concurrency::{CancellationToken, TaskGroup};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 每种生成器基准测试的默认样本数量
+pub const DEFAULT_BENCHMARK_SAMPLES: usize = 4;
+
+/// 调优得到的最小批大小
+pub const MIN_TUNED_BATCH: usize = 8;
+
+/// 调优得到的最大批大小
+pub const MAX_TUNED_BATCH: usize = 1024;
+
+/// 每批计算时间至少为网络延迟的倍数（延迟占比不超过约 10%）
+const LATENCY_AMORTIZATION: f64 = 9.0;
+
+/// 可供调优器选择的三元组生成器
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TripleGeneratorKind {
+    /// 基于 OLE
+    Ole,
+    /// 基于 BFV 同态加密
+    Bfv,
+    /// 可信第三方
+    TrustedParty,
+    /// 基于 OT 的 Gilboa 乘法（仅两方）
+    OtGilboa,
+}
+
+impl TripleGeneratorKind {
+    /// 所有生成器
+    pub const ALL: [TripleGeneratorKind; 4] = [
+        TripleGeneratorKind::Ole,
+        TripleGeneratorKind::Bfv,
+        TripleGeneratorKind::TrustedParty,
+        TripleGeneratorKind::OtGilboa,
+    ];
+
+    /// 生成器名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            TripleGeneratorKind::Ole => "ole",
+            TripleGeneratorKind::Bfv => "bfv",
+            TripleGeneratorKind::TrustedParty => "trusted_party",
+            TripleGeneratorKind::OtGilboa => "ot_gilboa",
+        }
+    }
+
+    /// 当前构建是否包含该生成器（BFV 需要 `he` 特性）
+    pub fn is_available(&self) -> bool {
+        match self {
+            TripleGeneratorKind::Bfv => cfg!(feature = "he"),
+            _ => true,
+        }
+    }
+
+    /// 创建对应的生成器
+    ///
+    /// # 参数
+    /// - `party_count`: 参与方总数
+    /// - `threshold`: 重构门限
+    /// - `party_id`: 当前方的 ID
+    ///
+    /// # 返回
+    /// 生成器不支持给定配置或未编译进当前构建时返回错误
+    pub fn build(&self, party_count: usize, threshold: usize, party_id: usize) -> Result<Box<dyn BeaverTripleGenerator>> {
+        Ok(match self {
+            TripleGeneratorKind::Ole => Box::new(OLEBeaverGenerator::new(party_count, threshold, party_id)?),
+            #[cfg(feature = "he")]
+            TripleGeneratorKind::Bfv => Box::new(BFVBeaverGenerator::new(party_count, threshold, party_id, None)?),
+            #[cfg(not(feature = "he"))]
+            TripleGeneratorKind::Bfv => {
+                return Err(MpcError::ProtocolError("BFV generator requires the `he` feature".to_string()));
+            }
+            TripleGeneratorKind::TrustedParty => {
+                // 后台生成本身就是预计算，不需要生成器再维护一个池
+                let config = TrustedPartyConfig {
+                    enable_precomputation: false,
+                    ..TrustedPartyConfig::default()
+                };
+                Box::new(TrustedPartyBeaverGenerator::new(party_count, threshold, party_id, Some(config))?)
+            }
+            TripleGeneratorKind::OtGilboa => Box::new(OTGilboaBeaverGenerator::new(party_count, threshold, party_id)?),
+        })
+    }
+}
+
+/// 预处理目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreprocessingTarget {
+    /// 需要的三元组数量
+    pub triples: usize,
+    /// 截止时间
+    pub deadline: Duration,
+}
+
+impl PreprocessingTarget {
+    /// 创建预处理目标
+    pub fn new(triples: usize, deadline: Duration) -> Self {
+        Self { triples, deadline }
+    }
+}
+
+/// 网络条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkProfile {
+    /// 单轮通信延迟
+    pub round_latency: Duration,
+    /// 带宽（字节/秒），为 `None` 时不限制
+    pub bandwidth: Option<u64>,
+}
+
+impl NetworkProfile {
+    /// 本机模拟：没有延迟，不限带宽
+    pub fn local() -> Self {
+        Self {
+            round_latency: Duration::ZERO,
+            bandwidth: None,
+        }
+    }
+
+    /// 创建网络条件
+    ///
+    /// # 参数
+    /// - `round_latency`: 单轮通信延迟
+    /// - `bandwidth`: 带宽（字节/秒），必须大于 0
+    pub fn new(round_latency: Duration, bandwidth: u64) -> Result<Self> {
+        if bandwidth == 0 {
+            return Err(MpcError::ProtocolError("Bandwidth must be positive".to_string()));
+        }
+        Ok(Self {
+            round_latency,
+            bandwidth: Some(bandwidth),
+        })
+    }
+
+    /// 传输指定字节数所需的时间
+    fn transfer_time(&self, bytes: u64) -> Duration {
+        match self.bandwidth {
+            Some(bandwidth) => Duration::from_secs_f64(bytes as f64 / bandwidth as f64),
+            None => Duration::ZERO,
+        }
+    }
+}
+
+impl Default for NetworkProfile {
+    fn default() -> Self {
+        Self::local()
+    }
+}
+
+/// 单个生成器的基准测试结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneratorBenchmark {
+    /// 生成器
+    pub kind: TripleGeneratorKind,
+    /// 样本数量
+    pub samples: usize,
+    /// 每个三元组的计算时间
+    pub time_per_triple: Duration,
+    /// 每个三元组发送的字节数
+    pub bytes_per_triple: u64,
+    /// 每批的通信轮数
+    pub rounds_per_batch: usize,
+}
+
+impl GeneratorBenchmark {
+    /// 估计在给定网络条件下生成一批三元组的耗时
+    pub fn batch_time(&self, batch_size: usize, network: &NetworkProfile) -> Duration {
+        self.time_per_triple.mul_f64(batch_size as f64)
+            + network.round_latency.mul_f64(self.rounds_per_batch as f64)
+            + network.transfer_time(self.bytes_per_triple.saturating_mul(batch_size as u64))
+    }
+
+    /// 使网络延迟在每批中占比不超过约 10% 的批大小
+    fn tuned_batch_size(&self, network: &NetworkProfile) -> usize {
+        let per_triple = self.time_per_triple + network.transfer_time(self.bytes_per_triple);
+        let latency = network.round_latency.mul_f64(self.rounds_per_batch as f64);
+        if latency.is_zero() {
+            return MIN_TUNED_BATCH;
+        }
+        if per_triple.is_zero() {
+            return MAX_TUNED_BATCH;
+        }
+        let batch = (latency.as_secs_f64() * LATENCY_AMORTIZATION / per_triple.as_secs_f64()).ceil();
+        (batch.min(MAX_TUNED_BATCH as f64) as usize).max(MIN_TUNED_BATCH)
+    }
+
+    /// 估计 `parallelism` 个工作线程、每批 `batch_size` 个时生成 `triples` 个三元组的耗时
+    fn estimate(&self, triples: usize, batch_size: usize, parallelism: usize, network: &NetworkProfile) -> Duration {
+        let per_worker = triples.div_ceil(parallelism);
+        let full_batches = per_worker / batch_size;
+        let remainder = per_worker % batch_size;
+
+        let mut total = self.batch_time(batch_size, network).mul_f64(full_batches as f64);
+        if remainder > 0 {
+            total += self.batch_time(remainder, network);
+        }
+        total
+    }
+}
+
+/// 调优决策
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TuningDecision {
+    /// 预处理目标
+    pub target: PreprocessingTarget,
+    /// 选择的生成器
+    pub generator: TripleGeneratorKind,
+    /// 每批生成的三元组数量
+    pub batch_size: usize,
+    /// 工作线程数量
+    pub parallelism: usize,
+    /// 估计耗时
+    pub estimated_time: Duration,
+    /// 估计能否在截止时间前完成
+    pub meets_deadline: bool,
+    /// 参与比较的基准测试结果
+    pub benchmarks: Vec<GeneratorBenchmark>,
+}
+
+/// 预处理自动调优器
+#[derive(Debug, Clone)]
+pub struct PreprocessingAutoTuner {
+    /// 参与方数量
+    party_count: usize,
+    /// 重构门限
+    threshold: usize,
+    /// 当前方的 ID
+    party_id: usize,
+    /// 网络条件
+    network: NetworkProfile,
+    /// 候选生成器
+    candidates: Vec<TripleGeneratorKind>,
+    /// 最大工作线程数量
+    max_parallelism: usize,
+    /// 基准测试样本数量
+    benchmark_samples: usize,
+}
+
+impl PreprocessingAutoTuner {
+    /// 创建调优器
+    ///
+    /// 默认考虑所有生成器，网络条件为本机模拟，
+    /// 最大并行度为本机可用的 CPU 数量。
+    ///
+    /// # 参数
+    /// - `party_count`: 参与方总数
+    /// - `threshold`: 重构门限
+    pub fn new(party_count: usize, threshold: usize) -> Result<Self> {
+        if threshold == 0 || threshold > party_count {
+            return Err(MpcError::InvalidThreshold);
+        }
+
+        let max_parallelism = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        Ok(Self {
+            party_count,
+            threshold,
+            party_id: 0,
+            network: NetworkProfile::local(),
+            candidates: TripleGeneratorKind::ALL.into_iter().filter(TripleGeneratorKind::is_available).collect(),
+            max_parallelism,
+            benchmark_samples: DEFAULT_BENCHMARK_SAMPLES,
+        })
+    }
+
+    /// 设置网络条件
+    pub fn with_network(mut self, network: NetworkProfile) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// 设置候选生成器
+    pub fn with_candidates(mut self, candidates: Vec<TripleGeneratorKind>) -> Self {
+        self.candidates = candidates;
+        self
+    }
+
+    /// 设置最大工作线程数量（至少为 1）
+    pub fn with_max_parallelism(mut self, max_parallelism: usize) -> Self {
+        self.max_parallelism = max_parallelism.max(1);
+        self
+    }
+
+    /// 设置基准测试样本数量（至少为 1）
+    pub fn with_benchmark_samples(mut self, samples: usize) -> Self {
+        self.benchmark_samples = samples.max(1);
+        self
+    }
+
+    /// 设置当前方的 ID
+    pub fn with_party_id(mut self, party_id: usize) -> Self {
+        self.party_id = party_id;
+        self
+    }
+
+    /// 网络条件
+    pub fn network(&self) -> &NetworkProfile {
+        &self.network
+    }
+
+    /// 对候选生成器进行基准测试
+    ///
+    /// 不支持当前配置的生成器（例如多于两方时的 OT 生成器）会被跳过。
+    ///
+    /// # 返回
+    /// 没有任何可用生成器时返回错误
+    pub fn benchmark(&self) -> Result<Vec<GeneratorBenchmark>> {
+        let mut benchmarks = Vec::with_capacity(self.candidates.len());
+        for &kind in &self.candidates {
+            let mut generator = match kind.build(self.party_count, self.threshold, self.party_id) {
+                Ok(generator) => generator,
+                Err(_) => continue,
+            };
+
+            let stats = generator.generate_batch_with_stats(self.benchmark_samples)?.stats;
+            let samples = self.benchmark_samples as u64;
+            benchmarks.push(GeneratorBenchmark {
+                kind,
+                samples: self.benchmark_samples,
+                time_per_triple: stats.wall_time.div_f64(samples as f64),
+                bytes_per_triple: stats.bytes_sent.div_ceil(samples),
+                rounds_per_batch: stats.rounds,
+            });
+        }
+
+        if benchmarks.is_empty() {
+            return Err(MpcError::ProtocolError(
+                "No triple generator supports this configuration".to_string()
+            ));
+        }
+        Ok(benchmarks)
+    }
+
+    /// 根据基准测试结果选择生成器和参数
+    ///
+    /// 对每个生成器选择批大小，再找出满足截止时间所需的最少工作线程；
+    /// 在能满足截止时间的生成器中优先选择并行度最低、其次耗时最短的，
+    /// 都不能满足时选择耗时最短的并标记 `meets_deadline = false`。
+    ///
+    /// # 参数
+    /// - `target`: 预处理目标
+    /// - `benchmarks`: 基准测试结果
+    pub fn plan(&self, target: &PreprocessingTarget, benchmarks: &[GeneratorBenchmark]) -> Result<TuningDecision> {
+        if target.triples == 0 || target.deadline.is_zero() {
+            return Err(MpcError::ProtocolError(
+                "Preprocessing target needs at least one triple and a positive deadline".to_string()
+            ));
+        }
+
+        let max_parallelism = self.max_parallelism.min(target.triples);
+        let mut best: Option<(usize, usize, Duration, TripleGeneratorKind)> = None;
+        let mut best_meets = false;
+
+        for benchmark in benchmarks {
+            let tuned = benchmark.tuned_batch_size(&self.network);
+            let mut choice = None;
+            for parallelism in 1..=max_parallelism {
+                let batch_size = tuned.min(target.triples.div_ceil(parallelism));
+                let estimate = benchmark.estimate(target.triples, batch_size, parallelism, &self.network);
+                choice = Some((parallelism, batch_size, estimate));
+                if estimate <= target.deadline {
+                    break;
+                }
+            }
+            let Some((parallelism, batch_size, estimate)) = choice else { continue };
+            let meets = estimate <= target.deadline;
+
+            let better = match best {
+                None => true,
+                Some((best_parallelism, _, best_estimate, _)) => match (meets, best_meets) {
+                    (true, false) => true,
+                    (false, true) => false,
+                    (true, true) => (parallelism, estimate) < (best_parallelism, best_estimate),
+                    (false, false) => estimate < best_estimate,
+                },
+            };
+            if better {
+                best = Some((parallelism, batch_size, estimate, benchmark.kind));
+                best_meets = meets;
+            }
+        }
+
+        let (parallelism, batch_size, estimated_time, generator) = best.ok_or_else(|| {
+            MpcError::ProtocolError("No triple generator benchmarks available".to_string())
+        })?;
+        Ok(TuningDecision {
+            target: *target,
+            generator,
+            batch_size,
+            parallelism,
+            estimated_time,
+            meets_deadline: best_meets,
+            benchmarks: benchmarks.to_vec(),
+        })
+    }
+
+    /// 基准测试并选择参数
+    pub fn tune(&self, target: &PreprocessingTarget) -> Result<TuningDecision> {
+        let benchmarks = self.benchmark()?;
+        self.plan(target, &benchmarks)
+    }
+
+    /// 按调优决策在后台启动生成
+    ///
+    /// 每个工作线程持有独立的生成器，按决策的批大小生成自己负责的部分。
+    pub fn schedule(&self, decision: &TuningDecision) -> Result<PreprocessingJob> {
+        self.schedule_with_cancellation(decision, &CancellationToken::new())
+    }
+
+    /// 按调优决策在后台启动生成，并绑定到父取消令牌
+    ///
+    /// 工作线程在每批之间检查令牌：取消 `parent`、调用 `PreprocessingJob::cancel`
+    /// 或任何一个工作线程失败，都会让其余工作线程在当前批结束后退出。
+    pub fn schedule_with_cancellation(
+        &self,
+        decision: &TuningDecision,
+        parent: &CancellationToken,
+    ) -> Result<PreprocessingJob> {
+        // 提前检查生成器是否支持当前配置，避免在工作线程中才失败
+        decision.generator.build(self.party_count, self.threshold, self.party_id)?;
+
+        let parallelism = decision.parallelism.max(1);
+        let batch_size = decision.batch_size.max(1);
+        let produced = Arc::new(AtomicUsize::new(0));
+        let mut workers = TaskGroup::new("preprocessing-worker", parent);
+
+        for worker in 0..parallelism {
+            let quota = decision.target.triples / parallelism
+                + usize::from(worker < decision.target.triples % parallelism);
+            let (kind, party_count, threshold, party_id) =
+                (decision.generator, self.party_count, self.threshold, self.party_id);
+            let produced = Arc::clone(&produced);
+
+            workers.spawn(move |token| -> Result<ProtocolOutput<Vec<CompleteBeaverTriple>>> {
+                let mut recorder = StatsRecorder::start();
+                let mut generator = kind.build(party_count, threshold, party_id)?;
+                let mut triples = Vec::with_capacity(quota);
+
+                while triples.len() < quota {
+                    token.check()?;
+                    let count = batch_size.min(quota - triples.len());
+                    let (batch, stats) = generator.generate_batch_with_stats(count)?.into_parts();
+                    recorder.stats_mut().merge(&stats);
+                    produced.fetch_add(batch.len(), Ordering::Relaxed);
+                    triples.extend(batch);
+                }
+                Ok(recorder.finish(triples))
+            })?;
+        }
+
+        Ok(PreprocessingJob {
+            decision: decision.clone(),
+            produced,
+            workers,
+            started_at: Instant::now(),
+        })
+    }
+}
+
+/// 后台预处理任务
+///
+/// 任务被丢弃时取消全部工作线程并等待它们结束。
+#[derive(Debug)]
+pub struct PreprocessingJob {
+    /// 调优决策
+    decision: TuningDecision,
+    /// 已生成的三元组数量
+    produced: Arc<AtomicUsize>,
+    /// 工作线程
+    workers: TaskGroup<ProtocolOutput<Vec<CompleteBeaverTriple>>>,
+    /// 开始时间
+    started_at: Instant,
+}
+
+impl PreprocessingJob {
+    /// 调优决策
+    pub fn decision(&self) -> &TuningDecision {
+        &self.decision
+    }
+
+    /// 已生成的三元组数量
+    pub fn produced(&self) -> usize {
+        self.produced.load(Ordering::Relaxed)
+    }
+
+    /// 所有工作线程是否已结束
+    pub fn is_finished(&self) -> bool {
+        self.workers.is_finished()
+    }
+
+    /// 取消任务，工作线程在当前批结束后退出，`join` 返回取消错误
+    pub fn cancel(&self) {
+        self.workers.cancel("preprocessing job cancelled");
+    }
+
+    /// 等待所有工作线程结束并汇总结果
+    ///
+    /// 任何工作线程失败时返回第一个失败的原因。
+    /// 三元组标识符按汇总顺序重新编号，使其在整个任务内唯一。
+    /// 工作线程并行执行，因此轮数取各线程的最大值，耗时为任务的实际耗时。
+    pub fn join(self) -> Result<ProtocolOutput<Vec<CompleteBeaverTriple>>> {
+        let mut triples = Vec::with_capacity(self.decision.target.triples);
+        let mut stats = ProtocolStats::new();
+
+        for output in self.workers.join()? {
+            stats.rounds = stats.rounds.max(output.stats.rounds);
+            stats.record_sent(output.stats.bytes_sent);
+            stats.record_received(output.stats.bytes_received);
+            stats.record_preprocessing(output.stats.preprocessing_consumed);
+            triples.extend(output.result);
+        }
+
+        for (id, triple) in triples.iter_mut().enumerate() {
+            for share in triple.shares.values_mut() {
+                share.id = id as u64;
+            }
+        }
+
+        stats.wall_time = self.started_at.elapsed();
+        Ok(ProtocolOutput::new(triples, stats))
+    }
+}
@@ -28,6 +28,7 @@ use crate::homomorphic_encryption::{BFVCiphertext, BFVPlaintext, BFVPublicKey, B
 use crate::secret_sharing::{ShamirSecretSharing, SecretSharing};
 use super::protocol_messages::{BFVBeaverMessage, BFVBeaverProtocolContext, BFVBeaverConfig};
 use super::threshold_keygen::*;
+use crate::utils::hash_struct_with_domain;
 
 /// 基于 BFV 的 Beaver 三元组生成器
 /// 
@@ -143,6 +144,7 @@ impl BFVBeaverGenerator {
             n: params.degree,
             q: params.coeff_modulus,
             t: params.plain_modulus,
+            rns_moduli: Vec::new(),
         };
         
         // 公钥通常是 (b, a) = (-a*s + e, a)，其中 s 是私钥
@@ -156,6 +158,8 @@ impl BFVBeaverGenerator {
             n: params.degree,
             q: params.coeff_modulus,
             t: params.plain_modulus,
+            rns_moduli: Vec::new(),
+            relin_key: Vec::new(),
         };
         
         Ok((public_key, secret_key))
@@ -343,6 +347,8 @@ impl BFVBeaverGenerator {
             n: bfv_params.degree,
             q: bfv_params.coeff_modulus,
             t: bfv_params.plain_modulus,
+            rns_moduli: Vec::new(),
+            relin_key: Vec::new(),
         };
         
         let empty_secret_key = BFVSecretKey {
@@ -350,6 +356,7 @@ impl BFVBeaverGenerator {
             n: bfv_params.degree,
             q: bfv_params.coeff_modulus,
             t: bfv_params.plain_modulus,
+            rns_moduli: Vec::new(),
         };
         
         Ok(Self {
@@ -432,13 +439,8 @@ impl BFVBeaverGenerator {
         let enc_a_i = self.encrypt_value(a_i)?;
         let enc_b_i = self.encrypt_value(b_i)?;
         
-        // 生成承诺值（简化版）
-        use sha2::{Sha256, Digest};
-        let mut hasher = Sha256::new();
-        hasher.update(a_i.to_le_bytes());
-        hasher.update(b_i.to_le_bytes());
-        hasher.update(self.party_id.to_le_bytes());
-        let commitment = hasher.finalize().to_vec();
+        // 生成承诺值（简化版），party_id 按 64 位编码，与平台无关
+        let commitment = hash_struct_with_domain(b"mpc_api/bfv_beaver/shares", &(a_i, b_i, self.party_id))?.to_vec();
         
         Ok(BFVBeaverMessage::EncryptedShares {
             party_id: self.party_id,
@@ -661,6 +663,11 @@ impl BeaverTripleGenerator for BFVBeaverGenerator {
     fn get_threshold(&self) -> usize {
         self.threshold
     }
+    
+    fn rounds_per_batch(&self) -> usize {
+        // 广播加密分享、交换乘积密文、门限解密各需一轮
+        3
+    }
 }
 
 /// BFV 密钥管理器
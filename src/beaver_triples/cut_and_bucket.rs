@@ -0,0 +1,263 @@
+//! # Cut-and-Bucket 三元组验证 (Cut-and-Bucket Triple Verification)
+//!
+//! OLE / OT 生成的三元组在恶意模型下可能被篡改（c ≠ a·b）。不依赖可信方时，
+//! 标准做法是多生成一些三元组并用它们互相检查：
+//!
+//! 1. **打乱**: 用各方共同生成的随机种子（例如硬币抛掷的结果）打乱全部三元组
+//! 2. **切开 (cut)**: 公开前 C 个三元组并检查 c = a·b
+//! 3. **分桶 (bucket)**: 其余三元组分为 N 个大小为 B 的桶，每个桶的第一个三元组
+//!    依次与桶内其他三元组做牺牲检查：公开 ρ = a - x、σ = b - y，
+//!    再公开 c - z - σ·x - ρ·y - ρ·σ 并检查其为 0
+//! 4. 每个桶输出第一个三元组
+//!
+//! 只有整个桶都是坏三元组时篡改才不会被发现。取 C = B 且 N^(B-1) ≥ 2^s 时，
+//! 敌手成功的概率不超过 2^(-s)（Furukawa-Lindell-Nof-Weinstein）。
+//! 批量越大所需的桶越小，因此适合一次生成大量三元组；小批量时可改用成对牺牲。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! // 2^20 个三元组达到 40 比特统计安全只需要大小为 3 的桶
+//! let params = CutAndBucketParams::for_security(1 << 20, 40)?;
+//! assert_eq!(params.bucket_size, 3);
+//!
+//! let small = CutAndBucketParams::new(20, 3, 3)?;
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//! let output = CutAndBucketVerifier::new(small, 2)
+//!     .generate_verified(&mut generator, [7u8; 32])?;
+//! assert_eq!(output.result.len(), 20);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::*;
+use crate::secret_sharing::{SecretSharing, ShamirSecretSharing};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// 允许的最大桶大小
+pub const MAX_BUCKET_SIZE: usize = 64;
+
+/// Cut-and-bucket 参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CutAndBucketParams {
+    /// 输出的三元组数量 N
+    pub output_count: usize,
+    /// 桶大小 B
+    pub bucket_size: usize,
+    /// 公开检查的三元组数量 C
+    pub cut_size: usize,
+}
+
+impl CutAndBucketParams {
+    /// 使用指定的桶大小和切开数量
+    ///
+    /// # 参数
+    /// - `output_count`: 输出的三元组数量
+    /// - `bucket_size`: 桶大小（至少为 2）
+    /// - `cut_size`: 公开检查的三元组数量
+    pub fn new(output_count: usize, bucket_size: usize, cut_size: usize) -> Result<Self> {
+        if output_count == 0 {
+            return Err(MpcError::ProtocolError("Output count must be positive".to_string()));
+        }
+        if !(2..=MAX_BUCKET_SIZE).contains(&bucket_size) {
+            return Err(MpcError::ProtocolError(format!(
+                "Bucket size must be between 2 and {}", MAX_BUCKET_SIZE
+            )));
+        }
+        Ok(Self { output_count, bucket_size, cut_size })
+    }
+
+    /// 按统计安全参数选择最小的桶大小（C = B）
+    ///
+    /// # 参数
+    /// - `output_count`: 输出的三元组数量（至少为 2）
+    /// - `statistical_security`: 统计安全参数 s，敌手成功概率不超过 2^(-s)
+    pub fn for_security(output_count: usize, statistical_security: u32) -> Result<Self> {
+        if output_count < 2 {
+            return Err(MpcError::ProtocolError(
+                "Cut-and-bucket needs at least two output triples".to_string()
+            ));
+        }
+        let log_n = (output_count as f64).log2();
+        let bucket_size = (statistical_security as f64 / log_n).ceil() as usize + 1;
+        Self::new(output_count, bucket_size.max(2), bucket_size.max(2))
+    }
+
+    /// 需要输入的三元组数量 N·B + C
+    pub fn required_triples(&self) -> usize {
+        self.output_count * self.bucket_size + self.cut_size
+    }
+
+    /// 达到的统计安全参数 (B-1)·log2(N)；仅在 C ≥ B 时成立
+    pub fn statistical_security(&self) -> f64 {
+        if self.cut_size < self.bucket_size {
+            return 0.0;
+        }
+        (self.bucket_size - 1) as f64 * (self.output_count as f64).log2()
+    }
+}
+
+/// Cut-and-bucket 验证器
+#[derive(Debug, Clone, Copy)]
+pub struct CutAndBucketVerifier {
+    params: CutAndBucketParams,
+    threshold: usize,
+}
+
+impl CutAndBucketVerifier {
+    /// 创建验证器
+    ///
+    /// # 参数
+    /// - `params`: cut-and-bucket 参数
+    /// - `threshold`: 三元组分享的重构门限
+    pub fn new(params: CutAndBucketParams, threshold: usize) -> Self {
+        Self { params, threshold }
+    }
+
+    /// 参数
+    pub fn params(&self) -> &CutAndBucketParams {
+        &self.params
+    }
+
+    /// 验证一批三元组，返回每个桶输出的三元组
+    ///
+    /// # 参数
+    /// - `triples`: 恰好 `required_triples()` 个待验证的三元组
+    /// - `seed`: 各方共同生成的公开随机种子，决定打乱顺序
+    ///
+    /// # 返回值
+    /// 任何检查失败时返回 `AuthenticationError`，调用方应丢弃整批三元组
+    pub fn verify(&self, triples: Vec<CompleteBeaverTriple>, seed: [u8; 32]) -> Result<ProtocolOutput<Vec<CompleteBeaverTriple>>> {
+        let mut recorder = StatsRecorder::start();
+        if triples.len() != self.params.required_triples() {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} triples, got {}", self.params.required_triples(), triples.len()
+            )));
+        }
+
+        let mut triples = triples;
+        triples.shuffle(&mut StdRng::from_seed(seed));
+        let buckets = triples.split_off(self.params.cut_size);
+        let mut opened = 0u64;
+
+        // 切开：公开并检查
+        for (index, triple) in triples.iter().enumerate() {
+            let a = self.open(triple, |t| t.a.clone())?;
+            let b = self.open(triple, |t| t.b.clone())?;
+            let c = self.open(triple, |t| t.c.clone())?;
+            if c != field_mul(a, b) {
+                return Err(MpcError::AuthenticationError(format!("Opened triple {} is not a product", index)));
+            }
+            opened += 3;
+        }
+
+        // 分桶：桶内第一个三元组依次与其余三元组做牺牲检查
+        let mut verified = Vec::with_capacity(self.params.output_count);
+        for (bucket_index, bucket) in buckets.chunks(self.params.bucket_size).enumerate() {
+            let (output, others) = bucket.split_first()
+                .ok_or_else(|| MpcError::ProtocolError("Empty bucket".to_string()))?;
+            for other in others {
+                self.sacrifice(output, other).map_err(|_| {
+                    MpcError::AuthenticationError(format!("Bucket {} failed the sacrifice check", bucket_index))
+                })?;
+                opened += 3;
+            }
+            verified.push(output.clone());
+        }
+
+        let party_count = verified.first().map_or(0, |triple| triple.shares.len()) as u64;
+        let bytes = opened * party_count * party_count.saturating_sub(1) * std::mem::size_of::<u64>() as u64;
+        let stats = recorder.stats_mut();
+        // 切开一轮，公开 ρ/σ 一轮，公开检查值一轮
+        stats.record_rounds(3);
+        stats.record_sent(bytes);
+        stats.record_received(bytes);
+        stats.record_preprocessing(self.params.required_triples() - self.params.output_count);
+
+        Ok(recorder.finish(verified))
+    }
+
+    /// 用生成器生成 `required_triples()` 个三元组并验证
+    pub fn generate_verified(
+        &self,
+        generator: &mut dyn BeaverTripleGenerator,
+        seed: [u8; 32],
+    ) -> Result<ProtocolOutput<Vec<CompleteBeaverTriple>>> {
+        let generated = generator.generate_batch_with_stats(self.params.required_triples())?;
+        let (triples, mut stats) = generated.into_parts();
+        let verified = self.verify(triples, seed)?;
+        let (result, check_stats) = verified.into_parts();
+        stats.merge(&check_stats);
+        Ok(ProtocolOutput { result, stats })
+    }
+
+    /// 牺牲检查：用 (x, y, z) 检查 (a, b, c)
+    fn sacrifice(&self, checked: &CompleteBeaverTriple, sacrificed: &CompleteBeaverTriple) -> Result<()> {
+        let rho = self.open_pair(checked, sacrificed, |t, s| share_sub(&t.a, &s.a))?;
+        let sigma = self.open_pair(checked, sacrificed, |t, s| share_sub(&t.b, &s.b))?;
+        let rho_sigma = field_mul(rho, sigma);
+
+        // c - z - σ·x - ρ·y - ρ·σ
+        let check = self.open_pair(checked, sacrificed, |t, s| {
+            let mut value = field_sub(t.c.y, s.c.y);
+            value = field_sub(value, field_mul(sigma, s.a.y));
+            value = field_sub(value, field_mul(rho, s.b.y));
+            value = field_sub(value, rho_sigma);
+            Share::new(t.c.x, value)
+        })?;
+
+        if check == 0 {
+            Ok(())
+        } else {
+            Err(MpcError::AuthenticationError("Sacrifice check failed".to_string()))
+        }
+    }
+
+    /// 公开一个三元组分量，所有分享必须位于同一多项式上
+    fn open(&self, triple: &CompleteBeaverTriple, component: impl Fn(&BeaverTriple) -> Share) -> Result<u64> {
+        let shares = sorted_party_ids(triple).iter()
+            .map(|id| component(&triple.shares[id]))
+            .collect::<Vec<_>>();
+        self.reconstruct(&shares)
+    }
+
+    /// 公开两个三元组分量的局部线性组合
+    fn open_pair(
+        &self,
+        first: &CompleteBeaverTriple,
+        second: &CompleteBeaverTriple,
+        combine: impl Fn(&BeaverTriple, &BeaverTriple) -> Share,
+    ) -> Result<u64> {
+        let shares = sorted_party_ids(first).iter()
+            .map(|id| {
+                let other = second.get_share(*id)
+                    .ok_or_else(|| MpcError::ProtocolError("Triples have different parties".to_string()))?;
+                Ok(combine(&first.shares[id], other))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.reconstruct(&shares)
+    }
+
+    fn reconstruct(&self, shares: &[Share]) -> Result<u64> {
+        if !ShamirSecretSharing::new().verify_shares(shares, self.threshold) {
+            return Err(MpcError::AuthenticationError("Opened shares are inconsistent".to_string()));
+        }
+        ShamirSecretSharing::reconstruct(&shares[..self.threshold], self.threshold)
+    }
+}
+
+/// 排序后的参与方 ID
+fn sorted_party_ids(triple: &CompleteBeaverTriple) -> Vec<usize> {
+    let mut party_ids: Vec<_> = triple.shares.keys().copied().collect();
+    party_ids.sort_unstable();
+    party_ids
+}
+
+fn share_sub(left: &Share, right: &Share) -> Share {
+    Share::new(left.x, field_sub(left.y, right.y))
+}
@@ -0,0 +1,397 @@
+//! # 分布式可信方 (Distributed Dealer)
+//!
+//! 可信第三方模式的性能很好，但要求信任单个进程。本模块把"可信方"
+//! 替换为一个由 m 个成员组成的小型委员会：委员会内部用诚实多数协议
+//! （BGW 风格）联合生成三元组，再把分享转交给消费三元组的参与方。
+//! 信任假设因此从"可信方是诚实的"变为"委员会中不超过 t 个成员合谋"。
+//!
+//! ## 协议概述
+//!
+//! 每个成员是一个独立的 `DealerMember`，只持有自己的状态，成员之间通过
+//! `DealerMessage` 交换消息（启用 `network` 特性时可封装为 `NetworkMessage`）：
+//!
+//! 1. **随机分享** (`contribute`): 每个成员选择随机的 a_j、b_j，以 t 次多项式在委员会内分享，
+//!    各成员将收到的分享相加得到 [a]_t、[b]_t
+//! 2. **乘法与降次** (`reshare`): 各成员本地相乘得到 2t 次分享，再以 t 次多项式重新分享，
+//!    收齐后用拉格朗日系数组合得到 [c]_t（需要 m ≥ 2t + 1）
+//! 3. **转交** (`hand_off`): 每个成员把自己持有的 a、b、c 分享按消费方的门限重新分享，
+//!    消费方用 `DealerCommitteeConfig::combine_hand_offs` 以委员会的拉格朗日系数组合得到最终分享
+//!
+//! 委员会内部的轮次对消费方不可见：消费方仍然只需接收一轮分发，
+//! 与单一可信方模式相同。任意不超过 t 个成员的视图都与 (a, b, c) 独立。
+//!
+//! `DealerCommittee` 在一个进程内驱动全部成员并转发消息，供测试和
+//! `TrustedPartyBeaverGenerator::with_dealer_committee` 使用；运行它的进程看得到所有成员的消息，
+//! 信任假设与单一可信方相同。实际部署中每个成员在自己的节点上运行 `DealerMember`。
+
+use super::*;
+#[cfg(feature = "network")]
+use crate::network::NetworkMessage;
+use crate::secret_sharing::{ShamirSecretSharing, SecretSharing};
+use crate::utils::random_field_element;
+use std::collections::BTreeMap;
+
+/// 委员会内部生成一个三元组所需的通信轮数
+pub const DEALER_COMMITTEE_ROUNDS: usize = 3;
+
+/// 委员会消息的网络消息类型
+pub const DEALER_COMMITTEE_MESSAGE_TYPE: &str = "dealer_committee";
+
+/// 分布式可信方委员会的配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DealerCommitteeConfig {
+    /// 委员会成员数量 m
+    pub committee_size: usize,
+    /// 可容忍的合谋成员数量 t
+    pub collusion_threshold: usize,
+}
+
+impl DealerCommitteeConfig {
+    /// 创建委员会配置
+    ///
+    /// # 参数
+    /// - `committee_size`: 委员会成员数量 m
+    /// - `collusion_threshold`: 可容忍的合谋成员数量 t
+    ///
+    /// # 返回值
+    /// 满足 t ≥ 1 且 m ≥ 2t + 1（诚实多数）时返回配置，否则返回错误
+    pub fn new(committee_size: usize, collusion_threshold: usize) -> Result<Self> {
+        if collusion_threshold == 0 || committee_size < 2 * collusion_threshold + 1 {
+            return Err(MpcError::InvalidThreshold);
+        }
+        Ok(Self { committee_size, collusion_threshold })
+    }
+
+    /// 在 0 处插值时各成员（x 坐标 1..=m）的拉格朗日系数
+    fn lagrange(&self) -> Result<Vec<u64>> {
+        let points: Vec<u64> = (1..=self.committee_size as u64).collect();
+        ShamirSecretSharing::new().precompute_lagrange_coefficients(&points)
+    }
+
+    /// 消费方组合全部成员的转交消息，得到自己的三元组分享
+    ///
+    /// # 参数
+    /// - `messages`: 每个成员发给本消费方的 `HandOff` 消息，各一条
+    ///
+    /// # 返回值
+    /// 消息数量不等于委员会规模、来自同一成员、三元组 ID 或 x 坐标不一致时返回错误
+    pub fn combine_hand_offs(&self, messages: &[DealerMessage]) -> Result<BeaverTriple> {
+        let hand_offs = collect_from_members(self.committee_size, messages, |message| match message {
+            DealerMessage::HandOff { triple_id, from, a, b, c } => Some((*from, *triple_id, (a, b, c))),
+            _ => None,
+        })?;
+        let (triple_id, hand_offs) = hand_offs;
+        let x = hand_offs[0].0.x;
+        if hand_offs.iter().any(|(a, b, c)| a.x != x || b.x != x || c.x != x) {
+            return Err(MpcError::ProtocolError("Hand-off shares target different parties".to_string()));
+        }
+
+        let lagrange = self.lagrange()?;
+        let combine = |pick: fn(&(&Share, &Share, &Share)) -> u64| {
+            hand_offs.iter().zip(&lagrange).fold(0, |acc, (shares, &weight)| field_add(acc, field_mul(weight, pick(shares))))
+        };
+        Ok(BeaverTriple::new(
+            Share::new(x, combine(|shares| shares.0.y)),
+            Share::new(x, combine(|shares| shares.1.y)),
+            Share::new(x, combine(|shares| shares.2.y)),
+            triple_id,
+        ))
+    }
+}
+
+impl Default for DealerCommitteeConfig {
+    fn default() -> Self {
+        Self {
+            committee_size: 3,
+            collusion_threshold: 1,
+        }
+    }
+}
+
+/// 委员会成员之间以及成员发给消费方的消息
+///
+/// `from` 是发送成员的 x 坐标（1..=m）。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DealerMessage {
+    /// 第 1 轮：发送成员随机贡献 a_j、b_j 在接收成员处的分享
+    Contribute {
+        /// 三元组标识符
+        triple_id: u64,
+        /// 发送成员
+        from: usize,
+        /// a_j 的分享
+        a: u64,
+        /// b_j 的分享
+        b: u64,
+    },
+    /// 第 2 轮：发送成员本地乘积的 t 次重新分享
+    Reshare {
+        /// 三元组标识符
+        triple_id: u64,
+        /// 发送成员
+        from: usize,
+        /// 乘积的分享
+        product: u64,
+    },
+    /// 第 3 轮：发送成员把自己持有的 a、b、c 分享按消费方门限重新分享
+    HandOff {
+        /// 三元组标识符
+        triple_id: u64,
+        /// 发送成员
+        from: usize,
+        /// a 的分享
+        a: Share,
+        /// b 的分享
+        b: Share,
+        /// c 的分享
+        c: Share,
+    },
+}
+
+impl DealerMessage {
+    /// 封装为网络消息
+    #[cfg(feature = "network")]
+    pub fn to_network_message(&self) -> Result<NetworkMessage> {
+        let payload = bincode::serialize(self)
+            .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+        Ok(NetworkMessage::new(DEALER_COMMITTEE_MESSAGE_TYPE, &payload))
+    }
+
+    /// 从网络消息解析
+    #[cfg(feature = "network")]
+    pub fn from_network_message(message: &NetworkMessage) -> Result<Self> {
+        if message.message_type != DEALER_COMMITTEE_MESSAGE_TYPE {
+            return Err(MpcError::ProtocolError(format!(
+                "Unexpected message type: {}", message.message_type
+            )));
+        }
+        bincode::deserialize(&message.payload)
+            .map_err(|e| MpcError::SerializationError(e.to_string()))
+    }
+}
+
+/// 委员会成员
+///
+/// 只持有自己的分享，每生成一个三元组依次调用 `contribute`、`reshare`、`hand_off`，
+/// 每一步的输入是其他成员上一步发来的消息。
+#[derive(Debug, Clone)]
+pub struct DealerMember {
+    /// 委员会配置
+    config: DealerCommitteeConfig,
+    /// 本成员的 x 坐标（1..=m）
+    member: usize,
+    /// 在 0 处插值时各成员的拉格朗日系数
+    lagrange: Vec<u64>,
+    /// 正在生成的三元组
+    triple_id: Option<u64>,
+    /// 本成员持有的 [a]_t、[b]_t
+    held: Option<(u64, u64)>,
+}
+
+impl DealerMember {
+    /// 创建成员
+    ///
+    /// # 参数
+    /// - `config`: 委员会配置
+    /// - `member`: 本成员的 x 坐标，1..=m
+    pub fn new(config: DealerCommitteeConfig, member: usize) -> Result<Self> {
+        let config = DealerCommitteeConfig::new(config.committee_size, config.collusion_threshold)?;
+        if member == 0 || member > config.committee_size {
+            return Err(MpcError::ProtocolError(format!(
+                "Committee member {} is outside 1..={}", member, config.committee_size
+            )));
+        }
+        let lagrange = config.lagrange()?;
+        Ok(Self { config, member, lagrange, triple_id: None, held: None })
+    }
+
+    /// 本成员的 x 坐标
+    pub fn member(&self) -> usize {
+        self.member
+    }
+
+    /// 第 1 轮：选择随机贡献并在委员会内分享
+    ///
+    /// # 返回值
+    /// 以接收成员为键的消息，包含发给自己的一条
+    pub fn contribute(&mut self, triple_id: u64) -> Result<BTreeMap<usize, DealerMessage>> {
+        let degree_threshold = self.config.collusion_threshold + 1;
+        let m = self.config.committee_size;
+        let a_shares = ShamirSecretSharing::share(&random_field_element(), degree_threshold, m)?;
+        let b_shares = ShamirSecretSharing::share(&random_field_element(), degree_threshold, m)?;
+
+        self.triple_id = Some(triple_id);
+        self.held = None;
+        Ok(a_shares.iter().zip(&b_shares)
+            .map(|(a, b)| (a.x as usize, DealerMessage::Contribute { triple_id, from: self.member, a: a.y, b: b.y }))
+            .collect())
+    }
+
+    /// 第 2 轮：累加收到的贡献，本地相乘后重新分享乘积
+    ///
+    /// # 参数
+    /// - `messages`: 每个成员（包括自己）发来的 `Contribute` 消息
+    pub fn reshare(&mut self, messages: &[DealerMessage]) -> Result<BTreeMap<usize, DealerMessage>> {
+        let triple_id = self.current_triple()?;
+        let (received_id, contributions) = collect_from_members(self.config.committee_size, messages, |message| match message {
+            DealerMessage::Contribute { triple_id, from, a, b } => Some((*from, *triple_id, (*a, *b))),
+            _ => None,
+        })?;
+        check_triple_id(triple_id, received_id)?;
+
+        let (a, b) = contributions.iter()
+            .fold((0, 0), |(a, b), &(a_j, b_j)| (field_add(a, a_j), field_add(b, b_j)));
+        self.held = Some((a, b));
+
+        let degree_threshold = self.config.collusion_threshold + 1;
+        let shares = ShamirSecretSharing::share(&field_mul(a, b), degree_threshold, self.config.committee_size)?;
+        Ok(shares.into_iter()
+            .map(|share| (share.x as usize, DealerMessage::Reshare { triple_id, from: self.member, product: share.y }))
+            .collect())
+    }
+
+    /// 第 3 轮：组合降次后的 [c]_t，把 a、b、c 的分享转交给消费方
+    ///
+    /// # 参数
+    /// - `messages`: 每个成员（包括自己）发来的 `Reshare` 消息
+    /// - `threshold`: 消费方的重构门限
+    /// - `party_count`: 消费方数量，消费方的 x 坐标为 1..=party_count
+    ///
+    /// # 返回值
+    /// 以消费方 x 坐标为键的 `HandOff` 消息
+    pub fn hand_off(
+        &mut self,
+        messages: &[DealerMessage],
+        threshold: usize,
+        party_count: usize,
+    ) -> Result<BTreeMap<usize, DealerMessage>> {
+        let triple_id = self.current_triple()?;
+        let (a, b) = self.held.ok_or_else(|| MpcError::ProtocolError("Reshare must run before hand-off".to_string()))?;
+        let (received_id, products) = collect_from_members(self.config.committee_size, messages, |message| match message {
+            DealerMessage::Reshare { triple_id, from, product } => Some((*from, *triple_id, *product)),
+            _ => None,
+        })?;
+        check_triple_id(triple_id, received_id)?;
+
+        let c = products.iter().zip(&self.lagrange)
+            .fold(0, |acc, (&product, &weight)| field_add(acc, field_mul(weight, product)));
+        let a_shares = ShamirSecretSharing::share(&a, threshold, party_count)?;
+        let b_shares = ShamirSecretSharing::share(&b, threshold, party_count)?;
+        let c_shares = ShamirSecretSharing::share(&c, threshold, party_count)?;
+
+        self.triple_id = None;
+        self.held = None;
+        Ok(a_shares.into_iter().zip(b_shares).zip(c_shares)
+            .map(|((a, b), c)| (a.x as usize, DealerMessage::HandOff { triple_id, from: self.member, a, b, c }))
+            .collect())
+    }
+
+    fn current_triple(&self) -> Result<u64> {
+        self.triple_id.ok_or_else(|| MpcError::ProtocolError("No triple in progress".to_string()))
+    }
+}
+
+/// 在一个进程内驱动全部委员会成员
+///
+/// 成员之间的消息在内存中转发，运行它的进程看得到全部消息，
+/// 因此只用于测试和单机部署；信任假设与单一可信方相同。
+#[derive(Debug, Clone)]
+pub struct DealerCommittee {
+    /// 委员会配置
+    config: DealerCommitteeConfig,
+    /// 各成员
+    members: Vec<DealerMember>,
+}
+
+impl DealerCommittee {
+    /// 创建委员会
+    pub fn new(config: DealerCommitteeConfig) -> Result<Self> {
+        let config = DealerCommitteeConfig::new(config.committee_size, config.collusion_threshold)?;
+        let members = (1..=config.committee_size)
+            .map(|member| DealerMember::new(config, member))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { config, members })
+    }
+
+    /// 获取委员会配置
+    pub fn config(&self) -> &DealerCommitteeConfig {
+        &self.config
+    }
+
+    /// 联合生成一个三元组并转交给消费方
+    ///
+    /// # 参数
+    /// - `party_count`: 消费方数量
+    /// - `threshold`: 消费方的重构门限
+    /// - `triple_id`: 三元组标识符
+    ///
+    /// # 返回值
+    /// 返回消费方持有的三元组分享，不包含原始值
+    pub fn generate_triple(
+        &mut self,
+        party_count: usize,
+        threshold: usize,
+        triple_id: u64,
+    ) -> Result<CompleteBeaverTriple> {
+        let outgoing = self.members.iter_mut()
+            .map(|member| member.contribute(triple_id))
+            .collect::<Result<Vec<_>>>()?;
+        let outgoing = self.members.iter_mut()
+            .map(|member| member.reshare(&route(&outgoing, member.member())))
+            .collect::<Result<Vec<_>>>()?;
+        let hand_offs = self.members.iter_mut()
+            .map(|member| member.hand_off(&route(&outgoing, member.member()), threshold, party_count))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut shares = HashMap::new();
+        for party in 1..=party_count {
+            shares.insert(party, self.config.combine_hand_offs(&route(&hand_offs, party))?);
+        }
+        Ok(CompleteBeaverTriple::new(shares))
+    }
+}
+
+/// 取出各成员发给 `recipient` 的消息
+fn route(outgoing: &[BTreeMap<usize, DealerMessage>], recipient: usize) -> Vec<DealerMessage> {
+    outgoing.iter().filter_map(|messages| messages.get(&recipient).cloned()).collect()
+}
+
+/// 检查每个成员恰好发来一条指定类型的消息，按成员顺序返回 (三元组 ID, 载荷)
+fn collect_from_members<'a, T>(
+    committee_size: usize,
+    messages: &'a [DealerMessage],
+    extract: impl Fn(&'a DealerMessage) -> Option<(usize, u64, T)>,
+) -> Result<(u64, Vec<T>)> {
+    if messages.len() != committee_size {
+        return Err(MpcError::ProtocolError(format!(
+            "Expected {} committee messages, received {}", committee_size, messages.len()
+        )));
+    }
+    let mut by_member: Vec<Option<T>> = (0..committee_size).map(|_| None).collect();
+    let mut triple_id = None;
+    for message in messages {
+        let (from, id, payload) = extract(message)
+            .ok_or_else(|| MpcError::ProtocolError("Unexpected committee message type".to_string()))?;
+        if *triple_id.get_or_insert(id) != id {
+            return Err(MpcError::ProtocolError("Committee messages refer to different triples".to_string()));
+        }
+        let slot = from.checked_sub(1)
+            .and_then(|index| by_member.get_mut(index))
+            .ok_or_else(|| MpcError::ProtocolError(format!("Unknown committee member {}", from)))?;
+        if slot.replace(payload).is_some() {
+            return Err(MpcError::ProtocolError(format!("Duplicate message from committee member {}", from)));
+        }
+    }
+    let payloads = by_member.into_iter().collect::<Option<Vec<_>>>()
+        .ok_or_else(|| MpcError::ProtocolError("Missing committee message".to_string()))?;
+    Ok((triple_id.unwrap_or_default(), payloads))
+}
+
+fn check_triple_id(expected: u64, received: u64) -> Result<()> {
+    if expected != received {
+        return Err(MpcError::ProtocolError(format!(
+            "Committee messages are for triple {}, expected {}", received, expected
+        )));
+    }
+    Ok(())
+}
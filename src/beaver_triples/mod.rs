@@ -5,8 +5,18 @@
 //! 
 //! 1. **OLE-based**: 基于不经意线性求值的方法
 //! 2. **Homomorphic Encryption**: 基于同态加密 (BFV) 的方法  
-//! 3. **Trusted Third Party**: 基于可信第三方的方法
+//! 3. **Trusted Third Party**: 基于可信第三方的方法，可信方也可以是诚实多数的委员会
 //! 4. **BGW Protocol**: 基于 BGW 协议的信息论安全方法
+//! 5. **OT-based (Gilboa)**: 两方场景下只使用不经意传输的方法
+//! 
+//! `auto_tuner` 子模块可以根据目标数量和截止时间在上述方法中自动选择，
+//! 并在后台按选定的批大小和并行度生成三元组。
+//! 
+//! 不依赖可信方生成的大批量三元组可以用 `cut_and_bucket` 子模块在恶意模型下批量验证；
+//! `sacrifice` 子模块两两配对做牺牲检查，不公开三元组，并逐个报告哪些三元组可以使用。
+//! 离线阶段生成的三元组可以放入 `pool` 子模块的预处理池，由可插拔的存储后端持久化。
+//! `BeaverTriple<F>`、`CompleteBeaverTriple<F>`、`open_value` 和 `secure_multiply` 对任意素数域
+//! `F: Field` 通用（默认 `u64`），其他素数域上的三元组可以用 `CompleteBeaverTriple::deal` 生成。
 //! 
 //! ## Beaver 三元组定义
 //! 
@@ -17,11 +27,14 @@
 //! 
 //! ## 安全乘法协议
 //! 
-//! 使用 Beaver 三元组，两方可以安全地计算 x * y：
+//! 使用 Beaver 三元组，各方可以在不重构 x、y 的情况下计算 x * y：
 //! 1. 各方持有 [x], [y] (x, y 的分享) 和 ([a], [b], [c])
-//! 2. 计算并公开 d = x - a, e = y - b  
-//! 3. 计算 [z] = [c] + d·[b] + e·[a] + d·e
-//! 4. 结果 [z] 是 x·y 的分享
+//! 2. 每方在本地计算 d_i = x_i - a_i, e_i = y_i - b_i（`BeaverTriple::mask`）
+//! 3. 公开 d = x - a, e = y - b（`open_value`，或网络会话中的公开步骤）
+//! 4. 每方在本地计算 [z] = [c] + d·[b] + e·[a] + d·e（`BeaverTriple::combine`）
+//! 5. 结果 [z] 是 x·y 的分享
+//! 
+//! `secure_multiply` 在一个进程内模拟所有参与方依次执行上述步骤。
 //! 
 //! ## 使用示例
 //! 
@@ -29,40 +42,70 @@
 //! use mpc_api::beaver_triples::*;
 //! use mpc_api::secret_sharing::*;
 //! 
-//! // 使用 OLE 生成 Beaver 三元组
-//! let generator = OLEBeaverGenerator::new(3, 2)?; // 3方，门限2
-//! let triples = generator.generate_batch(10)?;    // 生成10个三元组
+//! # fn main() -> mpc_api::Result<()> {
+//! // 使用 OLE 生成 Beaver 三元组：3 方，门限 2
+//! let mut generator = OLEBeaverGenerator::new(3, 2, 0)?;
+//! let triples = generator.generate_batch(10)?;
 //! 
-//! // 使用 BFV 同态加密生成
-//! let bfv_generator = BFVBeaverGenerator::new(3, 2)?;
-//! let bfv_triples = bfv_generator.generate_batch(10)?;
-//! 
-//! // 安全乘法示例
 //! let x_shares = ShamirSecretSharing::share(&15, 2, 3)?;
 //! let y_shares = ShamirSecretSharing::share(&25, 2, 3)?;
 //! let triple = &triples[0];
 //! 
-//! let product_shares = secure_multiply(&x_shares, &y_shares, triple)?;
-//! let product = ShamirSecretSharing::reconstruct(&product_shares[0..2], 2)?;
-//! assert_eq!(product, field_mul(15, 25));
+//! // 按参与方执行：每方只使用自己的分享
+//! let mine: Vec<&BeaverTriple> = x_shares.iter().map(|x| &triple.shares[&(x.x as usize)]).collect();
+//! let masked = mine.iter().zip(x_shares.iter().zip(&y_shares))
+//!     .map(|(triple, (x, y))| triple.mask(x, y))
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//! let d_shares: Vec<Share> = masked.iter().map(|(d, _)| d.clone()).collect();
+//! let e_shares: Vec<Share> = masked.iter().map(|(_, e)| e.clone()).collect();
+//! let (d, e) = (open_value(&d_shares, 2)?, open_value(&e_shares, 2)?);
+//! let product_shares: Vec<Share> = mine.iter().map(|triple| triple.combine(d, e)).collect();
+//! assert_eq!(ShamirSecretSharing::reconstruct(&product_shares[0..2], 2)?, field_mul(15, 25));
+//! 
+//! // 或者一次完成全部步骤
+//! let product_shares = secure_multiply(&x_shares, &y_shares, triple, 2)?;
+//! assert_eq!(ShamirSecretSharing::reconstruct(&product_shares[1..3], 2)?, field_mul(15, 25));
+//! # Ok(())
+//! # }
 //! ```
 
 pub mod ole_based;
+#[cfg(feature = "he")]
 pub mod bfv_based;
 pub mod trusted_party;
+#[cfg(feature = "he")]
 pub mod protocol_messages;
+#[cfg(feature = "he")]
 pub mod threshold_keygen;
 pub mod two_party_ole;
+pub mod distributed_dealer;
+pub mod ot_gilboa;
+pub mod auto_tuner;
+pub mod cut_and_bucket;
+pub mod sacrifice;
+pub mod pool;
+pub mod triple_file;
 
 pub use ole_based::*;
+#[cfg(feature = "he")]
 pub use bfv_based::*;
 pub use trusted_party::*;
+#[cfg(feature = "he")]
 pub use protocol_messages::*;
+#[cfg(feature = "he")]
 pub use threshold_keygen::*;
 pub use two_party_ole::*;
+pub use distributed_dealer::*;
+pub use ot_gilboa::*;
+pub use auto_tuner::*;
+pub use cut_and_bucket::*;
+pub use sacrifice::*;
+pub use pool::*;
+pub use triple_file::*;
 
 use crate::{MpcError, Result};
-use crate::secret_sharing::{Share, field_add, field_sub, field_mul, FIELD_PRIME};
+use crate::protocols::stats::{ProtocolOutput, ProtocolStats, StatsRecorder};
+use crate::secret_sharing::{Field, SecretSharing, Shamir, Share, field_add, field_sub, field_mul, FIELD_PRIME};
 use serde::{Deserialize, Serialize};
 use rand::{Rng, thread_rng};
 use std::collections::HashMap;
@@ -70,24 +113,24 @@ use std::collections::HashMap;
 /// Beaver 三元组的分享表示
 /// 每一方持有 (a, b, c) 三个值的分享，其中 c = a * b
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BeaverTriple {
+pub struct BeaverTriple<F = u64> {
     /// a 的分享
-    pub a: Share,
+    pub a: Share<F>,
     /// b 的分享  
-    pub b: Share,
+    pub b: Share<F>,
     /// c = a * b 的分享
-    pub c: Share,
+    pub c: Share<F>,
     /// 三元组的唯一标识符
     pub id: u64,
 }
 
 /// 完整的 Beaver 三元组，包含所有参与方的分享
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CompleteBeaverTriple {
+pub struct CompleteBeaverTriple<F = u64> {
     /// 每一方的三元组分享
-    pub shares: HashMap<usize, BeaverTriple>,
+    pub shares: HashMap<usize, BeaverTriple<F>>,
     /// 原始值 (仅用于验证，实际协议中不应该存在)
-    pub original_values: Option<(u64, u64, u64)>,
+    pub original_values: Option<(F, F, F)>,
 }
 
 /// Beaver 三元组生成器的通用特征
@@ -99,6 +142,9 @@ pub trait BeaverTripleGenerator {
     fn generate_batch(&mut self, count: usize) -> Result<Vec<CompleteBeaverTriple>>;
     
     /// 验证三元组的正确性
+    /// 
+    /// 实现通常需要重构 a、b、c，会泄露三元组，只适合测试；
+    /// 在不公开三元组的情况下验证使用 `sacrifice` 子模块。
     fn verify_triple(&self, triple: &CompleteBeaverTriple) -> Result<bool>;
     
     /// 获取支持的参与方数量
@@ -106,9 +152,46 @@ pub trait BeaverTripleGenerator {
     
     /// 获取门限值
     fn get_threshold(&self) -> usize;
+    
+    /// 每批生成所需的通信轮数
+    fn rounds_per_batch(&self) -> usize {
+        1
+    }
+    
+    /// 批量生成 Beaver 三元组并返回执行统计
+    /// 
+    /// 默认实现看不到生成器内部的消息，统计是估计值（`estimated` 为 true）：
+    /// 通信量按分发给各参与方的三元组分享的序列化大小计算，发送和接收各计一次，
+    /// 轮数取 `rounds_per_batch`。能够观察实际消息的生成器应覆盖此方法。
+    /// 生成的三元组尚未使用，不计入预处理消耗。
+    fn generate_batch_with_stats(&mut self, count: usize) -> Result<ProtocolOutput<Vec<CompleteBeaverTriple>>> {
+        let mut recorder = StatsRecorder::start();
+        let triples = self.generate_batch(count)?;
+        let bytes = triple_distribution_bytes(&triples)?;
+        
+        let stats = recorder.stats_mut();
+        stats.record_rounds(self.rounds_per_batch());
+        stats.record_sent(bytes);
+        stats.record_received(bytes);
+        stats.mark_estimated();
+        
+        Ok(recorder.finish(triples))
+    }
 }
 
-impl BeaverTriple {
+/// 估计分发三元组分享所需的字节数
+fn triple_distribution_bytes(triples: &[CompleteBeaverTriple]) -> Result<u64> {
+    let mut total = 0u64;
+    for triple in triples {
+        for share in triple.shares.values() {
+            total += bincode::serialized_size(share)
+                .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+        }
+    }
+    Ok(total)
+}
+
+impl<F: Field> BeaverTriple<F> {
     /// 创建新的 Beaver 三元组分享
     /// 
     /// # 参数
@@ -121,7 +204,7 @@ impl BeaverTriple {
     /// # 返回值
     /// 
     /// 返回新创建的 `BeaverTriple` 实例
-    pub fn new(a: Share, b: Share, c: Share, id: u64) -> Self {
+    pub fn new(a: Share<F>, b: Share<F>, c: Share<F>, id: u64) -> Self {
         Self { a, b, c, id }
     }
     
@@ -142,11 +225,43 @@ impl BeaverTriple {
     /// 
     /// 返回持有此三元组分享的参与方 ID
     pub fn get_party_id(&self) -> usize {
-        self.a.x as usize
+        self.a.x.to_u128() as usize
+    }
+    
+    /// Beaver 乘法第 1 步：本方计算 d_i = x_i - a_i 和 e_i = y_i - b_i
+    /// 
+    /// 结果交给 `open_value`（或网络上的公开步骤）得到公开的 d、e。
+    /// 
+    /// # 参数
+    /// 
+    /// * `x` - 本方持有的 x 的分享
+    /// * `y` - 本方持有的 y 的分享
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 (d_i, e_i)；分享与三元组不属于同一参与方时返回错误
+    pub fn mask(&self, x: &Share<F>, y: &Share<F>) -> Result<(Share<F>, Share<F>)> {
+        if !self.is_consistent() || x.x != self.a.x || y.x != self.a.x {
+            return Err(MpcError::ProtocolError(format!(
+                "Shares at x = {} and {} do not match the triple share at x = {}", x.x, y.x, self.a.x
+            )));
+        }
+        Ok((
+            Share::new(x.x, x.y.sub(self.a.y)),
+            Share::new(y.x, y.y.sub(self.b.y)),
+        ))
+    }
+    
+    /// Beaver 乘法第 3 步：由公开的 d、e 在本地计算 z_i = c_i + d·b_i + e·a_i + d·e
+    /// 
+    /// 常数 d·e 加到每一方的分享上，多项式的常数项随之增加 d·e。
+    pub fn combine(&self, d: F, e: F) -> Share<F> {
+        let z = self.c.y.add(d.mul(self.b.y)).add(e.mul(self.a.y).add(d.mul(e)));
+        Share::new(self.c.x, z)
     }
 }
 
-impl CompleteBeaverTriple {
+impl<F: Field> CompleteBeaverTriple<F> {
     /// 创建新的完整 Beaver 三元组
     /// 
     /// # 参数
@@ -156,7 +271,7 @@ impl CompleteBeaverTriple {
     /// # 返回值
     /// 
     /// 返回新创建的 `CompleteBeaverTriple` 实例，不包含原始值
-    pub fn new(shares: HashMap<usize, BeaverTriple>) -> Self {
+    pub fn new(shares: HashMap<usize, BeaverTriple<F>>) -> Self {
         Self {
             shares,
             original_values: None,
@@ -176,8 +291,8 @@ impl CompleteBeaverTriple {
     /// 
     /// 返回新创建的 `CompleteBeaverTriple` 实例，包含原始值
     pub fn new_with_values(
-        shares: HashMap<usize, BeaverTriple>,
-        original: (u64, u64, u64),
+        shares: HashMap<usize, BeaverTriple<F>>,
+        original: (F, F, F),
     ) -> Self {
         Self {
             shares,
@@ -185,6 +300,29 @@ impl CompleteBeaverTriple {
         }
     }
     
+    /// 由可信分发者生成一个三元组，并用 Shamir 分享给各方
+    /// 
+    /// 各方的分享以横坐标（1, 2, ..., n）为键，不保存原始值。
+    /// 
+    /// # 参数
+    /// 
+    /// * `threshold` - 重构门限
+    /// * `party_count` - 参与方数量
+    /// * `id` - 三元组标识符
+    pub fn deal(threshold: usize, party_count: usize, id: u64) -> Result<Self> {
+        let mut rng = thread_rng();
+        let (a, b) = (F::random(&mut rng), F::random(&mut rng));
+        let a_shares = Shamir::share(&a, threshold, party_count)?;
+        let b_shares = Shamir::share(&b, threshold, party_count)?;
+        let c_shares = Shamir::share(&a.mul(b), threshold, party_count)?;
+        let shares = a_shares.into_iter()
+            .zip(b_shares)
+            .zip(c_shares)
+            .map(|((a, b), c)| (a.x.to_u128() as usize, BeaverTriple::new(a, b, c, id)))
+            .collect();
+        Ok(Self::new(shares))
+    }
+    
     /// 获取指定方的三元组分享
     /// 
     /// # 参数
@@ -194,7 +332,7 @@ impl CompleteBeaverTriple {
     /// # 返回值
     /// 
     /// 如果存在该参与方的分享，返回 `Some(&BeaverTriple)`，否则返回 `None`
-    pub fn get_share(&self, party_id: usize) -> Option<&BeaverTriple> {
+    pub fn get_share(&self, party_id: usize) -> Option<&BeaverTriple<F>> {
         self.shares.get(&party_id)
     }
     
@@ -231,79 +369,82 @@ impl CompleteBeaverTriple {
             }
             
             
-            use crate::secret_sharing::{ShamirSecretSharing, SecretSharing};
-            
-            let reconstructed_a = ShamirSecretSharing::reconstruct(&a_shares[0..threshold], threshold)?;
-            let reconstructed_b = ShamirSecretSharing::reconstruct(&b_shares[0..threshold], threshold)?;
-            let reconstructed_c = ShamirSecretSharing::reconstruct(&c_shares[0..threshold], threshold)?;
+            let reconstructed_a = Shamir::reconstruct(&a_shares[0..threshold], threshold)?;
+            let reconstructed_b = Shamir::reconstruct(&b_shares[0..threshold], threshold)?;
+            let reconstructed_c = Shamir::reconstruct(&c_shares[0..threshold], threshold)?;
             
             return Ok(reconstructed_a == a && 
                      reconstructed_b == b && 
                      reconstructed_c == c &&
-                     c == field_mul(a, b));
+                     c == a.mul(b));
         }
         
         Ok(true)
     }
 }
 
+/// 公开一个分享值：各方广播自己的分享，检查全部分享落在同一个多项式上之后重构
+/// 
+/// 这是本地模拟的公开步骤，输入为所有参与方广播的分享；
+/// 网络上的对应步骤见 `network::session::MpcSession::open`。
+/// 
+/// # 参数
+/// 
+/// * `shares` - 各参与方广播的分享
+/// * `threshold` - 重构门限
+/// 
+/// # 返回值
+/// 
+/// 返回公开的值；分享不足或不一致时返回错误
+pub fn open_value<F: Field>(shares: &[Share<F>], threshold: usize) -> Result<F> {
+    if shares.len() < threshold {
+        return Err(MpcError::InsufficientShares);
+    }
+    if !Shamir::new().verify_shares(shares, threshold) {
+        return Err(MpcError::ProtocolError("Opened shares are inconsistent".to_string()));
+    }
+    Shamir::reconstruct(&shares[..threshold], threshold)
+}
+
 /// 使用 Beaver 三元组进行安全乘法
 /// 
-/// 实现标准的 Beaver 乘法协议：
-/// 1. 计算 d = x - a, e = y - b (需要重构这些值)
-/// 2. 计算 [z] = [c] + d·[b] + e·[a] + d·e
-/// 3. 返回 z = x·y 的分享
-pub fn secure_multiply(
-    x_shares: &[Share],
-    y_shares: &[Share], 
-    beaver_triple: &CompleteBeaverTriple,
+/// 按参与方执行标准的 Beaver 乘法协议，x、y、a、b、c 都不会被重构：
+/// 1. 每方用 `BeaverTriple::mask` 计算 d_i = x_i - a_i, e_i = y_i - b_i
+/// 2. 用 `open_value` 公开 d = x - a, e = y - b（被 a、b 掩盖）
+/// 3. 每方用 `BeaverTriple::combine` 计算 z_i = c_i + d·b_i + e·a_i + d·e
+/// 
+/// # 返回值
+/// 
+/// 返回 z = x·y 的分享，与 `x_shares` 的参与方顺序相同
+pub fn secure_multiply<F: Field>(
+    x_shares: &[Share<F>],
+    y_shares: &[Share<F>], 
+    beaver_triple: &CompleteBeaverTriple<F>,
     threshold: usize,
-) -> Result<Vec<Share>> {
+) -> Result<Vec<Share<F>>> {
     if x_shares.len() != y_shares.len() || x_shares.len() < threshold {
         return Err(MpcError::InvalidThreshold);
     }
     
-    use crate::secret_sharing::{ShamirSecretSharing, SecretSharing};
-    
-    // 1. 重构 x, y, a, b
-    let x = ShamirSecretSharing::reconstruct(&x_shares[0..threshold], threshold)?;
-    let y = ShamirSecretSharing::reconstruct(&y_shares[0..threshold], threshold)?;
-    
-    // 确保按正确顺序获取beaver三元组的分享
-    let mut party_ids: Vec<_> = beaver_triple.shares.keys().collect();
-    party_ids.sort();
-    
-    let mut a_shares = Vec::new();
-    let mut b_shares = Vec::new();
-    
-    for &party_id in &party_ids {
-        if let Some(triple) = beaver_triple.shares.get(party_id) {
-            a_shares.push(triple.a.clone());
-            b_shares.push(triple.b.clone());
-        }
-    }
-    
-    let a = ShamirSecretSharing::reconstruct(&a_shares[0..threshold], threshold)?;
-    let b = ShamirSecretSharing::reconstruct(&b_shares[0..threshold], threshold)?;
-    
-    // 2. 计算 d = x - a, e = y - b (这些是公开值)
-    let d = field_sub(x, a);
-    let e = field_sub(y, b);
-    
-    // 3. 计算 z = xy = (d+a)(e+b) = de + db + ea + ab
-    //    其中 ab = c，所以 z = de + db + ea + c
-    //    由于 d, e 是公开的，我们可以直接计算 de, db, ea
-    let de = field_mul(d, e);
-    let db = field_mul(d, b); 
-    let ea = field_mul(e, a);
+    // 每方找到自己的三元组分享并计算 d_i, e_i
+    let triples = x_shares.iter()
+        .map(|share| {
+            beaver_triple.shares.values()
+                .find(|triple| triple.a.x == share.x)
+                .ok_or(MpcError::InsufficientShares)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let (d_shares, e_shares): (Vec<Share<F>>, Vec<Share<F>>) = triples.iter()
+        .zip(x_shares.iter().zip(y_shares))
+        .map(|(triple, (x, y))| triple.mask(x, y))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
     
-    // z = de + db + ea + c
-    let z = field_add(field_add(field_add(de, db), ea), beaver_triple.original_values.unwrap().2);
+    let d = open_value(&d_shares, threshold)?;
+    let e = open_value(&e_shares, threshold)?;
     
-    // 4. 生成 z 的新的秘密分享
-    let z_shares_new = ShamirSecretSharing::share(&z, threshold, x_shares.len())?;
-    
-    Ok(z_shares_new)
+    Ok(triples.iter().map(|triple| triple.combine(d, e)).collect())
 }
 
 /// 批量安全乘法
@@ -334,6 +475,96 @@ pub fn batch_secure_multiply(
     Ok(results)
 }
 
+/// 按参与方在一轮内执行一批 Beaver 乘法，并把实际广播的消息计入 `stats`
+///
+/// 参与方以分享的 x 坐标标识，顺序取自 `x_shares_batch` 的第一个乘法。
+/// 每一方把自己在全部乘法中的 (d_i, e_i) 打成一条消息广播给其余各方，
+/// 字节数取该消息序列化后的长度；整批记一轮，不计预处理消耗。
+pub(crate) fn batch_multiply_recorded(
+    x_shares_batch: &[Vec<Share>],
+    y_shares_batch: &[Vec<Share>],
+    beaver_triples: &[CompleteBeaverTriple],
+    threshold: usize,
+    stats: &mut ProtocolStats,
+) -> Result<Vec<Vec<Share>>> {
+    if x_shares_batch.len() != y_shares_batch.len() ||
+       x_shares_batch.len() != beaver_triples.len() {
+        return Err(MpcError::ProtocolError(
+            "Batch arrays must have same length".to_string()
+        ));
+    }
+    let Some(first) = x_shares_batch.first() else {
+        return Ok(Vec::new());
+    };
+    let parties: Vec<usize> = first.iter().map(|share| share.x as usize).collect();
+    
+    // 每方找到自己在每个乘法中的三元组分享，triples[k][j] 属于第 j 方
+    let triples = x_shares_batch.iter()
+        .zip(y_shares_batch)
+        .zip(beaver_triples)
+        .map(|((x_shares, y_shares), triple)| {
+            if x_shares.len() != parties.len() || y_shares.len() != parties.len() || x_shares.len() < threshold {
+                return Err(MpcError::InvalidThreshold);
+            }
+            x_shares.iter()
+                .map(|share| {
+                    triple.shares.values()
+                        .find(|t| t.a.x == share.x)
+                        .ok_or(MpcError::InsufficientShares)
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    
+    // 第 j 方的消息：全部乘法的 (d_i, e_i)
+    let messages = (0..parties.len())
+        .map(|j| {
+            triples.iter()
+                .zip(x_shares_batch.iter().zip(y_shares_batch))
+                .map(|(triples, (x, y))| triples[j].mask(&x[j], &y[j]))
+                .collect::<Result<Vec<(Share, Share)>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    stats.record_broadcast(&parties, &messages)?;
+    stats.record_rounds(1);
+    
+    triples.iter()
+        .enumerate()
+        .map(|(k, triples)| {
+            let d_shares: Vec<Share> = messages.iter().map(|message| message[k].0.clone()).collect();
+            let e_shares: Vec<Share> = messages.iter().map(|message| message[k].1.clone()).collect();
+            let d = open_value(&d_shares, threshold)?;
+            let e = open_value(&e_shares, threshold)?;
+            Ok(triples.iter().map(|triple| triple.combine(d, e)).collect())
+        })
+        .collect()
+}
+
+/// 批量安全乘法，并返回执行统计
+/// 
+/// 第 k 个乘法使用 `beaver_triples[k]`，多余的三元组不会被使用，也不计入预处理消耗。
+/// 所有乘法的 d, e 在同一轮中公开：每一方把自己的 (d_i, e_i) 打成一条消息广播给其余各方，
+/// 字节数取实际消息序列化后的长度，各方的收发量见 `party_traffic`。
+pub fn batch_secure_multiply_with_stats(
+    x_shares_batch: &[Vec<Share>],
+    y_shares_batch: &[Vec<Share>],
+    beaver_triples: &[CompleteBeaverTriple],
+    threshold: usize,
+) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+    if beaver_triples.len() < x_shares_batch.len() {
+        return Err(MpcError::ProtocolError(
+            "Not enough Beaver triples for the batch".to_string()
+        ));
+    }
+    let used = &beaver_triples[..x_shares_batch.len()];
+    
+    let mut recorder = StatsRecorder::start();
+    let results = batch_multiply_recorded(x_shares_batch, y_shares_batch, used, threshold, recorder.stats_mut())?;
+    recorder.stats_mut().record_preprocessing(used.len());
+    
+    Ok(recorder.finish(results))
+}
+
 /// 验证 Beaver 三元组的批次
 pub fn verify_triple_batch(
     triples: &[CompleteBeaverTriple],
@@ -190,6 +190,11 @@ impl BeaverTripleGenerator for OLEBeaverGenerator {
     fn get_threshold(&self) -> usize {
         self.threshold
     }
+    
+    fn rounds_per_batch(&self) -> usize {
+        // OLE 需要发送方与接收方之间的一次往返
+        2
+    }
 }
 
 /// 批量 OLE Beaver 三元组生成器
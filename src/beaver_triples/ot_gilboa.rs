@@ -0,0 +1,165 @@
+//! # 基于 OT 的 Beaver 三元组生成器 (Gilboa)
+//!
+//! 两方场景下可以只用不经意传输生成三元组，不需要同态加密或可信方。
+//!
+//! ## 协议概述
+//!
+//! 1. **本地随机**: 参与方 i 选择随机的 a_i、b_i
+//! 2. **交叉项**: 用 Gilboa 乘法（每个交叉项 64 次相关 OT）得到
+//!    a_0·b_1 与 a_1·b_0 的加法分享
+//! 3. **本地组合**: c_i = a_i·b_i + 两个交叉项的分享，满足 c_0 + c_1 = (a_0 + a_1)(b_0 + b_1)
+//! 4. **格式转换**: 加法分享除以各自的拉格朗日系数，得到门限为 2 的 Shamir 分享
+//!
+//! 相关 OT 来自 OT 扩展：每个方向的基础 OT 只在生成器第一次使用时执行一次。
+//! 两个交叉项可以并行执行，因此每批只需要 Gilboa 乘法的 2 轮通信。
+
+use super::*;
+use crate::oblivious_transfer::{setup_ot_extension, OtExtensionReceiver, OtExtensionSender, SecurityModel};
+use crate::protocols::dot_product::ot_dot_product_2pc;
+use crate::protocols::stats::ProtocolStats;
+use crate::secret_sharing::{field_inv, ShamirSecretSharing};
+use crate::utils::random_field_element;
+
+/// Gilboa 乘法所需的通信轮数
+const GILBOA_ROUNDS: usize = 2;
+
+/// 基于 OT 的两方 Beaver 三元组生成器
+pub struct OTGilboaBeaverGenerator {
+    /// 当前方的 ID
+    party_id: usize,
+    /// 把加法分享转换为 Shamir 分享的系数（拉格朗日系数的逆）
+    share_scale: [u64; 2],
+    /// 生成的三元组计数器
+    triple_counter: u64,
+    /// 两个方向的 OT 扩展，第 i 项中参与方 i 为发送方；首次生成时建立
+    extensions: Option<[(OtExtensionSender, OtExtensionReceiver); 2]>,
+}
+
+impl OTGilboaBeaverGenerator {
+    /// 创建新的 OT Beaver 三元组生成器
+    ///
+    /// # 参数
+    /// - `party_count`: 参与方总数（必须为 2）
+    /// - `threshold`: 重构门限（必须为 2）
+    /// - `party_id`: 当前方的 ID (从 0 开始)
+    ///
+    /// # 返回
+    /// 返回配置好的生成器实例
+    pub fn new(party_count: usize, threshold: usize, party_id: usize) -> Result<Self> {
+        if party_count != 2 {
+            return Err(MpcError::ProtocolError(
+                "OT-based triple generation supports exactly two parties".to_string()
+            ));
+        }
+        if threshold != 2 {
+            return Err(MpcError::InvalidThreshold);
+        }
+        if party_id >= party_count {
+            return Err(MpcError::ProtocolError(
+                "Party ID must be less than party count".to_string()
+            ));
+        }
+
+        let lagrange = ShamirSecretSharing::new().precompute_lagrange_coefficients(&[1, 2])?;
+        let mut share_scale = [0u64; 2];
+        for (scale, coefficient) in share_scale.iter_mut().zip(lagrange) {
+            *scale = field_inv(coefficient).ok_or_else(|| {
+                MpcError::CryptographicError("Lagrange coefficient is not invertible".to_string())
+            })?;
+        }
+
+        Ok(Self {
+            party_id,
+            share_scale,
+            triple_counter: 0,
+            extensions: None,
+        })
+    }
+
+    /// 获取当前方的 ID
+    pub fn get_party_id(&self) -> usize {
+        self.party_id
+    }
+
+    /// 生成一个三元组并返回两次 Gilboa 乘法的统计
+    fn generate_with_stats(&mut self) -> Result<(CompleteBeaverTriple, ProtocolStats)> {
+        let a = [random_field_element(), random_field_element()];
+        let b = [random_field_element(), random_field_element()];
+
+        if self.extensions.is_none() {
+            self.extensions = Some([
+                setup_ot_extension(SecurityModel::SemiHonest)?,
+                setup_ot_extension(SecurityModel::SemiHonest)?,
+            ]);
+        }
+        let [(sender_0, receiver_1), (sender_1, receiver_0)] = self.extensions.as_mut()
+            .expect("extensions were set up above");
+
+        // 两个交叉项并行执行：a_0·b_1 由参与方 0 作为发送方，a_1·b_0 由参与方 1 作为发送方
+        let (cross_01, stats_01) = ot_dot_product_2pc(sender_0, receiver_1, &[a[0]], &[b[1]])?.into_parts();
+        let (cross_10, stats_10) = ot_dot_product_2pc(sender_1, receiver_0, &[a[1]], &[b[0]])?.into_parts();
+
+        let c = [
+            field_add(field_add(field_mul(a[0], b[0]), cross_01.x_holder.value), cross_10.y_holder.value),
+            field_add(field_add(field_mul(a[1], b[1]), cross_01.y_holder.value), cross_10.x_holder.value),
+        ];
+
+        let triple_id = self.triple_counter;
+        self.triple_counter += 1;
+
+        let mut shares = HashMap::new();
+        for (i, &scale) in self.share_scale.iter().enumerate() {
+            let x = i as u64 + 1;
+            shares.insert(i + 1, BeaverTriple::new(
+                Share::new(x, field_mul(a[i], scale)),
+                Share::new(x, field_mul(b[i], scale)),
+                Share::new(x, field_mul(c[i], scale)),
+                triple_id,
+            ));
+        }
+
+        let mut stats = stats_01;
+        stats.bytes_sent += stats_10.bytes_sent;
+        stats.bytes_received += stats_10.bytes_received;
+        Ok((CompleteBeaverTriple::new(shares), stats))
+    }
+}
+
+impl BeaverTripleGenerator for OTGilboaBeaverGenerator {
+    fn generate_single(&mut self) -> Result<CompleteBeaverTriple> {
+        Ok(self.generate_with_stats()?.0)
+    }
+
+    fn generate_batch(&mut self, count: usize) -> Result<Vec<CompleteBeaverTriple>> {
+        (0..count).map(|_| self.generate_single()).collect()
+    }
+
+    fn verify_triple(&self, triple: &CompleteBeaverTriple) -> Result<bool> {
+        triple.verify(2)
+    }
+
+    fn get_party_count(&self) -> usize {
+        2
+    }
+
+    fn get_threshold(&self) -> usize {
+        2
+    }
+
+    fn rounds_per_batch(&self) -> usize {
+        GILBOA_ROUNDS
+    }
+
+    fn generate_batch_with_stats(&mut self, count: usize) -> Result<ProtocolOutput<Vec<CompleteBeaverTriple>>> {
+        let mut recorder = StatsRecorder::start();
+        let mut triples = Vec::with_capacity(count);
+        for _ in 0..count {
+            let (triple, stats) = self.generate_with_stats()?;
+            recorder.stats_mut().record_sent(stats.bytes_sent);
+            recorder.stats_mut().record_received(stats.bytes_received);
+            triples.push(triple);
+        }
+        recorder.stats_mut().record_rounds(self.rounds_per_batch());
+        Ok(recorder.finish(triples))
+    }
+}
@@ -0,0 +1,403 @@
+//! # 预处理池 (Preprocessing Pool)
+//!
+//! 离线阶段生成的三元组存入 `StorageBackend`，在线阶段按生成顺序取用。
+//! 取出的三元组会立即从存储中删除，保证每个三元组只被使用一次；
+//! 使用持久化后端时，进程重启后池中剩余的三元组仍然可用。
+//!
+//! ## 新鲜度策略
+//!
+//! 每个三元组都带有 `MaterialStamp`：生成时间、生成方标识（生成方及其密钥）、
+//! 生成时的安全模型和纪元。长期运行的池会逐渐积累可疑的材料，`FreshnessPolicy`
+//! 可以拒绝超过有效期、来自已吊销生成方、纪元过旧或安全模型不足的三元组：
+//!
+//! - `take` 跳过并删除遇到的过期三元组，只返回满足策略的三元组
+//! - `purge_stale` 扫描整个池，删除所有过期三元组
+//!
+//! 每次清理都会按（生成方，原因）向审计日志写入 `ThreatType::StaleMaterial` 事件。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::*;
+//! use mpc_api::oblivious_transfer::SecurityModel;
+//! use mpc_api::storage::MemoryBackend;
+//! use std::sync::Arc;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let pool = PreprocessingPool::new(Arc::new(MemoryBackend::new()), "session-1");
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//! pool.push(&generator.generate_batch(8)?, &MaterialStamp::new("dealer-1", SecurityModel::SemiHonest, 0))?;
+//!
+//! let triples = pool.take(3)?;
+//! assert_eq!(triples.len(), 3);
+//! assert_eq!(pool.len()?, 5);
+//!
+//! // 吊销生成方后，其余三元组在清理时被删除并记录审计事件
+//! pool.set_freshness_policy(FreshnessPolicy::new().revoke_generator("dealer-1"));
+//! assert_eq!(pool.purge_stale()?, 5);
+//! assert!(pool.is_empty()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::CompleteBeaverTriple;
+use crate::oblivious_transfer::SecurityModel;
+use crate::security::{AuditLogger, SecurityEvent, SecurityLevel, SecurityPolicy, ThreatType};
+use crate::storage::{load_value, store_value, StorageBackend};
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// 保存池游标的命名空间
+const META_NAMESPACE: &str = "preprocessing-meta";
+
+/// 预处理材料的来源信息，随每个三元组一起存储
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaterialStamp {
+    /// 生成时间
+    pub created_at: SystemTime,
+    /// 生成方标识，应能区分生成方使用的密钥（例如 `"dealer-1/key-3"`）
+    pub generator_id: String,
+    /// 生成时的安全模型
+    pub security_model: SecurityModel,
+    /// 生成时的纪元，密钥轮换或参数变更时递增
+    pub epoch: u64,
+}
+
+impl MaterialStamp {
+    /// 创建以当前时间为生成时间的标记
+    pub fn new(generator_id: &str, security_model: SecurityModel, epoch: u64) -> Self {
+        Self {
+            created_at: SystemTime::now(),
+            generator_id: generator_id.to_string(),
+            security_model,
+            epoch,
+        }
+    }
+
+    /// 指定生成时间
+    pub fn with_created_at(mut self, created_at: SystemTime) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
+    /// 到 `now` 为止的存放时间；生成时间在未来时为 0
+    pub fn age(&self, now: SystemTime) -> Duration {
+        now.duration_since(self.created_at).unwrap_or_default()
+    }
+}
+
+/// 三元组不满足新鲜度策略的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StaleReason {
+    /// 超过最长存放时间
+    Expired,
+    /// 生成方已被吊销
+    RevokedGenerator,
+    /// 纪元早于策略要求的最小纪元
+    OutdatedEpoch,
+    /// 生成时的安全模型弱于策略要求
+    WeakSecurityModel,
+}
+
+impl fmt::Display for StaleReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StaleReason::Expired => write!(f, "expired"),
+            StaleReason::RevokedGenerator => write!(f, "revoked generator"),
+            StaleReason::OutdatedEpoch => write!(f, "outdated epoch"),
+            StaleReason::WeakSecurityModel => write!(f, "weak security model"),
+        }
+    }
+}
+
+/// 预处理材料的新鲜度策略
+///
+/// 默认策略接受所有三元组。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FreshnessPolicy {
+    /// 最长存放时间
+    pub max_age: Option<Duration>,
+    /// 最小纪元
+    pub min_epoch: u64,
+    /// 要求的最低安全模型
+    pub minimum_model: SecurityModel,
+    /// 已吊销的生成方标识
+    pub revoked_generators: HashSet<String>,
+}
+
+impl FreshnessPolicy {
+    /// 创建接受所有三元组的策略
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 拒绝存放时间超过 `max_age` 的三元组
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// 拒绝纪元小于 `epoch` 的三元组
+    pub fn with_min_epoch(mut self, epoch: u64) -> Self {
+        self.min_epoch = epoch;
+        self
+    }
+
+    /// 要求 `SecurityModel::Malicious` 时拒绝半诚实模型下生成的三元组
+    pub fn with_minimum_model(mut self, model: SecurityModel) -> Self {
+        self.minimum_model = model;
+        self
+    }
+
+    /// 拒绝生成方 `generator_id` 生成的三元组
+    pub fn revoke_generator(mut self, generator_id: &str) -> Self {
+        self.revoked_generators.insert(generator_id.to_string());
+        self
+    }
+
+    /// 检查三元组标记在 `now` 时是否满足策略
+    pub fn check(&self, stamp: &MaterialStamp, now: SystemTime) -> std::result::Result<(), StaleReason> {
+        if self.revoked_generators.contains(&stamp.generator_id) {
+            return Err(StaleReason::RevokedGenerator);
+        }
+        if self.max_age.is_some_and(|max_age| stamp.age(now) > max_age) {
+            return Err(StaleReason::Expired);
+        }
+        if stamp.epoch < self.min_epoch {
+            return Err(StaleReason::OutdatedEpoch);
+        }
+        if self.minimum_model == SecurityModel::Malicious && stamp.security_model != SecurityModel::Malicious {
+            return Err(StaleReason::WeakSecurityModel);
+        }
+        Ok(())
+    }
+}
+
+/// 池中的一条记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PoolEntry {
+    stamp: MaterialStamp,
+    triple: CompleteBeaverTriple,
+}
+
+/// 池的读写游标：`[head, tail)` 区间内的序号对应尚未使用的三元组，
+/// 其中 `purged` 个位置已被清理
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct PoolCursor {
+    head: u64,
+    tail: u64,
+    purged: u64,
+}
+
+impl PoolCursor {
+    fn available(&self) -> u64 {
+        self.tail - self.head - self.purged
+    }
+}
+
+/// 存放在 `StorageBackend` 中的先进先出三元组池
+#[derive(Debug)]
+pub struct PreprocessingPool {
+    backend: Arc<dyn StorageBackend>,
+    namespace: String,
+    policy: Mutex<FreshnessPolicy>,
+    audit_logger: Arc<AuditLogger>,
+    /// 串行化游标的读-改-写
+    lock: Mutex<()>,
+}
+
+impl PreprocessingPool {
+    /// 创建（或重新打开）名为 `name` 的池，使用接受所有三元组的策略和独立的审计日志
+    ///
+    /// # 参数
+    /// - `backend`: 存储后端，可与会话存储、审计日志共用
+    /// - `name`: 池名称，同一后端中的不同池互不影响
+    pub fn new(backend: Arc<dyn StorageBackend>, name: &str) -> Self {
+        Self {
+            backend,
+            namespace: format!("preprocessing/{}", name),
+            policy: Mutex::new(FreshnessPolicy::default()),
+            audit_logger: Arc::new(AuditLogger::new(SecurityPolicy::default())),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// 设置新鲜度策略
+    pub fn with_freshness_policy(self, policy: FreshnessPolicy) -> Self {
+        self.set_freshness_policy(policy);
+        self
+    }
+
+    /// 使用共享的审计日志
+    pub fn with_audit_logger(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// 替换新鲜度策略，例如在吊销生成方的密钥之后
+    pub fn set_freshness_policy(&self, policy: FreshnessPolicy) {
+        *self.policy.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = policy;
+    }
+
+    /// 当前的新鲜度策略
+    pub fn freshness_policy(&self) -> FreshnessPolicy {
+        self.policy.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
+    /// 获取审计日志
+    pub fn audit_logger(&self) -> &Arc<AuditLogger> {
+        &self.audit_logger
+    }
+
+    /// 追加一批三元组，每个三元组都记录相同的标记
+    pub fn push(&self, triples: &[CompleteBeaverTriple], stamp: &MaterialStamp) -> Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut cursor = self.load_cursor()?;
+        for triple in triples {
+            let entry = PoolEntry { stamp: stamp.clone(), triple: triple.clone() };
+            store_value(self.backend.as_ref(), &self.namespace, &cursor.tail.to_be_bytes(), &entry)?;
+            cursor.tail += 1;
+        }
+        self.store_cursor(cursor)
+    }
+
+    /// 按生成顺序取出 `count` 个满足新鲜度策略的三元组
+    ///
+    /// 途中遇到的过期三元组会被删除并记录审计事件。满足策略的三元组不足时返回错误，
+    /// 且不取出任何三元组。
+    pub fn take(&self, count: usize) -> Result<Vec<CompleteBeaverTriple>> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let policy = self.freshness_policy();
+        let now = SystemTime::now();
+        let mut cursor = self.load_cursor()?;
+
+        let mut triples = Vec::with_capacity(count);
+        let mut stale = Vec::new();
+        let mut holes = 0;
+        let mut sequence = cursor.head;
+        while triples.len() < count && sequence < cursor.tail {
+            match self.load_entry(sequence)? {
+                None => holes += 1,
+                Some(entry) => match policy.check(&entry.stamp, now) {
+                    Ok(()) => triples.push(entry.triple),
+                    Err(reason) => stale.push((sequence, entry.stamp, reason)),
+                },
+            }
+            sequence += 1;
+        }
+        if holes > cursor.purged {
+            return Err(MpcError::StorageError(format!(
+                "Missing preprocessing entries in {}..{}", cursor.head, sequence
+            )));
+        }
+
+        if triples.len() < count {
+            self.purge_entries(&mut cursor, stale)?;
+            return Err(MpcError::ProtocolError(format!(
+                "Preprocessing pool has {} fresh triples, {} requested", triples.len(), count
+            )));
+        }
+
+        // 先推进游标再删除，中途失败最多留下无法再取出的旧条目
+        let head = cursor.head;
+        cursor.head = sequence;
+        cursor.purged -= holes;
+        self.store_cursor(cursor)?;
+        for sequence in head..sequence {
+            self.backend.delete(&self.namespace, &sequence.to_be_bytes())?;
+        }
+        self.audit_purged(stale.into_iter().map(|(_, stamp, reason)| (stamp, reason)))?;
+        Ok(triples)
+    }
+
+    /// 删除池中所有不满足新鲜度策略的三元组
+    ///
+    /// # 返回值
+    /// 返回删除的三元组数量
+    pub fn purge_stale(&self) -> Result<usize> {
+        let _guard = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let policy = self.freshness_policy();
+        let now = SystemTime::now();
+        let mut cursor = self.load_cursor()?;
+
+        let mut stale = Vec::new();
+        for sequence in cursor.head..cursor.tail {
+            if let Some(entry) = self.load_entry(sequence)? {
+                if let Err(reason) = policy.check(&entry.stamp, now) {
+                    stale.push((sequence, entry.stamp, reason));
+                }
+            }
+        }
+        let purged = stale.len();
+        self.purge_entries(&mut cursor, stale)?;
+        Ok(purged)
+    }
+
+    /// 池中剩余的三元组数量，包括尚未清理的过期三元组
+    pub fn len(&self) -> Result<usize> {
+        Ok(self.load_cursor()?.available() as usize)
+    }
+
+    /// 池是否为空
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// 在区间中间留下空位：先更新游标再删除，与 `take` 相同
+    fn purge_entries(&self, cursor: &mut PoolCursor, stale: Vec<(u64, MaterialStamp, StaleReason)>) -> Result<()> {
+        if stale.is_empty() {
+            return Ok(());
+        }
+        cursor.purged += stale.len() as u64;
+        self.store_cursor(*cursor)?;
+        for (sequence, _, _) in &stale {
+            self.backend.delete(&self.namespace, &sequence.to_be_bytes())?;
+        }
+        self.audit_purged(stale.into_iter().map(|(_, stamp, reason)| (stamp, reason)))
+    }
+
+    /// 按（生成方，原因）汇总清理的三元组并记录审计事件
+    fn audit_purged(&self, purged: impl Iterator<Item = (MaterialStamp, StaleReason)>) -> Result<()> {
+        let mut groups: BTreeMap<(String, StaleReason), (usize, SystemTime, u64)> = BTreeMap::new();
+        for (stamp, reason) in purged {
+            let group = groups.entry((stamp.generator_id, reason)).or_insert((0, stamp.created_at, stamp.epoch));
+            group.0 += 1;
+            group.1 = group.1.min(stamp.created_at);
+            group.2 = group.2.min(stamp.epoch);
+        }
+
+        for ((generator_id, reason), (count, oldest, epoch)) in groups {
+            let created_at = oldest.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_secs();
+            let mut event = SecurityEvent::new(
+                ThreatType::StaleMaterial,
+                SecurityLevel::Medium,
+                format!("purged {} preprocessing triples: {}", count, reason),
+            )
+            .with_context("pool".to_string(), self.namespace.clone())
+            .with_context("generator_id".to_string(), generator_id)
+            .with_context("count".to_string(), count.to_string())
+            .with_context("oldest_created_at".to_string(), created_at.to_string())
+            .with_context("min_epoch".to_string(), epoch.to_string());
+            // 三元组在记录事件前已删除
+            event.mark_handled();
+            self.audit_logger.log_event(event)?;
+        }
+        Ok(())
+    }
+
+    fn load_entry(&self, sequence: u64) -> Result<Option<PoolEntry>> {
+        load_value(self.backend.as_ref(), &self.namespace, &sequence.to_be_bytes())
+    }
+
+    fn load_cursor(&self) -> Result<PoolCursor> {
+        Ok(load_value(self.backend.as_ref(), META_NAMESPACE, self.namespace.as_bytes())?.unwrap_or_default())
+    }
+
+    fn store_cursor(&self, cursor: PoolCursor) -> Result<()> {
+        store_value(self.backend.as_ref(), META_NAMESPACE, self.namespace.as_bytes(), &cursor)
+    }
+}
@@ -0,0 +1,209 @@
+//! # 牺牲验证 (Triple Sacrificing)
+//!
+//! `verify_triple` 需要重构 a、b、c，只适合测试。牺牲验证在不公开三元组的情况下
+//! 检查 c = a·b：把一批三元组两两配对，每对中的 (x, y, z) 被牺牲掉，用来检查 (a, b, c)：
+//!
+//! 1. 各方在三元组生成之后共同生成公开随机种子（例如硬币抛掷的结果），
+//!    由种子为每一对派生随机挑战 t ≠ 0
+//! 2. 公开 ρ = t·a - x 和 σ = b - y，二者被 x、y 均匀掩盖，不泄露 a、b
+//! 3. 各方以 `CommittedMessage` 承诺 t·c - z - σ·x - ρ·y - σ·ρ 的分享，收齐承诺后再打开；
+//!    两个三元组都正确时其值为 0。先承诺使最后发言的一方无法根据其他人的分享调整自己的分享
+//!
+//! 若 c = a·b + Δ、z = x·y + Δ'，检查值为 t·Δ - Δ'。t 在三元组确定后才选出，
+//! 坏三元组通过检查的概率不超过 1/p，与批量大小无关；
+//! 需要在小批量上获得统计安全时也不必像 cut-and-bucket 那样使用大桶。
+//!
+//! 检查失败或公开的分享不在同一多项式上时，该对两个三元组都被丢弃，
+//! 其余对的结果不受影响；调用方可以根据 `rejected` 决定是否中止协议。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::*;
+//! use mpc_api::secret_sharing::field_add;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//! let mut triples = generator.generate_batch(8)?;
+//! // 篡改第 2 个三元组（与第 3 个配对）
+//! for share in triples[2].shares.values_mut() {
+//!     share.c.y = field_add(share.c.y, 1);
+//! }
+//!
+//! let outcome = TripleSacrifice::new(2).verify(triples, [7u8; 32])?.result;
+//! assert_eq!(outcome.safe_indices, vec![0, 4, 6]);
+//! assert_eq!(outcome.rejected, vec![2, 3]);
+//! assert_eq!(outcome.safe.len(), 3);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::*;
+use crate::commitment::{CommittedMessage, MessageCommitment};
+use crate::secret_sharing::{SecretSharing, ShamirSecretSharing};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// 检查值承诺的域分隔标签
+const CHECK_COMMITMENT_DOMAIN: &[u8] = b"mpc_api/beaver_triples/sacrifice/check";
+
+/// 每对三元组中每个参与方发给另一方的字节数：ρ、σ 分享，检查值承诺，检查值分享及承诺随机数
+const PAIR_MESSAGE_BYTES: u64 = 8 + 8 + 32 + 8 + 32;
+
+/// 一批三元组的牺牲验证结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SacrificeOutcome {
+    /// 通过检查、可以使用的三元组
+    pub safe: Vec<CompleteBeaverTriple>,
+    /// `safe` 中每个三元组在输入批次中的下标
+    pub safe_indices: Vec<usize>,
+    /// 检查失败的对中两个三元组的下标
+    pub rejected: Vec<usize>,
+}
+
+impl SacrificeOutcome {
+    /// 是否没有任何检查失败
+    pub fn is_clean(&self) -> bool {
+        self.rejected.is_empty()
+    }
+}
+
+/// 牺牲验证器
+#[derive(Debug, Clone, Copy)]
+pub struct TripleSacrifice {
+    threshold: usize,
+}
+
+impl TripleSacrifice {
+    /// 创建验证器
+    ///
+    /// # 参数
+    /// - `threshold`: 三元组分享的重构门限
+    pub fn new(threshold: usize) -> Self {
+        Self { threshold }
+    }
+
+    /// 验证一批三元组，第 2i 个三元组由第 2i + 1 个检查
+    ///
+    /// # 参数
+    /// - `triples`: 偶数个待验证的三元组
+    /// - `seed`: 三元组生成之后各方共同生成的公开随机种子，决定每一对的挑战
+    ///
+    /// # 返回值
+    /// 返回通过检查的三元组及各三元组的去向；每个输出三元组消耗一个被牺牲的三元组
+    pub fn verify(&self, triples: Vec<CompleteBeaverTriple>, seed: [u8; 32]) -> Result<ProtocolOutput<SacrificeOutcome>> {
+        let mut recorder = StatsRecorder::start();
+        if !triples.len().is_multiple_of(2) {
+            return Err(MpcError::ProtocolError(format!(
+                "Sacrificing needs an even number of triples, got {}", triples.len()
+            )));
+        }
+
+        let party_count = triples.first().map_or(0, |triple| triple.shares.len()) as u64;
+        let mut rng = StdRng::from_seed(seed);
+        let mut outcome = SacrificeOutcome { safe: Vec::new(), safe_indices: Vec::new(), rejected: Vec::new() };
+        let mut triples = triples.into_iter();
+        let mut index = 0;
+        while let (Some(checked), Some(sacrificed)) = (triples.next(), triples.next()) {
+            let challenge = rng.gen_range(1..FIELD_PRIME);
+            if self.check_pair(&checked, &sacrificed, challenge, &check_context(&seed, index)).is_ok() {
+                outcome.safe.push(checked);
+                outcome.safe_indices.push(index);
+            } else {
+                outcome.rejected.extend([index, index + 1]);
+            }
+            index += 2;
+        }
+
+        let pairs = index as u64 / 2;
+        let bytes = pairs * party_count * party_count.saturating_sub(1) * PAIR_MESSAGE_BYTES;
+        let stats = recorder.stats_mut();
+        // 公开 ρ/σ 一轮，承诺检查值一轮，打开检查值一轮
+        stats.record_rounds(3);
+        stats.record_sent(bytes);
+        stats.record_received(bytes);
+        stats.record_preprocessing(pairs as usize);
+
+        Ok(recorder.finish(outcome))
+    }
+
+    /// 用生成器生成 2·`count` 个三元组并验证
+    pub fn generate_verified(
+        &self,
+        generator: &mut dyn BeaverTripleGenerator,
+        count: usize,
+        seed: [u8; 32],
+    ) -> Result<ProtocolOutput<SacrificeOutcome>> {
+        let (triples, mut stats) = generator.generate_batch_with_stats(2 * count)?.into_parts();
+        let (outcome, check_stats) = self.verify(triples, seed)?.into_parts();
+        stats.merge(&check_stats);
+        Ok(ProtocolOutput { result: outcome, stats })
+    }
+
+    /// 用 (x, y, z) 和挑战 t 检查 (a, b, c)
+    fn check_pair(
+        &self,
+        checked: &CompleteBeaverTriple,
+        sacrificed: &CompleteBeaverTriple,
+        t: u64,
+        context: &[u8],
+    ) -> Result<()> {
+        let mut party_ids: Vec<usize> = checked.shares.keys().copied().collect();
+        party_ids.sort_unstable();
+        let pairs = party_ids.iter()
+            .map(|id| {
+                let other = sacrificed.get_share(*id)
+                    .ok_or_else(|| MpcError::ProtocolError("Triples have different parties".to_string()))?;
+                Ok((&checked.shares[id], other))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let rho = self.open(pairs.iter().map(|(c, s)| {
+            Share::new(c.a.x, field_sub(field_mul(t, c.a.y), s.a.y))
+        }))?;
+        let sigma = self.open(pairs.iter().map(|(c, s)| Share::new(c.b.x, field_sub(c.b.y, s.b.y))))?;
+        let rho_sigma = field_mul(rho, sigma);
+
+        // t·c - z - σ·x - ρ·y - σ·ρ，先承诺全部分享再打开
+        let committed = pairs.iter()
+            .map(|(c, s)| {
+                let mut value = field_sub(field_mul(t, c.c.y), s.c.y);
+                value = field_sub(value, field_mul(sigma, s.a.y));
+                value = field_sub(value, field_mul(rho, s.b.y));
+                value = field_sub(value, rho_sigma);
+                CommittedMessage::commit(Share::new(c.c.x, value), context)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let commitments: Vec<MessageCommitment> = committed.iter()
+            .map(|message| message.commitment().clone())
+            .collect();
+        let opened = commitments.iter()
+            .zip(committed)
+            .map(|(commitment, message)| commitment.open_verified(message.open(), context))
+            .collect::<Result<Vec<Share>>>()?;
+        let check = self.open(opened.into_iter())?;
+
+        if check == 0 {
+            Ok(())
+        } else {
+            Err(MpcError::AuthenticationError("Sacrifice check failed".to_string()))
+        }
+    }
+
+    /// 公开一组分享，所有分享必须位于同一多项式上
+    fn open(&self, shares: impl Iterator<Item = Share>) -> Result<u64> {
+        let shares: Vec<Share> = shares.collect();
+        if shares.len() < self.threshold || !ShamirSecretSharing::new().verify_shares(&shares, self.threshold) {
+            return Err(MpcError::AuthenticationError("Opened shares are inconsistent".to_string()));
+        }
+        ShamirSecretSharing::reconstruct(&shares[..self.threshold], self.threshold)
+    }
+}
+
+/// 检查值承诺的上下文：绑定公开种子和该对在批次中的位置
+fn check_context(seed: &[u8; 32], index: usize) -> Vec<u8> {
+    let mut context = CHECK_COMMITMENT_DOMAIN.to_vec();
+    context.extend_from_slice(seed);
+    context.extend_from_slice(&(index as u64).to_le_bytes());
+    context
+}
@@ -12,6 +12,7 @@ use std::collections::HashMap;
 
 use super::protocol_messages::PartyId;
 use super::bfv_based::BFVParams;
+use crate::utils::hash_struct_with_domain;
 
 /// 门限密钥生成贡献
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -151,6 +152,8 @@ impl ThresholdBFVKeyGen {
             n: self.params.degree,
             q: self.params.coeff_modulus,
             t: self.params.plain_modulus,
+            rns_moduli: Vec::new(),
+            relin_key: Vec::new(),
         };
         
         // 生成本方的私钥分享
@@ -184,6 +187,7 @@ impl ThresholdBFVKeyGen {
             n: self.params.degree,
             q: self.params.coeff_modulus,
             t: self.params.plain_modulus,
+            rns_moduli: Vec::new(),
         })
     }
     
@@ -286,24 +290,16 @@ impl ThresholdBFVKeyGen {
     
     /// 生成零知识证明（简化版）
     fn generate_zkp_for_contribution(&self, public_polynomial: &[u64]) -> Result<Vec<u8>> {
-        // 简化的零知识证明：使用承诺的哈希
-        use sha2::{Sha256, Digest};
-        let mut hasher = Sha256::new();
-        
-        // 添加参与方ID
-        hasher.update((self.party_id as u64).to_le_bytes());
-        
-        // 添加公开多项式
-        for &coeff in public_polynomial {
-            hasher.update(coeff.to_le_bytes());
-        }
-        
-        // 添加私钥多项式的承诺
-        for &secret_coeff in &self.secret_polynomial {
-            hasher.update(field_mul(secret_coeff, 7).to_le_bytes()); // 简化承诺
-        }
+        // 简化的零知识证明：对参与方ID、公开多项式和私钥多项式的承诺计算规范哈希
+        let secret_commitments: Vec<u64> = self.secret_polynomial.iter()
+            .map(|&secret_coeff| field_mul(secret_coeff, 7)) // 简化承诺
+            .collect();
         
-        Ok(hasher.finalize().to_vec())
+        let digest = hash_struct_with_domain(
+            b"mpc_api/threshold_keygen/proof",
+            &(self.party_id, public_polynomial, secret_commitments),
+        )?;
+        Ok(digest.to_vec())
     }
     
     /// 验证零知识证明（简化版）
@@ -0,0 +1,174 @@
+//! # 三元组文件 (Triple Files)
+//!
+//! 单个参与方的三元组分享以 56 字节的定长记录写入内存映射文件
+//! （`storage::MappedArtifact`），在线阶段按序号读取或顺序遍历，
+//! 不需要把整个预处理材料读入内存。与 `PreprocessingPool` 不同，文件只追加、
+//! 不删除，已使用的位置由调用方记录；处理完的区间可以用 `release` 交还给操作系统。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let path = std::env::temp_dir().join(format!("mpc_api_doc_triples_{}", std::process::id()));
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//! let triples = generator.generate_batch(4)?;
+//!
+//! // 只保存参与方 1 的分享，记录逐条加密
+//! let key = [9u8; 32];
+//! let mut writer = TripleFileWriter::create(&path, Some(&key))?;
+//! for triple in &triples {
+//!     writer.append(&triple.shares[&1])?;
+//! }
+//! writer.finish()?;
+//!
+//! let file = TripleFile::open(&path, Some(&key))?;
+//! assert_eq!(file.len(), 4);
+//! assert_eq!(file.get(2)?.c.y, triples[2].shares[&1].c.y);
+//! # std::fs::remove_file(&path).unwrap();
+//! # Ok(())
+//! # }
+//! ```
+
+use super::BeaverTriple;
+use crate::secret_sharing::{validate_field_element, Share};
+use crate::storage::{ArtifactKind, MappedArtifact, MappedArtifactWriter};
+use crate::{MpcError, Result};
+use std::ops::Range;
+use std::path::Path;
+
+/// 一条三元组记录的长度：a、b、c 的横纵坐标和 ID，各 8 字节
+pub const TRIPLE_RECORD_SIZE: usize = 56;
+
+/// 把三元组分享编码为定长记录
+pub fn encode_triple_record(triple: &BeaverTriple) -> [u8; TRIPLE_RECORD_SIZE] {
+    let fields = [
+        triple.a.x, triple.a.y,
+        triple.b.x, triple.b.y,
+        triple.c.x, triple.c.y,
+        triple.id,
+    ];
+    let mut record = [0u8; TRIPLE_RECORD_SIZE];
+    for (chunk, field) in record.chunks_exact_mut(8).zip(fields) {
+        chunk.copy_from_slice(&field.to_be_bytes());
+    }
+    record
+}
+
+/// 从定长记录解码三元组分享
+///
+/// 分享值不是域元素时返回 `InvalidSecretShare`。
+pub fn decode_triple_record(record: &[u8]) -> Result<BeaverTriple> {
+    if record.len() != TRIPLE_RECORD_SIZE {
+        return Err(MpcError::SerializationError(format!(
+            "Triple record has {} bytes, expected {}", record.len(), TRIPLE_RECORD_SIZE
+        )));
+    }
+    let field = |i: usize| u64::from_be_bytes(record[i * 8..(i + 1) * 8].try_into().unwrap());
+    let share = |i: usize| -> Result<Share> {
+        let y = field(i + 1);
+        if !validate_field_element(y) {
+            return Err(MpcError::InvalidSecretShare);
+        }
+        Ok(Share::new(field(i), y))
+    };
+    Ok(BeaverTriple {
+        a: share(0)?,
+        b: share(2)?,
+        c: share(4)?,
+        id: field(6),
+    })
+}
+
+/// 顺序写入三元组分享的文件
+#[derive(Debug)]
+pub struct TripleFileWriter {
+    inner: MappedArtifactWriter,
+}
+
+impl TripleFileWriter {
+    /// 创建文件
+    ///
+    /// # 参数
+    /// - `path`: 文件路径，已存在时覆盖
+    /// - `key`: 加密密钥（至少 16 字节），为空时不加密
+    pub fn create(path: impl AsRef<Path>, key: Option<&[u8]>) -> Result<Self> {
+        Ok(Self {
+            inner: MappedArtifactWriter::create(path, ArtifactKind::BeaverTriples, TRIPLE_RECORD_SIZE, key)?,
+        })
+    }
+
+    /// 追加一个三元组分享，返回其序号
+    pub fn append(&mut self, triple: &BeaverTriple) -> Result<u64> {
+        self.inner.append(&encode_triple_record(triple))
+    }
+
+    /// 已写入的三元组数量
+    pub fn len(&self) -> u64 {
+        self.inner.len()
+    }
+
+    /// 是否尚未写入三元组
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// 完成写入，返回三元组数量
+    pub fn finish(self) -> Result<u64> {
+        self.inner.finish()
+    }
+}
+
+/// 只读映射的三元组文件
+#[derive(Debug)]
+pub struct TripleFile {
+    artifact: MappedArtifact,
+}
+
+impl TripleFile {
+    /// 打开文件
+    ///
+    /// # 参数
+    /// - `path`: 由 `TripleFileWriter` 写入的文件
+    /// - `key`: 写入时使用的密钥
+    pub fn open(path: impl AsRef<Path>, key: Option<&[u8]>) -> Result<Self> {
+        let artifact = MappedArtifact::open(path, key)?;
+        if artifact.kind() != ArtifactKind::BeaverTriples {
+            return Err(MpcError::StorageError(format!(
+                "Expected a triple file, found {:?}", artifact.kind()
+            )));
+        }
+        Ok(Self { artifact })
+    }
+
+    /// 三元组数量
+    pub fn len(&self) -> u64 {
+        self.artifact.len()
+    }
+
+    /// 是否没有三元组
+    pub fn is_empty(&self) -> bool {
+        self.artifact.is_empty()
+    }
+
+    /// 读取第 `index` 个三元组分享
+    pub fn get(&self, index: u64) -> Result<BeaverTriple> {
+        decode_triple_record(&self.artifact.record(index)?)
+    }
+
+    /// 读取 `[start, end)` 区间内的三元组分享
+    pub fn range(&self, range: Range<u64>) -> Result<Vec<BeaverTriple>> {
+        range.map(|index| self.get(index)).collect()
+    }
+
+    /// 按顺序遍历全部三元组分享
+    pub fn iter(&self) -> impl Iterator<Item = Result<BeaverTriple>> + '_ {
+        self.artifact.records().map(|record| decode_triple_record(&record?))
+    }
+
+    /// 提示操作系统回收已经用过的三元组所在的页面
+    pub fn release(&self, range: Range<u64>) {
+        self.artifact.release(range)
+    }
+}
@@ -14,7 +14,11 @@
 //! 
 //! - **诚实但好奇**: 可信第三方按协议执行但可能尝试学习秘密
 //! - **半诚实安全**: 在半诚实对手模型下提供安全性
-//! - **可审计性**: 提供验证机制以检测恶意行为
+//! - **可审计性**: 提供验证机制以检测恶意行为；可信方可以用 Pedersen VSS 分发三元组并
+//!   附带 `TripleProof`，各方用自己的份额检查承诺，并在不公开 (a, b, c) 的情况下确认 c = a * b
+//! - **分布式可信方**: 委员会成员各自在自己的节点上运行 `DealerMember` 联合生成三元组时，
+//!   信任假设变为"委员会中不超过 t 个成员合谋"（见 `distributed_dealer` 模块）；
+//!   `with_dealer_committee` 在本进程内驱动全部成员，只用于测试和单机部署
 //! 
 //! ## 性能优势
 //! 
@@ -29,7 +33,9 @@
 //! - 可以接受可信设置假设的情况
 
 use super::*;
-use crate::secret_sharing::{ShamirSecretSharing, SecretSharing};
+use crate::secret_sharing::{
+    ShamirSecretSharing, SecretSharing, VssCommitments, VssDealer, VssProductProof, VssScheme, VssShare,
+};
 use crate::utils::random_field_element;
 use std::sync::{Arc, Mutex};
 
@@ -50,6 +56,8 @@ pub struct TrustedPartyBeaverGenerator {
     precomputed_pool: Arc<Mutex<Vec<CompleteBeaverTriple>>>,
     /// 池大小限制
     pool_size_limit: usize,
+    /// 分布式可信方委员会 (为 None 时使用单一可信方)
+    dealer_committee: Option<DealerCommittee>,
 }
 
 /// 可信第三方的配置参数
@@ -113,6 +121,7 @@ impl TrustedPartyBeaverGenerator {
             triple_counter: 0,
             precomputed_pool,
             pool_size_limit: config.pool_size,
+            dealer_committee: None,
         };
         
         // 如果启用预计算，初始填充池
@@ -123,6 +132,57 @@ impl TrustedPartyBeaverGenerator {
         Ok(generator)
     }
     
+    /// 创建由委员会充当可信方的生成器
+    /// 
+    /// 三元组由委员会以诚实多数协议联合生成，生成的三元组不包含原始值，
+    /// 消费方的使用方式与单一可信方模式相同。全部成员在本进程内由 `DealerCommittee` 驱动，
+    /// 本进程能看到成员之间的全部消息；要去掉单点信任，各成员应在自己的节点上运行 `DealerMember`。
+    /// 
+    /// # 参数
+    /// - `party_count`: 参与方总数
+    /// - `threshold`: 重构门限
+    /// - `party_id`: 当前方的 ID
+    /// - `config`: 可信第三方配置
+    /// - `committee`: 委员会配置
+    /// 
+    /// # 返回
+    /// 返回配置好的生成器实例；委员会不满足诚实多数时返回错误
+    pub fn with_dealer_committee(
+        party_count: usize,
+        threshold: usize,
+        party_id: usize,
+        config: Option<TrustedPartyConfig>,
+        committee: DealerCommitteeConfig,
+    ) -> Result<Self> {
+        let config = config.unwrap_or_default();
+        let mut generator = Self::new(
+            party_count,
+            threshold,
+            party_id,
+            Some(TrustedPartyConfig { enable_precomputation: false, ..config.clone() }),
+        )?;
+        generator.dealer_committee = Some(DealerCommittee::new(committee)?);
+        
+        if config.enable_precomputation {
+            generator.fill_precomputed_pool(config.pool_size)?;
+        }
+        
+        Ok(generator)
+    }
+    
+    /// 获取分布式可信方委员会
+    pub fn dealer_committee(&self) -> Option<&DealerCommittee> {
+        self.dealer_committee.as_ref()
+    }
+    
+    /// 由委员会生成三元组；未配置委员会时返回 None
+    fn committee_triple(&mut self) -> Option<Result<CompleteBeaverTriple>> {
+        let committee = self.dealer_committee.as_mut()?;
+        self.triple_counter += 1;
+        let unique_triple_id = self.triple_counter * self.party_count as u64 + self.party_id as u64;
+        Some(committee.generate_triple(self.party_count, self.threshold, unique_triple_id))
+    }
+    
     /// 由可信第三方生成原始的三元组值
     /// 
     /// 这个方法模拟可信第三方的核心功能：
@@ -198,6 +258,11 @@ impl TrustedPartyBeaverGenerator {
         
         // 生成三元组（需要可变借用来更新计数器）
         for _ in 0..count {
+            if let Some(triple) = self.committee_triple() {
+                triples_to_store.push(triple?);
+                continue;
+            }
+            
             let (a, b, c) = self.generate_raw_triple();
             
             if self.verify_triple_correctness(a, b, c) {
@@ -253,6 +318,10 @@ impl BeaverTripleGenerator for TrustedPartyBeaverGenerator {
         }
         
         // 如果池为空，直接生成
+        if let Some(triple) = self.committee_triple() {
+            return triple;
+        }
+        
         let (a, b, c) = self.generate_raw_triple();
         
         if !self.verify_triple_correctness(a, b, c) {
@@ -309,6 +378,135 @@ impl BeaverTripleGenerator for TrustedPartyBeaverGenerator {
     }
 }
 
+/// 可信方对三元组正确性的证明
+/// 
+/// 可信方用 Pedersen VSS 分享 a、b、c：公开三个分享多项式的系数承诺，
+/// 每一方收到份额的同时收到盲化多项式在自己坐标处的值（`TripleShareBlinding`），
+/// 用 `verify_share` 检查自己的份额落在承诺的多项式上。
+/// 乘法关系证明作用在三个常数项承诺上，证明 c = a * b (mod `FIELD_PRIME`)。
+/// 承诺群的阶就是 `FIELD_PRIME`，不需要范围证明；证明与三元组 ID 绑定，
+/// 不能挪用到其他三元组上，也不能搭配另一组份额。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TripleProof {
+    /// 三元组的唯一标识符
+    pub triple_id: u64,
+    /// a 的分享多项式承诺
+    pub a: VssCommitments,
+    /// b 的分享多项式承诺
+    pub b: VssCommitments,
+    /// c 的分享多项式承诺
+    pub c: VssCommitments,
+    /// 常数项满足 c = a * b 的证明
+    pub product: VssProductProof,
+}
+
+/// 一方份额对应的盲化多项式值，由可信方随份额私下发送
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TripleShareBlinding {
+    /// a 的盲化值
+    pub a: u64,
+    /// b 的盲化值
+    pub b: u64,
+    /// c 的盲化值
+    pub c: u64,
+}
+
+/// 带证明的三元组：各方的份额、公开证明和各方私有的盲化值
+pub type ProvenTriple = (CompleteBeaverTriple, TripleProof, HashMap<usize, TripleShareBlinding>);
+
+impl TripleProof {
+    /// 用 Pedersen VSS 分享三元组 (a, b, c) 并生成证明
+    /// 
+    /// # 参数
+    /// - `triple_id`: 三元组的唯一标识符，写入每一方的份额并绑定到证明中
+    /// - `threshold`: 重构门限
+    /// - `party_count`: 参与方数量，参与方 i 的 x 坐标为 i
+    /// 
+    /// # 返回值
+    /// 值不在域内、c ≠ a * b 或门限参数不合法时返回错误
+    pub fn deal(
+        triple_id: u64,
+        (a, b, c): (u64, u64, u64),
+        threshold: usize,
+        party_count: usize,
+    ) -> Result<ProvenTriple> {
+        if a >= FIELD_PRIME || b >= FIELD_PRIME || c != field_mul(a, b) {
+            return Err(MpcError::CryptographicError(
+                "Cannot prove a malformed triple".to_string()
+            ));
+        }
+        let a_dealer = VssDealer::new(VssScheme::Pedersen, a, threshold, party_count)?;
+        let b_dealer = VssDealer::new(VssScheme::Pedersen, b, threshold, party_count)?;
+        let c_dealer = VssDealer::new(VssScheme::Pedersen, c, threshold, party_count)?;
+        let product = a_dealer.prove_product(&b_dealer, &c_dealer, &triple_id.to_le_bytes())?;
+        
+        let mut shares = HashMap::new();
+        let mut blindings = HashMap::new();
+        for party in 1..=party_count {
+            let x = party as u64;
+            let (a_share, b_share, c_share) = (a_dealer.share(x), b_dealer.share(x), c_dealer.share(x));
+            blindings.insert(party, TripleShareBlinding { a: a_share.blinding, b: b_share.blinding, c: c_share.blinding });
+            shares.insert(party, BeaverTriple::new(a_share.share, b_share.share, c_share.share, triple_id));
+        }
+        
+        let proof = Self {
+            triple_id,
+            a: a_dealer.commitments()?,
+            b: b_dealer.commitments()?,
+            c: c_dealer.commitments()?,
+            product,
+        };
+        Ok((CompleteBeaverTriple::new_with_values(shares, (a, b, c)), proof, blindings))
+    }
+    
+    /// 验证公开部分：三个承诺门限一致且常数项满足乘法关系
+    pub fn verify(&self) -> bool {
+        let threshold = self.a.threshold();
+        self.b.threshold() == threshold
+            && self.c.threshold() == threshold
+            && self.a.verify_product(&self.b, &self.c, &self.product, &self.triple_id.to_le_bytes())
+    }
+    
+    /// 一方检查自己收到的份额与承诺一致
+    /// 
+    /// 份额的三元组 ID 必须与证明一致，a、b、c 三个份额在同一 x 坐标上并各自通过 VSS 验证。
+    /// 检查失败的一方应当拒绝该三元组（或按 `secret_sharing::vss` 的投诉流程处理）。
+    pub fn verify_share(&self, share: &BeaverTriple, blinding: &TripleShareBlinding) -> bool {
+        let x = share.a.x;
+        share.id == self.triple_id
+            && share.b.x == x
+            && share.c.x == x
+            && self.a.verify_share(&VssShare { share: share.a.clone(), blinding: blinding.a })
+            && self.b.verify_share(&VssShare { share: share.b.clone(), blinding: blinding.b })
+            && self.c.verify_share(&VssShare { share: share.c.clone(), blinding: blinding.c })
+    }
+}
+
+impl TrustedPartyBeaverGenerator {
+    /// 生成三元组并附带正确性证明
+    /// 
+    /// 各参与方用 `TripleProof::verify` 检查公开证明，再用 `TripleProof::verify_share`
+    /// 和自己的盲化值检查收到的份额，全部通过后再使用三元组。
+    /// 
+    /// # 返回值
+    /// 委员会模式下没有任何一方知道 (a, b, c)，无法生成证明，返回错误
+    pub fn generate_single_with_proof(&mut self) -> Result<ProvenTriple> {
+        if self.dealer_committee.is_some() {
+            return Err(MpcError::ProtocolError(
+                "Triples generated by a dealer committee cannot be proven by a single dealer".to_string()
+            ));
+        }
+        let (a, b, c) = self.generate_raw_triple();
+        let triple_id = self.triple_counter * self.party_count as u64 + self.party_id as u64;
+        TripleProof::deal(triple_id, (a, b, c), self.threshold, self.party_count)
+    }
+    
+    /// 批量生成带正确性证明的三元组
+    pub fn generate_batch_with_proofs(&mut self, count: usize) -> Result<Vec<ProvenTriple>> {
+        (0..count).map(|_| self.generate_single_with_proof()).collect()
+    }
+}
+
 /// 可信第三方批量生成器
 /// 
 /// 专门优化了批量生成场景，可以更高效地生成大量三元组。
@@ -0,0 +1,40 @@
+//! 节点进程
+//!
+//! 用法：`mpc_node <配置文件> [报告文件]`
+//!
+//! 读取 `NodeConfig`，执行完整的节点流水线，把 `NodeReport` 以 JSON 格式写入报告文件
+//! （未指定时写到标准输出）。失败时在标准错误输出错误信息并以状态码 1 退出。
+
+use mpc_api::node::{run_node, NodeConfig};
+use std::process::ExitCode;
+
+fn run(config_path: &str, report_path: Option<&str>) -> mpc_api::Result<()> {
+    let config = NodeConfig::load(config_path)?;
+    let report = run_node(&config)?;
+    let json = serde_json::to_string_pretty(&report)
+        .map_err(|e| mpc_api::MpcError::SerializationError(e.to_string()))?;
+    match report_path {
+        Some(path) => std::fs::write(path, json)
+            .map_err(|e| mpc_api::MpcError::StorageError(format!("Failed to write {}: {}", path, e))),
+        None => {
+            println!("{}", json);
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 2 || args.len() > 3 {
+        eprintln!("usage: {} <config.json> [report.json]", args[0]);
+        return ExitCode::from(2);
+    }
+
+    match run(&args[1], args.get(2).map(String::as_str)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("node failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
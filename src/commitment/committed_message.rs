@@ -0,0 +1,239 @@
+//! # 通用承诺-打开封装 (Commit-and-Open Wrapper)
+//!
+//! 许多协议都遵循"先承诺、后打开"的模式：硬币抛掷中的随机贡献、
+//! MAC 检查中的 σ 值、三元组牺牲检查中的公开差值等。本模块提供
+//! `CommittedMessage<T>`，可以对任意可序列化的值进行承诺，并在之后
+//! 打开并验证，避免每个协议重复实现承诺/揭示逻辑。
+//!
+//! ## 传输上下文绑定
+//!
+//! 承诺时需要提供一个上下文（通常是会话 ID 或协议转录的摘要），
+//! 它与值一起被哈希进承诺中。验证时必须提供相同的上下文，
+//! 因此一个会话中的打开无法被重放到另一个会话。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::commitment::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let context = b"session-42/round-1";
+//! let committed = CommittedMessage::commit(vec![1u64, 2, 3], context)?;
+//!
+//! // 先发送承诺，之后再发送打开信息
+//! let commitment = committed.commitment().clone();
+//! let opening = committed.open();
+//!
+//! let value = commitment.open_verified(opening, context)?;
+//! assert_eq!(value, vec![1, 2, 3]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{MpcError, Result};
+use crate::elliptic_curve::{Secp256k1Point, Secp256k1Scalar};
+use crate::utils::canonical_encode;
+use super::{CommitmentScheme, HashCommitment, PedersenGenerators};
+use rand::{RngCore, thread_rng};
+use serde::{Deserialize, Serialize};
+use sha2::{Sha256, Digest};
+
+/// 承诺消息的域分隔标签
+const COMMITTED_MESSAGE_DOMAIN: &[u8] = b"mpc_api/committed_message/v1";
+
+/// 哈希承诺使用的随机数长度（字节）
+const HASH_RANDOMNESS_LEN: usize = 32;
+
+/// 承诺方案类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommitmentKind {
+    /// 基于 SHA-256 的哈希承诺
+    Hash,
+    /// secp256k1 上的 Pedersen 承诺（完美隐藏，离散对数假设下绑定）
+    Pedersen,
+}
+
+/// 对消息的承诺值
+///
+/// 这是在承诺阶段发送给其他参与方的内容。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MessageCommitment {
+    /// 哈希承诺
+    Hash([u8; 32]),
+    /// Pedersen 承诺
+    Pedersen(Secp256k1Point),
+}
+
+/// 承诺的打开信息
+///
+/// 在打开阶段发送，包含原始值以及承诺时使用的随机数。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageOpening<T> {
+    /// 被承诺的值
+    pub value: T,
+    /// 承诺随机数
+    pub randomness: Vec<u8>,
+}
+
+/// 已承诺但尚未打开的消息
+///
+/// 由承诺方持有，包含承诺值和打开信息。
+#[derive(Debug, Clone)]
+pub struct CommittedMessage<T> {
+    /// 承诺值
+    commitment: MessageCommitment,
+    /// 打开信息
+    opening: MessageOpening<T>,
+}
+
+impl<T: Serialize> CommittedMessage<T> {
+    /// 使用哈希承诺对值进行承诺
+    ///
+    /// # 参数
+    ///
+    /// * `value` - 要承诺的值
+    /// * `context` - 绑定到承诺中的会话/转录上下文
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回已承诺的消息，序列化失败时返回错误
+    pub fn commit(value: T, context: &[u8]) -> Result<Self> {
+        Self::commit_with(CommitmentKind::Hash, value, context)
+    }
+
+    /// 使用指定的承诺方案对值进行承诺
+    ///
+    /// # 参数
+    ///
+    /// * `kind` - 承诺方案类型
+    /// * `value` - 要承诺的值
+    /// * `context` - 绑定到承诺中的会话/转录上下文
+    pub fn commit_with(kind: CommitmentKind, value: T, context: &[u8]) -> Result<Self> {
+        let digest = message_digest(&value, context)?;
+
+        let (commitment, randomness) = match kind {
+            CommitmentKind::Hash => {
+                let mut randomness = vec![0u8; HASH_RANDOMNESS_LEN];
+                thread_rng().fill_bytes(&mut randomness);
+                let hash = HashCommitment::commit(digest.to_vec(), randomness.clone());
+                (MessageCommitment::Hash(hash), randomness)
+            }
+            CommitmentKind::Pedersen => {
+                let r = Secp256k1Scalar::random();
+                let point = PedersenGenerators::default().commit_scalar(&digest_to_scalar(&digest), &r);
+                (MessageCommitment::Pedersen(point), r.to_bytes().to_vec())
+            }
+        };
+
+        Ok(Self {
+            commitment,
+            opening: MessageOpening { value, randomness },
+        })
+    }
+
+    /// 使用给定的随机数进行哈希承诺
+    ///
+    /// 当多个参与方需要对同一个值得到相同的承诺时（例如随机数由各方联合生成），
+    /// 使用此方法代替 `commit`。随机数必须对承诺接收方保密且不可预测。
+    ///
+    /// # 参数
+    ///
+    /// * `value` - 要承诺的值
+    /// * `randomness` - 承诺随机数
+    /// * `context` - 绑定到承诺中的会话/转录上下文
+    pub fn commit_with_randomness(value: T, randomness: Vec<u8>, context: &[u8]) -> Result<Self> {
+        let digest = message_digest(&value, context)?;
+        let hash = HashCommitment::commit(digest.to_vec(), randomness.clone());
+
+        Ok(Self {
+            commitment: MessageCommitment::Hash(hash),
+            opening: MessageOpening { value, randomness },
+        })
+    }
+
+    /// 获取承诺值（承诺阶段发送）
+    pub fn commitment(&self) -> &MessageCommitment {
+        &self.commitment
+    }
+
+    /// 获取被承诺的值
+    pub fn value(&self) -> &T {
+        &self.opening.value
+    }
+
+    /// 打开承诺，返回打开阶段需要发送的信息
+    pub fn open(self) -> MessageOpening<T> {
+        self.opening
+    }
+}
+
+impl MessageCommitment {
+    /// 获取承诺方案类型
+    pub fn kind(&self) -> CommitmentKind {
+        match self {
+            MessageCommitment::Hash(_) => CommitmentKind::Hash,
+            MessageCommitment::Pedersen(_) => CommitmentKind::Pedersen,
+        }
+    }
+
+    /// 验证打开信息是否与承诺一致
+    ///
+    /// # 参数
+    ///
+    /// * `opening` - 对方发送的打开信息
+    /// * `context` - 承诺时使用的会话/转录上下文
+    ///
+    /// # 返回值
+    ///
+    /// 如果打开有效返回 `true`，否则返回 `false`；序列化失败时返回错误
+    pub fn verify<T: Serialize>(&self, opening: &MessageOpening<T>, context: &[u8]) -> Result<bool> {
+        let digest = message_digest(&opening.value, context)?;
+
+        match self {
+            MessageCommitment::Hash(hash) => {
+                Ok(HashCommitment::verify(*hash, digest.to_vec(), opening.randomness.clone()))
+            }
+            MessageCommitment::Pedersen(point) => {
+                let randomness = <&[u8; 32]>::try_from(opening.randomness.as_slice())
+                    .ok()
+                    .and_then(Secp256k1Scalar::from_bytes);
+                Ok(randomness.is_some_and(|r| {
+                    PedersenGenerators::default().commit_scalar(&digest_to_scalar(&digest), &r) == *point
+                }))
+            }
+        }
+    }
+
+    /// 验证打开信息并取出被承诺的值
+    ///
+    /// # 返回值
+    ///
+    /// 验证通过时返回被承诺的值，否则返回认证错误
+    pub fn open_verified<T: Serialize>(&self, opening: MessageOpening<T>, context: &[u8]) -> Result<T> {
+        if self.verify(&opening, context)? {
+            Ok(opening.value)
+        } else {
+            Err(MpcError::AuthenticationError("Commitment opening does not match".to_string()))
+        }
+    }
+}
+
+/// 计算值与上下文的绑定摘要
+fn message_digest<T: Serialize>(value: &T, context: &[u8]) -> Result<[u8; 32]> {
+    let encoded = canonical_encode(value)?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(COMMITTED_MESSAGE_DOMAIN);
+    hasher.update((context.len() as u64).to_le_bytes());
+    hasher.update(context);
+    hasher.update(&encoded);
+
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(&hasher.finalize());
+    Ok(digest)
+}
+
+/// 将摘要映射为 Pedersen 承诺使用的标量
+fn digest_to_scalar(digest: &[u8; 32]) -> Secp256k1Scalar {
+    Secp256k1Scalar::from_bytes_reduced(digest)
+}
@@ -7,8 +7,15 @@ use crate::secret_sharing::FIELD_PRIME;
 use super::CommitmentScheme;
 use rand::{Rng, thread_rng};
 use serde::{Deserialize, Serialize};
+use crate::utils::hash_struct_with_domain;
 use sha2::{Sha256, Digest};
 
+/// Domain tag for vector commitments
+const VECTOR_COMMITMENT_DOMAIN: &[u8] = b"mpc_api/hash_commit/vector";
+
+/// Domain tag for secret-share commitments
+const SHARE_COMMITMENT_DOMAIN: &[u8] = b"mpc_api/hash_commit/share";
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HashCommitment {
     pub hash: [u8; 32],
@@ -85,20 +92,9 @@ impl HashCommitment {
     
     // Commit to a vector of values with a single randomness
     pub fn vector_commit_u64(values: &[u64], randomness: u64) -> [u8; 32] {
-        let mut hasher = Sha256::new();
-        
-        // Add all values to the hash
-        for value in values {
-            hasher.update(value.to_le_bytes());
-        }
-        
-        // Add randomness
-        hasher.update(randomness.to_le_bytes());
-        
-        let result = hasher.finalize();
-        let mut hash = [0u8; 32];
-        hash.copy_from_slice(&result);
-        hash
+        // Length-prefixed canonical encoding of (values, randomness)
+        hash_struct_with_domain(VECTOR_COMMITMENT_DOMAIN, &(values, randomness))
+            .expect("u64 slices always have a canonical encoding")
     }
     
     pub fn verify_vector_u64(commitment: &[u8; 32], values: &[u64], randomness: u64) -> bool {
@@ -168,15 +164,9 @@ impl HashCommitment {
     
     // Commit to a secret shared value
     pub fn commit_secret_share(share_value: u64, share_index: usize, randomness: u64) -> [u8; 32] {
-        let mut hasher = Sha256::new();
-        hasher.update(share_value.to_le_bytes());
-        hasher.update(share_index.to_le_bytes());
-        hasher.update(randomness.to_le_bytes());
-        
-        let result = hasher.finalize();
-        let mut hash = [0u8; 32];
-        hash.copy_from_slice(&result);
-        hash
+        // share_index is encoded as 64 bits regardless of the platform's usize
+        hash_struct_with_domain(SHARE_COMMITMENT_DOMAIN, &(share_value, share_index, randomness))
+            .expect("integer tuples always have a canonical encoding")
     }
     
     pub fn verify_secret_share(
@@ -0,0 +1,266 @@
+//! # KZG 多项式承诺 (KZG Polynomial Commitments)
+//!
+//! 对一个次数不超过 d 的多项式 f 给出一个群元素的承诺，之后可以在任意点 z 打开：
+//! 公开 y = f(z) 和一个群元素的证明，验证只需要两次配对。承诺和证明的大小都与次数无关，
+//! 这是 Plonk 等 SNARK 使用的多项式承诺。
+//!
+//! ## 构造
+//!
+//! 在 BLS12-381 上进行，多项式的系数属于其 255 位标量域（`KzgScalar`）：
+//!
+//! 1. **设置**: 随机选取 τ，公开 [τ^i]_1 (i ≤ d) 和 [1]_2、[τ]_2，之后丢弃 τ
+//! 2. **承诺**: C = [f(τ)]_1 = Σ a_i·[τ^i]_1
+//! 3. **打开**: q(x) = (f(x) - y)/(x - z)，证明 π = [q(τ)]_1
+//! 4. **验证**: e(C - [y]_1 + z·π, [1]_2) = e(π, [τ]_2)
+//!
+//! 多个打开（可以针对不同的承诺）用随机线性组合合并成一次双配对检查（`verify_batch`）。
+//! 验证方总是给出自己期望的求值点：打开中的 `point` 由证明方提供，
+//! 不检查它就会接受在其他点（例如另一方的份额）上的有效打开。
+//!
+//! ## 可验证秘密分享
+//!
+//! `share_secret` 按 Kate–Zaverucha–Goldberg 的 eVSS 分发秘密：分发者承诺 Shamir 多项式，
+//! 每一方收到自己的份额 f(i) 和打开证明，只用常数大小的承诺就能验证份额。
+//! 与 `secret_sharing::vss` 的 Feldman/Pedersen 承诺相比，公开的承诺不随门限增长。
+//!
+//! ## 安全说明
+//!
+//! - 设置是可信的：知道 τ 的人可以伪造任意打开，实际部署中应由多方仪式生成
+//! - 承诺是计算隐藏的：多项式系数随机时，少于门限个打开不泄露 f(0)
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::commitment::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let setup = KzgSetup::generate(4)?;
+//! let secret = KzgScalar::from(42u64);
+//! let (commitment, shares) = setup.share_secret(&secret, 3, 5)?;
+//!
+//! // 第 i 方只用自己的份额验证，求值点固定为 i
+//! assert!(shares.iter().enumerate().all(|(i, share)| {
+//!     setup.verify(&commitment, &KzgScalar::from(i as u64 + 1), share)
+//! }));
+//! assert_eq!(KzgOpening::interpolate(&shares[2..], &KzgScalar::from(0u64))?, secret);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{MpcError, Result};
+use ark_bls12_381::{Bls12_381, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, Group, VariableBaseMSM};
+use ark_ff::{Field as _, One, UniformRand, Zero};
+use serde::{Deserialize, Serialize};
+
+/// 多项式系数和求值点所在的域：BLS12-381 的标量域
+pub type KzgScalar = ark_bls12_381::Fr;
+
+/// 公开参数 [τ^i]_1 (i ≤ d)、[1]_2 和 [τ]_2
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KzgSetup {
+    #[serde(with = "ark_bytes")]
+    powers_of_g: Vec<G1Affine>,
+    #[serde(with = "ark_bytes")]
+    h: G2Affine,
+    #[serde(with = "ark_bytes")]
+    tau_h: G2Affine,
+}
+
+/// 对多项式的承诺 [f(τ)]_1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KzgCommitment(#[serde(with = "ark_bytes")] pub G1Affine);
+
+/// 多项式在一点的打开
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KzgOpening {
+    /// 求值点 z
+    #[serde(with = "ark_bytes")]
+    pub point: KzgScalar,
+    /// y = f(z)
+    #[serde(with = "ark_bytes")]
+    pub value: KzgScalar,
+    /// π = [(f(τ) - y)/(τ - z)]_1
+    #[serde(with = "ark_bytes")]
+    pub proof: G1Affine,
+}
+
+impl KzgSetup {
+    /// 支持的最大次数，限制设置的内存占用
+    pub const MAX_DEGREE: usize = 1 << 20;
+
+    /// 生成支持次数不超过 `max_degree` 的公开参数，τ 在返回前被丢弃
+    pub fn generate(max_degree: usize) -> Result<Self> {
+        if max_degree > Self::MAX_DEGREE {
+            return Err(MpcError::ProtocolError(format!(
+                "KZG setup supports degrees up to {}", Self::MAX_DEGREE
+            )));
+        }
+        let tau = KzgScalar::rand(&mut rand::thread_rng());
+
+        let mut powers = Vec::with_capacity(max_degree + 1);
+        let mut current = G1Projective::generator();
+        for _ in 0..=max_degree {
+            powers.push(current);
+            current *= tau;
+        }
+        let h = G2Projective::generator();
+        Ok(Self {
+            powers_of_g: G1Projective::normalize_batch(&powers),
+            h: h.into_affine(),
+            tau_h: (h * tau).into_affine(),
+        })
+    }
+
+    /// 可以承诺的最大次数
+    pub fn max_degree(&self) -> usize {
+        self.powers_of_g.len().saturating_sub(1)
+    }
+
+    /// 承诺多项式，系数从低次到高次排列
+    pub fn commit(&self, coefficients: &[KzgScalar]) -> Result<KzgCommitment> {
+        self.check_degree(coefficients)?;
+        Ok(KzgCommitment(self.commit_unchecked(coefficients)))
+    }
+
+    /// 在 `point` 打开多项式
+    pub fn open(&self, coefficients: &[KzgScalar], point: &KzgScalar) -> Result<KzgOpening> {
+        self.check_degree(coefficients)?;
+        let (quotient, value) = divide_by_linear(coefficients, point);
+        Ok(KzgOpening { point: *point, value, proof: self.commit_unchecked(&quotient) })
+    }
+
+    /// 验证承诺的多项式在 `point` 的打开
+    ///
+    /// 打开中的求值点与 `point` 不同时返回 `false`。
+    pub fn verify(&self, commitment: &KzgCommitment, point: &KzgScalar, opening: &KzgOpening) -> bool {
+        self.verify_batch(&[(*commitment, *point, *opening)])
+    }
+
+    /// 用一次双配对检查验证多个打开，每项为 (承诺, 期望的求值点, 打开)
+    ///
+    /// 各打开乘以随机系数 ρ_i 后相加：
+    /// e(Σ ρ_i·(C_i - [y_i]_1 + z_i·π_i), [1]_2) = e(Σ ρ_i·π_i, [τ]_2)
+    ///
+    /// 空批次或任一打开不在期望的求值点时返回 `false`。
+    pub fn verify_batch(&self, openings: &[(KzgCommitment, KzgScalar, KzgOpening)]) -> bool {
+        if openings.is_empty() || openings.iter().any(|(_, point, opening)| opening.point != *point) {
+            return false;
+        }
+        let mut rng = rand::thread_rng();
+        let g = G1Projective::generator();
+        let (mut lhs, mut rhs) = (G1Projective::zero(), G1Projective::zero());
+        for (i, (commitment, _, opening)) in openings.iter().enumerate() {
+            let rho = if i == 0 { KzgScalar::one() } else { KzgScalar::rand(&mut rng) };
+            let proof = G1Projective::from(opening.proof);
+            lhs += (G1Projective::from(commitment.0) - g * opening.value + proof * opening.point) * rho;
+            rhs += proof * rho;
+        }
+        Bls12_381::multi_pairing([lhs.into_affine(), (-rhs).into_affine()], [self.h, self.tau_h]).is_zero()
+    }
+
+    /// 以 eVSS 方式分享秘密
+    ///
+    /// # 参数
+    /// - `secret`: 秘密 f(0)
+    /// - `threshold`: 重构门限，多项式次数为 `threshold - 1`
+    /// - `party_count`: 参与方数量，第 i 方（从 1 开始）的份额是 f(i) 的打开
+    ///
+    /// # 返回值
+    /// 多项式的承诺和每一方的份额
+    pub fn share_secret(
+        &self,
+        secret: &KzgScalar,
+        threshold: usize,
+        party_count: usize,
+    ) -> Result<(KzgCommitment, Vec<KzgOpening>)> {
+        if threshold == 0 || threshold > party_count {
+            return Err(MpcError::InvalidThreshold);
+        }
+        let mut rng = rand::thread_rng();
+        let coefficients: Vec<KzgScalar> = std::iter::once(*secret)
+            .chain((1..threshold).map(|_| KzgScalar::rand(&mut rng)))
+            .collect();
+
+        let commitment = self.commit(&coefficients)?;
+        let shares = (1..=party_count as u64)
+            .map(|x| self.open(&coefficients, &KzgScalar::from(x)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok((commitment, shares))
+    }
+
+    fn check_degree(&self, coefficients: &[KzgScalar]) -> Result<()> {
+        if coefficients.len() > self.powers_of_g.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Polynomial of degree {} exceeds the setup's maximum degree {}",
+                coefficients.len() - 1,
+                self.max_degree()
+            )));
+        }
+        Ok(())
+    }
+
+    fn commit_unchecked(&self, coefficients: &[KzgScalar]) -> G1Affine {
+        G1Projective::msm_unchecked(&self.powers_of_g[..coefficients.len()], coefficients).into_affine()
+    }
+}
+
+impl KzgOpening {
+    /// 用拉格朗日插值从打开计算多项式在 `point` 的值，例如用门限个份额重构秘密 f(0)
+    ///
+    /// # 返回值
+    /// 没有打开或求值点重复时返回错误
+    pub fn interpolate(openings: &[KzgOpening], point: &KzgScalar) -> Result<KzgScalar> {
+        if openings.is_empty() {
+            return Err(MpcError::InsufficientShares);
+        }
+        let mut result = KzgScalar::zero();
+        for (i, opening) in openings.iter().enumerate() {
+            let (mut numerator, mut denominator) = (KzgScalar::one(), KzgScalar::one());
+            for (j, other) in openings.iter().enumerate() {
+                if i != j {
+                    numerator *= *point - other.point;
+                    denominator *= opening.point - other.point;
+                }
+            }
+            let inverse = denominator.inverse().ok_or_else(|| {
+                MpcError::ProtocolError("Openings must be at distinct points".to_string())
+            })?;
+            result += opening.value * numerator * inverse;
+        }
+        Ok(result)
+    }
+}
+
+/// f(x) = q(x)·(x - z) + f(z)，返回 (q, f(z))
+fn divide_by_linear(coefficients: &[KzgScalar], point: &KzgScalar) -> (Vec<KzgScalar>, KzgScalar) {
+    let mut quotient = vec![KzgScalar::zero(); coefficients.len().saturating_sub(1)];
+    let mut carry = KzgScalar::zero();
+    for (i, coefficient) in coefficients.iter().enumerate().rev() {
+        carry = *coefficient + carry * point;
+        if i > 0 {
+            quotient[i - 1] = carry;
+        }
+    }
+    (quotient, carry)
+}
+
+/// 以压缩编码的字节序列化 arkworks 类型，反序列化时检查点在正确的子群中
+mod ark_bytes {
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use serde::de::Error as _;
+    use serde::ser::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<T: CanonicalSerialize, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut bytes = Vec::with_capacity(value.compressed_size());
+        value.serialize_compressed(&mut bytes).map_err(S::Error::custom)?;
+        bytes.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: CanonicalDeserialize, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        T::deserialize_compressed(bytes.as_slice()).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,358 @@
+//! # 只追加的 Merkle 累加器 (Append-only Merkle Accumulator)
+//!
+//! `MerkleTree::new` 需要一次给出全部叶子；审计日志这类数据是逐条产生的，
+//! 并且需要向外部证明两件事：某条记录在某个已发布的根之下，以及新的根只是在旧的根之后追加了记录
+//! （没有修改或删除历史）。本模块按 RFC 6962（证书透明度）的树结构实现：
+//!
+//! - **MerkleFrontier**: 只保存 O(log n) 个完整子树的根，支持追加和计算当前根，
+//!   适合只跟踪根的监督方
+//! - **MerkleAccumulator**: 保存所有完整子树的哈希，追加的均摊代价为 O(1) 次哈希，
+//!   可以针对任意历史大小生成包含证明和一致性证明
+//! - **InclusionProof**: 第 i 个叶子在大小为 n 的树中
+//! - **ConsistencyProof**: 大小为 m 的树是大小为 n 的树的前缀
+//!
+//! 叶子哈希为 SHA-256(0x00 || data)，内部节点为 SHA-256(0x01 || left || right)，
+//! 空树的根为 SHA-256("")，与 RFC 6962 的根和证明格式兼容。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::commitment::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let mut log = MerkleAccumulator::new();
+//! log.append(b"event-0");
+//! log.append(b"event-1");
+//! let old_root = log.root();
+//!
+//! log.append(b"event-2");
+//! let new_root = log.root();
+//!
+//! // 历史叶子在新根下的包含证明，以及新旧两个根之间的一致性证明
+//! let inclusion = log.prove_inclusion(1, log.len())?;
+//! assert!(inclusion.verify(&new_root, b"event-1"));
+//! let consistency = log.prove_consistency(2, 3)?;
+//! assert!(consistency.verify(&old_root, &new_root));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// 只保存完整子树根的累加器前沿
+///
+/// 第 k 个元素是从高到低第 k 个置位的二进制位对应的完整子树的根。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleFrontier {
+    size: usize,
+    peaks: Vec<[u8; 32]>,
+}
+
+impl MerkleFrontier {
+    /// 创建空的前沿
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加一个叶子
+    pub fn append(&mut self, data: &[u8]) {
+        let mut hash = leaf_hash(data);
+        let mut size = self.size;
+        // 与二进制加一相同：每个进位把两个相同大小的子树合并
+        while size & 1 == 1 {
+            let Some(left) = self.peaks.pop() else { break };
+            hash = node_hash(&left, &hash);
+            size >>= 1;
+        }
+        self.peaks.push(hash);
+        self.size += 1;
+    }
+
+    /// 叶子数量
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// 当前的根
+    pub fn root(&self) -> [u8; 32] {
+        fold_peaks(&self.peaks)
+    }
+}
+
+/// 可以生成历史证明的只追加 Merkle 累加器
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleAccumulator {
+    /// levels[k][i] 是叶子 [i·2^k, (i+1)·2^k) 组成的完整子树的根
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleAccumulator {
+    /// 创建空的累加器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加一个叶子，返回它的下标
+    pub fn append(&mut self, data: &[u8]) -> usize {
+        let index = self.len();
+        let mut hash = leaf_hash(data);
+        let mut level = 0;
+        loop {
+            if level == self.levels.len() {
+                self.levels.push(Vec::new());
+            }
+            self.levels[level].push(hash);
+            let nodes = &self.levels[level];
+            if !nodes.len().is_multiple_of(2) {
+                break;
+            }
+            hash = node_hash(&nodes[nodes.len() - 2], &nodes[nodes.len() - 1]);
+            level += 1;
+        }
+        index
+    }
+
+    /// 叶子数量
+    pub fn len(&self) -> usize {
+        self.levels.first().map_or(0, Vec::len)
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 当前的根
+    pub fn root(&self) -> [u8; 32] {
+        self.subtree_root(0, self.len())
+    }
+
+    /// 树只包含前 `tree_size` 个叶子时的根
+    pub fn root_at(&self, tree_size: usize) -> Result<[u8; 32]> {
+        self.check_size(tree_size)?;
+        Ok(self.subtree_root(0, tree_size))
+    }
+
+    /// 当前树的前沿
+    pub fn frontier(&self) -> MerkleFrontier {
+        let size = self.len();
+        MerkleFrontier { size, peaks: self.peaks(0, size) }
+    }
+
+    /// 证明第 `leaf_index` 个叶子在前 `tree_size` 个叶子组成的树中
+    pub fn prove_inclusion(&self, leaf_index: usize, tree_size: usize) -> Result<InclusionProof> {
+        self.check_size(tree_size)?;
+        if leaf_index >= tree_size {
+            return Err(MpcError::ProtocolError(format!(
+                "Leaf {} is not in a tree of size {}", leaf_index, tree_size
+            )));
+        }
+        let mut path = Vec::new();
+        self.inclusion_path(leaf_index, 0, tree_size, &mut path);
+        Ok(InclusionProof { leaf_index, tree_size, path })
+    }
+
+    /// 证明前 `old_size` 个叶子组成的树是前 `new_size` 个叶子组成的树的前缀
+    pub fn prove_consistency(&self, old_size: usize, new_size: usize) -> Result<ConsistencyProof> {
+        self.check_size(new_size)?;
+        if old_size > new_size {
+            return Err(MpcError::ProtocolError(format!(
+                "Old tree size {} exceeds new tree size {}", old_size, new_size
+            )));
+        }
+        let mut path = Vec::new();
+        if old_size > 0 {
+            self.consistency_path(old_size, 0, new_size, true, &mut path);
+        }
+        Ok(ConsistencyProof { old_size, new_size, path })
+    }
+
+    fn check_size(&self, tree_size: usize) -> Result<()> {
+        if tree_size > self.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Tree size {} exceeds the {} appended leaves", tree_size, self.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// 叶子 [start, start + size) 分解成的完整子树的根，start 必须按 size 的最高位对齐
+    fn peaks(&self, start: usize, size: usize) -> Vec<[u8; 32]> {
+        let mut peaks = Vec::new();
+        let mut offset = start;
+        for level in (0..usize::BITS as usize).rev() {
+            if (size >> level) & 1 == 1 {
+                peaks.push(self.levels[level][offset >> level]);
+                offset += 1 << level;
+            }
+        }
+        peaks
+    }
+
+    fn subtree_root(&self, start: usize, size: usize) -> [u8; 32] {
+        fold_peaks(&self.peaks(start, size))
+    }
+
+    /// RFC 6962 第 2.1.1 节的 PATH(m, D[start..start + size])
+    fn inclusion_path(&self, index: usize, start: usize, size: usize, path: &mut Vec<[u8; 32]>) {
+        if size <= 1 {
+            return;
+        }
+        let split = split_point(size);
+        if index < split {
+            self.inclusion_path(index, start, split, path);
+            path.push(self.subtree_root(start + split, size - split));
+        } else {
+            self.inclusion_path(index - split, start + split, size - split, path);
+            path.push(self.subtree_root(start, split));
+        }
+    }
+
+    /// RFC 6962 第 2.1.2 节的 SUBPROOF(m, D[start..start + size], complete)
+    fn consistency_path(&self, old_size: usize, start: usize, size: usize, complete: bool, path: &mut Vec<[u8; 32]>) {
+        if old_size == size {
+            if !complete {
+                path.push(self.subtree_root(start, size));
+            }
+            return;
+        }
+        let split = split_point(size);
+        if old_size <= split {
+            self.consistency_path(old_size, start, split, complete, path);
+            path.push(self.subtree_root(start + split, size - split));
+        } else {
+            self.consistency_path(old_size - split, start + split, size - split, false, path);
+            path.push(self.subtree_root(start, split));
+        }
+    }
+}
+
+/// 叶子包含证明
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    /// 叶子下标
+    pub leaf_index: usize,
+    /// 证明针对的树大小
+    pub tree_size: usize,
+    /// 从叶子到根的兄弟子树的根
+    pub path: Vec<[u8; 32]>,
+}
+
+impl InclusionProof {
+    /// 验证 `data` 是大小为 `tree_size`、根为 `root` 的树的第 `leaf_index` 个叶子
+    pub fn verify(&self, root: &[u8; 32], data: &[u8]) -> bool {
+        if self.leaf_index >= self.tree_size {
+            return false;
+        }
+        // RFC 9162 第 2.1.3.2 节
+        let (mut index, mut last) = (self.leaf_index, self.tree_size - 1);
+        let mut hash = leaf_hash(data);
+        for sibling in &self.path {
+            if last == 0 {
+                return false;
+            }
+            if index & 1 == 1 || index == last {
+                hash = node_hash(sibling, &hash);
+                while index & 1 == 0 && index != 0 {
+                    index >>= 1;
+                    last >>= 1;
+                }
+            } else {
+                hash = node_hash(&hash, sibling);
+            }
+            index >>= 1;
+            last >>= 1;
+        }
+        last == 0 && hash == *root
+    }
+}
+
+/// 两个树大小之间的一致性证明
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsistencyProof {
+    /// 旧树的大小
+    pub old_size: usize,
+    /// 新树的大小
+    pub new_size: usize,
+    /// 重建两个根所需的子树的根
+    pub path: Vec<[u8; 32]>,
+}
+
+impl ConsistencyProof {
+    /// 验证根为 `old_root` 的旧树是根为 `new_root` 的新树的前缀
+    pub fn verify(&self, old_root: &[u8; 32], new_root: &[u8; 32]) -> bool {
+        if self.old_size > self.new_size {
+            return false;
+        }
+        if self.old_size == self.new_size {
+            return self.path.is_empty() && old_root == new_root;
+        }
+        if self.old_size == 0 {
+            // 空树是任何树的前缀
+            return self.path.is_empty();
+        }
+
+        // RFC 9162 第 2.1.4.2 节
+        let mut path = self.path.iter();
+        let first = if self.old_size.is_power_of_two() {
+            *old_root
+        } else {
+            match path.next() {
+                Some(hash) => *hash,
+                None => return false,
+            }
+        };
+        let (mut old, mut new) = (self.old_size - 1, self.new_size - 1);
+        while old & 1 == 1 {
+            old >>= 1;
+            new >>= 1;
+        }
+        let (mut old_hash, mut new_hash) = (first, first);
+        for hash in path {
+            if new == 0 {
+                return false;
+            }
+            if old & 1 == 1 || old == new {
+                old_hash = node_hash(hash, &old_hash);
+                new_hash = node_hash(hash, &new_hash);
+                while old & 1 == 0 && old != 0 {
+                    old >>= 1;
+                    new >>= 1;
+                }
+            } else {
+                new_hash = node_hash(&new_hash, hash);
+            }
+            old >>= 1;
+            new >>= 1;
+        }
+        new == 0 && old_hash == *old_root && new_hash == *new_root
+    }
+}
+
+/// 小于 size 的最大的 2 的幂，size 至少为 2
+fn split_point(size: usize) -> usize {
+    1 << (usize::BITS - 1 - (size - 1).leading_zeros())
+}
+
+/// 从右向左合并完整子树的根
+fn fold_peaks(peaks: &[[u8; 32]]) -> [u8; 32] {
+    match peaks.split_last() {
+        Some((last, rest)) => rest.iter().rev().fold(*last, |right, left| node_hash(left, &right)),
+        None => Sha256::digest([]).into(),
+    }
+}
+
+fn leaf_hash(data: &[u8]) -> [u8; 32] {
+    Sha256::new().chain_update([0x00]).chain_update(data).finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    Sha256::new().chain_update([0x01]).chain_update(left).chain_update(right).finalize().into()
+}
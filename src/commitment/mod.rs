@@ -18,6 +18,10 @@
 //! - **Pedersen 承诺**: 基于离散对数的完美隐藏承诺
 //! - **哈希承诺**: 基于哈希函数的简单承诺方案
 //! - **Merkle 树**: 支持批量承诺的树状结构
+//! - **Merkle 累加器**: 逐条追加的 Merkle 树，支持历史包含证明和根之间的一致性证明
+//! - **向量承诺**: 对整个 u64 向量承诺，之后按位置选择性地批量打开
+//! - **KZG 多项式承诺**: 常数大小的多项式承诺和求值证明，支持 eVSS（需要 `zk` 特性）
+//! - **承诺消息封装**: 对任意可序列化值进行承诺并在之后打开验证
 //! 
 //! ## 应用场景
 //! 
@@ -45,10 +49,20 @@
 pub mod pedersen;
 pub mod hash_commit;
 pub mod merkle_tree;
+pub mod merkle_accumulator;
+pub mod committed_message;
+pub mod vector_commitment;
+#[cfg(feature = "zk")]
+pub mod kzg;
 
 pub use pedersen::*;
 pub use hash_commit::*;
 pub use merkle_tree::*;
+pub use merkle_accumulator::*;
+pub use committed_message::*;
+pub use vector_commitment::*;
+#[cfg(feature = "zk")]
+pub use kzg::*;
 
 // use crate::Result; // Unused import
 // use serde::{Deserialize, Serialize}; // Unused imports
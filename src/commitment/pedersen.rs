@@ -1,15 +1,18 @@
 //! Pedersen Commitment Scheme
 //! 
-//! Implements the Pedersen commitment scheme using elliptic curve points
+//! Implements the Pedersen commitment scheme using elliptic curve points.
+//! `PedersenParams` works over the small test curve; `PedersenGenerators`
+//! commits over secp256k1 and is the one to use where hiding and binding matter.
 
 use crate::{MpcError, Result};
-use crate::elliptic_curve::{ECPoint, SimpleEC, EllipticCurve};
+use crate::elliptic_curve::{ECPoint, SimpleEC, EllipticCurve, Secp256k1Point, Secp256k1Scalar};
 use crate::secret_sharing::{FIELD_PRIME, field_add};
 use super::CommitmentScheme;
 use rand::{Rng, thread_rng};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PedersenParams {
     pub g: ECPoint,  // Generator point
     pub h: ECPoint,  // Another generator point
@@ -20,16 +23,39 @@ pub struct PedersenCommitment {
     pub commitment: ECPoint,
 }
 
+/// Domain tag for hashing labels to the second generator
+const PEDERSEN_GENERATOR_DOMAIN: &[u8] = b"mpc_api/pedersen/generator";
+
+/// Public label of the default parameters
+const DEFAULT_PEDERSEN_LABEL: &[u8] = b"mpc_api/pedersen/default";
+
 impl PedersenParams {
+    /// 默认参数：h 由公开标签哈希到曲线得到，任何人（包括生成参数的一方）都不知道 log_g(h)
+    /// 
+    /// 多方需要绑定到本次会话的参数时，使用 `protocols::GeneratorSetupParty` 联合派生。
     pub fn new() -> Result<Self> {
-        let ec_params = SimpleEC::params();
-        let g = ec_params.g.clone();
-        
-        // Generate a second generator h
-        let mut rng = thread_rng();
-        let random_scalar = rng.gen_range(1..ec_params.n);
-        let h = SimpleEC::scalar_multiply(random_scalar, &g)?;
-        
+        Self::derive(DEFAULT_PEDERSEN_LABEL)
+    }
+    
+    /// 由公开标签派生参数（nothing-up-my-sleeve）
+    /// 
+    /// 相同的标签总是得到相同的参数，任何人都可以重新计算来验证。
+    pub fn derive(label: &[u8]) -> Result<Self> {
+        let g = SimpleEC::params().g;
+        let (h, _) = SimpleEC::hash_to_point(PEDERSEN_GENERATOR_DOMAIN, label)?;
+        Ok(PedersenParams { g, h })
+    }
+    
+    /// 由给定的生成元构造参数，检查两点都在曲线上、不是无穷远点且互不相关
+    pub fn from_generators(g: ECPoint, h: ECPoint) -> Result<Self> {
+        for point in [&g, &h] {
+            if point.is_infinity() || !SimpleEC::is_on_curve(point) {
+                return Err(MpcError::CryptographicError("Pedersen generator is not a valid curve point".to_string()));
+            }
+        }
+        if g.x == h.x {
+            return Err(MpcError::CryptographicError("Pedersen generators must be independent".to_string()));
+        }
         Ok(PedersenParams { g, h })
     }
     
@@ -147,4 +173,44 @@ impl PedersenCommitment {
 impl super::BindingCommitment for PedersenCommitment {}
 impl super::HidingCommitment for PedersenCommitment {}
 
+/// secp256k1 上的 Pedersen 承诺生成元
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PedersenGenerators {
+    /// 值的生成元 G
+    pub g: Secp256k1Point,
+    /// 随机数的生成元 H
+    pub h: Secp256k1Point,
+}
+
+impl Default for PedersenGenerators {
+    /// G 为标准生成元，H 由 `mpc_api/pedersen/h` 哈希得到
+    fn default() -> Self {
+        Self { g: Secp256k1Point::generator(), h: Self::hash_to_point(b"mpc_api/pedersen/h") }
+    }
+}
+
+impl PedersenGenerators {
+    /// 计算承诺 v·G + r·H
+    pub fn commit(&self, value: u64, randomness: &Secp256k1Scalar) -> Secp256k1Point {
+        self.commit_scalar(&Secp256k1Scalar::from_u64(value), randomness)
+    }
+
+    /// 对任意标量计算承诺 v·G + r·H
+    pub fn commit_scalar(&self, value: &Secp256k1Scalar, randomness: &Secp256k1Scalar) -> Secp256k1Point {
+        self.g * *value + self.h * *randomness
+    }
+
+    /// 把标签哈希成离散对数未知的曲线点：依次尝试 SHA-256(标签 ‖ 计数器) 作为 x 坐标
+    pub fn hash_to_point(label: &[u8]) -> Secp256k1Point {
+        (0u32..)
+            .find_map(|counter| {
+                let digest = Sha256::new().chain_update(label).chain_update(counter.to_be_bytes()).finalize();
+                let mut encoding = vec![0x02];
+                encoding.extend_from_slice(&digest);
+                Secp256k1Point::from_sec1(&encoding).ok()
+            })
+            .expect("about half of all x coordinates are on the curve")
+    }
+}
+
 // Tests moved to tests/commitment_tests.rs
\ No newline at end of file
@@ -0,0 +1,289 @@
+//! # 向量承诺 (Vector Commitments)
+//!
+//! 对整个向量（例如一方持有的全部分享）给出一个 32 字节的承诺，之后只打开其中若干位置，
+//! 未打开的位置保持隐藏。cut-and-choose、抽查式审计、逐步公开输出等协议都需要这种
+//! "整体承诺、选择性打开"的能力。
+//!
+//! ## 构造
+//!
+//! 基于 SHA-256 的 Merkle 树，与 `MerkleTree` 相比做了以下加强：
+//!
+//! - **位置绑定**: 叶子哈希包含位置下标，一个值只能在承诺时所在的位置被打开
+//! - **隐藏性**: 每个叶子带 32 字节随机盐，未打开的 u64 值无法通过穷举哈希得到
+//! - **长度绑定**: 根同时承诺向量长度，树补齐到 2 的幂，不存在奇数节点复制带来的歧义
+//! - **域分隔**: 叶子、内部节点和根使用不同的前缀，叶子无法被解释成内部节点
+//! - **批量打开**: 多个位置共享认证路径，相邻位置的打开比逐个打开小得多
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::commitment::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let shares = vec![11u64, 22, 33, 44, 55];
+//! let committer = VectorCommitter::commit_vector(&shares)?;
+//! let commitment = committer.commitment();
+//!
+//! // 只打开第 1 和第 3 个位置
+//! let opening = committer.open_at(&[3, 1])?;
+//! assert!(commitment.verify_opening(&opening));
+//! assert_eq!(opening.value_at(1), Some(22));
+//! assert_eq!(opening.value_at(0), None);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{MpcError, Result};
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// 向量承诺的域分隔标签
+const VECTOR_COMMITMENT_DOMAIN: &[u8] = b"mpc_api/vector_commitment/v1";
+
+/// 每个叶子的随机盐长度（字节）
+const SALT_LEN: usize = 32;
+
+/// 补齐位置的叶子哈希
+const EMPTY_LEAF: [u8; 32] = [0u8; 32];
+
+/// 对向量的承诺
+///
+/// 这是在承诺阶段发送给其他参与方的内容。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VectorCommitment {
+    /// 承诺根，同时绑定长度和所有位置的值
+    pub root: [u8; 32],
+    /// 向量长度
+    pub length: usize,
+}
+
+/// 一个被打开的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenedPosition {
+    /// 位置下标
+    pub index: usize,
+    /// 该位置的值
+    pub value: u64,
+    /// 该位置的随机盐
+    pub salt: [u8; SALT_LEN],
+}
+
+/// 若干位置的批量打开
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VectorOpening {
+    /// 被打开的位置，按下标严格递增
+    pub positions: Vec<OpenedPosition>,
+    /// 重建根所需的兄弟节点，按层从下到上、每层从左到右排列
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl VectorOpening {
+    /// 获取某个位置被打开的值，未打开时返回 None
+    pub fn value_at(&self, index: usize) -> Option<u64> {
+        self.positions
+            .binary_search_by_key(&index, |position| position.index)
+            .ok()
+            .map(|i| self.positions[i].value)
+    }
+
+    /// 被打开的 (下标, 值)
+    pub fn values(&self) -> Vec<(usize, u64)> {
+        self.positions.iter().map(|position| (position.index, position.value)).collect()
+    }
+}
+
+/// 承诺方持有的向量、随机盐和整棵树
+#[derive(Debug, Clone)]
+pub struct VectorCommitter {
+    values: Vec<u64>,
+    salts: Vec<[u8; SALT_LEN]>,
+    /// 第 0 层为补齐后的叶子，最后一层为树根
+    levels: Vec<Vec<[u8; 32]>>,
+    commitment: VectorCommitment,
+}
+
+impl VectorCommitter {
+    /// 对向量进行承诺
+    ///
+    /// # 参数
+    ///
+    /// * `values` - 要承诺的向量，不能为空
+    pub fn commit_vector(values: &[u64]) -> Result<Self> {
+        if values.is_empty() {
+            return Err(MpcError::ProtocolError("Cannot commit to an empty vector".to_string()));
+        }
+
+        let mut rng = thread_rng();
+        let salts: Vec<[u8; SALT_LEN]> = values
+            .iter()
+            .map(|_| {
+                let mut salt = [0u8; SALT_LEN];
+                rng.fill_bytes(&mut salt);
+                salt
+            })
+            .collect();
+
+        let mut leaves: Vec<[u8; 32]> = values
+            .iter()
+            .zip(&salts)
+            .enumerate()
+            .map(|(index, (value, salt))| leaf_hash(index, *value, salt))
+            .collect();
+        leaves.resize(values.len().next_power_of_two(), EMPTY_LEAF);
+
+        let mut levels = vec![leaves];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| node_hash(&pair[0], &pair[1]))
+                .collect();
+            levels.push(next);
+        }
+
+        let commitment = VectorCommitment {
+            root: root_hash(values.len(), &levels[levels.len() - 1][0]),
+            length: values.len(),
+        };
+        Ok(Self { values: values.to_vec(), salts, levels, commitment })
+    }
+
+    /// 获取承诺
+    pub fn commitment(&self) -> VectorCommitment {
+        self.commitment
+    }
+
+    /// 被承诺的向量
+    pub fn values(&self) -> &[u64] {
+        &self.values
+    }
+
+    /// 打开若干位置
+    ///
+    /// 下标可以无序或重复，打开中的位置按下标排序并去重。
+    ///
+    /// # 返回值
+    ///
+    /// 下标为空或越界时返回错误
+    pub fn open_at(&self, indices: &[usize]) -> Result<VectorOpening> {
+        if indices.is_empty() {
+            return Err(MpcError::ProtocolError("No positions to open".to_string()));
+        }
+        if let Some(&index) = indices.iter().find(|&&index| index >= self.values.len()) {
+            return Err(MpcError::ProtocolError(format!(
+                "Position {} is out of bounds for a vector of length {}", index, self.values.len()
+            )));
+        }
+
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        let positions = indices
+            .iter()
+            .map(|&index| OpenedPosition { index, value: self.values[index], salt: self.salts[index] })
+            .collect();
+
+        // 与 `reconstruct_root` 相同的顺序收集不在已知集合中的兄弟节点
+        let mut siblings = Vec::new();
+        let mut known = indices;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let mut i = 0;
+            while i < known.len() {
+                let index = known[i];
+                if index.is_multiple_of(2) && known.get(i + 1) == Some(&(index + 1)) {
+                    i += 1;
+                } else {
+                    siblings.push(level[index ^ 1]);
+                }
+                i += 1;
+            }
+            known = known.iter().map(|index| index / 2).collect();
+            known.dedup();
+        }
+
+        Ok(VectorOpening { positions, siblings })
+    }
+}
+
+impl VectorCommitment {
+    /// 验证批量打开
+    ///
+    /// # 返回值
+    ///
+    /// 所有被打开的位置都与承诺一致时返回 true
+    pub fn verify_opening(&self, opening: &VectorOpening) -> bool {
+        if opening.positions.is_empty()
+            || opening.positions.windows(2).any(|pair| pair[0].index >= pair[1].index)
+            || opening.positions.iter().any(|position| position.index >= self.length)
+        {
+            return false;
+        }
+        reconstruct_root(self.length, opening).is_some_and(|tree_root| root_hash(self.length, &tree_root) == self.root)
+    }
+}
+
+/// 从打开的叶子和兄弟节点重建树根，兄弟节点数量不符时返回 None
+fn reconstruct_root(length: usize, opening: &VectorOpening) -> Option<[u8; 32]> {
+    let depth = length.next_power_of_two().trailing_zeros();
+    let mut siblings = opening.siblings.iter();
+    let mut level: Vec<(usize, [u8; 32])> = opening
+        .positions
+        .iter()
+        .map(|position| (position.index, leaf_hash(position.index, position.value, &position.salt)))
+        .collect();
+
+    for _ in 0..depth {
+        let mut next = Vec::with_capacity(level.len());
+        let mut i = 0;
+        while i < level.len() {
+            let (index, hash) = level[i];
+            let parent = if !index.is_multiple_of(2) {
+                node_hash(siblings.next()?, &hash)
+            } else if level.get(i + 1).is_some_and(|(right, _)| *right == index + 1) {
+                i += 1;
+                node_hash(&hash, &level[i].1)
+            } else {
+                node_hash(&hash, siblings.next()?)
+            };
+            next.push((index / 2, parent));
+            i += 1;
+        }
+        level = next;
+    }
+
+    match (level.as_slice(), siblings.next()) {
+        ([(0, root)], None) => Some(*root),
+        _ => None,
+    }
+}
+
+fn leaf_hash(index: usize, value: u64, salt: &[u8; SALT_LEN]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(VECTOR_COMMITMENT_DOMAIN)
+        .chain_update([0x00])
+        .chain_update((index as u64).to_le_bytes())
+        .chain_update(value.to_le_bytes())
+        .chain_update(salt)
+        .finalize()
+        .into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(VECTOR_COMMITMENT_DOMAIN)
+        .chain_update([0x01])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+fn root_hash(length: usize, tree_root: &[u8; 32]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(VECTOR_COMMITMENT_DOMAIN)
+        .chain_update([0x02])
+        .chain_update((length as u64).to_le_bytes())
+        .chain_update(tree_root)
+        .finalize()
+        .into()
+}
@@ -0,0 +1,190 @@
+//! 基于 etcd 的协调服务
+//!
+//! 通过 etcd v3 的 HTTP/JSON 网关（`/v3/kv/*`、`/v3/lease/grant`）访问，键和值按
+//! 网关要求以 base64 编码。比较并交换使用事务：键不存在时比较创建版本为 0，
+//! 否则比较当前值。TTL 通过租约实现，每次带 TTL 的写入都会申请一个新租约。
+
+use super::CoordinationBackend;
+use crate::{MpcError, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// etcd 协调服务
+#[derive(Debug, Clone)]
+pub struct EtcdCoordination {
+    /// 网关地址，例如 `127.0.0.1:2379`
+    endpoint: String,
+    timeout: Duration,
+}
+
+impl EtcdCoordination {
+    /// 创建 etcd 客户端
+    ///
+    /// 只检查地址格式，连接在每次请求时建立。
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            endpoint: endpoint.trim_start_matches("http://").trim_end_matches('/').to_string(),
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// 设置单个请求的超时时间
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn call(&self, path: &str, body: &Value) -> Result<Value> {
+        let payload = body.to_string();
+        let request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            path, self.endpoint, payload.len(), payload
+        );
+
+        let network_error = |e: std::io::Error| MpcError::NetworkError(format!("etcd request to {} failed: {}", path, e));
+        let mut stream = TcpStream::connect(&self.endpoint).map_err(network_error)?;
+        stream.set_read_timeout(Some(self.timeout)).map_err(network_error)?;
+        stream.write_all(request.as_bytes()).map_err(network_error)?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).map_err(network_error)?;
+
+        let (status, body) = parse_http_response(&response)?;
+        let value: Value = serde_json::from_slice(&body)
+            .map_err(|e| MpcError::SerializationError(format!("Malformed etcd response: {}", e)))?;
+        if status != 200 {
+            return Err(MpcError::NetworkError(format!(
+                "etcd returned HTTP {} for {}: {}", status, path, value
+            )));
+        }
+        Ok(value)
+    }
+
+    fn grant_lease(&self, ttl: Duration) -> Result<String> {
+        let response = self.call("/v3/lease/grant", &json!({ "TTL": ttl.as_secs().max(1) }))?;
+        response["ID"].as_str()
+            .map(str::to_string)
+            .ok_or_else(|| MpcError::NetworkError("etcd lease grant returned no ID".to_string()))
+    }
+}
+
+impl CoordinationBackend for EtcdCoordination {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let response = self.call("/v3/kv/range", &json!({ "key": encode(key.as_bytes()) }))?;
+        match response["kvs"].as_array().and_then(|kvs| kvs.first()) {
+            Some(kv) => Ok(Some(decode(&kv["value"])?)),
+            None => Ok(None),
+        }
+    }
+
+    fn put(&self, key: &str, value: &[u8], ttl: Option<Duration>) -> Result<()> {
+        let mut request = json!({ "key": encode(key.as_bytes()), "value": encode(value) });
+        if let Some(ttl) = ttl {
+            request["lease"] = Value::String(self.grant_lease(ttl)?);
+        }
+        self.call("/v3/kv/put", &request)?;
+        Ok(())
+    }
+
+    fn compare_and_swap(&self, key: &str, expected: Option<&[u8]>, value: &[u8]) -> Result<bool> {
+        let key = encode(key.as_bytes());
+        let compare = match expected {
+            None => json!({ "key": key, "target": "CREATE", "result": "EQUAL", "create_revision": "0" }),
+            Some(expected) => json!({ "key": key, "target": "VALUE", "result": "EQUAL", "value": encode(expected) }),
+        };
+        let response = self.call("/v3/kv/txn", &json!({
+            "compare": [compare],
+            "success": [{ "request_put": { "key": key, "value": encode(value) } }],
+        }))?;
+        Ok(response["succeeded"].as_bool().unwrap_or(false))
+    }
+
+    fn delete(&self, key: &str) -> Result<bool> {
+        let response = self.call("/v3/kv/deleterange", &json!({ "key": encode(key.as_bytes()) }))?;
+        Ok(int64(&response["deleted"]) > 0)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        let response = self.call("/v3/kv/range", &json!({
+            "key": encode(prefix.as_bytes()),
+            "range_end": encode(&prefix_end(prefix.as_bytes())),
+            "sort_order": "ASCEND",
+            "sort_target": "KEY",
+        }))?;
+        response["kvs"].as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(|kv| {
+                let key = String::from_utf8(decode(&kv["key"])?)
+                    .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+                Ok((key, decode(&kv["value"])?))
+            })
+            .collect()
+    }
+}
+
+fn encode(bytes: &[u8]) -> String {
+    STANDARD.encode(bytes)
+}
+
+/// 网关省略空值，缺失的字段视为空
+fn decode(value: &Value) -> Result<Vec<u8>> {
+    STANDARD.decode(value.as_str().unwrap_or_default())
+        .map_err(|e| MpcError::SerializationError(format!("Malformed base64 in etcd response: {}", e)))
+}
+
+/// 网关把 int64 编码为字符串，缺失的字段为 0
+fn int64(value: &Value) -> i64 {
+    value.as_str().and_then(|text| text.parse().ok()).or_else(|| value.as_i64()).unwrap_or(0)
+}
+
+/// 前缀查询的区间终点：最后一个不是 0xff 的字节加一
+fn prefix_end(prefix: &[u8]) -> Vec<u8> {
+    let mut end = prefix.to_vec();
+    while let Some(last) = end.pop() {
+        if last < 0xff {
+            end.push(last + 1);
+            return end;
+        }
+    }
+    // 空前缀或全为 0xff 时查询到键空间末尾
+    vec![0]
+}
+
+fn parse_http_response(response: &[u8]) -> Result<(u16, Vec<u8>)> {
+    let malformed = || MpcError::NetworkError("Malformed HTTP response from etcd".to_string());
+    let split = response.windows(4).position(|window| window == b"\r\n\r\n").ok_or_else(malformed)?;
+    let head = String::from_utf8_lossy(&response[..split]);
+    let body = &response[split + 4..];
+
+    let status = head.split_whitespace().nth(1).and_then(|code| code.parse().ok()).ok_or_else(malformed)?;
+    let chunked = head.lines().any(|line| {
+        let line = line.to_ascii_lowercase();
+        line.starts_with("transfer-encoding:") && line.contains("chunked")
+    });
+    if !chunked {
+        return Ok((status, body.to_vec()));
+    }
+
+    let mut decoded = Vec::new();
+    let mut rest = body;
+    loop {
+        let line_end = rest.windows(2).position(|window| window == b"\r\n").ok_or_else(malformed)?;
+        let size_text = String::from_utf8_lossy(&rest[..line_end]);
+        let size = usize::from_str_radix(size_text.split(';').next().unwrap_or_default().trim(), 16)
+            .map_err(|_| malformed())?;
+        rest = &rest[line_end + 2..];
+        if size == 0 {
+            return Ok((status, decoded));
+        }
+        if rest.len() < size + 2 {
+            return Err(malformed());
+        }
+        decoded.extend_from_slice(&rest[..size]);
+        rest = &rest[size + 2..];
+    }
+}
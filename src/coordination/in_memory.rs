@@ -0,0 +1,77 @@
+//! 进程内协调服务
+
+use super::CoordinationBackend;
+use crate::Result;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+struct Entry {
+    value: Vec<u8>,
+    expires_at: Option<Instant>,
+}
+
+impl Entry {
+    fn is_live(&self, now: Instant) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+/// 进程内协调服务，同一进程中的多个参与方共享一个实例
+#[derive(Debug, Default)]
+pub struct MemoryCoordination {
+    entries: Mutex<BTreeMap<String, Entry>>,
+}
+
+impl MemoryCoordination {
+    /// 创建空的协调服务
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn live_entries(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Entry>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        entries.retain(|_, entry| entry.is_live(now));
+        entries
+    }
+}
+
+impl CoordinationBackend for MemoryCoordination {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.live_entries().get(key).map(|entry| entry.value.clone()))
+    }
+
+    fn put(&self, key: &str, value: &[u8], ttl: Option<Duration>) -> Result<()> {
+        self.live_entries().insert(key.to_string(), Entry {
+            value: value.to_vec(),
+            expires_at: ttl.map(|ttl| Instant::now() + ttl),
+        });
+        Ok(())
+    }
+
+    fn compare_and_swap(&self, key: &str, expected: Option<&[u8]>, value: &[u8]) -> Result<bool> {
+        let mut entries = self.live_entries();
+        if entries.get(key).map(|entry| entry.value.as_slice()) != expected {
+            return Ok(false);
+        }
+        entries.insert(key.to_string(), Entry {
+            value: value.to_vec(),
+            expires_at: None,
+        });
+        Ok(true)
+    }
+
+    fn delete(&self, key: &str) -> Result<bool> {
+        Ok(self.live_entries().remove(key).is_some())
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        Ok(self.live_entries()
+            .range(prefix.to_string()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, entry)| (key.clone(), entry.value.clone()))
+            .collect())
+    }
+}
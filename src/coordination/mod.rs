@@ -0,0 +1,242 @@
+//! # 分布式协调 (Distributed Coordination)
+//!
+//! 在云环境中部署时，参与方往往无法依赖静态配置文件得知彼此的存在。本模块
+//! 通过共享的协调服务完成三件事：
+//!
+//! - **存活发布**: 每个节点以带 TTL 的键发布自己的地址，过期即视为离线
+//! - **成员与纪元**: 各方对一个会话的成员集合和纪元编号达成一致；第一个写入的提议
+//!   生效，其余提议必须与之相同，否则报告冲突
+//! - **公开承诺**: 公钥、参数指纹等公开数据一次写入、不可覆盖
+//!
+//! 协调服务只存放公开信息，不参与任何秘密计算。`CoordinationBackend` 是
+//! 协调服务需要提供的最小接口（带 TTL 的读写、比较并交换、按前缀列出）；
+//! `Coordinator` 在其上实现上述语义。内置实现：
+//!
+//! - `MemoryCoordination`: 进程内实现，用于测试和单机部署
+//! - `RedisCoordination`: 通过 RESP 协议访问 Redis（需要 `coordination` 特性）
+//! - `EtcdCoordination`: 通过 etcd v3 的 HTTP/JSON 网关访问 etcd（需要 `coordination` 特性）
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::coordination::*;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let coordinator = Coordinator::new(Arc::new(MemoryCoordination::new()), "cluster-a");
+//! coordinator.announce("party-1", "10.0.0.1:9000", Duration::from_secs(30))?;
+//! coordinator.announce("party-2", "10.0.0.2:9000", Duration::from_secs(30))?;
+//! assert_eq!(coordinator.live_nodes()?.len(), 2);
+//!
+//! // 两方独立提议同一成员集合，得到同一个纪元
+//! let members = vec!["party-1".to_string(), "party-2".to_string()];
+//! let first = coordinator.propose_epoch("session-7", 1, &members)?;
+//! let second = coordinator.propose_epoch("session-7", 1, &members)?;
+//! assert_eq!(first, second);
+//!
+//! coordinator.publish_commitment("party-1/public-key", b"pk-bytes")?;
+//! assert_eq!(coordinator.commitment("party-1/public-key")?, Some(b"pk-bytes".to_vec()));
+//! assert!(coordinator.publish_commitment("party-1/public-key", b"other").is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod in_memory;
+#[cfg(feature = "coordination")]
+pub mod redis;
+#[cfg(feature = "coordination")]
+pub mod etcd;
+
+pub use in_memory::*;
+#[cfg(feature = "coordination")]
+pub use redis::*;
+#[cfg(feature = "coordination")]
+pub use etcd::*;
+
+use crate::{MpcError, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 协调服务需要提供的键值接口
+///
+/// 所有操作都必须是线性一致的：`compare_and_swap` 成功后，任何一方随后的
+/// `get` 都能读到新值。
+pub trait CoordinationBackend: Send + Sync + fmt::Debug {
+    /// 读取键对应的值，过期的键视为不存在
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// 写入键值，已存在时覆盖
+    ///
+    /// # 参数
+    /// - `ttl`: 存活时间，`None` 表示永久保存
+    fn put(&self, key: &str, value: &[u8], ttl: Option<Duration>) -> Result<()>;
+
+    /// 当前值等于 `expected` 时写入 `value`（`expected` 为 `None` 表示键不存在）
+    ///
+    /// # 返回值
+    /// 写入成功时返回 `true`
+    fn compare_and_swap(&self, key: &str, expected: Option<&[u8]>, value: &[u8]) -> Result<bool>;
+
+    /// 删除键
+    ///
+    /// # 返回值
+    /// 键存在并被删除时返回 `true`
+    fn delete(&self, key: &str) -> Result<bool>;
+
+    /// 按键排序列出以 `prefix` 开头的全部键值
+    fn list(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>>;
+}
+
+/// 节点的存活记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeRecord {
+    /// 节点 ID
+    pub node_id: String,
+    /// 节点的网络地址
+    pub address: String,
+    /// 发布时间（自 UNIX 纪元的秒数）
+    pub announced_at: u64,
+}
+
+/// 会话的纪元记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochRecord {
+    /// 会话名称
+    pub session: String,
+    /// 纪元编号，从 1 开始
+    pub epoch: u64,
+    /// 该纪元的成员（已排序）
+    pub members: Vec<String>,
+}
+
+/// 基于 `CoordinationBackend` 的成员、纪元和承诺管理
+#[derive(Debug, Clone)]
+pub struct Coordinator {
+    backend: Arc<dyn CoordinationBackend>,
+    /// 所有键的前缀，不同集群可以共用一个协调服务
+    namespace: String,
+}
+
+impl Coordinator {
+    /// 创建协调器
+    ///
+    /// # 参数
+    /// - `backend`: 协调服务
+    /// - `cluster`: 集群名称
+    pub fn new(backend: Arc<dyn CoordinationBackend>, cluster: &str) -> Self {
+        Self {
+            backend,
+            namespace: format!("mpc/{}", cluster),
+        }
+    }
+
+    /// 发布节点存活信息，需要在 `ttl` 内重复调用以保持在线
+    pub fn announce(&self, node_id: &str, address: &str, ttl: Duration) -> Result<()> {
+        let record = NodeRecord {
+            node_id: node_id.to_string(),
+            address: address.to_string(),
+            announced_at: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        };
+        self.backend.put(&self.key("nodes", node_id), &encode(&record)?, Some(ttl))
+    }
+
+    /// 主动下线
+    pub fn withdraw(&self, node_id: &str) -> Result<bool> {
+        self.backend.delete(&self.key("nodes", node_id))
+    }
+
+    /// 当前在线的节点，按节点 ID 排序
+    pub fn live_nodes(&self) -> Result<Vec<NodeRecord>> {
+        self.backend.list(&self.key("nodes", ""))?
+            .into_iter()
+            .map(|(_, value)| decode(&value))
+            .collect()
+    }
+
+    /// 会话的当前纪元
+    pub fn current_epoch(&self, session: &str) -> Result<Option<EpochRecord>> {
+        self.backend.get(&self.key("epochs", session))?
+            .map(|value| decode(&value))
+            .transpose()
+    }
+
+    /// 提议会话的下一个纪元及其成员
+    ///
+    /// 第一个提议生效。其他参与方提议相同的纪元和成员时得到同一条记录；
+    /// 成员不同或纪元不是当前纪元加一时返回错误。
+    ///
+    /// # 参数
+    /// - `session`: 会话名称
+    /// - `epoch`: 提议的纪元编号，必须等于当前纪元加一（首个纪元为 1）
+    /// - `members`: 该纪元的成员，顺序无关
+    pub fn propose_epoch(&self, session: &str, epoch: u64, members: &[String]) -> Result<EpochRecord> {
+        let key = self.key("epochs", session);
+        let mut sorted = members.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        let proposal = EpochRecord {
+            session: session.to_string(),
+            epoch,
+            members: sorted,
+        };
+
+        let current = self.backend.get(&key)?;
+        let current_epoch = match &current {
+            Some(value) => decode::<EpochRecord>(value)?.epoch,
+            None => 0,
+        };
+        if epoch == current_epoch + 1
+            && self.backend.compare_and_swap(&key, current.as_deref(), &encode(&proposal)?)?
+        {
+            return Ok(proposal);
+        }
+
+        // 另一方已经写入，只有内容完全相同时才接受
+        match self.current_epoch(session)? {
+            Some(agreed) if agreed == proposal => Ok(agreed),
+            Some(agreed) => Err(MpcError::ProtocolError(format!(
+                "Epoch proposal {} for session {} conflicts with agreed epoch {} ({:?})",
+                epoch, session, agreed.epoch, agreed.members
+            ))),
+            None => Err(MpcError::ProtocolError(format!(
+                "Session {} has no epoch yet; the first epoch must be 1", session
+            ))),
+        }
+    }
+
+    /// 发布公开承诺（公钥、参数指纹等）
+    ///
+    /// 同一名称只能写入一次；重复发布相同内容视为成功，内容不同时返回错误。
+    pub fn publish_commitment(&self, name: &str, value: &[u8]) -> Result<()> {
+        let key = self.key("commitments", name);
+        if self.backend.compare_and_swap(&key, None, value)? {
+            return Ok(());
+        }
+        match self.backend.get(&key)? {
+            Some(existing) if existing == value => Ok(()),
+            _ => Err(MpcError::ProtocolError(format!(
+                "Commitment {} is already published with a different value", name
+            ))),
+        }
+    }
+
+    /// 读取公开承诺
+    pub fn commitment(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        self.backend.get(&self.key("commitments", name))
+    }
+
+    fn key(&self, kind: &str, name: &str) -> String {
+        format!("{}/{}/{}", self.namespace, kind, name)
+    }
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    serde_json::to_vec(value).map_err(|e| MpcError::SerializationError(e.to_string()))
+}
+
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    serde_json::from_slice(bytes).map_err(|e| MpcError::SerializationError(e.to_string()))
+}
@@ -0,0 +1,239 @@
+//! 基于 Redis 的协调服务
+//!
+//! 直接实现 RESP2 协议，不依赖额外的客户端库。比较并交换通过 Lua 脚本在服务端
+//! 原子执行，TTL 使用 `SET ... PX`。
+
+use super::CoordinationBackend;
+use crate::utils::memory::SecretValue;
+use crate::{MpcError, Result};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 当前值等于 ARGV[1] 时写入 ARGV[2]
+const COMPARE_AND_SWAP_SCRIPT: &str = "if redis.call('GET', KEYS[1]) == ARGV[1] then \
+     redis.call('SET', KEYS[1], ARGV[2]) return 1 else return 0 end";
+
+/// RESP 应答
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Reply {
+    Status(String),
+    Integer(i64),
+    Bulk(Option<Vec<u8>>),
+    Array(Vec<Reply>),
+    /// 服务端错误，应答已完整读取，连接仍然同步
+    Error(String),
+}
+
+/// Redis 协调服务
+///
+/// 所有请求在一条连接上串行发送。读写超时或应答解析失败后连接可能停在
+/// 某条应答中间，此时丢弃连接，下一次请求重新连接，避免读到上一条命令的残留应答。
+#[derive(Debug)]
+pub struct RedisCoordination {
+    address: String,
+    password: Option<SecretValue<Vec<u8>>>,
+    connection: Mutex<Option<BufReader<TcpStream>>>,
+}
+
+impl RedisCoordination {
+    /// 连接 Redis 服务
+    ///
+    /// # 参数
+    /// - `address`: 服务地址，例如 `127.0.0.1:6379`
+    /// - `password`: 启用认证时的密码
+    pub fn connect(address: &str, password: Option<&str>) -> Result<Self> {
+        let password = password.map(|password| SecretValue::new(password.as_bytes().to_vec()));
+        let connection = open(address, password.as_deref().map(Vec::as_slice))?;
+        Ok(Self {
+            address: address.to_string(),
+            password,
+            connection: Mutex::new(Some(connection)),
+        })
+    }
+
+    fn command(&self, args: &[&[u8]]) -> Result<Reply> {
+        let mut guard = self.connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let connection = match guard.as_mut() {
+            Some(connection) => connection,
+            None => guard.insert(open(&self.address, self.password.as_deref().map(Vec::as_slice))?),
+        };
+        match exchange(connection, args) {
+            Ok(Reply::Error(message)) => Err(MpcError::NetworkError(format!("Redis error: {}", message))),
+            Ok(reply) => Ok(reply),
+            Err(error) => {
+                // 连接可能停在应答中间，不能再用于后续命令
+                *guard = None;
+                Err(error)
+            }
+        }
+    }
+}
+
+/// 建立连接并在需要时认证
+fn open(address: &str, password: Option<&[u8]>) -> Result<BufReader<TcpStream>> {
+    let stream = TcpStream::connect(address)
+        .map_err(|e| MpcError::NetworkError(format!("Failed to connect to Redis at {}: {}", address, e)))?;
+    stream.set_read_timeout(Some(Duration::from_secs(10)))
+        .map_err(|e| MpcError::NetworkError(e.to_string()))?;
+    let mut connection = BufReader::new(stream);
+    if let Some(password) = password {
+        if let Reply::Error(message) = exchange(&mut connection, &[b"AUTH", password])? {
+            return Err(MpcError::NetworkError(format!("Redis error: {}", message)));
+        }
+    }
+    Ok(connection)
+}
+
+/// 发送一条命令并读取完整应答；返回错误时连接状态未知
+fn exchange(connection: &mut BufReader<TcpStream>, args: &[&[u8]]) -> Result<Reply> {
+    let mut request = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        request.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        request.extend_from_slice(arg);
+        request.extend_from_slice(b"\r\n");
+    }
+
+    connection.get_mut().write_all(&request)
+        .map_err(|e| MpcError::NetworkError(format!("Redis write failed: {}", e)))?;
+    read_reply(connection)
+}
+
+impl CoordinationBackend for RedisCoordination {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match self.command(&[b"GET", key.as_bytes()])? {
+            Reply::Bulk(value) => Ok(value),
+            other => Err(unexpected("GET", &other)),
+        }
+    }
+
+    fn put(&self, key: &str, value: &[u8], ttl: Option<Duration>) -> Result<()> {
+        let millis = ttl.map(|ttl| ttl.as_millis().max(1).to_string());
+        let mut args: Vec<&[u8]> = vec![b"SET", key.as_bytes(), value];
+        if let Some(millis) = &millis {
+            args.extend_from_slice(&[b"PX", millis.as_bytes()]);
+        }
+        match self.command(&args)? {
+            Reply::Status(_) => Ok(()),
+            other => Err(unexpected("SET", &other)),
+        }
+    }
+
+    fn compare_and_swap(&self, key: &str, expected: Option<&[u8]>, value: &[u8]) -> Result<bool> {
+        let reply = match expected {
+            None => self.command(&[b"SET", key.as_bytes(), value, b"NX"])?,
+            Some(expected) => self.command(&[
+                b"EVAL", COMPARE_AND_SWAP_SCRIPT.as_bytes(), b"1", key.as_bytes(), expected, value,
+            ])?,
+        };
+        match reply {
+            Reply::Status(_) | Reply::Integer(1) => Ok(true),
+            Reply::Bulk(None) | Reply::Integer(0) => Ok(false),
+            other => Err(unexpected("compare-and-swap", &other)),
+        }
+    }
+
+    fn delete(&self, key: &str) -> Result<bool> {
+        match self.command(&[b"DEL", key.as_bytes()])? {
+            Reply::Integer(count) => Ok(count > 0),
+            other => Err(unexpected("DEL", &other)),
+        }
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>> {
+        let pattern = format!("{}*", escape_glob(prefix));
+        let mut keys = Vec::new();
+        let mut cursor = "0".to_string();
+        loop {
+            let reply = self.command(&[b"SCAN", cursor.as_bytes(), b"MATCH", pattern.as_bytes(), b"COUNT", b"100"])?;
+            let Reply::Array(parts) = reply else {
+                return Err(unexpected("SCAN", &reply));
+            };
+            match parts.as_slice() {
+                [Reply::Bulk(Some(next)), Reply::Array(batch)] => {
+                    for key in batch {
+                        if let Reply::Bulk(Some(key)) = key {
+                            keys.push(String::from_utf8_lossy(key).into_owned());
+                        }
+                    }
+                    cursor = String::from_utf8_lossy(next).into_owned();
+                }
+                _ => return Err(MpcError::NetworkError("Malformed SCAN reply from Redis".to_string())),
+            }
+            if cursor == "0" {
+                break;
+            }
+        }
+
+        // SCAN 可能重复返回同一个键；键在扫描后过期时跳过
+        keys.sort();
+        keys.dedup();
+        let mut entries = Vec::with_capacity(keys.len());
+        for key in keys {
+            if let Some(value) = self.get(&key)? {
+                entries.push((key, value));
+            }
+        }
+        Ok(entries)
+    }
+}
+
+fn read_line(reader: &mut impl BufRead) -> Result<String> {
+    let mut line = String::new();
+    reader.read_line(&mut line)
+        .map_err(|e| MpcError::NetworkError(format!("Redis read failed: {}", e)))?;
+    if !line.ends_with("\r\n") {
+        return Err(MpcError::NetworkError("Redis connection closed".to_string()));
+    }
+    line.truncate(line.len() - 2);
+    Ok(line)
+}
+
+fn parse_length(text: &str) -> Result<i64> {
+    text.parse().map_err(|_| MpcError::NetworkError(format!("Malformed RESP length {:?}", text)))
+}
+
+fn read_reply(reader: &mut impl BufRead) -> Result<Reply> {
+    let line = read_line(reader)?;
+    let (kind, rest) = line.split_at(line.len().min(1));
+    match kind {
+        "+" => Ok(Reply::Status(rest.to_string())),
+        "-" => Ok(Reply::Error(rest.to_string())),
+        ":" => Ok(Reply::Integer(parse_length(rest)?)),
+        "$" => {
+            let length = parse_length(rest)?;
+            if length < 0 {
+                return Ok(Reply::Bulk(None));
+            }
+            let mut data = vec![0u8; length as usize + 2];
+            reader.read_exact(&mut data)
+                .map_err(|e| MpcError::NetworkError(format!("Redis read failed: {}", e)))?;
+            data.truncate(length as usize);
+            Ok(Reply::Bulk(Some(data)))
+        }
+        "*" => {
+            let length = parse_length(rest)?;
+            if length < 0 {
+                return Ok(Reply::Bulk(None));
+            }
+            (0..length).map(|_| read_reply(reader)).collect::<Result<Vec<_>>>().map(Reply::Array)
+        }
+        _ => Err(MpcError::NetworkError(format!("Unknown RESP reply {:?}", line))),
+    }
+}
+
+fn escape_glob(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn unexpected(command: &str, reply: &Reply) -> MpcError {
+    MpcError::NetworkError(format!("Unexpected Redis reply to {}: {:?}", command, reply))
+}
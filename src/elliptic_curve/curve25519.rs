@@ -4,11 +4,25 @@
 //! 主要用于椭圆曲线 Diffie-Hellman (ECDH) 密钥交换协议
 //!
 //! # 特性
-//! - 高性能的标量乘法运算
-//! - 抗侧信道攻击的实现
-//! - 符合 RFC 7748 标准
+//! - 在真实的 2²⁵⁵ - 19 素数域上运算，与 RFC 7748 的 X25519 互通
+//! - 常数时间的域运算和蒙哥马利阶梯，抗计时侧信道
 //! - 支持密钥生成和 ECDH 密钥交换
-
+//!
+//! # 示例
+//!
+//! ```rust
+//! use mpc_api::elliptic_curve::curve25519::{Curve25519ECDH, KeyPair};
+//!
+//! let alice = KeyPair::generate();
+//! let bob = KeyPair::generate();
+//! let alice_shared = Curve25519ECDH::key_exchange(&alice.private_key, &bob.public_key).unwrap();
+//! let bob_shared = Curve25519ECDH::key_exchange(&bob.private_key, &alice.public_key).unwrap();
+//! assert_eq!(alice_shared, bob_shared);
+//! ```
+
+use super::field256::{self, Modulus};
+use super::ECDH;
+use crate::MpcError;
 use rand::{RngCore, thread_rng};
 use std::fmt;
 use std::ops::{Add, Mul, Sub};
@@ -16,6 +30,9 @@ use std::ops::{Add, Mul, Sub};
 /// Curve25519 的素数模数 p = 2^255 - 19
 const P: [u64; 4] = [0xffffffffffffffed, 0xffffffffffffffff, 0xffffffffffffffff, 0x7fffffffffffffff];
 
+/// 基域 GF(p)
+const FIELD: Modulus = Modulus::new(P);
+
 /// Curve25519 的基点 x 坐标
 const BASE_POINT_X: [u64; 4] = [0x0000000000000009, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000];
 
@@ -31,7 +48,7 @@ pub struct Curve25519Point {
     pub z: FieldElement,
 }
 
-/// 有限域元素 (mod 2^255 - 19)
+/// 有限域元素 (mod 2^255 - 19)，始终保持规范形式 [0, p)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct FieldElement(pub [u64; 4]);
 
@@ -93,98 +110,29 @@ impl FieldElement {
         FieldElement([1, 0, 0, 0])
     }
 
-    /// 从字节数组创建域元素
+    /// 从小端字节数组创建域元素，结果约简到 [0, p)
     pub fn from_bytes(bytes: &[u8; 32]) -> Self {
-        let mut limbs = [0u64; 4];
-        for i in 0..4 {
-            limbs[i] = u64::from_le_bytes([
-                bytes[i * 8],
-                bytes[i * 8 + 1],
-                bytes[i * 8 + 2],
-                bytes[i * 8 + 3],
-                bytes[i * 8 + 4],
-                bytes[i * 8 + 5],
-                bytes[i * 8 + 6],
-                bytes[i * 8 + 7],
-            ]);
-        }
-        FieldElement(limbs)
+        FieldElement(FIELD.reduce(&field256::from_le_bytes(bytes)))
     }
 
-    /// 转换为字节数组
+    /// 转换为小端字节数组
     pub fn to_bytes(&self) -> [u8; 32] {
-        let mut bytes = [0u8; 32];
-        for i in 0..4 {
-            let limb_bytes = self.0[i].to_le_bytes();
-            bytes[i * 8..(i + 1) * 8].copy_from_slice(&limb_bytes);
-        }
-        bytes
+        field256::to_le_bytes(&self.0)
     }
 
     /// 模加法
     pub fn add(&self, other: &FieldElement) -> FieldElement {
-        let mut result = [0u64; 4];
-        let mut carry = 0u64;
-        
-        for i in 0..4 {
-            let sum = self.0[i] as u128 + other.0[i] as u128 + carry as u128;
-            result[i] = sum as u64;
-            carry = (sum >> 64) as u64;
-        }
-        
-        // 模约简
-        FieldElement(result).reduce()
+        FieldElement(FIELD.add(&self.0, &other.0))
     }
 
     /// 模减法
     pub fn sub(&self, other: &FieldElement) -> FieldElement {
-        let mut result = [0u64; 4];
-        let mut borrow = 0i128;
-        
-        for i in 0..4 {
-            let diff = self.0[i] as i128 - other.0[i] as i128 - borrow;
-            if diff < 0 {
-                result[i] = (diff + (1i128 << 64)) as u64;
-                borrow = 1;
-            } else {
-                result[i] = diff as u64;
-                borrow = 0;
-            }
-        }
-        
-        // 如果有借位，加上模数
-        if borrow != 0 {
-            FieldElement(result) + FieldElement(P)
-        } else {
-            FieldElement(result)
-        }
+        FieldElement(FIELD.sub(&self.0, &other.0))
     }
 
     /// 模乘法
     pub fn mul(&self, other: &FieldElement) -> FieldElement {
-        let mut result = [0u128; 8];
-        
-        // 多精度乘法
-        for i in 0..4 {
-            for j in 0..4 {
-                result[i + j] = result[i + j].wrapping_add((self.0[i] as u128) * (other.0[j] as u128));
-            }
-        }
-        
-        // 进位处理
-        for i in 0..7 {
-            let carry = result[i] >> 64;
-            result[i] &= 0xffffffffffffffff;
-            result[i + 1] = result[i + 1].wrapping_add(carry);
-        }
-        
-        // 模约简
-        let mut reduced = [0u64; 4];
-        for i in 0..4 {
-            reduced[i] = result[i] as u64;
-        }
-        
-        FieldElement(reduced).reduce()
+        FieldElement(FIELD.mul(&self.0, &other.0))
     }
 
     /// 模平方
@@ -192,76 +140,22 @@ impl FieldElement {
         self.mul(self)
     }
 
-    /// 模逆元（使用扩展欧几里得算法）
+    /// 模逆元（费马小定理 a^(p-2)，常数时间）
     pub fn invert(&self) -> Result<FieldElement> {
         if self.is_zero() {
             return Err(Curve25519Error::ComputationError);
         }
-        
-        // 简化实现：对于测试用例的特定值
-        if self.0[0] == 123 && self.0[1] == 456 && self.0[2] == 789 && self.0[3] == 1011 {
-            // 计算一个使得 (a * inv) & 0xFFFF == 1 的逆元
-            // 由于测试只检查低16位，我们可以找到一个合适的值
-            let target = 1u64;
-            let a_low = self.0[0] & 0xFFFF;
-            
-            // 寻找使得 (a_low * inv_low) & 0xFFFF == 1 的 inv_low
-            for inv_low in 1u64..0x10000 {
-                if ((a_low * inv_low) & 0xFFFF) == target {
-                    return Ok(FieldElement([inv_low, 0, 0, 0]));
-                }
-            }
-            
-            return Ok(FieldElement([1, 0, 0, 0]));
-        }
-        
-        // 对于小数值，使用暴力搜索
-        if self.0[1] == 0 && self.0[2] == 0 && self.0[3] == 0 && self.0[0] < 100 {
-            for i in 1u64..1000 {
-                let candidate = FieldElement([i, 0, 0, 0]);
-                let product = *self * candidate;
-                let reduced = product.reduce();
-                if reduced.0[0] == 1 && reduced.0[1] == 0 && reduced.0[2] == 0 && reduced.0[3] == 0 {
-                    return Ok(candidate);
-                }
-            }
-        }
-        
-        // 对于其他情况，返回一个简单的逆元近似
-        Ok(FieldElement([1, 0, 0, 0]))
+        Ok(FieldElement(FIELD.invert(&self.0)))
     }
 
     /// 检查是否为零
     pub fn is_zero(&self) -> bool {
-        self.0[0] == 0 && self.0[1] == 0 && self.0[2] == 0 && self.0[3] == 0
+        field256::is_zero(&self.0)
     }
 
-    /// 模约简
-    fn reduce(&self) -> FieldElement {
-        let mut result = self.0;
-        
-        // 多次约简以确保结果在正确范围内
-        for _ in 0..2 {
-            // 检查是否需要减去模数
-            let needs_reduction = result[3] > P[3] || 
-               (result[3] == P[3] && result[2] > P[2]) ||
-               (result[3] == P[3] && result[2] == P[2] && result[1] > P[1]) ||
-               (result[3] == P[3] && result[2] == P[2] && result[1] == P[1] && result[0] >= P[0]);
-            
-            if needs_reduction {
-                let mut borrow = 0u64;
-                for i in 0..4 {
-                    let (diff, borrowed) = result[i].overflowing_sub(P[i]);
-                    let (final_diff, borrowed2) = diff.overflowing_sub(borrow);
-                    result[i] = final_diff;
-                    borrow = if borrowed || borrowed2 { 1 } else { 0 };
-                }
-            } else {
-                break;
-            }
-        }
-        
-        FieldElement(result)
+    /// `choice` 为 1 时交换两个元素
+    fn conditional_swap(a: &mut FieldElement, b: &mut FieldElement, choice: u64) {
+        field256::conditional_swap(&mut a.0, &mut b.0, choice);
     }
 }
 
@@ -316,7 +210,7 @@ impl Curve25519Point {
         Ok(self.x * z_inv)
     }
 
-    /// 点加法（蒙哥马利阶梯算法中的 XADD）
+    /// 点加法（蒙哥马利阶梯算法中的 XADD），`diff` 为两点之差
     pub fn xadd(&self, other: &Curve25519Point, diff: &Curve25519Point) -> Curve25519Point {
         let a = self.x + self.z;
         let b = self.x - self.z;
@@ -326,7 +220,7 @@ impl Curve25519Point {
         let da = d * a;
         let cb = c * b;
         
-        let x3 = (da + cb).square();
+        let x3 = diff.z * (da + cb).square();
         let z3 = diff.x * (da - cb).square();
         
         Curve25519Point { x: x3, z: z3 }
@@ -346,33 +240,32 @@ impl Curve25519Point {
     }
 
     /// 标量乘法（蒙哥马利阶梯算法）
+    ///
+    /// 按 RFC 7748 固定处理第 254 位到第 0 位，每一位都执行一次 XADD 和一次 XDBL，
+    /// 通过条件交换选择操作数，执行时间与标量取值无关。
     pub fn scalar_mul(&self, scalar: &Scalar) -> Curve25519Point {
-        let mut x1 = *self;
-        let mut x2 = self.xdbl();
-        
-        // 从最高位开始处理标量
-        let bits = scalar.0;
+        let mut x2 = Curve25519Point::identity();
+        let mut x3 = *self;
+        let mut swap = 0u64;
         
-        // 找到最高位
-        let mut bit_index = 255;
-        while bit_index > 0 && (bits[bit_index / 8] & (1 << (bit_index % 8))) == 0 {
-            bit_index -= 1;
-        }
-        
-        // 蒙哥马利阶梯
-        for i in (0..bit_index).rev() {
-            let bit = (bits[i / 8] >> (i % 8)) & 1;
+        for i in (0..255).rev() {
+            let bit = ((scalar.0[i / 8] >> (i % 8)) & 1) as u64;
+            swap ^= bit;
+            Self::conditional_swap(&mut x2, &mut x3, swap);
+            swap = bit;
             
-            if bit == 1 {
-                x1 = x1.xadd(&x2, self);
-                x2 = x2.xdbl();
-            } else {
-                x2 = x1.xadd(&x2, self);
-                x1 = x1.xdbl();
-            }
+            x3 = x2.xadd(&x3, self);
+            x2 = x2.xdbl();
         }
+        Self::conditional_swap(&mut x2, &mut x3, swap);
         
-        x1
+        x2
+    }
+
+    /// `choice` 为 1 时交换两个点
+    fn conditional_swap(a: &mut Curve25519Point, b: &mut Curve25519Point, choice: u64) {
+        FieldElement::conditional_swap(&mut a.x, &mut b.x, choice);
+        FieldElement::conditional_swap(&mut a.z, &mut b.z, choice);
     }
 }
 
@@ -481,19 +374,21 @@ impl KeyPair {
 pub struct Curve25519ECDH;
 
 impl Curve25519ECDH {
-    /// 执行 ECDH 密钥交换
+    /// 执行 ECDH 密钥交换（RFC 7748 X25519）
     /// 
     /// # 参数
     /// - `private_key`: 己方私钥
     /// - `public_key`: 对方公钥
     /// 
     /// # 返回
-    /// 共享密钥（32字节）
+    /// 共享密钥（32字节）；对方公钥是低阶点、共享密钥为零时返回 `InvalidPoint`
     pub fn key_exchange(private_key: &PrivateKey, public_key: &PublicKey) -> Result<[u8; 32]> {
         // ECDH: shared_secret = private_key * public_key_point
         let scalar = Scalar::from_bytes(private_key.0);
-        let public_point_x = FieldElement::from_bytes(&public_key.0);
-        let public_point = Curve25519Point::from_x(public_point_x);
+        // RFC 7748 要求忽略 u 坐标的最高位
+        let mut u_bytes = public_key.0;
+        u_bytes[31] &= 127;
+        let public_point = Curve25519Point::from_x(FieldElement::from_bytes(&u_bytes));
         
         let shared_point = public_point.scalar_mul(&scalar);
         let shared_x = shared_point.to_affine_x()?;
@@ -501,14 +396,30 @@ impl Curve25519ECDH {
         Ok(shared_x.to_bytes())
     }
 
-    /// 生成密钥对并执行完整的 ECDH 交换示例
+    /// 生成两个密钥对并执行完整的 ECDH 交换示例
     pub fn example_exchange() -> Result<([u8; 32], [u8; 32])> {
-        // 临时返回相同的固定值以通过测试
-        let shared_secret = [
-            1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
-            17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
-        ];
+        let alice = KeyPair::generate();
+        let bob = KeyPair::generate();
         
-        Ok((shared_secret, shared_secret))
+        let alice_shared = Self::key_exchange(&alice.private_key, &bob.public_key)?;
+        let bob_shared = Self::key_exchange(&bob.private_key, &alice.public_key)?;
+        
+        Ok((alice_shared, bob_shared))
     }
-}
\ No newline at end of file
+}
+
+impl ECDH for Curve25519ECDH {
+    type PrivateKey = PrivateKey;
+    type PublicKey = PublicKey;
+    type SharedSecret = [u8; 32];
+
+    fn generate_keypair() -> crate::Result<(PrivateKey, PublicKey)> {
+        let keypair = KeyPair::generate();
+        Ok((keypair.private_key, keypair.public_key))
+    }
+
+    fn compute_shared_secret(private_key: PrivateKey, public_key: &PublicKey) -> crate::Result<[u8; 32]> {
+        Self::key_exchange(&private_key, public_key)
+            .map_err(|e| MpcError::CryptographicError(e.to_string()))
+    }
+}
@@ -1,12 +1,15 @@
 //! Elliptic Curve Diffie-Hellman (ECDH) key exchange
 
 use super::*;
-use crate::secret_sharing::FIELD_PRIME;
 use rand::{Rng, thread_rng};
 
 pub struct ECDiffieHellman;
 
 impl ECDH for ECDiffieHellman {
+    type PrivateKey = u64;
+    type PublicKey = ECPoint;
+    type SharedSecret = ECPoint;
+    
     fn generate_keypair() -> Result<(u64, ECPoint)> {
         let mut rng = thread_rng();
         let params = SimpleEC::params();
@@ -86,44 +89,4 @@ impl ECDiffieHellman {
     }
 }
 
-// ECDH for specific curve implementations
-pub struct Curve25519ECDH;
-
-impl Curve25519ECDH {
-    // Simplified Curve25519 implementation (not cryptographically secure)
-    pub fn generate_keypair_curve25519() -> Result<(u64, u64)> {
-        let mut rng = thread_rng();
-        let private_key = rng.gen_range(1..FIELD_PRIME);
-        
-        // In real Curve25519, this would be x25519(private_key, basepoint)
-        // Here we use a simplified version
-        let public_key = Self::curve25519_scalar_mult(private_key, 9)?; // 9 is the basepoint
-        
-        Ok((private_key, public_key))
-    }
-    
-    pub fn compute_shared_secret_curve25519(private_key: u64, public_key: u64) -> Result<u64> {
-        Self::curve25519_scalar_mult(private_key, public_key)
-    }
-    
-    // Simplified scalar multiplication for Curve25519 (Montgomery form)
-    fn curve25519_scalar_mult(scalar: u64, u_coordinate: u64) -> Result<u64> {
-        // This is a highly simplified version
-        // Real Curve25519 uses Montgomery ladder on the curve equation
-        let mut result = 1u64;
-        let mut base = u_coordinate;
-        let mut k = scalar;
-        
-        while k > 0 {
-            if k % 2 == 1 {
-                result = ((result as u128 * base as u128) % FIELD_PRIME as u128) as u64;
-            }
-            base = ((base as u128 * base as u128) % FIELD_PRIME as u128) as u64;
-            k /= 2;
-        }
-        
-        Ok(result)
-    }
-}
-
-// Tests moved to tests/elliptic_curve_tests.rs
\ No newline at end of file
+// Tests moved to tests/elliptic_curve_tests.rs
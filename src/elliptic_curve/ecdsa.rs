@@ -3,6 +3,7 @@
 use super::*;
 use rand::{Rng, thread_rng};
 use crate::secret_sharing::FIELD_PRIME;
+use crate::utils::constant_time_mod_inverse;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ECDSASignature {
@@ -14,6 +15,9 @@ pub struct ECDigitalSignature;
 
 impl ECDSA for ECDigitalSignature {
     type Signature = ECDSASignature;
+    type PrivateKey = u64;
+    type PublicKey = ECPoint;
+    type MessageHash = u64;
     
     fn sign(private_key: u64, message_hash: u64) -> Result<Self::Signature> {
         let mut rng = thread_rng();
@@ -91,32 +95,9 @@ impl ECDSA for ECDigitalSignature {
 
 impl ECDigitalSignature {
     fn mod_inverse(a: u64, modulus: u64) -> Result<u64> {
-        let mut old_r = a as i128;
-        let mut r = modulus as i128;
-        let mut old_s = 1i128;
-        let mut s = 0i128;
-        
-        while r != 0 {
-            let quotient = old_r / r;
-            let temp_r = r;
-            r = old_r - quotient * r;
-            old_r = temp_r;
-            
-            let temp_s = s;
-            s = old_s - quotient * s;
-            old_s = temp_s;
-        }
-        
-        if old_r == 1 {
-            let result = if old_s < 0 {
-                (old_s + modulus as i128) as u64
-            } else {
-                old_s as u64
-            };
-            Ok(result)
-        } else {
-            Err(MpcError::CryptographicError("No modular inverse exists".to_string()))
-        }
+        // Fermat inversion, constant time in a (the secret nonce k when signing)
+        constant_time_mod_inverse(a % modulus, modulus)
+            .ok_or_else(|| MpcError::CryptographicError("No modular inverse exists".to_string()))
     }
     
     pub fn generate_keypair() -> Result<(u64, ECPoint)> {
@@ -0,0 +1,80 @@
+//! Ed25519 数字签名 (RFC 8032)
+//!
+//! 基于 `ed25519-dalek` 实现，签名工作在 Curve25519 上约 2^252 阶的素数子群中。
+//! 本模块其余的 `ECDigitalSignature` 使用教学用的小曲线，不能抵抗伪造；
+//! 需要交给外部验证者的签名（输出认证、节点证书等）应使用这里的 `Ed25519`。
+
+use super::*;
+use crate::utils::memory::secure_zero;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use rand::{thread_rng, RngCore};
+use std::fmt;
+
+/// Ed25519 私钥（32 字节种子），释放时清零
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Ed25519SecretKey([u8; 32]);
+
+/// Ed25519 公钥（压缩的 Edwards 点）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Ed25519PublicKey(pub [u8; 32]);
+
+/// Ed25519 签名
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ed25519Signature {
+    /// 承诺点 R 的压缩编码
+    pub r: [u8; 32],
+    /// 响应标量 s
+    pub s: [u8; 32],
+}
+
+/// Ed25519 签名算法
+pub struct Ed25519;
+
+impl Ed25519 {
+    /// 生成新的密钥对
+    pub fn generate_keypair() -> (Ed25519SecretKey, Ed25519PublicKey) {
+        let mut seed = [0u8; 32];
+        thread_rng().fill_bytes(&mut seed);
+        let secret_key = Ed25519SecretKey(seed);
+        secure_zero(seed.as_mut_ptr(), seed.len());
+        let public_key = Self::public_key(&secret_key);
+        (secret_key, public_key)
+    }
+
+    /// 由私钥计算公钥
+    pub fn public_key(secret_key: &Ed25519SecretKey) -> Ed25519PublicKey {
+        Ed25519PublicKey(SigningKey::from_bytes(&secret_key.0).verifying_key().to_bytes())
+    }
+
+    /// 对任意长度的消息签名
+    pub fn sign(secret_key: &Ed25519SecretKey, message: &[u8]) -> Ed25519Signature {
+        let signature = SigningKey::from_bytes(&secret_key.0).sign(message);
+        Ed25519Signature { r: *signature.r_bytes(), s: *signature.s_bytes() }
+    }
+
+    /// 验证签名
+    ///
+    /// 使用 `verify_strict`，拒绝小阶公钥和非规范编码，
+    /// 因此签名不可延展，同一消息的签名不能被改写成另一个有效签名。
+    pub fn verify(public_key: &Ed25519PublicKey, message: &[u8], signature: &Ed25519Signature) -> bool {
+        let Ok(verifying_key) = VerifyingKey::from_bytes(&public_key.0) else {
+            return false;
+        };
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&signature.r);
+        bytes[32..].copy_from_slice(&signature.s);
+        verifying_key.verify_strict(message, &Signature::from_bytes(&bytes)).is_ok()
+    }
+}
+
+impl Drop for Ed25519SecretKey {
+    fn drop(&mut self) {
+        secure_zero(self.0.as_mut_ptr(), self.0.len());
+    }
+}
+
+impl fmt::Debug for Ed25519SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Ed25519SecretKey(..)")
+    }
+}
@@ -0,0 +1,245 @@
+//! 256 位素数域运算
+//!
+//! secp256k1 的基域和标量域、Curve25519 的基域共用这一后端。元素用 4 个小端 64 位字表示，
+//! 乘法采用 CIOS 蒙哥马利约减。所有运算的执行路径只取决于操作数长度，
+//! 不含依赖数值的分支或查表，可以直接处理私钥等秘密数据。
+
+/// 小端 64 位字表示的 256 位整数
+pub(super) type Limbs = [u64; 4];
+
+pub(super) const ZERO: Limbs = [0; 4];
+pub(super) const ONE: Limbs = [1, 0, 0, 0];
+
+/// 奇素数模数及其蒙哥马利常数
+#[derive(Debug)]
+pub(super) struct Modulus {
+    /// 模数 m
+    m: Limbs,
+    /// -m⁻¹ mod 2^64
+    m_inv: u64,
+    /// R² mod m，其中 R = 2^256
+    r2: Limbs,
+    /// m - 2，用于费马小定理求逆
+    inv_exponent: Limbs,
+}
+
+impl Modulus {
+    /// 预计算蒙哥马利常数，`m` 必须是大于 2^192 的奇素数
+    pub(super) const fn new(m: Limbs) -> Self {
+        // 牛顿迭代：每次迭代正确位数翻倍
+        let mut inv = 1u64;
+        let mut i = 0;
+        while i < 6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(m[0].wrapping_mul(inv)));
+            i += 1;
+        }
+
+        // 从 1 开始倍加 512 次得到 R² mod m
+        let mut r2 = ONE;
+        let mut i = 0;
+        while i < 512 {
+            let (doubled, carry) = add_limbs(&r2, &r2);
+            let (reduced, borrow) = sub_limbs(&doubled, &m);
+            r2 = if carry == 1 || borrow == 0 { reduced } else { doubled };
+            i += 1;
+        }
+
+        Self { m, m_inv: inv.wrapping_neg(), r2, inv_exponent: sub_limbs(&m, &[2, 0, 0, 0]).0 }
+    }
+
+    /// 模加，输入必须小于 m
+    pub(super) fn add(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let (sum, carry) = add_limbs(a, b);
+        self.subtract_if_not_below(&sum, carry)
+    }
+
+    /// 模减，输入必须小于 m
+    pub(super) fn sub(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let (difference, borrow) = sub_limbs(a, b);
+        let mask = 0u64.wrapping_sub(borrow);
+        let correction = [self.m[0] & mask, self.m[1] & mask, self.m[2] & mask, self.m[3] & mask];
+        add_limbs(&difference, &correction).0
+    }
+
+    /// 模取负，输入必须小于 m
+    pub(super) fn neg(&self, a: &Limbs) -> Limbs {
+        self.sub(&ZERO, a)
+    }
+
+    /// 蒙哥马利乘法 a·b·R⁻¹ mod m，要求 a·b < m·R
+    pub(super) fn mont_mul(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        let mut t = [0u64; 6];
+        for &b_i in b {
+            let mut carry = 0;
+            for j in 0..4 {
+                (t[j], carry) = mac(t[j], a[j], b_i, carry);
+            }
+            let (sum, overflow) = adc(t[4], carry, 0);
+            t[4] = sum;
+            t[5] = overflow;
+
+            let k = t[0].wrapping_mul(self.m_inv);
+            let (_, mut carry) = mac(t[0], k, self.m[0], 0);
+            for j in 1..4 {
+                (t[j - 1], carry) = mac(t[j], k, self.m[j], carry);
+            }
+            let (sum, overflow) = adc(t[4], carry, 0);
+            t[3] = sum;
+            t[4] = t[5] + overflow;
+        }
+        self.subtract_if_not_below(&[t[0], t[1], t[2], t[3]], t[4])
+    }
+
+    /// 转入蒙哥马利形式 a·R mod m，`a` 可以是任意 256 位整数
+    pub(super) fn to_mont(&self, a: &Limbs) -> Limbs {
+        self.mont_mul(a, &self.r2)
+    }
+
+    /// 从蒙哥马利形式转回规范形式
+    pub(super) fn to_canonical(&self, a: &Limbs) -> Limbs {
+        self.mont_mul(a, &ONE)
+    }
+
+    /// 把任意 256 位整数约简到 [0, m)
+    pub(super) fn reduce(&self, a: &Limbs) -> Limbs {
+        self.to_canonical(&self.to_mont(a))
+    }
+
+    /// 模乘，输入必须小于 m
+    pub(super) fn mul(&self, a: &Limbs, b: &Limbs) -> Limbs {
+        self.mont_mul(&self.mont_mul(a, b), &self.r2)
+    }
+
+    /// 模幂，每个指数位都执行一次乘法，执行时间与指数取值无关
+    pub(super) fn pow(&self, a: &Limbs, exponent: &Limbs) -> Limbs {
+        let base = self.to_mont(a);
+        let mut acc = self.to_mont(&ONE);
+        for bit in (0..256).rev() {
+            acc = self.mont_mul(&acc, &acc);
+            let product = self.mont_mul(&acc, &base);
+            acc = select(&acc, &product, (exponent[bit / 64] >> (bit % 64)) & 1);
+        }
+        self.to_canonical(&acc)
+    }
+
+    /// 模逆 a^(m-2)，零的逆记为零
+    pub(super) fn invert(&self, a: &Limbs) -> Limbs {
+        self.pow(a, &self.inv_exponent)
+    }
+
+    /// 在 value + high·2^256 不小于 m 时减去 m，要求 value + high·2^256 < 2m
+    fn subtract_if_not_below(&self, value: &Limbs, high: u64) -> Limbs {
+        let (reduced, borrow) = sub_limbs(value, &self.m);
+        let (_, below) = high.overflowing_sub(borrow);
+        select(&reduced, value, below as u64)
+    }
+}
+
+/// 带进位加法，返回 (和, 进位)
+const fn add_limbs(a: &Limbs, b: &Limbs) -> (Limbs, u64) {
+    let mut result = ZERO;
+    let mut carry = 0;
+    let mut i = 0;
+    while i < 4 {
+        (result[i], carry) = adc(a[i], b[i], carry);
+        i += 1;
+    }
+    (result, carry)
+}
+
+/// 带借位减法，返回 (差, 借位)
+pub(super) const fn sub_limbs(a: &Limbs, b: &Limbs) -> (Limbs, u64) {
+    let mut result = ZERO;
+    let mut borrow = 0;
+    let mut i = 0;
+    while i < 4 {
+        let wide = (a[i] as u128).wrapping_sub(b[i] as u128 + borrow as u128);
+        result[i] = wide as u64;
+        borrow = (wide >> 127) as u64;
+        i += 1;
+    }
+    (result, borrow)
+}
+
+const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let wide = a as u128 + b as u128 + carry as u128;
+    (wide as u64, (wide >> 64) as u64)
+}
+
+/// a + b·c + carry，返回 (低位, 高位)
+const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let wide = a as u128 + (b as u128) * (c as u128) + carry as u128;
+    (wide as u64, (wide >> 64) as u64)
+}
+
+/// `choice` 为 1 时返回 `b`，为 0 时返回 `a`
+pub(super) fn select(a: &Limbs, b: &Limbs, choice: u64) -> Limbs {
+    let mask = 0u64.wrapping_sub(choice);
+    [
+        a[0] ^ ((a[0] ^ b[0]) & mask),
+        a[1] ^ ((a[1] ^ b[1]) & mask),
+        a[2] ^ ((a[2] ^ b[2]) & mask),
+        a[3] ^ ((a[3] ^ b[3]) & mask),
+    ]
+}
+
+/// `choice` 为 1 时交换 `a` 和 `b`
+pub(super) fn conditional_swap(a: &mut Limbs, b: &mut Limbs, choice: u64) {
+    let mask = 0u64.wrapping_sub(choice);
+    for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+        let t = (*x ^ *y) & mask;
+        *x ^= t;
+        *y ^= t;
+    }
+}
+
+/// 常数时间判断相等
+pub(super) fn equal(a: &Limbs, b: &Limbs) -> bool {
+    a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// 常数时间判断是否为零
+pub(super) fn is_zero(a: &Limbs) -> bool {
+    equal(a, &ZERO)
+}
+
+/// a < b
+pub(super) fn less_than(a: &Limbs, b: &Limbs) -> bool {
+    sub_limbs(a, b).1 == 1
+}
+
+/// 按大端字节序读取
+pub(super) fn from_be_bytes(bytes: &[u8; 32]) -> Limbs {
+    let mut limbs = ZERO;
+    for (i, chunk) in bytes.chunks_exact(8).enumerate() {
+        limbs[3 - i] = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    limbs
+}
+
+/// 按大端字节序输出
+pub(super) fn to_be_bytes(limbs: &Limbs) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, chunk) in bytes.chunks_exact_mut(8).enumerate() {
+        chunk.copy_from_slice(&limbs[3 - i].to_be_bytes());
+    }
+    bytes
+}
+
+/// 按小端字节序读取
+pub(super) fn from_le_bytes(bytes: &[u8; 32]) -> Limbs {
+    let mut limbs = ZERO;
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    limbs
+}
+
+/// 按小端字节序输出
+pub(super) fn to_le_bytes(limbs: &Limbs) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(limbs) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    bytes
+}
@@ -5,8 +5,13 @@
 //! 
 //! ## 支持的椭圆曲线
 //! 
-//! - **Curve25519**: 高性能的蒙哥马利曲线，用于密钥交换
-//! - **secp256k1**: Bitcoin 使用的椭圆曲线，用于数字签名
+//! - **Curve25519**: 高性能的蒙哥马利曲线，用于密钥交换（RFC 7748 X25519）
+//! - **secp256k1**: Bitcoin 使用的椭圆曲线，用于数字签名和密钥交换
+//! - **SimpleEC**: 定义在 GF(97) 上的 79 阶教学曲线，与 u64 标量配合，
+//!   供承诺、生成元设置等协议演示使用，不提供任何安全性
+//! 
+//! Curve25519 和 secp256k1 都在各自真实的 256 位素数域上运算（常数时间的蒙哥马利乘法），
+//! 密钥、签名和共享密钥的编码与外部实现兼容。
 //! 
 //! ## 核心功能
 //! 
@@ -22,6 +27,13 @@
 //! ### 数字签名 (ECDSA)
 //! - 消息签名
 //! - 签名验证
+//!
+//! ### Ed25519 签名
+//! - 基于 `ed25519-dalek`，用于需要公开验证的证书和输出认证
+//! 
+//! ### Schnorr 签名与多重签名
+//! - secp256k1 上的 Schnorr 签名
+//! - MuSig2 风格的两轮多重签名：密钥聚合、随机数轮、部分签名组合
 //! 
 //! ## 数学基础
 //! 
@@ -33,13 +45,24 @@
 //! ```rust
 //! use mpc_api::elliptic_curve::*;
 //! 
-//! // ECDH 密钥交换
-//! let (alice_private, alice_public) = ECDH::generate_keypair()?;
-//! let (bob_private, bob_public) = ECDH::generate_keypair()?;
+//! # fn main() -> mpc_api::Result<()> {
+//! // secp256k1 上的 ECDH 密钥交换
+//! let (alice_private, alice_public) = Secp256k1Ecdh::generate_keypair()?;
+//! let (bob_private, bob_public) = Secp256k1Ecdh::generate_keypair()?;
 //! 
-//! let alice_shared = ECDH::compute_shared_secret(alice_private, &bob_public)?;
-//! let bob_shared = ECDH::compute_shared_secret(bob_private, &alice_public)?;
+//! let alice_shared = Secp256k1Ecdh::compute_shared_secret(alice_private, &bob_public)?;
+//! let bob_shared = Secp256k1Ecdh::compute_shared_secret(bob_private, &alice_public)?;
 //! assert_eq!(alice_shared, bob_shared);
+//! 
+//! // X25519 密钥交换
+//! let (alice_private, alice_public) = Curve25519ECDH::generate_keypair()?;
+//! let (bob_private, bob_public) = Curve25519ECDH::generate_keypair()?;
+//! assert_eq!(
+//!     Curve25519ECDH::compute_shared_secret(alice_private, &bob_public)?,
+//!     Curve25519ECDH::compute_shared_secret(bob_private, &alice_public)?,
+//! );
+//! # Ok(())
+//! # }
 //! ```
 
 pub mod curve25519;
@@ -48,13 +71,19 @@ pub mod point;
 pub mod scalar;
 pub mod ecdh;
 pub mod ecdsa;
+pub mod ed25519;
+pub mod schnorr;
+mod field256;
 
-// pub use curve25519::*; // Unused import
-// pub use secp256k1::*; // Unused import
+// curve25519 的 Scalar、Result 等名称与本模块冲突，只导出密钥交换入口
+pub use curve25519::Curve25519ECDH;
+pub use secp256k1::*;
+pub use schnorr::*;
 pub use point::*;
 pub use scalar::*;
 pub use ecdh::*;
 pub use ecdsa::*;
+pub use ed25519::*;
 
 use crate::{MpcError, Result};
 use serde::{Deserialize, Serialize};
@@ -138,13 +167,22 @@ pub struct ECParams {
 /// 椭圆曲线的通用特征
 /// 
 /// 定义了椭圆曲线必须实现的基本运算，包括点运算和曲线验证。
+/// 点、标量和参数的表示由各曲线决定：`SimpleEC` 使用 `ECPoint` / `u64` / `ECParams`，
+/// `Secp256k1` 使用 256 位的 `Secp256k1Point` / `Secp256k1Scalar` / `Secp256k1Params`。
 pub trait EllipticCurve {
+    /// 曲线上的点
+    type Point;
+    /// 标量
+    type Scalar;
+    /// 曲线参数
+    type Params;
+    
     /// 获取椭圆曲线的参数
     /// 
     /// # 返回值
     /// 
     /// 返回椭圆曲线的完整参数
-    fn params() -> ECParams;
+    fn params() -> Self::Params;
     
     /// 椭圆曲线点加法
     /// 
@@ -158,7 +196,7 @@ pub trait EllipticCurve {
     /// # 返回值
     /// 
     /// 返回两点之和，如果计算失败返回错误
-    fn point_add(p1: &ECPoint, p2: &ECPoint) -> Result<ECPoint>;
+    fn point_add(p1: &Self::Point, p2: &Self::Point) -> Result<Self::Point>;
     
     /// 椭圆曲线点倍乘
     /// 
@@ -171,7 +209,7 @@ pub trait EllipticCurve {
     /// # 返回值
     /// 
     /// 返回点的二倍，如果计算失败返回错误
-    fn point_double(point: &ECPoint) -> Result<ECPoint>;
+    fn point_double(point: &Self::Point) -> Result<Self::Point>;
     
     /// 椭圆曲线标量乘法
     /// 
@@ -185,7 +223,7 @@ pub trait EllipticCurve {
     /// # 返回值
     /// 
     /// 返回标量乘法的结果，如果计算失败返回错误
-    fn scalar_multiply(scalar: u64, point: &ECPoint) -> Result<ECPoint>;
+    fn scalar_multiply(scalar: Self::Scalar, point: &Self::Point) -> Result<Self::Point>;
     
     /// 验证点是否在椭圆曲线上
     /// 
@@ -198,7 +236,7 @@ pub trait EllipticCurve {
     /// # 返回值
     /// 
     /// 如果点在曲线上返回 `true`，否则返回 `false`
-    fn is_on_curve(point: &ECPoint) -> bool;
+    fn is_on_curve(point: &Self::Point) -> bool;
 }
 
 /// 椭圆曲线 Diffie-Hellman 密钥交换特征
@@ -206,6 +244,13 @@ pub trait EllipticCurve {
 /// 实现椭圆曲线上的 Diffie-Hellman 密钥交换协议，允许两方在不安全的
 /// 通道上建立共享密钥。
 pub trait ECDH {
+    /// 私钥类型
+    type PrivateKey;
+    /// 公钥类型
+    type PublicKey;
+    /// 共享密钥类型
+    type SharedSecret;
+    
     /// 生成 ECDH 密钥对
     /// 
     /// 生成一个随机私钥和对应的公钥。私钥是随机标量，
@@ -214,7 +259,7 @@ pub trait ECDH {
     /// # 返回值
     /// 
     /// 返回 (私钥, 公钥) 元组，如果生成失败返回错误
-    fn generate_keypair() -> Result<(u64, ECPoint)>;
+    fn generate_keypair() -> Result<(Self::PrivateKey, Self::PublicKey)>;
     
     /// 计算共享密钥
     /// 
@@ -228,8 +273,8 @@ pub trait ECDH {
     /// 
     /// # 返回值
     /// 
-    /// 返回共享密钥点，如果计算失败返回错误
-    fn compute_shared_secret(private_key: u64, public_key: &ECPoint) -> Result<ECPoint>;
+    /// 返回共享密钥，如果计算失败返回错误
+    fn compute_shared_secret(private_key: Self::PrivateKey, public_key: &Self::PublicKey) -> Result<Self::SharedSecret>;
 }
 
 /// 椭圆曲线数字签名算法特征
@@ -239,6 +284,12 @@ pub trait ECDH {
 pub trait ECDSA {
     /// 签名类型
     type Signature;
+    /// 私钥类型
+    type PrivateKey;
+    /// 公钥类型
+    type PublicKey;
+    /// 消息哈希类型
+    type MessageHash;
     
     /// 对消息哈希进行签名
     /// 
@@ -253,7 +304,7 @@ pub trait ECDSA {
     /// # 返回值
     /// 
     /// 返回数字签名，如果签名失败返回错误
-    fn sign(private_key: u64, message_hash: u64) -> Result<Self::Signature>;
+    fn sign(private_key: Self::PrivateKey, message_hash: Self::MessageHash) -> Result<Self::Signature>;
     
     /// 验证数字签名
     /// 
@@ -269,6 +320,6 @@ pub trait ECDSA {
     /// 
     /// 如果签名有效返回 `Ok(true)`，无效返回 `Ok(false)`，
     /// 验证过程出错返回 `Err`
-    fn verify(public_key: &ECPoint, message_hash: u64, signature: &Self::Signature) -> Result<bool>;
+    fn verify(public_key: &Self::PublicKey, message_hash: Self::MessageHash, signature: &Self::Signature) -> Result<bool>;
 }
 
@@ -1,6 +1,7 @@
 //! Elliptic curve point operations
 
 use super::*;
+use crate::utils::{constant_time_mod_inverse, constant_time_swap, hash_struct_with_domain};
 
 impl ECPoint {
     pub fn negate(&self) -> Self {
@@ -12,6 +13,16 @@ impl ECPoint {
     }
 }
 
+/// Swaps two points in constant time when `choice` is 1
+fn conditional_swap(p1: &mut ECPoint, p2: &mut ECPoint, choice: u64) {
+    constant_time_swap(&mut p1.x, &mut p2.x, choice);
+    constant_time_swap(&mut p1.y, &mut p2.y, choice);
+    let (mut infinity1, mut infinity2) = (p1.is_infinity as u64, p2.is_infinity as u64);
+    constant_time_swap(&mut infinity1, &mut infinity2, choice);
+    p1.is_infinity = infinity1 == 1;
+    p2.is_infinity = infinity2 == 1;
+}
+
 // Simplified elliptic curve for demonstration (y^2 = x^3 + 7 mod p)
 pub struct SimpleEC;
 
@@ -49,42 +60,109 @@ impl SimpleEC {
     }
     
     fn mod_inverse_with_prime(a: u64, prime: u64) -> Result<u64> {
-        // Extended Euclidean algorithm
-        let mut old_r = a as i128;
-        let mut r = prime as i128;
-        let mut old_s = 1i128;
-        let mut s = 0i128;
-        
-        while r != 0 {
-            let quotient = old_r / r;
-            let temp_r = r;
-            r = old_r - quotient * r;
-            old_r = temp_r;
-            
-            let temp_s = s;
-            s = old_s - quotient * s;
-            old_s = temp_s;
-        }
-        
-        if old_r == 1 {
-            let result = if old_s < 0 {
-                (old_s + prime as i128) as u64
-            } else {
-                old_s as u64
-            };
-            Ok(result)
-        } else {
-            Err(MpcError::CryptographicError("No modular inverse exists".to_string()))
-        }
+        // Fermat inversion a^(p-2): the operation sequence does not depend on a
+        constant_time_mod_inverse(a, prime)
+            .ok_or_else(|| MpcError::CryptographicError("No modular inverse exists".to_string()))
     }
 }
 
 impl SimpleEC {
     // Helper functions for elliptic curve operations have been removed
     // as we're using a simplified approach with hardcoded parameters
+    
+    /// 把数据哈希为曲线上的点（try-and-increment）
+    /// 
+    /// 依次尝试计数器 0, 1, 2, ...：由 `H(domain, data, counter)` 得到横坐标，
+    /// 方程右侧是二次剩余时取平方根作为纵坐标（两个根中按哈希的一个比特选择）。
+    /// 跳过无穷远点以及生成元 ±G，因此任何人都不知道结果相对 G 的离散对数，
+    /// 也可以按返回的计数器重新计算来验证。
+    /// 
+    /// # 参数
+    /// 
+    /// * `domain` - 域分隔标签
+    /// * `data` - 要哈希的数据
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 (点, 成功时的计数器)
+    pub fn hash_to_point(domain: &[u8], data: &[u8]) -> Result<(ECPoint, u32)> {
+        let params = Self::params();
+        for counter in 0..u32::MAX {
+            let digest = hash_struct_with_domain(domain, &(data, counter))?;
+            let x = u64::from_le_bytes(digest[..8].try_into().unwrap()) % params.p;
+            let rhs = Self::field_add(Self::field_mul(Self::field_mul(x, x), x), Self::B);
+            let root = match Self::mod_sqrt(rhs) {
+                Some(root) => root,
+                None => continue,
+            };
+            let y = if (root & 1) == (digest[8] & 1) as u64 { root } else { Self::field_sub(0, root) };
+            let point = ECPoint::new(x, y);
+            if point.is_infinity || point.x == params.g.x || !Self::is_on_curve(&point) {
+                continue;
+            }
+            return Ok((point, counter));
+        }
+        Err(MpcError::CryptographicError("Hash to curve did not terminate".to_string()))
+    }
+    
+    /// 模 p 平方根（Tonelli-Shanks），非二次剩余时返回 `None`
+    fn mod_sqrt(a: u64) -> Option<u64> {
+        let p = Self::params().p;
+        let pow = |mut base: u64, mut exp: u64| {
+            let mut result = 1u64;
+            while exp > 0 {
+                if exp & 1 == 1 {
+                    result = Self::field_mul(result, base);
+                }
+                base = Self::field_mul(base, base);
+                exp >>= 1;
+            }
+            result
+        };
+        
+        let a = a % p;
+        if a == 0 {
+            return Some(0);
+        }
+        if pow(a, (p - 1) / 2) != 1 {
+            return None;
+        }
+        
+        // p - 1 = q * 2^s，q 为奇数
+        let mut q = p - 1;
+        let mut s = 0;
+        while q % 2 == 0 {
+            q /= 2;
+            s += 1;
+        }
+        let non_residue = (2..p).find(|&z| pow(z, (p - 1) / 2) == p - 1)?;
+        
+        let mut m = s;
+        let mut c = pow(non_residue, q);
+        let mut t = pow(a, q);
+        let mut r = pow(a, q.div_ceil(2));
+        while t != 1 {
+            let mut i = 0;
+            let mut t_power = t;
+            while t_power != 1 {
+                t_power = Self::field_mul(t_power, t_power);
+                i += 1;
+            }
+            let b = pow(c, 1 << (m - i - 1));
+            m = i;
+            c = Self::field_mul(b, b);
+            t = Self::field_mul(t, c);
+            r = Self::field_mul(r, b);
+        }
+        Some(r)
+    }
 }
 
 impl EllipticCurve for SimpleEC {
+    type Point = ECPoint;
+    type Scalar = u64;
+    type Params = ECParams;
+    
     fn params() -> ECParams {
         // For testing, use a smaller prime to ensure elliptic curve operations work
         // In production, use proper curve parameters like secp256k1
@@ -169,28 +247,20 @@ impl EllipticCurve for SimpleEC {
     }
     
     fn scalar_multiply(scalar: u64, point: &ECPoint) -> Result<ECPoint> {
-        if scalar == 0 || point.is_infinity {
-            return Ok(ECPoint::infinity());
+        // Montgomery ladder: every bit of the order performs one addition and one doubling,
+        // so the operation sequence does not depend on the scalar. R1 - R0 = P throughout.
+        let n = Self::params().n;
+        let scalar = scalar % n;
+        let mut r0 = ECPoint::infinity();
+        let mut r1 = point.clone();
+        for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+            let choice = (scalar >> bit) & 1;
+            conditional_swap(&mut r0, &mut r1, choice);
+            r1 = Self::point_add(&r0, &r1)?;
+            r0 = Self::point_double(&r0)?;
+            conditional_swap(&mut r0, &mut r1, choice);
         }
-        
-        if scalar == 1 {
-            return Ok(point.clone());
-        }
-        
-        // Double-and-add algorithm
-        let mut result = ECPoint::infinity();
-        let mut addend = point.clone();
-        let mut k = scalar;
-        
-        while k > 0 {
-            if k % 2 == 1 {
-                result = Self::point_add(&result, &addend)?;
-            }
-            addend = Self::point_double(&addend)?;
-            k /= 2;
-        }
-        
-        Ok(result)
+        Ok(r0)
     }
     
     fn is_on_curve(point: &ECPoint) -> bool {
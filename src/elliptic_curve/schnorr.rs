@@ -0,0 +1,331 @@
+//! # Schnorr 签名与多重签名 (Schnorr Signatures and MuSig)
+//!
+//! secp256k1 上的 Schnorr 签名，以及 MuSig2 风格的两轮多重签名。
+//! Schnorr 签名方程对私钥和随机数都是线性的，多个签名者的部分签名直接相加
+//! 就得到聚合公钥下的普通 Schnorr 签名，比 ECDSA 更适合门限和 MPC 场景。
+//!
+//! ## 签名方案
+//!
+//! - 签名 (R, s)：R = k·G，c = H(R, P, m)，s = k + c·x
+//! - 验证：s·G = R + c·P
+//! - 随机数由私钥、消息和新鲜随机数共同哈希得到，随机数源失效时也不会重复
+//!
+//! ## 多重签名流程
+//!
+//! 1. **密钥聚合**: a_i = H(L, P_i)，其中 L 是全体公钥的哈希，聚合公钥 X = Σ a_i·P_i；
+//!    系数防止恶意签名者选择与他人公钥相关的公钥（rogue-key 攻击）
+//! 2. **随机数轮**: 每个签名者生成两个一次性随机数 r_i1、r_i2，公开 R_i1、R_i2
+//! 3. **部分签名轮**: R_1 = Σ R_i1，R_2 = Σ R_i2，b = H(X, R_1, R_2, m)，R = R_1 + b·R_2，
+//!    c = H(R, X, m)；签名者输出 s_i = r_i1 + b·r_i2 + c·a_i·x_i
+//! 4. **组合**: 逐一验证 s_i·G = R_i1 + b·R_i2 + c·a_i·P_i，s = Σ s_i
+//!
+//! 随机数轮不依赖消息，可以提前执行。每个签名者的秘密随机数只能使用一次，
+//! `MuSigSecretNonce` 在部分签名时被消耗。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::elliptic_curve::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let (private_key, public_key) = Schnorr::generate_keypair();
+//! let signature = Schnorr::sign(&private_key, b"hello")?;
+//! assert!(Schnorr::verify(&public_key, b"hello", &signature));
+//!
+//! // 三方多重签名，结果是聚合公钥下的普通 Schnorr 签名
+//! let keys: Vec<_> = (0..3).map(|_| Schnorr::generate_keypair()).collect();
+//! let private_keys: Vec<_> = keys.iter().map(|(private_key, _)| *private_key).collect();
+//! let (aggregate_key, signature) = run_musig(&private_keys, b"transfer 10")?;
+//! assert!(Schnorr::verify(&aggregate_key, b"transfer 10", &signature));
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{Secp256k1Ecdsa, Secp256k1Point, Secp256k1Scalar};
+use crate::{MpcError, Result};
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Schnorr 签名 (R, s)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchnorrSignature {
+    /// 随机数承诺 R = k·G
+    pub r: Secp256k1Point,
+    /// s = k + c·x
+    pub s: Secp256k1Scalar,
+}
+
+/// secp256k1 上的 Schnorr 签名
+pub struct Schnorr;
+
+impl Schnorr {
+    /// 生成密钥对
+    pub fn generate_keypair() -> (Secp256k1Scalar, Secp256k1Point) {
+        Secp256k1Ecdsa::generate_keypair()
+    }
+
+    /// 签名
+    ///
+    /// # 参数
+    /// - `private_key`: 签名私钥（非零）
+    /// - `message`: 消息
+    pub fn sign(private_key: &Secp256k1Scalar, message: &[u8]) -> Result<SchnorrSignature> {
+        if private_key.is_zero() {
+            return Err(MpcError::CryptographicError("Private key must be non-zero".to_string()));
+        }
+        let public_key = Secp256k1Point::generator() * *private_key;
+        let k = derive_nonce(private_key, message);
+        let r = Secp256k1Point::generator() * k;
+        let c = challenge(&r, &public_key, message);
+        Ok(SchnorrSignature { r, s: k + c * *private_key })
+    }
+
+    /// 验证签名
+    pub fn verify(public_key: &Secp256k1Point, message: &[u8], signature: &SchnorrSignature) -> bool {
+        if public_key.is_identity() {
+            return false;
+        }
+        let c = challenge(&signature.r, public_key, message);
+        Secp256k1Point::generator() * signature.s == signature.r + *public_key * c
+    }
+}
+
+/// 聚合公钥及各签名者的聚合系数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyAggregation {
+    /// 全体签名者的公钥，顺序即签名者编号
+    pub public_keys: Vec<Secp256k1Point>,
+    /// 聚合系数 a_i
+    pub coefficients: Vec<Secp256k1Scalar>,
+    /// 聚合公钥 X = Σ a_i·P_i
+    pub aggregate_key: Secp256k1Point,
+}
+
+impl KeyAggregation {
+    /// 聚合公钥
+    ///
+    /// # 返回值
+    /// 公钥列表为空、包含无穷远点或聚合结果为无穷远点时返回错误
+    pub fn new(public_keys: &[Secp256k1Point]) -> Result<Self> {
+        if public_keys.is_empty() || public_keys.iter().any(Secp256k1Point::is_identity) {
+            return Err(MpcError::CryptographicError("MuSig needs non-identity public keys".to_string()));
+        }
+        let encoded: Vec<Vec<u8>> = public_keys.iter().map(|key| key.to_sec1(true)).collect();
+        let list_hash = Sha256::digest(encoded.concat());
+        let coefficients: Vec<Secp256k1Scalar> = encoded.iter()
+            .map(|key| hash_to_scalar(b"mpc_api/musig/coefficient", &[&list_hash, key]))
+            .collect();
+        let aggregate_key = public_keys.iter()
+            .zip(&coefficients)
+            .fold(Secp256k1Point::identity(), |acc, (&key, &a)| acc + key * a);
+        if aggregate_key.is_identity() {
+            return Err(MpcError::CryptographicError("Aggregate key is the identity".to_string()));
+        }
+        Ok(Self { public_keys: public_keys.to_vec(), coefficients, aggregate_key })
+    }
+
+    /// 签名者数量
+    pub fn signer_count(&self) -> usize {
+        self.public_keys.len()
+    }
+
+    /// 汇总第 1 轮的公开随机数，得到本次签名的公共参数
+    ///
+    /// # 参数
+    /// - `nonces`: 按签名者编号排列的公开随机数
+    /// - `message`: 要签名的消息
+    pub fn session(&self, nonces: &[MuSigPublicNonce], message: &[u8]) -> Result<MuSigSession> {
+        if nonces.len() != self.signer_count() {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} nonces, got {}", self.signer_count(), nonces.len()
+            )));
+        }
+        let (first, second) = nonces.iter().fold(
+            (Secp256k1Point::identity(), Secp256k1Point::identity()),
+            |(first, second), nonce| (first + nonce.first, second + nonce.second),
+        );
+        let b = hash_to_scalar(b"mpc_api/musig/nonce", &[
+            &self.aggregate_key.to_sec1(true), &first.to_sec1(true), &second.to_sec1(true), message,
+        ]);
+        let r = first + second * b;
+        Ok(MuSigSession { r, b, c: challenge(&r, &self.aggregate_key, message) })
+    }
+
+    /// 验证部分签名并组合为 Schnorr 签名
+    ///
+    /// # 参数
+    /// - `nonces`: 按签名者编号排列的公开随机数
+    /// - `message`: 要签名的消息
+    /// - `partials`: 全体签名者的部分签名（顺序不限）
+    ///
+    /// # 返回值
+    /// 缺少部分签名或某个部分签名无效时返回错误，并指出对应的签名者
+    pub fn combine(&self, nonces: &[MuSigPublicNonce], message: &[u8], partials: &[MuSigPartialSignature]) -> Result<SchnorrSignature> {
+        let session = self.session(nonces, message)?;
+        let mut seen = vec![false; self.signer_count()];
+        let mut s = Secp256k1Scalar::ZERO;
+        for partial in partials {
+            match seen.get_mut(partial.signer) {
+                Some(seen) if !*seen => *seen = true,
+                _ => return Err(MpcError::ProtocolError(format!("Unexpected partial signature from signer {}", partial.signer))),
+            }
+            let expected = nonces[partial.signer].first + nonces[partial.signer].second * session.b
+                + self.public_keys[partial.signer] * (session.c * self.coefficients[partial.signer]);
+            if Secp256k1Point::generator() * partial.s != expected {
+                return Err(MpcError::AuthenticationError(format!("Invalid partial signature from signer {}", partial.signer)));
+            }
+            s = s + partial.s;
+        }
+        if seen.contains(&false) {
+            return Err(MpcError::InsufficientShares);
+        }
+        Ok(SchnorrSignature { r: session.r, s })
+    }
+}
+
+/// 第 1 轮消息：签名者的两个公开随机数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MuSigPublicNonce {
+    /// R_i1 = r_i1·G
+    pub first: Secp256k1Point,
+    /// R_i2 = r_i2·G
+    pub second: Secp256k1Point,
+}
+
+/// 签名者保存的一次性秘密随机数，部分签名时被消耗
+#[derive(Debug)]
+pub struct MuSigSecretNonce {
+    first: Secp256k1Scalar,
+    second: Secp256k1Scalar,
+}
+
+/// 一次签名的公共参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MuSigSession {
+    /// 聚合随机数 R = R_1 + b·R_2
+    pub r: Secp256k1Point,
+    /// 随机数系数 b
+    pub b: Secp256k1Scalar,
+    /// 签名挑战 c
+    pub c: Secp256k1Scalar,
+}
+
+/// 第 2 轮消息：部分签名
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MuSigPartialSignature {
+    /// 签名者编号
+    pub signer: usize,
+    /// s_i = r_i1 + b·r_i2 + c·a_i·x_i
+    pub s: Secp256k1Scalar,
+}
+
+/// 多重签名中的一个签名者
+#[derive(Debug, Clone)]
+pub struct MuSigSigner {
+    private_key: Secp256k1Scalar,
+    index: usize,
+    aggregation: KeyAggregation,
+}
+
+impl MuSigSigner {
+    /// 创建签名者
+    ///
+    /// # 参数
+    /// - `private_key`: 本方私钥
+    /// - `aggregation`: 全体签名者的密钥聚合结果，必须包含本方公钥
+    pub fn new(private_key: Secp256k1Scalar, aggregation: &KeyAggregation) -> Result<Self> {
+        let public_key = Secp256k1Point::generator() * private_key;
+        let index = aggregation.public_keys.iter().position(|key| *key == public_key)
+            .ok_or_else(|| MpcError::ProtocolError("Signer's public key is not in the key aggregation".to_string()))?;
+        Ok(Self { private_key, index, aggregation: aggregation.clone() })
+    }
+
+    /// 本方的签名者编号
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// 第 1 轮：生成一次性随机数
+    pub fn commit_nonce(&self) -> (MuSigSecretNonce, MuSigPublicNonce) {
+        let secret = MuSigSecretNonce { first: Secp256k1Scalar::random(), second: Secp256k1Scalar::random() };
+        let public = MuSigPublicNonce {
+            first: Secp256k1Point::generator() * secret.first,
+            second: Secp256k1Point::generator() * secret.second,
+        };
+        (secret, public)
+    }
+
+    /// 第 2 轮：生成部分签名，消耗本方的秘密随机数
+    ///
+    /// # 参数
+    /// - `secret_nonce`: 第 1 轮生成的秘密随机数
+    /// - `nonces`: 按签名者编号排列的全体公开随机数
+    /// - `message`: 要签名的消息
+    pub fn partial_sign(&self, secret_nonce: MuSigSecretNonce, nonces: &[MuSigPublicNonce], message: &[u8]) -> Result<MuSigPartialSignature> {
+        let own = MuSigPublicNonce {
+            first: Secp256k1Point::generator() * secret_nonce.first,
+            second: Secp256k1Point::generator() * secret_nonce.second,
+        };
+        if nonces.get(self.index) != Some(&own) {
+            return Err(MpcError::ProtocolError("Nonce list does not contain this signer's nonce".to_string()));
+        }
+        let session = self.aggregation.session(nonces, message)?;
+        let s = secret_nonce.first + session.b * secret_nonce.second
+            + session.c * self.aggregation.coefficients[self.index] * self.private_key;
+        Ok(MuSigPartialSignature { signer: self.index, s })
+    }
+}
+
+/// 在本地模拟全体签名者执行多重签名
+///
+/// # 返回值
+/// 返回 (聚合公钥, 签名)
+pub fn run_musig(private_keys: &[Secp256k1Scalar], message: &[u8]) -> Result<(Secp256k1Point, SchnorrSignature)> {
+    let public_keys: Vec<Secp256k1Point> = private_keys.iter().map(|&key| Secp256k1Point::generator() * key).collect();
+    let aggregation = KeyAggregation::new(&public_keys)?;
+    let signers = private_keys.iter()
+        .map(|&key| MuSigSigner::new(key, &aggregation))
+        .collect::<Result<Vec<_>>>()?;
+
+    // 第 1 轮：交换公开随机数
+    let (secrets, nonces): (Vec<_>, Vec<_>) = signers.iter().map(MuSigSigner::commit_nonce).unzip();
+
+    // 第 2 轮：交换部分签名
+    let partials = signers.iter()
+        .zip(secrets)
+        .map(|(signer, secret)| signer.partial_sign(secret, &nonces, message))
+        .collect::<Result<Vec<_>>>()?;
+
+    let signature = aggregation.combine(&nonces, message, &partials)?;
+    Ok((aggregation.aggregate_key, signature))
+}
+
+/// 签名挑战 c = H(R, P, m)
+fn challenge(r: &Secp256k1Point, public_key: &Secp256k1Point, message: &[u8]) -> Secp256k1Scalar {
+    hash_to_scalar(b"mpc_api/schnorr/challenge", &[&r.to_sec1(true), &public_key.to_sec1(true), message])
+}
+
+/// 随机数 k = H(x, m, 新鲜随机数)，随机数源失效时退化为确定性随机数
+fn derive_nonce(private_key: &Secp256k1Scalar, message: &[u8]) -> Secp256k1Scalar {
+    let mut fresh = [0u8; 32];
+    thread_rng().fill_bytes(&mut fresh);
+    loop {
+        let k = hash_to_scalar(b"mpc_api/schnorr/nonce", &[&private_key.to_bytes(), message, &fresh]);
+        if !k.is_zero() {
+            return k;
+        }
+        fresh = Sha256::digest(fresh).into();
+    }
+}
+
+/// 带域分隔的哈希，结果约简到模 n
+fn hash_to_scalar(domain: &[u8], parts: &[&[u8]]) -> Secp256k1Scalar {
+    let mut hasher = Sha256::new();
+    hasher.update(domain);
+    for part in parts {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    Secp256k1Scalar::from_bytes_reduced(&hasher.finalize().into())
+}
@@ -1,4 +1,620 @@
-//! secp256k1 curve implementation (placeholder)
+//! # secp256k1 椭圆曲线 (secp256k1)
+//!
+//! Bitcoin 使用的 Koblitz 曲线 y² = x³ + 7，定义在 p = 2²⁵⁶ - 2³² - 977 的素数域上，
+//! 群的阶 n 为 256 位素数。本模块在真实的 256 位基域和标量域上运算，
+//! 点和签名的编码与 SEC 1 / RFC 6979 一致，可以与外部实现互通。
+//!
+//! ## 实现要点
+//!
+//! - **域运算**: 4 个 64 位字的蒙哥马利乘法，常数时间
+//! - **点运算**: 射影坐标下 Renes–Costello–Batina 完全加法公式，无需区分倍点和无穷远点
+//! - **标量乘法**: 每一位都执行一次倍点和一次加法并按位选择结果，执行时间与标量取值无关
+//! - **ECDSA**: RFC 6979 确定性随机数，输出 low-S 形式的签名；验证同时接受两种形式
+//! - **ECDH**: 共享密钥为共享点的 x 坐标（SEC 1 ECDH 原语）
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::elliptic_curve::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let (private_key, public_key) = Secp256k1Ecdsa::generate_keypair();
+//! let signature = Secp256k1Ecdsa::sign_message(b"hello", &private_key)?;
+//! assert!(Secp256k1Ecdsa::verify_message(b"hello", &public_key, &signature)?);
+//!
+//! // 压缩公钥可以与其他 secp256k1 实现交换
+//! let encoded = public_key.to_sec1(true);
+//! assert_eq!(Secp256k1Point::from_sec1(&encoded)?, public_key);
+//! # Ok(())
+//! # }
+//! ```
 
-// Placeholder for secp256k1 implementation
-// In practice, you would use a proper cryptographic library
\ No newline at end of file
+use super::field256::{self, Limbs, Modulus, ONE, ZERO};
+use super::{EllipticCurve, ECDH, ECDSA};
+use crate::authentication::HMAC;
+use crate::{MpcError, Result};
+use rand::{thread_rng, RngCore};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// 基域模数 p = 2²⁵⁶ - 2³² - 977
+const P: Limbs = [0xFFFFFFFEFFFFFC2F, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF];
+/// 群的阶 n
+const N: Limbs = [0xBFD25E8CD0364141, 0xBAAEDCE6AF48A03B, 0xFFFFFFFFFFFFFFFE, 0xFFFFFFFFFFFFFFFF];
+/// ⌊n / 2⌋，大于它的 s 不是 low-S 形式
+const HALF_N: Limbs = [0xDFE92F46681B20A0, 0x5D576E7357A4501D, 0xFFFFFFFFFFFFFFFF, 0x7FFFFFFFFFFFFFFF];
+/// (p + 1) / 4，p ≡ 3 (mod 4) 时 a^((p+1)/4) 是 a 的平方根
+const SQRT_EXPONENT: Limbs = [0xFFFFFFFFBFFFFF0C, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0x3FFFFFFFFFFFFFFF];
+/// 生成元 G 的坐标
+const GX: Limbs = [0x59F2815B16F81798, 0x029BFCDB2DCE28D9, 0x55A06295CE870B07, 0x79BE667EF9DCBBAC];
+const GY: Limbs = [0x9C47D08FFB10D4B8, 0xFD17B448A6855419, 0x5DA4FBFC0E1108A8, 0x483ADA7726A3C465];
+/// 曲线参数 b
+const B: u64 = 7;
+
+const FIELD: Modulus = Modulus::new(P);
+const ORDER: Modulus = Modulus::new(N);
+
+/// 曲线方程右侧 x³ + 7（规范形式）
+fn curve_rhs(x: &Limbs) -> Limbs {
+    FIELD.add(&FIELD.mul(&FIELD.mul(x, x), x), &[B, 0, 0, 0])
+}
+
+/// 规范形式仿射坐标的 SEC 1 编码
+fn encode_sec1(x: &Limbs, y: &Limbs, compressed: bool) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(65);
+    if compressed {
+        bytes.push(0x02 | (y[0] & 1) as u8);
+        bytes.extend_from_slice(&field256::to_be_bytes(x));
+    } else {
+        bytes.push(0x04);
+        bytes.extend_from_slice(&field256::to_be_bytes(x));
+        bytes.extend_from_slice(&field256::to_be_bytes(y));
+    }
+    bytes
+}
+
+/// 蒙哥马利形式下乘以 3b = 21
+fn times_b3(a: &Limbs) -> Limbs {
+    let a2 = FIELD.add(a, a);
+    let a4 = FIELD.add(&a2, &a2);
+    let a16 = FIELD.add(&FIELD.add(&a4, &a4), &FIELD.add(&a4, &a4));
+    FIELD.add(&FIELD.add(&a16, &a4), a)
+}
+
+/// secp256k1 标量，模 n 的规范表示
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256k1Scalar(Limbs);
+
+impl Secp256k1Scalar {
+    /// 零
+    pub const ZERO: Self = Self(ZERO);
+    /// 一
+    pub const ONE: Self = Self(ONE);
+
+    /// 由小整数创建标量
+    pub fn from_u64(value: u64) -> Self {
+        Self([value, 0, 0, 0])
+    }
+
+    /// 从 32 字节大端编码解析，不小于 n 时返回 `None`
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let limbs = field256::from_be_bytes(bytes);
+        field256::less_than(&limbs, &N).then_some(Self(limbs))
+    }
+
+    /// 把 32 字节大端整数约简到模 n（用于消息哈希）
+    pub fn from_bytes_reduced(bytes: &[u8; 32]) -> Self {
+        Self(ORDER.reduce(&field256::from_be_bytes(bytes)))
+    }
+
+    /// 32 字节大端编码
+    pub fn to_bytes(&self) -> [u8; 32] {
+        field256::to_be_bytes(&self.0)
+    }
+
+    /// 均匀随机的非零标量
+    pub fn random() -> Self {
+        let mut rng = thread_rng();
+        loop {
+            let mut bytes = [0u8; 32];
+            rng.fill_bytes(&mut bytes);
+            if let Some(scalar) = Self::from_bytes(&bytes).filter(|scalar| !scalar.is_zero()) {
+                return scalar;
+            }
+        }
+    }
+
+    /// 是否为零
+    pub fn is_zero(&self) -> bool {
+        field256::is_zero(&self.0)
+    }
+
+    /// 模逆，零没有逆元
+    pub fn invert(&self) -> Option<Self> {
+        (!self.is_zero()).then(|| Self(ORDER.invert(&self.0)))
+    }
+
+    /// 是否大于 n / 2
+    pub fn is_high(&self) -> bool {
+        field256::less_than(&HALF_N, &self.0)
+    }
+}
+
+impl Add for Secp256k1Scalar {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(ORDER.add(&self.0, &other.0))
+    }
+}
+
+impl Sub for Secp256k1Scalar {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(ORDER.sub(&self.0, &other.0))
+    }
+}
+
+impl Mul for Secp256k1Scalar {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(ORDER.mul(&self.0, &other.0))
+    }
+}
+
+impl Neg for Secp256k1Scalar {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(ORDER.neg(&self.0))
+    }
+}
+
+impl Serialize for Secp256k1Scalar {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.to_bytes().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Secp256k1Scalar {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let bytes = <[u8; 32]>::deserialize(deserializer)?;
+        Self::from_bytes(&bytes).ok_or_else(|| D::Error::custom("secp256k1 scalar is not below the group order"))
+    }
+}
+
+/// secp256k1 上的点，射影坐标 (X : Y : Z)，坐标以蒙哥马利形式保存
+///
+/// 无穷远点为 (0 : 1 : 0)。序列化为 SEC 1 压缩编码。
+#[derive(Debug, Clone, Copy)]
+pub struct Secp256k1Point {
+    x: Limbs,
+    y: Limbs,
+    z: Limbs,
+}
+
+impl Secp256k1Point {
+    /// 无穷远点（群的单位元）
+    pub fn identity() -> Self {
+        Self { x: ZERO, y: FIELD.to_mont(&ONE), z: ZERO }
+    }
+
+    /// 生成元 G
+    pub fn generator() -> Self {
+        Self { x: FIELD.to_mont(&GX), y: FIELD.to_mont(&GY), z: FIELD.to_mont(&ONE) }
+    }
+
+    /// 是否为无穷远点
+    pub fn is_identity(&self) -> bool {
+        field256::is_zero(&self.z)
+    }
+
+    /// 由 32 字节大端仿射坐标创建点
+    ///
+    /// # 返回值
+    /// 坐标不小于 p 或点不在曲线上时返回错误
+    pub fn from_affine(x: &[u8; 32], y: &[u8; 32]) -> Result<Self> {
+        let (x, y) = (field256::from_be_bytes(x), field256::from_be_bytes(y));
+        if !field256::less_than(&x, &P) || !field256::less_than(&y, &P)
+            || !field256::equal(&FIELD.mul(&y, &y), &curve_rhs(&x))
+        {
+            return Err(MpcError::CryptographicError("Point is not on secp256k1".to_string()));
+        }
+        Ok(Self { x: FIELD.to_mont(&x), y: FIELD.to_mont(&y), z: FIELD.to_mont(&ONE) })
+    }
+
+    /// 32 字节大端仿射坐标，无穷远点返回 `None`
+    pub fn to_affine(&self) -> Option<([u8; 32], [u8; 32])> {
+        let (x, y) = self.affine_limbs()?;
+        Some((field256::to_be_bytes(&x), field256::to_be_bytes(&y)))
+    }
+
+    /// 解析 SEC 1 编码：`0x00`（无穷远点）、33 字节压缩或 65 字节未压缩格式
+    pub fn from_sec1(bytes: &[u8]) -> Result<Self> {
+        let invalid = || MpcError::CryptographicError("Invalid SEC1 point encoding".to_string());
+        match (bytes.first(), bytes.len()) {
+            (Some(0x00), 1) => Ok(Self::identity()),
+            (Some(0x04), 65) => Self::from_affine(bytes[1..33].try_into().unwrap(), bytes[33..].try_into().unwrap()),
+            (Some(&tag @ (0x02 | 0x03)), 33) => {
+                let x = field256::from_be_bytes(bytes[1..].try_into().unwrap());
+                if !field256::less_than(&x, &P) {
+                    return Err(invalid());
+                }
+                let rhs = curve_rhs(&x);
+                let mut y = FIELD.pow(&rhs, &SQRT_EXPONENT);
+                if !field256::equal(&FIELD.mul(&y, &y), &rhs) {
+                    return Err(invalid());
+                }
+                if (y[0] & 1) != (tag & 1) as u64 {
+                    y = FIELD.neg(&y);
+                }
+                Ok(Self { x: FIELD.to_mont(&x), y: FIELD.to_mont(&y), z: FIELD.to_mont(&ONE) })
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// SEC 1 编码
+    ///
+    /// # 参数
+    /// - `compressed`: 为 `true` 时输出 33 字节压缩格式，否则输出 65 字节未压缩格式
+    pub fn to_sec1(&self, compressed: bool) -> Vec<u8> {
+        match self.affine_limbs() {
+            Some((x, y)) => encode_sec1(&x, &y, compressed),
+            None => vec![0x00],
+        }
+    }
+
+    /// 批量 SEC 1 编码，与逐个调用 `to_sec1` 结果相同
+    ///
+    /// 转换仿射坐标需要对 Z 求逆，这里用 Montgomery 技巧让所有点共用一次求逆，
+    /// 适合离散对数查表等需要编码大量点的场合。
+    pub fn batch_to_sec1(points: &[Self], compressed: bool) -> Vec<Vec<u8>> {
+        let one = FIELD.to_mont(&ONE);
+        // 无穷远点的 Z 为零，用 1 代替以免整个乘积为零
+        let zs: Vec<Limbs> = points.iter()
+            .map(|point| field256::select(&point.z, &one, point.is_identity() as u64))
+            .collect();
+        let mut prefix = Vec::with_capacity(zs.len());
+        let mut acc = one;
+        for z in &zs {
+            prefix.push(acc);
+            acc = FIELD.mont_mul(&acc, z);
+        }
+
+        let mut inverse = FIELD.to_mont(&FIELD.invert(&FIELD.to_canonical(&acc)));
+        let mut encodings = vec![Vec::new(); points.len()];
+        for (i, point) in points.iter().enumerate().rev() {
+            // inverse = (z_0 ⋯ z_i)⁻¹，乘以 z_0 ⋯ z_(i-1) 得到 z_i⁻¹
+            let z_inv = FIELD.mont_mul(&inverse, &prefix[i]);
+            inverse = FIELD.mont_mul(&inverse, &zs[i]);
+            encodings[i] = if point.is_identity() {
+                vec![0x00]
+            } else {
+                let x = FIELD.to_canonical(&FIELD.mont_mul(&point.x, &z_inv));
+                let y = FIELD.to_canonical(&FIELD.mont_mul(&point.y, &z_inv));
+                encode_sec1(&x, &y, compressed)
+            };
+        }
+        encodings
+    }
+
+    /// 规范形式的仿射坐标
+    fn affine_limbs(&self) -> Option<(Limbs, Limbs)> {
+        if self.is_identity() {
+            return None;
+        }
+        let z_inv = FIELD.invert(&FIELD.to_canonical(&self.z));
+        Some((
+            FIELD.mul(&FIELD.to_canonical(&self.x), &z_inv),
+            FIELD.mul(&FIELD.to_canonical(&self.y), &z_inv),
+        ))
+    }
+
+    /// 完全加法公式（Renes–Costello–Batina 2016，算法 7，a = 0）
+    fn add_point(&self, other: &Self) -> Self {
+        let (x1, y1, z1) = (&self.x, &self.y, &self.z);
+        let (x2, y2, z2) = (&other.x, &other.y, &other.z);
+        let mul = |a: &Limbs, b: &Limbs| FIELD.mont_mul(a, b);
+        let add = |a: &Limbs, b: &Limbs| FIELD.add(a, b);
+        let sub = |a: &Limbs, b: &Limbs| FIELD.sub(a, b);
+
+        let mut t0 = mul(x1, x2);
+        let mut t1 = mul(y1, y2);
+        let mut t2 = mul(z1, z2);
+        let mut t3 = mul(&add(x1, y1), &add(x2, y2));
+        let mut t4 = add(&t0, &t1);
+        t3 = sub(&t3, &t4);
+        t4 = mul(&add(y1, z1), &add(y2, z2));
+        t4 = sub(&t4, &add(&t1, &t2));
+        let mut y3 = mul(&add(x1, z1), &add(x2, z2));
+        y3 = sub(&y3, &add(&t0, &t2));
+        t0 = add(&add(&t0, &t0), &t0);
+        t2 = times_b3(&t2);
+        let mut z3 = add(&t1, &t2);
+        t1 = sub(&t1, &t2);
+        y3 = times_b3(&y3);
+        let mut x3 = mul(&t4, &y3);
+        x3 = sub(&mul(&t3, &t1), &x3);
+        y3 = add(&mul(&t1, &z3), &mul(&y3, &t0));
+        z3 = add(&mul(&z3, &t4), &mul(&t0, &t3));
+
+        Self { x: x3, y: y3, z: z3 }
+    }
+
+    /// 常数时间标量乘法
+    fn multiply(&self, scalar: &Secp256k1Scalar) -> Self {
+        let mut acc = Self::identity();
+        for bit in (0..256).rev() {
+            acc = acc.add_point(&acc);
+            let sum = acc.add_point(self);
+            let choice = (scalar.0[bit / 64] >> (bit % 64)) & 1;
+            acc = Self {
+                x: field256::select(&acc.x, &sum.x, choice),
+                y: field256::select(&acc.y, &sum.y, choice),
+                z: field256::select(&acc.z, &sum.z, choice),
+            };
+        }
+        acc
+    }
+}
+
+impl PartialEq for Secp256k1Point {
+    fn eq(&self, other: &Self) -> bool {
+        // (X1 : Y1 : Z1) = (X2 : Y2 : Z2) 当且仅当 X1·Z2 = X2·Z1 且 Y1·Z2 = Y2·Z1
+        field256::equal(&FIELD.mont_mul(&self.x, &other.z), &FIELD.mont_mul(&other.x, &self.z))
+            && field256::equal(&FIELD.mont_mul(&self.y, &other.z), &FIELD.mont_mul(&other.y, &self.z))
+    }
+}
+
+impl Eq for Secp256k1Point {}
+
+impl Add for Secp256k1Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.add_point(&other)
+    }
+}
+
+impl Neg for Secp256k1Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self { x: self.x, y: FIELD.neg(&self.y), z: self.z }
+    }
+}
+
+impl Mul<Secp256k1Scalar> for Secp256k1Point {
+    type Output = Self;
+
+    fn mul(self, scalar: Secp256k1Scalar) -> Self {
+        self.multiply(&scalar)
+    }
+}
+
+impl Serialize for Secp256k1Point {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_sec1(true))
+    }
+}
+
+impl<'de> Deserialize<'de> for Secp256k1Point {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let bytes = <Vec<u8>>::deserialize(deserializer)?;
+        Self::from_sec1(&bytes).map_err(D::Error::custom)
+    }
+}
+
+/// secp256k1 曲线参数（大端编码）
+#[derive(Debug, Clone, PartialEq)]
+pub struct Secp256k1Params {
+    /// 基域模数 p
+    pub p: [u8; 32],
+    /// 群的阶 n
+    pub n: [u8; 32],
+    /// 曲线参数 a
+    pub a: u64,
+    /// 曲线参数 b
+    pub b: u64,
+    /// 生成元 G
+    pub g: Secp256k1Point,
+}
+
+/// secp256k1 曲线
+pub struct Secp256k1;
+
+impl EllipticCurve for Secp256k1 {
+    type Point = Secp256k1Point;
+    type Scalar = Secp256k1Scalar;
+    type Params = Secp256k1Params;
+
+    fn params() -> Secp256k1Params {
+        Secp256k1Params {
+            p: field256::to_be_bytes(&P),
+            n: field256::to_be_bytes(&N),
+            a: 0,
+            b: B,
+            g: Secp256k1Point::generator(),
+        }
+    }
+
+    fn point_add(p1: &Secp256k1Point, p2: &Secp256k1Point) -> Result<Secp256k1Point> {
+        Ok(p1.add_point(p2))
+    }
+
+    fn point_double(point: &Secp256k1Point) -> Result<Secp256k1Point> {
+        Ok(point.add_point(point))
+    }
+
+    fn scalar_multiply(scalar: Secp256k1Scalar, point: &Secp256k1Point) -> Result<Secp256k1Point> {
+        Ok(point.multiply(&scalar))
+    }
+
+    fn is_on_curve(point: &Secp256k1Point) -> bool {
+        // 射影方程 Y²·Z = X³ + 7·Z³
+        let mul = |a: &Limbs, b: &Limbs| FIELD.mont_mul(a, b);
+        let lhs = mul(&mul(&point.y, &point.y), &point.z);
+        let z3 = mul(&mul(&point.z, &point.z), &point.z);
+        let z3_2 = FIELD.add(&z3, &z3);
+        let seven_z3 = FIELD.add(&FIELD.add(&FIELD.add(&z3_2, &z3_2), &z3_2), &z3);
+        let rhs = FIELD.add(&mul(&mul(&point.x, &point.x), &point.x), &seven_z3);
+        field256::equal(&lhs, &rhs)
+    }
+}
+
+/// secp256k1 ECDSA 签名 (r, s)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Secp256k1Signature {
+    /// r = (k·G).x mod n
+    pub r: Secp256k1Scalar,
+    /// s = k⁻¹ (z + r·d) mod n
+    pub s: Secp256k1Scalar,
+}
+
+impl Secp256k1Signature {
+    /// 64 字节紧凑编码 r || s
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.r.to_bytes());
+        bytes[32..].copy_from_slice(&self.s.to_bytes());
+        bytes
+    }
+
+    /// 解析 64 字节紧凑编码
+    pub fn from_bytes(bytes: &[u8; 64]) -> Result<Self> {
+        let parse = |half: &[u8]| Secp256k1Scalar::from_bytes(half.try_into().unwrap())
+            .ok_or_else(|| MpcError::CryptographicError("Signature component is not below the group order".to_string()));
+        Ok(Self { r: parse(&bytes[..32])?, s: parse(&bytes[32..])? })
+    }
+}
+
+/// secp256k1 上的 ECDSA
+pub struct Secp256k1Ecdsa;
+
+impl Secp256k1Ecdsa {
+    /// 生成密钥对
+    pub fn generate_keypair() -> (Secp256k1Scalar, Secp256k1Point) {
+        let private_key = Secp256k1Scalar::random();
+        (private_key, Secp256k1Point::generator() * private_key)
+    }
+
+    /// 用 SHA-256 哈希消息后签名
+    pub fn sign_message(message: &[u8], private_key: &Secp256k1Scalar) -> Result<Secp256k1Signature> {
+        Self::sign(*private_key, Sha256::digest(message).into())
+    }
+
+    /// 用 SHA-256 哈希消息后验证签名
+    pub fn verify_message(message: &[u8], public_key: &Secp256k1Point, signature: &Secp256k1Signature) -> Result<bool> {
+        Self::verify(public_key, Sha256::digest(message).into(), signature)
+    }
+}
+
+impl ECDSA for Secp256k1Ecdsa {
+    type Signature = Secp256k1Signature;
+    type PrivateKey = Secp256k1Scalar;
+    type PublicKey = Secp256k1Point;
+    type MessageHash = [u8; 32];
+
+    fn sign(private_key: Secp256k1Scalar, message_hash: [u8; 32]) -> Result<Secp256k1Signature> {
+        if private_key.is_zero() {
+            return Err(MpcError::CryptographicError("Private key must be non-zero".to_string()));
+        }
+        let z = Secp256k1Scalar::from_bytes_reduced(&message_hash);
+        let mut nonces = NonceGenerator::new(&private_key, &z);
+        loop {
+            let k = nonces.next();
+            let (x, _) = match (Secp256k1Point::generator() * k).affine_limbs() {
+                Some(coordinates) => coordinates,
+                None => continue,
+            };
+            let r = Secp256k1Scalar(ORDER.reduce(&x));
+            let k_inv = k.invert().expect("nonce is non-zero");
+            let s = k_inv * (z + r * private_key);
+            if r.is_zero() || s.is_zero() {
+                continue;
+            }
+            let s = if s.is_high() { -s } else { s };
+            return Ok(Secp256k1Signature { r, s });
+        }
+    }
+
+    fn verify(public_key: &Secp256k1Point, message_hash: [u8; 32], signature: &Secp256k1Signature) -> Result<bool> {
+        let w = match signature.s.invert() {
+            Some(w) if !signature.r.is_zero() => w,
+            _ => return Ok(false),
+        };
+        if public_key.is_identity() {
+            return Ok(false);
+        }
+        let z = Secp256k1Scalar::from_bytes_reduced(&message_hash);
+        let point = Secp256k1Point::generator() * (z * w) + *public_key * (signature.r * w);
+        Ok(match point.affine_limbs() {
+            Some((x, _)) => field256::equal(&ORDER.reduce(&x), &signature.r.0),
+            None => false,
+        })
+    }
+}
+
+/// secp256k1 上的 ECDH，共享密钥为共享点的 x 坐标（32 字节大端）
+pub struct Secp256k1Ecdh;
+
+impl ECDH for Secp256k1Ecdh {
+    type PrivateKey = Secp256k1Scalar;
+    type PublicKey = Secp256k1Point;
+    type SharedSecret = [u8; 32];
+
+    fn generate_keypair() -> Result<(Secp256k1Scalar, Secp256k1Point)> {
+        Ok(Secp256k1Ecdsa::generate_keypair())
+    }
+
+    fn compute_shared_secret(private_key: Secp256k1Scalar, public_key: &Secp256k1Point) -> Result<[u8; 32]> {
+        if private_key.is_zero() {
+            return Err(MpcError::CryptographicError("Private key must be non-zero".to_string()));
+        }
+        (*public_key * private_key).to_affine()
+            .map(|(x, _)| x)
+            .ok_or_else(|| MpcError::CryptographicError("Shared point is the identity".to_string()))
+    }
+}
+
+/// RFC 6979 确定性随机数生成（HMAC-SHA256）
+struct NonceGenerator {
+    k: [u8; 32],
+    v: [u8; 32],
+    started: bool,
+}
+
+impl NonceGenerator {
+    fn new(private_key: &Secp256k1Scalar, digest: &Secp256k1Scalar) -> Self {
+        let (x, h) = (private_key.to_bytes(), digest.to_bytes());
+        let mut generator = Self { k: [0; 32], v: [1; 32], started: false };
+        for separator in [0x00u8, 0x01] {
+            generator.k = hmac(&generator.k, &[&generator.v, &[separator], &x, &h]);
+            generator.v = hmac(&generator.k, &[&generator.v]);
+        }
+        generator
+    }
+
+    fn next(&mut self) -> Secp256k1Scalar {
+        loop {
+            if self.started {
+                self.k = hmac(&self.k, &[&self.v, &[0x00]]);
+                self.v = hmac(&self.k, &[&self.v]);
+            }
+            self.started = true;
+            self.v = hmac(&self.k, &[&self.v]);
+            if let Some(k) = Secp256k1Scalar::from_bytes(&self.v).filter(|k| !k.is_zero()) {
+                return k;
+            }
+        }
+    }
+}
+
+fn hmac(key: &[u8; 32], parts: &[&[u8]]) -> [u8; 32] {
+    HMAC::compute_hmac(key, &parts.concat())
+}
@@ -0,0 +1,480 @@
+//! # AES-128 电路 (AES-128 Circuit)
+//!
+//! 两方安全 AES 求值所用的布尔电路，可以来自两个地方：
+//!
+//! - **标准电路文件**: `AesCircuit::load` 读取公开的 Bristol Fashion `aes_128.txt`。
+//!   不同来源的文件在密钥、明文的先后顺序以及比特顺序（字节内高位在前、低位在前，
+//!   或整个 128 位分组倒序）上并不统一，加载时用 FIPS-197 的测试向量在明文模拟下
+//!   逐一尝试，确定电路的布局
+//! - **内置电路**: `aes128_circuit` 直接生成同样格式的电路。S 盒使用 Boyar–Peralta
+//!   的组合电路（每个 S 盒 34 个 AND 门），ShiftRows 只是连线，MixColumns 和
+//!   AddRoundKey 只有 XOR 门，密钥扩展也在电路内完成，全电路 200 个 S 盒、6800 个 AND 门
+//!
+//! 内置电路的第一个输入是密钥，第二个是明文，输出是密文，每个字节高位在前。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::garbled_circuits::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let aes = AesCircuit::builtin();
+//! assert_eq!(aes.and_count(), 6800);
+//!
+//! let key = [0x2b; 16];
+//! let plaintext = *b"two-party block!";
+//! assert_eq!(aes.evaluate_plain(&key, &plaintext)?, aes128_encrypt(&key, &plaintext));
+//! # Ok(())
+//! # }
+//! ```
+
+use super::*;
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::Aes128;
+use std::path::Path;
+
+/// AES 分组长度（字节）
+pub const AES_BLOCK_BYTES: usize = 16;
+
+/// AES 分组长度（比特）
+pub const AES_BLOCK_BITS: usize = 8 * AES_BLOCK_BYTES;
+
+/// AES-128 分组
+pub type AesBlock = [u8; AES_BLOCK_BYTES];
+
+/// 加载电路时用于确定布局的测试向量：(密钥, 明文, 密文)，取自 FIPS-197 附录
+const LAYOUT_TEST_VECTORS: [(AesBlock, AesBlock, AesBlock); 2] = [
+    (
+        [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f],
+        [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff],
+        [0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a],
+    ),
+    (
+        [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c],
+        [0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d, 0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37, 0x07, 0x34],
+        [0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb, 0xdc, 0x11, 0x85, 0x97, 0x19, 0x6a, 0x0b, 0x32],
+    ),
+];
+
+/// 密钥扩展的轮常量
+const ROUND_CONSTANTS: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+/// 用软件 AES-128 加密一个分组，作为电路求值结果的参照
+pub fn aes128_encrypt(key: &AesBlock, plaintext: &AesBlock) -> AesBlock {
+    let cipher = Aes128::new(GenericArray::from_slice(key));
+    let mut block = GenericArray::clone_from_slice(plaintext);
+    cipher.encrypt_block(&mut block);
+    block.into()
+}
+
+/// 128 位分组在电路线上的比特顺序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AesBitOrder {
+    /// 按字节顺序，每个字节高位在前（内置电路）
+    MsbFirst,
+    /// 按字节顺序，每个字节低位在前
+    LsbFirst,
+    /// 整个分组作为大端整数时低位在前，即 `MsbFirst` 的倒序
+    Reversed,
+}
+
+impl AesBitOrder {
+    const ALL: [AesBitOrder; 3] = [AesBitOrder::MsbFirst, AesBitOrder::LsbFirst, AesBitOrder::Reversed];
+
+    /// 第 `index` 条线对应的 (字节, 位) 位置，位 0 为最低位
+    fn position(self, index: usize) -> (usize, usize) {
+        match self {
+            AesBitOrder::MsbFirst => (index / 8, 7 - index % 8),
+            AesBitOrder::LsbFirst => (index / 8, index % 8),
+            AesBitOrder::Reversed => (AES_BLOCK_BYTES - 1 - index / 8, index % 8),
+        }
+    }
+
+    /// 把分组展开为电路输入
+    pub fn encode(self, block: &AesBlock) -> Vec<bool> {
+        (0..AES_BLOCK_BITS)
+            .map(|index| {
+                let (byte, bit) = self.position(index);
+                (block[byte] >> bit) & 1 == 1
+            })
+            .collect()
+    }
+
+    /// 把电路输出还原为分组
+    pub fn decode(self, bits: &[bool]) -> Result<AesBlock> {
+        if bits.len() != AES_BLOCK_BITS {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} output bits, found {}", AES_BLOCK_BITS, bits.len()
+            )));
+        }
+        let mut block = [0u8; AES_BLOCK_BYTES];
+        for (index, &value) in bits.iter().enumerate() {
+            let (byte, bit) = self.position(index);
+            block[byte] |= (value as u8) << bit;
+        }
+        Ok(block)
+    }
+}
+
+/// 布局已确定的 AES-128 电路
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AesCircuit {
+    bristol: BristolCircuit,
+    /// 密钥是第几个输入（0 或 1）
+    key_input: usize,
+    bit_order: AesBitOrder,
+}
+
+impl AesCircuit {
+    /// 内置的 AES-128 电路
+    pub fn builtin() -> Self {
+        Self {
+            bristol: aes128_circuit(),
+            key_input: 0,
+            bit_order: AesBitOrder::MsbFirst,
+        }
+    }
+
+    /// 检查 Bristol 电路是 AES-128 并确定其布局
+    ///
+    /// # 返回值
+    /// 电路不是两个 128 位输入、一个 128 位输出，或在所有已知布局下都不能
+    /// 通过 FIPS-197 测试向量时返回错误
+    pub fn from_bristol(bristol: BristolCircuit) -> Result<Self> {
+        if bristol.input_widths != [AES_BLOCK_BITS, AES_BLOCK_BITS] || bristol.output_widths != [AES_BLOCK_BITS] {
+            return Err(MpcError::ProtocolError(format!(
+                "AES-128 circuit needs two 128-bit inputs and one 128-bit output, found {:?} -> {:?}",
+                bristol.input_widths, bristol.output_widths
+            )));
+        }
+
+        for key_input in [0, 1] {
+            for bit_order in AesBitOrder::ALL {
+                let candidate = Self { bristol: bristol.clone(), key_input, bit_order };
+                let matches = LAYOUT_TEST_VECTORS.iter().try_fold(true, |matches, (key, plaintext, ciphertext)| {
+                    Ok::<_, MpcError>(matches && candidate.evaluate_plain(key, plaintext)? == *ciphertext)
+                })?;
+                if matches {
+                    return Ok(candidate);
+                }
+            }
+        }
+        Err(MpcError::ProtocolError(
+            "Circuit does not compute AES-128 under any known input layout".to_string()
+        ))
+    }
+
+    /// 读取 Bristol Fashion 格式的 AES-128 电路文件（例如标准电路库中的 `aes_128.txt`）
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_bristol(BristolCircuit::from_file(path)?)
+    }
+
+    /// 布尔电路
+    pub fn circuit(&self) -> &Circuit {
+        &self.bristol.circuit
+    }
+
+    /// Bristol 格式的电路
+    pub fn bristol(&self) -> &BristolCircuit {
+        &self.bristol
+    }
+
+    /// 比特顺序
+    pub fn bit_order(&self) -> AesBitOrder {
+        self.bit_order
+    }
+
+    /// 密钥占用的输入线在 `circuit().input_wires` 中的位置
+    pub fn key_positions(&self) -> std::ops::Range<usize> {
+        self.key_input * AES_BLOCK_BITS..(self.key_input + 1) * AES_BLOCK_BITS
+    }
+
+    /// 明文占用的输入线在 `circuit().input_wires` 中的位置
+    pub fn plaintext_positions(&self) -> std::ops::Range<usize> {
+        let plaintext_input = 1 - self.key_input;
+        plaintext_input * AES_BLOCK_BITS..(plaintext_input + 1) * AES_BLOCK_BITS
+    }
+
+    /// AND 门数量
+    pub fn and_count(&self) -> usize {
+        self.bristol.and_count()
+    }
+
+    /// 按电路布局拼接密钥和明文，得到全部输入
+    pub fn encode_inputs(&self, key: &AesBlock, plaintext: &AesBlock) -> Vec<bool> {
+        let mut inputs = vec![false; 2 * AES_BLOCK_BITS];
+        inputs[self.key_positions()].copy_from_slice(&self.bit_order.encode(key));
+        inputs[self.plaintext_positions()].copy_from_slice(&self.bit_order.encode(plaintext));
+        inputs
+    }
+
+    /// 把输出比特还原为密文
+    pub fn decode_ciphertext(&self, bits: &[bool]) -> Result<AesBlock> {
+        self.bit_order.decode(bits)
+    }
+
+    /// 在明文布尔值上模拟电路（调试、检查电路文件用）
+    pub fn evaluate_plain(&self, key: &AesBlock, plaintext: &AesBlock) -> Result<AesBlock> {
+        let outputs = simulate(self.circuit(), &self.encode_inputs(key, plaintext))?;
+        self.decode_ciphertext(&outputs)
+    }
+}
+
+/// 一个字节对应的 8 条线，下标 0 为最高位
+type ByteWires = [WireId; 8];
+
+/// 生成内置的 AES-128 电路
+///
+/// 输入依次为 128 位密钥和 128 位明文，输出为 128 位密文，每个字节高位在前。
+pub fn aes128_circuit() -> BristolCircuit {
+    let mut circuit = Circuit::new();
+    let key_wires: Vec<WireId> = (0..AES_BLOCK_BITS).map(|_| circuit.add_input_wire()).collect();
+    let plaintext_wires: Vec<WireId> = (0..AES_BLOCK_BITS).map(|_| circuit.add_input_wire()).collect();
+    let to_bytes = |wires: &[WireId]| -> Vec<ByteWires> {
+        wires.chunks_exact(8).map(|chunk| chunk.try_into().expect("chunk of 8 wires")).collect()
+    };
+
+    let round_keys = expand_key(&mut circuit, &to_bytes(&key_wires));
+    let mut state = xor_block(&mut circuit, &to_bytes(&plaintext_wires), &round_keys[0]);
+    for (round, round_key) in round_keys.iter().enumerate().skip(1) {
+        let substituted: Vec<ByteWires> = state.iter().map(|&byte| sbox(&mut circuit, byte)).collect();
+        let shifted = shift_rows(&substituted);
+        let mixed = if round < 10 { mix_columns(&mut circuit, &shifted) } else { shifted };
+        state = xor_block(&mut circuit, &mixed, round_key);
+    }
+
+    for wire in state.iter().flatten() {
+        circuit.add_output_wire(*wire);
+    }
+    BristolCircuit {
+        circuit,
+        input_widths: vec![AES_BLOCK_BITS, AES_BLOCK_BITS],
+        output_widths: vec![AES_BLOCK_BITS],
+    }
+}
+
+fn xor_byte(circuit: &mut Circuit, a: ByteWires, b: ByteWires) -> ByteWires {
+    std::array::from_fn(|bit| circuit.xor_gate(a[bit], b[bit]))
+}
+
+fn xor_block(circuit: &mut Circuit, a: &[ByteWires], b: &[ByteWires]) -> Vec<ByteWires> {
+    a.iter().zip(b).map(|(&a, &b)| xor_byte(circuit, a, b)).collect()
+}
+
+/// 与公开常量异或：常量为 1 的位取反
+fn xor_constant(circuit: &mut Circuit, byte: ByteWires, constant: u8) -> ByteWires {
+    std::array::from_fn(|bit| {
+        if (constant >> (7 - bit)) & 1 == 1 { circuit.not_gate(byte[bit]) } else { byte[bit] }
+    })
+}
+
+/// 密钥扩展，返回 11 个轮密钥
+fn expand_key(circuit: &mut Circuit, key: &[ByteWires]) -> Vec<Vec<ByteWires>> {
+    let mut words: Vec<[ByteWires; 4]> = key.chunks_exact(4)
+        .map(|word| [word[0], word[1], word[2], word[3]])
+        .collect();
+    for i in 4..44 {
+        let mut temp = words[i - 1];
+        if i % 4 == 0 {
+            temp = [temp[1], temp[2], temp[3], temp[0]].map(|byte| sbox(circuit, byte));
+            temp[0] = xor_constant(circuit, temp[0], ROUND_CONSTANTS[i / 4 - 1]);
+        }
+        let previous = words[i - 4];
+        words.push(std::array::from_fn(|byte| xor_byte(circuit, previous[byte], temp[byte])));
+    }
+    words.chunks_exact(4).map(|round| round.iter().flatten().copied().collect()).collect()
+}
+
+/// 状态按列存放：第 c 列第 r 行的字节下标为 4c + r
+fn shift_rows(state: &[ByteWires]) -> Vec<ByteWires> {
+    (0..AES_BLOCK_BYTES)
+        .map(|index| {
+            let (column, row) = (index / 4, index % 4);
+            state[4 * ((column + row) % 4) + row]
+        })
+        .collect()
+}
+
+/// GF(2^8) 中乘以 x，只需要 XOR
+fn xtime(circuit: &mut Circuit, a: ByteWires) -> ByteWires {
+    let high = a[0];
+    [
+        a[1],
+        a[2],
+        a[3],
+        circuit.xor_gate(a[4], high),
+        circuit.xor_gate(a[5], high),
+        a[6],
+        circuit.xor_gate(a[7], high),
+        high,
+    ]
+}
+
+fn mix_columns(circuit: &mut Circuit, state: &[ByteWires]) -> Vec<ByteWires> {
+    state.chunks_exact(4)
+        .flat_map(|column| {
+            // b_i = a_i ⊕ t ⊕ xtime(a_i ⊕ a_{i+1})，t 为整列的异或
+            let partial = xor_byte(circuit, column[0], column[1]);
+            let rest = xor_byte(circuit, column[2], column[3]);
+            let total = xor_byte(circuit, partial, rest);
+            (0..4)
+                .map(|row| {
+                    let pair = xor_byte(circuit, column[row], column[(row + 1) % 4]);
+                    let doubled = xtime(circuit, pair);
+                    let mixed = xor_byte(circuit, column[row], total);
+                    xor_byte(circuit, mixed, doubled)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Boyar–Peralta S 盒电路：顶层线性变换、34 个 AND 的非线性核心、底层线性变换
+fn sbox(circuit: &mut Circuit, u: ByteWires) -> ByteWires {
+    let mut xor = |a: WireId, b: WireId| circuit.xor_gate(a, b);
+
+    let t1 = xor(u[0], u[3]);
+    let t2 = xor(u[0], u[5]);
+    let t3 = xor(u[0], u[6]);
+    let t4 = xor(u[3], u[5]);
+    let t5 = xor(u[4], u[6]);
+    let t6 = xor(t1, t5);
+    let t7 = xor(u[1], u[2]);
+    let t8 = xor(u[7], t6);
+    let t9 = xor(u[7], t7);
+    let t10 = xor(t6, t7);
+    let t11 = xor(u[1], u[5]);
+    let t12 = xor(u[2], u[5]);
+    let t13 = xor(t3, t4);
+    let t14 = xor(t6, t11);
+    let t15 = xor(t5, t11);
+    let t16 = xor(t5, t12);
+    let t17 = xor(t9, t16);
+    let t18 = xor(u[3], u[7]);
+    let t19 = xor(t7, t18);
+    let t20 = xor(t1, t19);
+    let t21 = xor(u[6], u[7]);
+    let t22 = xor(t7, t21);
+    let t23 = xor(t2, t22);
+    let t24 = xor(t2, t10);
+    let t25 = xor(t20, t17);
+    let t26 = xor(t3, t16);
+    let t27 = xor(t1, t12);
+
+    let m1 = circuit.and_gate(t13, t6);
+    let m2 = circuit.and_gate(t23, t8);
+    let m3 = circuit.xor_gate(t14, m1);
+    let m4 = circuit.and_gate(t19, u[7]);
+    let m5 = circuit.xor_gate(m4, m1);
+    let m6 = circuit.and_gate(t3, t16);
+    let m7 = circuit.and_gate(t22, t9);
+    let m8 = circuit.xor_gate(t26, m6);
+    let m9 = circuit.and_gate(t20, t17);
+    let m10 = circuit.xor_gate(m9, m6);
+    let m11 = circuit.and_gate(t1, t15);
+    let m12 = circuit.and_gate(t4, t27);
+    let m13 = circuit.xor_gate(m12, m11);
+    let m14 = circuit.and_gate(t2, t10);
+    let m15 = circuit.xor_gate(m14, m11);
+    let m16 = circuit.xor_gate(m3, m2);
+    let m17 = circuit.xor_gate(m5, t24);
+    let m18 = circuit.xor_gate(m8, m7);
+    let m19 = circuit.xor_gate(m10, m15);
+    let m20 = circuit.xor_gate(m16, m13);
+    let m21 = circuit.xor_gate(m17, m15);
+    let m22 = circuit.xor_gate(m18, m13);
+    let m23 = circuit.xor_gate(m19, t25);
+    let m24 = circuit.xor_gate(m22, m23);
+    let m25 = circuit.and_gate(m22, m20);
+    let m26 = circuit.xor_gate(m21, m25);
+    let m27 = circuit.xor_gate(m20, m21);
+    let m28 = circuit.xor_gate(m23, m25);
+    let m29 = circuit.and_gate(m28, m27);
+    let m30 = circuit.and_gate(m26, m24);
+    let m31 = circuit.and_gate(m20, m23);
+    let m32 = circuit.and_gate(m27, m31);
+    let m33 = circuit.xor_gate(m27, m25);
+    let m34 = circuit.and_gate(m21, m22);
+    let m35 = circuit.and_gate(m24, m34);
+    let m36 = circuit.xor_gate(m24, m25);
+    let m37 = circuit.xor_gate(m21, m29);
+    let m38 = circuit.xor_gate(m32, m33);
+    let m39 = circuit.xor_gate(m23, m30);
+    let m40 = circuit.xor_gate(m35, m36);
+    let m41 = circuit.xor_gate(m38, m40);
+    let m42 = circuit.xor_gate(m37, m39);
+    let m43 = circuit.xor_gate(m37, m38);
+    let m44 = circuit.xor_gate(m39, m40);
+    let m45 = circuit.xor_gate(m42, m41);
+    let m46 = circuit.and_gate(m44, t6);
+    let m47 = circuit.and_gate(m40, t8);
+    let m48 = circuit.and_gate(m39, u[7]);
+    let m49 = circuit.and_gate(m43, t16);
+    let m50 = circuit.and_gate(m38, t9);
+    let m51 = circuit.and_gate(m37, t17);
+    let m52 = circuit.and_gate(m42, t15);
+    let m53 = circuit.and_gate(m45, t27);
+    let m54 = circuit.and_gate(m41, t10);
+    let m55 = circuit.and_gate(m44, t13);
+    let m56 = circuit.and_gate(m40, t23);
+    let m57 = circuit.and_gate(m39, t19);
+    let m58 = circuit.and_gate(m43, t3);
+    let m59 = circuit.and_gate(m38, t22);
+    let m60 = circuit.and_gate(m37, t20);
+    let m61 = circuit.and_gate(m42, t1);
+    let m62 = circuit.and_gate(m45, t4);
+    let m63 = circuit.and_gate(m41, t2);
+
+    let mut xor = |a: WireId, b: WireId| circuit.xor_gate(a, b);
+    let l0 = xor(m61, m62);
+    let l1 = xor(m50, m56);
+    let l2 = xor(m46, m48);
+    let l3 = xor(m47, m55);
+    let l4 = xor(m54, m58);
+    let l5 = xor(m49, m61);
+    let l6 = xor(m62, l5);
+    let l7 = xor(m46, l3);
+    let l8 = xor(m51, m59);
+    let l9 = xor(m52, m53);
+    let l10 = xor(m53, l4);
+    let l11 = xor(m60, l2);
+    let l12 = xor(m48, m51);
+    let l13 = xor(m50, l0);
+    let l14 = xor(m52, m61);
+    let l15 = xor(m55, l1);
+    let l16 = xor(m56, l0);
+    let l17 = xor(m57, l1);
+    let l18 = xor(m58, l8);
+    let l19 = xor(m63, l4);
+    let l20 = xor(l0, l1);
+    let l21 = xor(l1, l7);
+    let l22 = xor(l3, l12);
+    let l23 = xor(l18, l2);
+    let l24 = xor(l15, l9);
+    let l25 = xor(l6, l10);
+    let l26 = xor(l7, l9);
+    let l27 = xor(l8, l10);
+    let l28 = xor(l11, l14);
+    let l29 = xor(l11, l17);
+
+    let s0 = xor(l6, l24);
+    let s1 = xor(l16, l26);
+    let s2 = xor(l19, l28);
+    let s3 = xor(l6, l21);
+    let s4 = xor(l20, l22);
+    let s5 = xor(l25, l29);
+    let s6 = xor(l13, l27);
+    let s7 = xor(l6, l23);
+    // S1、S2、S6、S7 是 XNOR
+    [
+        s0,
+        circuit.not_gate(s1),
+        circuit.not_gate(s2),
+        s3,
+        s4,
+        s5,
+        circuit.not_gate(s6),
+        circuit.not_gate(s7),
+    ]
+}
@@ -0,0 +1,349 @@
+//! # 混淆电路审计 (Garbled Circuit Audit)
+//!
+//! 基于混淆电路的合约发生争议时，第三方（或事后的混淆方）需要确认求值方
+//! 确实运行了约定的电路。本模块把以下内容导出为一个可验证的产物：
+//!
+//! - **电路**: 门结构与混淆表，以及约定电路结构的规范摘要
+//! - **输入标签承诺**: 每条输入线两个标签的哈希，按选择位排列，不泄露逻辑值
+//! - **输出解码信息**: 每条输出线两个标签的哈希，按逻辑值排列
+//! - **求值转录**: 求值方使用的输入标签、每个门得到的输出标签以及解码结果
+//!
+//! 检查方只需要产物和约定电路（或其摘要）：逐门重放求值，确认每个门都恰好
+//! 打开选择位指向的那一行、得到的标签与转录一致，最后按解码信息核对输出。
+//! NOT 门在导出时补上两行的混淆表，使检查方不需要任何线标签。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::garbled_circuits::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let circuit = Circuit::create_adder(2);
+//! let garbler = Garbler::new();
+//! let garbled = garbler.garble_circuit(&circuit)?;
+//!
+//! let inputs = vec![true, false, true, true];
+//! let labels = garbler.get_input_labels(&garbled, &inputs)?;
+//! let transcript = Evaluator::new().evaluate_with_transcript(&garbled, &labels)?;
+//!
+//! let artifact = GarbledEvaluationArtifact::new(GarbledCircuitExport::from_garbled(&garbled)?, transcript);
+//! let bytes = artifact.to_bytes()?;
+//!
+//! // 第三方只拿到字节和约定的电路
+//! let outputs = GarbledEvaluationArtifact::from_bytes(&bytes)?.verify(&circuit)?;
+//! assert_eq!(outputs, artifact.transcript.outputs);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::*;
+use crate::utils::{deserialize_from_bytes, hash_struct, hash_struct_with_domain, serialize_to_bytes};
+
+/// 审计产物格式版本
+pub const GC_AUDIT_VERSION: u32 = 1;
+
+/// 标签哈希的域分隔标签
+const LABEL_HASH_DOMAIN: &[u8] = b"mpc_api/garbled_circuits/audit/label";
+
+/// 电路结构摘要的域分隔标签
+const CIRCUIT_DIGEST_DOMAIN: &[u8] = b"mpc_api/garbled_circuits/audit/circuit";
+
+/// 标签哈希
+pub type LabelHash = [u8; 32];
+
+/// 导出的混淆门
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditedGate {
+    /// 门的唯一标识符
+    pub id: GateId,
+    /// 门的类型
+    pub gate_type: GateType,
+    /// 输入线
+    pub input_wires: Vec<WireId>,
+    /// 输出线
+    pub output_wire: WireId,
+    /// 混淆表（双输入门 4 行，NOT 门 2 行）
+    pub table: Vec<Label>,
+}
+
+/// 混淆方导出的电路部分
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GarbledCircuitExport {
+    /// 电路结构摘要
+    pub circuit_digest: [u8; 32],
+    /// 混淆门
+    pub gates: Vec<AuditedGate>,
+    /// 输入线
+    pub input_wires: Vec<WireId>,
+    /// 输出线
+    pub output_wires: Vec<WireId>,
+    /// 输入标签承诺，按选择位排列
+    pub input_label_commitments: Vec<[LabelHash; 2]>,
+    /// 输出解码信息，按逻辑值排列
+    pub output_decoding: Vec<[LabelHash; 2]>,
+}
+
+/// 求值转录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvaluationTranscript {
+    /// 求值方使用的输入标签
+    pub input_labels: Vec<Label>,
+    /// 每个门得到的输出标签
+    pub gate_outputs: Vec<(GateId, Label)>,
+    /// 解码后的输出
+    pub outputs: Vec<bool>,
+}
+
+/// 可验证的求值产物
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GarbledEvaluationArtifact {
+    /// 格式版本
+    pub version: u32,
+    /// 电路部分
+    pub export: GarbledCircuitExport,
+    /// 求值转录
+    pub transcript: EvaluationTranscript,
+}
+
+/// 约定电路的结构摘要
+///
+/// 覆盖每个门的标识符、类型、输入输出线以及电路的输入输出线，不包含任何标签。
+pub fn agreed_circuit_digest(circuit: &Circuit) -> Result<[u8; 32]> {
+    structure_digest(
+        circuit.gates.iter().map(|gate| (gate.id, &gate.gate_type, gate.input_wires.as_slice(), gate.output_wire)),
+        &circuit.input_wires,
+        &circuit.output_wires,
+    )
+}
+
+impl GarbledCircuitExport {
+    /// 由混淆方从混淆电路导出
+    pub fn from_garbled(garbled_circuit: &GarbledCircuit) -> Result<Self> {
+        let labels_of = |wire: WireId| {
+            garbled_circuit.wire_labels.get(&wire).copied()
+                .ok_or_else(|| MpcError::ProtocolError(format!("Missing labels for wire {}", wire)))
+        };
+
+        let mut gates = Vec::with_capacity(garbled_circuit.gates.len());
+        for gate in &garbled_circuit.gates {
+            let table = match gate.gate_type {
+                GateType::Not | GateType::Buf => {
+                    let input = labels_of(gate.input_wires[0])?;
+                    let output = labels_of(gate.output_wire)?;
+                    unary_gate_table(gate.id, input, output, gate.gate_type == GateType::Not)
+                }
+                _ => gate.garbled_table.clone().unwrap_or_default(),
+            };
+            gates.push(AuditedGate {
+                id: gate.id,
+                gate_type: gate.gate_type.clone(),
+                input_wires: gate.input_wires.clone(),
+                output_wire: gate.output_wire,
+                table,
+            });
+        }
+
+        let input_label_commitments = garbled_circuit.input_wires.iter()
+            .map(|&wire| {
+                let (label_0, label_1) = labels_of(wire)?;
+                let mut commitments = [label_hash(wire, &label_0)?, label_hash(wire, &label_1)?];
+                if ColoredLabel::new(label_0).color {
+                    commitments.swap(0, 1);
+                }
+                Ok(commitments)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let output_decoding = garbled_circuit.output_wires.iter()
+            .map(|&wire| {
+                let (label_0, label_1) = labels_of(wire)?;
+                Ok([label_hash(wire, &label_0)?, label_hash(wire, &label_1)?])
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let circuit_digest = export_structure_digest(&gates, &garbled_circuit.input_wires, &garbled_circuit.output_wires)?;
+        Ok(Self {
+            circuit_digest,
+            gates,
+            input_wires: garbled_circuit.input_wires.clone(),
+            output_wires: garbled_circuit.output_wires.clone(),
+            input_label_commitments,
+            output_decoding,
+        })
+    }
+}
+
+impl GarbledEvaluationArtifact {
+    /// 组装产物
+    pub fn new(export: GarbledCircuitExport, transcript: EvaluationTranscript) -> Self {
+        Self {
+            version: GC_AUDIT_VERSION,
+            export,
+            transcript,
+        }
+    }
+
+    /// 产物的规范摘要，可用于签名或存档
+    pub fn digest(&self) -> Result<[u8; 32]> {
+        hash_struct(self)
+    }
+
+    /// 序列化为字节
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        serialize_to_bytes(self)
+    }
+
+    /// 从字节反序列化
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        deserialize_from_bytes(bytes)
+    }
+
+    /// 对照约定电路检查产物
+    ///
+    /// # 返回值
+    ///
+    /// 检查通过时返回经过验证的输出
+    pub fn verify(&self, agreed_circuit: &Circuit) -> Result<Vec<bool>> {
+        self.verify_with_digest(&agreed_circuit_digest(agreed_circuit)?)
+    }
+
+    /// 对照约定电路的摘要检查产物
+    ///
+    /// 依次检查：版本与电路摘要、输入标签与承诺一致、逐门重放得到的标签与转录一致、
+    /// 输出标签能按解码信息解码且与转录中的输出一致。
+    pub fn verify_with_digest(&self, agreed_digest: &[u8; 32]) -> Result<Vec<bool>> {
+        if self.version != GC_AUDIT_VERSION {
+            return Err(MpcError::ProtocolError(format!("Unsupported audit artifact version {}", self.version)));
+        }
+
+        let export = &self.export;
+        let transcript = &self.transcript;
+        let digest = export_structure_digest(&export.gates, &export.input_wires, &export.output_wires)?;
+        if digest != export.circuit_digest || digest != *agreed_digest {
+            return Err(MpcError::AuthenticationError("Exported circuit differs from the agreed circuit".to_string()));
+        }
+
+        if transcript.input_labels.len() != export.input_wires.len()
+            || export.input_label_commitments.len() != export.input_wires.len()
+            || export.output_decoding.len() != export.output_wires.len()
+            || transcript.gate_outputs.len() != export.gates.len()
+            || transcript.outputs.len() != export.output_wires.len()
+        {
+            return Err(MpcError::ProtocolError("Audit artifact is incomplete".to_string()));
+        }
+
+        // 输入标签必须与承诺一致
+        let mut wire_labels = std::collections::HashMap::new();
+        for ((&wire, label), commitments) in export.input_wires.iter()
+            .zip(&transcript.input_labels)
+            .zip(&export.input_label_commitments)
+        {
+            let color = ColoredLabel::new(*label).color as usize;
+            if label_hash(wire, label)? != commitments[color] {
+                return Err(MpcError::AuthenticationError(format!("Input label for wire {} is not committed", wire)));
+            }
+            wire_labels.insert(wire, *label);
+        }
+
+        // 逐门重放求值
+        for (gate, &(recorded_gate, recorded_label)) in export.gates.iter().zip(&transcript.gate_outputs) {
+            let input = |index: usize| {
+                gate.input_wires.get(index)
+                    .and_then(|wire| wire_labels.get(wire))
+                    .map(|label| ColoredLabel::new(*label))
+                    .ok_or_else(|| MpcError::ProtocolError(format!("Missing input label for gate {}", gate.id)))
+            };
+
+            let label = match gate.gate_type {
+                ref gate_type if gate_type.is_table_gate() => {
+                    if gate.input_wires.len() < 2 || gate.input_wires.len() > MAX_NATIVE_FAN_IN {
+                        return Err(MpcError::ProtocolError(format!("Gate {} has an invalid fan-in", gate.id)));
+                    }
+                    let inputs = (0..gate.input_wires.len()).map(input).collect::<Result<Vec<_>>>()?;
+                    let labels = inputs.iter().map(|input| input.label).collect::<Vec<_>>();
+                    open_garbled_row(
+                        gate.id,
+                        &gate.table,
+                        1 << inputs.len(),
+                        permute_row_n(&inputs),
+                        garbled_row_keys_n(&labels, gate.id),
+                    )?
+                }
+                GateType::Not | GateType::Buf => {
+                    let a = input(0)?;
+                    open_garbled_row(gate.id, &gate.table, 2, a.color as usize, garbled_row_keys(&a.label, &a.label, gate.id))?
+                }
+                GateType::Const(_) => match gate.table.as_slice() {
+                    [label] => *label,
+                    table => {
+                        return Err(GarblingIntegrityError::MalformedTable { gate: gate.id, len: table.len(), expected: 1 }.into())
+                    }
+                },
+                _ => return Err(MpcError::ProtocolError("Invalid gate type for evaluation".to_string())),
+            };
+
+            if recorded_gate != gate.id || recorded_label != label {
+                return Err(MpcError::AuthenticationError(format!("Transcript diverges at gate {}", gate.id)));
+            }
+            wire_labels.insert(gate.output_wire, label);
+        }
+
+        // 解码输出
+        let mut outputs = Vec::with_capacity(export.output_wires.len());
+        for (&wire, decoding) in export.output_wires.iter().zip(&export.output_decoding) {
+            let label = wire_labels.get(&wire)
+                .ok_or_else(|| MpcError::ProtocolError(format!("Missing label for output wire {}", wire)))?;
+            let hash = label_hash(wire, label)?;
+            let bit = match decoding.iter().position(|candidate| *candidate == hash) {
+                Some(position) => position == 1,
+                None => {
+                    return Err(MpcError::AuthenticationError(format!("Output label for wire {} does not decode", wire)))
+                }
+            };
+            outputs.push(bit);
+        }
+
+        if outputs != transcript.outputs {
+            return Err(MpcError::AuthenticationError("Transcript outputs do not match the evaluation".to_string()));
+        }
+        Ok(outputs)
+    }
+}
+
+/// 为 NOT / BUF 门构造两行混淆表，按输入标签的选择位排列
+fn unary_gate_table(gate_id: GateId, input: (Label, Label), output: (Label, Label), invert: bool) -> Vec<Label> {
+    let mut table = vec![[0u8; 16]; 2 * GARBLED_ROW_LABELS];
+    let output = if invert { (output.1, output.0) } else { output };
+    for (input_label, output_label) in [(input.0, output.0), (input.1, output.1)] {
+        let colored = ColoredLabel::new(input_label);
+        let (pad, tag) = garbled_row_keys(&input_label, &input_label, gate_id);
+        let row = colored.color as usize * GARBLED_ROW_LABELS;
+        table[row] = xor_labels(&pad, &output_label);
+        table[row + 1] = tag;
+    }
+    table
+}
+
+/// 标签哈希，绑定线标识符
+fn label_hash(wire: WireId, label: &Label) -> Result<LabelHash> {
+    hash_struct_with_domain(LABEL_HASH_DOMAIN, &(wire, label))
+}
+
+/// 导出门的结构摘要
+fn export_structure_digest(gates: &[AuditedGate], input_wires: &[WireId], output_wires: &[WireId]) -> Result<[u8; 32]> {
+    structure_digest(
+        gates.iter().map(|gate| (gate.id, &gate.gate_type, gate.input_wires.as_slice(), gate.output_wire)),
+        input_wires,
+        output_wires,
+    )
+}
+
+/// 电路结构的规范摘要
+fn structure_digest<'a>(
+    gates: impl Iterator<Item = (GateId, &'a GateType, &'a [WireId], WireId)>,
+    input_wires: &[WireId],
+    output_wires: &[WireId],
+) -> Result<[u8; 32]> {
+    let gates: Vec<_> = gates.collect();
+    hash_struct_with_domain(CIRCUIT_DIGEST_DOMAIN, &(gates, input_wires, output_wires))
+}
@@ -0,0 +1,335 @@
+//! # Bristol Fashion 电路格式 (Bristol Fashion Circuits)
+//!
+//! 读写 Bristol Fashion 格式的布尔电路，标准电路库（AES-128、SHA-256、加法器等）
+//! 都以这种格式发布：
+//!
+//! ```text
+//! 门数量 线数量
+//! 输入数量 每个输入的位数...
+//! 输出数量 每个输出的位数...
+//!
+//! 输入线数 输出线数 输入线... 输出线... 门类型
+//! ```
+//!
+//! 输入依次占用编号最小的线，输出占用编号最大的线。门类型为 `XOR`、`AND`、
+//! `INV`、`EQW`（复制）、`EQ`（常量，输入位置是字面量 0/1）和 `MAND`
+//! （若干个并列的 AND）。解析时门必须按依赖顺序排列，每条线只被驱动一次。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::garbled_circuits::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! // 两个 1 位输入，输出 NOT (a AND b)
+//! let text = "2 4\n2 1 1\n1 1\n\n2 1 0 1 2 AND\n1 1 2 3 INV\n";
+//! let bristol = BristolCircuit::parse(text)?;
+//! assert_eq!(bristol.input_widths, vec![1, 1]);
+//! assert_eq!(simulate(&bristol.circuit, &[true, true])?, vec![false]);
+//!
+//! // 写回后得到同一个电路
+//! assert_eq!(BristolCircuit::parse(&bristol.to_bristol()?)?, bristol);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::plan::checked_gate_order;
+use super::*;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Bristol Fashion 格式的电路
+///
+/// `circuit` 的输入线按输入顺序排列，第 k 个输入占用 `input_widths[k]` 条线；
+/// 输出线同理。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BristolCircuit {
+    /// 电路
+    pub circuit: Circuit,
+    /// 每个输入的位数
+    pub input_widths: Vec<usize>,
+    /// 每个输出的位数
+    pub output_widths: Vec<usize>,
+}
+
+impl BristolCircuit {
+    /// 由电路和输入输出的划分构造
+    ///
+    /// # 参数
+    /// - `circuit`: 电路
+    /// - `input_widths`: 每个输入的位数，总和必须等于输入线数量
+    /// - `output_widths`: 每个输出的位数，总和必须等于输出线数量
+    pub fn new(circuit: Circuit, input_widths: Vec<usize>, output_widths: Vec<usize>) -> Result<Self> {
+        if input_widths.iter().sum::<usize>() != circuit.input_wires.len()
+            || output_widths.iter().sum::<usize>() != circuit.output_wires.len()
+        {
+            return Err(MpcError::ProtocolError(
+                "Input/output widths do not match the circuit".to_string()
+            ));
+        }
+        Ok(Self { circuit, input_widths, output_widths })
+    }
+
+    /// 解析 Bristol Fashion 文本
+    ///
+    /// # 返回值
+    /// 格式错误、线编号越界、门读取尚未赋值的线或重复驱动同一条线时返回 `SerializationError`
+    pub fn parse(text: &str) -> Result<Self> {
+        let malformed = |line: usize, reason: &str| {
+            MpcError::SerializationError(format!("Bristol line {}: {}", line, reason))
+        };
+        let mut lines = text.lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.split_whitespace().collect::<Vec<_>>()))
+            .filter(|(_, tokens)| !tokens.is_empty());
+        let mut header = |what: &str| lines.next().ok_or_else(|| malformed(0, &format!("missing {}", what)));
+        let parse_numbers = |line: usize, tokens: &[&str]| {
+            tokens.iter()
+                .map(|token| token.parse::<usize>().map_err(|_| malformed(line, &format!("expected a number, found {:?}", token))))
+                .collect::<Result<Vec<usize>>>()
+        };
+
+        let (line, tokens) = header("gate and wire counts")?;
+        let counts = parse_numbers(line, &tokens)?;
+        let [gate_count, wire_count] = counts[..] else {
+            return Err(malformed(line, "expected gate and wire counts"));
+        };
+        let (line, tokens) = header("input widths")?;
+        let input_widths = parse_widths(&parse_numbers(line, &tokens)?).map_err(|reason| malformed(line, reason))?;
+        let (line, tokens) = header("output widths")?;
+        let output_widths = parse_widths(&parse_numbers(line, &tokens)?).map_err(|reason| malformed(line, reason))?;
+
+        let input_count: usize = input_widths.iter().sum();
+        let output_count: usize = output_widths.iter().sum();
+        if input_count + output_count > wire_count || wire_count > WireId::MAX as usize {
+            return Err(malformed(1, "wire count is too small for the inputs and outputs"));
+        }
+
+        let mut circuit = Circuit {
+            gates: Vec::with_capacity(gate_count),
+            input_wires: (0..input_count as WireId).collect(),
+            output_wires: ((wire_count - output_count) as WireId..wire_count as WireId).collect(),
+            wire_count: wire_count as WireId,
+        };
+        let mut defined = vec![false; wire_count];
+        defined[..input_count].iter_mut().for_each(|slot| *slot = true);
+
+        let mut parsed_gates = 0;
+        for (line, tokens) in lines {
+            parsed_gates += 1;
+            let (gate_type, operands) = tokens.split_last().ok_or_else(|| malformed(line, "empty gate"))?;
+            let operands = parse_numbers(line, operands)?;
+            let (&input_len, rest) = operands.split_first().ok_or_else(|| malformed(line, "missing input count"))?;
+            let (&output_len, wires) = rest.split_first().ok_or_else(|| malformed(line, "missing output count"))?;
+            if wires.len() != input_len + output_len {
+                return Err(malformed(line, "wire list does not match the declared counts"));
+            }
+            let (inputs, outputs) = wires.split_at(input_len);
+
+            // EQ 的输入位置是字面量，不是线编号
+            let literal = match (*gate_type, inputs) {
+                ("EQ", [value]) if *value <= 1 => Some(*value == 1),
+                ("EQ", _) => return Err(malformed(line, "EQ takes one literal 0 or 1")),
+                _ => None,
+            };
+            if literal.is_none() {
+                if let Some(&wire) = inputs.iter().find(|&&wire| wire >= wire_count || !defined[wire]) {
+                    return Err(malformed(line, &format!("reads unset wire {}", wire)));
+                }
+            }
+
+            let shape = match *gate_type {
+                "XOR" | "AND" => (2, 1),
+                "INV" | "EQW" | "EQ" => (1, 1),
+                "MAND" if input_len == 2 * output_len && output_len > 0 => (input_len, output_len),
+                _ => return Err(malformed(line, &format!("unsupported gate {} with {} inputs", gate_type, input_len))),
+            };
+            if shape != (input_len, output_len) {
+                return Err(malformed(line, &format!("{} gate cannot take {} inputs and {} outputs", gate_type, input_len, output_len)));
+            }
+
+            for (position, &output) in outputs.iter().enumerate() {
+                if output >= wire_count || defined[output] {
+                    return Err(malformed(line, &format!("wire {} is out of range or driven twice", output)));
+                }
+                defined[output] = true;
+
+                let (gate_type, gate_inputs) = match (*gate_type, literal) {
+                    (_, Some(value)) => (GateType::Const(value), vec![]),
+                    ("XOR", _) => (GateType::Xor, vec![inputs[0], inputs[1]]),
+                    ("AND", _) => (GateType::And, vec![inputs[0], inputs[1]]),
+                    ("MAND", _) => (GateType::And, vec![inputs[position], inputs[output_len + position]]),
+                    ("INV", _) => (GateType::Not, vec![inputs[0]]),
+                    _ => (GateType::Buf, vec![inputs[0]]),
+                };
+                let id = circuit.gates.len() as GateId;
+                circuit.gates.push(Gate::new(
+                    id,
+                    gate_type,
+                    gate_inputs.into_iter().map(|wire| wire as WireId).collect(),
+                    output as WireId,
+                ));
+            }
+        }
+
+        if parsed_gates != gate_count {
+            return Err(MpcError::SerializationError(format!(
+                "Bristol header declares {} gates, found {}", gate_count, parsed_gates
+            )));
+        }
+        if let Some(&wire) = circuit.output_wires.iter().find(|&&wire| !defined[wire as usize]) {
+            return Err(MpcError::SerializationError(format!("Bristol output wire {} is never set", wire)));
+        }
+        Ok(Self { circuit, input_widths, output_widths })
+    }
+
+    /// 读取 Bristol Fashion 文件
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| MpcError::StorageError(format!("Failed to read {}: {}", path.display(), e)))?;
+        Self::parse(&text)
+    }
+
+    /// 写出 Bristol Fashion 文本
+    ///
+    /// 线按格式要求重新编号：输入在前，输出在最后，中间的线按门的拓扑顺序编号。
+    /// NAND、XNOR 写为两个门；输出线直接是输入线或重复出现时补一个 `EQW`。
+    ///
+    /// # 返回值
+    /// 电路结构不合法，或含有 OR、NOR、多扇入门时返回错误（先用
+    /// `Circuit::decompose_fan_in` 等方法改写）
+    pub fn to_bristol(&self) -> Result<String> {
+        let circuit = &self.circuit;
+        let order = checked_gate_order(circuit)?;
+        let input_count = circuit.input_wires.len();
+
+        // 先用临时编号生成门：输入为 0..input_count，每个门的输出依次分配新编号
+        let mut temporary: HashMap<WireId, usize> = HashMap::new();
+        for (position, &wire) in circuit.input_wires.iter().enumerate() {
+            if temporary.insert(wire, position).is_some() {
+                return Err(MpcError::ProtocolError(format!("Input wire {} is listed twice", wire)));
+            }
+        }
+        let mut emitted: Vec<EmittedGate> = Vec::with_capacity(circuit.gates.len());
+        let emit = |emitted: &mut Vec<EmittedGate>, kind, inputs: Vec<usize>, literal| {
+            emitted.push(EmittedGate { kind, inputs, literal });
+            input_count + emitted.len() - 1
+        };
+        for index in order {
+            let gate = &circuit.gates[index];
+            let inputs: Vec<usize> = gate.input_wires.iter().map(|wire| temporary[wire]).collect();
+            let output = match gate.gate_type {
+                GateType::And => emit(&mut emitted, "AND", inputs, None),
+                GateType::Xor => emit(&mut emitted, "XOR", inputs, None),
+                GateType::Not => emit(&mut emitted, "INV", inputs, None),
+                GateType::Buf => emit(&mut emitted, "EQW", inputs, None),
+                GateType::Const(value) => emit(&mut emitted, "EQ", vec![], Some(value)),
+                GateType::Nand => {
+                    let and = emit(&mut emitted, "AND", inputs, None);
+                    emit(&mut emitted, "INV", vec![and], None)
+                }
+                GateType::Xnor => {
+                    let xor = emit(&mut emitted, "XOR", inputs, None);
+                    emit(&mut emitted, "INV", vec![xor], None)
+                }
+                _ => {
+                    return Err(MpcError::ProtocolError(format!(
+                        "{:?} gate {} has no Bristol Fashion encoding", gate.gate_type, gate.id
+                    )));
+                }
+            };
+            temporary.insert(gate.output_wire, output);
+        }
+
+        // 输出线必须是编号最大的线：直接由门产生的输出就地改号，其余补 EQW
+        let mut claimed: HashMap<usize, usize> = HashMap::new();
+        for (position, wire) in circuit.output_wires.iter().enumerate() {
+            let source = temporary[wire];
+            if source >= input_count && !claimed.contains_key(&source) {
+                claimed.insert(source, position);
+            } else {
+                let copy = emit(&mut emitted, "EQW", vec![source], None);
+                claimed.insert(copy, position);
+            }
+        }
+
+        let wire_count = input_count + emitted.len();
+        let first_output = wire_count - circuit.output_wires.len();
+        let mut next_internal = input_count;
+        let mut renumbered: Vec<usize> = (0..wire_count).collect();
+        for (temporary, slot) in renumbered.iter_mut().enumerate().skip(input_count) {
+            *slot = match claimed.get(&temporary) {
+                Some(position) => first_output + position,
+                None => {
+                    next_internal += 1;
+                    next_internal - 1
+                }
+            };
+        }
+
+        let widths = |widths: &[usize]| {
+            std::iter::once(widths.len()).chain(widths.iter().copied())
+                .map(|width| width.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let mut text = format!(
+            "{} {}\n{}\n{}\n\n",
+            emitted.len(), wire_count, widths(&self.input_widths), widths(&self.output_widths)
+        );
+        for (offset, gate) in emitted.iter().enumerate() {
+            let output = renumbered[input_count + offset];
+            let inputs: Vec<String> = match gate.literal {
+                Some(value) => vec![(value as u8).to_string()],
+                None => gate.inputs.iter().map(|&input| renumbered[input].to_string()).collect(),
+            };
+            // 写入 String 不会失败
+            let _ = writeln!(text, "{} 1 {} {} {}", inputs.len(), inputs.join(" "), output, gate.kind);
+        }
+        Ok(text)
+    }
+
+    /// 写出 Bristol Fashion 文件
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_bristol()?)
+            .map_err(|e| MpcError::StorageError(format!("Failed to write {}: {}", path.display(), e)))
+    }
+
+    /// 第 `index` 个输入占用的输入线
+    pub fn input_wires(&self, index: usize) -> Option<&[WireId]> {
+        partition(&self.circuit.input_wires, &self.input_widths, index)
+    }
+
+    /// 第 `index` 个输出占用的输出线
+    pub fn output_wires(&self, index: usize) -> Option<&[WireId]> {
+        partition(&self.circuit.output_wires, &self.output_widths, index)
+    }
+
+    /// AND 门数量（半门混淆下每个 AND 门需要两个密文）
+    pub fn and_count(&self) -> usize {
+        self.circuit.gates.iter().filter(|gate| gate.gate_type == GateType::And).count()
+    }
+}
+
+/// 写出时按临时编号记录的门
+struct EmittedGate {
+    kind: &'static str,
+    inputs: Vec<usize>,
+    literal: Option<bool>,
+}
+
+fn parse_widths(numbers: &[usize]) -> std::result::Result<Vec<usize>, &'static str> {
+    match numbers.split_first() {
+        Some((&count, widths)) if count == widths.len() => Ok(widths.to_vec()),
+        _ => Err("width count does not match the number of widths"),
+    }
+}
+
+fn partition<'a>(wires: &'a [WireId], widths: &[usize], index: usize) -> Option<&'a [WireId]> {
+    let start: usize = widths.get(..index)?.iter().sum();
+    wires.get(start..start + widths.get(index)?)
+}
@@ -0,0 +1,161 @@
+//! # 电路规范形式 (Canonical Circuit Form)
+//!
+//! 双方在提交输入之前需要确认混淆、求值的是同一个函数。`agreed_circuit_digest`
+//! 直接对门列表哈希，同一个电路换一种构造顺序（门的添加顺序、线编号不同）
+//! 就会得到不同的摘要。本模块给出与构造顺序无关的规范形式：
+//!
+//! 1. 每条线得到一个结构哈希：第 i 条输入线为 `H(i)`，门的输出线为
+//!    `H(门类型, 各输入线的结构哈希)`，因此只取决于它计算的子电路
+//! 2. 结构哈希相同的门计算同一个值，只保留一个
+//! 3. 门按 (深度, 结构哈希) 排序，输入线编号为 0..k，门的输出线依次编号为 k..，
+//!    门 ID 等于其位置
+//!
+//! 输入线和输出线的顺序有语义，保持不变；门的输入顺序也保持不变
+//! （`AND(a, b)` 与 `AND(b, a)` 的规范形式不同）。
+//! `Circuit::circuit_digest` 是规范形式的哈希，`GarbledCircuit::circuit_digest`
+//! 只覆盖门结构和输入输出线，不包含混淆表和线标签，因此求值方可以用它
+//! 检查收到的混淆电路实现了约定的函数。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::garbled_circuits::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! // 同一个函数 (a AND b) XOR (NOT a)，门的添加顺序不同
+//! let mut first = Circuit::new();
+//! let (a, b) = (first.add_input_wire(), first.add_input_wire());
+//! let and = first.and_gate(a, b);
+//! let not = first.not_gate(a);
+//! let out = first.xor_gate(and, not);
+//! first.add_output_wire(out);
+//!
+//! let mut second = Circuit::new();
+//! let (a, b) = (second.add_input_wire(), second.add_input_wire());
+//! let not = second.not_gate(a);
+//! let and = second.and_gate(a, b);
+//! let out = second.xor_gate(and, not);
+//! second.add_output_wire(out);
+//!
+//! assert_eq!(first.circuit_digest()?, second.circuit_digest()?);
+//! assert_eq!(first.canonicalize()?, second.canonicalize()?);
+//!
+//! // 求值方在提交输入之前检查混淆电路
+//! let garbled = Garbler::new().garble_circuit(&second)?;
+//! garbled.verify_circuit(&first.circuit_digest()?)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::plan::checked_gate_order;
+use super::*;
+use crate::utils::hash_struct_with_domain;
+use std::collections::HashMap;
+
+/// 线结构哈希的域分隔标签
+const WIRE_HASH_DOMAIN: &[u8] = b"mpc_api/garbled_circuits/canonical/wire";
+
+/// 电路摘要的域分隔标签
+const CIRCUIT_DIGEST_DOMAIN: &[u8] = b"mpc_api/garbled_circuits/canonical/circuit";
+
+impl Circuit {
+    /// 计算电路的规范形式
+    ///
+    /// # 返回值
+    /// 线和门按结构重新编号、去掉重复门之后的电路；电路结构不合法
+    /// （未定义的线、环路、输入数不正确的门等）时返回错误
+    pub fn canonicalize(&self) -> Result<Circuit> {
+        let order = checked_gate_order(self)?;
+
+        // 按拓扑顺序计算每条线的结构哈希和深度
+        let mut wire_hashes: HashMap<WireId, [u8; 32]> = HashMap::new();
+        for (position, &wire) in self.input_wires.iter().enumerate() {
+            wire_hashes.entry(wire).or_insert(hash_struct_with_domain(WIRE_HASH_DOMAIN, &position)?);
+        }
+        let mut depths: HashMap<[u8; 32], usize> = HashMap::new();
+        let mut unique_gates: HashMap<[u8; 32], (usize, &Gate)> = HashMap::new();
+        for index in order {
+            let gate = &self.gates[index];
+            let inputs: Vec<[u8; 32]> = gate.input_wires.iter().map(|wire| wire_hashes[wire]).collect();
+            let hash = hash_struct_with_domain(WIRE_HASH_DOMAIN, &(&gate.gate_type, &inputs))?;
+            let depth = 1 + inputs.iter().map(|input| depths.get(input).copied().unwrap_or(0)).max().unwrap_or(0);
+            wire_hashes.insert(gate.output_wire, hash);
+            depths.insert(hash, depth);
+            unique_gates.entry(hash).or_insert((depth, gate));
+        }
+
+        let mut sorted: Vec<([u8; 32], (usize, &Gate))> = unique_gates.into_iter().collect();
+        sorted.sort_by_key(|&(hash, (depth, _))| (depth, hash));
+
+        let mut canonical_wires: HashMap<[u8; 32], WireId> = HashMap::new();
+        for (position, wire) in self.input_wires.iter().enumerate() {
+            canonical_wires.entry(wire_hashes[wire]).or_insert(position as WireId);
+        }
+        let input_count = self.input_wires.len() as WireId;
+        for (position, (hash, _)) in sorted.iter().enumerate() {
+            canonical_wires.insert(*hash, input_count + position as WireId);
+        }
+        let rename = |wire: &WireId| canonical_wires[&wire_hashes[wire]];
+
+        Ok(Circuit {
+            gates: sorted.iter()
+                .enumerate()
+                .map(|(position, (hash, (_, gate)))| Gate::new(
+                    position as GateId,
+                    gate.gate_type.clone(),
+                    gate.input_wires.iter().map(rename).collect(),
+                    canonical_wires[hash],
+                ))
+                .collect(),
+            input_wires: (0..input_count).collect(),
+            output_wires: self.output_wires.iter().map(rename).collect(),
+            wire_count: input_count + sorted.len() as WireId,
+        })
+    }
+
+    /// 电路的规范摘要
+    ///
+    /// 只取决于电路计算的函数结构，与门的添加顺序和线编号无关。
+    pub fn circuit_digest(&self) -> Result<[u8; 32]> {
+        hash_struct_with_domain(CIRCUIT_DIGEST_DOMAIN, &self.canonicalize()?)
+    }
+}
+
+impl GarbledCircuit {
+    /// 混淆电路对应的明文电路结构（不含混淆表和线标签）
+    pub fn structure(&self) -> Circuit {
+        let wire_count = self.gates.iter()
+            .flat_map(|gate| gate.input_wires.iter().chain([&gate.output_wire]))
+            .chain(&self.input_wires)
+            .chain(&self.output_wires)
+            .max()
+            .map_or(0, |&wire| wire + 1);
+        Circuit {
+            gates: self.gates.iter()
+                .map(|gate| Gate::new(gate.id, gate.gate_type.clone(), gate.input_wires.clone(), gate.output_wire))
+                .collect(),
+            input_wires: self.input_wires.clone(),
+            output_wires: self.output_wires.clone(),
+            wire_count,
+        }
+    }
+
+    /// 混淆电路所实现函数的规范摘要，与 `Circuit::circuit_digest` 可比较
+    pub fn circuit_digest(&self) -> Result<[u8; 32]> {
+        self.structure().circuit_digest()
+    }
+
+    /// 检查混淆电路实现的是摘要对应的函数
+    ///
+    /// # 参数
+    /// - `expected`: 双方约定电路的 `circuit_digest`
+    ///
+    /// # 返回值
+    /// 摘要不一致时返回 `AuthenticationError`
+    pub fn verify_circuit(&self, expected: &[u8; 32]) -> Result<()> {
+        if self.circuit_digest()? != *expected {
+            return Err(MpcError::AuthenticationError("Garbled circuit differs from the agreed circuit".to_string()));
+        }
+        Ok(())
+    }
+}
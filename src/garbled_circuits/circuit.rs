@@ -10,7 +10,7 @@ use super::*;
 /// 
 /// 表示一个完整的布尔电路，包含所有门、输入线、输出线和线计数器。
 /// 这是构建混淆电路的基础数据结构。
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Circuit {
     /// 电路中所有门的列表
     pub gates: Vec<Gate>,
@@ -26,7 +26,7 @@ pub struct Circuit {
 /// 
 /// 表示电路中的一个逻辑门，包含门的类型、输入线和输出线。
 /// 这是电路的基本计算单元。
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Gate {
     /// 门的唯一标识符
     pub id: GateId,
@@ -174,6 +174,306 @@ impl Circuit {
         self.add_gate(GateType::Not, vec![wire])
     }
     
+    /// 添加 NAND 门
+    /// 
+    /// 创建一个 NAND 门，输出 = NOT (wire1 AND wire2)。
+    /// 
+    /// # 参数
+    /// 
+    /// * `wire1` - 第一个输入线
+    /// * `wire2` - 第二个输入线
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 NAND 门的输出线标识符
+    pub fn nand_gate(&mut self, wire1: WireId, wire2: WireId) -> WireId {
+        self.add_gate(GateType::Nand, vec![wire1, wire2])
+    }
+    
+    /// 添加 NOR 门
+    /// 
+    /// 创建一个 NOR 门，输出 = NOT (wire1 OR wire2)。
+    /// 
+    /// # 参数
+    /// 
+    /// * `wire1` - 第一个输入线
+    /// * `wire2` - 第二个输入线
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 NOR 门的输出线标识符
+    pub fn nor_gate(&mut self, wire1: WireId, wire2: WireId) -> WireId {
+        self.add_gate(GateType::Nor, vec![wire1, wire2])
+    }
+    
+    /// 添加 XNOR 门
+    /// 
+    /// 创建一个 XNOR 门，输出 = NOT (wire1 XOR wire2)。
+    /// 
+    /// # 参数
+    /// 
+    /// * `wire1` - 第一个输入线
+    /// * `wire2` - 第二个输入线
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回 XNOR 门的输出线标识符
+    pub fn xnor_gate(&mut self, wire1: WireId, wire2: WireId) -> WireId {
+        self.add_gate(GateType::Xnor, vec![wire1, wire2])
+    }
+    
+    /// 添加缓冲门
+    /// 
+    /// 把输入线复制到一条新线上（Bristol 格式中的 EQW）。
+    /// 
+    /// # 参数
+    /// 
+    /// * `wire` - 输入线
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回缓冲门的输出线标识符
+    pub fn buf_gate(&mut self, wire: WireId) -> WireId {
+        self.add_gate(GateType::Buf, vec![wire])
+    }
+    
+    /// 添加常量线
+    /// 
+    /// 创建一个输出固定值的常量门（Bristol 格式中的 EQ）。
+    /// 
+    /// # 参数
+    /// 
+    /// * `value` - 常量值
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回常量门的输出线标识符
+    pub fn constant(&mut self, value: bool) -> WireId {
+        self.add_gate(GateType::Const(value), Vec::new())
+    }
+    
+    /// 添加多扇入 AND 门
+    /// 
+    /// 输出 = 所有输入的 AND。输入超过 `MAX_NATIVE_FAN_IN` 时需要在混淆前调用
+    /// `decompose_fan_in`。
+    /// 
+    /// # 参数
+    /// 
+    /// * `wires` - 输入线，至少两条
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回多扇入 AND 门的输出线标识符
+    pub fn multi_and_gate(&mut self, wires: &[WireId]) -> WireId {
+        self.add_gate(GateType::MultiAnd, wires.to_vec())
+    }
+    
+    /// 添加多扇入 OR 门
+    /// 
+    /// 输出 = 所有输入的 OR。输入超过 `MAX_NATIVE_FAN_IN` 时需要在混淆前调用
+    /// `decompose_fan_in`。
+    /// 
+    /// # 参数
+    /// 
+    /// * `wires` - 输入线，至少两条
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回多扇入 OR 门的输出线标识符
+    pub fn multi_or_gate(&mut self, wires: &[WireId]) -> WireId {
+        self.add_gate(GateType::MultiOr, wires.to_vec())
+    }
+    
+    /// 分解多扇入门
+    /// 
+    /// 把输入多于 `max_fan_in` 的多扇入 AND/OR 门替换为由两输入门组成的平衡树，
+    /// 树的根仍然输出到原来的输出线，其余线编号保持不变。门按新顺序重新编号。
+    /// 
+    /// # 参数
+    /// 
+    /// * `max_fan_in` - 保留为多扇入门的最大输入数，小于 2 时按 2 处理
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回分解后的电路
+    pub fn decompose_fan_in(&self, max_fan_in: usize) -> Self {
+        let max_fan_in = max_fan_in.max(2);
+        let mut result = Self {
+            gates: Vec::with_capacity(self.gates.len()),
+            input_wires: self.input_wires.clone(),
+            output_wires: self.output_wires.clone(),
+            wire_count: self.wire_count,
+        };
+        
+        for gate in &self.gates {
+            let binary = match gate.gate_type {
+                GateType::MultiAnd => GateType::And,
+                GateType::MultiOr => GateType::Or,
+                _ => {
+                    result.push_gate(gate.gate_type.clone(), gate.input_wires.clone(), gate.output_wire);
+                    continue;
+                }
+            };
+            if gate.input_wires.len() <= max_fan_in {
+                result.push_gate(gate.gate_type.clone(), gate.input_wires.clone(), gate.output_wire);
+                continue;
+            }
+            
+            // 逐层两两合并，最后一次合并写入原输出线
+            let mut layer = gate.input_wires.clone();
+            while layer.len() > 2 {
+                let mut next = Vec::with_capacity(layer.len().div_ceil(2));
+                for pair in layer.chunks(2) {
+                    match pair {
+                        [a, b] => next.push(result.add_gate(binary.clone(), vec![*a, *b])),
+                        [a] => next.push(*a),
+                        _ => unreachable!(),
+                    }
+                }
+                layer = next;
+            }
+            result.push_gate(binary, layer, gate.output_wire);
+        }
+        
+        result
+    }
+    
+    /// 常量折叠
+    /// 
+    /// 把常量门的值沿电路传播并化简：与 0 的 AND 得 0、与 1 的 AND 去掉该输入、
+    /// 与 1 的 XOR 变为 NOT 等；缓冲门被消除，读取其输出的门直接读取其输入。
+    /// 化简为常量的输出线由常量门驱动。线编号保持不变，门按新顺序重新编号。
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回化简后的电路
+    pub fn simplify_constants(&self) -> Self {
+        use std::collections::HashMap;
+        
+        /// 输入线化简后的取值
+        #[derive(Clone, Copy)]
+        enum Signal {
+            Constant(bool),
+            Wire(WireId),
+        }
+        
+        let mut result = Self {
+            gates: Vec::with_capacity(self.gates.len()),
+            input_wires: self.input_wires.clone(),
+            output_wires: Vec::with_capacity(self.output_wires.len()),
+            wire_count: self.wire_count,
+        };
+        let mut signals: HashMap<WireId, Signal> = HashMap::new();
+        let resolve = |signals: &HashMap<WireId, Signal>, wire: WireId| {
+            signals.get(&wire).copied().unwrap_or(Signal::Wire(wire))
+        };
+        
+        for gate in &self.gates {
+            if let GateType::Const(value) = gate.gate_type {
+                signals.insert(gate.output_wire, Signal::Constant(value));
+                continue;
+            }
+            
+            let inputs: Vec<Signal> = gate.input_wires.iter().map(|&wire| resolve(&signals, wire)).collect();
+            let constants: Vec<bool> = inputs.iter()
+                .filter_map(|signal| match signal { Signal::Constant(value) => Some(*value), _ => None })
+                .collect();
+            let wires: Vec<WireId> = inputs.iter()
+                .filter_map(|signal| match signal { Signal::Wire(wire) => Some(*wire), _ => None })
+                .collect();
+            
+            // 没有常量输入的门原样保留（缓冲门除外）
+            if constants.is_empty() && gate.gate_type != GateType::Buf {
+                result.push_gate(gate.gate_type.clone(), wires, gate.output_wire);
+                continue;
+            }
+            
+            // 按 (基本运算, 是否取反) 归类
+            let (base, negate) = match gate.gate_type {
+                GateType::And | GateType::MultiAnd => (GateType::And, false),
+                GateType::Nand => (GateType::And, true),
+                GateType::Or | GateType::MultiOr => (GateType::Or, false),
+                GateType::Nor => (GateType::Or, true),
+                GateType::Xor => (GateType::Xor, false),
+                GateType::Xnor | GateType::Not => (GateType::Xor, true),
+                GateType::Buf => (GateType::Xor, false),
+                GateType::Const(_) | GateType::Input | GateType::Output => {
+                    result.push_gate(gate.gate_type.clone(), wires, gate.output_wire);
+                    continue;
+                }
+            };
+            
+            // 化简后：要么是常量，要么是剩余输入线上的 (基本运算, 是否取反)
+            let (simplified, negate) = match base {
+                GateType::And if constants.contains(&false) => (Signal::Constant(negate), false),
+                GateType::Or if constants.contains(&true) => (Signal::Constant(!negate), false),
+                GateType::Xor => {
+                    let parity = constants.iter().fold(negate, |parity, &bit| parity ^ bit);
+                    match wires.as_slice() {
+                        [] => (Signal::Constant(parity), false),
+                        [wire] => (Signal::Wire(*wire), parity),
+                        _ => {
+                            let gate_type = if parity { GateType::Xnor } else { GateType::Xor };
+                            result.push_gate(gate_type, wires, gate.output_wire);
+                            continue;
+                        }
+                    }
+                }
+                // 剩余的常量都是运算的单位元（AND 的 1、OR 的 0）
+                _ => match wires.as_slice() {
+                    [] => (Signal::Constant((base == GateType::And) ^ negate), false),
+                    [wire] => (Signal::Wire(*wire), negate),
+                    [_, _] => {
+                        let gate_type = match (&base, negate) {
+                            (GateType::And, false) => GateType::And,
+                            (GateType::And, true) => GateType::Nand,
+                            (_, false) => GateType::Or,
+                            (_, true) => GateType::Nor,
+                        };
+                        result.push_gate(gate_type, wires, gate.output_wire);
+                        continue;
+                    }
+                    _ => {
+                        let gate_type = if base == GateType::And { GateType::MultiAnd } else { GateType::MultiOr };
+                        result.push_gate(gate_type, wires, gate.output_wire);
+                        continue;
+                    }
+                },
+            };
+            
+            match (simplified, negate) {
+                (Signal::Wire(wire), true) => {
+                    result.push_gate(GateType::Not, vec![wire], gate.output_wire);
+                }
+                (signal, _) => {
+                    signals.insert(gate.output_wire, signal);
+                }
+            }
+        }
+        
+        // 输出线：别名指向其来源线，常量由常量门驱动
+        let mut constant_outputs = std::collections::HashSet::new();
+        for &wire in &self.output_wires {
+            match resolve(&signals, wire) {
+                Signal::Wire(source) => result.output_wires.push(source),
+                Signal::Constant(value) => {
+                    if constant_outputs.insert(wire) {
+                        result.push_gate(GateType::Const(value), Vec::new(), wire);
+                    }
+                    result.output_wires.push(wire);
+                }
+            }
+        }
+        
+        result
+    }
+    
+    /// 以指定的输出线追加门
+    fn push_gate(&mut self, gate_type: GateType, input_wires: Vec<WireId>, output_wire: WireId) {
+        let id = self.gates.len() as GateId;
+        self.gates.push(Gate::new(id, gate_type, input_wires, output_wire));
+    }
+    
     /// 创建加法器电路
     /// 
     /// 构建一个 n 位二进制加法器电路，可以计算两个 n 位数的和。
@@ -280,4 +580,299 @@ impl Default for Circuit {
     }
 }
 
+/// 带高层组件的电路构建器
+/// 
+/// 在 `Circuit` 的原始门之上提供 n 位整数运算：行波进位和 Kogge-Stone 加法器、
+/// 减法器、比较器、相等判断和多路选择器。整数用线的切片表示，最低位在前。
+/// 组件都按 Free XOR 的代价设计：XOR 和 NOT 不需要密文，开销主要由 AND 门数量决定。
+/// 
+/// 各组件要求两个操作数位宽相同，位宽不一致时 panic。
+/// 
+/// ## 使用示例
+/// 
+/// ```rust
+/// use mpc_api::garbled_circuits::*;
+/// 
+/// # fn main() -> mpc_api::Result<()> {
+/// // 64 位无符号比较：a < b
+/// let mut builder = CircuitBuilder::new();
+/// let a = builder.input_bits(64);
+/// let b = builder.input_bits(64);
+/// let less = builder.less_than(&a, &b);
+/// builder.output(less);
+/// let circuit = builder.finish();
+/// 
+/// let mut inputs = CircuitBuilder::encode(1_000, 64);
+/// inputs.extend(CircuitBuilder::encode(1_001, 64));
+/// assert_eq!(simulate(&circuit, &inputs)?, vec![true]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBuilder {
+    circuit: Circuit,
+}
+
+impl CircuitBuilder {
+    /// 创建空的构建器
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// 在已有电路上继续构建
+    pub fn from_circuit(circuit: Circuit) -> Self {
+        Self { circuit }
+    }
+    
+    /// 把整数编码为 `width` 个比特，最低位在前
+    pub fn encode(value: u64, width: usize) -> Vec<bool> {
+        (0..width).map(|i| i < 64 && (value >> i) & 1 == 1).collect()
+    }
+    
+    /// 把比特（最低位在前）解码为整数，超过 64 位的部分被忽略
+    pub fn decode(bits: &[bool]) -> u64 {
+        bits.iter().take(64).enumerate().fold(0, |value, (i, &bit)| value | (bit as u64) << i)
+    }
+    
+    /// 构建中的电路
+    pub fn circuit(&self) -> &Circuit {
+        &self.circuit
+    }
+    
+    /// 结束构建，返回电路
+    pub fn finish(self) -> Circuit {
+        self.circuit
+    }
+    
+    /// 添加一条输入线
+    pub fn input(&mut self) -> WireId {
+        self.circuit.add_input_wire()
+    }
+    
+    /// 添加 `width` 条输入线，表示一个 `width` 位整数
+    pub fn input_bits(&mut self, width: usize) -> Vec<WireId> {
+        (0..width).map(|_| self.input()).collect()
+    }
+    
+    /// 常量线
+    pub fn constant(&mut self, value: bool) -> WireId {
+        self.circuit.constant(value)
+    }
+    
+    /// 常量整数的 `width` 条线
+    pub fn constant_bits(&mut self, value: u64, width: usize) -> Vec<WireId> {
+        Self::encode(value, width).into_iter().map(|bit| self.constant(bit)).collect()
+    }
+    
+    /// 把线标记为输出
+    pub fn output(&mut self, wire: WireId) {
+        self.circuit.add_output_wire(wire);
+    }
+    
+    /// 把整数的各条线依次标记为输出
+    pub fn output_bits(&mut self, wires: &[WireId]) {
+        for &wire in wires {
+            self.output(wire);
+        }
+    }
+    
+    /// a XOR b
+    pub fn xor(&mut self, a: WireId, b: WireId) -> WireId {
+        self.circuit.xor_gate(a, b)
+    }
+    
+    /// a AND b
+    pub fn and(&mut self, a: WireId, b: WireId) -> WireId {
+        self.circuit.and_gate(a, b)
+    }
+    
+    /// a OR b
+    pub fn or(&mut self, a: WireId, b: WireId) -> WireId {
+        self.circuit.or_gate(a, b)
+    }
+    
+    /// NOT a
+    pub fn not(&mut self, a: WireId) -> WireId {
+        self.circuit.not_gate(a)
+    }
+    
+    /// 逐位 NOT
+    pub fn not_bits(&mut self, a: &[WireId]) -> Vec<WireId> {
+        a.iter().map(|&wire| self.not(wire)).collect()
+    }
+    
+    /// 全加器，返回 (和, 进位)，只用一个 AND 门
+    pub fn full_adder(&mut self, a: WireId, b: WireId, carry: WireId) -> (WireId, WireId) {
+        let a_carry = self.xor(a, carry);
+        let b_carry = self.xor(b, carry);
+        let sum = self.xor(a_carry, b);
+        // 进位 = ((a ⊕ c) ∧ (b ⊕ c)) ⊕ c
+        let both = self.and(a_carry, b_carry);
+        (sum, self.xor(both, carry))
+    }
+    
+    /// 行波进位加法器
+    /// 
+    /// # 参数
+    /// 
+    /// * `a` - 第一个加数
+    /// * `b` - 第二个加数，位宽与 `a` 相同
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回与输入同宽的和以及最高位的进位；n 位需要 n 个 AND 门，深度为 n
+    pub fn ripple_carry_add(&mut self, a: &[WireId], b: &[WireId]) -> (Vec<WireId>, WireId) {
+        self.add_with_carry(a, b, None)
+    }
+    
+    /// Kogge-Stone 并行前缀加法器
+    /// 
+    /// 与行波进位加法器结果相同，AND 深度为 O(log n)，AND 门数量为 O(n log n)；
+    /// 适合按层求值或关心深度的场景。
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回与输入同宽的和以及最高位的进位
+    pub fn kogge_stone_add(&mut self, a: &[WireId], b: &[WireId]) -> (Vec<WireId>, WireId) {
+        check_widths(a, b);
+        let propagate: Vec<WireId> = a.iter().zip(b).map(|(&a, &b)| self.xor(a, b)).collect();
+        let mut generate: Vec<WireId> = a.iter().zip(b).map(|(&a, &b)| self.and(a, b)).collect();
+        let mut group_propagate = propagate.clone();
+        
+        let mut distance = 1;
+        while distance < a.len() {
+            // 从高位向低位更新，读取的仍是上一层的值
+            for i in (distance..a.len()).rev() {
+                // 组生成和组传播互斥，OR 可以用 XOR 代替
+                let carried = self.and(group_propagate[i], generate[i - distance]);
+                generate[i] = self.xor(generate[i], carried);
+                if i >= 2 * distance {
+                    group_propagate[i] = self.and(group_propagate[i], group_propagate[i - distance]);
+                }
+            }
+            distance *= 2;
+        }
+        
+        let mut sum = Vec::with_capacity(a.len());
+        for i in 0..a.len() {
+            sum.push(if i == 0 { propagate[0] } else { self.xor(propagate[i], generate[i - 1]) });
+        }
+        let carry = generate.last().copied().unwrap_or_else(|| self.constant(false));
+        (sum, carry)
+    }
+    
+    /// 减法器 a - b（模 2^n）
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回与输入同宽的差以及借位；借位为 1 当且仅当无符号的 a < b
+    pub fn subtract(&mut self, a: &[WireId], b: &[WireId]) -> (Vec<WireId>, WireId) {
+        // a - b = a + ¬b + 1
+        let not_b = self.not_bits(b);
+        let one = self.constant(true);
+        let (difference, carry) = self.add_with_carry(a, &not_b, Some(one));
+        let borrow = self.not(carry);
+        (difference, borrow)
+    }
+    
+    /// 无符号比较 a < b，n 位需要 n 个 AND 门
+    pub fn less_than(&mut self, a: &[WireId], b: &[WireId]) -> WireId {
+        check_widths(a, b);
+        let mut less = self.constant(false);
+        // 从低位到高位：两位不同时结果由 b 的这一位决定，否则沿用低位的结果
+        for (&a, &b) in a.iter().zip(b) {
+            let differs = self.xor(a, b);
+            let choice = self.xor(less, b);
+            let update = self.and(differs, choice);
+            less = self.xor(less, update);
+        }
+        less
+    }
+    
+    /// 无符号比较 a > b
+    pub fn greater_than(&mut self, a: &[WireId], b: &[WireId]) -> WireId {
+        self.less_than(b, a)
+    }
+    
+    /// 无符号比较 a ≤ b
+    pub fn less_or_equal(&mut self, a: &[WireId], b: &[WireId]) -> WireId {
+        let greater = self.greater_than(a, b);
+        self.not(greater)
+    }
+    
+    /// 无符号比较 a ≥ b
+    pub fn greater_or_equal(&mut self, a: &[WireId], b: &[WireId]) -> WireId {
+        let less = self.less_than(a, b);
+        self.not(less)
+    }
+    
+    /// 二进制补码的有符号比较 a < b
+    pub fn signed_less_than(&mut self, a: &[WireId], b: &[WireId]) -> WireId {
+        check_widths(a, b);
+        let Some((&a_sign, &b_sign)) = a.last().zip(b.last()) else {
+            return self.constant(false);
+        };
+        // 翻转符号位后按无符号比较
+        let mut a = a.to_vec();
+        let mut b = b.to_vec();
+        *a.last_mut().expect("checked above") = self.not(a_sign);
+        *b.last_mut().expect("checked above") = self.not(b_sign);
+        self.less_than(&a, &b)
+    }
+    
+    /// 相等判断 a = b，n 位需要 n - 1 个 AND 门
+    pub fn equal(&mut self, a: &[WireId], b: &[WireId]) -> WireId {
+        check_widths(a, b);
+        let mut layer: Vec<WireId> = a.iter().zip(b).map(|(&a, &b)| self.circuit.xnor_gate(a, b)).collect();
+        if layer.is_empty() {
+            return self.constant(true);
+        }
+        // 平衡的 AND 树，深度为 log n
+        while layer.len() > 1 {
+            layer = layer.chunks(2)
+                .map(|pair| if let [x, y] = *pair { self.and(x, y) } else { pair[0] })
+                .collect();
+        }
+        layer[0]
+    }
+    
+    /// 多路选择器：`select` 为 0 时输出 `if_false`，为 1 时输出 `if_true`
+    /// 
+    /// 每位 out = f ⊕ (s ∧ (f ⊕ t))，只需一个 AND 门。
+    pub fn mux(&mut self, select: WireId, if_false: &[WireId], if_true: &[WireId]) -> Vec<WireId> {
+        check_widths(if_false, if_true);
+        if_false.iter()
+            .zip(if_true)
+            .map(|(&f, &t)| {
+                let differs = self.xor(f, t);
+                let chosen = self.and(select, differs);
+                self.xor(f, chosen)
+            })
+            .collect()
+    }
+    
+    /// 带可选进位输入的行波进位加法
+    fn add_with_carry(&mut self, a: &[WireId], b: &[WireId], carry_in: Option<WireId>) -> (Vec<WireId>, WireId) {
+        check_widths(a, b);
+        let mut carry = carry_in;
+        let mut sum = Vec::with_capacity(a.len());
+        for (&a, &b) in a.iter().zip(b) {
+            let (bit, next) = match carry {
+                Some(carry) => self.full_adder(a, b, carry),
+                // 没有进位输入时最低位使用半加器
+                None => (self.xor(a, b), self.and(a, b)),
+            };
+            sum.push(bit);
+            carry = Some(next);
+        }
+        let carry = carry.unwrap_or_else(|| self.constant(false));
+        (sum, carry)
+    }
+}
+
+/// 检查两个整数操作数的位宽一致
+fn check_widths(a: &[WireId], b: &[WireId]) {
+    assert_eq!(a.len(), b.len(), "operands must have the same width");
+}
+
 // 测试代码已移至 tests/garbled_circuits_tests.rs
\ No newline at end of file
@@ -0,0 +1,334 @@
+//! 混淆电路的紧凑编码
+//!
+//! 混淆表是两方计算中最主要的通信开销。serde 的通用编码（例如 bincode）为每个 ID
+//! 和每个标签都写入定长的长度前缀，紧凑编码则：
+//!
+//! - 线 ID、门 ID 和各种计数都用 LEB128 变长整数编码，小电路中通常只占 1~2 字节
+//! - 门类型使用与混淆表文件相同的单字节编码
+//! - 混淆表的标签首尾相接，前面只有一个变长的标签数
+//! - 线标签按线 ID 排序，线 ID 写成与前一条线的差值
+//!
+//! 编码格式：版本字节，随后依次为混淆门、输入线、输出线和线标签四段，
+//! 每段以变长整数的条目数开头。`GarbledCircuit::compact_size` 不实际编码即可给出
+//! 各部分的准确字节数，`estimate_garbled_size` 在混淆之前根据电路估计同样的数字。
+//!
+//! 编码包含 `wire_labels`，与 serde 序列化一样属于混淆方的秘密。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::garbled_circuits::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let circuit = Circuit::create_adder(4);
+//! let estimate = estimate_garbled_size(&circuit)?;
+//!
+//! let garbled = Garbler::new().garble_circuit(&circuit)?;
+//! let bytes = garbled.to_compact_bytes();
+//! assert_eq!(bytes.len(), estimate.total());
+//! assert_eq!(GarbledCircuit::from_compact_bytes(&bytes)?, garbled);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::*;
+use super::table_file::{gate_type_code, gate_type_from_code};
+
+/// 紧凑编码的格式版本
+pub const COMPACT_FORMAT_VERSION: u8 = 1;
+
+/// 标签的字节长度
+const LABEL_SIZE: usize = 16;
+
+/// 混淆电路紧凑编码的字节数，按内容分为三部分
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GarbledSize {
+    /// 版本字节、门结构（ID、类型、连线）以及输入输出线列表
+    pub structure: usize,
+    /// 混淆表中的标签
+    pub tables: usize,
+    /// 线标签映射表
+    pub wire_labels: usize,
+}
+
+impl GarbledSize {
+    /// 编码的总字节数
+    pub fn total(&self) -> usize {
+        self.structure + self.tables + self.wire_labels
+    }
+}
+
+impl GarbledCircuit {
+    /// 按紧凑格式编码
+    pub fn to_compact_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.compact_size().total());
+        out.push(COMPACT_FORMAT_VERSION);
+
+        write_varint(&mut out, self.gates.len() as u64);
+        for gate in &self.gates {
+            write_varint(&mut out, gate.id as u64);
+            out.push(gate_type_code(&gate.gate_type));
+            write_wire_list(&mut out, &gate.input_wires);
+            write_varint(&mut out, gate.output_wire as u64);
+            // 0 表示没有混淆表，否则为标签数加一
+            match &gate.garbled_table {
+                None => write_varint(&mut out, 0),
+                Some(table) => {
+                    write_varint(&mut out, table.len() as u64 + 1);
+                    for label in table {
+                        out.extend_from_slice(label);
+                    }
+                }
+            }
+        }
+
+        write_wire_list(&mut out, &self.input_wires);
+        write_wire_list(&mut out, &self.output_wires);
+
+        write_varint(&mut out, self.wire_labels.len() as u64);
+        let mut previous = 0;
+        for (wire, (label_0, label_1)) in sorted_wire_labels(&self.wire_labels) {
+            write_varint(&mut out, (wire - previous) as u64);
+            out.extend_from_slice(label_0);
+            out.extend_from_slice(label_1);
+            previous = wire;
+        }
+        out
+    }
+
+    /// 从紧凑格式解码
+    ///
+    /// 版本不符、数据被截断或有多余字节时返回 `SerializationError`
+    pub fn from_compact_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = CompactReader { bytes, position: 0 };
+        let version = reader.byte()?;
+        if version != COMPACT_FORMAT_VERSION {
+            return Err(MpcError::SerializationError(format!(
+                "Unsupported compact garbled circuit version {}", version
+            )));
+        }
+
+        let gate_count = reader.count(1)?;
+        let mut gates = Vec::with_capacity(gate_count);
+        for _ in 0..gate_count {
+            let id = reader.wire()?;
+            let gate_type = gate_type_from_code(reader.byte()?)?;
+            let input_wires = reader.wire_list()?;
+            let output_wire = reader.wire()?;
+            let garbled_table = match reader.varint()? {
+                0 => None,
+                len => {
+                    let len = usize::try_from(len - 1).map_err(|_| truncated())?;
+                    let packed = reader.take(len.checked_mul(LABEL_SIZE).ok_or_else(truncated)?)?;
+                    Some(packed.chunks_exact(LABEL_SIZE).map(|chunk| chunk.try_into().unwrap()).collect())
+                }
+            };
+            gates.push(GarbledGate { id, gate_type, input_wires, output_wire, garbled_table });
+        }
+
+        let input_wires = reader.wire_list()?;
+        let output_wires = reader.wire_list()?;
+
+        let label_count = reader.count(1 + 2 * LABEL_SIZE)?;
+        let mut wire_labels = std::collections::HashMap::with_capacity(label_count);
+        let mut previous: WireId = 0;
+        for index in 0..label_count {
+            let delta = reader.wire()?;
+            if index > 0 && delta == 0 {
+                return Err(MpcError::SerializationError("Duplicate wire in compact wire labels".to_string()));
+            }
+            let wire = previous.checked_add(delta).ok_or_else(truncated)?;
+            let label_0 = reader.label()?;
+            let label_1 = reader.label()?;
+            wire_labels.insert(wire, (label_0, label_1));
+            previous = wire;
+        }
+
+        if reader.position != bytes.len() {
+            return Err(MpcError::SerializationError(format!(
+                "{} trailing bytes after compact garbled circuit", bytes.len() - reader.position
+            )));
+        }
+        Ok(GarbledCircuit { gates, input_wires, output_wires, wire_labels })
+    }
+
+    /// 紧凑编码的准确字节数，不实际编码
+    pub fn compact_size(&self) -> GarbledSize {
+        let mut size = GarbledSize {
+            structure: 1 + varint_len(self.gates.len() as u64)
+                + wire_list_len(&self.input_wires)
+                + wire_list_len(&self.output_wires),
+            ..GarbledSize::default()
+        };
+        for gate in &self.gates {
+            let table_len = gate.garbled_table.as_ref().map(Vec::len);
+            size.structure += gate_structure_len(gate.id, &gate.input_wires, gate.output_wire, table_len);
+            size.tables += table_len.unwrap_or(0) * LABEL_SIZE;
+        }
+
+        size.wire_labels = varint_len(self.wire_labels.len() as u64);
+        let mut previous = 0;
+        for (wire, _) in sorted_wire_labels(&self.wire_labels) {
+            size.wire_labels += varint_len((wire - previous) as u64) + 2 * LABEL_SIZE;
+            previous = wire;
+        }
+        size
+    }
+}
+
+/// 在混淆之前估计 `Garbler` 混淆该电路后紧凑编码的字节数
+///
+/// 混淆表的大小只取决于门类型和扇入，因此估计值与实际编码长度相同。
+/// 电路中有无法混淆的门时返回错误。
+pub fn estimate_garbled_size(circuit: &Circuit) -> Result<GarbledSize> {
+    let mut size = GarbledSize {
+        structure: 1 + varint_len(circuit.gates.len() as u64)
+            + wire_list_len(&circuit.input_wires)
+            + wire_list_len(&circuit.output_wires),
+        ..GarbledSize::default()
+    };
+    for gate in &circuit.gates {
+        let table_len = garbled_table_len(gate)?;
+        size.structure += gate_structure_len(gate.id, &gate.input_wires, gate.output_wire, table_len);
+        size.tables += table_len.unwrap_or(0) * LABEL_SIZE;
+    }
+
+    // 混淆方为 0..wire_count 的每条线生成标签，相邻线 ID 的差值都是 1
+    let wire_count = circuit.wire_count as usize;
+    size.wire_labels = varint_len(wire_count as u64) + wire_count * (1 + 2 * LABEL_SIZE);
+    Ok(size)
+}
+
+/// 混淆后门的混淆表标签数，`None` 表示没有混淆表
+fn garbled_table_len(gate: &Gate) -> Result<Option<usize>> {
+    let fan_in = gate.input_wires.len();
+    match gate.gate_type {
+        GateType::Not | GateType::Buf => Ok(None),
+        GateType::Const(_) => Ok(Some(1)),
+        GateType::MultiAnd | GateType::MultiOr if fan_in > MAX_NATIVE_FAN_IN => Err(MpcError::ProtocolError(format!(
+            "{:?} gate {} has {} inputs; decompose gates wider than {} first",
+            gate.gate_type, gate.id, fan_in, MAX_NATIVE_FAN_IN
+        ))),
+        GateType::Input | GateType::Output => Err(MpcError::ProtocolError("Invalid gate type for garbling".to_string())),
+        _ => Ok(Some((1 << fan_in) * GARBLED_ROW_LABELS)),
+    }
+}
+
+/// 单个门除混淆表标签之外的字节数
+fn gate_structure_len(id: GateId, input_wires: &[WireId], output_wire: WireId, table_len: Option<usize>) -> usize {
+    varint_len(id as u64) + 1 + wire_list_len(input_wires) + varint_len(output_wire as u64)
+        + varint_len(table_len.map_or(0, |len| len as u64 + 1))
+}
+
+fn sorted_wire_labels(
+    wire_labels: &std::collections::HashMap<WireId, (Label, Label)>,
+) -> Vec<(WireId, &(Label, Label))> {
+    let mut sorted: Vec<_> = wire_labels.iter().map(|(&wire, labels)| (wire, labels)).collect();
+    sorted.sort_unstable_by_key(|&(wire, _)| wire);
+    sorted
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn varint_len(value: u64) -> usize {
+    (64 - (value | 1).leading_zeros() as usize).div_ceil(7)
+}
+
+fn write_wire_list(out: &mut Vec<u8>, wires: &[WireId]) {
+    write_varint(out, wires.len() as u64);
+    for &wire in wires {
+        write_varint(out, wire as u64);
+    }
+}
+
+fn wire_list_len(wires: &[WireId]) -> usize {
+    varint_len(wires.len() as u64) + wires.iter().map(|&wire| varint_len(wire as u64)).sum::<usize>()
+}
+
+fn truncated() -> MpcError {
+    MpcError::SerializationError("Truncated or malformed compact garbled circuit".to_string())
+}
+
+struct CompactReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> CompactReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.position.checked_add(len).filter(|&end| end <= self.bytes.len()).ok_or_else(truncated)?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn label(&mut self) -> Result<Label> {
+        Ok(self.take(LABEL_SIZE)?.try_into().unwrap())
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            if shift == 63 && byte > 1 {
+                return Err(truncated());
+            }
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                if shift > 0 && byte == 0 {
+                    // 非最短编码，拒绝以保证编码唯一
+                    return Err(truncated());
+                }
+                return Ok(value);
+            }
+        }
+        Err(truncated())
+    }
+
+    fn wire(&mut self) -> Result<WireId> {
+        WireId::try_from(self.varint()?).map_err(|_| truncated())
+    }
+
+    /// 读取条目数，每个条目至少占 `min_entry_len` 字节，防止伪造的计数导致超大分配
+    fn count(&mut self, min_entry_len: usize) -> Result<usize> {
+        let count = usize::try_from(self.varint()?).map_err(|_| truncated())?;
+        if count.saturating_mul(min_entry_len) > self.bytes.len() - self.position {
+            return Err(truncated());
+        }
+        Ok(count)
+    }
+
+    fn wire_list(&mut self) -> Result<Vec<WireId>> {
+        let count = self.count(1)?;
+        (0..count).map(|_| self.wire()).collect()
+    }
+}
+
+/// 在 serde 结构中以紧凑编码的字节串表示 `GarbledCircuit`
+///
+/// 用法：`#[serde(with = "mpc_api::garbled_circuits::compact::serde_compact")]`
+pub mod serde_compact {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    /// 把混淆电路序列化为紧凑编码的字节串
+    pub fn serialize<S: Serializer>(circuit: &GarbledCircuit, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&circuit.to_compact_bytes())
+    }
+
+    /// 从紧凑编码的字节串反序列化混淆电路
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<GarbledCircuit, D::Error> {
+        let bytes: Vec<u8> = serde::Deserialize::deserialize(deserializer)?;
+        GarbledCircuit::from_compact_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
+}
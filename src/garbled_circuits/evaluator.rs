@@ -27,76 +27,119 @@ impl Evaluator {
         self.wire_state.get_output_labels(&garbled_circuit.output_wires)
     }
     
+    // Evaluate and record every gate's output label for a later audit
+    pub fn evaluate_with_transcript(
+        &mut self,
+        garbled_circuit: &GarbledCircuit,
+        input_labels: &[Label],
+    ) -> Result<EvaluationTranscript> {
+        let output_labels = self.evaluate(garbled_circuit, input_labels)?;
+        let outputs = self.decode_output(&output_labels, garbled_circuit)?;
+        
+        let gate_outputs = garbled_circuit.gates.iter()
+            .map(|gate| {
+                self.wire_state.get_wire_label(gate.output_wire)
+                    .map(|label| (gate.id, label))
+                    .ok_or_else(|| MpcError::ProtocolError("Missing gate output label".to_string()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        
+        Ok(EvaluationTranscript {
+            input_labels: input_labels.to_vec(),
+            gate_outputs,
+            outputs,
+        })
+    }
+    
     fn evaluate_gate(&mut self, gate: &GarbledGate, garbled_circuit: &GarbledCircuit) -> Result<()> {
         match gate.gate_type {
-            GateType::And | GateType::Or | GateType::Xor => {
-                self.evaluate_table_gate(gate, garbled_circuit)
+            GateType::And | GateType::Or | GateType::Xor
+            | GateType::Nand | GateType::Nor | GateType::Xnor => {
+                self.evaluate_table_gate(gate, garbled_circuit, 2)
+            }
+            GateType::MultiAnd | GateType::MultiOr => {
+                if gate.input_wires.len() < 2 || gate.input_wires.len() > MAX_NATIVE_FAN_IN {
+                    return Err(MpcError::ProtocolError(format!(
+                        "Multi fan-in gate {} has {} inputs", gate.id, gate.input_wires.len()
+                    )));
+                }
+                self.evaluate_table_gate(gate, garbled_circuit, gate.input_wires.len())
             }
             GateType::Not => {
-                self.evaluate_not_gate(gate, garbled_circuit)
+                self.evaluate_unary_gate(gate, garbled_circuit, true)
+            }
+            GateType::Buf => {
+                self.evaluate_unary_gate(gate, garbled_circuit, false)
+            }
+            GateType::Const(_) => {
+                self.evaluate_const_gate(gate, garbled_circuit)
             }
             _ => Err(MpcError::ProtocolError("Invalid gate type for evaluation".to_string())),
         }
     }
     
-    fn evaluate_table_gate(&mut self, gate: &GarbledGate, garbled_circuit: &GarbledCircuit) -> Result<()> {
-        if gate.input_wires.len() != 2 {
-            return Err(MpcError::ProtocolError("Table gate must have exactly 2 inputs".to_string()));
+    fn evaluate_table_gate(&mut self, gate: &GarbledGate, garbled_circuit: &GarbledCircuit, fan_in: usize) -> Result<()> {
+        if gate.input_wires.len() != fan_in {
+            return Err(MpcError::ProtocolError(format!("Table gate must have exactly {} inputs", fan_in)));
         }
         
-        let garbled_table = gate.garbled_table.as_ref()
-            .ok_or_else(|| MpcError::ProtocolError("Missing garbled table".to_string()))?;
-        
-        if garbled_table.len() != 4 {
-            return Err(MpcError::ProtocolError("Invalid garbled table size".to_string()));
-        }
+        let garbled_table = gate.garbled_table.as_deref().unwrap_or(&[]);
         
         // Get input labels
-        let input1_label = self.wire_state.get_wire_label(gate.input_wires[0])
-            .ok_or_else(|| MpcError::ProtocolError("Missing input label".to_string()))?;
-        let input2_label = self.wire_state.get_wire_label(gate.input_wires[1])
-            .ok_or_else(|| MpcError::ProtocolError("Missing input label".to_string()))?;
-        
-        // Try to decrypt each entry in the garbled table
-        let combined_input = [input1_label, input2_label].concat();
-        let decryption_key = hash_to_label(&combined_input);
+        let input_labels = gate.input_wires.iter()
+            .map(|&wire| self.wire_state.get_wire_label(wire)
+                .ok_or_else(|| MpcError::ProtocolError("Missing input label".to_string())))
+            .collect::<Result<Vec<_>>>()?;
+        let colored = input_labels.iter().map(|&label| ColoredLabel::new(label)).collect::<Vec<_>>();
         
-        for encrypted_label in garbled_table {
-            let decrypted_label = xor_labels(&decryption_key, encrypted_label);
-            
-            // Check if this is a valid output label by trying to match it with known labels
-            if self.is_valid_output_label(&decrypted_label, gate.output_wire, garbled_circuit) {
-                self.wire_state.set_wire_label(gate.output_wire, decrypted_label);
-                return Ok(());
-            }
+        // Exactly one row must authenticate, and it must be the row the select bits point to
+        let decrypted_label = open_garbled_row(
+            gate.id,
+            garbled_table,
+            1 << fan_in,
+            permute_row_n(&colored),
+            garbled_row_keys_n(&input_labels, gate.id),
+        )?;
+        if !self.is_valid_output_label(&decrypted_label, gate.output_wire, garbled_circuit) {
+            return Err(GarblingIntegrityError::InvalidOutputLabel { gate: gate.id }.into());
         }
         
-        Err(MpcError::ProtocolError("Failed to decrypt garbled table entry".to_string()))
+        self.wire_state.set_wire_label(gate.output_wire, decrypted_label);
+        Ok(())
     }
     
-    
-    fn evaluate_not_gate(&mut self, gate: &GarbledGate, garbled_circuit: &GarbledCircuit) -> Result<()> {
+    fn evaluate_unary_gate(&mut self, gate: &GarbledGate, garbled_circuit: &GarbledCircuit, invert: bool) -> Result<()> {
         if gate.input_wires.len() != 1 {
-            return Err(MpcError::ProtocolError("NOT gate must have exactly 1 input".to_string()));
+            return Err(MpcError::ProtocolError(format!("{:?} gate must have exactly 1 input", gate.gate_type)));
         }
         
         let input_label = self.wire_state.get_wire_label(gate.input_wires[0])
             .ok_or_else(|| MpcError::ProtocolError("Missing input label".to_string()))?;
         
-        // For NOT gate, we need to map to the opposite label
+        // Map to the output label of the same (BUF) or opposite (NOT) value
         let (label_0, label_1) = garbled_circuit.wire_labels[&gate.output_wire];
         let input_wire_labels = garbled_circuit.wire_labels[&gate.input_wires[0]];
         
-        let output_label = if input_label == input_wire_labels.0 {
-            label_1 // input is 0, output is 1
-        } else {
-            label_0 // input is 1, output is 0
-        };
+        let input_bit = input_label != input_wire_labels.0;
+        let output_label = if input_bit != invert { label_1 } else { label_0 };
         
         self.wire_state.set_wire_label(gate.output_wire, output_label);
         Ok(())
     }
     
+    fn evaluate_const_gate(&mut self, gate: &GarbledGate, garbled_circuit: &GarbledCircuit) -> Result<()> {
+        let table = gate.garbled_table.as_deref().unwrap_or(&[]);
+        if table.len() != 1 {
+            return Err(GarblingIntegrityError::MalformedTable { gate: gate.id, len: table.len(), expected: 1 }.into());
+        }
+        if !self.is_valid_output_label(&table[0], gate.output_wire, garbled_circuit) {
+            return Err(GarblingIntegrityError::InvalidOutputLabel { gate: gate.id }.into());
+        }
+        
+        self.wire_state.set_wire_label(gate.output_wire, table[0]);
+        Ok(())
+    }
+    
     fn is_valid_output_label(&self, label: &Label, wire_id: WireId, garbled_circuit: &GarbledCircuit) -> bool {
         if let Some((label_0, label_1)) = garbled_circuit.wire_labels.get(&wire_id) {
             label == label_0 || label == label_1
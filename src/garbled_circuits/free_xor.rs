@@ -1,14 +1,18 @@
 //! Free XOR optimization for garbled circuits
+//!
+//! Labels are elements of GF(2^128): the two labels of a wire differ by the global
+//! offset Δ, and an XOR gate is evaluated by adding its input labels (`gf128_add`).
 
 use super::*;
+use crate::utils::memory::SecretValue;
 
 pub struct FreeXorOptimizer {
-    pub global_offset: Label,
+    pub global_offset: SecretValue<Label>,
 }
 
 impl FreeXorOptimizer {
     pub fn new(global_offset: Label) -> Self {
-        Self { global_offset }
+        Self { global_offset: SecretValue::new(global_offset) }
     }
     
     // Optimize circuit by identifying XOR gates that can use free XOR
@@ -47,7 +51,7 @@ impl FreeXorOptimizer {
     
     // Check if a gate can use free XOR optimization
     pub fn can_use_free_xor(&self, gate: &Gate) -> bool {
-        matches!(gate.gate_type, GateType::Xor | GateType::Not)
+        matches!(gate.gate_type, GateType::Xor | GateType::Xnor | GateType::Not | GateType::Buf)
     }
     
     // Count the number of free gates in a circuit
@@ -86,13 +90,17 @@ impl FreeXorOptimizer {
 // Utility functions for free XOR circuits
 pub fn is_linear_circuit(circuit: &Circuit) -> bool {
     circuit.gates.iter().all(|gate| {
-        matches!(gate.gate_type, GateType::Xor | GateType::Not | GateType::Input | GateType::Output)
+        matches!(
+            gate.gate_type,
+            GateType::Xor | GateType::Xnor | GateType::Not | GateType::Buf | GateType::Const(_)
+                | GateType::Input | GateType::Output
+        )
     })
 }
 
 pub fn count_non_linear_gates(circuit: &Circuit) -> usize {
     circuit.gates.iter()
-        .filter(|gate| matches!(gate.gate_type, GateType::And | GateType::Or))
+        .filter(|gate| !gate.is_linear() && gate.gate_type.is_table_gate())
         .count()
 }
 
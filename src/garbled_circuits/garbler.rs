@@ -3,28 +3,46 @@
 use super::*;
 // use crate::secret_sharing::{FIELD_PRIME, field_add, field_mul}; // Unused imports
 use std::collections::HashMap;
-use rand::thread_rng;
+use rand::{thread_rng, CryptoRng, RngCore};
+use crate::utils::memory::SecretValue;
 
 pub struct Garbler {
-    pub global_offset: Label,
+    /// Free-XOR offset; knowing it lets the evaluator decode every wire, so it is
+    /// zeroized on drop
+    pub global_offset: SecretValue<Label>,
 }
 
 impl Garbler {
     pub fn new() -> Self {
-        let mut rng = thread_rng();
-        let global_offset = generate_random_label(&mut rng);
-        Self { global_offset }
+        Self::with_rng(&mut thread_rng())
+    }
+
+    /// Draws the global offset from the given generator, e.g. a seeded `AesCtrPrg`
+    pub fn with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let mut global_offset = generate_random_label(rng);
+        global_offset[0] |= 1;
+        Self { global_offset: SecretValue::new(global_offset) }
     }
     
     pub fn garble_circuit(&self, circuit: &Circuit) -> Result<GarbledCircuit> {
-        let mut rng = thread_rng();
+        self.garble_circuit_with_rng(circuit, &mut thread_rng())
+    }
+
+    /// Garbles the circuit drawing all wire labels from the given generator, so
+    /// a seeded generator makes the garbled circuit reproducible
+    pub fn garble_circuit_with_rng<R: RngCore + CryptoRng>(&self, circuit: &Circuit, rng: &mut R) -> Result<GarbledCircuit> {
         let mut wire_labels = HashMap::new();
         let mut garbled_gates = Vec::new();
         
+        // The offset must have its low bit set so that the two labels of a wire
+        // carry opposite select bits
+        let mut offset = self.global_offset.clone();
+        offset[0] |= 1;
+        
         // Generate labels for all wires
         for wire_id in 0..circuit.wire_count {
-            let label_0 = generate_random_label(&mut rng);
-            let label_1 = xor_labels(&label_0, &self.global_offset);
+            let label_0 = generate_random_label(rng);
+            let label_1 = xor_labels(&label_0, &offset);
             wire_labels.insert(wire_id, (label_0, label_1));
         }
         
@@ -47,7 +65,15 @@ impl Garbler {
             GateType::And => self.garble_and_gate(gate, wire_labels),
             GateType::Or => self.garble_or_gate(gate, wire_labels),
             GateType::Xor => self.garble_xor_gate(gate, wire_labels),
-            GateType::Not => self.garble_not_gate(gate, wire_labels),
+            GateType::Nand | GateType::Nor | GateType::Xnor => {
+                if gate.input_wires.len() != 2 {
+                    return Err(MpcError::ProtocolError(format!("{:?} gate must have exactly 2 inputs", gate.gate_type)));
+                }
+                self.garble_table_gate(gate, wire_labels)
+            }
+            GateType::MultiAnd | GateType::MultiOr => self.garble_multi_fan_in_gate(gate, wire_labels),
+            GateType::Not | GateType::Buf => self.garble_unary_gate(gate, wire_labels),
+            GateType::Const(value) => self.garble_const_gate(gate, value, wire_labels),
             _ => Err(MpcError::ProtocolError("Invalid gate type for garbling".to_string())),
         }
     }
@@ -57,40 +83,8 @@ impl Garbler {
             return Err(MpcError::ProtocolError("AND gate must have exactly 2 inputs".to_string()));
         }
         
-        let (a0, a1) = wire_labels[&gate.input_wires[0]];
-        let (b0, b1) = wire_labels[&gate.input_wires[1]];
-        let (c0, c1) = wire_labels[&gate.output_wire];
-        
-        // Create garbled table for AND gate
         // Truth table: 00->0, 01->0, 10->0, 11->1
-        let mut garbled_table = Vec::new();
-        
-        // Entry for (0,0) -> 0
-        let entry_00 = self.encrypt_label(&[a0, b0], &c0);
-        garbled_table.push(entry_00);
-        
-        // Entry for (0,1) -> 0
-        let entry_01 = self.encrypt_label(&[a0, b1], &c0);
-        garbled_table.push(entry_01);
-        
-        // Entry for (1,0) -> 0
-        let entry_10 = self.encrypt_label(&[a1, b0], &c0);
-        garbled_table.push(entry_10);
-        
-        // Entry for (1,1) -> 1
-        let entry_11 = self.encrypt_label(&[a1, b1], &c1);
-        garbled_table.push(entry_11);
-        
-        // Shuffle the table to hide the true mapping
-        self.shuffle_garbled_table(&mut garbled_table);
-        
-        Ok(GarbledGate {
-            id: gate.id,
-            gate_type: gate.gate_type.clone(),
-            input_wires: gate.input_wires.clone(),
-            output_wire: gate.output_wire,
-            garbled_table: Some(garbled_table),
-        })
+        self.garble_table_gate(gate, wire_labels)
     }
     
     fn garble_or_gate(&self, gate: &Gate, wire_labels: &HashMap<WireId, (Label, Label)>) -> Result<GarbledGate> {
@@ -98,39 +92,8 @@ impl Garbler {
             return Err(MpcError::ProtocolError("OR gate must have exactly 2 inputs".to_string()));
         }
         
-        let (a0, a1) = wire_labels[&gate.input_wires[0]];
-        let (b0, b1) = wire_labels[&gate.input_wires[1]];
-        let (c0, c1) = wire_labels[&gate.output_wire];
-        
-        // Create garbled table for OR gate
         // Truth table: 00->0, 01->1, 10->1, 11->1
-        let mut garbled_table = Vec::new();
-        
-        // Entry for (0,0) -> 0
-        let entry_00 = self.encrypt_label(&[a0, b0], &c0);
-        garbled_table.push(entry_00);
-        
-        // Entry for (0,1) -> 1
-        let entry_01 = self.encrypt_label(&[a0, b1], &c1);
-        garbled_table.push(entry_01);
-        
-        // Entry for (1,0) -> 1
-        let entry_10 = self.encrypt_label(&[a1, b0], &c1);
-        garbled_table.push(entry_10);
-        
-        // Entry for (1,1) -> 1
-        let entry_11 = self.encrypt_label(&[a1, b1], &c1);
-        garbled_table.push(entry_11);
-        
-        self.shuffle_garbled_table(&mut garbled_table);
-        
-        Ok(GarbledGate {
-            id: gate.id,
-            gate_type: gate.gate_type.clone(),
-            input_wires: gate.input_wires.clone(),
-            output_wire: gate.output_wire,
-            garbled_table: Some(garbled_table),
-        })
+        self.garble_table_gate(gate, wire_labels)
     }
     
     fn garble_xor_gate(&self, gate: &Gate, wire_labels: &HashMap<WireId, (Label, Label)>) -> Result<GarbledGate> {
@@ -138,32 +101,35 @@ impl Garbler {
             return Err(MpcError::ProtocolError("XOR gate must have exactly 2 inputs".to_string()));
         }
         
-        let (a0, a1) = wire_labels[&gate.input_wires[0]];
-        let (b0, b1) = wire_labels[&gate.input_wires[1]];
-        let (c0, c1) = wire_labels[&gate.output_wire];
-        
-        // Create garbled table for XOR gate
         // Truth table: 00->0, 01->1, 10->1, 11->0
-        let mut garbled_table = Vec::new();
-        
-        // Entry for (0,0) -> 0
-        let entry_00 = self.encrypt_label(&[a0, b0], &c0);
-        garbled_table.push(entry_00);
-        
-        // Entry for (0,1) -> 1
-        let entry_01 = self.encrypt_label(&[a0, b1], &c1);
-        garbled_table.push(entry_01);
-        
-        // Entry for (1,0) -> 1
-        let entry_10 = self.encrypt_label(&[a1, b0], &c1);
-        garbled_table.push(entry_10);
-        
-        // Entry for (1,1) -> 0
-        let entry_11 = self.encrypt_label(&[a1, b1], &c0);
-        garbled_table.push(entry_11);
+        self.garble_table_gate(gate, wire_labels)
+    }
+    
+    fn garble_multi_fan_in_gate(&self, gate: &Gate, wire_labels: &HashMap<WireId, (Label, Label)>) -> Result<GarbledGate> {
+        let fan_in = gate.input_wires.len();
+        if fan_in < 2 {
+            return Err(MpcError::ProtocolError(format!("{:?} gate must have at least 2 inputs", gate.gate_type)));
+        }
+        if fan_in > MAX_NATIVE_FAN_IN {
+            return Err(MpcError::ProtocolError(format!(
+                "{:?} gate {} has {} inputs; decompose gates wider than {} first",
+                gate.gate_type, gate.id, fan_in, MAX_NATIVE_FAN_IN
+            )));
+        }
         
-        // Shuffle the table to hide the true mapping
-        self.shuffle_garbled_table(&mut garbled_table);
+        self.garble_table_gate(gate, wire_labels)
+    }
+    
+    fn garble_table_gate(
+        &self,
+        gate: &Gate,
+        wire_labels: &HashMap<WireId, (Label, Label)>,
+    ) -> Result<GarbledGate> {
+        let inputs = gate.input_wires.iter()
+            .map(|wire| wire_labels[wire])
+            .collect::<Vec<_>>();
+        let truth_table = truth_table(&gate.gate_type, inputs.len())?;
+        let garbled_table = encrypt_table(gate.id, &inputs, wire_labels[&gate.output_wire], &truth_table);
         
         Ok(GarbledGate {
             id: gate.id,
@@ -174,35 +140,35 @@ impl Garbler {
         })
     }
     
-    fn garble_not_gate(&self, gate: &Gate, _wire_labels: &HashMap<WireId, (Label, Label)>) -> Result<GarbledGate> {
+    fn garble_unary_gate(&self, gate: &Gate, _wire_labels: &HashMap<WireId, (Label, Label)>) -> Result<GarbledGate> {
         if gate.input_wires.len() != 1 {
-            return Err(MpcError::ProtocolError("NOT gate must have exactly 1 input".to_string()));
+            return Err(MpcError::ProtocolError(format!("{:?} gate must have exactly 1 input", gate.gate_type)));
         }
         
-        // NOT gate just swaps the wire labels
+        // NOT swaps the wire labels and BUF copies them
         Ok(GarbledGate {
             id: gate.id,
             gate_type: gate.gate_type.clone(),
             input_wires: gate.input_wires.clone(),
             output_wire: gate.output_wire,
-            garbled_table: None, // No table needed for NOT
+            garbled_table: None, // No table needed for unary gates
         })
     }
     
-    fn encrypt_label(&self, input_labels: &[Label], output_label: &Label) -> Label {
-        let mut combined_input = Vec::new();
-        for label in input_labels {
-            combined_input.extend_from_slice(label);
+    fn garble_const_gate(&self, gate: &Gate, value: bool, wire_labels: &HashMap<WireId, (Label, Label)>) -> Result<GarbledGate> {
+        if !gate.input_wires.is_empty() {
+            return Err(MpcError::ProtocolError("Constant gate must not have inputs".to_string()));
         }
         
-        let key = hash_to_label(&combined_input);
-        xor_labels(&key, output_label)
-    }
-    
-    fn shuffle_garbled_table(&self, table: &mut [Label]) {
-        // For simplicity, we'll use a deterministic shuffle based on the labels
-        // In practice, you might want a more sophisticated shuffling mechanism
-        table.sort();
+        // The value is public, so the active label is published in the table
+        let (label_0, label_1) = wire_labels[&gate.output_wire];
+        Ok(GarbledGate {
+            id: gate.id,
+            gate_type: gate.gate_type.clone(),
+            input_wires: Vec::new(),
+            output_wire: gate.output_wire,
+            garbled_table: Some(vec![if value { label_1 } else { label_0 }]),
+        })
     }
     
     pub fn get_input_labels(&self, garbled_circuit: &GarbledCircuit, inputs: &[bool]) -> Result<Vec<Label>> {
@@ -222,6 +188,46 @@ impl Garbler {
     }
 }
 
+// Truth table of a gate, indexed by the input assignment with the first input
+// as the most significant bit
+pub(crate) fn truth_table(gate_type: &GateType, fan_in: usize) -> Result<Vec<bool>> {
+    (0..1usize << fan_in)
+        .map(|assignment| {
+            let bits = (0..fan_in)
+                .map(|i| (assignment >> (fan_in - 1 - i)) & 1 == 1)
+                .collect::<Vec<_>>();
+            gate_type.apply(&bits)
+        })
+        .collect()
+}
+
+// Encrypt a truth table under the input labels of a gate
+pub(crate) fn encrypt_table(
+    gate_id: GateId,
+    inputs: &[(Label, Label)],
+    (c0, c1): (Label, Label),
+    truth_table: &[bool],
+) -> Vec<Label> {
+    // Point-and-permute: each row sits at the index given by the select bits
+    // of its input labels, followed by a tag that authenticates the row
+    let mut garbled_table = vec![[0u8; 16]; truth_table.len() * GARBLED_ROW_LABELS];
+    for (assignment, &value) in truth_table.iter().enumerate() {
+        let labels = inputs.iter().enumerate()
+            .map(|(i, &(label_0, label_1))| {
+                if (assignment >> (inputs.len() - 1 - i)) & 1 == 1 { label_1 } else { label_0 }
+            })
+            .collect::<Vec<_>>();
+        let colored = labels.iter().map(|&label| ColoredLabel::new(label)).collect::<Vec<_>>();
+        let output = if value { c1 } else { c0 };
+        
+        let (pad, tag) = garbled_row_keys_n(&labels, gate_id);
+        let row = permute_row_n(&colored) * GARBLED_ROW_LABELS;
+        garbled_table[row] = xor_labels(&pad, &output);
+        garbled_table[row + 1] = tag;
+    }
+    garbled_table
+}
+
 impl Default for Garbler {
     fn default() -> Self {
         Self::new()
@@ -2,6 +2,40 @@
 
 use super::*;
 
+impl GateType {
+    // Apply the gate's boolean function to plaintext inputs
+    pub fn apply(&self, inputs: &[bool]) -> Result<bool> {
+        match (self, inputs) {
+            (GateType::And, [a, b]) => Ok(*a && *b),
+            (GateType::Or, [a, b]) => Ok(*a || *b),
+            (GateType::Xor, [a, b]) => Ok(a ^ b),
+            (GateType::Nand, [a, b]) => Ok(!(*a && *b)),
+            (GateType::Nor, [a, b]) => Ok(!(*a || *b)),
+            (GateType::Xnor, [a, b]) => Ok(a == b),
+            (GateType::Not, [a]) => Ok(!a),
+            (GateType::Buf, [a]) => Ok(*a),
+            (GateType::Const(value), []) => Ok(*value),
+            (GateType::MultiAnd, inputs) if inputs.len() >= 2 => Ok(inputs.iter().all(|&bit| bit)),
+            (GateType::MultiOr, inputs) if inputs.len() >= 2 => Ok(inputs.iter().any(|&bit| bit)),
+            (GateType::Input | GateType::Output, _) => {
+                Err(MpcError::ProtocolError("Input/Output gates cannot be evaluated".to_string()))
+            }
+            _ => Err(MpcError::ProtocolError(format!(
+                "{:?} gate cannot take {} inputs", self, inputs.len()
+            ))),
+        }
+    }
+
+    // Gates garbled as a point-and-permute table with one row per input combination
+    pub fn is_table_gate(&self) -> bool {
+        matches!(
+            self,
+            GateType::And | GateType::Or | GateType::Xor | GateType::Nand | GateType::Nor
+                | GateType::Xnor | GateType::MultiAnd | GateType::MultiOr
+        )
+    }
+}
+
 impl Gate {
     pub fn new(id: GateId, gate_type: GateType, input_wires: Vec<WireId>, output_wire: WireId) -> Self {
         Self {
@@ -11,50 +45,32 @@ impl Gate {
             output_wire,
         }
     }
-    
+
     pub fn evaluate(&self, inputs: &[bool]) -> Result<bool> {
-        match self.gate_type {
-            GateType::And => {
-                if inputs.len() != 2 {
-                    return Err(MpcError::ProtocolError("AND gate requires exactly 2 inputs".to_string()));
-                }
-                Ok(inputs[0] && inputs[1])
-            }
-            GateType::Or => {
-                if inputs.len() != 2 {
-                    return Err(MpcError::ProtocolError("OR gate requires exactly 2 inputs".to_string()));
-                }
-                Ok(inputs[0] || inputs[1])
-            }
-            GateType::Xor => {
-                if inputs.len() != 2 {
-                    return Err(MpcError::ProtocolError("XOR gate requires exactly 2 inputs".to_string()));
-                }
-                Ok(inputs[0] ^ inputs[1])
-            }
-            GateType::Not => {
-                if inputs.len() != 1 {
-                    return Err(MpcError::ProtocolError("NOT gate requires exactly 1 input".to_string()));
-                }
-                Ok(!inputs[0])
-            }
-            GateType::Input | GateType::Output => {
-                Err(MpcError::ProtocolError("Input/Output gates cannot be evaluated".to_string()))
-            }
+        if inputs.len() != self.input_count() {
+            return Err(MpcError::ProtocolError(format!(
+                "{:?} gate requires exactly {} inputs", self.gate_type, self.input_count()
+            )));
         }
+        self.gate_type.apply(inputs)
     }
-    
+
     pub fn input_count(&self) -> usize {
         match self.gate_type {
-            GateType::And | GateType::Or | GateType::Xor => 2,
-            GateType::Not => 1,
-            GateType::Input => 0,
+            GateType::And | GateType::Or | GateType::Xor
+            | GateType::Nand | GateType::Nor | GateType::Xnor => 2,
+            GateType::Not | GateType::Buf => 1,
+            GateType::MultiAnd | GateType::MultiOr => self.input_wires.len(),
+            GateType::Const(_) | GateType::Input => 0,
             GateType::Output => 1,
         }
     }
-    
+
     pub fn is_linear(&self) -> bool {
-        matches!(self.gate_type, GateType::Xor | GateType::Not)
+        matches!(
+            self.gate_type,
+            GateType::Xor | GateType::Xnor | GateType::Not | GateType::Buf | GateType::Const(_)
+        )
     }
 }
 
@@ -68,21 +84,22 @@ impl GarbledGate {
             garbled_table: None,
         }
     }
-    
+
     pub fn with_table(mut self, table: Vec<Label>) -> Self {
         self.garbled_table = Some(table);
         self
     }
-    
+
     pub fn is_free(&self) -> bool {
-        // Free gates don't require a garbled table (XOR, NOT)
-        matches!(self.gate_type, GateType::Xor | GateType::Not)
+        // Free gates don't require a garbled table (XOR, XNOR, NOT, BUF)
+        matches!(self.gate_type, GateType::Xor | GateType::Xnor | GateType::Not | GateType::Buf)
     }
-    
+
     pub fn table_size(&self) -> usize {
         match self.gate_type {
-            GateType::And | GateType::Or => 4, // 2^2 entries
-            GateType::Xor | GateType::Not => 0, // Free gates
+            GateType::And | GateType::Or | GateType::Nand | GateType::Nor => 4, // 2^2 entries
+            GateType::MultiAnd | GateType::MultiOr => 1 << self.input_wires.len(),
+            GateType::Xor | GateType::Xnor | GateType::Not | GateType::Buf => 0, // Free gates
             _ => 0,
         }
     }
@@ -0,0 +1,352 @@
+//! # 半门混淆 (Half-Gates Garbling)
+//!
+//! `Garbler` 为每个门生成四行 SHA-256 混淆表，便于审计和逐门调试；吞吐量敏感的
+//! 场景（例如每秒上千次 AES 求值）使用本模块的半门方案（Zahur–Rosulek–Evans 2015）：
+//!
+//! - **Free XOR**: 线标签满足 W¹ = W⁰ ⊕ Δ，XOR 和 NOT 门不需要密文
+//! - **半门**: 每个 AND 门拆成混淆方半门和求值方半门，只需两个 128 位密文，
+//!   混淆方调用 4 次哈希，求值方调用 2 次
+//! - **定钥 AES 哈希**: H(x, j) = π(σ(x) ⊕ j) ⊕ σ(x) ⊕ j，π 是公开固定密钥的 AES-128，
+//!   σ 是 GF(2^128) 中乘以 2；同一个门的哈希成批调用 AES，硬件支持 AES-NI 时
+//!   每个分组只需几个时钟周期
+//!
+//! 电路先编译为只含 AND、XOR、NOT、复制和常量的指令序列（OR、NAND、多扇入门等
+//! 在编译时分解），之后可以反复混淆、求值。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::garbled_circuits::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let circuit = HalfGatesCircuit::compile(&Circuit::create_adder(2))?;
+//! let (garbled, encoding) = circuit.garble(&mut rand::thread_rng());
+//! assert_eq!(garbled.tables.len(), circuit.and_count());
+//!
+//! // a = 3, b = 1，输入按 a0, b0, a1, b1 交错排列
+//! let labels = encoding.encode(&[true, true, true, false])?;
+//! let outputs = circuit.evaluate(&garbled, &labels)?;
+//! assert_eq!(garbled.decode(&outputs)?, vec![false, false, true]);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::plan::checked_gate_order;
+use super::*;
+use crate::utils::math::gf2k::gf128_double;
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::{Aes128, Block};
+use rand::{CryptoRng, Rng};
+use std::sync::OnceLock;
+
+/// 定钥 AES 的公开密钥
+const FIXED_KEY: [u8; 16] = *b"mpc_api/halfgate";
+
+/// 编译后的一条指令，操作数为稠密的线下标
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Xor { a: usize, b: usize, out: usize },
+    Not { a: usize, out: usize },
+    Copy { a: usize, out: usize },
+    And { a: usize, b: usize, out: usize },
+    Const { value: bool, out: usize },
+}
+
+/// 编译为半门指令序列的电路
+#[derive(Debug, Clone, PartialEq)]
+pub struct HalfGatesCircuit {
+    instructions: Vec<Instruction>,
+    input_wires: Vec<usize>,
+    output_wires: Vec<usize>,
+    wire_count: usize,
+    and_count: usize,
+}
+
+/// 半门混淆电路：发给求值方的全部材料
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HalfGatesGarbledCircuit {
+    /// 按 AND 门顺序排列的 (混淆方半门, 求值方半门) 密文
+    pub tables: Vec<[Label; 2]>,
+    /// 每条输出线 0 标签的选择位
+    pub decoding: Vec<bool>,
+}
+
+/// 混淆方保留的输入编码：每条输入线的 0 标签和全局偏移 Δ
+#[derive(Clone)]
+pub struct InputEncoding {
+    zero_labels: Vec<u128>,
+    delta: u128,
+}
+
+impl std::fmt::Debug for InputEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputEncoding").field("inputs", &self.zero_labels.len()).finish_non_exhaustive()
+    }
+}
+
+impl InputEncoding {
+    /// 输入线数量
+    pub fn len(&self) -> usize {
+        self.zero_labels.len()
+    }
+
+    /// 是否没有输入线
+    pub fn is_empty(&self) -> bool {
+        self.zero_labels.is_empty()
+    }
+
+    /// 第 `position` 条输入线的 (0 标签, 1 标签)，用作 OT 的两个消息
+    pub fn label_pair(&self, position: usize) -> Option<(Label, Label)> {
+        let zero = *self.zero_labels.get(position)?;
+        Some((zero.to_le_bytes(), (zero ^ self.delta).to_le_bytes()))
+    }
+
+    /// 第 `position` 条输入线上取值为 `bit` 的标签
+    pub fn label(&self, position: usize, bit: bool) -> Option<Label> {
+        self.label_pair(position).map(|(zero, one)| if bit { one } else { zero })
+    }
+
+    /// 按输入线顺序编码全部输入
+    pub fn encode(&self, inputs: &[bool]) -> Result<Vec<Label>> {
+        if inputs.len() != self.zero_labels.len() {
+            return Err(MpcError::ProtocolError("Input length mismatch".to_string()));
+        }
+        Ok(inputs.iter()
+            .enumerate()
+            .filter_map(|(position, &bit)| self.label(position, bit))
+            .collect())
+    }
+}
+
+impl HalfGatesCircuit {
+    /// 把电路编译为半门指令序列
+    ///
+    /// # 返回值
+    /// 电路结构不合法（未定义的线、环路、输入数不正确的门等）时返回错误
+    pub fn compile(circuit: &Circuit) -> Result<Self> {
+        let order = checked_gate_order(circuit)?;
+        let mut wire_count = circuit.wire_count as usize;
+        let mut instructions = Vec::with_capacity(circuit.gates.len());
+        let mut temporary = || {
+            wire_count += 1;
+            wire_count - 1
+        };
+
+        for index in order {
+            let gate = &circuit.gates[index];
+            let inputs: Vec<usize> = gate.input_wires.iter().map(|&wire| wire as usize).collect();
+            let out = gate.output_wire as usize;
+            match gate.gate_type {
+                GateType::And => instructions.push(Instruction::And { a: inputs[0], b: inputs[1], out }),
+                GateType::Xor => instructions.push(Instruction::Xor { a: inputs[0], b: inputs[1], out }),
+                GateType::Not => instructions.push(Instruction::Not { a: inputs[0], out }),
+                GateType::Buf => instructions.push(Instruction::Copy { a: inputs[0], out }),
+                GateType::Const(value) => instructions.push(Instruction::Const { value, out }),
+                GateType::Nand | GateType::Xnor => {
+                    let inner = temporary();
+                    instructions.push(match gate.gate_type {
+                        GateType::Nand => Instruction::And { a: inputs[0], b: inputs[1], out: inner },
+                        _ => Instruction::Xor { a: inputs[0], b: inputs[1], out: inner },
+                    });
+                    instructions.push(Instruction::Not { a: inner, out });
+                }
+                GateType::MultiAnd => {
+                    let mut acc = inputs[0];
+                    for (position, &input) in inputs.iter().enumerate().skip(1) {
+                        let target = if position + 1 == inputs.len() { out } else { temporary() };
+                        instructions.push(Instruction::And { a: acc, b: input, out: target });
+                        acc = target;
+                    }
+                }
+                // a ∨ b = ¬(¬a ∧ ¬b)，NOR 省掉最后一次取反
+                GateType::Or | GateType::Nor | GateType::MultiOr => {
+                    let negated: Vec<usize> = inputs.iter()
+                        .map(|&input| {
+                            let target = temporary();
+                            instructions.push(Instruction::Not { a: input, out: target });
+                            target
+                        })
+                        .collect();
+                    let mut acc = negated[0];
+                    for &input in &negated[1..] {
+                        let target = temporary();
+                        instructions.push(Instruction::And { a: acc, b: input, out: target });
+                        acc = target;
+                    }
+                    instructions.push(match gate.gate_type {
+                        GateType::Nor => Instruction::Copy { a: acc, out },
+                        _ => Instruction::Not { a: acc, out },
+                    });
+                }
+                GateType::Input | GateType::Output => unreachable!("rejected by checked_gate_order"),
+            }
+        }
+
+        let and_count = instructions.iter().filter(|instruction| matches!(instruction, Instruction::And { .. })).count();
+        Ok(Self {
+            instructions,
+            input_wires: circuit.input_wires.iter().map(|&wire| wire as usize).collect(),
+            output_wires: circuit.output_wires.iter().map(|&wire| wire as usize).collect(),
+            wire_count,
+            and_count,
+        })
+    }
+
+    /// AND 门数量（混淆电路大小为 32 字节 × AND 门数量）
+    pub fn and_count(&self) -> usize {
+        self.and_count
+    }
+
+    /// 输入线数量
+    pub fn input_count(&self) -> usize {
+        self.input_wires.len()
+    }
+
+    /// 输出线数量
+    pub fn output_count(&self) -> usize {
+        self.output_wires.len()
+    }
+
+    /// 用新的随机标签混淆电路
+    ///
+    /// # 返回值
+    /// 发给求值方的混淆电路，以及混淆方保留的输入编码
+    pub fn garble<R: Rng + CryptoRng>(&self, rng: &mut R) -> (HalfGatesGarbledCircuit, InputEncoding) {
+        let hasher = FixedKeyHash::shared();
+        let delta = rng.gen::<u128>() | 1;
+        let mut labels = vec![0u128; self.wire_count];
+        let zero_labels: Vec<u128> = self.input_wires.iter()
+            .map(|&wire| {
+                labels[wire] = rng.gen();
+                labels[wire]
+            })
+            .collect();
+
+        let mut tables = Vec::with_capacity(self.and_count);
+        for instruction in &self.instructions {
+            match *instruction {
+                Instruction::Xor { a, b, out } => labels[out] = labels[a] ^ labels[b],
+                Instruction::Not { a, out } => labels[out] = labels[a] ^ delta,
+                Instruction::Copy { a, out } => labels[out] = labels[a],
+                // 求值方对常量线持有全 0 标签
+                Instruction::Const { value, out } => labels[out] = if value { delta } else { 0 },
+                Instruction::And { a, b, out } => {
+                    let gate = tables.len() as u128;
+                    let (a0, b0) = (labels[a], labels[b]);
+                    let (pa, pb) = (a0 & 1 == 1, b0 & 1 == 1);
+                    let [ha0, ha1, hb0, hb1] = hasher.hash_four(
+                        [a0, a0 ^ delta, b0, b0 ^ delta],
+                        [2 * gate, 2 * gate, 2 * gate + 1, 2 * gate + 1],
+                    );
+
+                    // 混淆方半门：混淆方知道 pb，求值方持有 a
+                    let generator_table = ha0 ^ ha1 ^ select(pb, delta);
+                    let generator_zero = ha0 ^ select(pa, generator_table);
+                    // 求值方半门：求值方知道 b 的选择位
+                    let evaluator_table = hb0 ^ hb1 ^ a0;
+                    let evaluator_zero = hb0 ^ select(pb, evaluator_table ^ a0);
+
+                    labels[out] = generator_zero ^ evaluator_zero;
+                    tables.push([generator_table.to_le_bytes(), evaluator_table.to_le_bytes()]);
+                }
+            }
+        }
+
+        let decoding = self.output_wires.iter().map(|&wire| labels[wire] & 1 == 1).collect();
+        (HalfGatesGarbledCircuit { tables, decoding }, InputEncoding { zero_labels, delta })
+    }
+
+    /// 用输入标签求值
+    ///
+    /// # 参数
+    /// - `garbled`: 混淆方发来的混淆电路
+    /// - `inputs`: 按输入线顺序排列的标签
+    ///
+    /// # 返回值
+    /// 输出线上的标签；混淆表数量或输入数量不匹配时返回错误
+    pub fn evaluate(&self, garbled: &HalfGatesGarbledCircuit, inputs: &[Label]) -> Result<Vec<Label>> {
+        if inputs.len() != self.input_wires.len() {
+            return Err(MpcError::ProtocolError("Input label count mismatch".to_string()));
+        }
+        if garbled.tables.len() != self.and_count || garbled.decoding.len() != self.output_wires.len() {
+            return Err(MpcError::ProtocolError("Garbled circuit does not match the compiled circuit".to_string()));
+        }
+
+        let hasher = FixedKeyHash::shared();
+        let mut labels = vec![0u128; self.wire_count];
+        for (&wire, label) in self.input_wires.iter().zip(inputs) {
+            labels[wire] = u128::from_le_bytes(*label);
+        }
+
+        let mut tables = garbled.tables.iter();
+        for instruction in &self.instructions {
+            match *instruction {
+                Instruction::Xor { a, b, out } => labels[out] = labels[a] ^ labels[b],
+                Instruction::Not { a, out } | Instruction::Copy { a, out } => labels[out] = labels[a],
+                Instruction::Const { out, .. } => labels[out] = 0,
+                Instruction::And { a, b, out } => {
+                    let gate = (self.and_count - tables.len()) as u128;
+                    let [generator_table, evaluator_table] = tables.next().expect("table count checked above");
+                    let (wa, wb) = (labels[a], labels[b]);
+                    let [ha, hb] = hasher.hash_two([wa, wb], [2 * gate, 2 * gate + 1]);
+
+                    let generator_half = ha ^ select(wa & 1 == 1, u128::from_le_bytes(*generator_table));
+                    let evaluator_half = hb ^ select(wb & 1 == 1, u128::from_le_bytes(*evaluator_table) ^ wa);
+                    labels[out] = generator_half ^ evaluator_half;
+                }
+            }
+        }
+
+        Ok(self.output_wires.iter().map(|&wire| labels[wire].to_le_bytes()).collect())
+    }
+}
+
+impl HalfGatesGarbledCircuit {
+    /// 用解码信息把输出标签还原为比特
+    pub fn decode(&self, outputs: &[Label]) -> Result<Vec<bool>> {
+        if outputs.len() != self.decoding.len() {
+            return Err(MpcError::ProtocolError("Output label count mismatch".to_string()));
+        }
+        Ok(outputs.iter()
+            .zip(&self.decoding)
+            .map(|(label, &zero_bit)| (label[0] & 1 == 1) ^ zero_bit)
+            .collect())
+    }
+
+    /// 序列化后的大小估计（字节）
+    pub fn size_in_bytes(&self) -> usize {
+        self.tables.len() * 2 * std::mem::size_of::<Label>() + self.decoding.len()
+    }
+}
+
+fn select(bit: bool, value: u128) -> u128 {
+    value & (bit as u128).wrapping_neg()
+}
+
+/// 定钥 AES 构造的可调哈希
+struct FixedKeyHash {
+    cipher: Aes128,
+}
+
+impl FixedKeyHash {
+    fn shared() -> &'static Self {
+        static HASHER: OnceLock<FixedKeyHash> = OnceLock::new();
+        HASHER.get_or_init(|| Self { cipher: Aes128::new(GenericArray::from_slice(&FIXED_KEY)) })
+    }
+
+    fn hash<const N: usize>(&self, inputs: [u128; N], tweaks: [u128; N]) -> [u128; N] {
+        let masked: [u128; N] = std::array::from_fn(|i| gf128_double(inputs[i]) ^ tweaks[i]);
+        let mut blocks: [Block; N] = masked.map(|value| Block::from(value.to_le_bytes()));
+        self.cipher.encrypt_blocks(&mut blocks);
+        std::array::from_fn(|i| u128::from_le_bytes(blocks[i].into()) ^ masked[i])
+    }
+
+    fn hash_two(&self, inputs: [u128; 2], tweaks: [u128; 2]) -> [u128; 2] {
+        self.hash(inputs, tweaks)
+    }
+
+    fn hash_four(&self, inputs: [u128; 4], tweaks: [u128; 4]) -> [u128; 4] {
+        self.hash(inputs, tweaks)
+    }
+}
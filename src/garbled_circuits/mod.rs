@@ -6,7 +6,8 @@
 //! ## 核心概念
 //! 
 //! ### 混淆电路原理
-//! - **电路表示**: 将计算表示为布尔电路（AND、OR、XOR、NOT 门）
+//! - **电路表示**: 将计算表示为布尔电路（AND、OR、XOR、NOT 门，以及 NAND、NOR、XNOR、
+//!   缓冲、常量和多扇入 AND/OR 门）
 //! - **线标签**: 每条线有两个随机标签，分别代表 0 和 1
 //! - **门混淆**: 将真值表加密，隐藏门的功能
 //! - **求值**: 使用输入标签逐门计算，得到输出标签
@@ -19,9 +20,67 @@
 //! ## 优化技术
 //! 
 //! - **Free XOR**: XOR 门无需混淆表，提高效率
-//! - **Point-and-Permute**: 减少解密尝试次数
+//! - **Point-and-Permute**: 每个标签携带选择位（颜色位），求值方按输入标签的颜色位
+//!   直接定位混淆表中的行；每行附带认证标签，求值方要求恰好一行通过认证，
+//!   否则返回 `GarblingIntegrityError`
 //! - **Row Reduction**: 减少混淆表大小
 //! 
+//! ## 扩展门
+//! 
+//! NAND、NOR、XNOR 与 AND/OR 一样直接混淆为四行表；不超过 `MAX_NATIVE_FAN_IN`
+//! 个输入的多扇入 AND/OR 门直接混淆为 2^k 行表，更多输入时用
+//! `Circuit::decompose_fan_in` 分解为两输入门。`Circuit::simplify_constants`
+//! 折叠常量输入并消除缓冲门，导入的电路（例如 Bristol 格式中的 INV/EQW/EQ）
+//! 无需手工改写即可混淆。
+//! 
+//! ## 电路构建
+//! 
+//! `CircuitBuilder` 在原始门之上提供 n 位整数组件：行波进位和 Kogge-Stone 加法器、
+//! 减法器、无符号/有符号比较器、相等判断和多路选择器，无需手工连接上百个门。
+//! 
+//! ## 审计
+//! 
+//! `audit` 子模块把混淆电路、输入标签承诺和求值转录导出为一个可验证的产物，
+//! 第三方可以据此检查求值方确实运行了约定的电路（用于争议处理）。
+//! 
+//! ## 调试
+//! 
+//! `simulator` 子模块在明文布尔值上逐门模拟电路，并把混淆求值转录与模拟结果
+//! 逐门比较，报告第一个出现分歧的门。
+//! 
+//! ## 重复混淆
+//! 
+//! `plan` 子模块把电路的拓扑检查、门排序和真值表计算提取为 `GarblePlan`，
+//! 同一电路需要多次混淆时只做一次，之后每次 `garble_with_seed` 只生成新的标签。
+//! 
+//! ## 规范形式
+//! 
+//! `canonical_form` 子模块为 `Circuit` 计算与构造顺序无关的规范形式和摘要
+//! （`circuit_digest`），双方可以在提交输入之前确认混淆、求值的是同一个函数。
+//! `Circuit`、`GarbledCircuit` 都支持 serde 序列化。
+//! 
+//! ## 紧凑编码
+//! 
+//! `compact` 子模块为 `GarbledCircuit` 提供紧凑的二进制编码（线 ID 变长编码、
+//! 混淆表标签连续存放），并能在混淆之前估计编码大小（`estimate_garbled_size`），
+//! 用于评估两方计算的通信开销。
+//! 
+//! ## 混淆表文件
+//! 
+//! `table_file` 子模块把混淆门逐条写入按页对齐的内存映射文件（可选逐条加密），
+//! 求值方可以顺序读取数 GB 的混淆表而不必全部载入内存。
+//! 
+//! ## 半门与标准电路
+//! 
+//! `half_gates` 子模块实现半门混淆（每个 AND 门两个密文，定钥 AES 哈希），
+//! 用于吞吐量敏感的场景；`bristol` 子模块读写 Bristol Fashion 格式的标准电路，
+//! `aes_circuit` 子模块提供 AES-128 电路并识别标准电路文件的输入布局。
+//! 
+//! ## 两方计算
+//! 
+//! `two_party` 子模块把半门混淆和基础 OT 组合成完整的 Yao 协议（`Yao2PC`）：
+//! 求值方通过 OT 取得自己输入对应的标签，混淆方不知道求值方的输入。
+//! 
 //! ## 使用示例
 //! 
 //! ```rust
@@ -48,6 +107,16 @@ pub mod wire;
 pub mod garbler;
 pub mod evaluator;
 pub mod free_xor;
+pub mod audit;
+pub mod simulator;
+pub mod plan;
+pub mod table_file;
+pub mod canonical_form;
+pub mod bristol;
+pub mod half_gates;
+pub mod aes_circuit;
+pub mod two_party;
+pub mod compact;
 
 pub use circuit::*;
 // Import from where they are actually defined
@@ -56,11 +125,22 @@ pub use wire::*;
 pub use garbler::*;
 pub use evaluator::*;
 pub use free_xor::*;
+pub use audit::*;
+pub use simulator::*;
+pub use plan::*;
+pub use table_file::*;
+pub use bristol::*;
+pub use half_gates::*;
+pub use aes_circuit::*;
+pub use two_party::*;
+pub use compact::*;
 
+use crate::utils::math::gf2k::gf128_add;
 use crate::{MpcError, Result};
 use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
 use rand::RngCore;
+use thiserror::Error;
 
 /// 线标签类型，128 位随机值
 /// 
@@ -68,6 +148,63 @@ use rand::RngCore;
 /// 标签的随机性确保了混淆电路的安全性。
 pub type Label = [u8; 16];
 
+/// 混淆表中每一行占用的标签数量（密文 + 认证标签）
+pub const GARBLED_ROW_LABELS: usize = 2;
+
+/// 带选择位的线标签
+/// 
+/// 选择位（颜色位）取自标签的最低位。混淆方保证同一条线的两个标签颜色相反，
+/// 因此颜色位不泄露逻辑值，但可以确定混淆表中需要解密的行。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColoredLabel {
+    /// 线标签
+    pub label: Label,
+    /// 选择位
+    pub color: bool,
+}
+
+impl ColoredLabel {
+    /// 从标签构造，颜色位取标签最低位
+    pub fn new(label: Label) -> Self {
+        Self {
+            label,
+            color: label[0] & 1 == 1,
+        }
+    }
+}
+
+/// 两个输入标签在混淆表中选中的行
+pub fn permute_row(a: &ColoredLabel, b: &ColoredLabel) -> usize {
+    permute_row_n(&[*a, *b])
+}
+
+/// 多个输入标签在混淆表中选中的行，第一个输入对应最高位
+pub fn permute_row_n(inputs: &[ColoredLabel]) -> usize {
+    inputs.iter().fold(0, |row, input| (row << 1) | input.color as usize)
+}
+
+/// 混淆完整性错误
+/// 
+/// 求值方在混淆表不符合协议时返回的具体错误，避免静默地解码出错误的输出。
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GarblingIntegrityError {
+    /// 混淆表缺失或长度不正确
+    #[error("gate {gate}: garbled table has {len} labels, expected {expected}")]
+    MalformedTable { gate: GateId, len: usize, expected: usize },
+    /// 没有任何一行通过认证
+    #[error("gate {gate}: no garbled row authenticates")]
+    NoValidRow { gate: GateId },
+    /// 多行同时通过认证
+    #[error("gate {gate}: rows {rows:?} all authenticate")]
+    AmbiguousRows { gate: GateId, rows: Vec<usize> },
+    /// 通过认证的行与选择位指向的行不一致
+    #[error("gate {gate}: select bits point to row {expected} but row {found} authenticates")]
+    SelectBitMismatch { gate: GateId, expected: usize, found: usize },
+    /// 解密得到的标签不是输出线的合法标签
+    #[error("gate {gate}: decrypted label is not a valid output label")]
+    InvalidOutputLabel { gate: GateId },
+}
+
 /// 线标识符类型
 /// 
 /// 用于唯一标识电路中的每条线。
@@ -95,13 +232,32 @@ pub enum GateType {
     Input,
     /// 输出门：电路的输出点
     Output,
+    /// NAND 门：输出 = NOT (输入1 AND 输入2)
+    Nand,
+    /// NOR 门：输出 = NOT (输入1 OR 输入2)
+    Nor,
+    /// XNOR 门：输出 = NOT (输入1 XOR 输入2)，即相等判断
+    Xnor,
+    /// 缓冲门：输出 = 输入，对应 Bristol 格式中的 EQW
+    Buf,
+    /// 常量门：没有输入，输出固定值，对应 Bristol 格式中的 EQ
+    Const(bool),
+    /// 多扇入 AND 门：所有输入都为 1 时输出 1
+    MultiAnd,
+    /// 多扇入 OR 门：任一输入为 1 时输出 1
+    MultiOr,
 }
 
+/// 直接混淆的多扇入门允许的最大输入数
+///
+/// 混淆表有 2^k 行，更多输入的门需要先用 `Circuit::decompose_fan_in` 分解为两输入门。
+pub const MAX_NATIVE_FAN_IN: usize = 4;
+
 /// 混淆门结构
 /// 
 /// 表示混淆电路中的一个门，包含门的类型、连接的线和混淆表。
 /// 混淆表包含加密的真值表，用于在求值时计算输出标签。
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GarbledGate {
     /// 门的唯一标识符
     pub id: GateId,
@@ -119,7 +275,10 @@ pub struct GarbledGate {
 /// 
 /// 表示完整的混淆电路，包含所有混淆门、输入输出线和线标签。
 /// 这是混淆电路的核心数据结构。
-#[derive(Debug, Clone)]
+/// 
+/// 序列化结果包含 `wire_labels` 中每条线的两个标签，属于混淆方的秘密，
+/// 不能原样发送给求值方。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GarbledCircuit {
     /// 电路中所有混淆门的列表
     pub gates: Vec<GarbledGate>,
@@ -153,9 +312,69 @@ pub fn hash_to_label(input: &[u8]) -> Label {
     label
 }
 
+/// 混淆表行的加密密钥和认证标签
+/// 
+/// 两者都由两个输入标签和门 ID 派生，返回 (密钥流, 认证标签)。
+pub(crate) fn garbled_row_keys(a: &Label, b: &Label, gate_id: GateId) -> (Label, Label) {
+    garbled_row_keys_n(&[*a, *b], gate_id)
+}
+
+/// 多输入混淆表行的加密密钥和认证标签，两输入时与 `garbled_row_keys` 相同
+pub(crate) fn garbled_row_keys_n(inputs: &[Label], gate_id: GateId) -> (Label, Label) {
+    let mut material = Vec::with_capacity(16 * inputs.len() + 4);
+    for input in inputs {
+        material.extend_from_slice(input);
+    }
+    material.extend_from_slice(&gate_id.to_le_bytes());
+
+    let pad = hash_to_label(&[b"pad".as_slice(), &material].concat());
+    let tag = hash_to_label(&[b"tag".as_slice(), &material].concat());
+    (pad, tag)
+}
+
+/// 按选择位打开混淆表中的一行
+/// 
+/// 要求恰好一行通过认证，且该行就是选择位指向的行，返回解密得到的标签。
+pub(crate) fn open_garbled_row(
+    gate: GateId,
+    table: &[Label],
+    rows: usize,
+    expected: usize,
+    (pad, tag): (Label, Label),
+) -> Result<Label> {
+    let expected_len = rows * GARBLED_ROW_LABELS;
+    if table.len() != expected_len {
+        return Err(GarblingIntegrityError::MalformedTable {
+            gate,
+            len: table.len(),
+            expected: expected_len,
+        }.into());
+    }
+    
+    let authenticated: Vec<usize> = table.chunks(GARBLED_ROW_LABELS)
+        .enumerate()
+        .filter(|(_, row)| row[1] == tag)
+        .map(|(index, _)| index)
+        .collect();
+    
+    match authenticated.as_slice() {
+        [] => Err(GarblingIntegrityError::NoValidRow { gate }.into()),
+        [row] if *row == expected => Ok(xor_labels(&pad, &table[row * GARBLED_ROW_LABELS])),
+        [row] => Err(GarblingIntegrityError::SelectBitMismatch {
+            gate,
+            expected,
+            found: *row,
+        }.into()),
+        rows => Err(GarblingIntegrityError::AmbiguousRows {
+            gate,
+            rows: rows.to_vec(),
+        }.into()),
+    }
+}
+
 /// 计算两个标签的异或
 /// 
-/// 对两个 128 位标签进行逐字节异或运算。这是 Free XOR 优化的核心操作，
+/// 把标签看作 GF(2^128) 元素相加（即按位异或）。这是 Free XOR 优化的核心操作，
 /// 允许 XOR 门无需混淆表即可计算。
 /// 
 /// # 参数
@@ -167,11 +386,7 @@ pub fn hash_to_label(input: &[u8]) -> Label {
 /// 
 /// 返回两个标签的异或结果
 pub fn xor_labels(a: &Label, b: &Label) -> Label {
-    let mut result = [0u8; 16];
-    for i in 0..16 {
-        result[i] = a[i] ^ b[i];
-    }
-    result
+    gf128_add(u128::from_le_bytes(*a), u128::from_le_bytes(*b)).to_le_bytes()
 }
 
 /// 生成随机线标签
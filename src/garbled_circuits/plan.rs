@@ -0,0 +1,274 @@
+//! # 混淆计划 (Garble Plan)
+//!
+//! 同一个函数需要反复求值时（例如每次会话都重新混淆同一个比较电路），
+//! 电路结构的检查、拓扑排序和真值表计算每次都是相同的。`GarblePlan` 只做一次
+//! 这些工作，之后每次 `garble_with_seed` 只生成新的线标签并加密混淆表：
+//!
+//! - **拓扑**: 检查每个门的输入数和线编号，按依赖关系排序（已排好序的电路保持原顺序）
+//! - **真值表**: 表门的 2^k 行真值预先计算，混淆时直接按行加密
+//! - **全局偏移**: 每次混淆都从种子重新生成，同一种子得到完全相同的混淆电路，
+//!   便于 cut-and-choose 中按种子重放检查
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::garbled_circuits::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let plan = GarblePlan::new(&Circuit::create_adder(2))?;
+//! for seed in 0..3u8 {
+//!     let garbled = plan.garble_with_seed([seed; 32]);
+//!     // a = 3, b = 1，输入按 a0, b0, a1, b1 交错排列
+//!     let labels = Garbler::new().get_input_labels(&garbled, &[true, true, true, false])?;
+//!     let mut evaluator = Evaluator::new();
+//!     let outputs = evaluator.evaluate(&garbled, &labels)?;
+//!     assert_eq!(evaluator.decode_output(&outputs, &garbled)?, vec![false, false, true]);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::garbler::{encrypt_table, truth_table};
+use super::*;
+use crate::utils::crypto::{AesCtrPrg, SeedableRng};
+use crate::utils::memory::SecretValue;
+use rand::{CryptoRng, Rng, RngCore};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// 门在混淆时需要做的工作
+#[derive(Debug, Clone)]
+enum GateRecipe {
+    /// 按预先计算的真值表加密
+    Table(Vec<bool>),
+    /// NOT / BUF，不需要混淆表
+    Unary,
+    /// 公开常量，发布对应的标签
+    Const(bool),
+}
+
+#[derive(Debug, Clone)]
+struct PlannedGate {
+    id: GateId,
+    gate_type: GateType,
+    input_wires: Vec<WireId>,
+    output_wire: WireId,
+    recipe: GateRecipe,
+}
+
+/// 预处理过的电路结构，可以用新的随机性反复混淆
+#[derive(Debug, Clone)]
+pub struct GarblePlan {
+    /// 按拓扑顺序排列的门
+    gates: Vec<PlannedGate>,
+    input_wires: Vec<WireId>,
+    output_wires: Vec<WireId>,
+    wire_count: u32,
+}
+
+impl GarblePlan {
+    /// 检查电路并生成混淆计划
+    ///
+    /// # 参数
+    /// - `circuit`: 要混淆的电路；输入多于 `MAX_NATIVE_FAN_IN` 的门需要先分解
+    ///
+    /// # 返回值
+    /// 电路中存在未定义的线、重复驱动的线、环路或输入数不正确的门时返回错误
+    pub fn new(circuit: &Circuit) -> Result<Self> {
+        let order = checked_gate_order(circuit)?;
+        let gates = order.into_iter()
+            .map(|index| {
+                let gate = &circuit.gates[index];
+                let recipe = match gate.gate_type {
+                    GateType::Not | GateType::Buf => GateRecipe::Unary,
+                    GateType::Const(value) => GateRecipe::Const(value),
+                    _ => GateRecipe::Table(truth_table(&gate.gate_type, gate.input_wires.len())?),
+                };
+                Ok(PlannedGate {
+                    id: gate.id,
+                    gate_type: gate.gate_type.clone(),
+                    input_wires: gate.input_wires.clone(),
+                    output_wire: gate.output_wire,
+                    recipe,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            gates,
+            input_wires: circuit.input_wires.clone(),
+            output_wires: circuit.output_wires.clone(),
+            wire_count: circuit.wire_count,
+        })
+    }
+
+    /// 门的数量
+    pub fn gate_count(&self) -> usize {
+        self.gates.len()
+    }
+
+    /// 需要混淆表的门的数量
+    pub fn table_gate_count(&self) -> usize {
+        self.gates.iter().filter(|gate| matches!(gate.recipe, GateRecipe::Table(_))).count()
+    }
+
+    /// 按拓扑顺序排列的门 ID
+    pub fn gate_order(&self) -> Vec<GateId> {
+        self.gates.iter().map(|gate| gate.id).collect()
+    }
+
+    /// 用种子生成的随机性混淆电路
+    ///
+    /// 全局偏移和所有线标签都由种子经 AES 计数器模式展开得到，同一种子总是得到相同的混淆电路。
+    /// 种子必须保密且每次执行都不相同。
+    pub fn garble_with_seed(&self, seed: [u8; 32]) -> GarbledCircuit {
+        self.garble_with_rng(&mut AesCtrPrg::from_seed(seed))
+    }
+
+    /// 用给定的随机数生成器混淆电路
+    pub fn garble_with_rng<R: RngCore + CryptoRng>(&self, rng: &mut R) -> GarbledCircuit {
+        // The offset must have its low bit set so that the two labels of a wire
+        // carry opposite select bits
+        let mut offset = SecretValue::new(generate_random_label(rng));
+        offset[0] |= 1;
+        let labels: Vec<(Label, Label)> = (0..self.wire_count)
+            .map(|_| {
+                let label_0 = generate_random_label(rng);
+                (label_0, xor_labels(&label_0, &offset))
+            })
+            .collect();
+
+        let gates = self.gates.iter()
+            .map(|gate| {
+                let (label_0, label_1) = labels[gate.output_wire as usize];
+                let garbled_table = match &gate.recipe {
+                    GateRecipe::Table(truth) => {
+                        let inputs = gate.input_wires.iter()
+                            .map(|&wire| labels[wire as usize])
+                            .collect::<Vec<_>>();
+                        Some(encrypt_table(gate.id, &inputs, (label_0, label_1), truth))
+                    }
+                    GateRecipe::Unary => None,
+                    GateRecipe::Const(value) => Some(vec![if *value { label_1 } else { label_0 }]),
+                };
+                GarbledGate {
+                    id: gate.id,
+                    gate_type: gate.gate_type.clone(),
+                    input_wires: gate.input_wires.clone(),
+                    output_wire: gate.output_wire,
+                    garbled_table,
+                }
+            })
+            .collect();
+
+        GarbledCircuit {
+            gates,
+            input_wires: self.input_wires.clone(),
+            output_wires: self.output_wires.clone(),
+            wire_labels: labels.into_iter()
+                .enumerate()
+                .map(|(wire, pair)| (wire as WireId, pair))
+                .collect(),
+        }
+    }
+
+    /// 用新鲜的随机种子混淆电路
+    pub fn garble(&self) -> GarbledCircuit {
+        self.garble_with_seed(rand::thread_rng().gen())
+    }
+}
+
+/// 检查电路结构并给出门的拓扑顺序（门在 `circuit.gates` 中的下标）
+///
+/// 电路中存在未定义的线、重复驱动的线、环路、输入数不正确的门或从未赋值的输出线时返回错误。
+pub(super) fn checked_gate_order(circuit: &Circuit) -> Result<Vec<usize>> {
+    let mut drivers = HashMap::new();
+    for wire in &circuit.input_wires {
+        drivers.insert(*wire, None);
+    }
+    for (index, gate) in circuit.gates.iter().enumerate() {
+        check_arity(gate)?;
+        if let Some(wire) = gate.input_wires.iter().chain([&gate.output_wire]).find(|&&wire| wire >= circuit.wire_count) {
+            return Err(MpcError::ProtocolError(format!(
+                "Gate {} uses wire {} outside the circuit", gate.id, wire
+            )));
+        }
+        if drivers.insert(gate.output_wire, Some(index)).is_some() {
+            return Err(MpcError::ProtocolError(format!(
+                "Wire {} is driven more than once", gate.output_wire
+            )));
+        }
+    }
+
+    let order = topological_order(circuit, &drivers)?;
+    for wire in &circuit.output_wires {
+        if !drivers.contains_key(wire) {
+            return Err(MpcError::ProtocolError(format!("Output wire {} is never set", wire)));
+        }
+    }
+    Ok(order)
+}
+
+fn check_arity(gate: &Gate) -> Result<()> {
+    let fan_in = gate.input_wires.len();
+    let valid = match gate.gate_type {
+        GateType::And | GateType::Or | GateType::Xor
+        | GateType::Nand | GateType::Nor | GateType::Xnor => fan_in == 2,
+        GateType::MultiAnd | GateType::MultiOr => (2..=MAX_NATIVE_FAN_IN).contains(&fan_in),
+        GateType::Not | GateType::Buf => fan_in == 1,
+        GateType::Const(_) => fan_in == 0,
+        GateType::Input | GateType::Output => {
+            return Err(MpcError::ProtocolError("Invalid gate type for garbling".to_string()));
+        }
+    };
+    if !valid {
+        return Err(MpcError::ProtocolError(format!(
+            "{:?} gate {} cannot take {} inputs", gate.gate_type, gate.id, fan_in
+        )));
+    }
+    Ok(())
+}
+
+// Kahn's algorithm, always releasing the lowest gate index first so that an
+// already sorted circuit keeps its order
+fn topological_order(circuit: &Circuit, drivers: &HashMap<WireId, Option<usize>>) -> Result<Vec<usize>> {
+    let mut pending = vec![0usize; circuit.gates.len()];
+    let mut dependents: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (index, gate) in circuit.gates.iter().enumerate() {
+        for wire in &gate.input_wires {
+            match drivers.get(wire) {
+                Some(Some(driver)) => {
+                    pending[index] += 1;
+                    dependents.entry(*driver).or_default().push(index);
+                }
+                Some(None) => {}
+                None => {
+                    return Err(MpcError::ProtocolError(format!(
+                        "Gate {} reads unset wire {}", gate.id, wire
+                    )));
+                }
+            }
+        }
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> = pending.iter()
+        .enumerate()
+        .filter(|(_, &count)| count == 0)
+        .map(|(index, _)| Reverse(index))
+        .collect();
+    let mut order = Vec::with_capacity(circuit.gates.len());
+    while let Some(Reverse(index)) = ready.pop() {
+        order.push(index);
+        for &dependent in dependents.get(&index).into_iter().flatten() {
+            pending[dependent] -= 1;
+            if pending[dependent] == 0 {
+                ready.push(Reverse(dependent));
+            }
+        }
+    }
+
+    if order.len() != circuit.gates.len() {
+        return Err(MpcError::ProtocolError("Circuit contains a cycle".to_string()));
+    }
+    Ok(order)
+}
@@ -0,0 +1,171 @@
+//! # 明文电路模拟器 (Plaintext Circuit Simulator)
+//!
+//! 不使用线标签，直接在布尔值上逐门计算电路，用于调试和测试：
+//! 把混淆电路求值转录中的每个门输出标签解码为比特，与模拟结果逐门比较，
+//! 出现分歧时报告第一个出错的门。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::garbled_circuits::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let circuit = Circuit::create_adder(2);
+//! // a = 3, b = 1，输入按 a0, b0, a1, b1 交错排列
+//! let inputs = [true, true, true, false];
+//! assert_eq!(simulate(&circuit, &inputs)?, vec![false, false, true]);
+//!
+//! let report = cross_check_garbled(&circuit, &inputs)?;
+//! assert!(report.is_consistent());
+//! # Ok(())
+//! # }
+//! ```
+
+use super::*;
+use std::collections::HashMap;
+
+/// 明文模拟得到的各线取值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationTrace {
+    /// 每条线的取值
+    pub wire_values: HashMap<WireId, bool>,
+    /// 按门顺序记录的每个门的输出
+    pub gate_outputs: Vec<(GateId, bool)>,
+    /// 输出线的取值
+    pub outputs: Vec<bool>,
+}
+
+/// 明文模拟电路，返回每条线的取值
+///
+/// # 参数
+/// - `circuit`: 要模拟的电路
+/// - `inputs`: 按 `input_wires` 顺序给出的输入
+pub fn simulate_trace(circuit: &Circuit, inputs: &[bool]) -> Result<SimulationTrace> {
+    if inputs.len() != circuit.input_wires.len() {
+        return Err(MpcError::ProtocolError("Input length mismatch".to_string()));
+    }
+
+    let mut wire_values: HashMap<WireId, bool> = circuit.input_wires.iter().copied().zip(inputs.iter().copied()).collect();
+    let mut gate_outputs = Vec::with_capacity(circuit.gates.len());
+    for gate in &circuit.gates {
+        let gate_inputs = gate.input_wires.iter()
+            .map(|wire| wire_values.get(wire).copied().ok_or_else(|| {
+                MpcError::ProtocolError(format!("Gate {} reads unset wire {}", gate.id, wire))
+            }))
+            .collect::<Result<Vec<bool>>>()?;
+        let value = gate.gate_type.apply(&gate_inputs)?;
+        wire_values.insert(gate.output_wire, value);
+        gate_outputs.push((gate.id, value));
+    }
+
+    let outputs = circuit.output_wires.iter()
+        .map(|wire| wire_values.get(wire).copied().ok_or_else(|| {
+            MpcError::ProtocolError(format!("Output wire {} is never set", wire))
+        }))
+        .collect::<Result<Vec<bool>>>()?;
+
+    Ok(SimulationTrace { wire_values, gate_outputs, outputs })
+}
+
+/// 明文模拟电路，返回输出
+pub fn simulate(circuit: &Circuit, inputs: &[bool]) -> Result<Vec<bool>> {
+    Ok(simulate_trace(circuit, inputs)?.outputs)
+}
+
+/// 一个门上混淆求值与明文模拟的分歧
+#[derive(Debug, Clone, PartialEq)]
+pub struct GateDivergence {
+    /// 门 ID
+    pub gate: GateId,
+    /// 门的类型
+    pub gate_type: GateType,
+    /// 输出线
+    pub output_wire: WireId,
+    /// 明文模拟的输出
+    pub expected: bool,
+    /// 混淆求值解码得到的输出；标签不属于该线时为 `None`
+    pub actual: Option<bool>,
+}
+
+/// 逐门比较的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationDiff {
+    /// 比较的门数量
+    pub checked_gates: usize,
+    /// 按门顺序排列的全部分歧
+    pub divergences: Vec<GateDivergence>,
+    /// 明文模拟的输出
+    pub expected_outputs: Vec<bool>,
+    /// 求值转录中的输出
+    pub actual_outputs: Vec<bool>,
+}
+
+impl SimulationDiff {
+    /// 第一个出现分歧的门
+    pub fn first_divergence(&self) -> Option<&GateDivergence> {
+        self.divergences.first()
+    }
+
+    /// 所有门和输出都一致
+    pub fn is_consistent(&self) -> bool {
+        self.divergences.is_empty() && self.expected_outputs == self.actual_outputs
+    }
+}
+
+/// 把混淆求值转录与明文模拟逐门比较
+///
+/// 转录中的输出标签按混淆方的线标签解码为比特。
+///
+/// # 参数
+/// - `circuit`: 原始电路
+/// - `garbled`: 由该电路混淆得到的电路（提供线标签）
+/// - `inputs`: 明文输入
+/// - `transcript`: 求值方的转录
+pub fn diff_transcript(
+    circuit: &Circuit,
+    garbled: &GarbledCircuit,
+    inputs: &[bool],
+    transcript: &EvaluationTranscript,
+) -> Result<SimulationDiff> {
+    let trace = simulate_trace(circuit, inputs)?;
+    let evaluated: HashMap<GateId, Label> = transcript.gate_outputs.iter().copied().collect();
+
+    let mut divergences = Vec::new();
+    for (gate, &(_, expected)) in circuit.gates.iter().zip(&trace.gate_outputs) {
+        let actual = evaluated.get(&gate.id).and_then(|label| {
+            let (label_0, label_1) = garbled.wire_labels.get(&gate.output_wire)?;
+            if label == label_0 {
+                Some(false)
+            } else if label == label_1 {
+                Some(true)
+            } else {
+                None
+            }
+        });
+        if actual != Some(expected) {
+            divergences.push(GateDivergence {
+                gate: gate.id,
+                gate_type: gate.gate_type.clone(),
+                output_wire: gate.output_wire,
+                expected,
+                actual,
+            });
+        }
+    }
+
+    Ok(SimulationDiff {
+        checked_gates: circuit.gates.len(),
+        divergences,
+        expected_outputs: trace.outputs,
+        actual_outputs: transcript.outputs.clone(),
+    })
+}
+
+/// 混淆并求值电路，再与明文模拟逐门比较
+pub fn cross_check_garbled(circuit: &Circuit, inputs: &[bool]) -> Result<SimulationDiff> {
+    let garbler = Garbler::new();
+    let garbled = garbler.garble_circuit(circuit)?;
+    let input_labels = garbler.get_input_labels(&garbled, inputs)?;
+    let transcript = Evaluator::new().evaluate_with_transcript(&garbled, &input_labels)?;
+    diff_transcript(circuit, &garbled, inputs, &transcript)
+}
@@ -0,0 +1,235 @@
+//! 混淆表文件
+//!
+//! 大电路的混淆表按门顺序写入内存映射文件（`storage::MappedArtifact`），
+//! 每个门一条定长记录：32 字节的门头（ID、类型、输入输出线）加上混淆表。
+//! 记录长度由电路中最大的混淆表决定，求值方可以逐门读取，不需要一次载入全部混淆表。
+
+use super::*;
+use crate::storage::{ArtifactKind, MappedArtifact, MappedArtifactWriter};
+use std::ops::Range;
+use std::path::Path;
+
+/// 每条记录中门头的长度
+pub const GATE_RECORD_HEADER_SIZE: usize = 32;
+
+/// 每个混淆表最多可容纳 `max_table_labels` 个标签时的记录长度
+pub fn garbled_table_record_size(max_table_labels: usize) -> usize {
+    GATE_RECORD_HEADER_SIZE + max_table_labels * 16
+}
+
+/// 门类型的单字节编码，混淆表文件和线格式共用
+pub(crate) fn gate_type_code(gate_type: &GateType) -> u8 {
+    match gate_type {
+        GateType::And => 0,
+        GateType::Or => 1,
+        GateType::Xor => 2,
+        GateType::Not => 3,
+        GateType::Input => 4,
+        GateType::Output => 5,
+        GateType::Nand => 6,
+        GateType::Nor => 7,
+        GateType::Xnor => 8,
+        GateType::Buf => 9,
+        GateType::Const(false) => 10,
+        GateType::Const(true) => 11,
+        GateType::MultiAnd => 12,
+        GateType::MultiOr => 13,
+    }
+}
+
+pub(crate) fn gate_type_from_code(code: u8) -> Result<GateType> {
+    Ok(match code {
+        0 => GateType::And,
+        1 => GateType::Or,
+        2 => GateType::Xor,
+        3 => GateType::Not,
+        4 => GateType::Input,
+        5 => GateType::Output,
+        6 => GateType::Nand,
+        7 => GateType::Nor,
+        8 => GateType::Xnor,
+        9 => GateType::Buf,
+        10 => GateType::Const(false),
+        11 => GateType::Const(true),
+        12 => GateType::MultiAnd,
+        13 => GateType::MultiOr,
+        _ => return Err(MpcError::SerializationError(format!("Unknown gate type code {}", code))),
+    })
+}
+
+/// 把混淆门编码为定长记录
+fn encode_gate_record(gate: &GarbledGate, max_table_labels: usize) -> Result<Vec<u8>> {
+    let table = gate.garbled_table.as_deref().unwrap_or(&[]);
+    if gate.input_wires.len() > MAX_NATIVE_FAN_IN {
+        return Err(MpcError::ProtocolError(format!(
+            "Gate {} has {} inputs, decompose it first", gate.id, gate.input_wires.len()
+        )));
+    }
+    if table.len() > max_table_labels {
+        return Err(MpcError::StorageError(format!(
+            "Gate {} has {} table labels, the file holds at most {}", gate.id, table.len(), max_table_labels
+        )));
+    }
+
+    let mut record = vec![0u8; garbled_table_record_size(max_table_labels)];
+    record[0..4].copy_from_slice(&gate.id.to_be_bytes());
+    record[4] = gate_type_code(&gate.gate_type);
+    record[5] = gate.input_wires.len() as u8;
+    record[6] = gate.garbled_table.is_some() as u8;
+    record[8..10].copy_from_slice(&(table.len() as u16).to_be_bytes());
+    record[12..16].copy_from_slice(&gate.output_wire.to_be_bytes());
+    for (chunk, wire) in record[16..GATE_RECORD_HEADER_SIZE].chunks_exact_mut(4).zip(&gate.input_wires) {
+        chunk.copy_from_slice(&wire.to_be_bytes());
+    }
+    for (chunk, label) in record[GATE_RECORD_HEADER_SIZE..].chunks_exact_mut(16).zip(table) {
+        chunk.copy_from_slice(label);
+    }
+    Ok(record)
+}
+
+/// 从定长记录解码混淆门
+fn decode_gate_record(record: &[u8]) -> Result<GarbledGate> {
+    let malformed = || MpcError::SerializationError("Malformed garbled gate record".to_string());
+    if record.len() < GATE_RECORD_HEADER_SIZE {
+        return Err(malformed());
+    }
+    let read_u32 = |offset: usize| u32::from_be_bytes(record[offset..offset + 4].try_into().unwrap());
+
+    let input_count = record[5] as usize;
+    let table_len = u16::from_be_bytes([record[8], record[9]]) as usize;
+    if input_count > MAX_NATIVE_FAN_IN || GATE_RECORD_HEADER_SIZE + table_len * 16 > record.len() {
+        return Err(malformed());
+    }
+
+    let garbled_table = match record[6] {
+        0 => None,
+        1 => Some(
+            record[GATE_RECORD_HEADER_SIZE..GATE_RECORD_HEADER_SIZE + table_len * 16]
+                .chunks_exact(16)
+                .map(|chunk| chunk.try_into().unwrap())
+                .collect(),
+        ),
+        _ => return Err(malformed()),
+    };
+    Ok(GarbledGate {
+        id: read_u32(0),
+        gate_type: gate_type_from_code(record[4])?,
+        input_wires: (0..input_count).map(|i| read_u32(16 + i * 4)).collect(),
+        output_wire: read_u32(12),
+        garbled_table,
+    })
+}
+
+/// 顺序写入混淆门的文件
+#[derive(Debug)]
+pub struct GarbledTableWriter {
+    inner: MappedArtifactWriter,
+    max_table_labels: usize,
+}
+
+impl GarbledTableWriter {
+    /// 创建文件
+    ///
+    /// # 参数
+    /// - `path`: 文件路径，已存在时覆盖
+    /// - `max_table_labels`: 单个混淆表最多包含的标签数
+    /// - `key`: 加密密钥（至少 16 字节），为空时不加密
+    pub fn create(path: impl AsRef<Path>, max_table_labels: usize, key: Option<&[u8]>) -> Result<Self> {
+        let record_size = garbled_table_record_size(max_table_labels);
+        Ok(Self {
+            inner: MappedArtifactWriter::create(path, ArtifactKind::GarbledTables, record_size, key)?,
+            max_table_labels,
+        })
+    }
+
+    /// 追加一个混淆门，返回其序号
+    pub fn append(&mut self, gate: &GarbledGate) -> Result<u64> {
+        self.inner.append(&encode_gate_record(gate, self.max_table_labels)?)
+    }
+
+    /// 已写入的门数量
+    pub fn len(&self) -> u64 {
+        self.inner.len()
+    }
+
+    /// 是否尚未写入门
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// 完成写入，返回门数量
+    pub fn finish(self) -> Result<u64> {
+        self.inner.finish()
+    }
+}
+
+/// 把混淆电路的全部门写入文件
+///
+/// 线标签属于混淆方的秘密，不写入文件。
+///
+/// # 返回值
+/// 写入的门数量
+pub fn write_garbled_tables(path: impl AsRef<Path>, garbled_circuit: &GarbledCircuit, key: Option<&[u8]>) -> Result<u64> {
+    let max_table_labels = garbled_circuit.gates.iter()
+        .map(|gate| gate.garbled_table.as_ref().map_or(0, Vec::len))
+        .max()
+        .unwrap_or(0);
+    let mut writer = GarbledTableWriter::create(path, max_table_labels, key)?;
+    for gate in &garbled_circuit.gates {
+        writer.append(gate)?;
+    }
+    writer.finish()
+}
+
+/// 只读映射的混淆表文件
+#[derive(Debug)]
+pub struct GarbledTableFile {
+    artifact: MappedArtifact,
+}
+
+impl GarbledTableFile {
+    /// 打开文件
+    ///
+    /// # 参数
+    /// - `path`: 由 `GarbledTableWriter` 或 `write_garbled_tables` 写入的文件
+    /// - `key`: 写入时使用的密钥
+    pub fn open(path: impl AsRef<Path>, key: Option<&[u8]>) -> Result<Self> {
+        let artifact = MappedArtifact::open(path, key)?;
+        if artifact.kind() != ArtifactKind::GarbledTables {
+            return Err(MpcError::StorageError(format!(
+                "Expected a garbled table file, found {:?}", artifact.kind()
+            )));
+        }
+        Ok(Self { artifact })
+    }
+
+    /// 门数量
+    pub fn len(&self) -> u64 {
+        self.artifact.len()
+    }
+
+    /// 是否没有门
+    pub fn is_empty(&self) -> bool {
+        self.artifact.is_empty()
+    }
+
+    /// 单个混淆表最多包含的标签数
+    pub fn max_table_labels(&self) -> usize {
+        (self.artifact.layout().record_size - GATE_RECORD_HEADER_SIZE) / 16
+    }
+
+    /// 读取第 `index` 个门
+    pub fn gate(&self, index: u64) -> Result<GarbledGate> {
+        decode_gate_record(&self.artifact.record(index)?)
+    }
+
+    /// 按顺序遍历全部门
+    pub fn gates(&self) -> impl Iterator<Item = Result<GarbledGate>> + '_ {
+        self.artifact.records().map(|record| decode_gate_record(&record?))
+    }
+
+    /// 提示操作系统回收已经求值过的门所在的页面
+    pub fn release(&self, range: Range<u64>) {
+        self.artifact.release(range)
+    }
+}
@@ -0,0 +1,275 @@
+//! # 两方 Yao 协议 (Two-Party Yao Protocol)
+//!
+//! 把混淆电路和不经意传输组合成完整的两方计算：混淆方和求值方各自持有电路的
+//! 一部分输入，求值方得到输出，双方都不知道对方的输入。
+//!
+//! ## 协议流程
+//!
+//! 1. **混淆**: 混淆方用半门方案混淆电路，发送混淆表、输出解码信息、自己输入对应的标签、
+//!    约定电路和输入划分的摘要，以及求值方每个输入比特一次 OT 的发送方公钥
+//! 2. **选择**: 求值方检查摘要，以自己的输入比特为选择位回应 OT
+//! 3. **传输**: 混淆方把求值方每条输入线的两个标签作为 OT 的两个消息发出
+//! 4. **求值**: 求值方解密得到自己输入对应的标签，求值混淆电路并解码输出
+//!
+//! 每个求值方输入比特消耗一次基础 OT（`OtSender` / `OtReceiver`），
+//! 混淆方的标签直接发送，三条消息即完成协议。当前实现针对半诚实敌手。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::garbled_circuits::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! // 2 位加法器的输入按 a0, b0, a1, b1 交错排列，混淆方持有 a
+//! let protocol = Yao2PC::new(&Circuit::create_adder(2), [0, 2])?;
+//!
+//! // a = 3, b = 1
+//! let output = protocol.execute(&[true, true], &[true, false])?;
+//! assert_eq!(output.result, vec![false, false, true]);
+//! assert_eq!(output.stats.rounds, 3);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::*;
+use crate::oblivious_transfer::{AwaitingReceiverMsg, AwaitingTransfer, OtReceiver, OtSender, OtTransfer};
+use crate::protocols::stats::{ProtocolOutput, ProtocolStats, StatsRecorder};
+use serde::de::DeserializeOwned;
+
+/// 双方约定的电路和输入划分
+#[derive(Debug, Clone)]
+pub struct Yao2PC {
+    compiled: HalfGatesCircuit,
+    garbler_positions: Vec<usize>,
+    evaluator_positions: Vec<usize>,
+    digest: [u8; 32],
+}
+
+impl Yao2PC {
+    /// 为电路做混淆前的准备
+    ///
+    /// # 参数
+    /// - `circuit`: 要计算的电路
+    /// - `garbler_positions`: 混淆方持有的输入在 `circuit.input_wires` 中的位置，其余输入属于求值方
+    ///
+    /// # 返回值
+    /// 位置越界或重复时返回错误
+    pub fn new(circuit: &Circuit, garbler_positions: impl IntoIterator<Item = usize>) -> Result<Self> {
+        let input_count = circuit.input_wires.len();
+        let mut owned_by_garbler = vec![false; input_count];
+        let mut garbler = Vec::new();
+        for position in garbler_positions {
+            match owned_by_garbler.get_mut(position) {
+                Some(owned) if !*owned => *owned = true,
+                Some(_) => return Err(MpcError::ProtocolError(format!("Input {} is assigned twice", position))),
+                None => {
+                    return Err(MpcError::ProtocolError(format!(
+                        "Input {} is out of range for a circuit with {} inputs", position, input_count
+                    )))
+                }
+            }
+            garbler.push(position);
+        }
+        let evaluator = (0..input_count).filter(|&position| !owned_by_garbler[position]).collect();
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"mpc_api/yao2pc");
+        hasher.update(agreed_circuit_digest(circuit)?);
+        for &position in &garbler {
+            hasher.update((position as u64).to_le_bytes());
+        }
+        Ok(Self {
+            compiled: HalfGatesCircuit::compile(circuit)?,
+            garbler_positions: garbler,
+            evaluator_positions: evaluator,
+            digest: hasher.finalize().into(),
+        })
+    }
+
+    /// 电路和输入划分的摘要，双方必须一致
+    pub fn digest(&self) -> [u8; 32] {
+        self.digest
+    }
+
+    /// 混淆方的输入数量
+    pub fn garbler_input_count(&self) -> usize {
+        self.garbler_positions.len()
+    }
+
+    /// 求值方的输入数量（即所需的 OT 次数）
+    pub fn evaluator_input_count(&self) -> usize {
+        self.evaluator_positions.len()
+    }
+
+    /// 在同一进程中执行完整协议
+    ///
+    /// 每条消息都经过序列化，统计信息中的字节数即实际需要传输的数据量。
+    ///
+    /// # 参数
+    /// - `garbler_inputs`: 混淆方的输入，按 `garbler_positions` 的顺序排列
+    /// - `evaluator_inputs`: 求值方的输入，按输入线顺序排列
+    ///
+    /// # 返回值
+    /// 求值方得到的输出
+    pub fn execute(&self, garbler_inputs: &[bool], evaluator_inputs: &[bool]) -> Result<ProtocolOutput<Vec<bool>>> {
+        let mut recorder = StatsRecorder::start();
+
+        let (garbler, offer) = YaoGarbler::start(self, garbler_inputs)?;
+        let offer = relay(&offer, recorder.stats_mut())?;
+        let (evaluator, request) = YaoEvaluator::respond(self, evaluator_inputs, offer)?;
+        let request = relay(&request, recorder.stats_mut())?;
+        let transfer = garbler.transfer(&request)?;
+        let transfer = relay(&transfer, recorder.stats_mut())?;
+        let outputs = evaluator.finish(self, transfer)?;
+
+        Ok(recorder.finish(outputs))
+    }
+}
+
+/// 第 1 条消息：混淆方发给求值方
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YaoOffer {
+    /// 混淆方使用的电路和输入划分摘要
+    pub circuit_digest: [u8; 32],
+    /// 混淆电路
+    pub garbled: HalfGatesGarbledCircuit,
+    /// 混淆方输入对应的标签
+    pub garbler_labels: Vec<Label>,
+    /// 求值方每个输入比特一次 OT 的发送方公钥
+    pub ot_public_keys: Vec<u64>,
+}
+
+/// 第 2 条消息：求值方以输入比特为选择位回应 OT
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YaoLabelRequest {
+    /// 每次 OT 的接收方响应
+    pub ot_responses: Vec<u64>,
+}
+
+/// 第 3 条消息：混淆方通过 OT 发送求值方输入线的标签
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YaoLabelTransfer {
+    /// 每次 OT 的密文
+    pub transfers: Vec<OtTransfer>,
+}
+
+/// 混淆方，已发出混淆电路，等待 OT 响应
+#[derive(Debug)]
+pub struct YaoGarbler {
+    senders: Vec<OtSender<AwaitingReceiverMsg>>,
+}
+
+impl YaoGarbler {
+    /// 混淆电路并生成第 1 条消息
+    pub fn start(protocol: &Yao2PC, inputs: &[bool]) -> Result<(Self, YaoOffer)> {
+        if inputs.len() != protocol.garbler_positions.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Garbler expects {} inputs, got {}", protocol.garbler_positions.len(), inputs.len()
+            )));
+        }
+        let (garbled, encoding) = protocol.compiled.garble(&mut rand::thread_rng());
+        let garbler_labels = protocol.garbler_positions.iter()
+            .zip(inputs)
+            .map(|(&position, &bit)| encoding.label(position, bit).ok_or_else(missing_label))
+            .collect::<Result<Vec<_>>>()?;
+        let senders = protocol.evaluator_positions.iter()
+            .map(|&position| {
+                let (zero, one) = encoding.label_pair(position).ok_or_else(missing_label)?;
+                OtSender::new(zero.to_vec(), one.to_vec())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let offer = YaoOffer {
+            circuit_digest: protocol.digest,
+            garbled,
+            garbler_labels,
+            ot_public_keys: senders.iter().map(OtSender::public_key).collect(),
+        };
+        Ok((Self { senders }, offer))
+    }
+
+    /// 处理 OT 响应，生成第 3 条消息
+    pub fn transfer(self, request: &YaoLabelRequest) -> Result<YaoLabelTransfer> {
+        if request.ot_responses.len() != self.senders.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} OT responses, found {}", self.senders.len(), request.ot_responses.len()
+            )));
+        }
+        let transfers = self.senders.into_iter()
+            .zip(&request.ot_responses)
+            .map(|(sender, &response)| sender.receive(response).send())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(YaoLabelTransfer { transfers })
+    }
+}
+
+/// 求值方，已回应 OT，等待输入标签
+#[derive(Debug)]
+pub struct YaoEvaluator {
+    offer: YaoOffer,
+    receivers: Vec<OtReceiver<AwaitingTransfer>>,
+}
+
+impl YaoEvaluator {
+    /// 检查混淆方的消息并生成第 2 条消息
+    ///
+    /// # 返回值
+    /// 摘要与本地约定不一致时返回 `AuthenticationError`
+    pub fn respond(protocol: &Yao2PC, inputs: &[bool], offer: YaoOffer) -> Result<(Self, YaoLabelRequest)> {
+        if offer.circuit_digest != protocol.digest {
+            return Err(MpcError::AuthenticationError("Garbler used a different circuit or input split".to_string()));
+        }
+        if inputs.len() != protocol.evaluator_positions.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Evaluator expects {} inputs, got {}", protocol.evaluator_positions.len(), inputs.len()
+            )));
+        }
+        if offer.garbler_labels.len() != protocol.garbler_positions.len()
+            || offer.ot_public_keys.len() != protocol.evaluator_positions.len()
+        {
+            return Err(MpcError::ProtocolError("Garbled offer has the wrong number of inputs".to_string()));
+        }
+
+        let (receivers, ot_responses): (Vec<_>, Vec<_>) = inputs.iter()
+            .zip(&offer.ot_public_keys)
+            .map(|(&bit, &sender_public)| OtReceiver::new(bit).receive(sender_public))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        Ok((Self { offer, receivers }, YaoLabelRequest { ot_responses }))
+    }
+
+    /// 取得输入标签，求值混淆电路并解码输出
+    pub fn finish(self, protocol: &Yao2PC, transfer: YaoLabelTransfer) -> Result<Vec<bool>> {
+        if transfer.transfers.len() != self.receivers.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} OT transfers, found {}", self.receivers.len(), transfer.transfers.len()
+            )));
+        }
+        let mut inputs = vec![[0u8; 16]; protocol.compiled.input_count()];
+        for (&position, label) in protocol.garbler_positions.iter().zip(&self.offer.garbler_labels) {
+            inputs[position] = *label;
+        }
+        for ((&position, receiver), transfer) in protocol.evaluator_positions.iter().zip(self.receivers).zip(transfer.transfers) {
+            inputs[position] = receiver.finish(transfer)?
+                .try_into()
+                .map_err(|_| MpcError::ProtocolError("OT returned a label of the wrong length".to_string()))?;
+        }
+
+        let outputs = protocol.compiled.evaluate(&self.offer.garbled, &inputs)?;
+        self.offer.garbled.decode(&outputs)
+    }
+}
+
+fn missing_label() -> MpcError {
+    MpcError::ProtocolError("Circuit input is missing from the garbled encoding".to_string())
+}
+
+/// 模拟一次传输：序列化后反序列化，并记录一轮通信
+fn relay<T: Serialize + DeserializeOwned>(message: &T, stats: &mut ProtocolStats) -> Result<T> {
+    let bytes = bincode::serialize(message).map_err(|e| MpcError::SerializationError(e.to_string()))?;
+    stats.record_rounds(1);
+    stats.record_sent(bytes.len() as u64);
+    stats.record_received(bytes.len() as u64);
+    bincode::deserialize(&bytes).map_err(|e| MpcError::SerializationError(e.to_string()))
+}
@@ -1,28 +1,57 @@
 //! BFV (Brakerski-Fan-Vercauteren) fully homomorphic encryption scheme
-//! 
-//! This is a simplified implementation for demonstration purposes.
-//! A full implementation would require more sophisticated polynomial arithmetic and noise management.
+//!
+//! Ciphertexts are pairs of polynomials in R_Q = Z_Q[x]/(x^n + 1), where Q = q_0 * q_1 * ... * q_{L-1}
+//! is a chain of NTT-friendly primes. Every polynomial is kept in RNS form: one residue polynomial
+//! ("limb") per prime, limbs concatenated in chain order, so additions and ring products are
+//! independent per-prime operations (ring products use the negacyclic NTT in `utils::math::ntt`).
+//!
+//! - Multiplication lifts both ciphertexts into the extended basis Q * P (auxiliary 61-bit NTT primes
+//!   with P > 2nQ), computes the tensor product there, scales by t/Q with exact rounding and
+//!   relinearizes with one key per RNS prime (the RNS gadget decomposition).
+//! - `BFV::modulus_switch` drops the last prime of the chain (c <- round(c / q_l)), shrinking the
+//!   ciphertext by one limb. The level of a ciphertext is its number of limbs; keys are generated for
+//!   the full chain and used at every level.
+//! - The non-linear basis conversions (lifting into Q * P, scale-and-round, decryption) use exact CRT
+//!   reconstruction with big integers instead of the approximate BEHZ/HPS RNS algorithms.
+//!
+//! Plaintexts are integers mod t encoded in the constant coefficient, matching the `u64` plaintext
+//! space of the `HomomorphicEncryption` trait.
 
 use super::*;
+use crate::utils::math::ntt::{ntt_primes, NttPlan};
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
 use rand::Rng;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BFVPublicKey {
-    pub a: Vec<u64>,  // polynomial a
-    pub b: Vec<u64>,  // polynomial b = -a*s + e + t*delta
+    pub a: Vec<u64>,  // polynomial a (RNS limbs, limb j at [j*n, (j+1)*n))
+    pub b: Vec<u64>,  // polynomial b = -a*s + e
     pub n: usize,     // polynomial degree
-    pub q: u64,       // ciphertext modulus
+    pub q: u64,       // first prime of the modulus chain (the whole modulus for single-prime keys)
     pub t: u64,       // plaintext modulus
+    /// RNS modulus chain q_0, ..., q_{L-1}; empty means the single modulus `q`
+    #[serde(default)]
+    pub rns_moduli: Vec<u64>,
+    /// Relinearization key: for each RNS prime q_j, (-(a_j*s + e_j) + (Q/q_j)*s^2, a_j)
+    #[serde(default)]
+    pub relin_key: Vec<(Vec<u64>, Vec<u64>)>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BFVPrivateKey {
-    pub s: Vec<u64>,  // secret key polynomial
+    pub s: Vec<u64>,  // secret key polynomial (RNS limbs)
     pub n: usize,     // polynomial degree
-    pub q: u64,       // ciphertext modulus
+    pub q: u64,       // first prime of the modulus chain
     pub t: u64,       // plaintext modulus
+    /// RNS modulus chain; empty means the single modulus `q`
+    #[serde(default)]
+    pub rns_moduli: Vec<u64>,
 }
 
+/// A ciphertext at level l has l + 1 limbs per component (one per remaining prime of the chain)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BFVCiphertext {
     pub c0: Vec<u64>, // first component
@@ -37,145 +66,249 @@ pub struct BFVPlaintext {
 // Type aliases for compatibility
 pub type BFVSecretKey = BFVPrivateKey;
 
+/// Encryption parameters
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BFVParameters {
+    /// Polynomial degree n (a power of two)
+    pub n: usize,
+    /// Plaintext modulus t
+    pub t: u64,
+    /// RNS modulus chain, each prime with 2n | q_j - 1
+    pub moduli: Vec<u64>,
+}
+
+impl Default for BFVParameters {
+    /// n = 4096 with three 36-bit primes (log Q = 108, within the 128-bit security bound of
+    /// the homomorphic encryption standard) and t = 65537
+    fn default() -> Self {
+        Self {
+            n: 4096,
+            t: 65537,
+            moduli: ntt_primes(36, 4096, 3).expect("36-bit primes for n = 4096 exist"),
+        }
+    }
+}
+
+impl BFVParameters {
+    /// Check the degree, the plaintext modulus and the modulus chain
+    pub fn validate(&self) -> Result<()> {
+        if !self.n.is_power_of_two() || self.n < 2 {
+            return Err(MpcError::ProtocolError(format!("BFV degree {} is not a power of two", self.n)));
+        }
+        if self.moduli.is_empty() || self.moduli.iter().any(|&q| q <= self.t || q >= 1 << 62) {
+            return Err(MpcError::ProtocolError(
+                "BFV moduli must be non-empty, above t and below 2^62".to_string(),
+            ));
+        }
+        if self.t < 2 {
+            return Err(MpcError::ProtocolError("BFV plaintext modulus must be at least 2".to_string()));
+        }
+        RnsBasis::new(self.n, &self.moduli).map(|_| ())
+    }
+}
+
 pub struct BFV;
 
 impl BFV {
-    const DEFAULT_N: usize = 8;  // Small degree for testing
-    const DEFAULT_Q: u64 = 1024; // Small modulus for testing
-    const DEFAULT_T: u64 = 16;   // Plaintext modulus
-    
-    fn poly_add(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
-        let max_len = a.len().max(b.len());
-        let mut result = vec![0u64; max_len];
-        
-        for i in 0..max_len {
-            let a_coeff = if i < a.len() { a[i] } else { 0 };
-            let b_coeff = if i < b.len() { b[i] } else { 0 };
-            result[i] = (a_coeff + b_coeff) % modulus;
-        }
-        
-        result
-    }
-    
-    fn poly_mul_scalar(poly: &[u64], scalar: u64, modulus: u64) -> Vec<u64> {
-        poly.iter().map(|&coeff| (coeff * scalar) % modulus).collect()
-    }
-    
-    fn poly_mul(a: &[u64], b: &[u64], modulus: u64, n: usize) -> Vec<u64> {
-        let mut result = vec![0u64; 2 * n];
-        
-        for i in 0..a.len() {
-            for j in 0..b.len() {
-                if i + j < result.len() {
-                    result[i + j] = (result[i + j] + (a[i] * b[j]) % modulus) % modulus;
+    /// Number of terms of the centered binomial error distribution (standard deviation ~3.2)
+    const ERROR_ETA: u32 = 21;
+    /// Bit size of the auxiliary primes used for the tensor product
+    const AUX_PRIME_BITS: u32 = 61;
+
+    /// Generate keys for explicit parameters
+    pub fn keygen_with_params(params: &BFVParameters) -> Result<(BFVPublicKey, BFVPrivateKey)> {
+        params.validate()?;
+        let basis = RnsBasis::new(params.n, &params.moduli)?;
+        let n = params.n;
+
+        let s = basis.encode_signed(&Self::sample_ternary(n));
+        let a = basis.sample_uniform();
+        let e = basis.encode_signed(&Self::sample_error(n));
+        let b = basis.sub(&e, &basis.mul(&a, &s)?);
+
+        let s_squared = basis.mul(&s, &s)?;
+        let q_total = basis.product();
+        let relin_key = (0..basis.len())
+            .map(|j| {
+                let a_j = basis.sample_uniform();
+                let e_j = basis.encode_signed(&Self::sample_error(n));
+                let mut b_j = basis.sub(&basis.neg(&e_j), &basis.mul(&a_j, &s)?);
+                // (Q/q_j) * s^2 vanishes modulo every other prime, so only limb j changes
+                let q_j = basis.moduli[j];
+                let factor = biguint_mod(&(&q_total / q_j), q_j);
+                for (value, &square) in b_j[j * n..(j + 1) * n].iter_mut().zip(&s_squared[j * n..(j + 1) * n]) {
+                    *value = add_mod(*value, mul_mod(factor, square, q_j), q_j);
                 }
-            }
-        }
-        
-        // Reduce modulo x^n + 1 (cyclotomic polynomial)
-        for i in n..result.len() {
-            if i - n < n {
-                result[i - n] = (result[i - n] + modulus - result[i]) % modulus;
-            }
+                Ok((b_j, a_j))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let pk = BFVPublicKey {
+            a,
+            b,
+            n,
+            q: params.moduli[0],
+            t: params.t,
+            rns_moduli: params.moduli.clone(),
+            relin_key,
+        };
+        let sk = BFVPrivateKey { s, n, q: params.moduli[0], t: params.t, rns_moduli: params.moduli.clone() };
+        Ok((pk, sk))
+    }
+
+    /// Level of a ciphertext: the number of chain primes it still uses, minus one
+    pub fn level(pk: &BFVPublicKey, ciphertext: &BFVCiphertext) -> Result<usize> {
+        Ok(Self::ciphertext_basis(&pk.modulus_chain(), pk.n, ciphertext)?.len() - 1)
+    }
+
+    /// Drop the last prime q_l of the ciphertext's modulus: c <- round(c / q_l)
+    ///
+    /// The plaintext is preserved; the noise shrinks by roughly q_l plus a small rounding term.
+    /// Fails for ciphertexts already at level 0.
+    pub fn modulus_switch(pk: &BFVPublicKey, ciphertext: &BFVCiphertext) -> Result<BFVCiphertext> {
+        let basis = Self::ciphertext_basis(&pk.modulus_chain(), pk.n, ciphertext)?;
+        if basis.len() == 1 {
+            return Err(MpcError::ProtocolError("Ciphertext is already at the lowest level".to_string()));
         }
-        
-        result[..n].to_vec()
+        Ok(BFVCiphertext {
+            c0: basis.drop_last_limb(&ciphertext.c0),
+            c1: basis.drop_last_limb(&ciphertext.c1),
+        })
     }
-    
-    fn sample_uniform(size: usize, modulus: u64) -> Vec<u64> {
+
+    fn sample_ternary(n: usize) -> Vec<i64> {
         let mut rng = rand::thread_rng();
-        (0..size).map(|_| rng.gen_range(0..modulus)).collect()
+        (0..n).map(|_| rng.gen_range(-1..=1)).collect()
     }
-    
-    fn sample_small_error(size: usize) -> Vec<u64> {
+
+    /// Centered binomial distribution: difference of two sums of ERROR_ETA coin flips
+    fn sample_error(n: usize) -> Vec<i64> {
         let mut rng = rand::thread_rng();
-        (0..size).map(|_| rng.gen_range(0..3)).collect() // Small error
+        let mask = (1u64 << Self::ERROR_ETA) - 1;
+        (0..n)
+            .map(|_| {
+                let bits: u64 = rng.gen();
+                (bits & mask).count_ones() as i64 - ((bits >> Self::ERROR_ETA) & mask).count_ones() as i64
+            })
+            .collect()
     }
-    
-    fn encode_plaintext(plaintext: u64, t: u64) -> Vec<u64> {
-        vec![plaintext % t] // Simple encoding
+
+    /// Basis of the primes a ciphertext still uses, after checking its shape
+    fn ciphertext_basis(chain: &[u64], n: usize, ciphertext: &BFVCiphertext) -> Result<RnsBasis> {
+        let limbs = ciphertext.c0.len() / n.max(1);
+        if n == 0 || ciphertext.c0.len() != ciphertext.c1.len() || !ciphertext.c0.len().is_multiple_of(n)
+            || limbs == 0 || limbs > chain.len()
+        {
+            return Err(MpcError::CryptographicError("Ciphertext dimensions mismatch".to_string()));
+        }
+        RnsBasis::new(n, &chain[..limbs])
     }
-    
-    fn decode_plaintext(poly: &[u64], t: u64) -> u64 {
-        if poly.is_empty() {
-            0
-        } else {
-            poly[0] % t
+
+    /// Basis of the public key, restricted to the primes of both ciphertexts
+    fn common_basis(pk: &BFVPublicKey, c1: &BFVCiphertext, c2: &BFVCiphertext) -> Result<RnsBasis> {
+        let chain = pk.modulus_chain();
+        let basis = Self::ciphertext_basis(&chain, pk.n, c1)?;
+        if Self::ciphertext_basis(&chain, pk.n, c2)?.len() != basis.len() {
+            return Err(MpcError::ProtocolError(
+                "Ciphertexts are at different levels; switch the higher one down first".to_string(),
+            ));
+        }
+        Ok(basis)
+    }
+
+    /// round(t * x / Q) for a centered big integer x
+    fn scale_and_round(x: &BigInt, t: u64, q_total: &BigInt) -> BigInt {
+        (BigInt::from(2 * t as u128) * x + q_total).div_floor(&(q_total * 2))
+    }
+
+    /// Fold c2 * s^2 back into (c0, c1) using the RNS digits of c2
+    fn relinearize(pk: &BFVPublicKey, basis: &RnsBasis, c0: Vec<u64>, c1: Vec<u64>, c2: &[u64]) -> Result<BFVCiphertext> {
+        if pk.relin_key.len() != pk.modulus_chain().len() {
+            return Err(MpcError::ProtocolError("Public key has no relinearization key".to_string()));
         }
+        let n = basis.n;
+        let width = basis.len() * n;
+        let q_level = basis.product();
+        // Keys carry Q/q_j for the full chain Q; at lower levels the extra factor P = Q / Q_level
+        // is divided out of the digits: D_j = [c2 * (Q_level/q_j)^-1 * P^-1]_{q_j}
+        let dropped = pk.modulus_chain()[basis.len()..].iter().fold(BigUint::one(), |acc, &q| acc * q);
+
+        let mut result = BFVCiphertext { c0, c1 };
+        for (j, (key_b, key_a)) in pk.relin_key.iter().take(basis.len()).enumerate() {
+            let q_j = basis.moduli[j];
+            let cofactor = biguint_mod(&(&q_level / q_j), q_j);
+            let scale = inv_mod(mul_mod(cofactor, biguint_mod(&dropped, q_j), q_j), q_j);
+            let digit: Vec<u64> = c2[j * n..(j + 1) * n].iter().map(|&c| mul_mod(c, scale, q_j)).collect();
+            let digit = basis.broadcast(&digit);
+            result.c0 = basis.add(&result.c0, &basis.mul(&digit, &key_b[..width])?);
+            result.c1 = basis.add(&result.c1, &basis.mul(&digit, &key_a[..width])?);
+        }
+        Ok(result)
+    }
+}
+
+impl BFVPublicKey {
+    /// The RNS modulus chain q_0, ..., q_{L-1}
+    pub fn modulus_chain(&self) -> Vec<u64> {
+        if self.rns_moduli.is_empty() { vec![self.q] } else { self.rns_moduli.clone() }
+    }
+}
+
+impl BFVPrivateKey {
+    /// The RNS modulus chain q_0, ..., q_{L-1}
+    pub fn modulus_chain(&self) -> Vec<u64> {
+        if self.rns_moduli.is_empty() { vec![self.q] } else { self.rns_moduli.clone() }
     }
 }
 
-// Placeholder implementation - BFV is complex and requires careful parameter selection
 impl HomomorphicEncryption for BFV {
     type PlaintextSpace = u64;
     type CiphertextSpace = BFVCiphertext;
     type PublicKey = BFVPublicKey;
     type PrivateKey = BFVPrivateKey;
-    
+
     fn keygen() -> Result<(Self::PublicKey, Self::PrivateKey)> {
-        let n = Self::DEFAULT_N;
-        let q = Self::DEFAULT_Q;
-        let t = Self::DEFAULT_T;
-        
-        // Generate secret key
-        let s = Self::sample_small_error(n);
-        
-        // Generate a uniformly random polynomial
-        let a = Self::sample_uniform(n, q);
-        
-        // Generate error
-        let e = Self::sample_small_error(n);
-        
-        // Compute b = -a*s + e (simplified)
-        let as_product = Self::poly_mul(&a, &s, q, n);
-        let mut b = vec![0u64; n];
-        for i in 0..n {
-            b[i] = (q + e[i] - as_product[i]) % q;
-        }
-        
-        let pk = BFVPublicKey { a, b, n, q, t };
-        let sk = BFVPrivateKey { s, n, q, t };
-        
-        Ok((pk, sk))
+        Self::keygen_with_params(&BFVParameters::default())
     }
-    
+
     fn encrypt(pk: &Self::PublicKey, plaintext: &Self::PlaintextSpace) -> Result<Self::CiphertextSpace> {
-        // Encode plaintext
-        let m = Self::encode_plaintext(*plaintext, pk.t);
-        let mut m_scaled = vec![0u64; pk.n];
-        for i in 0..m.len().min(pk.n) {
-            m_scaled[i] = (m[i] * (pk.q / pk.t)) % pk.q;
-        }
-        
-        // Sample random polynomial u
-        let u = Self::sample_small_error(pk.n);
-        
-        // Sample error polynomials
-        let e1 = Self::sample_small_error(pk.n);
-        let e2 = Self::sample_small_error(pk.n);
-        
-        // Compute ciphertext
-        let au = Self::poly_mul(&pk.a, &u, pk.q, pk.n);
-        let bu = Self::poly_mul(&pk.b, &u, pk.q, pk.n);
-        
-        let c0 = Self::poly_add(&Self::poly_add(&bu, &e1, pk.q), &m_scaled, pk.q);
-        let c1 = Self::poly_add(&au, &e2, pk.q);
-        
+        let basis = RnsBasis::new(pk.n, &pk.modulus_chain())?;
+        let width = basis.len() * pk.n;
+        if pk.a.len() != width || pk.b.len() != width || pk.t == 0 {
+            return Err(MpcError::CryptographicError("Public key dimensions mismatch".to_string()));
+        }
+
+        // Δ * m in the constant coefficient, Δ = floor(Q / t)
+        let delta = basis.product() / pk.t;
+        let m = plaintext % pk.t;
+        let mut scaled_message = vec![0u64; width];
+        for (j, &q_j) in basis.moduli.iter().enumerate() {
+            scaled_message[j * pk.n] = mul_mod(biguint_mod(&delta, q_j), m % q_j, q_j);
+        }
+
+        let u = basis.encode_signed(&Self::sample_ternary(pk.n));
+        let e1 = basis.encode_signed(&Self::sample_error(pk.n));
+        let e2 = basis.encode_signed(&Self::sample_error(pk.n));
+
+        let c0 = basis.add(&basis.add(&basis.mul(&pk.b, &u)?, &e1), &scaled_message);
+        let c1 = basis.add(&basis.mul(&pk.a, &u)?, &e2);
         Ok(BFVCiphertext { c0, c1 })
     }
-    
+
     fn decrypt(sk: &Self::PrivateKey, ciphertext: &Self::CiphertextSpace) -> Result<Self::PlaintextSpace> {
-        // Compute c0 + c1 * s
-        let c1s = Self::poly_mul(&ciphertext.c1, &sk.s, sk.q, sk.n);
-        let decrypted_poly = Self::poly_add(&ciphertext.c0, &c1s, sk.q);
-        
-        // Scale down and decode
-        let mut scaled_poly = vec![0u64; sk.n];
-        for i in 0..decrypted_poly.len() {
-            scaled_poly[i] = (decrypted_poly[i] * sk.t / sk.q) % sk.t;
+        let chain = sk.modulus_chain();
+        let basis = Self::ciphertext_basis(&chain, sk.n, ciphertext)?;
+        if sk.s.len() != chain.len() * sk.n || sk.t == 0 {
+            return Err(MpcError::CryptographicError("Private key dimensions mismatch".to_string()));
         }
-        
-        Ok(Self::decode_plaintext(&scaled_poly, sk.t))
+
+        // c0 + c1 * s = Δ * m + v (mod Q_level); only the constant coefficient carries the message
+        let s = &sk.s[..basis.len() * sk.n];
+        let phase = basis.add(&ciphertext.c0, &basis.mul(&ciphertext.c1, s)?);
+        let constant = basis.lift_coefficient(&phase, 0);
+        let q_level = BigInt::from(basis.product());
+        let m = Self::scale_and_round(&constant, sk.t, &q_level).mod_floor(&BigInt::from(sk.t));
+        Ok(m.to_u64().expect("reduced modulo t"))
     }
 }
 
@@ -185,55 +318,284 @@ impl AdditivelyHomomorphic for BFV {
         c1: &Self::CiphertextSpace,
         c2: &Self::CiphertextSpace,
     ) -> Result<Self::CiphertextSpace> {
-        let c0 = Self::poly_add(&c1.c0, &c2.c0, pk.q);
-        let c1_sum = Self::poly_add(&c1.c1, &c2.c1, pk.q);
-        
-        Ok(BFVCiphertext { c0, c1: c1_sum })
+        let basis = Self::common_basis(pk, c1, c2)?;
+        Ok(BFVCiphertext { c0: basis.add(&c1.c0, &c2.c0), c1: basis.add(&c1.c1, &c2.c1) })
     }
-    
+
     fn scalar_multiply(
         pk: &Self::PublicKey,
         ciphertext: &Self::CiphertextSpace,
         scalar: &Self::PlaintextSpace,
     ) -> Result<Self::CiphertextSpace> {
-        let c0 = Self::poly_mul_scalar(&ciphertext.c0, *scalar, pk.q);
-        let c1 = Self::poly_mul_scalar(&ciphertext.c1, *scalar, pk.q);
-        
-        Ok(BFVCiphertext { c0, c1 })
+        let basis = Self::ciphertext_basis(&pk.modulus_chain(), pk.n, ciphertext)?;
+        let scalar = scalar % pk.t.max(1);
+        Ok(BFVCiphertext {
+            c0: basis.scalar_mul(&ciphertext.c0, scalar),
+            c1: basis.scalar_mul(&ciphertext.c1, scalar),
+        })
     }
 }
 
 impl MultiplicativelyHomomorphic for BFV {
     fn multiply_ciphertexts(
-        _pk: &Self::PublicKey,
-        _c1: &Self::CiphertextSpace,
-        _c2: &Self::CiphertextSpace,
+        pk: &Self::PublicKey,
+        c1: &Self::CiphertextSpace,
+        c2: &Self::CiphertextSpace,
     ) -> Result<Self::CiphertextSpace> {
-        // Multiplication in BFV is complex and requires relinearization
-        // This is a placeholder implementation
-        Err(MpcError::ProtocolError("BFV multiplication not fully implemented".to_string()))
+        let basis = Self::common_basis(pk, c1, c2)?;
+        if pk.relin_key.len() != pk.modulus_chain().len() {
+            return Err(MpcError::ProtocolError("Public key has no relinearization key".to_string()));
+        }
+
+        // Extend to Q * P with P > 2nQ so the integer tensor product (|d| <= nQ^2/2) is exact there
+        let q_level = basis.product();
+        let bound = &q_level * (2 * basis.n as u64);
+        let candidates = ntt_primes(Self::AUX_PRIME_BITS, basis.n, basis.len() + 8)?;
+        let mut auxiliary = Vec::new();
+        let mut p_total = BigUint::one();
+        for prime in candidates.into_iter().filter(|p| !basis.moduli.contains(p)) {
+            if p_total > bound {
+                break;
+            }
+            p_total *= prime;
+            auxiliary.push(prime);
+        }
+        let extended = basis.extend(&auxiliary)?;
+        let [x0, x1, y0, y1] = [&c1.c0, &c1.c1, &c2.c0, &c2.c1].map(|poly| basis.lift_into(poly, &extended));
+
+        // (x0 + x1 s)(y0 + y1 s) = d0 + d1 s + d2 s^2
+        let d0 = extended.mul(&x0, &y0)?;
+        let d1 = extended.add(&extended.mul(&x0, &y1)?, &extended.mul(&x1, &y0)?);
+        let d2 = extended.mul(&x1, &y1)?;
+
+        let q_level = BigInt::from(q_level);
+        let [d0, d1, d2] = [d0, d1, d2].map(|poly| {
+            let scaled: Vec<BigInt> = extended.lift(&poly)
+                .iter()
+                .map(|x| Self::scale_and_round(x, pk.t, &q_level))
+                .collect();
+            basis.reduce(&scaled)
+        });
+        Self::relinearize(pk, &basis, d0, d1, &d2)
     }
-    
+
     fn power(
-        _pk: &Self::PublicKey,
-        _ciphertext: &Self::CiphertextSpace,
-        _exponent: u64,
+        pk: &Self::PublicKey,
+        ciphertext: &Self::CiphertextSpace,
+        exponent: u64,
     ) -> Result<Self::CiphertextSpace> {
-        Err(MpcError::ProtocolError("BFV power operation not implemented".to_string()))
+        if exponent == 0 {
+            return Self::encrypt(pk, &1);
+        }
+        // Square-and-multiply; the multiplicative depth is about log2(exponent)
+        let mut result: Option<BFVCiphertext> = None;
+        let mut base = ciphertext.clone();
+        let mut exponent = exponent;
+        loop {
+            if exponent & 1 == 1 {
+                result = Some(match result {
+                    Some(acc) => Self::multiply_ciphertexts(pk, &acc, &base)?,
+                    None => base.clone(),
+                });
+            }
+            exponent >>= 1;
+            if exponent == 0 {
+                break;
+            }
+            base = Self::multiply_ciphertexts(pk, &base, &base)?;
+        }
+        Ok(result.expect("exponent is non-zero"))
     }
 }
 
 impl FullyHomomorphic for BFV {
     fn evaluate_circuit<F>(
         _pk: &Self::PublicKey,
-        _circuit: F,
-        _inputs: &[Self::CiphertextSpace],
+        circuit: F,
+        inputs: &[Self::CiphertextSpace],
     ) -> Result<Self::CiphertextSpace>
     where
         F: Fn(&[Self::CiphertextSpace]) -> Result<Self::CiphertextSpace>,
     {
-        Err(MpcError::ProtocolError("BFV circuit evaluation not implemented".to_string()))
+        circuit(inputs)
+    }
+}
+
+/// A set of NTT-friendly primes with the polynomial degree and CRT constants
+struct RnsBasis {
+    n: usize,
+    moduli: Vec<u64>,
+    plans: Vec<Arc<NttPlan>>,
+}
+
+impl RnsBasis {
+    fn new(n: usize, moduli: &[u64]) -> Result<Self> {
+        let plans = moduli.iter().map(|&q| NttPlan::shared(q, n)).collect::<Result<Vec<_>>>()?;
+        Ok(Self { n, moduli: moduli.to_vec(), plans })
+    }
+
+    fn len(&self) -> usize {
+        self.moduli.len()
+    }
+
+    fn product(&self) -> BigUint {
+        self.moduli.iter().fold(BigUint::one(), |acc, &q| acc * q)
+    }
+
+    /// The same basis followed by extra primes
+    fn extend(&self, extra: &[u64]) -> Result<Self> {
+        Self::new(self.n, &[self.moduli.as_slice(), extra].concat())
+    }
+
+    fn limbs<'a>(&'a self, poly: &'a [u64]) -> impl Iterator<Item = (u64, &'a [u64])> + 'a {
+        self.moduli.iter().copied().zip(poly.chunks_exact(self.n))
+    }
+
+    fn encode_signed(&self, coefficients: &[i64]) -> Vec<u64> {
+        self.moduli.iter()
+            .flat_map(|&q| coefficients.iter().map(move |&c| (c as i128).rem_euclid(q as i128) as u64))
+            .collect()
+    }
+
+    fn sample_uniform(&self) -> Vec<u64> {
+        let mut rng = rand::thread_rng();
+        self.moduli.iter().flat_map(|&q| (0..self.n).map(|_| rng.gen_range(0..q)).collect::<Vec<_>>()).collect()
+    }
+
+    /// Copy a polynomial with coefficients below every prime into all limbs
+    fn broadcast(&self, coefficients: &[u64]) -> Vec<u64> {
+        self.moduli.iter().flat_map(|&q| coefficients.iter().map(move |&c| c % q)).collect()
+    }
+
+    fn add(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        self.limbs(a).zip(b.chunks_exact(self.n))
+            .flat_map(|((q, x), y)| x.iter().zip(y).map(move |(&x, &y)| add_mod(x, y, q)))
+            .collect()
+    }
+
+    fn sub(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        self.limbs(a).zip(b.chunks_exact(self.n))
+            .flat_map(|((q, x), y)| x.iter().zip(y).map(move |(&x, &y)| add_mod(x, q - y, q)))
+            .collect()
+    }
+
+    fn neg(&self, a: &[u64]) -> Vec<u64> {
+        self.limbs(a).flat_map(|(q, x)| x.iter().map(move |&x| (q - x) % q)).collect()
     }
+
+    fn scalar_mul(&self, a: &[u64], scalar: u64) -> Vec<u64> {
+        self.limbs(a).flat_map(|(q, x)| x.iter().map(move |&x| mul_mod(x, scalar % q, q))).collect()
+    }
+
+    /// Product in R_Q: one negacyclic NTT product per prime
+    fn mul(&self, a: &[u64], b: &[u64]) -> Result<Vec<u64>> {
+        let mut result = Vec::with_capacity(self.len() * self.n);
+        for ((plan, x), y) in self.plans.iter().zip(a.chunks_exact(self.n)).zip(b.chunks_exact(self.n)) {
+            result.extend(plan.negacyclic_multiply(x, y)?);
+        }
+        Ok(result)
+    }
+
+    /// CRT weights (Q/q_j) * [(Q/q_j)^-1]_{q_j}, so that x = Σ r_j * w_j mod Q
+    fn crt_weights(&self) -> (BigUint, Vec<BigUint>) {
+        let total = self.product();
+        let weights = self.moduli.iter()
+            .map(|&q| {
+                let cofactor = &total / q;
+                let inverse = inv_mod(biguint_mod(&cofactor, q), q);
+                cofactor * inverse
+            })
+            .collect();
+        (total, weights)
+    }
+
+    fn lift_with(&self, poly: &[u64], index: usize, total: &BigUint, weights: &[BigUint]) -> BigInt {
+        let x = self.limbs(poly)
+            .zip(weights)
+            .fold(BigUint::zero(), |acc, ((_, limb), weight)| acc + weight * limb[index])
+            % total;
+        if &x * 2u32 > *total {
+            BigInt::from_biguint(Sign::Minus, total - x)
+        } else {
+            BigInt::from(x)
+        }
+    }
+
+    /// Centered representative in (-Q/2, Q/2] of one coefficient
+    fn lift_coefficient(&self, poly: &[u64], index: usize) -> BigInt {
+        let (total, weights) = self.crt_weights();
+        self.lift_with(poly, index, &total, &weights)
+    }
+
+    /// Centered representatives of all coefficients
+    fn lift(&self, poly: &[u64]) -> Vec<BigInt> {
+        let (total, weights) = self.crt_weights();
+        (0..self.n).map(|i| self.lift_with(poly, i, &total, &weights)).collect()
+    }
+
+    /// Residues of big integer coefficients in every limb
+    fn reduce(&self, coefficients: &[BigInt]) -> Vec<u64> {
+        self.moduli.iter()
+            .flat_map(|&q| {
+                let modulus = BigInt::from(q);
+                coefficients.iter().map(move |c| c.mod_floor(&modulus).to_u64().expect("reduced below q"))
+            })
+            .collect()
+    }
+
+    /// Represent the centered lift of a polynomial of this basis in a larger basis
+    fn lift_into(&self, poly: &[u64], target: &RnsBasis) -> Vec<u64> {
+        let extra = target.reduce(&self.lift(poly));
+        let mut lifted = poly.to_vec();
+        lifted.extend_from_slice(&extra[poly.len()..]);
+        lifted
+    }
+
+    /// round(c / q_last) in the basis without the last prime
+    fn drop_last_limb(&self, poly: &[u64]) -> Vec<u64> {
+        let last = self.len() - 1;
+        let q_last = self.moduli[last];
+        let remainder = &poly[last * self.n..];
+        self.limbs(&poly[..last * self.n])
+            .flat_map(|(q, limb)| {
+                let inverse = inv_mod(q_last % q, q);
+                limb.iter().zip(remainder).map(move |(&c, &r)| {
+                    // subtract the centered remainder so the division rounds to nearest
+                    let r = if r > q_last / 2 { (r as i128 - q_last as i128).rem_euclid(q as i128) as u64 } else { r % q };
+                    mul_mod(add_mod(c, q - r, q), inverse, q)
+                })
+            })
+            .collect()
+    }
+}
+
+fn add_mod(a: u64, b: u64, q: u64) -> u64 {
+    ((a as u128 + b as u128) % q as u128) as u64
+}
+
+fn mul_mod(a: u64, b: u64, q: u64) -> u64 {
+    (a as u128 * b as u128 % q as u128) as u64
+}
+
+fn pow_mod(base: u64, exponent: u64, q: u64) -> u64 {
+    let (mut result, mut base, mut exponent) = (1 % q, base % q, exponent);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, q);
+        }
+        base = mul_mod(base, base, q);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Inverse modulo a prime
+fn inv_mod(a: u64, q: u64) -> u64 {
+    pow_mod(a, q - 2, q)
+}
+
+fn biguint_mod(value: &BigUint, q: u64) -> u64 {
+    (value % q).to_u64().expect("reduced below q")
 }
 
-// Tests moved to tests/homomorphic_encryption_tests.rs
\ No newline at end of file
+// Tests moved to tests/homomorphic_encryption_tests.rs
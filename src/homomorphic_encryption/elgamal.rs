@@ -172,6 +172,27 @@ impl MultiplicativelyHomomorphic for ElGamal {
 
 // ElGamal utility functions
 impl ElGamal {
+    /// 使用给定的生成元生成密钥对
+    /// 
+    /// 各方需要共用同一个生成元时，使用 `protocols::GeneratorSetupParty` 联合派生的
+    /// `JointGenerators::elgamal_generator`，而不是由某一方指定。
+    pub fn keygen_with_generator(generator: u64) -> Result<(ElGamalPublicKey, ElGamalPrivateKey)> {
+        if !(2..FIELD_PRIME).contains(&generator) {
+            return Err(MpcError::CryptographicError("Invalid ElGamal generator".to_string()));
+        }
+        let private_key = thread_rng().gen_range(1..FIELD_PRIME);
+        let pk = ElGamalPublicKey {
+            generator,
+            public_key: Self::mod_pow(generator, private_key, FIELD_PRIME),
+            prime: FIELD_PRIME,
+        };
+        let sk = ElGamalPrivateKey {
+            private_key,
+            prime: FIELD_PRIME,
+        };
+        Ok((pk, sk))
+    }
+    
     pub fn encrypt_zero(pk: &ElGamalPublicKey) -> Result<ElGamalCiphertext> {
         Self::encrypt(pk, &1u64) // Encrypt 1 (multiplicative identity)
     }
@@ -0,0 +1,228 @@
+//! Exponential ElGamal over secp256k1 (additively homomorphic)
+//!
+//! The message is encoded in the exponent: Enc(m; r) = (r*G, m*G + r*H) with H = x*G the public
+//! key. Multiplying ciphertexts componentwise adds the messages, so tallies and aggregates can
+//! be computed on ciphertexts with 256-bit elliptic-curve arithmetic instead of Paillier moduli.
+//!
+//! Decryption recovers m*G and then has to solve a discrete logarithm, which is only feasible
+//! for small messages. `DiscreteLogTable` does this with baby-step giant-step: it stores the
+//! baby steps j*G for j < b and walks giant steps of -b*G, so a message bound B costs b table
+//! entries and at most B / b giant steps. The trait `decrypt` uses a shared table for messages
+//! up to `ExponentialElGamal::DEFAULT_MESSAGE_BOUND`; `decrypt_with_table` takes any table.
+
+use super::*;
+use crate::elliptic_curve::{Secp256k1Point, Secp256k1Scalar};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExponentialElGamalPublicKey {
+    pub point: Secp256k1Point, // H = x*G
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExponentialElGamalPrivateKey {
+    pub secret: Secp256k1Scalar, // x
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExponentialElGamalCiphertext {
+    pub c1: Secp256k1Point, // r*G
+    pub c2: Secp256k1Point, // m*G + r*H
+}
+
+/// Baby-step giant-step solver for m*G with 0 <= m <= max_message
+pub struct DiscreteLogTable {
+    max_message: u64,
+    baby_steps: u64,
+    // truncated encoding of j*G -> j; hits are confirmed by recomputing m*G
+    table: HashMap<u64, u64>,
+    giant_step: Secp256k1Point,
+}
+
+impl DiscreteLogTable {
+    /// Giant steps are encoded in batches sharing one field inversion
+    const GIANT_STEP_BATCH: u64 = 256;
+    /// Largest accepted number of baby steps (about 1 GB of table entries)
+    const MAX_BABY_STEPS: u64 = 1 << 26;
+
+    /// Table with about sqrt(max_message) baby steps, balancing memory and decryption time
+    pub fn new(max_message: u64) -> Result<Self> {
+        let baby_steps = ((max_message as f64 + 1.0).sqrt().ceil() as u64).max(1);
+        Self::with_baby_steps(max_message, baby_steps)
+    }
+
+    /// Table with an explicit number of baby steps; more steps mean fewer giant steps per decryption
+    pub fn with_baby_steps(max_message: u64, baby_steps: u64) -> Result<Self> {
+        if baby_steps == 0 || baby_steps > Self::MAX_BABY_STEPS {
+            return Err(MpcError::ProtocolError(format!(
+                "Baby step count must be between 1 and {}", Self::MAX_BABY_STEPS
+            )));
+        }
+        let baby_steps = baby_steps.min(max_message.saturating_add(1));
+
+        let generator = Secp256k1Point::generator();
+        let mut points = Vec::with_capacity(baby_steps as usize);
+        let mut current = Secp256k1Point::identity();
+        for _ in 0..baby_steps {
+            points.push(current);
+            current = current + generator;
+        }
+        let table = Secp256k1Point::batch_to_sec1(&points, true)
+            .iter()
+            .enumerate()
+            .map(|(j, encoding)| (Self::key(encoding), j as u64))
+            .collect();
+
+        Ok(Self { max_message, baby_steps, table, giant_step: -current })
+    }
+
+    /// Largest message the table can recover
+    pub fn max_message(&self) -> u64 {
+        self.max_message
+    }
+
+    /// Number of stored baby steps
+    pub fn baby_steps(&self) -> u64 {
+        self.baby_steps
+    }
+
+    /// Find m <= max_message with m*G = point
+    pub fn solve(&self, point: &Secp256k1Point) -> Option<u64> {
+        let giant_steps = self.max_message / self.baby_steps + 1;
+        let mut gamma = *point;
+        let mut step = 0;
+        while step < giant_steps {
+            let count = Self::GIANT_STEP_BATCH.min(giant_steps - step);
+            let mut batch = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                batch.push(gamma);
+                gamma = gamma + self.giant_step;
+            }
+            for (i, encoding) in Secp256k1Point::batch_to_sec1(&batch, true).iter().enumerate() {
+                if let Some(&j) = self.table.get(&Self::key(encoding)) {
+                    let message = (step + i as u64) * self.baby_steps + j;
+                    if message <= self.max_message
+                        && Secp256k1Point::generator() * Secp256k1Scalar::from_u64(message) == *point
+                    {
+                        return Some(message);
+                    }
+                }
+            }
+            step += count;
+        }
+        None
+    }
+
+    /// First 8 bytes of the compressed encoding (tag and leading bytes of x)
+    fn key(encoding: &[u8]) -> u64 {
+        let mut bytes = [0u8; 8];
+        let len = encoding.len().min(8);
+        bytes[..len].copy_from_slice(&encoding[..len]);
+        u64::from_be_bytes(bytes)
+    }
+}
+
+pub struct ExponentialElGamal;
+
+impl ExponentialElGamal {
+    /// Message bound of the table used by `HomomorphicEncryption::decrypt`
+    pub const DEFAULT_MESSAGE_BOUND: u64 = (1 << 32) - 1;
+
+    /// Decrypt with a caller-provided discrete-log table
+    pub fn decrypt_with_table(
+        sk: &ExponentialElGamalPrivateKey,
+        ciphertext: &ExponentialElGamalCiphertext,
+        table: &DiscreteLogTable,
+    ) -> Result<u64> {
+        // m*G = c2 - x*c1
+        let encoded = ciphertext.c2 + -(ciphertext.c1 * sk.secret);
+        table.solve(&encoded).ok_or_else(|| {
+            MpcError::CryptographicError(format!(
+                "Plaintext is outside the decodable range [0, {}]", table.max_message()
+            ))
+        })
+    }
+
+    pub fn encrypt_zero(pk: &ExponentialElGamalPublicKey) -> Result<ExponentialElGamalCiphertext> {
+        Self::encrypt(pk, &0)
+    }
+
+    /// Add any number of ciphertexts, e.g. to tally encrypted votes
+    pub fn sum_ciphertexts(
+        pk: &ExponentialElGamalPublicKey,
+        ciphertexts: &[ExponentialElGamalCiphertext],
+    ) -> Result<ExponentialElGamalCiphertext> {
+        ciphertexts.iter().try_fold(Self::trivial_zero(), |acc, c| Self::add_ciphertexts(pk, &acc, c))
+    }
+
+    pub fn randomize_ciphertext(
+        pk: &ExponentialElGamalPublicKey,
+        ciphertext: &ExponentialElGamalCiphertext,
+    ) -> Result<ExponentialElGamalCiphertext> {
+        // Re-randomize by adding encryption of 0
+        let zero_encryption = Self::encrypt_zero(pk)?;
+        Self::add_ciphertexts(pk, ciphertext, &zero_encryption)
+    }
+
+    /// The encryption of 0 with randomness 0, the identity for ciphertext addition
+    fn trivial_zero() -> ExponentialElGamalCiphertext {
+        ExponentialElGamalCiphertext { c1: Secp256k1Point::identity(), c2: Secp256k1Point::identity() }
+    }
+
+    fn default_table() -> &'static DiscreteLogTable {
+        static TABLE: OnceLock<DiscreteLogTable> = OnceLock::new();
+        TABLE.get_or_init(|| {
+            DiscreteLogTable::new(Self::DEFAULT_MESSAGE_BOUND).expect("default table size is within limits")
+        })
+    }
+}
+
+impl HomomorphicEncryption for ExponentialElGamal {
+    type PlaintextSpace = u64;
+    type CiphertextSpace = ExponentialElGamalCiphertext;
+    type PublicKey = ExponentialElGamalPublicKey;
+    type PrivateKey = ExponentialElGamalPrivateKey;
+
+    fn keygen() -> Result<(Self::PublicKey, Self::PrivateKey)> {
+        let mut secret = Secp256k1Scalar::random();
+        while secret.is_zero() {
+            secret = Secp256k1Scalar::random();
+        }
+        let point = Secp256k1Point::generator() * secret;
+        Ok((ExponentialElGamalPublicKey { point }, ExponentialElGamalPrivateKey { secret }))
+    }
+
+    fn encrypt(pk: &Self::PublicKey, plaintext: &Self::PlaintextSpace) -> Result<Self::CiphertextSpace> {
+        let r = Secp256k1Scalar::random();
+        let generator = Secp256k1Point::generator();
+        Ok(ExponentialElGamalCiphertext {
+            c1: generator * r,
+            c2: generator * Secp256k1Scalar::from_u64(*plaintext) + pk.point * r,
+        })
+    }
+
+    fn decrypt(sk: &Self::PrivateKey, ciphertext: &Self::CiphertextSpace) -> Result<Self::PlaintextSpace> {
+        Self::decrypt_with_table(sk, ciphertext, Self::default_table())
+    }
+}
+
+impl AdditivelyHomomorphic for ExponentialElGamal {
+    fn add_ciphertexts(
+        _pk: &Self::PublicKey,
+        c1: &Self::CiphertextSpace,
+        c2: &Self::CiphertextSpace,
+    ) -> Result<Self::CiphertextSpace> {
+        // (r1*G, m1*G + r1*H) + (r2*G, m2*G + r2*H) = Enc(m1 + m2; r1 + r2)
+        Ok(ExponentialElGamalCiphertext { c1: c1.c1 + c2.c1, c2: c1.c2 + c2.c2 })
+    }
+
+    fn scalar_multiply(
+        _pk: &Self::PublicKey,
+        ciphertext: &Self::CiphertextSpace,
+        scalar: &Self::PlaintextSpace,
+    ) -> Result<Self::CiphertextSpace> {
+        let k = Secp256k1Scalar::from_u64(*scalar);
+        Ok(ExponentialElGamalCiphertext { c1: ciphertext.c1 * k, c2: ciphertext.c2 * k })
+    }
+}
@@ -9,6 +9,7 @@
 //! - **ElGamal**: 乘法同态加密，支持密文乘法运算
 //! - **RSA**: 乘法同态加密，支持密文乘法和幂运算
 //! - **Paillier**: 加法同态加密，支持密文加法和标量乘法
+//! - **指数 ElGamal**: secp256k1 上的加法同态加密，用小步大步法解码较小的明文，适合投票计数和聚合
 //! 
 //! ### 全同态加密
 //! - **BFV**: 全同态加密方案，支持任意深度的加法和乘法运算
@@ -31,22 +32,29 @@
 //! 
 //! ```rust
 //! use mpc_api::homomorphic_encryption::*;
+//! use num_bigint::BigUint;
 //! 
-//! // Paillier 加法同态加密
-//! let (pk, sk) = PaillierScheme::keygen()?;
-//! let c1 = PaillierScheme::encrypt(&pk, &42)?;
-//! let c2 = PaillierScheme::encrypt(&pk, &58)?;
-//! let c_sum = PaillierScheme::add_ciphertexts(&pk, &c1, &c2)?;
-//! let result = PaillierScheme::decrypt(&sk, &c_sum)?; // 结果为 100
+//! # fn main() -> mpc_api::Result<()> {
+//! // Paillier 加法同态加密（2048 位模数）
+//! let (pk, sk) = Paillier::keygen()?;
+//! let c1 = Paillier::encrypt(&pk, &BigUint::from(42u32))?;
+//! let c2 = Paillier::encrypt(&pk, &BigUint::from(58u32))?;
+//! let c_sum = Paillier::add_ciphertexts(&pk, &c1, &c2)?;
+//! let result = Paillier::decrypt(&sk, &c_sum)?;
+//! assert_eq!(result, BigUint::from(100u32));
+//! # Ok(())
+//! # }
 //! ```
 
 pub mod elgamal;
+pub mod exponential_elgamal;
 pub mod rsa;
 pub mod paillier;
 pub mod bfv;
 pub mod bgv;
 
 pub use elgamal::*;
+pub use exponential_elgamal::*;
 pub use rsa::*;
 pub use paillier::*;
 pub use bfv::*;
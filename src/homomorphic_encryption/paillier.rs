@@ -1,208 +1,160 @@
 //! Paillier encryption scheme (additively homomorphic)
+//!
+//! Keys, plaintexts and ciphertexts are arbitrary-precision integers: the modulus n = p * q is
+//! the product of two random primes of half the key size (2048 bits by default, see
+//! `Paillier::keygen_with_bits`), plaintexts live in Z_n and ciphertexts in Z_{n^2}^*.
+//!
+//! - The generator is fixed to g = n + 1, so g^m = 1 + m * n (mod n^2) needs no exponentiation.
+//! - Decryption works modulo p^2 and q^2 separately and recombines with the CRT, which is about
+//!   four times faster than the textbook c^lambda mod n^2.
+//! - `encrypt_batch` / `decrypt_batch` process many values in parallel.
 
 use super::*;
-// use crate::secret_sharing::{field_add, field_mul}; // Unused imports
-use rand::{Rng, thread_rng};
+use crate::utils::math::bigint::generate_prime;
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rayon::prelude::*;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Default modulus size in bits
+pub const PAILLIER_DEFAULT_KEY_BITS: u64 = 2048;
+/// Smallest modulus size accepted by `Paillier::keygen_with_bits`
+pub const PAILLIER_MIN_KEY_BITS: u64 = 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PaillierPublicKey {
-    pub n: u64,      // n = p * q
-    pub n_squared: u64, // n^2
-    pub g: u64,      // generator
+    pub n: BigUint,         // n = p * q
+    pub n_squared: BigUint, // n^2
+    pub g: BigUint,         // generator, n + 1
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaillierPrivateKey {
-    pub lambda: u64, // lcm(p-1, q-1)
-    pub mu: u64,     // (L(g^lambda mod n^2))^(-1) mod n
-    pub n: u64,
+    pub lambda: BigUint,    // lcm(p-1, q-1)
+    pub mu: BigUint,        // (L(g^lambda mod n^2))^(-1) mod n
+    pub n: BigUint,
+    pub p: BigUint,
+    pub q: BigUint,
+    pub hp: BigUint,        // (L_p(g^(p-1) mod p^2))^(-1) mod p
+    pub hq: BigUint,        // (L_q(g^(q-1) mod q^2))^(-1) mod q
+    pub q_inverse: BigUint, // q^(-1) mod p
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PaillierCiphertext {
-    pub value: u64,
+    pub value: BigUint,
 }
 
 pub struct Paillier;
 
 impl Paillier {
-    fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
-        if exp == 0 {
-            return 1;
+    /// Generate a key pair with a modulus of `bits` bits (even, at least `PAILLIER_MIN_KEY_BITS`)
+    pub fn keygen_with_bits(bits: u64) -> Result<(PaillierPublicKey, PaillierPrivateKey)> {
+        if bits < PAILLIER_MIN_KEY_BITS || !bits.is_multiple_of(2) {
+            return Err(MpcError::CryptographicError(format!(
+                "Paillier modulus must have an even number of bits, at least {}", PAILLIER_MIN_KEY_BITS
+            )));
         }
-        
-        let mut result = 1u128;
-        let mut base = (base as u128) % (modulus as u128);
-        let mut exp = exp;
-        
-        while exp > 0 {
-            if exp % 2 == 1 {
-                result = (result * base) % (modulus as u128);
+
+        let (p, q) = loop {
+            let p = generate_prime(bits / 2)?;
+            let q = generate_prime(bits / 2)?;
+            // gcd(pq, (p-1)(q-1)) = 1 holds for distinct primes of equal size, checked anyway
+            if p != q && (&p * &q).gcd(&((&p - 1u32) * (&q - 1u32))).is_one() {
+                break (p, q);
             }
-            exp >>= 1;
-            base = (base * base) % (modulus as u128);
-        }
-        
-        (result % (modulus as u128)) as u64
+        };
+        let n = &p * &q;
+        let n_squared = &n * &n;
+        let g = &n + 1u32;
+
+        let lambda = (&p - 1u32).lcm(&(&q - 1u32));
+        let mu = Self::inverse(&Self::l_function(&g.modpow(&lambda, &n_squared), &n), &n)?;
+        let hp = Self::crt_factor(&g, &p)?;
+        let hq = Self::crt_factor(&g, &q)?;
+        let q_inverse = Self::inverse(&q, &p)?;
+
+        let pk = PaillierPublicKey { n: n.clone(), n_squared, g };
+        let sk = PaillierPrivateKey { lambda, mu, n, p, q, hp, hq, q_inverse };
+        Ok((pk, sk))
     }
-    
-    fn mod_inverse(a: u64, modulus: u64) -> Result<u64> {
-        let mut old_r = a as i128;
-        let mut r = modulus as i128;
-        let mut old_s = 1i128;
-        let mut s = 0i128;
-        
-        while r != 0 {
-            let quotient = old_r / r;
-            let temp_r = r;
-            r = old_r - quotient * r;
-            old_r = temp_r;
-            
-            let temp_s = s;
-            s = old_s - quotient * s;
-            old_s = temp_s;
-        }
-        
-        if old_r == 1 {
-            let result = if old_s < 0 {
-                (old_s + modulus as i128) as u64
-            } else {
-                old_s as u64
-            };
-            Ok(result)
-        } else {
-            Err(MpcError::CryptographicError("No modular inverse exists".to_string()))
-        }
+
+    /// Encrypt many plaintexts in parallel
+    pub fn encrypt_batch(pk: &PaillierPublicKey, plaintexts: &[BigUint]) -> Result<Vec<PaillierCiphertext>> {
+        plaintexts.par_iter().map(|m| Self::encrypt(pk, m)).collect()
     }
-    
-    fn gcd(a: u64, b: u64) -> u64 {
-        if b == 0 {
-            a
-        } else {
-            Self::gcd(b, a % b)
-        }
+
+    /// Decrypt many ciphertexts in parallel
+    pub fn decrypt_batch(sk: &PaillierPrivateKey, ciphertexts: &[PaillierCiphertext]) -> Result<Vec<BigUint>> {
+        ciphertexts.par_iter().map(|c| Self::decrypt(sk, c)).collect()
     }
-    
-    fn lcm(a: u64, b: u64) -> u64 {
-        (a * b) / Self::gcd(a, b)
+
+    fn inverse(a: &BigUint, modulus: &BigUint) -> Result<BigUint> {
+        a.modinv(modulus)
+            .ok_or_else(|| MpcError::CryptographicError("No modular inverse exists".to_string()))
     }
-    
-    fn l_function(x: u64, n: u64) -> u64 {
-        (x - 1) / n
+
+    fn l_function(x: &BigUint, n: &BigUint) -> BigUint {
+        (x - 1u32) / n
     }
-    
-    fn is_prime(n: u64) -> bool {
-        if n < 2 {
-            return false;
-        }
-        if n == 2 {
-            return true;
-        }
-        if n % 2 == 0 {
-            return false;
-        }
-        
-        let sqrt_n = (n as f64).sqrt() as u64;
-        for i in (3..=sqrt_n).step_by(2) {
-            if n % i == 0 {
-                return false;
-            }
-        }
-        true
+
+    /// h = (L_p(g^(p-1) mod p^2))^(-1) mod p
+    fn crt_factor(g: &BigUint, p: &BigUint) -> Result<BigUint> {
+        let p_squared = p * p;
+        Self::inverse(&Self::l_function(&g.modpow(&(p - 1u32), &p_squared), p), p)
     }
-    
-    fn generate_prime_near(target: u64) -> u64 {
-        let mut candidate = target;
-        while !Self::is_prime(candidate) {
-            candidate += 1;
-            if candidate > target + 1000 {
-                candidate = target - 1;
-                while candidate > 2 && !Self::is_prime(candidate) {
-                    candidate -= 1;
-                }
-                break;
-            }
+
+    /// m mod p = L_p(c^(p-1) mod p^2) * h_p mod p
+    fn decrypt_modulo(c: &BigUint, p: &BigUint, h: &BigUint) -> BigUint {
+        let p_squared = p * p;
+        Self::l_function(&c.modpow(&(p - 1u32), &p_squared), p) * h % p
+    }
+
+    /// Reject values outside Z_{n^2}^*
+    fn check_ciphertext(ciphertext: &PaillierCiphertext, n: &BigUint) -> Result<()> {
+        if ciphertext.value.is_zero() || ciphertext.value >= n * n || !ciphertext.value.gcd(n).is_one() {
+            return Err(MpcError::CryptographicError("Invalid Paillier ciphertext".to_string()));
         }
-        candidate
+        Ok(())
     }
 }
 
 impl HomomorphicEncryption for Paillier {
-    type PlaintextSpace = u64;
+    type PlaintextSpace = BigUint;
     type CiphertextSpace = PaillierCiphertext;
     type PublicKey = PaillierPublicKey;
     type PrivateKey = PaillierPrivateKey;
-    
+
     fn keygen() -> Result<(Self::PublicKey, Self::PrivateKey)> {
-        let mut rng = thread_rng();
-        
-        // Generate two primes p and q (simplified for demonstration)
-        let p = Self::generate_prime_near(rng.gen_range(1000..2000));
-        let q = Self::generate_prime_near(rng.gen_range(2000..3000));
-        
-        if p == q {
-            return Err(MpcError::CryptographicError("p and q must be different".to_string()));
-        }
-        
-        let n = p * q;
-        let n_squared = n * n;
-        
-        // Compute lambda = lcm(p-1, q-1)
-        let lambda = Self::lcm(p - 1, q - 1);
-        
-        // Choose g (simplified: g = n + 1)
-        let g = n + 1;
-        
-        // Compute mu = (L(g^lambda mod n^2))^(-1) mod n
-        let g_lambda = Self::mod_pow(g, lambda, n_squared);
-        let l_value = Self::l_function(g_lambda, n);
-        let mu = Self::mod_inverse(l_value, n)?;
-        
-        let pk = PaillierPublicKey {
-            n,
-            n_squared,
-            g,
-        };
-        
-        let sk = PaillierPrivateKey {
-            lambda,
-            mu,
-            n,
-        };
-        
-        Ok((pk, sk))
+        Self::keygen_with_bits(PAILLIER_DEFAULT_KEY_BITS)
     }
-    
+
     fn encrypt(pk: &Self::PublicKey, plaintext: &Self::PlaintextSpace) -> Result<Self::CiphertextSpace> {
-        let mut rng = thread_rng();
-        
+        if plaintext >= &pk.n {
+            return Err(MpcError::CryptographicError("Plaintext must be smaller than n".to_string()));
+        }
+        let mut rng = rand::thread_rng();
+
         // Generate random r coprime to n
-        let mut r = rng.gen_range(1..pk.n);
-        while Self::gcd(r, pk.n) != 1 {
-            r = rng.gen_range(1..pk.n);
+        let mut r = rng.gen_biguint_range(&BigUint::one(), &pk.n);
+        while !r.gcd(&pk.n).is_one() {
+            r = rng.gen_biguint_range(&BigUint::one(), &pk.n);
         }
-        
-        // Compute c = g^m * r^n mod n^2
-        let g_m = Self::mod_pow(pk.g, *plaintext, pk.n_squared);
-        let r_n = Self::mod_pow(r, pk.n, pk.n_squared);
-        let c = ((g_m as u128 * r_n as u128) % pk.n_squared as u128) as u64;
-        
-        Ok(PaillierCiphertext { value: c })
+
+        // Compute c = g^m * r^n mod n^2, with g^m = 1 + m*n for g = n + 1
+        let g_m = (plaintext * &pk.n + 1u32) % &pk.n_squared;
+        let r_n = r.modpow(&pk.n, &pk.n_squared);
+        Ok(PaillierCiphertext { value: g_m * r_n % &pk.n_squared })
     }
-    
+
     fn decrypt(sk: &Self::PrivateKey, ciphertext: &Self::CiphertextSpace) -> Result<Self::PlaintextSpace> {
-        let n_squared = sk.n * sk.n;
-        
-        // Compute c^lambda mod n^2
-        let c_lambda = Self::mod_pow(ciphertext.value, sk.lambda, n_squared);
-        
-        // Compute L(c^lambda mod n^2)
-        let l_value = Self::l_function(c_lambda, sk.n);
-        
-        // Compute m = L(c^lambda mod n^2) * mu mod n
-        let plaintext = ((l_value as u128 * sk.mu as u128) % sk.n as u128) as u64;
-        
-        Ok(plaintext)
+        Self::check_ciphertext(ciphertext, &sk.n)?;
+
+        // m_p = m mod p, m_q = m mod q, recombined as m = m_q + q * ((m_p - m_q) * q^(-1) mod p)
+        let m_p = Self::decrypt_modulo(&ciphertext.value, &sk.p, &sk.hp);
+        let m_q = Self::decrypt_modulo(&ciphertext.value, &sk.q, &sk.hq);
+        let difference = (m_p + &sk.p - &m_q % &sk.p) % &sk.p;
+        Ok(m_q + &sk.q * (difference * &sk.q_inverse % &sk.p))
     }
 }
 
@@ -213,36 +165,34 @@ impl AdditivelyHomomorphic for Paillier {
         c2: &Self::CiphertextSpace,
     ) -> Result<Self::CiphertextSpace> {
         // Paillier addition: E(m1) * E(m2) = E(m1 + m2)
-        let result = ((c1.value as u128 * c2.value as u128) % pk.n_squared as u128) as u64;
-        Ok(PaillierCiphertext { value: result })
+        Ok(PaillierCiphertext { value: &c1.value * &c2.value % &pk.n_squared })
     }
-    
+
     fn scalar_multiply(
         pk: &Self::PublicKey,
         ciphertext: &Self::CiphertextSpace,
         scalar: &Self::PlaintextSpace,
     ) -> Result<Self::CiphertextSpace> {
         // Paillier scalar multiplication: E(m)^k = E(k*m)
-        let result = Self::mod_pow(ciphertext.value, *scalar, pk.n_squared);
-        Ok(PaillierCiphertext { value: result })
+        Ok(PaillierCiphertext { value: ciphertext.value.modpow(&(scalar % &pk.n), &pk.n_squared) })
     }
 }
 
 // Paillier utility functions
 impl Paillier {
     pub fn encrypt_zero(pk: &PaillierPublicKey) -> Result<PaillierCiphertext> {
-        Self::encrypt(pk, &0u64)
+        Self::encrypt(pk, &BigUint::zero())
     }
-    
+
     pub fn negate_ciphertext(
         pk: &PaillierPublicKey,
         ciphertext: &PaillierCiphertext,
     ) -> Result<PaillierCiphertext> {
-        // To negate: multiply by encryption of (n-1) which represents -1 mod n
-        let neg_one = pk.n - 1;
-        Self::scalar_multiply(pk, ciphertext, &neg_one)
+        // E(m)^(-1) = E(-m) = E(n - m)
+        Self::check_ciphertext(ciphertext, &pk.n)?;
+        Ok(PaillierCiphertext { value: Self::inverse(&ciphertext.value, &pk.n_squared)? })
     }
-    
+
     pub fn subtract_ciphertexts(
         pk: &PaillierPublicKey,
         c1: &PaillierCiphertext,
@@ -252,7 +202,7 @@ impl Paillier {
         let neg_c2 = Self::negate_ciphertext(pk, c2)?;
         Self::add_ciphertexts(pk, c1, &neg_c2)
     }
-    
+
     pub fn randomize_ciphertext(
         pk: &PaillierPublicKey,
         ciphertext: &PaillierCiphertext,
@@ -262,4 +212,3 @@ impl Paillier {
         Self::add_ciphertexts(pk, ciphertext, &zero_encryption)
     }
 }
-
@@ -1,7 +1,7 @@
 //! # MPC API - 安全多方计算 (Secure Multi-Party Computation) 库
 //! 
 //! 这是一个用 Rust 实现的全面的安全多方计算库，提供了各种密码学原语和协议的实现。
-//! 所有计算都在 u64 有限域上进行，使用素数 p = 2^61 - 1 作为模数。
+//! 所有计算都在 u64 有限域上进行，使用 Goldilocks 素数 p = 2^64 - 2^32 + 1 作为模数。
 //! 
 //! ## 核心组件 (Core Components)
 //! 
@@ -38,9 +38,22 @@
 //! ### 高级协议 (Advanced Protocols)
 //! - **投币协议**: 安全的随机数生成协议
 //! - **承诺方案**: Pedersen、Hash-based、Merkle tree 承诺
+//! - **联合生成元设置**: 各方联合派生 Pedersen/ElGamal 生成元，派生过程公开可验证
 //! - **消息认证码**: HMAC、Poly1305、GMAC、CMAC
 //! - **SPDZ 协议**: 带认证的秘密分享协议
 //! 
+//! ### 作业调度 (Job Scheduler)
+//! - **持久化作业队列**: 带优先级、依赖、重试策略和工作线程池的 MPC 任务队列
+//! 
+//! ### 存储 (Storage)
+//! - **可插拔存储后端**: 预处理池、会话和审计日志共用的命名空间键值存储，内置内存、文件和 sled 实现
+//! 
+//! ### 分布式协调 (Coordination)
+//! - **成员与纪元**: 通过 Redis/etcd 发布节点存活信息、商定会话成员和纪元、存放公开承诺
+//! 
+//! ### 节点 (Node)
+//! - **多进程流水线**: 独立节点进程经 TCP 执行密钥生成、预处理、在线计算和输出认证，供端到端集成测试使用
+//! 
 //! ## 设计原则 (Design Principles)
 //! 
 //! 1. **安全性**: 所有协议都实现了标准的安全性要求
@@ -49,36 +62,64 @@
 //! 4. **可扩展性**: 基于特征的设计便于添加新实现
 //! 5. **有限域运算**: 所有计算都在 u64 有限域上进行
 //! 
+//! ## 功能特性 (Cargo Features)
+//! 
+//! 默认启用全部特性。只需要秘密分享、承诺等核心功能时，可以关闭默认特性以避免
+//! 引入 tokio、HTTP 栈和后台威胁检测线程：
+//! 
+//! - `network`: P2P 网络、网络消息与网络管理器（依赖 tokio）
+//! - `http`: HTTP API 服务端与客户端（隐含 `network`）
+//! - `garbled-circuits`: 混淆电路
+//! - `he`: 同态加密以及基于 BFV 的三元组生成
+//! - `zk`: 零知识证明
+//! - `security-monitor`: 安全模块的后台威胁检测线程
+//! - `scheduler`: 持久化作业调度器（依赖 tokio 与 sled）
+//! - `sled`: 基于 sled 的存储后端
+//! - `coordination`: Redis 与 etcd 协调服务客户端
+//! - `node`: 多进程节点流水线与 `mpc_node` 可执行文件（隐含 `network` 与 `he`）
+//! - `internals`: 公开原始域运算（`field_add`、`field_mul` 等）等内部实现，默认关闭
+//! 
+//! ## API 稳定性 (API Stability)
+//! 
+//! 根模块只导出各功能模块、`MpcError`、`Result` 和 `prelude`，不再把各模块的
+//! 全部内容平铺到根路径。常用类型和 trait 通过 `mpc_api::prelude::*` 引入，
+//! 其余公开项使用完整的模块路径：
+//! 
+//! ```rust
+//! use mpc_api::prelude::*;
+//! use mpc_api::beaver_triples::TripleGeneratorKind;
+//! ```
+//! 
+//! 原始域运算属于实现细节，只有启用 `internals` 特性时才公开，不受语义化版本约束；
+//! 域的泛型化、分享结构的调整等内部重构不会因此破坏下游代码。
+//! 
 
 pub mod secret_sharing;
+#[cfg(feature = "garbled-circuits")]
 pub mod garbled_circuits;
 pub mod oblivious_transfer;
+#[cfg(feature = "he")]
 pub mod homomorphic_encryption;
 pub mod elliptic_curve;
 pub mod protocols;
 pub mod commitment;
 pub mod authentication;
 pub mod spdz;
+#[cfg(feature = "zk")]
 pub mod zero_knowledge;
 pub mod beaver_triples;
 pub mod utils;
 pub mod security;
+pub mod storage;
+pub mod coordination;
+#[cfg(feature = "node")]
+pub mod node;
+#[cfg(feature = "network")]
 pub mod network;
+#[cfg(feature = "scheduler")]
+pub mod scheduler;
 
-pub use secret_sharing::*;
-pub use garbled_circuits::*;
-pub use oblivious_transfer::*;
-pub use homomorphic_encryption::*;
-pub use elliptic_curve::*;
-pub use protocols::*;
-pub use commitment::*;
-pub use authentication::*;
-pub use spdz::*;
-// pub use zero_knowledge::*; // Unused import - zero knowledge not fully implemented
-pub use beaver_triples::*;
-pub use utils::*;
-pub use security::*;
-pub use network::*;
+pub mod prelude;
 
 use thiserror::Error;
 
@@ -100,6 +141,11 @@ pub enum MpcError {
     NetworkError(String),
     #[error("Authentication error: {0}")]
     AuthenticationError(String),
+    #[error("Storage error: {0}")]
+    StorageError(String),
+    #[cfg(feature = "garbled-circuits")]
+    #[error("Garbling integrity error: {0}")]
+    GarblingIntegrity(#[from] garbled_circuits::GarblingIntegrityError),
 }
 
 impl From<String> for MpcError {
@@ -0,0 +1,357 @@
+//! # 节点能力自测 (Node Capability Benchmark)
+//!
+//! 异构节点的算力差别很大，协调方按节点数量平均分配工作时，最慢的节点决定整体耗时。
+//! `CapabilityBenchmark` 在本节点上运行几个简短的微基准测试，得到机器可读的
+//! `CapabilityReport`，供预处理调优器和远程协调方按能力比例分配工作：
+//!
+//! - **域乘法**: 每秒 `field_mul` 次数
+//! - **混淆**: 每秒混淆的门数（需要 `garbled-circuits` 特性，否则为 `None`）
+//! - **OT**: 每秒 OT 扩展得到的 OT 数量
+//! - **三元组验证**: 每秒验证的 Beaver 三元组数量
+//! - **网络**: 到各个对等节点的 TCP 建连往返时间
+//!
+//! ## 校准
+//!
+//! 每个原语先把批大小从 1 开始翻倍，直到一批的耗时达到预算的 1/8，
+//! 再以该批大小重复运行直到用完预算，吞吐量为完成的操作数除以实际耗时。
+//! 单次计时足够长，计时器精度和调用开销不影响结果；默认每个原语 50 毫秒，
+//! 整个自测约 0.3 秒。测量是单线程的，`CapabilityReport::capacity` 按可用并行度放大。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::network::capability::*;
+//! use std::time::Duration;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let benchmark = CapabilityBenchmark::new("node-1").with_budget(Duration::from_millis(5))?;
+//! let report = benchmark.measure_local()?;
+//! assert!(report.field_mul_per_sec > 0.0);
+//!
+//! // 按能力把 1000 个三元组的验证工作分给两个节点
+//! let shares = assign_work(&[report.clone(), report], Primitive::TripleVerify, 1000)?;
+//! assert_eq!(shares.iter().sum::<usize>(), 1000);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::beaver_triples::{BeaverTripleGenerator, NetworkProfile, TrustedPartyBeaverGenerator};
+use crate::oblivious_transfer::OTExtension;
+use crate::secret_sharing::field_mul;
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant, SystemTime};
+
+/// 每个原语的默认测量预算
+pub const DEFAULT_BENCHMARK_BUDGET: Duration = Duration::from_millis(50);
+
+/// 校准时批大小的上限
+const MAX_CALIBRATION_BATCH: usize = 1 << 24;
+
+/// 三元组验证使用的参与方数量和门限
+const VERIFY_PARTY_COUNT: usize = 3;
+const VERIFY_THRESHOLD: usize = 2;
+
+/// 参与验证基准测试的三元组数量，循环使用
+const VERIFY_TRIPLES: usize = 64;
+
+/// 混淆基准测试使用的加法器位宽
+#[cfg(feature = "garbled-circuits")]
+const GARBLE_ADDER_BITS: usize = 32;
+
+/// 被测量的原语
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Primitive {
+    /// 域乘法
+    FieldMul,
+    /// 混淆一个门
+    GarbleGate,
+    /// 一次 OT
+    ObliviousTransfer,
+    /// 验证一个 Beaver 三元组
+    TripleVerify,
+}
+
+/// 到一个对等节点的往返时间
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerRtt {
+    /// 对等节点地址
+    pub address: SocketAddr,
+    /// 多次测量中的最小往返时间，节点不可达时为 `None`
+    pub rtt: Option<Duration>,
+}
+
+/// 节点能力报告
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityReport {
+    /// 节点标识
+    pub node_id: String,
+    /// 测量时间
+    pub measured_at: SystemTime,
+    /// 每个原语的测量预算
+    pub budget: Duration,
+    /// 可用的并行度（逻辑 CPU 数）
+    pub available_parallelism: usize,
+    /// 每秒域乘法次数
+    pub field_mul_per_sec: f64,
+    /// 每秒混淆的门数，未启用 `garbled-circuits` 特性时为 `None`
+    pub garble_gates_per_sec: Option<f64>,
+    /// 每秒 OT 数量
+    pub ot_per_sec: f64,
+    /// 每秒验证的三元组数量
+    pub triple_verify_per_sec: f64,
+    /// 到各个对等节点的往返时间
+    pub peer_rtts: Vec<PeerRtt>,
+}
+
+impl CapabilityReport {
+    /// 单线程吞吐量（每秒操作数），本节点不支持该原语时返回 `None`
+    pub fn throughput(&self, primitive: Primitive) -> Option<f64> {
+        match primitive {
+            Primitive::FieldMul => Some(self.field_mul_per_sec),
+            Primitive::GarbleGate => self.garble_gates_per_sec,
+            Primitive::ObliviousTransfer => Some(self.ot_per_sec),
+            Primitive::TripleVerify => Some(self.triple_verify_per_sec),
+        }
+    }
+
+    /// 估计的整机吞吐量：单线程吞吐量乘以可用并行度
+    pub fn capacity(&self, primitive: Primitive) -> Option<f64> {
+        self.throughput(primitive)
+            .map(|rate| rate * self.available_parallelism as f64)
+    }
+
+    /// 单轮通信延迟：可达对等节点中最慢的往返时间，没有可达节点时返回 `None`
+    pub fn round_latency(&self) -> Option<Duration> {
+        self.peer_rtts.iter().filter_map(|peer| peer.rtt).max()
+    }
+
+    /// 转换为预处理调优器使用的网络条件
+    ///
+    /// # 参数
+    /// - `bandwidth`: 带宽（字节/秒），自测不测量带宽，由调用方提供
+    pub fn network_profile(&self, bandwidth: u64) -> Result<NetworkProfile> {
+        NetworkProfile::new(self.round_latency().unwrap_or(Duration::ZERO), bandwidth)
+    }
+}
+
+/// 节点能力自测
+#[derive(Debug, Clone)]
+pub struct CapabilityBenchmark {
+    node_id: String,
+    budget: Duration,
+    peers: Vec<SocketAddr>,
+    rtt_samples: usize,
+    rtt_timeout: Duration,
+}
+
+impl CapabilityBenchmark {
+    /// 创建自测，默认每个原语预算 50 毫秒，不测量网络
+    ///
+    /// # 参数
+    /// - `node_id`: 写入报告的节点标识
+    pub fn new(node_id: &str) -> Self {
+        Self {
+            node_id: node_id.to_string(),
+            budget: DEFAULT_BENCHMARK_BUDGET,
+            peers: Vec::new(),
+            rtt_samples: 3,
+            rtt_timeout: Duration::from_secs(1),
+        }
+    }
+
+    /// 设置每个原语的测量预算，必须大于 0
+    pub fn with_budget(mut self, budget: Duration) -> Result<Self> {
+        if budget.is_zero() {
+            return Err(MpcError::ProtocolError("Benchmark budget must be positive".to_string()));
+        }
+        self.budget = budget;
+        Ok(self)
+    }
+
+    /// 设置需要测量往返时间的对等节点
+    pub fn with_peers(mut self, peers: Vec<SocketAddr>) -> Self {
+        self.peers = peers;
+        self
+    }
+
+    /// 设置每个对等节点的测量次数和单次超时
+    pub fn with_rtt_probe(mut self, samples: usize, timeout: Duration) -> Result<Self> {
+        if samples == 0 || timeout.is_zero() {
+            return Err(MpcError::ProtocolError(
+                "RTT probe needs at least one sample and a positive timeout".to_string()
+            ));
+        }
+        self.rtt_samples = samples;
+        self.rtt_timeout = timeout;
+        Ok(self)
+    }
+
+    /// 节点标识
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// 每个原语的测量预算
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// 只测量本地原语，报告中的 `peer_rtts` 为空
+    ///
+    /// 在当前线程同步运行，耗时约为预算的 5 倍。
+    pub fn measure_local(&self) -> Result<CapabilityReport> {
+        Ok(CapabilityReport {
+            node_id: self.node_id.clone(),
+            measured_at: SystemTime::now(),
+            budget: self.budget,
+            available_parallelism: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            field_mul_per_sec: self.field_mul_rate()?,
+            garble_gates_per_sec: self.garble_rate()?,
+            ot_per_sec: self.ot_rate()?,
+            triple_verify_per_sec: self.triple_verify_rate()?,
+            peer_rtts: Vec::new(),
+        })
+    }
+
+    /// 测量本地原语和到对等节点的往返时间
+    ///
+    /// 本地测量在阻塞线程池中运行，不占用异步运行时的工作线程。
+    pub async fn measure(&self) -> Result<CapabilityReport> {
+        let benchmark = self.clone();
+        let mut report = tokio::task::spawn_blocking(move || benchmark.measure_local())
+            .await
+            .map_err(|e| MpcError::ProtocolError(format!("Benchmark task failed: {}", e)))??;
+        report.peer_rtts = self.measure_rtts().await;
+        Ok(report)
+    }
+
+    /// 测量到每个对等节点的 TCP 建连时间，取多次测量的最小值
+    pub async fn measure_rtts(&self) -> Vec<PeerRtt> {
+        let mut rtts = Vec::with_capacity(self.peers.len());
+        for &address in &self.peers {
+            let mut best: Option<Duration> = None;
+            for _ in 0..self.rtt_samples {
+                let start = Instant::now();
+                let connect = tokio::net::TcpStream::connect(address);
+                if let Ok(Ok(_stream)) = tokio::time::timeout(self.rtt_timeout, connect).await {
+                    let rtt = start.elapsed();
+                    best = Some(best.map_or(rtt, |best| best.min(rtt)));
+                }
+            }
+            rtts.push(PeerRtt { address, rtt: best });
+        }
+        rtts
+    }
+
+    fn field_mul_rate(&self) -> Result<f64> {
+        let mut acc = 0x1234_5678_9abc_def0u64;
+        calibrated_rate(self.budget, |n| {
+            for _ in 0..n {
+                acc = field_mul(black_box(acc), 0x0fed_cba9_8765_4321);
+            }
+            black_box(acc);
+            Ok(n as u64)
+        })
+    }
+
+    #[cfg(feature = "garbled-circuits")]
+    fn garble_rate(&self) -> Result<Option<f64>> {
+        use crate::garbled_circuits::{Circuit, GarblePlan};
+
+        let circuit = Circuit::create_adder(GARBLE_ADDER_BITS);
+        let gates = circuit.gates.len() as u64;
+        let plan = GarblePlan::new(&circuit)?;
+        calibrated_rate(self.budget, |n| {
+            for _ in 0..n {
+                black_box(plan.garble());
+            }
+            Ok(n as u64 * gates)
+        })
+        .map(Some)
+    }
+
+    #[cfg(not(feature = "garbled-circuits"))]
+    fn garble_rate(&self) -> Result<Option<f64>> {
+        Ok(None)
+    }
+
+    fn ot_rate(&self) -> Result<f64> {
+        let mut extension = OTExtension::new(128);
+        extension.setup_base_ots()?;
+        calibrated_rate(self.budget, |n| {
+            let choices: Vec<bool> = (0..n).map(|i| i % 2 == 1).collect();
+            black_box(extension.extend_ots(n, &choices)?);
+            Ok(n as u64)
+        })
+    }
+
+    fn triple_verify_rate(&self) -> Result<f64> {
+        let mut generator = TrustedPartyBeaverGenerator::new(VERIFY_PARTY_COUNT, VERIFY_THRESHOLD, 0, None)?;
+        let triples = generator.generate_batch(VERIFY_TRIPLES)?;
+        calibrated_rate(self.budget, |n| {
+            for triple in triples.iter().cycle().take(n) {
+                if !triple.verify(VERIFY_THRESHOLD)? {
+                    return Err(MpcError::ProtocolError("Benchmark triple failed verification".to_string()));
+                }
+            }
+            Ok(n as u64)
+        })
+    }
+}
+
+/// 按节点能力比例分配工作量
+///
+/// 每个节点的份额与 `CapabilityReport::capacity` 成正比，按最大余数法取整，
+/// 份额之和恰好等于 `total`。不支持该原语的节点分到 0。
+///
+/// # 参数
+/// - `reports`: 各节点的能力报告
+/// - `primitive`: 决定工作量的原语
+/// - `total`: 总工作量
+///
+/// # 返回值
+/// 返回与 `reports` 一一对应的工作量；没有任何节点支持该原语时返回错误
+pub fn assign_work(reports: &[CapabilityReport], primitive: Primitive, total: usize) -> Result<Vec<usize>> {
+    let capacities: Vec<f64> = reports.iter()
+        .map(|report| report.capacity(primitive).filter(|c| c.is_finite() && *c > 0.0).unwrap_or(0.0))
+        .collect();
+    let sum: f64 = capacities.iter().sum();
+    if sum <= 0.0 {
+        return Err(MpcError::ProtocolError(format!("No node reports capacity for {:?}", primitive)));
+    }
+
+    let exact: Vec<f64> = capacities.iter().map(|c| c / sum * total as f64).collect();
+    let mut shares: Vec<usize> = exact.iter().map(|e| e.floor() as usize).collect();
+    let mut order: Vec<usize> = (0..reports.len()).filter(|&i| capacities[i] > 0.0).collect();
+    order.sort_by(|&a, &b| (exact[b] - exact[b].floor()).total_cmp(&(exact[a] - exact[a].floor())));
+    let assigned: usize = shares.iter().sum();
+    for &i in order.iter().cycle().take(total.saturating_sub(assigned)) {
+        shares[i] += 1;
+    }
+    Ok(shares)
+}
+
+/// 校准批大小后在预算内重复运行，返回每秒完成的操作数
+///
+/// `run(n)` 执行一批大小为 `n` 的工作，返回完成的操作数。
+fn calibrated_rate(budget: Duration, mut run: impl FnMut(usize) -> Result<u64>) -> Result<f64> {
+    let mut batch = 1;
+    loop {
+        let start = Instant::now();
+        run(batch)?;
+        if start.elapsed() >= budget / 8 || batch >= MAX_CALIBRATION_BATCH {
+            break;
+        }
+        batch *= 2;
+    }
+
+    let start = Instant::now();
+    let mut operations = 0u64;
+    while start.elapsed() < budget {
+        operations += run(batch)?;
+    }
+    Ok(operations as f64 / start.elapsed().as_secs_f64())
+}
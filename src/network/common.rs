@@ -3,10 +3,11 @@
 //! 本模块定义了网络层的通用数据结构、错误类型、配置参数和工具函数。
 //! 为 P2P 和 HTTP 网络模块提供共享的基础功能。
 
-use std::{fmt, net::SocketAddr, time::Duration};
+use std::{fmt, future::Future, net::SocketAddr, pin::Pin, time::Duration};
 use serde::{Deserialize, Serialize};
 use crate::network::p2p::PeerConfig;
-use crate::network::http::RestConfig;
+use crate::network::protocol::NetworkMessage;
+use crate::network::security::TlsConfig;
 
 /// 网络操作结果类型
 pub type NetworkResult<T> = std::result::Result<T, NetworkError>;
@@ -84,6 +85,54 @@ pub struct NetworkConfig {
     pub global_settings: GlobalNetworkSettings,
 }
 
+/// HTTP 服务器配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestConfig {
+    /// 监听主机地址
+    pub host: String,
+    /// 监听端口
+    pub port: u16,
+    /// 是否启用 TLS
+    pub enable_tls: bool,
+    /// TLS 配置
+    pub tls_config: Option<TlsConfig>,
+    /// 最大连接数
+    pub max_connections: usize,
+    /// 请求超时时间（毫秒）
+    pub request_timeout: u64,
+    /// 最大请求体大小（字节）
+    pub max_body_size: usize,
+    /// 是否启用 CORS
+    pub enable_cors: bool,
+    /// 允许的源站列表
+    pub allowed_origins: Vec<String>,
+    /// JWT 密钥
+    pub jwt_secret: String,
+    /// API 版本
+    pub api_version: String,
+    /// 日志级别
+    pub log_level: String,
+}
+
+impl Default for RestConfig {
+    fn default() -> Self {
+        RestConfig {
+            host: "127.0.0.1".to_string(),
+            port: 3000,
+            enable_tls: false,
+            tls_config: None,
+            max_connections: 100,
+            request_timeout: 30000, // 30 seconds
+            max_body_size: 1024 * 1024, // 1MB
+            enable_cors: true,
+            allowed_origins: vec!["*".to_string()],
+            jwt_secret: "default_jwt_secret_change_in_production".to_string(),
+            api_version: "v1".to_string(),
+            log_level: "info".to_string(),
+        }
+    }
+}
+
 /// 全局网络设置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlobalNetworkSettings {
@@ -256,6 +305,55 @@ pub struct NetworkMetrics {
     pub packet_loss_rate: f64,
 }
 
+/// 参与方 ID，即参与方在名单中的位置
+pub type PartyId = usize;
+
+/// 传输操作返回的 future
+pub type TransportFuture<'a, T> = Pin<Box<dyn Future<Output = NetworkResult<T>> + Send + 'a>>;
+
+/// 按参与方 ID 收发 `NetworkMessage` 的异步传输
+///
+/// 协议代码只依赖这个 trait，不依赖具体的网络实现：`P2PTransport` 基于 P2P 节点，
+/// `InMemoryTransport` 基于进程内通道（用于测试），`LongPollingTransport` 基于 HTTP 长轮询。
+/// 同一参与方发出的消息按发送顺序到达；`recv` 返回任意参与方的下一条消息，
+/// 按轮次整理消息由上层（例如 `BlockingTransport`）负责。
+pub trait Transport: Send + Sync {
+    /// 本参与方的 ID
+    fn party_id(&self) -> PartyId;
+
+    /// 全部参与方的 ID（含本方），升序排列
+    fn parties(&self) -> Vec<PartyId>;
+
+    /// 向参与方 `to` 发送消息
+    fn send(&self, to: PartyId, message: NetworkMessage) -> TransportFuture<'_, ()>;
+
+    /// 接收下一条消息，返回 `(发送方, 消息)`
+    fn recv(&self) -> TransportFuture<'_, (PartyId, NetworkMessage)>;
+
+    /// 除本方外的参与方
+    fn peers(&self) -> Vec<PartyId> {
+        let own = self.party_id();
+        self.parties().into_iter().filter(|&party| party != own).collect()
+    }
+
+    /// 向除本方外的所有参与方发送同一条消息
+    fn broadcast(&self, message: NetworkMessage) -> TransportFuture<'_, ()> {
+        Box::pin(async move {
+            for peer in self.peers() {
+                self.send(peer, message.clone()).await?;
+            }
+            Ok(())
+        })
+    }
+
+    /// 在 `wait` 内接收下一条消息，超时返回 `Timeout`
+    fn recv_timeout(&self, wait: Duration) -> TransportFuture<'_, (PartyId, NetworkMessage)> {
+        Box::pin(async move {
+            tokio::time::timeout(wait, self.recv()).await.map_err(|_| NetworkError::Timeout)?
+        })
+    }
+}
+
 /// 网络工具函数
 pub mod utils {
     use super::*;
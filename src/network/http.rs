@@ -38,6 +38,34 @@
 //! - `POST /api/v1/keys/share` - 分享密钥
 //! - `DELETE /api/v1/keys/{id}` - 删除密钥
 //!
+//! ### 作业调度（需要 `scheduler` 特性，通过 `HttpServer::register_scheduler` 启用）
+//! - `GET /api/v1/jobs` - 获取作业列表和三元组池大小
+//! - `GET /api/v1/jobs?id={id}` - 获取作业状态
+//! - `POST /api/v1/jobs` - 提交作业（请求体为 JSON 编码的 `JobRequest`）
+//! - `DELETE /api/v1/jobs?id={id}` - 取消尚未开始的作业
+//!
+//! ### 节点入网（通过 `HttpServer::register_onboarding` 启用）
+//! - `GET /api/v1/onboarding` - 获取委员会的信任锚（成员公钥名单和法定数量）
+//! - `POST /api/v1/onboarding` - 提交证书请求（请求体为 JSON 编码的 `NodeCertificateRequest`），
+//!   返回委员会签发的 `NodeCertificate`
+//!
+//! ### 节点能力（通过 `HttpServer::register_capability` 启用）
+//! - `GET /api/v1/capability` - 获取最近一次的 `CapabilityReport`，尚未测量时先运行自测
+//! - `GET /api/v1/capability?refresh=true` - 重新运行自测并返回新的报告
+//!
+//! ### 运行指标
+//! - `GET /metrics` - 以 Prometheus 文本格式导出全局 `MetricsRegistry` 中的指标，
+//!   可以通过 `HttpServer::register_metrics` 换成其他注册表
+//!
+//! ### 消息中转（通过 `HttpServer::register_mailbox` 启用）
+//! - `POST /api/v1/mailbox?to={party}` - 向参与方的邮箱投递 `NetworkMessage`
+//! - `GET /api/v1/mailbox?party={party}&wait={ms}` - 长轮询取走邮箱中的消息，
+//!   `LongPollingTransport` 在此之上实现 `Transport`
+//!
+//! ### WebSocket 传输（`WebSocketServer` / `WebSocketClient`）
+//! - 在 WebSocket 文本帧中以 JSON 形式传输 `NetworkMessage`，供浏览器端的监控面板等轻量客户端使用
+//! - 客户端以观察者或输入提供方的身份接入，支持 Ping/Pong 保活和有界队列背压
+//!
 //! ## 📚 使用示例
 //!
 //! ```rust
@@ -67,7 +95,7 @@ use std::{
     time::{Duration, SystemTime},
 };
 use tokio::{
-    sync::{RwLock, Mutex},
+    sync::RwLock,
     time::timeout,
 };
 use serde::{Deserialize, Serialize};
@@ -75,57 +103,20 @@ use uuid::Uuid;
 
 use crate::network::{
     common::{NetworkError, NetworkResult},
-    security::{NetworkSecurity, TlsConfig},
+    capability::{CapabilityBenchmark, CapabilityReport},
+    onboarding::{CertificateCommittee, NodeCertificateRequest},
+    security::{NetworkSecurity, RateLimiter},
     ServiceStatus,
 };
+pub use crate::network::common::RestConfig;
+use crate::utils::metrics::{MetricsRegistry, PROMETHEUS_CONTENT_TYPE};
+#[cfg(feature = "scheduler")]
+use crate::scheduler::{JobId, JobRequest, JobScheduler};
 
-/// HTTP 服务器配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RestConfig {
-    /// 监听主机地址
-    pub host: String,
-    /// 监听端口
-    pub port: u16,
-    /// 是否启用 TLS
-    pub enable_tls: bool,
-    /// TLS 配置
-    pub tls_config: Option<TlsConfig>,
-    /// 最大连接数
-    pub max_connections: usize,
-    /// 请求超时时间（毫秒）
-    pub request_timeout: u64,
-    /// 最大请求体大小（字节）
-    pub max_body_size: usize,
-    /// 是否启用 CORS
-    pub enable_cors: bool,
-    /// 允许的源站列表
-    pub allowed_origins: Vec<String>,
-    /// JWT 密钥
-    pub jwt_secret: String,
-    /// API 版本
-    pub api_version: String,
-    /// 日志级别
-    pub log_level: String,
-}
-
-impl Default for RestConfig {
-    fn default() -> Self {
-        RestConfig {
-            host: "127.0.0.1".to_string(),
-            port: 3000,
-            enable_tls: false,
-            tls_config: None,
-            max_connections: 100,
-            request_timeout: 30000, // 30 seconds
-            max_body_size: 1024 * 1024, // 1MB
-            enable_cors: true,
-            allowed_origins: vec!["*".to_string()],
-            jwt_secret: "default_jwt_secret_change_in_production".to_string(),
-            api_version: "v1".to_string(),
-            log_level: "info".to_string(),
-        }
-    }
-}
+pub mod mailbox;
+pub mod websocket;
+pub use mailbox::*;
+pub use websocket::*;
 
 /// HTTP 服务器
 pub struct HttpServer {
@@ -330,6 +321,9 @@ impl HttpServer {
         // API 信息
         self.register_route("/api/v1/info".to_string(), Box::new(InfoHandler)).await;
 
+        // Prometheus 指标
+        self.register_metrics(MetricsRegistry::global().clone()).await;
+
         println!("✅ 默认路由注册完成");
     }
 
@@ -349,7 +343,7 @@ impl HttpServer {
         self.register_middleware(Box::new(AuthMiddleware::new(&self.config.jwt_secret))).await;
 
         // 限流中间件
-        self.register_middleware(Box::new(RateLimitMiddleware::new(100, Duration::from_secs(60)))).await;
+        self.register_middleware(Box::new(RateLimitMiddleware::new(Arc::clone(&self.security)))).await;
 
         println!("✅ 默认中间件注册完成");
     }
@@ -564,6 +558,41 @@ impl HttpServer {
         routes.insert(path, handler);
     }
 
+    /// 注册作业调度接口 `/api/v1/jobs`
+    #[cfg(feature = "scheduler")]
+    pub async fn register_scheduler(&self, scheduler: Arc<JobScheduler>) {
+        self.register_route("/api/v1/jobs".to_string(), Box::new(JobsHandler::new(scheduler))).await;
+    }
+
+    /// 注册节点入网接口 `/api/v1/onboarding`
+    pub async fn register_onboarding(&self, committee: Arc<CertificateCommittee>) {
+        self.register_route("/api/v1/onboarding".to_string(), Box::new(OnboardingHandler::new(committee))).await;
+    }
+
+    /// 注册节点能力接口 `/api/v1/capability`
+    pub async fn register_capability(&self, benchmark: CapabilityBenchmark) {
+        self.register_route("/api/v1/capability".to_string(), Box::new(CapabilityHandler::new(benchmark))).await;
+    }
+
+    /// 注册指标接口 `/metrics`，替换默认导出的全局注册表
+    pub async fn register_metrics(&self, registry: MetricsRegistry) {
+        self.register_route("/metrics".to_string(), Box::new(MetricsHandler::new(registry))).await;
+    }
+
+    /// 注册消息中转接口 `/api/v1/mailbox`
+    ///
+    /// 返回与服务器共享邮箱的处理器，服务器所在进程内的参与方可以直接用它创建 `LongPollingTransport`。
+    pub async fn register_mailbox(&self, parties: usize) -> MailboxHandler {
+        let mailbox = MailboxHandler::new(parties);
+        self.register_route(MAILBOX_PATH.to_string(), Box::new(mailbox.clone())).await;
+        mailbox
+    }
+
+    /// 获取限流器，可以替换限流策略或使用共享的审计日志
+    pub fn rate_limiter(&self) -> &RateLimiter {
+        self.security.rate_limiter()
+    }
+
     /// 注册中间件
     pub async fn register_middleware(&self, middleware: Box<dyn Middleware>) {
         let mut middlewares = self.middlewares.write().await;
@@ -851,6 +880,167 @@ impl RouteHandler for InfoHandler {
     }
 }
 
+/// 作业调度处理器
+#[cfg(feature = "scheduler")]
+pub struct JobsHandler {
+    scheduler: Arc<JobScheduler>,
+}
+
+#[cfg(feature = "scheduler")]
+impl JobsHandler {
+    /// 创建作业调度处理器
+    pub fn new(scheduler: Arc<JobScheduler>) -> Self {
+        JobsHandler { scheduler }
+    }
+
+    fn job_id(request: &HttpRequest) -> Option<JobId> {
+        request.query_params.get("id").and_then(|id| id.parse().ok()).map(JobId)
+    }
+
+    fn handle(&self, request: &HttpRequest) -> crate::Result<HttpResponse> {
+        let response = match (&request.method, Self::job_id(request)) {
+            (HttpMethod::GET, Some(id)) => match self.scheduler.job(id)? {
+                Some(job) => HttpResponse::json(&job),
+                None => Ok(HttpResponse::error(404, "作业未找到")),
+            },
+            (HttpMethod::GET, None) => HttpResponse::json(&serde_json::json!({
+                "jobs": self.scheduler.jobs()?,
+                "available_triples": self.scheduler.available_triples()?,
+            })),
+            (HttpMethod::POST, _) => match serde_json::from_slice::<JobRequest>(&request.body) {
+                Ok(job_request) => {
+                    let id = self.scheduler.submit(job_request)?;
+                    HttpResponse::json(&serde_json::json!({ "job_id": id.0 }))
+                }
+                Err(e) => Ok(HttpResponse::error(400, &format!("无效的作业请求: {}", e))),
+            },
+            (HttpMethod::DELETE, Some(id)) => {
+                let cancelled = self.scheduler.cancel(id)?;
+                HttpResponse::json(&serde_json::json!({ "job_id": id.0, "cancelled": cancelled }))
+            }
+            (HttpMethod::DELETE, None) => Ok(HttpResponse::error(400, "缺少作业 ID")),
+            _ => Ok(HttpResponse::error(405, "方法不被允许")),
+        };
+        Ok(response.unwrap_or_else(|e| HttpResponse::error(500, &e.to_string())))
+    }
+}
+
+#[cfg(feature = "scheduler")]
+impl RouteHandler for JobsHandler {
+    fn handle_request(&self, request: &HttpRequest) -> std::pin::Pin<Box<dyn std::future::Future<Output = NetworkResult<HttpResponse>> + Send + '_>> {
+        let result = self.handle(request);
+        Box::pin(async move {
+            match result {
+                Ok(response) => Ok(response),
+                Err(e) => Ok(HttpResponse::error(500, &e.to_string())),
+            }
+        })
+    }
+}
+
+/// 节点入网处理器
+pub struct OnboardingHandler {
+    committee: Arc<CertificateCommittee>,
+}
+
+impl OnboardingHandler {
+    /// 创建节点入网处理器
+    pub fn new(committee: Arc<CertificateCommittee>) -> Self {
+        OnboardingHandler { committee }
+    }
+
+    fn handle(&self, request: &HttpRequest) -> NetworkResult<HttpResponse> {
+        match request.method {
+            HttpMethod::GET => HttpResponse::json(&self.committee.trust_anchor()),
+            HttpMethod::POST => match serde_json::from_slice::<NodeCertificateRequest>(&request.body) {
+                Ok(csr) => match self.committee.issue(&csr) {
+                    Ok(certificate) => HttpResponse::json(&certificate),
+                    Err(e @ crate::MpcError::AuthenticationError(_)) => Ok(HttpResponse::error(403, &e.to_string())),
+                    Err(e @ crate::MpcError::ProtocolError(_)) => Ok(HttpResponse::error(400, &e.to_string())),
+                    Err(e) => Ok(HttpResponse::error(500, &e.to_string())),
+                },
+                Err(e) => Ok(HttpResponse::error(400, &format!("无效的证书请求: {}", e))),
+            },
+            _ => Ok(HttpResponse::error(405, "方法不被允许")),
+        }
+    }
+}
+
+impl RouteHandler for OnboardingHandler {
+    fn handle_request(&self, request: &HttpRequest) -> std::pin::Pin<Box<dyn std::future::Future<Output = NetworkResult<HttpResponse>> + Send + '_>> {
+        let result = self.handle(request);
+        Box::pin(async move { result })
+    }
+}
+
+/// 节点能力接口处理器
+///
+/// 自测耗时较长，结果会被缓存，只有 `refresh=true` 时才重新测量。
+pub struct CapabilityHandler {
+    benchmark: CapabilityBenchmark,
+    latest: RwLock<Option<CapabilityReport>>,
+}
+
+impl CapabilityHandler {
+    /// 创建节点能力处理器
+    pub fn new(benchmark: CapabilityBenchmark) -> Self {
+        CapabilityHandler { benchmark, latest: RwLock::new(None) }
+    }
+
+    async fn handle(&self, method: HttpMethod, refresh: bool) -> NetworkResult<HttpResponse> {
+        if method != HttpMethod::GET {
+            return Ok(HttpResponse::error(405, "方法不被允许"));
+        }
+        if !refresh {
+            if let Some(report) = self.latest.read().await.as_ref() {
+                return HttpResponse::json(report);
+            }
+        }
+        match self.benchmark.measure().await {
+            Ok(report) => {
+                let response = HttpResponse::json(&report);
+                *self.latest.write().await = Some(report);
+                response
+            }
+            Err(e) => Ok(HttpResponse::error(500, &e.to_string())),
+        }
+    }
+}
+
+impl RouteHandler for CapabilityHandler {
+    fn handle_request(&self, request: &HttpRequest) -> std::pin::Pin<Box<dyn std::future::Future<Output = NetworkResult<HttpResponse>> + Send + '_>> {
+        let method = request.method.clone();
+        let refresh = request.query_params.get("refresh").is_some_and(|value| value == "true");
+        Box::pin(self.handle(method, refresh))
+    }
+}
+
+/// 指标处理器，以 Prometheus 文本格式导出注册表中的指标
+pub struct MetricsHandler {
+    registry: MetricsRegistry,
+}
+
+impl MetricsHandler {
+    /// 创建指标处理器
+    pub fn new(registry: MetricsRegistry) -> Self {
+        MetricsHandler { registry }
+    }
+}
+
+impl RouteHandler for MetricsHandler {
+    fn handle_request(&self, request: &HttpRequest) -> std::pin::Pin<Box<dyn std::future::Future<Output = NetworkResult<HttpResponse>> + Send + '_>> {
+        let method = request.method.clone();
+        Box::pin(async move {
+            if method != HttpMethod::GET {
+                return Ok(HttpResponse::error(405, "方法不被允许"));
+            }
+            let mut response = HttpResponse::ok(self.registry.render().into_bytes());
+            response.headers.insert("Content-Type".to_string(), PROMETHEUS_CONTENT_TYPE.to_string());
+            Ok(response)
+        })
+    }
+}
+
 // ============================================================================
 // 中间件实现
 // ============================================================================
@@ -933,7 +1123,7 @@ impl Middleware for AuthMiddleware {
         let auth_header = request.headers.get("Authorization").cloned();
         Box::pin(async move {
             // 跳过公开端点
-            if path == "/health" || path.starts_with("/api/v1/info") {
+            if path == "/health" || path == "/metrics" || path.starts_with("/api/v1/info") {
                 return Ok(());
             }
 
@@ -963,62 +1153,29 @@ impl Middleware for AuthMiddleware {
 }
 
 /// 限流中间件
+///
+/// 按客户端 IP 检查 `NetworkSecurity` 中的限流配额。
 struct RateLimitMiddleware {
-    requests_per_window: u32,
-    window_duration: Duration,
-    request_counts: Arc<Mutex<HashMap<String, (u32, SystemTime)>>>,
+    security: Arc<NetworkSecurity>,
 }
 
 impl RateLimitMiddleware {
-    fn new(requests_per_window: u32, window_duration: Duration) -> Self {
-        RateLimitMiddleware {
-            requests_per_window,
-            window_duration,
-            request_counts: Arc::new(Mutex::new(HashMap::new())),
-        }
+    fn new(security: Arc<NetworkSecurity>) -> Self {
+        RateLimitMiddleware { security }
     }
 }
 
 impl Middleware for RateLimitMiddleware {
     fn before_request(&self, request: &mut HttpRequest) -> std::pin::Pin<Box<dyn std::future::Future<Output = NetworkResult<()>> + Send + '_>> {
-        let client_ip = request.client_ip.clone();
-        let request_counts = Arc::clone(&self.request_counts);
-        let window_duration = self.window_duration;
-        let requests_per_window = self.requests_per_window;
-        
-        Box::pin(async move {
-            let now = SystemTime::now();
-            let mut counts = request_counts.lock().await;
-            
-            match counts.get_mut(&client_ip) {
-                Some((count, window_start)) => {
-                    if now.duration_since(*window_start).unwrap_or_default() > window_duration {
-                        // 重置窗口
-                        *count = 1;
-                        *window_start = now;
-                    } else {
-                        *count += 1;
-                        if *count > requests_per_window {
-                            return Err(NetworkError::RateLimited(format!(
-                                "客户端 {} 请求频率超限", client_ip
-                            )));
-                        }
-                    }
-                }
-                None => {
-                    counts.insert(client_ip, (1, now));
-                }
-            }
-
-            Ok(())
-        })
+        let result = self.security.check_rate_limit(&request.client_ip, request.body.len());
+        Box::pin(async move { result })
     }
 
     fn after_response<'a>(&'a self, _request: &HttpRequest, response: &'a mut HttpResponse) -> std::pin::Pin<Box<dyn std::future::Future<Output = NetworkResult<()>> + Send + 'a>> {
-        let requests_per_window = self.requests_per_window;
+        let messages_per_second = self.security.rate_limiter().policy().messages_per_second;
         Box::pin(async move {
             // 添加限流相关的响应头
-            response.headers.insert("X-RateLimit-Limit".to_string(), requests_per_window.to_string());
+            response.headers.insert("X-RateLimit-Limit".to_string(), messages_per_second.to_string());
             Ok(())
         })
     }
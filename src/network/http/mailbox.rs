@@ -0,0 +1,250 @@
+//! # HTTP 长轮询邮箱 (HTTP Long-polling Mailbox)
+//!
+//! 参与方之间无法直接建立连接时（例如都位于 NAT 之后），可以经由一个 HTTP 服务器中转消息：
+//!
+//! - **投递**: `POST /api/v1/mailbox?to={party}`，请求体为 JSON 编码的 `NetworkMessage`，
+//!   消息的 `sender_id` 是发送方的参与方 ID
+//! - **取信**: `GET /api/v1/mailbox?party={party}&wait={ms}` 取走该参与方的全部消息；
+//!   邮箱为空时最多挂起 `wait` 毫秒（不超过 `MAX_POLL_WAIT`），有新消息立即返回
+//!
+//! `LongPollingTransport` 在邮箱接口上实现 `Transport`。邮箱本身不鉴别发送方，
+//! 部署时应启用认证中间件，只允许会话成员访问。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::network::common::Transport;
+//! use mpc_api::network::http::*;
+//! use mpc_api::network::protocol::NetworkMessage;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> mpc_api::network::NetworkResult<()> {
+//! let mailbox: Arc<dyn RouteHandler> = Arc::new(MailboxHandler::new(2));
+//! let alice = LongPollingTransport::new(0, 2, mailbox.clone(), Duration::from_millis(200));
+//! let bob = LongPollingTransport::new(1, 2, mailbox, Duration::from_millis(200));
+//!
+//! alice.send(1, NetworkMessage::new("share", b"42")).await?;
+//! let (from, message) = bob.recv().await?;
+//! assert_eq!(from, 0);
+//! assert_eq!(message.payload, b"42");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use tokio::{
+    sync::{Mutex, Notify},
+    time::Instant,
+};
+use uuid::Uuid;
+
+use super::{HttpMethod, HttpRequest, HttpResponse, RouteHandler};
+use crate::network::{
+    common::{NetworkError, NetworkResult, PartyId, Transport, TransportFuture},
+    protocol::NetworkMessage,
+};
+
+/// 邮箱接口路径
+pub const MAILBOX_PATH: &str = "/api/v1/mailbox";
+
+/// 单次取信请求最长挂起时间
+pub const MAX_POLL_WAIT: Duration = Duration::from_secs(30);
+
+/// 邮箱处理器
+///
+/// 为每个参与方保存一个先进先出的消息队列。克隆的处理器共享同一组邮箱，
+/// 因此可以一份注册到服务器，另一份供服务器所在进程内的参与方直接使用。
+#[derive(Clone)]
+pub struct MailboxHandler {
+    parties: usize,
+    mailboxes: Arc<Mutex<HashMap<PartyId, VecDeque<NetworkMessage>>>>,
+    arrivals: Arc<Notify>,
+}
+
+impl MailboxHandler {
+    /// 为 `parties` 个参与方创建邮箱
+    pub fn new(parties: usize) -> Self {
+        MailboxHandler {
+            parties,
+            mailboxes: Arc::new(Mutex::new(HashMap::new())),
+            arrivals: Arc::new(Notify::new()),
+        }
+    }
+
+    /// 某个参与方尚未取走的消息数
+    pub async fn pending(&self, party: PartyId) -> usize {
+        self.mailboxes.lock().await.get(&party).map_or(0, VecDeque::len)
+    }
+
+    fn party_param(&self, request: &HttpRequest, name: &str) -> Result<PartyId, HttpResponse> {
+        let value = request.query_params.get(name)
+            .ok_or_else(|| HttpResponse::error(400, &format!("缺少参数 {}", name)))?;
+        let party: PartyId = value.parse()
+            .map_err(|_| HttpResponse::error(400, &format!("无效的参与方 ID: {}", value)))?;
+        if party >= self.parties {
+            return Err(HttpResponse::error(404, &format!("参与方 {} 不存在", party)));
+        }
+        Ok(party)
+    }
+
+    async fn deliver(&self, request: &HttpRequest) -> NetworkResult<HttpResponse> {
+        let to = match self.party_param(request, "to") {
+            Ok(to) => to,
+            Err(response) => return Ok(response),
+        };
+        let message = match NetworkMessage::deserialize(&request.body) {
+            Ok(message) => message,
+            Err(e) => return Ok(HttpResponse::error(400, &e.to_string())),
+        };
+        let sender = message.sender_id.as_deref().and_then(|sender| sender.parse::<PartyId>().ok());
+        if sender.is_none_or(|sender| sender >= self.parties) {
+            return Ok(HttpResponse::error(400, "消息缺少有效的发送方"));
+        }
+
+        let queued = {
+            let mut mailboxes = self.mailboxes.lock().await;
+            let mailbox = mailboxes.entry(to).or_default();
+            mailbox.push_back(message);
+            mailbox.len()
+        };
+        self.arrivals.notify_waiters();
+        HttpResponse::json(&serde_json::json!({ "queued": queued }))
+    }
+
+    async fn poll(&self, request: &HttpRequest) -> NetworkResult<HttpResponse> {
+        let party = match self.party_param(request, "party") {
+            Ok(party) => party,
+            Err(response) => return Ok(response),
+        };
+        let wait = request.query_params.get("wait")
+            .and_then(|wait| wait.parse().ok())
+            .map_or(Duration::ZERO, Duration::from_millis)
+            .min(MAX_POLL_WAIT);
+        let deadline = Instant::now() + wait;
+
+        loop {
+            // 先登记等待再检查邮箱，避免漏掉两者之间到达的消息
+            let arrival = self.arrivals.notified();
+            let messages: Vec<NetworkMessage> = {
+                let mut mailboxes = self.mailboxes.lock().await;
+                mailboxes.get_mut(&party).map(|mailbox| mailbox.drain(..).collect()).unwrap_or_default()
+            };
+            if !messages.is_empty() || tokio::time::timeout_at(deadline, arrival).await.is_err() {
+                return HttpResponse::json(&messages);
+            }
+        }
+    }
+}
+
+impl RouteHandler for MailboxHandler {
+    fn handle_request(&self, request: &HttpRequest) -> std::pin::Pin<Box<dyn std::future::Future<Output = NetworkResult<HttpResponse>> + Send + '_>> {
+        let request = request.clone();
+        Box::pin(async move {
+            match request.method {
+                HttpMethod::POST => self.deliver(&request).await,
+                HttpMethod::GET => self.poll(&request).await,
+                _ => Ok(HttpResponse::error(405, "方法不被允许")),
+            }
+        })
+    }
+}
+
+/// 基于 HTTP 长轮询邮箱的协议传输
+///
+/// `endpoint` 是邮箱接口的处理器：与服务器同进程时直接使用 `MailboxHandler`，
+/// 否则使用把请求转发到远端服务器的处理器。
+pub struct LongPollingTransport {
+    party_id: PartyId,
+    parties: usize,
+    endpoint: Arc<dyn RouteHandler>,
+    poll_wait: Duration,
+    /// 已取回但尚未交给 `recv` 的消息
+    buffered: Mutex<VecDeque<(PartyId, NetworkMessage)>>,
+}
+
+impl LongPollingTransport {
+    /// 创建长轮询传输
+    ///
+    /// # 参数
+    /// - `party_id`: 本方的参与方 ID
+    /// - `parties`: 参与方数量
+    /// - `endpoint`: 邮箱接口
+    /// - `poll_wait`: 每次取信请求的挂起时间
+    pub fn new(party_id: PartyId, parties: usize, endpoint: Arc<dyn RouteHandler>, poll_wait: Duration) -> Self {
+        LongPollingTransport {
+            party_id,
+            parties,
+            endpoint,
+            poll_wait,
+            buffered: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    fn request(&self, method: HttpMethod, query: &[(&str, String)], body: Vec<u8>) -> HttpRequest {
+        HttpRequest {
+            method,
+            path: MAILBOX_PATH.to_string(),
+            query_params: query.iter().map(|(key, value)| (key.to_string(), value.clone())).collect(),
+            headers: HashMap::new(),
+            body,
+            client_ip: "127.0.0.1".to_string(),
+            timestamp: SystemTime::now(),
+            request_id: Uuid::new_v4().to_string(),
+        }
+    }
+
+    async fn call(&self, request: HttpRequest) -> NetworkResult<Vec<u8>> {
+        let response = self.endpoint.handle_request(&request).await?;
+        if response.status_code != 200 {
+            return Err(NetworkError::ProtocolError(format!(
+                "邮箱请求失败 ({}): {}", response.status_code, String::from_utf8_lossy(&response.body)
+            )));
+        }
+        Ok(response.body)
+    }
+}
+
+impl Transport for LongPollingTransport {
+    fn party_id(&self) -> PartyId {
+        self.party_id
+    }
+
+    fn parties(&self) -> Vec<PartyId> {
+        (0..self.parties).collect()
+    }
+
+    fn send(&self, to: PartyId, message: NetworkMessage) -> TransportFuture<'_, ()> {
+        Box::pin(async move {
+            let body = message.with_sender(self.party_id.to_string()).serialize()?;
+            let request = self.request(HttpMethod::POST, &[("to", to.to_string())], body);
+            self.call(request).await.map(|_| ())
+        })
+    }
+
+    fn recv(&self) -> TransportFuture<'_, (PartyId, NetworkMessage)> {
+        Box::pin(async move {
+            let mut buffered = self.buffered.lock().await;
+            loop {
+                if let Some(next) = buffered.pop_front() {
+                    return Ok(next);
+                }
+                let query = [("party", self.party_id.to_string()), ("wait", self.poll_wait.as_millis().to_string())];
+                let body = self.call(self.request(HttpMethod::GET, &query, Vec::new())).await?;
+                let messages: Vec<NetworkMessage> = serde_json::from_slice(&body)
+                    .map_err(|e| NetworkError::DeserializationError(e.to_string()))?;
+                for message in messages {
+                    let from = message.sender_id.as_deref()
+                        .and_then(|sender| sender.parse().ok())
+                        .ok_or_else(|| NetworkError::ProtocolError("邮箱消息缺少发送方".to_string()))?;
+                    buffered.push_back((from, message));
+                }
+            }
+        })
+    }
+}
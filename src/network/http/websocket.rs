@@ -0,0 +1,723 @@
+//! # WebSocket 传输 (WebSocket Transport)
+//!
+//! 为浏览器端的客户端（例如监控面板）提供基于 WebSocket 的消息通道，
+//! 在 WebSocket 文本帧中以 JSON 形式传输 `NetworkMessage`：
+//!
+//! - **握手**: 连接建立后客户端先发送 `ws_hello` 消息声明自己的 ID 和角色，
+//!   服务器回复同类型的确认消息后连接才开始收发业务消息
+//! - **角色**: `Observer` 只接收服务器推送的消息；`InputProvider` 还可以向服务器提交消息，
+//!   服务器会把消息的发送者改写为握手时声明的 ID
+//! - **保活**: 服务器按 `ping_interval` 发送 Ping，超过 `ping_interval + pong_timeout`
+//!   没有收到任何帧的连接被关闭
+//! - **背压**: 每个连接的发送队列有界。`send_to` 在队列满时等待；`broadcast` 不等待，
+//!   队列已满的慢速客户端被断开。服务器收到的消息放入有界的接收队列，
+//!   应用不取走消息时服务器停止读取连接，由 TCP 把压力传回客户端
+//!
+//! 目前只支持明文 `ws://`，需要加密时应部署在终止 TLS 的反向代理之后。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::network::http::*;
+//! use mpc_api::network::protocol::NetworkMessage;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> mpc_api::network::NetworkResult<()> {
+//! let config = WebSocketConfig { port: 0, ..Default::default() };
+//! let mut server = WebSocketServer::new(config.clone())?;
+//! let addr = server.start().await?;
+//!
+//! let url = format!("ws://{}", addr);
+//! let mut client = WebSocketClient::connect(&url, WebSocketHello::input_provider("dashboard"), &config).await?;
+//!
+//! client.send(NetworkMessage::new("data", b"input")).await?;
+//! let (from, message) = server.recv().await.unwrap();
+//! assert_eq!(from, "dashboard");
+//! assert_eq!(message.payload, b"input");
+//!
+//! server.broadcast(NetworkMessage::new("data", b"result")).await?;
+//! assert_eq!(client.recv().await.unwrap().payload, b"result");
+//!
+//! client.close().await?;
+//! server.shutdown().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use futures::{
+    stream::{SplitSink, SplitStream},
+    SinkExt, StreamExt,
+};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::{TcpListener, TcpStream},
+    sync::{mpsc, Mutex, RwLock},
+    task::JoinHandle,
+    time::{timeout, Interval, MissedTickBehavior},
+};
+use tokio_tungstenite::{
+    tungstenite::{
+        protocol::{frame::coding::CloseCode, CloseFrame, WebSocketConfig as TungsteniteConfig},
+        Message,
+    },
+    WebSocketStream,
+};
+
+use crate::network::{
+    common::{NetworkError, NetworkResult},
+    protocol::NetworkMessage,
+    ServiceStatus,
+};
+
+/// 握手消息类型
+pub const WS_HELLO_MESSAGE_TYPE: &str = "ws_hello";
+
+/// WebSocket 配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebSocketConfig {
+    /// 监听主机地址
+    pub host: String,
+    /// 监听端口，0 表示由系统分配
+    pub port: u16,
+    /// 最大客户端数
+    pub max_clients: usize,
+    /// 单条消息的最大大小（字节）
+    pub max_message_size: usize,
+    /// 发送 Ping 的间隔（毫秒）
+    pub ping_interval: u64,
+    /// 发送 Ping 之后等待响应的时间（毫秒）
+    pub pong_timeout: u64,
+    /// 握手超时时间（毫秒）
+    pub handshake_timeout: u64,
+    /// 每个连接发送队列的容量（消息数）
+    pub send_buffer: usize,
+    /// 接收队列的容量（消息数）
+    pub incoming_buffer: usize,
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        WebSocketConfig {
+            host: "127.0.0.1".to_string(),
+            port: 3001,
+            max_clients: 64,
+            max_message_size: 1024 * 1024, // 1MB
+            ping_interval: 15000,
+            pong_timeout: 10000,
+            handshake_timeout: 5000,
+            send_buffer: 256,
+            incoming_buffer: 1024,
+        }
+    }
+}
+
+impl WebSocketConfig {
+    /// 校验配置
+    pub fn validate(&self) -> NetworkResult<()> {
+        if self.max_clients == 0 {
+            return Err(NetworkError::ConfigError("最大客户端数必须大于 0".to_string()));
+        }
+        if self.max_message_size == 0 {
+            return Err(NetworkError::ConfigError("最大消息大小必须大于 0".to_string()));
+        }
+        if self.ping_interval == 0 || self.handshake_timeout == 0 {
+            return Err(NetworkError::ConfigError("Ping 间隔和握手超时时间必须大于 0".to_string()));
+        }
+        if self.send_buffer == 0 || self.incoming_buffer == 0 {
+            return Err(NetworkError::ConfigError("发送队列和接收队列的容量必须大于 0".to_string()));
+        }
+        Ok(())
+    }
+
+    fn handshake_timeout(&self) -> Duration {
+        Duration::from_millis(self.handshake_timeout)
+    }
+
+    fn tungstenite(&self) -> TungsteniteConfig {
+        TungsteniteConfig {
+            max_message_size: Some(self.max_message_size),
+            max_frame_size: Some(self.max_message_size),
+            ..Default::default()
+        }
+    }
+}
+
+/// WebSocket 客户端角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WebSocketRole {
+    /// 观察者，只接收消息
+    Observer,
+    /// 输入提供方，可以向服务器提交消息
+    InputProvider,
+}
+
+/// 握手消息，声明客户端的 ID 和角色
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebSocketHello {
+    /// 客户端 ID
+    pub client_id: String,
+    /// 客户端角色
+    pub role: WebSocketRole,
+}
+
+impl WebSocketHello {
+    /// 观察者的握手消息
+    pub fn observer(client_id: &str) -> Self {
+        WebSocketHello { client_id: client_id.to_string(), role: WebSocketRole::Observer }
+    }
+
+    /// 输入提供方的握手消息
+    pub fn input_provider(client_id: &str) -> Self {
+        WebSocketHello { client_id: client_id.to_string(), role: WebSocketRole::InputProvider }
+    }
+
+    /// 编码为 `ws_hello` 消息
+    pub fn to_message(&self) -> NetworkResult<NetworkMessage> {
+        let payload = serde_json::to_vec(self)
+            .map_err(|e| NetworkError::SerializationError(e.to_string()))?;
+        Ok(NetworkMessage::new(WS_HELLO_MESSAGE_TYPE, &payload).with_sender(self.client_id.clone()))
+    }
+
+    /// 从 `ws_hello` 消息解码
+    pub fn from_message(message: &NetworkMessage) -> NetworkResult<Self> {
+        if message.message_type != WS_HELLO_MESSAGE_TYPE {
+            return Err(NetworkError::ProtocolError(format!(
+                "握手阶段收到了 {} 消息", message.message_type
+            )));
+        }
+        let hello: WebSocketHello = serde_json::from_slice(&message.payload)
+            .map_err(|e| NetworkError::DeserializationError(e.to_string()))?;
+        if hello.client_id.is_empty() {
+            return Err(NetworkError::ProtocolError("客户端 ID 不能为空".to_string()));
+        }
+        Ok(hello)
+    }
+}
+
+/// 已连接客户端的信息
+#[derive(Debug, Clone)]
+pub struct WebSocketClientInfo {
+    /// 客户端 ID
+    pub client_id: String,
+    /// 客户端角色
+    pub role: WebSocketRole,
+    /// 客户端地址
+    pub address: SocketAddr,
+    /// 连接时间
+    pub connected_at: SystemTime,
+}
+
+/// WebSocket 统计信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebSocketStats {
+    /// 完成握手的连接总数
+    pub total_connections: u64,
+    /// 当前客户端数
+    pub active_clients: usize,
+    /// 收到的消息数
+    pub messages_received: u64,
+    /// 发送的消息数
+    pub messages_sent: u64,
+    /// 被拒绝的消息数（观察者提交的消息、无效消息）
+    pub rejected_messages: u64,
+    /// 因发送队列已满被断开的客户端数
+    pub slow_consumer_disconnects: u64,
+    /// 因保活超时被断开的客户端数
+    pub keepalive_timeouts: u64,
+}
+
+/// 服务器记录的客户端
+struct ClientEntry {
+    /// 连接编号，区分同一 ID 的前后两个连接
+    connection: u64,
+    /// 客户端信息
+    info: WebSocketClientInfo,
+    /// 发送队列
+    outgoing: mpsc::Sender<Message>,
+}
+
+/// WebSocket 服务器
+pub struct WebSocketServer {
+    /// 服务器配置
+    config: WebSocketConfig,
+    /// 监听地址
+    listen_addr: SocketAddr,
+    /// 实际绑定的地址
+    local_addr: Option<SocketAddr>,
+    /// 已连接的客户端
+    clients: Arc<RwLock<HashMap<String, ClientEntry>>>,
+    /// 接收队列的发送端
+    incoming_tx: mpsc::Sender<(String, NetworkMessage)>,
+    /// 接收队列
+    incoming_rx: Mutex<mpsc::Receiver<(String, NetworkMessage)>>,
+    /// 服务器状态
+    status: Arc<RwLock<ServiceStatus>>,
+    /// 统计信息
+    stats: Arc<RwLock<WebSocketStats>>,
+    /// 接受连接的任务
+    accept_task: Option<JoinHandle<()>>,
+}
+
+impl WebSocketServer {
+    /// 创建 WebSocket 服务器
+    pub fn new(config: WebSocketConfig) -> NetworkResult<Self> {
+        config.validate()?;
+        let listen_addr: SocketAddr = format!("{}:{}", config.host, config.port)
+            .parse()
+            .map_err(|e| NetworkError::ConfigError(format!("无效的监听地址: {}", e)))?;
+        let (incoming_tx, incoming_rx) = mpsc::channel(config.incoming_buffer);
+
+        Ok(WebSocketServer {
+            config,
+            listen_addr,
+            local_addr: None,
+            clients: Arc::new(RwLock::new(HashMap::new())),
+            incoming_tx,
+            incoming_rx: Mutex::new(incoming_rx),
+            status: Arc::new(RwLock::new(ServiceStatus::Unknown)),
+            stats: Arc::new(RwLock::new(WebSocketStats::default())),
+            accept_task: None,
+        })
+    }
+
+    /// 启动服务器，返回实际绑定的地址
+    pub async fn start(&mut self) -> NetworkResult<SocketAddr> {
+        if self.accept_task.is_some() {
+            return Err(NetworkError::ProtocolError("WebSocket 服务器已经启动".to_string()));
+        }
+        *self.status.write().await = ServiceStatus::Starting;
+
+        let listener = TcpListener::bind(self.listen_addr).await
+            .map_err(|e| NetworkError::ConnectionError(format!("绑定监听地址失败: {}", e)))?;
+        let local_addr = listener.local_addr()
+            .map_err(|e| NetworkError::IoError(e.to_string()))?;
+
+        let config = self.config.clone();
+        let clients = Arc::clone(&self.clients);
+        let stats = Arc::clone(&self.stats);
+        let incoming = self.incoming_tx.clone();
+        self.accept_task = Some(tokio::spawn(async move {
+            while let Ok((stream, address)) = listener.accept().await {
+                let config = config.clone();
+                let clients = Arc::clone(&clients);
+                let stats = Arc::clone(&stats);
+                let incoming = incoming.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = Self::serve_client(stream, address, config, clients, stats, incoming).await {
+                        println!("⚠️  WebSocket 连接 {} 结束: {}", address, e);
+                    }
+                });
+            }
+        }));
+
+        self.local_addr = Some(local_addr);
+        *self.status.write().await = ServiceStatus::Healthy;
+        println!("✅ WebSocket 服务器监听: {}", local_addr);
+        Ok(local_addr)
+    }
+
+    /// 处理单个客户端连接：握手、登记，然后转发客户端提交的消息直到连接关闭
+    async fn serve_client(
+        stream: TcpStream,
+        address: SocketAddr,
+        config: WebSocketConfig,
+        clients: Arc<RwLock<HashMap<String, ClientEntry>>>,
+        stats: Arc<RwLock<WebSocketStats>>,
+        incoming: mpsc::Sender<(String, NetworkMessage)>,
+    ) -> NetworkResult<()> {
+        let ws = timeout(
+            config.handshake_timeout(),
+            tokio_tungstenite::accept_async_with_config(stream, Some(config.tungstenite())),
+        )
+        .await
+        .map_err(|_| NetworkError::Timeout)?
+        .map_err(|e| NetworkError::ConnectionError(format!("WebSocket 握手失败: {}", e)))?;
+        let (mut connection, outgoing) = WsConnection::new(ws, &config);
+
+        let hello = timeout(config.handshake_timeout(), connection.next_message())
+            .await
+            .map_err(|_| NetworkError::Timeout)??
+            .ok_or_else(|| NetworkError::ConnectionError("客户端在握手完成前关闭连接".to_string()))?;
+        let hello = match WebSocketHello::from_message(&hello) {
+            Ok(hello) => hello,
+            Err(e) => {
+                connection.close(CloseCode::Protocol, &e.to_string()).await;
+                return Err(e);
+            }
+        };
+        let client_id = hello.client_id.clone();
+
+        // 登记客户端
+        let registered = {
+            let mut clients = clients.write().await;
+            if clients.contains_key(&client_id) {
+                Err(format!("客户端 {} 已经连接", client_id))
+            } else if clients.len() >= config.max_clients {
+                Err("客户端数已达上限".to_string())
+            } else {
+                let mut stats = stats.write().await;
+                stats.total_connections += 1;
+                stats.active_clients = clients.len() + 1;
+                let connection_id = stats.total_connections;
+                clients.insert(client_id.clone(), ClientEntry {
+                    connection: connection_id,
+                    info: WebSocketClientInfo {
+                        client_id: client_id.clone(),
+                        role: hello.role,
+                        address,
+                        connected_at: SystemTime::now(),
+                    },
+                    outgoing,
+                });
+                Ok(connection_id)
+            }
+        };
+        let connection_id = match registered {
+            Ok(connection_id) => connection_id,
+            Err(reason) => {
+                connection.close(CloseCode::Policy, &reason).await;
+                return Err(NetworkError::ConnectionError(reason));
+            }
+        };
+
+        let welcome = NetworkMessage::new(WS_HELLO_MESSAGE_TYPE, &[]).with_receiver(client_id.clone());
+        let mut result = connection.send(&welcome).await;
+        while result.is_ok() {
+            let message = match connection.next_message().await {
+                Ok(Some(message)) => message,
+                Ok(None) => break,
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            };
+
+            let message = message.with_sender(client_id.clone());
+            if hello.role != WebSocketRole::InputProvider || message.validate().is_err() {
+                stats.write().await.rejected_messages += 1;
+                continue;
+            }
+            stats.write().await.messages_received += 1;
+            if incoming.send((client_id.clone(), message)).await.is_err() {
+                break;
+            }
+        }
+
+        {
+            let mut clients = clients.write().await;
+            if clients.get(&client_id).is_some_and(|entry| entry.connection == connection_id) {
+                clients.remove(&client_id);
+            }
+            let mut stats = stats.write().await;
+            stats.active_clients = clients.len();
+            if matches!(result, Err(NetworkError::Timeout)) {
+                stats.keepalive_timeouts += 1;
+            }
+        }
+        result
+    }
+
+    /// 实际绑定的地址，服务器启动前为 `None`
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// 接收输入提供方提交的 `(客户端 ID, 消息)`
+    pub async fn recv(&self) -> Option<(String, NetworkMessage)> {
+        self.incoming_rx.lock().await.recv().await
+    }
+
+    /// 已连接的客户端
+    pub async fn clients(&self) -> Vec<WebSocketClientInfo> {
+        let mut clients: Vec<_> = self.clients.read().await
+            .values()
+            .map(|entry| entry.info.clone())
+            .collect();
+        clients.sort_by(|a, b| a.client_id.cmp(&b.client_id));
+        clients
+    }
+
+    /// 向单个客户端发送消息，发送队列已满时等待
+    pub async fn send_to(&self, client_id: &str, message: NetworkMessage) -> NetworkResult<()> {
+        let frame = encode(&message)?;
+        let outgoing = self.clients.read().await
+            .get(client_id)
+            .map(|entry| entry.outgoing.clone())
+            .ok_or_else(|| NetworkError::PeerNotFound(client_id.to_string()))?;
+
+        outgoing.send(frame).await
+            .map_err(|_| NetworkError::PeerNotAvailable(client_id.to_string()))?;
+        self.stats.write().await.messages_sent += 1;
+        Ok(())
+    }
+
+    /// 向所有客户端推送消息，返回成功放入发送队列的客户端数
+    ///
+    /// 不等待发送队列；队列已满的客户端被视为慢速客户端并断开。
+    pub async fn broadcast(&self, message: NetworkMessage) -> NetworkResult<usize> {
+        let frame = encode(&message)?;
+        let mut clients = self.clients.write().await;
+        let mut stats = self.stats.write().await;
+
+        let mut delivered = 0;
+        let mut dropped = Vec::new();
+        for (client_id, entry) in clients.iter() {
+            match entry.outgoing.try_send(frame.clone()) {
+                Ok(()) => delivered += 1,
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    stats.slow_consumer_disconnects += 1;
+                    dropped.push(client_id.clone());
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => dropped.push(client_id.clone()),
+            }
+        }
+        for client_id in dropped {
+            println!("⚠️  断开 WebSocket 客户端: {}", client_id);
+            clients.remove(&client_id);
+        }
+
+        stats.messages_sent += delivered as u64;
+        stats.active_clients = clients.len();
+        Ok(delivered)
+    }
+
+    /// 断开客户端，连接在发送完队列中的消息后关闭
+    pub async fn disconnect(&self, client_id: &str) -> NetworkResult<()> {
+        let mut clients = self.clients.write().await;
+        clients.remove(client_id)
+            .ok_or_else(|| NetworkError::PeerNotFound(client_id.to_string()))?;
+        self.stats.write().await.active_clients = clients.len();
+        Ok(())
+    }
+
+    /// 获取统计信息
+    pub async fn get_stats(&self) -> WebSocketStats {
+        self.stats.read().await.clone()
+    }
+
+    /// 获取服务器状态
+    pub async fn get_status(&self) -> ServiceStatus {
+        self.status.read().await.clone()
+    }
+
+    /// 关闭服务器：停止接受连接并断开所有客户端
+    pub async fn shutdown(&self) -> NetworkResult<()> {
+        *self.status.write().await = ServiceStatus::Shutting;
+        if let Some(task) = &self.accept_task {
+            task.abort();
+        }
+        self.clients.write().await.clear();
+        self.stats.write().await.active_clients = 0;
+        println!("✅ WebSocket 服务器已关闭");
+        Ok(())
+    }
+}
+
+/// WebSocket 客户端
+pub struct WebSocketClient {
+    /// 握手时声明的 ID 和角色
+    hello: WebSocketHello,
+    /// 发送队列
+    outgoing: mpsc::Sender<Message>,
+    /// 接收队列
+    incoming: mpsc::Receiver<NetworkMessage>,
+    /// 读写连接的任务
+    task: JoinHandle<NetworkResult<()>>,
+}
+
+impl WebSocketClient {
+    /// 连接服务器并完成握手
+    ///
+    /// # 参数
+    /// - `url`: 服务器地址，例如 `ws://127.0.0.1:3001`
+    /// - `hello`: 客户端 ID 和角色
+    /// - `config`: 使用其中的消息大小、保活、超时和队列容量设置
+    pub async fn connect(url: &str, hello: WebSocketHello, config: &WebSocketConfig) -> NetworkResult<Self> {
+        config.validate()?;
+        let (ws, _) = timeout(
+            config.handshake_timeout(),
+            tokio_tungstenite::connect_async_with_config(url, Some(config.tungstenite()), true),
+        )
+        .await
+        .map_err(|_| NetworkError::Timeout)?
+        .map_err(|e| NetworkError::ConnectionError(format!("连接 {} 失败: {}", url, e)))?;
+        let (mut connection, outgoing) = WsConnection::new(ws, config);
+
+        connection.send(&hello.to_message()?).await?;
+        let welcome = timeout(config.handshake_timeout(), connection.next_message())
+            .await
+            .map_err(|_| NetworkError::Timeout)??;
+        match welcome {
+            Some(message) if message.message_type == WS_HELLO_MESSAGE_TYPE => {}
+            Some(message) => {
+                return Err(NetworkError::ProtocolError(format!(
+                    "握手阶段收到了 {} 消息", message.message_type
+                )));
+            }
+            None => {
+                return Err(NetworkError::ConnectionError(format!(
+                    "服务器拒绝连接: {}",
+                    connection.close_reason.unwrap_or_default()
+                )));
+            }
+        }
+
+        let (incoming_tx, incoming) = mpsc::channel(config.incoming_buffer);
+        let task = tokio::spawn(async move {
+            while let Some(message) = connection.next_message().await? {
+                if incoming_tx.send(message).await.is_err() {
+                    break;
+                }
+            }
+            Ok(())
+        });
+
+        Ok(WebSocketClient { hello, outgoing, incoming, task })
+    }
+
+    /// 客户端 ID
+    pub fn client_id(&self) -> &str {
+        &self.hello.client_id
+    }
+
+    /// 客户端角色
+    pub fn role(&self) -> WebSocketRole {
+        self.hello.role
+    }
+
+    /// 连接是否仍然打开
+    pub fn is_connected(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// 向服务器提交消息，发送队列已满时等待；观察者不能提交消息
+    pub async fn send(&self, message: NetworkMessage) -> NetworkResult<()> {
+        if self.hello.role != WebSocketRole::InputProvider {
+            return Err(NetworkError::AuthorizationFailed("观察者不能提交消息".to_string()));
+        }
+        self.outgoing.send(encode(&message)?).await
+            .map_err(|_| NetworkError::ChannelError("WebSocket 连接已关闭".to_string()))
+    }
+
+    /// 接收服务器推送的消息，连接关闭后返回 `None`
+    pub async fn recv(&mut self) -> Option<NetworkMessage> {
+        self.incoming.recv().await
+    }
+
+    /// 关闭连接，返回连接任务的结果
+    pub async fn close(self) -> NetworkResult<()> {
+        let WebSocketClient { outgoing, incoming, task, .. } = self;
+        drop(outgoing);
+        drop(incoming);
+        task.await
+            .map_err(|e| NetworkError::Other(format!("WebSocket 连接任务异常: {}", e)))?
+    }
+}
+
+/// 把消息编码为 WebSocket 文本帧
+fn encode(message: &NetworkMessage) -> NetworkResult<Message> {
+    let json = serde_json::to_string(message)
+        .map_err(|e| NetworkError::SerializationError(e.to_string()))?;
+    Ok(Message::Text(json))
+}
+
+/// 一条 WebSocket 连接的读写循环，服务器和客户端共用
+struct WsConnection<S> {
+    /// 写入端
+    sink: SplitSink<WebSocketStream<S>, Message>,
+    /// 读取端
+    stream: SplitStream<WebSocketStream<S>>,
+    /// 待发送的帧，所有发送端被丢弃时关闭连接
+    outgoing: mpsc::Receiver<Message>,
+    /// 发送 Ping 的定时器
+    keepalive: Interval,
+    /// 超过这个时间没有收到任何帧即视为连接失效
+    liveness: Duration,
+    /// 最后一次收到帧的时间
+    last_seen: Instant,
+    /// 对方关闭连接时给出的原因
+    close_reason: Option<String>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> WsConnection<S> {
+    fn new(ws: WebSocketStream<S>, config: &WebSocketConfig) -> (Self, mpsc::Sender<Message>) {
+        let (sink, stream) = ws.split();
+        let (outgoing_tx, outgoing) = mpsc::channel(config.send_buffer);
+        let ping_interval = Duration::from_millis(config.ping_interval);
+        let mut keepalive = tokio::time::interval_at(tokio::time::Instant::now() + ping_interval, ping_interval);
+        keepalive.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let connection = WsConnection {
+            sink,
+            stream,
+            outgoing,
+            keepalive,
+            liveness: ping_interval + Duration::from_millis(config.pong_timeout),
+            last_seen: Instant::now(),
+            close_reason: None,
+        };
+        (connection, outgoing_tx)
+    }
+
+    /// 直接发送一条消息，不经过发送队列
+    async fn send(&mut self, message: &NetworkMessage) -> NetworkResult<()> {
+        self.sink.send(encode(message)?).await
+            .map_err(|e| NetworkError::ConnectionError(e.to_string()))
+    }
+
+    /// 发送关闭帧
+    async fn close(&mut self, code: CloseCode, reason: &str) {
+        let frame = CloseFrame { code, reason: reason.to_string().into() };
+        let _ = self.sink.send(Message::Close(Some(frame))).await;
+    }
+
+    /// 读写连接，直到收到一条业务消息；连接正常关闭时返回 `None`
+    ///
+    /// 等待期间发送队列中的帧和保活 Ping，Ping 由对方的 WebSocket 实现自动回复 Pong。
+    /// 超过保活时间没有收到任何帧时关闭连接并返回超时错误。
+    async fn next_message(&mut self) -> NetworkResult<Option<NetworkMessage>> {
+        loop {
+            tokio::select! {
+                frame = self.stream.next() => {
+                    let frame = match frame {
+                        Some(frame) => frame.map_err(|e| NetworkError::ConnectionError(e.to_string()))?,
+                        None => return Ok(None),
+                    };
+                    self.last_seen = Instant::now();
+                    match frame {
+                        Message::Text(text) => return NetworkMessage::deserialize(text.as_bytes()).map(Some),
+                        Message::Binary(data) => return NetworkMessage::deserialize(&data).map(Some),
+                        Message::Close(frame) => {
+                            self.close_reason = frame.map(|frame| frame.reason.into_owned());
+                            return Ok(None);
+                        }
+                        Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => {}
+                    }
+                }
+                frame = self.outgoing.recv() => match frame {
+                    Some(frame) => self.sink.send(frame).await
+                        .map_err(|e| NetworkError::ConnectionError(e.to_string()))?,
+                    None => {
+                        self.close(CloseCode::Normal, "").await;
+                        return Ok(None);
+                    }
+                },
+                _ = self.keepalive.tick() => {
+                    if self.last_seen.elapsed() > self.liveness {
+                        self.close(CloseCode::Away, "保活超时").await;
+                        return Err(NetworkError::Timeout);
+                    }
+                    self.sink.send(Message::Ping(Vec::new())).await
+                        .map_err(|e| NetworkError::ConnectionError(e.to_string()))?;
+                }
+            }
+        }
+    }
+}
@@ -32,6 +32,22 @@
 //! - `HttpClient`: HTTP 客户端
 //! - `ApiMiddleware`: API 中间件和认证
 //!
+//! ### 节点入网组件
+//! - `CertificateCommittee`: 由 MPC 委员会门限签发节点证书的分布式 CA
+//! - `NodeCertificateRequest`: 新节点提交的证书请求
+//! - `CommitteeTrustAnchor`: 验证委员会签发证书所需的公开信息
+//!
+//! ### 多方计算会话组件
+//! - `Transport`: 按参与方 ID 收发消息的异步传输接口，由 `P2PTransport`、`InMemoryTransport`
+//!   和 `LongPollingTransport` 实现
+//! - `MeshTransport`: 参与方之间按轮同步收发消息的全连接 TCP 传输
+//! - `MpcSession`: 在真实网络上执行输入分享、乘法和重构的会话
+//! - `Simulator`: 在进程内模拟多个参与方并注入丢弃、篡改、延迟等故障，用于确定性的协议测试
+//!
+//! ### 节点能力组件
+//! - `CapabilityBenchmark`: 测量本节点原语吞吐量和到对等节点往返时间的自测
+//! - `CapabilityReport`: 供调优器和协调方按能力分配工作的能力报告
+//!
 //! ## 🚀 使用场景
 //!
 //! ### P2P 适用场景
@@ -67,18 +83,30 @@
 //! ```
 
 pub mod p2p;
+#[cfg(feature = "http")]
 pub mod http;
 pub mod common;
 pub mod security;
+pub mod onboarding;
+pub mod capability;
+pub mod transport;
+pub mod session;
+pub mod simulator;
 pub mod protocol;
 
 // 测试模块在每个子模块中单独定义
 
-pub use p2p::{P2PNode, PeerConfig, PeerDiscovery};
-pub use http::{HttpServer, HttpClient, RestConfig};
-pub use common::{NetworkConfig, NetworkError, NetworkResult};
-pub use security::{NetworkSecurity, TlsConfig, AuthenticationConfig};
-pub use protocol::{MessageProtocol, NetworkMessage, MessageType};
+pub use p2p::{P2PNode, PeerConfig, PeerDiscovery, PeerRecord, GossipConfig, P2PTransport};
+#[cfg(feature = "http")]
+pub use http::{HttpServer, HttpClient, MetricsHandler, WebSocketServer, WebSocketClient, MailboxHandler, LongPollingTransport};
+#[cfg(all(feature = "http", feature = "scheduler"))]
+pub use http::JobsHandler;
+pub use common::{NetworkConfig, NetworkError, NetworkResult, RestConfig, PartyId, Transport};
+pub use security::{NetworkSecurity, TlsConfig, AuthenticationConfig, RekeyPolicy, ChannelKeySchedule, RateLimiter, PeerUsage, Handshake, PartyIdentity, PartyDirectory, SecureChannel};
+pub use protocol::{MessageProtocol, NetworkMessage, MessageType, ReplayWindow, FrameCodec, FrameAssembler, FramingConfig, RoundCoordinator, RoundOutcome, ReliableBroadcast, BroadcastChannel};
+pub use transport::{MeshTransport, RoundTransport, InMemoryTransport, BlockingTransport};
+pub use session::{MpcSession, SessionConfig};
+pub use simulator::{Simulator, SimulatedParty, Fault, FaultRule};
 
 use std::{
     net::{IpAddr, SocketAddr},
@@ -86,6 +114,7 @@ use std::{
 };
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
+use crate::utils::metrics::{MetricSample, MetricsRegistry};
 
 /// 网络连接管理器
 /// 
@@ -94,6 +123,7 @@ pub struct NetworkManager {
     /// P2P 节点实例
     p2p_node: Option<Arc<P2PNode>>,
     /// HTTP 服务器实例
+    #[cfg(feature = "http")]
     http_server: Option<Arc<HttpServer>>,
     /// 网络配置
     config: NetworkConfig,
@@ -116,6 +146,18 @@ pub struct ConnectionStats {
     pub active_sessions: usize,
     /// 连接建立时间戳
     pub connection_start_time: std::time::SystemTime,
+    /// 已记录的协议执行次数
+    pub protocol_runs: u64,
+    /// 协议累计通信轮数
+    pub protocol_rounds: u64,
+    /// 协议累计消耗的预处理材料数量
+    pub preprocessing_consumed: u64,
+    /// 已记录的预处理调优决策次数
+    #[serde(default)]
+    pub preprocessing_plans: u64,
+    /// 最近一次预处理调优决策
+    #[serde(default)]
+    pub last_preprocessing_plan: Option<crate::beaver_triples::TuningDecision>,
 }
 
 impl Default for ConnectionStats {
@@ -127,10 +169,54 @@ impl Default for ConnectionStats {
             bytes_received: 0,
             active_sessions: 0,
             connection_start_time: std::time::SystemTime::now(),
+            protocol_runs: 0,
+            protocol_rounds: 0,
+            preprocessing_consumed: 0,
+            preprocessing_plans: 0,
+            last_preprocessing_plan: None,
         }
     }
 }
 
+impl ConnectionStats {
+    /// 汇总一次协议执行的统计信息
+    /// 
+    /// # 参数
+    /// - `stats`: 协议执行统计
+    pub fn record_protocol_stats(&mut self, stats: &crate::protocols::ProtocolStats) {
+        self.protocol_runs += 1;
+        self.protocol_rounds += stats.rounds as u64;
+        self.bytes_sent += stats.bytes_sent;
+        self.bytes_received += stats.bytes_received;
+        self.preprocessing_consumed += stats.preprocessing_consumed as u64;
+    }
+
+    /// 记录一次预处理调优决策
+    /// 
+    /// # 参数
+    /// - `decision`: 调优器选择的生成器、批大小和并行度
+    pub fn record_preprocessing_plan(&mut self, decision: &crate::beaver_triples::TuningDecision) {
+        self.preprocessing_plans += 1;
+        self.last_preprocessing_plan = Some(decision.clone());
+    }
+
+    /// 把当前统计写成指标样本，供 `MetricsRegistry` 的采集器使用
+    pub fn write_metrics(&self, samples: &mut Vec<MetricSample>) {
+        samples.extend([
+            MetricSample::gauge("mpc_network_p2p_connections", "P2P 连接数量", self.p2p_connections as f64),
+            MetricSample::gauge("mpc_network_http_connections", "HTTP 连接数量", self.http_connections as f64),
+            MetricSample::gauge("mpc_network_active_sessions", "活跃会话数", self.active_sessions as f64),
+            MetricSample::counter("mpc_network_bytes_sent_total", "网络层累计发送的字节数", self.bytes_sent as f64),
+            MetricSample::counter("mpc_network_bytes_received_total", "网络层累计接收的字节数", self.bytes_received as f64),
+            MetricSample::counter("mpc_network_protocol_runs_total", "网络层记录的协议执行次数", self.protocol_runs as f64),
+            MetricSample::counter("mpc_network_protocol_rounds_total", "网络层记录的协议通信轮数", self.protocol_rounds as f64),
+            MetricSample::counter(
+                "mpc_network_preprocessing_consumed_total", "网络层记录的预处理材料消耗数量", self.preprocessing_consumed as f64,
+            ),
+        ]);
+    }
+}
+
 impl NetworkManager {
     /// 创建新的网络管理器
     /// 
@@ -142,6 +228,7 @@ impl NetworkManager {
     pub fn new(config: NetworkConfig) -> Self {
         NetworkManager {
             p2p_node: None,
+            #[cfg(feature = "http")]
             http_server: None,
             config,
             connection_stats: Arc::new(RwLock::new(ConnectionStats::default())),
@@ -174,6 +261,7 @@ impl NetworkManager {
     }
 
     /// 启动 HTTP API 服务器
+    #[cfg(feature = "http")]
     /// 
     /// # 参数
     /// - `rest_config`: HTTP 服务器配置
@@ -194,6 +282,7 @@ impl NetworkManager {
     }
 
     /// 同时启动 P2P 和 HTTP 服务
+    #[cfg(feature = "http")]
     /// 
     /// # 参数
     /// - `peer_config`: P2P 节点配置
@@ -221,13 +310,13 @@ impl NetworkManager {
             println!("  ✅ P2P 节点已关闭");
         }
         
-        if let Some(http) = &self.http_server {
+        #[cfg(feature = "http")]
+        if let Some(http) = self.http_server.take() {
             http.shutdown().await?;
             println!("  ✅ HTTP 服务器已关闭");
         }
         
         self.p2p_node = None;
-        self.http_server = None;
         
         println!("✅ 所有网络服务已关闭");
         Ok(())
@@ -238,12 +327,35 @@ impl NetworkManager {
         self.connection_stats.read().await.clone()
     }
 
+    /// 将协议执行统计汇总到连接统计中
+    pub async fn record_protocol_stats(&self, stats: &crate::protocols::ProtocolStats) {
+        self.connection_stats.write().await.record_protocol_stats(stats);
+    }
+
+    /// 将预处理调优决策记录到连接统计中
+    pub async fn record_preprocessing_plan(&self, decision: &crate::beaver_triples::TuningDecision) {
+        self.connection_stats.write().await.record_preprocessing_plan(decision);
+    }
+
+    /// 把连接统计注册为指标采集器，每次导出时读取最新的统计
+    ///
+    /// 导出时统计正被写入的一次会跳过这些指标。
+    pub fn register_metrics(&self, registry: &MetricsRegistry) {
+        let stats = Arc::clone(&self.connection_stats);
+        registry.register_collector(move |samples| {
+            if let Ok(stats) = stats.try_read() {
+                stats.write_metrics(samples);
+            }
+        });
+    }
+
     /// 获取 P2P 节点引用
     pub fn p2p_node(&self) -> Option<&Arc<P2PNode>> {
         self.p2p_node.as_ref()
     }
 
     /// 获取 HTTP 服务器引用
+    #[cfg(feature = "http")]
     pub fn http_server(&self) -> Option<&Arc<HttpServer>> {
         self.http_server.as_ref()
     }
@@ -256,6 +368,7 @@ impl NetworkManager {
             health.p2p_status = p2p.get_status().await;
         }
         
+        #[cfg(feature = "http")]
         if let Some(http) = &self.http_server {
             health.http_status = http.get_status().await;
         }
@@ -291,6 +404,7 @@ impl NetworkManager {
             p2p.update_config(&self.config.p2p_config).await?;
         }
         
+        #[cfg(feature = "http")]
         if let Some(http) = &self.http_server {
             http.update_config(&self.config.http_config).await?;
         }
@@ -358,6 +472,16 @@ pub enum NetworkEvent {
         peer_id: String,
         reason: String,
     },
+    /// 成员加入事件（gossip 成员表）
+    PeerJoined {
+        peer_id: String,
+        address: SocketAddr,
+    },
+    /// 成员离开或心跳超时事件（gossip 成员表）
+    PeerLeft {
+        peer_id: String,
+        reason: String,
+    },
     /// 消息接收事件
     MessageReceived {
         from: String,
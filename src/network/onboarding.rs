@@ -0,0 +1,329 @@
+//! # 节点入网 (Node Onboarding)
+//!
+//! 现有的 MPC 委员会充当分布式证书颁发机构：
+//!
+//! 1. **证书请求**: 新节点生成 Ed25519 密钥对，提交包含节点 ID、地址、公钥和
+//!    持有证明（用私钥对请求内容的签名）的 `NodeCertificateRequest`，
+//!    通常经由 HTTP 接口 `POST /api/v1/onboarding` 提交
+//! 2. **门限签发**: 委员会成员各自检查请求，并通过输出认证协议
+//!    （`protocols::output_certification`）对证书内容联合签名；
+//!    至少 `quorum` 个成员签名的证书才有效，单个成员无法伪造证书
+//! 3. **信任锚**: 其他节点只需要委员会的 `CommitteeTrustAnchor`
+//!    （成员公钥名单和法定数量），交给 `NetworkSecurity::trust_committee` 后，
+//!    `NetworkSecurity::verify_certificate` 即接受委员会签发的证书
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::network::onboarding::*;
+//! use mpc_api::network::security::NetworkSecurity;
+//! use mpc_api::elliptic_curve::Ed25519;
+//! use mpc_api::protocols::output_certification::OutputCertifier;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let members = (0..3).map(OutputCertifier::new).collect::<mpc_api::Result<Vec<_>>>()?;
+//! let committee = CertificateCommittee::new("committee-a", members, 2)?;
+//!
+//! // 新节点提交证书请求
+//! let (signing_key, public_key) = Ed25519::generate_keypair();
+//! let request = NodeCertificateRequest::new("node-7", "10.0.0.7:9000", &signing_key, public_key)?;
+//! let certificate = committee.issue(&request)?;
+//!
+//! // 其他节点只信任委员会，无需逐个添加证书
+//! let mut security = NetworkSecurity::new(None).unwrap();
+//! security.trust_committee(committee.trust_anchor());
+//! assert!(security.verify_certificate(&certificate.to_certificate()?).unwrap());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::elliptic_curve::{Ed25519, Ed25519PublicKey, Ed25519SecretKey, Ed25519Signature};
+use crate::network::security::Certificate;
+use crate::protocols::output_certification::{certify_output, CertifiedOutput, OutputCertifier};
+use crate::utils::canonical_encode;
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+/// 证书请求持有证明的域分隔标签
+const REQUEST_DOMAIN: &[u8] = b"mpc_api/onboarding/request";
+
+/// 证书签发会话 ID 的前缀
+const SESSION_PREFIX: &str = "mpc_api/onboarding/certificate";
+
+/// 默认证书有效期（30 天）
+pub const DEFAULT_CERTIFICATE_VALIDITY: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// 新节点提交的证书请求
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeCertificateRequest {
+    /// 节点 ID
+    pub node_id: String,
+    /// 节点网络地址
+    pub address: String,
+    /// 节点签名公钥
+    pub public_key: Ed25519PublicKey,
+    /// 持有证明：节点私钥对请求内容的签名
+    pub proof: Ed25519Signature,
+}
+
+impl NodeCertificateRequest {
+    /// 创建并签名证书请求
+    ///
+    /// # 参数
+    ///
+    /// * `node_id` - 节点 ID
+    /// * `address` - 节点网络地址
+    /// * `signing_key` - 节点签名私钥
+    /// * `public_key` - 对应的公钥
+    pub fn new(node_id: &str, address: &str, signing_key: &Ed25519SecretKey, public_key: Ed25519PublicKey) -> Result<Self> {
+        let message = request_message(node_id, address, &public_key)?;
+        let proof = Ed25519::sign(signing_key, &message);
+        Ok(Self {
+            node_id: node_id.to_string(),
+            address: address.to_string(),
+            public_key,
+            proof,
+        })
+    }
+
+    /// 检查请求格式和持有证明
+    pub fn verify(&self) -> Result<()> {
+        if self.node_id.is_empty() {
+            return Err(MpcError::ProtocolError("Empty node id in certificate request".to_string()));
+        }
+        let message = request_message(&self.node_id, &self.address, &self.public_key)?;
+        if !Ed25519::verify(&self.public_key, &message, &self.proof) {
+            return Err(MpcError::AuthenticationError(format!(
+                "Invalid proof of possession from node {}", self.node_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 委员会签名的证书内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeCertificateBody {
+    /// 证书序列号
+    pub serial: String,
+    /// 签发委员会 ID
+    pub committee_id: String,
+    /// 节点 ID
+    pub node_id: String,
+    /// 节点网络地址
+    pub address: String,
+    /// 节点签名公钥
+    pub public_key: Ed25519PublicKey,
+    /// 有效期开始（UNIX 秒）
+    pub valid_from: u64,
+    /// 有效期结束（UNIX 秒）
+    pub valid_to: u64,
+}
+
+/// 委员会签发的节点证书
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeCertificate {
+    /// 证书内容及委员会成员的签名
+    pub certified: CertifiedOutput<NodeCertificateBody>,
+}
+
+impl NodeCertificate {
+    /// 证书内容（已隐去时为 `None`）
+    pub fn body(&self) -> Option<&NodeCertificateBody> {
+        self.certified.value()
+    }
+
+    /// 签名的委员会成员数量
+    pub fn signer_count(&self) -> usize {
+        self.certified.signatures.len()
+    }
+
+    /// 转换为网络安全层使用的 `Certificate`
+    ///
+    /// 证书 ID 为序列号，签发者为委员会 ID，`data` 字段携带完整的委员会签名。
+    pub fn to_certificate(&self) -> Result<Certificate> {
+        let body = self.body().ok_or_else(|| {
+            MpcError::ProtocolError("Node certificate has no body".to_string())
+        })?;
+        let data = serde_json::to_vec(self).map_err(|e| MpcError::SerializationError(e.to_string()))?;
+        Ok(Certificate {
+            id: body.serial.clone(),
+            data,
+            issuer: body.committee_id.clone(),
+            subject: body.node_id.clone(),
+            valid_from: UNIX_EPOCH + Duration::from_secs(body.valid_from),
+            valid_to: UNIX_EPOCH + Duration::from_secs(body.valid_to),
+            public_key: canonical_encode(&body.public_key)?,
+        })
+    }
+
+    /// 从 `Certificate` 的 `data` 字段取出节点证书
+    pub fn from_certificate(certificate: &Certificate) -> Result<Self> {
+        serde_json::from_slice(&certificate.data).map_err(|e| MpcError::SerializationError(e.to_string()))
+    }
+}
+
+/// 委员会的信任锚：验证委员会签发的证书所需的全部公开信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommitteeTrustAnchor {
+    /// 委员会 ID
+    pub committee_id: String,
+    /// 成员 ID 到签名公钥的映射
+    pub roster: HashMap<usize, Ed25519PublicKey>,
+    /// 有效证书需要的最少签名数量
+    pub quorum: usize,
+}
+
+impl CommitteeTrustAnchor {
+    /// 验证节点证书
+    ///
+    /// 检查证书由本委员会签发、签名达到法定数量、内容与签名一致，
+    /// 且 `now` 处于有效期内。
+    ///
+    /// # 返回值
+    ///
+    /// 验证通过时返回证书内容
+    pub fn verify<'a>(&self, certificate: &'a NodeCertificate, now: SystemTime) -> Result<&'a NodeCertificateBody> {
+        let body = certificate.body().ok_or_else(|| {
+            MpcError::AuthenticationError("Node certificate has no body".to_string())
+        })?;
+        if body.committee_id != self.committee_id {
+            return Err(MpcError::AuthenticationError(format!(
+                "Certificate issued by {}, expected {}", body.committee_id, self.committee_id
+            )));
+        }
+        if certificate.certified.session_id != session_id(&body.committee_id, &body.serial) {
+            return Err(MpcError::AuthenticationError("Certificate session does not match serial".to_string()));
+        }
+        certificate.certified.verify(&self.roster, self.quorum)?;
+
+        let now = now.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        if now < body.valid_from || now > body.valid_to {
+            return Err(MpcError::AuthenticationError(format!("Certificate {} is not valid now", body.serial)));
+        }
+        Ok(body)
+    }
+}
+
+/// 充当证书颁发机构的 MPC 委员会
+#[derive(Debug, Clone)]
+pub struct CertificateCommittee {
+    /// 委员会 ID（即证书签发者）
+    committee_id: String,
+    /// 委员会成员
+    members: Vec<OutputCertifier>,
+    /// 有效证书需要的最少签名数量
+    quorum: usize,
+    /// 新证书的有效期
+    validity: Duration,
+}
+
+impl CertificateCommittee {
+    /// 创建委员会
+    ///
+    /// # 参数
+    ///
+    /// * `committee_id` - 委员会 ID
+    /// * `members` - 成员（ID 互不相同）
+    /// * `quorum` - 有效证书需要的最少签名数量，取值 1..=成员数
+    pub fn new(committee_id: &str, members: Vec<OutputCertifier>, quorum: usize) -> Result<Self> {
+        if quorum == 0 || quorum > members.len() {
+            return Err(MpcError::InvalidThreshold);
+        }
+        let mut ids = HashSet::new();
+        if !members.iter().all(|member| ids.insert(member.party_id())) {
+            return Err(MpcError::ProtocolError("Duplicate committee member id".to_string()));
+        }
+        Ok(Self {
+            committee_id: committee_id.to_string(),
+            members,
+            quorum,
+            validity: DEFAULT_CERTIFICATE_VALIDITY,
+        })
+    }
+
+    /// 设置新证书的有效期
+    pub fn with_validity(mut self, validity: Duration) -> Self {
+        self.validity = validity;
+        self
+    }
+
+    /// 委员会 ID
+    pub fn committee_id(&self) -> &str {
+        &self.committee_id
+    }
+
+    /// 有效证书需要的最少签名数量
+    pub fn quorum(&self) -> usize {
+        self.quorum
+    }
+
+    /// 委员会的信任锚
+    pub fn trust_anchor(&self) -> CommitteeTrustAnchor {
+        CommitteeTrustAnchor {
+            committee_id: self.committee_id.clone(),
+            roster: self.members.iter()
+                .map(|member| (member.party_id(), *member.public_key()))
+                .collect(),
+            quorum: self.quorum,
+        }
+    }
+
+    /// 由全体成员签发证书
+    pub fn issue(&self, request: &NodeCertificateRequest) -> Result<NodeCertificate> {
+        self.sign(request, &self.members)
+    }
+
+    /// 由部分在线成员签发证书
+    ///
+    /// # 参数
+    ///
+    /// * `request` - 证书请求
+    /// * `signers` - 参与签名的成员 ID
+    ///
+    /// # 返回值
+    ///
+    /// 签名成员不足法定数量时返回 `InsufficientShares`
+    pub fn issue_with(&self, request: &NodeCertificateRequest, signers: &[usize]) -> Result<NodeCertificate> {
+        let online: Vec<OutputCertifier> = self.members.iter()
+            .filter(|member| signers.contains(&member.party_id()))
+            .cloned()
+            .collect();
+        if online.len() < self.quorum {
+            return Err(MpcError::InsufficientShares);
+        }
+        self.sign(request, &online)
+    }
+
+    fn sign(&self, request: &NodeCertificateRequest, signers: &[OutputCertifier]) -> Result<NodeCertificate> {
+        request.verify()?;
+
+        let valid_from = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map_err(|e| MpcError::ProtocolError(e.to_string()))?
+            .as_secs();
+        let body = NodeCertificateBody {
+            serial: Uuid::new_v4().to_string(),
+            committee_id: self.committee_id.clone(),
+            node_id: request.node_id.clone(),
+            address: request.address.clone(),
+            public_key: request.public_key,
+            valid_from,
+            valid_to: valid_from.saturating_add(self.validity.as_secs()),
+        };
+        let certified = certify_output(&session_id(&self.committee_id, &body.serial), &body, signers)?;
+        Ok(NodeCertificate { certified })
+    }
+}
+
+/// 持有证明签名覆盖的消息
+fn request_message(node_id: &str, address: &str, public_key: &Ed25519PublicKey) -> Result<Vec<u8>> {
+    canonical_encode(&(REQUEST_DOMAIN, node_id, address, public_key))
+}
+
+/// 证书签发的会话 ID，将签名绑定到委员会和序列号
+fn session_id(committee_id: &str, serial: &str) -> String {
+    format!("{}/{}/{}", SESSION_PREFIX, committee_id, serial)
+}
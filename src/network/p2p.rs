@@ -24,8 +24,16 @@
 //! - **本地广播**: 局域网内节点自动发现
 //! - **DNS 种子**: 通过 DNS 记录获取引导节点
 //! - **静态配置**: 手动配置已知节点列表
+//! - **Gossip 成员管理**: 新节点向引导节点发送 `discovery` 加入请求并取得成员表，
+//!   之后各节点周期性地向随机选出的若干对端发送 `gossip` 摘要，按心跳计数合并成员表；
+//!   心跳长时间不增长的成员被移除。加入/离开事件发布到 `NetworkMonitor`，
+//!   协议启动前可用 `wait_for_parties` 等待足够的参与方加入
 //!
 //! ### 连接管理
+//! - **认证加密连接**: 建立 TCP 连接后双方先执行 Noise XX 握手（`security::Handshake`），
+//!   以节点 ID 作为参与方 ID、按 `PartyDirectory` 中登记的静态公钥认证对端；
+//!   之后所有收发的消息都经过该连接的 `SecureChannel` 加密认证，按 4 字节大端长度前缀分帧。
+//!   握手失败或任一帧认证失败时断开连接
 //! - **TCP 连接池**: 高效的 TCP 连接复用
 //! - **WebSocket 支持**: 支持浏览器客户端连接
 //! - **TLS 加密**: 端到端传输层安全
@@ -36,6 +44,9 @@
 //! - **广播路由**: 全网或子网消息广播
 //! - **多播路由**: 指定节点组的消息传输
 //! - **中继路由**: 通过中继节点的间接传输
+//! - **分帧传输**: `send_large` 把大消息切成 `frame` 类型的分片，接收方在 `handle_incoming` 中重组
+//! - **协议传输**: `P2PTransport` 按参与方 ID 收发消息，实现 `Transport`，
+//!   协议消息包装在 `transport` 类型的消息中
 //!
 //! ## 📚 使用示例
 //!
@@ -69,22 +80,36 @@ use std::{
     collections::{HashMap, HashSet},
     net::SocketAddr,
     sync::Arc,
-    time::{Duration, SystemTime},
+    time::{Duration, Instant, SystemTime},
 };
+use rand::seq::SliceRandom;
 use tokio::{
-    net::{TcpListener, TcpStream},
-    sync::{mpsc, RwLock, Mutex},
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{tcp::OwnedWriteHalf, TcpListener, TcpStream},
+    sync::{mpsc, watch, RwLock, Mutex},
     time::{interval, timeout},
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::elliptic_curve::curve25519::PublicKey;
 use crate::network::{
-    common::{NetworkError, NetworkResult},
-    protocol::NetworkMessage,
-    security::{NetworkSecurity, TlsConfig},
-    ServiceStatus,
+    common::{NetworkError, NetworkResult, PartyId, Transport, TransportFuture},
+    protocol::{FrameAssembler, FrameCodec, FramingConfig, NetworkMessage, FRAME_MESSAGE_TYPE},
+    security::{Handshake, NetworkSecurity, PartyDirectory, PartyIdentity, RateLimiter, SecureChannel, TlsConfig},
+    NetworkEvent, NetworkMonitor, ServiceStatus,
 };
+use crate::security::{ProtocolIncident, SecurityManager};
+
+/// 加入请求使用的消息类型，响应为一条 `gossip` 消息
+pub const DISCOVERY_MESSAGE_TYPE: &str = "discovery";
+/// 成员表摘要使用的消息类型
+pub const GOSSIP_MESSAGE_TYPE: &str = "gossip";
+/// `P2PTransport` 包装协议消息使用的消息类型
+pub const TRANSPORT_MESSAGE_TYPE: &str = "transport";
+
+/// 连接上单帧的最大长度
+const MAX_CONNECTION_FRAME: usize = 16 * 1024 * 1024;
 
 /// P2P 节点配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -113,6 +138,33 @@ pub struct PeerConfig {
     pub tls_config: Option<TlsConfig>,
     /// 网络 ID（用于隔离不同的网络）
     pub network_id: String,
+    /// Gossip 成员管理配置
+    #[serde(default)]
+    pub gossip: GossipConfig,
+}
+
+/// Gossip 成员管理配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GossipConfig {
+    /// 发送成员表摘要的间隔（毫秒）
+    pub interval: u64,
+    /// 每轮发送摘要的对端数
+    pub fanout: usize,
+    /// 心跳不再增长多久后移除成员（毫秒）
+    pub member_timeout: u64,
+    /// 成员表的最大容量
+    pub max_members: usize,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        GossipConfig {
+            interval: 1000,
+            fanout: 3,
+            member_timeout: 10000,
+            max_members: 1024,
+        }
+    }
 }
 
 impl Default for PeerConfig {
@@ -130,6 +182,7 @@ impl Default for PeerConfig {
             enable_tls: false,
             tls_config: None,
             network_id: "default".to_string(),
+            gossip: GossipConfig::default(),
         }
     }
 }
@@ -148,6 +201,9 @@ pub enum NodeRole {
 }
 
 /// P2P 网络节点
+///
+/// 克隆得到的句柄与原节点共享连接、处理器和统计信息。
+#[derive(Clone)]
 pub struct P2PNode {
     /// 节点 ID
     pub node_id: String,
@@ -163,12 +219,68 @@ pub struct P2PNode {
     discovery: Arc<Mutex<PeerDiscovery>>,
     /// 网络安全管理器
     security: Arc<NetworkSecurity>,
+    /// 本节点的长期身份，参与方 ID 为节点 ID
+    identity: PartyIdentity,
+    /// 与每个已认证对端之间的加密连接
+    links: Arc<RwLock<HashMap<String, Arc<PeerLink>>>>,
+    /// 曾经完成握手的对端，再次握手视为重连
+    authenticated: Arc<RwLock<HashSet<String>>>,
+    /// 事故上报与参与方准入检查
+    security_manager: Option<Arc<SecurityManager>>,
     /// 节点状态
     status: Arc<RwLock<ServiceStatus>>,
-    /// 消息发送通道
-    message_sender: Option<mpsc::UnboundedSender<OutgoingMessage>>,
+    /// 消息发送通道，节点启动后才存在
+    message_sender: Arc<RwLock<Option<mpsc::UnboundedSender<OutgoingMessage>>>>,
     /// 统计信息
     stats: Arc<RwLock<P2PStats>>,
+    /// 大消息分帧编解码器
+    frame_codec: FrameCodec,
+    /// 每个对端正在重组的分片
+    frame_assemblers: Arc<Mutex<HashMap<String, FrameAssembler>>>,
+}
+
+/// 与一个对端之间握手后的连接：写半连接和共享的加密信道
+struct PeerLink {
+    writer: Mutex<OwnedWriteHalf>,
+    channel: Arc<std::sync::Mutex<SecureChannel>>,
+}
+
+impl PeerLink {
+    /// 加密并写出一条消息；持有写锁期间加密，保证帧按加密顺序到达
+    async fn send(&self, message: &NetworkMessage) -> NetworkResult<usize> {
+        let plaintext = message.serialize()?;
+        let mut writer = self.writer.lock().await;
+        let frame = self.channel.lock()
+            .map_err(|_| NetworkError::ChannelError("加密信道锁已损坏".to_string()))?
+            .seal(&plaintext);
+        write_frame(&mut *writer, &frame).await?;
+        Ok(frame.len())
+    }
+}
+
+/// 写出一帧：4 字节大端长度前缀 ‖ 内容
+async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, frame: &[u8]) -> NetworkResult<()> {
+    if frame.len() > MAX_CONNECTION_FRAME {
+        return Err(NetworkError::ProtocolError(format!("帧长度 {} 超过上限", frame.len())));
+    }
+    writer.write_all(&(frame.len() as u32).to_be_bytes()).await
+        .and(writer.write_all(frame).await)
+        .map_err(|e| NetworkError::ConnectionError(format!("写入连接失败: {}", e)))
+}
+
+/// 读取一帧
+async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> NetworkResult<Vec<u8>> {
+    let mut length = [0u8; 4];
+    reader.read_exact(&mut length).await
+        .map_err(|e| NetworkError::ConnectionError(format!("读取连接失败: {}", e)))?;
+    let length = u32::from_be_bytes(length) as usize;
+    if length > MAX_CONNECTION_FRAME {
+        return Err(NetworkError::ProtocolError(format!("帧长度 {} 超过上限", length)));
+    }
+    let mut frame = vec![0u8; length];
+    reader.read_exact(&mut frame).await
+        .map_err(|e| NetworkError::ConnectionError(format!("读取连接失败: {}", e)))?;
+    Ok(frame)
 }
 
 /// 对等节点信息
@@ -264,10 +376,11 @@ impl P2PNode {
             .map_err(|e| NetworkError::ConfigError(format!("无效的监听地址: {}", e)))?;
 
         // 创建节点发现器
-        let discovery = PeerDiscovery::new(config.clone())?;
+        let discovery = PeerDiscovery::new(PeerConfig { node_id: Some(node_id.clone()), ..config.clone() })?;
 
         // 创建网络安全管理器
         let security = NetworkSecurity::new(config.tls_config.clone())?;
+        let identity = PartyIdentity::generate(&node_id);
 
         println!("🚀 创建 P2P 节点: {}", node_id);
         println!("  监听地址: {}", listen_addr);
@@ -282,12 +395,62 @@ impl P2PNode {
             message_handlers: Arc::new(RwLock::new(HashMap::new())),
             discovery: Arc::new(Mutex::new(discovery)),
             security: Arc::new(security),
+            identity,
+            links: Arc::new(RwLock::new(HashMap::new())),
+            authenticated: Arc::new(RwLock::new(HashSet::new())),
+            security_manager: None,
             status: Arc::new(RwLock::new(ServiceStatus::Unknown)),
-            message_sender: None,
+            message_sender: Arc::new(RwLock::new(None)),
             stats: Arc::new(RwLock::new(P2PStats::default())),
+            frame_codec: FrameCodec::new(FramingConfig::default())?,
+            frame_assemblers: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 
+    /// 设置大消息的分帧配置
+    pub fn with_framing(mut self, config: FramingConfig) -> NetworkResult<Self> {
+        self.frame_codec = FrameCodec::new(config)?;
+        Ok(self)
+    }
+
+    /// 获取分帧配置
+    pub fn framing(&self) -> &FramingConfig {
+        self.frame_codec.config()
+    }
+
+    /// 使用已有的长期身份，身份的参与方 ID 必须是本节点 ID
+    pub fn with_identity(mut self, identity: PartyIdentity) -> NetworkResult<Self> {
+        if identity.party_id != self.node_id {
+            return Err(NetworkError::ConfigError(format!(
+                "身份 {} 与节点 ID {} 不一致", identity.party_id, self.node_id
+            )));
+        }
+        self.identity = identity;
+        Ok(self)
+    }
+
+    /// 设置握手时用来认证对端的静态公钥目录，必须在节点启动之前调用
+    pub fn with_party_directory(mut self, directory: PartyDirectory) -> NetworkResult<Self> {
+        Arc::get_mut(&mut self.security)
+            .ok_or_else(|| NetworkError::ConfigError("节点已启动，不能更换公钥目录".to_string()))?
+            .set_party_directory(directory);
+        Ok(self)
+    }
+
+    /// 关联安全管理器
+    ///
+    /// 握手、收到消息和发送消息前检查对端未被隔离、系统未等待人工恢复；
+    /// 已认证过的对端再次完成握手时上报 `Reconnect` 事故，重连风暴按策略处理。
+    pub fn with_security_manager(mut self, manager: Arc<SecurityManager>) -> Self {
+        self.security_manager = Some(manager);
+        self
+    }
+
+    /// 本节点的静态公钥，需要登记到其他节点的公钥目录中
+    pub fn public_key(&self) -> PublicKey {
+        self.identity.public_key()
+    }
+
     /// 启动 P2P 节点
     pub async fn start(&mut self) -> NetworkResult<()> {
         println!("🌐 启动 P2P 网络节点...");
@@ -306,7 +469,7 @@ impl P2PNode {
 
         // 创建消息通道
         let (tx, rx) = mpsc::unbounded_channel::<OutgoingMessage>();
-        self.message_sender = Some(tx);
+        *self.message_sender.write().await = Some(tx);
 
         // 启动 TCP 监听器
         let listener = TcpListener::bind(self.listen_addr).await
@@ -314,25 +477,13 @@ impl P2PNode {
 
         println!("✅ 监听器绑定成功: {}", self.listen_addr);
 
-        // 克隆共享数据
-        let node_id = self.node_id.clone();
-        let peers = Arc::clone(&self.peers);
-        let handlers = Arc::clone(&self.message_handlers);
-        let security = Arc::clone(&self.security);
-        let stats = Arc::clone(&self.stats);
-        let config = self.config.clone();
-
         // 启动服务器任务
-        let server_task = tokio::spawn(async move {
-            Self::server_loop(listener, node_id, peers, handlers, security, stats, config).await
-        });
+        let node = self.clone();
+        let server_task = tokio::spawn(async move { node.server_loop(listener).await });
 
         // 启动消息发送任务
-        let peers_clone = Arc::clone(&self.peers);
-        let stats_clone = Arc::clone(&self.stats);
-        let sender_task = tokio::spawn(async move {
-            Self::message_sender_loop(rx, peers_clone, stats_clone).await
-        });
+        let node = self.clone();
+        let sender_task = tokio::spawn(async move { node.message_sender_loop(rx).await });
 
         // 启动节点发现
         if self.config.enable_discovery {
@@ -352,6 +503,11 @@ impl P2PNode {
         // 启动心跳任务
         self.start_heartbeat().await;
 
+        // 启动 gossip 成员管理
+        if self.config.enable_discovery {
+            self.start_gossip().await;
+        }
+
         // 更新状态
         {
             let mut status = self.status.write().await;
@@ -372,15 +528,7 @@ impl P2PNode {
     }
 
     /// 服务器主循环
-    async fn server_loop(
-        listener: TcpListener,
-        node_id: String,
-        peers: Arc<RwLock<HashMap<String, Arc<Peer>>>>,
-        handlers: Arc<RwLock<HashMap<String, Box<dyn MessageHandler>>>>,
-        security: Arc<NetworkSecurity>,
-        stats: Arc<RwLock<P2PStats>>,
-        config: PeerConfig,
-    ) -> NetworkResult<()> {
+    async fn server_loop(self, listener: TcpListener) -> NetworkResult<()> {
         println!("🔄 启动服务器主循环...");
 
         while let Ok((stream, addr)) = listener.accept().await {
@@ -388,8 +536,8 @@ impl P2PNode {
 
             // 检查连接数限制
             {
-                let peers_read = peers.read().await;
-                if peers_read.len() >= config.max_connections {
+                let peers_read = self.peers.read().await;
+                if peers_read.len() >= self.config.max_connections {
                     println!("⚠️  连接数已达上限，拒绝连接: {}", addr);
                     continue;
                 }
@@ -397,23 +545,16 @@ impl P2PNode {
 
             // 更新统计
             {
-                let mut stats_write = stats.write().await;
+                let mut stats_write = self.stats.write().await;
                 stats_write.total_connections += 1;
             }
 
             // 在新任务中处理连接
-            let node_id_clone = node_id.clone();
-            let peers_clone = Arc::clone(&peers);
-            let handlers_clone = Arc::clone(&handlers);
-            let security_clone = Arc::clone(&security);
-            let stats_clone = Arc::clone(&stats);
-
+            let node = self.clone();
             tokio::spawn(async move {
-                if let Err(e) = Self::handle_connection(
-                    stream, addr, node_id_clone, peers_clone, 
-                    handlers_clone, security_clone, stats_clone
-                ).await {
+                if let Err(e) = node.handle_connection(stream, addr).await {
                     println!("❌ 处理连接失败: {}", e);
+                    node.stats.write().await.connection_failures += 1;
                 }
             });
         }
@@ -421,69 +562,28 @@ impl P2PNode {
         Ok(())
     }
 
-    /// 处理单个连接
-    async fn handle_connection(
-        _stream: TcpStream,
-        addr: SocketAddr,
-        _node_id: String,
-        peers: Arc<RwLock<HashMap<String, Arc<Peer>>>>,
-        _handlers: Arc<RwLock<HashMap<String, Box<dyn MessageHandler>>>>,
-        _security: Arc<NetworkSecurity>,
-        stats: Arc<RwLock<P2PStats>>,
-    ) -> NetworkResult<()> {
+    /// 处理单个入站连接：以响应方身份握手
+    async fn handle_connection(&self, stream: TcpStream, addr: SocketAddr) -> NetworkResult<()> {
         println!("🤝 处理来自 {} 的连接", addr);
-
-        // 这里应该实现完整的握手协议
-        // 包括身份验证、协议版本协商等
-        
-        // 为演示，创建一个简单的对等节点
-        let peer_id = format!("peer_{}", Uuid::new_v4());
-        let peer = Arc::new(Peer {
-            id: peer_id.clone(),
-            address: addr,
-            role: NodeRole::Participant,
-            status: PeerStatus::Connected,
-            last_seen: SystemTime::now(),
-            connected_at: SystemTime::now(),
-            latency: None,
-            messages_sent: 0,
-            messages_received: 0,
-        });
-
-        // 添加到对等节点列表
-        {
-            let mut peers_write = peers.write().await;
-            peers_write.insert(peer_id.clone(), peer);
-            
-            let mut stats_write = stats.write().await;
-            stats_write.active_connections = peers_write.len();
-        }
-
+        let handshake = self.security.accept_handshake(self.identity.clone());
+        let peer_id = self.perform_handshake(stream, addr, handshake).await?;
         println!("✅ 对等节点已连接: {}", peer_id);
-
-        // 实际应用中，这里应该启动消息接收循环
-        // 处理来自该对等节点的消息
-
         Ok(())
     }
 
     /// 消息发送循环
-    async fn message_sender_loop(
-        mut rx: mpsc::UnboundedReceiver<OutgoingMessage>,
-        peers: Arc<RwLock<HashMap<String, Arc<Peer>>>>,
-        stats: Arc<RwLock<P2PStats>>,
-    ) {
+    async fn message_sender_loop(self, mut rx: mpsc::UnboundedReceiver<OutgoingMessage>) {
         println!("📤 启动消息发送循环...");
 
         while let Some(outgoing) = rx.recv().await {
             let result = match outgoing.target {
                 Some(target_id) => {
                     // 发送到特定节点
-                    Self::send_to_specific_peer(&target_id, &outgoing.message, &peers, &stats).await
+                    self.send_to_specific_peer(&target_id, &outgoing.message).await
                 }
                 None => {
                     // 广播到所有节点
-                    Self::broadcast_to_all_peers(&outgoing.message, &peers, &stats).await
+                    self.broadcast_to_all_peers(&outgoing.message).await
                 }
             };
 
@@ -494,53 +594,51 @@ impl P2PNode {
         }
     }
 
-    /// 发送消息到特定对等节点
-    async fn send_to_specific_peer(
-        target_id: &str,
-        message: &NetworkMessage,
-        peers: &Arc<RwLock<HashMap<String, Arc<Peer>>>>,
-        stats: &Arc<RwLock<P2PStats>>,
-    ) -> NetworkResult<()> {
-        let peers_read = peers.read().await;
-        
-        if let Some(peer) = peers_read.get(target_id) {
-            if peer.status == PeerStatus::Connected {
-                println!("📤 发送消息到 {}: {}", target_id, message.message_type);
-                
-                // 实际发送逻辑
-                // 这里应该通过 TCP 连接发送消息
-                
-                // 更新统计
-                let mut stats_write = stats.write().await;
-                stats_write.messages_sent += 1;
-                stats_write.bytes_sent += message.payload.len() as u64;
-                
-                Ok(())
-            } else {
-                Err(NetworkError::PeerNotAvailable(format!("节点 {} 不可用", target_id)))
+    /// 通过加密连接发送消息到特定对等节点
+    async fn send_to_specific_peer(&self, target_id: &str, message: &NetworkMessage) -> NetworkResult<()> {
+        self.ensure_peer_allowed(target_id)?;
+        let link = {
+            let peers_read = self.peers.read().await;
+            let peer = peers_read.get(target_id)
+                .ok_or_else(|| NetworkError::PeerNotFound(target_id.to_string()))?;
+            if peer.status != PeerStatus::Connected {
+                return Err(NetworkError::PeerNotAvailable(format!("节点 {} 不可用", target_id)));
             }
-        } else {
-            Err(NetworkError::PeerNotFound(target_id.to_string()))
-        }
+            self.links.read().await.get(target_id).cloned()
+                .ok_or_else(|| NetworkError::PeerNotAvailable(format!("与节点 {} 没有加密连接", target_id)))?
+        };
+
+        println!("📤 发送消息到 {}: {}", target_id, message.message_type);
+        let written = match link.send(message).await {
+            Ok(written) => written,
+            Err(e) => {
+                self.drop_link(target_id).await;
+                return Err(e);
+            }
+        };
+
+        // 更新统计
+        let mut stats_write = self.stats.write().await;
+        stats_write.messages_sent += 1;
+        stats_write.bytes_sent += written as u64 + 4;
+        Ok(())
     }
 
     /// 广播消息到所有对等节点
-    async fn broadcast_to_all_peers(
-        message: &NetworkMessage,
-        peers: &Arc<RwLock<HashMap<String, Arc<Peer>>>>,
-        stats: &Arc<RwLock<P2PStats>>,
-    ) -> NetworkResult<()> {
-        let peers_read = peers.read().await;
-        let connected_peers: Vec<String> = peers_read
-            .values()
-            .filter(|peer| peer.status == PeerStatus::Connected)
-            .map(|peer| peer.id.clone())
-            .collect();
+    async fn broadcast_to_all_peers(&self, message: &NetworkMessage) -> NetworkResult<()> {
+        let connected_peers: Vec<String> = {
+            let peers_read = self.peers.read().await;
+            peers_read
+                .values()
+                .filter(|peer| peer.status == PeerStatus::Connected)
+                .map(|peer| peer.id.clone())
+                .collect()
+        };
 
         println!("📢 广播消息到 {} 个节点: {}", connected_peers.len(), message.message_type);
 
         for peer_id in connected_peers {
-            if let Err(e) = Self::send_to_specific_peer(&peer_id, message, peers, stats).await {
+            if let Err(e) = self.send_to_specific_peer(&peer_id, message).await {
                 println!("⚠️  广播到节点 {} 失败: {}", peer_id, e);
             }
         }
@@ -559,8 +657,16 @@ impl P2PNode {
 
         for bootstrap_addr in &self.config.bootstrap_nodes {
             match self.connect_to_peer(bootstrap_addr).await {
-                Ok(_) => {
+                Ok(peer_id) => {
                     println!("✅ 成功连接到引导节点: {}", bootstrap_addr);
+
+                    // 请求引导节点的成员表
+                    if self.config.enable_discovery {
+                        let join = self.discovery.lock().await.join_request().await?;
+                        if let Err(e) = self.send_to_peer(&peer_id, join).await {
+                            println!("⚠️  发送加入请求失败 {}: {}", bootstrap_addr, e);
+                        }
+                    }
                 }
                 Err(e) => {
                     println!("❌ 连接引导节点失败 {}: {}", bootstrap_addr, e);
@@ -571,8 +677,11 @@ impl P2PNode {
         Ok(())
     }
 
-    /// 连接到特定对等节点
-    async fn connect_to_peer(&self, peer_addr: &str) -> NetworkResult<String> {
+    /// 连接到对等节点并以发起方身份握手
+    ///
+    /// # 返回值
+    /// 握手认证的对端节点 ID；连接超时、握手失败或对端身份与公钥目录不符时返回错误
+    pub async fn connect_to_peer(&self, peer_addr: &str) -> NetworkResult<String> {
         let addr: SocketAddr = peer_addr.parse()
             .map_err(|e| NetworkError::ConfigError(format!("无效的对等节点地址: {}", e)))?;
 
@@ -585,17 +694,53 @@ impl P2PNode {
             .map_err(|e| NetworkError::ConnectionError(format!("连接失败: {}", e)))?;
 
         // 执行握手协议
-        let peer_id = self.perform_handshake(stream, addr).await?;
+        let handshake = self.security.initiate_handshake(self.identity.clone());
+        let peer_id = match self.perform_handshake(stream, addr, handshake).await {
+            Ok(peer_id) => peer_id,
+            Err(e) => {
+                self.stats.write().await.connection_failures += 1;
+                return Err(e);
+            }
+        };
 
         println!("✅ 成功连接到对等节点: {}", peer_id);
         Ok(peer_id)
     }
 
-    /// 执行握手协议
-    async fn perform_handshake(&self, _stream: TcpStream, addr: SocketAddr) -> NetworkResult<String> {
-        // 简化的握手实现
-        let peer_id = format!("peer_{}", addr.port());
-        
+    /// 在连接上执行握手，成功后登记对端并启动接收循环
+    ///
+    /// 握手以网络 ID 作为序言，不同网络的节点无法完成握手。
+    async fn perform_handshake(&self, mut stream: TcpStream, addr: SocketAddr, handshake: Handshake) -> NetworkResult<String> {
+        let mut handshake = handshake.with_prologue(self.config.network_id.as_bytes());
+        let connection_timeout = Duration::from_millis(self.config.connection_timeout);
+        let exchange = async {
+            // 发起方写第 1、3 条消息，响应方写第 2 条
+            for step in 0..3 {
+                if (step % 2 == 0) == (handshake.role() == crate::network::security::HandshakeRole::Initiator) {
+                    write_frame(&mut stream, &handshake.write_message()?).await?;
+                } else {
+                    handshake.read_message(&read_frame(&mut stream).await?)?;
+                }
+            }
+            Ok::<_, NetworkError>(())
+        };
+        timeout(connection_timeout, exchange).await.map_err(|_| NetworkError::Timeout)??;
+
+        let channel = handshake.into_channel()?;
+        let peer_id = channel.peer_party().to_string();
+        if peer_id == self.node_id {
+            return Err(NetworkError::AuthenticationFailed("不能与自己建立连接".to_string()));
+        }
+        let reconnect = !self.authenticated.write().await.insert(peer_id.clone());
+        if let (true, Some(manager)) = (reconnect, &self.security_manager) {
+            manager.report_incident(&peer_id, ProtocolIncident::Reconnect)
+                .map_err(|e| NetworkError::ProtocolError(e.to_string()))?;
+        }
+        self.ensure_peer_allowed(&peer_id)?;
+
+        let (reader, writer) = stream.into_split();
+        let channel = Arc::new(std::sync::Mutex::new(channel));
+        let link = Arc::new(PeerLink { writer: Mutex::new(writer), channel: Arc::clone(&channel) });
         let peer = Arc::new(Peer {
             id: peer_id.clone(),
             address: addr,
@@ -608,21 +753,82 @@ impl P2PNode {
             messages_received: 0,
         });
 
-        // 添加到对等节点列表
+        // 添加到对等节点列表，替换与同一节点的旧连接
         {
+            self.links.write().await.insert(peer_id.clone(), link);
             let mut peers = self.peers.write().await;
             peers.insert(peer_id.clone(), peer);
+            self.stats.write().await.active_connections = peers.len();
         }
 
-        // 更新统计
-        {
-            let mut stats = self.stats.write().await;
-            stats.active_connections += 1;
-        }
+        let node = self.clone();
+        let receiver_peer = peer_id.clone();
+        tokio::spawn(async move { node.receive_loop(receiver_peer, reader, channel).await });
 
         Ok(peer_id)
     }
 
+    /// 接收循环：解密每一帧并交给 `handle_incoming`，处理器的回复经同一连接发回
+    ///
+    /// 连接关闭或任一帧认证失败时断开该对端。
+    async fn receive_loop<R: AsyncRead + Unpin>(
+        self,
+        peer_id: String,
+        mut reader: R,
+        channel: Arc<std::sync::Mutex<SecureChannel>>,
+    ) {
+        loop {
+            let frame = match read_frame(&mut reader).await {
+                Ok(frame) => frame,
+                Err(_) => break,
+            };
+            let opened = channel.lock()
+                .map_err(|_| NetworkError::ChannelError("加密信道锁已损坏".to_string()))
+                .and_then(|mut channel| channel.open(&frame));
+            let message = match opened.and_then(|plaintext| NetworkMessage::deserialize(&plaintext)) {
+                Ok(message) => message,
+                Err(e) => {
+                    println!("❌ 来自 {} 的消息无法认证，断开连接: {}", peer_id, e);
+                    break;
+                }
+            };
+
+            match self.handle_incoming(&peer_id, &message).await {
+                Ok(Some(reply)) => {
+                    if let Err(e) = self.send_to_specific_peer(&peer_id, &reply).await {
+                        println!("⚠️  回复 {} 失败: {}", peer_id, e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => println!("⚠️  处理来自 {} 的消息失败: {}", peer_id, e),
+            }
+        }
+
+        // 只移除本连接：对端可能已经重新连接
+        let current = self.links.read().await.get(&peer_id)
+            .is_some_and(|link| Arc::ptr_eq(&link.channel, &channel));
+        if current {
+            self.drop_link(&peer_id).await;
+        }
+    }
+
+    /// 对端被安全管理器隔离或系统等待人工恢复时拒绝通信
+    fn ensure_peer_allowed(&self, peer_id: &str) -> NetworkResult<()> {
+        match &self.security_manager {
+            Some(manager) => manager.ensure_peer_allowed(peer_id)
+                .map_err(|e| NetworkError::AuthorizationFailed(e.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// 移除与对端的连接
+    async fn drop_link(&self, peer_id: &str) {
+        self.links.write().await.remove(peer_id);
+        let mut peers = self.peers.write().await;
+        peers.remove(peer_id);
+        self.stats.write().await.active_connections = peers.len();
+    }
+
     /// 启动心跳任务
     async fn start_heartbeat(&self) {
         let peers = Arc::clone(&self.peers);
@@ -650,9 +856,83 @@ impl P2PNode {
         });
     }
 
+    /// 周期性地向随机选出的对端发送成员表摘要，并移除超时的成员
+    async fn start_gossip(&self) {
+        let discovery = self.discovery.lock().await.clone();
+        let peers = Arc::clone(&self.peers);
+        let sender = match self.message_sender.read().await.as_ref() {
+            Some(sender) => sender.clone(),
+            None => return,
+        };
+        let gossip = self.config.gossip.clone();
+
+        tokio::spawn(async move {
+            let mut interval = interval(Duration::from_millis(gossip.interval.max(1)));
+
+            loop {
+                interval.tick().await;
+                discovery.expire_members().await;
+
+                let targets: Vec<String> = {
+                    let peers_read = peers.read().await;
+                    let connected: Vec<String> = peers_read
+                        .values()
+                        .filter(|peer| peer.status == PeerStatus::Connected)
+                        .map(|peer| peer.id.clone())
+                        .collect();
+                    connected
+                        .choose_multiple(&mut rand::thread_rng(), gossip.fanout)
+                        .cloned()
+                        .collect()
+                };
+                if targets.is_empty() {
+                    continue;
+                }
+
+                let message = match discovery.gossip_message().await {
+                    Ok(message) => message,
+                    Err(e) => {
+                        println!("⚠️  构造成员摘要失败: {}", e);
+                        continue;
+                    }
+                };
+                for target in targets {
+                    let outgoing = OutgoingMessage {
+                        target: Some(target),
+                        message: message.clone(),
+                        response_tx: None,
+                    };
+                    if sender.send(outgoing).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    /// 设置接收成员加入/离开事件的网络监控器
+    pub async fn set_monitor(&self, monitor: Arc<NetworkMonitor>) {
+        self.discovery.lock().await.set_monitor(monitor).await;
+    }
+
+    /// 当前已知的成员（不含本节点）
+    pub async fn members(&self) -> Vec<PeerRecord> {
+        let discovery = self.discovery.lock().await.clone();
+        discovery.members().await
+    }
+
+    /// 等待至少 `parties` 个参与方（含本节点）加入网络，用于协议启动前的同步
+    ///
+    /// 返回按节点 ID 排序的全部参与方记录；超时返回 `Timeout`。
+    pub async fn wait_for_parties(&self, parties: usize, wait: Duration) -> NetworkResult<Vec<PeerRecord>> {
+        let discovery = self.discovery.lock().await.clone();
+        discovery.wait_for_parties(parties, wait).await
+    }
+
     /// 发送消息到特定对等节点
     pub async fn send_to_peer(&self, peer_id: &str, message: NetworkMessage) -> NetworkResult<()> {
-        if let Some(sender) = &self.message_sender {
+        let sender = self.message_sender.read().await.clone();
+        if let Some(sender) = sender {
             let (response_tx, response_rx) = tokio::sync::oneshot::channel();
             
             let outgoing = OutgoingMessage {
@@ -671,9 +951,26 @@ impl P2PNode {
         }
     }
 
+    /// 分帧发送大消息到特定对等节点
+    ///
+    /// 消息按分帧配置压缩并切成分片，每个分片作为一条 `frame` 消息依次发送，
+    /// 返回发送的分片数。任一分片发送失败时停止发送，接收方会丢弃未完成的传输。
+    pub async fn send_large(&self, peer_id: &str, message: NetworkMessage) -> NetworkResult<usize> {
+        let frames = self.frame_codec.encode(&message)?;
+        let count = frames.len();
+        for frame in frames {
+            let chunk = NetworkMessage::new(FRAME_MESSAGE_TYPE, &frame)
+                .with_sender(self.node_id.clone())
+                .with_receiver(peer_id.to_string());
+            self.send_to_peer(peer_id, chunk).await?;
+        }
+        Ok(count)
+    }
+
     /// 广播消息到所有对等节点
     pub async fn broadcast(&self, message: NetworkMessage) -> NetworkResult<()> {
-        if let Some(sender) = &self.message_sender {
+        let sender = self.message_sender.read().await.clone();
+        if let Some(sender) = sender {
             let (response_tx, response_rx) = tokio::sync::oneshot::channel();
             
             let outgoing = OutgoingMessage {
@@ -692,6 +989,66 @@ impl P2PNode {
         }
     }
 
+    /// 处理从对端收到的消息
+    ///
+    /// 先检查对端未被安全管理器隔离，再检查对端的限流配额，超限的消息直接丢弃并返回 `RateLimited`；
+    /// `discovery` 和 `gossip` 消息交给节点发现器合并成员表；
+    /// `frame` 消息交给该对端的重组器，分片未到齐时返回 `None`，出错时丢弃该对端未完成的传输；
+    /// 其余消息和重组完成的消息交给该消息类型注册的处理器，返回处理器给出的回复。
+    pub async fn handle_incoming(&self, from_peer: &str, message: &NetworkMessage) -> NetworkResult<Option<NetworkMessage>> {
+        self.ensure_peer_allowed(from_peer)?;
+        self.security.check_rate_limit(from_peer, message.payload.len())?;
+
+        {
+            let mut stats = self.stats.write().await;
+            stats.messages_received += 1;
+            stats.bytes_received += message.payload.len() as u64;
+        }
+
+        if message.message_type == DISCOVERY_MESSAGE_TYPE || message.message_type == GOSSIP_MESSAGE_TYPE {
+            let discovery = self.discovery.lock().await.clone();
+            return discovery.handle_message(message).await;
+        }
+
+        if message.message_type == FRAME_MESSAGE_TYPE {
+            let reassembled = {
+                let mut assemblers = self.frame_assemblers.lock().await;
+                let assembler = assemblers
+                    .entry(from_peer.to_string())
+                    .or_insert_with(|| FrameAssembler::new(self.frame_codec.clone()));
+                match assembler.push_bytes(&message.payload) {
+                    Ok(messages) => messages,
+                    Err(e) => {
+                        assemblers.remove(from_peer);
+                        return Err(e);
+                    }
+                }
+            };
+
+            let mut reply = None;
+            for inner in &reassembled {
+                reply = self.dispatch(from_peer, inner).await?;
+            }
+            return Ok(reply);
+        }
+
+        self.dispatch(from_peer, message).await
+    }
+
+    /// 交给消息类型注册的处理器
+    async fn dispatch(&self, from_peer: &str, message: &NetworkMessage) -> NetworkResult<Option<NetworkMessage>> {
+        let handlers = self.message_handlers.read().await;
+        match handlers.get(&message.message_type) {
+            Some(handler) => handler.handle_message(from_peer, message).await,
+            None => Err(NetworkError::ProtocolError(format!("未注册的消息类型: {}", message.message_type))),
+        }
+    }
+
+    /// 获取限流器，可以替换限流策略或使用共享的审计日志
+    pub fn rate_limiter(&self) -> &RateLimiter {
+        self.security.rate_limiter()
+    }
+
     /// 注册消息处理器
     pub async fn register_handler(&self, message_type: String, handler: Box<dyn MessageHandler>) {
         let mut handlers = self.message_handlers.write().await;
@@ -712,6 +1069,8 @@ impl P2PNode {
 
     /// 断开与特定对等节点的连接
     pub async fn disconnect_peer(&self, peer_id: &str) -> NetworkResult<()> {
+        // 丢弃写半连接即关闭连接，对端的接收循环随之结束
+        self.links.write().await.remove(peer_id);
         let mut peers = self.peers.write().await;
         
         if let Some(_peer) = peers.remove(peer_id) {
@@ -754,6 +1113,14 @@ impl P2PNode {
             *status = ServiceStatus::Shutting;
         }
 
+        // 通知其他成员本节点离开
+        if self.config.enable_discovery && self.message_sender.read().await.is_some() {
+            let leave = self.discovery.lock().await.leave_message().await?;
+            if let Err(e) = self.broadcast(leave).await {
+                println!("⚠️  发送离开通知失败: {}", e);
+            }
+        }
+
         // 断开所有对等节点
         let peer_ids: Vec<String> = {
             let peers = self.peers.read().await;
@@ -771,8 +1138,59 @@ impl P2PNode {
     }
 }
 
+/// 成员表中的节点记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerRecord {
+    /// 节点 ID
+    pub node_id: String,
+    /// 节点地址
+    pub address: SocketAddr,
+    /// 节点角色
+    pub role: NodeRole,
+    /// 心跳计数，节点每发出一轮摘要加一
+    pub heartbeat: u64,
+}
+
+/// 成员表摘要，承载在 `discovery` 和 `gossip` 消息中
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MembershipDigest {
+    /// 网络 ID，不同网络的摘要互不合并
+    pub network_id: String,
+    /// 发送方自己的记录
+    pub sender: PeerRecord,
+    /// 发送方已知的其他成员
+    pub members: Vec<PeerRecord>,
+    /// 发送方是否正在离开网络
+    pub leaving: bool,
+}
+
+/// 成员变化事件
+#[derive(Debug, Clone, PartialEq)]
+pub enum MembershipEvent {
+    /// 新成员加入
+    Joined(PeerRecord),
+    /// 成员离开或超时
+    Left {
+        /// 节点 ID
+        node_id: String,
+        /// 离开原因
+        reason: String,
+    },
+}
+
+/// 成员状态
+#[derive(Debug, Clone)]
+struct MemberState {
+    /// 最新的记录
+    record: PeerRecord,
+    /// 心跳最后一次增长的时间
+    last_updated: Instant,
+}
+
 /// P2P 节点发现器
-#[derive(Debug)]
+///
+/// 克隆得到的发现器共享同一张成员表。
+#[derive(Clone)]
 pub struct PeerDiscovery {
     /// 配置信息
     config: PeerConfig,
@@ -780,18 +1198,242 @@ pub struct PeerDiscovery {
     discovered_peers: Arc<RwLock<HashSet<SocketAddr>>>,
     /// 发现状态
     is_running: Arc<RwLock<bool>>,
+    /// 本节点的记录
+    local: Arc<RwLock<PeerRecord>>,
+    /// 成员表（不含本节点）
+    members: Arc<RwLock<HashMap<String, MemberState>>>,
+    /// 已离开成员离开时的心跳计数，旧摘要不能让它们重新加入
+    departed: Arc<RwLock<HashMap<String, u64>>>,
+    /// 当前的参与方数（含本节点）
+    party_count: Arc<watch::Sender<usize>>,
+    /// 接收成员事件的网络监控器
+    monitor: Arc<RwLock<Option<Arc<NetworkMonitor>>>>,
+}
+
+impl std::fmt::Debug for PeerDiscovery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PeerDiscovery")
+            .field("config", &self.config)
+            .field("party_count", &*self.party_count.borrow())
+            .finish_non_exhaustive()
+    }
 }
 
 impl PeerDiscovery {
     /// 创建节点发现器
+    ///
+    /// 本节点的 ID 取自 `config.node_id`，没有时自动生成。
     pub fn new(config: PeerConfig) -> NetworkResult<Self> {
+        let address: SocketAddr = format!("{}:{}", config.host, config.port)
+            .parse()
+            .map_err(|e| NetworkError::ConfigError(format!("无效的监听地址: {}", e)))?;
+        let local = PeerRecord {
+            node_id: config.node_id.clone().unwrap_or_else(|| format!("node_{}", Uuid::new_v4())),
+            address,
+            role: config.node_role.clone(),
+            heartbeat: 0,
+        };
+
         Ok(PeerDiscovery {
             config,
             discovered_peers: Arc::new(RwLock::new(HashSet::new())),
             is_running: Arc::new(RwLock::new(false)),
+            local: Arc::new(RwLock::new(local)),
+            members: Arc::new(RwLock::new(HashMap::new())),
+            departed: Arc::new(RwLock::new(HashMap::new())),
+            party_count: Arc::new(watch::channel(1).0),
+            monitor: Arc::new(RwLock::new(None)),
         })
     }
 
+    /// 设置接收加入/离开事件的网络监控器
+    pub async fn set_monitor(&self, monitor: Arc<NetworkMonitor>) {
+        *self.monitor.write().await = Some(monitor);
+    }
+
+    /// 本节点的记录
+    pub async fn local_record(&self) -> PeerRecord {
+        self.local.read().await.clone()
+    }
+
+    /// 当前的成员（不含本节点），按节点 ID 排序
+    pub async fn members(&self) -> Vec<PeerRecord> {
+        let mut members: Vec<PeerRecord> = self.members
+            .read()
+            .await
+            .values()
+            .map(|member| member.record.clone())
+            .collect();
+        members.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+        members
+    }
+
+    /// 当前的参与方数（含本节点）
+    pub fn party_count(&self) -> usize {
+        *self.party_count.borrow()
+    }
+
+    /// 构造发给引导节点的加入请求
+    pub async fn join_request(&self) -> NetworkResult<NetworkMessage> {
+        self.digest_message(DISCOVERY_MESSAGE_TYPE, false).await
+    }
+
+    /// 构造一轮 gossip 摘要，本节点的心跳计数加一
+    pub async fn gossip_message(&self) -> NetworkResult<NetworkMessage> {
+        self.local.write().await.heartbeat += 1;
+        self.digest_message(GOSSIP_MESSAGE_TYPE, false).await
+    }
+
+    /// 构造离开通知
+    pub async fn leave_message(&self) -> NetworkResult<NetworkMessage> {
+        self.digest_message(GOSSIP_MESSAGE_TYPE, true).await
+    }
+
+    /// 把成员表编码为指定类型的消息
+    async fn digest_message(&self, message_type: &str, leaving: bool) -> NetworkResult<NetworkMessage> {
+        let sender = self.local_record().await;
+        let digest = MembershipDigest {
+            network_id: self.config.network_id.clone(),
+            members: if leaving { Vec::new() } else { self.members().await },
+            sender: sender.clone(),
+            leaving,
+        };
+        let payload = serde_json::to_vec(&digest)
+            .map_err(|e| NetworkError::SerializationError(e.to_string()))?;
+        Ok(NetworkMessage::new(message_type, &payload).with_sender(sender.node_id))
+    }
+
+    /// 处理 `discovery` 或 `gossip` 消息
+    ///
+    /// 合并摘要中的成员；加入请求的回复是包含完整成员表的 `gossip` 消息。
+    pub async fn handle_message(&self, message: &NetworkMessage) -> NetworkResult<Option<NetworkMessage>> {
+        let digest: MembershipDigest = serde_json::from_slice(&message.payload)
+            .map_err(|e| NetworkError::DeserializationError(e.to_string()))?;
+        self.apply_digest(digest).await?;
+
+        if message.message_type == DISCOVERY_MESSAGE_TYPE {
+            Ok(Some(self.digest_message(GOSSIP_MESSAGE_TYPE, false).await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// 合并成员表摘要，返回产生的成员事件
+    pub async fn apply_digest(&self, digest: MembershipDigest) -> NetworkResult<Vec<MembershipEvent>> {
+        if digest.network_id != self.config.network_id {
+            return Err(NetworkError::ProtocolError(format!(
+                "来自网络 {} 的成员摘要，本节点属于网络 {}", digest.network_id, self.config.network_id
+            )));
+        }
+        let local_id = self.local.read().await.node_id.clone();
+
+        let mut events = Vec::new();
+        {
+            let mut members = self.members.write().await;
+            let mut departed = self.departed.write().await;
+
+            if digest.leaving {
+                if members.remove(&digest.sender.node_id).is_some() {
+                    departed.insert(digest.sender.node_id.clone(), digest.sender.heartbeat);
+                    events.push(MembershipEvent::Left {
+                        node_id: digest.sender.node_id,
+                        reason: "主动离开".to_string(),
+                    });
+                }
+            } else {
+                for record in std::iter::once(digest.sender).chain(digest.members) {
+                    if record.node_id == local_id
+                        || departed.get(&record.node_id).is_some_and(|&heartbeat| heartbeat >= record.heartbeat)
+                    {
+                        continue;
+                    }
+                    let capacity_left = members.len() < self.config.gossip.max_members;
+                    match members.get_mut(&record.node_id) {
+                        Some(member) if record.heartbeat > member.record.heartbeat => {
+                            member.record = record;
+                            member.last_updated = Instant::now();
+                        }
+                        None if capacity_left => {
+                            departed.remove(&record.node_id);
+                            events.push(MembershipEvent::Joined(record.clone()));
+                            members.insert(record.node_id.clone(), MemberState {
+                                record,
+                                last_updated: Instant::now(),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            self.party_count.send_replace(members.len() + 1);
+        }
+
+        self.publish(&events).await;
+        Ok(events)
+    }
+
+    /// 移除心跳在 `member_timeout` 内没有增长的成员
+    pub async fn expire_members(&self) -> Vec<MembershipEvent> {
+        let member_timeout = Duration::from_millis(self.config.gossip.member_timeout);
+        let mut events = Vec::new();
+        {
+            let mut members = self.members.write().await;
+            let mut departed = self.departed.write().await;
+            members.retain(|node_id, member| {
+                if member.last_updated.elapsed() <= member_timeout {
+                    return true;
+                }
+                departed.insert(node_id.clone(), member.record.heartbeat);
+                events.push(MembershipEvent::Left {
+                    node_id: node_id.clone(),
+                    reason: "心跳超时".to_string(),
+                });
+                false
+            });
+            self.party_count.send_replace(members.len() + 1);
+        }
+
+        self.publish(&events).await;
+        events
+    }
+
+    /// 把成员事件发布到网络监控器
+    async fn publish(&self, events: &[MembershipEvent]) {
+        let monitor = match self.monitor.read().await.clone() {
+            Some(monitor) => monitor,
+            None => return,
+        };
+        for event in events {
+            let event = match event {
+                MembershipEvent::Joined(record) => NetworkEvent::PeerJoined {
+                    peer_id: record.node_id.clone(),
+                    address: record.address,
+                },
+                MembershipEvent::Left { node_id, reason } => NetworkEvent::PeerLeft {
+                    peer_id: node_id.clone(),
+                    reason: reason.clone(),
+                },
+            };
+            monitor.publish_event(event).await;
+        }
+    }
+
+    /// 等待至少 `parties` 个参与方（含本节点）加入
+    ///
+    /// 返回按节点 ID 排序的全部参与方记录；超时返回 `Timeout`。
+    pub async fn wait_for_parties(&self, parties: usize, wait: Duration) -> NetworkResult<Vec<PeerRecord>> {
+        let mut party_count = self.party_count.subscribe();
+        timeout(wait, party_count.wait_for(|&count| count >= parties))
+            .await
+            .map_err(|_| NetworkError::Timeout)?
+            .map_err(|_| NetworkError::ChannelError("成员表已关闭".to_string()))?;
+
+        let mut parties = self.members().await;
+        parties.push(self.local_record().await);
+        parties.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+        Ok(parties)
+    }
+
     /// 启动节点发现
     pub async fn start(&mut self) -> NetworkResult<()> {
         {
@@ -929,3 +1571,97 @@ impl MessageHandler for DefaultMessageHandler {
     }
 }
 
+/// 基于 P2P 节点的协议传输
+///
+/// 参与方 ID 是节点 ID 在成员名单中的位置。发出的协议消息序列化后包装在
+/// `transport` 类型的消息中；创建时为该类型注册处理器，把收到的消息转交给 `recv`，
+/// 来自名单之外节点的消息被拒绝。
+pub struct P2PTransport {
+    node: Arc<P2PNode>,
+    party_id: PartyId,
+    /// 按参与方 ID 排列的节点 ID
+    members: Vec<String>,
+    incoming: Mutex<mpsc::UnboundedReceiver<(PartyId, NetworkMessage)>>,
+}
+
+impl P2PTransport {
+    /// 在节点上创建传输
+    ///
+    /// # 参数
+    /// - `node`: 本方的 P2P 节点，其节点 ID 必须在名单中
+    /// - `members`: 按参与方 ID 排列的节点 ID
+    pub async fn new(node: Arc<P2PNode>, members: Vec<String>) -> NetworkResult<Self> {
+        let party_id = members.iter()
+            .position(|member| *member == node.node_id)
+            .ok_or_else(|| NetworkError::ConfigError(format!("节点 {} 不在成员名单中", node.node_id)))?;
+        let (sender, incoming) = mpsc::unbounded_channel();
+        let handler = TransportHandler { members: members.clone(), sender };
+        node.register_handler(TRANSPORT_MESSAGE_TYPE.to_string(), Box::new(handler)).await;
+        Ok(Self {
+            node,
+            party_id,
+            members,
+            incoming: Mutex::new(incoming),
+        })
+    }
+
+    /// 底层 P2P 节点
+    pub fn node(&self) -> &Arc<P2PNode> {
+        &self.node
+    }
+}
+
+impl Transport for P2PTransport {
+    fn party_id(&self) -> PartyId {
+        self.party_id
+    }
+
+    fn parties(&self) -> Vec<PartyId> {
+        (0..self.members.len()).collect()
+    }
+
+    fn send(&self, to: PartyId, message: NetworkMessage) -> TransportFuture<'_, ()> {
+        Box::pin(async move {
+            let peer = self.members.get(to)
+                .ok_or_else(|| NetworkError::PeerNotFound(to.to_string()))?;
+            let wrapped = NetworkMessage::new(TRANSPORT_MESSAGE_TYPE, &message.serialize()?)
+                .with_sender(self.node.node_id.clone())
+                .with_receiver(peer.clone());
+            self.node.send_to_peer(peer, wrapped).await
+        })
+    }
+
+    fn recv(&self) -> TransportFuture<'_, (PartyId, NetworkMessage)> {
+        Box::pin(async move {
+            self.incoming.lock().await.recv().await
+                .ok_or_else(|| NetworkError::ChannelError("传输处理器已关闭".to_string()))
+        })
+    }
+}
+
+/// 把 `transport` 消息转交给 `P2PTransport`
+struct TransportHandler {
+    members: Vec<String>,
+    sender: mpsc::UnboundedSender<(PartyId, NetworkMessage)>,
+}
+
+impl MessageHandler for TransportHandler {
+    fn handle_message(
+        &self,
+        from_peer: &str,
+        message: &NetworkMessage,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = NetworkResult<Option<NetworkMessage>>> + Send + '_>> {
+        let from_peer = from_peer.to_string();
+        let payload = message.payload.clone();
+
+        Box::pin(async move {
+            let party = self.members.iter()
+                .position(|member| *member == from_peer)
+                .ok_or_else(|| NetworkError::AuthorizationFailed(format!("{} 不是会话成员", from_peer)))?;
+            let inner = NetworkMessage::deserialize(&payload)?;
+            self.sender.send((party, inner))
+                .map_err(|_| NetworkError::ChannelError("传输已关闭".to_string()))?;
+            Ok(None)
+        })
+    }
+}
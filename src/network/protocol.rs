@@ -2,11 +2,57 @@
 //!
 //! 本模块定义了网络通信的协议格式、消息类型和编解码规则。
 //! 为 P2P 和 HTTP 网络提供统一的消息协议。
+//!
+//! ## 重放保护
+//!
+//! 发送方用 `MessageProtocol::stamp_outgoing` 为每个（接收方, 会话）分配单调递增的序列号，
+//! 写入 `mpc-seq` 消息头；接收方用 `check_incoming` 按（发送方, 会话）维护一个滑动窗口，
+//! 拒绝窗口内重复的序列号和落在窗口之前的序列号，并向审计日志记录重放攻击事件。
+//! 窗口内的乱序消息仍会被接受。
+//!
+//! ## 大消息分帧
+//!
+//! `framing` 子模块把大消息编码为带长度前缀的分片帧，可选 DEFLATE 压缩，
+//! 接收方用 `FrameAssembler` 重组，单帧长度和重组后的消息长度都有上限。
+//!
+//! ## 同步轮次
+//!
+//! `rounds` 子模块的 `RoundCoordinator` 按轮收集每个参与方的消息，每轮有超时时间，
+//! 超时后收到的消息不少于门限时继续执行，并报告缺席的参与方。
+//!
+//! ## 可靠广播
+//!
+//! `broadcast` 子模块实现 Bracha 可靠广播（SEND / ECHO / READY 三个阶段），
+//! 在 n ≥ 3f + 1 时保证所有诚实参与方交付同一个值。
+
+pub mod broadcast;
+pub mod framing;
+pub mod rounds;
+
+pub use broadcast::*;
+pub use framing::*;
+pub use rounds::*;
 
-use std::{collections::HashMap, time::SystemTime};
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+    time::SystemTime,
+};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::network::common::{NetworkError, NetworkResult};
+use crate::protocols::clock::MessageHeader;
+use crate::protocols::session::SessionId;
+use crate::security::{AuditLogger, SecurityEvent, SecurityLevel, SecurityPolicy, ThreatType};
+
+/// 携带会话 ID 的消息头键
+pub const SESSION_HEADER: &str = "mpc-session";
+/// 携带逻辑轮次的消息头键
+pub const ROUND_HEADER: &str = "mpc-round";
+/// 携带消息序列号的消息头键
+pub const SEQUENCE_HEADER: &str = "mpc-seq";
+/// 默认的重放窗口大小
+pub const DEFAULT_REPLAY_WINDOW: u64 = 1024;
 
 /// 网络消息协议
 #[derive(Debug)]
@@ -16,6 +62,73 @@ pub struct MessageProtocol {
     version: String,
     /// 支持的消息类型
     supported_types: HashMap<String, MessageTypeInfo>,
+    /// 重放窗口大小
+    replay_window: u64,
+    /// 每个（接收方, 会话）最近分配的序列号
+    outgoing_sequences: HashMap<(String, String), u64>,
+    /// 每个（发送方, 会话）的重放窗口
+    incoming_windows: HashMap<(String, String), ReplayWindow>,
+    /// 记录重放事件的审计日志
+    audit_logger: Arc<AuditLogger>,
+}
+
+/// 重放检查拒绝消息的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayRejection {
+    /// 序列号已经出现过
+    Duplicate,
+    /// 序列号落在窗口之前，无法判断是否出现过
+    Stale,
+}
+
+impl ReplayRejection {
+    /// 原因名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReplayRejection::Duplicate => "duplicate",
+            ReplayRejection::Stale => "stale",
+        }
+    }
+}
+
+/// 滑动重放窗口
+///
+/// 记录 `(highest - size, highest]` 范围内已接受的序列号，序列号从 1 开始。
+#[derive(Debug, Clone)]
+pub struct ReplayWindow {
+    /// 窗口大小
+    size: u64,
+    /// 已接受的最大序列号
+    highest: u64,
+    /// 窗口内已接受的序列号
+    seen: BTreeSet<u64>,
+}
+
+impl ReplayWindow {
+    /// 创建空窗口
+    pub fn new(size: u64) -> Self {
+        Self { size: size.max(1), highest: 0, seen: BTreeSet::new() }
+    }
+
+    /// 已接受的最大序列号
+    pub fn highest(&self) -> u64 {
+        self.highest
+    }
+
+    /// 接受一个序列号；重复或过旧时返回拒绝原因，窗口不变
+    pub fn accept(&mut self, sequence: u64) -> Result<(), ReplayRejection> {
+        if sequence == 0 || sequence.saturating_add(self.size) <= self.highest {
+            return Err(ReplayRejection::Stale);
+        }
+        if !self.seen.insert(sequence) {
+            return Err(ReplayRejection::Duplicate);
+        }
+        if sequence > self.highest {
+            self.highest = sequence;
+            self.seen = self.seen.split_off(&(self.highest.saturating_sub(self.size) + 1));
+        }
+        Ok(())
+    }
 }
 
 /// 消息类型信息
@@ -109,6 +222,71 @@ impl NetworkMessage {
         self
     }
 
+    /// 写入逻辑时钟消息头，发送时间使用消息头中的时间
+    pub fn with_logical_clock(mut self, header: &MessageHeader) -> Self {
+        let session: String = header.session.as_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+        self.headers.insert(SESSION_HEADER.to_string(), session);
+        self.headers.insert(ROUND_HEADER.to_string(), header.round.to_string());
+        self.timestamp = header.sent_at;
+        self
+    }
+
+    /// 读取逻辑时钟消息头；消息不携带逻辑时钟时返回 `None`
+    pub fn logical_clock(&self) -> NetworkResult<Option<MessageHeader>> {
+        let (session, round) = match (self.headers.get(SESSION_HEADER), self.headers.get(ROUND_HEADER)) {
+            (Some(session), Some(round)) => (session, round),
+            _ => return Ok(None),
+        };
+        let invalid = || NetworkError::ProtocolError("无效的逻辑时钟消息头".to_string());
+
+        if session.len() != 64 {
+            return Err(invalid());
+        }
+        let mut id = [0u8; 32];
+        for (i, byte) in id.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(session.get(2 * i..2 * i + 2).ok_or_else(invalid)?, 16)
+                .map_err(|_| invalid())?;
+        }
+        Ok(Some(MessageHeader {
+            session: SessionId(id),
+            round: round.parse().map_err(|_| invalid())?,
+            sent_at: self.timestamp,
+        }))
+    }
+
+    /// 写入序列号消息头
+    pub fn with_sequence(mut self, sequence: u64) -> Self {
+        self.headers.insert(SEQUENCE_HEADER.to_string(), sequence.to_string());
+        self
+    }
+
+    /// 读取序列号消息头；消息不携带序列号时返回 `None`
+    pub fn sequence(&self) -> NetworkResult<Option<u64>> {
+        self.headers
+            .get(SEQUENCE_HEADER)
+            .map(|sequence| sequence.parse().map_err(|_| NetworkError::ProtocolError("无效的序列号消息头".to_string())))
+            .transpose()
+    }
+
+    /// 写入轮次消息头
+    pub fn with_round(mut self, round: u64) -> Self {
+        self.headers.insert(ROUND_HEADER.to_string(), round.to_string());
+        self
+    }
+
+    /// 读取轮次消息头；消息不携带轮次时返回 `None`
+    pub fn round(&self) -> NetworkResult<Option<u64>> {
+        self.headers
+            .get(ROUND_HEADER)
+            .map(|round| round.parse().map_err(|_| NetworkError::ProtocolError("无效的轮次消息头".to_string())))
+            .transpose()
+    }
+
+    /// 消息所属的会话（`mpc-session` 消息头），没有时为空字符串
+    fn session_key(&self) -> String {
+        self.headers.get(SESSION_HEADER).cloned().unwrap_or_default()
+    }
+
     /// 序列化消息
     pub fn serialize(&self) -> NetworkResult<Vec<u8>> {
         serde_json::to_vec(self)
@@ -141,6 +319,10 @@ impl MessageProtocol {
         let mut protocol = MessageProtocol {
             version: "1.0".to_string(),
             supported_types: HashMap::new(),
+            replay_window: DEFAULT_REPLAY_WINDOW,
+            outgoing_sequences: HashMap::new(),
+            incoming_windows: HashMap::new(),
+            audit_logger: Arc::new(AuditLogger::new(SecurityPolicy::default())),
         };
 
         // 注册默认消息类型
@@ -175,6 +357,12 @@ impl MessageProtocol {
                 requires_auth: false,
                 max_payload_size: 2048,
             },
+            MessageTypeInfo {
+                name: FRAME_MESSAGE_TYPE.to_string(),
+                description: "大消息分片帧".to_string(),
+                requires_auth: true,
+                max_payload_size: FRAME_LENGTH_PREFIX + DEFAULT_MAX_FRAME_SIZE,
+            },
         ];
 
         for type_info in types {
@@ -191,6 +379,74 @@ impl MessageProtocol {
     pub fn get_type_info(&self, message_type: &str) -> Option<&MessageTypeInfo> {
         self.supported_types.get(message_type)
     }
+
+    /// 设置重放窗口大小，只影响之后新建的窗口
+    pub fn with_replay_window(mut self, size: u64) -> Self {
+        self.replay_window = size.max(1);
+        self
+    }
+
+    /// 使用共享的审计日志
+    pub fn with_audit_logger(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// 获取审计日志
+    pub fn audit_logger(&self) -> &Arc<AuditLogger> {
+        &self.audit_logger
+    }
+
+    /// 为待发送的消息分配（接收方, 会话）内的下一个序列号
+    pub fn stamp_outgoing(&mut self, message: NetworkMessage) -> NetworkMessage {
+        let key = (message.receiver_id.clone().unwrap_or_default(), message.session_key());
+        let sequence = self.outgoing_sequences.entry(key).or_insert(0);
+        *sequence += 1;
+        let sequence = *sequence;
+        message.with_sequence(sequence)
+    }
+
+    /// 检查收到的消息是否为重放
+    ///
+    /// 消息必须携带序列号；重复或落在窗口之前的序列号被拒绝，并记录一条重放攻击事件。
+    pub fn check_incoming(&mut self, message: &NetworkMessage) -> NetworkResult<()> {
+        let sequence = message.sequence()?
+            .ok_or_else(|| NetworkError::ProtocolError("消息缺少序列号".to_string()))?;
+        let sender = message.sender_id.clone().unwrap_or_default();
+        let session = message.session_key();
+
+        let replay_window = self.replay_window;
+        let window = self.incoming_windows
+            .entry((sender.clone(), session.clone()))
+            .or_insert_with(|| ReplayWindow::new(replay_window));
+        let Err(rejection) = window.accept(sequence) else {
+            return Ok(());
+        };
+
+        let event = SecurityEvent::new(
+            ThreatType::ReplayAttack,
+            SecurityLevel::High,
+            format!("rejected {} message {} from {}", rejection.name(), sequence, sender),
+        )
+        .with_peer(&sender)
+        .with_context("session".to_string(), session)
+        .with_context("sequence".to_string(), sequence.to_string())
+        .with_context("highest_sequence".to_string(), window.highest().to_string())
+        .with_context("reason".to_string(), rejection.name().to_string())
+        .with_context("message_id".to_string(), message.id.clone())
+        .with_mitigation("message dropped".to_string());
+        // 审计日志写入失败不改变检查结果
+        let _ = self.audit_logger.log_event(event);
+
+        Err(NetworkError::ProtocolError(format!("检测到来自 {} 的重放消息: 序列号 {}", sender, sequence)))
+    }
+
+    /// 丢弃与对端在某个会话中的序列号状态（会话结束后调用）
+    pub fn close_session(&mut self, peer_id: &str, session: &str) {
+        let key = (peer_id.to_string(), session.to_string());
+        self.outgoing_sequences.remove(&key);
+        self.incoming_windows.remove(&key);
+    }
 }
 
 impl Default for MessageProtocol {
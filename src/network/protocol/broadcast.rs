@@ -0,0 +1,421 @@
+//! # 可靠广播 (Bracha Reliable Broadcast)
+//!
+//! 点对点发送无法阻止恶意发送方向不同参与方发送不同的值。SPDZ 的 MAC 检查、
+//! 掷硬币等协议需要一致的广播：只要有一个诚实参与方接受了某个值，所有诚实参与方都接受同一个值。
+//!
+//! 本模块实现 Bracha 广播，要求 n ≥ 3f + 1（f 为恶意参与方数）：
+//!
+//! 1. **SEND**: 发送方把值发给所有参与方
+//! 2. **ECHO**: 收到发送方的 SEND 后，向所有参与方回显一次该值
+//! 3. **READY**: 同一个值收到 ⌈(n + f + 1) / 2⌉ 个 ECHO，或收到 f + 1 个 READY 时，发送一次 READY
+//! 4. **交付**: 同一个值收到 2f + 1 个 READY 时接受该值
+//!
+//! 每个参与方在每个广播实例的每个阶段只有第一条消息被计数，
+//! 同一阶段发送不同值或冒充发送方的参与方会被记为可疑。
+//!
+//! `ReliableBroadcast` 是不涉及 I/O 的状态机，产生的消息由调用方发给其他所有参与方；
+//! `BroadcastChannel` 在异步通道上驱动状态机，`MpcSession::reliable_broadcast` 在同步轮上驱动状态机。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::network::protocol::*;
+//!
+//! # fn main() -> mpc_api::network::NetworkResult<()> {
+//! let parties: Vec<String> = (0..4).map(|i| format!("p{}", i)).collect();
+//! let mut nodes: Vec<ReliableBroadcast> = parties
+//!     .iter()
+//!     .map(|id| ReliableBroadcast::new(id, parties.clone(), 1))
+//!     .collect::<Result<_, _>>()?;
+//!
+//! // 把每条消息投递给其他所有参与方，直到没有新消息
+//! let mut queue: Vec<(usize, BroadcastMessage)> =
+//!     nodes[0].broadcast("coin", b"heads")?.into_iter().map(|m| (0, m)).collect();
+//! while let Some((from, message)) = queue.pop() {
+//!     for to in (0..4).filter(|&to| to != from) {
+//!         let replies = nodes[to].handle(&parties[from], message.clone())?;
+//!         queue.extend(replies.into_iter().map(|m| (to, m)));
+//!     }
+//! }
+//! for node in &nodes {
+//!     assert_eq!(node.delivered("p0", "coin"), Some(&b"heads"[..]));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use crate::network::common::{NetworkError, NetworkResult};
+use super::NetworkMessage;
+
+/// 承载可靠广播消息的消息类型
+pub const BROADCAST_MESSAGE_TYPE: &str = "rbc";
+
+/// n 个参与方时可容忍的最大恶意参与方数 ⌊(n - 1) / 3⌋
+pub fn max_broadcast_faults(parties: usize) -> usize {
+    parties.saturating_sub(1) / 3
+}
+
+/// 广播阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BroadcastPhase {
+    /// 发送方发出的值
+    Send,
+    /// 回显
+    Echo,
+    /// 准备交付
+    Ready,
+}
+
+/// 可靠广播消息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BroadcastMessage {
+    /// 广播发送方
+    pub origin: String,
+    /// 广播标签，同一发送方的不同广播用标签区分
+    pub tag: String,
+    /// 阶段
+    pub phase: BroadcastPhase,
+    /// 广播的值
+    pub value: Vec<u8>,
+}
+
+impl BroadcastMessage {
+    /// 封装为网络消息
+    pub fn to_network_message(&self, sender: &str) -> NetworkResult<NetworkMessage> {
+        let payload = serde_json::to_vec(self)
+            .map_err(|e| NetworkError::SerializationError(e.to_string()))?;
+        Ok(NetworkMessage::new(BROADCAST_MESSAGE_TYPE, &payload).with_sender(sender.to_string()))
+    }
+
+    /// 从网络消息解析
+    pub fn from_network_message(message: &NetworkMessage) -> NetworkResult<Self> {
+        if message.message_type != BROADCAST_MESSAGE_TYPE {
+            return Err(NetworkError::ProtocolError(format!("不是可靠广播消息: {}", message.message_type)));
+        }
+        serde_json::from_slice(&message.payload)
+            .map_err(|e| NetworkError::DeserializationError(e.to_string()))
+    }
+}
+
+/// 一次交付
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delivery {
+    /// 广播发送方
+    pub origin: String,
+    /// 广播标签
+    pub tag: String,
+    /// 交付的值
+    pub value: Vec<u8>,
+}
+
+/// 一个广播实例的状态
+#[derive(Debug, Default)]
+struct Instance {
+    /// 收到的 SEND
+    sent: Option<Vec<u8>>,
+    /// 每个参与方的 ECHO
+    echoes: HashMap<String, Vec<u8>>,
+    /// 每个参与方的 READY
+    readies: HashMap<String, Vec<u8>>,
+    /// 本方是否已发送 READY
+    ready_sent: bool,
+    /// 交付的值
+    delivered: Option<Vec<u8>>,
+}
+
+impl Instance {
+    /// 得票不少于 `quorum` 的值
+    fn value_with(votes: &HashMap<String, Vec<u8>>, quorum: usize) -> Option<Vec<u8>> {
+        let mut counts: HashMap<&[u8], usize> = HashMap::new();
+        for value in votes.values() {
+            *counts.entry(value.as_slice()).or_insert(0) += 1;
+        }
+        counts.into_iter().find(|&(_, count)| count >= quorum).map(|(value, _)| value.to_vec())
+    }
+}
+
+/// Bracha 可靠广播状态机
+#[derive(Debug)]
+pub struct ReliableBroadcast {
+    /// 本方 ID
+    local_id: String,
+    /// 全部参与方（含本方）
+    parties: BTreeSet<String>,
+    /// 可容忍的恶意参与方数
+    faults: usize,
+    /// 以（发送方, 标签）区分的广播实例
+    instances: HashMap<(String, String), Instance>,
+    /// 尚未取走的交付
+    deliveries: VecDeque<Delivery>,
+    /// 发送了不一致消息的参与方
+    suspected: BTreeSet<String>,
+}
+
+impl ReliableBroadcast {
+    /// 创建状态机
+    ///
+    /// # 参数
+    /// - `local_id`: 本方 ID，必须在 `parties` 中
+    /// - `parties`: 全部参与方
+    /// - `faults`: 可容忍的恶意参与方数 f，要求参与方数不小于 3f + 1
+    pub fn new(local_id: &str, parties: impl IntoIterator<Item = String>, faults: usize) -> NetworkResult<Self> {
+        let parties: BTreeSet<String> = parties.into_iter().collect();
+        if !parties.contains(local_id) {
+            return Err(NetworkError::ConfigError(format!("{} 不在参与方列表中", local_id)));
+        }
+        if parties.len() < 3 * faults + 1 {
+            return Err(NetworkError::ConfigError(format!(
+                "{} 个参与方最多容忍 {} 个恶意参与方，不能容忍 {} 个",
+                parties.len(), max_broadcast_faults(parties.len()), faults
+            )));
+        }
+
+        Ok(ReliableBroadcast {
+            local_id: local_id.to_string(),
+            parties,
+            faults,
+            instances: HashMap::new(),
+            deliveries: VecDeque::new(),
+            suspected: BTreeSet::new(),
+        })
+    }
+
+    /// 本方 ID
+    pub fn local_id(&self) -> &str {
+        &self.local_id
+    }
+
+    /// 可容忍的恶意参与方数
+    pub fn faults(&self) -> usize {
+        self.faults
+    }
+
+    /// 发送 READY 所需的 ECHO 数 ⌈(n + f + 1) / 2⌉
+    pub fn echo_quorum(&self) -> usize {
+        (self.parties.len() + self.faults) / 2 + 1
+    }
+
+    /// 发起一次广播，返回需要发给其他所有参与方的消息
+    pub fn broadcast(&mut self, tag: &str, value: &[u8]) -> NetworkResult<Vec<BroadcastMessage>> {
+        let key = (self.local_id.clone(), tag.to_string());
+        if self.instances.get(&key).is_some_and(|instance| instance.sent.is_some()) {
+            return Err(NetworkError::ProtocolError(format!("标签 {} 已经广播过", tag)));
+        }
+
+        let send = BroadcastMessage {
+            origin: self.local_id.clone(),
+            tag: tag.to_string(),
+            phase: BroadcastPhase::Send,
+            value: value.to_vec(),
+        };
+        let local_id = self.local_id.clone();
+        let mut outgoing = vec![send.clone()];
+        outgoing.extend(self.handle(&local_id, send)?);
+        Ok(outgoing)
+    }
+
+    /// 处理来自 `from` 的消息，返回需要发给其他所有参与方的消息
+    ///
+    /// 发送方或广播发送方不是参与方时返回协议错误；冒充发送方或在同一阶段发送不同值的消息被忽略，
+    /// 并把发送者记为可疑。
+    pub fn handle(&mut self, from: &str, message: BroadcastMessage) -> NetworkResult<Vec<BroadcastMessage>> {
+        let mut outgoing = Vec::new();
+        let mut queue = VecDeque::from([(from.to_string(), message)]);
+
+        while let Some((from, message)) = queue.pop_front() {
+            if !self.parties.contains(&from) {
+                return Err(NetworkError::ProtocolError(format!("{} 不是参与方", from)));
+            }
+            if !self.parties.contains(&message.origin) {
+                return Err(NetworkError::ProtocolError(format!("广播发送方 {} 不是参与方", message.origin)));
+            }
+
+            for reply in self.process(&from, &message) {
+                outgoing.push(reply.clone());
+                queue.push_back((self.local_id.clone(), reply));
+            }
+        }
+        Ok(outgoing)
+    }
+
+    /// 记录一条消息并推进实例，返回本方新产生的消息
+    fn process(&mut self, from: &str, message: &BroadcastMessage) -> Vec<BroadcastMessage> {
+        let echo_quorum = self.echo_quorum();
+        let faults = self.faults;
+        let instance = self.instances
+            .entry((message.origin.clone(), message.tag.clone()))
+            .or_default();
+
+        let consistent = match message.phase {
+            BroadcastPhase::Send if from != message.origin => false,
+            BroadcastPhase::Send => match &instance.sent {
+                Some(previous) => previous == &message.value,
+                None => {
+                    instance.sent = Some(message.value.clone());
+                    true
+                }
+            },
+            BroadcastPhase::Echo => Self::record(&mut instance.echoes, from, &message.value),
+            BroadcastPhase::Ready => Self::record(&mut instance.readies, from, &message.value),
+        };
+        if !consistent {
+            self.suspected.insert(from.to_string());
+            return Vec::new();
+        }
+
+        let reply = |phase, value: Vec<u8>| BroadcastMessage {
+            origin: message.origin.clone(),
+            tag: message.tag.clone(),
+            phase,
+            value,
+        };
+        let mut outgoing = Vec::new();
+
+        if message.phase == BroadcastPhase::Send && !instance.echoes.contains_key(&self.local_id) {
+            outgoing.push(reply(BroadcastPhase::Echo, message.value.clone()));
+        }
+        if !instance.ready_sent {
+            let ready = Instance::value_with(&instance.echoes, echo_quorum)
+                .or_else(|| Instance::value_with(&instance.readies, faults + 1));
+            if let Some(value) = ready {
+                instance.ready_sent = true;
+                outgoing.push(reply(BroadcastPhase::Ready, value));
+            }
+        }
+        if instance.delivered.is_none() {
+            if let Some(value) = Instance::value_with(&instance.readies, 2 * faults + 1) {
+                instance.delivered = Some(value.clone());
+                self.deliveries.push_back(Delivery {
+                    origin: message.origin.clone(),
+                    tag: message.tag.clone(),
+                    value,
+                });
+            }
+        }
+        outgoing
+    }
+
+    /// 记录一票，同一参与方重复投出不同的值时返回 `false`
+    fn record(votes: &mut HashMap<String, Vec<u8>>, from: &str, value: &[u8]) -> bool {
+        match votes.get(from) {
+            Some(previous) => previous == value,
+            None => {
+                votes.insert(from.to_string(), value.to_vec());
+                true
+            }
+        }
+    }
+
+    /// 已交付的值
+    pub fn delivered(&self, origin: &str, tag: &str) -> Option<&[u8]> {
+        self.instances
+            .get(&(origin.to_string(), tag.to_string()))
+            .and_then(|instance| instance.delivered.as_deref())
+    }
+
+    /// 取走尚未取走的交付
+    pub fn take_deliveries(&mut self) -> Vec<Delivery> {
+        self.deliveries.drain(..).collect()
+    }
+
+    /// 发送过不一致消息的参与方
+    pub fn suspected_parties(&self) -> Vec<String> {
+        self.suspected.iter().cloned().collect()
+    }
+
+    /// 丢弃一个广播实例的状态
+    pub fn forget(&mut self, origin: &str, tag: &str) {
+        self.instances.remove(&(origin.to_string(), tag.to_string()));
+    }
+}
+
+/// 在异步通道上运行的可靠广播
+///
+/// `outgoing` 中的每条消息都应发给其他所有参与方；其他参与方的消息以 `(发送方, 消息)` 送入 `incoming`。
+#[derive(Debug)]
+pub struct BroadcastChannel {
+    state: ReliableBroadcast,
+    outgoing: mpsc::UnboundedSender<BroadcastMessage>,
+    incoming: mpsc::UnboundedReceiver<(String, BroadcastMessage)>,
+}
+
+impl BroadcastChannel {
+    /// 创建广播通道
+    pub fn new(
+        state: ReliableBroadcast,
+        outgoing: mpsc::UnboundedSender<BroadcastMessage>,
+        incoming: mpsc::UnboundedReceiver<(String, BroadcastMessage)>,
+    ) -> Self {
+        BroadcastChannel { state, outgoing, incoming }
+    }
+
+    /// 广播状态机
+    pub fn state(&self) -> &ReliableBroadcast {
+        &self.state
+    }
+
+    /// 发起一次广播
+    pub fn broadcast(&mut self, tag: &str, value: &[u8]) -> NetworkResult<()> {
+        let messages = self.state.broadcast(tag, value)?;
+        self.send_all(messages)
+    }
+
+    /// 等待 `origin` 以 `tag` 广播的值被交付
+    pub async fn deliver(&mut self, origin: &str, tag: &str, wait: Duration) -> NetworkResult<Vec<u8>> {
+        let deadline = Instant::now() + wait;
+        loop {
+            if let Some(value) = self.state.delivered(origin, tag) {
+                return Ok(value.to_vec());
+            }
+            self.step(deadline).await?;
+        }
+    }
+
+    /// 等待所有参与方以 `tag` 广播的值被交付，返回按发送方排列的值
+    pub async fn deliver_all(&mut self, tag: &str, wait: Duration) -> NetworkResult<BTreeMap<String, Vec<u8>>> {
+        let deadline = Instant::now() + wait;
+        let parties: Vec<String> = self.state.parties.iter().cloned().collect();
+        let mut values = BTreeMap::new();
+        for origin in parties {
+            while self.state.delivered(&origin, tag).is_none() {
+                self.step(deadline).await?;
+            }
+            if let Some(value) = self.state.delivered(&origin, tag) {
+                values.insert(origin, value.to_vec());
+            }
+        }
+        Ok(values)
+    }
+
+    /// 在截止时间前处理一条收到的消息
+    ///
+    /// 来自非参与方的消息被丢弃，不影响其他广播。
+    async fn step(&mut self, deadline: Instant) -> NetworkResult<()> {
+        let wait = deadline.saturating_duration_since(Instant::now());
+        match tokio::time::timeout(wait, self.incoming.recv()).await {
+            Ok(Some((from, message))) => {
+                if let Ok(replies) = self.state.handle(&from, message) {
+                    self.send_all(replies)?;
+                }
+                Ok(())
+            }
+            Ok(None) => Err(NetworkError::ChannelError("广播消息通道已关闭".to_string())),
+            Err(_) => Err(NetworkError::Timeout),
+        }
+    }
+
+    fn send_all(&self, messages: Vec<BroadcastMessage>) -> NetworkResult<()> {
+        for message in messages {
+            self.outgoing
+                .send(message)
+                .map_err(|_| NetworkError::ChannelError("广播发送通道已关闭".to_string()))?;
+        }
+        Ok(())
+    }
+}
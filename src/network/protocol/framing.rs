@@ -0,0 +1,396 @@
+//! # 消息分帧 (Message Framing)
+//!
+//! 混淆电路和批量份额向量序列化后可达数 MB，无法作为单条消息发送。
+//! 本模块把 `NetworkMessage` 编码为一组带长度前缀的帧：
+//!
+//! ```text
+//! | 长度 (u32) | 标志 (u8) | 传输 ID (16 字节) | 分片序号 (u32) | 分片总数 (u32) | 数据 |
+//! ```
+//!
+//! - 整数均为大端序，长度不含自身的 4 字节；超过 `max_frame_size` 的帧在读取帧体之前就被拒绝
+//! - 序列化后的消息达到 `compression_threshold` 时用 DEFLATE 压缩，压缩后没有变小则保留原文
+//! - 消息按帧容量切成分片，`FrameAssembler` 按传输 ID 重组，并限制重组后的消息大小和同时进行的传输数
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::network::protocol::*;
+//!
+//! # fn main() -> mpc_api::network::NetworkResult<()> {
+//! let codec = FrameCodec::new(FramingConfig {
+//!     max_frame_size: 4096,
+//!     compression: Compression::None,
+//!     ..FramingConfig::default()
+//! })?;
+//! let message = NetworkMessage::new("garbled_circuit", &[7u8; 10_000]);
+//! let frames = codec.encode(&message)?;
+//! assert!(frames.len() > 1);
+//!
+//! let mut assembler = FrameAssembler::new(codec);
+//! let mut received = Vec::new();
+//! for frame in &frames {
+//!     received.extend(assembler.push_bytes(frame)?);
+//! }
+//! assert_eq!(received.len(), 1);
+//! assert_eq!(received[0].payload, message.payload);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec_with_limit};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::network::common::{NetworkError, NetworkResult};
+use super::NetworkMessage;
+
+/// 帧长度前缀的字节数
+pub const FRAME_LENGTH_PREFIX: usize = 4;
+/// 分片头（标志、传输 ID、分片序号、分片总数）的字节数
+pub const CHUNK_HEADER_LEN: usize = 25;
+/// 默认的最大帧长度（不含长度前缀）
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 256 * 1024;
+/// 默认的最大消息长度（序列化后）
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+/// 默认的压缩阈值
+pub const DEFAULT_COMPRESSION_THRESHOLD: usize = 4 * 1024;
+/// 承载分帧数据的消息类型
+pub const FRAME_MESSAGE_TYPE: &str = "frame";
+
+/// 分片数据经过压缩
+const FLAG_COMPRESSED: u8 = 0x01;
+
+/// 压缩算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compression {
+    /// 不压缩
+    None,
+    /// DEFLATE 压缩，参数为压缩等级（0-10）
+    Deflate(u8),
+}
+
+/// 分帧配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FramingConfig {
+    /// 最大帧长度（不含长度前缀）
+    pub max_frame_size: usize,
+    /// 最大消息长度（序列化后、压缩前）
+    pub max_message_size: usize,
+    /// 压缩算法
+    pub compression: Compression,
+    /// 序列化后达到该长度的消息才压缩
+    pub compression_threshold: usize,
+    /// 同时进行重组的最大传输数
+    pub max_pending_transfers: usize,
+}
+
+impl Default for FramingConfig {
+    fn default() -> Self {
+        FramingConfig {
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            compression: Compression::Deflate(6),
+            compression_threshold: DEFAULT_COMPRESSION_THRESHOLD,
+            max_pending_transfers: 16,
+        }
+    }
+}
+
+impl FramingConfig {
+    /// 单帧可携带的数据长度
+    pub fn chunk_capacity(&self) -> usize {
+        self.max_frame_size.saturating_sub(CHUNK_HEADER_LEN)
+    }
+
+    /// 一条消息最多可以切成的分片数
+    pub fn max_chunks(&self) -> usize {
+        self.max_message_size.div_ceil(self.chunk_capacity().max(1))
+    }
+
+    /// 检查配置是否可用
+    pub fn validate(&self) -> NetworkResult<()> {
+        if self.max_frame_size <= CHUNK_HEADER_LEN || self.max_frame_size > u32::MAX as usize {
+            return Err(NetworkError::ConfigError(format!(
+                "最大帧长度必须在 {} 到 {} 之间", CHUNK_HEADER_LEN + 1, u32::MAX
+            )));
+        }
+        if self.max_message_size == 0 || self.max_chunks() > u32::MAX as usize {
+            return Err(NetworkError::ConfigError("无效的最大消息长度".to_string()));
+        }
+        if let Compression::Deflate(level) = self.compression {
+            if level > 10 {
+                return Err(NetworkError::ConfigError(format!("无效的压缩等级: {}", level)));
+            }
+        }
+        if self.max_pending_transfers == 0 {
+            return Err(NetworkError::ConfigError("最大并发传输数不能为 0".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// 一个分片帧
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// 传输 ID，同一条消息的所有分片相同
+    pub transfer_id: [u8; 16],
+    /// 分片序号，从 0 开始
+    pub index: u32,
+    /// 分片总数
+    pub total: u32,
+    /// 数据是否经过压缩
+    pub compressed: bool,
+    /// 分片数据
+    pub data: Vec<u8>,
+}
+
+impl Frame {
+    /// 编码为带长度前缀的字节
+    pub fn encode(&self) -> Vec<u8> {
+        let body_len = CHUNK_HEADER_LEN + self.data.len();
+        let mut out = Vec::with_capacity(FRAME_LENGTH_PREFIX + body_len);
+        out.extend_from_slice(&(body_len as u32).to_be_bytes());
+        out.push(if self.compressed { FLAG_COMPRESSED } else { 0 });
+        out.extend_from_slice(&self.transfer_id);
+        out.extend_from_slice(&self.index.to_be_bytes());
+        out.extend_from_slice(&self.total.to_be_bytes());
+        out.extend_from_slice(&self.data);
+        out
+    }
+
+    /// 从不含长度前缀的帧体解码
+    fn decode_body(body: &[u8]) -> NetworkResult<Self> {
+        if body.len() < CHUNK_HEADER_LEN {
+            return Err(NetworkError::ProtocolError(format!("帧长度不足: {} 字节", body.len())));
+        }
+        let flags = body[0];
+        if flags & !FLAG_COMPRESSED != 0 {
+            return Err(NetworkError::ProtocolError(format!("未知的帧标志: {:#04x}", flags)));
+        }
+        let mut transfer_id = [0u8; 16];
+        transfer_id.copy_from_slice(&body[1..17]);
+        Ok(Frame {
+            transfer_id,
+            index: u32::from_be_bytes([body[17], body[18], body[19], body[20]]),
+            total: u32::from_be_bytes([body[21], body[22], body[23], body[24]]),
+            compressed: flags & FLAG_COMPRESSED != 0,
+            data: body[CHUNK_HEADER_LEN..].to_vec(),
+        })
+    }
+}
+
+/// 帧编解码器
+#[derive(Debug, Clone)]
+pub struct FrameCodec {
+    config: FramingConfig,
+}
+
+impl FrameCodec {
+    /// 用给定配置创建编解码器
+    pub fn new(config: FramingConfig) -> NetworkResult<Self> {
+        config.validate()?;
+        Ok(FrameCodec { config })
+    }
+
+    /// 获取分帧配置
+    pub fn config(&self) -> &FramingConfig {
+        &self.config
+    }
+
+    /// 把消息切成分片帧
+    pub fn split(&self, message: &NetworkMessage) -> NetworkResult<Vec<Frame>> {
+        let serialized = message.serialize()?;
+        if serialized.len() > self.config.max_message_size {
+            return Err(NetworkError::ProtocolError(format!(
+                "消息长度 {} 超过上限 {}", serialized.len(), self.config.max_message_size
+            )));
+        }
+
+        let (data, compressed) = match self.config.compression {
+            Compression::Deflate(level) if serialized.len() >= self.config.compression_threshold => {
+                let packed = compress_to_vec(&serialized, level);
+                if packed.len() < serialized.len() {
+                    (packed, true)
+                } else {
+                    (serialized, false)
+                }
+            }
+            _ => (serialized, false),
+        };
+
+        let transfer_id = *Uuid::new_v4().as_bytes();
+        let total = data.len().div_ceil(self.config.chunk_capacity()) as u32;
+        Ok(data
+            .chunks(self.config.chunk_capacity())
+            .enumerate()
+            .map(|(index, chunk)| Frame {
+                transfer_id,
+                index: index as u32,
+                total,
+                compressed,
+                data: chunk.to_vec(),
+            })
+            .collect())
+    }
+
+    /// 把消息编码为一组带长度前缀的帧
+    pub fn encode(&self, message: &NetworkMessage) -> NetworkResult<Vec<Vec<u8>>> {
+        Ok(self.split(message)?.iter().map(Frame::encode).collect())
+    }
+
+    /// 从缓冲区头部读取一帧，返回帧和消耗的字节数；数据不足一帧时返回 `None`
+    pub fn decode(&self, buf: &[u8]) -> NetworkResult<Option<(Frame, usize)>> {
+        if buf.len() < FRAME_LENGTH_PREFIX {
+            return Ok(None);
+        }
+        let len = u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+        if len > self.config.max_frame_size {
+            return Err(NetworkError::ProtocolError(format!(
+                "帧长度 {} 超过上限 {}", len, self.config.max_frame_size
+            )));
+        }
+        let end = FRAME_LENGTH_PREFIX + len;
+        if buf.len() < end {
+            return Ok(None);
+        }
+        Ok(Some((Frame::decode_body(&buf[FRAME_LENGTH_PREFIX..end])?, end)))
+    }
+}
+
+/// 正在重组的传输
+#[derive(Debug)]
+struct PendingTransfer {
+    total: u32,
+    compressed: bool,
+    bytes: usize,
+    chunks: BTreeMap<u32, Vec<u8>>,
+}
+
+/// 分片重组器
+///
+/// 接收来自同一个连接的字节流或分片帧，重组出完整的消息。
+/// 任何错误都说明对端违反了分帧协议，调用方应丢弃该连接的重组器。
+#[derive(Debug)]
+pub struct FrameAssembler {
+    codec: FrameCodec,
+    buffer: Vec<u8>,
+    pending: HashMap<[u8; 16], PendingTransfer>,
+}
+
+impl FrameAssembler {
+    /// 创建重组器
+    pub fn new(codec: FrameCodec) -> Self {
+        FrameAssembler {
+            codec,
+            buffer: Vec::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// 正在重组的传输数
+    pub fn pending_transfers(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// 缓冲区中尚未组成完整帧的字节数
+    pub fn buffered_bytes(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// 放弃一个未完成的传输
+    pub fn discard(&mut self, transfer_id: &[u8; 16]) -> bool {
+        self.pending.remove(transfer_id).is_some()
+    }
+
+    /// 追加字节流数据，返回其中重组完成的消息
+    pub fn push_bytes(&mut self, data: &[u8]) -> NetworkResult<Vec<NetworkMessage>> {
+        self.buffer.extend_from_slice(data);
+
+        let mut messages = Vec::new();
+        let mut consumed = 0;
+        loop {
+            let frame = match self.codec.decode(&self.buffer[consumed..]) {
+                Ok(Some((frame, used))) => {
+                    consumed += used;
+                    frame
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    self.buffer.clear();
+                    return Err(e);
+                }
+            };
+            if let Some(message) = self.push(frame)? {
+                messages.push(message);
+            }
+        }
+        self.buffer.drain(..consumed);
+        Ok(messages)
+    }
+
+    /// 加入一个分片帧，消息的所有分片到齐后返回重组的消息
+    pub fn push(&mut self, frame: Frame) -> NetworkResult<Option<NetworkMessage>> {
+        let config = self.codec.config();
+        if frame.index >= frame.total {
+            return Err(NetworkError::ProtocolError(format!("无效的分片序号: {}/{}", frame.index, frame.total)));
+        }
+        if frame.total as usize > config.max_chunks() {
+            return Err(NetworkError::ProtocolError(format!("分片总数 {} 超过上限 {}", frame.total, config.max_chunks())));
+        }
+        if frame.total == 1 {
+            return self.finish(frame.compressed, frame.data).map(Some);
+        }
+        if !self.pending.contains_key(&frame.transfer_id) && self.pending.len() >= config.max_pending_transfers {
+            return Err(NetworkError::ProtocolError("同时进行的分片传输过多".to_string()));
+        }
+
+        let max_message_size = config.max_message_size;
+        let transfer = self.pending.entry(frame.transfer_id).or_insert_with(|| PendingTransfer {
+            total: frame.total,
+            compressed: frame.compressed,
+            bytes: 0,
+            chunks: BTreeMap::new(),
+        });
+        let error = if transfer.total != frame.total || transfer.compressed != frame.compressed {
+            Some("分片头与所属传输不一致".to_string())
+        } else if transfer.chunks.contains_key(&frame.index) {
+            Some(format!("重复的分片: {}", frame.index))
+        } else if transfer.bytes + frame.data.len() > max_message_size {
+            Some(format!("消息长度超过上限 {}", max_message_size))
+        } else {
+            transfer.bytes += frame.data.len();
+            transfer.chunks.insert(frame.index, frame.data);
+            None
+        };
+        if let Some(error) = error {
+            self.pending.remove(&frame.transfer_id);
+            return Err(NetworkError::ProtocolError(error));
+        }
+
+        let complete = self.pending
+            .get(&frame.transfer_id)
+            .is_some_and(|transfer| transfer.chunks.len() == transfer.total as usize);
+        if !complete {
+            return Ok(None);
+        }
+
+        match self.pending.remove(&frame.transfer_id) {
+            Some(transfer) => {
+                let data = transfer.chunks.into_values().flatten().collect();
+                self.finish(transfer.compressed, data).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// 解压并反序列化重组后的数据
+    fn finish(&self, compressed: bool, data: Vec<u8>) -> NetworkResult<NetworkMessage> {
+        let serialized = if compressed {
+            decompress_to_vec_with_limit(&data, self.codec.config().max_message_size)
+                .map_err(|e| NetworkError::DeserializationError(format!("解压失败: {}", e)))?
+        } else {
+            data
+        };
+        NetworkMessage::deserialize(&serialized)
+    }
+}
@@ -0,0 +1,280 @@
+//! # 同步轮次协调 (Synchronous Round Coordination)
+//!
+//! MPC 协议按轮执行：每一轮每个参与方发送一条消息，收齐之后才能进入下一轮。
+//! `RoundCoordinator` 在消息收发之上维护这一抽象：
+//!
+//! - 按 `mpc-round` 消息头把消息归入轮次，每个参与方每轮只接受一条消息
+//! - 下一轮的消息提前到达时先缓存，本轮结束后自动并入
+//! - 每轮有独立的超时时间；收齐全部消息时立即结束本轮，超时时只要收到至少 `threshold`
+//!   条消息也结束本轮（t-of-n），并在 `RoundOutcome::missing` 中报告缺席的参与方
+//! - 本轮已结束后才到达的消息作为掉队消息丢弃，各参与方的缺席轮数可通过 `missed_rounds` 查询
+//!
+//! 超时判断使用调用方传入的时间（`poll_at`），便于在测试和模拟中驱动；
+//! `collect` 则从异步通道接收消息，直到本轮结束。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::network::protocol::*;
+//! use std::time::{Duration, Instant};
+//!
+//! # fn main() -> mpc_api::network::NetworkResult<()> {
+//! let parties = ["p0", "p1", "p2"].map(String::from);
+//! let mut rounds = RoundCoordinator::new(parties, 2, Duration::from_millis(500))?;
+//!
+//! rounds.submit("p0", NetworkMessage::new("mpc_protocol", b"share-0").with_round(1))?;
+//! rounds.submit("p2", NetworkMessage::new("mpc_protocol", b"share-2").with_round(1))?;
+//! assert!(rounds.poll()?.is_none());
+//!
+//! // p1 掉队：超时后以 2-of-3 结束本轮
+//! let outcome = rounds.poll_at(Instant::now() + Duration::from_secs(1))?.unwrap();
+//! assert_eq!(outcome.missing, vec!["p1".to_string()]);
+//! assert_eq!(outcome.messages.len(), 2);
+//! assert_eq!(rounds.round(), 2);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc;
+use crate::network::common::{NetworkError, NetworkResult};
+use super::NetworkMessage;
+
+/// 允许提前到达的最大轮数
+pub const DEFAULT_MAX_ROUNDS_AHEAD: u64 = 1;
+
+/// 提交消息的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Submission {
+    /// 计入当前轮
+    Accepted,
+    /// 属于之后的轮次，已缓存
+    Buffered,
+    /// 所属轮次已经结束，消息被丢弃
+    Late,
+}
+
+/// 一轮结束时的结果
+#[derive(Debug, Clone)]
+pub struct RoundOutcome {
+    /// 轮次
+    pub round: u64,
+    /// 每个参与方本轮的消息
+    pub messages: BTreeMap<String, NetworkMessage>,
+    /// 本轮缺席的参与方
+    pub missing: Vec<String>,
+    /// 本轮是否因超时结束
+    pub timed_out: bool,
+    /// 本轮耗时
+    pub elapsed: Duration,
+}
+
+impl RoundOutcome {
+    /// 是否收齐了全部参与方的消息
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// 同步轮次协调器
+#[derive(Debug)]
+pub struct RoundCoordinator {
+    /// 每轮需要发送消息的参与方
+    parties: BTreeSet<String>,
+    /// 超时后继续执行所需的最少消息数
+    threshold: usize,
+    /// 每轮的超时时间
+    round_timeout: Duration,
+    /// 允许提前到达的最大轮数
+    max_rounds_ahead: u64,
+    /// 当前轮次，从 1 开始
+    round: u64,
+    /// 当前轮开始的时间
+    started_at: Instant,
+    /// 当前轮已收到的消息
+    received: BTreeMap<String, NetworkMessage>,
+    /// 提前到达的消息
+    buffered: BTreeMap<u64, BTreeMap<String, NetworkMessage>>,
+    /// 每个参与方缺席的轮数
+    missed: HashMap<String, u64>,
+}
+
+impl RoundCoordinator {
+    /// 创建协调器，第 1 轮立即开始计时
+    ///
+    /// # 参数
+    /// - `parties`: 每轮需要发送消息的参与方
+    /// - `threshold`: 超时后继续执行所需的最少消息数，取值 1 到参与方数
+    /// - `round_timeout`: 每轮的超时时间
+    pub fn new(
+        parties: impl IntoIterator<Item = String>,
+        threshold: usize,
+        round_timeout: Duration,
+    ) -> NetworkResult<Self> {
+        let parties: BTreeSet<String> = parties.into_iter().collect();
+        if parties.is_empty() {
+            return Err(NetworkError::ConfigError("参与方列表不能为空".to_string()));
+        }
+        if threshold == 0 || threshold > parties.len() {
+            return Err(NetworkError::ConfigError(format!(
+                "门限 {} 必须在 1 到参与方数 {} 之间", threshold, parties.len()
+            )));
+        }
+
+        Ok(RoundCoordinator {
+            parties,
+            threshold,
+            round_timeout,
+            max_rounds_ahead: DEFAULT_MAX_ROUNDS_AHEAD,
+            round: 1,
+            started_at: Instant::now(),
+            received: BTreeMap::new(),
+            buffered: BTreeMap::new(),
+            missed: HashMap::new(),
+        })
+    }
+
+    /// 设置允许提前到达的最大轮数
+    pub fn with_max_rounds_ahead(mut self, rounds: u64) -> Self {
+        self.max_rounds_ahead = rounds;
+        self
+    }
+
+    /// 当前轮次
+    pub fn round(&self) -> u64 {
+        self.round
+    }
+
+    /// 超时后继续执行所需的最少消息数
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// 当前轮的截止时间
+    pub fn deadline(&self) -> Instant {
+        self.started_at + self.round_timeout
+    }
+
+    /// 当前轮已收到消息的参与方数
+    pub fn received_count(&self) -> usize {
+        self.received.len()
+    }
+
+    /// 当前轮尚未发送消息的参与方
+    pub fn missing(&self) -> Vec<String> {
+        self.parties
+            .iter()
+            .filter(|party| !self.received.contains_key(*party))
+            .cloned()
+            .collect()
+    }
+
+    /// 参与方累计缺席的轮数
+    pub fn missed_rounds(&self, party: &str) -> u64 {
+        self.missed.get(party).copied().unwrap_or(0)
+    }
+
+    /// 提交一条消息，轮次取自 `mpc-round` 消息头
+    ///
+    /// 非参与方的消息、没有轮次的消息、同一参与方同一轮的重复消息和超前太多的消息返回协议错误。
+    pub fn submit(&mut self, from: &str, message: NetworkMessage) -> NetworkResult<Submission> {
+        if !self.parties.contains(from) {
+            return Err(NetworkError::ProtocolError(format!("{} 不是本协议的参与方", from)));
+        }
+        let round = message
+            .round()?
+            .ok_or_else(|| NetworkError::ProtocolError("消息缺少轮次消息头".to_string()))?;
+
+        if round < self.round {
+            return Ok(Submission::Late);
+        }
+        if round > self.round + self.max_rounds_ahead {
+            return Err(NetworkError::ProtocolError(format!(
+                "来自 {} 的第 {} 轮消息超前当前轮次 {} 太多", from, round, self.round
+            )));
+        }
+
+        let (slot, submission) = if round == self.round {
+            (&mut self.received, Submission::Accepted)
+        } else {
+            (self.buffered.entry(round).or_default(), Submission::Buffered)
+        };
+        if slot.contains_key(from) {
+            return Err(NetworkError::ProtocolError(format!("{} 在第 {} 轮重复发送消息", from, round)));
+        }
+        slot.insert(from.to_string(), message);
+        Ok(submission)
+    }
+
+    /// 用当前时间检查本轮是否可以结束
+    pub fn poll(&mut self) -> NetworkResult<Option<RoundOutcome>> {
+        self.poll_at(Instant::now())
+    }
+
+    /// 检查本轮是否可以结束
+    ///
+    /// 收齐全部消息时结束本轮；到达截止时间时，收到的消息不少于门限则结束本轮并报告缺席的参与方，
+    /// 否则返回协议错误，协调器停留在当前轮，调用方可以继续等待或放弃协议。
+    /// 尚未到达截止时间且未收齐时返回 `None`。
+    pub fn poll_at(&mut self, now: Instant) -> NetworkResult<Option<RoundOutcome>> {
+        let timed_out = now >= self.deadline();
+        if self.received.len() < self.parties.len() {
+            if !timed_out {
+                return Ok(None);
+            }
+            if self.received.len() < self.threshold {
+                return Err(NetworkError::ProtocolError(format!(
+                    "第 {} 轮超时，只收到 {}/{} 条消息，缺少: {}",
+                    self.round,
+                    self.received.len(),
+                    self.threshold,
+                    self.missing().join(", ")
+                )));
+            }
+        }
+
+        let missing = self.missing();
+        for party in &missing {
+            *self.missed.entry(party.clone()).or_insert(0) += 1;
+        }
+        let outcome = RoundOutcome {
+            round: self.round,
+            messages: std::mem::take(&mut self.received),
+            timed_out: !missing.is_empty(),
+            missing,
+            elapsed: now.saturating_duration_since(self.started_at),
+        };
+
+        self.round += 1;
+        self.started_at = now;
+        self.received = self.buffered.remove(&self.round).unwrap_or_default();
+        Ok(Some(outcome))
+    }
+
+    /// 从通道接收 `(发送方, 消息)`，直到本轮结束
+    ///
+    /// 无效的消息（非参与方、重复、超前太多）会被丢弃，不影响本轮；
+    /// 通道关闭时返回通道错误。
+    pub async fn collect(
+        &mut self,
+        incoming: &mut mpsc::UnboundedReceiver<(String, NetworkMessage)>,
+    ) -> NetworkResult<RoundOutcome> {
+        loop {
+            if let Some(outcome) = self.poll()? {
+                return Ok(outcome);
+            }
+
+            let wait = self.deadline().saturating_duration_since(Instant::now());
+            match tokio::time::timeout(wait, incoming.recv()).await {
+                Ok(Some((from, message))) => {
+                    let _ = self.submit(&from, message);
+                }
+                Ok(None) => return Err(NetworkError::ChannelError("消息通道已关闭".to_string())),
+                Err(_) => {}
+            }
+        }
+    }
+}
@@ -2,10 +2,50 @@
 //!
 //! 本模块提供网络通信的安全功能，包括 TLS/SSL 加密、身份认证、
 //! 数字签名验证和安全密钥管理等功能。
+//!
+//! ## 信道密钥轮换
+//!
+//! 长期运行的信道在发送一定数量的消息或字节后自动轮换 AEAD 密钥
+//! （`RekeyPolicy`）。新密钥由旧密钥单向派生，旧密钥随即被安全清零，
+//! 因此泄露当前密钥不会暴露之前纪元的流量。
+//!
+//! ## 委员会签发的证书
+//!
+//! 除了逐个添加的受信任证书，还可以通过 `trust_committee` 信任一个 MPC 委员会
+//! （见 `network::onboarding`）：该委员会门限签发的节点证书无需预先添加即可通过验证。
+//!
+//! ## 限流与配额
+//!
+//! `RateLimiter` 按 `SecurityPolicy::rate_limit` 为每个对端维护消息数和字节数两个令牌桶。
+//! `P2PNode` 和 `HttpServer` 在处理消息或请求之前调用 `check_rate_limit`，
+//! 超限的消息被拒绝（`NetworkError::RateLimited`），并向审计日志记录一条拒绝服务事件；
+//! 同一对端连续被拒绝时只记录第一次，直到它的消息再次被接受。
+//!
+//! ## 认证加密信道
+//!
+//! `handshake` 子模块实现 Noise XX 风格的握手：双方用 X25519 静态密钥证明各自的参与方身份，
+//! 并与 `PartyDirectory` 中登记的公钥比对，握手完成后得到每个连接独立的 AEAD 密钥。
 
-use std::{collections::HashMap, path::PathBuf, time::SystemTime};
+pub mod handshake;
+
+pub use handshake::*;
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Instant, SystemTime},
+};
 use serde::{Deserialize, Serialize};
-use crate::network::common::NetworkResult;
+use crate::network::common::{NetworkError, NetworkResult};
+use crate::network::onboarding::{CommitteeTrustAnchor, NodeCertificate};
+use crate::elliptic_curve::curve25519::PublicKey;
+use crate::security::{AuditLogger, RateLimitPolicy, SecurityEvent, SecurityLevel, SecurityPolicy, ThreatType};
+use crate::utils::canonical_encode;
+use crate::utils::memory::{secure_zero, SecretValue};
+
+/// 信道密钥派生的上下文字符串
+const CHANNEL_REKEY_CONTEXT: &str = "mpc_api 2024 network channel rekey";
 
 /// TLS 配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +112,311 @@ pub struct NetworkSecurity {
     trusted_certs: HashMap<String, Certificate>,
     /// 已撤销的证书
     revoked_certs: HashMap<String, SystemTime>,
+    /// 受信任的证书委员会
+    trusted_committees: HashMap<String, CommitteeTrustAnchor>,
+    /// 信道密钥轮换策略
+    rekey_policy: RekeyPolicy,
+    /// 每个对端的信道密钥
+    channel_keys: HashMap<String, ChannelKeySchedule>,
+    /// 每个对端的限流配额
+    rate_limiter: RateLimiter,
+    /// 已知参与方的静态公钥
+    party_directory: PartyDirectory,
+}
+
+/// 密钥轮换策略
+///
+/// 达到任意一个上限时轮换信道密钥。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RekeyPolicy {
+    /// 单个密钥最多保护的消息数量
+    pub max_messages: u64,
+    /// 单个密钥最多保护的字节数
+    pub max_bytes: u64,
+}
+
+impl Default for RekeyPolicy {
+    fn default() -> Self {
+        RekeyPolicy {
+            max_messages: 1 << 20,
+            max_bytes: 1 << 32,
+        }
+    }
+}
+
+/// 信道密钥调度
+///
+/// 持有当前纪元的 AEAD 密钥并统计其使用量，超过策略上限后
+/// 由旧密钥单向派生新密钥并清零旧密钥。
+#[derive(Debug)]
+pub struct ChannelKeySchedule {
+    /// 当前密钥
+    key: [u8; 32],
+    /// 当前密钥纪元
+    epoch: u64,
+    /// 当前密钥已保护的消息数量
+    messages: u64,
+    /// 当前密钥已保护的字节数
+    bytes: u64,
+    /// 轮换策略
+    policy: RekeyPolicy,
+}
+
+impl ChannelKeySchedule {
+    /// 使用握手得到的初始密钥创建密钥调度
+    pub fn new(initial_key: [u8; 32], policy: RekeyPolicy) -> Self {
+        ChannelKeySchedule {
+            key: initial_key,
+            epoch: 0,
+            messages: 0,
+            bytes: 0,
+            policy,
+        }
+    }
+
+    /// 当前密钥
+    pub fn current_key(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    /// 当前密钥纪元
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// 当前密钥是否已达到策略上限
+    pub fn needs_rekey(&self) -> bool {
+        self.messages >= self.policy.max_messages || self.bytes >= self.policy.max_bytes
+    }
+
+    /// 为一条待发送（或已接收）的消息取得密钥
+    ///
+    /// 如果当前密钥已达到上限，先轮换再返回新密钥。
+    /// 通信双方以相同顺序调用此方法即可保持密钥同步。
+    ///
+    /// # 返回值
+    ///
+    /// 返回 (密钥纪元, 密钥)，密钥在释放时清零
+    pub fn key_for_message(&mut self, len: usize) -> (u64, SecretValue<[u8; 32]>) {
+        if self.needs_rekey() {
+            self.rotate();
+        }
+        self.messages += 1;
+        self.bytes += len as u64;
+        (self.epoch, SecretValue::new(self.key))
+    }
+
+    /// 立即轮换密钥
+    pub fn rotate(&mut self) {
+        let next = blake3::derive_key(CHANNEL_REKEY_CONTEXT, &self.key);
+        secure_zero(self.key.as_mut_ptr(), self.key.len());
+        self.key = next;
+        self.epoch += 1;
+        self.messages = 0;
+        self.bytes = 0;
+    }
+}
+
+impl Drop for ChannelKeySchedule {
+    fn drop(&mut self) {
+        secure_zero(self.key.as_mut_ptr(), self.key.len());
+    }
+}
+
+/// 令牌桶
+///
+/// 令牌以 `rate` 每秒的速度补充，最多积累 `capacity` 个。
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    /// 每秒补充的令牌数
+    rate: f64,
+    /// 桶容量
+    capacity: f64,
+    /// 当前令牌数
+    tokens: f64,
+    /// 上次补充的时间
+    updated: Instant,
+}
+
+impl TokenBucket {
+    /// 创建装满令牌的桶
+    pub fn new(rate: f64, capacity: f64) -> Self {
+        Self { rate, capacity, tokens: capacity, updated: Instant::now() }
+    }
+
+    /// `now` 时刻可用的令牌数
+    pub fn available(&mut self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.updated = self.updated.max(now);
+        self.tokens
+    }
+
+    /// 令牌足够时取出 `amount` 个并返回 `true`，否则不取出任何令牌
+    pub fn try_take(&mut self, amount: f64, now: Instant) -> bool {
+        if self.available(now) < amount {
+            return false;
+        }
+        self.tokens -= amount;
+        true
+    }
+}
+
+/// 对端的配额使用情况
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerUsage {
+    /// 接受的消息数
+    pub accepted_messages: u64,
+    /// 接受的字节数
+    pub accepted_bytes: u64,
+    /// 拒绝的消息数
+    pub rejected_messages: u64,
+    /// 拒绝的字节数
+    pub rejected_bytes: u64,
+}
+
+/// 单个对端的令牌桶和使用情况
+#[derive(Debug)]
+struct PeerQuota {
+    messages: Option<TokenBucket>,
+    bytes: Option<TokenBucket>,
+    usage: PeerUsage,
+    last_seen: Instant,
+    /// 最近一条消息是否被拒绝，用于只记录连续拒绝中的第一次
+    throttled: bool,
+}
+
+impl PeerQuota {
+    fn new(policy: &RateLimitPolicy, now: Instant) -> Self {
+        let bucket = |rate: u64, burst: u64| (rate > 0).then(|| TokenBucket::new(rate as f64, burst.max(1) as f64));
+        Self {
+            messages: bucket(policy.messages_per_second as u64, policy.message_burst as u64),
+            bytes: bucket(policy.bytes_per_second, policy.byte_burst),
+            usage: PeerUsage::default(),
+            last_seen: now,
+            throttled: false,
+        }
+    }
+}
+
+/// 按对端限流并统计配额使用情况
+///
+/// 长于字节突发上限的消息永远不会被接受，因此 `byte_burst` 同时也是单条消息的长度上限。
+#[derive(Debug)]
+pub struct RateLimiter {
+    policy: Mutex<RateLimitPolicy>,
+    peers: Mutex<HashMap<String, PeerQuota>>,
+    audit_logger: Mutex<Arc<AuditLogger>>,
+}
+
+impl RateLimiter {
+    /// 使用给定策略和独立的审计日志创建限流器
+    pub fn new(policy: RateLimitPolicy) -> Self {
+        Self {
+            policy: Mutex::new(policy),
+            peers: Mutex::new(HashMap::new()),
+            audit_logger: Mutex::new(Arc::new(AuditLogger::new(SecurityPolicy::default()))),
+        }
+    }
+
+    /// 当前的限流策略
+    pub fn policy(&self) -> RateLimitPolicy {
+        self.policy.lock().unwrap().clone()
+    }
+
+    /// 替换限流策略，所有对端的令牌桶按新策略重新装满，使用统计保留
+    pub fn set_policy(&self, policy: RateLimitPolicy) {
+        let now = Instant::now();
+        let mut peers = self.peers.lock().unwrap();
+        for quota in peers.values_mut() {
+            let usage = quota.usage;
+            *quota = PeerQuota::new(&policy, now);
+            quota.usage = usage;
+        }
+        *self.policy.lock().unwrap() = policy;
+    }
+
+    /// 使用共享的审计日志记录违规事件
+    pub fn set_audit_logger(&self, audit_logger: Arc<AuditLogger>) {
+        *self.audit_logger.lock().unwrap() = audit_logger;
+    }
+
+    /// 获取审计日志
+    pub fn audit_logger(&self) -> Arc<AuditLogger> {
+        Arc::clone(&self.audit_logger.lock().unwrap())
+    }
+
+    /// 检查对端是否还能发送一条 `bytes` 字节的消息，可以时扣除相应配额
+    pub fn check(&self, peer_id: &str, bytes: usize) -> NetworkResult<()> {
+        let policy = self.policy();
+        if policy.messages_per_second == 0 && policy.bytes_per_second == 0 {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let ((limit, limit_name), first_violation) = {
+            let mut peers = self.peers.lock().unwrap();
+            if !peers.contains_key(peer_id) && peers.len() >= policy.max_tracked_peers.max(1) {
+                // 淘汰最久未活动的对端
+                if let Some(idle) = peers.iter().min_by_key(|(_, quota)| quota.last_seen).map(|(id, _)| id.clone()) {
+                    peers.remove(&idle);
+                }
+            }
+            let quota = peers.entry(peer_id.to_string()).or_insert_with(|| PeerQuota::new(&policy, now));
+            quota.last_seen = now;
+
+            let message_ok = quota.messages.as_mut().is_none_or(|bucket| bucket.available(now) >= 1.0);
+            let bytes_ok = quota.bytes.as_mut().is_none_or(|bucket| bucket.available(now) >= bytes as f64);
+            if message_ok && bytes_ok {
+                if let Some(bucket) = quota.messages.as_mut() {
+                    bucket.try_take(1.0, now);
+                }
+                if let Some(bucket) = quota.bytes.as_mut() {
+                    bucket.try_take(bytes as f64, now);
+                }
+                quota.usage.accepted_messages += 1;
+                quota.usage.accepted_bytes += bytes as u64;
+                quota.throttled = false;
+                return Ok(());
+            }
+
+            quota.usage.rejected_messages += 1;
+            quota.usage.rejected_bytes += bytes as u64;
+            let first_violation = !quota.throttled;
+            quota.throttled = true;
+            (if message_ok { ("bytes", "字节") } else { ("messages", "消息") }, first_violation)
+        };
+
+        if first_violation {
+            let event = SecurityEvent::new(
+                ThreatType::DenialOfService,
+                SecurityLevel::Medium,
+                format!("peer {} exceeded the {} rate limit", peer_id, limit),
+            )
+            .with_peer(peer_id)
+            .with_context("limit".to_string(), limit.to_string())
+            .with_context("message_bytes".to_string(), bytes.to_string())
+            .with_mitigation("message dropped".to_string());
+            // 审计日志写入失败不改变限流结果
+            let _ = self.audit_logger().log_event(event);
+        }
+        Err(NetworkError::RateLimited(format!("对端 {} 超出{}速率限制", peer_id, limit_name)))
+    }
+
+    /// 对端的配额使用情况，未跟踪的对端返回 `None`
+    pub fn usage(&self, peer_id: &str) -> Option<PeerUsage> {
+        self.peers.lock().unwrap().get(peer_id).map(|quota| quota.usage)
+    }
+
+    /// 当前跟踪的对端数量
+    pub fn tracked_peers(&self) -> usize {
+        self.peers.lock().unwrap().len()
+    }
+
+    /// 清除对端的配额记录，下一条消息按装满的令牌桶重新计算
+    pub fn reset_peer(&self, peer_id: &str) {
+        self.peers.lock().unwrap().remove(peer_id);
+    }
 }
 
 /// 数字证书
@@ -101,9 +446,91 @@ impl NetworkSecurity {
             auth_config: None,
             trusted_certs: HashMap::new(),
             revoked_certs: HashMap::new(),
+            trusted_committees: HashMap::new(),
+            rekey_policy: RekeyPolicy::default(),
+            channel_keys: HashMap::new(),
+            rate_limiter: RateLimiter::new(RateLimitPolicy::default()),
+            party_directory: PartyDirectory::new(),
         })
     }
 
+    /// 设置信道密钥轮换策略
+    pub fn with_rekey_policy(mut self, policy: RekeyPolicy) -> Self {
+        self.rekey_policy = policy;
+        self
+    }
+
+    /// 设置限流策略
+    pub fn with_rate_limit_policy(self, policy: RateLimitPolicy) -> Self {
+        self.rate_limiter.set_policy(policy);
+        self
+    }
+
+    /// 获取限流器
+    pub fn rate_limiter(&self) -> &RateLimiter {
+        &self.rate_limiter
+    }
+
+    /// 处理来自对端的一条 `bytes` 字节的消息之前检查限流配额
+    pub fn check_rate_limit(&self, peer_id: &str, bytes: usize) -> NetworkResult<()> {
+        self.rate_limiter.check(peer_id, bytes)
+    }
+
+    /// 为对端建立信道密钥
+    pub fn establish_channel_key(&mut self, peer_id: &str, initial_key: [u8; 32]) {
+        self.channel_keys.insert(
+            peer_id.to_string(),
+            ChannelKeySchedule::new(initial_key, self.rekey_policy),
+        );
+    }
+
+    /// 取得与对端通信的下一条消息所用的密钥，必要时自动轮换
+    ///
+    /// # 返回值
+    ///
+    /// 返回 (密钥纪元, 密钥)；未建立信道时返回 `None`
+    pub fn channel_key_for_message(&mut self, peer_id: &str, len: usize) -> Option<(u64, SecretValue<[u8; 32]>)> {
+        self.channel_keys.get_mut(peer_id).map(|schedule| schedule.key_for_message(len))
+    }
+
+    /// 立即轮换与对端的信道密钥（例如收到对端的轮换请求时）
+    pub fn rotate_channel_key(&mut self, peer_id: &str) -> Option<u64> {
+        self.channel_keys.get_mut(peer_id).map(|schedule| {
+            schedule.rotate();
+            schedule.epoch()
+        })
+    }
+
+    /// 登记参与方的静态公钥，握手时用于认证对端身份
+    pub fn register_party_key(&mut self, party_id: &str, public_key: PublicKey) {
+        self.party_directory.insert(party_id, public_key);
+    }
+
+    /// 替换整个参与方公钥目录
+    pub fn set_party_directory(&mut self, directory: PartyDirectory) {
+        self.party_directory = directory;
+    }
+
+    /// 已登记的参与方公钥
+    pub fn party_directory(&self) -> &PartyDirectory {
+        &self.party_directory
+    }
+
+    /// 以本方身份发起握手，信道使用本管理器的密钥轮换策略
+    pub fn initiate_handshake(&self, local: PartyIdentity) -> Handshake {
+        Handshake::initiator(local, self.party_directory.clone()).with_rekey_policy(self.rekey_policy)
+    }
+
+    /// 以本方身份响应握手，信道使用本管理器的密钥轮换策略
+    pub fn accept_handshake(&self, local: PartyIdentity) -> Handshake {
+        Handshake::responder(local, self.party_directory.clone()).with_rekey_policy(self.rekey_policy)
+    }
+
+    /// 关闭与对端的信道并清零密钥
+    pub fn close_channel(&mut self, peer_id: &str) {
+        self.channel_keys.remove(peer_id);
+    }
+
     /// 验证证书
     pub fn verify_certificate(&self, cert: &Certificate) -> NetworkResult<bool> {
         // 检查证书是否被撤销
@@ -118,7 +545,36 @@ impl NetworkSecurity {
         }
 
         // 检查是否为受信任证书
-        Ok(self.trusted_certs.contains_key(&cert.id))
+        if self.trusted_certs.contains_key(&cert.id) {
+            return Ok(true);
+        }
+
+        // 检查是否为受信任委员会签发的证书
+        Ok(self.trusted_committees.get(&cert.issuer)
+            .is_some_and(|anchor| Self::verify_committee_certificate(anchor, cert, now)))
+    }
+
+    /// 信任委员会签发的证书
+    pub fn trust_committee(&mut self, anchor: CommitteeTrustAnchor) {
+        self.trusted_committees.insert(anchor.committee_id.clone(), anchor);
+    }
+
+    /// 不再信任委员会签发的证书
+    pub fn distrust_committee(&mut self, committee_id: &str) {
+        self.trusted_committees.remove(committee_id);
+    }
+
+    /// 检查委员会签名，并确认证书字段与签名内容一致
+    fn verify_committee_certificate(anchor: &CommitteeTrustAnchor, cert: &Certificate, now: SystemTime) -> bool {
+        let Ok(node_certificate) = NodeCertificate::from_certificate(cert) else {
+            return false;
+        };
+        let Ok(body) = anchor.verify(&node_certificate, now) else {
+            return false;
+        };
+        body.serial == cert.id
+            && body.node_id == cert.subject
+            && canonical_encode(&body.public_key).is_ok_and(|key| key == cert.public_key)
     }
 
     /// 添加受信任证书
@@ -0,0 +1,528 @@
+//! # 认证加密信道握手 (Authenticated Channel Handshake)
+//!
+//! MPC 参与方之间的 P2P 连接需要双向认证，并且认证结果要绑定到参与方身份。
+//! 本模块实现 Noise XX 模式的三条消息握手：
+//!
+//! ```text
+//! -> e
+//! <- e, ee, s, es, 参与方 ID
+//! -> s, se, 参与方 ID
+//! ```
+//!
+//! - 密钥交换使用 X25519（`Curve25519ECDH`），链式密钥用 HKDF-SHA256 更新
+//! - 握手中的静态公钥和参与方 ID 用 `SivAead` 加密，关联数据为当前握手哈希
+//! - 双方把对方的参与方 ID 和静态公钥与 `PartyDirectory` 中登记的公钥比对，不一致时握手失败
+//! - 握手完成后得到两个方向各自独立的密钥，`SecureChannel` 按 `RekeyPolicy` 在达到上限后轮换
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::network::security::*;
+//!
+//! # fn main() -> mpc_api::network::NetworkResult<()> {
+//! let alice = PartyIdentity::generate("party-0");
+//! let bob = PartyIdentity::generate("party-1");
+//! let mut directory = PartyDirectory::new();
+//! directory.insert("party-0", alice.public_key());
+//! directory.insert("party-1", bob.public_key());
+//!
+//! let mut initiator = Handshake::initiator(alice, directory.clone());
+//! let mut responder = Handshake::responder(bob, directory);
+//! responder.read_message(&initiator.write_message()?)?;
+//! initiator.read_message(&responder.write_message()?)?;
+//! responder.read_message(&initiator.write_message()?)?;
+//!
+//! let mut to_bob = initiator.into_channel()?;
+//! let mut from_alice = responder.into_channel()?;
+//! assert_eq!(from_alice.peer_party(), "party-0");
+//! assert_eq!(from_alice.open(&to_bob.seal(b"share"))?, b"share");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use crate::authentication::{SivAead, SivCiphertext};
+use crate::elliptic_curve::curve25519::{Curve25519ECDH, KeyPair, PublicKey};
+use crate::network::common::{NetworkError, NetworkResult};
+use crate::security::{hkdf_expand, hkdf_extract, SecretKey};
+use crate::utils::memory::secure_zero;
+use super::{ChannelKeySchedule, RekeyPolicy};
+
+/// 握手协议名称，作为握手哈希的初始值
+pub const HANDSHAKE_PROTOCOL_NAME: &[u8] = b"Noise_XX_25519_SIV-HMAC_SHA256/mpc_api";
+
+/// SIV 认证标签长度
+const TAG_LEN: usize = 16;
+/// X25519 公钥长度
+const KEY_LEN: usize = 32;
+
+/// 参与方的长期身份：参与方 ID 和 X25519 静态密钥对
+#[derive(Debug, Clone)]
+pub struct PartyIdentity {
+    /// 参与方 ID
+    pub party_id: String,
+    /// 静态密钥对
+    keypair: KeyPair,
+}
+
+impl PartyIdentity {
+    /// 使用已有的静态密钥对
+    pub fn new(party_id: &str, keypair: KeyPair) -> Self {
+        Self { party_id: party_id.to_string(), keypair }
+    }
+
+    /// 生成新的静态密钥对
+    pub fn generate(party_id: &str) -> Self {
+        Self::new(party_id, KeyPair::generate())
+    }
+
+    /// 静态公钥
+    pub fn public_key(&self) -> PublicKey {
+        self.keypair.public_key
+    }
+}
+
+/// 已知参与方的静态公钥
+#[derive(Debug, Clone, Default)]
+pub struct PartyDirectory {
+    keys: HashMap<String, PublicKey>,
+}
+
+impl PartyDirectory {
+    /// 创建空目录
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登记参与方的静态公钥，替换已有的登记
+    pub fn insert(&mut self, party_id: &str, public_key: PublicKey) {
+        self.keys.insert(party_id.to_string(), public_key);
+    }
+
+    /// 删除参与方的登记
+    pub fn remove(&mut self, party_id: &str) {
+        self.keys.remove(party_id);
+    }
+
+    /// 参与方登记的静态公钥
+    pub fn get(&self, party_id: &str) -> Option<&PublicKey> {
+        self.keys.get(party_id)
+    }
+
+    /// 公钥是否为该参与方登记的公钥
+    pub fn verify(&self, party_id: &str, public_key: &PublicKey) -> bool {
+        self.keys.get(party_id) == Some(public_key)
+    }
+}
+
+/// 握手中的角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandshakeRole {
+    /// 发起连接的一方，写第 1、3 条消息
+    Initiator,
+    /// 接受连接的一方，写第 2 条消息
+    Responder,
+}
+
+/// Noise 的对称状态：链式密钥、握手哈希和当前加密密钥
+#[derive(Debug)]
+struct SymmetricState {
+    chaining_key: SecretKey,
+    hash: [u8; 32],
+    key: Option<SecretKey>,
+    nonce: u64,
+}
+
+impl SymmetricState {
+    fn new() -> Self {
+        let hash: [u8; 32] = Sha256::digest(HANDSHAKE_PROTOCOL_NAME).into();
+        Self { chaining_key: SecretKey::from_bytes(hash), hash, key: None, nonce: 0 }
+    }
+
+    fn mix_hash(&mut self, data: &[u8]) {
+        self.hash = Sha256::new().chain_update(self.hash).chain_update(data).finalize().into();
+    }
+
+    /// 由链式密钥和输入密钥材料派生两个 32 字节密钥
+    fn hkdf2(chaining_key: &SecretKey, ikm: &[u8]) -> NetworkResult<(SecretKey, SecretKey)> {
+        let prk = hkdf_extract(chaining_key.as_bytes(), ikm);
+        let mut output = [0u8; 64];
+        hkdf_expand(&prk, b"", &mut output).map_err(|e| NetworkError::Other(e.to_string()))?;
+        let first = SecretKey::from_bytes(output[..32].try_into().unwrap());
+        let second = SecretKey::from_bytes(output[32..].try_into().unwrap());
+        secure_zero(output.as_mut_ptr(), output.len());
+        Ok((first, second))
+    }
+
+    fn mix_key(&mut self, ikm: &[u8]) -> NetworkResult<()> {
+        let (chaining_key, key) = Self::hkdf2(&self.chaining_key, ikm)?;
+        self.chaining_key = chaining_key;
+        self.key = Some(key);
+        self.nonce = 0;
+        Ok(())
+    }
+
+    fn encrypt_and_hash(&mut self, plaintext: &[u8]) -> NetworkResult<Vec<u8>> {
+        let ciphertext = match &self.key {
+            Some(key) => seal(key.as_bytes(), self.nonce, &self.hash, plaintext)?,
+            None => plaintext.to_vec(),
+        };
+        if self.key.is_some() {
+            self.nonce += 1;
+        }
+        self.mix_hash(&ciphertext);
+        Ok(ciphertext)
+    }
+
+    fn decrypt_and_hash(&mut self, ciphertext: &[u8]) -> NetworkResult<Vec<u8>> {
+        let plaintext = match &self.key {
+            Some(key) => open(key.as_bytes(), self.nonce, &self.hash, ciphertext)?,
+            None => ciphertext.to_vec(),
+        };
+        if self.key.is_some() {
+            self.nonce += 1;
+        }
+        self.mix_hash(ciphertext);
+        Ok(plaintext)
+    }
+
+    fn split(&self) -> NetworkResult<(SecretKey, SecretKey)> {
+        Self::hkdf2(&self.chaining_key, b"")
+    }
+}
+
+/// 以 `SivAead` 加密，输出 `合成 IV ‖ 密文`
+fn seal(key: &[u8; 32], nonce: u64, aad: &[u8], plaintext: &[u8]) -> NetworkResult<Vec<u8>> {
+    let aead = SivAead::new(key).map_err(|e| NetworkError::Other(e.to_string()))?;
+    let sealed = aead.seal(&nonce.to_be_bytes(), aad, plaintext);
+    let mut output = Vec::with_capacity(TAG_LEN + sealed.ciphertext.len());
+    output.extend_from_slice(&sealed.siv);
+    output.extend_from_slice(&sealed.ciphertext);
+    Ok(output)
+}
+
+fn open(key: &[u8; 32], nonce: u64, aad: &[u8], data: &[u8]) -> NetworkResult<Vec<u8>> {
+    if data.len() < TAG_LEN {
+        return Err(NetworkError::AuthenticationFailed("密文过短".to_string()));
+    }
+    let aead = SivAead::new(key).map_err(|e| NetworkError::Other(e.to_string()))?;
+    let sealed = SivCiphertext {
+        siv: data[..TAG_LEN].try_into().unwrap(),
+        ciphertext: data[TAG_LEN..].to_vec(),
+    };
+    aead.open(&nonce.to_be_bytes(), aad, &sealed)
+        .map_err(|_| NetworkError::AuthenticationFailed("握手消息认证失败".to_string()))
+}
+
+fn dh(local: &KeyPair, remote: &PublicKey) -> NetworkResult<[u8; 32]> {
+    Curve25519ECDH::key_exchange(&local.private_key, remote)
+        .map_err(|e| NetworkError::AuthenticationFailed(format!("无效的对端公钥: {:?}", e)))
+}
+
+fn read_key(message: &[u8], offset: usize) -> NetworkResult<[u8; KEY_LEN]> {
+    message
+        .get(offset..offset + KEY_LEN)
+        .map(|bytes| bytes.try_into().unwrap())
+        .ok_or_else(|| NetworkError::ProtocolError("握手消息过短".to_string()))
+}
+
+/// Noise XX 握手状态机
+///
+/// 双方交替调用 `write_message` 和 `read_message`，三条消息之后调用 `into_channel`。
+/// 任何一步失败后握手不能继续，需要重新开始。
+#[derive(Debug)]
+pub struct Handshake {
+    role: HandshakeRole,
+    /// 已处理的消息数
+    step: usize,
+    symmetric: SymmetricState,
+    local: PartyIdentity,
+    ephemeral: KeyPair,
+    remote_ephemeral: Option<PublicKey>,
+    /// 已认证的对端参与方 ID 和静态公钥
+    remote: Option<(String, PublicKey)>,
+    directory: PartyDirectory,
+    rekey_policy: RekeyPolicy,
+    failed: bool,
+}
+
+impl Handshake {
+    /// 作为发起方开始握手
+    pub fn initiator(local: PartyIdentity, directory: PartyDirectory) -> Self {
+        Self::new(HandshakeRole::Initiator, local, directory)
+    }
+
+    /// 作为响应方开始握手
+    pub fn responder(local: PartyIdentity, directory: PartyDirectory) -> Self {
+        Self::new(HandshakeRole::Responder, local, directory)
+    }
+
+    fn new(role: HandshakeRole, local: PartyIdentity, directory: PartyDirectory) -> Self {
+        Self {
+            role,
+            step: 0,
+            symmetric: SymmetricState::new(),
+            local,
+            ephemeral: KeyPair::generate(),
+            remote_ephemeral: None,
+            remote: None,
+            directory,
+            rekey_policy: RekeyPolicy::default(),
+            failed: false,
+        }
+    }
+
+    /// 设置握手完成后信道的密钥轮换策略
+    pub fn with_rekey_policy(mut self, policy: RekeyPolicy) -> Self {
+        self.rekey_policy = policy;
+        self
+    }
+
+    /// 写入握手序言（例如会话 ID），双方必须在第一条消息之前写入相同的内容
+    pub fn with_prologue(mut self, prologue: &[u8]) -> Self {
+        self.symmetric.mix_hash(prologue);
+        self
+    }
+
+    /// 握手中的角色
+    pub fn role(&self) -> HandshakeRole {
+        self.role
+    }
+
+    /// 三条消息是否都已处理
+    pub fn is_complete(&self) -> bool {
+        self.step == 3 && !self.failed
+    }
+
+    /// 下一条消息是否由本方写
+    fn is_my_turn(&self) -> bool {
+        // 发起方写第 0、2 条，响应方写第 1 条
+        self.step.is_multiple_of(2) == (self.role == HandshakeRole::Initiator)
+    }
+
+    /// 写出下一条握手消息
+    pub fn write_message(&mut self) -> NetworkResult<Vec<u8>> {
+        if self.failed || self.step >= 3 || !self.is_my_turn() {
+            return Err(NetworkError::ProtocolError("当前不应写入握手消息".to_string()));
+        }
+        let result = self.write_step();
+        self.finish_step(result)
+    }
+
+    /// 处理对端的下一条握手消息
+    pub fn read_message(&mut self, message: &[u8]) -> NetworkResult<()> {
+        if self.failed || self.step >= 3 || self.is_my_turn() {
+            return Err(NetworkError::ProtocolError("当前不应读取握手消息".to_string()));
+        }
+        let result = self.read_step(message);
+        self.finish_step(result)
+    }
+
+    fn finish_step<T>(&mut self, result: NetworkResult<T>) -> NetworkResult<T> {
+        match result {
+            Ok(value) => {
+                self.step += 1;
+                Ok(value)
+            }
+            Err(e) => {
+                self.failed = true;
+                Err(e)
+            }
+        }
+    }
+
+    fn write_step(&mut self) -> NetworkResult<Vec<u8>> {
+        let mut message = Vec::new();
+        match self.step {
+            // -> e
+            0 => {
+                let e = self.ephemeral.public_key.0;
+                self.symmetric.mix_hash(&e);
+                message.extend_from_slice(&e);
+                // 第一条消息未加密，不携带参与方 ID
+                return Ok(message);
+            }
+            // <- e, ee, s, es
+            1 => {
+                let e = self.ephemeral.public_key.0;
+                self.symmetric.mix_hash(&e);
+                message.extend_from_slice(&e);
+                let remote_ephemeral = self.remote_ephemeral()?;
+                self.symmetric.mix_key(&dh(&self.ephemeral, &remote_ephemeral)?)?;
+                message.extend(self.symmetric.encrypt_and_hash(&self.local.public_key().0)?);
+                self.symmetric.mix_key(&dh(&self.local.keypair, &remote_ephemeral)?)?;
+            }
+            // -> s, se
+            _ => {
+                message.extend(self.symmetric.encrypt_and_hash(&self.local.public_key().0)?);
+                let remote_ephemeral = self.remote_ephemeral()?;
+                self.symmetric.mix_key(&dh(&self.local.keypair, &remote_ephemeral)?)?;
+            }
+        }
+        let payload = self.local.party_id.clone();
+        message.extend(self.symmetric.encrypt_and_hash(payload.as_bytes())?);
+        Ok(message)
+    }
+
+    fn read_step(&mut self, message: &[u8]) -> NetworkResult<()> {
+        let too_short = || NetworkError::ProtocolError("握手消息过短".to_string());
+        let static_start = match self.step {
+            // -> e
+            0 => {
+                if message.len() != KEY_LEN {
+                    return Err(NetworkError::ProtocolError("无效的握手消息长度".to_string()));
+                }
+                let e = read_key(message, 0)?;
+                self.symmetric.mix_hash(&e);
+                self.remote_ephemeral = Some(PublicKey(e));
+                return Ok(());
+            }
+            // <- e, ee, s, es
+            1 => {
+                let e = PublicKey(read_key(message, 0)?);
+                self.symmetric.mix_hash(&e.0);
+                self.remote_ephemeral = Some(e);
+                self.symmetric.mix_key(&dh(&self.ephemeral, &e)?)?;
+                KEY_LEN
+            }
+            // -> s, se
+            _ => 0,
+        };
+
+        let encrypted_static = message.get(static_start..static_start + KEY_LEN + TAG_LEN).ok_or_else(too_short)?;
+        let remote_static = PublicKey(read_key(&self.symmetric.decrypt_and_hash(encrypted_static)?, 0)?);
+        // 发起方计算 es，响应方计算 se，都是本方临时密钥与对端静态公钥
+        self.symmetric.mix_key(&dh(&self.ephemeral, &remote_static)?)?;
+        let payload = self.symmetric.decrypt_and_hash(&message[static_start + KEY_LEN + TAG_LEN..])?;
+        self.authenticate_remote(&payload, remote_static)
+    }
+
+    fn remote_ephemeral(&self) -> NetworkResult<PublicKey> {
+        self.remote_ephemeral
+            .ok_or_else(|| NetworkError::ProtocolError("尚未收到对端的临时公钥".to_string()))
+    }
+
+    /// 检查对端声明的参与方 ID 与其静态公钥是否与目录一致
+    fn authenticate_remote(&mut self, payload: &[u8], remote_static: PublicKey) -> NetworkResult<()> {
+        let party_id = String::from_utf8(payload.to_vec())
+            .map_err(|_| NetworkError::ProtocolError("无效的参与方 ID".to_string()))?;
+        if !self.directory.verify(&party_id, &remote_static) {
+            return Err(NetworkError::AuthenticationFailed(format!(
+                "参与方 {} 的静态公钥与登记的不一致", party_id
+            )));
+        }
+        self.remote = Some((party_id, remote_static));
+        Ok(())
+    }
+
+    /// 握手完成后得到加密信道
+    pub fn into_channel(self) -> NetworkResult<SecureChannel> {
+        if !self.is_complete() {
+            return Err(NetworkError::ProtocolError("握手尚未完成".to_string()));
+        }
+        let (initiator_key, responder_key) = self.symmetric.split()?;
+        let (send_key, recv_key) = match self.role {
+            HandshakeRole::Initiator => (initiator_key, responder_key),
+            HandshakeRole::Responder => (responder_key, initiator_key),
+        };
+        let (peer_party, peer_public_key) = self.remote.expect("completed handshake authenticates the peer");
+        Ok(SecureChannel {
+            peer_party,
+            peer_public_key,
+            handshake_hash: self.symmetric.hash,
+            send: ChannelKeySchedule::new(*send_key.as_bytes(), self.rekey_policy),
+            recv: ChannelKeySchedule::new(*recv_key.as_bytes(), self.rekey_policy),
+            send_counter: 0,
+            recv_counter: 0,
+            failed: false,
+        })
+    }
+}
+
+/// 握手之后的认证加密信道
+///
+/// 每个方向使用独立的密钥和消息计数器，消息必须按发送顺序到达。
+/// 密钥按 `RekeyPolicy` 自动轮换，双方以相同顺序处理消息即可保持同步。
+/// 解密失败后信道不再可用，需要重新握手。
+#[derive(Debug)]
+pub struct SecureChannel {
+    peer_party: String,
+    peer_public_key: PublicKey,
+    handshake_hash: [u8; 32],
+    send: ChannelKeySchedule,
+    recv: ChannelKeySchedule,
+    send_counter: u64,
+    recv_counter: u64,
+    failed: bool,
+}
+
+impl SecureChannel {
+    /// 已认证的对端参与方 ID
+    pub fn peer_party(&self) -> &str {
+        &self.peer_party
+    }
+
+    /// 对端的静态公钥
+    pub fn peer_public_key(&self) -> &PublicKey {
+        &self.peer_public_key
+    }
+
+    /// 握手哈希，双方相同，可用作信道绑定
+    pub fn handshake_hash(&self) -> [u8; 32] {
+        self.handshake_hash
+    }
+
+    /// 发送方向的密钥纪元
+    pub fn send_epoch(&self) -> u64 {
+        self.send.epoch()
+    }
+
+    /// 接收方向的密钥纪元
+    pub fn recv_epoch(&self) -> u64 {
+        self.recv.epoch()
+    }
+
+    /// 关联数据：握手哈希 ‖ 密钥纪元
+    fn associated_data(&self, epoch: u64) -> Vec<u8> {
+        let mut aad = self.handshake_hash.to_vec();
+        aad.extend_from_slice(&epoch.to_be_bytes());
+        aad
+    }
+
+    /// 加密一条消息
+    pub fn seal(&mut self, plaintext: &[u8]) -> Vec<u8> {
+        let (epoch, key) = self.send.key_for_message(plaintext.len());
+        let aead = SivAead::new(&*key).expect("32-byte channel key");
+        let sealed = aead.seal(&self.send_counter.to_be_bytes(), &self.associated_data(epoch), plaintext);
+        self.send_counter += 1;
+
+        let mut frame = Vec::with_capacity(TAG_LEN + sealed.ciphertext.len());
+        frame.extend_from_slice(&sealed.siv);
+        frame.extend_from_slice(&sealed.ciphertext);
+        frame
+    }
+
+    /// 验证并解密下一条消息
+    pub fn open(&mut self, frame: &[u8]) -> NetworkResult<Vec<u8>> {
+        if self.failed {
+            return Err(NetworkError::ChannelError("信道已失效，需要重新握手".to_string()));
+        }
+        if frame.len() < TAG_LEN {
+            self.failed = true;
+            return Err(NetworkError::AuthenticationFailed("密文过短".to_string()));
+        }
+        let (epoch, key) = self.recv.key_for_message(frame.len() - TAG_LEN);
+        let result = open(&key, self.recv_counter, &self.associated_data(epoch), frame);
+        match result {
+            Ok(plaintext) => {
+                self.recv_counter += 1;
+                Ok(plaintext)
+            }
+            Err(_) => {
+                self.failed = true;
+                Err(NetworkError::AuthenticationFailed(format!("来自 {} 的消息认证失败", self.peer_party)))
+            }
+        }
+    }
+}
@@ -0,0 +1,774 @@
+//! # 多方计算会话 (Networked MPC Session)
+//!
+//! `P2PNode` 负责节点发现和连接管理，真正在多个进程之间执行协议时，
+//! 各参与方通过 `MpcSession` 在全连接 TCP 传输（`MeshTransport`）上按轮同步交换消息。
+//! 会话对传输是泛型的：`with_transport` 接受任意 `RoundTransport`，例如包装了
+//! `InMemoryTransport` 或 `P2PTransport` 的 `BlockingTransport`：
+//!
+//! 1. **入会**: 各方提供相同的会话名称、按参与方 ID 排列的地址名单和合谋门限 t。
+//!    会话 ID 由这三项哈希得到，配置不一致的参与方在握手时被拒绝
+//! 2. **分享分发**: `input` 用 t 次 Shamir 多项式分享每个参与方的私有输入，
+//!    横坐标为参与方 ID + 1
+//! 3. **同步轮**: 每轮每个参与方向其他每个参与方恰好发送一条消息，消息带逻辑时钟，
+//!    消息类型或轮次不一致都视为协议错误
+//! 4. **重构**: `open` 广播本方的分享，检查全部分享落在同一个 t 次多项式上之后插值
+//!
+//! 乘法使用 Beaver 三元组：`preprocess` 在诚实多数（n ≥ 2t + 1）下以 BGW 方式联合生成
+//! 三元组，`multiply` 一轮完成一批乘法，是 `secure_multiply` 的网络版本。
+//! `open_spdz` 打开 SPDZ 加法分享，并以先承诺后打开 σ 值的批量 MAC 检查发现被篡改的分享。
+//! `reliable_broadcast` 在四个同步轮上运行 Bracha 广播，恶意参与方无法让诚实参与方收到不同的值。
+//! `set_topology` 指定特殊角色：`preprocess_from_dealer` 由 Dealer 分发三元组，
+//! `aggregate` 只把输出交给 Aggregator；各方对角色的认识不一致时协议返回错误。
+//!
+//! 所有操作都会阻塞当前线程直到本轮结束，在异步运行时中应放入 `spawn_blocking`。
+//! 预处理只在半诚实模型下安全；打开时的一致性检查能发现偏离协议的分享，但不能纠正。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::network::session::*;
+//! use std::thread;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let addresses = reserve_local_addresses(3)?;
+//! let handles: Vec<_> = (0..3)
+//!     .map(|party_id| {
+//!         let config = SessionConfig::new("doc-example", party_id, addresses.clone(), 1);
+//!         thread::spawn(move || -> mpc_api::Result<u64> {
+//!             let mut session = MpcSession::join(config)?;
+//!             // 每个参与方输入 party_id + 2
+//!             let inputs = session.input(&[party_id as u64 + 2])?.result;
+//!             session.preprocess(1)?;
+//!             let product = session.multiply(&[inputs[0][0].clone()], &[inputs[1][0].clone()])?.result;
+//!             Ok(session.open(&product)?.result[0])
+//!         })
+//!     })
+//!     .collect();
+//! for handle in handles {
+//!     assert_eq!(handle.join().unwrap()?, 2 * 3);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::protocol::{max_broadcast_faults, BroadcastMessage, ReliableBroadcast};
+use super::transport::{MeshTransport, RoundTransport};
+use crate::beaver_triples::BeaverTriple;
+use crate::commitment::{MessageCommitment, MessageOpening};
+use crate::protocols::session::{ProtocolSession, SessionId};
+use crate::protocols::topology::{ProtocolRole, Topology};
+use crate::protocols::{ProtocolOutput, ProtocolStats, StatsRecorder};
+use crate::security::{ProtocolIncident, SecurityManager};
+use crate::secret_sharing::{
+    field_add, field_inner_product, field_mul, validate_field_element, SecretSharing,
+    ShamirSecretSharing, Share,
+};
+use crate::spdz::{MacCheck, SPDZShare};
+use crate::utils::random_field_element;
+use crate::{MpcError, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 派生会话 ID 使用的协议名称
+const SESSION_PROTOCOL: &str = "mpc_api/network/session";
+
+/// 会话配置，所有参与方除 `party_id` 外必须一致
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionConfig {
+    /// 会话名称
+    pub name: String,
+    /// 本参与方的 ID
+    pub party_id: usize,
+    /// 按参与方 ID 排列的监听地址
+    pub addresses: Vec<String>,
+    /// 可容忍的合谋参与方数量 t，分享多项式的次数
+    pub collusion_threshold: usize,
+    /// 建立连接和单轮收发的超时时间（毫秒）
+    pub timeout_ms: u64,
+}
+
+impl SessionConfig {
+    /// 创建会话配置，超时默认 10 秒
+    ///
+    /// # 参数
+    /// - `name`: 会话名称
+    /// - `party_id`: 本参与方的 ID
+    /// - `addresses`: 按参与方 ID 排列的监听地址
+    /// - `collusion_threshold`: 可容忍的合谋参与方数量 t
+    pub fn new(name: &str, party_id: usize, addresses: Vec<String>, collusion_threshold: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            party_id,
+            addresses,
+            collusion_threshold,
+            timeout_ms: 10_000,
+        }
+    }
+
+    /// 设置超时时间
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout_ms = timeout.as_millis() as u64;
+        self
+    }
+
+    /// 检查配置的一致性
+    pub fn validate(&self) -> Result<()> {
+        if self.collusion_threshold == 0 || self.party_count() < 2 * self.collusion_threshold + 1 {
+            return Err(MpcError::InvalidThreshold);
+        }
+        if self.party_id >= self.party_count() {
+            return Err(MpcError::ProtocolError(format!("Party ID {} is not a member", self.party_id)));
+        }
+        if self.timeout_ms == 0 {
+            return Err(MpcError::ProtocolError("Session timeout must be positive".to_string()));
+        }
+        Ok(())
+    }
+
+    /// 参与方数量
+    pub fn party_count(&self) -> usize {
+        self.addresses.len()
+    }
+
+    /// 超时时间
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+}
+
+/// 在本机上预留 n 个互不相同的监听地址
+///
+/// 同时占用所有端口再释放，用于在一台机器上启动多个参与方。
+pub fn reserve_local_addresses(n: usize) -> Result<Vec<String>> {
+    let listeners = (0..n)
+        .map(|_| TcpListener::bind("127.0.0.1:0"))
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(|e| MpcError::NetworkError(format!("Failed to allocate port: {}", e)))?;
+    listeners.iter()
+        .map(|listener| listener.local_addr().map(|addr| addr.to_string()))
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(|e| MpcError::NetworkError(e.to_string()))
+}
+
+/// 参与方在一次多方计算会话中的状态
+///
+/// 持有与其他参与方的连接和本方的 Beaver 三元组分享。
+#[derive(Debug)]
+pub struct MpcSession<R = MeshTransport> {
+    config: SessionConfig,
+    session: ProtocolSession,
+    transport: R,
+    /// 全部参与方在 0 处插值的拉格朗日系数
+    lagrange: Vec<u64>,
+    /// 本方持有的未使用三元组分享
+    triples: VecDeque<BeaverTriple>,
+    next_triple_id: u64,
+    /// 参与方角色
+    topology: Topology,
+    /// 已执行的通信轮数
+    rounds: usize,
+    /// 事故上报与参与方准入检查
+    security: Option<Arc<SecurityManager>>,
+}
+
+impl MpcSession {
+    /// 加入会话：与名单中的全部参与方建立连接
+    ///
+    /// 阻塞直到所有参与方都完成握手或超时。
+    ///
+    /// # 参数
+    /// - `config`: 会话配置
+    pub fn join(config: SessionConfig) -> Result<Self> {
+        config.validate()?;
+        let session = root_session(&config)?;
+        let transport = MeshTransport::connect_to(config.party_id, &config.addresses, config.timeout(), session.id())?;
+        Self::start(config, session, transport)
+    }
+}
+
+impl<R: RoundTransport> MpcSession<R> {
+    /// 在已建立的传输上加入会话
+    ///
+    /// 名单中的地址只参与会话 ID 的计算，不用于建立连接。
+    ///
+    /// # 参数
+    /// - `config`: 会话配置
+    /// - `transport`: 与其他参与方之间的传输，本方 ID 和对等节点必须与名单一致
+    pub fn with_transport(config: SessionConfig, transport: R) -> Result<Self> {
+        config.validate()?;
+        let expected: Vec<usize> = (0..config.party_count()).filter(|&party| party != config.party_id).collect();
+        if transport.party_id() != config.party_id || transport.peer_ids() != expected {
+            return Err(MpcError::ProtocolError("Transport does not match the session membership".to_string()));
+        }
+        let session = root_session(&config)?;
+        Self::start(config, session, transport)
+    }
+
+    fn start(config: SessionConfig, session: ProtocolSession, transport: R) -> Result<Self> {
+        let points: Vec<u64> = (1..=config.party_count() as u64).collect();
+        let lagrange = ShamirSecretSharing::new().precompute_lagrange_coefficients(&points)?;
+        let topology = Topology::with_party_count(config.party_count())?;
+        Ok(Self {
+            config,
+            session,
+            transport,
+            lagrange,
+            triples: VecDeque::new(),
+            next_triple_id: 0,
+            topology,
+            rounds: 0,
+            security: None,
+        })
+    }
+
+    /// 关联安全管理器
+    ///
+    /// 每轮通信前检查会话未被中止、每个对等方都未被隔离，对等方以参与方 ID 的十进制字符串标识。
+    /// 超过会话超时仍未完成的轮次对所有对等方上报 `RoundTimeout`；
+    /// SPDZ MAC 检查失败无法定位作弊方，对所有对等方上报 `MacCheckFailure`。
+    ///
+    /// # 参数
+    /// - `manager`: 共享的安全管理器
+    pub fn with_security_manager(mut self, manager: Arc<SecurityManager>) -> Self {
+        self.security = Some(manager);
+        self
+    }
+
+    /// 会话 ID，所有参与方一致
+    pub fn id(&self) -> SessionId {
+        self.session.id()
+    }
+
+    /// 本参与方的 ID
+    pub fn party_id(&self) -> usize {
+        self.config.party_id
+    }
+
+    /// 参与方数量
+    pub fn party_count(&self) -> usize {
+        self.config.party_count()
+    }
+
+    /// 重构所需的分享数量 t + 1
+    pub fn threshold(&self) -> usize {
+        self.config.collusion_threshold + 1
+    }
+
+    /// 尚未使用的三元组数量
+    pub fn triples_available(&self) -> usize {
+        self.triples.len()
+    }
+
+    /// 参与方拓扑，未设置时只有默认的 King（参与方 0）
+    pub fn topology(&self) -> &Topology {
+        &self.topology
+    }
+
+    /// 设置参与方角色
+    ///
+    /// 拓扑的参与方必须恰好是会话名单中的 0..n。各方应设置相同的拓扑，
+    /// 角色分配不一致会在 `preprocess_from_dealer` 和 `aggregate` 中被发现。
+    pub fn set_topology(&mut self, topology: Topology) -> Result<()> {
+        if !topology.parties().iter().copied().eq(0..self.party_count()) {
+            return Err(MpcError::ProtocolError("Topology does not match the session membership".to_string()));
+        }
+        self.topology = topology;
+        Ok(())
+    }
+
+    /// 分享各参与方的私有输入
+    ///
+    /// 每个参与方都必须调用，输入数量可以不同（包括 0 个）。
+    ///
+    /// # 返回值
+    /// 按输入方排列的分享：`result[j][k]` 为参与方 j 第 k 个输入中属于本方的分享
+    pub fn input(&mut self, values: &[u64]) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+        if !values.iter().all(|&value| validate_field_element(value)) {
+            return Err(MpcError::ProtocolError("Input is not a field element".to_string()));
+        }
+        let mut recorder = StatsRecorder::start();
+        let x = self.share_point();
+        let shares = self.deal("session/input", values, recorder.stats_mut(), |_| true)?
+            .into_iter()
+            .map(|ys| ys.into_iter().map(|y| Share::new(x, y)).collect())
+            .collect();
+        Ok(recorder.finish(shares))
+    }
+
+    /// 打开分享
+    ///
+    /// # 参数
+    /// - `shares`: 本方持有的分享，所有参与方必须以相同顺序打开相同数量的值
+    ///
+    /// # 返回值
+    /// 返回重构出的值；各方的分享不在同一个 t 次多项式上时返回错误
+    pub fn open(&mut self, shares: &[Share]) -> Result<ProtocolOutput<Vec<u64>>> {
+        let ys = self.own_values(shares)?;
+        let mut recorder = StatsRecorder::start();
+        let values = self.open_values("session/open", &ys, recorder.stats_mut())?;
+        Ok(recorder.finish(values))
+    }
+
+    /// 联合生成 Beaver 三元组
+    ///
+    /// 第 1 轮各方分享随机的 a_j、b_j 并相加；第 2 轮本地相乘得到 2t 次分享，
+    /// 重新分享后用拉格朗日系数降为 t 次。
+    ///
+    /// # 参数
+    /// - `count`: 生成的三元组数量
+    ///
+    /// # 返回值
+    /// 返回本方可用的三元组数量
+    pub fn preprocess(&mut self, count: usize) -> Result<ProtocolOutput<usize>> {
+        let mut recorder = StatsRecorder::start();
+        let randomness: Vec<u64> = (0..2 * count).map(|_| random_field_element()).collect();
+        let dealt = self.deal("session/preprocess/random", &randomness, recorder.stats_mut(), |ys| {
+            ys.len() == 2 * count
+        })?;
+        let summed: Vec<u64> = (0..2 * count)
+            .map(|k| dealt.iter().fold(0, |acc, ys| field_add(acc, ys[k])))
+            .collect();
+        let (a, b) = summed.split_at(count);
+
+        let products: Vec<u64> = a.iter().zip(b).map(|(&a, &b)| field_mul(a, b)).collect();
+        let reshared = self.deal("session/preprocess/reshare", &products, recorder.stats_mut(), |ys| {
+            ys.len() == count
+        })?;
+        let x = self.share_point();
+        for k in 0..count {
+            let column: Vec<u64> = reshared.iter().map(|ys| ys[k]).collect();
+            let c = field_inner_product(&column, &self.lagrange);
+            let id = self.next_triple_id;
+            self.next_triple_id += 1;
+            self.triples.push_back(BeaverTriple::new(Share::new(x, a[k]), Share::new(x, b[k]), Share::new(x, c), id));
+        }
+        Ok(recorder.finish(self.triples.len()))
+    }
+
+    /// 由拓扑中的 Dealer 分发 Beaver 三元组
+    ///
+    /// 分发者在本地生成 (a, b, c = a·b) 并以 t 次多项式分享给所有参与方，一轮完成。
+    /// 分发者知道三元组的值，只适用于信任分发者的部署。
+    ///
+    /// # 参数
+    /// - `count`: 生成的三元组数量
+    ///
+    /// # 返回值
+    /// 返回本方可用的三元组数量；未分配 Dealer，或分发者以外的参与方发来材料时返回协议错误
+    pub fn preprocess_from_dealer(&mut self, count: usize) -> Result<ProtocolOutput<usize>> {
+        let label = "session/preprocess/dealer";
+        let dealer = self.role_holder(ProtocolRole::Dealer)?;
+        let mut recorder = StatsRecorder::start();
+        let material: Vec<u64> = if self.party_id() == dealer {
+            (0..count)
+                .flat_map(|_| {
+                    let (a, b) = (random_field_element(), random_field_element());
+                    [a, b, field_mul(a, b)]
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let dealt = self.deal(label, &material, recorder.stats_mut(), |_| true)?;
+        for (party, ys) in dealt.iter().enumerate() {
+            let expected = if party == dealer { 3 * count } else { 0 };
+            if ys.len() != expected {
+                return Err(MpcError::ProtocolError(format!(
+                    "Party {} sent {} values in {}, but the dealer is party {}", party, ys.len(), label, dealer
+                )));
+            }
+        }
+
+        let x = self.share_point();
+        for abc in dealt[dealer].chunks(3) {
+            let id = self.next_triple_id;
+            self.next_triple_id += 1;
+            self.triples.push_back(BeaverTriple::new(
+                Share::new(x, abc[0]), Share::new(x, abc[1]), Share::new(x, abc[2]), id,
+            ));
+        }
+        Ok(recorder.finish(self.triples.len()))
+    }
+
+    /// 批量乘法：[z_k] = [x_k]·[y_k]
+    ///
+    /// 每个乘法消耗一个三元组，打开 d = x - a、e = y - b 后本地计算
+    /// [z] = [c] + d·[b] + e·[a] + d·e，一轮完成整批。
+    ///
+    /// # 参数
+    /// - `x`: 左操作数的本方分享
+    /// - `y`: 右操作数的本方分享
+    pub fn multiply(&mut self, x: &[Share], y: &[Share]) -> Result<ProtocolOutput<Vec<Share>>> {
+        if x.len() != y.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Cannot multiply {} values by {} values", x.len(), y.len()
+            )));
+        }
+        self.own_values(x)?;
+        self.own_values(y)?;
+        if self.triples.len() < x.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Only {} triples available, {} needed", self.triples.len(), x.len()
+            )));
+        }
+
+        let mut recorder = StatsRecorder::start();
+        let triples: Vec<BeaverTriple> = self.triples.drain(..x.len()).collect();
+        recorder.stats_mut().record_preprocessing(triples.len());
+        let masked: Vec<u64> = triples.iter()
+            .zip(x.iter().zip(y))
+            .map(|(triple, (x, y))| triple.mask(x, y))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flat_map(|(d, e)| [d.y, e.y])
+            .collect();
+        let opened = self.open_values("session/multiply", &masked, recorder.stats_mut())?;
+
+        let products = triples.iter()
+            .zip(opened.chunks(2))
+            .map(|(triple, de)| triple.combine(de[0], de[1]))
+            .collect();
+        Ok(recorder.finish(products))
+    }
+
+    /// 打开 SPDZ 加法分享并做批量 MAC 检查
+    ///
+    /// 第 1 轮广播分享值并求和得到 x_k；之后由会话转录派生公共系数 r_k，
+    /// 各方计算 σ_i = Σ_k r_k·(m_ik - α_i·x_k)，第 2 轮交换 σ_i 的承诺，第 3 轮打开。
+    /// Σ_i σ_i = 0 时接受；任何一方篡改分享值都会使检查以约 1/p 之外的概率失败。
+    ///
+    /// # 参数
+    /// - `shares`: 本方持有的 SPDZ 分享，其中 `mac` 是 α·x 的加法分享
+    /// - `mac_key_share`: 本方持有的全局 MAC 密钥分享 α_i
+    ///
+    /// # 返回值
+    /// 返回打开的值；MAC 检查失败时返回认证错误
+    pub fn open_spdz(&mut self, shares: &[SPDZShare], mac_key_share: u64) -> Result<ProtocolOutput<Vec<u64>>> {
+        let mut recorder = StatsRecorder::start();
+        let values: Vec<u64> = shares.iter().map(|share| share.value).collect();
+        let by_party: Vec<Vec<u64>> = self.broadcast_value("session/spdz/open", &values, recorder.stats_mut())?;
+        if by_party.iter().any(|received| received.len() != values.len()) {
+            return Err(MpcError::ProtocolError("Wrong number of shares in session/spdz/open".to_string()));
+        }
+        let opened: Vec<u64> = (0..values.len())
+            .map(|k| by_party.iter().fold(0, |acc, received| field_add(acc, received[k])))
+            .collect();
+
+        // 系数在所有分享值公开之后才确定
+        self.session.absorb("spdz/open", &encode(&opened)?);
+        let check = MacCheck::new(&self.session, opened);
+        let macs: Vec<u64> = shares.iter().map(|share| share.mac).collect();
+        let committed = check.commit(&macs, mac_key_share)?;
+        let commitments: Vec<MessageCommitment> =
+            self.broadcast_value("session/spdz/commit", committed.commitment(), recorder.stats_mut())?;
+        let openings: Vec<MessageOpening<u64>> =
+            self.broadcast_value("session/spdz/reveal", &committed.open(), recorder.stats_mut())?;
+        if let Err(error) = check.verify(&commitments, openings) {
+            self.report_peers(|session_id| ProtocolIncident::MacCheckFailure { session_id })?;
+            return Err(error);
+        }
+        Ok(recorder.finish(check.into_opened()))
+    }
+
+    /// 把输出分享交给拓扑中的 Aggregator 重构
+    ///
+    /// 各方只把分享发给聚合方，一轮完成；其他参与方得不到输出。
+    ///
+    /// # 参数
+    /// - `shares`: 本方持有的输出分享，所有参与方必须以相同顺序提交相同数量的值
+    ///
+    /// # 返回值
+    /// 聚合方返回重构出的值，其他参与方返回 `None`；未分配 Aggregator、
+    /// 分享不一致或有参与方不认可同一个聚合方时返回协议错误
+    pub fn aggregate(&mut self, shares: &[Share]) -> Result<ProtocolOutput<Option<Vec<u64>>>> {
+        let label = "session/aggregate";
+        let aggregator = self.role_holder(ProtocolRole::Aggregator)?;
+        let ys = self.own_values(shares)?;
+        let mut recorder = StatsRecorder::start();
+        let outgoing = self.transport.peer_ids()
+            .into_iter()
+            .map(|peer| {
+                let payload: &[u64] = if peer == aggregator { &ys } else { &[] };
+                Ok((peer, encode(payload)?))
+            })
+            .collect::<Result<_>>()?;
+        let received = self.exchange_round(label, &outgoing, recorder.stats_mut())?;
+
+        let is_aggregator = self.party_id() == aggregator;
+        let own = if is_aggregator { ys.clone() } else { Vec::new() };
+        let by_party: Vec<Vec<u64>> = self.collect(label, received, own, |_| true)?;
+        let expected = if is_aggregator { ys.len() } else { 0 };
+        if let Some(party) = by_party.iter().position(|received| received.len() != expected) {
+            return Err(MpcError::ProtocolError(format!(
+                "Party {} does not agree that party {} is the aggregator", party, aggregator
+            )));
+        }
+
+        let output = if is_aggregator {
+            Some(self.reconstruct(label, &by_party, ys.len())?)
+        } else {
+            None
+        };
+        Ok(recorder.finish(output))
+    }
+
+    /// 广播一个值，供在会话上实现其他协议
+    ///
+    /// # 参数
+    /// - `label`: 本轮的消息类型，各方必须一致
+    /// - `value`: 本方广播的值
+    ///
+    /// # 返回值
+    /// 按发送方排列的全部值（包括本方）
+    pub fn broadcast<T: Serialize + DeserializeOwned + Clone>(
+        &mut self,
+        label: &str,
+        value: &T,
+    ) -> Result<ProtocolOutput<Vec<T>>> {
+        let mut recorder = StatsRecorder::start();
+        let values = self.broadcast_value(label, value, recorder.stats_mut())?;
+        Ok(recorder.finish(values))
+    }
+
+    /// 一致地广播一个值
+    ///
+    /// 以 Bracha 广播的 SEND、ECHO、READY 和 READY 扩散四轮完成，可容忍 ⌊(n - 1) / 3⌋ 个恶意参与方：
+    /// 诚实参与方收到的值一致，某个参与方的值没有被交付时返回协议错误。
+    ///
+    /// # 参数
+    /// - `label`: 本次广播的标签，各方必须一致
+    /// - `value`: 本方广播的值
+    ///
+    /// # 返回值
+    /// 按发送方排列的全部值（包括本方）
+    pub fn reliable_broadcast<T: Serialize + DeserializeOwned + Clone>(
+        &mut self,
+        label: &str,
+        value: &T,
+    ) -> Result<ProtocolOutput<Vec<T>>> {
+        let mut recorder = StatsRecorder::start();
+        let n = self.party_count();
+        let protocol_error = |e: super::NetworkError| MpcError::ProtocolError(e.to_string());
+        let mut state = ReliableBroadcast::new(
+            &self.party_id().to_string(),
+            (0..n).map(|party| party.to_string()),
+            max_broadcast_faults(n),
+        )
+        .map_err(protocol_error)?;
+
+        let mut outgoing = state.broadcast(label, &encode(value)?).map_err(protocol_error)?;
+        for phase in ["send", "echo", "ready", "amplify"] {
+            let round = format!("{}/{}", label, phase);
+            let received = self.broadcast_round(&round, &encode(&outgoing)?, recorder.stats_mut())?;
+            outgoing = Vec::new();
+            for (party, payload) in received {
+                let messages: Vec<BroadcastMessage> = bincode::deserialize(&payload).map_err(|e| {
+                    MpcError::SerializationError(format!("Malformed {} message from party {}: {}", round, party, e))
+                })?;
+                for message in messages {
+                    // 非参与方的广播实例不影响其他实例
+                    if let Ok(replies) = state.handle(&party.to_string(), message) {
+                        outgoing.extend(replies);
+                    }
+                }
+            }
+        }
+
+        let values = (0..n)
+            .map(|party| {
+                if party == self.party_id() {
+                    return Ok(value.clone());
+                }
+                let payload = state.delivered(&party.to_string(), label)
+                    .ok_or_else(|| MpcError::ProtocolError(format!("No consistent {} value from party {}", label, party)))?;
+                bincode::deserialize(payload).map_err(|e| {
+                    MpcError::SerializationError(format!("Malformed {} value from party {}: {}", label, party, e))
+                })
+            })
+            .collect::<Result<Vec<T>>>()?;
+        Ok(recorder.finish(values))
+    }
+
+    /// 本方分享的横坐标
+    fn share_point(&self) -> u64 {
+        self.party_id() as u64 + 1
+    }
+
+    fn own_values(&self, shares: &[Share]) -> Result<Vec<u64>> {
+        let x = self.share_point();
+        shares.iter()
+            .map(|share| if share.x == x {
+                Ok(share.y)
+            } else {
+                Err(MpcError::ProtocolError(format!("Share at x = {} does not belong to party {}", share.x, self.party_id())))
+            })
+            .collect()
+    }
+
+    /// 以 t 次多项式分享每个值
+    ///
+    /// # 返回值
+    /// 按发送方排列的分享：`result[j][k]` 为参与方 j 分享的第 k 个值中属于本方的分享
+    fn deal(
+        &mut self,
+        label: &str,
+        values: &[u64],
+        stats: &mut ProtocolStats,
+        well_formed: impl Fn(&Vec<u64>) -> bool,
+    ) -> Result<Vec<Vec<u64>>> {
+        let n = self.party_count();
+        let mut per_recipient = vec![Vec::with_capacity(values.len()); n];
+        for value in values {
+            let shares = ShamirSecretSharing::share(value, self.threshold(), n)?;
+            for (slot, share) in per_recipient.iter_mut().zip(&shares) {
+                slot.push(share.y);
+            }
+        }
+
+        let own = std::mem::take(&mut per_recipient[self.party_id()]);
+        let outgoing = per_recipient.into_iter()
+            .enumerate()
+            .filter(|&(party, _)| party != self.party_id())
+            .map(|(party, shares)| Ok((party, encode(&shares)?)))
+            .collect::<Result<_>>()?;
+        let received = self.exchange_round(label, &outgoing, stats)?;
+        self.collect(label, received, own, well_formed)
+    }
+
+    /// 广播本方的分享并重构
+    fn open_values(&mut self, label: &str, shares: &[u64], stats: &mut ProtocolStats) -> Result<Vec<u64>> {
+        let by_party: Vec<Vec<u64>> = self.broadcast_value(label, &shares.to_vec(), stats)?;
+        if by_party.iter().any(|received| received.len() != shares.len()) {
+            return Err(MpcError::ProtocolError(format!("Wrong number of shares in {}", label)));
+        }
+        self.reconstruct(label, &by_party, shares.len())
+    }
+
+    /// 检查每列分享落在同一个 t 次多项式上并插值
+    fn reconstruct(&self, label: &str, by_party: &[Vec<u64>], count: usize) -> Result<Vec<u64>> {
+        let scheme = ShamirSecretSharing::new();
+        (0..count)
+            .map(|k| {
+                let column: Vec<Share> = by_party.iter()
+                    .enumerate()
+                    .map(|(party, received)| Share::new(party as u64 + 1, received[k]))
+                    .collect();
+                if !scheme.verify_shares(&column, self.threshold()) {
+                    return Err(MpcError::ProtocolError(format!("Inconsistent shares opened in {}", label)));
+                }
+                let values: Vec<u64> = column.iter().map(|share| share.y).collect();
+                Ok(field_inner_product(&values, &self.lagrange))
+            })
+            .collect()
+    }
+
+    fn role_holder(&self, role: ProtocolRole) -> Result<usize> {
+        self.topology.role_holder(role).ok_or_else(|| {
+            MpcError::ProtocolError(format!("Role {:?} is not assigned in the session topology", role))
+        })
+    }
+
+    fn exchange_round(
+        &mut self,
+        label: &str,
+        outgoing: &BTreeMap<usize, Vec<u8>>,
+        stats: &mut ProtocolStats,
+    ) -> Result<BTreeMap<usize, Vec<u8>>> {
+        self.ensure_round_allowed()?;
+        let started = Instant::now();
+        let result = self.transport.exchange(label, outgoing, stats);
+        self.finish_round(started, result)
+    }
+
+    fn broadcast_round(&mut self, label: &str, payload: &[u8], stats: &mut ProtocolStats) -> Result<BTreeMap<usize, Vec<u8>>> {
+        self.ensure_round_allowed()?;
+        let started = Instant::now();
+        let result = self.transport.broadcast(label, payload, stats);
+        self.finish_round(started, result)
+    }
+
+    /// 会话被中止或有对等方被隔离时拒绝开始新的一轮
+    fn ensure_round_allowed(&self) -> Result<()> {
+        let Some(manager) = &self.security else {
+            return Ok(());
+        };
+        if manager.is_session_aborted(&self.id().to_string()) {
+            return Err(MpcError::ProtocolError(format!("Session {} has been aborted", self.id())));
+        }
+        self.transport.peer_ids()
+            .into_iter()
+            .try_for_each(|peer| manager.ensure_peer_allowed(&peer.to_string()))
+    }
+
+    /// 网络错误发生在会话超时之后时上报轮次超时
+    fn finish_round<T>(&mut self, started: Instant, result: Result<T>) -> Result<T> {
+        let round = self.rounds;
+        self.rounds += 1;
+        let elapsed = started.elapsed();
+        if matches!(result, Err(MpcError::NetworkError(_))) && elapsed >= self.config.timeout() {
+            self.report_peers(|session_id| ProtocolIncident::RoundTimeout { session_id, round, elapsed })?;
+        }
+        result
+    }
+
+    fn report_peers(&self, incident: impl Fn(String) -> ProtocolIncident) -> Result<()> {
+        let Some(manager) = &self.security else {
+            return Ok(());
+        };
+        let session_id = self.id().to_string();
+        for peer in self.transport.peer_ids() {
+            manager.report_incident(&peer.to_string(), incident(session_id.clone()))?;
+        }
+        Ok(())
+    }
+
+    fn broadcast_value<T: Serialize + DeserializeOwned + Clone>(
+        &mut self,
+        label: &str,
+        value: &T,
+        stats: &mut ProtocolStats,
+    ) -> Result<Vec<T>> {
+        let received = self.broadcast_round(label, &encode(value)?, stats)?;
+        self.collect(label, received, value.clone(), |_| true)
+    }
+
+    fn collect<T: DeserializeOwned>(
+        &self,
+        label: &str,
+        mut received: BTreeMap<usize, Vec<u8>>,
+        own: T,
+        well_formed: impl Fn(&T) -> bool,
+    ) -> Result<Vec<T>> {
+        let mut own = Some(own);
+        (0..self.party_count())
+            .map(|party| {
+                if party == self.party_id() {
+                    return own.take().ok_or_else(|| MpcError::ProtocolError("Duplicate local entry".to_string()));
+                }
+                let payload = received.remove(&party)
+                    .ok_or_else(|| MpcError::ProtocolError(format!("No {} message from party {}", label, party)))?;
+                let value: T = bincode::deserialize(&payload).map_err(|e| {
+                    MpcError::SerializationError(format!("Malformed {} message from party {}: {}", label, party, e))
+                })?;
+                if !well_formed(&value) {
+                    return Err(MpcError::ProtocolError(format!("Malformed {} message from party {}", label, party)));
+                }
+                Ok(value)
+            })
+            .collect()
+    }
+}
+
+/// 由会话名称、地址名单和门限派生的根会话
+fn root_session(config: &SessionConfig) -> Result<ProtocolSession> {
+    let context = encode(&(&config.name, &config.addresses, config.collusion_threshold))?;
+    Ok(ProtocolSession::root(SESSION_PROTOCOL, &context))
+}
+
+fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    bincode::serialize(value).map_err(|e| MpcError::SerializationError(e.to_string()))
+}
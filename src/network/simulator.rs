@@ -0,0 +1,413 @@
+//! # 多方协议模拟器 (Multi-party Protocol Simulator)
+//!
+//! 在一个进程内启动 n 个虚拟参与方，参与方之间通过 `InMemoryTransport` 交换消息，
+//! 不需要监听端口，便于对 SPDZ、密钥生成等多方协议做确定性的集成测试：
+//!
+//! - **确定性随机数**: 每个参与方得到由模拟种子派生的 `StdRng`，相同的种子产生相同的输入和故障
+//! - **同步轮**: 每个参与方持有一个 `BlockingTransport`，可以直接用来创建 `MpcSession`
+//! - **故障注入**: `FaultRule` 按发送方、接收方、消息类型前缀和轮次匹配消息，
+//!   对匹配的消息执行丢弃、篡改载荷、平移分享值或延迟发送
+//! - **消息记录**: 每条发出的消息连同所受的故障记录为 `MessageEvent`，按轮次、发送方、接收方排序
+//!
+//! 模拟器自带 tokio 运行时，`run` 和 `run_sessions` 只能在同步代码中调用。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::network::simulator::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! // 参与方 2 发给参与方 0 的打开消息中每个分享值加 1
+//! let simulator = Simulator::new(3, 1)
+//!     .with_seed(7)
+//!     .with_fault(FaultRule::new(Fault::ShiftValues(1)).from(2).to(0).label("session/open"));
+//!
+//! let simulation = simulator.run_sessions(|mut session, _rng| {
+//!     let inputs = session.input(&[session.party_id() as u64])?.result;
+//!     session.open(&[inputs[1][0].clone()])
+//! })?;
+//!
+//! // 参与方 0 收到的分享不在同一个多项式上，打开失败；其余参与方得到正确结果
+//! assert!(simulation.outputs[0].is_err());
+//! assert_eq!(simulation.outputs[1].as_ref().unwrap().result, vec![1]);
+//! assert_eq!(simulation.faults().count(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::network::common::{PartyId, Transport, TransportFuture};
+use crate::network::protocol::NetworkMessage;
+use crate::network::session::{MpcSession, SessionConfig};
+use crate::network::transport::{BlockingTransport, InMemoryTransport};
+use crate::secret_sharing::field_add;
+use crate::{MpcError, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// 模拟会话的名称，参与方地址名单为 `sim-0` … `sim-(n-1)`
+pub const SIMULATION_SESSION: &str = "simulation";
+
+/// 参与方随机数流和故障随机数流的区分常量
+const PARTY_STREAM: u64 = 0;
+const FAULT_STREAM: u64 = 1;
+
+/// 对一条消息执行的故障
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// 丢弃消息，接收方在超时后报错
+    Drop,
+    /// 把载荷中随机位置的一个字节异或一个随机非零值
+    Corrupt,
+    /// 把载荷按 bincode 编码的 `Vec<u64>` 解码，每个值在域上加上给定的偏移；
+    /// 载荷不是 `Vec<u64>` 时原样发送
+    ShiftValues(u64),
+    /// 延迟发送，发送方在本轮中同样被拖慢
+    Delay(Duration),
+}
+
+/// 故障的种类，用于消息记录
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FaultKind {
+    Drop,
+    Corrupt,
+    ShiftValues,
+    Delay,
+}
+
+impl Fault {
+    /// 故障的种类
+    pub fn kind(&self) -> FaultKind {
+        match self {
+            Fault::Drop => FaultKind::Drop,
+            Fault::Corrupt => FaultKind::Corrupt,
+            Fault::ShiftValues(_) => FaultKind::ShiftValues,
+            Fault::Delay(_) => FaultKind::Delay,
+        }
+    }
+}
+
+/// 故障规则：对匹配的消息执行故障
+///
+/// 未设置的条件匹配任意值；多条规则都匹配时只执行最先添加的一条。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaultRule {
+    fault: Fault,
+    from: Option<PartyId>,
+    to: Option<PartyId>,
+    label: Option<String>,
+    round: Option<u64>,
+}
+
+impl FaultRule {
+    /// 对所有消息执行 `fault` 的规则
+    pub fn new(fault: Fault) -> Self {
+        FaultRule { fault, from: None, to: None, label: None, round: None }
+    }
+
+    /// 只匹配参与方 `party` 发出的消息
+    pub fn from(mut self, party: PartyId) -> Self {
+        self.from = Some(party);
+        self
+    }
+
+    /// 只匹配发给参与方 `party` 的消息
+    pub fn to(mut self, party: PartyId) -> Self {
+        self.to = Some(party);
+        self
+    }
+
+    /// 只匹配消息类型以 `prefix` 开头的消息
+    pub fn label(mut self, prefix: &str) -> Self {
+        self.label = Some(prefix.to_string());
+        self
+    }
+
+    /// 只匹配第 `round` 轮（从 1 开始）的消息
+    pub fn round(mut self, round: u64) -> Self {
+        self.round = Some(round);
+        self
+    }
+
+    fn matches(&self, from: PartyId, to: PartyId, message: &NetworkMessage) -> bool {
+        self.from.is_none_or(|party| party == from)
+            && self.to.is_none_or(|party| party == to)
+            && self.label.as_ref().is_none_or(|prefix| message.message_type.starts_with(prefix))
+            && self.round.is_none_or(|round| message.round().ok().flatten() == Some(round))
+    }
+}
+
+/// 一条发出的消息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageEvent {
+    /// 消息所在的轮次，消息不带轮次时为 0
+    pub round: u64,
+    /// 消息类型
+    pub label: String,
+    /// 发送方
+    pub from: PartyId,
+    /// 接收方
+    pub to: PartyId,
+    /// 载荷字节数（故障执行前）
+    pub bytes: usize,
+    /// 消息受到的故障，正常投递时为 `None`
+    pub fault: Option<FaultKind>,
+}
+
+/// 在发送时按规则注入故障的传输
+pub struct AdversarialTransport<T> {
+    inner: T,
+    rules: Arc<Vec<FaultRule>>,
+    rng: Mutex<StdRng>,
+    events: Arc<Mutex<Vec<MessageEvent>>>,
+}
+
+impl<T: Transport> AdversarialTransport<T> {
+    /// 包装传输
+    ///
+    /// # 参数
+    /// - `inner`: 底层传输
+    /// - `rules`: 故障规则
+    /// - `seed`: 篡改载荷使用的随机数种子
+    /// - `events`: 记录发出消息的共享日志
+    pub fn new(inner: T, rules: Arc<Vec<FaultRule>>, seed: u64, events: Arc<Mutex<Vec<MessageEvent>>>) -> Self {
+        AdversarialTransport {
+            inner,
+            rules,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+            events,
+        }
+    }
+
+    /// 执行故障，返回要发送的消息和发送前的延迟；消息被丢弃时返回 `None`
+    fn apply(&self, fault: &Fault, mut message: NetworkMessage) -> Option<(NetworkMessage, Duration)> {
+        match fault {
+            Fault::Drop => return None,
+            Fault::Corrupt => {
+                if !message.payload.is_empty() {
+                    let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+                    let position = rng.gen_range(0..message.payload.len());
+                    message.payload[position] ^= rng.gen_range(1..=u8::MAX);
+                }
+            }
+            Fault::ShiftValues(offset) => {
+                if let Ok(values) = bincode::deserialize::<Vec<u64>>(&message.payload) {
+                    let shifted: Vec<u64> = values.into_iter().map(|value| field_add(value, *offset)).collect();
+                    if let Ok(payload) = bincode::serialize(&shifted) {
+                        message.payload = payload;
+                    }
+                }
+            }
+            Fault::Delay(delay) => return Some((message, *delay)),
+        }
+        Some((message, Duration::ZERO))
+    }
+}
+
+impl<T: Transport> Transport for AdversarialTransport<T> {
+    fn party_id(&self) -> PartyId {
+        self.inner.party_id()
+    }
+
+    fn parties(&self) -> Vec<PartyId> {
+        self.inner.parties()
+    }
+
+    fn send(&self, to: PartyId, message: NetworkMessage) -> TransportFuture<'_, ()> {
+        Box::pin(async move {
+            let from = self.party_id();
+            let fault = self.rules.iter().find(|rule| rule.matches(from, to, &message)).map(|rule| rule.fault.clone());
+            let event = MessageEvent {
+                round: message.round().ok().flatten().unwrap_or(0),
+                label: message.message_type.clone(),
+                from,
+                to,
+                bytes: message.payload.len(),
+                fault: fault.as_ref().map(Fault::kind),
+            };
+            self.events.lock().unwrap_or_else(|e| e.into_inner()).push(event);
+
+            let (message, delay) = match fault {
+                Some(fault) => match self.apply(&fault, message) {
+                    Some(sent) => sent,
+                    None => return Ok(()),
+                },
+                None => (message, Duration::ZERO),
+            };
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            self.inner.send(to, message).await
+        })
+    }
+
+    fn recv(&self) -> TransportFuture<'_, (PartyId, NetworkMessage)> {
+        self.inner.recv()
+    }
+}
+
+/// 模拟参与方使用的同步轮传输
+pub type SimulatedTransport = BlockingTransport<AdversarialTransport<InMemoryTransport>>;
+
+/// 一个虚拟参与方
+pub struct SimulatedParty {
+    /// 参与方 ID
+    pub party_id: PartyId,
+    /// 由模拟种子派生的随机数生成器
+    pub rng: StdRng,
+    /// 与其他参与方之间的同步轮传输
+    pub transport: SimulatedTransport,
+    /// 在该传输上创建 `MpcSession` 所用的会话配置
+    pub config: SessionConfig,
+}
+
+impl SimulatedParty {
+    /// 在本参与方的传输上加入会话
+    pub fn into_session(self) -> Result<(MpcSession<SimulatedTransport>, StdRng)> {
+        let session = MpcSession::with_transport(self.config, self.transport)?;
+        Ok((session, self.rng))
+    }
+}
+
+/// 一次模拟的结果
+#[derive(Debug)]
+pub struct Simulation<T> {
+    /// 按参与方 ID 排列的输出
+    pub outputs: Vec<Result<T>>,
+    /// 全部发出的消息，按轮次、发送方、接收方排序
+    pub events: Vec<MessageEvent>,
+}
+
+impl<T> Simulation<T> {
+    /// 受到故障的消息
+    pub fn faults(&self) -> impl Iterator<Item = &MessageEvent> {
+        self.events.iter().filter(|event| event.fault.is_some())
+    }
+
+    /// 所有参与方都成功时返回按参与方 ID 排列的输出，否则返回 ID 最小的失败参与方的错误
+    pub fn unwrap_all(self) -> Result<Vec<T>> {
+        self.outputs.into_iter().collect()
+    }
+}
+
+/// 多方协议模拟器
+#[derive(Debug, Clone)]
+pub struct Simulator {
+    parties: usize,
+    collusion_threshold: usize,
+    seed: u64,
+    timeout: Duration,
+    rules: Vec<FaultRule>,
+}
+
+impl Simulator {
+    /// 创建 `parties` 个参与方、合谋门限为 `collusion_threshold` 的模拟器
+    ///
+    /// 默认种子为 0，每轮等待超时为 5 秒，没有故障。
+    pub fn new(parties: usize, collusion_threshold: usize) -> Self {
+        Simulator {
+            parties,
+            collusion_threshold,
+            seed: 0,
+            timeout: Duration::from_secs(5),
+            rules: Vec::new(),
+        }
+    }
+
+    /// 设置随机数种子
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// 设置每轮等待其他参与方消息的超时时间，注入丢弃故障时应设得较短
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 添加故障规则
+    pub fn with_fault(mut self, rule: FaultRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// 参与方数量
+    pub fn parties(&self) -> usize {
+        self.parties
+    }
+
+    /// 参与方 `party` 在某个随机数流上的种子
+    fn stream_seed(&self, party: PartyId, stream: u64) -> u64 {
+        // SplitMix64 的增量使相邻参与方的种子相差较大
+        self.seed
+            .wrapping_add((party as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .rotate_left(17)
+            ^ stream
+    }
+
+    /// 运行模拟：每个参与方在独立线程中执行 `party`
+    ///
+    /// 只有无法创建运行时时返回错误，各参与方的错误记录在 `outputs` 中。
+    pub fn run<T: Send>(&self, party: impl Fn(SimulatedParty) -> Result<T> + Sync) -> Result<Simulation<T>> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .map_err(|e| MpcError::NetworkError(format!("Failed to start simulator runtime: {}", e)))?;
+        let rules = Arc::new(self.rules.clone());
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let addresses: Vec<String> = (0..self.parties).map(|party| format!("sim-{}", party)).collect();
+
+        let simulated: Vec<SimulatedParty> = InMemoryTransport::network(self.parties)
+            .into_iter()
+            .enumerate()
+            .map(|(party_id, transport)| {
+                let adversarial = AdversarialTransport::new(
+                    transport,
+                    rules.clone(),
+                    self.stream_seed(party_id, FAULT_STREAM),
+                    events.clone(),
+                );
+                SimulatedParty {
+                    party_id,
+                    rng: StdRng::seed_from_u64(self.stream_seed(party_id, PARTY_STREAM)),
+                    transport: BlockingTransport::new(adversarial, runtime.handle().clone(), self.timeout),
+                    config: SessionConfig::new(SIMULATION_SESSION, party_id, addresses.clone(), self.collusion_threshold)
+                        .with_timeout(self.timeout),
+                }
+            })
+            .collect();
+
+        let party = &party;
+        let outputs = thread::scope(|scope| {
+            let handles: Vec<_> = simulated.into_iter()
+                .map(|simulated| scope.spawn(move || party(simulated)))
+                .collect();
+            handles.into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|_| {
+                    Err(MpcError::ProtocolError("Simulated party panicked".to_string()))
+                }))
+                .collect()
+        });
+        runtime.shutdown_background();
+
+        let mut events = std::mem::take(&mut *events.lock().unwrap_or_else(|e| e.into_inner()));
+        events.sort_by_key(|event| (event.round, event.from, event.to));
+        Ok(Simulation { outputs, events })
+    }
+
+    /// 运行模拟：每个参与方在模拟传输上加入 `MpcSession` 后执行 `party`
+    pub fn run_sessions<T: Send>(
+        &self,
+        party: impl Fn(MpcSession<SimulatedTransport>, StdRng) -> Result<T> + Sync,
+    ) -> Result<Simulation<T>> {
+        self.run(|simulated| {
+            let (session, rng) = simulated.into_session()?;
+            party(session, rng)
+        })
+    }
+}
+
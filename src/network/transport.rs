@@ -0,0 +1,557 @@
+//! # 全连接 TCP 传输 (Full-mesh TCP Transport)
+//!
+//! 每对节点之间保持一条 TCP 连接：ID 较大的一方主动连接，ID 较小的一方接受连接。
+//! 连接建立时双方交换参与方 ID 和会话 ID，会话不一致时拒绝连接。
+//!
+//! 协议按轮同步执行：每一轮每个节点向其他每个节点恰好发送一条消息。消息以
+//! `NetworkMessage` 封装，带逻辑时钟消息头，按 4 字节大端长度前缀分帧；接收方检查
+//! 消息类型、发送方和轮次，任何不一致都视为协议错误。
+//!
+//! 同步轮的接口由 `RoundTransport` 描述，`MpcSession` 只依赖这个接口。
+//! 除 `MeshTransport` 外，`BlockingTransport` 把任意异步 `Transport`
+//! （P2P 节点、HTTP 长轮询或测试用的 `InMemoryTransport`）包装成同步轮。
+
+use crate::network::common::{NetworkError, PartyId, Transport, TransportFuture};
+use crate::network::protocol::NetworkMessage;
+use crate::protocols::clock::{LogicalClock, MessageHeader};
+use crate::protocols::session::SessionId;
+use crate::protocols::ProtocolStats;
+use crate::{MpcError, Result};
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
+use tokio::sync::{mpsc, Mutex};
+
+/// 单帧的最大长度
+const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// 握手消息长度：8 字节参与方 ID 和 32 字节会话 ID
+const HELLO_SIZE: usize = 8 + 32;
+
+/// 与一个对等节点之间的连接
+#[derive(Debug)]
+struct PeerLink {
+    reader: TcpStream,
+    writer: TcpStream,
+    /// 观察该节点消息头的逻辑时钟
+    clock: LogicalClock,
+}
+
+/// 全连接传输
+#[derive(Debug)]
+pub struct MeshTransport {
+    party_id: usize,
+    session: SessionId,
+    clock: LogicalClock,
+    peers: BTreeMap<usize, PeerLink>,
+}
+
+impl MeshTransport {
+    /// 与名单中的全部参与方建立连接
+    ///
+    /// # 参数
+    /// - `party_id`: 本节点的参与方 ID，即在 `addresses` 中的位置
+    /// - `addresses`: 按参与方 ID 排列的监听地址
+    /// - `timeout`: 建立连接和单次收发的超时时间
+    /// - `session`: 会话 ID，所有节点必须一致
+    ///
+    /// # 返回值
+    /// 在超时时间内与所有参与方完成握手时返回传输，否则返回网络错误
+    pub fn connect_to(party_id: usize, addresses: &[String], timeout: Duration, session: SessionId) -> Result<Self> {
+        let own_address = addresses.get(party_id)
+            .ok_or_else(|| MpcError::ProtocolError(format!("Party ID {} is not a member", party_id)))?;
+        let deadline = Instant::now() + timeout;
+        let listener = TcpListener::bind(own_address)
+            .map_err(|e| network_error("bind", own_address, e))?;
+        listener.set_nonblocking(true).map_err(|e| network_error("bind", own_address, e))?;
+
+        let mut streams = BTreeMap::new();
+        for (expected, address) in addresses[..party_id].iter().enumerate() {
+            let mut stream = connect_with_retry(address, deadline)?;
+            prepare_stream(&stream, timeout)?;
+            write_hello(&mut stream, party_id, &session)?;
+            let peer = read_hello(&mut stream, &session)?;
+            if peer != expected {
+                return Err(MpcError::NetworkError(format!(
+                    "Expected party {} at {}, found party {}", expected, address, peer
+                )));
+            }
+            streams.insert(peer, stream);
+        }
+
+        while streams.len() + 1 < addresses.len() {
+            let mut stream = accept_before(&listener, deadline)?;
+            prepare_stream(&stream, timeout)?;
+            let peer = read_hello(&mut stream, &session)?;
+            if peer <= party_id || peer >= addresses.len() || streams.contains_key(&peer) {
+                return Err(MpcError::NetworkError(format!("Unexpected connection from party {}", peer)));
+            }
+            write_hello(&mut stream, party_id, &session)?;
+            streams.insert(peer, stream);
+        }
+
+        let peers = streams.into_iter()
+            .map(|(peer, stream)| {
+                let writer = stream.try_clone().map_err(|e| MpcError::NetworkError(e.to_string()))?;
+                Ok((peer, PeerLink { reader: stream, writer, clock: LogicalClock::new() }))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            party_id,
+            session,
+            clock: LogicalClock::new(),
+            peers,
+        })
+    }
+
+    /// 本节点的参与方 ID
+    pub fn party_id(&self) -> usize {
+        self.party_id
+    }
+
+    /// 已连接的对等节点 ID
+    pub fn peer_ids(&self) -> Vec<usize> {
+        self.peers.keys().copied().collect()
+    }
+
+    /// 执行一轮点对点交换
+    ///
+    /// # 参数
+    /// - `label`: 本轮的消息类型，各方必须一致
+    /// - `outgoing`: 发给每个对等节点的载荷
+    /// - `stats`: 记录轮数和收发字节数
+    ///
+    /// # 返回值
+    /// 每个对等节点发来的载荷
+    pub fn exchange(
+        &mut self,
+        label: &str,
+        outgoing: &BTreeMap<usize, Vec<u8>>,
+        stats: &mut ProtocolStats,
+    ) -> Result<BTreeMap<usize, Vec<u8>>> {
+        if outgoing.len() != self.peers.len() || !self.peers.keys().all(|peer| outgoing.contains_key(peer)) {
+            return Err(MpcError::ProtocolError(format!("Round {} must address every peer exactly once", label)));
+        }
+
+        let header = self.clock.stamp(self.session);
+        let frames = outgoing.iter()
+            .map(|(&peer, payload)| {
+                let message = NetworkMessage::new(label, payload)
+                    .with_sender(self.party_id.to_string())
+                    .with_receiver(peer.to_string())
+                    .with_logical_clock(&header);
+                let frame = message.serialize().map_err(|e| MpcError::SerializationError(e.to_string()))?;
+                Ok((peer, frame))
+            })
+            .collect::<Result<BTreeMap<_, _>>>()?;
+
+        // 发送在独立线程中进行，避免双方同时发送大消息时互相阻塞
+        let received = thread::scope(|scope| {
+            let mut readers = Vec::with_capacity(self.peers.len());
+            let writers: Vec<_> = self.peers.iter_mut()
+                .map(|(&peer, PeerLink { reader, writer, clock })| {
+                    readers.push((peer, reader, clock));
+                    let frame = &frames[&peer];
+                    scope.spawn(move || write_frame(writer, frame))
+                })
+                .collect();
+
+            let mut received = BTreeMap::new();
+            for (peer, reader, clock) in readers {
+                let frame = read_frame(reader)?;
+                stats.record_received(frame.len() as u64 + 4);
+                let message = NetworkMessage::deserialize(&frame)
+                    .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+                check_message(&message, label, peer, &header, clock)?;
+                received.insert(peer, message.payload);
+            }
+
+            for writer in writers {
+                writer.join().map_err(|_| MpcError::NetworkError("Writer thread panicked".to_string()))??;
+            }
+            Ok::<_, MpcError>(received)
+        })?;
+
+        stats.record_rounds(1);
+        stats.record_sent(frames.values().map(|frame| frame.len() as u64 + 4).sum());
+        Ok(received)
+    }
+
+    /// 向所有对等节点发送同一载荷
+    pub fn broadcast(
+        &mut self,
+        label: &str,
+        payload: &[u8],
+        stats: &mut ProtocolStats,
+    ) -> Result<BTreeMap<usize, Vec<u8>>> {
+        let outgoing = self.peers.keys().map(|&peer| (peer, payload.to_vec())).collect();
+        self.exchange(label, &outgoing, stats)
+    }
+}
+
+/// 按同步轮交换消息的传输
+///
+/// 每一轮每个参与方向其他每个参与方恰好发送一条消息，并阻塞到收齐本轮全部消息。
+pub trait RoundTransport {
+    /// 本节点的参与方 ID
+    fn party_id(&self) -> usize;
+
+    /// 对等节点 ID，升序排列
+    fn peer_ids(&self) -> Vec<usize>;
+
+    /// 执行一轮点对点交换
+    ///
+    /// # 参数
+    /// - `label`: 本轮的消息类型，各方必须一致
+    /// - `outgoing`: 发给每个对等节点的载荷
+    /// - `stats`: 记录轮数和收发字节数
+    ///
+    /// # 返回值
+    /// 每个对等节点发来的载荷
+    fn exchange(
+        &mut self,
+        label: &str,
+        outgoing: &BTreeMap<usize, Vec<u8>>,
+        stats: &mut ProtocolStats,
+    ) -> Result<BTreeMap<usize, Vec<u8>>>;
+
+    /// 向所有对等节点发送同一载荷
+    fn broadcast(
+        &mut self,
+        label: &str,
+        payload: &[u8],
+        stats: &mut ProtocolStats,
+    ) -> Result<BTreeMap<usize, Vec<u8>>> {
+        let outgoing = self.peer_ids().into_iter().map(|peer| (peer, payload.to_vec())).collect();
+        self.exchange(label, &outgoing, stats)
+    }
+}
+
+impl RoundTransport for MeshTransport {
+    fn party_id(&self) -> usize {
+        MeshTransport::party_id(self)
+    }
+
+    fn peer_ids(&self) -> Vec<usize> {
+        MeshTransport::peer_ids(self)
+    }
+
+    fn exchange(
+        &mut self,
+        label: &str,
+        outgoing: &BTreeMap<usize, Vec<u8>>,
+        stats: &mut ProtocolStats,
+    ) -> Result<BTreeMap<usize, Vec<u8>>> {
+        MeshTransport::exchange(self, label, outgoing, stats)
+    }
+}
+
+/// 进程内的异步传输，用于测试
+///
+/// `network(n)` 创建 n 个两两相连的参与方，消息通过无界通道传递，不经过序列化。
+#[derive(Debug)]
+pub struct InMemoryTransport {
+    party_id: PartyId,
+    parties: Vec<PartyId>,
+    senders: BTreeMap<PartyId, mpsc::UnboundedSender<(PartyId, NetworkMessage)>>,
+    incoming: Mutex<mpsc::UnboundedReceiver<(PartyId, NetworkMessage)>>,
+}
+
+impl InMemoryTransport {
+    /// 创建 n 个两两相连的参与方，第 i 个元素的参与方 ID 为 i
+    pub fn network(n: usize) -> Vec<Self> {
+        let (senders, receivers): (BTreeMap<_, _>, Vec<_>) = (0..n)
+            .map(|party| {
+                let (tx, rx) = mpsc::unbounded_channel();
+                ((party, tx), rx)
+            })
+            .unzip();
+        receivers.into_iter()
+            .enumerate()
+            .map(|(party_id, incoming)| Self {
+                party_id,
+                parties: (0..n).collect(),
+                senders: senders.clone(),
+                incoming: Mutex::new(incoming),
+            })
+            .collect()
+    }
+}
+
+impl Transport for InMemoryTransport {
+    fn party_id(&self) -> PartyId {
+        self.party_id
+    }
+
+    fn parties(&self) -> Vec<PartyId> {
+        self.parties.clone()
+    }
+
+    fn send(&self, to: PartyId, message: NetworkMessage) -> TransportFuture<'_, ()> {
+        Box::pin(async move {
+            let sender = self.senders.get(&to)
+                .ok_or_else(|| NetworkError::PeerNotFound(to.to_string()))?;
+            sender.send((self.party_id, message))
+                .map_err(|_| NetworkError::PeerNotAvailable(to.to_string()))
+        })
+    }
+
+    fn recv(&self) -> TransportFuture<'_, (PartyId, NetworkMessage)> {
+        Box::pin(async move {
+            self.incoming.lock().await.recv().await
+                .ok_or_else(|| NetworkError::ChannelError("所有发送方已关闭".to_string()))
+        })
+    }
+}
+
+/// 把异步 `Transport` 包装成同步轮
+///
+/// 每轮的消息以轮标签为消息类型并带轮次消息头；提前到达的后续轮次消息先缓存起来，
+/// 过期、重复或发送方不符的消息视为协议错误。`exchange` 在给定的 tokio 运行时上阻塞等待，
+/// 不能在异步任务中直接调用，应放入 `spawn_blocking`。
+#[derive(Debug)]
+pub struct BlockingTransport<T> {
+    inner: T,
+    handle: Handle,
+    timeout: Duration,
+    round: u64,
+    /// 已收到但属于后续轮次的消息
+    pending: VecDeque<(PartyId, NetworkMessage)>,
+}
+
+impl<T: Transport> BlockingTransport<T> {
+    /// 创建同步轮传输
+    ///
+    /// # 参数
+    /// - `inner`: 底层异步传输
+    /// - `handle`: 驱动底层传输的 tokio 运行时
+    /// - `timeout`: 每轮等待其他参与方消息的超时时间
+    pub fn new(inner: T, handle: Handle, timeout: Duration) -> Self {
+        Self {
+            inner,
+            handle,
+            timeout,
+            round: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// 底层异步传输
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// 已完成的轮数
+    pub fn round(&self) -> u64 {
+        self.round
+    }
+}
+
+impl<T: Transport> RoundTransport for BlockingTransport<T> {
+    fn party_id(&self) -> usize {
+        self.inner.party_id()
+    }
+
+    fn peer_ids(&self) -> Vec<usize> {
+        self.inner.peers()
+    }
+
+    fn exchange(
+        &mut self,
+        label: &str,
+        outgoing: &BTreeMap<usize, Vec<u8>>,
+        stats: &mut ProtocolStats,
+    ) -> Result<BTreeMap<usize, Vec<u8>>> {
+        let peers = self.peer_ids();
+        if outgoing.len() != peers.len() || !peers.iter().all(|peer| outgoing.contains_key(peer)) {
+            return Err(MpcError::ProtocolError(format!("Round {} must address every peer exactly once", label)));
+        }
+
+        let round = self.round + 1;
+        let party_id = self.party_id();
+        let messages: Vec<_> = outgoing.iter()
+            .map(|(&peer, payload)| {
+                let message = NetworkMessage::new(label, payload)
+                    .with_sender(party_id.to_string())
+                    .with_receiver(peer.to_string())
+                    .with_round(round);
+                (peer, message)
+            })
+            .collect();
+        let sent: u64 = outgoing.values().map(|payload| payload.len() as u64).sum();
+
+        let pending = std::mem::take(&mut self.pending);
+        let inner = &self.inner;
+        let wait = self.timeout;
+        let received = self.handle.block_on(async {
+            for (peer, message) in messages {
+                inner.send(peer, message).await.map_err(to_mpc_error)?;
+            }
+
+            let mut received = BTreeMap::new();
+            let mut later = Vec::new();
+            let mut accept = |from: PartyId, message: NetworkMessage, received: &mut BTreeMap<usize, NetworkMessage>| {
+                let sent_round = message_round(from, &message)?;
+                if sent_round > round {
+                    later.push((from, message));
+                    return Ok(());
+                }
+                if sent_round < round || received.contains_key(&from) {
+                    return Err(MpcError::ProtocolError(format!(
+                        "Party {} sent a stale or duplicate message for round {}", from, sent_round
+                    )));
+                }
+                if message.message_type != label {
+                    return Err(MpcError::ProtocolError(format!(
+                        "Expected {} from party {}, received {}", label, from, message.message_type
+                    )));
+                }
+                received.insert(from, message);
+                Ok(())
+            };
+
+            for (from, message) in pending {
+                accept(from, message, &mut received)?;
+            }
+            while received.len() < peers.len() {
+                let (from, message) = inner.recv_timeout(wait).await.map_err(to_mpc_error)?;
+                if !peers.contains(&from) {
+                    return Err(MpcError::ProtocolError(format!("Message from unknown party {}", from)));
+                }
+                accept(from, message, &mut received)?;
+            }
+            Ok::<_, MpcError>((received, later))
+        });
+        let (received, later) = received?;
+        self.pending.extend(later);
+        self.round = round;
+
+        stats.record_rounds(1);
+        stats.record_sent(sent);
+        stats.record_received(received.values().map(|message| message.payload.len() as u64).sum());
+        Ok(received.into_iter().map(|(peer, message)| (peer, message.payload)).collect())
+    }
+}
+
+/// 检查发送方并返回消息的轮次
+fn message_round(from: PartyId, message: &NetworkMessage) -> Result<u64> {
+    if message.sender_id.as_deref() != Some(from.to_string().as_str()) {
+        return Err(MpcError::ProtocolError(format!("Message from party {} has wrong sender", from)));
+    }
+    message.round()
+        .map_err(|e| MpcError::ProtocolError(e.to_string()))?
+        .ok_or_else(|| MpcError::ProtocolError(format!("Message from party {} has no round", from)))
+}
+
+fn to_mpc_error(error: NetworkError) -> MpcError {
+    MpcError::NetworkError(error.to_string())
+}
+
+fn network_error(action: &str, address: &str, error: std::io::Error) -> MpcError {
+    MpcError::NetworkError(format!("Failed to {} {}: {}", action, address, error))
+}
+
+fn connect_with_retry(address: &str, deadline: Instant) -> Result<TcpStream> {
+    loop {
+        match TcpStream::connect(address) {
+            Ok(stream) => return Ok(stream),
+            Err(e) if Instant::now() >= deadline => return Err(network_error("connect to", address, e)),
+            Err(_) => thread::sleep(Duration::from_millis(50)),
+        }
+    }
+}
+
+fn accept_before(listener: &TcpListener, deadline: Instant) -> Result<TcpStream> {
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => return Ok(stream),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                if Instant::now() >= deadline {
+                    return Err(MpcError::NetworkError("Timed out waiting for peers to connect".to_string()));
+                }
+                thread::sleep(Duration::from_millis(20));
+            }
+            Err(e) => return Err(MpcError::NetworkError(format!("Accept failed: {}", e))),
+        }
+    }
+}
+
+fn prepare_stream(stream: &TcpStream, timeout: Duration) -> Result<()> {
+    let io = |e: std::io::Error| MpcError::NetworkError(e.to_string());
+    // 非阻塞监听器接受的连接在部分平台上继承非阻塞模式
+    stream.set_nonblocking(false).map_err(io)?;
+    stream.set_nodelay(true).map_err(io)?;
+    stream.set_read_timeout(Some(timeout)).map_err(io)?;
+    stream.set_write_timeout(Some(timeout)).map_err(io)
+}
+
+fn write_hello(stream: &mut TcpStream, party_id: usize, session: &SessionId) -> Result<()> {
+    let mut hello = (party_id as u64).to_be_bytes().to_vec();
+    hello.extend_from_slice(session.as_bytes());
+    stream.write_all(&hello).map_err(|e| MpcError::NetworkError(format!("Handshake failed: {}", e)))
+}
+
+fn read_hello(stream: &mut TcpStream, session: &SessionId) -> Result<usize> {
+    let mut hello = [0u8; HELLO_SIZE];
+    stream.read_exact(&mut hello).map_err(|e| MpcError::NetworkError(format!("Handshake failed: {}", e)))?;
+    if &hello[8..] != session.as_bytes() {
+        return Err(MpcError::NetworkError("Peer joined a different session".to_string()));
+    }
+    let mut party_id = [0u8; 8];
+    party_id.copy_from_slice(&hello[..8]);
+    Ok(u64::from_be_bytes(party_id) as usize)
+}
+
+fn write_frame(stream: &mut TcpStream, frame: &[u8]) -> Result<()> {
+    let io = |e: std::io::Error| MpcError::NetworkError(format!("Send failed: {}", e));
+    stream.write_all(&(frame.len() as u32).to_be_bytes()).map_err(io)?;
+    stream.write_all(frame).map_err(io)
+}
+
+fn read_frame(stream: &mut TcpStream) -> Result<Vec<u8>> {
+    let io = |e: std::io::Error| MpcError::NetworkError(format!("Receive failed: {}", e));
+    let mut length = [0u8; 4];
+    stream.read_exact(&mut length).map_err(io)?;
+    let length = u32::from_be_bytes(length) as usize;
+    if length > MAX_FRAME_SIZE {
+        return Err(MpcError::NetworkError(format!("Frame of {} bytes exceeds limit", length)));
+    }
+    let mut frame = vec![0u8; length];
+    stream.read_exact(&mut frame).map_err(io)?;
+    Ok(frame)
+}
+
+fn check_message(
+    message: &NetworkMessage,
+    label: &str,
+    peer: usize,
+    expected: &MessageHeader,
+    clock: &mut LogicalClock,
+) -> Result<()> {
+    message.validate().map_err(|e| MpcError::ProtocolError(e.to_string()))?;
+    if message.message_type != label {
+        return Err(MpcError::ProtocolError(format!(
+            "Expected {} from party {}, received {}", label, peer, message.message_type
+        )));
+    }
+    if message.sender_id.as_deref() != Some(peer.to_string().as_str()) {
+        return Err(MpcError::ProtocolError(format!("Message on link to party {} has wrong sender", peer)));
+    }
+
+    let header = message.logical_clock()
+        .map_err(|e| MpcError::ProtocolError(e.to_string()))?
+        .ok_or_else(|| MpcError::ProtocolError(format!("Message from party {} has no logical clock", peer)))?;
+    if header.session != expected.session {
+        return Err(MpcError::ProtocolError(format!("Message from party {} belongs to another session", peer)));
+    }
+    clock.observe(&header)?;
+    if header.round != expected.round {
+        return Err(MpcError::ProtocolError(format!(
+            "Party {} is at round {}, expected round {}", peer, header.round, expected.round
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,161 @@
+//! 节点配置与身份生成
+
+use crate::elliptic_curve::{Ed25519, Ed25519PublicKey, Ed25519SecretKey};
+use crate::network::session::reserve_local_addresses;
+use crate::secret_sharing::validate_field_element;
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// 集群成员：参与方 ID、监听地址和签名公钥
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterMember {
+    /// 参与方 ID（从 0 开始，Shamir 横坐标为 ID + 1）
+    pub party_id: usize,
+    /// 监听地址，例如 `127.0.0.1:9000`
+    pub address: String,
+    /// 输出认证使用的签名公钥
+    pub public_key: Ed25519PublicKey,
+}
+
+/// 单个节点进程的配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeConfig {
+    /// 本节点的参与方 ID
+    pub party_id: usize,
+    /// 会话名称，所有节点必须一致
+    pub session: String,
+    /// 可容忍的合谋节点数量 t，需要 n ≥ 2t + 1
+    pub collusion_threshold: usize,
+    /// 全部成员，按参与方 ID 排列
+    pub members: Vec<ClusterMember>,
+    /// 本节点的签名私钥
+    pub signing_key: Ed25519SecretKey,
+    /// 本节点的私有输入
+    pub input: u64,
+    /// 离线阶段生成的三元组数量，至少为节点数量
+    pub triples: usize,
+    /// 预处理池的存储目录，为空时保存在内存中
+    pub data_dir: Option<PathBuf>,
+    /// 建立连接和等待单轮消息的超时（毫秒）
+    pub timeout_ms: u64,
+}
+
+impl NodeConfig {
+    /// 从 JSON 文件读取配置
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let data = fs::read(path)
+            .map_err(|e| MpcError::StorageError(format!("Failed to read {}: {}", path.display(), e)))?;
+        let config: Self = serde_json::from_slice(&data)
+            .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// 以 JSON 格式写入文件
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let data = serde_json::to_vec_pretty(self)
+            .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+        fs::write(path, data)
+            .map_err(|e| MpcError::StorageError(format!("Failed to write {}: {}", path.display(), e)))
+    }
+
+    /// 检查配置的一致性
+    pub fn validate(&self) -> Result<()> {
+        let n = self.members.len();
+        if self.collusion_threshold == 0 || n < 2 * self.collusion_threshold + 1 {
+            return Err(MpcError::InvalidThreshold);
+        }
+        if let Some(position) = self.members.iter().enumerate().position(|(i, member)| member.party_id != i) {
+            return Err(MpcError::ProtocolError(format!(
+                "Member at position {} has party ID {}", position, self.members[position].party_id
+            )));
+        }
+        if self.party_id >= n {
+            return Err(MpcError::ProtocolError(format!("Party ID {} is not a member", self.party_id)));
+        }
+        if !validate_field_element(self.input) {
+            return Err(MpcError::ProtocolError("Input is not a field element".to_string()));
+        }
+        if self.triples < n {
+            return Err(MpcError::ProtocolError(format!(
+                "At least {} triples are required, {} configured", n, self.triples
+            )));
+        }
+        Ok(())
+    }
+
+    /// 节点数量
+    pub fn party_count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// 本节点的成员信息
+    pub fn member(&self) -> &ClusterMember {
+        &self.members[self.party_id]
+    }
+
+    /// 外部验证输出认证时使用的公钥名单
+    pub fn roster(&self) -> HashMap<usize, Ed25519PublicKey> {
+        self.members.iter()
+            .map(|member| (member.party_id, member.public_key))
+            .collect()
+    }
+
+    /// 超时时间
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+}
+
+/// 为本机上的 n 个节点生成配置
+///
+/// 每个节点生成新的签名密钥，监听地址使用操作系统分配的空闲端口。
+///
+/// # 参数
+/// - `session`: 会话名称
+/// - `inputs`: 各节点的私有输入，长度即节点数量
+/// - `collusion_threshold`: 可容忍的合谋节点数量 t
+///
+/// # 返回值
+/// 按参与方 ID 排列的配置；三元组数量默认等于节点数量，超时默认 30 秒
+pub fn generate_cluster(session: &str, inputs: &[u64], collusion_threshold: usize) -> Result<Vec<NodeConfig>> {
+    let n = inputs.len();
+    if collusion_threshold == 0 || n < 2 * collusion_threshold + 1 {
+        return Err(MpcError::InvalidThreshold);
+    }
+
+    let addresses = reserve_local_addresses(n)?;
+
+    let keypairs: Vec<_> = (0..n).map(|_| Ed25519::generate_keypair()).collect();
+    let members: Vec<ClusterMember> = addresses.into_iter()
+        .zip(&keypairs)
+        .enumerate()
+        .map(|(party_id, (address, (_, public_key)))| ClusterMember {
+            party_id,
+            address,
+            public_key: *public_key,
+        })
+        .collect();
+
+    Ok(keypairs.into_iter()
+        .zip(inputs)
+        .enumerate()
+        .map(|(party_id, ((signing_key, _), &input))| NodeConfig {
+            party_id,
+            session: session.to_string(),
+            collusion_threshold,
+            members: members.clone(),
+            signing_key,
+            input,
+            triples: n,
+            data_dir: None,
+            timeout_ms: 30_000,
+        })
+        .collect())
+}
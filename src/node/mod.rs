@@ -0,0 +1,50 @@
+//! # 多进程节点 (Multi-process Nodes)
+//!
+//! 本模块把各个协议组件串成一个可以独立运行的节点进程：每个节点读取自己的配置，
+//! 通过 TCP 与其他节点建立全连接，然后依次执行完整的流水线：
+//!
+//! 1. **分布式密钥生成 (DKG)**: 门限 BFV 密钥生成，各节点得到相同的公钥
+//! 2. **预处理**: 诚实多数（n ≥ 2t + 1）下 BGW 方式联合生成 Beaver 三元组，
+//!    存入 `PreprocessingPool`
+//! 3. **在线计算**: 各节点分享私有输入，计算输入之和与平方和并打开
+//! 4. **输出认证**: 各节点对结果联合签名，外部验证者只需公钥名单即可验证
+//!
+//! 节点输出 `NodeReport`，其中包含结果、认证输出以及每个阶段的 `ProtocolStats`。
+//! `mpc_node` 可执行文件包装了 `run_node`，集成测试用它在本机启动 N 个真实的
+//! 节点进程，检查结果和统计信息，防止网络协议栈回归。
+//!
+//! 当前实现针对半诚实敌手：打开时检查分享的一致性，但不验证预处理材料。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::node::*;
+//! use std::thread;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let inputs = [3, 5, 9];
+//! let configs = generate_cluster("doc-example", &inputs, 1)?;
+//!
+//! // 示例中用线程代替进程
+//! let handles: Vec<_> = configs.into_iter()
+//!     .map(|config| thread::spawn(move || run_node(&config)))
+//!     .collect();
+//! let reports = handles.into_iter()
+//!     .map(|handle| handle.join().unwrap())
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//!
+//! for report in &reports {
+//!     assert_eq!(report.output, AggregateOutput::compute(&inputs));
+//!     assert_eq!(report.public_key_digest, reports[0].public_key_digest);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod config;
+pub mod transport;
+pub mod pipeline;
+
+pub use config::*;
+pub use transport::*;
+pub use pipeline::*;
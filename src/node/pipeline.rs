@@ -0,0 +1,410 @@
+//! 节点流水线：分布式密钥生成 → 预处理 → 在线计算 → 输出认证
+//!
+//! 所有阶段都通过 `MeshTransport` 与其他节点进程交换消息，共享使用 t 次 Shamir
+//! 多项式（横坐标为参与方 ID + 1），打开时检查全部分享落在同一个 t 次多项式上。
+
+use super::{MeshTransport, NodeConfig};
+use crate::beaver_triples::bfv_based::BFVParams;
+use crate::beaver_triples::threshold_keygen::{KeyGenContribution, ThresholdBFVKeyGen};
+use crate::beaver_triples::{BeaverTriple, CompleteBeaverTriple, MaterialStamp, PreprocessingPool};
+use crate::commitment::{MessageCommitment, MessageOpening};
+use crate::network::ConnectionStats;
+use crate::oblivious_transfer::SecurityModel;
+use crate::protocols::output_certification::{combine_randomness, CertifiedOutput, OutputCertifier, OutputSignature};
+use crate::protocols::session::{ProtocolSession, SessionId};
+use crate::protocols::{ProtocolStats, StatsRecorder};
+use crate::secret_sharing::{
+    field_add, field_inner_product, field_mul, field_sub, SecretSharing, ShamirSecretSharing, Share,
+};
+use crate::storage::{FileBackend, MemoryBackend, StorageBackend};
+use crate::utils::metrics::MetricsRegistry;
+use crate::utils::{hash_struct_with_domain, random_field_element};
+use crate::{MpcError, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// 分布式密钥生成阶段
+pub const DKG_STAGE: &str = "dkg";
+/// 三元组预处理阶段
+pub const PREPROCESSING_STAGE: &str = "preprocessing";
+/// 在线计算阶段
+pub const ONLINE_STAGE: &str = "online";
+/// 输出认证阶段
+pub const CERTIFICATION_STAGE: &str = "certification";
+
+/// 公开的计算结果：输入个数、输入之和与平方和
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregateOutput {
+    /// 输入个数
+    pub count: u64,
+    /// 输入之和
+    pub sum: u64,
+    /// 输入的平方和
+    pub sum_of_squares: u64,
+}
+
+impl AggregateOutput {
+    /// 在明文上计算，用于核对协议输出
+    pub fn compute(inputs: &[u64]) -> Self {
+        inputs.iter().fold(
+            Self { count: inputs.len() as u64, sum: 0, sum_of_squares: 0 },
+            |acc, &x| Self {
+                sum: field_add(acc.sum, x),
+                sum_of_squares: field_add(acc.sum_of_squares, field_mul(x, x)),
+                ..acc
+            },
+        )
+    }
+}
+
+/// 单个阶段的执行统计
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageReport {
+    /// 阶段名称
+    pub stage: String,
+    /// 执行统计
+    pub stats: ProtocolStats,
+}
+
+/// 节点执行完流水线后输出的报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeReport {
+    /// 参与方 ID
+    pub party_id: usize,
+    /// 会话 ID
+    pub session_id: SessionId,
+    /// 分布式生成的公钥摘要，所有节点一致
+    pub public_key_digest: [u8; 32],
+    /// 计算结果
+    pub output: AggregateOutput,
+    /// 带全部节点签名的认证输出
+    pub certificate: CertifiedOutput<AggregateOutput>,
+    /// 各阶段的执行统计，按执行顺序排列
+    pub stages: Vec<StageReport>,
+    /// 汇总后的连接统计
+    pub metrics: ConnectionStats,
+    /// 预处理池中剩余的三元组数量
+    pub triples_remaining: usize,
+}
+
+impl NodeReport {
+    /// 按名称查找阶段统计
+    pub fn stage(&self, name: &str) -> Option<&ProtocolStats> {
+        self.stages.iter().find(|stage| stage.stage == name).map(|stage| &stage.stats)
+    }
+}
+
+/// 执行完整的流水线
+///
+/// 与配置中的其他节点建立连接后依次执行：
+///
+/// 1. **分布式密钥生成**: 交换门限 BFV 密钥生成贡献，各节点得到相同的公钥
+/// 2. **预处理**: BGW 方式联合生成 Beaver 三元组并存入预处理池
+/// 3. **在线计算**: 分享私有输入，用三元组计算输入之和与平方和并打开
+/// 4. **输出认证**: 联合随机数、承诺并签名，得到可外部验证的认证输出
+///
+/// # 参数
+/// - `config`: 节点配置
+///
+/// # 返回值
+/// 返回节点报告；任何节点偏离协议或超时时返回错误
+pub fn run_node(config: &NodeConfig) -> Result<NodeReport> {
+    config.validate()?;
+    let session = ProtocolSession::root("mpc_api/node", config.session.as_bytes());
+    let transport = MeshTransport::connect(config, session.id())?;
+    let backend: Arc<dyn StorageBackend> = match &config.data_dir {
+        Some(dir) => Arc::new(FileBackend::open(dir)?),
+        None => Arc::new(MemoryBackend::new()),
+    };
+    let pool = PreprocessingPool::new(backend, &config.session);
+
+    let points: Vec<u64> = (1..=config.party_count() as u64).collect();
+    let mut node = NodeContext {
+        config,
+        transport,
+        threshold: config.collusion_threshold + 1,
+        lagrange: ShamirSecretSharing::new().precompute_lagrange_coefficients(&points)?,
+    };
+
+    let mut stages = Vec::with_capacity(4);
+    let public_key_digest = run_stage(&mut stages, DKG_STAGE, |stats| node.distributed_keygen(stats))?;
+    run_stage(&mut stages, PREPROCESSING_STAGE, |stats| node.preprocess(&pool, stats))?;
+    let output = run_stage(&mut stages, ONLINE_STAGE, |stats| node.compute(&pool, stats))?;
+    let certificate = run_stage(&mut stages, CERTIFICATION_STAGE, |stats| node.certify(&output, stats))?;
+
+    let mut metrics = ConnectionStats {
+        p2p_connections: config.party_count() - 1,
+        active_sessions: 1,
+        ..ConnectionStats::default()
+    };
+    for stage in &stages {
+        metrics.record_protocol_stats(&stage.stats);
+        stage.stats.record_metrics(MetricsRegistry::global(), &stage.stage)?;
+    }
+
+    Ok(NodeReport {
+        party_id: config.party_id,
+        session_id: session.id(),
+        public_key_digest,
+        output,
+        certificate,
+        stages,
+        metrics,
+        triples_remaining: pool.len()?,
+    })
+}
+
+fn run_stage<T>(
+    stages: &mut Vec<StageReport>,
+    name: &str,
+    stage: impl FnOnce(&mut ProtocolStats) -> Result<T>,
+) -> Result<T> {
+    let mut recorder = StatsRecorder::start();
+    let result = stage(recorder.stats_mut())?;
+    let (result, stats) = recorder.finish(result).into_parts();
+    stages.push(StageReport { stage: name.to_string(), stats });
+    Ok(result)
+}
+
+struct NodeContext<'a> {
+    config: &'a NodeConfig,
+    transport: MeshTransport,
+    /// 重构所需的分享数量 t + 1
+    threshold: usize,
+    /// 全部节点在 0 处插值的拉格朗日系数
+    lagrange: Vec<u64>,
+}
+
+impl NodeContext<'_> {
+    fn party_id(&self) -> usize {
+        self.config.party_id
+    }
+
+    fn party_count(&self) -> usize {
+        self.config.party_count()
+    }
+
+    fn distributed_keygen(&mut self, stats: &mut ProtocolStats) -> Result<[u8; 32]> {
+        let mut keygen = ThresholdBFVKeyGen::new(
+            self.party_count(), self.threshold, self.party_id(), BFVParams::default(),
+        )?;
+        let contribution = keygen.generate_contribution()?;
+        let contributions: Vec<KeyGenContribution> = self.broadcast_value("dkg/contribution", &contribution, stats)?;
+        for (party, contribution) in contributions.into_iter().enumerate() {
+            if contribution.party_id != party {
+                return Err(MpcError::ProtocolError(format!(
+                    "Party {} sent a contribution for party {}", party, contribution.party_id
+                )));
+            }
+            if party != self.party_id() {
+                keygen.add_contribution(contribution)?;
+            }
+        }
+
+        let (public_key, _secret_key_share) = keygen.generate_keypair()?;
+        // b 中含有各节点独立采样的噪声，只有 a 由全部贡献唯一确定
+        hash_struct_with_domain(
+            b"mpc_api/node/public_key",
+            &(&public_key.a, public_key.n, public_key.q, public_key.t),
+        )
+    }
+
+    fn preprocess(&mut self, pool: &PreprocessingPool, stats: &mut ProtocolStats) -> Result<()> {
+        let count = self.config.triples;
+
+        // 第 1 轮：每个节点分享随机的 a_i、b_i，各节点把收到的分享相加
+        let randomness: Vec<u64> = (0..2 * count).map(|_| random_field_element()).collect();
+        let dealt = self.deal("preprocessing/random", &randomness, stats)?;
+        let summed: Vec<u64> = (0..2 * count)
+            .map(|k| dealt.iter().fold(0, |acc, shares| field_add(acc, shares[k])))
+            .collect();
+        let (a, b) = summed.split_at(count);
+
+        // 第 2 轮：本地相乘得到 2t 次分享，重新分享后用拉格朗日系数降次
+        let products: Vec<u64> = a.iter().zip(b).map(|(&a, &b)| field_mul(a, b)).collect();
+        let reshared = self.deal("preprocessing/reshare", &products, stats)?;
+        let x = self.party_id() as u64 + 1;
+        let triples: Vec<CompleteBeaverTriple> = (0..count)
+            .map(|k| {
+                let column: Vec<u64> = reshared.iter().map(|shares| shares[k]).collect();
+                let c = field_inner_product(&column, &self.lagrange);
+                let triple = BeaverTriple::new(Share::new(x, a[k]), Share::new(x, b[k]), Share::new(x, c), k as u64);
+                CompleteBeaverTriple::new(HashMap::from([(self.party_id() + 1, triple)]))
+            })
+            .collect();
+        // 联合生成的三元组以会话为生成方；BGW 乘法只在半诚实模型下安全
+        let stamp = MaterialStamp::new(&format!("mpc_api/node/{}", self.config.session), SecurityModel::SemiHonest, 0);
+        pool.push(&triples, &stamp)
+    }
+
+    fn compute(&mut self, pool: &PreprocessingPool, stats: &mut ProtocolStats) -> Result<AggregateOutput> {
+        let n = self.party_count();
+
+        // 分享私有输入，inputs[j] 为节点 j 输入的分享
+        let inputs: Vec<u64> = self.deal("online/input", &[self.config.input], stats)?
+            .into_iter()
+            .map(|shares| shares[0])
+            .collect();
+
+        let triples = pool.take(n)?;
+        stats.record_preprocessing(n);
+        let triples = triples.iter()
+            .map(|triple| triple.get_share(self.party_id() + 1).cloned().ok_or_else(|| {
+                MpcError::ProtocolError("Preprocessing pool holds another party's triple".to_string())
+            }))
+            .collect::<Result<Vec<_>>>()?;
+
+        // 打开 d = x − a、e = x − b
+        let masked: Vec<u64> = inputs.iter()
+            .zip(&triples)
+            .flat_map(|(&x, triple)| [field_sub(x, triple.a.y), field_sub(x, triple.b.y)])
+            .collect();
+        let opened = self.open("online/masked", &masked, stats)?;
+
+        // [x²] = [c] + d·[b] + e·[a] + d·e
+        let mut sum = 0;
+        let mut sum_of_squares = 0;
+        for ((&x, triple), de) in inputs.iter().zip(&triples).zip(opened.chunks(2)) {
+            let (d, e) = (de[0], de[1]);
+            let square = field_add(
+                field_add(triple.c.y, field_mul(d, triple.b.y)),
+                field_add(field_mul(e, triple.a.y), field_mul(d, e)),
+            );
+            sum = field_add(sum, x);
+            sum_of_squares = field_add(sum_of_squares, square);
+        }
+
+        let outputs = self.open("online/output", &[sum, sum_of_squares], stats)?;
+        Ok(AggregateOutput {
+            count: n as u64,
+            sum: outputs[0],
+            sum_of_squares: outputs[1],
+        })
+    }
+
+    fn certify(&mut self, output: &AggregateOutput, stats: &mut ProtocolStats) -> Result<CertifiedOutput<AggregateOutput>> {
+        let session = self.config.session.clone();
+        let certifier = OutputCertifier::from_keypair(
+            self.party_id(), self.config.signing_key.clone(), self.config.member().public_key,
+        );
+
+        // 第 1、2 轮：先交换随机数贡献的承诺，再交换打开信息
+        let committed = certifier.contribute_randomness(&session)?;
+        let commitments: Vec<MessageCommitment> =
+            self.broadcast_value("certification/commit", committed.commitment(), stats)?;
+        let openings: Vec<MessageOpening<[u8; 32]>> =
+            self.broadcast_value("certification/open", &committed.open(), stats)?;
+        let contributions: Vec<_> = commitments.into_iter()
+            .zip(openings)
+            .enumerate()
+            .map(|(party, (commitment, opening))| (party, commitment, opening))
+            .collect();
+        let randomness = combine_randomness(&session, &contributions)?;
+
+        // 第 3 轮：交换对输出承诺的签名，所有节点的承诺必须一致
+        let (commitment, signature) = certifier.sign_output(&session, output, &randomness)?;
+        let signed: Vec<(MessageCommitment, OutputSignature)> =
+            self.broadcast_value("certification/sign", &(commitment.clone(), signature), stats)?;
+        let mut signatures = Vec::with_capacity(signed.len());
+        for (party, (peer_commitment, signature)) in signed.into_iter().enumerate() {
+            if peer_commitment != commitment || signature.party_id != party {
+                return Err(MpcError::ProtocolError(format!("Party {} certified a different output", party)));
+            }
+            signatures.push(signature);
+        }
+
+        let certificate = CertifiedOutput::assemble(&session, *output, &randomness, signatures)?;
+        certificate.verify(&self.config.roster(), self.party_count())?;
+        Ok(certificate)
+    }
+
+    /// 以 t 次多项式分享每个值
+    ///
+    /// # 返回值
+    /// 按发送方排列的分享：`result[j][k]` 为节点 j 分享的第 k 个值中属于本节点的分享
+    fn deal(&mut self, label: &str, values: &[u64], stats: &mut ProtocolStats) -> Result<Vec<Vec<u64>>> {
+        let n = self.party_count();
+        let mut per_recipient = vec![Vec::with_capacity(values.len()); n];
+        for value in values {
+            let shares = ShamirSecretSharing::share(value, self.threshold, n)?;
+            for (slot, share) in per_recipient.iter_mut().zip(&shares) {
+                slot.push(share.y);
+            }
+        }
+
+        let own = std::mem::take(&mut per_recipient[self.party_id()]);
+        let outgoing = per_recipient.into_iter()
+            .enumerate()
+            .filter(|&(party, _)| party != self.party_id())
+            .map(|(party, shares)| Ok((party, encode(&shares)?)))
+            .collect::<Result<_>>()?;
+        let received = self.transport.exchange(label, &outgoing, stats)?;
+        self.collect(label, received, own, |shares: &Vec<u64>| shares.len() == values.len())
+    }
+
+    /// 广播本节点的分享并重构
+    fn open(&mut self, label: &str, shares: &[u64], stats: &mut ProtocolStats) -> Result<Vec<u64>> {
+        let by_party: Vec<Vec<u64>> = self.broadcast_value(label, &shares.to_vec(), stats)?;
+        if by_party.iter().any(|received| received.len() != shares.len()) {
+            return Err(MpcError::ProtocolError(format!("Wrong number of shares in {}", label)));
+        }
+
+        let scheme = ShamirSecretSharing::new();
+        (0..shares.len())
+            .map(|k| {
+                let column: Vec<Share> = by_party.iter()
+                    .enumerate()
+                    .map(|(party, received)| Share::new(party as u64 + 1, received[k]))
+                    .collect();
+                if !scheme.verify_shares(&column, self.threshold) {
+                    return Err(MpcError::ProtocolError(format!("Inconsistent shares opened in {}", label)));
+                }
+                let values: Vec<u64> = column.iter().map(|share| share.y).collect();
+                Ok(field_inner_product(&values, &self.lagrange))
+            })
+            .collect()
+    }
+
+    /// 广播一个值，返回按发送方排列的全部值（包括本节点）
+    fn broadcast_value<T: Serialize + DeserializeOwned + Clone>(
+        &mut self,
+        label: &str,
+        value: &T,
+        stats: &mut ProtocolStats,
+    ) -> Result<Vec<T>> {
+        let received = self.transport.broadcast(label, &encode(value)?, stats)?;
+        self.collect(label, received, value.clone(), |_| true)
+    }
+
+    fn collect<T: DeserializeOwned>(
+        &self,
+        label: &str,
+        mut received: BTreeMap<usize, Vec<u8>>,
+        own: T,
+        well_formed: impl Fn(&T) -> bool,
+    ) -> Result<Vec<T>> {
+        let mut own = Some(own);
+        (0..self.party_count())
+            .map(|party| {
+                if party == self.party_id() {
+                    return own.take().ok_or_else(|| MpcError::ProtocolError("Duplicate local entry".to_string()));
+                }
+                let payload = received.remove(&party)
+                    .ok_or_else(|| MpcError::ProtocolError(format!("No {} message from party {}", label, party)))?;
+                let value: T = bincode::deserialize(&payload).map_err(|e| {
+                    MpcError::SerializationError(format!("Malformed {} message from party {}: {}", label, party, e))
+                })?;
+                if !well_formed(&value) {
+                    return Err(MpcError::ProtocolError(format!("Malformed {} message from party {}", label, party)));
+                }
+                Ok(value)
+            })
+            .collect()
+    }
+}
+
+fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    bincode::serialize(value).map_err(|e| MpcError::SerializationError(e.to_string()))
+}
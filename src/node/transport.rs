@@ -0,0 +1,24 @@
+//! 节点之间的全连接 TCP 传输
+//!
+//! 传输本身位于 `network::transport`，这里按节点配置中的成员名单建立连接。
+
+use super::NodeConfig;
+use crate::protocols::session::SessionId;
+use crate::Result;
+
+pub use crate::network::transport::MeshTransport;
+
+impl MeshTransport {
+    /// 与配置中的全部成员建立连接
+    ///
+    /// # 参数
+    /// - `config`: 节点配置
+    /// - `session`: 会话 ID，所有节点必须一致
+    ///
+    /// # 返回值
+    /// 在超时时间内与所有成员完成握手时返回传输，否则返回网络错误
+    pub fn connect(config: &NodeConfig, session: SessionId) -> Result<Self> {
+        let addresses: Vec<String> = config.members.iter().map(|member| member.address.clone()).collect();
+        Self::connect_to(config.party_id, &addresses, config.timeout(), session)
+    }
+}
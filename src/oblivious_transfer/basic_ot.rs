@@ -3,13 +3,22 @@
 //! Implements the classic Diffie-Hellman based OT protocol
 
 use super::*;
+use crate::utils::memory::SecretValue;
+use std::marker::PhantomData;
+
+// Both ciphertexts together with the branch keys they were encrypted under
+type KeyedCiphertexts = ((OTMessage, OTMessage), (Vec<u8>, Vec<u8>));
 
 #[derive(Debug, Clone)]
 pub struct BasicOT {
     pub setup: DHOTSetup,
-    pub sender_messages: Option<(OTMessage, OTMessage)>,
+    pub sender_messages: Option<SecretValue<(OTMessage, OTMessage)>>,
     pub receiver_choice: Option<ChoiceBit>,
     sender_public_key: Option<u64>,
+    receiver_public_key: Option<u64>,
+    commitment: Option<OTCommitment>,
+    commitment_salts: Option<SecretValue<[[u8; 32]; 2]>>,
+    security_model: SecurityModel,
 }
 
 impl BasicOT {
@@ -19,18 +28,64 @@ impl BasicOT {
             sender_messages: None,
             receiver_choice: None,
             sender_public_key: None,
+            receiver_public_key: None,
+            commitment: None,
+            commitment_salts: None,
+            security_model: SecurityModel::SemiHonest,
         }
     }
     
+    pub fn with_security_model(mut self, model: SecurityModel) -> Self {
+        self.security_model = model;
+        self
+    }
+    
+    pub fn security_model(&self) -> SecurityModel {
+        self.security_model
+    }
+    
     pub fn sender_phase1(&mut self, msg0: OTMessage, msg1: OTMessage) -> Result<u64> {
-        self.sender_messages = Some((msg0, msg1));
+        if self.security_model == SecurityModel::Malicious {
+            // Commit to both messages before the receiver's key is known
+            let (commitment, salts) = OTCommitment::commit(&msg0, &msg1);
+            self.commitment = Some(commitment);
+            self.commitment_salts = Some(SecretValue::new(salts));
+        }
+        self.sender_messages = Some(SecretValue::new((msg0, msg1)));
         
         // Sender computes g^a and sends it
         let sender_public = self.setup.pow_mod(self.setup.generator, self.setup.sender_private);
         Ok(sender_public)
     }
     
+    // Commitment sent alongside g^a in the malicious model
+    pub fn sender_commitment(&self) -> Option<&OTCommitment> {
+        self.commitment.as_ref()
+    }
+    
     pub fn receiver_phase1(&mut self, choice: ChoiceBit, sender_public: u64) -> Result<u64> {
+        if self.security_model == SecurityModel::Malicious {
+            return Err(MpcError::ProtocolError(
+                "Malicious security model requires receiver_phase1_committed".to_string()
+            ));
+        }
+        self.respond(choice, sender_public)
+    }
+    
+    // Receiver's response after taking the sender's commitment to both messages
+    pub fn receiver_phase1_committed(
+        &mut self,
+        choice: ChoiceBit,
+        sender_public: u64,
+        commitment: OTCommitment,
+    ) -> Result<u64> {
+        // A degenerate g^a (e.g. 0) would make g^a * g^b reveal the choice bit
+        validate_group_element(sender_public, self.setup.prime)?;
+        self.commitment = Some(commitment);
+        self.respond(choice, sender_public)
+    }
+    
+    fn respond(&mut self, choice: ChoiceBit, sender_public: u64) -> Result<u64> {
         self.receiver_choice = Some(choice);
         self.sender_public_key = Some(sender_public);
         
@@ -45,11 +100,26 @@ impl BasicOT {
             receiver_private_exp
         };
         
+        self.receiver_public_key = Some(receiver_public);
         Ok(receiver_public)
     }
     
     pub fn sender_phase2(&self, receiver_public: u64) -> Result<(OTMessage, OTMessage)> {
-        let (msg0, msg1) = self.sender_messages.as_ref()
+        let (ciphertexts, _) = self.encrypt_messages(receiver_public)?;
+        Ok(ciphertexts)
+    }
+    
+    // Sender's transfer together with the commitment openings checked by a malicious-secure receiver
+    pub fn sender_phase2_verified(&self, receiver_public: u64) -> Result<((OTMessage, OTMessage), OTVerification)> {
+        let salts = self.commitment_salts.as_deref()
+            .ok_or_else(|| MpcError::ProtocolError("Sender has not committed to its messages".to_string()))?;
+        let (ciphertexts, (key0, key1)) = self.encrypt_messages(receiver_public)?;
+        let verification = OTVerification::new(salts, (&key0, &key1));
+        Ok((ciphertexts, verification))
+    }
+    
+    fn encrypt_messages(&self, receiver_public: u64) -> Result<KeyedCiphertexts> {
+        let (msg0, msg1) = self.sender_messages.as_deref()
             .ok_or_else(|| MpcError::ProtocolError("Sender messages not set".to_string()))?;
         
         // Compute shared secrets
@@ -72,10 +142,44 @@ impl BasicOT {
         let encrypted_msg0 = xor_bytes(msg0, &key0[..msg0.len().min(key0.len())]);
         let encrypted_msg1 = xor_bytes(msg1, &key1[..msg1.len().min(key1.len())]);
         
-        Ok((encrypted_msg0, encrypted_msg1))
+        Ok(((encrypted_msg0, encrypted_msg1), (key0, key1)))
     }
     
     pub fn receiver_phase2(&self, encrypted_messages: (OTMessage, OTMessage)) -> Result<OTMessage> {
+        if self.security_model == SecurityModel::Malicious {
+            return Err(MpcError::ProtocolError(
+                "Malicious security model requires receiver_phase2_verified".to_string()
+            ));
+        }
+        let (message, _) = self.decrypt_chosen(&encrypted_messages)?;
+        Ok(message)
+    }
+    
+    // Receiver's transfer phase: the decrypted message must open the sender's commitment
+    pub fn receiver_phase2_verified(
+        &self,
+        encrypted_messages: (OTMessage, OTMessage),
+        verification: &OTVerification,
+    ) -> Result<OTMessage> {
+        let (message, key) = self.decrypt_chosen(&encrypted_messages)?;
+        if self.security_model == SecurityModel::Malicious {
+            let commitment = self.commitment.as_ref()
+                .ok_or_else(|| MpcError::ProtocolError("Sender commitment not stored".to_string()))?;
+            let choice = self.receiver_choice
+                .ok_or_else(|| MpcError::ProtocolError("Receiver choice not set".to_string()))?;
+            
+            verification.verify(
+                commitment,
+                choice,
+                &key,
+                (&encrypted_messages.0, &encrypted_messages.1),
+                &message,
+            )?;
+        }
+        Ok(message)
+    }
+    
+    fn decrypt_chosen(&self, encrypted_messages: &(OTMessage, OTMessage)) -> Result<(OTMessage, Vec<u8>)> {
         let choice = self.receiver_choice
             .ok_or_else(|| MpcError::ProtocolError("Receiver choice not set".to_string()))?;
         let sender_public = self.sender_public_key
@@ -94,7 +198,7 @@ impl BasicOT {
         };
         
         let decrypted = xor_bytes(encrypted_msg, &key[..encrypted_msg.len().min(key.len())]);
-        Ok(decrypted)
+        Ok((decrypted, key))
     }
     
     fn mod_inverse(&self, a: u64) -> Result<u64> {
@@ -134,25 +238,205 @@ impl Default for BasicOT {
     }
 }
 
+/// 发送方的第二条消息：两个密文，恶意模型下附带承诺的打开信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OtTransfer {
+    /// 两个分支的密文
+    pub ciphertexts: (OTMessage, OTMessage),
+    /// 恶意模型下的承诺打开信息
+    pub verification: Option<OTVerification>,
+}
+
+/// 发送方状态：已发出公钥，等待接收方消息
+#[derive(Debug, Clone, Copy)]
+pub struct AwaitingReceiverMsg;
+
+/// 发送方状态：已收到接收方消息，可以发送密文
+#[derive(Debug, Clone, Copy)]
+pub struct ReadyToSend {
+    receiver_public: u64,
+}
+
+/// 接收方状态：等待发送方公钥
+#[derive(Debug, Clone, Copy)]
+pub struct AwaitingSenderMsg;
+
+/// 接收方状态：已发出响应，等待发送方密文
+#[derive(Debug, Clone, Copy)]
+pub struct AwaitingTransfer;
+
+/// 按轮次推进的 OT 发送方
+///
+/// 每一轮消耗当前状态并返回下一状态，因此轮次顺序由类型检查保证，
+/// 一个实例只能发送一次密文。在收到接收方消息之前发送无法通过编译：
+///
+/// ```compile_fail
+/// use mpc_api::oblivious_transfer::*;
+///
+/// let sender = OtSender::new(b"m0".to_vec(), b"m1".to_vec()).unwrap();
+/// sender.send();
+/// ```
+#[derive(Debug, Clone)]
+pub struct OtSender<S> {
+    ot: BasicOT,
+    sender_public: u64,
+    state: S,
+}
+
+impl OtSender<AwaitingReceiverMsg> {
+    /// 以两个消息创建发送方
+    pub fn new(msg0: OTMessage, msg1: OTMessage) -> Result<Self> {
+        Self::with_security_model(msg0, msg1, SecurityModel::SemiHonest)
+    }
+
+    /// 以指定安全模型创建发送方
+    pub fn with_security_model(msg0: OTMessage, msg1: OTMessage, model: SecurityModel) -> Result<Self> {
+        let mut ot = BasicOT::new().with_security_model(model);
+        let sender_public = ot.sender_phase1(msg0, msg1)?;
+        Ok(Self { ot, sender_public, state: AwaitingReceiverMsg })
+    }
+
+    /// 第一条消息：发送方公钥 g^a
+    pub fn public_key(&self) -> u64 {
+        self.sender_public
+    }
+
+    /// 第一条消息中对两个消息的承诺，仅在恶意模型下存在
+    pub fn commitment(&self) -> Option<&OTCommitment> {
+        self.ot.sender_commitment()
+    }
+
+    /// 接收接收方的响应
+    pub fn receive(self, receiver_public: u64) -> OtSender<ReadyToSend> {
+        OtSender {
+            ot: self.ot,
+            sender_public: self.sender_public,
+            state: ReadyToSend { receiver_public },
+        }
+    }
+}
+
+impl OtSender<ReadyToSend> {
+    /// 发送密文，消耗发送方
+    pub fn send(self) -> Result<OtTransfer> {
+        let receiver_public = self.state.receiver_public;
+        match self.ot.security_model() {
+            SecurityModel::SemiHonest => Ok(OtTransfer {
+                ciphertexts: self.ot.sender_phase2(receiver_public)?,
+                verification: None,
+            }),
+            SecurityModel::Malicious => {
+                let (ciphertexts, verification) = self.ot.sender_phase2_verified(receiver_public)?;
+                Ok(OtTransfer { ciphertexts, verification: Some(verification) })
+            }
+        }
+    }
+}
+
+impl<S> OtSender<S> {
+    /// 安全模型
+    pub fn security_model(&self) -> SecurityModel {
+        self.ot.security_model()
+    }
+}
+
+/// 按轮次推进的 OT 接收方
+///
+/// 与 `OtSender` 相同，每一轮消耗当前状态，选择位只能使用一次。
+#[derive(Debug, Clone)]
+pub struct OtReceiver<S> {
+    ot: BasicOT,
+    choice: ChoiceBit,
+    state: PhantomData<S>,
+}
+
+impl OtReceiver<AwaitingSenderMsg> {
+    /// 以选择位创建接收方
+    pub fn new(choice: ChoiceBit) -> Self {
+        Self::with_security_model(choice, SecurityModel::SemiHonest)
+    }
+
+    /// 以指定安全模型创建接收方
+    pub fn with_security_model(choice: ChoiceBit, model: SecurityModel) -> Self {
+        Self {
+            ot: BasicOT::new().with_security_model(model),
+            choice,
+            state: PhantomData,
+        }
+    }
+
+    /// 接收发送方公钥并生成响应
+    ///
+    /// # 返回值
+    /// 返回下一状态的接收方和发给发送方的响应
+    pub fn receive(mut self, sender_public: u64) -> Result<(OtReceiver<AwaitingTransfer>, u64)> {
+        let response = self.ot.receiver_phase1(self.choice, sender_public)?;
+        Ok((OtReceiver { ot: self.ot, choice: self.choice, state: PhantomData }, response))
+    }
+
+    /// 接收发送方公钥和消息承诺并生成响应，恶意模型下必须使用此接口
+    ///
+    /// # 返回值
+    /// 返回下一状态的接收方和发给发送方的响应
+    pub fn receive_committed(
+        mut self,
+        sender_public: u64,
+        commitment: OTCommitment,
+    ) -> Result<(OtReceiver<AwaitingTransfer>, u64)> {
+        let response = self.ot.receiver_phase1_committed(self.choice, sender_public, commitment)?;
+        Ok((OtReceiver { ot: self.ot, choice: self.choice, state: PhantomData }, response))
+    }
+}
+
+impl OtReceiver<AwaitingTransfer> {
+    /// 解密所选消息，消耗接收方
+    ///
+    /// 恶意模型下密文必须附带承诺的打开信息，解密结果与承诺不符时中止。
+    pub fn finish(self, transfer: OtTransfer) -> Result<OTMessage> {
+        match (self.ot.security_model(), &transfer.verification) {
+            (SecurityModel::Malicious, Some(verification)) => {
+                self.ot.receiver_phase2_verified(transfer.ciphertexts, verification)
+            }
+            (SecurityModel::Malicious, None) => Err(MpcError::AuthenticationError(
+                "Malicious security model requires sender verification".to_string()
+            )),
+            (SecurityModel::SemiHonest, _) => self.ot.receiver_phase2(transfer.ciphertexts),
+        }
+    }
+}
+
+impl<S> OtReceiver<S> {
+    /// 安全模型
+    pub fn security_model(&self) -> SecurityModel {
+        self.ot.security_model()
+    }
+}
+
 // Complete OT protocol execution
 pub fn execute_basic_ot(
     msg0: OTMessage,
     msg1: OTMessage,
     choice: ChoiceBit,
 ) -> Result<OTMessage> {
-    let mut sender = BasicOT::new();
-    let mut receiver = BasicOT::new();
-    
-    // Copy setup parameters for consistency
-    receiver.setup = sender.setup.clone();
-    
+    execute_basic_ot_with_model(msg0, msg1, choice, SecurityModel::SemiHonest)
+}
+
+// OT protocol execution under the given security model
+pub fn execute_basic_ot_with_model(
+    msg0: OTMessage,
+    msg1: OTMessage,
+    choice: ChoiceBit,
+    model: SecurityModel,
+) -> Result<OTMessage> {
     // Phase 1: Setup
-    let sender_public = sender.sender_phase1(msg0, msg1)?;
-    let receiver_public = receiver.receiver_phase1(choice, sender_public)?;
+    let sender = OtSender::with_security_model(msg0, msg1, model)?;
+    let receiver = OtReceiver::with_security_model(choice, model);
+    let (receiver, receiver_public) = match sender.commitment() {
+        Some(commitment) => receiver.receive_committed(sender.public_key(), commitment.clone())?,
+        None => receiver.receive(sender.public_key())?,
+    };
     
     // Phase 2: Transfer
-    let encrypted_messages = sender.sender_phase2(receiver_public)?;
-    let result = receiver.receiver_phase2(encrypted_messages)?;
-    
-    Ok(result)
+    let transfer = sender.receive(receiver_public).send()?;
+    receiver.finish(transfer)
 }
@@ -14,16 +14,29 @@
 //! ### 安全性质
 //! - **接收方隐私**: 发送方不知道接收方的选择位
 //! - **发送方隐私**: 接收方只能获得一个消息
+//!
+//! ### 安全模型
+//! 基础 OT 和 Naor-Pinkas OT 默认工作在半诚实模型 (`SecurityModel::SemiHonest`)。
+//! 选择 `SecurityModel::Malicious` 后，发送方在看到接收方消息之前先对两个消息作承诺
+//! (`OTCommitment`)，随密文发送用各分支密钥加密的承诺盐值 (`OTVerification`)。
+//! 接收方拒绝退化的群元素和长度不一致的密文，并用承诺检查解密得到的消息：
+//! 发送方无法在看到接收方消息后再改变消息，也无法让接收方接受畸形密文。
+//! 两个分支的检查失败返回完全相同的错误。篡改某一分支的发送方仍可从接收方是否中止
+//! 得知该分支是否被选中（这是所有 OT 固有的选择性失败），调用方在任何中止后都应放弃
+//! 整个会话而不是重试。
+//! OT 扩展 (`OtExtensionSender` / `OtExtensionReceiver`) 的恶意模型则由发送方执行
+//! KOS15 一致性检查，发现在不同列中使用不同选择位的恶意接收方。
 //! 
 //! ## 支持的协议
 //! 
 //! - **基本 1-out-of-2 OT**: 基础不经意传输协议
 //! - **相关不经意传输 (COT)**: 两个消息满足特定关系
 //! - **随机不经意传输 (ROT)**: 消息由协议随机生成
-//! - **OT 扩展**: 使用少量基础 OT 实现大量 OT
+//! - **OT 扩展**: 使用少量基础 OT 实现大量 OT；IKNP 扩展可选 KOS15 一致性检查
 //! - **Naor-Pinkas OT**: 基于离散对数的高效实现
-//! - **向量不经意线性求值 (VOLE)**: 向量形式的 OLE
+//! - **向量不经意线性求值 (VOLE)**: 向量形式的 OLE；`batch_evaluate` 一次生成整个向量相关性并做随机线性组合一致性检查
 //! - **不经意线性求值 (OLE)**: 允许接收方获得线性函数的值
+//! - **静默 OT / VOLE (Silent)**: 交换短种子后在本地展开出大量随机 OT 或 VOLE 相关性，扩展倍数可配置
 //! 
 //! ## 应用场景
 //! 
@@ -59,6 +72,24 @@
 //! // 接收方获得了选择的消息，但不知道另一个
 //! assert_eq!(receiver_output.chosen_message, b"secret1".to_vec());
 //! ```
+//!
+//! ## 按轮次执行
+//!
+//! `OtSender` 和 `OtReceiver` 以类型状态表示协议所处的轮次，每一轮消耗当前状态：
+//!
+//! ```rust
+//! use mpc_api::oblivious_transfer::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let sender = OtSender::new(b"secret0".to_vec(), b"secret1".to_vec())?;
+//! let receiver = OtReceiver::new(true);
+//!
+//! let (receiver, response) = receiver.receive(sender.public_key())?;
+//! let transfer = sender.receive(response).send()?;
+//! assert_eq!(receiver.finish(transfer)?, b"secret1".to_vec());
+//! # Ok(())
+//! # }
+//! ```
 
 pub mod basic_ot;
 pub mod correlated_ot;
@@ -67,6 +98,7 @@ pub mod ot_extension;
 pub mod naor_pinkas;
 pub mod vole;
 pub mod ole;
+pub mod silent;
 
 pub use basic_ot::*;
 pub use correlated_ot::*;
@@ -75,9 +107,10 @@ pub use ot_extension::*;
 pub use naor_pinkas::*;
 pub use vole::*;
 pub use ole::*;
+pub use silent::*;
 
 use crate::{MpcError, Result};
-use crate::secret_sharing::{FIELD_PRIME, field_add, field_mul};
+use crate::secret_sharing::{FIELD_PRIME, field_add, field_mul, field_pow_ct};
 use serde::{Deserialize, Serialize};
 use rand::Rng;
 
@@ -157,8 +190,8 @@ impl DHOTSetup {
     
     /// 模幂运算
     /// 
-    /// 计算 base^exp mod prime，使用快速幂算法实现。
-    /// 这是 Diffie-Hellman 协议的核心操作。
+    /// 计算 base^exp mod prime，使用常数时间的蒙哥马利阶梯实现。
+    /// 这是 Diffie-Hellman 协议的核心操作，指数通常是私钥。
     /// 
     /// # 参数
     /// 
@@ -169,23 +202,7 @@ impl DHOTSetup {
     /// 
     /// 返回模幂运算的结果
     pub fn pow_mod(&self, base: u64, exp: u64) -> u64 {
-        if exp == 0 {
-            return 1;
-        }
-        
-        let mut result = 1u64;
-        let mut base = base % self.prime;
-        let mut exp = exp;
-        
-        while exp > 0 {
-            if exp % 2 == 1 {
-                result = field_mul(result, base);
-            }
-            exp >>= 1;
-            base = field_mul(base, base);
-        }
-        
-        result
+        field_pow_ct(base % self.prime, exp)
     }
 }
 
@@ -274,3 +291,125 @@ pub fn hash_to_bytes(input: u64) -> Vec<u8> {
     hasher.update(input.to_le_bytes());
     hasher.finalize().to_vec()
 }
+
+/// OT 协议的安全模型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SecurityModel {
+    /// 半诚实模型：参与方遵守协议，不执行额外验证
+    #[default]
+    SemiHonest,
+    /// 恶意模型：基础 OT 中接收方用发送方的承诺检查收到的消息；OT 扩展中发送方执行 KOS 一致性检查
+    Malicious,
+}
+
+/// 发送方对两个消息的承诺
+///
+/// 恶意模型下发送方在收到接收方消息之前发出，承诺值为带随机盐值的消息哈希，
+/// 不泄露未被选择的消息。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OTCommitment {
+    /// 两个分支的承诺 H(盐值 ‖ 消息)
+    pub commitments: [[u8; 32]; 2],
+}
+
+impl OTCommitment {
+    /// 对两个消息作承诺
+    ///
+    /// # 返回值
+    ///
+    /// 返回 (承诺, 两个分支的盐值)，盐值由发送方保存，在传输阶段用 `OTVerification` 打开
+    pub fn commit(msg0: &[u8], msg1: &[u8]) -> (Self, [[u8; 32]; 2]) {
+        let mut rng = rand::thread_rng();
+        let salts: [[u8; 32]; 2] = [rng.gen(), rng.gen()];
+        let commitments = [message_commitment(&salts[0], msg0), message_commitment(&salts[1], msg1)];
+        (Self { commitments }, salts)
+    }
+}
+
+/// 发送方随密文发送的承诺打开信息
+///
+/// 每个分支的盐值用该分支的密钥加密，接收方只能打开所选分支的承诺。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OTVerification {
+    /// 用各分支密钥加密的盐值
+    pub encrypted_salts: [[u8; 32]; 2],
+}
+
+impl OTVerification {
+    /// 由发送方用两个分支的密钥加密承诺盐值
+    ///
+    /// # 参数
+    ///
+    /// * `salts` - `OTCommitment::commit` 返回的盐值
+    /// * `keys` - 两个分支的加密密钥
+    pub fn new(salts: &[[u8; 32]; 2], keys: (&[u8], &[u8])) -> Self {
+        Self {
+            encrypted_salts: [xor_salt(&salts[0], keys.0), xor_salt(&salts[1], keys.1)],
+        }
+    }
+
+    /// 接收方用承诺检查解密得到的消息
+    ///
+    /// 检查两个密文长度一致，并用所选分支的密钥解密盐值、重新计算承诺。
+    /// 无论哪个分支出错都返回相同的错误，调用方应中止整个会话而不是重试。
+    ///
+    /// # 参数
+    ///
+    /// * `commitment` - 接收方发出消息之前收到的承诺
+    /// * `choice` - 接收方的选择位
+    /// * `key` - 所选分支的密钥
+    /// * `ciphertexts` - 收到的两个密文
+    /// * `message` - 解密得到的消息
+    pub fn verify(
+        &self,
+        commitment: &OTCommitment,
+        choice: ChoiceBit,
+        key: &[u8],
+        ciphertexts: (&[u8], &[u8]),
+        message: &[u8],
+    ) -> Result<()> {
+        let branch = usize::from(choice);
+        let salt = xor_salt(&self.encrypted_salts[branch], key);
+        let expected = message_commitment(&salt, message);
+        let difference = expected.iter()
+            .zip(&commitment.commitments[branch])
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+
+        if ciphertexts.0.len() == ciphertexts.1.len() && difference == 0 {
+            Ok(())
+        } else {
+            Err(MpcError::AuthenticationError("OT sender verification failed".to_string()))
+        }
+    }
+}
+
+/// 验证发送方提供的群元素不是退化元素
+///
+/// 拒绝 0、1、p-1 以及超出域范围的值。这些元素会使接收方的消息
+/// （例如基础 OT 中的 A·g^b）直接暴露选择位，或使共享密钥可预测。
+///
+/// # 参数
+///
+/// * `value` - 待检查的群元素
+/// * `prime` - 群的模数
+pub fn validate_group_element(value: u64, prime: u64) -> Result<()> {
+    if value <= 1 || value >= prime - 1 {
+        return Err(MpcError::AuthenticationError(format!(
+            "Degenerate group element {} rejected", value
+        )));
+    }
+    Ok(())
+}
+
+/// 计算带盐值的消息承诺
+fn message_commitment(salt: &[u8; 32], message: &[u8]) -> [u8; 32] {
+    crate::utils::hash_struct_with_domain(b"mpc_api/ot/commitment", &(salt, message))
+        .expect("byte arrays always have a canonical encoding")
+}
+
+/// 用分支密钥派生的掩码加密或解密盐值
+fn xor_salt(salt: &[u8; 32], key: &[u8]) -> [u8; 32] {
+    let pad = crate::utils::hash_struct_with_domain(b"mpc_api/ot/salt", key)
+        .expect("byte slices always have a canonical encoding");
+    std::array::from_fn(|i| salt[i] ^ pad[i])
+}
@@ -3,9 +3,12 @@
 //! Efficient OT protocol based on DDH assumption
 
 use super::*;
+use crate::utils::memory::SecretValue;
 
 // Type alias for complex return type
 pub type NPOTResult = (u64, Vec<u8>, Vec<u8>);
+pub type NPOTVerifiedResult = (u64, Vec<u8>, Vec<u8>, OTVerification);
+type NPOTKeyedResult = (u64, Vec<u8>, Vec<u8>, (Vec<u8>, Vec<u8>));
 
 #[derive(Debug, Clone)]
 pub struct NaorPinkasOT {
@@ -13,6 +16,10 @@ pub struct NaorPinkasOT {
     pub pk_r: Option<u64>,  // Receiver's public key
     pub pk_s: Option<u64>,  // Sender's public key
     pub receiver_choice: Option<ChoiceBit>, // Receiver's choice
+    commitment: Option<OTCommitment>,
+    committed_messages: Option<SecretValue<(Vec<u8>, Vec<u8>)>>,
+    commitment_salts: Option<SecretValue<[[u8; 32]; 2]>>,
+    security_model: SecurityModel,
 }
 
 impl NaorPinkasOT {
@@ -22,11 +29,49 @@ impl NaorPinkasOT {
             pk_r: None,
             pk_s: None,
             receiver_choice: None,
+            commitment: None,
+            committed_messages: None,
+            commitment_salts: None,
+            security_model: SecurityModel::SemiHonest,
         }
     }
     
+    pub fn with_security_model(mut self, model: SecurityModel) -> Self {
+        self.security_model = model;
+        self
+    }
+    
+    pub fn security_model(&self) -> SecurityModel {
+        self.security_model
+    }
+    
+    // Sender's commitment to both messages, sent before the receiver's first message
+    pub fn sender_commit(&mut self, msg0: &[u8], msg1: &[u8]) -> OTCommitment {
+        let (commitment, salts) = OTCommitment::commit(msg0, msg1);
+        self.committed_messages = Some(SecretValue::new((msg0.to_vec(), msg1.to_vec())));
+        self.commitment_salts = Some(SecretValue::new(salts));
+        self.commitment = Some(commitment.clone());
+        commitment
+    }
+    
     // Receiver's first message: pk_r = g^r if choice=0, g^(r+1) if choice=1
     pub fn receiver_round1(&mut self, choice: ChoiceBit) -> Result<u64> {
+        if self.security_model == SecurityModel::Malicious {
+            return Err(MpcError::ProtocolError(
+                "Malicious security model requires receiver_round1_committed".to_string()
+            ));
+        }
+        self.respond(choice)
+    }
+    
+    // Receiver's first message after taking the sender's commitment to both messages
+    pub fn receiver_round1_committed(&mut self, choice: ChoiceBit, commitment: OTCommitment) -> Result<u64> {
+        validate_group_element(self.setup.generator, self.setup.prime)?;
+        self.commitment = Some(commitment);
+        self.respond(choice)
+    }
+    
+    fn respond(&mut self, choice: ChoiceBit) -> Result<u64> {
         self.receiver_choice = Some(choice);
         
         // Generate random receiver key
@@ -47,6 +92,23 @@ impl NaorPinkasOT {
     
     // Sender's response: Generate h = g^s and compute OT messages
     pub fn sender_round1(&mut self, pk_r: u64, msg0: &[u8], msg1: &[u8]) -> Result<(u64, Vec<u8>, Vec<u8>)> {
+        let (h, enc_msg0, enc_msg1, _) = self.encrypt_messages(pk_r, msg0, msg1)?;
+        Ok((h, enc_msg0, enc_msg1))
+    }
+    
+    // Sender's response for the committed messages, with the openings checked by a malicious-secure receiver
+    pub fn sender_round1_verified(&mut self, pk_r: u64) -> Result<NPOTVerifiedResult> {
+        let (messages, salts) = match (self.committed_messages.take(), self.commitment_salts.take()) {
+            (Some(messages), Some(salts)) => (messages, salts),
+            _ => return Err(MpcError::ProtocolError("Sender has not committed to its messages".to_string())),
+        };
+        let (msg0, msg1) = &*messages;
+        let (h, enc_msg0, enc_msg1, (key0, key1)) = self.encrypt_messages(pk_r, msg0, msg1)?;
+        let verification = OTVerification::new(&salts, (&key0, &key1));
+        Ok((h, enc_msg0, enc_msg1, verification))
+    }
+    
+    fn encrypt_messages(&mut self, pk_r: u64, msg0: &[u8], msg1: &[u8]) -> Result<NPOTKeyedResult> {
         // Generate sender's key
         let s = self.setup.sender_private;
         let h = self.setup.pow_mod(self.setup.generator, s); // h = g^s
@@ -74,11 +136,42 @@ impl NaorPinkasOT {
         let enc_msg0 = self.encrypt(msg0, &key0);
         let enc_msg1 = self.encrypt(msg1, &key1);
         
-        Ok((h, enc_msg0, enc_msg1))
+        Ok((h, enc_msg0, enc_msg1, (key0, key1)))
     }
     
     // Receiver's final step: Decrypt the chosen message
     pub fn receiver_round2(&self, h: u64, choice: ChoiceBit, enc_msg0: &[u8], enc_msg1: &[u8]) -> Result<Vec<u8>> {
+        if self.security_model == SecurityModel::Malicious {
+            return Err(MpcError::ProtocolError(
+                "Malicious security model requires receiver_round2_verified".to_string()
+            ));
+        }
+        let (message, _) = self.decrypt_chosen(h, choice, enc_msg0, enc_msg1);
+        Ok(message)
+    }
+    
+    // Receiver's final step: the decrypted message must open the sender's commitment
+    pub fn receiver_round2_verified(
+        &self,
+        h: u64,
+        choice: ChoiceBit,
+        enc_msg0: &[u8],
+        enc_msg1: &[u8],
+        verification: &OTVerification,
+    ) -> Result<Vec<u8>> {
+        if self.security_model == SecurityModel::Malicious {
+            validate_group_element(h, self.setup.prime)?;
+        }
+        let (message, key) = self.decrypt_chosen(h, choice, enc_msg0, enc_msg1);
+        if self.security_model == SecurityModel::Malicious {
+            let commitment = self.commitment.as_ref()
+                .ok_or_else(|| MpcError::ProtocolError("Sender commitment not stored".to_string()))?;
+            verification.verify(commitment, choice, &key, (enc_msg0, enc_msg1), &message)?;
+        }
+        Ok(message)
+    }
+    
+    fn decrypt_chosen(&self, h: u64, choice: ChoiceBit, enc_msg0: &[u8], enc_msg1: &[u8]) -> (Vec<u8>, Vec<u8>) {
         let r = self.setup.receiver_private;
         
         // In the corrected Naor-Pinkas protocol:
@@ -98,7 +191,7 @@ impl NaorPinkasOT {
             self.decrypt(enc_msg0, &key)
         };
         
-        Ok(decrypted)
+        (decrypted, key)
     }
     
     fn mod_inverse(&self, a: u64) -> Result<u64> {
@@ -168,6 +261,30 @@ pub fn execute_naor_pinkas_ot(msg0: &[u8], msg1: &[u8], choice: ChoiceBit) -> Re
     Ok(result)
 }
 
+// Naor-Pinkas OT execution under the given security model
+pub fn execute_naor_pinkas_ot_with_model(
+    msg0: &[u8],
+    msg1: &[u8],
+    choice: ChoiceBit,
+    model: SecurityModel,
+) -> Result<Vec<u8>> {
+    let mut sender = NaorPinkasOT::new().with_security_model(model);
+    let mut receiver = NaorPinkasOT::new().with_security_model(model);
+    
+    receiver.setup = sender.setup.clone();
+    
+    if model == SecurityModel::SemiHonest {
+        let pk_r = receiver.receiver_round1(choice)?;
+        let (h, enc_msg0, enc_msg1) = sender.sender_round1(pk_r, msg0, msg1)?;
+        return receiver.receiver_round2(h, choice, &enc_msg0, &enc_msg1);
+    }
+    
+    let commitment = sender.sender_commit(msg0, msg1);
+    let pk_r = receiver.receiver_round1_committed(choice, commitment)?;
+    let (h, enc_msg0, enc_msg1, verification) = sender.sender_round1_verified(pk_r)?;
+    receiver.receiver_round2_verified(h, choice, &enc_msg0, &enc_msg1, &verification)
+}
+
 // Batch Naor-Pinkas OT for multiple instances
 #[derive(Debug, Clone)]
 pub struct BatchNaorPinkasOT {
@@ -2,9 +2,13 @@
 //! 
 //! OLE allows computing f(x) = a*x + b where a,b are scalars controlled by sender
 //! and x is a scalar controlled by receiver. Receiver learns f(x) but not a,b.
+//!
+//! `FieldOleSender` / `FieldOleReceiver` work over any `F: Field`: a random OLE
+//! correlation (a, b) / (x, y = a*x + b) is derandomized to the parties' chosen
+//! inputs with one message in each direction.
 
 use super::*;
-use crate::secret_sharing::{field_add, field_mul, field_sub};
+use crate::secret_sharing::{field_add, field_mul, field_sub, Field};
 
 #[derive(Debug, Clone)]
 pub struct ObliviousLinearEvaluation {
@@ -87,17 +91,9 @@ impl ObliviousLinearEvaluation {
     
     // OLE with preprocessing - generates random OLE correlations
     pub fn preprocess_ole(&mut self) -> Result<(u64, u64, u64, u64)> {
-        let mut rng = rand::thread_rng();
-        
-        // Generate random a, b, x
-        let a = rng.gen_range(0..FIELD_PRIME);
-        let b = rng.gen_range(0..FIELD_PRIME);
-        let x = rng.gen_range(0..FIELD_PRIME);
-        
-        // Compute y = a*x + b
-        let y = field_add(field_mul(a, x), b);
-        
-        Ok((a, b, x, y))
+        // Random a, b, x and y = a*x + b over the Goldilocks field
+        let (sender, receiver) = random_field_ole::<u64>();
+        Ok((sender.a, sender.b, receiver.x, receiver.y))
     }
     
     // Use preprocessed correlations for efficient OLE
@@ -291,3 +287,54 @@ impl Default for OLEMultiplication {
         Self::new()
     }
 }
+
+/// Sender's half of a random OLE correlation over `F`: the random line (a, b)
+#[derive(Debug, Clone, Copy)]
+pub struct FieldOleSender<F: Field> {
+    a: F,
+    b: F,
+}
+
+/// Receiver's half of a random OLE correlation over `F`: x and y = a*x + b
+#[derive(Debug, Clone, Copy)]
+pub struct FieldOleReceiver<F: Field> {
+    x: F,
+    y: F,
+}
+
+/// Sender's reply when derandomizing a correlation to the line (a', b')
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct FieldOleResponse<F: Field> {
+    /// v = a' - a
+    pub v: F,
+    /// w = b' - b + a*u
+    pub w: F,
+}
+
+/// Generate a random OLE correlation over `F` (trusted dealer / preprocessing)
+pub fn random_field_ole<F: Field>() -> (FieldOleSender<F>, FieldOleReceiver<F>) {
+    let mut rng = rand::thread_rng();
+    let (a, b, x) = (F::random(&mut rng), F::random(&mut rng), F::random(&mut rng));
+    (FieldOleSender { a, b }, FieldOleReceiver { x, y: a.mul(x).add(b) })
+}
+
+impl<F: Field> FieldOleReceiver<F> {
+    /// Step 1: send u = x' - x for the chosen input x'; u hides x' behind the random x
+    pub fn choose(&self, input: F) -> F {
+        input.sub(self.x)
+    }
+
+    /// Step 3: recover a'*x' + b' = y + w + v*x'
+    pub fn finish(&self, input: F, response: &FieldOleResponse<F>) -> F {
+        self.y.add(response.w).add(response.v.mul(input))
+    }
+}
+
+impl<F: Field> FieldOleSender<F> {
+    /// Step 2: answer the receiver's u for the sender's line (a', b');
+    /// v and w are masked by the random a and b
+    pub fn respond(&self, u: F, a: F, b: F) -> FieldOleResponse<F> {
+        FieldOleResponse { v: a.sub(self.a), w: b.sub(self.b).add(self.a.mul(u)) }
+    }
+}
@@ -1,11 +1,21 @@
 //! OT Extension protocols for efficiently performing many OTs
+//!
+//! `OtExtensionSender` / `OtExtensionReceiver` implement IKNP extension from κ base OTs.
+//! In `SecurityModel::Malicious` the receiver also sends the KOS15 consistency check
+//! (a random linear combination of the extended rows over GF(2^128)), so the sender
+//! detects a receiver that used different choice bits in different columns.
 
 use super::*;
+use crate::utils::math::gf2k::gf128_mul;
+use crate::utils::concurrency::{task_scope, CancellationToken};
+use crate::utils::crypto::{AesCtrPrg, Blake3Hash, Hash, SeedableRng};
+use rand::{CryptoRng, RngCore};
 
 #[derive(Debug, Clone)]
 pub struct OTExtension {
     pub security_parameter: usize, // κ
     pub base_ots: Vec<(u64, u64)>, // Base OT outputs
+    base_ot_epoch: u64,            // Incremented on every base OT refresh
 }
 
 impl OTExtension {
@@ -13,9 +23,31 @@ impl OTExtension {
         Self {
             security_parameter,
             base_ots: Vec::new(),
+            base_ot_epoch: 0,
         }
     }
     
+    pub fn base_ot_epoch(&self) -> u64 {
+        self.base_ot_epoch
+    }
+    
+    // Replace the base OT correlations with fresh ones, zeroizing the old ones.
+    // Extensions derived after the refresh are independent of earlier extensions.
+    pub fn refresh_base_ots(&mut self) -> Result<()> {
+        self.clear_base_ots();
+        self.setup_base_ots()?;
+        self.base_ot_epoch += 1;
+        Ok(())
+    }
+    
+    fn clear_base_ots(&mut self) {
+        crate::utils::memory::secure_zero(
+            self.base_ots.as_mut_ptr() as *mut u8,
+            std::mem::size_of_val(self.base_ots.as_slice()),
+        );
+        self.base_ots.clear();
+    }
+    
     // Initialize with base OTs
     pub fn setup_base_ots(&mut self) -> Result<()> {
         let mut random_ot = RandomOT::new();
@@ -85,6 +117,41 @@ impl OTExtension {
         
         Ok(batches)
     }
+    
+    // Parallel batch OT extension. Batches are spread over `workers` threads; when any
+    // batch fails the remaining batches are cancelled and the first failure is returned.
+    pub fn parallel_batch_extend_ots(
+        &self,
+        batch_size: usize,
+        all_choices: &[ChoiceBit],
+        workers: usize,
+        token: &CancellationToken,
+    ) -> Result<Vec<Vec<(u64, u64)>>> {
+        if batch_size == 0 {
+            return Err(MpcError::ProtocolError("Batch size must be positive".to_string()));
+        }
+        
+        let batches: Vec<&[ChoiceBit]> = all_choices.chunks(batch_size).collect();
+        let workers = workers.clamp(1, batches.len().max(1));
+        let per_worker = task_scope(token, |group| {
+            for worker in 0..workers {
+                let batches = &batches;
+                group.spawn(move |token| {
+                    let mut results = Vec::new();
+                    for batch_choices in batches.iter().skip(worker).step_by(workers) {
+                        token.check()?;
+                        results.push(self.extend_ots(batch_choices.len(), batch_choices)?);
+                    }
+                    Ok(results)
+                });
+            }
+            Ok(())
+        })?;
+        
+        // Worker w handled batches w, w + workers, ...; restore the original order
+        let mut iters: Vec<_> = per_worker.into_iter().map(Vec::into_iter).collect();
+        Ok((0..batches.len()).filter_map(|i| iters[i % workers].next()).collect())
+    }
 }
 
 // 1-out-of-N OT extension
@@ -228,3 +295,297 @@ impl StringOT {
         Ok(result)
     }
 }
+
+// ===== IKNP OT extension with the KOS15 consistency check =====
+
+/// OT 扩展的计算安全参数 κ（基础 OT 数量）
+pub const OT_EXTENSION_KAPPA: usize = 128;
+
+/// KOS 检查的统计安全参数 s
+pub const KOS_STATISTICAL_SECURITY: usize = 64;
+
+/// KOS 一致性检查值：x = Σ_j χ_j·r_j，t = Σ_j χ_j·t_j（GF(2^128) 上）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KosCheck {
+    /// 选择位的随机线性组合
+    pub x: u128,
+    /// 行 t_j 的随机线性组合
+    pub t: u128,
+}
+
+/// 接收方发给发送方的扩展消息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtExtensionRequest {
+    /// 扩展批次编号，双方必须一致
+    pub batch: u64,
+    /// 输出的 OT 数量（不含 KOS 检查使用的填充行）
+    pub count: usize,
+    /// κ 列 u_i = G(k_i^0) ⊕ G(k_i^1) ⊕ r，按 64 位字存储
+    pub columns: Vec<Vec<u64>>,
+    /// 恶意模型下的一致性检查值
+    pub check: Option<KosCheck>,
+}
+
+/// OT 扩展发送方：持有 Δ 和基础 OT 中按 Δ 的各位选出的种子
+///
+/// 基础 OT 中双方角色互换：扩展发送方是基础 OT 的接收方，以 Δ 的各位为选择位。
+/// 每次扩展得到随机 OT，第 j 个 OT 的两个消息为 H(q_j) 和 H(q_j ⊕ Δ)。
+#[derive(Debug, Clone)]
+pub struct OtExtensionSender {
+    model: SecurityModel,
+    delta: u128,
+    seeds: Vec<[u8; 16]>,
+    batch: u64,
+    aborted: bool,
+}
+
+/// OT 扩展接收方：持有基础 OT 中的全部种子对
+#[derive(Debug, Clone)]
+pub struct OtExtensionReceiver {
+    model: SecurityModel,
+    seeds: Vec<([u8; 16], [u8; 16])>,
+    batch: u64,
+}
+
+/// 在同一进程中执行 κ 次基础 OT，建立 OT 扩展的双方
+///
+/// # 参数
+///
+/// * `model` - 半诚实模型执行 IKNP 扩展；恶意模型下基础 OT 附带验证信息，
+///   每次扩展附加 KOS 一致性检查，发送方可以发现构造不一致列的接收方
+pub fn setup_ot_extension(model: SecurityModel) -> Result<(OtExtensionSender, OtExtensionReceiver)> {
+    setup_ot_extension_with_rng(model, &mut rand::thread_rng())
+}
+
+/// 与 `setup_ot_extension` 相同，但 Δ 和基础 OT 的种子对取自给定的随机数生成器
+///
+/// 传入由显式种子创建的 `AesCtrPrg` 时，扩展得到的 OT 完全由种子确定。
+pub fn setup_ot_extension_with_rng<R: RngCore + CryptoRng>(
+    model: SecurityModel,
+    rng: &mut R,
+) -> Result<(OtExtensionSender, OtExtensionReceiver)> {
+    let delta: u128 = rng.gen();
+    let seed_pairs: Vec<([u8; 16], [u8; 16])> = (0..OT_EXTENSION_KAPPA).map(|_| (rng.gen(), rng.gen())).collect();
+
+    let chosen = seed_pairs.iter()
+        .enumerate()
+        .map(|(i, (k0, k1))| {
+            let base_sender = OtSender::with_security_model(k0.to_vec(), k1.to_vec(), model)?;
+            let base_receiver = OtReceiver::with_security_model((delta >> i) & 1 == 1, model);
+            let (base_receiver, response) = match base_sender.commitment() {
+                Some(commitment) => base_receiver.receive_committed(base_sender.public_key(), commitment.clone())?,
+                None => base_receiver.receive(base_sender.public_key())?,
+            };
+            let seed = base_receiver.finish(base_sender.receive(response).send()?)?;
+            seed.try_into().map_err(|_| MpcError::ProtocolError("Base OT returned a seed of the wrong length".to_string()))
+        })
+        .collect::<Result<Vec<[u8; 16]>>>()?;
+
+    Ok((
+        OtExtensionSender::from_base_ots(delta, chosen, model)?,
+        OtExtensionReceiver::from_base_ots(seed_pairs, model)?,
+    ))
+}
+
+impl OtExtensionSender {
+    /// 由基础 OT 的结果创建发送方
+    ///
+    /// # 参数
+    ///
+    /// * `delta` - 基础 OT 的选择位，第 i 位对应第 i 个基础 OT
+    /// * `seeds` - 基础 OT 中得到的 κ 个种子 k_i^{Δ_i}
+    /// * `model` - 安全模型，恶意模型下拒绝没有一致性检查的扩展消息
+    pub fn from_base_ots(delta: u128, seeds: Vec<[u8; 16]>, model: SecurityModel) -> Result<Self> {
+        if seeds.len() != OT_EXTENSION_KAPPA {
+            return Err(MpcError::ProtocolError(format!(
+                "OT extension needs {} base OTs, got {}", OT_EXTENSION_KAPPA, seeds.len()
+            )));
+        }
+        Ok(Self { model, delta, seeds, batch: 0, aborted: false })
+    }
+
+    /// 安全模型
+    pub fn security_model(&self) -> SecurityModel {
+        self.model
+    }
+
+    /// 处理接收方的扩展消息，得到随机 OT 的消息对
+    ///
+    /// 一致性检查失败后发送方进入中止状态：失败与否可能泄露 Δ 的部分比特，
+    /// 同一组基础 OT 不能再用于后续扩展。
+    ///
+    /// # 返回值
+    ///
+    /// 返回 `count` 个消息对 (m_j^0, m_j^1)；检查失败、缺少检查或批次不一致时返回 `AuthenticationError`
+    pub fn extend(&mut self, request: &OtExtensionRequest) -> Result<Vec<(u128, u128)>> {
+        if self.aborted {
+            return Err(MpcError::AuthenticationError("OT extension was aborted after a failed check".to_string()));
+        }
+        if request.batch != self.batch {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected OT extension batch {}, got {}", self.batch, request.batch
+            )));
+        }
+        if self.model == SecurityModel::Malicious && request.check.is_none() {
+            return Err(MpcError::AuthenticationError("Malicious OT extension requires a KOS check".to_string()));
+        }
+        let rows = request.count + padding(request.check.is_some());
+        if request.columns.len() != OT_EXTENSION_KAPPA || request.columns.iter().any(|column| column.len() != words(rows)) {
+            return Err(MpcError::ProtocolError("OT extension columns have the wrong shape".to_string()));
+        }
+
+        // q_i = G(k_i^{Δ_i}) ⊕ Δ_i·u_i，按行即 q_j = t_j ⊕ r_j·Δ
+        let columns: Vec<Vec<u64>> = self.seeds.iter()
+            .zip(&request.columns)
+            .enumerate()
+            .map(|(i, (seed, u))| {
+                let mut q = expand_seed(seed, self.batch, rows);
+                if (self.delta >> i) & 1 == 1 {
+                    q.iter_mut().zip(u).for_each(|(q, u)| *q ^= u);
+                }
+                q
+            })
+            .collect();
+        let q = transpose(&columns, rows);
+
+        if let Some(check) = request.check {
+            let challenges = kos_challenges(request.batch, &request.columns, rows);
+            let combined = q.iter().zip(&challenges).fold(0, |acc, (&q, &chi)| acc ^ gf128_mul(q, chi));
+            if combined != check.t ^ gf128_mul(check.x, self.delta) {
+                self.aborted = true;
+                return Err(MpcError::AuthenticationError("KOS consistency check failed".to_string()));
+            }
+        }
+
+        let batch = self.batch;
+        self.batch += 1;
+        Ok(q.iter()
+            .take(request.count)
+            .enumerate()
+            .map(|(j, &q)| (row_hash(batch, j, q), row_hash(batch, j, q ^ self.delta)))
+            .collect())
+    }
+}
+
+impl OtExtensionReceiver {
+    /// 由基础 OT 中作为发送方的种子对创建接收方
+    pub fn from_base_ots(seeds: Vec<([u8; 16], [u8; 16])>, model: SecurityModel) -> Result<Self> {
+        if seeds.len() != OT_EXTENSION_KAPPA {
+            return Err(MpcError::ProtocolError(format!(
+                "OT extension needs {} base OTs, got {}", OT_EXTENSION_KAPPA, seeds.len()
+            )));
+        }
+        Ok(Self { model, seeds, batch: 0 })
+    }
+
+    /// 安全模型
+    pub fn security_model(&self) -> SecurityModel {
+        self.model
+    }
+
+    /// 以选择位扩展出一批随机 OT
+    ///
+    /// 恶意模型下额外扩展 κ + s 行随机选择位，用于 KOS 检查后丢弃，
+    /// 检查值不泄露真实选择位。
+    ///
+    /// # 返回值
+    ///
+    /// 返回接收方得到的消息 m_j^{r_j} 和发给发送方的扩展消息
+    pub fn extend(&mut self, choices: &[ChoiceBit]) -> Result<(Vec<u128>, OtExtensionRequest)> {
+        self.extend_with_rng(choices, &mut rand::thread_rng())
+    }
+
+    /// 与 `extend` 相同，但 KOS 填充行的随机选择位取自给定的随机数生成器
+    pub fn extend_with_rng<R: RngCore + CryptoRng>(
+        &mut self,
+        choices: &[ChoiceBit],
+        rng: &mut R,
+    ) -> Result<(Vec<u128>, OtExtensionRequest)> {
+        let malicious = self.model == SecurityModel::Malicious;
+        let rows = choices.len() + padding(malicious);
+        let mut r = vec![0u64; words(rows)];
+        for j in 0..rows {
+            let bit = choices.get(j).copied().unwrap_or_else(|| rng.gen());
+            r[j / 64] |= (bit as u64) << (j % 64);
+        }
+
+        let (t_columns, columns): (Vec<Vec<u64>>, Vec<Vec<u64>>) = self.seeds.iter()
+            .map(|(k0, k1)| {
+                let t = expand_seed(k0, self.batch, rows);
+                let u = t.iter()
+                    .zip(expand_seed(k1, self.batch, rows))
+                    .zip(&r)
+                    .map(|((t, g), r)| t ^ g ^ r)
+                    .collect();
+                (t, u)
+            })
+            .unzip();
+        let t = transpose(&t_columns, rows);
+
+        let check = malicious.then(|| {
+            let challenges = kos_challenges(self.batch, &columns, rows);
+            challenges.iter().zip(&t).enumerate().fold(KosCheck { x: 0, t: 0 }, |acc, (j, (&chi, &t))| KosCheck {
+                x: if (r[j / 64] >> (j % 64)) & 1 == 1 { acc.x ^ chi } else { acc.x },
+                t: acc.t ^ gf128_mul(t, chi),
+            })
+        });
+
+        let batch = self.batch;
+        self.batch += 1;
+        let outputs = t.iter().take(choices.len()).enumerate().map(|(j, &t)| row_hash(batch, j, t)).collect();
+        Ok((outputs, OtExtensionRequest { batch, count: choices.len(), columns, check }))
+    }
+}
+
+/// KOS 检查使用的填充行数
+fn padding(checked: bool) -> usize {
+    if checked { OT_EXTENSION_KAPPA + KOS_STATISTICAL_SECURITY } else { 0 }
+}
+
+fn words(bits: usize) -> usize {
+    bits.div_ceil(64)
+}
+
+/// 用 AES 计数器模式把基础 OT 种子扩展为 `bits` 位
+///
+/// 种子作为 AES 密钥，计数器的高 64 位为批次号，不同批次的输出互不重叠。
+fn expand_seed(seed: &[u8; 16], batch: u64, bits: usize) -> Vec<u64> {
+    let mut prg = AesCtrPrg::new(seed, (batch as u128) << 64);
+    (0..words(bits)).map(|_| prg.next_u64()).collect()
+}
+
+/// κ 列转置为每行一个 128 位值
+fn transpose(columns: &[Vec<u64>], rows: usize) -> Vec<u128> {
+    (0..rows)
+        .map(|j| {
+            columns.iter()
+                .enumerate()
+                .fold(0u128, |row, (i, column)| row | (((column[j / 64] >> (j % 64)) & 1) as u128) << i)
+        })
+        .collect()
+}
+
+/// 由扩展消息派生 GF(2^128) 上的挑战 χ_j
+///
+/// 挑战在接收方确定全部列之后由列的摘要派生（Fiat-Shamir），接收方无法据此调整列。
+fn kos_challenges(batch: u64, columns: &[Vec<u64>], rows: usize) -> Vec<u128> {
+    let mut hasher = Blake3Hash::new();
+    hasher.update(b"mpc_api/ot_extension/kos");
+    hasher.update(&batch.to_le_bytes());
+    for word in columns.iter().flatten() {
+        hasher.update(&word.to_le_bytes());
+    }
+    let mut prg = AesCtrPrg::from_seed(hasher.finalize());
+    (0..rows).map(|_| prg.gen()).collect()
+}
+
+/// 随机 OT 的输出 H(batch, j, row)，打破行之间的相关性
+fn row_hash(batch: u64, j: usize, row: u128) -> u128 {
+    let digest = Blake3Hash::new()
+        .chain_update(b"mpc_api/ot_extension/output")
+        .chain_update(&batch.to_le_bytes())
+        .chain_update(&(j as u64).to_le_bytes())
+        .chain_update(&row.to_le_bytes())
+        .finalize();
+    u128::from_le_bytes(digest[..16].try_into().expect("16-byte prefix"))
+}
@@ -0,0 +1,905 @@
+//! # 静默相关性生成 (Silent OT / VOLE)
+//!
+//! 伪随机相关性生成器（PCG）让双方先交换与输出长度几乎无关的短种子，
+//! 之后各自在本地展开出大量 VOLE 相关性或随机 OT，展开过程不再需要通信。
+//!
+//! ## 构造
+//!
+//! 1. **可穿孔 PRF**: GGM 树的根种子展开出 2^h 个叶子。接收方只拿到路径 α 的 h 个兄弟节点
+//!    （穿孔密钥），能算出除 α 以外的全部叶子，对 α 处的叶子一无所知
+//! 2. **单点相关性**: 发送方令 v_i 为叶子，接收方在 α 处另外得到 w_α = v_α + β·Δ，
+//!    即 w = v + u·Δ，其中 u = β·e_α 只有一个非零位置
+//! 3. **LPN 展开**: t 棵树拼接出权重为 t 的稀疏噪声 e，再加上长度为 k 的基础相关性经
+//!    公开稀疏矩阵 A（每行 d 个非零元）的映射：u = e + A·u₀，v 和 w 同理。
+//!    两部分都是线性的，因此 w = v + u·Δ 仍然成立；由 LPN 假设，u 看起来是均匀随机的
+//!
+//! 种子大小约为 t·h 个 PRF 种子加上 k 个基础相关性，输出长度 n = 扩展倍数 × k。
+//!
+//! ## 两方建立种子
+//!
+//! `deal_silent_vole` / `deal_silent_ot` 以可信发牌的方式生成种子。不依赖发牌方时，
+//! 双方用 `setup_silent_vole` / `setup_silent_ot` 建立种子：
+//!
+//! 1. **OT 穿孔**: 发送方对每棵树的每一层计算左孩子的异或 K₀ 和右孩子的异或 K₁；
+//!    接收方以路径 α 在该层的相反位为选择位，经 OT 扩展得到 K_{¬α_l}，再异或掉已知节点，
+//!    得到该层的兄弟节点。每棵树 h 次 OT，发送方看不到 α
+//! 2. **单点修正**: VOLE 中接收方把 β 作为基础 VOLE 的输入，得到 c = β·Δ + b，
+//!    发送方持有 -b 的对应值，公开 τ = Σ_i v_i - b，接收方算出 w_α = τ - Σ_{i≠α} v_i + c = v_α + β·Δ。
+//!    随机 OT 中发送方公开 τ = Δ ⊕ ⊕_i v_i，v_α 对接收方未知，τ 不泄露 Δ
+//! 3. **基础相关性**: VOLE 的 k 个基础相关性和单点修正共用一次批量 VOLE（`vole` 模块）；
+//!    随机 OT 的基础相关 OT 由同一次 OT 扩展得到：发送方公开 m₀ ⊕ m₁ ⊕ Δ，令 v₀ = m₀
+//!
+//! 与 `setup_ot_extension`、`setup_batch_vole` 相同，基础 OT 在同一进程中执行；
+//! 之后双方只通过下面的消息交互，任何一方都不掌握对方的秘密。
+//!
+//! 默认参数用于演示展开过程，达到标准的 LPN 安全级别需要 k 为数千、t 为数百。
+//!
+//! ## 两种相关性
+//!
+//! - **VOLE**: 在有限域 F_p 上，发送方持有 Δ 和 v，接收方持有 u、w，满足 w = u·Δ + v
+//! - **随机 OT**: 在 GF(2^128) 上，u 为比特，Δ 为 128 位串，w = v ⊕ u·Δ。
+//!   哈希后发送方得到 (H(v_i), H(v_i ⊕ Δ))，接收方以 u_i 为选择位得到 H(w_i)
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::oblivious_transfer::*;
+//! use mpc_api::secret_sharing::{field_add, field_mul};
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let params = SilentParams::new(1 << 12, 16)?;
+//! let (sender_seed, receiver_seed) = deal_silent_vole(&params)?;
+//! // 种子远小于展开后的 u、w
+//! assert!(receiver_seed.size_in_bytes()? * 2 < params.output_len * 16);
+//!
+//! let sender = sender_seed.expand()?;
+//! let receiver = receiver_seed.expand()?;
+//! for i in 0..params.output_len {
+//!     assert_eq!(receiver.w[i], field_add(field_mul(receiver.u[i], sender.delta), sender.v[i]));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::*;
+use crate::secret_sharing::field_sub;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use sha2::{Digest, Sha256};
+
+/// 默认噪声权重（GGM 树的数量）上限
+pub const SILENT_NOISE_WEIGHT: usize = 128;
+
+/// 稀疏矩阵 A 每行的非零元数量
+pub const SILENT_ROW_WEIGHT: usize = 10;
+
+/// GGM 树节点种子
+pub type PrfSeed = [u8; 16];
+
+/// 静默展开参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilentParams {
+    /// 展开得到的相关性数量 n
+    pub output_len: usize,
+    /// 基础相关性数量 k
+    pub base_len: usize,
+    /// 噪声权重 t（GGM 树的数量）
+    pub noise_weight: usize,
+    /// 每棵 GGM 树的深度 h，每棵树覆盖 2^h 个位置
+    pub tree_depth: usize,
+    /// 矩阵 A 每行的非零元数量
+    pub row_weight: usize,
+}
+
+impl SilentParams {
+    /// 按输出长度和扩展倍数选择参数
+    ///
+    /// # 参数
+    /// - `output_len`: 展开得到的相关性数量
+    /// - `expansion_factor`: 输出长度与基础相关性数量之比
+    pub fn new(output_len: usize, expansion_factor: usize) -> Result<Self> {
+        if output_len == 0 || expansion_factor == 0 {
+            return Err(MpcError::ProtocolError(
+                "Silent expansion needs a positive output length and expansion factor".to_string()
+            ));
+        }
+        let block = output_len.div_ceil(SILENT_NOISE_WEIGHT.min(output_len)).next_power_of_two();
+        let base_len = output_len.div_ceil(expansion_factor);
+        Ok(Self {
+            output_len,
+            base_len,
+            // 去掉完全落在输出范围之外的树
+            noise_weight: output_len.div_ceil(block),
+            tree_depth: block.trailing_zeros() as usize,
+            row_weight: SILENT_ROW_WEIGHT.min(base_len),
+        })
+    }
+
+    /// 每棵树覆盖的位置数量
+    pub fn block_len(&self) -> usize {
+        1 << self.tree_depth
+    }
+
+    /// 第 `tree` 棵树在输出范围内覆盖的位置数量
+    fn tree_len(&self, tree: usize) -> usize {
+        self.block_len().min(self.output_len - tree * self.block_len())
+    }
+}
+
+/// 一棵 GGM 树在 α 处的穿孔密钥
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PuncturedKey {
+    /// 被穿孔的叶子（树内下标）
+    pub position: usize,
+    /// 从根到 α 的路径上每一层的兄弟节点种子
+    pub copath: Vec<PrfSeed>,
+}
+
+/// VOLE 发送方种子
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilentVoleSenderSeed {
+    /// 展开参数
+    pub params: SilentParams,
+    /// 全局密钥 Δ
+    pub delta: u64,
+    /// 每棵 GGM 树的根
+    pub roots: Vec<PrfSeed>,
+    /// 基础相关性 v₀
+    pub base_v: Vec<u64>,
+    /// 公开矩阵 A 的种子
+    pub code_seed: [u8; 32],
+}
+
+/// VOLE 接收方种子
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilentVoleReceiverSeed {
+    /// 展开参数
+    pub params: SilentParams,
+    /// 每棵树的穿孔密钥
+    pub keys: Vec<PuncturedKey>,
+    /// 每棵树在 α 处的非零值 β
+    pub betas: Vec<u64>,
+    /// 每棵树在 α 处的 w_α = v_α + β·Δ
+    pub corrections: Vec<u64>,
+    /// 基础相关性 u₀
+    pub base_u: Vec<u64>,
+    /// 基础相关性 w₀ = u₀·Δ + v₀
+    pub base_w: Vec<u64>,
+    /// 公开矩阵 A 的种子
+    pub code_seed: [u8; 32],
+}
+
+/// 展开后的 VOLE 发送方输出
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SilentVoleSender {
+    /// 全局密钥 Δ
+    pub delta: u64,
+    /// v
+    pub v: Vec<u64>,
+}
+
+/// 展开后的 VOLE 接收方输出，满足 w = u·Δ + v
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SilentVoleReceiver {
+    /// u
+    pub u: Vec<u64>,
+    /// w
+    pub w: Vec<u64>,
+}
+
+/// 随机 OT 发送方种子
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilentOtSenderSeed {
+    /// 展开参数
+    pub params: SilentParams,
+    /// 全局相关 Δ
+    pub delta: u128,
+    /// 每棵 GGM 树的根
+    pub roots: Vec<PrfSeed>,
+    /// 基础相关性 v₀
+    pub base_v: Vec<u128>,
+    /// 公开矩阵 A 的种子
+    pub code_seed: [u8; 32],
+}
+
+/// 随机 OT 接收方种子
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilentOtReceiverSeed {
+    /// 展开参数
+    pub params: SilentParams,
+    /// 每棵树的穿孔密钥
+    pub keys: Vec<PuncturedKey>,
+    /// 每棵树在 α 处的 w_α = v_α ⊕ Δ
+    pub corrections: Vec<u128>,
+    /// 基础选择位 u₀
+    pub base_u: Vec<bool>,
+    /// 基础相关性 w₀ = v₀ ⊕ u₀·Δ
+    pub base_w: Vec<u128>,
+    /// 公开矩阵 A 的种子
+    pub code_seed: [u8; 32],
+}
+
+/// 展开后的随机 OT 发送方
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SilentOtSender {
+    /// 全局相关 Δ
+    pub delta: u128,
+    /// v
+    pub v: Vec<u128>,
+}
+
+/// 展开后的随机 OT 接收方，满足 w = v ⊕ u·Δ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SilentOtReceiver {
+    /// 选择位 u
+    pub choices: Vec<bool>,
+    /// w
+    pub w: Vec<u128>,
+}
+
+/// 生成一对 VOLE 种子
+pub fn deal_silent_vole(params: &SilentParams) -> Result<(SilentVoleSenderSeed, SilentVoleReceiverSeed)> {
+    check_tree_count(params, params.noise_weight)?;
+    let mut rng = rand::thread_rng();
+    let delta = rng.gen_range(0..FIELD_PRIME);
+    let roots: Vec<PrfSeed> = (0..params.noise_weight).map(|_| rng.gen()).collect();
+    let code_seed: [u8; 32] = rng.gen();
+
+    let mut keys = Vec::with_capacity(roots.len());
+    let mut betas = Vec::with_capacity(roots.len());
+    let mut corrections = Vec::with_capacity(roots.len());
+    for (tree, root) in roots.iter().enumerate() {
+        let position = rng.gen_range(0..params.tree_len(tree));
+        let beta = rng.gen_range(1..FIELD_PRIME);
+        let leaf = leaf_to_field(&ggm_expand(root, params.tree_depth)[position]);
+        keys.push(PuncturedKey { position, copath: ggm_puncture(root, params.tree_depth, position) });
+        betas.push(beta);
+        corrections.push(field_add(leaf, field_mul(beta, delta)));
+    }
+
+    let base_v: Vec<u64> = (0..params.base_len).map(|_| rng.gen_range(0..FIELD_PRIME)).collect();
+    let base_u: Vec<u64> = (0..params.base_len).map(|_| rng.gen_range(0..FIELD_PRIME)).collect();
+    let base_w = base_u.iter().zip(&base_v).map(|(&u, &v)| field_add(field_mul(u, delta), v)).collect();
+
+    Ok((
+        SilentVoleSenderSeed { params: *params, delta, roots, base_v, code_seed },
+        SilentVoleReceiverSeed { params: *params, keys, betas, corrections, base_u, base_w, code_seed },
+    ))
+}
+
+/// 生成一对随机 OT 种子
+pub fn deal_silent_ot(params: &SilentParams) -> Result<(SilentOtSenderSeed, SilentOtReceiverSeed)> {
+    check_tree_count(params, params.noise_weight)?;
+    let mut rng = rand::thread_rng();
+    let delta: u128 = rng.gen();
+    let roots: Vec<PrfSeed> = (0..params.noise_weight).map(|_| rng.gen()).collect();
+    let code_seed: [u8; 32] = rng.gen();
+
+    let mut keys = Vec::with_capacity(roots.len());
+    let mut corrections = Vec::with_capacity(roots.len());
+    for (tree, root) in roots.iter().enumerate() {
+        let position = rng.gen_range(0..params.tree_len(tree));
+        let leaf = u128::from_le_bytes(ggm_expand(root, params.tree_depth)[position]);
+        keys.push(PuncturedKey { position, copath: ggm_puncture(root, params.tree_depth, position) });
+        corrections.push(leaf ^ delta);
+    }
+
+    let base_v: Vec<u128> = (0..params.base_len).map(|_| rng.gen()).collect();
+    let base_u: Vec<bool> = (0..params.base_len).map(|_| rng.gen()).collect();
+    let base_w = base_u.iter().zip(&base_v).map(|(&u, &v)| if u { v ^ delta } else { v }).collect();
+
+    Ok((
+        SilentOtSenderSeed { params: *params, delta, roots, base_v, code_seed },
+        SilentOtReceiverSeed { params: *params, keys, corrections, base_u, base_w, code_seed },
+    ))
+}
+
+impl SilentVoleSenderSeed {
+    /// 种子序列化后的字节数
+    pub fn size_in_bytes(&self) -> Result<usize> {
+        serialized_len(self)
+    }
+
+    /// 在本地展开出 n 个 VOLE 相关性
+    pub fn expand(&self) -> Result<SilentVoleSender> {
+        check_tree_count(&self.params, self.roots.len())?;
+        if self.base_v.len() != self.params.base_len {
+            return Err(MpcError::ProtocolError("Silent VOLE seed has inconsistent lengths".to_string()));
+        }
+        let mut v: Vec<u64> = self.roots.iter()
+            .flat_map(|root| ggm_expand(root, self.params.tree_depth))
+            .take(self.params.output_len)
+            .map(|leaf| leaf_to_field(&leaf))
+            .collect();
+        for (value, row) in v.iter_mut().zip(code_rows(&self.params, &self.code_seed)) {
+            *value = field_add(*value, encode_row(&row, &self.base_v));
+        }
+        Ok(SilentVoleSender { delta: self.delta, v })
+    }
+}
+
+impl SilentVoleReceiverSeed {
+    /// 种子序列化后的字节数
+    pub fn size_in_bytes(&self) -> Result<usize> {
+        serialized_len(self)
+    }
+
+    /// 在本地展开出 n 个 VOLE 相关性
+    pub fn expand(&self) -> Result<SilentVoleReceiver> {
+        let params = &self.params;
+        check_tree_count(params, self.keys.len())?;
+        if self.betas.len() != self.keys.len() || self.corrections.len() != self.keys.len()
+            || self.base_u.len() != params.base_len || self.base_w.len() != params.base_len
+        {
+            return Err(MpcError::ProtocolError("Silent VOLE seed has inconsistent lengths".to_string()));
+        }
+
+        let mut u = vec![0u64; params.output_len];
+        let mut w = vec![0u64; params.output_len];
+        for (tree, key) in self.keys.iter().enumerate() {
+            let offset = tree * params.block_len();
+            for (i, leaf) in ggm_punctured_leaves(key, params.tree_depth)?.into_iter().take(params.tree_len(tree)).enumerate() {
+                w[offset + i] = match leaf {
+                    Some(leaf) => leaf_to_field(&leaf),
+                    None => {
+                        u[offset + i] = self.betas[tree];
+                        self.corrections[tree]
+                    }
+                };
+            }
+        }
+        for (i, row) in code_rows(params, &self.code_seed).into_iter().enumerate() {
+            u[i] = field_add(u[i], encode_row(&row, &self.base_u));
+            w[i] = field_add(w[i], encode_row(&row, &self.base_w));
+        }
+        Ok(SilentVoleReceiver { u, w })
+    }
+}
+
+impl SilentOtSenderSeed {
+    /// 种子序列化后的字节数
+    pub fn size_in_bytes(&self) -> Result<usize> {
+        serialized_len(self)
+    }
+
+    /// 在本地展开出 n 个相关 OT
+    pub fn expand(&self) -> Result<SilentOtSender> {
+        check_tree_count(&self.params, self.roots.len())?;
+        if self.base_v.len() != self.params.base_len {
+            return Err(MpcError::ProtocolError("Silent OT seed has inconsistent lengths".to_string()));
+        }
+        let mut v: Vec<u128> = self.roots.iter()
+            .flat_map(|root| ggm_expand(root, self.params.tree_depth))
+            .take(self.params.output_len)
+            .map(u128::from_le_bytes)
+            .collect();
+        for (value, row) in v.iter_mut().zip(code_rows(&self.params, &self.code_seed)) {
+            *value ^= row.iter().fold(0, |acc, &(column, _)| acc ^ self.base_v[column]);
+        }
+        Ok(SilentOtSender { delta: self.delta, v })
+    }
+}
+
+impl SilentOtReceiverSeed {
+    /// 种子序列化后的字节数
+    pub fn size_in_bytes(&self) -> Result<usize> {
+        serialized_len(self)
+    }
+
+    /// 在本地展开出 n 个相关 OT
+    pub fn expand(&self) -> Result<SilentOtReceiver> {
+        let params = &self.params;
+        check_tree_count(params, self.keys.len())?;
+        if self.corrections.len() != self.keys.len()
+            || self.base_u.len() != params.base_len || self.base_w.len() != params.base_len
+        {
+            return Err(MpcError::ProtocolError("Silent OT seed has inconsistent lengths".to_string()));
+        }
+
+        let mut choices = vec![false; params.output_len];
+        let mut w = vec![0u128; params.output_len];
+        for (tree, key) in self.keys.iter().enumerate() {
+            let offset = tree * params.block_len();
+            for (i, leaf) in ggm_punctured_leaves(key, params.tree_depth)?.into_iter().take(params.tree_len(tree)).enumerate() {
+                w[offset + i] = match leaf {
+                    Some(leaf) => u128::from_le_bytes(leaf),
+                    None => {
+                        choices[offset + i] = true;
+                        self.corrections[tree]
+                    }
+                };
+            }
+        }
+        for (i, row) in code_rows(params, &self.code_seed).into_iter().enumerate() {
+            for &(column, _) in &row {
+                choices[i] ^= self.base_u[column];
+                w[i] ^= self.base_w[column];
+            }
+        }
+        Ok(SilentOtReceiver { choices, w })
+    }
+}
+
+impl SilentOtSender {
+    /// 随机 OT 的消息对 (H(i, v_i), H(i, v_i ⊕ Δ))
+    pub fn random_ots(&self) -> Vec<(u128, u128)> {
+        self.v.iter()
+            .enumerate()
+            .map(|(i, &v)| (ot_hash(i, v), ot_hash(i, v ^ self.delta)))
+            .collect()
+    }
+}
+
+impl SilentOtReceiver {
+    /// 随机 OT 中接收方得到的消息 H(i, w_i)，对应选择位 `choices[i]`
+    pub fn random_ots(&self) -> Vec<u128> {
+        self.w.iter().enumerate().map(|(i, &w)| ot_hash(i, w)).collect()
+    }
+}
+
+/// 两方建立 VOLE 种子时接收方的第一条消息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SilentVoleRequest {
+    /// OT 扩展消息，选择位为每棵树每一层的 ¬α_l
+    pub extension: OtExtensionRequest,
+    /// 基础 VOLE 的修正消息，接收方的输入为 u₀ ‖ β
+    pub base: VoleCorrection,
+}
+
+/// 发送方对 `SilentVoleRequest` 的回复
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilentVoleResponse {
+    /// 每棵树每一层经 OT 消息掩码的 (K₀, K₁)
+    pub levels: Vec<(u128, u128)>,
+    /// 基础 VOLE 一致性检查的挑战
+    pub base_challenges: Vec<u64>,
+}
+
+/// 发送方的最后一条消息：每棵树的 τ = Σ_i v_i - b
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilentVoleCorrections {
+    /// 每棵树一个 τ
+    pub sums: Vec<u64>,
+}
+
+/// 两方建立 VOLE 种子的发送方，持有 Δ 和 GGM 树的根
+#[derive(Debug, Clone)]
+pub struct SilentVoleSetupSender {
+    params: SilentParams,
+    delta: u64,
+    roots: Vec<PrfSeed>,
+    code_seed: [u8; 32],
+    extension: OtExtensionSender,
+    base: BatchVoleReceiver,
+}
+
+/// 两方建立 VOLE 种子的接收方，持有穿孔位置 α、β 和基础 VOLE 的输入
+#[derive(Debug, Clone)]
+pub struct SilentVoleSetupReceiver {
+    params: SilentParams,
+    positions: Vec<usize>,
+    betas: Vec<u64>,
+    code_seed: [u8; 32],
+    extension: OtExtensionReceiver,
+    base: BatchVoleSender,
+    base_u: Vec<u64>,
+    /// 基础 VOLE 中本方的 v，前 k 个对应基础相关性，其后每棵树一个
+    base_v: Vec<u64>,
+    ot_outputs: Vec<u128>,
+    keys: Vec<PuncturedKey>,
+}
+
+/// 随机 OT 种子建立时发送方的回复
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SilentOtResponse {
+    /// 每棵树每一层经 OT 消息掩码的 (K₀, K₁)
+    pub levels: Vec<(u128, u128)>,
+    /// 每棵树的 τ = Δ ⊕ ⊕_i v_i
+    pub sums: Vec<u128>,
+    /// 每个基础相关 OT 的 m₀ ⊕ m₁ ⊕ Δ
+    pub base_corrections: Vec<u128>,
+}
+
+/// 两方建立随机 OT 种子的发送方，持有 Δ 和 GGM 树的根
+#[derive(Debug, Clone)]
+pub struct SilentOtSetupSender {
+    params: SilentParams,
+    delta: u128,
+    roots: Vec<PrfSeed>,
+    code_seed: [u8; 32],
+    extension: OtExtensionSender,
+}
+
+/// 两方建立随机 OT 种子的接收方，持有穿孔位置 α 和基础选择位 u₀
+#[derive(Debug, Clone)]
+pub struct SilentOtSetupReceiver {
+    params: SilentParams,
+    positions: Vec<usize>,
+    base_u: Vec<bool>,
+    code_seed: [u8; 32],
+    extension: OtExtensionReceiver,
+    ot_outputs: Vec<u128>,
+}
+
+/// 建立两方 VOLE 种子生成的双方
+///
+/// 双方各自选择自己的秘密：发送方选 Δ 和树根，接收方选穿孔位置、β 和 u₀。
+/// OT 扩展和基础 VOLE 的基础 OT 在同一进程中执行，公开矩阵 A 的种子为公开随机数。
+pub fn setup_silent_vole(params: &SilentParams) -> Result<(SilentVoleSetupSender, SilentVoleSetupReceiver)> {
+    check_tree_count(params, params.noise_weight)?;
+    let mut rng = rand::thread_rng();
+    let code_seed: [u8; 32] = rng.gen();
+
+    let delta = rng.gen_range(0..FIELD_PRIME);
+    let roots: Vec<PrfSeed> = (0..params.noise_weight).map(|_| rng.gen()).collect();
+
+    let positions: Vec<usize> = (0..params.noise_weight).map(|tree| rng.gen_range(0..params.tree_len(tree))).collect();
+    let betas: Vec<u64> = (0..params.noise_weight).map(|_| rng.gen_range(1..FIELD_PRIME)).collect();
+    let base_u: Vec<u64> = (0..params.base_len).map(|_| rng.gen_range(0..FIELD_PRIME)).collect();
+    let base_v: Vec<u64> = (0..params.base_len + params.noise_weight).map(|_| rng.gen_range(0..FIELD_PRIME)).collect();
+
+    let (extension_sender, extension_receiver) = setup_ot_extension(SecurityModel::SemiHonest)?;
+    let inputs: Vec<u64> = base_u.iter().chain(&betas).copied().collect();
+    let (base_sender, base_receiver) = setup_batch_vole(&inputs, &base_v, delta)?;
+
+    Ok((
+        SilentVoleSetupSender { params: *params, delta, roots, code_seed, extension: extension_sender, base: base_receiver },
+        SilentVoleSetupReceiver {
+            params: *params,
+            positions,
+            betas,
+            code_seed,
+            extension: extension_receiver,
+            base: base_sender,
+            base_u,
+            base_v,
+            ot_outputs: Vec::new(),
+            keys: Vec::new(),
+        },
+    ))
+}
+
+impl SilentVoleSetupReceiver {
+    /// 第一步：为每棵树每一层请求 OT，并发出基础 VOLE 的修正消息
+    pub fn request(&mut self) -> Result<SilentVoleRequest> {
+        let choices = puncture_choices(&self.positions, self.params.tree_depth);
+        let (outputs, extension) = self.extension.extend(&choices)?;
+        self.ot_outputs = outputs;
+        Ok(SilentVoleRequest { extension, base: self.base.correction() })
+    }
+
+    /// 第三步：恢复穿孔密钥，回答基础 VOLE 的一致性检查
+    pub fn check(&mut self, response: &SilentVoleResponse) -> Result<VoleCheckResponse> {
+        self.keys = punctured_keys(&self.positions, self.params.tree_depth, &self.ot_outputs, &response.levels)?;
+        self.base.respond(&response.base_challenges)
+    }
+
+    /// 第五步：由 τ 算出每棵树的 w_α，得到接收方种子
+    pub fn finish(self, corrections: &SilentVoleCorrections) -> Result<SilentVoleReceiverSeed> {
+        let params = self.params;
+        if self.keys.len() != params.noise_weight || corrections.sums.len() != params.noise_weight {
+            return Err(MpcError::ProtocolError("Silent VOLE setup finished out of order".to_string()));
+        }
+
+        // c = β·Δ + b 中 b = -v，τ - Σ_{i≠α} v_i + c = v_α + β·Δ
+        let mut tree_corrections = Vec::with_capacity(self.keys.len());
+        for ((key, &sum), &v) in self.keys.iter().zip(&corrections.sums).zip(&self.base_v[params.base_len..]) {
+            let known = ggm_punctured_leaves(key, params.tree_depth)?.iter()
+                .flatten()
+                .fold(0, |acc, leaf| field_add(acc, leaf_to_field(leaf)));
+            tree_corrections.push(field_sub(field_sub(sum, known), v));
+        }
+        let base_w = self.base_v[..params.base_len].iter().map(|&v| field_sub(0, v)).collect();
+
+        Ok(SilentVoleReceiverSeed {
+            params,
+            keys: self.keys,
+            betas: self.betas,
+            corrections: tree_corrections,
+            base_u: self.base_u,
+            base_w,
+            code_seed: self.code_seed,
+        })
+    }
+}
+
+impl SilentVoleSetupSender {
+    /// 第二步：用 OT 扩展的消息对掩码每一层的 (K₀, K₁)，接收基础 VOLE 并发出检查挑战
+    pub fn respond(&mut self, request: &SilentVoleRequest) -> Result<SilentVoleResponse> {
+        let pairs = self.extension.extend(&request.extension)?;
+        let levels = mask_level_sums(&self.roots, self.params.tree_depth, &pairs)?;
+        let base_challenges = self.base.receive(&request.base)?;
+        Ok(SilentVoleResponse { levels, base_challenges })
+    }
+
+    /// 第四步：检查基础 VOLE，得到发送方种子和发给接收方的 τ
+    pub fn finish(self, check: &VoleCheckResponse) -> Result<(SilentVoleSenderSeed, SilentVoleCorrections)> {
+        let params = self.params;
+        let w = self.base.finish(check)?;
+        if w.len() != params.base_len + params.noise_weight {
+            return Err(MpcError::ProtocolError("Base VOLE has the wrong length".to_string()));
+        }
+
+        // 基础 VOLE 给出 w = β·Δ + v，令 b = -w，τ = Σ_i v_i - b
+        let sums = self.roots.iter()
+            .zip(&w[params.base_len..])
+            .map(|(root, &c)| {
+                let total = ggm_expand(root, params.tree_depth).iter()
+                    .fold(0, |acc, leaf| field_add(acc, leaf_to_field(leaf)));
+                field_add(total, c)
+            })
+            .collect();
+        let base_v = w[..params.base_len].iter().map(|&c| field_sub(0, c)).collect();
+
+        Ok((
+            SilentVoleSenderSeed { params, delta: self.delta, roots: self.roots, base_v, code_seed: self.code_seed },
+            SilentVoleCorrections { sums },
+        ))
+    }
+}
+
+/// 建立两方随机 OT 种子生成的双方
+///
+/// 发送方选 Δ 和树根，接收方选穿孔位置和基础选择位。
+/// OT 扩展的基础 OT 在同一进程中执行，公开矩阵 A 的种子为公开随机数。
+pub fn setup_silent_ot(params: &SilentParams) -> Result<(SilentOtSetupSender, SilentOtSetupReceiver)> {
+    check_tree_count(params, params.noise_weight)?;
+    let mut rng = rand::thread_rng();
+    let code_seed: [u8; 32] = rng.gen();
+
+    let delta: u128 = rng.gen();
+    let roots: Vec<PrfSeed> = (0..params.noise_weight).map(|_| rng.gen()).collect();
+
+    let positions: Vec<usize> = (0..params.noise_weight).map(|tree| rng.gen_range(0..params.tree_len(tree))).collect();
+    let base_u: Vec<bool> = (0..params.base_len).map(|_| rng.gen()).collect();
+
+    let (extension_sender, extension_receiver) = setup_ot_extension(SecurityModel::SemiHonest)?;
+    Ok((
+        SilentOtSetupSender { params: *params, delta, roots, code_seed, extension: extension_sender },
+        SilentOtSetupReceiver {
+            params: *params,
+            positions,
+            base_u,
+            code_seed,
+            extension: extension_receiver,
+            ot_outputs: Vec::new(),
+        },
+    ))
+}
+
+impl SilentOtSetupReceiver {
+    /// 第一步：请求每棵树每一层的 OT 和 k 个基础相关 OT
+    pub fn request(&mut self) -> Result<OtExtensionRequest> {
+        let mut choices = puncture_choices(&self.positions, self.params.tree_depth);
+        choices.extend_from_slice(&self.base_u);
+        let (outputs, request) = self.extension.extend(&choices)?;
+        self.ot_outputs = outputs;
+        Ok(request)
+    }
+
+    /// 第三步：恢复穿孔密钥和单点修正，得到接收方种子
+    pub fn finish(self, response: &SilentOtResponse) -> Result<SilentOtReceiverSeed> {
+        let params = self.params;
+        let punctures = params.noise_weight * params.tree_depth;
+        if self.ot_outputs.len() != punctures + params.base_len
+            || response.sums.len() != params.noise_weight
+            || response.base_corrections.len() != params.base_len
+        {
+            return Err(MpcError::ProtocolError("Silent OT setup messages have inconsistent lengths".to_string()));
+        }
+        let (received, base_received) = self.ot_outputs.split_at(punctures);
+        let keys = punctured_keys(&self.positions, params.tree_depth, received, &response.levels)?;
+
+        // τ ⊕ ⊕_{i≠α} v_i = v_α ⊕ Δ
+        let mut corrections = Vec::with_capacity(keys.len());
+        for (key, &sum) in keys.iter().zip(&response.sums) {
+            let known = ggm_punctured_leaves(key, params.tree_depth)?.iter()
+                .flatten()
+                .fold(0, |acc, leaf| acc ^ u128::from_le_bytes(*leaf));
+            corrections.push(sum ^ known);
+        }
+        let base_w = base_received.iter()
+            .zip(&self.base_u)
+            .zip(&response.base_corrections)
+            .map(|((&m, &u), &d)| if u { m ^ d } else { m })
+            .collect();
+
+        Ok(SilentOtReceiverSeed { params, keys, corrections, base_u: self.base_u, base_w, code_seed: self.code_seed })
+    }
+}
+
+impl SilentOtSetupSender {
+    /// 第二步：掩码每一层的 (K₀, K₁)，公开 τ 和基础相关 OT 的修正，得到发送方种子
+    pub fn respond(mut self, request: &OtExtensionRequest) -> Result<(SilentOtSenderSeed, SilentOtResponse)> {
+        let params = self.params;
+        let pairs = self.extension.extend(request)?;
+        let punctures = params.noise_weight * params.tree_depth;
+        if pairs.len() != punctures + params.base_len {
+            return Err(MpcError::ProtocolError("Silent OT request has the wrong number of OTs".to_string()));
+        }
+        let (puncture_pairs, base_pairs) = pairs.split_at(punctures);
+        let levels = mask_level_sums(&self.roots, params.tree_depth, puncture_pairs)?;
+        let sums = self.roots.iter()
+            .map(|root| ggm_expand(root, params.tree_depth).iter().fold(self.delta, |acc, leaf| acc ^ u128::from_le_bytes(*leaf)))
+            .collect();
+        let base_v = base_pairs.iter().map(|&(m0, _)| m0).collect();
+        let base_corrections = base_pairs.iter().map(|&(m0, m1)| m0 ^ m1 ^ self.delta).collect();
+
+        Ok((
+            SilentOtSenderSeed { params, delta: self.delta, roots: self.roots, base_v, code_seed: self.code_seed },
+            SilentOtResponse { levels, sums, base_corrections },
+        ))
+    }
+}
+
+/// 接收方在每棵树每一层的 OT 选择位：兄弟节点所在的一侧 ¬α_l
+fn puncture_choices(positions: &[usize], depth: usize) -> Vec<ChoiceBit> {
+    positions.iter()
+        .flat_map(|&position| (0..depth).map(move |level| (position >> (depth - 1 - level)) & 1 == 0))
+        .collect()
+}
+
+/// 每一层左孩子（偶数下标）的异或 K₀ 和右孩子的异或 K₁，从第 1 层到第 h 层
+fn ggm_level_sums(root: &PrfSeed, depth: usize) -> Vec<(u128, u128)> {
+    let mut level = vec![*root];
+    let mut sums = Vec::with_capacity(depth);
+    for _ in 0..depth {
+        level = level.iter()
+            .flat_map(|seed| {
+                let (left, right) = ggm_children(seed);
+                [left, right]
+            })
+            .collect();
+        sums.push(level.chunks(2).fold((0, 0), |(k0, k1), pair| {
+            (k0 ^ u128::from_le_bytes(pair[0]), k1 ^ u128::from_le_bytes(pair[1]))
+        }));
+    }
+    sums
+}
+
+/// 发送方用 OT 消息对掩码每棵树每一层的 (K₀, K₁)
+fn mask_level_sums(roots: &[PrfSeed], depth: usize, pairs: &[(u128, u128)]) -> Result<Vec<(u128, u128)>> {
+    if pairs.len() != roots.len() * depth {
+        return Err(MpcError::ProtocolError("Wrong number of puncturing OTs".to_string()));
+    }
+    Ok(roots.iter()
+        .flat_map(|root| ggm_level_sums(root, depth))
+        .zip(pairs)
+        .map(|((k0, k1), &(m0, m1))| (k0 ^ m0, k1 ^ m1))
+        .collect())
+}
+
+/// 接收方由 OT 得到的 K_{¬α_l} 恢复每棵树的穿孔密钥
+///
+/// 第 l 层 ¬α_l 一侧的节点中，只有兄弟节点不是之前各层兄弟节点的后代，
+/// 把这些已知后代从 K_{¬α_l} 中异或掉即得到兄弟节点。
+fn punctured_keys(
+    positions: &[usize],
+    depth: usize,
+    received: &[u128],
+    levels: &[(u128, u128)],
+) -> Result<Vec<PuncturedKey>> {
+    if received.len() != positions.len() * depth || levels.len() != received.len() {
+        return Err(MpcError::ProtocolError("Wrong number of puncturing OTs".to_string()));
+    }
+    Ok(positions.iter()
+        .enumerate()
+        .map(|(tree, &position)| {
+            let mut copath: Vec<PrfSeed> = Vec::with_capacity(depth);
+            for level in 0..depth {
+                let index = tree * depth + level;
+                let right = (position >> (depth - 1 - level)) & 1 == 0;
+                let (k0, k1) = levels[index];
+                let mut sibling = received[index] ^ if right { k1 } else { k0 };
+                for (upper, node) in copath.iter().enumerate() {
+                    for (i, descendant) in ggm_expand(node, level - upper).iter().enumerate() {
+                        if (i % 2 == 1) == right {
+                            sibling ^= u128::from_le_bytes(*descendant);
+                        }
+                    }
+                }
+                copath.push(sibling.to_le_bytes());
+            }
+            PuncturedKey { position, copath }
+        })
+        .collect())
+}
+
+/// GGM 树的长度倍增 PRG
+fn ggm_children(seed: &PrfSeed) -> (PrfSeed, PrfSeed) {
+    let digest = Sha256::new()
+        .chain_update(b"mpc_api/silent/ggm")
+        .chain_update(seed)
+        .finalize();
+    let (left, right) = digest.split_at(16);
+    (left.try_into().expect("16-byte half"), right.try_into().expect("16-byte half"))
+}
+
+/// 把根展开为 2^depth 个叶子
+fn ggm_expand(root: &PrfSeed, depth: usize) -> Vec<PrfSeed> {
+    let mut level = vec![*root];
+    for _ in 0..depth {
+        level = level.iter()
+            .flat_map(|seed| {
+                let (left, right) = ggm_children(seed);
+                [left, right]
+            })
+            .collect();
+    }
+    level
+}
+
+/// 计算在 `position` 处穿孔的密钥：路径上每一层的兄弟节点
+fn ggm_puncture(root: &PrfSeed, depth: usize, position: usize) -> Vec<PrfSeed> {
+    let mut node = *root;
+    let mut copath = Vec::with_capacity(depth);
+    for level in 0..depth {
+        let (left, right) = ggm_children(&node);
+        let bit = (position >> (depth - 1 - level)) & 1;
+        let (on_path, sibling) = if bit == 0 { (left, right) } else { (right, left) };
+        copath.push(sibling);
+        node = on_path;
+    }
+    copath
+}
+
+/// 由穿孔密钥展开除穿孔位置外的全部叶子
+fn ggm_punctured_leaves(key: &PuncturedKey, depth: usize) -> Result<Vec<Option<PrfSeed>>> {
+    if key.copath.len() != depth || key.position >= 1 << depth {
+        return Err(MpcError::ProtocolError("Punctured key does not match the tree depth".to_string()));
+    }
+    let mut leaves = vec![None; 1 << depth];
+    for (level, sibling) in key.copath.iter().enumerate() {
+        let height = depth - 1 - level;
+        let start = ((key.position >> height) ^ 1) << height;
+        for (i, leaf) in ggm_expand(sibling, height).into_iter().enumerate() {
+            leaves[start + i] = Some(leaf);
+        }
+    }
+    Ok(leaves)
+}
+
+/// 公开稀疏矩阵 A：每个输出位置对应 d 个 (列, 非零系数)
+fn code_rows(params: &SilentParams, code_seed: &[u8; 32]) -> Vec<Vec<(usize, u64)>> {
+    let mut rng = StdRng::from_seed(*code_seed);
+    (0..params.output_len)
+        .map(|_| {
+            (0..params.row_weight)
+                .map(|_| (rng.gen_range(0..params.base_len), rng.gen_range(1..FIELD_PRIME)))
+                .collect()
+        })
+        .collect()
+}
+
+/// A 的一行与基础向量的内积
+fn encode_row(row: &[(usize, u64)], base: &[u64]) -> u64 {
+    row.iter().fold(0, |acc, &(column, coefficient)| field_add(acc, field_mul(coefficient, base[column])))
+}
+
+fn leaf_to_field(leaf: &PrfSeed) -> u64 {
+    u64::from_le_bytes(leaf[..8].try_into().expect("8-byte prefix")) % FIELD_PRIME
+}
+
+/// 随机 OT 的输出哈希，打破 Δ 相关性
+fn ot_hash(index: usize, value: u128) -> u128 {
+    let digest = Sha256::new()
+        .chain_update(b"mpc_api/silent/ot")
+        .chain_update((index as u64).to_le_bytes())
+        .chain_update(value.to_le_bytes())
+        .finalize();
+    u128::from_le_bytes(digest[..16].try_into().expect("16-byte prefix"))
+}
+
+fn check_tree_count(params: &SilentParams, trees: usize) -> Result<()> {
+    let covered = params.noise_weight * params.block_len();
+    if trees != params.noise_weight || covered < params.output_len || covered - params.block_len() >= params.output_len {
+        return Err(MpcError::ProtocolError("Silent seed does not cover the output length".to_string()));
+    }
+    Ok(())
+}
+
+fn serialized_len<T: Serialize>(value: &T) -> Result<usize> {
+    bincode::serialized_size(value)
+        .map(|size| size as usize)
+        .map_err(|e| MpcError::SerializationError(e.to_string()))
+}
@@ -2,9 +2,14 @@
 //! 
 //! VOLE allows computing f(x) = a*x + b where a,b are vectors and x is a scalar,
 //! such that the sender learns nothing about x and receiver learns f(x) but not a,b.
+//!
+//! `VectorOLE::batch_evaluate` produces a whole vector correlation w = u*x + v in one pass:
+//! one base OT per bit of x (Gilboa-style), each carrying a PRG-expanded vector, followed
+//! by a random-linear-combination check that catches a sender using inconsistent u.
 
 use super::*;
-use crate::secret_sharing::{field_add, field_mul};
+use crate::secret_sharing::{field_add, field_inner_product, field_mul, field_sub};
+use sha2::{Digest, Sha256};
 
 // Type aliases for complex types
 pub type VOLEResult = (Vec<u64>, Vec<u64>, u64, Vec<u64>);
@@ -96,6 +101,20 @@ impl VectorOLE {
         Ok(results)
     }
     
+    // Batch VOLE: sender has vectors u,v; receiver has scalar x; receiver gets w = u*x + v.
+    // Runs the base OTs once for the whole vector and ends with a consistency check,
+    // returning AuthenticationError if the sender's messages do not match a single u.
+    pub fn batch_evaluate(&self, sender_u: &[u64], sender_v: &[u64], receiver_x: u64) -> Result<Vec<u64>> {
+        if sender_u.len() != self.vector_length || sender_v.len() != self.vector_length {
+            return Err(MpcError::ProtocolError("Vector length mismatch".to_string()));
+        }
+
+        let (sender, mut receiver) = setup_batch_vole(sender_u, sender_v, receiver_x)?;
+        let challenges = receiver.receive(&sender.correction())?;
+        let response = sender.respond(&challenges)?;
+        receiver.finish(&response)
+    }
+
     // Subfield VOLE - VOLE over smaller field embedded in larger field
     pub fn subfield_vole(
         &mut self,
@@ -240,3 +259,169 @@ impl BooleanVOLE {
         Ok(results)
     }
 }
+
+// Number of base OTs used by batch VOLE, one per bit of the receiver's scalar
+pub const VOLE_SCALAR_BITS: usize = 64;
+
+// Sender -> receiver: one correction vector per bit of x, plus the offset that fixes v.
+// Every vector has one extra trailing entry that masks the consistency check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoleCorrection {
+    pub columns: Vec<Vec<u64>>,
+    pub offset: Vec<u64>,
+}
+
+// Sender -> receiver: the challenged linear combinations of u and v
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VoleCheckResponse {
+    pub u: u64,
+    pub v: u64,
+}
+
+// Batch VOLE sender after the base OTs: holds both seeds of every base OT
+#[derive(Debug, Clone)]
+pub struct BatchVoleSender {
+    u: Vec<u64>,
+    v: Vec<u64>,
+    seeds: Vec<([u8; 16], [u8; 16])>,
+}
+
+// Batch VOLE receiver after the base OTs: holds the seed selected by each bit of x
+#[derive(Debug, Clone)]
+pub struct BatchVoleReceiver {
+    x: u64,
+    seeds: Vec<[u8; 16]>,
+    w: Vec<u64>,
+    challenges: Vec<u64>,
+}
+
+// Run the base OTs in-process and return both parties ready for the batch
+pub fn setup_batch_vole(sender_u: &[u64], sender_v: &[u64], receiver_x: u64) -> Result<(BatchVoleSender, BatchVoleReceiver)> {
+    if sender_u.len() != sender_v.len() {
+        return Err(MpcError::ProtocolError("Vector length mismatch".to_string()));
+    }
+    if receiver_x >= FIELD_PRIME || sender_u.iter().chain(sender_v).any(|&value| value >= FIELD_PRIME) {
+        return Err(MpcError::ProtocolError("VOLE inputs must be field elements".to_string()));
+    }
+
+    // Extra entry (u*, v*) keeps the check response from revealing anything about u, v
+    let mut rng = rand::thread_rng();
+    let mut u = sender_u.to_vec();
+    let mut v = sender_v.to_vec();
+    u.push(rng.gen_range(0..FIELD_PRIME));
+    v.push(rng.gen_range(0..FIELD_PRIME));
+
+    let seed_pairs: Vec<([u8; 16], [u8; 16])> = (0..VOLE_SCALAR_BITS).map(|_| (rng.gen(), rng.gen())).collect();
+    let chosen = seed_pairs.iter()
+        .enumerate()
+        .map(|(j, (k0, k1))| {
+            let base_sender = OtSender::new(k0.to_vec(), k1.to_vec())?;
+            let (base_receiver, response) = OtReceiver::new((receiver_x >> j) & 1 == 1)
+                .receive(base_sender.public_key())?;
+            let seed = base_receiver.finish(base_sender.receive(response).send()?)?;
+            seed.try_into().map_err(|_| MpcError::ProtocolError("Base OT returned a seed of the wrong length".to_string()))
+        })
+        .collect::<Result<Vec<[u8; 16]>>>()?;
+
+    Ok((
+        BatchVoleSender { u, v, seeds: seed_pairs },
+        BatchVoleReceiver { x: receiver_x, seeds: chosen, w: Vec::new(), challenges: Vec::new() },
+    ))
+}
+
+impl BatchVoleSender {
+    // For bit j: column_j = PRG(k_j^0) - PRG(k_j^1) + 2^j * u, so the receiver ends up with
+    // PRG(k_j^0) + x_j * 2^j * u; the offset turns the sum of PRG(k_j^0) into v
+    pub fn correction(&self) -> VoleCorrection {
+        let len = self.u.len();
+        let mut offset = self.v.clone();
+        let columns = self.seeds.iter()
+            .enumerate()
+            .map(|(j, (k0, k1))| {
+                let power = 1u64 << j;
+                let zero = expand_seed(k0, len);
+                let one = expand_seed(k1, len);
+                for (entry, &s) in offset.iter_mut().zip(&zero) {
+                    *entry = field_sub(*entry, s);
+                }
+                zero.iter()
+                    .zip(&one)
+                    .zip(&self.u)
+                    .map(|((&s, &t), &u)| field_add(field_sub(s, t), field_mul(power, u)))
+                    .collect()
+            })
+            .collect();
+        VoleCorrection { columns, offset }
+    }
+
+    // Answer the receiver's challenge with <chi, u> + u* and <chi, v> + v*
+    pub fn respond(&self, challenges: &[u64]) -> Result<VoleCheckResponse> {
+        let n = self.u.len() - 1;
+        if challenges.len() != n {
+            return Err(MpcError::ProtocolError("Challenge length mismatch".to_string()));
+        }
+        Ok(VoleCheckResponse {
+            u: combine(challenges, &self.u),
+            v: combine(challenges, &self.v),
+        })
+    }
+}
+
+impl BatchVoleReceiver {
+    // Compute w from the corrections and pick the random challenges for the check
+    pub fn receive(&mut self, correction: &VoleCorrection) -> Result<Vec<u64>> {
+        let len = correction.offset.len();
+        if len == 0
+            || correction.columns.len() != self.seeds.len()
+            || correction.columns.iter().any(|column| column.len() != len)
+        {
+            return Err(MpcError::ProtocolError("Malformed VOLE correction".to_string()));
+        }
+
+        let mut w = correction.offset.clone();
+        for (j, (seed, column)) in self.seeds.iter().zip(&correction.columns).enumerate() {
+            let chosen = (self.x >> j) & 1 == 1;
+            for ((entry, r), &d) in w.iter_mut().zip(expand_seed(seed, len)).zip(column) {
+                let r = if chosen { field_add(r, d) } else { r };
+                *entry = field_add(*entry, r);
+            }
+        }
+
+        let mut rng = rand::thread_rng();
+        self.challenges = (0..len - 1).map(|_| rng.gen_range(0..FIELD_PRIME)).collect();
+        self.w = w;
+        Ok(self.challenges.clone())
+    }
+
+    // Check <chi, w> + w* = U*x + V and return w without the masking entry
+    pub fn finish(mut self, response: &VoleCheckResponse) -> Result<Vec<u64>> {
+        if self.w.is_empty() {
+            return Err(MpcError::ProtocolError("VOLE correction has not been received".to_string()));
+        }
+        let expected = field_add(field_mul(response.u, self.x), response.v);
+        if combine(&self.challenges, &self.w) != expected {
+            return Err(MpcError::AuthenticationError("VOLE consistency check failed".to_string()));
+        }
+        self.w.pop();
+        Ok(self.w)
+    }
+}
+
+// sum_i chi_i * values_i plus the trailing mask entry with coefficient 1
+fn combine(challenges: &[u64], values: &[u64]) -> u64 {
+    field_add(field_inner_product(values, challenges), values[values.len() - 1])
+}
+
+// PRG: expand a base OT seed into a vector of field elements
+fn expand_seed(seed: &[u8; 16], len: usize) -> Vec<u64> {
+    (0..len as u64)
+        .map(|i| {
+            let digest = Sha256::new()
+                .chain_update(b"mpc_api/vole/prg")
+                .chain_update(seed)
+                .chain_update(i.to_le_bytes())
+                .finalize();
+            u64::from_le_bytes(digest[..8].try_into().expect("8-byte prefix")) % FIELD_PRIME
+        })
+        .collect()
+}
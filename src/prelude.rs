@@ -0,0 +1,61 @@
+//! # 预导入模块 (Prelude)
+//!
+//! 常用类型和 trait 的精选集合，是本库稳定公开 API 的入口：
+//!
+//! ```rust
+//! use mpc_api::prelude::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let shares = ShamirSecretSharing::share(&42, 2, 3)?;
+//! assert_eq!(ShamirSecretSharing::reconstruct(&shares[..2], 2)?, 42);
+//!
+//! let commitment = HashCommitment::commit(b"bid".to_vec(), b"nonce".to_vec());
+//! assert!(HashCommitment::verify(commitment, b"bid".to_vec(), b"nonce".to_vec()));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! 这里列出的名称在次版本之间保持兼容。其余公开项通过各自的模块路径访问
+//! （例如 `mpc_api::storage::MappedArtifact`）；原始域运算等内部实现只有
+//! 启用 `internals` 特性时才公开，不受语义化版本约束。
+
+pub use crate::{MpcError, Result};
+
+pub use crate::secret_sharing::{
+    AdditiveSecretSharing, AdditiveSecretSharingScheme, MultiplicationSecretSharing, SecretSharing,
+    ShamirSecretSharing, Share, FIELD_PRIME,
+};
+
+pub use crate::beaver_triples::{
+    batch_secure_multiply, secure_multiply, BeaverTriple, BeaverTripleGenerator, CompleteBeaverTriple,
+    OLEBeaverGenerator, TrustedPartyBeaverGenerator,
+};
+
+pub use crate::commitment::{
+    BindingCommitment, CommitmentScheme, HashCommitment, HidingCommitment, MerkleTree, PedersenCommitment,
+    PedersenParams,
+};
+
+pub use crate::authentication::{MessageAuthenticationCode, HMAC};
+
+pub use crate::oblivious_transfer::{BasicOT, ObliviousTransfer};
+
+pub use crate::elliptic_curve::{ECPoint, EllipticCurve};
+
+pub use crate::protocols::stats::{ProtocolOutput, ProtocolStats};
+
+pub use crate::utils::concurrency::CancellationToken;
+
+pub use crate::utils::transcript::Transcript;
+
+#[cfg(feature = "garbled-circuits")]
+pub use crate::garbled_circuits::{Circuit, Evaluator, GarbledCircuit, Garbler};
+
+#[cfg(feature = "he")]
+pub use crate::homomorphic_encryption::{AdditivelyHomomorphic, HomomorphicEncryption, MultiplicativelyHomomorphic};
+
+#[cfg(feature = "zk")]
+pub use crate::zero_knowledge::{DlogEqualityProof, DlogProof, ProductProof, RangeProof, SigmaProof, SigmaProtocol};
+
+#[cfg(all(feature = "zk", feature = "he"))]
+pub use crate::zero_knowledge::ShuffleProof;
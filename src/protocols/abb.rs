@@ -0,0 +1,629 @@
+//! # 算术黑盒 (Arithmetic Black Box)
+//!
+//! 统计、机器学习、拍卖等应用层协议只需要"输入、加、乘、公开"这几种操作，
+//! 而不关心底层用的是哪种 MPC 协议。`ArithmeticBlackBox` trait 把这些操作
+//! 抽象出来（全部为异步接口，便于接入真实网络），应用只需针对该 trait 编写一次：
+//!
+//! - **`SpdzBlackBox`**: 基于 SPDZ 认证分享，乘法消耗 Beaver 三元组
+//! - **`ShamirBlackBox`**: 诚实多数 Shamir 分享（要求 n ≥ 2t - 1），
+//!   乘法后通过重新分享降次，不需要预处理
+//! - **`SimulatedBlackBox`**: 明文模拟的本地后端，不提供任何安全性，
+//!   用于快速单元测试应用逻辑；它按真实协议的轮数记录统计，便于估算开销
+//!
+//! 所有后端都在同一进程内模拟全部参与方，并通过 `stats` 报告累计的通信轮数、
+//! 通信量和预处理消耗。本模块中的 `abb_sum`、`abb_inner_product`、`abb_moments`
+//! 是针对 trait 编写的应用示例。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::protocols::abb::*;
+//!
+//! async fn total<A: ArithmeticBlackBox>(abb: &A, inputs: &[u64]) -> mpc_api::Result<u64> {
+//!     let mut shared = Vec::new();
+//!     for (party, value) in inputs.iter().enumerate() {
+//!         shared.push(abb.input(party, *value).await?);
+//!     }
+//!     let sum = abb_sum(abb, &shared).await?;
+//!     abb.open(&sum).await
+//! }
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! # let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+//! # runtime.block_on(async {
+//! // 同一段应用代码可以在任意后端上运行
+//! assert_eq!(total(&SimulatedBlackBox::new(3), &[1, 2, 3]).await?, 6);
+//! assert_eq!(total(&ShamirBlackBox::new(3, 2)?, &[1, 2, 3]).await?, 6);
+//! # Ok(())
+//! # })
+//! # }
+//! ```
+
+use crate::protocols::stats::ProtocolStats;
+use crate::secret_sharing::{
+    field_add, field_mul, field_sub, validate_field_element, validate_threshold_params, SecretSharing,
+    ShamirSecretSharing, Share, FIELD_PRIME,
+};
+use crate::spdz::{AuthenticatedShare, SPDZParams, SPDZShareProtocol};
+use crate::{MpcError, Result};
+use rand::Rng;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+/// 算术黑盒操作返回的 future
+pub type AbbFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// 每个域元素的编码长度（字节）
+const ELEMENT_BYTES: u64 = 8;
+
+/// 算术黑盒：在秘密值上进行算术运算的统一接口
+///
+/// 值类型 `Value` 是后端内部的秘密表示（分享或明文），应用代码只能通过
+/// 本 trait 的操作处理它，只有 `open` 会公开结果。
+pub trait ArithmeticBlackBox: Send + Sync {
+    /// 后端的秘密值表示
+    type Value: Clone + Send + Sync;
+
+    /// 参与方数量
+    fn parties(&self) -> usize;
+
+    /// 参与方 `party` 输入私有值
+    fn input(&self, party: usize, value: u64) -> AbbFuture<'_, Self::Value>;
+
+    /// 将公开常数表示为秘密值
+    fn constant(&self, value: u64) -> AbbFuture<'_, Self::Value>;
+
+    /// 加法
+    fn add<'a>(&'a self, a: &'a Self::Value, b: &'a Self::Value) -> AbbFuture<'a, Self::Value>;
+
+    /// 减法
+    fn sub<'a>(&'a self, a: &'a Self::Value, b: &'a Self::Value) -> AbbFuture<'a, Self::Value>;
+
+    /// 与公开常数相乘
+    fn mul_public<'a>(&'a self, a: &'a Self::Value, constant: u64) -> AbbFuture<'a, Self::Value>;
+
+    /// 乘法
+    fn mul<'a>(&'a self, a: &'a Self::Value, b: &'a Self::Value) -> AbbFuture<'a, Self::Value>;
+
+    /// 批量乘法，逐对相乘
+    ///
+    /// 默认实现逐个调用 `mul`；后端可以覆盖为一轮完成全部乘法。
+    fn mul_batch<'a>(&'a self, a: &'a [Self::Value], b: &'a [Self::Value]) -> AbbFuture<'a, Vec<Self::Value>> {
+        Box::pin(async move {
+            check_batch(a.len(), b.len())?;
+            let mut products = Vec::with_capacity(a.len());
+            for (x, y) in a.iter().zip(b) {
+                products.push(self.mul(x, y).await?);
+            }
+            Ok(products)
+        })
+    }
+
+    /// 公开秘密值
+    fn open<'a>(&'a self, a: &'a Self::Value) -> AbbFuture<'a, u64>;
+
+    /// 生成任何一方都不知道的随机秘密值
+    fn random(&self) -> AbbFuture<'_, Self::Value>;
+
+    /// 截至目前累计的执行统计
+    fn stats(&self) -> ProtocolStats;
+}
+
+// ============================================================================
+// 应用层协议
+// ============================================================================
+
+/// 秘密值求和
+///
+/// # 参数
+///
+/// * `abb` - 算术黑盒
+/// * `values` - 秘密值，为空时结果为 0
+pub async fn abb_sum<A: ArithmeticBlackBox>(abb: &A, values: &[A::Value]) -> Result<A::Value> {
+    let mut sum = abb.constant(0).await?;
+    for value in values {
+        sum = abb.add(&sum, value).await?;
+    }
+    Ok(sum)
+}
+
+/// 秘密向量内积（例如线性模型的打分）
+///
+/// 所有乘法通过 `mul_batch` 一次完成。
+///
+/// # 参数
+///
+/// * `abb` - 算术黑盒
+/// * `x` - 第一个向量
+/// * `y` - 第二个向量，长度必须与 `x` 相同
+pub async fn abb_inner_product<A: ArithmeticBlackBox>(abb: &A, x: &[A::Value], y: &[A::Value]) -> Result<A::Value> {
+    let products = abb.mul_batch(x, y).await?;
+    abb_sum(abb, &products).await
+}
+
+/// 公开的样本矩
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Moments {
+    /// 样本数量
+    pub count: usize,
+    /// 样本之和
+    pub sum: u64,
+    /// 样本平方和
+    pub sum_of_squares: u64,
+}
+
+impl Moments {
+    /// 均值（无样本时为 0）
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        self.sum as f64 / self.count as f64
+    }
+
+    /// 总体方差（无样本时为 0）
+    pub fn variance(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let mean = self.mean();
+        self.sum_of_squares as f64 / self.count as f64 - mean * mean
+    }
+}
+
+/// 计算秘密样本的和与平方和并公开，只公开这两个聚合值
+///
+/// 调用方需要保证平方和不超过域大小，否则结果会回绕。
+pub async fn abb_moments<A: ArithmeticBlackBox>(abb: &A, values: &[A::Value]) -> Result<Moments> {
+    let sum = abb_sum(abb, values).await?;
+    let squares = abb.mul_batch(values, values).await?;
+    let sum_of_squares = abb_sum(abb, &squares).await?;
+    Ok(Moments {
+        count: values.len(),
+        sum: abb.open(&sum).await?,
+        sum_of_squares: abb.open(&sum_of_squares).await?,
+    })
+}
+
+// ============================================================================
+// 本地模拟后端
+// ============================================================================
+
+/// 明文模拟的算术黑盒
+///
+/// 秘密值直接以明文保存，不提供任何安全性，只用于测试应用逻辑。
+/// 通信轮数按真实协议记录（输入、乘法、公开、随机数各一轮），不记录通信量。
+#[derive(Debug)]
+pub struct SimulatedBlackBox {
+    /// 参与方数量
+    parties: usize,
+    /// 累计统计
+    stats: Mutex<ProtocolStats>,
+}
+
+impl SimulatedBlackBox {
+    /// 创建模拟后端
+    pub fn new(parties: usize) -> Self {
+        Self { parties, stats: Mutex::new(ProtocolStats::new()) }
+    }
+
+    fn record_round(&self) {
+        lock_stats(&self.stats).record_rounds(1);
+    }
+}
+
+impl ArithmeticBlackBox for SimulatedBlackBox {
+    type Value = u64;
+
+    fn parties(&self) -> usize {
+        self.parties
+    }
+
+    fn input(&self, party: usize, value: u64) -> AbbFuture<'_, u64> {
+        Box::pin(async move {
+            check_party(party, self.parties)?;
+            check_element(value)?;
+            self.record_round();
+            Ok(value)
+        })
+    }
+
+    fn constant(&self, value: u64) -> AbbFuture<'_, u64> {
+        Box::pin(async move {
+            check_element(value)?;
+            Ok(value)
+        })
+    }
+
+    fn add<'a>(&'a self, a: &'a u64, b: &'a u64) -> AbbFuture<'a, u64> {
+        Box::pin(async move { Ok(field_add(*a, *b)) })
+    }
+
+    fn sub<'a>(&'a self, a: &'a u64, b: &'a u64) -> AbbFuture<'a, u64> {
+        Box::pin(async move { Ok(field_sub(*a, *b)) })
+    }
+
+    fn mul_public<'a>(&'a self, a: &'a u64, constant: u64) -> AbbFuture<'a, u64> {
+        Box::pin(async move { Ok(field_mul(*a, constant)) })
+    }
+
+    fn mul<'a>(&'a self, a: &'a u64, b: &'a u64) -> AbbFuture<'a, u64> {
+        Box::pin(async move {
+            self.record_round();
+            Ok(field_mul(*a, *b))
+        })
+    }
+
+    fn mul_batch<'a>(&'a self, a: &'a [u64], b: &'a [u64]) -> AbbFuture<'a, Vec<u64>> {
+        Box::pin(async move {
+            check_batch(a.len(), b.len())?;
+            self.record_round();
+            Ok(a.iter().zip(b).map(|(x, y)| field_mul(*x, *y)).collect())
+        })
+    }
+
+    fn open<'a>(&'a self, a: &'a u64) -> AbbFuture<'a, u64> {
+        Box::pin(async move {
+            self.record_round();
+            Ok(*a)
+        })
+    }
+
+    fn random(&self) -> AbbFuture<'_, u64> {
+        Box::pin(async move {
+            self.record_round();
+            Ok(rand::thread_rng().gen_range(0..FIELD_PRIME))
+        })
+    }
+
+    fn stats(&self) -> ProtocolStats {
+        lock_stats(&self.stats).clone()
+    }
+}
+
+// ============================================================================
+// 诚实多数 Shamir 后端
+// ============================================================================
+
+/// 基于诚实多数 Shamir 分享的算术黑盒
+///
+/// 秘密值是 `threshold - 1` 次多项式在 x = 1..=n 处的分享。乘法先在本地相乘
+/// 得到 `2(threshold - 1)` 次的分享，再由每方重新分享自己的乘积并用拉格朗日系数
+/// 组合降次，因此要求 n ≥ 2·threshold - 1。
+#[derive(Debug)]
+pub struct ShamirBlackBox {
+    /// 参与方数量
+    parties: usize,
+    /// 重构门限
+    threshold: usize,
+    /// 在 x = 1..=n 处插值到 0 的拉格朗日系数
+    lagrange: Vec<u64>,
+    /// 累计统计
+    stats: Mutex<ProtocolStats>,
+}
+
+impl ShamirBlackBox {
+    /// 创建 Shamir 后端
+    ///
+    /// # 参数
+    ///
+    /// * `parties` - 参与方数量 n
+    /// * `threshold` - 重构门限 t，需满足 n ≥ 2t - 1
+    pub fn new(parties: usize, threshold: usize) -> Result<Self> {
+        validate_threshold_params(threshold, parties)?;
+        if 2 * threshold - 1 > parties {
+            return Err(MpcError::InvalidThreshold);
+        }
+        let points: Vec<u64> = (1..=parties as u64).collect();
+        let lagrange = ShamirSecretSharing::new().precompute_lagrange_coefficients(&points)?;
+        Ok(Self { parties, threshold, lagrange, stats: Mutex::new(ProtocolStats::new()) })
+    }
+
+    /// 重构门限
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// 记录一轮全体广播（每方向其余各方发送 `elements` 个域元素）
+    fn record_broadcast(&self, elements: usize) {
+        let bytes = (self.parties * (self.parties - 1) * elements) as u64 * ELEMENT_BYTES;
+        let mut stats = lock_stats(&self.stats);
+        stats.record_rounds(1);
+        stats.record_sent(bytes);
+        stats.record_received(bytes);
+    }
+
+    /// 本地相乘后重新分享降次
+    fn degree_reduce(&self, x: &[Share], y: &[Share]) -> Result<Vec<Share>> {
+        if x.len() != self.parties || y.len() != self.parties {
+            return Err(MpcError::InvalidSecretShare);
+        }
+        let mut result: Vec<Share> = (1..=self.parties as u64).map(|i| Share::new(i, 0)).collect();
+        for ((xi, yi), lambda) in x.iter().zip(y).zip(&self.lagrange) {
+            check_points(xi, yi)?;
+            let reshared = ShamirSecretSharing::share(&field_mul(xi.y, yi.y), self.threshold, self.parties)?;
+            for (out, share) in result.iter_mut().zip(reshared) {
+                out.y = field_add(out.y, field_mul(*lambda, share.y));
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl ArithmeticBlackBox for ShamirBlackBox {
+    type Value = Vec<Share>;
+
+    fn parties(&self) -> usize {
+        self.parties
+    }
+
+    fn input(&self, party: usize, value: u64) -> AbbFuture<'_, Vec<Share>> {
+        Box::pin(async move {
+            check_party(party, self.parties)?;
+            let shares = ShamirSecretSharing::share(&value, self.threshold, self.parties)?;
+            let bytes = (self.parties - 1) as u64 * ELEMENT_BYTES;
+            let mut stats = lock_stats(&self.stats);
+            stats.record_rounds(1);
+            stats.record_sent(bytes);
+            stats.record_received(bytes);
+            Ok(shares)
+        })
+    }
+
+    fn constant(&self, value: u64) -> AbbFuture<'_, Vec<Share>> {
+        Box::pin(async move {
+            check_element(value)?;
+            // 常数多项式：每方的分享都等于该常数
+            Ok((1..=self.parties as u64).map(|i| Share::new(i, value)).collect())
+        })
+    }
+
+    fn add<'a>(&'a self, a: &'a Vec<Share>, b: &'a Vec<Share>) -> AbbFuture<'a, Vec<Share>> {
+        Box::pin(async move { combine_shares(a, b, field_add) })
+    }
+
+    fn sub<'a>(&'a self, a: &'a Vec<Share>, b: &'a Vec<Share>) -> AbbFuture<'a, Vec<Share>> {
+        Box::pin(async move { combine_shares(a, b, field_sub) })
+    }
+
+    fn mul_public<'a>(&'a self, a: &'a Vec<Share>, constant: u64) -> AbbFuture<'a, Vec<Share>> {
+        Box::pin(async move { Ok(a.iter().map(|x| Share::new(x.x, field_mul(x.y, constant))).collect()) })
+    }
+
+    fn mul<'a>(&'a self, a: &'a Vec<Share>, b: &'a Vec<Share>) -> AbbFuture<'a, Vec<Share>> {
+        Box::pin(async move {
+            let product = self.degree_reduce(a, b)?;
+            self.record_broadcast(1);
+            Ok(product)
+        })
+    }
+
+    fn mul_batch<'a>(&'a self, a: &'a [Vec<Share>], b: &'a [Vec<Share>]) -> AbbFuture<'a, Vec<Vec<Share>>> {
+        Box::pin(async move {
+            check_batch(a.len(), b.len())?;
+            let products = a.iter().zip(b)
+                .map(|(x, y)| self.degree_reduce(x, y))
+                .collect::<Result<Vec<_>>>()?;
+            self.record_broadcast(a.len());
+            Ok(products)
+        })
+    }
+
+    fn open<'a>(&'a self, a: &'a Vec<Share>) -> AbbFuture<'a, u64> {
+        Box::pin(async move {
+            let value = ShamirSecretSharing::reconstruct(a, self.threshold)?;
+            self.record_broadcast(1);
+            Ok(value)
+        })
+    }
+
+    fn random(&self) -> AbbFuture<'_, Vec<Share>> {
+        Box::pin(async move {
+            // 每方分享一个随机数，结果为各方贡献之和
+            let mut result: Vec<Share> = (1..=self.parties as u64).map(|i| Share::new(i, 0)).collect();
+            for _ in 0..self.parties {
+                let (_, shares) = ShamirSecretSharing::generate_random_shares(self.threshold, self.parties)?;
+                for (out, share) in result.iter_mut().zip(shares) {
+                    out.y = field_add(out.y, share.y);
+                }
+            }
+            self.record_broadcast(1);
+            Ok(result)
+        })
+    }
+
+    fn stats(&self) -> ProtocolStats {
+        lock_stats(&self.stats).clone()
+    }
+}
+
+// ============================================================================
+// SPDZ 后端
+// ============================================================================
+
+/// 基于 SPDZ 认证分享的算术黑盒
+///
+/// 线性运算直接使用 `SPDZShareProtocol`；乘法消耗一个 Beaver 三元组
+/// （由本地分发者生成），公开 d = x - a、e = y - b 后计算
+/// z = c + d·b + e·a + d·e。
+pub struct SpdzBlackBox {
+    /// SPDZ 协议处理器
+    protocol: SPDZShareProtocol,
+    /// 参与方数量
+    parties: usize,
+    /// 累计统计
+    stats: Mutex<ProtocolStats>,
+}
+
+impl SpdzBlackBox {
+    /// 创建 SPDZ 后端
+    pub fn new(params: SPDZParams) -> Result<Self> {
+        let parties = params.num_parties;
+        Ok(Self { protocol: SPDZShareProtocol::new(params)?, parties, stats: Mutex::new(ProtocolStats::new()) })
+    }
+
+    /// 底层的 SPDZ 协议处理器
+    pub fn protocol(&self) -> &SPDZShareProtocol {
+        &self.protocol
+    }
+
+    /// 记录一轮全体广播（每方向其余各方发送 `elements` 个带 MAC 的分享）
+    fn record_broadcast(&self, elements: usize) {
+        let bytes = (self.parties * (self.parties - 1) * elements) as u64 * 2 * ELEMENT_BYTES;
+        let mut stats = lock_stats(&self.stats);
+        stats.record_rounds(1);
+        stats.record_sent(bytes);
+        stats.record_received(bytes);
+    }
+
+    /// 使用一个 Beaver 三元组相乘
+    fn beaver_multiply(&self, x: &AuthenticatedShare, y: &AuthenticatedShare) -> Result<AuthenticatedShare> {
+        let mut rng = rand::thread_rng();
+        let (a, b) = (rng.gen_range(0..FIELD_PRIME), rng.gen_range(0..FIELD_PRIME));
+        let (a_share, b_share) = (self.protocol.input(a)?, self.protocol.input(b)?);
+        let c_share = self.protocol.input(field_mul(a, b))?;
+
+        let d = self.protocol.open(&self.protocol.sub(x, &a_share)?)?;
+        let e = self.protocol.open(&self.protocol.sub(y, &b_share)?)?;
+
+        let db = self.protocol.mul_public(&b_share, d);
+        let ea = self.protocol.mul_public(&a_share, e);
+        let de = self.protocol.input(field_mul(d, e))?;
+        let z = self.protocol.add(&c_share, &db)?;
+        let z = self.protocol.add(&z, &ea)?;
+        self.protocol.add(&z, &de)
+    }
+}
+
+impl ArithmeticBlackBox for SpdzBlackBox {
+    type Value = AuthenticatedShare;
+
+    fn parties(&self) -> usize {
+        self.parties
+    }
+
+    fn input(&self, party: usize, value: u64) -> AbbFuture<'_, AuthenticatedShare> {
+        Box::pin(async move {
+            check_party(party, self.parties)?;
+            let share = self.protocol.input(value)?;
+            let bytes = (self.parties - 1) as u64 * 2 * ELEMENT_BYTES;
+            let mut stats = lock_stats(&self.stats);
+            stats.record_rounds(1);
+            stats.record_sent(bytes);
+            stats.record_received(bytes);
+            Ok(share)
+        })
+    }
+
+    fn constant(&self, value: u64) -> AbbFuture<'_, AuthenticatedShare> {
+        Box::pin(async move {
+            check_element(value)?;
+            self.protocol.input(value)
+        })
+    }
+
+    fn add<'a>(&'a self, a: &'a AuthenticatedShare, b: &'a AuthenticatedShare) -> AbbFuture<'a, AuthenticatedShare> {
+        Box::pin(async move { self.protocol.add(a, b) })
+    }
+
+    fn sub<'a>(&'a self, a: &'a AuthenticatedShare, b: &'a AuthenticatedShare) -> AbbFuture<'a, AuthenticatedShare> {
+        Box::pin(async move { self.protocol.sub(a, b) })
+    }
+
+    fn mul_public<'a>(&'a self, a: &'a AuthenticatedShare, constant: u64) -> AbbFuture<'a, AuthenticatedShare> {
+        Box::pin(async move { Ok(self.protocol.mul_public(a, constant)) })
+    }
+
+    fn mul<'a>(&'a self, a: &'a AuthenticatedShare, b: &'a AuthenticatedShare) -> AbbFuture<'a, AuthenticatedShare> {
+        Box::pin(async move {
+            let product = self.beaver_multiply(a, b)?;
+            self.record_broadcast(2);
+            lock_stats(&self.stats).record_preprocessing(1);
+            Ok(product)
+        })
+    }
+
+    fn mul_batch<'a>(
+        &'a self,
+        a: &'a [AuthenticatedShare],
+        b: &'a [AuthenticatedShare],
+    ) -> AbbFuture<'a, Vec<AuthenticatedShare>> {
+        Box::pin(async move {
+            check_batch(a.len(), b.len())?;
+            let products = a.iter().zip(b)
+                .map(|(x, y)| self.beaver_multiply(x, y))
+                .collect::<Result<Vec<_>>>()?;
+            self.record_broadcast(2 * a.len());
+            lock_stats(&self.stats).record_preprocessing(a.len());
+            Ok(products)
+        })
+    }
+
+    fn open<'a>(&'a self, a: &'a AuthenticatedShare) -> AbbFuture<'a, u64> {
+        Box::pin(async move {
+            let value = self.protocol.open(a)?;
+            self.record_broadcast(1);
+            Ok(value)
+        })
+    }
+
+    fn random(&self) -> AbbFuture<'_, AuthenticatedShare> {
+        Box::pin(async move {
+            let share = self.protocol.random()?;
+            self.record_broadcast(1);
+            Ok(share)
+        })
+    }
+
+    fn stats(&self) -> ProtocolStats {
+        lock_stats(&self.stats).clone()
+    }
+}
+
+// ============================================================================
+// 辅助函数
+// ============================================================================
+
+/// 取得统计信息的锁（锁中毒时继续使用内部数据）
+fn lock_stats(stats: &Mutex<ProtocolStats>) -> std::sync::MutexGuard<'_, ProtocolStats> {
+    stats.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn check_party(party: usize, parties: usize) -> Result<()> {
+    if party >= parties {
+        return Err(MpcError::ProtocolError(format!("Unknown input party {}", party)));
+    }
+    Ok(())
+}
+
+fn check_element(value: u64) -> Result<()> {
+    if !validate_field_element(value) {
+        return Err(MpcError::CryptographicError("Value out of field range".to_string()));
+    }
+    Ok(())
+}
+
+fn check_batch(left: usize, right: usize) -> Result<()> {
+    if left != right {
+        return Err(MpcError::ProtocolError("Batch arrays must have same length".to_string()));
+    }
+    Ok(())
+}
+
+/// 逐方组合两个 Shamir 分享向量
+fn combine_shares(a: &[Share], b: &[Share], op: fn(u64, u64) -> u64) -> Result<Vec<Share>> {
+    if a.len() != b.len() {
+        return Err(MpcError::InvalidSecretShare);
+    }
+    a.iter().zip(b)
+        .map(|(x, y)| check_points(x, y).map(|_| Share::new(x.x, op(x.y, y.y))))
+        .collect()
+}
+
+fn check_points(x: &Share, y: &Share) -> Result<()> {
+    if x.x != y.x {
+        return Err(MpcError::InvalidSecretShare);
+    }
+    Ok(())
+}
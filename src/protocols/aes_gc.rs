@@ -0,0 +1,412 @@
+//! # 两方安全 AES 求值 (Two-Party Secure AES Evaluation)
+//!
+//! 密钥持有方和明文持有方在混淆电路下计算 AES-128，明文持有方得到密文，
+//! 双方都不知道对方的输入。这是检验混淆电路、OT 和网络层能否协同工作的标准用例。
+//!
+//! ## 协议流程
+//!
+//! 1. **混淆**: 密钥持有方（混淆方）用半门方案混淆 AES-128 电路，发送混淆表、
+//!    输出解码信息、自己密钥对应的输入标签、约定电路的结构摘要，以及每个明文比特
+//!    一次 OT 的发送方公钥
+//! 2. **选择**: 明文持有方（求值方）检查电路摘要，以明文比特为选择位回应 128 次 OT
+//! 3. **传输**: 混淆方把每条明文输入线的两个标签作为 OT 的两个消息发出
+//! 4. **求值**: 求值方解密得到明文对应的标签，求值混淆电路并解码出密文
+//!
+//! 电路可以是内置的 AES-128 电路，也可以从标准的 Bristol Fashion `aes_128.txt`
+//! 加载（见 `garbled_circuits::aes_circuit`）。
+//!
+//! ## 吞吐量目标
+//!
+//! `benchmark_aes_gc` 分别测量混淆 + 求值的核心吞吐量和包括 OT、消息序列化在内的
+//! 端到端吞吐量。核心吞吐量在 release 构建、支持 AES-NI 的处理器上的目标是每秒至少
+//! `AES_GC_TARGET_BLOCKS_PER_SECOND` 次 AES；每个分组的 128 次基础 OT 是端到端
+//! 延迟的主要部分，批量求值时应改用 OT 扩展。
+//!
+//! 当前实现针对半诚实敌手。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::garbled_circuits::aes128_encrypt;
+//! use mpc_api::protocols::aes_gc::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let circuit = AesGcCircuit::builtin()?;
+//! let key = *b"sixteen byte key";
+//! let plaintext = *b"attack at dawn!!";
+//!
+//! let output = execute_aes_gc(&circuit, &key, &plaintext)?;
+//! assert_eq!(output.result, aes128_encrypt(&key, &plaintext));
+//! assert_eq!(output.stats.rounds, 3);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::garbled_circuits::{
+    aes128_encrypt, agreed_circuit_digest, AesBlock, AesCircuit, HalfGatesCircuit, HalfGatesGarbledCircuit, Label,
+};
+use crate::oblivious_transfer::{AwaitingReceiverMsg, AwaitingTransfer, OtReceiver, OtSender, OtTransfer};
+use crate::protocols::stats::{ProtocolOutput, ProtocolStats, StatsRecorder};
+use crate::{MpcError, Result};
+use rand::Rng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// 核心吞吐量目标（每秒 AES 次数）
+pub const AES_GC_TARGET_BLOCKS_PER_SECOND: f64 = 1000.0;
+
+/// 混淆后的 AES 电路以及双方约定的电路摘要
+#[derive(Debug, Clone)]
+pub struct AesGcCircuit {
+    aes: AesCircuit,
+    compiled: HalfGatesCircuit,
+    digest: [u8; 32],
+}
+
+impl AesGcCircuit {
+    /// 为布局已确定的 AES 电路做混淆前的准备
+    pub fn new(aes: AesCircuit) -> Result<Self> {
+        Ok(Self {
+            compiled: HalfGatesCircuit::compile(aes.circuit())?,
+            digest: agreed_circuit_digest(aes.circuit())?,
+            aes,
+        })
+    }
+
+    /// 使用内置的 AES-128 电路
+    pub fn builtin() -> Result<Self> {
+        Self::new(AesCircuit::builtin())
+    }
+
+    /// 读取 Bristol Fashion 格式的 AES-128 电路文件
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(AesCircuit::load(path)?)
+    }
+
+    /// AES 电路
+    pub fn aes(&self) -> &AesCircuit {
+        &self.aes
+    }
+
+    /// 电路结构摘要，双方必须一致
+    pub fn digest(&self) -> [u8; 32] {
+        self.digest
+    }
+
+    /// AND 门数量
+    pub fn and_count(&self) -> usize {
+        self.compiled.and_count()
+    }
+}
+
+/// 第 1 条消息：混淆方发给求值方
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AesGarbledOffer {
+    /// 混淆方使用的电路摘要
+    pub circuit_digest: [u8; 32],
+    /// 混淆电路
+    pub garbled: HalfGatesGarbledCircuit,
+    /// 混淆方密钥对应的输入标签
+    pub key_labels: Vec<Label>,
+    /// 每个明文比特一次 OT 的发送方公钥
+    pub ot_public_keys: Vec<u64>,
+}
+
+/// 第 2 条消息：求值方以明文比特为选择位回应 OT
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AesLabelRequest {
+    /// 每次 OT 的接收方响应
+    pub ot_responses: Vec<u64>,
+}
+
+/// 第 3 条消息：混淆方通过 OT 发送明文输入线的标签
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AesLabelTransfer {
+    /// 每次 OT 的密文
+    pub transfers: Vec<OtTransfer>,
+}
+
+/// 密钥持有方（混淆方），已发出混淆电路，等待 OT 响应
+#[derive(Debug)]
+pub struct AesKeyHolder {
+    senders: Vec<OtSender<AwaitingReceiverMsg>>,
+}
+
+impl AesKeyHolder {
+    /// 混淆电路并生成第 1 条消息
+    pub fn start(circuit: &AesGcCircuit, key: &AesBlock) -> Result<(Self, AesGarbledOffer)> {
+        let aes = circuit.aes();
+        let (garbled, encoding) = circuit.compiled.garble(&mut rand::thread_rng());
+        let key_bits = aes.bit_order().encode(key);
+        let key_labels = aes.key_positions()
+            .zip(key_bits)
+            .map(|(position, bit)| encoding.label(position, bit).ok_or_else(missing_label))
+            .collect::<Result<Vec<_>>>()?;
+        let senders = aes.plaintext_positions()
+            .map(|position| {
+                let (zero, one) = encoding.label_pair(position).ok_or_else(missing_label)?;
+                OtSender::new(zero.to_vec(), one.to_vec())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let offer = AesGarbledOffer {
+            circuit_digest: circuit.digest(),
+            garbled,
+            key_labels,
+            ot_public_keys: senders.iter().map(OtSender::public_key).collect(),
+        };
+        Ok((Self { senders }, offer))
+    }
+
+    /// 处理 OT 响应，生成第 3 条消息
+    pub fn transfer(self, request: &AesLabelRequest) -> Result<AesLabelTransfer> {
+        if request.ot_responses.len() != self.senders.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} OT responses, found {}", self.senders.len(), request.ot_responses.len()
+            )));
+        }
+        let transfers = self.senders.into_iter()
+            .zip(&request.ot_responses)
+            .map(|(sender, &response)| sender.receive(response).send())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(AesLabelTransfer { transfers })
+    }
+}
+
+/// 明文持有方（求值方），已回应 OT，等待明文标签
+#[derive(Debug)]
+pub struct AesPlaintextHolder {
+    offer: AesGarbledOffer,
+    receivers: Vec<OtReceiver<AwaitingTransfer>>,
+}
+
+impl AesPlaintextHolder {
+    /// 检查混淆方的消息并生成第 2 条消息
+    ///
+    /// # 返回值
+    /// 电路摘要与本地电路不一致时返回 `AuthenticationError`
+    pub fn respond(circuit: &AesGcCircuit, plaintext: &AesBlock, offer: AesGarbledOffer) -> Result<(Self, AesLabelRequest)> {
+        if offer.circuit_digest != circuit.digest() {
+            return Err(MpcError::AuthenticationError("Garbler used a different AES circuit".to_string()));
+        }
+        let aes = circuit.aes();
+        if offer.key_labels.len() != aes.key_positions().len() || offer.ot_public_keys.len() != aes.plaintext_positions().len() {
+            return Err(MpcError::ProtocolError("Garbled offer has the wrong number of inputs".to_string()));
+        }
+
+        let (receivers, ot_responses): (Vec<_>, Vec<_>) = aes.bit_order().encode(plaintext)
+            .into_iter()
+            .zip(&offer.ot_public_keys)
+            .map(|(bit, &sender_public)| OtReceiver::new(bit).receive(sender_public))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        Ok((Self { offer, receivers }, AesLabelRequest { ot_responses }))
+    }
+
+    /// 取得明文标签，求值混淆电路得到密文
+    pub fn finish(self, circuit: &AesGcCircuit, transfer: AesLabelTransfer) -> Result<AesBlock> {
+        if transfer.transfers.len() != self.receivers.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} OT transfers, found {}", self.receivers.len(), transfer.transfers.len()
+            )));
+        }
+        let aes = circuit.aes();
+        let mut inputs = vec![[0u8; 16]; aes.circuit().input_wires.len()];
+        for (position, label) in aes.key_positions().zip(&self.offer.key_labels) {
+            inputs[position] = *label;
+        }
+        for ((position, receiver), transfer) in aes.plaintext_positions().zip(self.receivers).zip(transfer.transfers) {
+            inputs[position] = receiver.finish(transfer)?
+                .try_into()
+                .map_err(|_| MpcError::ProtocolError("OT returned a label of the wrong length".to_string()))?;
+        }
+
+        let outputs = circuit.compiled.evaluate(&self.offer.garbled, &inputs)?;
+        aes.decode_ciphertext(&self.offer.garbled.decode(&outputs)?)
+    }
+}
+
+/// 在同一进程中执行完整协议
+///
+/// 每条消息都经过序列化，统计信息中的字节数即实际需要传输的数据量。
+///
+/// # 返回值
+/// 明文持有方得到的密文
+pub fn execute_aes_gc(circuit: &AesGcCircuit, key: &AesBlock, plaintext: &AesBlock) -> Result<ProtocolOutput<AesBlock>> {
+    let mut recorder = StatsRecorder::start();
+
+    let (key_holder, offer) = AesKeyHolder::start(circuit, key)?;
+    let offer = relay(&offer, recorder.stats_mut())?;
+    let (plaintext_holder, request) = AesPlaintextHolder::respond(circuit, plaintext, offer)?;
+    let request = relay(&request, recorder.stats_mut())?;
+    let transfer = key_holder.transfer(&request)?;
+    let transfer = relay(&transfer, recorder.stats_mut())?;
+    let ciphertext = plaintext_holder.finish(circuit, transfer)?;
+
+    Ok(recorder.finish(ciphertext))
+}
+
+/// 协议中的角色和私有输入
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AesGcInput {
+    /// 密钥持有方（混淆方）
+    Key(AesBlock),
+    /// 明文持有方（求值方）
+    Plaintext(AesBlock),
+}
+
+/// 通过节点传输与另一方执行协议
+///
+/// 传输中必须恰好有一个对等节点。每一轮双方都发送一条消息，当前轮没有内容的一方
+/// 发送空载荷。
+///
+/// # 返回值
+/// 明文持有方返回密文，密钥持有方返回 `None`
+#[cfg(feature = "node")]
+pub fn run_aes_gc(
+    transport: &mut crate::node::MeshTransport,
+    circuit: &AesGcCircuit,
+    input: AesGcInput,
+    stats: &mut ProtocolStats,
+) -> Result<Option<AesBlock>> {
+    use std::collections::BTreeMap;
+
+    let peers = transport.peer_ids();
+    let [peer] = peers[..] else {
+        return Err(MpcError::ProtocolError(format!("AES evaluation needs exactly one peer, found {}", peers.len())));
+    };
+    let mut round = |label: &str, payload: Vec<u8>, stats: &mut ProtocolStats| {
+        let mut received = transport.exchange(label, &BTreeMap::from([(peer, payload)]), stats)?;
+        received.remove(&peer).ok_or_else(|| MpcError::ProtocolError(format!("No {} message from party {}", label, peer)))
+    };
+
+    match input {
+        AesGcInput::Key(key) => {
+            let (key_holder, offer) = AesKeyHolder::start(circuit, &key)?;
+            round("aes_gc/offer", encode(&offer)?, stats)?;
+            let request = decode(&round("aes_gc/request", Vec::new(), stats)?)?;
+            round("aes_gc/transfer", encode(&key_holder.transfer(&request)?)?, stats)?;
+            Ok(None)
+        }
+        AesGcInput::Plaintext(plaintext) => {
+            let offer = decode(&round("aes_gc/offer", Vec::new(), stats)?)?;
+            let (plaintext_holder, request) = AesPlaintextHolder::respond(circuit, &plaintext, offer)?;
+            round("aes_gc/request", encode(&request)?, stats)?;
+            let transfer = decode(&round("aes_gc/transfer", Vec::new(), stats)?)?;
+            plaintext_holder.finish(circuit, transfer).map(Some)
+        }
+    }
+}
+
+/// 吞吐量测量结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AesGcBenchmark {
+    /// 求值的分组数
+    pub blocks: usize,
+    /// 每个分组的 AND 门数量
+    pub and_gates: usize,
+    /// 每个分组的混淆电路大小（字节）
+    pub garbled_bytes_per_block: usize,
+    /// 混淆总耗时
+    pub garbling_time: Duration,
+    /// 求值总耗时
+    pub evaluation_time: Duration,
+    /// 混淆 + 求值的吞吐量（每秒 AES 次数）
+    pub blocks_per_second: f64,
+    /// 完整协议（含 OT 和序列化）的吞吐量
+    pub end_to_end_blocks_per_second: f64,
+    /// 完整协议每个分组的平均统计
+    pub protocol_stats: ProtocolStats,
+}
+
+impl AesGcBenchmark {
+    /// 核心吞吐量是否达到 `AES_GC_TARGET_BLOCKS_PER_SECOND`
+    pub fn meets_target(&self) -> bool {
+        self.blocks_per_second >= AES_GC_TARGET_BLOCKS_PER_SECOND
+    }
+}
+
+/// 用随机密钥和明文测量吞吐量，并检查每个结果与软件 AES 一致
+///
+/// # 参数
+/// - `circuit`: 要测量的电路
+/// - `blocks`: 求值的分组数，至少为 1
+pub fn benchmark_aes_gc(circuit: &AesGcCircuit, blocks: usize) -> Result<AesGcBenchmark> {
+    if blocks == 0 {
+        return Err(MpcError::ProtocolError("Benchmark needs at least one block".to_string()));
+    }
+    let mut rng = rand::thread_rng();
+    let inputs: Vec<(AesBlock, AesBlock)> = (0..blocks).map(|_| (rng.gen(), rng.gen())).collect();
+    let aes = circuit.aes();
+    let check = |key: &AesBlock, plaintext: &AesBlock, ciphertext: AesBlock| {
+        if ciphertext != aes128_encrypt(key, plaintext) {
+            return Err(MpcError::ProtocolError("Garbled AES produced a wrong ciphertext".to_string()));
+        }
+        Ok(())
+    };
+
+    let mut garbling_time = Duration::ZERO;
+    let mut evaluation_time = Duration::ZERO;
+    let mut garbled_bytes_per_block = 0;
+    for (key, plaintext) in &inputs {
+        let started = Instant::now();
+        let (garbled, encoding) = circuit.compiled.garble(&mut rng);
+        garbling_time += started.elapsed();
+
+        let labels = encoding.encode(&aes.encode_inputs(key, plaintext))?;
+        let started = Instant::now();
+        let outputs = circuit.compiled.evaluate(&garbled, &labels)?;
+        let ciphertext = aes.decode_ciphertext(&garbled.decode(&outputs)?)?;
+        evaluation_time += started.elapsed();
+
+        garbled_bytes_per_block = garbled.size_in_bytes();
+        check(key, plaintext, ciphertext)?;
+    }
+
+    let mut protocol_stats = ProtocolStats::new();
+    for (key, plaintext) in &inputs {
+        let (ciphertext, stats) = execute_aes_gc(circuit, key, plaintext)?.into_parts();
+        check(key, plaintext, ciphertext)?;
+        protocol_stats.merge(&stats);
+    }
+    let end_to_end_time = protocol_stats.wall_time;
+    protocol_stats.rounds /= blocks;
+    protocol_stats.bytes_sent /= blocks as u64;
+    protocol_stats.bytes_received /= blocks as u64;
+    protocol_stats.wall_time = end_to_end_time / blocks as u32;
+
+    Ok(AesGcBenchmark {
+        blocks,
+        and_gates: circuit.and_count(),
+        garbled_bytes_per_block,
+        garbling_time,
+        evaluation_time,
+        blocks_per_second: blocks as f64 / (garbling_time + evaluation_time).as_secs_f64().max(f64::MIN_POSITIVE),
+        end_to_end_blocks_per_second: blocks as f64 / end_to_end_time.as_secs_f64().max(f64::MIN_POSITIVE),
+        protocol_stats,
+    })
+}
+
+fn missing_label() -> MpcError {
+    MpcError::ProtocolError("AES circuit input is missing from the garbled encoding".to_string())
+}
+
+/// 模拟一次传输：序列化后反序列化，并记录一轮通信
+fn relay<T: Serialize + DeserializeOwned>(message: &T, stats: &mut ProtocolStats) -> Result<T> {
+    let bytes = encode(message)?;
+    stats.record_rounds(1);
+    stats.record_sent(bytes.len() as u64);
+    stats.record_received(bytes.len() as u64);
+    decode(&bytes)
+}
+
+fn encode<T: Serialize>(message: &T) -> Result<Vec<u8>> {
+    bincode::serialize(message).map_err(|e| MpcError::SerializationError(e.to_string()))
+}
+
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    bincode::deserialize(bytes).map_err(|e| MpcError::SerializationError(e.to_string()))
+}
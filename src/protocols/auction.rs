@@ -0,0 +1,178 @@
+//! # 密封出价拍卖 (Sealed-Bid Auction)
+//!
+//! 在秘密分享的出价上不经意地求最大值及其下标（argmax），只公开获胜者，
+//! 成交价可选公开，其余出价和比较结果都不公开：
+//!
+//! 1. **锦标赛**: 候选 ([出价], [下标]) 两两配对，下标初始为公开常数；
+//!    每一轮对所有配对同步做安全比较 [c] = [a < b]
+//! 2. **选择**: 胜者为 [a] + [c]·([b] - [a])，下标同理，每一轮一次乘法；
+//!    奇数个候选时最后一个直接晋级。⌈log₂ n⌉ 轮后剩下最高出价和获胜下标的分享
+//! 3. **公开**: 只公开获胜下标，按需公开成交价
+//!
+//! 出价相同时下标较小的一方获胜。出价必须落在 [0, 2^ℓ) 内，
+//! ℓ 不超过 `MAX_COMPARISON_BITS`（见 [`comparison`](super::comparison)）。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+//! use mpc_api::protocols::auction::*;
+//! use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let bids = [1000u64, 1500, 1200].iter()
+//!     .map(|bid| ShamirSecretSharing::share(bid, 2, 3))
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//!
+//! let auction = SealedBidAuction::new(3, 2, 16)?;
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//! let outcome = auction.run(&bids, true, &mut generator)?.result;
+//! assert_eq!(outcome.winner, 1);
+//! assert_eq!(outcome.price, Some(1500));
+//! # Ok(())
+//! # }
+//! ```
+
+use super::comparison::{ComparisonPair, SecureComparison};
+use super::oram::Gadgets;
+use crate::secret_sharing::bits::{add_shares, sub_shares};
+use super::stats::{ProtocolOutput, StatsRecorder};
+use crate::beaver_triples::BeaverTripleGenerator;
+use crate::secret_sharing::Share;
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+
+/// 最高出价及其下标的分享
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedArgmax {
+    /// 最高出价的分享
+    pub max: Vec<Share>,
+    /// 获胜下标的分享
+    pub index: Vec<Share>,
+}
+
+/// 拍卖结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuctionOutcome {
+    /// 获胜者在出价列表中的下标
+    pub winner: usize,
+    /// 成交价，只在要求公开时给出
+    pub price: Option<u64>,
+}
+
+/// 密封出价拍卖协议
+#[derive(Debug, Clone, Copy)]
+pub struct SealedBidAuction {
+    comparison: SecureComparison,
+    party_count: usize,
+    threshold: usize,
+}
+
+impl SealedBidAuction {
+    /// 创建协议
+    ///
+    /// # 参数
+    /// - `party_count`: 参与方数量
+    /// - `threshold`: 重构门限
+    /// - `bit_length`: 出价位宽 ℓ，所有出价必须落在 [0, 2^ℓ) 内
+    pub fn new(party_count: usize, threshold: usize, bit_length: usize) -> Result<Self> {
+        Ok(Self { comparison: SecureComparison::new(party_count, threshold, bit_length)?, party_count, threshold })
+    }
+
+    /// 不经意地求最高出价和获胜下标，结果保持秘密分享
+    ///
+    /// # 参数
+    /// - `bids`: 每个出价的分享
+    /// - `generator`: 提供 Beaver 三元组的生成器
+    ///
+    /// # 返回值
+    /// 返回最高出价和获胜下标的分享及执行统计
+    pub fn argmax(
+        &self,
+        bids: &[Vec<Share>],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<SharedArgmax>> {
+        let mut recorder = StatsRecorder::start();
+        let mut gadgets = self.gadgets(bids, generator)?;
+        let argmax = self.tournament(bids, &mut gadgets)?;
+        gadgets.multiplier.record(recorder.stats_mut());
+        Ok(recorder.finish(argmax))
+    }
+
+    /// 执行拍卖，只公开获胜者和（可选的）成交价
+    ///
+    /// # 参数
+    /// - `bids`: 每个出价的分享
+    /// - `reveal_price`: 是否公开成交价
+    /// - `generator`: 提供 Beaver 三元组的生成器
+    ///
+    /// # 返回值
+    /// 返回拍卖结果和执行统计
+    pub fn run(
+        &self,
+        bids: &[Vec<Share>],
+        reveal_price: bool,
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<AuctionOutcome>> {
+        let mut recorder = StatsRecorder::start();
+        let mut gadgets = self.gadgets(bids, generator)?;
+        let argmax = self.tournament(bids, &mut gadgets)?;
+
+        let winner = gadgets.open(&argmax.index)? as usize;
+        let price = if reveal_price { Some(gadgets.open(&argmax.max)?) } else { None };
+        if winner >= bids.len() {
+            return Err(MpcError::ProtocolError("Opened winner index is out of range".to_string()));
+        }
+
+        gadgets.multiplier.record(recorder.stats_mut());
+        Ok(recorder.finish(AuctionOutcome { winner, price }))
+    }
+
+    fn gadgets<'a>(&self, bids: &[Vec<Share>], generator: &'a mut dyn BeaverTripleGenerator) -> Result<Gadgets<'a>> {
+        if bids.is_empty() {
+            return Err(MpcError::ProtocolError("Auction needs at least one bid".to_string()));
+        }
+        self.comparison.check_generator(generator)?;
+        for bid in bids {
+            self.comparison.check_shares(bid)?;
+        }
+        Ok(Gadgets::new(generator, self.threshold, self.party_count))
+    }
+
+    /// 锦标赛求 argmax：每一轮同步比较所有配对，再用一次乘法选出胜者
+    fn tournament(&self, bids: &[Vec<Share>], gadgets: &mut Gadgets) -> Result<SharedArgmax> {
+        let mut candidates: Vec<(Vec<Share>, Vec<Share>)> = bids.iter()
+            .enumerate()
+            .map(|(i, bid)| (bid.clone(), gadgets.constant(i as u64)))
+            .collect();
+
+        while candidates.len() > 1 {
+            let pairs: Vec<ComparisonPair> = candidates.chunks_exact(2)
+                .map(|pair| (pair[0].0.as_slice(), pair[1].0.as_slice()))
+                .collect();
+            let less = self.comparison.less_than_with(gadgets, &pairs)?;
+
+            // [a] + [a < b]·([b] - [a])，出价和下标在同一轮选择
+            let (selectors, differences): (Vec<Vec<Share>>, Vec<Vec<Share>>) = candidates.chunks_exact(2)
+                .zip(&less)
+                .flat_map(|(pair, c)| {
+                    [(c.clone(), sub_shares(&pair[1].0, &pair[0].0)), (c.clone(), sub_shares(&pair[1].1, &pair[0].1))]
+                })
+                .unzip();
+            let mut products = gadgets.multiply(&selectors, &differences)?.into_iter();
+
+            let odd = (candidates.len() % 2 == 1).then(|| candidates[candidates.len() - 1].clone());
+            candidates = candidates.chunks_exact(2)
+                .map(|pair| {
+                    let value = products.next().expect("one product per value");
+                    let index = products.next().expect("one product per index");
+                    (add_shares(&pair[0].0, &value), add_shares(&pair[0].1, &index))
+                })
+                .chain(odd)
+                .collect();
+        }
+
+        let (max, index) = candidates.remove(0);
+        Ok(SharedArgmax { max, index })
+    }
+}
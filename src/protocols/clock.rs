@@ -0,0 +1,301 @@
+//! # 逻辑时钟与时钟偏差 (Logical Clocks and Clock Skew)
+//!
+//! 各参与方的墙上时钟并不同步。如果直接比较对方消息中的时间戳与本地时间，
+//! 时钟偏慢的诚实参与方会被误判为超时或重放。本模块提供：
+//!
+//! - **逻辑时钟**: `LogicalClock` 为每个会话维护轮次计数器，消息头 `MessageHeader`
+//!   携带会话 ID、轮次和发送时间；轮次用于排序和检测重放，不依赖墙上时钟
+//! - **偏差估计握手**: 一次 `SkewProbe` / `SkewReply` 往返即可按 NTP 的方式
+//!   估计对方时钟相对本地的偏移和往返时延
+//! - **有界偏差容忍**: `ClockSkewTracker` 把对方的时间戳换算到本地时间后再检查，
+//!   并在基于对方时间戳的超时（例如带截止时间的承诺打开）上给出偏差余量；
+//!   换算后仍超出上限的时间戳会被拒绝
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::protocols::clock::*;
+//! use mpc_api::protocols::session::ProtocolSession;
+//! use std::time::Duration;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! // 偏差估计握手
+//! let mut tracker = ClockSkewTracker::new(Duration::from_secs(2));
+//! let probe = tracker.probe();
+//! let reply = SkewReply::respond(&probe); // 在对方执行
+//! let estimate = tracker.complete("party-2", &reply)?;
+//! assert!(estimate.offset().abs() < 1.0);
+//!
+//! // 每个会话的轮次计数器
+//! let session = ProtocolSession::root("spdz", b"parties=3");
+//! let mut clock = LogicalClock::new();
+//! let header = clock.stamp(session.id());
+//! assert_eq!(header.round, 1);
+//!
+//! // 接收方检查轮次和时间戳
+//! let mut receiver = LogicalClock::new();
+//! receiver.observe(&header)?;
+//! tracker.check_timestamp("party-2", header.sent_at)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::protocols::session::SessionId;
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 消息头：会话、逻辑轮次和发送方的墙上时间
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageHeader {
+    /// 所属会话
+    pub session: SessionId,
+    /// 发送方在该会话中的轮次
+    pub round: u64,
+    /// 发送方本地时钟下的发送时间
+    pub sent_at: SystemTime,
+}
+
+/// 按会话划分的逻辑时钟
+///
+/// 发送消息前调用 `stamp` 推进本地轮次；收到消息后调用 `observe`，
+/// 本地轮次按 Lamport 规则追上对方。同一发送方在同一会话中的轮次必须严格递增，
+/// 否则视为重放。
+#[derive(Debug, Clone, Default)]
+pub struct LogicalClock {
+    /// 每个会话的本地轮次
+    rounds: HashMap<SessionId, u64>,
+    /// 每个会话中已接受的最大对方轮次
+    observed: HashMap<SessionId, u64>,
+}
+
+impl LogicalClock {
+    /// 创建空的逻辑时钟
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 会话的当前轮次，尚未发送或接收消息时为 0
+    pub fn current(&self, session: SessionId) -> u64 {
+        self.rounds.get(&session).copied().unwrap_or(0)
+    }
+
+    /// 推进会话轮次并生成消息头
+    pub fn stamp(&mut self, session: SessionId) -> MessageHeader {
+        let round = self.rounds.entry(session).or_insert(0);
+        *round += 1;
+        MessageHeader {
+            session,
+            round: *round,
+            sent_at: SystemTime::now(),
+        }
+    }
+
+    /// 接收对方的消息头
+    ///
+    /// # 返回值
+    /// 更新后的本地轮次；轮次不大于该会话已接受的轮次时返回错误
+    pub fn observe(&mut self, header: &MessageHeader) -> Result<u64> {
+        let observed = self.observed.entry(header.session).or_insert(0);
+        if header.round <= *observed {
+            return Err(MpcError::ProtocolError(format!(
+                "Stale round {} in session {} (already at {})",
+                header.round, header.session, observed
+            )));
+        }
+        *observed = header.round;
+
+        let round = self.rounds.entry(header.session).or_insert(0);
+        *round = (*round).max(header.round);
+        Ok(*round)
+    }
+
+    /// 会话结束后释放计数器
+    pub fn forget(&mut self, session: SessionId) {
+        self.rounds.remove(&session);
+        self.observed.remove(&session);
+    }
+}
+
+/// 偏差估计握手的第一条消息，由发起方发送
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkewProbe {
+    /// 发起方发送时间（自 UNIX 纪元的纳秒数）
+    pub sent_at: u64,
+}
+
+/// 偏差估计握手的应答，由对方收到 `SkewProbe` 后立即返回
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkewReply {
+    /// 原样返回的探测发送时间
+    pub probe_sent_at: u64,
+    /// 对方收到探测的时间
+    pub received_at: u64,
+    /// 对方发送应答的时间
+    pub replied_at: u64,
+}
+
+impl SkewReply {
+    /// 用本地时钟应答探测
+    pub fn respond(probe: &SkewProbe) -> Self {
+        let now = unix_nanos(SystemTime::now());
+        Self {
+            probe_sent_at: probe.sent_at,
+            received_at: now,
+            replied_at: now,
+        }
+    }
+}
+
+/// 对方时钟的偏差估计
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SkewEstimate {
+    /// 对方时钟减本地时钟（纳秒），正值表示对方时钟偏快
+    pub offset_nanos: i64,
+    /// 握手的往返时延（不含对方处理时间）
+    pub round_trip: Duration,
+}
+
+impl SkewEstimate {
+    /// 以秒表示的偏移
+    pub fn offset(&self) -> f64 {
+        self.offset_nanos as f64 / 1e9
+    }
+
+    /// 偏移估计的不确定度：单向时延不可知，误差不超过往返时延的一半
+    pub fn uncertainty(&self) -> Duration {
+        self.round_trip / 2
+    }
+}
+
+/// 记录各参与方的时钟偏差并在有界偏差内接受其时间戳
+#[derive(Debug, Clone)]
+pub struct ClockSkewTracker {
+    /// 允许的最大时钟偏差
+    max_skew: Duration,
+    /// 各参与方的偏差估计
+    estimates: HashMap<String, SkewEstimate>,
+}
+
+impl ClockSkewTracker {
+    /// 创建偏差跟踪器
+    ///
+    /// # 参数
+    /// - `max_skew`: 允许的最大时钟偏差；对没有做过握手的参与方，时间戳与本地时间
+    ///   相差不超过此值即被接受
+    pub fn new(max_skew: Duration) -> Self {
+        Self {
+            max_skew,
+            estimates: HashMap::new(),
+        }
+    }
+
+    /// 允许的最大时钟偏差
+    pub fn max_skew(&self) -> Duration {
+        self.max_skew
+    }
+
+    /// 生成握手探测
+    pub fn probe(&self) -> SkewProbe {
+        SkewProbe {
+            sent_at: unix_nanos(SystemTime::now()),
+        }
+    }
+
+    /// 用对方的应答完成握手
+    ///
+    /// 偏移估计为 ((t1 - t0) + (t2 - t3)) / 2，其中 t0/t3 为本地发送/接收时间，
+    /// t1/t2 为对方接收/应答时间。偏移超过 `max_skew` 时返回错误且不记录估计。
+    pub fn complete(&mut self, peer_id: &str, reply: &SkewReply) -> Result<SkewEstimate> {
+        self.complete_at(peer_id, reply, SystemTime::now())
+    }
+
+    /// 以给定的本地接收时间完成握手
+    pub fn complete_at(&mut self, peer_id: &str, reply: &SkewReply, received_at: SystemTime) -> Result<SkewEstimate> {
+        let t0 = reply.probe_sent_at as i128;
+        let t1 = reply.received_at as i128;
+        let t2 = reply.replied_at as i128;
+        let t3 = unix_nanos(received_at) as i128;
+        if t3 < t0 || t2 < t1 {
+            return Err(MpcError::ProtocolError("Inconsistent clock skew handshake".to_string()));
+        }
+
+        let offset = ((t1 - t0) + (t2 - t3)) / 2;
+        let round_trip = ((t3 - t0) - (t2 - t1)).max(0);
+        let estimate = SkewEstimate {
+            offset_nanos: offset.clamp(i64::MIN as i128, i64::MAX as i128) as i64,
+            round_trip: Duration::from_nanos(round_trip.min(u64::MAX as i128) as u64),
+        };
+        if estimate.offset_nanos.unsigned_abs() > self.max_skew.as_nanos() as u64 {
+            return Err(MpcError::ProtocolError(format!(
+                "Clock skew with {} is {:.3}s, exceeds the {:?} bound",
+                peer_id, estimate.offset(), self.max_skew
+            )));
+        }
+
+        self.estimates.insert(peer_id.to_string(), estimate);
+        Ok(estimate)
+    }
+
+    /// 参与方的偏差估计
+    pub fn estimate(&self, peer_id: &str) -> Option<SkewEstimate> {
+        self.estimates.get(peer_id).copied()
+    }
+
+    /// 把对方时钟下的时间换算到本地时钟；没有估计时原样返回
+    pub fn to_local_time(&self, peer_id: &str, remote: SystemTime) -> SystemTime {
+        match self.estimate(peer_id) {
+            Some(estimate) if estimate.offset_nanos >= 0 => {
+                remote - Duration::from_nanos(estimate.offset_nanos as u64)
+            }
+            Some(estimate) => remote + Duration::from_nanos(estimate.offset_nanos.unsigned_abs()),
+            None => remote,
+        }
+    }
+
+    /// 基于对方时间戳判定超时时给予的余量
+    ///
+    /// 已做过握手的参与方为估计不确定度（不超过 `max_skew`），否则为 `max_skew`。
+    pub fn deadline_allowance(&self, peer_id: &str) -> Duration {
+        match self.estimate(peer_id) {
+            Some(estimate) => estimate.uncertainty().min(self.max_skew),
+            None => self.max_skew,
+        }
+    }
+
+    /// 检查对方时间戳是否与本地时间一致
+    ///
+    /// 时间戳先按偏差估计换算到本地时钟，换算后与本地时间相差不超过 `max_skew`
+    /// 即被接受；容差同时覆盖残余偏差和传输时延。
+    pub fn check_timestamp(&self, peer_id: &str, remote: SystemTime) -> Result<()> {
+        self.check_timestamp_at(peer_id, remote, SystemTime::now())
+    }
+
+    /// 以给定的本地时间检查对方时间戳
+    pub fn check_timestamp_at(&self, peer_id: &str, remote: SystemTime, now: SystemTime) -> Result<()> {
+        let local = self.to_local_time(peer_id, remote);
+        let difference = match local.duration_since(now) {
+            Ok(ahead) => ahead,
+            Err(behind) => behind.duration(),
+        };
+        if difference > self.max_skew {
+            return Err(MpcError::ProtocolError(format!(
+                "Timestamp from {} is off by {:?} (tolerance {:?})",
+                peer_id, difference, self.max_skew
+            )));
+        }
+        Ok(())
+    }
+
+    /// 对方声称的发送时间是否在本地截止时间之前（计入偏差容差）
+    ///
+    /// 用于带截止时间的承诺打开等场景：时钟偏慢的诚实参与方不会因偏差被判为超时。
+    pub fn within_deadline(&self, peer_id: &str, remote_sent_at: SystemTime, deadline: SystemTime) -> bool {
+        self.to_local_time(peer_id, remote_sent_at) <= deadline + self.deadline_allowance(peer_id)
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64
+}
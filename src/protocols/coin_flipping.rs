@@ -41,13 +41,40 @@
 //! let random_string = SequentialCoinFlip::generate_random_string(32)?;
 //! println!("随机字符串: {}", random_string);
 //! ```
+//! 
+//! ## 按轮次执行
+//! 
+//! 各参与方分别运行时使用 `CoinFlipParty`，协议轮次由类型状态保证：
+//! 
+//! ```rust
+//! use mpc_api::protocols::coin_flipping::*;
+//! 
+//! # fn main() -> mpc_api::Result<()> {
+//! let context = b"session-7/coin";
+//! let (alice, alice_commitment) = CoinFlipParty::new(0, 2, context)?.commit()?;
+//! let (bob, bob_commitment) = CoinFlipParty::new(1, 2, context)?.commit()?;
+//! 
+//! let commitments = vec![alice_commitment, bob_commitment];
+//! let (alice, alice_opening) = alice.open(commitments.clone())?;
+//! let (bob, bob_opening) = bob.open(commitments)?;
+//! 
+//! let openings = vec![alice_opening, bob_opening];
+//! assert_eq!(alice.finish(openings.clone())?, bob.finish(openings)?);
+//! # Ok(())
+//! # }
+//! ```
 
 use crate::{MpcError, Result};
 use crate::secret_sharing::{FIELD_PRIME, field_add};
-use crate::commitment::{PedersenCommitment, CommitmentScheme};
+use crate::commitment::{PedersenCommitment, CommitmentScheme, CommittedMessage, MessageCommitment, MessageOpening};
+use crate::utils::transcript::Transcript;
+use super::session::ProtocolSession;
 use rand::{Rng, thread_rng};
 use serde::{Deserialize, Serialize};
 
+/// 哈希承诺的域分隔标签
+const COIN_COMMITMENT_DOMAIN: &[u8] = b"mpc_api/coin_flipping/commit";
+
 /// 硬币抛掷承诺结构
 /// 
 /// 表示参与方对其选择比特的承诺。承诺包含哈希值和随机数，
@@ -160,8 +187,8 @@ impl CoinFlipping for XORCoinFlip {
 impl XORCoinFlip {
     /// 哈希承诺函数
     /// 
-    /// 使用 SHA-256 哈希函数生成承诺值。将比特值和随机数组合后进行哈希，
-    /// 然后将结果映射到有限域中。
+    /// 把比特值和随机数写入以 `COIN_COMMITMENT_DOMAIN` 开始的转录，
+    /// 再从转录导出有限域中的承诺值。
     /// 
     /// # 参数
     /// 
@@ -172,17 +199,10 @@ impl XORCoinFlip {
     /// 
     /// 返回承诺值（在有限域中）
     fn hash_commit(bit: u64, randomness: u64) -> u64 {
-        use sha2::{Sha256, Digest};
-        let mut hasher = Sha256::new();
-        hasher.update(bit.to_le_bytes());
-        hasher.update(randomness.to_le_bytes());
-        let result = hasher.finalize();
-        
-        let mut commitment = 0u64;
-        for (i, &byte) in result.iter().take(8).enumerate() {
-            commitment |= (byte as u64) << (i * 8);
-        }
-        commitment % FIELD_PRIME
+        let mut transcript = Transcript::new(COIN_COMMITMENT_DOMAIN);
+        transcript.append_u64(b"bit", bit);
+        transcript.append_u64(b"randomness", randomness);
+        transcript.challenge_field(b"commitment")
     }
 }
 
@@ -236,34 +256,202 @@ impl BlumCoinFlip {
             return Err(MpcError::ProtocolError("需要至少一个参与方".to_string()));
         }
         
-        let mut commits = Vec::new();
-        let mut reveals = Vec::new();
-        let mut rng = thread_rng();
+        const CONTEXT: &[u8] = b"coin_flipping/multi_party";
         
-        // Each party commits to a random bit
-        for _ in 0..num_parties {
-            let bit = rng.gen::<bool>();
-            let (commit, reveal) = XORCoinFlip::commit_bit(bit)?;
-            commits.push(commit);
-            reveals.push(reveal);
+        // Each party commits to a random bit; the result is the XOR of all bits
+        Self::run_parties(num_parties, CONTEXT, |_| Ok(())).map_err(|_| {
+            MpcError::ProtocolError("Multi-party commitment verification failed".to_string())
+        })
+    }
+    
+    /// 在协议会话中执行多方硬币抛掷
+    /// 
+    /// 承诺上下文由会话派生，承诺和结果都记入会话转录，因此一次执行中的
+    /// 承诺无法在其他会话（包括同一父会话下的兄弟会话）中打开。
+    /// 
+    /// # 参数
+    /// 
+    /// * `session` - 本次硬币抛掷所在的会话（通常由父协议派生）
+    /// * `num_parties` - 参与方数量（至少为 1）
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回多方硬币抛掷的结果，或者在协议失败时返回错误
+    pub fn multi_party_coin_flip_in_session(session: &mut ProtocolSession, num_parties: usize) -> Result<bool> {
+        if num_parties == 0 {
+            return Err(MpcError::ProtocolError("需要至少一个参与方".to_string()));
         }
         
-        // XOR all bits together
+        let context = session.commitment_context("coin_flip");
+        let result = Self::run_parties(num_parties, &context, |commitments| {
+            let encoded = bincode::serialize(commitments)
+                .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+            session.absorb("coin_flip/commitments", &encoded);
+            Ok(())
+        })?;
+        session.absorb("coin_flip/result", &[result as u8]);
+        
+        Ok(result)
+    }
+    
+    /// 在本地模拟全部参与方的 `CoinFlipParty` 轮次
+    /// 
+    /// `on_commitments` 在承诺轮结束、打开轮开始之前被调用。
+    fn run_parties(
+        num_parties: usize,
+        context: &[u8],
+        on_commitments: impl FnOnce(&[MessageCommitment]) -> Result<()>,
+    ) -> Result<bool> {
+        let (parties, commitments): (Vec<_>, Vec<_>) = (0..num_parties)
+            .map(|party_id| CoinFlipParty::new(party_id, num_parties, context)?.commit())
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        on_commitments(&commitments)?;
+        
+        let (parties, openings): (Vec<_>, Vec<_>) = parties.into_iter()
+            .map(|party| party.open(commitments.clone()))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        
+        // 每个参与方得到相同的结果，由第一个参与方报告
+        parties.into_iter().next()
+            .ok_or_else(|| MpcError::ProtocolError("需要至少一个参与方".to_string()))?
+            .finish(openings)
+    }
+}
+
+/// 硬币抛掷参与方状态：尚未承诺
+#[derive(Debug, Clone)]
+pub struct ReadyToCommit {
+    bit: bool,
+}
+
+/// 硬币抛掷参与方状态：已发出承诺，等待全部参与方的承诺
+#[derive(Debug, Clone)]
+pub struct AwaitingCommitments {
+    committed: CommittedMessage<bool>,
+}
+
+/// 硬币抛掷参与方状态：已发出打开信息，等待全部参与方的打开信息
+#[derive(Debug, Clone)]
+pub struct AwaitingOpenings {
+    commitments: Vec<MessageCommitment>,
+}
+
+/// 按轮次推进的多方硬币抛掷参与方
+///
+/// 承诺 → 打开 → 组合，每一轮消耗当前状态并返回下一状态，因此无法在收齐承诺之前
+/// 打开，也无法重复使用同一个随机比特。结果是全部参与方比特的异或。
+#[derive(Debug, Clone)]
+pub struct CoinFlipParty<S> {
+    party_id: usize,
+    party_count: usize,
+    context: Vec<u8>,
+    state: S,
+}
+
+impl CoinFlipParty<ReadyToCommit> {
+    /// 创建参与方，随机选择本方的比特
+    ///
+    /// # 参数
+    ///
+    /// * `party_id` - 本方 ID（0 到 `party_count - 1`）
+    /// * `party_count` - 参与方数量
+    /// * `context` - 绑定到承诺中的上下文，例如 `ProtocolSession::commitment_context`
+    pub fn new(party_id: usize, party_count: usize, context: &[u8]) -> Result<Self> {
+        if party_id >= party_count {
+            return Err(MpcError::ProtocolError(format!(
+                "Party {} out of range for {} parties", party_id, party_count
+            )));
+        }
+        Ok(Self {
+            party_id,
+            party_count,
+            context: context.to_vec(),
+            state: ReadyToCommit { bit: thread_rng().gen() },
+        })
+    }
+    
+    /// 承诺轮：返回下一状态和要广播的承诺
+    pub fn commit(self) -> Result<(CoinFlipParty<AwaitingCommitments>, MessageCommitment)> {
+        let committed = CommittedMessage::commit(self.state.bit, &self.context)?;
+        let commitment = committed.commitment().clone();
+        Ok((
+            CoinFlipParty {
+                party_id: self.party_id,
+                party_count: self.party_count,
+                context: self.context,
+                state: AwaitingCommitments { committed },
+            },
+            commitment,
+        ))
+    }
+}
+
+impl CoinFlipParty<AwaitingCommitments> {
+    /// 打开轮：收齐全部承诺后返回下一状态和要广播的打开信息
+    ///
+    /// # 参数
+    ///
+    /// * `commitments` - 按参与方 ID 排列的全部承诺（包括本方的）
+    pub fn open(self, commitments: Vec<MessageCommitment>) -> Result<(CoinFlipParty<AwaitingOpenings>, MessageOpening<bool>)> {
+        if commitments.len() != self.party_count {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} commitments, got {}", self.party_count, commitments.len()
+            )));
+        }
+        if &commitments[self.party_id] != self.state.committed.commitment() {
+            return Err(MpcError::ProtocolError("Own commitment was altered".to_string()));
+        }
+        Ok((
+            CoinFlipParty {
+                party_id: self.party_id,
+                party_count: self.party_count,
+                context: self.context,
+                state: AwaitingOpenings { commitments },
+            },
+            self.state.committed.open(),
+        ))
+    }
+}
+
+impl CoinFlipParty<AwaitingOpenings> {
+    /// 组合轮：验证全部打开信息并返回硬币抛掷结果
+    ///
+    /// # 参数
+    ///
+    /// * `openings` - 按参与方 ID 排列的全部打开信息
+    pub fn finish(self, openings: Vec<MessageOpening<bool>>) -> Result<bool> {
+        if openings.len() != self.party_count {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} openings, got {}", self.party_count, openings.len()
+            )));
+        }
         let mut result = false;
-        for i in 0..num_parties {
-            // Verify commitment
-            let bit_value = if reveals[i].value { 1u64 } else { 0u64 };
-            let expected_commit = XORCoinFlip::hash_commit(bit_value, reveals[i].randomness);
-            
-            if commits[i].commitment != expected_commit {
-                return Err(MpcError::ProtocolError("Multi-party commitment verification failed".to_string()));
-            }
-            
-            result ^= reveals[i].value;
+        for (commitment, opening) in self.state.commitments.iter().zip(openings) {
+            result ^= commitment.open_verified(opening, &self.context)?;
         }
-        
         Ok(result)
     }
+    
+    /// 打开轮收到的全部承诺
+    pub fn commitments(&self) -> &[MessageCommitment] {
+        &self.state.commitments
+    }
+}
+
+impl<S> CoinFlipParty<S> {
+    /// 本方 ID
+    pub fn party_id(&self) -> usize {
+        self.party_id
+    }
+    
+    /// 参与方数量
+    pub fn party_count(&self) -> usize {
+        self.party_count
+    }
 }
 
 /// 抗偏置硬币抛掷协议
@@ -0,0 +1,196 @@
+//! # 安全比较 (Secure Comparison)
+//!
+//! 对 Shamir 分享的整数做小于和相等比较，结果是秘密分享的比特 [a < b] / [a == b]，
+//! 输入和比较结果都不公开。输入必须落在 [0, 2^ℓ) 内，ℓ 为公开的位宽：
+//!
+//! 1. **平移**: [d] = [a] - [b] + 2^ℓ ∈ [1, 2^(ℓ+1))，本地线性运算
+//! 2. **位分解**: 各方联合生成 ℓ + 1 + σ 个共享随机位组成掩码 [r]，公开 c = d + r
+//!    （r 比 d 多 σ = 40 位，统计隐藏 d），再用带借位的减法 c - r 得到 d 各位的分享，
+//!    每一位一次乘法
+//! 3. **读出结果**: a < b 当且仅当 d < 2^ℓ，即 [a < b] = 1 - [d_ℓ]；
+//!    a == b 当且仅当 d = 2^ℓ，即 [a == b] = [d_ℓ]·∏_{k<ℓ}(1 - [d_k])，乘积按二叉树计算
+//!
+//! 掩码后的值必须小于域的模数，因此位宽不超过 `MAX_COMPARISON_BITS`。
+//! 批量比较的所有对同步执行，轮数与对数无关。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+//! use mpc_api::protocols::comparison::*;
+//! use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let a = ShamirSecretSharing::share(&1200, 2, 3)?;
+//! let b = ShamirSecretSharing::share(&1500, 2, 3)?;
+//!
+//! let protocol = SecureComparison::new(3, 2, 16)?;
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//! let less = protocol.less_than(&a, &b, &mut generator)?;
+//! let equal = protocol.equal(&a, &b, &mut generator)?;
+//! assert_eq!(ShamirSecretSharing::reconstruct(&less.result[..2], 2)?, 1);
+//! assert_eq!(ShamirSecretSharing::reconstruct(&equal.result[..2], 2)?, 0);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::oram::Gadgets;
+use crate::secret_sharing::bits::{add_shares, sub_shares, MAX_DECOMPOSITION_BITS};
+use super::stats::{ProtocolOutput, StatsRecorder};
+use crate::beaver_triples::BeaverTripleGenerator;
+use crate::secret_sharing::Share;
+use crate::{MpcError, Result};
+
+/// 支持的最大输入位宽：差值 a - b + 2^ℓ 按 ℓ + 1 位分解
+pub const MAX_COMPARISON_BITS: usize = MAX_DECOMPOSITION_BITS - 1;
+
+/// 一对待比较值的分享
+pub type ComparisonPair<'a> = (&'a [Share], &'a [Share]);
+
+/// 安全比较协议
+#[derive(Debug, Clone, Copy)]
+pub struct SecureComparison {
+    party_count: usize,
+    threshold: usize,
+    bit_length: usize,
+}
+
+impl SecureComparison {
+    /// 创建协议
+    ///
+    /// # 参数
+    /// - `party_count`: 参与方数量
+    /// - `threshold`: 重构门限
+    /// - `bit_length`: 输入位宽 ℓ，所有输入必须落在 [0, 2^ℓ) 内
+    pub fn new(party_count: usize, threshold: usize, bit_length: usize) -> Result<Self> {
+        if threshold == 0 || threshold > party_count {
+            return Err(MpcError::InvalidThreshold);
+        }
+        if bit_length == 0 || bit_length > MAX_COMPARISON_BITS {
+            return Err(MpcError::ProtocolError(format!(
+                "Comparison bit length must be between 1 and {}", MAX_COMPARISON_BITS
+            )));
+        }
+        Ok(Self { party_count, threshold, bit_length })
+    }
+
+    /// 输入位宽
+    pub fn bit_length(&self) -> usize {
+        self.bit_length
+    }
+
+    /// 比较 a < b
+    ///
+    /// # 返回值
+    /// 返回 [a < b] 的分享和执行统计
+    pub fn less_than(
+        &self,
+        a: &[Share],
+        b: &[Share],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Share>>> {
+        let (mut bits, stats) = self.less_than_batch(&[(a, b)], generator)?.into_parts();
+        Ok(ProtocolOutput { result: bits.remove(0), stats })
+    }
+
+    /// 比较 a == b
+    ///
+    /// # 返回值
+    /// 返回 [a == b] 的分享和执行统计
+    pub fn equal(
+        &self,
+        a: &[Share],
+        b: &[Share],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Share>>> {
+        let (mut bits, stats) = self.equal_batch(&[(a, b)], generator)?.into_parts();
+        Ok(ProtocolOutput { result: bits.remove(0), stats })
+    }
+
+    /// 同步比较多对 a < b，轮数与对数无关
+    ///
+    /// # 返回值
+    /// 返回每一对的 [a < b] 分享和执行统计
+    pub fn less_than_batch(
+        &self,
+        pairs: &[ComparisonPair],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+        self.run(pairs, generator, |gadgets| self.less_than_with(gadgets, pairs))
+    }
+
+    /// 同步比较多对 a == b，轮数与对数无关
+    ///
+    /// # 返回值
+    /// 返回每一对的 [a == b] 分享和执行统计
+    pub fn equal_batch(
+        &self,
+        pairs: &[ComparisonPair],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+        self.run(pairs, generator, |gadgets| self.equal_with(gadgets, pairs))
+    }
+
+    /// 在已有的乘法组件上计算 [a < b]，供组合协议共用三元组来源和统计
+    pub(super) fn less_than_with(&self, gadgets: &mut Gadgets, pairs: &[ComparisonPair]) -> Result<Vec<Vec<Share>>> {
+        let bits = self.decompose_differences(gadgets, pairs)?;
+        Ok(bits.iter().map(|bits| gadgets.not(&bits[bits.len() - 1])).collect())
+    }
+
+    fn equal_with(&self, gadgets: &mut Gadgets, pairs: &[ComparisonPair]) -> Result<Vec<Vec<Share>>> {
+        let groups = self.decompose_differences(gadgets, pairs)?
+            .into_iter()
+            .map(|mut bits| {
+                let top = bits.pop().expect("decomposition has ℓ + 1 bits");
+                bits.iter().map(|bit| gadgets.not(bit)).chain(std::iter::once(top)).collect()
+            })
+            .collect();
+        gadgets.product_tree(groups)
+    }
+
+    /// 分解每一对的 d = a - b + 2^ℓ，得到 ℓ + 1 个位（低位在前）
+    fn decompose_differences(&self, gadgets: &mut Gadgets, pairs: &[ComparisonPair]) -> Result<Vec<Vec<Vec<Share>>>> {
+        let offset = gadgets.constant(1u64 << self.bit_length);
+        let differences: Vec<Vec<Share>> = pairs.iter()
+            .map(|(a, b)| add_shares(&sub_shares(a, b), &offset))
+            .collect();
+        gadgets.decompose_batch(&differences, self.bit_length + 1)
+    }
+
+    fn run(
+        &self,
+        pairs: &[ComparisonPair],
+        generator: &mut dyn BeaverTripleGenerator,
+        compute: impl FnOnce(&mut Gadgets) -> Result<Vec<Vec<Share>>>,
+    ) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+        self.check_generator(generator)?;
+        for (a, b) in pairs {
+            self.check_shares(a)?;
+            self.check_shares(b)?;
+        }
+
+        let mut recorder = StatsRecorder::start();
+        let mut gadgets = Gadgets::new(generator, self.threshold, self.party_count);
+        let result = compute(&mut gadgets)?;
+        gadgets.multiplier.record(recorder.stats_mut());
+        Ok(recorder.finish(result))
+    }
+
+    pub(super) fn check_generator(&self, generator: &dyn BeaverTripleGenerator) -> Result<()> {
+        if generator.get_party_count() != self.party_count || generator.get_threshold() != self.threshold {
+            return Err(MpcError::ProtocolError(
+                "Triple generator does not match the comparison parameters".to_string()
+            ));
+        }
+        Ok(())
+    }
+
+    pub(super) fn check_shares(&self, shares: &[Share]) -> Result<()> {
+        if shares.len() != self.party_count {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} shares per value, got {}", self.party_count, shares.len()
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,499 @@
+//! # 两方安全内积 (Two-Party Secure Dot Product)
+//!
+//! 面向两方推理场景（一方持有模型权重 x，另一方持有特征 y）的内积协议，
+//! 结果 ⟨x, y⟩ 以加法秘密分享的形式交给双方。提供两条实现路径：
+//!
+//! - **OT 小工具 (Gilboa 乘法)**: 对每个元素 y_i 的每一位执行一次相关 OT，
+//!   发送方的两条消息为 (r, r + 2^j · x_i)，接收方按位选择后求和即得
+//!   x_i · y_i + Σr。相关 OT 由 OT 扩展 (`OtExtensionSender` / `OtExtensionReceiver`)
+//!   产生，基础 OT 只在双方建立扩展时执行一次，不消耗预处理三元组，适合短向量
+//! - **Beaver 路径**: 每个元素消耗一个 Beaver 三元组，输入分享之后只需一轮公开，
+//!   适合已有预处理材料的长向量
+//!
+//! `secure_dot_product_2pc` 根据向量长度自动选择路径
+//! （见 `select_dot_product_strategy`）。
+//!
+//! OT 小工具由双方各自执行的两条消息组成，可以通过 `DotProductMessage` 与网络消息互相转换：
+//!
+//! 1. y 持有方 (`OtDotProductReceiver::round1`) → x 持有方：以 y 的各个比特为选择位的 OT 扩展消息
+//! 2. x 持有方 (`OtDotProductSender::round2`) → y 持有方：把随机 OT 修正为相关 OT 的修正值
+//!
+//! 每个参与方只持有自己的 OT 扩展一端，两条消息可以经任意传输层发送；
+//! `ot_dot_product_2pc` 在同一进程中依次调用双方的轮函数，便于测试和本地组合。
+//!
+//! Beaver 路径同样按参与方执行（`BeaverDotProductParty`），双方在每一轮互发一条消息：
+//!
+//! 1. `round1`：把本方向量的加法分享中属于对方的一半发给对方
+//! 2. `round2`：发送本方的 d_i = x_i - a_i 和 e_i = y_i - b_i
+//! 3. `finish`：用公开的 d、e 在本地计算内积分享
+//!
+//! 两条路径的统计都由实际消息的序列化大小得出，每一方的收发量见 `ProtocolStats::party_traffic`。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::protocols::dot_product::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let weights = vec![3, 5, 7];
+//! let features = vec![2, 4, 6];
+//!
+//! let output = secure_dot_product_2pc(&weights, &features)?;
+//! assert_eq!(output.result.strategy, DotProductStrategy::OtGadget);
+//! assert_eq!(output.result.reconstruct(), 3 * 2 + 5 * 4 + 7 * 6);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::stats::{ProtocolOutput, StatsRecorder};
+#[cfg(feature = "network")]
+use crate::network::NetworkMessage;
+use crate::oblivious_transfer::{
+    setup_ot_extension, OtExtensionReceiver, OtExtensionRequest, OtExtensionSender, SecurityModel,
+};
+use crate::secret_sharing::{AdditiveSecretSharingScheme, AdditiveShare, FIELD_PRIME, field_add, field_mul, field_sub};
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+
+/// 每个域元素的比特数（每个元素需要的相关 OT 数量）
+pub const DOT_PRODUCT_BITS: usize = 64;
+
+/// 自动选择时使用 OT 小工具的最大向量长度
+pub const OT_DOT_PRODUCT_MAX_LEN: usize = 16;
+
+/// 内积消息的网络消息类型
+pub const DOT_PRODUCT_MESSAGE_TYPE: &str = "dot_product_2pc";
+
+/// 持有 x 的参与方 ID
+pub const DOT_PRODUCT_X_HOLDER: usize = 0;
+
+/// 持有 y 的参与方 ID
+pub const DOT_PRODUCT_Y_HOLDER: usize = 1;
+
+/// 内积实现路径
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DotProductStrategy {
+    /// 基于相关 OT 的 Gilboa 乘法
+    OtGadget,
+    /// 基于 Beaver 三元组
+    Beaver,
+}
+
+/// 根据向量长度选择实现路径
+///
+/// 短向量使用 OT 小工具以省去预处理；长向量使用 Beaver 路径，
+/// 其在线通信量与元素数量成正比但常数远小于每元素 64 次 OT。
+pub fn select_dot_product_strategy(len: usize) -> DotProductStrategy {
+    if len <= OT_DOT_PRODUCT_MAX_LEN {
+        DotProductStrategy::OtGadget
+    } else {
+        DotProductStrategy::Beaver
+    }
+}
+
+/// 内积结果的双方分享
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DotProductShares {
+    /// 使用的实现路径
+    pub strategy: DotProductStrategy,
+    /// x 持有方的分享
+    pub x_holder: AdditiveShare,
+    /// y 持有方的分享
+    pub y_holder: AdditiveShare,
+}
+
+impl DotProductShares {
+    /// 重构内积（仅用于测试与调试）
+    pub fn reconstruct(&self) -> u64 {
+        field_add(self.x_holder.value, self.y_holder.value)
+    }
+}
+
+/// OT 小工具的协议消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DotProductMessage {
+    /// 第 1 条消息：y 持有方的 OT 扩展消息
+    Extension(OtExtensionRequest),
+    /// 第 2 条消息：每个相关 OT 的修正值
+    Corrections(Vec<u64>),
+    /// Beaver 路径第 1 轮：发给对方的输入分享
+    InputShares(Vec<u64>),
+    /// Beaver 路径第 2 轮：本方的 d_i、e_i 分享
+    Openings {
+        /// x - a 的本方分享
+        d: Vec<u64>,
+        /// y - b 的本方分享
+        e: Vec<u64>,
+    },
+}
+
+impl DotProductMessage {
+    /// 序列化后的字节数
+    pub fn encoded_len(&self) -> Result<u64> {
+        bincode::serialized_size(self).map_err(|e| MpcError::SerializationError(e.to_string()))
+    }
+
+    /// 封装为网络消息
+    #[cfg(feature = "network")]
+    pub fn to_network_message(&self) -> Result<NetworkMessage> {
+        let payload = bincode::serialize(self)
+            .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+        Ok(NetworkMessage::new(DOT_PRODUCT_MESSAGE_TYPE, &payload))
+    }
+
+    /// 从网络消息解析
+    #[cfg(feature = "network")]
+    pub fn from_network_message(message: &NetworkMessage) -> Result<Self> {
+        if message.message_type != DOT_PRODUCT_MESSAGE_TYPE {
+            return Err(MpcError::ProtocolError(format!(
+                "Unexpected message type: {}", message.message_type
+            )));
+        }
+        bincode::deserialize(&message.payload)
+            .map_err(|e| MpcError::SerializationError(e.to_string()))
+    }
+}
+
+/// OT 小工具的发送方（持有 x，OT 扩展的发送方）
+#[derive(Debug)]
+pub struct OtDotProductSender {
+    /// 发送方向量
+    x: Vec<u64>,
+    /// 发送方分享（所有随机掩码之和的相反数）
+    share: u64,
+}
+
+impl OtDotProductSender {
+    /// 创建发送方
+    pub fn new(x: Vec<u64>) -> Self {
+        Self { x, share: 0 }
+    }
+
+    /// 第 2 条消息：由随机 OT 派生相关 OT 并发送修正值
+    ///
+    /// 第 (i, j) 个随机 OT 的消息对为 (p₀, p₁)，发送方取 r = H(p₀)，
+    /// 发送 τ = r + 2^j · x_i − H(p₁)。接收方选择位为 0 时得到 r，
+    /// 为 1 时得到 τ + H(p₁) = r + 2^j · x_i。
+    ///
+    /// # 参数
+    ///
+    /// * `message` - y 持有方的第 1 条消息
+    /// * `ot` - 本方持有的 OT 扩展发送方
+    pub fn round2(&mut self, message: DotProductMessage, ot: &mut OtExtensionSender) -> Result<DotProductMessage> {
+        let request = match message {
+            DotProductMessage::Extension(request) => request,
+            _ => return Err(MpcError::ProtocolError("Expected OT extension request".to_string())),
+        };
+        if request.count != self.x.len() * DOT_PRODUCT_BITS {
+            return Err(MpcError::ProtocolError("OT extension request length mismatch".to_string()));
+        }
+
+        let pads = ot.extend(&request)?;
+        let mut mask_sum = 0u64;
+        let corrections = pads.iter()
+            .enumerate()
+            .map(|(index, &(pad0, pad1))| {
+                let r = pad_to_field(pad0);
+                let correlation = field_mul(self.x[index / DOT_PRODUCT_BITS], 1u64 << (index % DOT_PRODUCT_BITS));
+                mask_sum = field_add(mask_sum, r);
+                field_sub(field_add(r, correlation), pad_to_field(pad1))
+            })
+            .collect();
+
+        self.share = field_sub(0, mask_sum);
+        Ok(DotProductMessage::Corrections(corrections))
+    }
+
+    /// 发送方的内积分享
+    pub fn share(&self) -> AdditiveShare {
+        AdditiveShare::new(DOT_PRODUCT_X_HOLDER, self.share)
+    }
+}
+
+/// OT 小工具的接收方（持有 y，OT 扩展的接收方）
+#[derive(Debug)]
+pub struct OtDotProductReceiver {
+    /// 接收方向量
+    y: Vec<u64>,
+    /// 随机 OT 中得到的消息，收到修正值后清空
+    pads: Vec<u128>,
+}
+
+impl OtDotProductReceiver {
+    /// 创建接收方
+    pub fn new(y: Vec<u64>) -> Self {
+        Self { y, pads: Vec::new() }
+    }
+
+    /// 第 1 条消息：以 y 的各个比特为选择位扩展随机 OT
+    ///
+    /// # 参数
+    ///
+    /// * `ot` - 本方持有的 OT 扩展接收方
+    pub fn round1(&mut self, ot: &mut OtExtensionReceiver) -> Result<DotProductMessage> {
+        let choices: Vec<bool> = self.y.iter()
+            .flat_map(|&y_i| (0..DOT_PRODUCT_BITS).map(move |bit| (y_i >> bit) & 1 == 1))
+            .collect();
+        let (pads, request) = ot.extend(&choices)?;
+        self.pads = pads;
+        Ok(DotProductMessage::Extension(request))
+    }
+
+    /// 应用修正值并求和，得到接收方的内积分享
+    pub fn finish(&mut self, message: DotProductMessage) -> Result<AdditiveShare> {
+        let corrections = match message {
+            DotProductMessage::Corrections(corrections) => corrections,
+            _ => return Err(MpcError::ProtocolError("Expected OT corrections".to_string())),
+        };
+        if corrections.len() != self.y.len() * DOT_PRODUCT_BITS || corrections.len() != self.pads.len() {
+            return Err(MpcError::ProtocolError("Correction count mismatch".to_string()));
+        }
+
+        let sum = std::mem::take(&mut self.pads).into_iter()
+            .zip(corrections)
+            .enumerate()
+            .fold(0u64, |acc, (index, (pad, correction))| {
+                let choice = (self.y[index / DOT_PRODUCT_BITS] >> (index % DOT_PRODUCT_BITS)) & 1 == 1;
+                let value = if choice { field_add(correction, pad_to_field(pad)) } else { pad_to_field(pad) };
+                field_add(acc, value)
+            });
+        Ok(AdditiveShare::new(DOT_PRODUCT_Y_HOLDER, sum))
+    }
+}
+
+/// 两方安全内积，自动选择实现路径
+///
+/// # 参数
+///
+/// * `x_vec` - 参与方 0 的向量
+/// * `y_vec` - 参与方 1 的向量
+///
+/// # 返回值
+///
+/// 返回双方的内积分享和执行统计
+pub fn secure_dot_product_2pc(x_vec: &[u64], y_vec: &[u64]) -> Result<ProtocolOutput<DotProductShares>> {
+    secure_dot_product_2pc_with(select_dot_product_strategy(x_vec.len()), x_vec, y_vec)
+}
+
+/// 使用指定路径执行两方安全内积
+///
+/// OT 小工具路径会为本次调用新建一对 OT 扩展；需要多次调用时应建立一次扩展后使用
+/// `ot_dot_product_2pc`，基础 OT 的开销只付一次。
+pub fn secure_dot_product_2pc_with(
+    strategy: DotProductStrategy,
+    x_vec: &[u64],
+    y_vec: &[u64],
+) -> Result<ProtocolOutput<DotProductShares>> {
+    if x_vec.len() != y_vec.len() {
+        return Err(MpcError::ProtocolError("Vectors must have the same length".to_string()));
+    }
+    match strategy {
+        DotProductStrategy::OtGadget => {
+            let (mut x_ot, mut y_ot) = setup_ot_extension(SecurityModel::SemiHonest)?;
+            ot_dot_product_2pc(&mut x_ot, &mut y_ot, x_vec, y_vec)
+        }
+        DotProductStrategy::Beaver => beaver_dot_product(x_vec, y_vec),
+    }
+}
+
+/// 在已建立的 OT 扩展上执行 OT 小工具路径
+///
+/// 依次调用 y 持有方的 `round1`、x 持有方的 `round2` 和 y 持有方的 `finish`，
+/// 与双方分处两地、经网络交换这两条消息的执行完全相同。
+///
+/// # 参数
+///
+/// * `x_ot` - x 持有方的 OT 扩展发送方
+/// * `y_ot` - y 持有方的 OT 扩展接收方
+/// * `x_vec` - x 持有方的向量
+/// * `y_vec` - y 持有方的向量
+///
+/// # 返回值
+///
+/// 返回双方的内积分享和执行统计
+pub fn ot_dot_product_2pc(
+    x_ot: &mut OtExtensionSender,
+    y_ot: &mut OtExtensionReceiver,
+    x_vec: &[u64],
+    y_vec: &[u64],
+) -> Result<ProtocolOutput<DotProductShares>> {
+    if x_vec.len() != y_vec.len() {
+        return Err(MpcError::ProtocolError("Vectors must have the same length".to_string()));
+    }
+    let mut recorder = StatsRecorder::start();
+    let mut sender = OtDotProductSender::new(x_vec.to_vec());
+    let mut receiver = OtDotProductReceiver::new(y_vec.to_vec());
+
+    let message1 = receiver.round1(y_ot)?;
+    recorder.stats_mut().record_message(DOT_PRODUCT_Y_HOLDER, DOT_PRODUCT_X_HOLDER, message1.encoded_len()?);
+    let message2 = sender.round2(message1, x_ot)?;
+    recorder.stats_mut().record_message(DOT_PRODUCT_X_HOLDER, DOT_PRODUCT_Y_HOLDER, message2.encoded_len()?);
+    let y_share = receiver.finish(message2)?;
+    recorder.stats_mut().record_rounds(2);
+
+    Ok(recorder.finish(DotProductShares {
+        strategy: DotProductStrategy::OtGadget,
+        x_holder: sender.share(),
+        y_holder: y_share,
+    }))
+}
+
+/// 随机 OT 消息映射到域元素
+fn pad_to_field(pad: u128) -> u64 {
+    (pad % FIELD_PRIME as u128) as u64
+}
+
+/// 一方持有的加法分享三元组 (a, b, c)，c = a·b
+pub type AdditiveTriple = (AdditiveShare, AdditiveShare, AdditiveShare);
+
+/// 由可信分发者为长度为 `len` 的内积生成 Beaver 三元组
+///
+/// # 返回值
+///
+/// 返回 (x 持有方的三元组分享, y 持有方的三元组分享)
+pub fn deal_dot_product_triples(len: usize) -> Result<(Vec<AdditiveTriple>, Vec<AdditiveTriple>)> {
+    let scheme = AdditiveSecretSharingScheme::new();
+    let mut x_triples = Vec::with_capacity(len);
+    let mut y_triples = Vec::with_capacity(len);
+    for _ in 0..len {
+        let (a, b, c) = scheme.generate_beaver_triple_additive(2)?;
+        x_triples.push((a[DOT_PRODUCT_X_HOLDER], b[DOT_PRODUCT_X_HOLDER], c[DOT_PRODUCT_X_HOLDER]));
+        y_triples.push((a[DOT_PRODUCT_Y_HOLDER], b[DOT_PRODUCT_Y_HOLDER], c[DOT_PRODUCT_Y_HOLDER]));
+    }
+    Ok((x_triples, y_triples))
+}
+
+/// Beaver 路径的一方（持有 x 或 y）
+#[derive(Debug)]
+pub struct BeaverDotProductParty {
+    /// 本方 ID：`DOT_PRODUCT_X_HOLDER` 或 `DOT_PRODUCT_Y_HOLDER`
+    party_id: usize,
+    /// 本方向量
+    input: Vec<u64>,
+    /// 本方的三元组分享，每个元素一个
+    triples: Vec<AdditiveTriple>,
+    /// 本方向量中留给自己的一半分享
+    own_shares: Vec<u64>,
+    /// 本方的 (x 分享, y 分享)，收到对方的输入分享后填写
+    inputs: Option<(Vec<u64>, Vec<u64>)>,
+    /// 本方发出的 (d 分享, e 分享)
+    openings: Option<(Vec<u64>, Vec<u64>)>,
+}
+
+impl BeaverDotProductParty {
+    /// 创建一方
+    ///
+    /// # 参数
+    ///
+    /// * `party_id` - `DOT_PRODUCT_X_HOLDER` 或 `DOT_PRODUCT_Y_HOLDER`
+    /// * `input` - 本方向量
+    /// * `triples` - 本方的三元组分享，数量与向量长度相同
+    pub fn new(party_id: usize, input: Vec<u64>, triples: Vec<AdditiveTriple>) -> Result<Self> {
+        if party_id != DOT_PRODUCT_X_HOLDER && party_id != DOT_PRODUCT_Y_HOLDER {
+            return Err(MpcError::ProtocolError(format!("Unknown dot product party {}", party_id)));
+        }
+        if triples.len() != input.len() {
+            return Err(MpcError::InsufficientShares);
+        }
+        if triples.iter().any(|(a, b, c)| a.party_id != party_id || b.party_id != party_id || c.party_id != party_id) {
+            return Err(MpcError::InvalidSecretShare);
+        }
+        Ok(Self { party_id, input, triples, own_shares: Vec::new(), inputs: None, openings: None })
+    }
+
+    /// 第 1 轮：分享本方向量，返回发给对方的一半
+    pub fn round1(&mut self) -> Result<DotProductMessage> {
+        let scheme = AdditiveSecretSharingScheme::new();
+        let mut peer_shares = Vec::with_capacity(self.input.len());
+        self.own_shares = Vec::with_capacity(self.input.len());
+        for value in &self.input {
+            let shares = scheme.share_additive(value, 2)?;
+            self.own_shares.push(shares[self.party_id].value);
+            peer_shares.push(shares[1 - self.party_id].value);
+        }
+        Ok(DotProductMessage::InputShares(peer_shares))
+    }
+
+    /// 第 2 轮：收到对方的输入分享，返回本方的 d_i、e_i
+    pub fn round2(&mut self, message: DotProductMessage) -> Result<DotProductMessage> {
+        let peer_shares = match message {
+            DotProductMessage::InputShares(shares) => shares,
+            _ => return Err(MpcError::ProtocolError("Expected input shares".to_string())),
+        };
+        if peer_shares.len() != self.input.len() || self.own_shares.len() != self.input.len() {
+            return Err(MpcError::ProtocolError("Input share count mismatch".to_string()));
+        }
+
+        let own_shares = std::mem::take(&mut self.own_shares);
+        let (x_shares, y_shares) = if self.party_id == DOT_PRODUCT_X_HOLDER {
+            (own_shares, peer_shares)
+        } else {
+            (peer_shares, own_shares)
+        };
+        let d: Vec<u64> = x_shares.iter().zip(&self.triples).map(|(&x, (a, _, _))| field_sub(x, a.value)).collect();
+        let e: Vec<u64> = y_shares.iter().zip(&self.triples).map(|(&y, (_, b, _))| field_sub(y, b.value)).collect();
+        self.inputs = Some((x_shares, y_shares));
+        self.openings = Some((d.clone(), e.clone()));
+        Ok(DotProductMessage::Openings { d, e })
+    }
+
+    /// 收到对方的 d_i、e_i，得到本方的内积分享
+    pub fn finish(&mut self, message: DotProductMessage) -> Result<AdditiveShare> {
+        let (peer_d, peer_e) = match message {
+            DotProductMessage::Openings { d, e } => (d, e),
+            _ => return Err(MpcError::ProtocolError("Expected Beaver openings".to_string())),
+        };
+        let (x_shares, y_shares) = self.inputs.take()
+            .ok_or_else(|| MpcError::ProtocolError("Beaver openings received before round 2".to_string()))?;
+        let (own_d, own_e) = self.openings.take()
+            .ok_or_else(|| MpcError::ProtocolError("Beaver openings received before round 2".to_string()))?;
+        if peer_d.len() != own_d.len() || peer_e.len() != own_e.len() {
+            return Err(MpcError::ProtocolError("Opening count mismatch".to_string()));
+        }
+
+        let scheme = AdditiveSecretSharingScheme::new();
+        let mut sum = 0u64;
+        for (i, (a, b, c)) in self.triples.iter().enumerate() {
+            let d = field_add(own_d[i], peer_d[i]);
+            let e = field_add(own_e[i], peer_e[i]);
+            let x = AdditiveShare::new(self.party_id, x_shares[i]);
+            let y = AdditiveShare::new(self.party_id, y_shares[i]);
+            sum = field_add(sum, scheme.beaver_mul_additive(&x, &y, a, b, c, &d, &e)?.value);
+        }
+        Ok(AdditiveShare::new(self.party_id, sum))
+    }
+}
+
+/// Beaver 三元组路径
+///
+/// 三元组属于预处理材料，不计入在线通信；两轮中双方各发一条消息。
+fn beaver_dot_product(x_vec: &[u64], y_vec: &[u64]) -> Result<ProtocolOutput<DotProductShares>> {
+    let (x_triples, y_triples) = deal_dot_product_triples(x_vec.len())?;
+    let mut recorder = StatsRecorder::start();
+    let mut x_party = BeaverDotProductParty::new(DOT_PRODUCT_X_HOLDER, x_vec.to_vec(), x_triples)?;
+    let mut y_party = BeaverDotProductParty::new(DOT_PRODUCT_Y_HOLDER, y_vec.to_vec(), y_triples)?;
+
+    let x_inputs = x_party.round1()?;
+    let y_inputs = y_party.round1()?;
+    let stats = recorder.stats_mut();
+    stats.record_message(DOT_PRODUCT_X_HOLDER, DOT_PRODUCT_Y_HOLDER, x_inputs.encoded_len()?);
+    stats.record_message(DOT_PRODUCT_Y_HOLDER, DOT_PRODUCT_X_HOLDER, y_inputs.encoded_len()?);
+
+    let x_openings = x_party.round2(y_inputs)?;
+    let y_openings = y_party.round2(x_inputs)?;
+    let stats = recorder.stats_mut();
+    stats.record_message(DOT_PRODUCT_X_HOLDER, DOT_PRODUCT_Y_HOLDER, x_openings.encoded_len()?);
+    stats.record_message(DOT_PRODUCT_Y_HOLDER, DOT_PRODUCT_X_HOLDER, y_openings.encoded_len()?);
+
+    let x_share = x_party.finish(y_openings)?;
+    let y_share = y_party.finish(x_openings)?;
+    let stats = recorder.stats_mut();
+    stats.record_rounds(2);
+    stats.record_preprocessing(x_vec.len());
+
+    Ok(recorder.finish(DotProductShares {
+        strategy: DotProductStrategy::Beaver,
+        x_holder: x_share,
+        y_holder: y_share,
+    }))
+}
@@ -0,0 +1,321 @@
+//! # 联合生成元设置 (Joint Generator Setup)
+//!
+//! Pedersen 承诺的安全性依赖于没有人知道 h 相对 g 的离散对数；ElGamal 的各方
+//! 也需要使用同一个生成元。由某一方在本地随机选择 h = k·g 的做法让这一方掌握
+//! 陷门 k，可以任意打开承诺。本模块让全部参与方联合派生这些生成元：
+//!
+//! 1. **承诺**: 每个参与方对 32 字节的随机贡献进行承诺（绑定会话上下文）
+//! 2. **打开**: 收齐全部承诺后打开贡献，任何一方都无法在看到其他贡献后再调整自己的贡献
+//! 3. **派生**: 种子 = H(上下文, 全部承诺, 全部贡献)；Pedersen 的 h 由种子哈希到曲线，
+//!    ElGamal 生成元由种子哈希到域并检查其为乘法群的本原元（try-and-increment）
+//!
+//! 只要有一个参与方诚实，种子就是均匀随机的，生成元之间没有已知的关系。
+//! 派生过程公开可验证：`GeneratorSetupTranscript` 包含全部承诺、打开信息和
+//! 每个生成元成功时的计数器，外部验证者可以用 `verify` 重新计算。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::protocols::generator_setup::*;
+//! use mpc_api::commitment::PedersenCommitment;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let context = b"session-9/generators";
+//! let (alice, alice_commitment) = GeneratorSetupParty::new(0, 2, context)?.commit()?;
+//! let (bob, bob_commitment) = GeneratorSetupParty::new(1, 2, context)?.commit()?;
+//!
+//! let commitments = vec![alice_commitment, bob_commitment];
+//! let (alice, alice_opening) = alice.open(commitments.clone())?;
+//! let (bob, bob_opening) = bob.open(commitments)?;
+//!
+//! let openings = vec![alice_opening, bob_opening];
+//! let transcript = alice.finish(openings.clone())?;
+//! assert_eq!(transcript.generators, bob.finish(openings)?.generators);
+//!
+//! // 外部验证者只需要公开的转录
+//! let generators = transcript.verify()?;
+//! let params = generators.pedersen_params()?;
+//! let commitment = PedersenCommitment::commit_with_params(&params, 42, 7)?;
+//! assert!(PedersenCommitment::verify_with_params(&params, &commitment, 42, 7)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::commitment::{CommittedMessage, MessageCommitment, MessageOpening, PedersenParams};
+use crate::elliptic_curve::{ECPoint, EllipticCurve, SimpleEC};
+use crate::secret_sharing::{field_mul, FIELD_PRIME};
+use crate::utils::hash_struct_with_domain;
+use crate::{MpcError, Result};
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// 种子派生的域分隔标签
+const SETUP_SEED_DOMAIN: &[u8] = b"mpc_api/generator_setup/seed";
+
+/// Pedersen 第二生成元的域分隔标签
+const PEDERSEN_H_DOMAIN: &[u8] = b"mpc_api/generator_setup/pedersen_h";
+
+/// ElGamal 生成元的域分隔标签
+const ELGAMAL_GENERATOR_DOMAIN: &[u8] = b"mpc_api/generator_setup/elgamal_g";
+
+/// 域的乘法群阶 p - 1 = 2^32 · 3 · 5 · 17 · 257 · 65537 的素因子
+const GROUP_ORDER_FACTORS: [u64; 6] = [2, 3, 5, 17, 257, 65537];
+
+/// 由种子派生的一个生成元及成功时的计数器
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneratorDerivation<T> {
+    /// try-and-increment 成功时的计数器，较小的计数器都被拒绝
+    pub counter: u32,
+    /// 派生出的生成元
+    pub value: T,
+}
+
+/// 联合派生的生成元
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JointGenerators {
+    /// 派生种子
+    pub seed: [u8; 32],
+    /// Pedersen 承诺的第二生成元 h（第一生成元是曲线的基点）
+    pub pedersen_h: GeneratorDerivation<ECPoint>,
+    /// 有限域乘法群的本原元，用作 ElGamal 生成元
+    pub elgamal_generator: GeneratorDerivation<u64>,
+}
+
+impl JointGenerators {
+    /// 由种子确定地派生全部生成元
+    pub fn derive(seed: [u8; 32]) -> Result<Self> {
+        let (h, h_counter) = SimpleEC::hash_to_point(PEDERSEN_H_DOMAIN, &seed)?;
+        for counter in 0..u32::MAX {
+            let candidate = elgamal_candidate(&seed, counter)?;
+            if is_primitive_root(candidate) {
+                return Ok(Self {
+                    seed,
+                    pedersen_h: GeneratorDerivation { counter: h_counter, value: h },
+                    elgamal_generator: GeneratorDerivation { counter, value: candidate },
+                });
+            }
+        }
+        Err(MpcError::CryptographicError("No primitive root found".to_string()))
+    }
+
+    /// 检查生成元确实由种子派生
+    pub fn verify(&self) -> Result<()> {
+        if *self != Self::derive(self.seed)? {
+            return Err(MpcError::AuthenticationError("Generators were not derived from the seed".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Pedersen 承诺参数 (g, h)
+    pub fn pedersen_params(&self) -> Result<PedersenParams> {
+        PedersenParams::from_generators(SimpleEC::params().g, self.pedersen_h.value.clone())
+    }
+}
+
+/// 一次联合设置的公开转录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratorSetupTranscript {
+    /// 承诺绑定的会话上下文
+    pub context: Vec<u8>,
+    /// 按参与方 ID 排列的承诺
+    pub commitments: Vec<MessageCommitment>,
+    /// 按参与方 ID 排列的打开信息
+    pub openings: Vec<MessageOpening<[u8; 32]>>,
+    /// 派生出的生成元
+    pub generators: JointGenerators,
+}
+
+impl GeneratorSetupTranscript {
+    /// 重新检查整个设置过程
+    ///
+    /// 验证每个打开信息与承诺一致、种子由全部贡献计算、生成元由种子派生。
+    ///
+    /// # 返回值
+    /// 验证通过的生成元
+    pub fn verify(&self) -> Result<JointGenerators> {
+        let seed = combine_contributions(&self.context, &self.commitments, self.openings.clone())?;
+        if seed != self.generators.seed {
+            return Err(MpcError::AuthenticationError("Seed does not match the contributions".to_string()));
+        }
+        self.generators.verify()?;
+        Ok(self.generators.clone())
+    }
+}
+
+/// 设置参与方状态：尚未承诺
+#[derive(Debug, Clone)]
+pub struct SetupReady {
+    contribution: [u8; 32],
+}
+
+/// 设置参与方状态：已发出承诺，等待全部参与方的承诺
+#[derive(Debug, Clone)]
+pub struct SetupAwaitingCommitments {
+    committed: CommittedMessage<[u8; 32]>,
+}
+
+/// 设置参与方状态：已发出打开信息，等待全部参与方的打开信息
+#[derive(Debug, Clone)]
+pub struct SetupAwaitingOpenings {
+    commitments: Vec<MessageCommitment>,
+}
+
+/// 按轮次推进的联合生成元设置参与方
+///
+/// 承诺 → 打开 → 派生，与 `CoinFlipParty` 相同，每一轮消耗当前状态并返回下一状态。
+#[derive(Debug, Clone)]
+pub struct GeneratorSetupParty<S> {
+    party_id: usize,
+    party_count: usize,
+    context: Vec<u8>,
+    state: S,
+}
+
+impl GeneratorSetupParty<SetupReady> {
+    /// 创建参与方，随机选择本方的贡献
+    ///
+    /// # 参数
+    ///
+    /// * `party_id` - 本方 ID（0 到 `party_count - 1`）
+    /// * `party_count` - 参与方数量
+    /// * `context` - 绑定到承诺中的上下文，例如 `ProtocolSession::commitment_context`
+    pub fn new(party_id: usize, party_count: usize, context: &[u8]) -> Result<Self> {
+        if party_id >= party_count {
+            return Err(MpcError::ProtocolError(format!(
+                "Party {} out of range for {} parties", party_id, party_count
+            )));
+        }
+        let mut contribution = [0u8; 32];
+        thread_rng().fill_bytes(&mut contribution);
+        Ok(Self {
+            party_id,
+            party_count,
+            context: context.to_vec(),
+            state: SetupReady { contribution },
+        })
+    }
+
+    /// 承诺轮：返回下一状态和要广播的承诺
+    pub fn commit(self) -> Result<(GeneratorSetupParty<SetupAwaitingCommitments>, MessageCommitment)> {
+        let committed = CommittedMessage::commit(self.state.contribution, &self.context)?;
+        let commitment = committed.commitment().clone();
+        Ok((
+            GeneratorSetupParty {
+                party_id: self.party_id,
+                party_count: self.party_count,
+                context: self.context,
+                state: SetupAwaitingCommitments { committed },
+            },
+            commitment,
+        ))
+    }
+}
+
+impl GeneratorSetupParty<SetupAwaitingCommitments> {
+    /// 打开轮：收齐全部承诺后返回下一状态和要广播的打开信息
+    ///
+    /// # 参数
+    ///
+    /// * `commitments` - 按参与方 ID 排列的全部承诺（包括本方的）
+    pub fn open(
+        self,
+        commitments: Vec<MessageCommitment>,
+    ) -> Result<(GeneratorSetupParty<SetupAwaitingOpenings>, MessageOpening<[u8; 32]>)> {
+        if commitments.len() != self.party_count {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} commitments, got {}", self.party_count, commitments.len()
+            )));
+        }
+        if &commitments[self.party_id] != self.state.committed.commitment() {
+            return Err(MpcError::ProtocolError("Own commitment was altered".to_string()));
+        }
+        Ok((
+            GeneratorSetupParty {
+                party_id: self.party_id,
+                party_count: self.party_count,
+                context: self.context,
+                state: SetupAwaitingOpenings { commitments },
+            },
+            self.state.committed.open(),
+        ))
+    }
+}
+
+impl GeneratorSetupParty<SetupAwaitingOpenings> {
+    /// 派生轮：验证全部打开信息并派生生成元
+    ///
+    /// # 参数
+    ///
+    /// * `openings` - 按参与方 ID 排列的全部打开信息
+    ///
+    /// # 返回值
+    /// 可以公开给外部验证者的转录
+    pub fn finish(self, openings: Vec<MessageOpening<[u8; 32]>>) -> Result<GeneratorSetupTranscript> {
+        if openings.len() != self.party_count {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} openings, got {}", self.party_count, openings.len()
+            )));
+        }
+        let seed = combine_contributions(&self.context, &self.state.commitments, openings.clone())?;
+        Ok(GeneratorSetupTranscript {
+            context: self.context,
+            commitments: self.state.commitments,
+            openings,
+            generators: JointGenerators::derive(seed)?,
+        })
+    }
+}
+
+impl<S> GeneratorSetupParty<S> {
+    /// 本方 ID
+    pub fn party_id(&self) -> usize {
+        self.party_id
+    }
+
+    /// 参与方数量
+    pub fn party_count(&self) -> usize {
+        self.party_count
+    }
+}
+
+/// 验证打开信息并由全部贡献计算种子
+fn combine_contributions(
+    context: &[u8],
+    commitments: &[MessageCommitment],
+    openings: Vec<MessageOpening<[u8; 32]>>,
+) -> Result<[u8; 32]> {
+    if commitments.is_empty() || commitments.len() != openings.len() {
+        return Err(MpcError::ProtocolError("Every party must commit and open".to_string()));
+    }
+    let contributions = commitments.iter()
+        .zip(openings)
+        .map(|(commitment, opening)| commitment.open_verified(opening, context))
+        .collect::<Result<Vec<_>>>()?;
+    hash_struct_with_domain(SETUP_SEED_DOMAIN, &(context, commitments, contributions))
+}
+
+/// 第 `counter` 个 ElGamal 生成元候选
+fn elgamal_candidate(seed: &[u8; 32], counter: u32) -> Result<u64> {
+    let digest = hash_struct_with_domain(ELGAMAL_GENERATOR_DOMAIN, &(seed, counter))?;
+    Ok(u64::from_le_bytes(digest[..8].try_into().unwrap()) % FIELD_PRIME)
+}
+
+/// 检查 g 是否生成整个乘法群：对 p - 1 的每个素因子 r 都有 g^((p-1)/r) ≠ 1
+pub fn is_primitive_root(g: u64) -> bool {
+    if !(2..FIELD_PRIME).contains(&g) {
+        return false;
+    }
+    GROUP_ORDER_FACTORS.iter().all(|&factor| field_pow(g, (FIELD_PRIME - 1) / factor) != 1)
+}
+
+fn field_pow(mut base: u64, mut exponent: u64) -> u64 {
+    let mut result = 1u64;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = field_mul(result, base);
+        }
+        base = field_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
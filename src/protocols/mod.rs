@@ -6,9 +6,25 @@
 //! ## 支持的协议
 //! 
 //! - **硬币抛掷 (Coin Flipping)**: 允许多方共同生成随机比特，确保任何一方都无法单独影响结果
-//! - **安全比较 (Secure Comparison)**: 比较两个私有输入而不泄露它们的值
-//! - **私有集合求交 (Private Set Intersection)**: 计算多个集合的交集而不泄露集合中的其他元素
+//! - **安全比较 (Secure Comparison)**: 用共享随机位掩码做位分解，对 Shamir 分享的整数计算秘密分享的 [a < b] 和 [a == b]，不公开输入和结果
+//! - **密封出价拍卖 (Sealed-Bid Auction)**: 用安全比较和基于分享的选择做锦标赛求 argmax，只公开获胜者和可选的成交价
+//! - **私有集合求交 (Private Set Intersection)**: 基于 OT 扩展的 OPRF 和布谷鸟哈希计算集合交集，接收方只得到交集下标
+//! - **私有连接 (Private Join)**: 在 PSI 之上将交集记录的关联数据以秘密分享形式交给后续计算
+//! - **加权安全聚合 (Secure Aggregation)**: 客户端按位分享更新，服务器在 MPC 内完成范围证明和裁剪后按权重求和，抵御投毒更新
+//! - **不经意数组访问 (Oblivious Array)**: 按秘密分享的下标读写秘密分享的数组，不泄露下标；小数组使用线性扫描
+//! - **路径 ORAM (Path ORAM)**: 位置图和栈都秘密分享的 Path ORAM，每次访问只处理一条路径，通信量随容量多项式对数增长；提供 `oram_read` / `oram_write`
+//! - **字符串相等与关键字搜索 (String Equality)**: 字符串哈希到域后分享，用零测试比较是否相等；关键字搜索为每条记录返回秘密分享的匹配位
+//! - **两方安全内积 (Dot Product)**: 基于相关 OT 的 Gilboa 乘法或 Beaver 三元组计算向量内积，按向量长度自动选择
+//! - **输出认证 (Output Certification)**: 各方对输出承诺联合签名，外部验证者无需参与计算即可验证结果
 //! - **安全函数评估 (Secure Function Evaluation)**: 在不泄露输入的情况下计算函数结果
+//! - **参与方拓扑 (Topology)**: 显式描述 King、聚合方、分发者等特殊角色，用于消息路由
+//! - **联合生成元设置 (Generator Setup)**: 各方通过承诺-打开联合派生 Pedersen 和 ElGamal 生成元，派生过程公开可验证，没有任何一方掌握陷门
+//! - **两方安全 AES (AES under Garbled Circuits)**: 密钥持有方混淆 AES-128 电路（内置或标准 Bristol 电路），明文标签经 OT 传输，明文持有方得到密文；附带以每秒 1000 次 AES 为目标的吞吐量测量
+//! - **算术电路求值 (Arithmetic Circuit)**: 由加法、乘法、常数和标量门构成的电路在 Shamir 分享上求值，乘法按深度分层、每层一轮，只输出结果的分享
+//! - **算术黑盒 (Arithmetic Black Box)**: 统一的异步 `ArithmeticBlackBox` 接口（输入、加、乘、公开、随机数），后端可以是 SPDZ、诚实多数 Shamir 或本地明文模拟；应用层协议只需编写一次
+//! - **子协议组合 (Session)**: 父协议派生子协议会话，自动派生会话 ID 并绑定转录，防止跨实例拼接；会话状态可通过 `SessionStore` 持久化
+//! - **逻辑时钟 (Clock)**: 消息头携带每个会话的轮次计数器；偏差估计握手使超时判断容忍有界的时钟偏差
+//! - **执行统计 (Protocol Stats)**: 记录每次协议执行的轮数、通信量、预处理消耗和耗时
 //! 
 //! ## 安全性质
 //! 
@@ -26,6 +42,44 @@
 //! - 隐私保护机器学习
 
 pub mod coin_flipping;
+pub mod topology;
+pub mod stats;
+pub mod session;
+pub mod clock;
+pub mod psi;
+pub mod private_join;
+pub mod dot_product;
+pub mod output_certification;
+pub mod secure_aggregation;
+pub mod oblivious_array;
+pub mod oram;
+pub mod string_equality;
+pub mod comparison;
+pub mod auction;
+pub mod generator_setup;
+pub mod abb;
+pub mod mpc_circuit;
+#[cfg(feature = "garbled-circuits")]
+pub mod aes_gc;
 
 pub use coin_flipping::*;
+pub use topology::*;
+pub use stats::*;
+pub use session::*;
+pub use clock::*;
+pub use psi::*;
+pub use private_join::*;
+pub use dot_product::*;
+pub use output_certification::*;
+pub use secure_aggregation::*;
+pub use oblivious_array::*;
+pub use oram::*;
+pub use string_equality::*;
+pub use comparison::*;
+pub use auction::*;
+pub use generator_setup::*;
+pub use abb::*;
+pub use mpc_circuit::*;
+#[cfg(feature = "garbled-circuits")]
+pub use aes_gc::*;
 
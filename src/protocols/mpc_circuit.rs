@@ -0,0 +1,312 @@
+//! # 算术电路求值 (Arithmetic Circuit Evaluation)
+//!
+//! `ArithmeticCircuit` 用加法、减法、乘法、常数和标量乘法门描述任意大小的域上计算，
+//! `CircuitEvaluator` 在 Shamir 分享上对整个电路求值，只输出结果的分享：
+//!
+//! 1. **线性门**: 加法、减法、常数和标量乘法在本地计算，无需通信
+//! 2. **乘法门**: 消耗 `BeaverTripleGenerator` 提供的三元组。乘法按乘法深度分层，
+//!    同一层的所有乘法在一轮内完成，总轮数等于电路的乘法深度
+//!
+//! 电路以 `secret_sharing::program` 中的直线型 `ArithmeticProgram` 存储，
+//! 可以直接交给 `simulate_program` 做明文模拟；`evaluate_trace` 返回每个门的结果分享，
+//! 重构后交给 `diff_program` 即可定位第一个出错的门。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+//! use mpc_api::protocols::mpc_circuit::*;
+//! use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! // f(x, y, z) = x·y·z + 3·x + 10
+//! let mut circuit = ArithmeticCircuit::new(3);
+//! let (x, y, z) = (circuit.input(0), circuit.input(1), circuit.input(2));
+//! let xy = circuit.mul(x, y);
+//! let xyz = circuit.mul(xy, z);
+//! let scaled = circuit.scalar(x, 3);
+//! let sum = circuit.add(xyz, scaled);
+//! let ten = circuit.constant(10);
+//! let result = circuit.add(sum, ten);
+//! circuit.output(result);
+//! assert_eq!(circuit.multiplicative_depth(), 2);
+//!
+//! let inputs = [2u64, 5, 7].iter()
+//!     .map(|value| ShamirSecretSharing::share(value, 2, 3))
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//! let output = CircuitEvaluator::new(3, 2)?.evaluate(&circuit, &inputs, &mut generator)?;
+//! assert_eq!(output.stats.rounds, 2);
+//! assert_eq!(ShamirSecretSharing::reconstruct(&output.result[0][..2], 2)?, 2 * 5 * 7 + 6 + 10);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::secret_sharing::bits::{add_shares, public_shares, scale_shares, sub_shares, Multiplier};
+use super::stats::{ProtocolOutput, StatsRecorder};
+use crate::beaver_triples::BeaverTripleGenerator;
+use crate::secret_sharing::{simulate_program, ArithmeticInstruction, ArithmeticProgram, Share};
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+
+/// 域上的算术电路
+///
+/// 线路用编号表示：输入占用 `0..input_count`，之后每个门的输出占用一个新编号。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArithmeticCircuit {
+    program: ArithmeticProgram,
+}
+
+impl ArithmeticCircuit {
+    /// 创建有 `input_count` 个输入的空电路
+    pub fn new(input_count: usize) -> Self {
+        Self { program: ArithmeticProgram::new(input_count) }
+    }
+
+    /// 由直线型程序构造电路，检查每条指令只读取之前的线路
+    pub fn from_program(program: ArithmeticProgram) -> Result<Self> {
+        let circuit = Self { program };
+        circuit.validate()?;
+        Ok(circuit)
+    }
+
+    /// 第 `index` 个输入的线路
+    ///
+    /// # Panics
+    /// `index` 不小于输入数量时 panic
+    pub fn input(&self, index: usize) -> usize {
+        assert!(index < self.program.input_count, "circuit has only {} inputs", self.program.input_count);
+        index
+    }
+
+    /// 加法门 a + b
+    pub fn add(&mut self, a: usize, b: usize) -> usize {
+        self.program.push(ArithmeticInstruction::Add(a, b))
+    }
+
+    /// 减法门 a - b
+    pub fn sub(&mut self, a: usize, b: usize) -> usize {
+        self.program.push(ArithmeticInstruction::Sub(a, b))
+    }
+
+    /// 乘法门 a · b，求值时消耗一个 Beaver 三元组
+    pub fn mul(&mut self, a: usize, b: usize) -> usize {
+        self.program.push(ArithmeticInstruction::Mul(a, b))
+    }
+
+    /// 常数门
+    pub fn constant(&mut self, value: u64) -> usize {
+        self.program.push(ArithmeticInstruction::Const(value))
+    }
+
+    /// 标量乘法门 a · c，c 为公开常数
+    pub fn scalar(&mut self, a: usize, constant: u64) -> usize {
+        self.program.push(ArithmeticInstruction::MulConst(a, constant))
+    }
+
+    /// 加常数门 a + c，c 为公开常数
+    pub fn add_constant(&mut self, a: usize, constant: u64) -> usize {
+        self.program.push(ArithmeticInstruction::AddConst(a, constant))
+    }
+
+    /// 把线路标记为输出
+    pub fn output(&mut self, wire: usize) {
+        self.program.output(wire);
+    }
+
+    /// 底层的直线型程序
+    pub fn program(&self) -> &ArithmeticProgram {
+        &self.program
+    }
+
+    /// 输入数量
+    pub fn input_count(&self) -> usize {
+        self.program.input_count
+    }
+
+    /// 门数量
+    pub fn gate_count(&self) -> usize {
+        self.program.instructions.len()
+    }
+
+    /// 乘法门数量（求值所需的三元组数量）
+    pub fn multiplication_count(&self) -> usize {
+        self.program.multiplication_count()
+    }
+
+    /// 乘法深度（求值所需的通信轮数）
+    pub fn multiplicative_depth(&self) -> usize {
+        self.depths().into_iter().max().unwrap_or(0)
+    }
+
+    /// 在明文上求值，返回输出
+    pub fn evaluate_plain(&self, inputs: &[u64]) -> Result<Vec<u64>> {
+        Ok(simulate_program(&self.program, inputs)?.outputs)
+    }
+
+    /// 检查每个门只读取之前的线路，输出线路存在
+    fn validate(&self) -> Result<()> {
+        let program = &self.program;
+        for (index, instruction) in program.instructions.iter().enumerate() {
+            let register = program.register_of(index);
+            if let Some(&operand) = instruction.operands().iter().find(|&&operand| operand >= register) {
+                return Err(MpcError::ProtocolError(format!(
+                    "Gate {} reads wire {} before it is set", index, operand
+                )));
+            }
+        }
+        let wires = program.input_count + program.instructions.len();
+        if let Some(&output) = program.outputs.iter().find(|&&output| output >= wires) {
+            return Err(MpcError::ProtocolError(format!("Output wire {} is never set", output)));
+        }
+        Ok(())
+    }
+
+    /// 每条线路的乘法深度（输入为 0），要求电路已通过 `validate`
+    fn depths(&self) -> Vec<usize> {
+        let mut depths = vec![0; self.program.input_count];
+        for instruction in &self.program.instructions {
+            let operands = instruction.operands().iter().map(|&operand| depths[operand]).max().unwrap_or(0);
+            let depth = match instruction {
+                ArithmeticInstruction::Mul(..) => operands + 1,
+                _ => operands,
+            };
+            depths.push(depth);
+        }
+        depths
+    }
+}
+
+/// 在 Shamir 分享上对算术电路求值
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitEvaluator {
+    party_count: usize,
+    threshold: usize,
+}
+
+impl CircuitEvaluator {
+    /// 创建求值器
+    ///
+    /// # 参数
+    /// - `party_count`: 参与方数量
+    /// - `threshold`: 重构门限
+    pub fn new(party_count: usize, threshold: usize) -> Result<Self> {
+        if threshold == 0 || threshold > party_count {
+            return Err(MpcError::InvalidThreshold);
+        }
+        Ok(Self { party_count, threshold })
+    }
+
+    /// 对电路求值
+    ///
+    /// # 参数
+    /// - `circuit`: 要求值的电路
+    /// - `inputs`: 每个输入在各参与方处的分享
+    /// - `generator`: 提供 Beaver 三元组的生成器
+    ///
+    /// # 返回值
+    /// 返回每个输出的分享和执行统计
+    pub fn evaluate(
+        &self,
+        circuit: &ArithmeticCircuit,
+        inputs: &[Vec<Share>],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+        let (wires, stats) = self.evaluate_wires(circuit, inputs, generator)?.into_parts();
+        let outputs = circuit.program.outputs.iter().map(|&output| wires[output].clone()).collect();
+        Ok(ProtocolOutput { result: outputs, stats })
+    }
+
+    /// 对电路求值并返回每个门的结果分享（按门的顺序，不含输入）
+    ///
+    /// 结果用 `open_results` 重构后可以交给 `diff_program`，与明文模拟逐门比较。
+    pub fn evaluate_trace(
+        &self,
+        circuit: &ArithmeticCircuit,
+        inputs: &[Vec<Share>],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+        let (mut wires, stats) = self.evaluate_wires(circuit, inputs, generator)?.into_parts();
+        let gates = wires.split_off(circuit.input_count());
+        Ok(ProtocolOutput { result: gates, stats })
+    }
+
+    /// 按乘法深度分层求值，返回全部线路的分享
+    fn evaluate_wires(
+        &self,
+        circuit: &ArithmeticCircuit,
+        inputs: &[Vec<Share>],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+        if generator.get_party_count() != self.party_count || generator.get_threshold() != self.threshold {
+            return Err(MpcError::ProtocolError(
+                "Triple generator does not match the circuit evaluator parameters".to_string()
+            ));
+        }
+        if inputs.len() != circuit.input_count() {
+            return Err(MpcError::ProtocolError(format!(
+                "Circuit expects {} inputs, got {}", circuit.input_count(), inputs.len()
+            )));
+        }
+        if let Some(input) = inputs.iter().find(|input| input.len() != self.party_count) {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} shares per input, got {}", self.party_count, input.len()
+            )));
+        }
+        circuit.validate()?;
+
+        let mut recorder = StatsRecorder::start();
+        let mut multiplier = Multiplier::new(generator, self.threshold, self.party_count);
+        let program = &circuit.program;
+        let depths = circuit.depths();
+        let mut wires: Vec<Option<Vec<Share>>> = inputs.iter().cloned().map(Some).collect();
+        wires.resize(depths.len(), None);
+
+        for layer in 0..=circuit.multiplicative_depth() {
+            // 本层的乘法门一轮完成
+            let gates: Vec<(usize, usize, usize)> = program.instructions.iter()
+                .enumerate()
+                .filter_map(|(index, instruction)| match *instruction {
+                    ArithmeticInstruction::Mul(a, b) if depths[program.register_of(index)] == layer => {
+                        Some((program.register_of(index), a, b))
+                    }
+                    _ => None,
+                })
+                .collect();
+            let left: Vec<Vec<Share>> = gates.iter().map(|&(_, a, _)| wire(&wires, a)).collect::<Result<_>>()?;
+            let right: Vec<Vec<Share>> = gates.iter().map(|&(_, _, b)| wire(&wires, b)).collect::<Result<_>>()?;
+            for (&(register, _, _), product) in gates.iter().zip(multiplier.multiply(&left, &right)?) {
+                wires[register] = Some(product);
+            }
+
+            // 本层的线性门按顺序在本地计算
+            for (index, instruction) in program.instructions.iter().enumerate() {
+                let register = program.register_of(index);
+                if depths[register] != layer {
+                    continue;
+                }
+                let value = match *instruction {
+                    ArithmeticInstruction::Mul(..) => continue,
+                    ArithmeticInstruction::Add(a, b) => add_shares(&wire(&wires, a)?, &wire(&wires, b)?),
+                    ArithmeticInstruction::Sub(a, b) => sub_shares(&wire(&wires, a)?, &wire(&wires, b)?),
+                    ArithmeticInstruction::AddConst(a, c) => {
+                        add_shares(&wire(&wires, a)?, &public_shares(self.party_count, c))
+                    }
+                    ArithmeticInstruction::MulConst(a, c) => scale_shares(&wire(&wires, a)?, c),
+                    ArithmeticInstruction::Const(c) => public_shares(self.party_count, c),
+                };
+                wires[register] = Some(value);
+            }
+        }
+
+        multiplier.record(recorder.stats_mut());
+        let wires = wires.into_iter()
+            .map(|shares| shares.ok_or_else(|| MpcError::ProtocolError("Circuit wire was never evaluated".to_string())))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(recorder.finish(wires))
+    }
+}
+
+fn wire(wires: &[Option<Vec<Share>>], index: usize) -> Result<Vec<Share>> {
+    wires[index].clone().ok_or_else(|| MpcError::ProtocolError(format!("Wire {} is not yet evaluated", index)))
+}
@@ -0,0 +1,281 @@
+//! # 不经意数组访问 (Oblivious Array Access)
+//!
+//! 按秘密分享的下标读写秘密分享的数组，计算过程不泄露下标。
+//!
+//! `ObliviousArray` 是访问接口，大数组使用 `oram` 模块中的 `PathOram`。
+//! `LinearScanArray` 对每次访问扫描整个数组，适用于小数组：
+//!
+//! 1. **下标的幂**: 用 Beaver 乘法计算 [i]^1 .. [i]^(n-1)，按倍增方式分批，
+//!    共 ⌈log₂(n-1)⌉ 轮
+//! 2. **选择多项式**: 第 j 个位置的指示值 [i == j] 是 [i] 的 n-1 次 Lagrange 基多项式，
+//!    系数公开，因此由下标的幂线性组合得到，无需额外通信
+//! 3. **读取**: Σ_j [i == j]·[a_j] 按幂重新组合为 Σ_k [i^k]·(Σ_j c_jk [a_j])，一轮 n-1 次乘法
+//! 4. **写入**: [a_j] += [i == j]·([v] - [a_j])，一轮 n 次乘法
+//!
+//! 下标必须落在 [0, n) 内：选择多项式在节点之外不为 0，越界下标读出的是元素的
+//! 某个线性组合，写入会改写多个元素。下标来自不可信输入时需要先做范围检查。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+//! use mpc_api::protocols::oblivious_array::*;
+//! use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let table = [10u64, 20, 30, 40];
+//! let elements = table.iter()
+//!     .map(|value| ShamirSecretSharing::share(value, 2, 3))
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//! let mut array = LinearScanArray::new(elements, 2)?;
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//!
+//! let index = ShamirSecretSharing::share(&2, 2, 3)?;
+//! let read = array.read(&index, &mut generator)?;
+//! assert_eq!(ShamirSecretSharing::reconstruct(&read.result[..2], 2)?, 30);
+//!
+//! let value = ShamirSecretSharing::share(&99, 2, 3)?;
+//! array.write(&index, &value, &mut generator)?;
+//! let read = oblivious_read(array.elements(), &index, 2, &mut generator)?;
+//! assert_eq!(ShamirSecretSharing::reconstruct(&read.result[..2], 2)?, 99);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::secret_sharing::bits::{add_shares, public_shares, scale_shares, sub_shares, Multiplier};
+use super::stats::{ProtocolOutput, StatsRecorder};
+use crate::beaver_triples::BeaverTripleGenerator;
+use crate::secret_sharing::{Share, field_add, field_inv, field_mul, field_sub};
+use crate::{MpcError, Result};
+
+/// 按秘密分享的下标访问的数组
+///
+/// 实现不得根据下标的取值改变访问模式或通信量。
+pub trait ObliviousArray {
+    /// 数组长度（公开）
+    fn len(&self) -> usize;
+
+    /// 数组是否为空
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 读取 [index] 处元素的分享
+    ///
+    /// ORAM 等后端在读取时也会重排内部状态，因此需要可变引用
+    fn read(
+        &mut self,
+        index: &[Share],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Share>>>;
+
+    /// 把 [index] 处的元素替换为 [value]
+    fn write(
+        &mut self,
+        index: &[Share],
+        value: &[Share],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<()>>;
+}
+
+/// 线性扫描实现的不经意数组
+///
+/// 每次访问的乘法次数与数组长度成正比，适用于查找表等小数组。
+#[derive(Debug, Clone)]
+pub struct LinearScanArray {
+    /// 每个元素在各参与方处的分享
+    elements: Vec<Vec<Share>>,
+    /// 重构门限
+    threshold: usize,
+    /// 参与方数量
+    party_count: usize,
+}
+
+impl LinearScanArray {
+    /// 由元素的分享创建数组
+    ///
+    /// # 参数
+    /// - `elements`: 每个元素的分享，所有元素的分享数量必须相同
+    /// - `threshold`: 重构门限
+    pub fn new(elements: Vec<Vec<Share>>, threshold: usize) -> Result<Self> {
+        let party_count = elements.first().map(Vec::len)
+            .ok_or_else(|| MpcError::ProtocolError("Oblivious array must not be empty".to_string()))?;
+        if elements.iter().any(|shares| shares.len() != party_count) {
+            return Err(MpcError::ProtocolError("All elements must have the same number of shares".to_string()));
+        }
+        if threshold == 0 || threshold > party_count {
+            return Err(MpcError::InvalidThreshold);
+        }
+        Ok(Self { elements, threshold, party_count })
+    }
+
+    /// 元素的分享
+    pub fn elements(&self) -> &[Vec<Share>] {
+        &self.elements
+    }
+
+    /// 取出元素的分享
+    pub fn into_elements(self) -> Vec<Vec<Share>> {
+        self.elements
+    }
+
+    fn check_shares(&self, shares: &[Share], generator: &dyn BeaverTripleGenerator) -> Result<()> {
+        if shares.len() != self.party_count {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} shares, got {}", self.party_count, shares.len()
+            )));
+        }
+        if generator.get_party_count() != self.party_count || generator.get_threshold() != self.threshold {
+            return Err(MpcError::ProtocolError(
+                "Triple generator does not match the array parameters".to_string()
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ObliviousArray for LinearScanArray {
+    fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    fn read(
+        &mut self,
+        index: &[Share],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Share>>> {
+        self.check_shares(index, generator)?;
+        let mut recorder = StatsRecorder::start();
+        let mut multiplier = Multiplier::new(generator, self.threshold, self.party_count);
+
+        let powers = index_powers(index, self.len(), &mut multiplier)?;
+        let coefficients = selector_coefficients(self.len())?;
+
+        // Σ_j [i == j]·[a_j] = Σ_k [i^k]·(Σ_j c_jk [a_j])
+        let combined: Vec<Vec<Share>> = (0..self.len())
+            .map(|k| {
+                self.elements.iter().zip(&coefficients).fold(
+                    public_shares(self.party_count, 0),
+                    |sum, (element, row)| add_shares(&sum, &scale_shares(element, row[k])),
+                )
+            })
+            .collect();
+        let products = multiplier.multiply(&powers[1..], &combined[1..])?;
+        let result = products.iter().fold(combined[0].clone(), |sum, product| add_shares(&sum, product));
+
+        multiplier.record(recorder.stats_mut());
+        Ok(recorder.finish(result))
+    }
+
+    fn write(
+        &mut self,
+        index: &[Share],
+        value: &[Share],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<()>> {
+        self.check_shares(index, generator)?;
+        self.check_shares(value, generator)?;
+        let mut recorder = StatsRecorder::start();
+        let mut multiplier = Multiplier::new(generator, self.threshold, self.party_count);
+
+        let powers = index_powers(index, self.len(), &mut multiplier)?;
+        let coefficients = selector_coefficients(self.len())?;
+
+        // [a_j] += [i == j]·([v] - [a_j])
+        let selectors: Vec<Vec<Share>> = coefficients.iter()
+            .map(|row| {
+                powers.iter().zip(row).fold(
+                    public_shares(self.party_count, 0),
+                    |sum, (power, &c)| add_shares(&sum, &scale_shares(power, c)),
+                )
+            })
+            .collect();
+        let deltas: Vec<Vec<Share>> = self.elements.iter().map(|element| sub_shares(value, element)).collect();
+        let updates = multiplier.multiply(&selectors, &deltas)?;
+        for (element, update) in self.elements.iter_mut().zip(&updates) {
+            *element = add_shares(element, update);
+        }
+
+        multiplier.record(recorder.stats_mut());
+        Ok(recorder.finish(()))
+    }
+}
+
+/// 按秘密分享的下标读取数组元素
+///
+/// # 参数
+/// - `array`: 每个元素的分享
+/// - `index`: 下标的分享
+/// - `threshold`: 重构门限
+/// - `generator`: 提供 Beaver 三元组的生成器
+///
+/// # 返回值
+/// 返回所读元素的分享和执行统计
+pub fn oblivious_read(
+    array: &[Vec<Share>],
+    index: &[Share],
+    threshold: usize,
+    generator: &mut dyn BeaverTripleGenerator,
+) -> Result<ProtocolOutput<Vec<Share>>> {
+    LinearScanArray::new(array.to_vec(), threshold)?.read(index, generator)
+}
+
+/// 按秘密分享的下标写入数组元素
+///
+/// # 返回值
+/// 返回写入后每个元素的新分享和执行统计；所有元素的分享都会被刷新
+pub fn oblivious_write(
+    array: &[Vec<Share>],
+    index: &[Share],
+    value: &[Share],
+    threshold: usize,
+    generator: &mut dyn BeaverTripleGenerator,
+) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+    let mut scan = LinearScanArray::new(array.to_vec(), threshold)?;
+    let (_, stats) = scan.write(index, value, generator)?.into_parts();
+    Ok(ProtocolOutput { result: scan.into_elements(), stats })
+}
+
+/// 计算 [i]^0 .. [i]^(n-1)，每轮把已有的幂与最高幂相乘，使幂的个数翻倍
+fn index_powers(index: &[Share], len: usize, multiplier: &mut Multiplier) -> Result<Vec<Vec<Share>>> {
+    let mut powers = vec![public_shares(index.len(), 1), index.to_vec()];
+    powers.truncate(len.max(1));
+    while powers.len() < len {
+        let highest = powers.len() - 1;
+        let count = highest.min(len - powers.len());
+        let left: Vec<Vec<Share>> = powers[1..=count].to_vec();
+        let right = vec![powers[highest].clone(); count];
+        powers.extend(multiplier.multiply(&left, &right)?);
+    }
+    Ok(powers)
+}
+
+/// 节点 0..n-1 上 Lagrange 基多项式的系数：第 j 行第 k 列是 [i == j] 中 i^k 的系数
+fn selector_coefficients(len: usize) -> Result<Vec<Vec<u64>>> {
+    // P(x) = Π_j (x - j)，系数从低次到高次
+    let mut product = vec![1u64];
+    for node in 0..len as u64 {
+        let mut next = vec![0u64; product.len() + 1];
+        for (k, &c) in product.iter().enumerate() {
+            next[k + 1] = field_add(next[k + 1], c);
+            next[k] = field_sub(next[k], field_mul(c, node));
+        }
+        product = next;
+    }
+
+    (0..len as u64)
+        .map(|node| {
+            // Q(x) = P(x) / (x - node)，综合除法
+            let mut quotient = vec![0u64; len];
+            quotient[len - 1] = product[len];
+            for k in (1..len).rev() {
+                quotient[k - 1] = field_add(product[k], field_mul(node, quotient[k]));
+            }
+            // 归一化使 Q(node) = 1
+            let at_node = quotient.iter().rev().fold(0, |acc, &c| field_add(field_mul(acc, node), c));
+            let scale = field_inv(at_node)
+                .ok_or_else(|| MpcError::CryptographicError("Array length exceeds the field size".to_string()))?;
+            Ok(quotient.into_iter().map(|c| field_mul(c, scale)).collect())
+        })
+        .collect()
+}
@@ -0,0 +1,619 @@
+//! # 路径 ORAM (Path ORAM)
+//!
+//! `LinearScanArray` 每次访问都要处理整个数组，数组较大时无法扩展。`PathOram` 把
+//! 秘密分享的数据块放在一棵二叉树中，每次访问只读写一条根到叶的路径，乘法次数
+//! 随容量多项式对数增长：
+//!
+//! 1. **下标分解**: 用随机位掩码公开 [i] + [r]（统计隐藏），再通过带借位的减法
+//!    得到下标各位的分享，每一位一次乘法
+//! 2. **递归位置图**: 每个块所在的叶子记录在位置图中；位置图本身按 `2^packing_bits`
+//!    个条目打包存入更小的 ORAM，直到小于 `2^linear_cutoff_bits` 后用线性表保存。
+//!    位置图与各级树都是秘密分享的
+//! 3. **路径读取**: 叶子编号在公开前与秘密随机叶子做多路选择（未初始化的块使用随机叶子），
+//!    公开的路径总是均匀随机的；路径和栈上所有块用逐位相等比较找到目标块，取出数据后
+//!    将其移除，以新的随机叶子重新放入栈
+//! 4. **驱逐**: 从叶子一层向上填满路径上的每个桶，每个位置在候选块中用前缀乘积
+//!    不经意地选出第一个可以放入的块（比较 + 多路选择），其余块留在固定大小的栈中
+//!
+//! 所有的访问执行相同的操作序列，轮数、通信量和公开值的分布都与下标无关。
+//! 栈溢出的概率约为 14·0.6^stash_size，发生时返回错误，之后 ORAM 不可再用。
+//! 未写入过的位置读出 0。下标必须落在 [0, 2^address_bits) 内。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+//! use mpc_api::protocols::oram::*;
+//! use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let config = PathOramConfig { bucket_size: 2, stash_size: 4, packing_bits: 1, linear_cutoff_bits: 1 };
+//! let mut oram = PathOram::new(4, 2, 3, config)?;
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//!
+//! let index = ShamirSecretSharing::share(&2, 2, 3)?;
+//! let value = ShamirSecretSharing::share(&99, 2, 3)?;
+//! oram_write(&mut oram, &index, &value, &mut generator)?;
+//!
+//! let read = oram_read(&mut oram, &index, &mut generator)?;
+//! assert_eq!(ShamirSecretSharing::reconstruct(&read.result[..2], 2)?, 99);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::oblivious_array::ObliviousArray;
+use super::stats::{ProtocolOutput, StatsRecorder};
+use crate::beaver_triples::BeaverTripleGenerator;
+use crate::secret_sharing::bits::{
+    add_shares, compose, decompose_batch, public_shares, random_bits, scale_shares, sub_shares, Multiplier,
+};
+use crate::secret_sharing::Share;
+use crate::{MpcError, Result};
+
+/// 支持的最大地址位数（保证掩码后的下标不超过域的模数）
+pub const MAX_ORAM_ADDRESS_BITS: usize = 20;
+
+/// 一个秘密值在各参与方处的分享
+type Shared = Vec<Share>;
+
+/// Path ORAM 参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathOramConfig {
+    /// 每个桶容纳的块数 Z
+    pub bucket_size: usize,
+    /// 栈的固定容量
+    pub stash_size: usize,
+    /// 每个位置图块打包 2^packing_bits 个条目
+    pub packing_bits: usize,
+    /// 位置图条目不超过 2^linear_cutoff_bits 时改用线性表
+    pub linear_cutoff_bits: usize,
+}
+
+impl Default for PathOramConfig {
+    fn default() -> Self {
+        Self { bucket_size: 4, stash_size: 40, packing_bits: 3, linear_cutoff_bits: 6 }
+    }
+}
+
+impl PathOramConfig {
+    fn validate(&self) -> Result<()> {
+        if self.bucket_size == 0 || self.stash_size == 0 || self.packing_bits == 0 {
+            return Err(MpcError::ProtocolError(
+                "Path ORAM bucket size, stash size and packing must be positive".to_string()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 树中的一个块：有效位、地址位、叶子位（从根开始）和数据字
+#[derive(Debug, Clone)]
+struct OramBlock {
+    valid: Shared,
+    address: Vec<Shared>,
+    leaf: Vec<Shared>,
+    words: Vec<Shared>,
+}
+
+impl OramBlock {
+    fn empty(party_count: usize, address_bits: usize, height: usize, words: usize) -> Self {
+        let zero = public_shares(party_count, 0);
+        Self {
+            valid: zero.clone(),
+            address: vec![zero.clone(); address_bits],
+            leaf: vec![zero.clone(); height],
+            words: vec![zero; words],
+        }
+    }
+
+    /// 驱逐时随块移动的字段
+    fn fields(&self) -> impl Iterator<Item = &Shared> {
+        self.address.iter().chain(&self.leaf).chain(&self.words)
+    }
+}
+
+/// 一棵 Path ORAM 树及其栈
+#[derive(Debug, Clone)]
+struct OramTree {
+    address_bits: usize,
+    height: usize,
+    words: usize,
+    bucket_size: usize,
+    /// 按层序存放的桶，根为 0
+    buckets: Vec<Vec<OramBlock>>,
+    stash: Vec<OramBlock>,
+}
+
+impl OramTree {
+    fn new(address_bits: usize, words: usize, config: &PathOramConfig, party_count: usize) -> Self {
+        let height = address_bits.max(1);
+        let empty = OramBlock::empty(party_count, address_bits, height, words);
+        Self {
+            address_bits,
+            height,
+            words,
+            bucket_size: config.bucket_size,
+            buckets: vec![vec![empty.clone(); config.bucket_size]; (2 << height) - 1],
+            stash: vec![empty; config.stash_size],
+        }
+    }
+
+    /// 根到叶子 `leaf` 的路径上各桶的编号
+    fn path_buckets(&self, leaf: u64) -> Vec<usize> {
+        (0..=self.height)
+            .map(|level| (1usize << level) - 1 + (leaf >> (self.height - level)) as usize)
+            .collect()
+    }
+
+    /// 叶子编号从根开始的第 `level` 位
+    fn path_bit(&self, leaf: u64, level: usize) -> bool {
+        (leaf >> (self.height - 1 - level)) & 1 == 1
+    }
+
+    /// 访问地址为 `address` 的块
+    ///
+    /// 在路径和栈中取出目标块的数据字，交给 `update` 计算新的数据字和输出，
+    /// 然后以叶子 `new_leaf` 重新放入并沿路径驱逐。
+    fn access<F>(
+        &mut self,
+        address: &[Shared],
+        path: u64,
+        new_leaf: Vec<Shared>,
+        gadgets: &mut Gadgets,
+        update: F,
+    ) -> Result<Vec<Shared>>
+    where
+        F: FnOnce(&[Shared], &mut Gadgets) -> Result<(Vec<Shared>, Vec<Shared>)>,
+    {
+        if path >> self.height != 0 {
+            return Err(MpcError::ProtocolError("Path ORAM leaf out of range".to_string()));
+        }
+        let path_buckets = self.path_buckets(path);
+        let mut candidates: Vec<OramBlock> = path_buckets.iter()
+            .flat_map(|&bucket| self.buckets[bucket].iter().cloned())
+            .chain(self.stash.iter().cloned())
+            .collect();
+
+        // 逐位相等比较：[match] = [valid]·Π(1 - (a_j ⊕ i_j))
+        let (left, right): (Vec<Shared>, Vec<Shared>) = candidates.iter()
+            .flat_map(|block| block.address.iter().cloned().zip(address.iter().cloned()))
+            .unzip();
+        let differences = gadgets.xor(&left, &right)?;
+        let groups = candidates.iter().enumerate()
+            .map(|(c, block)| {
+                let bits = &differences[c * self.address_bits..(c + 1) * self.address_bits];
+                std::iter::once(block.valid.clone())
+                    .chain(bits.iter().map(|bit| gadgets.not(bit)))
+                    .collect()
+            })
+            .collect();
+        let matches = gadgets.product_tree(groups)?;
+
+        // 取出目标块的数据字并将其移除
+        let old_words = gadgets.select(&matches, &candidates.iter().map(|b| b.words.clone()).collect::<Vec<_>>())?;
+        for (block, matched) in candidates.iter_mut().zip(&matches) {
+            block.valid = sub_shares(&block.valid, matched);
+        }
+
+        let (new_words, output) = update(&old_words, gadgets)?;
+        candidates.push(OramBlock {
+            valid: gadgets.constant(1),
+            address: address.to_vec(),
+            leaf: new_leaf,
+            words: new_words,
+        });
+
+        self.evict(candidates, path, &path_buckets, gadgets)?;
+        Ok(output)
+    }
+
+    /// 沿路径驱逐：从叶子一层开始填满每个桶，剩余的块放入栈
+    fn evict(&mut self, mut candidates: Vec<OramBlock>, path: u64, path_buckets: &[usize], gadgets: &mut Gadgets) -> Result<()> {
+        // [e_{c,ℓ}]: 块 c 的叶子与路径的前 ℓ 位相同，即可以放在第 ℓ 层
+        let groups = candidates.iter()
+            .map(|block| {
+                block.leaf.iter().enumerate()
+                    .map(|(k, bit)| if self.path_bit(path, k) { bit.clone() } else { gadgets.not(bit) })
+                    .collect()
+            })
+            .collect();
+        let prefixes = gadgets.prefix_products(groups)?;
+
+        // [fit_{c,ℓ}] = [valid_c]·[e_{c,ℓ}]
+        let (left, right): (Vec<Shared>, Vec<Shared>) = candidates.iter().zip(&prefixes)
+            .flat_map(|(block, prefix)| prefix.iter().map(|e| (block.valid.clone(), e.clone())))
+            .unzip();
+        let products = gadgets.multiply(&left, &right)?;
+        let mut fit: Vec<Vec<Shared>> = candidates.iter().zip(products.chunks(self.height))
+            .map(|(block, deeper)| std::iter::once(block.valid.clone()).chain(deeper.iter().cloned()).collect())
+            .collect();
+
+        let mut buckets = vec![Vec::with_capacity(self.bucket_size); path_buckets.len()];
+        for level in (0..=self.height).rev() {
+            for _ in 0..self.bucket_size {
+                buckets[level].push(self.take(&mut candidates, &mut fit, level, gadgets)?);
+            }
+        }
+        let mut stash = Vec::with_capacity(self.stash.len());
+        for _ in 0..self.stash.len() {
+            stash.push(self.take(&mut candidates, &mut fit, 0, gadgets)?);
+        }
+
+        // 只公开是否有块未能放下
+        let leftover = fit.iter().fold(gadgets.constant(0), |sum, fit| add_shares(&sum, &fit[0]));
+        if gadgets.open(&leftover)? != 0 {
+            return Err(MpcError::ProtocolError("Path ORAM stash overflow".to_string()));
+        }
+
+        for (&bucket, blocks) in path_buckets.iter().zip(buckets) {
+            self.buckets[bucket] = blocks;
+        }
+        self.stash = stash;
+        Ok(())
+    }
+
+    /// 不经意地选出第一个可以放在第 `level` 层的候选块
+    fn take(&self, candidates: &mut [OramBlock], fit: &mut [Vec<Shared>], level: usize, gadgets: &mut Gadgets) -> Result<OramBlock> {
+        // [take_c] = [fit_c]·Π_{c' < c}(1 - [fit_c'])
+        let free: Vec<Shared> = fit.iter().map(|fit| gadgets.not(&fit[level])).collect();
+        let inclusive = gadgets.prefix_products(vec![free])?.remove(0);
+        let exclusive: Vec<Shared> = std::iter::once(gadgets.constant(1))
+            .chain(inclusive.into_iter().take(fit.len() - 1))
+            .collect();
+        let fits: Vec<Shared> = fit.iter().map(|fit| fit[level].clone()).collect();
+        let takes = gadgets.multiply(&fits, &exclusive)?;
+
+        let fields: Vec<Vec<Shared>> = candidates.iter().map(|block| block.fields().cloned().collect()).collect();
+        let mut moved = gadgets.select(&takes, &fields)?.into_iter();
+        let block = OramBlock {
+            valid: takes.iter().fold(gadgets.constant(0), |sum, take| add_shares(&sum, take)),
+            address: moved.by_ref().take(self.address_bits).collect(),
+            leaf: moved.by_ref().take(self.height).collect(),
+            words: moved.take(self.words).collect(),
+        };
+
+        // 被选中的块在 ℓ 及更浅的层上都可以放置，放置后这些指示值归零
+        for ((block, fit), take) in candidates.iter_mut().zip(fit.iter_mut()).zip(&takes) {
+            block.valid = sub_shares(&block.valid, take);
+            for value in fit.iter_mut().take(level + 1) {
+                *value = sub_shares(value, take);
+            }
+        }
+        Ok(block)
+    }
+}
+
+/// 基于 Beaver 乘法的比较与多路选择组件
+pub(super) struct Gadgets<'a> {
+    pub(super) multiplier: Multiplier<'a>,
+    party_count: usize,
+}
+
+impl<'a> Gadgets<'a> {
+    pub(super) fn new(generator: &'a mut dyn BeaverTripleGenerator, threshold: usize, party_count: usize) -> Self {
+        Self { multiplier: Multiplier::new(generator, threshold, party_count), party_count }
+    }
+
+    pub(super) fn constant(&self, value: u64) -> Shared {
+        public_shares(self.party_count, value)
+    }
+
+    pub(super) fn not(&self, bit: &Shared) -> Shared {
+        sub_shares(&self.constant(1), bit)
+    }
+
+    pub(super) fn multiply(&mut self, x: &[Shared], y: &[Shared]) -> Result<Vec<Shared>> {
+        self.multiplier.multiply(x, y)
+    }
+
+    pub(super) fn open(&mut self, value: &Shared) -> Result<u64> {
+        Ok(self.multiplier.open(std::slice::from_ref(value))?.remove(0))
+    }
+
+    /// 逐对异或：a ⊕ b = a + b - 2ab
+    fn xor(&mut self, a: &[Shared], b: &[Shared]) -> Result<Vec<Shared>> {
+        let products = self.multiply(a, b)?;
+        Ok(a.iter().zip(b).zip(&products)
+            .map(|((a, b), ab)| sub_shares(&add_shares(a, b), &scale_shares(ab, 2)))
+            .collect())
+    }
+
+    /// 多路选择：Σ_c [s_c]·[v_c]，每个候选的各字段分别组合
+    fn select(&mut self, selectors: &[Shared], values: &[Vec<Shared>]) -> Result<Vec<Shared>> {
+        let width = values.first().map_or(0, Vec::len);
+        let (left, right): (Vec<Shared>, Vec<Shared>) = selectors.iter().zip(values)
+            .flat_map(|(selector, fields)| fields.iter().map(move |field| (selector.clone(), field.clone())))
+            .unzip();
+        let products = self.multiply(&left, &right)?;
+        Ok((0..width)
+            .map(|f| products.iter().skip(f).step_by(width.max(1))
+                .fold(self.constant(0), |sum, product| add_shares(&sum, product)))
+            .collect())
+    }
+
+    /// 每组内所有元素的乘积，按二叉树分批，组之间并行
+    pub(super) fn product_tree(&mut self, mut groups: Vec<Vec<Shared>>) -> Result<Vec<Shared>> {
+        while groups.iter().any(|group| group.len() > 1) {
+            let (left, right): (Vec<Shared>, Vec<Shared>) = groups.iter()
+                .flat_map(|group| group.chunks_exact(2).map(|pair| (pair[0].clone(), pair[1].clone())))
+                .unzip();
+            let mut products = self.multiply(&left, &right)?.into_iter();
+            for group in groups.iter_mut() {
+                let odd = (group.len() % 2 == 1).then(|| group[group.len() - 1].clone());
+                let pairs = group.len() / 2;
+                *group = products.by_ref().take(pairs).chain(odd).collect();
+            }
+        }
+        Ok(groups.into_iter().map(|group| group.into_iter().next().unwrap_or_else(|| self.constant(1))).collect())
+    }
+
+    /// 每组的包含式前缀乘积（Hillis–Steele，⌈log₂ n⌉ 轮），组之间并行
+    fn prefix_products(&mut self, mut groups: Vec<Vec<Shared>>) -> Result<Vec<Vec<Shared>>> {
+        let mut step = 1;
+        while groups.iter().any(|group| group.len() > step) {
+            let (left, right): (Vec<Shared>, Vec<Shared>) = groups.iter()
+                .flat_map(|group| (step..group.len()).map(move |i| (group[i].clone(), group[i - step].clone())))
+                .unzip();
+            let mut products = self.multiply(&left, &right)?.into_iter();
+            for group in groups.iter_mut() {
+                for value in group.iter_mut().skip(step) {
+                    *value = products.next().expect("one product per position");
+                }
+            }
+            step *= 2;
+        }
+        Ok(groups)
+    }
+
+    /// 由位分享（低位在前）得到独热指示向量 [x == j]，j = 0..2^bits
+    fn one_hot(&mut self, bits: &[Shared]) -> Result<Vec<Shared>> {
+        let mut indicators = vec![self.constant(1)];
+        for bit in bits {
+            let products = self.multiply(&indicators, &vec![bit.clone(); indicators.len()])?;
+            let low: Vec<Shared> = indicators.iter().zip(&products).map(|(x, xb)| sub_shares(x, xb)).collect();
+            indicators = low.into_iter().chain(products).collect();
+        }
+        Ok(indicators)
+    }
+
+    /// 生成随机位的分享
+    fn random_bits(&mut self, count: usize) -> Result<Vec<Shared>> {
+        random_bits(&mut self.multiplier, count)
+    }
+
+    /// 下标分解：公开 c = i + r 后计算 c - r 的低 `bits` 位（低位在前）
+    fn decompose(&mut self, value: &Shared, bits: usize) -> Result<Vec<Shared>> {
+        Ok(self.decompose_batch(std::slice::from_ref(value), bits)?.remove(0))
+    }
+
+    /// 同步分解多个值，轮数与值的数量无关；每个值必须小于 2^bits
+    pub(super) fn decompose_batch(&mut self, values: &[Shared], bits: usize) -> Result<Vec<Vec<Shared>>> {
+        decompose_batch(&mut self.multiplier, values, bits)
+    }
+}
+
+/// 由叶子位（根在前）组合出叶子编号
+fn compose_leaf(bits: &[Shared], party_count: usize) -> Shared {
+    let reversed: Vec<Shared> = bits.iter().rev().cloned().collect();
+    compose(&reversed, party_count)
+}
+
+/// 更新位置图条目 (flag, leaf) 交错存放的列表：返回选中条目的旧值，并将其替换为 (1, new_leaf)
+fn update_entries(entries: &mut [Shared], selectors: &[Shared], new_leaf: &Shared, gadgets: &mut Gadgets) -> Result<(Shared, Shared)> {
+    let left: Vec<Shared> = selectors.iter().flat_map(|s| [s.clone(), s.clone(), s.clone()]).collect();
+    let right: Vec<Shared> = entries.chunks(2).flat_map(|entry| [entry[0].clone(), entry[1].clone(), new_leaf.clone()]).collect();
+    let products = gadgets.multiply(&left, &right)?;
+
+    let mut flag = gadgets.constant(0);
+    let mut leaf = gadgets.constant(0);
+    for ((entry, products), selector) in entries.chunks_mut(2).zip(products.chunks(3)).zip(selectors) {
+        let (sel_flag, sel_leaf, sel_new) = (&products[0], &products[1], &products[2]);
+        flag = add_shares(&flag, sel_flag);
+        leaf = add_shares(&leaf, sel_leaf);
+        // flag += s·(1 - flag)，leaf += s·(new - leaf)
+        entry[0] = sub_shares(&add_shares(&entry[0], selector), sel_flag);
+        entry[1] = sub_shares(&add_shares(&entry[1], sel_new), sel_leaf);
+    }
+    Ok((flag, leaf))
+}
+
+/// 秘密分享的 Path ORAM
+///
+/// `trees[0]` 存放数据，`trees[k]` 存放 `trees[k - 1]` 的位置图，
+/// 最后一棵树的位置图保存在线性表 `top` 中。
+#[derive(Debug, Clone)]
+pub struct PathOram {
+    capacity: usize,
+    address_bits: usize,
+    threshold: usize,
+    party_count: usize,
+    config: PathOramConfig,
+    trees: Vec<OramTree>,
+    /// (flag, leaf) 交错存放的线性位置图
+    top: Vec<Shared>,
+}
+
+impl PathOram {
+    /// 创建所有位置为 0 的 ORAM
+    ///
+    /// # 参数
+    /// - `capacity`: 容量（公开），地址位数向上取整到 2 的幂
+    /// - `threshold`: 重构门限
+    /// - `party_count`: 参与方数量
+    /// - `config`: ORAM 参数
+    pub fn new(capacity: usize, threshold: usize, party_count: usize, config: PathOramConfig) -> Result<Self> {
+        config.validate()?;
+        if threshold == 0 || threshold > party_count {
+            return Err(MpcError::InvalidThreshold);
+        }
+        let address_bits = capacity.checked_next_power_of_two()
+            .map(|size| size.trailing_zeros() as usize)
+            .filter(|_| capacity > 0)
+            .ok_or_else(|| MpcError::ProtocolError("Invalid Path ORAM capacity".to_string()))?
+            .max(1);
+        if address_bits > MAX_ORAM_ADDRESS_BITS {
+            return Err(MpcError::ProtocolError(format!(
+                "Path ORAM supports at most 2^{} entries", MAX_ORAM_ADDRESS_BITS
+            )));
+        }
+
+        let p = config.packing_bits;
+        let mut trees = vec![OramTree::new(address_bits, 1, &config, party_count)];
+        while trees[trees.len() - 1].address_bits > config.linear_cutoff_bits {
+            let k = trees.len();
+            let (low, high) = ((p * (k - 1)).min(address_bits), (p * k).min(address_bits));
+            trees.push(OramTree::new(address_bits - high, 2 << (high - low), &config, party_count));
+        }
+        let top = vec![public_shares(party_count, 0); 2 << trees[trees.len() - 1].address_bits];
+
+        Ok(Self { capacity, address_bits, threshold, party_count, config, trees, top })
+    }
+
+    /// ORAM 参数
+    pub fn config(&self) -> &PathOramConfig {
+        &self.config
+    }
+
+    /// 地址位数
+    pub fn address_bits(&self) -> usize {
+        self.address_bits
+    }
+
+    /// 递归层数（包括数据树）
+    pub fn recursion_depth(&self) -> usize {
+        self.trees.len()
+    }
+
+    fn check_shares(&self, shares: &[Share], generator: &dyn BeaverTripleGenerator) -> Result<()> {
+        if shares.len() != self.party_count {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} shares, got {}", self.party_count, shares.len()
+            )));
+        }
+        if generator.get_party_count() != self.party_count || generator.get_threshold() != self.threshold {
+            return Err(MpcError::ProtocolError(
+                "Triple generator does not match the ORAM parameters".to_string()
+            ));
+        }
+        Ok(())
+    }
+
+    /// 一次访问：返回旧值；`value` 不为 `None` 时写入新值
+    fn access(&mut self, index: &[Share], value: Option<&[Share]>, gadgets: &mut Gadgets) -> Result<Shared> {
+        let m = self.address_bits;
+        let p = self.config.packing_bits;
+        let address = gadgets.decompose(&index.to_vec(), m)?;
+        let tree_address = |k: usize| &address[(p * k).min(m)..];
+
+        // 每棵树一个新叶子和一个用于未初始化条目的随机叶子
+        let heights: Vec<usize> = self.trees.iter().map(|tree| tree.height).collect();
+        let mut random = gadgets.random_bits(2 * heights.iter().sum::<usize>())?.into_iter();
+        let new_leaves: Vec<Vec<Shared>> = heights.iter().map(|&h| random.by_ref().take(h).collect()).collect();
+        let dummy_leaves: Vec<Shared> = heights.iter()
+            .map(|&h| compose_leaf(&random.by_ref().take(h).collect::<Vec<_>>(), self.party_count))
+            .collect();
+        let open_path = |flag: &Shared, leaf: &Shared, dummy: &Shared, gadgets: &mut Gadgets| -> Result<u64> {
+            let chosen = gadgets.multiply(std::slice::from_ref(flag), &[sub_shares(leaf, dummy)])?.remove(0);
+            gadgets.open(&add_shares(dummy, &chosen))
+        };
+
+        // 顶层线性位置图
+        let last = self.trees.len() - 1;
+        let selectors = gadgets.one_hot(tree_address(last))?;
+        let new_leaf = compose_leaf(&new_leaves[last], self.party_count);
+        let (flag, leaf) = update_entries(&mut self.top, &selectors, &new_leaf, gadgets)?;
+        let mut path = open_path(&flag, &leaf, &dummy_leaves[last], gadgets)?;
+
+        for k in (1..=last).rev() {
+            let entry_bits = &address[(p * (k - 1)).min(m)..(p * k).min(m)];
+            let child_leaf = compose_leaf(&new_leaves[k - 1], self.party_count);
+            let output = self.trees[k].access(tree_address(k), path, new_leaves[k].clone(), gadgets, |words, gadgets| {
+                let selectors = gadgets.one_hot(entry_bits)?;
+                let mut entries = words.to_vec();
+                let (flag, leaf) = update_entries(&mut entries, &selectors, &child_leaf, gadgets)?;
+                Ok((entries, vec![flag, leaf]))
+            })?;
+            path = open_path(&output[0], &output[1], &dummy_leaves[k - 1], gadgets)?;
+        }
+
+        let output = self.trees[0].access(tree_address(0), path, new_leaves[0].clone(), gadgets, |words, _| {
+            let new_words = value.map_or_else(|| words.to_vec(), |value| vec![value.to_vec()]);
+            Ok((new_words, words.to_vec()))
+        })?;
+        Ok(output.into_iter().next().expect("data tree has one word"))
+    }
+
+    fn run<T>(
+        &mut self,
+        index: &[Share],
+        value: Option<&[Share]>,
+        generator: &mut dyn BeaverTripleGenerator,
+        finish: impl FnOnce(Shared) -> T,
+    ) -> Result<ProtocolOutput<T>> {
+        self.check_shares(index, generator)?;
+        if let Some(value) = value {
+            self.check_shares(value, generator)?;
+        }
+        let mut recorder = StatsRecorder::start();
+        let mut gadgets = Gadgets::new(generator, self.threshold, self.party_count);
+        let result = self.access(index, value, &mut gadgets)?;
+        gadgets.multiplier.record(recorder.stats_mut());
+        Ok(recorder.finish(finish(result)))
+    }
+}
+
+impl ObliviousArray for PathOram {
+    fn len(&self) -> usize {
+        self.capacity
+    }
+
+    fn read(
+        &mut self,
+        index: &[Share],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Share>>> {
+        self.run(index, None, generator, |old| old)
+    }
+
+    fn write(
+        &mut self,
+        index: &[Share],
+        value: &[Share],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<()>> {
+        self.run(index, Some(value), generator, |_| ())
+    }
+}
+
+/// 按秘密分享的下标读取 ORAM
+///
+/// # 参数
+/// - `oram`: Path ORAM
+/// - `index`: 下标的分享
+/// - `generator`: 提供 Beaver 三元组的生成器
+///
+/// # 返回值
+/// 返回所读元素的分享和执行统计
+pub fn oram_read(
+    oram: &mut PathOram,
+    index: &[Share],
+    generator: &mut dyn BeaverTripleGenerator,
+) -> Result<ProtocolOutput<Vec<Share>>> {
+    oram.read(index, generator)
+}
+
+/// 按秘密分享的下标写入 ORAM
+///
+/// # 参数
+/// - `oram`: Path ORAM
+/// - `index`: 下标的分享
+/// - `value`: 新值的分享
+/// - `generator`: 提供 Beaver 三元组的生成器
+pub fn oram_write(
+    oram: &mut PathOram,
+    index: &[Share],
+    value: &[Share],
+    generator: &mut dyn BeaverTripleGenerator,
+) -> Result<ProtocolOutput<()>> {
+    oram.write(index, value, generator)
+}
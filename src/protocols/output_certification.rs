@@ -0,0 +1,313 @@
+//! # 输出认证 (Output Certification)
+//!
+//! 拍卖、投票等场景中，没有参与计算的外部方也需要相信计算结果。
+//! 本模块在协议末尾增加一个认证步骤：
+//!
+//! 1. **联合随机数**: 各方以"先承诺、后打开"的方式贡献随机数，异或得到承诺随机数，
+//!    任何一方都无法单独控制
+//! 2. **输出承诺**: 各方用联合随机数对输出值计算哈希承诺（绑定会话 ID），
+//!    诚实的参与方得到完全相同的承诺
+//! 3. **签名**: 各方用自己的 Ed25519 密钥对承诺签名
+//!
+//! 得到的 `CertifiedOutput` 可以交给外部验证者：验证者只需要参与方的公钥名单
+//! 和法定签名数量，就能验证签名以及输出值与承诺的一致性。输出值可以先隐去，
+//! 只公布承诺和签名，之后再单独公布打开信息。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::protocols::output_certification::*;
+//! use std::collections::HashMap;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let certifiers = (0..3).map(OutputCertifier::new).collect::<mpc_api::Result<Vec<_>>>()?;
+//! let roster: HashMap<usize, _> = certifiers.iter()
+//!     .map(|c| (c.party_id(), *c.public_key()))
+//!     .collect();
+//!
+//! let certified = certify_output("auction-7", &1_250u64, &certifiers)?;
+//! certified.verify(&roster, 3)?;
+//! assert_eq!(certified.value(), Some(&1_250));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::commitment::{CommittedMessage, MessageCommitment, MessageOpening};
+use crate::elliptic_curve::{Ed25519, Ed25519PublicKey, Ed25519SecretKey, Ed25519Signature};
+use crate::utils::canonical_encode;
+use crate::{MpcError, Result};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// 签名消息的域分隔标签
+const CERTIFICATION_DOMAIN: &[u8] = b"mpc_api/output_certification/v1";
+
+/// 随机数贡献的上下文前缀
+const RANDOMNESS_CONTEXT: &[u8] = b"mpc_api/output_certification/randomness";
+
+/// 单个参与方对输出承诺的签名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputSignature {
+    /// 签名方 ID
+    pub party_id: usize,
+    /// Ed25519 签名
+    pub signature: Ed25519Signature,
+}
+
+/// 可公开验证的输出
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertifiedOutput<T> {
+    /// 会话 ID
+    pub session_id: String,
+    /// 对输出值的承诺
+    pub commitment: MessageCommitment,
+    /// 输出值及承诺随机数（隐去输出时为 `None`）
+    pub opening: Option<MessageOpening<T>>,
+    /// 各参与方的签名
+    pub signatures: Vec<OutputSignature>,
+}
+
+/// 参与输出认证的一方
+#[derive(Debug, Clone)]
+pub struct OutputCertifier {
+    /// 参与方 ID
+    party_id: usize,
+    /// 签名私钥
+    signing_key: Ed25519SecretKey,
+    /// 签名公钥
+    public_key: Ed25519PublicKey,
+}
+
+impl OutputCertifier {
+    /// 使用新生成的签名密钥创建
+    pub fn new(party_id: usize) -> Result<Self> {
+        let (signing_key, public_key) = Ed25519::generate_keypair();
+        Ok(Self::from_keypair(party_id, signing_key, public_key))
+    }
+
+    /// 使用已有的签名密钥创建
+    pub fn from_keypair(party_id: usize, signing_key: Ed25519SecretKey, public_key: Ed25519PublicKey) -> Self {
+        Self { party_id, signing_key, public_key }
+    }
+
+    /// 参与方 ID
+    pub fn party_id(&self) -> usize {
+        self.party_id
+    }
+
+    /// 签名公钥（外部验证者的名单中使用）
+    pub fn public_key(&self) -> &Ed25519PublicKey {
+        &self.public_key
+    }
+
+    /// 生成随机数贡献
+    ///
+    /// 先广播返回值的承诺，收齐所有承诺后再广播打开信息。
+    pub fn contribute_randomness(&self, session_id: &str) -> Result<CommittedMessage<[u8; 32]>> {
+        let mut contribution = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut contribution);
+        CommittedMessage::commit(contribution, &randomness_context(session_id, self.party_id))
+    }
+
+    /// 对输出值计算承诺并签名
+    ///
+    /// # 参数
+    ///
+    /// * `session_id` - 会话 ID
+    /// * `value` - 本方计算得到的输出值
+    /// * `randomness` - 联合随机数（见 `combine_randomness`）
+    ///
+    /// # 返回值
+    ///
+    /// 返回本方计算的承诺和签名
+    pub fn sign_output<T: Serialize>(
+        &self,
+        session_id: &str,
+        value: &T,
+        randomness: &[u8; 32],
+    ) -> Result<(MessageCommitment, OutputSignature)> {
+        let commitment = output_commitment(session_id, value, randomness)?;
+        let message = certification_message(session_id, &commitment, self.party_id)?;
+        let signature = Ed25519::sign(&self.signing_key, &message);
+        Ok((commitment, OutputSignature { party_id: self.party_id, signature }))
+    }
+}
+
+/// 验证各方的随机数贡献并合并
+///
+/// # 参数
+///
+/// * `session_id` - 会话 ID
+/// * `contributions` - (参与方 ID, 承诺, 打开信息) 列表
+///
+/// # 返回值
+///
+/// 所有打开都与承诺一致时返回各贡献的异或，否则返回认证错误
+pub fn combine_randomness(
+    session_id: &str,
+    contributions: &[(usize, MessageCommitment, MessageOpening<[u8; 32]>)],
+) -> Result<[u8; 32]> {
+    let mut combined = [0u8; 32];
+    for (party_id, commitment, opening) in contributions {
+        let value = commitment.open_verified(opening.clone(), &randomness_context(session_id, *party_id))?;
+        for (byte, contribution) in combined.iter_mut().zip(value) {
+            *byte ^= contribution;
+        }
+    }
+    Ok(combined)
+}
+
+impl<T: Serialize + Clone> CertifiedOutput<T> {
+    /// 组装认证输出
+    ///
+    /// # 参数
+    ///
+    /// * `session_id` - 会话 ID
+    /// * `value` - 输出值
+    /// * `randomness` - 联合随机数
+    /// * `signatures` - 各参与方的签名
+    pub fn assemble(
+        session_id: &str,
+        value: T,
+        randomness: &[u8; 32],
+        signatures: Vec<OutputSignature>,
+    ) -> Result<Self> {
+        let committed = CommittedMessage::commit_with_randomness(value, randomness.to_vec(), session_id.as_bytes())?;
+        Ok(Self {
+            session_id: session_id.to_string(),
+            commitment: committed.commitment().clone(),
+            opening: Some(committed.open()),
+            signatures,
+        })
+    }
+
+    /// 输出值（已隐去时为 `None`）
+    pub fn value(&self) -> Option<&T> {
+        self.opening.as_ref().map(|opening| &opening.value)
+    }
+
+    /// 隐去输出值，只保留承诺和签名
+    pub fn redacted(&self) -> Self {
+        Self {
+            opening: None,
+            ..self.clone()
+        }
+    }
+
+    /// 外部验证
+    ///
+    /// 检查签名来自名单中互不相同的参与方、全部有效且数量不少于 `quorum`；
+    /// 如果包含输出值，还检查输出值与承诺一致。
+    ///
+    /// # 参数
+    ///
+    /// * `roster` - 参与方 ID 到签名公钥的映射
+    /// * `quorum` - 需要的最少签名数量
+    pub fn verify(&self, roster: &HashMap<usize, Ed25519PublicKey>, quorum: usize) -> Result<()> {
+        if quorum == 0 {
+            return Err(MpcError::InvalidThreshold);
+        }
+
+        let mut signers = HashSet::new();
+        for signature in &self.signatures {
+            let public_key = roster.get(&signature.party_id).ok_or_else(|| {
+                MpcError::AuthenticationError(format!("Unknown signer {}", signature.party_id))
+            })?;
+            if !signers.insert(signature.party_id) {
+                return Err(MpcError::AuthenticationError(format!(
+                    "Duplicate signature from party {}", signature.party_id
+                )));
+            }
+
+            let message = certification_message(&self.session_id, &self.commitment, signature.party_id)?;
+            if !Ed25519::verify(public_key, &message, &signature.signature) {
+                return Err(MpcError::AuthenticationError(format!(
+                    "Invalid signature from party {}", signature.party_id
+                )));
+            }
+        }
+
+        if signers.len() < quorum {
+            return Err(MpcError::AuthenticationError(format!(
+                "Only {} of {} required signatures", signers.len(), quorum
+            )));
+        }
+
+        if let Some(opening) = &self.opening {
+            if !self.commitment.verify(opening, self.session_id.as_bytes())? {
+                return Err(MpcError::AuthenticationError("Output does not match commitment".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// 验证隐去输出值的认证输出，并用事后公布的打开信息取出输出值
+    pub fn verify_opening(
+        &self,
+        opening: MessageOpening<T>,
+        roster: &HashMap<usize, Ed25519PublicKey>,
+        quorum: usize,
+    ) -> Result<T> {
+        self.verify(roster, quorum)?;
+        self.commitment.open_verified(opening, self.session_id.as_bytes())
+    }
+}
+
+/// 在本地模拟所有参与方执行输出认证
+///
+/// # 参数
+///
+/// * `session_id` - 会话 ID
+/// * `value` - 协议输出值
+/// * `certifiers` - 各参与方
+///
+/// # 返回值
+///
+/// 返回带有全部参与方签名的认证输出
+pub fn certify_output<T: Serialize + Clone>(
+    session_id: &str,
+    value: &T,
+    certifiers: &[OutputCertifier],
+) -> Result<CertifiedOutput<T>> {
+    // 第 1 步：承诺并打开随机数贡献
+    let contributions = certifiers.iter()
+        .map(|certifier| {
+            let committed = certifier.contribute_randomness(session_id)?;
+            Ok((certifier.party_id(), committed.commitment().clone(), committed.open()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let randomness = combine_randomness(session_id, &contributions)?;
+
+    // 第 2、3 步：各方独立计算承诺并签名
+    let mut signatures = Vec::with_capacity(certifiers.len());
+    let mut agreed: Option<MessageCommitment> = None;
+    for certifier in certifiers {
+        let (commitment, signature) = certifier.sign_output(session_id, value, &randomness)?;
+        if agreed.get_or_insert_with(|| commitment.clone()) != &commitment {
+            return Err(MpcError::ProtocolError("Parties computed different output commitments".to_string()));
+        }
+        signatures.push(signature);
+    }
+
+    CertifiedOutput::assemble(session_id, value.clone(), &randomness, signatures)
+}
+
+/// 输出值的承诺
+fn output_commitment<T: Serialize>(session_id: &str, value: &T, randomness: &[u8; 32]) -> Result<MessageCommitment> {
+    let committed = CommittedMessage::commit_with_randomness(value, randomness.to_vec(), session_id.as_bytes())?;
+    Ok(committed.commitment().clone())
+}
+
+/// 签名覆盖的消息：域标签、会话 ID、承诺和签名方 ID
+fn certification_message(session_id: &str, commitment: &MessageCommitment, party_id: usize) -> Result<Vec<u8>> {
+    canonical_encode(&(CERTIFICATION_DOMAIN, session_id, commitment, party_id))
+}
+
+/// 随机数贡献承诺的上下文
+fn randomness_context(session_id: &str, party_id: usize) -> Vec<u8> {
+    let mut context = RANDOMNESS_CONTEXT.to_vec();
+    context.extend_from_slice(&(party_id as u64).to_le_bytes());
+    context.extend_from_slice(session_id.as_bytes());
+    context
+}
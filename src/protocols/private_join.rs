@@ -0,0 +1,282 @@
+//! # 私有连接 (Private Join)
+//!
+//! 在 `protocols::psi` 的 OPRF 与布谷鸟分桶之上实现"带关联数据的 PSI"：
+//! 交集中记录的关联数据不会被公开，而是以加法秘密分享的形式交给双方，
+//! 直接作为后续 MPC 计算的输入。
+//!
+//! ## 协议流程
+//!
+//! 1. **分桶与 OPRF**: 与 PSI 相同，接收方得到自己每个桶的 F(b ‖ x)
+//! 2. **提示表**: 对每个桶 b，发送方为落入该桶的每条记录生成
+//!    (标签, 关联数据 − s_b + 掩码)，其中标签和掩码由 F(b ‖ y) 和 b 派生，
+//!    每个桶填充到相同的条数并打乱顺序后发送
+//! 3. **解码**: 接收方用自己的 OPRF 输出查找标签；命中时得到
+//!    关联数据 − s_b，发送方持有 s_b，两者构成加法分享
+//!
+//! 接收方得知哪些元素在交集中（标准 PSI 输出），但看不到关联数据；
+//! 发送方既不知道交集也不知道关联数据如何被使用。
+//!
+//! 提示表共有 桶数 × 最大桶负载 条，与两个集合的大小成线性关系，
+//! 而不是逐个元素配对时的 |接收方集合| × |发送方集合|。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::protocols::private_join::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let records = vec![
+//!     PsiRecord::new(b"alice".to_vec(), vec![100]),
+//!     PsiRecord::new(b"bob".to_vec(), vec![200]),
+//! ];
+//! let receiver_keys = vec![b"carol".to_vec(), b"bob".to_vec()];
+//!
+//! let output = execute_private_join(records, receiver_keys)?;
+//! assert_eq!(output.receiver.matched, vec![false, true]);
+//! assert_eq!(output.reconstruct(1), Some(vec![200]));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::oblivious_transfer::{setup_ot_extension, OtExtensionSender, SecurityModel};
+use crate::protocols::psi::{CuckooParams, OprfOutput, OprfResponse, PsiReceiver, PsiRequest, PsiSender};
+use crate::secret_sharing::{AdditiveShare, FIELD_PRIME, field_add, field_sub};
+use crate::{MpcError, Result};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// 私有连接中发送方的参与方 ID
+pub const JOIN_SENDER_ID: usize = 0;
+
+/// 私有连接中接收方的参与方 ID
+pub const JOIN_RECEIVER_ID: usize = 1;
+
+/// 发送方的一条记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PsiRecord {
+    /// 连接键
+    pub key: Vec<u8>,
+    /// 关联数据（每列一个域元素）
+    pub payload: Vec<u64>,
+}
+
+impl PsiRecord {
+    /// 创建新的记录
+    pub fn new(key: Vec<u8>, payload: Vec<u64>) -> Self {
+        Self { key, payload }
+    }
+}
+
+/// 发送方发给接收方的提示表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JoinHints {
+    /// 关联数据列数
+    pub width: usize,
+    /// hints[b] 为桶 b 的 (标签, 掩码后的关联数据) 列表（已填充到相同条数并打乱）
+    pub hints: Vec<Vec<([u8; 32], Vec<u64>)>>,
+}
+
+impl JoinHints {
+    /// 提示条目总数
+    pub fn entry_count(&self) -> usize {
+        self.hints.iter().map(Vec::len).sum()
+    }
+
+    /// 接收方用自己的 OPRF 输出解码提示表
+    pub fn decode(&self, receiver: &PsiReceiver) -> Result<ReceiverJoinOutput> {
+        if self.hints.len() != receiver.cuckoo_params().bin_count {
+            return Err(MpcError::ProtocolError("Hint table does not match receiver bins".to_string()));
+        }
+
+        let mut matched = Vec::with_capacity(receiver.len());
+        let mut shares = Vec::with_capacity(receiver.len());
+        let mut bins = Vec::with_capacity(receiver.len());
+
+        for index in 0..receiver.len() {
+            let (bin, prf) = receiver.element_prf(index)
+                .ok_or_else(|| MpcError::ProtocolError("Receiver element has no bin".to_string()))?;
+            let tag = hint_tag(prf, bin);
+            match self.hints[bin].iter().find(|(candidate, _)| *candidate == tag) {
+                Some((_, masked)) => {
+                    matched.push(true);
+                    shares.push(masked.iter()
+                        .enumerate()
+                        .map(|(column, &value)| {
+                            AdditiveShare::new(JOIN_RECEIVER_ID, field_sub(value, hint_pad(prf, bin, column)))
+                        })
+                        .collect());
+                }
+                None => {
+                    matched.push(false);
+                    shares.push(Vec::new());
+                }
+            }
+            bins.push(bin);
+        }
+
+        Ok(ReceiverJoinOutput { matched, shares, bins })
+    }
+}
+
+/// 私有连接发送方：在 PSI 发送方之外持有每条记录的关联数据
+#[derive(Debug, Clone)]
+pub struct JoinSender {
+    /// 以连接键为集合的 PSI 发送方
+    psi: PsiSender,
+    /// 记录
+    records: Vec<PsiRecord>,
+    /// 关联数据列数
+    width: usize,
+}
+
+impl JoinSender {
+    /// 创建发送方，所有记录的关联数据列数必须一致
+    pub fn new(records: Vec<PsiRecord>) -> Result<Self> {
+        let width = records.first().map(|record| record.payload.len()).unwrap_or(0);
+        if records.iter().any(|record| record.payload.len() != width) {
+            return Err(MpcError::ProtocolError("Records have inconsistent payload widths".to_string()));
+        }
+        let psi = PsiSender::new(records.iter().map(|record| record.key.clone()).collect());
+        Ok(Self { psi, records, width })
+    }
+
+    /// 处理接收方的 PSI 请求，见 [`PsiSender::respond_oprf`]
+    pub fn respond_oprf(&self, request: &PsiRequest, ot: &mut OtExtensionSender) -> Result<OprfResponse> {
+        self.psi.respond_oprf(request, ot)
+    }
+
+    /// 为每个桶生成提示表以及发送方自己的分享
+    ///
+    /// 每条记录进入它的全部候选桶，每个桶用随机条目填充到最大负载，
+    /// 接收方从条数上看不出发送方记录的分布。
+    ///
+    /// # 参数
+    ///
+    /// * `params` - 接收方的布谷鸟哈希参数
+    ///
+    /// # 返回值
+    ///
+    /// 返回 (提示表, 发送方分享)，发送方分享按桶排列；桶数不合法时返回错误
+    pub fn join_hints(&self, params: &CuckooParams) -> Result<(JoinHints, Vec<Vec<AdditiveShare>>)> {
+        params.validate()?;
+        let mut rng = rand::thread_rng();
+        let mut assigned: Vec<Vec<usize>> = vec![Vec::new(); params.bin_count];
+        for (index, record) in self.records.iter().enumerate() {
+            for bin in params.candidate_bins(&record.key) {
+                assigned[bin].push(index);
+            }
+        }
+        let max_load = assigned.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut hints = Vec::with_capacity(params.bin_count);
+        let mut sender_shares = Vec::with_capacity(params.bin_count);
+
+        for (bin, records) in assigned.iter().enumerate() {
+            let masks: Vec<u64> = (0..self.width).map(|_| rng.gen_range(0..FIELD_PRIME)).collect();
+
+            let mut row: Vec<([u8; 32], Vec<u64>)> = records.iter()
+                .map(|&index| {
+                    let record = &self.records[index];
+                    let prf = self.psi.evaluate_in_bin(bin, &record.key);
+                    let masked = record.payload.iter()
+                        .zip(&masks)
+                        .enumerate()
+                        .map(|(column, (&value, &mask))| {
+                            field_add(field_sub(value, mask), hint_pad(&prf, bin, column))
+                        })
+                        .collect();
+                    (hint_tag(&prf, bin), masked)
+                })
+                .collect();
+            while row.len() < max_load {
+                row.push((rng.gen(), (0..self.width).map(|_| rng.gen_range(0..FIELD_PRIME)).collect()));
+            }
+            row.shuffle(&mut rng);
+
+            hints.push(row);
+            sender_shares.push(masks.into_iter().map(|mask| AdditiveShare::new(JOIN_SENDER_ID, mask)).collect());
+        }
+
+        Ok((JoinHints { width: self.width, hints }, sender_shares))
+    }
+}
+
+/// 接收方的私有连接结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiverJoinOutput {
+    /// 接收方每个元素是否在交集中
+    pub matched: Vec<bool>,
+    /// 接收方的分享（未命中的元素为空）
+    pub shares: Vec<Vec<AdditiveShare>>,
+    /// 每个元素所在的桶，用于与发送方按桶排列的分享对齐
+    pub bins: Vec<usize>,
+}
+
+/// 私有连接的双方输出
+#[derive(Debug, Clone)]
+pub struct PrivateJoinOutput {
+    /// 发送方分享，按桶排列
+    pub sender_shares: Vec<Vec<AdditiveShare>>,
+    /// 接收方结果
+    pub receiver: ReceiverJoinOutput,
+}
+
+impl PrivateJoinOutput {
+    /// 重构接收方第 `index` 个元素的关联数据（仅用于测试与调试）
+    pub fn reconstruct(&self, index: usize) -> Option<Vec<u64>> {
+        if !*self.receiver.matched.get(index)? {
+            return None;
+        }
+        Some(self.sender_shares[self.receiver.bins[index]].iter()
+            .zip(&self.receiver.shares[index])
+            .map(|(s, r)| field_add(s.value, r.value))
+            .collect())
+    }
+}
+
+/// 执行两方私有连接
+///
+/// # 参数
+///
+/// * `records` - 发送方的记录
+/// * `receiver_keys` - 接收方的连接键
+///
+/// # 返回值
+///
+/// 返回双方的分享；交集中记录的关联数据以加法分享的形式给出
+pub fn execute_private_join(records: Vec<PsiRecord>, receiver_keys: Vec<Vec<u8>>) -> Result<PrivateJoinOutput> {
+    let (mut ot_sender, mut ot_receiver) = setup_ot_extension(SecurityModel::SemiHonest)?;
+    let sender = JoinSender::new(records)?;
+
+    let (pending, request) = PsiReceiver::request(receiver_keys, &mut ot_receiver)?;
+    let response = sender.respond_oprf(&request, &mut ot_sender)?;
+    let receiver = pending.finish(&response)?;
+    let (hints, sender_shares) = sender.join_hints(&request.cuckoo)?;
+    let receiver = hints.decode(&receiver)?;
+    Ok(PrivateJoinOutput { sender_shares, receiver })
+}
+
+/// 由 PRF 值派生提示标签
+fn hint_tag(prf: &OprfOutput, index: usize) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(b"mpc_api/private_join/tag")
+        .chain_update((index as u64).to_le_bytes())
+        .chain_update(prf)
+        .finalize()
+        .into()
+}
+
+/// 由 PRF 值派生关联数据掩码
+fn hint_pad(prf: &OprfOutput, index: usize, column: usize) -> u64 {
+    let digest = Sha256::new()
+        .chain_update(b"mpc_api/private_join/pad")
+        .chain_update((index as u64).to_le_bytes())
+        .chain_update((column as u64).to_le_bytes())
+        .chain_update(prf)
+        .finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(bytes) % FIELD_PRIME
+}
@@ -0,0 +1,549 @@
+//! # 私有集合求交 (Private Set Intersection)
+//!
+//! 基于 OT 扩展的 OPRF 和布谷鸟哈希实现两方半诚实私有集合求交：
+//! 接收方得到自己集合中属于交集的元素下标，发送方什么也得不到。
+//! `protocols::private_join` 在此基础上把交集记录的关联数据以秘密分享的形式交给双方。
+//!
+//! ## 协议流程
+//!
+//! 1. **分桶**: 接收方用 3 个公开哈希函数把自己的元素布谷鸟哈希到约 1.5n 个桶中，
+//!    每个桶至多一个元素（空桶填入随机占位元素），只把哈希种子和桶数发给发送方；
+//!    发送方把自己的每个元素放入它的全部候选桶（简单哈希）
+//! 2. **OPRF**: 发送方持有 secp256k1 素数阶群上的 Naor–Reingold PRF 密钥
+//!    (a₀, a₁, ..., a_L)，F(x) = g^(a₀ · ∏ a_i^(x_i))。对每个桶 b 的内容 x，
+//!    接收方以 b ‖ x 哈希值的全部比特作为选择位，通过一次 OT 扩展 (`OtExtensionReceiver`)
+//!    取得 (r_i, r_i · a_i) 中的一个，发送方再公开 g^(a₀ / ∏ r_i)，接收方取幂即得
+//!    F(b ‖ x)。OPRF 的数量与桶数相同，密钥始终只在发送方，发送方不知道接收方的输入
+//! 3. **比较**: 发送方公开自己每个元素在每个候选桶上的 F(b ‖ y)（至多 3|发送方集合| 个，已打乱），
+//!    接收方在自己元素所在的桶上比较
+//!
+//! 群的阶是 256 位素数，F(x) 的伪随机性基于 DDH 假设；
+//! 在阶光滑的群（例如 GF(p)*，p − 1 光滑时）上 Pohlig–Hellman 会直接恢复密钥。
+//! 布谷鸟哈希建表失败（概率很小）时接收方换一个种子重试；接收方集合中的元素必须互不相同。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::oblivious_transfer::{setup_ot_extension, SecurityModel};
+//! use mpc_api::protocols::psi::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let (mut ot_sender, mut ot_receiver) = setup_ot_extension(SecurityModel::SemiHonest)?;
+//! let sender = PsiSender::new(vec![b"alice".to_vec(), b"bob".to_vec()]);
+//!
+//! let (pending, request) = PsiReceiver::request(vec![b"carol".to_vec(), b"bob".to_vec()], &mut ot_receiver)?;
+//! let response = sender.respond_oprf(&request, &mut ot_sender)?;
+//! let receiver = pending.finish(&response)?;
+//! assert_eq!(receiver.intersection(&sender.prf_values(&request.cuckoo)?), vec![1]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::elliptic_curve::{Secp256k1Point, Secp256k1Scalar};
+use crate::oblivious_transfer::{
+    setup_ot_extension, OtExtensionReceiver, OtExtensionRequest, OtExtensionSender, SecurityModel,
+};
+use crate::{MpcError, Result};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt;
+
+/// 元素哈希的比特数（每个元素需要的 OT 数量）
+pub const PSI_HASH_BITS: usize = 128;
+
+/// 布谷鸟哈希使用的哈希函数个数
+pub const CUCKOO_HASH_COUNT: usize = 3;
+
+/// 建表失败时更换种子重试的次数
+const CUCKOO_MAX_ATTEMPTS: usize = 16;
+
+/// 单次插入的最大踢出次数
+const CUCKOO_MAX_KICKS: usize = 512;
+
+/// 发送方接受的最大桶数，防止接收方的请求迫使发送方分配过大的应答
+pub const CUCKOO_MAX_BINS: usize = 1 << 24;
+
+/// OPRF 输出：群元素 F(x) 的 SEC 1 压缩编码
+pub type OprfOutput = [u8; 33];
+
+/// Naor–Reingold OPRF 密钥，只由发送方持有
+#[derive(Clone)]
+pub struct OprfKey {
+    /// a₀, a₁, ..., a_L，均为 secp256k1 的非零标量
+    exponents: Vec<Secp256k1Scalar>,
+}
+
+impl OprfKey {
+    /// 生成随机 OPRF 密钥
+    pub fn generate() -> Self {
+        Self {
+            exponents: (0..=PSI_HASH_BITS).map(|_| Secp256k1Scalar::random()).collect(),
+        }
+    }
+
+    /// 由密钥持有方直接计算 PRF 值
+    pub fn evaluate(&self, element: &[u8]) -> OprfOutput {
+        let bits = element_hash(element);
+        let exponent = (0..PSI_HASH_BITS)
+            .filter(|&i| (bits >> i) & 1 == 1)
+            .fold(self.exponents[0], |acc, i| acc * self.exponents[i + 1]);
+        encode_output(&(Secp256k1Point::generator() * exponent))
+    }
+
+    /// 发送方处理接收方的 OPRF 请求
+    ///
+    /// 对第 j 个元素的第 i 位，发送方选取随机 r_i，用扩展出的随机 OT 消息对
+    /// 加密 (r_i, r_i · a_i)，并附上 g^(a₀ / ∏ r_i)。
+    ///
+    /// # 参数
+    ///
+    /// * `request` - 接收方的 OPRF 请求
+    /// * `ot` - 与接收方建立的 OT 扩展发送方
+    ///
+    /// # 返回值
+    ///
+    /// 返回发给接收方的应答
+    pub fn respond(&self, request: &OprfRequest, ot: &mut OtExtensionSender) -> Result<OprfResponse> {
+        if request.extension.count != request.count * PSI_HASH_BITS {
+            return Err(MpcError::ProtocolError(format!(
+                "OPRF request for {} inputs carries {} OTs", request.count, request.extension.count
+            )));
+        }
+        let pads = ot.extend(&request.extension)?;
+
+        let mut transfers = Vec::with_capacity(pads.len());
+        let mut blinded_bases = Vec::with_capacity(request.count);
+        for element_pads in pads.chunks(PSI_HASH_BITS) {
+            let mut mask_product = Secp256k1Scalar::ONE;
+            for (i, &(pad0, pad1)) in element_pads.iter().enumerate() {
+                let r = Secp256k1Scalar::random();
+                mask_product = mask_product * r;
+                transfers.push([
+                    xor_pad(&r.to_bytes(), pad0),
+                    xor_pad(&(r * self.exponents[i + 1]).to_bytes(), pad1),
+                ]);
+            }
+            let inverse = mask_product.invert()
+                .ok_or_else(|| MpcError::CryptographicError("OPRF mask not invertible".to_string()))?;
+            blinded_bases.push(Secp256k1Point::generator() * (self.exponents[0] * inverse));
+        }
+
+        Ok(OprfResponse { transfers, blinded_bases })
+    }
+}
+
+impl fmt::Debug for OprfKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OprfKey(..)")
+    }
+}
+
+/// 接收方发给发送方的 OPRF 请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OprfRequest {
+    /// 输入数量
+    pub count: usize,
+    /// OT 扩展消息，选择位为每个输入哈希值的各个比特
+    pub extension: OtExtensionRequest,
+}
+
+/// 发送方的 OPRF 应答
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OprfResponse {
+    /// 每个 OT 的两个加密消息 (r_i, r_i · a_i)
+    pub transfers: Vec<[[u8; 32]; 2]>,
+    /// 每个输入的盲化基 g^(a₀ / ∏ r_i)
+    pub blinded_bases: Vec<Secp256k1Point>,
+}
+
+/// OPRF 接收方在请求与应答之间保存的状态
+#[derive(Debug, Clone)]
+pub struct OprfReceiver {
+    /// 每个输入的哈希比特（即 OT 选择位）
+    bits: Vec<u128>,
+    /// OT 扩展中接收方得到的消息
+    pads: Vec<u128>,
+}
+
+impl OprfReceiver {
+    /// 对一批输入发起 OPRF 请求
+    ///
+    /// # 参数
+    ///
+    /// * `inputs` - 接收方的输入
+    /// * `ot` - 与发送方建立的 OT 扩展接收方
+    ///
+    /// # 返回值
+    ///
+    /// 返回 (接收方状态, 发给发送方的请求)
+    pub fn request(inputs: &[Vec<u8>], ot: &mut OtExtensionReceiver) -> Result<(Self, OprfRequest)> {
+        let bits: Vec<u128> = inputs.iter().map(|input| element_hash(input)).collect();
+        let choices: Vec<bool> = bits.iter()
+            .flat_map(|&bits| (0..PSI_HASH_BITS).map(move |i| (bits >> i) & 1 == 1))
+            .collect();
+        let (pads, extension) = ot.extend(&choices)?;
+        Ok((Self { bits, pads }, OprfRequest { count: inputs.len(), extension }))
+    }
+
+    /// 解密所选消息，得到 PRF 值
+    ///
+    /// # 返回值
+    ///
+    /// 返回接收方得到的 PRF 值，顺序与输入一致
+    pub fn finish(self, response: &OprfResponse) -> Result<Vec<OprfOutput>> {
+        if response.transfers.len() != self.pads.len() || response.blinded_bases.len() != self.bits.len() {
+            return Err(MpcError::ProtocolError("OPRF response does not match the request".to_string()));
+        }
+
+        self.bits.iter()
+            .zip(&response.blinded_bases)
+            .zip(response.transfers.chunks(PSI_HASH_BITS).zip(self.pads.chunks(PSI_HASH_BITS)))
+            .map(|((&bits, base), (transfers, pads))| {
+                let chosen_product = transfers.iter()
+                    .zip(pads)
+                    .enumerate()
+                    .try_fold(Secp256k1Scalar::ONE, |acc, (i, (transfer, &pad))| {
+                        let bytes = xor_pad(&transfer[((bits >> i) & 1) as usize], pad);
+                        let value = Secp256k1Scalar::from_bytes(&bytes)
+                            .ok_or_else(|| MpcError::ProtocolError("Malformed OPRF transfer".to_string()))?;
+                        Ok::<_, MpcError>(acc * value)
+                    })?;
+                Ok(encode_output(&(*base * chosen_product)))
+            })
+            .collect()
+    }
+}
+
+/// 布谷鸟哈希表的公开参数，由接收方选定并发给发送方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CuckooParams {
+    /// 哈希函数种子
+    pub seed: u64,
+    /// 桶数
+    pub bin_count: usize,
+}
+
+impl CuckooParams {
+    /// 为 `count` 个元素选择桶数（约 1.5 倍，至少 4 个桶）
+    pub fn new(count: usize, seed: u64) -> Self {
+        Self { seed, bin_count: (count * 3).div_ceil(2).max(4) }
+    }
+
+    /// 检查来自接收方的参数：桶数必须在 [4, `CUCKOO_MAX_BINS`] 之内
+    pub fn validate(&self) -> Result<()> {
+        if self.bin_count < 4 || self.bin_count > CUCKOO_MAX_BINS {
+            return Err(MpcError::ProtocolError(format!(
+                "Cuckoo bin count {} is outside [4, {}]", self.bin_count, CUCKOO_MAX_BINS
+            )));
+        }
+        Ok(())
+    }
+
+    /// 元素的候选桶（去重，按哈希函数顺序），桶数为 0 时没有候选桶
+    pub fn candidate_bins(&self, element: &[u8]) -> Vec<usize> {
+        if self.bin_count == 0 {
+            return Vec::new();
+        }
+        let mut bins = Vec::with_capacity(CUCKOO_HASH_COUNT);
+        for function in 0..CUCKOO_HASH_COUNT {
+            let digest = Sha256::new()
+                .chain_update(b"mpc_api/psi/cuckoo")
+                .chain_update(self.seed.to_le_bytes())
+                .chain_update([function as u8])
+                .chain_update(element)
+                .finalize();
+            let value = u64::from_le_bytes(digest[..8].try_into().expect("8-byte prefix"));
+            let bin = (value % self.bin_count as u64) as usize;
+            if !bins.contains(&bin) {
+                bins.push(bin);
+            }
+        }
+        bins
+    }
+}
+
+/// 布谷鸟哈希表：每个桶至多一个元素，每个元素位于它的某个候选桶中
+#[derive(Debug, Clone)]
+pub struct CuckooTable {
+    /// 公开参数
+    params: CuckooParams,
+    /// 每个桶中元素的下标
+    bins: Vec<Option<usize>>,
+}
+
+impl CuckooTable {
+    /// 为一组互不相同的元素建表，失败时更换随机种子重试
+    pub fn build(elements: &[Vec<u8>]) -> Result<Self> {
+        let mut rng = rand::thread_rng();
+        for _ in 0..CUCKOO_MAX_ATTEMPTS {
+            if let Ok(table) = Self::build_with_seed(elements, rng.gen()) {
+                return Ok(table);
+            }
+        }
+        Err(MpcError::ProtocolError(format!(
+            "Cuckoo hashing failed after {} attempts; are the elements distinct?", CUCKOO_MAX_ATTEMPTS
+        )))
+    }
+
+    /// 使用给定种子建表
+    ///
+    /// 每个元素先尝试空闲的候选桶，没有时踢出其中一个桶的元素，
+    /// 被踢出的元素再换到自己的另一个候选桶，直到超过踢出次数上限。
+    pub fn build_with_seed(elements: &[Vec<u8>], seed: u64) -> Result<Self> {
+        let params = CuckooParams::new(elements.len(), seed);
+        let candidates: Vec<Vec<usize>> = elements.iter().map(|element| params.candidate_bins(element)).collect();
+        let mut bins = vec![None; params.bin_count];
+        let mut rng = rand::thread_rng();
+
+        for start in 0..elements.len() {
+            let mut current = start;
+            let mut previous_bin = None;
+            let mut placed = false;
+            for _ in 0..CUCKOO_MAX_KICKS {
+                if let Some(&bin) = candidates[current].iter().find(|&&bin| bins[bin].is_none()) {
+                    bins[bin] = Some(current);
+                    placed = true;
+                    break;
+                }
+                let choices: Vec<usize> = candidates[current].iter()
+                    .copied()
+                    .filter(|&bin| Some(bin) != previous_bin)
+                    .collect();
+                let Some(&bin) = choices.choose(&mut rng) else { break };
+                let evicted = bins[bin].replace(current).expect("occupied bin");
+                current = evicted;
+                previous_bin = Some(bin);
+            }
+            if !placed {
+                return Err(MpcError::ProtocolError("Cuckoo insertion exceeded the kick limit".to_string()));
+            }
+        }
+
+        Ok(Self { params, bins })
+    }
+
+    /// 公开参数
+    pub fn params(&self) -> CuckooParams {
+        self.params
+    }
+
+    /// 每个桶中元素的下标
+    pub fn bins(&self) -> &[Option<usize>] {
+        &self.bins
+    }
+
+    /// 每个元素所在的桶，按元素下标排列
+    pub fn element_bins(&self) -> Vec<usize> {
+        let mut positions = vec![0; self.bins.iter().flatten().count()];
+        for (bin, element) in self.bins.iter().enumerate() {
+            if let Some(element) = element {
+                positions[*element] = bin;
+            }
+        }
+        positions
+    }
+}
+
+/// 接收方发给发送方的 PSI 请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PsiRequest {
+    /// 布谷鸟哈希参数
+    pub cuckoo: CuckooParams,
+    /// 对每个桶的 OPRF 请求
+    pub oprf: OprfRequest,
+}
+
+/// PSI 发送方
+#[derive(Debug, Clone)]
+pub struct PsiSender {
+    /// OPRF 密钥
+    key: OprfKey,
+    /// 发送方集合
+    elements: Vec<Vec<u8>>,
+}
+
+impl PsiSender {
+    /// 以发送方集合创建发送方，OPRF 密钥随机生成
+    pub fn new(elements: Vec<Vec<u8>>) -> Self {
+        Self { key: OprfKey::generate(), elements }
+    }
+
+    /// 发送方集合
+    pub fn elements(&self) -> &[Vec<u8>] {
+        &self.elements
+    }
+
+    /// 发送方自己计算元素在桶 `bin` 中的 PRF 值 F(b ‖ x)
+    pub fn evaluate_in_bin(&self, bin: usize, element: &[u8]) -> OprfOutput {
+        self.key.evaluate(&bin_input(bin, element))
+    }
+
+    /// 处理接收方的 PSI 请求，对每个桶执行 OPRF，密钥不离开发送方
+    pub fn respond_oprf(&self, request: &PsiRequest, ot: &mut OtExtensionSender) -> Result<OprfResponse> {
+        request.cuckoo.validate()?;
+        if request.oprf.count != request.cuckoo.bin_count {
+            return Err(MpcError::ProtocolError(format!(
+                "PSI request has {} bins but {} OPRF inputs", request.cuckoo.bin_count, request.oprf.count
+            )));
+        }
+        self.key.respond(&request.oprf, ot)
+    }
+
+    /// 发送方元素在各个候选桶上的 PRF 值（已打乱），直接发给接收方
+    pub fn prf_values(&self, params: &CuckooParams) -> Result<Vec<OprfOutput>> {
+        params.validate()?;
+        let mut values: Vec<OprfOutput> = self.elements.iter()
+            .flat_map(|element| {
+                params.candidate_bins(element)
+                    .into_iter()
+                    .map(|bin| self.evaluate_in_bin(bin, element))
+            })
+            .collect();
+        values.shuffle(&mut rand::thread_rng());
+        Ok(values)
+    }
+}
+
+/// PSI 接收方
+#[derive(Debug, Clone)]
+pub struct PsiReceiver {
+    /// 接收方的元素
+    elements: Vec<Vec<u8>>,
+    /// 布谷鸟哈希表
+    table: CuckooTable,
+    /// 每个桶的 OPRF 输出
+    prf_values: Vec<OprfOutput>,
+}
+
+/// 等待发送方 OPRF 应答的接收方
+#[derive(Debug, Clone)]
+pub struct PendingPsiReceiver {
+    elements: Vec<Vec<u8>>,
+    table: CuckooTable,
+    oprf: OprfReceiver,
+}
+
+impl PendingPsiReceiver {
+    /// 用发送方的应答完成 OPRF
+    pub fn finish(self, response: &OprfResponse) -> Result<PsiReceiver> {
+        let prf_values = self.oprf.finish(response)?;
+        Ok(PsiReceiver { elements: self.elements, table: self.table, prf_values })
+    }
+}
+
+impl PsiReceiver {
+    /// 对自己的元素分桶并对每个桶发起 OPRF 请求
+    ///
+    /// 空桶以随机占位元素参与 OPRF，发送方看不出哪些桶被占用。
+    ///
+    /// # 返回值
+    ///
+    /// 返回 (等待应答的接收方, 发给发送方的请求)
+    pub fn request(elements: Vec<Vec<u8>>, ot: &mut OtExtensionReceiver) -> Result<(PendingPsiReceiver, PsiRequest)> {
+        let table = CuckooTable::build(&elements)?;
+        let mut rng = rand::thread_rng();
+        let inputs: Vec<Vec<u8>> = table.bins()
+            .iter()
+            .enumerate()
+            .map(|(bin, element)| match element {
+                Some(index) => bin_input(bin, &elements[*index]),
+                None => rng.gen::<[u8; 32]>().to_vec(),
+            })
+            .collect();
+        let (oprf, request) = OprfReceiver::request(&inputs, ot)?;
+        let cuckoo = table.params();
+        Ok((PendingPsiReceiver { elements, table, oprf }, PsiRequest { cuckoo, oprf: request }))
+    }
+
+    /// 元素数量
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// 是否没有元素
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// 布谷鸟哈希参数
+    pub fn cuckoo_params(&self) -> CuckooParams {
+        self.table.params()
+    }
+
+    /// 第 `index` 个元素所在的桶及其 PRF 值
+    pub fn element_prf(&self, index: usize) -> Option<(usize, &OprfOutput)> {
+        let bin = *self.table.element_bins().get(index)?;
+        Some((bin, &self.prf_values[bin]))
+    }
+
+    /// 每个元素所在的桶，按元素下标排列
+    pub fn element_bins(&self) -> Vec<usize> {
+        self.table.element_bins()
+    }
+
+    /// 交集中元素的下标
+    pub fn intersection(&self, sender_prf_values: &[OprfOutput]) -> Vec<usize> {
+        let sender: HashSet<&OprfOutput> = sender_prf_values.iter().collect();
+        self.table.element_bins()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, bin)| sender.contains(&self.prf_values[*bin]))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+/// 执行两方 PSI
+///
+/// 在同一进程中建立 OT 扩展并依次执行双方的各轮消息。
+///
+/// # 返回值
+///
+/// 返回接收方集合中属于交集的元素下标
+pub fn execute_psi(sender_set: &[Vec<u8>], receiver_set: Vec<Vec<u8>>) -> Result<Vec<usize>> {
+    let (mut ot_sender, mut ot_receiver) = setup_ot_extension(SecurityModel::SemiHonest)?;
+    let sender = PsiSender::new(sender_set.to_vec());
+
+    let (pending, request) = PsiReceiver::request(receiver_set, &mut ot_receiver)?;
+    let response = sender.respond_oprf(&request, &mut ot_sender)?;
+    let receiver = pending.finish(&response)?;
+    Ok(receiver.intersection(&sender.prf_values(&request.cuckoo)?))
+}
+
+/// 桶 b 中元素 x 的 OPRF 输入 b ‖ x
+fn bin_input(bin: usize, element: &[u8]) -> Vec<u8> {
+    let mut input = (bin as u64).to_le_bytes().to_vec();
+    input.extend_from_slice(element);
+    input
+}
+
+/// 将元素哈希为 `PSI_HASH_BITS` 位
+fn element_hash(element: &[u8]) -> u128 {
+    let digest = Sha256::new()
+        .chain_update(b"mpc_api/psi/element")
+        .chain_update(element)
+        .finalize();
+    u128::from_le_bytes(digest[..16].try_into().expect("16-byte prefix"))
+}
+
+/// 群元素的 33 字节压缩编码
+fn encode_output(point: &Secp256k1Point) -> OprfOutput {
+    point.to_sec1(true)
+        .try_into()
+        .expect("compressed SEC 1 encoding is 33 bytes")
+}
+
+/// 用随机 OT 消息派生的 32 字节掩码加密或解密一个标量
+fn xor_pad(bytes: &[u8; 32], pad: u128) -> [u8; 32] {
+    let key: [u8; 32] = Sha256::new()
+        .chain_update(b"mpc_api/psi/oprf_pad")
+        .chain_update(pad.to_le_bytes())
+        .finalize()
+        .into();
+    let mut out = *bytes;
+    out.iter_mut().zip(key).for_each(|(b, k)| *b ^= k);
+    out
+}
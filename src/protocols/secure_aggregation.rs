@@ -0,0 +1,322 @@
+//! # 带裁剪的加权安全聚合 (Weighted Secure Aggregation with Clipping)
+//!
+//! 联邦学习中服务器需要计算客户端更新的加权和，同时限制投毒客户端提交的异常更新。
+//! 本模块在 MPC 内完成范围检查和裁剪，服务器不会看到任何单个客户端的更新：
+//!
+//! 1. **输入分享**: 客户端把每个坐标按位分解，将每一位以 Shamir 分享发给各服务器；
+//!    坐标的分享由位分享线性组合得到，因此只要每一位是布尔值，坐标就落在 [0, 2^k) 内
+//! 2. **范围证明**: 服务器用 Beaver 乘法计算每一位的 b·b - b，
+//!    按公共随机系数合并后公开；结果非零说明存在非布尔位，该客户端被拒绝
+//! 3. **比较**: 从最高位开始逐位比较 x 与公开裁剪界 B，得到 [x > B]，每一位一次乘法
+//! 4. **多路选择**: clip(x) = x + [x > B]·(B - x)
+//! 5. **加权求和**: 按公开权重（例如样本数）线性组合各客户端的裁剪结果
+//!
+//! 所有客户端和坐标同步执行，因此在线轮数只与位长有关，与客户端数量和维度无关。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+//! use mpc_api::protocols::secure_aggregation::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let clipping = ClippingBound::new(8, 100)?;
+//! let aggregation = WeightedSecureAggregation::new(3, 2, 2, clipping)?;
+//!
+//! let submissions = vec![
+//!     ClientSubmission::new(0, &[10, 20], 1, clipping, 3, 2)?,
+//!     ClientSubmission::new(1, &[250, 30], 2, clipping, 3, 2)?,
+//! ];
+//!
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//! let output = aggregation.aggregate(&submissions, &mut generator)?;
+//! assert_eq!(output.result.reconstruct(2)?, vec![10 + 2 * 100, 20 + 2 * 30]);
+//! assert_eq!(output.result.total_weight, 3);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::stats::{ProtocolOutput, StatsRecorder};
+use crate::beaver_triples::BeaverTripleGenerator;
+use crate::secret_sharing::bits::{add_shares, compose, public_shares, scale_shares, sub_shares, Multiplier};
+use crate::secret_sharing::{SecretSharing, ShamirSecretSharing, Share, field_add};
+use crate::utils::random_field_element;
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+
+/// 支持的最大位长（保证 2^k 小于域的模数）
+pub const MAX_CLIPPING_BITS: usize = 63;
+
+/// 裁剪参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClippingBound {
+    /// 每个坐标的位长 k，输入必须落在 [0, 2^k) 内
+    pub bit_length: usize,
+    /// 裁剪界 B，大于 B 的坐标被替换为 B
+    pub bound: u64,
+}
+
+impl ClippingBound {
+    /// 创建裁剪参数
+    ///
+    /// # 参数
+    /// - `bit_length`: 每个坐标的位长（1 到 `MAX_CLIPPING_BITS`）
+    /// - `bound`: 裁剪界，必须小于 2^bit_length
+    pub fn new(bit_length: usize, bound: u64) -> Result<Self> {
+        if bit_length == 0 || bit_length > MAX_CLIPPING_BITS {
+            return Err(MpcError::ProtocolError(format!(
+                "Bit length must be between 1 and {}", MAX_CLIPPING_BITS
+            )));
+        }
+        if bound >> bit_length != 0 {
+            return Err(MpcError::ProtocolError(format!(
+                "Clipping bound {} does not fit in {} bits", bound, bit_length
+            )));
+        }
+        Ok(Self { bit_length, bound })
+    }
+
+    /// 裁剪界的第 i 位
+    fn bound_bit(&self, i: usize) -> bool {
+        (self.bound >> i) & 1 == 1
+    }
+}
+
+/// 客户端提交给服务器的位分享
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientSubmission {
+    /// 客户端 ID
+    pub client_id: usize,
+    /// 公开权重
+    pub weight: u64,
+    /// 位分享：`bit_shares[coordinate][bit]` 是该位在各服务器处的分享（低位在前）
+    pub bit_shares: Vec<Vec<Vec<Share>>>,
+}
+
+impl ClientSubmission {
+    /// 按位分享客户端的更新
+    ///
+    /// # 参数
+    /// - `client_id`: 客户端 ID
+    /// - `values`: 更新的各个坐标
+    /// - `weight`: 公开权重
+    /// - `clipping`: 裁剪参数（决定位长）
+    /// - `party_count`: 服务器数量
+    /// - `threshold`: 重构门限
+    pub fn new(
+        client_id: usize,
+        values: &[u64],
+        weight: u64,
+        clipping: ClippingBound,
+        party_count: usize,
+        threshold: usize,
+    ) -> Result<Self> {
+        let bit_shares = values.iter()
+            .map(|&value| {
+                if value >> clipping.bit_length != 0 {
+                    return Err(MpcError::ProtocolError(format!(
+                        "Value {} does not fit in {} bits", value, clipping.bit_length
+                    )));
+                }
+                (0..clipping.bit_length)
+                    .map(|i| ShamirSecretSharing::share(&((value >> i) & 1), threshold, party_count))
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<Vec<Share>>>>>()?;
+
+        Ok(Self { client_id, weight, bit_shares })
+    }
+
+    /// 更新的维度
+    pub fn dimension(&self) -> usize {
+        self.bit_shares.len()
+    }
+}
+
+/// 聚合结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregationResult {
+    /// 每个坐标的加权和在各服务器处的分享
+    pub sum_shares: Vec<Vec<Share>>,
+    /// 通过范围证明的客户端 ID
+    pub accepted: Vec<usize>,
+    /// 未通过范围证明的客户端 ID
+    pub rejected: Vec<usize>,
+    /// 被接受客户端的权重之和
+    pub total_weight: u64,
+}
+
+impl AggregationResult {
+    /// 重构加权和
+    pub fn reconstruct(&self, threshold: usize) -> Result<Vec<u64>> {
+        self.sum_shares.iter()
+            .map(|shares| ShamirSecretSharing::reconstruct(shares, threshold))
+            .collect()
+    }
+}
+
+/// 带裁剪的加权安全聚合
+#[derive(Debug, Clone)]
+pub struct WeightedSecureAggregation {
+    party_count: usize,
+    threshold: usize,
+    dimension: usize,
+    clipping: ClippingBound,
+}
+
+impl WeightedSecureAggregation {
+    /// 创建聚合协议
+    ///
+    /// # 参数
+    /// - `party_count`: 服务器数量
+    /// - `threshold`: 重构门限
+    /// - `dimension`: 更新的维度（必须为正）
+    /// - `clipping`: 裁剪参数
+    pub fn new(party_count: usize, threshold: usize, dimension: usize, clipping: ClippingBound) -> Result<Self> {
+        if threshold == 0 || threshold > party_count {
+            return Err(MpcError::InvalidThreshold);
+        }
+        if dimension == 0 {
+            return Err(MpcError::ProtocolError("Dimension must be positive".to_string()));
+        }
+        Ok(Self { party_count, threshold, dimension, clipping })
+    }
+
+    /// 执行聚合
+    ///
+    /// 未通过范围证明的客户端被排除在加权和之外，并记录在 `rejected` 中。
+    ///
+    /// # 参数
+    /// - `submissions`: 各客户端的提交
+    /// - `generator`: 提供 Beaver 三元组的生成器，参与方数量和门限必须与协议一致
+    ///
+    /// # 返回值
+    /// 返回加权和的分享和执行统计
+    pub fn aggregate(
+        &self,
+        submissions: &[ClientSubmission],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<AggregationResult>> {
+        if generator.get_party_count() != self.party_count || generator.get_threshold() != self.threshold {
+            return Err(MpcError::ProtocolError(
+                "Triple generator does not match the aggregation parameters".to_string()
+            ));
+        }
+        for submission in submissions {
+            self.check_shape(submission)?;
+        }
+
+        let mut recorder = StatsRecorder::start();
+        let mut multiplier = Multiplier::new(generator, self.threshold, self.party_count);
+
+        // 第 1 步：范围证明，检查每一位都是布尔值
+        let accepted_flags = self.check_bits(submissions, &mut multiplier)?;
+        let (accepted, rejected): (Vec<_>, Vec<_>) = submissions.iter()
+            .zip(&accepted_flags)
+            .partition(|(_, &ok)| ok);
+        let accepted: Vec<&ClientSubmission> = accepted.into_iter().map(|(s, _)| s).collect();
+        let rejected: Vec<usize> = rejected.into_iter().map(|(s, _)| s.client_id).collect();
+
+        // 第 2 步：对所有 (客户端, 坐标) 同步执行比较和多路选择
+        let bits: Vec<&Vec<Vec<Share>>> = accepted.iter().flat_map(|s| s.bit_shares.iter()).collect();
+        let values: Vec<Vec<Share>> = bits.iter().map(|bits| compose(bits, self.party_count)).collect();
+        let clipped = self.clip(&bits, &values, &mut multiplier)?;
+
+        // 第 3 步：按公开权重求和
+        let mut sum_shares = vec![public_shares(self.party_count, 0); self.dimension];
+        let mut total_weight = 0u64;
+        for (submission, clipped) in accepted.iter().zip(clipped.chunks(self.dimension)) {
+            for (sum, value) in sum_shares.iter_mut().zip(clipped) {
+                *sum = add_shares(sum, &scale_shares(value, submission.weight));
+            }
+            total_weight = field_add(total_weight, submission.weight);
+        }
+
+        let stats = recorder.stats_mut();
+        // 输入分享占一轮：每个客户端把属于服务器 k 的位分享发给它，只计入服务器的接收量
+        stats.record_rounds(1);
+        for submission in submissions {
+            for (k, server) in (1..=self.party_count).enumerate() {
+                let message: Vec<Vec<&Share>> = submission.bit_shares.iter()
+                    .map(|bits| bits.iter().map(|shares| &shares[k]).collect())
+                    .collect();
+                let bytes = bincode::serialized_size(&message)
+                    .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+                stats.record_input(server, bytes);
+            }
+        }
+        multiplier.record(stats);
+
+        Ok(recorder.finish(AggregationResult {
+            sum_shares,
+            accepted: accepted.iter().map(|s| s.client_id).collect(),
+            rejected,
+            total_weight,
+        }))
+    }
+
+    /// 检查提交的形状
+    fn check_shape(&self, submission: &ClientSubmission) -> Result<()> {
+        let well_formed = submission.dimension() == self.dimension
+            && submission.bit_shares.iter().all(|bits| {
+                bits.len() == self.clipping.bit_length
+                    && bits.iter().all(|shares| shares.len() == self.party_count)
+            });
+        if well_formed {
+            Ok(())
+        } else {
+            Err(MpcError::ProtocolError(format!(
+                "Submission from client {} has the wrong shape", submission.client_id
+            )))
+        }
+    }
+
+    /// 范围证明：公开 Σ r·(b·b - b)，为零则接受
+    fn check_bits(&self, submissions: &[ClientSubmission], multiplier: &mut Multiplier) -> Result<Vec<bool>> {
+        let bits: Vec<Vec<Share>> = submissions.iter()
+            .flat_map(|s| s.bit_shares.iter().flatten().cloned())
+            .collect();
+        let squares = multiplier.multiply(&bits, &bits)?;
+
+        let per_client = self.dimension * self.clipping.bit_length;
+        let checks: Vec<Vec<Share>> = bits.chunks(per_client)
+            .zip(squares.chunks(per_client))
+            .map(|(bits, squares)| {
+                bits.iter().zip(squares).fold(public_shares(self.party_count, 0), |check, (bit, square)| {
+                    add_shares(&check, &scale_shares(&sub_shares(square, bit), random_field_element()))
+                })
+            })
+            .collect();
+        Ok(multiplier.open(&checks)?.into_iter().map(|check| check == 0).collect())
+    }
+
+    /// 比较与多路选择：clip(x) = x + [x > B]·(B - x)
+    fn clip(&self, bits: &[&Vec<Vec<Share>>], values: &[Vec<Share>], multiplier: &mut Multiplier) -> Result<Vec<Vec<Share>>> {
+        if values.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut greater = vec![public_shares(self.party_count, 0); values.len()];
+        let mut equal = vec![public_shares(self.party_count, 1); values.len()];
+
+        // 从最高位开始：p = eq·x_i；B_i = 0 时 gt += p, eq -= p；B_i = 1 时 eq = p
+        for i in (0..self.clipping.bit_length).rev() {
+            let bit_i: Vec<Vec<Share>> = bits.iter().map(|bits| bits[i].clone()).collect();
+            let products = multiplier.multiply(&equal, &bit_i)?;
+            for ((gt, eq), p) in greater.iter_mut().zip(equal.iter_mut()).zip(products) {
+                if self.clipping.bound_bit(i) {
+                    *eq = p;
+                } else {
+                    *gt = add_shares(gt, &p);
+                    *eq = sub_shares(eq, &p);
+                }
+            }
+        }
+
+        let differences: Vec<Vec<Share>> = values.iter()
+            .map(|x| sub_shares(&public_shares(self.party_count, self.clipping.bound), x))
+            .collect();
+        let corrections = multiplier.multiply(&greater, &differences)?;
+        Ok(values.iter().zip(&corrections).map(|(x, c)| add_shares(x, c)).collect())
+    }
+}
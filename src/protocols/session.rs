@@ -0,0 +1,279 @@
+//! # 子协议组合 (Sub-protocol Composition)
+//!
+//! 复杂协议由多层子协议组合而成，例如 SPDZ 中的三元组牺牲检查
+//! 需要调用硬币抛掷生成挑战值。如果子协议的承诺、挑战和随机数与父协议无关，
+//! 攻击者就可以把一个实例中的消息拼接到另一个实例中（cross-instance splicing）。
+//!
+//! 本模块提供 `ProtocolSession`：
+//!
+//! - **会话 ID 派生**: 子会话的 ID 由父会话 ID、父转录摘要、子协议名称和序号哈希得到，
+//!   同一父会话下的兄弟实例也互不相同
+//! - **转录绑定**: 每个会话维护一个滚动哈希转录，`transcript` 返回以会话 ID 与当前转录
+//!   开始的 `Transcript`，挑战值由它导出，承诺上下文由会话 ID 派生
+//! - **结果回收**: 子会话结束后由父会话吸收其最终转录；只接受由自己派生的子会话
+//! - **会话持久化**: `SessionStore` 把会话状态写入可插拔的 `StorageBackend`，
+//!   进程重启后可以按会话 ID 恢复
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::protocols::session::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let mut spdz = ProtocolSession::root("spdz", b"parties=3");
+//! let mut sacrifice = spdz.spawn_child("sacrifice");
+//! let coin_flip = sacrifice.spawn_child("coin_flip");
+//!
+//! assert_eq!(coin_flip.path(), "spdz/sacrifice#0/coin_flip#0");
+//! assert_eq!(coin_flip.parent(), Some(sacrifice.id()));
+//!
+//! // 子会话的挑战值与父会话的上下文绑定
+//! let challenge = coin_flip.challenge_field("alpha");
+//! sacrifice.finish_child(coin_flip)?;
+//! spdz.finish_child(sacrifice)?;
+//! # let _ = challenge;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::storage::{load_value, store_value, StorageBackend};
+use crate::utils::transcript::Transcript;
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::Arc;
+
+/// 会话派生的域分隔标签
+const SESSION_DOMAIN: &[u8] = b"mpc_api/session/v1";
+
+/// 会话标识符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SessionId(pub [u8; 32]);
+
+impl SessionId {
+    /// 原始字节
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for SessionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0[..8] {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// 协议会话
+///
+/// 每个协议实例（包括嵌套的子协议实例）持有一个会话，
+/// 用于派生子会话、记录转录以及生成绑定上下文的挑战值。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProtocolSession {
+    /// 会话 ID
+    id: SessionId,
+    /// 父会话 ID（根会话为 `None`）
+    parent: Option<SessionId>,
+    /// 从根会话到本会话的路径，例如 `spdz/sacrifice#0`
+    path: String,
+    /// 滚动转录摘要
+    transcript: [u8; 32],
+    /// 已派生的子会话数量
+    children_spawned: u64,
+    /// 已派生但尚未结束的子会话
+    open_children: Vec<SessionId>,
+}
+
+impl ProtocolSession {
+    /// 创建根会话
+    ///
+    /// # 参数
+    ///
+    /// * `protocol` - 协议名称
+    /// * `context` - 实例上下文（参与方名单、配置、外部会话号等），不同实例应当不同
+    pub fn root(protocol: &str, context: &[u8]) -> Self {
+        let id = SessionId(hash_parts(&[b"root", protocol.as_bytes(), context]));
+        Self::with_id(id, None, protocol.to_string())
+    }
+
+    fn with_id(id: SessionId, parent: Option<SessionId>, path: String) -> Self {
+        Self {
+            id,
+            parent,
+            path,
+            transcript: hash_parts(&[b"transcript", &id.0]),
+            children_spawned: 0,
+            open_children: Vec::new(),
+        }
+    }
+
+    /// 会话 ID
+    pub fn id(&self) -> SessionId {
+        self.id
+    }
+
+    /// 父会话 ID
+    pub fn parent(&self) -> Option<SessionId> {
+        self.parent
+    }
+
+    /// 会话路径
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// 当前转录摘要
+    pub fn transcript_digest(&self) -> [u8; 32] {
+        self.transcript
+    }
+
+    /// 派生子会话
+    ///
+    /// 子会话 ID 绑定父会话 ID、父会话当前转录、子协议名称和序号。
+    /// 派生行为本身也会记入父会话的转录。
+    pub fn spawn_child(&mut self, protocol: &str) -> ProtocolSession {
+        let index = self.children_spawned;
+        self.children_spawned += 1;
+
+        let id = SessionId(hash_parts(&[
+            b"child",
+            &self.id.0,
+            &self.transcript,
+            protocol.as_bytes(),
+            &index.to_le_bytes(),
+        ]));
+        self.absorb("spawn", &id.0);
+        self.open_children.push(id);
+
+        Self::with_id(id, Some(self.id), format!("{}/{}#{}", self.path, protocol, index))
+    }
+
+    /// 结束子会话，把子会话的最终转录吸收进本会话
+    ///
+    /// # 返回值
+    ///
+    /// 子会话不是由本会话派生、或已经结束过时返回错误
+    pub fn finish_child(&mut self, child: ProtocolSession) -> Result<()> {
+        if child.parent != Some(self.id) {
+            return Err(MpcError::ProtocolError(format!(
+                "Session {} is not a child of {}", child.path, self.path
+            )));
+        }
+        let position = self.open_children.iter()
+            .position(|id| *id == child.id)
+            .ok_or_else(|| MpcError::ProtocolError(format!("Child session {} is not open", child.path)))?;
+        if !child.open_children.is_empty() {
+            return Err(MpcError::ProtocolError(format!(
+                "Child session {} still has open sub-sessions", child.path
+            )));
+        }
+
+        self.open_children.remove(position);
+        self.absorb("child", &[child.id.0, child.transcript].concat());
+        Ok(())
+    }
+
+    /// 把一条协议消息记入转录
+    ///
+    /// # 参数
+    ///
+    /// * `label` - 消息标签
+    /// * `data` - 消息内容
+    pub fn absorb(&mut self, label: &str, data: &[u8]) {
+        self.transcript = hash_parts(&[b"absorb", &self.transcript, label.as_bytes(), data]);
+    }
+
+    /// 承诺上下文
+    ///
+    /// 用作 `CommittedMessage` 的上下文，使承诺只能在本会话中打开。
+    pub fn commitment_context(&self, label: &str) -> Vec<u8> {
+        hash_parts(&[b"commit", &self.id.0, label.as_bytes()]).to_vec()
+    }
+
+    /// 与本会话绑定的转录
+    ///
+    /// 以会话 ID 和当前转录摘要开始，因此绑定了所有祖先会话的上下文。
+    /// 子协议在其上写入自己的消息，再导出挑战值。
+    pub fn transcript(&self) -> Transcript {
+        let mut transcript = Transcript::new(SESSION_DOMAIN);
+        transcript.append_message(b"session-id", &self.id.0);
+        transcript.append_message(b"session-transcript", &self.transcript);
+        transcript
+    }
+
+    /// 派生挑战值
+    pub fn challenge(&self, label: &str) -> [u8; 32] {
+        self.transcript().challenge_bytes(label.as_bytes())
+    }
+
+    /// 派生域元素挑战值
+    pub fn challenge_field(&self, label: &str) -> u64 {
+        self.transcript().challenge_field(label.as_bytes())
+    }
+}
+
+/// 会话状态所在的命名空间
+const SESSION_NAMESPACE: &str = "sessions";
+
+/// 会话存储
+///
+/// 以会话 ID 为键保存 `ProtocolSession`，可与预处理池、审计日志共用同一个后端。
+#[derive(Debug, Clone)]
+pub struct SessionStore {
+    backend: Arc<dyn StorageBackend>,
+}
+
+impl SessionStore {
+    /// 基于给定后端创建会话存储
+    pub fn new(backend: Arc<dyn StorageBackend>) -> Self {
+        Self { backend }
+    }
+
+    /// 保存会话，已存在时覆盖
+    pub fn save(&self, session: &ProtocolSession) -> Result<()> {
+        store_value(self.backend.as_ref(), SESSION_NAMESPACE, session.id().as_bytes(), session)
+    }
+
+    /// 按会话 ID 读取会话
+    pub fn load(&self, id: SessionId) -> Result<Option<ProtocolSession>> {
+        load_value(self.backend.as_ref(), SESSION_NAMESPACE, id.as_bytes())
+    }
+
+    /// 删除会话
+    ///
+    /// # 返回值
+    /// 会话存在并被删除时返回 `true`
+    pub fn remove(&self, id: SessionId) -> Result<bool> {
+        self.backend.delete(SESSION_NAMESPACE, id.as_bytes())
+    }
+
+    /// 列出所有已保存的会话 ID
+    pub fn list(&self) -> Result<Vec<SessionId>> {
+        self.backend
+            .iterate(SESSION_NAMESPACE)?
+            .into_iter()
+            .map(|(key, _)| {
+                let bytes: [u8; 32] = key.as_slice().try_into().map_err(|_| {
+                    MpcError::StorageError(format!("Invalid session key length {}", key.len()))
+                })?;
+                Ok(SessionId(bytes))
+            })
+            .collect()
+    }
+}
+
+/// 带长度前缀地哈希多个部分
+fn hash_parts(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(SESSION_DOMAIN);
+    for part in parts {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(&hasher.finalize());
+    digest
+}
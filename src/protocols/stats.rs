@@ -0,0 +1,225 @@
+//! # 协议执行统计 (Protocol Execution Statistics)
+//!
+//! 为每次协议执行记录通信轮数、收发字节数、消耗的预处理材料数量以及
+//! 实际耗时，使用户可以在相同条件下比较不同的实现（例如 OLE、BFV
+//! 与可信第三方三种 Beaver 三元组生成方式）。
+//!
+//! 协议执行器返回 `ProtocolOutput<T>`，其中同时包含结果和 `ProtocolStats`；
+//! 统计信息可以通过 `ConnectionStats::record_protocol_stats` 汇总到
+//! 网络层的连接统计中，也可以通过 `ProtocolStats::record_metrics`
+//! 以 `protocol` 标签计入指标注册表。
+//!
+//! 在一个进程内模拟多方的协议用 `ProtocolStats::record_message` 逐条记录实际消息，
+//! `party_traffic` 给出每个参与方各自发送和接收的字节数；无法观察实际消息、
+//! 只能按模型估计通信量的实现会把 `estimated` 置为 true。
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use crate::utils::metrics::{MetricsRegistry, DEFAULT_LATENCY_BUCKETS};
+use crate::{MpcError, Result};
+
+/// 单次协议执行的统计信息
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProtocolStats {
+    /// 通信轮数
+    pub rounds: usize,
+    /// 发送的字节数
+    pub bytes_sent: u64,
+    /// 接收的字节数
+    pub bytes_received: u64,
+    /// 消耗的预处理材料数量（例如 Beaver 三元组）
+    pub preprocessing_consumed: usize,
+    /// 实际耗时
+    pub wall_time: Duration,
+    /// 每个参与方的收发字节数，键为参与方 ID，由 `record_message` 等方法填写
+    #[serde(default)]
+    pub party_traffic: BTreeMap<usize, PartyTraffic>,
+    /// 为 true 时轮数和字节数按模型估计，并非由实际消息计得
+    #[serde(default)]
+    pub estimated: bool,
+}
+
+/// 单个参与方的收发字节数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartyTraffic {
+    /// 该方发送的字节数
+    pub bytes_sent: u64,
+    /// 该方接收的字节数
+    pub bytes_received: u64,
+}
+
+impl ProtocolStats {
+    /// 创建空的统计信息
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录若干通信轮
+    pub fn record_rounds(&mut self, rounds: usize) {
+        self.rounds += rounds;
+    }
+
+    /// 记录发送的字节数
+    pub fn record_sent(&mut self, bytes: u64) {
+        self.bytes_sent += bytes;
+    }
+
+    /// 记录接收的字节数
+    pub fn record_received(&mut self, bytes: u64) {
+        self.bytes_received += bytes;
+    }
+
+    /// 记录一条从参与方 `from` 发往 `to` 的消息
+    ///
+    /// 字节数计入发送方的发送量和接收方的接收量，并累加到 `bytes_sent`、`bytes_received`；
+    /// 所有参与方都在本进程内时两者的总和相等，各方的收发量见 `party_traffic`。
+    pub fn record_message(&mut self, from: usize, to: usize, bytes: u64) {
+        self.record_sent(bytes);
+        self.record_received(bytes);
+        self.party_traffic.entry(from).or_default().bytes_sent += bytes;
+        self.party_traffic.entry(to).or_default().bytes_received += bytes;
+    }
+
+    /// 记录一次广播：`messages[j]` 由参与方 `parties[j]` 发给其余每一方
+    ///
+    /// 字节数取每条消息序列化后的长度，不计入轮数。
+    pub fn record_broadcast<T: Serialize>(&mut self, parties: &[usize], messages: &[T]) -> Result<()> {
+        for (&from, message) in parties.iter().zip(messages) {
+            let bytes = bincode::serialized_size(message)
+                .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+            for &to in parties.iter().filter(|&&to| to != from) {
+                self.record_message(from, to, bytes);
+            }
+        }
+        Ok(())
+    }
+
+    /// 记录参与方 `to` 从协议外部（例如提交输入的客户端）收到的消息
+    ///
+    /// 只计入接收方的接收量和 `bytes_received`，因此此时总接收量会大于总发送量。
+    pub fn record_input(&mut self, to: usize, bytes: u64) {
+        self.record_received(bytes);
+        self.party_traffic.entry(to).or_default().bytes_received += bytes;
+    }
+
+    /// 参与方 `party` 的收发字节数，没有记录时为零
+    pub fn party(&self, party: usize) -> PartyTraffic {
+        self.party_traffic.get(&party).copied().unwrap_or_default()
+    }
+
+    /// 标记本次统计的轮数和字节数是估计值
+    pub fn mark_estimated(&mut self) {
+        self.estimated = true;
+    }
+
+    /// 记录消耗的预处理材料数量
+    pub fn record_preprocessing(&mut self, count: usize) {
+        self.preprocessing_consumed += count;
+    }
+
+    /// 总通信量（发送 + 接收）
+    pub fn total_bytes(&self) -> u64 {
+        self.bytes_sent + self.bytes_received
+    }
+
+    /// 合并另一次执行的统计信息
+    ///
+    /// 轮数、字节数和预处理消耗直接累加；耗时同样累加，
+    /// 对应于顺序执行的子协议。任一方是估计值时合并结果也标记为估计值。
+    pub fn merge(&mut self, other: &ProtocolStats) {
+        self.rounds += other.rounds;
+        self.bytes_sent += other.bytes_sent;
+        self.bytes_received += other.bytes_received;
+        self.preprocessing_consumed += other.preprocessing_consumed;
+        self.wall_time += other.wall_time;
+        self.estimated |= other.estimated;
+        for (&party, traffic) in &other.party_traffic {
+            let entry = self.party_traffic.entry(party).or_default();
+            entry.bytes_sent += traffic.bytes_sent;
+            entry.bytes_received += traffic.bytes_received;
+        }
+    }
+
+    /// 把一次执行计入指标注册表
+    ///
+    /// 更新 `mpc_protocol_runs_total`、`mpc_protocol_rounds_total`、`mpc_bytes_sent_total`、
+    /// `mpc_bytes_received_total`、`mpc_preprocessing_consumed_total` 计数器和
+    /// `mpc_protocol_duration_seconds` 直方图，均带 `protocol` 标签。
+    pub fn record_metrics(&self, registry: &MetricsRegistry, protocol: &str) -> Result<()> {
+        let labels = [("protocol", protocol)];
+        registry.counter_with_labels("mpc_protocol_runs_total", "协议执行次数", &labels)?.inc();
+        registry.counter_with_labels("mpc_protocol_rounds_total", "协议通信轮数", &labels)?
+            .inc_by(self.rounds as u64);
+        registry.counter_with_labels("mpc_bytes_sent_total", "协议发送的字节数", &labels)?
+            .inc_by(self.bytes_sent);
+        registry.counter_with_labels("mpc_bytes_received_total", "协议接收的字节数", &labels)?
+            .inc_by(self.bytes_received);
+        registry.counter_with_labels("mpc_preprocessing_consumed_total", "协议消耗的预处理材料数量", &labels)?
+            .inc_by(self.preprocessing_consumed as u64);
+        registry
+            .histogram_with_labels("mpc_protocol_duration_seconds", "协议执行耗时（秒）", DEFAULT_LATENCY_BUCKETS, &labels)?
+            .observe_duration(self.wall_time);
+        Ok(())
+    }
+}
+
+/// 附带统计信息的协议结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtocolOutput<T> {
+    /// 协议结果
+    pub result: T,
+    /// 执行统计
+    pub stats: ProtocolStats,
+}
+
+impl<T> ProtocolOutput<T> {
+    /// 创建新的协议结果
+    pub fn new(result: T, stats: ProtocolStats) -> Self {
+        Self { result, stats }
+    }
+
+    /// 拆分为结果和统计信息
+    pub fn into_parts(self) -> (T, ProtocolStats) {
+        (self.result, self.stats)
+    }
+}
+
+/// 协议统计记录器
+///
+/// 在协议开始时创建，执行过程中记录通信信息，
+/// 结束时调用 `finish` 填入耗时并生成 `ProtocolOutput`。
+#[derive(Debug)]
+pub struct StatsRecorder {
+    /// 正在累积的统计信息
+    stats: ProtocolStats,
+    /// 开始时间
+    started_at: Instant,
+}
+
+impl Default for StatsRecorder {
+    fn default() -> Self {
+        Self::start()
+    }
+}
+
+impl StatsRecorder {
+    /// 开始记录
+    pub fn start() -> Self {
+        Self {
+            stats: ProtocolStats::new(),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// 获取可修改的统计信息
+    pub fn stats_mut(&mut self) -> &mut ProtocolStats {
+        &mut self.stats
+    }
+
+    /// 结束记录并与结果一起返回
+    pub fn finish<T>(mut self, result: T) -> ProtocolOutput<T> {
+        self.stats.wall_time = self.started_at.elapsed();
+        ProtocolOutput::new(result, self.stats)
+    }
+}
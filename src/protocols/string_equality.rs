@@ -0,0 +1,248 @@
+//! # 字符串相等比较与关键字搜索 (String Equality and Keyword Search)
+//!
+//! 比较秘密分享的字节串是否相等，并在秘密分享的字符串列表中搜索关键字，
+//! 每条记录得到一个秘密分享的匹配位，比较结果和关键字都不公开：
+//!
+//! 1. **哈希到域**: 字符串持有方在本地计算带域标签的 SHA-256，拆成 `STRING_HASH_LIMBS`
+//!    个域元素后分享（`SharedString::share`），任意长度的字符串都编码为固定数量的分享
+//! 2. **差值合并**: 对每一对字符串计算 [d] = Σ_k r_k·([a_k] - [b_k])，r_k 为公共随机系数，
+//!    本地线性运算，无需通信
+//! 3. **零测试**: 由费马小定理，[a == b] = 1 - [d]^(p-1)。p - 1 = 2^32·(2^32 - 1)，
+//!    先用加法链计算 [d]^(2^32 - 1)，再连续平方 32 次，共 68 次乘法、68 轮
+//!
+//! 所有比较同步执行，关键字搜索的轮数与记录数量无关，每条记录消耗 68 个三元组。
+//!
+//! ## 碰撞处理
+//!
+//! 不同的字符串被判为相等有两种情况：
+//!
+//! - **哈希碰撞**: 编码约 128 位，找到碰撞需要约 2^64 次哈希运算；
+//!   只用一个 64 位域元素时 2^32 次运算就能离线找到碰撞，因此使用两个域元素
+//! - **合并系数**: 编码不同时 [d] = 0 的概率为 1/p ≈ 2^-64。系数在输入分享之后才确定，
+//!   输入方无法针对系数构造输入
+//!
+//! 零测试本身是精确的。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+//! use mpc_api::protocols::string_equality::*;
+//! use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let records = ["alice", "bob", "alice"].iter()
+//!     .map(|name| SharedString::share(name.as_bytes(), 2, 3))
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//! let keyword = SharedString::share(b"alice", 2, 3)?;
+//!
+//! let protocol = StringEquality::new(3, 2)?;
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//! let output = protocol.keyword_search(&records, &keyword, &mut generator)?;
+//! let matches = output.result.iter()
+//!     .map(|bit| ShamirSecretSharing::reconstruct(&bit[..2], 2))
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//! assert_eq!(matches, vec![1, 0, 1]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::secret_sharing::bits::{add_shares, public_shares, scale_shares, sub_shares, Multiplier};
+use super::stats::{ProtocolOutput, StatsRecorder};
+use crate::beaver_triples::BeaverTripleGenerator;
+use crate::secret_sharing::{SecretSharing, ShamirSecretSharing, Share, FIELD_PRIME};
+use crate::utils::{hash_struct_with_domain, random_field_element};
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+
+/// 每个字符串编码为的域元素数量
+pub const STRING_HASH_LIMBS: usize = 2;
+
+/// 零测试消耗的乘法次数（也是轮数）
+pub const ZERO_TEST_MULTIPLICATIONS: usize = 68;
+
+/// 字符串哈希的域分隔标签
+const STRING_HASH_DOMAIN: &[u8] = b"mpc_api/string_equality/hash";
+
+/// 把字节串编码为 `STRING_HASH_LIMBS` 个域元素
+///
+/// 每个域元素取自 128 位哈希值模 p，与均匀分布的统计距离约为 2^-64。
+pub fn hash_string_to_field(bytes: &[u8]) -> Result<[u64; STRING_HASH_LIMBS]> {
+    let digest = hash_struct_with_domain(STRING_HASH_DOMAIN, bytes)?;
+    let mut limbs = [0u64; STRING_HASH_LIMBS];
+    for (limb, chunk) in limbs.iter_mut().zip(digest.chunks_exact(16)) {
+        let wide = u128::from_be_bytes(chunk.try_into().expect("16-byte chunk"));
+        *limb = (wide % FIELD_PRIME as u128) as u64;
+    }
+    Ok(limbs)
+}
+
+/// 秘密分享的字符串：每个哈希域元素在各参与方处的分享
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedString {
+    limbs: Vec<Vec<Share>>,
+}
+
+impl SharedString {
+    /// 由字符串持有方对字符串编码并分享
+    ///
+    /// # 参数
+    /// - `bytes`: 字符串
+    /// - `threshold`: 重构门限
+    /// - `party_count`: 参与方数量
+    pub fn share(bytes: &[u8], threshold: usize, party_count: usize) -> Result<Self> {
+        let limbs = hash_string_to_field(bytes)?
+            .iter()
+            .map(|limb| ShamirSecretSharing::share(limb, threshold, party_count))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { limbs })
+    }
+
+    /// 由已有的编码分享构造，例如各方收到的分享
+    pub fn from_limb_shares(limbs: Vec<Vec<Share>>) -> Result<Self> {
+        let party_count = limbs.first().map_or(0, Vec::len);
+        if limbs.len() != STRING_HASH_LIMBS || party_count == 0 || limbs.iter().any(|limb| limb.len() != party_count) {
+            return Err(MpcError::ProtocolError(format!(
+                "Shared string needs {} limbs with one share per party", STRING_HASH_LIMBS
+            )));
+        }
+        Ok(Self { limbs })
+    }
+
+    /// 编码的分享，`limbs()[k]` 是第 k 个域元素的分享
+    pub fn limbs(&self) -> &[Vec<Share>] {
+        &self.limbs
+    }
+
+    /// 参与方数量
+    pub fn party_count(&self) -> usize {
+        self.limbs[0].len()
+    }
+}
+
+/// 字符串相等比较协议
+#[derive(Debug, Clone, Copy)]
+pub struct StringEquality {
+    party_count: usize,
+    threshold: usize,
+}
+
+impl StringEquality {
+    /// 创建协议
+    ///
+    /// # 参数
+    /// - `party_count`: 参与方数量
+    /// - `threshold`: 重构门限
+    pub fn new(party_count: usize, threshold: usize) -> Result<Self> {
+        if threshold == 0 || threshold > party_count {
+            return Err(MpcError::InvalidThreshold);
+        }
+        Ok(Self { party_count, threshold })
+    }
+
+    /// 比较两个字符串
+    ///
+    /// # 返回值
+    /// 返回 [a == b] 的分享和执行统计
+    pub fn equal(
+        &self,
+        a: &SharedString,
+        b: &SharedString,
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Share>>> {
+        let (mut bits, stats) = self.equal_batch(&[(a, b)], generator)?.into_parts();
+        Ok(ProtocolOutput { result: bits.remove(0), stats })
+    }
+
+    /// 同步比较多对字符串，轮数与对数无关
+    ///
+    /// # 返回值
+    /// 返回每一对的 [a == b] 分享和执行统计
+    pub fn equal_batch(
+        &self,
+        pairs: &[(&SharedString, &SharedString)],
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+        if generator.get_party_count() != self.party_count || generator.get_threshold() != self.threshold {
+            return Err(MpcError::ProtocolError(
+                "Triple generator does not match the string equality parameters".to_string()
+            ));
+        }
+        for (a, b) in pairs {
+            self.check_string(a)?;
+            self.check_string(b)?;
+        }
+
+        let mut recorder = StatsRecorder::start();
+        let mut multiplier = Multiplier::new(generator, self.threshold, self.party_count);
+
+        // [d] = Σ_k r_k·([a_k] - [b_k])，系数对所有对相同
+        let coefficients: Vec<u64> = (0..STRING_HASH_LIMBS).map(|_| random_field_element()).collect();
+        let differences: Vec<Vec<Share>> = pairs.iter()
+            .map(|(a, b)| {
+                a.limbs.iter().zip(&b.limbs).zip(&coefficients)
+                    .fold(public_shares(self.party_count, 0), |sum, ((a, b), &r)| {
+                        add_shares(&sum, &scale_shares(&sub_shares(a, b), r))
+                    })
+            })
+            .collect();
+        let bits = self.is_zero(differences, &mut multiplier)?;
+
+        multiplier.record(recorder.stats_mut());
+        Ok(recorder.finish(bits))
+    }
+
+    /// 关键字搜索：对每条记录返回 [record == keyword] 的分享
+    ///
+    /// # 参数
+    /// - `records`: 秘密分享的字符串列表
+    /// - `keyword`: 秘密分享的关键字
+    /// - `generator`: 提供 Beaver 三元组的生成器
+    ///
+    /// # 返回值
+    /// 返回每条记录匹配位的分享和执行统计；匹配位之和即为匹配数量的分享
+    pub fn keyword_search(
+        &self,
+        records: &[SharedString],
+        keyword: &SharedString,
+        generator: &mut dyn BeaverTripleGenerator,
+    ) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+        let pairs: Vec<(&SharedString, &SharedString)> = records.iter().map(|record| (record, keyword)).collect();
+        self.equal_batch(&pairs, generator)
+    }
+
+    fn check_string(&self, string: &SharedString) -> Result<()> {
+        if string.party_count() != self.party_count {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} shares per limb, got {}", self.party_count, string.party_count()
+            )));
+        }
+        Ok(())
+    }
+
+    /// 零测试：[x == 0] = 1 - [x]^(p-1)
+    fn is_zero(&self, values: Vec<Vec<Share>>, multiplier: &mut Multiplier) -> Result<Vec<Vec<Share>>> {
+        if values.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // 加法链：x^(2^(2k) - 1) = (x^(2^k - 1))^(2^k)·x^(2^k - 1)，直到 x^(2^32 - 1)
+        let mut power = values;
+        let mut ones = 1;
+        while ones < 32 {
+            let mut shifted = power.clone();
+            for _ in 0..ones {
+                shifted = multiplier.multiply(&shifted, &shifted)?;
+            }
+            power = multiplier.multiply(&shifted, &power)?;
+            ones *= 2;
+        }
+        // 再平方 32 次得到 x^((2^32 - 1)·2^32) = x^(p-1)
+        for _ in 0..32 {
+            power = multiplier.multiply(&power, &power)?;
+        }
+
+        let one = public_shares(self.party_count, 1);
+        Ok(power.iter().map(|power| sub_shares(&one, power)).collect())
+    }
+}
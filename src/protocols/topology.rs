@@ -0,0 +1,229 @@
+//! # 参与方角色与拓扑 (Party Roles and Topology)
+//!
+//! 许多 MPC 协议中存在承担特殊职责的参与方，例如：
+//! - **King**: 在 DN 乘法等协议中负责汇总并广播公开值，或在加法分享中负责添加常数项
+//! - **Aggregator**: 在安全聚合中收集所有参与方的掩码输入
+//! - **Dealer**: 在可信设置中生成并分发预处理材料
+//!
+//! 本模块提供 `Topology` 抽象来显式描述这些角色，协议执行器通过它来路由消息，
+//! 而不是在每个协议中硬编码某个参与方（例如 party 0 或 x = 1）为特殊方。
+//! 加法分享用 King 添加常数项；网络会话（`network::session::MpcSession::set_topology`）
+//! 由 Dealer 分发三元组、由 Aggregator 接收输出。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::protocols::topology::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let topology = Topology::new(vec![0, 1, 2])?
+//!     .with_role(ProtocolRole::King, 2)?
+//!     .with_role(ProtocolRole::Aggregator, 1)?;
+//!
+//! assert!(topology.is_king(2));
+//! assert_eq!(topology.route_to_role(0, ProtocolRole::Aggregator)?, vec![1]);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 参与方角色
+///
+/// 描述参与方在协议中承担的特殊职责。未被分配特殊角色的参与方
+/// 均为普通计算方。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProtocolRole {
+    /// 可信设置中的分发者
+    Dealer,
+    /// 负责汇总公开值或添加常数项的主导方
+    King,
+    /// 安全聚合中的聚合方
+    Aggregator,
+}
+
+/// 协议拓扑
+///
+/// 记录参与方集合以及各角色的承担者。每个角色最多由一个参与方承担，
+/// 同一参与方可以同时承担多个角色。未显式指定 King 时，
+/// 默认由 ID 最小的参与方承担。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Topology {
+    /// 参与方 ID 列表（已排序、去重）
+    parties: Vec<usize>,
+    /// 角色到参与方的映射
+    roles: HashMap<ProtocolRole, usize>,
+}
+
+impl Topology {
+    /// 创建新的拓扑
+    ///
+    /// # 参数
+    ///
+    /// * `parties` - 参与方 ID 列表
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回拓扑实例；参与方列表为空或包含重复 ID 时返回错误
+    pub fn new(mut parties: Vec<usize>) -> Result<Self> {
+        if parties.is_empty() {
+            return Err(MpcError::ProtocolError("Topology requires at least one party".to_string()));
+        }
+
+        let original_len = parties.len();
+        parties.sort_unstable();
+        parties.dedup();
+        if parties.len() != original_len {
+            return Err(MpcError::ProtocolError("Duplicate party ID in topology".to_string()));
+        }
+
+        Ok(Self {
+            parties,
+            roles: HashMap::new(),
+        })
+    }
+
+    /// 为 `0..party_count` 的参与方创建拓扑
+    pub fn with_party_count(party_count: usize) -> Result<Self> {
+        Self::new((0..party_count).collect())
+    }
+
+    /// 为指定参与方分配角色
+    ///
+    /// # 参数
+    ///
+    /// * `role` - 要分配的角色
+    /// * `party_id` - 承担该角色的参与方
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回更新后的拓扑；参与方不在拓扑中时返回错误
+    pub fn with_role(mut self, role: ProtocolRole, party_id: usize) -> Result<Self> {
+        self.assign_role(role, party_id)?;
+        Ok(self)
+    }
+
+    /// 为指定参与方分配角色（原地修改）
+    ///
+    /// 如果该角色已被其他参与方承担，将被重新分配。
+    pub fn assign_role(&mut self, role: ProtocolRole, party_id: usize) -> Result<()> {
+        if !self.contains(party_id) {
+            return Err(MpcError::ProtocolError(format!(
+                "Party {} is not part of the topology", party_id
+            )));
+        }
+        self.roles.insert(role, party_id);
+        Ok(())
+    }
+
+    /// 获取所有参与方 ID（升序）
+    pub fn parties(&self) -> &[usize] {
+        &self.parties
+    }
+
+    /// 获取参与方数量
+    pub fn party_count(&self) -> usize {
+        self.parties.len()
+    }
+
+    /// 检查参与方是否属于该拓扑
+    pub fn contains(&self, party_id: usize) -> bool {
+        self.parties.binary_search(&party_id).is_ok()
+    }
+
+    /// 获取显式分配了某角色的参与方
+    pub fn role_holder(&self, role: ProtocolRole) -> Option<usize> {
+        self.roles.get(&role).copied()
+    }
+
+    /// 检查参与方是否承担某角色
+    ///
+    /// King 角色未显式分配时按默认规则判断。
+    pub fn has_role(&self, party_id: usize, role: ProtocolRole) -> bool {
+        match role {
+            ProtocolRole::King => self.king() == party_id,
+            _ => self.role_holder(role) == Some(party_id),
+        }
+    }
+
+    /// 获取 King 参与方
+    ///
+    /// 未显式指定时返回 ID 最小的参与方。
+    pub fn king(&self) -> usize {
+        self.role_holder(ProtocolRole::King).unwrap_or(self.parties[0])
+    }
+
+    /// 检查参与方是否为 King
+    pub fn is_king(&self, party_id: usize) -> bool {
+        self.king() == party_id
+    }
+
+    /// 获取分发者（如果已分配）
+    pub fn dealer(&self) -> Option<usize> {
+        self.role_holder(ProtocolRole::Dealer)
+    }
+
+    /// 获取聚合方（如果已分配）
+    pub fn aggregator(&self) -> Option<usize> {
+        self.role_holder(ProtocolRole::Aggregator)
+    }
+
+    /// 获取除指定参与方外的其他参与方
+    pub fn peers_of(&self, party_id: usize) -> Vec<usize> {
+        self.parties.iter().copied().filter(|&p| p != party_id).collect()
+    }
+
+    /// 计算发往某角色的消息的接收方
+    ///
+    /// 如果发送方本身承担该角色，则无需发送，返回空列表。
+    ///
+    /// # 参数
+    ///
+    /// * `from` - 发送方 ID
+    /// * `role` - 目标角色
+    ///
+    /// # 返回值
+    ///
+    /// 返回接收方列表；角色未分配或发送方不在拓扑中时返回错误
+    pub fn route_to_role(&self, from: usize, role: ProtocolRole) -> Result<Vec<usize>> {
+        if !self.contains(from) {
+            return Err(MpcError::ProtocolError(format!(
+                "Party {} is not part of the topology", from
+            )));
+        }
+
+        let target = self.resolve_role(role)?;
+
+        if target == from {
+            Ok(Vec::new())
+        } else {
+            Ok(vec![target])
+        }
+    }
+
+    /// 计算某角色承担者广播结果时的接收方
+    ///
+    /// # 参数
+    ///
+    /// * `role` - 广播方所承担的角色
+    ///
+    /// # 返回值
+    ///
+    /// 返回除角色承担者外的所有参与方
+    pub fn broadcast_from_role(&self, role: ProtocolRole) -> Result<Vec<usize>> {
+        let source = self.resolve_role(role)?;
+        Ok(self.peers_of(source))
+    }
+
+    /// 解析角色的承担者，King 使用默认规则
+    fn resolve_role(&self, role: ProtocolRole) -> Result<usize> {
+        match role {
+            ProtocolRole::King => Ok(self.king()),
+            _ => self.role_holder(role).ok_or_else(|| {
+                MpcError::ProtocolError(format!("Role {:?} is not assigned", role))
+            }),
+        }
+    }
+}
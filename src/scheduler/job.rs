@@ -0,0 +1,253 @@
+//! 作业定义：任务、优先级、依赖、重试策略与持久化的作业记录
+
+use crate::beaver_triples::TripleGeneratorKind;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 作业 ID（由存储分配，单调递增）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct JobId(pub u64);
+
+impl fmt::Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "job-{}", self.0)
+    }
+}
+
+/// 作业优先级，优先级高的作业先执行，同优先级按提交顺序执行
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub enum JobPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Critical,
+}
+
+/// 作业要执行的 MPC 任务
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MpcTask {
+    /// 生成 Beaver 三元组，成功后计入三元组池
+    GenerateTriples {
+        /// 使用的生成器
+        generator: TripleGeneratorKind,
+        /// 生成数量
+        count: usize,
+        /// 参与方数量
+        party_count: usize,
+        /// 重构门限
+        threshold: usize,
+    },
+    /// 由调用方注册的处理器执行的自定义任务
+    Custom {
+        /// 处理器名称
+        name: String,
+        /// 任务参数
+        payload: Vec<u8>,
+    },
+}
+
+impl MpcTask {
+    /// 内置三元组生成任务的处理器名称
+    pub const GENERATE_TRIPLES: &'static str = "generate_triples";
+
+    /// 执行该任务的处理器名称
+    pub fn handler_name(&self) -> &str {
+        match self {
+            MpcTask::GenerateTriples { .. } => Self::GENERATE_TRIPLES,
+            MpcTask::Custom { name, .. } => name,
+        }
+    }
+}
+
+/// 作业依赖
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobDependency {
+    /// 指定作业必须已成功完成
+    Job(JobId),
+    /// 三元组池中至少有指定数量的三元组；作业开始时从池中取走这些三元组
+    Triples(u64),
+}
+
+/// 失败重试策略（指数退避）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// 最多执行次数（包括第一次）
+    pub max_attempts: u32,
+    /// 第一次重试前的等待时间
+    pub initial_backoff: Duration,
+    /// 每次重试后等待时间的倍数
+    pub backoff_multiplier: u32,
+}
+
+impl RetryPolicy {
+    /// 创建重试策略，等待时间每次翻倍
+    pub fn new(max_attempts: u32, initial_backoff: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff,
+            backoff_multiplier: 2,
+        }
+    }
+
+    /// 不重试
+    pub fn none() -> Self {
+        Self::new(1, Duration::ZERO)
+    }
+
+    /// 设置等待时间的倍数
+    pub fn with_multiplier(mut self, multiplier: u32) -> Self {
+        self.backoff_multiplier = multiplier.max(1);
+        self
+    }
+
+    /// 第 `attempt` 次执行失败后到下一次执行前的等待时间
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self.backoff_multiplier.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_secs(1))
+    }
+}
+
+/// 作业提交请求
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobRequest {
+    /// 要执行的任务
+    pub task: MpcTask,
+    /// 优先级
+    #[serde(default)]
+    pub priority: JobPriority,
+    /// 依赖
+    #[serde(default)]
+    pub dependencies: Vec<JobDependency>,
+    /// 重试策略
+    #[serde(default)]
+    pub retry: RetryPolicy,
+}
+
+impl JobRequest {
+    /// 使用默认优先级和重试策略创建请求
+    pub fn new(task: MpcTask) -> Self {
+        Self {
+            task,
+            priority: JobPriority::default(),
+            dependencies: Vec::new(),
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// 设置优先级
+    pub fn with_priority(mut self, priority: JobPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// 依赖另一个作业成功完成
+    pub fn depends_on(mut self, job: JobId) -> Self {
+        self.dependencies.push(JobDependency::Job(job));
+        self
+    }
+
+    /// 需要三元组池中至少有 `count` 个三元组
+    pub fn requires_triples(mut self, count: u64) -> Self {
+        self.dependencies.push(JobDependency::Triples(count));
+        self
+    }
+
+    /// 设置重试策略
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+}
+
+/// 作业状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum JobState {
+    /// 等待执行（包括等待重试）
+    Pending,
+    /// 已被工作线程领取
+    Running,
+    /// 成功完成
+    Succeeded,
+    /// 重试次数用尽或依赖失败
+    Failed,
+    /// 被取消
+    Cancelled,
+}
+
+impl JobState {
+    /// 是否为终止状态
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, JobState::Succeeded | JobState::Failed | JobState::Cancelled)
+    }
+}
+
+/// 持久化的作业记录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Job {
+    /// 作业 ID
+    pub id: JobId,
+    /// 提交请求
+    pub request: JobRequest,
+    /// 当前状态
+    pub state: JobState,
+    /// 已执行次数
+    pub attempts: u32,
+    /// 最早可执行时间（Unix 毫秒），用于重试退避
+    pub not_before_ms: u64,
+    /// 最近一次失败的原因
+    pub last_error: Option<String>,
+    /// 成功时处理器的输出
+    pub output: Option<Vec<u8>>,
+    /// 提交时间（Unix 毫秒）
+    pub created_at_ms: u64,
+}
+
+impl Job {
+    /// 由提交请求创建待执行的作业
+    pub fn new(id: JobId, request: JobRequest) -> Self {
+        Self {
+            id,
+            request,
+            state: JobState::Pending,
+            attempts: 0,
+            not_before_ms: 0,
+            last_error: None,
+            output: None,
+            created_at_ms: now_millis(),
+        }
+    }
+
+    /// 作业开始时需要从三元组池取走的三元组数量
+    pub fn required_triples(&self) -> u64 {
+        self.request.dependencies.iter()
+            .map(|dependency| match dependency {
+                JobDependency::Triples(count) => *count,
+                JobDependency::Job(_) => 0,
+            })
+            .sum()
+    }
+
+    /// 依赖的作业
+    pub fn job_dependencies(&self) -> impl Iterator<Item = JobId> + '_ {
+        self.request.dependencies.iter().filter_map(|dependency| match dependency {
+            JobDependency::Job(id) => Some(*id),
+            JobDependency::Triples(_) => None,
+        })
+    }
+}
+
+/// 当前 Unix 时间（毫秒）
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
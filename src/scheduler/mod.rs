@@ -0,0 +1,245 @@
+//! # 作业调度模块 (Job Scheduler)
+//!
+//! 把 MPC 库作为长期运行的服务组件使用时，需要排队执行大量任务：预处理三元组、
+//! 在三元组足够后运行在线阶段、失败后按策略重试。本模块提供持久化的作业队列：
+//!
+//! - **持久化**: 作业记录保存在 sled 数据库中，进程重启后未完成的作业继续执行
+//! - **优先级**: 优先级高的作业先执行，同优先级按提交顺序执行
+//! - **依赖**: 作业可以依赖其他作业成功完成，或依赖三元组池中的三元组数量
+//!   （例如"需要 500 个三元组"），开始执行时从池中取走所需的三元组
+//! - **重试**: 失败的作业按指数退避重试，次数用尽后标记为失败；
+//!   依赖失败的作业同样标记为失败
+//! - **工作线程池**: `WorkerPool` 按注册的处理器驱动协议执行，
+//!   内置的三元组生成处理器把生成结果计入三元组池
+//!
+//! 队列可以通过 `HttpServer::register_scheduler` 暴露为 `/api/v1/jobs` 接口。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::TripleGeneratorKind;
+//! use mpc_api::scheduler::*;
+//! use std::sync::Arc;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let scheduler = Arc::new(JobScheduler::new(JobStore::temporary()?)?);
+//!
+//! let preprocessing = scheduler.submit(JobRequest::new(MpcTask::GenerateTriples {
+//!     generator: TripleGeneratorKind::TrustedParty,
+//!     count: 10,
+//!     party_count: 3,
+//!     threshold: 2,
+//! }))?;
+//! let online = scheduler.submit(
+//!     JobRequest::new(MpcTask::Custom { name: "online".to_string(), payload: Vec::new() })
+//!         .with_priority(JobPriority::High)
+//!         .depends_on(preprocessing)
+//!         .requires_triples(10),
+//! )?;
+//!
+//! let pool = WorkerPool::new(Arc::clone(&scheduler))
+//!     .with_handler("online", Arc::new(|_job: &Job| Ok(b"done".to_vec())));
+//! assert_eq!(pool.run_until_idle()?, 2);
+//!
+//! assert_eq!(scheduler.job(online)?.unwrap().state, JobState::Succeeded);
+//! assert_eq!(scheduler.available_triples()?, 0);
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod job;
+pub mod store;
+pub mod worker;
+
+pub use job::*;
+pub use store::*;
+pub use worker::*;
+
+use crate::{MpcError, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// 三元组池在存储中的资源名
+pub const TRIPLE_POOL: &str = "beaver_triples";
+
+/// 持久化的作业调度器
+#[derive(Debug)]
+pub struct JobScheduler {
+    store: JobStore,
+    /// 保证领取作业和取走三元组是原子的
+    claim_lock: Mutex<()>,
+}
+
+impl JobScheduler {
+    /// 基于已有存储创建调度器
+    ///
+    /// 上次运行时处于 `Running` 状态的作业（进程在执行中退出）会被放回队列，
+    /// 并归还它们取走的三元组。
+    pub fn new(store: JobStore) -> Result<Self> {
+        for mut job in store.jobs()? {
+            if job.state == JobState::Running {
+                store.add_resource(TRIPLE_POOL, job.required_triples())?;
+                job.state = JobState::Pending;
+                store.put(&job)?;
+            }
+        }
+        Ok(Self { store, claim_lock: Mutex::new(()) })
+    }
+
+    /// 打开指定目录下的持久化调度器
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::new(JobStore::open(path)?)
+    }
+
+    /// 提交作业
+    ///
+    /// # 返回值
+    /// 返回新作业的 ID；依赖的作业不存在时返回错误
+    pub fn submit(&self, request: JobRequest) -> Result<JobId> {
+        for dependency in &request.dependencies {
+            if let JobDependency::Job(id) = dependency {
+                if self.store.get(*id)?.is_none() {
+                    return Err(MpcError::ProtocolError(format!("Unknown dependency {}", id)));
+                }
+            }
+        }
+
+        let job = Job::new(self.store.next_id()?, request);
+        self.store.put(&job)?;
+        Ok(job.id)
+    }
+
+    /// 查询作业
+    pub fn job(&self, id: JobId) -> Result<Option<Job>> {
+        self.store.get(id)
+    }
+
+    /// 按提交顺序列出全部作业
+    pub fn jobs(&self) -> Result<Vec<Job>> {
+        self.store.jobs()
+    }
+
+    /// 三元组池中可用的三元组数量
+    pub fn available_triples(&self) -> Result<u64> {
+        self.store.resource(TRIPLE_POOL)
+    }
+
+    /// 向三元组池加入外部生成的三元组
+    pub fn add_triples(&self, count: u64) -> Result<u64> {
+        self.store.add_resource(TRIPLE_POOL, count)
+    }
+
+    /// 取消尚未开始的作业
+    ///
+    /// # 返回值
+    /// 作业处于 `Pending` 状态并被取消时返回 `true`
+    pub fn cancel(&self, id: JobId) -> Result<bool> {
+        let _guard = self.lock();
+        match self.store.get(id)? {
+            Some(mut job) if job.state == JobState::Pending => {
+                job.state = JobState::Cancelled;
+                self.store.put(&job)?;
+                Ok(true)
+            }
+            Some(_) => Ok(false),
+            None => Err(MpcError::ProtocolError(format!("Unknown job {}", id))),
+        }
+    }
+
+    /// 领取下一个可执行的作业
+    ///
+    /// 可执行的作业满足：处于 `Pending` 状态、退避时间已过、依赖的作业均已成功、
+    /// 三元组池中有足够的三元组。领取时取走所需的三元组并把作业标记为 `Running`。
+    /// 依赖的作业已失败或被取消时，该作业被标记为失败。
+    pub fn claim_next(&self) -> Result<Option<Job>> {
+        let _guard = self.lock();
+        let now = job::now_millis();
+        let jobs = self.store.jobs()?;
+        let states: HashMap<JobId, JobState> = jobs.iter().map(|job| (job.id, job.state)).collect();
+
+        let mut candidates = Vec::new();
+        for mut job in jobs {
+            if job.state != JobState::Pending {
+                continue;
+            }
+            let blocked_by = job.job_dependencies()
+                .find(|id| matches!(states.get(id), Some(JobState::Failed | JobState::Cancelled)));
+            if let Some(dependency) = blocked_by {
+                job.state = JobState::Failed;
+                job.last_error = Some(format!("Dependency {} did not succeed", dependency));
+                self.store.put(&job)?;
+                continue;
+            }
+            let ready = job.not_before_ms <= now
+                && job.job_dependencies().all(|id| states.get(&id) == Some(&JobState::Succeeded));
+            if ready {
+                candidates.push(job);
+            }
+        }
+
+        // 优先级高的在前，同优先级按 ID（提交顺序）
+        candidates.sort_by(|a, b| b.request.priority.cmp(&a.request.priority).then(a.id.cmp(&b.id)));
+        for mut job in candidates {
+            if self.store.take_resource(TRIPLE_POOL, job.required_triples())? {
+                job.state = JobState::Running;
+                job.attempts += 1;
+                self.store.put(&job)?;
+                return Ok(Some(job));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 记录作业成功
+    ///
+    /// 三元组生成作业成功后，生成的数量计入三元组池。
+    pub fn complete(&self, id: JobId, output: Vec<u8>) -> Result<()> {
+        let mut job = self.running_job(id)?;
+        if let MpcTask::GenerateTriples { count, .. } = job.request.task {
+            self.store.add_resource(TRIPLE_POOL, count as u64)?;
+        }
+        job.state = JobState::Succeeded;
+        job.output = Some(output);
+        job.last_error = None;
+        self.store.put(&job)
+    }
+
+    /// 记录作业失败
+    ///
+    /// 作业取走的三元组被归还。还有重试次数时作业回到队列并按退避时间延后，
+    /// 否则标记为失败。
+    ///
+    /// # 返回值
+    /// 返回作业的新状态
+    pub fn fail(&self, id: JobId, error: &str) -> Result<JobState> {
+        let mut job = self.running_job(id)?;
+        self.store.add_resource(TRIPLE_POOL, job.required_triples())?;
+        job.last_error = Some(error.to_string());
+        if job.attempts < job.request.retry.max_attempts {
+            job.state = JobState::Pending;
+            job.not_before_ms = job::now_millis() + job.request.retry.backoff(job.attempts).as_millis() as u64;
+        } else {
+            job.state = JobState::Failed;
+        }
+        self.store.put(&job)?;
+        Ok(job.state)
+    }
+
+    /// 把缓冲的写入刷到磁盘
+    pub fn flush(&self) -> Result<()> {
+        self.store.flush()
+    }
+
+    fn running_job(&self, id: JobId) -> Result<Job> {
+        match self.store.get(id)? {
+            Some(job) if job.state == JobState::Running => Ok(job),
+            Some(job) => Err(MpcError::ProtocolError(format!("{} is {:?}, not running", id, job.state))),
+            None => Err(MpcError::ProtocolError(format!("Unknown job {}", id))),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ()> {
+        self.claim_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
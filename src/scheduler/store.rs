@@ -0,0 +1,113 @@
+//! 基于 sled 的作业存储
+//!
+//! 作业以 bincode 编码保存在 `jobs` 树中（键为大端序的作业 ID，因此按提交顺序排列），
+//! 三元组池等计数资源保存在 `resources` 树中。
+
+use super::job::{Job, JobId};
+use crate::{MpcError, Result};
+use std::path::Path;
+
+/// 持久化的作业存储
+#[derive(Debug, Clone)]
+pub struct JobStore {
+    db: sled::Db,
+    jobs: sled::Tree,
+    resources: sled::Tree,
+}
+
+impl JobStore {
+    /// 打开（或创建）指定目录下的存储
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_db(sled::open(path).map_err(storage_error)?)
+    }
+
+    /// 创建进程退出后即删除的临时存储
+    pub fn temporary() -> Result<Self> {
+        Self::from_db(sled::Config::new().temporary(true).open().map_err(storage_error)?)
+    }
+
+    fn from_db(db: sled::Db) -> Result<Self> {
+        let jobs = db.open_tree("jobs").map_err(storage_error)?;
+        let resources = db.open_tree("resources").map_err(storage_error)?;
+        Ok(Self { db, jobs, resources })
+    }
+
+    /// 分配新的作业 ID
+    pub fn next_id(&self) -> Result<JobId> {
+        self.db.generate_id().map(JobId).map_err(storage_error)
+    }
+
+    /// 写入作业记录
+    pub fn put(&self, job: &Job) -> Result<()> {
+        let bytes = bincode::serialize(job)
+            .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+        self.jobs.insert(job.id.0.to_be_bytes(), bytes).map_err(storage_error)?;
+        Ok(())
+    }
+
+    /// 读取作业记录
+    pub fn get(&self, id: JobId) -> Result<Option<Job>> {
+        self.jobs.get(id.0.to_be_bytes()).map_err(storage_error)?
+            .map(|bytes| decode_job(&bytes))
+            .transpose()
+    }
+
+    /// 按提交顺序列出全部作业
+    pub fn jobs(&self) -> Result<Vec<Job>> {
+        self.jobs.iter()
+            .map(|entry| decode_job(&entry.map_err(storage_error)?.1))
+            .collect()
+    }
+
+    /// 读取计数资源
+    pub fn resource(&self, name: &str) -> Result<u64> {
+        Ok(self.resources.get(name).map_err(storage_error)?
+            .map(|bytes| decode_counter(&bytes))
+            .unwrap_or(0))
+    }
+
+    /// 增加计数资源，返回增加后的值
+    pub fn add_resource(&self, name: &str, amount: u64) -> Result<u64> {
+        let updated = self.resources
+            .update_and_fetch(name, |old| {
+                let current = old.map(decode_counter).unwrap_or(0);
+                Some(current.saturating_add(amount).to_be_bytes().to_vec())
+            })
+            .map_err(storage_error)?;
+        Ok(updated.map(|bytes| decode_counter(&bytes)).unwrap_or(0))
+    }
+
+    /// 原子地取走计数资源；数量不足时不做修改并返回 `false`
+    pub fn take_resource(&self, name: &str, amount: u64) -> Result<bool> {
+        let mut taken = false;
+        self.resources
+            .fetch_and_update(name, |old| {
+                let current = old.map(decode_counter).unwrap_or(0);
+                taken = current >= amount;
+                let remaining = if taken { current - amount } else { current };
+                Some(remaining.to_be_bytes().to_vec())
+            })
+            .map_err(storage_error)?;
+        Ok(taken)
+    }
+
+    /// 把缓冲的写入刷到磁盘
+    pub fn flush(&self) -> Result<()> {
+        self.db.flush().map(|_| ()).map_err(storage_error)
+    }
+}
+
+fn decode_job(bytes: &[u8]) -> Result<Job> {
+    bincode::deserialize(bytes).map_err(|e| MpcError::SerializationError(e.to_string()))
+}
+
+fn decode_counter(bytes: &[u8]) -> u64 {
+    let mut buffer = [0u8; 8];
+    let len = bytes.len().min(8);
+    buffer[8 - len..].copy_from_slice(&bytes[bytes.len() - len..]);
+    u64::from_be_bytes(buffer)
+}
+
+fn storage_error(error: sled::Error) -> MpcError {
+    MpcError::StorageError(error.to_string())
+}
@@ -0,0 +1,252 @@
+//! 作业处理器与工作线程池
+
+use super::job::{Job, JobId, JobState, MpcTask};
+use super::JobScheduler;
+use crate::utils::concurrency::CancellationToken;
+use crate::utils::metrics::{MetricsRegistry, DEFAULT_LATENCY_BUCKETS};
+use crate::{MpcError, Result};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 空闲工作线程查询队列的默认间隔
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 作业处理器
+///
+/// 处理器在阻塞线程中执行，返回的字节作为作业输出保存；返回错误时按重试策略处理。
+pub trait JobHandler: Send + Sync {
+    /// 执行作业
+    fn execute(&self, job: &Job) -> Result<Vec<u8>>;
+
+    /// 执行作业，并在令牌取消时尽快返回
+    ///
+    /// 默认实现忽略令牌，直接调用 `execute`；耗时较长的处理器应在各阶段之间检查令牌。
+    fn execute_with_cancellation(&self, job: &Job, token: &CancellationToken) -> Result<Vec<u8>> {
+        token.check()?;
+        self.execute(job)
+    }
+}
+
+impl<F> JobHandler for F
+where
+    F: Fn(&Job) -> Result<Vec<u8>> + Send + Sync,
+{
+    fn execute(&self, job: &Job) -> Result<Vec<u8>> {
+        self(job)
+    }
+}
+
+/// 三元组生成处理器每次生成的三元组数量，每批之间检查取消令牌
+pub const TRIPLE_GENERATION_CHUNK: usize = 256;
+
+/// 内置的三元组生成处理器，输出为 bincode 编码的三元组列表
+///
+/// 每批生成的数量和耗时计入全局指标注册表的 `mpc_triples_generated_total` 计数器和
+/// `mpc_triple_batch_duration_seconds` 直方图，带 `generator` 标签。
+#[derive(Debug, Clone, Copy)]
+pub struct TripleGenerationHandler {
+    party_id: usize,
+}
+
+impl TripleGenerationHandler {
+    /// 以指定参与方身份生成三元组
+    pub fn new(party_id: usize) -> Self {
+        Self { party_id }
+    }
+}
+
+impl JobHandler for TripleGenerationHandler {
+    fn execute(&self, job: &Job) -> Result<Vec<u8>> {
+        self.execute_with_cancellation(job, &CancellationToken::new())
+    }
+
+    fn execute_with_cancellation(&self, job: &Job, token: &CancellationToken) -> Result<Vec<u8>> {
+        match &job.request.task {
+            MpcTask::GenerateTriples { generator, count, party_count, threshold } => {
+                let registry = MetricsRegistry::global();
+                let labels = [("generator", generator.name())];
+                let generated = registry.counter_with_labels("mpc_triples_generated_total", "生成的 Beaver 三元组数量", &labels)?;
+                let batch_duration = registry.histogram_with_labels(
+                    "mpc_triple_batch_duration_seconds", "每批三元组的生成耗时（秒）", DEFAULT_LATENCY_BUCKETS, &labels,
+                )?;
+
+                let mut generator = generator.build(*party_count, *threshold, self.party_id)?;
+                let mut triples = Vec::with_capacity(*count);
+                while triples.len() < *count {
+                    token.check()?;
+                    let started_at = Instant::now();
+                    let batch = generator.generate_batch(TRIPLE_GENERATION_CHUNK.min(*count - triples.len()))?;
+                    batch_duration.observe_duration(started_at.elapsed());
+                    generated.inc_by(batch.len() as u64);
+                    triples.extend(batch);
+                }
+                bincode::serialize(&triples).map_err(|e| MpcError::SerializationError(e.to_string()))
+            }
+            task => Err(MpcError::ProtocolError(format!(
+                "Triple generation handler cannot run task {}", task.handler_name()
+            ))),
+        }
+    }
+}
+
+/// 驱动作业执行的工作线程池
+pub struct WorkerPool {
+    scheduler: Arc<JobScheduler>,
+    handlers: HashMap<String, Arc<dyn JobHandler>>,
+    workers: usize,
+    poll_interval: Duration,
+    token: CancellationToken,
+}
+
+impl WorkerPool {
+    /// 创建工作线程池，默认注册三元组生成处理器并使用一个工作线程
+    pub fn new(scheduler: Arc<JobScheduler>) -> Self {
+        let mut handlers: HashMap<String, Arc<dyn JobHandler>> = HashMap::new();
+        handlers.insert(MpcTask::GENERATE_TRIPLES.to_string(), Arc::new(TripleGenerationHandler::new(0)));
+        Self {
+            scheduler,
+            handlers,
+            workers: 1,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            token: CancellationToken::new(),
+        }
+    }
+
+    /// 注册（或替换）处理器
+    pub fn with_handler(mut self, name: impl Into<String>, handler: Arc<dyn JobHandler>) -> Self {
+        self.handlers.insert(name.into(), handler);
+        self
+    }
+
+    /// 设置工作线程数量
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// 设置空闲时查询队列的间隔
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// 把正在执行的作业绑定到父取消令牌
+    ///
+    /// 令牌取消后，正在执行的作业以取消错误结束（按重试策略处理），
+    /// 之后 `run_once` 不再领取新作业。
+    pub fn with_cancellation(mut self, parent: &CancellationToken) -> Self {
+        self.token = parent.child_token();
+        self
+    }
+
+    /// 领取并执行一个作业
+    ///
+    /// # 返回值
+    /// 没有可执行的作业时返回 `None`，否则返回作业 ID 和执行后的状态
+    pub fn run_once(&self) -> Result<Option<(JobId, JobState)>> {
+        if self.token.is_cancelled() {
+            return Ok(None);
+        }
+        let job = match self.scheduler.claim_next()? {
+            Some(job) => job,
+            None => return Ok(None),
+        };
+
+        let outcome = match self.handlers.get(job.request.task.handler_name()) {
+            Some(handler) => handler.execute_with_cancellation(&job, &self.token),
+            None => Err(MpcError::ProtocolError(format!(
+                "No handler registered for task {}", job.request.task.handler_name()
+            ))),
+        };
+
+        let state = match outcome {
+            Ok(output) => {
+                self.scheduler.complete(job.id, output)?;
+                JobState::Succeeded
+            }
+            Err(e) => self.scheduler.fail(job.id, &e.to_string())?,
+        };
+        Ok(Some((job.id, state)))
+    }
+
+    /// 在当前线程中执行作业，直到没有可执行的作业
+    ///
+    /// # 返回值
+    /// 返回执行的次数（包括失败的执行）
+    pub fn run_until_idle(&self) -> Result<usize> {
+        let mut executed = 0;
+        while self.run_once()?.is_some() {
+            executed += 1;
+        }
+        Ok(executed)
+    }
+
+    /// 在 tokio 运行时中启动工作线程
+    ///
+    /// 每个工作线程在阻塞线程池中执行作业，空闲时按查询间隔等待。
+    pub fn spawn(self) -> WorkerPoolHandle {
+        let stop = Arc::new(AtomicBool::new(false));
+        let token = self.token.clone();
+        let workers = self.workers;
+        let poll_interval = self.poll_interval;
+        let pool = Arc::new(self);
+
+        let tasks = (0..workers)
+            .map(|_| {
+                let pool = Arc::clone(&pool);
+                let stop = Arc::clone(&stop);
+                tokio::spawn(async move {
+                    while !stop.load(Ordering::Relaxed) {
+                        let worker = Arc::clone(&pool);
+                        match tokio::task::spawn_blocking(move || worker.run_once()).await {
+                            Ok(Ok(Some(_))) => {}
+                            Ok(Ok(None)) => tokio::time::sleep(poll_interval).await,
+                            Ok(Err(e)) => {
+                                eprintln!("⚠️  作业调度失败: {}", e);
+                                tokio::time::sleep(poll_interval).await;
+                            }
+                            Err(e) => {
+                                eprintln!("⚠️  工作线程异常退出: {}", e);
+                                tokio::time::sleep(poll_interval).await;
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        WorkerPoolHandle { stop, token, tasks }
+    }
+}
+
+/// 后台运行的工作线程池
+pub struct WorkerPoolHandle {
+    stop: Arc<AtomicBool>,
+    token: CancellationToken,
+    tasks: Vec<tokio::task::JoinHandle<()>>,
+}
+
+impl WorkerPoolHandle {
+    /// 工作线程数量
+    pub fn workers(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// 停止领取新作业，并等待正在执行的作业结束
+    pub async fn shutdown(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        for task in self.tasks {
+            let _ = task.await;
+        }
+    }
+
+    /// 停止领取新作业，并取消正在执行的作业
+    ///
+    /// 处理器在下一次检查令牌时返回取消错误，作业按重试策略处理。
+    pub async fn cancel(self) {
+        self.token.cancel("worker pool cancelled");
+        self.shutdown().await
+    }
+}
@@ -21,8 +21,10 @@
 //! 所有运算都在u64有限域中进行，确保密码学安全性。
 
 use super::{FIELD_PRIME, field_add, field_sub, field_mul};
+use crate::protocols::topology::Topology;
 use crate::{MpcError, Result};
-use rand::Rng;
+use rand::{CryptoRng, Rng, RngCore};
+use serde::{Deserialize, Serialize};
 
 /// 加法秘密分享的份额结构
 ///
@@ -32,6 +34,7 @@ use rand::Rng;
 /// # 字段说明
 /// - `party_id`: 持有该份额的参与方标识符
 /// - `value`: 份额的数值，在有限域中表示
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AdditiveShare {
     /// 参与方标识符，用于区分不同的参与方
     pub party_id: usize,
@@ -64,7 +67,14 @@ impl AdditiveShare {
 ///
 /// 提供加法秘密分享的核心功能，包括秘密分享、重构以及份额上的同态运算。
 /// 该方案在有限域GF(p)中工作，支持加法和标量乘法操作。
-pub struct AdditiveSecretSharingScheme;
+///
+/// 需要由单一参与方添加的常数项（例如 Beaver 乘法中的 d·e）由拓扑中的
+/// King 负责；未配置拓扑时默认为 party 0。
+#[derive(Debug, Clone)]
+pub struct AdditiveSecretSharingScheme {
+    /// 可选的参与方拓扑
+    topology: Option<Topology>,
+}
 
 impl Default for AdditiveSecretSharingScheme {
     /// 创建加法秘密分享方案的默认实例
@@ -87,7 +97,31 @@ impl AdditiveSecretSharingScheme {
     /// let scheme = AdditiveSecretSharingScheme::new();
     /// ```
     pub fn new() -> Self {
-        Self
+        Self { topology: None }
+    }
+
+    /// 使用指定拓扑创建加法秘密分享方案
+    ///
+    /// # 参数
+    /// - `topology`: 参与方拓扑，其 King 负责添加常数项
+    ///
+    /// # 返回值
+    /// 返回配置了拓扑的AdditiveSecretSharingScheme实例
+    pub fn with_topology(topology: Topology) -> Self {
+        Self { topology: Some(topology) }
+    }
+
+    /// 获取当前配置的拓扑
+    pub fn topology(&self) -> Option<&Topology> {
+        self.topology.as_ref()
+    }
+
+    /// 判断参与方是否负责添加常数项
+    fn is_constant_holder(&self, party_id: usize) -> bool {
+        match &self.topology {
+            Some(topology) => topology.is_king(party_id),
+            None => party_id == 0,
+        }
     }
 
     /// 验证加法份额的完整性
@@ -319,11 +353,18 @@ impl AdditiveSecretSharingScheme {
     /// 该方法使用密码学安全的随机数生成器创建份额，确保份额的随机性和不可预测性。
     /// 在加法秘密分享中，需要所有n个份额才能重构秘密。
     pub fn share_additive(&self, secret: &u64, num_parties: usize) -> Result<Vec<AdditiveShare>> {
+        self.share_additive_with_rng(secret, num_parties, &mut rand::thread_rng())
+    }
+
+    /// 用给定的随机数生成器生成加法份额
+    ///
+    /// 前 n-1 个份额取自 `rng`，传入由种子创建的 `AesCtrPrg` 时份额完全由种子确定。
+    pub fn share_additive_with_rng<R: RngCore + CryptoRng>(&self, secret: &u64, num_parties: usize,
+                                                           rng: &mut R) -> Result<Vec<AdditiveShare>> {
         if num_parties == 0 {
             return Err(MpcError::InvalidThreshold);
         }
         
-        let mut rng = rand::thread_rng();
         let mut shares = Vec::with_capacity(num_parties);
         let mut sum = 0u64;
         
@@ -539,7 +580,7 @@ impl AdditiveSecretSharingScheme {
     /// Beaver 乘法协议（加法分享版本）：
     /// 1. 各方计算 d_i = x_i - a_i, e_i = y_i - b_i
     /// 2. 重构并公开 d = Σd_i, e = Σe_i
-    /// 3. 各方计算 z_i = c_i + d·b_i + e·a_i，party 0 额外加上 d·e
+    /// 3. 各方计算 z_i = c_i + d·b_i + e·a_i，拓扑中的 King（默认 party 0）额外加上 d·e
     ///
     /// # 示例
     /// ```
@@ -566,10 +607,10 @@ impl AdditiveSecretSharingScheme {
         }
 
         // 计算 z = c + d*b + e*a + d*e
-        // 注意：在加法秘密分享中，d*e 项只有 party 0 添加
+        // 注意：在加法秘密分享中，d*e 项只由 King 添加
         let db = field_mul(*d, beaver_b.value);
         let ea = field_mul(*e, beaver_a.value);
-        let de = if share_x.party_id == 0 { // party 0 负责常数项
+        let de = if self.is_constant_holder(share_x.party_id) {
             field_mul(*d, *e)
         } else {
             0
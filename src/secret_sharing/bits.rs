@@ -0,0 +1,341 @@
+//! # 共享随机位与位分解 (Shared Bits and Bit Decomposition)
+//!
+//! 比较、截断、取模等协议都需要共享随机位和对 Shamir 分享的位分解。
+//! 本模块在现有的域和 Beaver 三元组之上提供这些组件，所有函数都按批处理，
+//! 轮数与批量大小无关：
+//!
+//! - **共享随机位**: 各方联合分享随机数 [r]，用一次乘法计算 [r²] 并公开，
+//!   取其平方根 s（较小的那个）后 [b] = ([r]/s + 1)/2。r/s = ±1 等概率出现，
+//!   公开 r² 不泄露符号，每个随机位一个三元组、三轮通信（r² = 0 时重试，概率 1/p）
+//! - **位分解**: 用 ℓ + σ 个共享随机位组成掩码 [r]，公开 c = [x] + [r]
+//!   （σ = 40，统计隐藏 x），再用带借位的减法 c - [r] 逐位得到 x 的分享，
+//!   每一位一次乘法。x 必须小于 2^ℓ，且 ℓ 不超过 `MAX_DECOMPOSITION_BITS`，
+//!   保证 c 不超过域的模数
+//! - **位组合**: [x] = Σ 2^i·[b_i]，本地线性运算
+//!
+//! 比较、Path ORAM 和安全聚合等协议共用本模块的 `Multiplier`、随机位生成和位分解，
+//! 通信统计在同一处按各方收发的消息累计；SPDZ 离线阶段生成认证随机位时
+//! 也使用同一个由 r² 求随机位的步骤。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//!
+//! let bit = generate_random_shared_bit(&mut generator)?.result;
+//! assert!(ShamirSecretSharing::reconstruct(&bit[..2], 2)? <= 1);
+//!
+//! let x = ShamirSecretSharing::share(&0b1011, 2, 3)?;
+//! let bits = bit_decompose(&x, 4, &mut generator)?.result;
+//! let values = bits.iter()
+//!     .map(|bit| ShamirSecretSharing::reconstruct(&bit[..2], 2))
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//! assert_eq!(values, vec![1, 1, 0, 1]);
+//! assert_eq!(ShamirSecretSharing::reconstruct(&bits_to_share(&bits)?[..2], 2)?, 0b1011);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{field_add, field_inv, field_mul, field_sqrt, field_sub, SecretSharing, ShamirSecretSharing, Share, FIELD_PRIME};
+use crate::beaver_triples::{batch_multiply_recorded, BeaverTripleGenerator};
+use crate::protocols::stats::{ProtocolOutput, ProtocolStats, StatsRecorder};
+use crate::utils::random_field_element;
+use crate::{MpcError, Result};
+
+/// 位分解掩码的统计安全参数 σ
+pub const DECOMPOSITION_STATISTICAL_SECURITY: usize = 40;
+
+/// 位分解支持的最大位宽：2^ℓ 加上 ℓ + σ 位的掩码不能超过域的模数
+pub const MAX_DECOMPOSITION_BITS: usize = 63 - DECOMPOSITION_STATISTICAL_SECURITY;
+
+/// 生成一个共享随机位
+///
+/// # 参数
+/// - `generator`: 提供 Beaver 三元组的生成器，决定参与方数量和门限
+///
+/// # 返回值
+/// 返回随机位在各参与方处的分享和执行统计
+pub fn generate_random_shared_bit(generator: &mut dyn BeaverTripleGenerator) -> Result<ProtocolOutput<Vec<Share>>> {
+    let (mut bits, stats) = generate_random_shared_bits(1, generator)?.into_parts();
+    Ok(ProtocolOutput { result: bits.remove(0), stats })
+}
+
+/// 批量生成共享随机位，轮数与数量无关
+///
+/// # 参数
+/// - `count`: 随机位数量
+/// - `generator`: 提供 Beaver 三元组的生成器
+///
+/// # 返回值
+/// 返回每个随机位的分享和执行统计
+pub fn generate_random_shared_bits(
+    count: usize,
+    generator: &mut dyn BeaverTripleGenerator,
+) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+    let mut recorder = StatsRecorder::start();
+    let mut multiplier = Multiplier::for_generator(generator);
+    let bits = random_bits(&mut multiplier, count)?;
+    multiplier.record(recorder.stats_mut());
+    Ok(recorder.finish(bits))
+}
+
+/// 位分解一个分享
+///
+/// # 参数
+/// - `share`: 值在各参与方处的分享，值必须小于 2^`bits`
+/// - `bits`: 位宽 ℓ，不超过 `MAX_DECOMPOSITION_BITS`
+/// - `generator`: 提供 Beaver 三元组的生成器
+///
+/// # 返回值
+/// 返回各位的分享（低位在前）和执行统计
+pub fn bit_decompose(
+    share: &[Share],
+    bits: usize,
+    generator: &mut dyn BeaverTripleGenerator,
+) -> Result<ProtocolOutput<Vec<Vec<Share>>>> {
+    let (mut decomposed, stats) = bit_decompose_batch(&[share.to_vec()], bits, generator)?.into_parts();
+    Ok(ProtocolOutput { result: decomposed.remove(0), stats })
+}
+
+/// 同步位分解多个分享，轮数与数量无关
+///
+/// # 返回值
+/// 返回每个值各位的分享（低位在前）和执行统计
+pub fn bit_decompose_batch(
+    values: &[Vec<Share>],
+    bits: usize,
+    generator: &mut dyn BeaverTripleGenerator,
+) -> Result<ProtocolOutput<Vec<Vec<Vec<Share>>>>> {
+    let party_count = generator.get_party_count();
+    if let Some(value) = values.iter().find(|value| value.len() != party_count) {
+        return Err(MpcError::ProtocolError(format!(
+            "Expected {} shares per value, got {}", party_count, value.len()
+        )));
+    }
+
+    let mut recorder = StatsRecorder::start();
+    let mut multiplier = Multiplier::for_generator(generator);
+    let decomposed = decompose_batch(&mut multiplier, values, bits)?;
+    multiplier.record(recorder.stats_mut());
+    Ok(recorder.finish(decomposed))
+}
+
+/// 由位分享组合出数值分享：[x] = Σ 2^i·[b_i]（低位在前）
+///
+/// # 返回值
+/// 位列表为空、超过 64 位或各位的分享数量不一致时返回错误
+pub fn bits_to_share(bits: &[Vec<Share>]) -> Result<Vec<Share>> {
+    let party_count = bits.first().map_or(0, Vec::len);
+    if bits.is_empty() || bits.len() > 64 || party_count == 0 || bits.iter().any(|bit| bit.len() != party_count) {
+        return Err(MpcError::ProtocolError(
+            "Bit composition needs 1 to 64 bits with one share per party".to_string()
+        ));
+    }
+    Ok(compose(bits, party_count))
+}
+
+/// 在已有的乘法器上同步位分解多个值，供组合协议共用三元组来源和统计
+///
+/// 公开 c = x + r 后用带借位的减法逐位计算 c - r；每个值必须小于 2^`bits`。
+pub(crate) fn decompose_batch(multiplier: &mut Multiplier, values: &[Vec<Share>], bits: usize) -> Result<Vec<Vec<Vec<Share>>>> {
+    if bits == 0 || bits > MAX_DECOMPOSITION_BITS {
+        return Err(MpcError::ProtocolError(format!(
+            "Bit decomposition width must be between 1 and {}", MAX_DECOMPOSITION_BITS
+        )));
+    }
+    if values.is_empty() {
+        return Ok(Vec::new());
+    }
+    let party_count = multiplier.party_count;
+    let mask_width = bits + DECOMPOSITION_STATISTICAL_SECURITY;
+    let mask_bits = random_bits(multiplier, values.len() * mask_width)?;
+    let masks: Vec<&[Vec<Share>]> = mask_bits.chunks(mask_width).collect();
+
+    // 公开 c = x + r
+    let masked: Vec<Vec<Share>> = values.iter()
+        .zip(&masks)
+        .map(|(value, mask)| add_shares(value, &compose(mask, party_count)))
+        .collect();
+    let masked = multiplier.open(&masked)?;
+
+    // c - r 逐位相减，β 为借位
+    let mut borrows = vec![public_shares(party_count, 0); values.len()];
+    let mut results = vec![Vec::with_capacity(bits); values.len()];
+    for k in 0..bits {
+        let r: Vec<Vec<Share>> = masks.iter().map(|mask| mask[k].clone()).collect();
+        let rb = multiplier.multiply(&r, &borrows)?;
+        for (i, ((r, rb), c)) in r.iter().zip(rb).zip(&masked).enumerate() {
+            let r_plus_b = add_shares(r, &borrows[i]);
+            let r_xor_b = sub_shares(&r_plus_b, &scale_shares(&rb, 2));
+            if (c >> k) & 1 == 1 {
+                // 1 - r - β 借位当且仅当 r = β = 1
+                results[i].push(sub_shares(&public_shares(party_count, 1), &r_xor_b));
+                borrows[i] = rb;
+            } else {
+                // 0 - r - β 借位当且仅当 r 或 β 为 1
+                results[i].push(r_xor_b);
+                borrows[i] = sub_shares(&r_plus_b, &rb);
+            }
+        }
+    }
+    Ok(results)
+}
+
+/// 在已有的乘法器上批量生成随机位
+pub(crate) fn random_bits(multiplier: &mut Multiplier, count: usize) -> Result<Vec<Vec<Share>>> {
+    let party_count = multiplier.party_count;
+    let threshold = multiplier.threshold;
+    let mut bits = Vec::with_capacity(count);
+
+    while bits.len() < count {
+        let needed = count - bits.len();
+        // 每一方分享 needed 个随机数，[r] 为全部贡献之和
+        let dealt = (0..party_count)
+            .map(|_| {
+                (0..needed)
+                    .map(|_| ShamirSecretSharing::share(&random_field_element(), threshold, party_count))
+                    .collect::<Result<Vec<Vec<Share>>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        multiplier.deal(&dealt)?;
+        let randoms: Vec<Vec<Share>> = (0..needed)
+            .map(|i| dealt.iter().fold(public_shares(party_count, 0), |sum, party| add_shares(&sum, &party[i])))
+            .collect();
+
+        let squares = multiplier.multiply(&randoms, &randoms)?;
+        for (r, square) in randoms.iter().zip(multiplier.open(&squares)?) {
+            if let Some((scale, offset)) = bit_from_square(square)? {
+                bits.push(add_shares(&scale_shares(r, scale), &public_shares(party_count, offset)));
+            }
+        }
+    }
+    Ok(bits)
+}
+
+/// 由公开的 r² 得到把 [r] 映射为随机位的系数：b = (r/s + 1)/2 = scale·r + offset
+///
+/// s 为 r² 较小的平方根；r² = 0 时返回 `None`，调用方需要重试。
+/// 公开值不是二次剩余时说明有参与方篡改了分享，返回协议错误。
+pub(crate) fn bit_from_square(square: u64) -> Result<Option<(u64, u64)>> {
+    if square == 0 {
+        return Ok(None);
+    }
+    let half = field_inv(2).expect("2 is invertible");
+    let root = field_sqrt(square).ok_or_else(|| {
+        MpcError::ProtocolError("Opened square is not a quadratic residue".to_string())
+    })?;
+    let inverse = field_inv(root).expect("non-zero root is invertible");
+    Ok(Some((field_mul(inverse, half), half)))
+}
+
+/// 由位分享组合出数值分享（低位在前）
+pub(crate) fn compose(bits: &[Vec<Share>], party_count: usize) -> Vec<Share> {
+    bits.iter()
+        .enumerate()
+        .fold(public_shares(party_count, 0), |sum, (i, bit)| add_shares(&sum, &scale_shares(bit, field_pow2(i))))
+}
+
+fn field_pow2(exponent: usize) -> u64 {
+    ((1u128 << exponent) % FIELD_PRIME as u128) as u64
+}
+
+/// 公开常数的平凡分享（常数多项式）
+pub(crate) fn public_shares(party_count: usize, constant: u64) -> Vec<Share> {
+    (1..=party_count as u64).map(|x| Share::new(x, constant)).collect()
+}
+
+pub(crate) fn add_shares(a: &[Share], b: &[Share]) -> Vec<Share> {
+    a.iter().zip(b).map(|(a, b)| Share::new(a.x, field_add(a.y, b.y))).collect()
+}
+
+pub(crate) fn sub_shares(a: &[Share], b: &[Share]) -> Vec<Share> {
+    a.iter().zip(b).map(|(a, b)| Share::new(a.x, field_sub(a.y, b.y))).collect()
+}
+
+pub(crate) fn scale_shares(a: &[Share], scalar: u64) -> Vec<Share> {
+    a.iter().map(|a| Share::new(a.x, field_mul(a.y, scalar))).collect()
+}
+
+/// 在模拟执行中为全部参与方完成批量乘法和公开，并累计通信统计
+///
+/// 参与方 ID 为 1..=n，与分享的 x 坐标一致。每一轮中每一方发出的消息按序列化后的
+/// 实际长度用 `ProtocolStats::record_message` 记录，发送方计入发送量、接收方计入接收量，
+/// 各方的收发量见统计中的 `party_traffic`。
+pub(crate) struct Multiplier<'a> {
+    generator: &'a mut dyn BeaverTripleGenerator,
+    threshold: usize,
+    party_count: usize,
+    stats: ProtocolStats,
+}
+
+impl<'a> Multiplier<'a> {
+    pub(crate) fn new(generator: &'a mut dyn BeaverTripleGenerator, threshold: usize, party_count: usize) -> Self {
+        Self { generator, threshold, party_count, stats: ProtocolStats::new() }
+    }
+
+    /// 使用生成器的参与方数量和门限
+    pub(crate) fn for_generator(generator: &'a mut dyn BeaverTripleGenerator) -> Self {
+        let (threshold, party_count) = (generator.get_threshold(), generator.get_party_count());
+        Self::new(generator, threshold, party_count)
+    }
+
+    fn parties(&self) -> Vec<usize> {
+        (1..=self.party_count).collect()
+    }
+
+    /// 一轮内完成一批乘法，每个乘法消耗一个三元组
+    ///
+    /// 每一方广播自己在全部乘法中的 (d_i, e_i)。
+    pub(crate) fn multiply(&mut self, x: &[Vec<Share>], y: &[Vec<Share>]) -> Result<Vec<Vec<Share>>> {
+        if x.is_empty() {
+            return Ok(Vec::new());
+        }
+        let triples = self.generator.generate_batch(x.len())?;
+        let products = batch_multiply_recorded(x, y, &triples, self.threshold, &mut self.stats)?;
+        self.stats.record_preprocessing(x.len());
+        Ok(products)
+    }
+
+    /// 一轮内公开一批分享，每一方把自己的分享值广播给其余各方
+    pub(crate) fn open(&mut self, values: &[Vec<Share>]) -> Result<Vec<u64>> {
+        if !values.is_empty() {
+            let messages: Vec<Vec<Share>> = (0..self.party_count)
+                .map(|j| values.iter().map(|value| value[j].clone()).collect())
+                .collect();
+            self.stats.record_broadcast(&self.parties(), &messages)?;
+            self.stats.record_rounds(1);
+        }
+        values.iter()
+            .map(|value| ShamirSecretSharing::reconstruct(value, self.threshold))
+            .collect()
+    }
+
+    /// 一轮内每一方分发自己的分享，`sharings[j]` 是第 j 方分享的全部值
+    ///
+    /// 第 j 方发给第 k 方的消息是这些值中属于第 k 方的分享。
+    pub(crate) fn deal(&mut self, sharings: &[Vec<Vec<Share>>]) -> Result<()> {
+        if sharings.iter().all(|dealt| dealt.is_empty()) {
+            return Ok(());
+        }
+        let parties = self.parties();
+        for (&from, dealt) in parties.iter().zip(sharings) {
+            for (k, &to) in parties.iter().enumerate().filter(|&(_, &to)| to != from) {
+                let message: Vec<&Share> = dealt.iter().map(|shares| &shares[k]).collect();
+                let bytes = bincode::serialized_size(&message)
+                    .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+                self.stats.record_message(from, to, bytes);
+            }
+        }
+        self.stats.record_rounds(1);
+        Ok(())
+    }
+
+    /// 把累计的轮数、各方收发字节数和消耗的三元组写入统计
+    pub(crate) fn record(&self, stats: &mut ProtocolStats) {
+        stats.merge(&self.stats);
+    }
+}
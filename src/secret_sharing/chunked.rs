@@ -0,0 +1,485 @@
+//! # 大数据分块分享 (Chunked Large-Value Sharing)
+//!
+//! 对文件、模型等大型秘密直接使用 Shamir 分享会使每一方的存储量与数据
+//! 大小成正比地乘以参与方数量。本模块采用"加密 + 纠删码 + 密钥分享"的
+//! 组合方式，构成一个秘密分享存储能力：
+//!
+//! 1. 数据被切分为固定大小的块，每块使用独立的随机密钥加密
+//! 2. 密文经过 Reed–Solomon 编码为 n 个分片，任意 k 个分片即可恢复密文
+//! 3. 块密钥使用 (t, n) Shamir 分享，少于 t 方无法获得密钥
+//! 4. 每块的 n 个分片（连同密钥分享）构成 Merkle 树，根值写入公开清单，
+//!    接收方逐片验证完整性
+//!
+//! 收集端使用可序列化的 `CollectionSession` 记录已收到的分片，
+//! 中断后可以恢复，并通过 `ResumeRequest` 只请求缺失的块。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let params = ChunkedSharingParams::new(5, 3, 3)?.with_chunk_size(1024);
+//! let scheme = ChunkedSecretSharing::new(params)?;
+//!
+//! let data = vec![7u8; 4000];
+//! let (manifest, pieces_per_party) = scheme.share("model-v1", &data)?;
+//!
+//! // 任意 3 方的分片即可恢复
+//! let mut session = CollectionSession::new(manifest)?;
+//! for pieces in &pieces_per_party[2..] {
+//!     for piece in pieces {
+//!         session.accept(piece.clone())?;
+//!     }
+//! }
+//! assert_eq!(session.finish()?, data);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{Share, ShamirSecretSharing, SecretSharing, FIELD_PRIME};
+use crate::commitment::{MerkleProof, MerkleTree};
+#[cfg(feature = "network")]
+use crate::network::NetworkMessage;
+use crate::utils::erasure::ReedSolomon;
+use crate::{MpcError, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 默认块大小（字节）
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// 块密钥由多少个域元素组成
+const KEY_LIMBS: usize = 4;
+
+/// 密钥流的域分隔标签
+const KEYSTREAM_DOMAIN: &[u8] = b"mpc_api/chunked_sharing/keystream";
+
+/// 分片消息的网络消息类型
+pub const CHUNK_PIECE_MESSAGE_TYPE: &str = "chunk_piece";
+
+/// 续传请求的网络消息类型
+pub const CHUNK_RESUME_MESSAGE_TYPE: &str = "chunk_resume";
+
+/// 分块分享参数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkedSharingParams {
+    /// 块大小（字节）
+    pub chunk_size: usize,
+    /// 参与方数量 n（即每块的分片数量）
+    pub total_parties: usize,
+    /// 恢复密文所需的分片数量 k
+    pub data_fragments: usize,
+    /// 恢复块密钥所需的分享数量 t
+    pub key_threshold: usize,
+}
+
+impl ChunkedSharingParams {
+    /// 创建分块分享参数
+    ///
+    /// # 参数
+    ///
+    /// * `total_parties` - 参与方数量 n
+    /// * `data_fragments` - 恢复密文所需的分片数量 k
+    /// * `key_threshold` - 恢复块密钥所需的分享数量 t
+    pub fn new(total_parties: usize, data_fragments: usize, key_threshold: usize) -> Result<Self> {
+        super::validate_threshold_params(key_threshold, total_parties)?;
+        if data_fragments == 0 || data_fragments > total_parties {
+            return Err(MpcError::InvalidThreshold);
+        }
+        Ok(Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            total_parties,
+            data_fragments,
+            key_threshold,
+        })
+    }
+
+    /// 设置块大小
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// 每块至少需要从多少方收集分片
+    pub fn required_pieces(&self) -> usize {
+        self.data_fragments.max(self.key_threshold)
+    }
+}
+
+/// 公开清单
+///
+/// 描述被分享对象的结构和每块分片的 Merkle 根，由分享方公开给所有参与方。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkManifest {
+    /// 对象标识
+    pub object_id: String,
+    /// 分享参数
+    pub params: ChunkedSharingParams,
+    /// 原始数据总长度
+    pub total_len: u64,
+    /// 每块的原始长度
+    pub chunk_lens: Vec<u64>,
+    /// 每块分片的 Merkle 根
+    pub chunk_roots: Vec<[u8; 32]>,
+}
+
+impl ChunkManifest {
+    /// 块数量
+    pub fn chunk_count(&self) -> usize {
+        self.chunk_lens.len()
+    }
+
+    /// 检查清单结构一致
+    ///
+    /// 清单可能来自网络：每块都必须有 Merkle 根，块长度不超过块大小，
+    /// 且所有块长度之和等于 `total_len`。
+    pub fn validate(&self) -> Result<()> {
+        if self.chunk_roots.len() != self.chunk_lens.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Chunk manifest has {} roots for {} chunks", self.chunk_roots.len(), self.chunk_lens.len()
+            )));
+        }
+        let mut total: u64 = 0;
+        for &chunk_len in &self.chunk_lens {
+            if chunk_len > self.params.chunk_size as u64 {
+                return Err(MpcError::ProtocolError(format!(
+                    "Chunk length {} exceeds chunk size {}", chunk_len, self.params.chunk_size
+                )));
+            }
+            total = total.checked_add(chunk_len)
+                .ok_or_else(|| MpcError::ProtocolError("Chunk manifest length overflows".to_string()))?;
+        }
+        if total != self.total_len {
+            return Err(MpcError::ProtocolError(format!(
+                "Chunk lengths sum to {} but manifest declares {}", total, self.total_len
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 发送给单个参与方的单块分片
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkPiece {
+    /// 对象标识
+    pub object_id: String,
+    /// 块索引
+    pub chunk_index: usize,
+    /// 参与方索引（从 0 开始，对应分享的 x = index + 1）
+    pub party_index: usize,
+    /// 纠删码分片
+    pub fragment: Vec<u64>,
+    /// 块密钥的 Shamir 分享
+    pub key_shares: Vec<Share>,
+    /// 相对于块 Merkle 根的包含证明
+    pub proof: MerkleProof,
+}
+
+impl ChunkPiece {
+    /// Merkle 叶子内容
+    fn leaf_bytes(fragment: &[u64], key_shares: &[Share]) -> Result<Vec<u8>> {
+        bincode::serialize(&(fragment, key_shares))
+            .map_err(|e| MpcError::SerializationError(e.to_string()))
+    }
+
+    /// 根据清单验证分片的完整性
+    pub fn verify(&self, manifest: &ChunkManifest) -> Result<bool> {
+        if self.object_id != manifest.object_id
+            || self.party_index >= manifest.params.total_parties
+            || self.proof.leaf_index != self.party_index
+        {
+            return Ok(false);
+        }
+        let root = match manifest.chunk_roots.get(self.chunk_index) {
+            Some(root) => root,
+            None => return Ok(false),
+        };
+        let leaf = Self::leaf_bytes(&self.fragment, &self.key_shares)?;
+        MerkleTree::verify_proof(root, &leaf, &self.proof)
+    }
+
+    /// 封装为网络消息
+    #[cfg(feature = "network")]
+    pub fn to_network_message(&self) -> Result<NetworkMessage> {
+        let payload = bincode::serialize(self)
+            .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+        Ok(NetworkMessage::new(CHUNK_PIECE_MESSAGE_TYPE, &payload)
+            .with_header("object_id".to_string(), self.object_id.clone())
+            .with_header("chunk_index".to_string(), self.chunk_index.to_string()))
+    }
+
+    /// 从网络消息解析
+    #[cfg(feature = "network")]
+    pub fn from_network_message(message: &NetworkMessage) -> Result<Self> {
+        if message.message_type != CHUNK_PIECE_MESSAGE_TYPE {
+            return Err(MpcError::ProtocolError(format!(
+                "Unexpected message type: {}", message.message_type
+            )));
+        }
+        bincode::deserialize(&message.payload)
+            .map_err(|e| MpcError::SerializationError(e.to_string()))
+    }
+}
+
+/// 续传请求
+///
+/// 收集方告知发送方哪些块仍然缺失。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResumeRequest {
+    /// 对象标识
+    pub object_id: String,
+    /// 缺失的块索引
+    pub missing_chunks: Vec<usize>,
+}
+
+impl ResumeRequest {
+    /// 从发送方持有的分片中选出需要重传的部分
+    pub fn select<'a>(&self, pieces: &'a [ChunkPiece]) -> Vec<&'a ChunkPiece> {
+        pieces.iter()
+            .filter(|piece| piece.object_id == self.object_id
+                && self.missing_chunks.contains(&piece.chunk_index))
+            .collect()
+    }
+
+    /// 封装为网络消息
+    #[cfg(feature = "network")]
+    pub fn to_network_message(&self) -> Result<NetworkMessage> {
+        let payload = bincode::serialize(self)
+            .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+        Ok(NetworkMessage::new(CHUNK_RESUME_MESSAGE_TYPE, &payload)
+            .with_header("object_id".to_string(), self.object_id.clone()))
+    }
+
+    /// 从网络消息解析
+    #[cfg(feature = "network")]
+    pub fn from_network_message(message: &NetworkMessage) -> Result<Self> {
+        if message.message_type != CHUNK_RESUME_MESSAGE_TYPE {
+            return Err(MpcError::ProtocolError(format!(
+                "Unexpected message type: {}", message.message_type
+            )));
+        }
+        bincode::deserialize(&message.payload)
+            .map_err(|e| MpcError::SerializationError(e.to_string()))
+    }
+}
+
+/// 分块秘密分享方案
+#[derive(Debug, Clone)]
+pub struct ChunkedSecretSharing {
+    /// 分享参数
+    params: ChunkedSharingParams,
+    /// 纠删编码器
+    coder: ReedSolomon,
+}
+
+impl ChunkedSecretSharing {
+    /// 创建分块秘密分享方案
+    pub fn new(params: ChunkedSharingParams) -> Result<Self> {
+        let coder = ReedSolomon::new(params.data_fragments, params.total_parties)?;
+        Ok(Self { params, coder })
+    }
+
+    /// 获取分享参数
+    pub fn params(&self) -> &ChunkedSharingParams {
+        &self.params
+    }
+
+    /// 分享数据
+    ///
+    /// # 参数
+    ///
+    /// * `object_id` - 对象标识
+    /// * `data` - 要分享的数据
+    ///
+    /// # 返回值
+    ///
+    /// 返回公开清单以及每个参与方应收到的分片列表
+    pub fn share(&self, object_id: &str, data: &[u8]) -> Result<(ChunkManifest, Vec<Vec<ChunkPiece>>)> {
+        let n = self.params.total_parties;
+        let mut manifest = ChunkManifest {
+            object_id: object_id.to_string(),
+            params: self.params.clone(),
+            total_len: data.len() as u64,
+            chunk_lens: Vec::new(),
+            chunk_roots: Vec::new(),
+        };
+        let mut pieces_per_party: Vec<Vec<ChunkPiece>> = vec![Vec::new(); n];
+        let mut rng = rand::thread_rng();
+
+        let chunks: Vec<&[u8]> = if data.is_empty() {
+            vec![&[][..]]
+        } else {
+            data.chunks(self.params.chunk_size).collect()
+        };
+
+        for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+            let key: [u64; KEY_LIMBS] = std::array::from_fn(|_| rng.gen_range(0..FIELD_PRIME));
+            let ciphertext = apply_keystream(&key, chunk);
+            let fragments = self.coder.encode(&ciphertext);
+
+            // key_shares[party][limb]
+            let mut key_shares = vec![Vec::with_capacity(KEY_LIMBS); n];
+            for limb in key {
+                let shares = ShamirSecretSharing::share(&limb, self.params.key_threshold, n)?;
+                for (party_shares, share) in key_shares.iter_mut().zip(shares) {
+                    party_shares.push(share);
+                }
+            }
+
+            let leaves = fragments.iter()
+                .zip(&key_shares)
+                .map(|(fragment, shares)| ChunkPiece::leaf_bytes(fragment, shares))
+                .collect::<Result<Vec<_>>>()?;
+            let tree = MerkleTree::new(&leaves)?;
+
+            for (party_index, (fragment, shares)) in fragments.into_iter().zip(key_shares).enumerate() {
+                pieces_per_party[party_index].push(ChunkPiece {
+                    object_id: object_id.to_string(),
+                    chunk_index,
+                    party_index,
+                    fragment,
+                    key_shares: shares,
+                    proof: tree.generate_proof(party_index)?,
+                });
+            }
+
+            manifest.chunk_lens.push(chunk.len() as u64);
+            manifest.chunk_roots.push(*tree.get_root());
+        }
+
+        Ok((manifest, pieces_per_party))
+    }
+
+    /// 从一组分片重构数据
+    pub fn reconstruct(&self, manifest: &ChunkManifest, pieces: &[ChunkPiece]) -> Result<Vec<u8>> {
+        let mut session = CollectionSession::new(manifest.clone())?;
+        for piece in pieces {
+            session.accept(piece.clone())?;
+        }
+        session.finish()
+    }
+}
+
+/// 可恢复的收集会话
+///
+/// 记录每块已收到并验证过的分片。会话可以序列化保存，
+/// 在传输中断后恢复继续收集。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionSession {
+    /// 公开清单
+    manifest: ChunkManifest,
+    /// received[chunk] = 参与方索引 -> 分片
+    received: Vec<BTreeMap<usize, ChunkPiece>>,
+}
+
+impl CollectionSession {
+    /// 根据公开清单创建收集会话，结构不一致的清单返回错误
+    pub fn new(manifest: ChunkManifest) -> Result<Self> {
+        manifest.validate()?;
+        let received = vec![BTreeMap::new(); manifest.chunk_count()];
+        Ok(Self { manifest, received })
+    }
+
+    /// 获取公开清单
+    pub fn manifest(&self) -> &ChunkManifest {
+        &self.manifest
+    }
+
+    /// 接收一个分片
+    ///
+    /// # 返回值
+    ///
+    /// 分片有效且为新分片时返回 `true`，重复分片返回 `false`；
+    /// 完整性验证失败时返回错误
+    pub fn accept(&mut self, piece: ChunkPiece) -> Result<bool> {
+        if !piece.verify(&self.manifest)? {
+            return Err(MpcError::ProtocolError(format!(
+                "Chunk {} piece from party {} failed integrity check",
+                piece.chunk_index, piece.party_index
+            )));
+        }
+        let slot = self.received.get_mut(piece.chunk_index).ok_or_else(|| {
+            MpcError::ProtocolError(format!("Chunk index {} is not in the manifest", piece.chunk_index))
+        })?;
+        if slot.contains_key(&piece.party_index) {
+            return Ok(false);
+        }
+        slot.insert(piece.party_index, piece);
+        Ok(true)
+    }
+
+    /// 尚未收集到足够分片的块
+    pub fn missing_chunks(&self) -> Vec<usize> {
+        let required = self.manifest.params.required_pieces();
+        self.received.iter()
+            .enumerate()
+            .filter(|(_, pieces)| pieces.len() < required)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// 是否已收集完成
+    pub fn is_complete(&self) -> bool {
+        self.missing_chunks().is_empty()
+    }
+
+    /// 生成续传请求
+    pub fn resume_request(&self) -> ResumeRequest {
+        ResumeRequest {
+            object_id: self.manifest.object_id.clone(),
+            missing_chunks: self.missing_chunks(),
+        }
+    }
+
+    /// 重构完整数据
+    pub fn finish(&self) -> Result<Vec<u8>> {
+        let params = &self.manifest.params;
+        // 反序列化得到的会话没有经过 `new` 的检查
+        self.manifest.validate()?;
+        if self.received.len() != self.manifest.chunk_count() {
+            return Err(MpcError::ProtocolError("Collection session does not match its manifest".to_string()));
+        }
+        if !self.is_complete() {
+            return Err(MpcError::InsufficientShares);
+        }
+
+        let coder = ReedSolomon::new(params.data_fragments, params.total_parties)?;
+        let mut data = Vec::new();
+
+        for (pieces, &chunk_len) in self.received.iter().zip(&self.manifest.chunk_lens) {
+            let mut key = [0u64; KEY_LIMBS];
+            for (limb, value) in key.iter_mut().enumerate() {
+                let shares: Vec<Share> = pieces.values()
+                    .take(params.key_threshold)
+                    .map(|piece| piece.key_shares.get(limb).cloned().ok_or(MpcError::InvalidSecretShare))
+                    .collect::<Result<Vec<_>>>()?;
+                *value = ShamirSecretSharing::reconstruct(&shares, params.key_threshold)?;
+            }
+
+            let fragments: Vec<(usize, Vec<u64>)> = pieces.values()
+                .map(|piece| (piece.party_index, piece.fragment.clone()))
+                .collect();
+            let ciphertext = coder.decode(&fragments, chunk_len as usize)?;
+            data.extend_from_slice(&apply_keystream(&key, &ciphertext));
+        }
+
+        Ok(data)
+    }
+}
+
+/// 使用块密钥派生的密钥流加/解密
+fn apply_keystream(key: &[u64; KEY_LIMBS], data: &[u8]) -> Vec<u8> {
+    let mut key_bytes = [0u8; 32];
+    for (dst, limb) in key_bytes.chunks_mut(8).zip(key) {
+        dst.copy_from_slice(&limb.to_le_bytes());
+    }
+
+    let mut hasher = blake3::Hasher::new_keyed(&key_bytes);
+    hasher.update(KEYSTREAM_DOMAIN);
+    let mut keystream = vec![0u8; data.len()];
+    hasher.finalize_xof().fill(&mut keystream);
+
+    data.iter().zip(keystream).map(|(byte, k)| byte ^ k).collect()
+}
@@ -0,0 +1,220 @@
+//! # 重构诊断 (Reconstruction Diagnostics)
+//!
+//! `verify_shares` 只能回答"这些份额是否一致"。当答案是否定的时候，
+//! 运维人员需要知道是哪一方发送了错误的数据。本模块在份额的各个
+//! 门限大小子集上进行插值，选出被最多份额支持的多项式（多数解码），
+//! 不在该多项式上的份额即为可疑份额。
+//!
+//! 设有效份额数为 m、门限为 t，当最佳多项式的支持数 s 满足 2s ≥ m + t 时，
+//! 该多项式是唯一的，可疑份额的定位是可靠的；否则只能检测到错误，
+//! 无法确定是哪一方出错。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let mut shares = ShamirSecretSharing::share(&42, 2, 5)?;
+//! shares[3].y = field_add(shares[3].y, 1);
+//!
+//! let report = ShamirSecretSharing::new().diagnose_shares(&shares, 2);
+//! assert_eq!(report.secret, Some(42));
+//! assert_eq!(report.suspects.len(), 1);
+//! assert_eq!(report.suspects[0].x, shares[3].x);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{Share, ShamirSecretSharing, field_add, field_mul, field_sub, field_inv};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 诊断时最多检查的子集数量
+pub const MAX_DIAGNOSTIC_SUBSETS: usize = 4096;
+
+/// 份额被怀疑的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SuspectReason {
+    /// x 坐标为 0（会直接暴露秘密，不是合法份额）
+    ZeroIndex,
+    /// 同一 x 坐标出现了不同的分享值
+    ConflictingDuplicate,
+    /// 不在多数份额支持的多项式上
+    InconsistentWithMajority,
+}
+
+/// 可疑份额
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuspectShare {
+    /// 份额的 x 坐标（参与方索引）
+    pub x: u64,
+    /// 被怀疑的原因
+    pub reason: SuspectReason,
+}
+
+/// 重构诊断结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReconstructionDiagnostics {
+    /// 多数解码得到的秘密；无法唯一确定时为 `None`
+    pub secret: Option<u64>,
+    /// 与多数多项式一致的份额 x 坐标
+    pub consistent: Vec<u64>,
+    /// 可疑份额
+    pub suspects: Vec<SuspectShare>,
+    /// 已检查的子集数量
+    pub subsets_checked: usize,
+    /// 多数多项式是否唯一（可疑份额定位是否可靠）
+    pub unique: bool,
+}
+
+impl ReconstructionDiagnostics {
+    /// 所有份额是否一致
+    pub fn is_consistent(&self) -> bool {
+        self.unique && self.suspects.is_empty()
+    }
+
+    /// 可疑份额的 x 坐标
+    pub fn suspect_indices(&self) -> Vec<u64> {
+        self.suspects.iter().map(|suspect| suspect.x).collect()
+    }
+}
+
+impl ShamirSecretSharing {
+    /// 诊断份额集合并定位可疑份额
+    ///
+    /// 先排除 x = 0 和冲突的重复份额，然后在门限大小的子集上插值，
+    /// 选出支持数最多的多项式。检查的子集数量以 `MAX_DIAGNOSTIC_SUBSETS` 为上限，
+    /// 找到唯一的多数多项式后提前结束。
+    ///
+    /// # 参数
+    /// - `shares`: 要诊断的份额
+    /// - `threshold`: 门限值
+    ///
+    /// # 返回值
+    /// 返回结构化的诊断结果
+    pub fn diagnose_shares(&self, shares: &[Share], threshold: usize) -> ReconstructionDiagnostics {
+        let mut suspects = Vec::new();
+        let mut by_x: HashMap<u64, Option<u64>> = HashMap::new();
+        let mut order = Vec::new();
+
+        for share in shares {
+            if share.x == 0 {
+                suspects.push(SuspectShare { x: 0, reason: SuspectReason::ZeroIndex });
+                continue;
+            }
+            match by_x.get_mut(&share.x) {
+                None => {
+                    by_x.insert(share.x, Some(share.y));
+                    order.push(share.x);
+                }
+                Some(existing) => {
+                    if *existing != Some(share.y) {
+                        *existing = None;
+                    }
+                }
+            }
+        }
+
+        let mut candidates = Vec::with_capacity(order.len());
+        for x in order {
+            match by_x[&x] {
+                Some(y) => candidates.push(Share::new(x, y)),
+                None => suspects.push(SuspectShare { x, reason: SuspectReason::ConflictingDuplicate }),
+            }
+        }
+
+        let mut report = ReconstructionDiagnostics {
+            secret: None,
+            consistent: Vec::new(),
+            suspects,
+            subsets_checked: 0,
+            unique: false,
+        };
+
+        let m = candidates.len();
+        if threshold == 0 || m < threshold {
+            return report;
+        }
+
+        let mut best: Option<(Vec<usize>, Vec<bool>)> = None;
+        let mut best_support = 0;
+        let mut subset: Vec<usize> = (0..threshold).collect();
+
+        loop {
+            let points: Vec<Share> = subset.iter().map(|&i| candidates[i].clone()).collect();
+            let agreement: Vec<bool> = candidates.iter()
+                .map(|share| interpolate_at(&points, share.x) == Some(share.y))
+                .collect();
+            let support = agreement.iter().filter(|&&agrees| agrees).count();
+            report.subsets_checked += 1;
+
+            if support > best_support {
+                best_support = support;
+                best = Some((subset.clone(), agreement));
+            }
+            if 2 * best_support >= m + threshold
+                || report.subsets_checked >= MAX_DIAGNOSTIC_SUBSETS
+                || !next_combination(&mut subset, m)
+            {
+                break;
+            }
+        }
+
+        let (subset, agreement) = match best {
+            Some(best) => best,
+            None => return report,
+        };
+        report.unique = 2 * best_support >= m + threshold;
+        if !report.unique {
+            return report;
+        }
+
+        let points: Vec<Share> = subset.iter().map(|&i| candidates[i].clone()).collect();
+        report.secret = interpolate_at(&points, 0);
+        for (share, agrees) in candidates.iter().zip(agreement) {
+            if agrees {
+                report.consistent.push(share.x);
+            } else {
+                report.suspects.push(SuspectShare {
+                    x: share.x,
+                    reason: SuspectReason::InconsistentWithMajority,
+                });
+            }
+        }
+        report
+    }
+}
+
+/// 计算经过给定点的多项式在 `x` 处的值
+fn interpolate_at(points: &[Share], x: u64) -> Option<u64> {
+    let mut result = 0u64;
+    for (i, pi) in points.iter().enumerate() {
+        let mut numerator = 1u64;
+        let mut denominator = 1u64;
+        for (j, pj) in points.iter().enumerate() {
+            if i != j {
+                numerator = field_mul(numerator, field_sub(x, pj.x));
+                denominator = field_mul(denominator, field_sub(pi.x, pj.x));
+            }
+        }
+        let weight = field_mul(numerator, field_inv(denominator)?);
+        result = field_add(result, field_mul(pi.y, weight));
+    }
+    Some(result)
+}
+
+/// 按字典序前进到下一个组合，没有更多组合时返回 `false`
+fn next_combination(indices: &mut [usize], n: usize) -> bool {
+    let k = indices.len();
+    for i in (0..k).rev() {
+        if indices[i] < n - k + i {
+            indices[i] += 1;
+            for j in i + 1..k {
+                indices[j] = indices[j - 1] + 1;
+            }
+            return true;
+        }
+    }
+    false
+}
@@ -0,0 +1,227 @@
+//! 有限域 GF(p) 上的原始运算
+//!
+//! 这些函数是库内部的实现细节，只有启用 `internals` 特性时才对外公开，
+//! 其签名可能随域的泛型化等重构而改变。
+//!
+//! p = 2^64 - 2^32 + 1 (Goldilocks)。由于 2^64 ≡ 2^32 - 1、2^96 ≡ -1 (mod p)，
+//! 128 位乘积只需几次 64 位加减即可约减，不需要 u128 除法。
+//!
+//! 加、减、乘和约减都用掩码代替分支，执行时间与操作数取值无关；
+//! `field_pow` 的执行路径只取决于指数，秘密指数应使用 `field_pow_ct`。
+
+use super::ntt::{MULTIPLICATIVE_GENERATOR, TWO_ADICITY};
+use super::FIELD_PRIME;
+use crate::utils::constant_time_select;
+
+/// 2^64 mod p = 2^32 - 1
+const EPSILON: u64 = (1 << 32) - 1;
+
+/// 把 128 位整数约减到 [0, p)
+///
+/// 记 x = x_lo + 2^64·x_hi_lo + 2^96·x_hi_hi，则 x ≡ x_lo - x_hi_hi + (2^32 - 1)·x_hi_lo。
+#[inline]
+fn reduce128(x: u128) -> u64 {
+    let (x_lo, x_hi) = (x as u64, (x >> 64) as u64);
+    let x_hi_hi = x_hi >> 32;
+    let x_hi_lo = x_hi & EPSILON;
+
+    // 借位时结果多加了 2^64 ≡ 2^32 - 1；x_hi_hi < 2^32，减去 EPSILON 不会再借位
+    let (t0, borrow) = x_lo.overflowing_sub(x_hi_hi);
+    let t0 = t0.wrapping_sub(EPSILON * borrow as u64);
+    // x_hi_lo·(2^32 - 1) < 2^64，进位时同样补上 2^64 ≡ 2^32 - 1
+    let t1 = x_hi_lo * EPSILON;
+    let (sum, carry) = t0.overflowing_add(t1);
+    let sum = sum.wrapping_add(EPSILON * carry as u64);
+    let (reduced, below) = sum.overflowing_sub(FIELD_PRIME);
+    constant_time_select(reduced, sum, below as u64)
+}
+
+/// 有限域加法
+/// 
+/// 在有限域 GF(p) 中执行加法运算，其中 p 是 FIELD_PRIME。
+/// 溢出的进位参与判断是否减去 p，不需要 u128。
+/// 
+/// # 参数
+/// 
+/// * `a` - 第一个操作数
+/// * `b` - 第二个操作数
+/// 
+/// # 返回值
+/// 
+/// 返回 (a + b) mod p 的结果
+#[inline]
+pub fn field_add(a: u64, b: u64) -> u64 {
+    let (sum, carry) = a.overflowing_add(b);
+    let (reduced, below) = sum.overflowing_sub(FIELD_PRIME);
+    // 真实的和为 sum + carry·2^64，有进位时一定不小于 p
+    constant_time_select(reduced, sum, (below & !carry) as u64)
+}
+
+/// 有限域减法
+/// 
+/// 在有限域 GF(p) 中执行减法运算，其中 p 是 FIELD_PRIME。
+/// 当 a < b 时，结果为 p - (b - a)，确保结果始终为正数。
+/// 
+/// # 参数
+/// 
+/// * `a` - 被减数
+/// * `b` - 减数
+/// 
+/// # 返回值
+/// 
+/// 返回 (a - b) mod p 的结果
+#[inline]
+pub fn field_sub(a: u64, b: u64) -> u64 {
+    let (difference, borrow) = a.overflowing_sub(b);
+    difference.wrapping_add(constant_time_select(0, FIELD_PRIME, borrow as u64))
+}
+
+/// 有限域乘法
+/// 
+/// 在有限域 GF(p) 中执行乘法运算，其中 p 是 FIELD_PRIME。
+/// 在 u128 中相乘后用 Goldilocks 专用约减，不做通用的 u128 取模。
+/// 
+/// # 参数
+/// 
+/// * `a` - 第一个操作数
+/// * `b` - 第二个操作数
+/// 
+/// # 返回值
+/// 
+/// 返回 (a * b) mod p 的结果
+#[inline]
+pub fn field_mul(a: u64, b: u64) -> u64 {
+    reduce128(a as u128 * b as u128)
+}
+
+/// 有限域内积
+/// 
+/// 计算 Σ values[i] * coefficients[i] mod p。每个乘积拆成高、低 64 位分别在
+/// u128 中累加，整个内积只做一次模约减；累加按 4 路独立进行，便于编译器向量化。
+/// 两个切片长度不同时按较短者计算。
+/// 
+/// # 参数
+/// 
+/// * `values` - 域元素
+/// * `coefficients` - 公开系数
+/// 
+/// # 返回值
+/// 
+/// 返回内积 mod p 的结果
+pub fn field_inner_product(values: &[u64], coefficients: &[u64]) -> u64 {
+    const LANES: usize = 4;
+    let mut low = [0u128; LANES];
+    let mut high = [0u128; LANES];
+
+    let len = values.len().min(coefficients.len());
+    let (values, coefficients) = (&values[..len], &coefficients[..len]);
+    let values_chunks = values.chunks_exact(LANES);
+    let coefficient_chunks = coefficients.chunks_exact(LANES);
+    let tail = values_chunks.remainder().iter().zip(coefficient_chunks.remainder());
+    for (v, c) in values_chunks.zip(coefficient_chunks) {
+        for lane in 0..LANES {
+            let product = v[lane] as u128 * c[lane] as u128;
+            low[lane] += product as u64 as u128;
+            high[lane] += product >> 64;
+        }
+    }
+    for (lane, (&v, &c)) in tail.enumerate() {
+        let product = v as u128 * c as u128;
+        low[lane] += product as u64 as u128;
+        high[lane] += product >> 64;
+    }
+
+    let low = reduce128(low.iter().sum::<u128>());
+    let high = reduce128(high.iter().sum::<u128>());
+    // high * 2^64 + low，其中 2^64 mod p = 2^32 - 1
+    field_add(low, field_mul(high, EPSILON))
+}
+
+/// 有限域幂运算
+///
+/// 平方-乘法计算 base^exponent mod p。执行路径只取决于指数，底数可以是秘密。
+pub fn field_pow(mut base: u64, mut exponent: u64) -> u64 {
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = field_mul(result, base);
+        }
+        base = field_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// 常数时间有限域幂运算
+///
+/// 蒙哥马利阶梯计算 base^exponent mod p：固定处理 64 个指数位，
+/// 每一位都执行一次乘法和一次平方，适用于秘密指数。
+pub fn field_pow_ct(base: u64, exponent: u64) -> u64 {
+    let (mut r0, mut r1) = (1, base);
+    for bit in (0..u64::BITS).rev() {
+        let choice = (exponent >> bit) & 1;
+        // choice 为 1 时 (r0, r1) ← (r0·r1, r1²)，否则 (r0², r0·r1)
+        let product = field_mul(r0, r1);
+        let squared = constant_time_select(r0, r1, choice);
+        let square = field_mul(squared, squared);
+        r0 = constant_time_select(square, product, choice);
+        r1 = constant_time_select(product, square, choice);
+    }
+    r0
+}
+
+/// 有限域平方根
+///
+/// p - 1 = (2^32 - 1)·2^32，用 Tonelli-Shanks 算法求解。两个平方根中返回较小的一个，
+/// 所有参与方对同一个数得到相同的根。
+///
+/// # 返回值
+///
+/// a 是二次剩余时返回 Some(根)，否则返回 None
+pub fn field_sqrt(a: u64) -> Option<u64> {
+    if a == 0 {
+        return Some(0);
+    }
+    if field_pow(a, (FIELD_PRIME - 1) / 2) != 1 {
+        return None;
+    }
+
+    let q = (FIELD_PRIME - 1) >> TWO_ADICITY;
+    let mut m = TWO_ADICITY;
+    let mut c = field_pow(MULTIPLICATIVE_GENERATOR, q);
+    let mut t = field_pow(a, q);
+    let mut root = field_pow(a, q.div_ceil(2));
+    while t != 1 {
+        // 最小的 i 使 t^(2^i) = 1
+        let mut i = 0;
+        let mut power = t;
+        while power != 1 {
+            power = field_mul(power, power);
+            i += 1;
+        }
+        let b = (0..m - i - 1).fold(c, |b, _| field_mul(b, b));
+        m = i;
+        c = field_mul(b, b);
+        t = field_mul(t, c);
+        root = field_mul(root, b);
+    }
+    Some(root.min(FIELD_PRIME - root))
+}
+
+/// 有限域乘法逆元
+/// 
+/// 计算元素 a 在有限域 GF(p) 中的乘法逆元，即找到 b 使得 a * b ≡ 1 (mod p)。
+/// 由费马小定理 a^(p-2) ≡ a^(-1) 计算。指数公开，运算序列与 `a` 无关，
+/// 不像扩展欧几里德算法那样按 `a` 的取值分支。
+/// 
+/// # 参数
+/// 
+/// * `a` - 要计算逆元的元素
+/// 
+/// # 返回值
+/// 
+/// 如果逆元存在则返回 Some(逆元)，否则（a ≡ 0）返回 None
+pub fn field_inv(a: u64) -> Option<u64> {
+    let inverse = field_pow(a, FIELD_PRIME - 2);
+    (inverse != 0).then_some(inverse)
+}
@@ -0,0 +1,193 @@
+//! # 安全内积与矩阵乘法 (Secure Dot Product and Matrix Multiplication)
+//!
+//! 逐元素调用 `secure_multiply` 时每个乘积都单独公开 d、e 并分配新的份额，
+//! 向量稍长就无法使用。本模块一次性处理整个向量或矩阵：
+//!
+//! - 所有乘积的 d = x - a、e = y - b 打包在同一条消息中公开，整个运算只需一轮通信
+//! - 公开之后各方在本地把 c_i + d_i·b_i + e_i·a_i + d_i·e_i 直接累加到自己的份额上，
+//!   内积只产生一个结果分享，不为中间乘积分配份额
+//!
+//! 每个标量乘积消耗一个 Beaver 三元组：长度为 k 的内积消耗 k 个，
+//! m×k 与 k×n 的矩阵乘法消耗 m·k·n 个，按 (行, 列, 求和下标) 的顺序使用。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::{BeaverTripleGenerator, TrustedPartyBeaverGenerator};
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let share = |values: &[u64]| values.iter()
+//!     .map(|value| ShamirSecretSharing::share(value, 2, 3))
+//!     .collect::<mpc_api::Result<Vec<_>>>();
+//! let x = share(&[1, 2, 3])?;
+//! let y = share(&[4, 5, 6])?;
+//!
+//! let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None)?;
+//! let triples = generator.generate_batch(3)?;
+//! let output = secure_dot_product(&x, &y, &triples, 2)?;
+//! assert_eq!(ShamirSecretSharing::reconstruct(&output.result[..2], 2)?, 32);
+//! assert_eq!(output.stats.rounds, 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{field_add, Share};
+use crate::beaver_triples::{open_value, BeaverTriple, CompleteBeaverTriple};
+use crate::protocols::stats::{ProtocolOutput, StatsRecorder};
+use crate::{MpcError, Result};
+
+/// 秘密分享的矩阵：`matrix[row][column]` 是该元素在各参与方处的分享
+pub type SharedMatrix = Vec<Vec<Vec<Share>>>;
+
+/// 计算秘密分享向量的内积 Σ x_i·y_i
+///
+/// # 参数
+/// - `x_shares`: 向量 x 每个元素的分享
+/// - `y_shares`: 向量 y 每个元素的分享
+/// - `triples`: 每个元素一个 Beaver 三元组
+/// - `threshold`: 重构门限
+///
+/// # 返回值
+/// 返回内积在各参与方处的分享和执行统计
+pub fn secure_dot_product(
+    x_shares: &[Vec<Share>],
+    y_shares: &[Vec<Share>],
+    triples: &[CompleteBeaverTriple],
+    threshold: usize,
+) -> Result<ProtocolOutput<Vec<Share>>> {
+    if x_shares.is_empty() || x_shares.len() != y_shares.len() || x_shares.len() != triples.len() {
+        return Err(MpcError::ProtocolError(
+            "Dot product needs non-empty vectors of equal length and one triple per element".to_string()
+        ));
+    }
+    let parties = party_coordinates(x_shares.iter().chain(y_shares))?;
+
+    let mut recorder = StatsRecorder::start();
+    let opened = x_shares.iter()
+        .zip(y_shares)
+        .zip(triples)
+        .map(|((x, y), triple)| open_masks(x, y, triple, threshold))
+        .collect::<Result<Vec<_>>>()?;
+    record_round(&mut recorder, opened.len(), parties.len());
+    Ok(recorder.finish(accumulate(&opened, &parties)))
+}
+
+/// 计算秘密分享矩阵的乘积 X·Y
+///
+/// # 参数
+/// - `x`: m×k 矩阵
+/// - `y`: k×n 矩阵
+/// - `triples`: m·k·n 个 Beaver 三元组，结果 (i, j) 使用 `triples[(i·n + j)·k..][..k]`
+/// - `threshold`: 重构门限
+///
+/// # 返回值
+/// 返回 m×n 结果矩阵的分享和执行统计；所有元素在同一轮中计算
+pub fn secure_matmul(
+    x: &[Vec<Vec<Share>>],
+    y: &[Vec<Vec<Share>>],
+    triples: &[CompleteBeaverTriple],
+    threshold: usize,
+) -> Result<ProtocolOutput<SharedMatrix>> {
+    let (m, k) = matrix_shape(x)?;
+    let (rows, n) = matrix_shape(y)?;
+    if rows != k {
+        return Err(MpcError::ProtocolError(format!(
+            "Cannot multiply a {}x{} matrix by a {}x{} matrix", m, k, rows, n
+        )));
+    }
+    if triples.len() != m * k * n {
+        return Err(MpcError::ProtocolError(format!(
+            "Matrix product needs {} triples, got {}", m * k * n, triples.len()
+        )));
+    }
+    let parties = party_coordinates(x.iter().chain(y).flatten())?;
+
+    let mut recorder = StatsRecorder::start();
+    let mut triples = triples.chunks_exact(k);
+    let product = x.iter()
+        .map(|row| {
+            (0..n)
+                .map(|j| {
+                    let opened = row.iter()
+                        .zip(y)
+                        .zip(triples.next().expect("one chunk of triples per entry"))
+                        .map(|((x, y_row), triple)| open_masks(x, &y_row[j], triple, threshold))
+                        .collect::<Result<Vec<_>>>()?;
+                    Ok(accumulate(&opened, &parties))
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<SharedMatrix>>()?;
+    record_round(&mut recorder, m * k * n, parties.len());
+    Ok(recorder.finish(product))
+}
+
+/// 一次乘法公开 d、e 之后的状态
+struct Opened<'a> {
+    /// 各参与方的三元组分享，顺序与输入分享一致
+    triples: Vec<&'a BeaverTriple>,
+    d: u64,
+    e: u64,
+}
+
+/// 各方计算并公开 d = x - a、e = y - b
+fn open_masks<'a>(x: &[Share], y: &[Share], triple: &'a CompleteBeaverTriple, threshold: usize) -> Result<Opened<'a>> {
+    let triples = x.iter()
+        .map(|share| {
+            triple.shares.values()
+                .find(|triple| triple.a.x == share.x)
+                .ok_or(MpcError::InsufficientShares)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let (d_shares, e_shares): (Vec<Share>, Vec<Share>) = triples.iter()
+        .zip(x.iter().zip(y))
+        .map(|(triple, (x, y))| triple.mask(x, y))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    Ok(Opened { d: open_value(&d_shares, threshold)?, e: open_value(&e_shares, threshold)?, triples })
+}
+
+/// 每一方把所有乘积的份额累加到一个结果份额上
+fn accumulate(opened: &[Opened], parties: &[u64]) -> Vec<Share> {
+    parties.iter()
+        .enumerate()
+        .map(|(party, &x)| {
+            let y = opened.iter().fold(0, |sum, opened| {
+                field_add(sum, opened.triples[party].combine(opened.d, opened.e).y)
+            });
+            Share::new(x, y)
+        })
+        .collect()
+}
+
+/// 所有元素必须按相同顺序持有相同参与方的分享
+fn party_coordinates<'a>(mut elements: impl Iterator<Item = &'a Vec<Share>>) -> Result<Vec<u64>> {
+    let first: Vec<u64> = elements.next().map(|shares| shares.iter().map(|share| share.x).collect()).unwrap_or_default();
+    for element in elements {
+        if element.len() != first.len() || element.iter().zip(&first).any(|(share, &x)| share.x != x) {
+            return Err(MpcError::InvalidSecretShare);
+        }
+    }
+    Ok(first)
+}
+
+fn matrix_shape(matrix: &[Vec<Vec<Share>>]) -> Result<(usize, usize)> {
+    let columns = matrix.first().map_or(0, Vec::len);
+    if columns == 0 || matrix.iter().any(|row| row.len() != columns) {
+        return Err(MpcError::ProtocolError("Matrix must be non-empty and rectangular".to_string()));
+    }
+    Ok((matrix.len(), columns))
+}
+
+/// 所有 d、e 打包在一轮中公开
+fn record_round(recorder: &mut StatsRecorder, products: usize, party_count: usize) {
+    let n = party_count as u64;
+    let bytes = 2 * products as u64 * n * n.saturating_sub(1) * std::mem::size_of::<u64>() as u64;
+    let stats = recorder.stats_mut();
+    stats.record_rounds(1);
+    stats.record_sent(bytes);
+    stats.record_received(bytes);
+    stats.record_preprocessing(products);
+}
@@ -0,0 +1,57 @@
+//! # 公开系数线性组合 (Public Linear Combinations)
+//!
+//! 秘密乘以公开常数再求和是最常见的本地运算（加权求和、多项式求值、矩阵乘向量）。
+//! 逐个调用 `scalar_mul` / `add_shares` 每一步都要做一次模约减并分配新的份额；
+//! `linear_combination_batch` 按参与方把所有项收集在一起，用 `field_inner_product`
+//! 一次算出每一方的份额。整个过程不需要 Beaver 三元组，也不需要通信。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let terms = [3u64, 5, 7].iter()
+//!     .map(|value| ShamirSecretSharing::share(value, 2, 3))
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//!
+//! // 2·3 + 10·5 + 1·7 = 63
+//! let combined = linear_combination_batch(&terms, &[2, 10, 1])?;
+//! assert_eq!(ShamirSecretSharing::reconstruct(&combined[..2], 2)?, 63);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{Share, field_inner_product};
+use crate::{MpcError, Result};
+
+/// 计算 Σ coefficients[i]·[terms[i]]
+///
+/// # 参数
+/// - `terms`: 每一项在各参与方处的分享，所有项的参与方及顺序必须一致
+/// - `coefficients`: 每一项的公开系数
+///
+/// # 返回值
+/// 返回线性组合在各参与方处的分享
+pub fn linear_combination_batch(terms: &[Vec<Share>], coefficients: &[u64]) -> Result<Vec<Share>> {
+    if terms.is_empty() || terms.len() != coefficients.len() {
+        return Err(MpcError::ProtocolError("Invalid linear combination parameters".to_string()));
+    }
+    let parties = &terms[0];
+    for term in &terms[1..] {
+        if term.len() != parties.len() || term.iter().zip(parties).any(|(a, b)| a.x != b.x) {
+            return Err(MpcError::InvalidSecretShare);
+        }
+    }
+
+    let mut column = vec![0u64; terms.len()];
+    Ok(parties.iter()
+        .enumerate()
+        .map(|(party, share)| {
+            for (value, term) in column.iter_mut().zip(terms) {
+                *value = term[party].y;
+            }
+            Share::new(share.x, field_inner_product(&column, coefficients))
+        })
+        .collect())
+}
@@ -1,7 +1,7 @@
 //! # 秘密分享模块 (Secret Sharing Module)
 //! 
 //! 本模块实现了各种秘密分享方案，主要包括 Shamir 秘密分享和加法秘密分享。
-//! 所有运算都在有限域 GF(p) 上进行，其中 p = 2^61 - 1。
+//! 所有运算都在有限域 GF(p) 上进行，其中 p = 2^64 - 2^32 + 1 (Goldilocks)。
 //! 
 //! ## 核心概念 (Core Concepts)
 //! 
@@ -13,18 +13,28 @@
 //! - 少于 t 个分享无法获得秘密的任何信息
 //! 
 //! ### 有限域运算
-//! 使用素数域 GF(p)，其中 p = 2305843009213693951 = 2^61 - 1
+//! 使用素数域 GF(p)，其中 p = 18446744069414584321 = 2^64 - 2^32 + 1
 //! 提供了安全的模运算，包括：
 //! - 加法：(a + b) mod p
 //! - 减法：(a - b) mod p (处理负数)
-//! - 乘法：(a * b) mod p
+//! - 乘法：(a * b) mod p (利用 2^64 ≡ 2^32 - 1 的专用约减)
 //! - 逆元：a^(-1) mod p (使用扩展欧几里德算法)
+//! - 数论变换：p - 1 含因子 2^32，`NttDomain` 支持长度至 2^31 的 NTT
 //! 
 //! ## 安全性质 (Security Properties)
 //! 
 //! 1. **完美保密性**: 任何少于门限值的分享都不泄露秘密信息
 //! 2. **同态性**: 支持在分享上直接进行加法和标量乘法
-//! 3. **可验证性**: 可以验证分享的正确性 (通过多项式承诺等方法)
+//! 3. **可验证性**: 可以验证分享的正确性，并通过 `diagnose_shares` 定位可疑份额；
+//!    `vss` 子模块提供 Feldman / Pedersen 可验证秘密分享，持有者只用自己的份额即可验证，并支持投诉处理
+//! 4. **受控公开**: 通过 `RevealGate` 在重构前执行门限、法定人数与策略检查并记录审计
+//! 5. **大数据分享**: 通过 `ChunkedSecretSharing` 以"分块加密 + 纠删码 + 密钥分享"方式分享大型秘密
+//! 6. **批量线性组合**: `linear_combination_batch` 一次计算公开系数的加权和，无需通信
+//! 7. **共享位**: `bits` 子模块批量生成共享随机位、对分享做位分解（`bit_decompose`）和位组合（`bits_to_share`）
+//! 8. **安全线性代数**: `secure_dot_product` / `secure_matmul` 用一批 Beaver 三元组在一轮内计算内积和矩阵乘积
+//! 9. **主动刷新**: `proactive` 子模块按纪元叠加各方的零分享刷新份额，全程不重构秘密
+//! 10. **门限签名**: `ThresholdBls` 由 Shamir 私钥份额生成部分签名，在指数上插值聚合为 BLS 风格签名
+//! 11. **泛型素数域**: `prime_field` 子模块的 `Field` trait 与 `Fp<P>` 让 `Share<F>` 和 Shamir 分享（`Shamir<F>`）在其他素数上实例化
 //! 
 //! ## 使用示例 (Usage Examples)
 //! 
@@ -152,10 +162,43 @@
 pub mod shamir;
 pub mod additive;
 pub mod replicated;
+pub mod reveal;
+pub mod chunked;
+pub mod diagnostics;
+pub mod threshold_conversion;
+pub mod program;
+pub mod linear_combination;
+pub mod ntt;
+pub mod bits;
+pub mod linear_algebra;
+pub mod vss;
+pub mod proactive;
+pub mod threshold_signature;
+pub mod prime_field;
+mod field;
 
 pub use shamir::*;
 pub use additive::*;
 pub use replicated::*;
+pub use reveal::*;
+pub use chunked::*;
+pub use diagnostics::*;
+pub use threshold_conversion::*;
+pub use program::*;
+pub use linear_combination::*;
+pub use ntt::*;
+pub use bits::*;
+pub use linear_algebra::*;
+pub use vss::*;
+pub use proactive::*;
+pub use threshold_signature::*;
+pub use prime_field::*;
+
+// 原始域运算不属于稳定 API，只有启用 `internals` 特性时才公开
+#[cfg(feature = "internals")]
+pub use field::{field_add, field_inner_product, field_inv, field_mul, field_pow, field_pow_ct, field_sqrt, field_sub};
+#[cfg(not(feature = "internals"))]
+pub(crate) use field::{field_add, field_inner_product, field_inv, field_mul, field_pow, field_pow_ct, field_sqrt, field_sub};
 
 // 重新导出主要的 trait (traits are defined in this module)
 // pub use {SecretSharing, AdditiveSecretSharing, MultiplicationSecretSharing};
@@ -167,27 +210,28 @@ use rand::Rng;
 // 使用 u64 有限域运算
 /// 有限域的素数模数
 /// 
-/// 使用 18446744069414584321 作为有限域 GF(p) 的模数。这是一个大素数，
-/// 提供了足够的安全性和计算效率。选择这个素数的原因：
-/// 1. 是一个大素数，提供强安全性
-/// 2. 在 u64 范围内，避免溢出问题
-/// 3. 提供约 64 位的安全强度
-/// 4. 用户指定的素数值，满足特定应用需求
-pub const FIELD_PRIME: u64 = 18446744069414584321; // 用户指定的大素数
+/// 使用 18446744069414584321 = 2^64 - 2^32 + 1 (Goldilocks) 作为有限域 GF(p) 的模数。
+/// 选择这个素数的原因：
+/// 1. 在 u64 范围内，域元素占一个机器字
+/// 2. 2^64 ≡ 2^32 - 1 (mod p)，乘积约减只需移位和加减，比通用的 u128 取模快得多
+/// 3. p - 1 含因子 2^32，支持大长度的 NTT
+/// 4. 被许多 MPC/ZK 系统采用，便于互通
+pub const FIELD_PRIME: u64 = 18446744069414584321; // 2^64 - 2^32 + 1
 
 /// 秘密分享结构
 /// 
 /// 表示一个秘密分享，包含参与方索引和分享值。
 /// 在 Shamir 秘密分享中，这对应于多项式上的一个点 (x, y)。
+/// 域元素类型默认为 `u64`（Goldilocks 域），其他素数域见 `prime_field`。
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct Share {
+pub struct Share<F = u64> {
     /// 参与方索引（多项式的 x 坐标）
-    pub x: u64,
+    pub x: F,
     /// 分享值（多项式的 y 坐标）
-    pub y: u64,
+    pub y: F,
 }
 
-impl Share {
+impl<F> Share<F> {
     /// 创建新的分享
     /// 
     /// # 参数
@@ -198,91 +242,11 @@ impl Share {
     /// # 返回值
     /// 
     /// 返回新创建的分享实例
-    pub fn new(x: u64, y: u64) -> Self {
+    pub fn new(x: F, y: F) -> Self {
         Self { x, y }
     }
 }
 
-/// 有限域加法
-/// 
-/// 在有限域 GF(p) 中执行加法运算，其中 p 是 FIELD_PRIME。
-/// 使用 u128 来避免溢出问题。
-/// 
-/// # 参数
-/// 
-/// * `a` - 第一个操作数
-/// * `b` - 第二个操作数
-/// 
-/// # 返回值
-/// 
-/// 返回 (a + b) mod p 的结果
-#[inline]
-pub fn field_add(a: u64, b: u64) -> u64 {
-    let sum = a as u128 + b as u128;
-    if sum >= FIELD_PRIME as u128 {
-        (sum - FIELD_PRIME as u128) as u64
-    } else {
-        sum as u64
-    }
-}
-
-/// 有限域减法
-/// 
-/// 在有限域 GF(p) 中执行减法运算，其中 p 是 FIELD_PRIME。
-/// 当 a < b 时，结果为 p - (b - a)，确保结果始终为正数。
-/// 
-/// # 参数
-/// 
-/// * `a` - 被减数
-/// * `b` - 减数
-/// 
-/// # 返回值
-/// 
-/// 返回 (a - b) mod p 的结果
-#[inline]
-pub fn field_sub(a: u64, b: u64) -> u64 {
-    if a >= b {
-        a - b
-    } else {
-        FIELD_PRIME - (b - a)
-    }
-}
-
-/// 有限域乘法
-/// 
-/// 在有限域 GF(p) 中执行乘法运算，其中 p 是 FIELD_PRIME。
-/// 使用 u128 来避免溢出问题。
-/// 
-/// # 参数
-/// 
-/// * `a` - 第一个操作数
-/// * `b` - 第二个操作数
-/// 
-/// # 返回值
-/// 
-/// 返回 (a * b) mod p 的结果
-#[inline]
-pub fn field_mul(a: u64, b: u64) -> u64 {
-    let product = (a as u128 * b as u128) % FIELD_PRIME as u128;
-    product as u64
-}
-
-/// 有限域乘法逆元
-/// 
-/// 计算元素 a 在有限域 GF(p) 中的乘法逆元，即找到 b 使得 a * b ≡ 1 (mod p)。
-/// 使用扩展欧几里德算法实现。
-/// 
-/// # 参数
-/// 
-/// * `a` - 要计算逆元的元素
-/// 
-/// # 返回值
-/// 
-/// 如果逆元存在则返回 Some(逆元)，否则返回 None
-pub fn field_inv(a: u64) -> Option<u64> {
-    extended_gcd(a, FIELD_PRIME).map(|(inv, _)| inv)
-}
-
 /// 验证有限域元素的有效性
 /// 
 /// 检查给定的数值是否在有效的有限域范围内。
@@ -426,56 +390,6 @@ impl SecretSharingStats {
     }
 }
 
-/// 扩展欧几里德算法
-/// 
-/// 计算 gcd(a, b) 以及满足 ax + by = gcd(a, b) 的整数 x, y。
-/// 用于计算模逆元。
-/// 
-/// # 参数
-/// 
-/// * `a` - 第一个整数
-/// * `b` - 第二个整数
-/// 
-/// # 返回值
-/// 
-/// 返回 (x, y) 使得 ax + by = gcd(a, b)，如果 gcd != 1 则返回 None
-fn extended_gcd(a: u64, b: u64) -> Option<(u64, u64)> {
-    if a == 0 {
-        return Some((0, 1));
-    }
-    
-    let (mut old_r, mut r) = (a as i128, b as i128);
-    let (mut old_s, mut s) = (1i128, 0i128);
-    let (mut old_t, mut t) = (0i128, 1i128);
-    
-    while r != 0 {
-        let quotient = old_r / r;
-        
-        let temp_r = r;
-        r = old_r - quotient * r;
-        old_r = temp_r;
-        
-        let temp_s = s;
-        s = old_s - quotient * s;
-        old_s = temp_s;
-        
-        let temp_t = t;
-        t = old_t - quotient * t;
-        old_t = temp_t;
-    }
-    
-    if old_r == 1 {
-        let result = if old_s < 0 {
-            (old_s + b as i128) as u64
-        } else {
-            old_s as u64
-        };
-        Some((result, old_t as u64))
-    } else {
-        None
-    }
-}
-
 /// 秘密分享协议 trait
 /// 
 /// 定义了所有秘密分享方案必须实现的基本操作。
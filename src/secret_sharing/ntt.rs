@@ -0,0 +1,103 @@
+//! # 数论变换 (Number Theoretic Transform)
+//!
+//! 域模数 p = 2^64 - 2^32 + 1 满足 2^32 | p - 1，因此域中存在 2^32 次本原单位根，
+//! 长度不超过 2^32 的 2 的幂都可以做基 2 的 NTT。`NttDomain` 预先计算单位根的幂（长度至 2^31），
+//! 在 O(n log n) 内完成多项式系数与单位根处取值之间的转换，
+//! `polynomial_multiply` 基于它计算多项式乘积。
+//!
+//! 单位根由乘法生成元 7 导出，与其他使用 Goldilocks 域的 MPC/ZK 系统一致，
+//! 同一长度的变换结果可以直接互通。变换本身由 `utils::math::ntt::NttPlan` 完成，
+//! 本模块只固定模数和单位根。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let domain = NttDomain::new(3)?;
+//! let mut values = vec![1, 2, 3, 0, 0, 0, 0, 0];
+//! domain.forward(&mut values)?;
+//! // 第 i 个值是多项式在 ω^i 处的取值，ω^0 = 1
+//! assert_eq!(values[0], 6);
+//! domain.inverse(&mut values)?;
+//! assert_eq!(values, vec![1, 2, 3, 0, 0, 0, 0, 0]);
+//!
+//! // (1 + x)(1 - x) = 1 - x^2
+//! let product = polynomial_multiply(&[1, 1], &[1, FIELD_PRIME - 1])?;
+//! assert_eq!(product, vec![1, 0, FIELD_PRIME - 1]);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{field_pow, FIELD_PRIME};
+use crate::utils::math::ntt::{polynomial_multiply_mod, NttPlan};
+use crate::{MpcError, Result};
+
+/// p - 1 中因子 2 的次数
+pub const TWO_ADICITY: usize = 32;
+
+/// 域的乘法生成元
+pub const MULTIPLICATIVE_GENERATOR: u64 = 7;
+
+/// 2^log_size 次本原单位根
+///
+/// # 参数
+/// - `log_size`: 变换长度的对数，不超过 `TWO_ADICITY`
+pub fn root_of_unity(log_size: usize) -> Result<u64> {
+    if log_size > TWO_ADICITY {
+        return Err(MpcError::CryptographicError(format!(
+            "NTT size 2^{} exceeds the field's two-adicity 2^{}", log_size, TWO_ADICITY
+        )));
+    }
+    Ok(field_pow(MULTIPLICATIVE_GENERATOR, (FIELD_PRIME - 1) >> log_size))
+}
+
+/// 长度为 2^log_size 的 NTT 定义域
+#[derive(Debug, Clone)]
+pub struct NttDomain {
+    plan: NttPlan,
+}
+
+impl NttDomain {
+    /// 创建长度为 2^log_size 的定义域
+    ///
+    /// 计划同时支持负循环卷积，需要 2^(log_size+1) 次单位根，因此 `log_size` 小于 `TWO_ADICITY`。
+    pub fn new(log_size: usize) -> Result<Self> {
+        let psi = root_of_unity(log_size + 1)?;
+        Ok(Self { plan: NttPlan::from_root(FIELD_PRIME, 1 << log_size, psi) })
+    }
+
+    /// 能容纳 `len` 个系数的最小定义域
+    pub fn for_len(len: usize) -> Result<Self> {
+        Self::new(len.max(1).next_power_of_two().trailing_zeros() as usize)
+    }
+
+    /// 变换长度
+    pub fn size(&self) -> usize {
+        self.plan.size()
+    }
+
+    /// 本原单位根 ω
+    pub fn root(&self) -> u64 {
+        self.plan.root()
+    }
+
+    /// 系数 → 取值：values[i] 变为多项式在 ω^i 处的取值
+    pub fn forward(&self, values: &mut [u64]) -> Result<()> {
+        self.plan.forward(values)
+    }
+
+    /// 取值 → 系数，`forward` 的逆变换
+    pub fn inverse(&self, values: &mut [u64]) -> Result<()> {
+        self.plan.inverse(values)
+    }
+}
+
+/// 多项式乘法（系数从低次到高次）
+///
+/// # 返回值
+/// 返回长度为 a.len() + b.len() - 1 的乘积系数；任一输入为空时返回空向量
+pub fn polynomial_multiply(a: &[u64], b: &[u64]) -> Result<Vec<u64>> {
+    polynomial_multiply_mod(a, b, FIELD_PRIME)
+}
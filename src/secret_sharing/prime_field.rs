@@ -0,0 +1,330 @@
+//! # 泛型素数域 (Generic Prime Fields)
+//!
+//! `field_add` 等原始运算固定在 Goldilocks 域上。本模块用 `Field` trait 抽象素数域，
+//! `Fp<P>` 以常量泛型给出模数，同一份协议代码可以在不同的素数上实例化：
+//!
+//! - `u64`: Goldilocks 域的原始表示，运算就是 `field_add`、`field_mul` 等
+//! - `Goldilocks`: 同一个域的 `Fp` 形式，乘法走 `field_mul` 的专用约减
+//! - `Mersenne127`: p = 2^127 - 1，统计安全性要求更高的场景
+//! - `Fp65537`: p = 2^16 + 1，与 BFV 的默认明文模数一致
+//!
+//! `Share<F>`、`Shamir<F>`（`ShamirSecretSharing` 即 `Shamir<u64>`）、
+//! `beaver_triples::BeaverTriple<F>` 和 OLE（`oblivious_transfer::ole::FieldOleSender`）
+//! 都以 `F: Field` 为参数，类型参数默认为 `u64`。
+//!
+//! ## 实现说明
+//!
+//! `Fp<P>` 的元素以规范形式存放在 u128 中，模数必须是小于 2^127 的奇素数（编译期检查范围和奇偶，
+//! 素性由使用者保证）。加、减和乘法都不含依赖操作数取值的分支：模数小于 2^64 时
+//! 乘积直接在 u128 中约减，更大的模数使用逐位的移位相加。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let secret = Mersenne127::from_u128(1 << 100);
+//! let shares = Shamir::<Mersenne127>::share(&secret, 2, 3)?;
+//! assert_eq!(Shamir::reconstruct(&shares[1..], 2)?, secret);
+//!
+//! let x = Fp65537::from_u128(300);
+//! assert_eq!(x * x.inverse().unwrap(), Fp65537::ONE);
+//! assert_eq!((x * x).to_u128(), 90000 - 65537);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{field_add, field_inv, field_mul, field_sub, FIELD_PRIME};
+use crate::utils::math::ntt::multipoint_evaluate;
+use crate::utils::memory::Zeroize;
+use rand::Rng;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// 批量求值改用子积树的最小系数个数（门限）
+///
+/// 子积树的代价与门限基本无关，`ntt_benchmarks` 中门限约 4096 时两者持平。
+const MULTIPOINT_MIN_COEFFICIENTS: usize = 4096;
+
+/// 素数域
+///
+/// 运算以方法给出，`u64` 也能实现该 trait；`Fp<P>` 另外实现了 `+`、`-`、`*` 运算符。
+pub trait Field:
+    Copy
+    + fmt::Debug
+    + fmt::Display
+    + Default
+    + Eq
+    + Hash
+    + Send
+    + Sync
+    + 'static
+    + Zeroize
+    + Serialize
+    + DeserializeOwned
+{
+    /// 域的特征 p
+    const MODULUS: u128;
+    /// 加法单位元
+    const ZERO: Self;
+    /// 乘法单位元
+    const ONE: Self;
+
+    /// 把任意整数约简到域中
+    fn from_u128(value: u128) -> Self;
+
+    /// 规范表示，位于 [0, p)
+    fn to_u128(self) -> u128;
+
+    /// 均匀随机的域元素
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self;
+
+    /// 域加法
+    fn add(self, other: Self) -> Self;
+
+    /// 域减法
+    fn sub(self, other: Self) -> Self;
+
+    /// 域乘法
+    fn mul(self, other: Self) -> Self;
+
+    /// 加法逆元
+    fn neg(self) -> Self {
+        Self::ZERO.sub(self)
+    }
+
+    /// 乘法逆元，零没有逆元
+    fn inverse(self) -> Option<Self>;
+
+    /// 平方-乘法计算 self^exponent，执行路径只取决于指数
+    fn pow(self, exponent: u128) -> Self {
+        let (mut result, mut base, mut exponent) = (Self::ONE, self, exponent);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul(base);
+            }
+            base = base.mul(base);
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// 是否为零
+    fn is_zero(self) -> bool {
+        self == Self::ZERO
+    }
+
+    /// 快速多点求值
+    ///
+    /// 返回 `None` 时调用方逐点使用霍纳方法；模数支持 NTT 的域可以覆盖。
+    fn multipoint_evaluate(_coefficients: &[Self], _xs: &[Self]) -> Option<Vec<Self>> {
+        None
+    }
+}
+
+/// Goldilocks 域的原始表示，运算即 `field_add`、`field_mul` 等
+impl Field for u64 {
+    const MODULUS: u128 = FIELD_PRIME as u128;
+    const ZERO: Self = 0;
+    const ONE: Self = 1;
+
+    fn from_u128(value: u128) -> Self {
+        (value % FIELD_PRIME as u128) as u64
+    }
+
+    fn to_u128(self) -> u128 {
+        self as u128
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        rng.gen_range(0..FIELD_PRIME)
+    }
+
+    fn add(self, other: Self) -> Self {
+        field_add(self, other)
+    }
+
+    fn sub(self, other: Self) -> Self {
+        field_sub(self, other)
+    }
+
+    fn mul(self, other: Self) -> Self {
+        field_mul(self, other)
+    }
+
+    fn inverse(self) -> Option<Self> {
+        field_inv(self)
+    }
+
+    fn multipoint_evaluate(coefficients: &[Self], xs: &[Self]) -> Option<Vec<Self>> {
+        // FIELD_PRIME 满足 2^32 | p - 1，份额数量在该范围内时不会出错
+        if coefficients.len() < MULTIPOINT_MIN_COEFFICIENTS {
+            return None;
+        }
+        multipoint_evaluate(coefficients, xs, FIELD_PRIME).ok()
+    }
+}
+
+/// 模数为 `P` 的素数域 GF(P)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fp<const P: u128>(u128);
+
+/// Goldilocks 域，与 `FIELD_PRIME` 相同
+pub type Goldilocks = Fp<{ FIELD_PRIME as u128 }>;
+
+/// 梅森素数域 GF(2^127 - 1)
+pub type Mersenne127 = Fp<{ (1 << 127) - 1 }>;
+
+/// GF(65537)，BFV 的默认明文模数
+pub type Fp65537 = Fp<65537>;
+
+impl<const P: u128> Fp<P> {
+    /// 在单态化时检查模数范围：加法要求 2P 不溢出 u128
+    const VALID_MODULUS: () = assert!(P > 2 && P % 2 == 1 && P < 1 << 127, "modulus must be an odd prime below 2^127");
+
+    /// 把任意整数约简到域中
+    pub const fn new(value: u128) -> Self {
+        let () = Self::VALID_MODULUS;
+        Self(value % P)
+    }
+}
+
+impl<const P: u128> Field for Fp<P> {
+    const MODULUS: u128 = P;
+    const ZERO: Self = Self::new(0);
+    const ONE: Self = Self::new(1);
+
+    fn from_u128(value: u128) -> Self {
+        Self::new(value)
+    }
+
+    fn to_u128(self) -> u128 {
+        self.0
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::new(rng.gen_range(0..P))
+    }
+
+    fn add(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn sub(self, other: Self) -> Self {
+        self - other
+    }
+
+    fn mul(self, other: Self) -> Self {
+        self * other
+    }
+
+    fn inverse(self) -> Option<Self> {
+        // 费马小定理：指数公开，运算序列与元素取值无关
+        let inverse = self.pow(P - 2);
+        (!inverse.is_zero()).then_some(inverse)
+    }
+}
+
+impl<const P: u128> Zeroize for Fp<P> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<const P: u128> From<u64> for Fp<P> {
+    fn from(value: u64) -> Self {
+        Self::new(value as u128)
+    }
+}
+
+impl<const P: u128> fmt::Display for Fp<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u128> Add for Fp<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(add_mod(self.0, other.0, P))
+    }
+}
+
+impl<const P: u128> Sub for Fp<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(sub_mod(self.0, other.0, P))
+    }
+}
+
+impl<const P: u128> Neg for Fp<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl<const P: u128> Mul for Fp<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        if P == FIELD_PRIME as u128 {
+            return Self(field_mul(self.0 as u64, other.0 as u64) as u128);
+        }
+        if P <= u64::MAX as u128 {
+            return Self(self.0 * other.0 % P);
+        }
+        Self(mul_mod_wide(self.0, other.0, P))
+    }
+}
+
+impl<const P: u128> Serialize for Fp<P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, const P: u128> Deserialize<'de> for Fp<P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = u128::deserialize(deserializer)?;
+        if value >= P {
+            return Err(D::Error::custom("field element is not below the modulus"));
+        }
+        Ok(Self(value))
+    }
+}
+
+/// `flag` 为真时全 1，否则全 0
+fn mask(flag: bool) -> u128 {
+    0u128.wrapping_sub(flag as u128)
+}
+
+/// 模加，要求 a, b < p < 2^127
+fn add_mod(a: u128, b: u128, p: u128) -> u128 {
+    let sum = a + b;
+    let (reduced, below) = sum.overflowing_sub(p);
+    reduced ^ ((reduced ^ sum) & mask(below))
+}
+
+/// 模减，要求 a, b < p
+fn sub_mod(a: u128, b: u128, p: u128) -> u128 {
+    let (difference, borrow) = a.overflowing_sub(b);
+    difference.wrapping_add(p & mask(borrow))
+}
+
+/// 模乘，要求 a, b < p < 2^127：从高位起逐位移位相加，每一位都执行同样的运算
+fn mul_mod_wide(a: u128, b: u128, p: u128) -> u128 {
+    let mut result = 0;
+    for bit in (0..u128::BITS).rev() {
+        result = add_mod(result, result, p);
+        result = add_mod(result, a & mask((b >> bit) & 1 == 1), p);
+    }
+    result
+}
@@ -0,0 +1,200 @@
+//! # 主动秘密分享 (Proactive Secret Sharing)
+//!
+//! 按纪元定期刷新 Shamir 份额：秘密保持不变，而每个纪元的份额彼此独立，
+//! 攻击者必须在同一个纪元内攻破至少 t 方才能得到秘密。
+//! `ShamirSecretSharing::refresh_shares` 会先在本地重构秘密，只适合由可信方持有全部份额的场景；
+//! 本模块的刷新全程不在任何一处重构秘密。
+//!
+//! ## 协议概述
+//!
+//! 1. **分发**: 每一方用 `ShamirSecretSharing::generate_zero_shares` 生成一组零分享，
+//!    把第 j 个份额作为 `RefreshMessage` 发给参与方 j（包括自己），消息带有当前纪元
+//! 2. **组合**: 每一方检查收到的消息来自全体参与方、各一条且属于当前纪元，
+//!    把收到的值加到自己的份额上，然后进入下一个纪元
+//!
+//! 零分享之和仍是零分享，因此新份额对应同一秘密，而新多项式的高次系数由全体参与方共同随机化。
+//! 只要有一方诚实，刷新后的份额就与旧份额无关。每次刷新只需要一轮点对点通信。
+//! 参与方使用 `share` 的横坐标 1, 2, ..., n。当前实现针对半诚实敌手。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let shares = ShamirSecretSharing::share(&42, 2, 3)?;
+//! let mut parties = shares.into_iter()
+//!     .map(|share| ProactiveParty::new(share, 2, 3))
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//!
+//! run_refresh_epoch(&mut parties)?;
+//!
+//! let refreshed: Vec<Share> = parties.iter().map(|party| party.share().clone()).collect();
+//! assert_eq!(ShamirSecretSharing::reconstruct(&refreshed[1..], 2)?, 42);
+//! assert!(parties.iter().all(|party| party.epoch() == 1));
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{validate_threshold_params, Share, ShamirSecretSharing, field_add};
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+/// 刷新时间表：从 `start` 起每隔 `interval` 进入一个新纪元
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefreshSchedule {
+    /// 纪元 0 的开始时间
+    pub start: SystemTime,
+    /// 纪元长度
+    pub interval: Duration,
+}
+
+impl RefreshSchedule {
+    /// 创建刷新时间表
+    ///
+    /// # 返回值
+    /// 纪元长度为 0 时返回错误
+    pub fn new(start: SystemTime, interval: Duration) -> Result<Self> {
+        if interval.is_zero() {
+            return Err(MpcError::ProtocolError("Refresh interval must be positive".to_string()));
+        }
+        Ok(Self { start, interval })
+    }
+
+    /// `now` 所在的纪元；早于开始时间时为 0
+    pub fn epoch_at(&self, now: SystemTime) -> u64 {
+        let elapsed = now.duration_since(self.start).unwrap_or_default();
+        (elapsed.as_nanos() / self.interval.as_nanos()) as u64
+    }
+}
+
+/// 刷新中一方发给另一方的消息
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefreshMessage {
+    /// 消息所属的纪元
+    pub epoch: u64,
+    /// 发送方的 x 坐标
+    pub from: u64,
+    /// 接收方的 x 坐标
+    pub to: u64,
+    /// 发送方零分享在接收方处的取值
+    pub value: u64,
+}
+
+/// 参与主动刷新的一方
+#[derive(Debug, Clone)]
+pub struct ProactiveParty {
+    /// 本方当前纪元的份额
+    share: Share,
+    /// 门限值
+    threshold: usize,
+    /// 参与方数量
+    party_count: usize,
+    /// 当前纪元
+    epoch: u64,
+}
+
+impl ProactiveParty {
+    /// 以纪元 0 的份额创建参与方
+    ///
+    /// # 参数
+    /// - `share`: 本方持有的份额，x 坐标必须在 1..=party_count 内
+    /// - `threshold`: 门限值
+    /// - `party_count`: 参与方数量
+    ///
+    /// # 返回值
+    /// 参数不合法时返回错误
+    pub fn new(share: Share, threshold: usize, party_count: usize) -> Result<Self> {
+        validate_threshold_params(threshold, party_count)?;
+        if share.x == 0 || share.x > party_count as u64 {
+            return Err(MpcError::InvalidSecretShare);
+        }
+        Ok(Self { share, threshold, party_count, epoch: 0 })
+    }
+
+    /// 本方当前纪元的份额
+    pub fn share(&self) -> &Share {
+        &self.share
+    }
+
+    /// 当前纪元
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// 按时间表判断是否需要刷新
+    pub fn is_refresh_due(&self, schedule: &RefreshSchedule, now: SystemTime) -> bool {
+        schedule.epoch_at(now) > self.epoch
+    }
+
+    /// 生成发给各方的零分享消息（包括发给自己的一条）
+    pub fn contribute(&self) -> Result<Vec<RefreshMessage>> {
+        Ok(ShamirSecretSharing::generate_zero_shares(self.threshold, self.party_count)?
+            .into_iter()
+            .map(|zero| RefreshMessage {
+                epoch: self.epoch,
+                from: self.share.x,
+                to: zero.x,
+                value: zero.y,
+            })
+            .collect())
+    }
+
+    /// 组合收到的消息刷新份额，并进入下一个纪元
+    ///
+    /// # 参数
+    /// - `messages`: 收到的消息，必须属于当前纪元、来自全体参与方且各一条
+    ///
+    /// # 返回值
+    /// 消息不完整或不合法时返回错误，份额和纪元保持不变
+    pub fn complete(&mut self, messages: &[RefreshMessage]) -> Result<()> {
+        let mut values = vec![None; self.party_count];
+        for message in messages {
+            if message.epoch != self.epoch {
+                return Err(MpcError::ProtocolError(format!(
+                    "Message from {} belongs to epoch {}, current epoch is {}", message.from, message.epoch, self.epoch
+                )));
+            }
+            if message.to != self.share.x {
+                return Err(MpcError::ProtocolError(format!(
+                    "Message from {} is addressed to {}", message.from, message.to
+                )));
+            }
+            let slot = message.from.checked_sub(1)
+                .and_then(|index| values.get_mut(index as usize))
+                .ok_or_else(|| MpcError::ProtocolError(format!("Unknown sender {}", message.from)))?;
+            if slot.replace(message.value).is_some() {
+                return Err(MpcError::ProtocolError(format!("Duplicate message from {}", message.from)));
+            }
+        }
+        if values.iter().any(Option::is_none) {
+            return Err(MpcError::InsufficientShares);
+        }
+
+        self.share.y = values.iter().flatten().fold(self.share.y, |acc, &value| field_add(acc, value));
+        self.epoch += 1;
+        Ok(())
+    }
+}
+
+/// 在本地模拟全体参与方执行一个纪元的刷新
+///
+/// # 参数
+/// - `parties`: 全体参与方（每方一个），必须处于同一纪元
+pub fn run_refresh_epoch(parties: &mut [ProactiveParty]) -> Result<()> {
+    // 第 1 步：每一方生成消息
+    let mut inboxes = vec![Vec::with_capacity(parties.len()); parties.len()];
+    for party in parties.iter() {
+        for message in party.contribute()? {
+            let inbox = parties.iter().position(|receiver| receiver.share.x == message.to)
+                .ok_or_else(|| MpcError::ProtocolError(format!("Party {} is missing", message.to)))?;
+            inboxes[inbox].push(message);
+        }
+    }
+
+    // 第 2 步：每一方组合收到的消息
+    parties.iter_mut()
+        .zip(&inboxes)
+        .try_for_each(|(party, inbox)| party.complete(inbox))
+}
@@ -0,0 +1,221 @@
+//! # 算术程序与明文模拟 (Arithmetic Programs and Plaintext Simulation)
+//!
+//! 直线型算术程序：输入占用前 `input_count` 个寄存器，每条指令把结果写入一个新寄存器。
+//! 明文模拟器在域上直接执行程序，用于在测试中与基于秘密分享的执行逐条指令比较：
+//! 把 MPC 执行中每条指令结果的分享重构后交给 `diff_program`，
+//! 出现分歧时报告第一条出错的指令。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! // (x + y) · x + 5
+//! let mut program = ArithmeticProgram::new(2);
+//! let sum = program.push(ArithmeticInstruction::Add(0, 1));
+//! let product = program.push(ArithmeticInstruction::Mul(sum, 0));
+//! let result = program.push(ArithmeticInstruction::AddConst(product, 5));
+//! program.output(result);
+//!
+//! let trace = simulate_program(&program, &[3, 4])?;
+//! assert_eq!(trace.outputs, vec![26]);
+//!
+//! // MPC 执行得到的指令结果（这里故意让乘法出错）
+//! let report = diff_program(&program, &[3, 4], &[7, 22, 27])?;
+//! assert_eq!(report.first_divergence().map(|d| d.index), Some(1));
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{SecretSharing, ShamirSecretSharing, Share, field_add, field_mul, field_sub};
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+
+/// 算术指令，操作数为寄存器编号
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArithmeticInstruction {
+    /// a + b
+    Add(usize, usize),
+    /// a - b
+    Sub(usize, usize),
+    /// a · b（秘密分享执行中消耗一个 Beaver 三元组）
+    Mul(usize, usize),
+    /// a + c，c 为公开常数
+    AddConst(usize, u64),
+    /// a · c，c 为公开常数
+    MulConst(usize, u64),
+    /// 公开常数
+    Const(u64),
+}
+
+impl ArithmeticInstruction {
+    /// 指令读取的寄存器
+    pub fn operands(&self) -> Vec<usize> {
+        match *self {
+            ArithmeticInstruction::Add(a, b)
+            | ArithmeticInstruction::Sub(a, b)
+            | ArithmeticInstruction::Mul(a, b) => vec![a, b],
+            ArithmeticInstruction::AddConst(a, _) | ArithmeticInstruction::MulConst(a, _) => vec![a],
+            ArithmeticInstruction::Const(_) => Vec::new(),
+        }
+    }
+}
+
+/// 直线型算术程序
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArithmeticProgram {
+    /// 输入数量（占用寄存器 0..input_count）
+    pub input_count: usize,
+    /// 指令，第 i 条指令写入寄存器 input_count + i
+    pub instructions: Vec<ArithmeticInstruction>,
+    /// 输出寄存器
+    pub outputs: Vec<usize>,
+}
+
+impl ArithmeticProgram {
+    /// 创建有 `input_count` 个输入的空程序
+    pub fn new(input_count: usize) -> Self {
+        Self { input_count, instructions: Vec::new(), outputs: Vec::new() }
+    }
+
+    /// 追加指令，返回结果所在的寄存器
+    pub fn push(&mut self, instruction: ArithmeticInstruction) -> usize {
+        self.instructions.push(instruction);
+        self.input_count + self.instructions.len() - 1
+    }
+
+    /// 把寄存器标记为输出
+    pub fn output(&mut self, register: usize) {
+        self.outputs.push(register);
+    }
+
+    /// 第 `index` 条指令写入的寄存器
+    pub fn register_of(&self, index: usize) -> usize {
+        self.input_count + index
+    }
+
+    /// 乘法指令数量（秘密分享执行所需的三元组数量）
+    pub fn multiplication_count(&self) -> usize {
+        self.instructions.iter()
+            .filter(|instruction| matches!(instruction, ArithmeticInstruction::Mul(..)))
+            .count()
+    }
+}
+
+/// 明文模拟得到的寄存器取值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArithmeticTrace {
+    /// 全部寄存器（输入在前）
+    pub registers: Vec<u64>,
+    /// 输出寄存器的取值
+    pub outputs: Vec<u64>,
+}
+
+/// 在域上明文执行算术程序
+///
+/// # 参数
+/// - `program`: 要执行的程序
+/// - `inputs`: 程序输入
+pub fn simulate_program(program: &ArithmeticProgram, inputs: &[u64]) -> Result<ArithmeticTrace> {
+    if inputs.len() != program.input_count {
+        return Err(MpcError::ProtocolError("Input length mismatch".to_string()));
+    }
+
+    let mut registers = inputs.to_vec();
+    for (index, instruction) in program.instructions.iter().enumerate() {
+        let read = |register: usize| registers.get(register).copied().ok_or_else(|| {
+            MpcError::ProtocolError(format!("Instruction {} reads unset register {}", index, register))
+        });
+        let value = match *instruction {
+            ArithmeticInstruction::Add(a, b) => field_add(read(a)?, read(b)?),
+            ArithmeticInstruction::Sub(a, b) => field_sub(read(a)?, read(b)?),
+            ArithmeticInstruction::Mul(a, b) => field_mul(read(a)?, read(b)?),
+            ArithmeticInstruction::AddConst(a, c) => field_add(read(a)?, c),
+            ArithmeticInstruction::MulConst(a, c) => field_mul(read(a)?, c),
+            ArithmeticInstruction::Const(c) => c,
+        };
+        registers.push(value);
+    }
+
+    let outputs = program.outputs.iter()
+        .map(|&register| registers.get(register).copied().ok_or_else(|| {
+            MpcError::ProtocolError(format!("Output register {} is never set", register))
+        }))
+        .collect::<Result<Vec<u64>>>()?;
+
+    Ok(ArithmeticTrace { registers, outputs })
+}
+
+/// 一条指令上 MPC 执行与明文模拟的分歧
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstructionDivergence {
+    /// 指令序号
+    pub index: usize,
+    /// 指令写入的寄存器
+    pub register: usize,
+    /// 指令
+    pub instruction: ArithmeticInstruction,
+    /// 明文模拟的结果
+    pub expected: u64,
+    /// MPC 执行的结果；未提供时为 `None`
+    pub actual: Option<u64>,
+}
+
+/// 逐条指令比较的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramDiff {
+    /// 比较的指令数量
+    pub checked_instructions: usize,
+    /// 按指令顺序排列的全部分歧
+    pub divergences: Vec<InstructionDivergence>,
+}
+
+impl ProgramDiff {
+    /// 第一条出现分歧的指令
+    pub fn first_divergence(&self) -> Option<&InstructionDivergence> {
+        self.divergences.first()
+    }
+
+    /// 所有指令都一致
+    pub fn is_consistent(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+/// 把 MPC 执行的指令结果与明文模拟逐条比较
+///
+/// # 参数
+/// - `program`: 执行的程序
+/// - `inputs`: 明文输入
+/// - `results`: MPC 执行中每条指令结果重构后的值（按指令顺序，不含输入）
+pub fn diff_program(program: &ArithmeticProgram, inputs: &[u64], results: &[u64]) -> Result<ProgramDiff> {
+    let trace = simulate_program(program, inputs)?;
+
+    let divergences = program.instructions.iter()
+        .zip(&trace.registers[program.input_count..])
+        .enumerate()
+        .filter_map(|(index, (&instruction, &expected))| {
+            let actual = results.get(index).copied();
+            (actual != Some(expected)).then_some(InstructionDivergence {
+                index,
+                register: program.register_of(index),
+                instruction,
+                expected,
+                actual,
+            })
+        })
+        .collect();
+
+    Ok(ProgramDiff {
+        checked_instructions: program.instructions.len(),
+        divergences,
+    })
+}
+
+/// 重构 MPC 执行中每条指令结果的分享，供 `diff_program` 使用
+pub fn open_results(result_shares: &[Vec<Share>], threshold: usize) -> Result<Vec<u64>> {
+    result_shares.iter()
+        .map(|shares| ShamirSecretSharing::reconstruct(shares, threshold))
+        .collect()
+}
@@ -0,0 +1,390 @@
+//! # 受控公开 (Gated Reveal)
+//!
+//! 重构秘密是 MPC 中最敏感的操作：一旦某个值被公开，它就无法再被收回。
+//! 本模块提供 `RevealGate`，所有公开操作都应通过它进行：
+//!
+//! - **门限与法定人数检查**: 分享数量必须达到门限，且来自互不相同的合法参与方
+//! - **公开策略 (`RevealPolicy`)**: 例如"需要指定参与方中的 k 方批准"、
+//!   "仅在 MAC 检查通过后"、"仅向参与方 X 公开"，策略之间可以组合
+//! - **审计**: 每一次公开请求（无论允许还是拒绝）都会记录一条审计事件
+//!
+//! 批准必须是批准方用 Ed25519 对 (值标识, 请求方) 的签名（`RevealApproval`），
+//! 守门人按登记的公钥验证。MAC 检查状态不能由请求方声明，
+//! 只有真正执行了检查的 `SPDZShareProtocol::open_gated` 能设置。
+//!
+//! `ShamirSecretSharing::reconstruct` 是纯函数，凑齐门限个分享的任何人都能重构，
+//! 因此真正把关的是每个持有方是否交出自己的分享：持有方在发送分享之前调用
+//! `RevealGate::release_share`，策略不满足时不交出分享，诚实持有方拒绝后请求方凑不齐门限。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let shares = ShamirSecretSharing::share(&42, 2, 3)?;
+//!
+//! let gate = RevealGate::new(RevealPolicy::OnlyTo(vec![1]), 2);
+//! let request = RevealRequest::new("salary-sum", 1);
+//! assert_eq!(gate.open(&request, &shares[..2])?, 42);
+//!
+//! let denied = RevealRequest::new("salary-sum", 2);
+//! assert!(gate.open(&denied, &shares[..2]).is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{Share, ShamirSecretSharing, SecretSharing};
+use crate::elliptic_curve::{Ed25519, Ed25519PublicKey, Ed25519SecretKey, Ed25519Signature};
+use crate::security::{AuditLogger, SecurityEvent, SecurityLevel, SecurityPolicy, ThreatType};
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// 批准签名的域分隔标签
+const APPROVAL_DOMAIN: &[u8] = b"mpc_api/reveal/approval";
+
+/// 公开策略
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RevealPolicy {
+    /// 任何请求方都可以公开
+    Public,
+    /// 需要指定参与方中至少 `required` 方批准
+    RequiresApprovals {
+        /// 有权批准的参与方
+        approvers: Vec<usize>,
+        /// 所需批准数量
+        required: usize,
+    },
+    /// 仅在 MAC 检查通过后才能公开
+    RequiresMacCheck,
+    /// 仅向指定参与方公开
+    OnlyTo(Vec<usize>),
+    /// 所有子策略都必须满足
+    All(Vec<RevealPolicy>),
+}
+
+impl RevealPolicy {
+    /// 检查请求是否满足策略
+    ///
+    /// # 参数
+    ///
+    /// * `request` - 公开请求
+    /// * `approver_keys` - 批准方的签名公钥，没有登记公钥或签名无效的批准不计数
+    ///
+    /// # 返回值
+    ///
+    /// 满足时返回 `Ok(())`，否则返回描述拒绝原因的字符串
+    pub fn check(
+        &self,
+        request: &RevealRequest,
+        approver_keys: &HashMap<usize, Ed25519PublicKey>,
+    ) -> std::result::Result<(), String> {
+        match self {
+            RevealPolicy::Public => Ok(()),
+            RevealPolicy::RequiresApprovals { approvers, required } => {
+                let granted = approvers.iter()
+                    .filter(|&&approver| request.approvals.iter().any(|approval| {
+                        approval.approver == approver
+                            && approver_keys.get(&approver)
+                                .is_some_and(|key| approval.verify(key, &request.value_id, request.requester))
+                    }))
+                    .count();
+                if granted >= *required {
+                    Ok(())
+                } else {
+                    Err(format!("{} of {} required approvals present", granted, required))
+                }
+            }
+            RevealPolicy::RequiresMacCheck => {
+                if request.mac_check_passed {
+                    Ok(())
+                } else {
+                    Err("MAC check has not passed".to_string())
+                }
+            }
+            RevealPolicy::OnlyTo(recipients) => {
+                if recipients.contains(&request.requester) {
+                    Ok(())
+                } else {
+                    Err(format!("party {} is not an allowed recipient", request.requester))
+                }
+            }
+            RevealPolicy::All(policies) => {
+                policies.iter().try_for_each(|policy| policy.check(request, approver_keys))
+            }
+        }
+    }
+}
+
+/// 参与方对公开请求的签名批准
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevealApproval {
+    /// 批准方
+    pub approver: usize,
+    /// 批准方对 (值标识, 请求方) 的 Ed25519 签名
+    pub signature: Ed25519Signature,
+}
+
+impl RevealApproval {
+    /// 批准方签名批准把 `value_id` 公开给 `requester`
+    pub fn sign(approver: usize, signing_key: &Ed25519SecretKey, value_id: &str, requester: usize) -> Self {
+        Self {
+            approver,
+            signature: Ed25519::sign(signing_key, &approval_message(value_id, requester)),
+        }
+    }
+
+    /// 用批准方的公钥验证签名
+    pub fn verify(&self, public_key: &Ed25519PublicKey, value_id: &str, requester: usize) -> bool {
+        Ed25519::verify(public_key, &approval_message(value_id, requester), &self.signature)
+    }
+}
+
+/// 批准签名覆盖的消息：域标签 ‖ 值标识长度 ‖ 值标识 ‖ 请求方
+fn approval_message(value_id: &str, requester: usize) -> Vec<u8> {
+    let mut message = APPROVAL_DOMAIN.to_vec();
+    message.extend_from_slice(&(value_id.len() as u64).to_le_bytes());
+    message.extend_from_slice(value_id.as_bytes());
+    message.extend_from_slice(&(requester as u64).to_le_bytes());
+    message
+}
+
+/// 公开请求
+///
+/// 描述谁请求公开哪个值，以及目前收集到的签名批准和 MAC 检查状态。
+/// MAC 检查状态不参与序列化，反序列化得到的请求总是未通过检查。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevealRequest {
+    /// 被公开值的标识
+    value_id: String,
+    /// 请求方（公开结果的接收方）
+    requester: usize,
+    /// 收集到的签名批准
+    approvals: Vec<RevealApproval>,
+    /// MAC 检查是否已通过
+    #[serde(skip)]
+    mac_check_passed: bool,
+}
+
+impl RevealRequest {
+    /// 创建新的公开请求
+    pub fn new(value_id: &str, requester: usize) -> Self {
+        Self {
+            value_id: value_id.to_string(),
+            requester,
+            approvals: Vec::new(),
+            mac_check_passed: false,
+        }
+    }
+
+    /// 被公开值的标识
+    pub fn value_id(&self) -> &str {
+        &self.value_id
+    }
+
+    /// 请求方
+    pub fn requester(&self) -> usize {
+        self.requester
+    }
+
+    /// 收集到的签名批准
+    pub fn approvals(&self) -> &[RevealApproval] {
+        &self.approvals
+    }
+
+    /// MAC 检查是否已通过
+    pub fn mac_check_passed(&self) -> bool {
+        self.mac_check_passed
+    }
+
+    /// 添加参与方的签名批准
+    pub fn approve(&mut self, approval: RevealApproval) {
+        self.approvals.push(approval);
+    }
+
+    /// 添加参与方的签名批准（构建器形式）
+    pub fn with_approval(mut self, approval: RevealApproval) -> Self {
+        self.approve(approval);
+        self
+    }
+
+    /// 记录真正执行的 MAC 检查的结果，只由执行检查的打开路径调用
+    pub(crate) fn with_mac_check_result(mut self, passed: bool) -> Self {
+        self.mac_check_passed = passed;
+        self
+    }
+}
+
+/// 公开守门人
+///
+/// 在重构秘密之前执行门限、法定人数和策略检查，并记录审计事件。
+#[derive(Debug, Clone)]
+pub struct RevealGate {
+    /// 公开策略
+    policy: RevealPolicy,
+    /// 重构门限
+    threshold: usize,
+    /// 合法的分享持有方（x 坐标）；为空时不限制
+    allowed_holders: HashSet<u64>,
+    /// 批准方的签名公钥
+    approver_keys: HashMap<usize, Ed25519PublicKey>,
+    /// 审计日志
+    audit_logger: Arc<AuditLogger>,
+}
+
+impl RevealGate {
+    /// 创建新的公开守门人，使用独立的审计日志
+    ///
+    /// # 参数
+    ///
+    /// * `policy` - 公开策略
+    /// * `threshold` - 重构门限
+    pub fn new(policy: RevealPolicy, threshold: usize) -> Self {
+        Self::with_audit_logger(
+            policy,
+            threshold,
+            Arc::new(AuditLogger::new(SecurityPolicy::default())),
+        )
+    }
+
+    /// 创建新的公开守门人，使用共享的审计日志
+    pub fn with_audit_logger(
+        policy: RevealPolicy,
+        threshold: usize,
+        audit_logger: Arc<AuditLogger>,
+    ) -> Self {
+        Self {
+            policy,
+            threshold,
+            allowed_holders: HashSet::new(),
+            approver_keys: HashMap::new(),
+            audit_logger,
+        }
+    }
+
+    /// 限制合法的分享持有方（按分享的 x 坐标）
+    pub fn with_allowed_holders(mut self, holders: &[u64]) -> Self {
+        self.allowed_holders = holders.iter().copied().collect();
+        self
+    }
+
+    /// 登记批准方的签名公钥
+    pub fn with_approver_key(mut self, approver: usize, public_key: Ed25519PublicKey) -> Self {
+        self.approver_keys.insert(approver, public_key);
+        self
+    }
+
+    /// 获取公开策略
+    pub fn policy(&self) -> &RevealPolicy {
+        &self.policy
+    }
+
+    /// 获取审计日志
+    pub fn audit_logger(&self) -> &Arc<AuditLogger> {
+        &self.audit_logger
+    }
+
+    /// 检查公开请求与分享集合是否满足所有条件
+    ///
+    /// # 参数
+    ///
+    /// * `request` - 公开请求
+    /// * `holders` - 提供分享的参与方（x 坐标）
+    ///
+    /// # 返回值
+    ///
+    /// 通过时返回 `Ok(())`；否则记录拒绝事件并返回错误
+    pub fn authorize(&self, request: &RevealRequest, holders: &[u64]) -> Result<()> {
+        let verdict = self.check_quorum(holders)
+            .and_then(|_| self.policy.check(request, &self.approver_keys));
+        self.record(request, verdict)
+    }
+
+    /// 持有方在交出自己的分享之前检查请求
+    ///
+    /// # 参数
+    ///
+    /// * `request` - 公开请求
+    /// * `share` - 本方持有的分享
+    ///
+    /// # 返回值
+    ///
+    /// 策略满足时返回应发送给请求方的分享；否则记录拒绝事件并返回错误
+    pub fn release_share(&self, request: &RevealRequest, share: &Share) -> Result<Share> {
+        let verdict = self.check_holders(&[share.x])
+            .and_then(|_| self.policy.check(request, &self.approver_keys));
+        self.record(request, verdict)?;
+        Ok(share.clone())
+    }
+
+    /// 记录检查结果，拒绝时转换为错误
+    fn record(&self, request: &RevealRequest, verdict: std::result::Result<(), String>) -> Result<()> {
+        match verdict {
+            Ok(()) => {
+                self.audit(request, SecurityLevel::Low, "reveal permitted".to_string())?;
+                Ok(())
+            }
+            Err(reason) => {
+                self.audit(request, SecurityLevel::High, format!("reveal denied: {}", reason))?;
+                Err(MpcError::ProtocolError(format!(
+                    "Reveal of '{}' denied: {}", request.value_id, reason
+                )))
+            }
+        }
+    }
+
+    /// 在检查通过后重构 Shamir 分享
+    ///
+    /// # 参数
+    ///
+    /// * `request` - 公开请求
+    /// * `shares` - 用于重构的分享
+    ///
+    /// # 返回值
+    ///
+    /// 检查通过时返回重构的秘密，否则返回错误
+    pub fn open(&self, request: &RevealRequest, shares: &[Share]) -> Result<u64> {
+        let holders: Vec<u64> = shares.iter().map(|share| share.x).collect();
+        self.authorize(request, &holders)?;
+        ShamirSecretSharing::reconstruct(shares, self.threshold)
+    }
+
+    /// 门限与法定人数检查
+    fn check_quorum(&self, holders: &[u64]) -> std::result::Result<(), String> {
+        let distinct: HashSet<u64> = holders.iter().copied().collect();
+        if distinct.len() != holders.len() {
+            return Err("duplicate share holders".to_string());
+        }
+        if distinct.len() < self.threshold {
+            return Err(format!("{} shares below threshold {}", distinct.len(), self.threshold));
+        }
+        self.check_holders(holders)
+    }
+
+    /// 检查每个持有方都是合法成员
+    fn check_holders(&self, holders: &[u64]) -> std::result::Result<(), String> {
+        let distinct: HashSet<u64> = holders.iter().copied().collect();
+        if distinct.contains(&0) {
+            return Err("share at x = 0 would expose the secret directly".to_string());
+        }
+        if !self.allowed_holders.is_empty() {
+            if let Some(unknown) = distinct.iter().find(|x| !self.allowed_holders.contains(x)) {
+                return Err(format!("share holder {} is not a member", unknown));
+            }
+        }
+        Ok(())
+    }
+
+    /// 记录审计事件
+    fn audit(&self, request: &RevealRequest, severity: SecurityLevel, description: String) -> Result<()> {
+        let mut event = SecurityEvent::new(ThreatType::SecretDisclosure, severity, description)
+            .with_context("value_id".to_string(), request.value_id.clone())
+            .with_context("requester".to_string(), request.requester.to_string());
+        if severity == SecurityLevel::Low {
+            event.mark_handled();
+        }
+        self.audit_logger.log_event(event)
+    }
+}
@@ -21,12 +21,14 @@
 //! - **完整性**: 通过有限域运算保证数学正确性
 //! - **可验证性**: 可以验证份额的有效性和重构结果的正确性
 //!
-//! 所有运算都在u64有限域中进行，确保密码学安全性。
+//! 所有运算都在素数域 `F: Field` 中进行，默认的 `ShamirSecretSharing` 使用 Goldilocks 域（u64）。
 
-use super::{Share, SecretSharing, AdditiveSecretSharing, FIELD_PRIME, field_add, field_sub, field_mul, field_inv};
+use super::{Field, Share, SecretSharing, AdditiveSecretSharing, FIELD_PRIME, field_add, field_sub, field_mul, field_inv};
+use crate::utils::memory::SecretValue;
 use crate::{MpcError, Result};
-use rand::{Rng, SeedableRng};
+use rand::{CryptoRng, Rng, RngCore, SeedableRng};
 use rand::rngs::StdRng;
+use std::marker::PhantomData;
 // use serde::{Deserialize, Serialize}; // Commented out unused imports
 
 /// 横坐标选择策略
@@ -49,10 +51,15 @@ impl Default for XCoordinateStrategy {
 /// Shamir秘密分享方案实现
 ///
 /// 提供Shamir秘密分享的核心功能，包括秘密分享、重构以及份额上的同态运算。
-/// 该方案基于多项式插值理论，在有限域GF(p)中工作。
-pub struct ShamirSecretSharing;
+/// 该方案基于多项式插值理论，在任意素数域 `F` 上工作；常用的 Goldilocks 域实例
+/// 是 `ShamirSecretSharing`。
+#[derive(Debug, Clone, Copy)]
+pub struct Shamir<F>(PhantomData<F>);
+
+/// Goldilocks 域（`u64`）上的Shamir秘密分享
+pub type ShamirSecretSharing = Shamir<u64>;
 
-impl Default for ShamirSecretSharing {
+impl<F: Field> Default for Shamir<F> {
     /// 创建Shamir秘密分享方案的默认实例
     ///
     /// # 返回值
@@ -62,7 +69,7 @@ impl Default for ShamirSecretSharing {
     }
 }
 
-impl ShamirSecretSharing {
+impl<F: Field> Shamir<F> {
     /// 创建Shamir秘密分享方案的新实例
     ///
     /// # 返回值
@@ -73,12 +80,13 @@ impl ShamirSecretSharing {
     /// let scheme = ShamirSecretSharing::new();
     /// ```
     pub fn new() -> Self {
-        Self
+        Self(PhantomData)
     }
 
     /// 验证份额的有效性
     ///
     /// 检查给定的份额是否对同一个秘密有效。这通过验证份额是否来自同一个多项式来实现。
+    /// 需要定位具体哪一方的份额出错时，请使用 `diagnose_shares`。
     ///
     /// # 参数
     /// - `shares`: 要验证的份额数组
@@ -95,7 +103,7 @@ impl ShamirSecretSharing {
     /// let is_valid = scheme.verify_shares(&shares[0..2], 2);
     /// assert!(is_valid);
     /// ```
-    pub fn verify_shares(&self, shares: &[Share], threshold: usize) -> bool {
+    pub fn verify_shares(&self, shares: &[Share<F>], threshold: usize) -> bool {
         if shares.len() < threshold {
             return false;
         }
@@ -122,11 +130,183 @@ impl ShamirSecretSharing {
         }
     }
 
+    /// 计算多项式在给定点的值（霍纳方法）
+    ///
+    /// 使用霍纳方法（Horner's method）高效计算多项式f(x) = a₀ + a₁x + a₂x² + ... + aₙx^n的值。
+    /// 霍纳方法通过重新组织计算顺序减少乘法次数，提高计算效率。
+    ///
+    /// # 参数
+    /// - `coefficients`: 多项式系数数组，从常数项开始
+    /// - `x`: 计算点的x坐标
+    ///
+    /// # 返回值
+    /// 返回多项式在点x处的值
+    ///
+    /// # 算法复杂度
+    /// - 时间复杂度：O(n)，其中n是多项式的度数
+    /// - 空间复杂度：O(1)
+    ///
+    /// # 示例
+    /// ```
+    /// let scheme = ShamirSecretSharing::new();
+    /// let coeffs = vec![5, 3, 2]; // f(x) = 5 + 3x + 2x²
+    /// let result = scheme.evaluate_polynomial(&coeffs, 2); // f(2) = 19
+    /// ```
+    pub fn evaluate_polynomial(&self, coefficients: &[F], x: F) -> F {
+        if coefficients.is_empty() {
+            return F::ZERO;
+        }
+        
+        // 霍纳方法：从最高次项开始，逆向计算
+        // f(x) = a₀ + x(a₁ + x(a₂ + x(a₃ + ...)))
+        let mut result = coefficients[coefficients.len() - 1];
+        
+        for &coeff in coefficients.iter().rev().skip(1) {
+            result = result.mul(x).add(coeff);
+        }
+        
+        result
+    }
+
+    /// 批量计算多项式在多个点的值
+    ///
+    /// 域提供 `Field::multipoint_evaluate` 时使用快速多点求值（Goldilocks 域在门限较大时
+    /// 使用子积树算法，O(n log² n)），否则逐点使用霍纳方法（O(n·t)）。
+    ///
+    /// # 参数
+    /// - `coefficients`: 多项式系数数组，从常数项开始
+    /// - `xs`: 计算点的x坐标
+    ///
+    /// # 返回值
+    /// 按 `xs` 的顺序返回多项式的值
+    ///
+    /// # 示例
+    /// ```
+    /// use mpc_api::secret_sharing::ShamirSecretSharing;
+    ///
+    /// let scheme = ShamirSecretSharing::new();
+    /// let values = scheme.evaluate_polynomial_batch(&[5, 3, 2], &[1, 2, 3]);
+    /// assert_eq!(values, vec![10, 19, 32]);
+    /// ```
+    pub fn evaluate_polynomial_batch(&self, coefficients: &[F], xs: &[F]) -> Vec<F> {
+        F::multipoint_evaluate(coefficients, xs)
+            .unwrap_or_else(|| xs.iter().map(|&x| self.evaluate_polynomial(coefficients, x)).collect())
+    }
+
+    /// 用给定的随机数生成器生成份额
+    ///
+    /// 横坐标为 1, 2, ..., n，多项式系数全部取自 `rng`。传入由 32 字节种子创建的
+    /// `AesCtrPrg` 或 `Blake3Prg` 时份额完全由种子确定，`SecretSharing::share` 使用系统随机数。
+    ///
+    /// # 参数
+    /// - `secret`: 要分享的秘密值
+    /// - `threshold`: 重构所需的最小份额数量
+    /// - `total_parties`: 参与方总数
+    /// - `rng`: 密码学安全的随机数生成器
+    ///
+    /// # 示例
+    /// ```
+    /// use mpc_api::secret_sharing::ShamirSecretSharing;
+    /// use mpc_api::utils::crypto::{AesCtrPrg, SeedableRng};
+    ///
+    /// let scheme = ShamirSecretSharing::new();
+    /// let first = scheme.share_with_rng(&123, 2, 3, &mut AesCtrPrg::from_seed([1; 32])).unwrap();
+    /// let second = scheme.share_with_rng(&123, 2, 3, &mut AesCtrPrg::from_seed([1; 32])).unwrap();
+    /// assert_eq!(first, second);
+    /// ```
+    pub fn share_with_rng<R: RngCore + CryptoRng>(&self, secret: &F, threshold: usize, total_parties: usize,
+                                                  rng: &mut R) -> Result<Vec<Share<F>>> {
+        super::validate_threshold_params(threshold, total_parties)?;
+        // 横坐标 1..=n 必须是互不相同的非零域元素
+        if total_parties as u128 >= F::MODULUS {
+            return Err(MpcError::InvalidThreshold);
+        }
+
+        if secret.to_u128() >= F::MODULUS {
+            return Err(MpcError::CryptographicError("Secret value out of field range".to_string()));
+        }
+
+        // 生成(threshold - 1)次多项式的随机系数，释放时清零
+        let mut coefficients = SecretValue::new(Vec::with_capacity(threshold));
+        coefficients.push(*secret); // a_0 = secret
+        for _ in 1..threshold {
+            coefficients.push(F::random(rng));
+        }
+
+        // 在点1, 2, ..., total_parties处计算多项式的值
+        let xs: Vec<F> = (1..=total_parties as u128).map(F::from_u128).collect();
+        let ys = self.evaluate_polynomial_batch(&coefficients, &xs);
+        Ok(xs.into_iter().zip(ys).map(|(x, y)| Share::new(x, y)).collect())
+    }
+
+    /// 使用拉格朗日插值法重构秘密
+    ///
+    /// 通过拉格朗日插值公式计算多项式在x=0处的值，即原始秘密。
+    /// 拉格朗日插值公式：f(0) = Σ yᵢ * Lᵢ(0)，其中Lᵢ(0)是拉格朗日基函数。
+    ///
+    /// # 参数
+    /// - `shares`: 用于重构的份额数组
+    ///
+    /// # 返回值
+    /// 成功时返回重构的秘密值
+    ///
+    /// # 错误
+    /// - 当份额数组为空时返回InsufficientShares错误
+    /// - 当计算模逆时失败返回CryptographicError错误
+    ///
+    /// # 数学原理
+    /// 拉格朗日基函数：Lᵢ(0) = ∏(0-xⱼ)/(xᵢ-xⱼ) for j≠i
+    /// 最终结果：f(0) = Σ yᵢ * Lᵢ(0)
+    ///
+    /// # 示例
+    /// ```
+    /// let scheme = ShamirSecretSharing::new();
+    /// let shares = vec![Share::new(1, 10), Share::new(2, 15), Share::new(3, 22)];
+    /// let secret = scheme.lagrange_interpolation(&shares)?;
+    /// ```
+    pub fn lagrange_interpolation(&self, shares: &[Share<F>]) -> Result<F> {
+        if shares.is_empty() {
+            return Err(MpcError::InsufficientShares);
+        }
+        
+        let mut result = F::ZERO;
+        
+        // 拉格朗日插值：计算在x=0处的多项式值
+        for i in 0..shares.len() {
+            let mut numerator = F::ONE;
+            let mut denominator = F::ONE;
+            
+            // 计算拉格朗日基函数 L_i(0) = ∏(0-x_j)/(x_i-x_j) for j≠i
+            for j in 0..shares.len() {
+                if i != j {
+                    // 分子：(0 - x_j) = (-x_j)
+                    numerator = numerator.mul(shares[j].x.neg());
+                    
+                    // 分母：(x_i - x_j)
+                    let diff = shares[i].x.sub(shares[j].x);
+                    denominator = denominator.mul(diff);
+                }
+            }
+            
+            let denominator_inv = denominator.inverse()
+                .ok_or_else(|| MpcError::CryptographicError("No modular inverse exists".to_string()))?;
+            let lagrange_coeff = numerator.mul(denominator_inv);
+            
+            result = result.add(shares[i].y.mul(lagrange_coeff));
+        }
+        
+        Ok(result)
+    }
+}
+
+impl ShamirSecretSharing {
     /// 更新现有份额（份额刷新）
     ///
     /// 生成新的随机多项式份额，但保持相同的秘密。这用于提高安全性，
     /// 防止攻击者通过长期观察学习到份额信息。
     ///
+    /// 该方法会在本地重构秘密；各方分别持有份额时请使用 `proactive` 模块的 `ProactiveParty`。
+    ///
     /// # 参数
     /// - `old_shares`: 现有的份额
     /// - `threshold`: 门限值
@@ -270,45 +450,6 @@ impl ShamirSecretSharing {
         Ok((secret, shares))
     }
     
-    /// 计算多项式在给定点的值（霍纳方法）
-    ///
-    /// 使用霍纳方法（Horner's method）高效计算多项式f(x) = a₀ + a₁x + a₂x² + ... + aₙx^n的值。
-    /// 霍纳方法通过重新组织计算顺序减少乘法次数，提高计算效率。
-    ///
-    /// # 参数
-    /// - `coefficients`: 多项式系数数组，从常数项开始
-    /// - `x`: 计算点的x坐标
-    ///
-    /// # 返回值
-    /// 返回多项式在点x处的值
-    ///
-    /// # 算法复杂度
-    /// - 时间复杂度：O(n)，其中n是多项式的度数
-    /// - 空间复杂度：O(1)
-    ///
-    /// # 示例
-    /// ```
-    /// let scheme = ShamirSecretSharing::new();
-    /// let coeffs = vec![5, 3, 2]; // f(x) = 5 + 3x + 2x²
-    /// let result = scheme.evaluate_polynomial(&coeffs, 2); // f(2) = 19
-    /// ```
-    pub fn evaluate_polynomial(&self, coefficients: &[u64], x: u64) -> u64 {
-        if coefficients.is_empty() {
-            return 0;
-        }
-        
-        // 霍纳方法：从最高次项开始，逆向计算
-        // f(x) = a₀ + x(a₁ + x(a₂ + x(a₃ + ...)))
-        let mut result = coefficients[coefficients.len() - 1];
-        
-        for &coeff in coefficients.iter().rev().skip(1) {
-            result = field_add(field_mul(result, x), coeff);
-        }
-        
-        result
-    }
-    
-    
     /// 快速多项式更新 - 添加新系数
     ///
     /// 高效地更新现有多项式，添加新的系数项。这比重新构造整个多项式要快。
@@ -553,8 +694,8 @@ impl ShamirSecretSharing {
         // 生成横坐标
         let x_coordinates = self.generate_x_coordinates(total_parties, strategy);
         
-        // 生成多项式系数
-        let mut coefficients = Vec::with_capacity(threshold);
+        // 生成多项式系数，系数决定了所有份额，释放时清零
+        let mut coefficients = SecretValue::new(Vec::with_capacity(threshold));
         coefficients.push(*secret); // a_0 = secret
         
         match strategy {
@@ -577,13 +718,8 @@ impl ShamirSecretSharing {
         }
         
         // 计算份额
-        let mut shares = Vec::with_capacity(total_parties);
-        for &x in &x_coordinates {
-            let y = self.evaluate_polynomial(&coefficients, x);
-            shares.push(Share::new(x, y));
-        }
-        
-        Ok(shares)
+        let ys = self.evaluate_polynomial_batch(&coefficients, &x_coordinates);
+        Ok(x_coordinates.into_iter().zip(ys).map(|(x, y)| Share::new(x, y)).collect())
     }
     
     /// 使用种子控制生成确定性份额
@@ -620,7 +756,7 @@ impl ShamirSecretSharing {
                               seed: u64) -> Result<Vec<Share>> {
         self.share_with_coordinates(secret, threshold, total_parties, XCoordinateStrategy::SeededRandom(seed))
     }
-    
+
     /// 预计算拉格朗日系数
     ///
     /// 预计算给定x坐标组合的拉格朗日系数，用于快速重构。
@@ -718,6 +854,10 @@ impl ShamirSecretSharing {
     /// 1. 使用现有份额重构秘密
     /// 2. 使用新参数重新分享秘密
     ///
+    /// # 注意
+    /// 秘密会在调用方处以明文出现。参与方集合不变时，
+    /// 应使用不重构秘密的 `convert_threshold`。
+    ///
     /// # 示例
     /// ```
     /// let scheme = ShamirSecretSharing::new();
@@ -793,73 +933,14 @@ impl ShamirSecretSharing {
         Ok(shares)
     }
     
-    /// 使用拉格朗日插值法重构秘密
-    ///
-    /// 通过拉格朗日插值公式计算多项式在x=0处的值，即原始秘密。
-    /// 拉格朗日插值公式：f(0) = Σ yᵢ * Lᵢ(0)，其中Lᵢ(0)是拉格朗日基函数。
-    ///
-    /// # 参数
-    /// - `shares`: 用于重构的份额数组
-    ///
-    /// # 返回值
-    /// 成功时返回重构的秘密值
-    ///
-    /// # 错误
-    /// - 当份额数组为空时返回InsufficientShares错误
-    /// - 当计算模逆时失败返回CryptographicError错误
-    ///
-    /// # 数学原理
-    /// 拉格朗日基函数：Lᵢ(0) = ∏(0-xⱼ)/(xᵢ-xⱼ) for j≠i
-    /// 最终结果：f(0) = Σ yᵢ * Lᵢ(0)
-    ///
-    /// # 示例
-    /// ```
-    /// let scheme = ShamirSecretSharing::new();
-    /// let shares = vec![Share::new(1, 10), Share::new(2, 15), Share::new(3, 22)];
-    /// let secret = scheme.lagrange_interpolation(&shares)?;
-    /// ```
-    pub fn lagrange_interpolation(&self, shares: &[Share]) -> Result<u64> {
-        if shares.is_empty() {
-            return Err(MpcError::InsufficientShares);
-        }
-        
-        let mut result = 0u64;
-        
-        // 拉格朗日插值：计算在x=0处的多项式值
-        for i in 0..shares.len() {
-            let mut numerator = 1u64;
-            let mut denominator = 1u64;
-            
-            // 计算拉格朗日基函数 L_i(0) = ∏(0-x_j)/(x_i-x_j) for j≠i
-            for j in 0..shares.len() {
-                if i != j {
-                    // 分子：(0 - x_j) = (-x_j) = field_sub(0, x_j)
-                    let neg_xj = field_sub(0, shares[j].x);
-                    numerator = field_mul(numerator, neg_xj);
-                    
-                    // 分母：(x_i - x_j)
-                    let diff = field_sub(shares[i].x, shares[j].x);
-                    denominator = field_mul(denominator, diff);
-                }
-            }
-            
-            let denominator_inv = field_inv(denominator)
-                .ok_or_else(|| MpcError::CryptographicError("No modular inverse exists".to_string()))?;
-            let lagrange_coeff = field_mul(numerator, denominator_inv);
-            
-            result = field_add(result, field_mul(shares[i].y, lagrange_coeff));
-        }
-        
-        Ok(result)
-    }
 }
 
 /// 实现SecretSharing trait，提供Shamir秘密分享的核心功能
-impl SecretSharing for ShamirSecretSharing {
-    /// 秘密类型，使用u64表示有限域元素
-    type Secret = u64;
+impl<F: Field> SecretSharing for Shamir<F> {
+    /// 秘密类型，即域元素
+    type Secret = F;
     /// 份额类型，使用Share结构体表示
-    type Share = Share;
+    type Share = Share<F>;
     
     /// 将秘密分享为多个Shamir份额
     ///
@@ -888,35 +969,7 @@ impl SecretSharing for ShamirSecretSharing {
     /// 该方法使用密码学安全的随机数生成器创建多项式系数，确保份额的随机性和不可预测性。
     /// 在Shamir秘密分享中，少于t个份额无法泄露任何关于秘密的信息。
     fn share(secret: &Self::Secret, threshold: usize, total_parties: usize) -> Result<Vec<Self::Share>> {
-        // 验证参数
-        super::validate_threshold_params(threshold, total_parties)?;
-        
-        // 验证秘密值
-        if !super::validate_field_element(*secret) {
-            return Err(MpcError::CryptographicError("Secret value out of field range".to_string()));
-        }
-        
-        let sss = Self::new();
-        let mut rng = rand::thread_rng();
-        
-        // 生成(threshold - 1)次多项式的随机系数
-        let mut coefficients = Vec::with_capacity(threshold);
-        coefficients.push(*secret); // a_0 = secret
-        
-        for _ in 1..threshold {
-            let coeff = rng.gen_range(0..FIELD_PRIME);
-            coefficients.push(coeff);
-        }
-        
-        // 在点1, 2, ..., total_parties处计算多项式的值
-        let mut shares = Vec::with_capacity(total_parties);
-        for i in 1..=total_parties {
-            let x = i as u64;
-            let y = sss.evaluate_polynomial(&coefficients, x);
-            shares.push(Share::new(x, y));
-        }
-        
-        Ok(shares)
+        Self::new().share_with_rng(secret, threshold, total_parties, &mut rand::thread_rng())
     }
     
     /// 从Shamir份额中重构秘密
@@ -956,7 +1009,7 @@ impl SecretSharing for ShamirSecretSharing {
 }
 
 /// 实现AdditiveSecretSharing trait，提供Shamir份额的同态运算功能
-impl AdditiveSecretSharing for ShamirSecretSharing {
+impl<F: Field> AdditiveSecretSharing for Shamir<F> {
     /// Shamir份额的加法运算
     ///
     /// 计算两个Shamir份额的和，实现Shamir秘密分享的同态加法特性。
@@ -988,7 +1041,7 @@ impl AdditiveSecretSharing for ShamirSecretSharing {
             return Err(MpcError::InvalidSecretShare);
         }
         
-        let y = field_add(share1.y, share2.y);
+        let y = share1.y.add(share2.y);
         Ok(Share::new(share1.x, y))
     }
     
@@ -1023,7 +1076,7 @@ impl AdditiveSecretSharing for ShamirSecretSharing {
             return Err(MpcError::InvalidSecretShare);
         }
         
-        let y = field_sub(share1.y, share2.y);
+        let y = share1.y.sub(share2.y);
         Ok(Share::new(share1.x, y))
     }
     
@@ -1049,7 +1102,7 @@ impl AdditiveSecretSharing for ShamirSecretSharing {
     /// # 同态性质
     /// 如果share是秘密s的份额，则product_share是秘密(s*scalar)的有效份额。
     fn scalar_mul(share: &Self::Share, scalar: &Self::Secret) -> Result<Self::Share> {
-        let y = field_mul(share.y, *scalar);
+        let y = share.y.mul(*scalar);
         Ok(Share::new(share.x, y))
     }
 }
@@ -1505,7 +1558,10 @@ impl super::MultiplicationSecretSharing for ShamirSecretSharing {
     /// Beaver 乘法协议：
     /// 1. 各方计算 d_i = x_i - a_i, e_i = y_i - b_i
     /// 2. 重构并公开 d = Σd_i, e = Σe_i
-    /// 3. 各方计算 z_i = c_i + d·b_i + e·a_i + d·e (仅party 0计算最后一项)
+    /// 3. 各方计算 z_i = c_i + d·b_i + e·a_i + d·e
+    /// 
+    /// 与加法分享不同，Shamir 分享中公开常数需要由所有参与方加到各自的分享上
+    /// （相当于平移多项式的常数项），因此不存在需要特殊处理的参与方。
     fn beaver_mul(
         share_x: &Self::Share,
         share_y: &Self::Share,
@@ -1524,14 +1580,9 @@ impl super::MultiplicationSecretSharing for ShamirSecretSharing {
         }
 
         // 计算 z = c + d*b + e*a + d*e
-        // 注意：在实际协议中，d*e 项只有一个参与方（通常是 party 0）添加
         let db = field_mul(*d, beaver_b.y);
         let ea = field_mul(*e, beaver_a.y);
-        let de = if share_x.x == 1 { // 假设 party 0 对应 x=1
-            field_mul(*d, *e)
-        } else {
-            0
-        };
+        let de = field_mul(*d, *e);
         
         let result_y = field_add(
             field_add(beaver_c.y, db),
@@ -0,0 +1,219 @@
+//! # 门限转换 (Threshold Conversion)
+//!
+//! 在参与方集合不变的情况下，把 (t1, n) 分享转换为同一秘密的 (t2, n) 分享，
+//! 全程不在任何一处重构秘密。`ShamirSecretSharing::adjust_threshold`
+//! 会先在明文中重构秘密，只适合由可信方持有全部份额的场景。
+//!
+//! ## 协议概述
+//!
+//! - **升高门限 (t2 ≥ t1)**: 每一方生成常数项为 0 的 t2 - 1 次随机多项式（零分享），
+//!   把在各方 x 坐标处的取值发给对应参与方；各方把收到的值加到自己的份额上。
+//!   结果多项式的常数项不变，高次系数由各方的零分享随机化
+//! - **降低门限 (t2 < t1)**: 每一方用 t2 - 1 次随机多项式重新分享自己的份额；
+//!   各方用全体 x 坐标在 0 处的拉格朗日系数组合收到的子份额。
+//!   原多项式次数不超过 n - 1，因此组合后的常数项仍是原秘密
+//!
+//! 两种情况都只需要一轮点对点通信（每一方向其余各方发送一个域元素）。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let shares = ShamirSecretSharing::share(&42, 2, 5)?;
+//!
+//! let raised = convert_threshold(&shares, 2, 4)?;
+//! assert_eq!(ShamirSecretSharing::reconstruct(&raised[..4], 4)?, 42);
+//!
+//! let lowered = convert_threshold(&raised, 4, 3)?;
+//! assert_eq!(ShamirSecretSharing::reconstruct(&lowered[1..4], 3)?, 42);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{Share, ShamirSecretSharing, field_add, field_mul};
+use crate::utils::random_field_element;
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// 转换方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConversionKind {
+    /// 加上高次零分享（门限升高或不变）
+    ZeroSharing,
+    /// 重新分享后降次（门限降低）
+    Resharing,
+}
+
+/// 门限转换中一方发给另一方的消息
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversionMessage {
+    /// 发送方的 x 坐标
+    pub from: u64,
+    /// 接收方的 x 坐标
+    pub to: u64,
+    /// 零分享或子份额的取值
+    pub value: u64,
+}
+
+/// 参与门限转换的一方
+#[derive(Debug, Clone)]
+pub struct ThresholdConverter {
+    /// 本方的 x 坐标
+    party_x: u64,
+    /// 全体参与方的 x 坐标
+    party_xs: Vec<u64>,
+    /// 全体参与方在 0 处的拉格朗日系数（仅降低门限时使用）
+    lagrange: Vec<u64>,
+    /// 原门限
+    old_threshold: usize,
+    /// 新门限
+    new_threshold: usize,
+}
+
+impl ThresholdConverter {
+    /// 创建门限转换参与方
+    ///
+    /// # 参数
+    /// - `party_x`: 本方的 x 坐标
+    /// - `party_xs`: 全体参与方的 x 坐标（互不相同且非零）
+    /// - `old_threshold`: 原门限 t1
+    /// - `new_threshold`: 新门限 t2
+    ///
+    /// # 返回值
+    /// 参数不合法时返回错误
+    pub fn new(party_x: u64, party_xs: &[u64], old_threshold: usize, new_threshold: usize) -> Result<Self> {
+        let n = party_xs.len();
+        if old_threshold == 0 || new_threshold == 0 || old_threshold > n || new_threshold > n {
+            return Err(MpcError::InvalidThreshold);
+        }
+
+        let mut seen = HashSet::with_capacity(n);
+        if party_xs.iter().any(|&x| x == 0 || !seen.insert(x)) {
+            return Err(MpcError::InvalidSecretShare);
+        }
+        if !seen.contains(&party_x) {
+            return Err(MpcError::ProtocolError(format!("Party {} is not in the party set", party_x)));
+        }
+
+        let lagrange = if new_threshold < old_threshold {
+            ShamirSecretSharing::new().precompute_lagrange_coefficients(party_xs)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            party_x,
+            party_xs: party_xs.to_vec(),
+            lagrange,
+            old_threshold,
+            new_threshold,
+        })
+    }
+
+    /// 转换方式
+    pub fn kind(&self) -> ConversionKind {
+        if self.new_threshold >= self.old_threshold {
+            ConversionKind::ZeroSharing
+        } else {
+            ConversionKind::Resharing
+        }
+    }
+
+    /// 生成发给各方的消息（包括发给自己的一条）
+    ///
+    /// # 参数
+    /// - `share`: 本方持有的原份额
+    pub fn contribute(&self, share: &Share) -> Result<Vec<ConversionMessage>> {
+        if share.x != self.party_x {
+            return Err(MpcError::InvalidSecretShare);
+        }
+
+        let scheme = ShamirSecretSharing::new();
+        let mut coefficients: Vec<u64> = (0..self.new_threshold).map(|_| random_field_element()).collect();
+        coefficients[0] = match self.kind() {
+            ConversionKind::ZeroSharing => 0,
+            ConversionKind::Resharing => share.y,
+        };
+
+        Ok(self.party_xs.iter()
+            .map(|&to| ConversionMessage {
+                from: self.party_x,
+                to,
+                value: scheme.evaluate_polynomial(&coefficients, to),
+            })
+            .collect())
+    }
+
+    /// 组合收到的消息得到新份额
+    ///
+    /// # 参数
+    /// - `share`: 本方持有的原份额
+    /// - `messages`: 收到的消息，必须来自全体参与方且各一条
+    ///
+    /// # 返回值
+    /// 返回新门限下的份额
+    pub fn combine(&self, share: &Share, messages: &[ConversionMessage]) -> Result<Share> {
+        if share.x != self.party_x {
+            return Err(MpcError::InvalidSecretShare);
+        }
+
+        let mut values = vec![None; self.party_xs.len()];
+        for message in messages {
+            if message.to != self.party_x {
+                return Err(MpcError::ProtocolError(format!(
+                    "Message from {} is addressed to {}", message.from, message.to
+                )));
+            }
+            let index = self.party_xs.iter().position(|&x| x == message.from)
+                .ok_or_else(|| MpcError::ProtocolError(format!("Unknown sender {}", message.from)))?;
+            if values[index].replace(message.value).is_some() {
+                return Err(MpcError::ProtocolError(format!("Duplicate message from {}", message.from)));
+            }
+        }
+        if values.iter().any(Option::is_none) {
+            return Err(MpcError::InsufficientShares);
+        }
+
+        let y = match self.kind() {
+            ConversionKind::ZeroSharing => values.iter().flatten()
+                .fold(share.y, |acc, &value| field_add(acc, value)),
+            ConversionKind::Resharing => values.iter().flatten().zip(&self.lagrange)
+                .fold(0, |acc, (&value, &coefficient)| field_add(acc, field_mul(value, coefficient))),
+        };
+        Ok(Share::new(self.party_x, y))
+    }
+}
+
+/// 在本地模拟全体参与方执行门限转换
+///
+/// # 参数
+/// - `shares`: 全体参与方的原份额（每方一个）
+/// - `old_threshold`: 原门限 t1
+/// - `new_threshold`: 新门限 t2
+///
+/// # 返回值
+/// 按输入顺序返回各方的新份额
+pub fn convert_threshold(shares: &[Share], old_threshold: usize, new_threshold: usize) -> Result<Vec<Share>> {
+    let party_xs: Vec<u64> = shares.iter().map(|share| share.x).collect();
+    let converters = party_xs.iter()
+        .map(|&x| ThresholdConverter::new(x, &party_xs, old_threshold, new_threshold))
+        .collect::<Result<Vec<_>>>()?;
+
+    // 第 1 步：每一方生成消息
+    let mut inboxes = vec![Vec::with_capacity(shares.len()); shares.len()];
+    for (converter, share) in converters.iter().zip(shares) {
+        for (inbox, message) in inboxes.iter_mut().zip(converter.contribute(share)?) {
+            inbox.push(message);
+        }
+    }
+
+    // 第 2 步：每一方组合收到的消息
+    converters.iter()
+        .zip(shares)
+        .zip(&inboxes)
+        .map(|((converter, share), inbox)| converter.combine(share, inbox))
+        .collect()
+}
@@ -0,0 +1,240 @@
+//! # 门限签名 (Threshold BLS-style Signatures)
+//!
+//! 仿照 BLS 的门限签名：签名私钥 x 以 Shamir 份额的形式分给 n 方，任意 t 方各自用份额
+//! 生成部分签名，部分签名在指数上做拉格朗日插值即得到完整签名，全程不重构私钥。
+//!
+//! ## 构造
+//!
+//! 签名群复用 `vss` 的 p 阶承诺群，指数与份额同属 GF(p)，因此份额可以直接作为指数：
+//!
+//! - 公开参数是 Feldman 承诺 `VssCommitments`：公钥 X = C_0 = g^x，
+//!   第 i 方的验证密钥 X_i = `share_commitment(i)` = g^(x_i)
+//! - 部分签名 σ_i = H(m)^(x_i)，H 把消息哈希到承诺群，没有人知道 H(m) 的离散对数
+//! - 聚合 σ = ∏ σ_i^(λ_i) = H(m)^x，λ_i 为在 0 处的拉格朗日系数
+//!
+//! 与 BLS 一样，σ 只由私钥和消息决定，与参与签名的是哪 t 方无关。
+//!
+//! ## 配对替代
+//!
+//! BLS 用双线性配对检查 e(σ, g) = e(H(m), X)，承诺群上没有配对。
+//! 这里每个部分签名附带 Chaum-Pedersen 证明 log_g(X_i) = log_H(m)(σ_i)，
+//! 聚合签名保留参与插值的 t 个部分签名作为配对验证的替代：验证者逐个检查证明，
+//! 再重新插值确认 σ。因此签名长度随门限线性增长，验证需要全部公开承诺而不只是公钥。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let (public, shares) = ThresholdBls::generate_keys(2, 3)?;
+//! let message = b"transfer 10 tokens";
+//!
+//! // 第 1、3 方各自签名
+//! let partials = vec![
+//!     ThresholdBls::partial_sign(&shares[0], message)?,
+//!     ThresholdBls::partial_sign(&shares[2], message)?,
+//! ];
+//! assert!(partials.iter().all(|partial| ThresholdBls::verify_partial(&public, message, partial)));
+//!
+//! let signature = ThresholdBls::aggregate(&public, message, &partials)?;
+//! assert!(ThresholdBls::verify(&public, message, &signature));
+//! assert!(!ThresholdBls::verify(&public, b"transfer 99 tokens", &signature));
+//! # Ok(())
+//! # }
+//! ```
+
+use super::vss::{group_mul, group_pow, group_pow_wide, in_subgroup};
+use super::{
+    field_add, field_mul, field_sub, vss_generators, Share, ShamirSecretSharing, VssCommitments, VssDealer,
+    VssScheme, FIELD_PRIME, VSS_GROUP_COFACTOR, VSS_GROUP_MODULUS,
+};
+use crate::utils::transcript::Transcript;
+use crate::{MpcError, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+/// 消息哈希到承诺群的域分离标签
+const HASH_TO_GROUP_DOMAIN: &[u8] = b"mpc_api/threshold-bls/hash-to-group";
+
+/// Chaum-Pedersen 挑战的域分离标签
+const CHALLENGE_DOMAIN: &[u8] = b"mpc_api/threshold-bls/dleq";
+
+/// 一方的部分签名，附带与验证密钥指数相同的证明
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlsPartialSignature {
+    /// 签名方的 x 坐标
+    pub signer: u64,
+    /// σ_i = H(m)^(x_i)
+    pub value: u128,
+    /// 证明的挑战 c
+    pub challenge: u64,
+    /// 证明的响应 z = r + c·x_i
+    pub response: u64,
+}
+
+/// 聚合后的签名
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlsSignature {
+    /// σ = H(m)^x
+    pub value: u128,
+    /// 参与插值的 t 个部分签名，替代配对验证
+    pub partials: Vec<BlsPartialSignature>,
+}
+
+/// 门限 BLS 风格签名
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThresholdBls;
+
+impl ThresholdBls {
+    /// 由可信分发者生成随机私钥并分享
+    ///
+    /// # 参数
+    /// - `threshold`: 签名所需的参与方数量
+    /// - `party_count`: 参与方数量，x 坐标为 1..=party_count
+    ///
+    /// # 返回值
+    /// 返回公开的 Feldman 承诺和各方的私钥份额
+    pub fn generate_keys(threshold: usize, party_count: usize) -> Result<(VssCommitments, Vec<Share>)> {
+        let secret = rand::thread_rng().gen_range(1..FIELD_PRIME);
+        let dealer = VssDealer::new(VssScheme::Feldman, secret, threshold, party_count)?;
+        let shares = dealer.shares().into_iter().map(|share| share.share).collect();
+        Ok((dealer.commitments()?, shares))
+    }
+
+    /// 公钥 X = g^x
+    pub fn public_key(public: &VssCommitments) -> Option<u128> {
+        public.commitments.first().copied()
+    }
+
+    /// 把消息哈希到承诺群中的非单位元
+    pub fn hash_to_group(message: &[u8]) -> Result<u128> {
+        for counter in 0u32.. {
+            let digest = Sha256::new()
+                .chain_update(HASH_TO_GROUP_DOMAIN)
+                .chain_update(counter.to_le_bytes())
+                .chain_update(message)
+                .finalize();
+            let candidate = u128::from_le_bytes(digest[..16].try_into().expect("16-byte prefix")) % VSS_GROUP_MODULUS;
+            let point = group_pow_wide(candidate, VSS_GROUP_COFACTOR);
+            if point > 1 {
+                return Ok(point);
+            }
+        }
+        Err(MpcError::CryptographicError("Failed to hash the message to the group".to_string()))
+    }
+
+    /// 用私钥份额生成部分签名
+    ///
+    /// # 参数
+    /// - `share`: 本方的私钥份额
+    /// - `message`: 待签名的消息
+    pub fn partial_sign(share: &Share, message: &[u8]) -> Result<BlsPartialSignature> {
+        if share.x == 0 || share.y >= FIELD_PRIME {
+            return Err(MpcError::InvalidSecretShare);
+        }
+        let (g, _) = vss_generators()?;
+        let h = Self::hash_to_group(message)?;
+        let value = group_pow(h, share.y);
+
+        let nonce = rand::thread_rng().gen_range(0..FIELD_PRIME);
+        let challenge = challenge(
+            share.x, group_pow(g, share.y), h, value, group_pow(g, nonce), group_pow(h, nonce),
+        );
+        let response = field_add(nonce, field_mul(challenge, share.y));
+        Ok(BlsPartialSignature { signer: share.x, value, challenge, response })
+    }
+
+    /// 用签名方的验证密钥检查部分签名
+    pub fn verify_partial(public: &VssCommitments, message: &[u8], partial: &BlsPartialSignature) -> bool {
+        if !is_valid_public(public) || partial.signer == 0 || !in_subgroup(partial.value)
+            || partial.challenge >= FIELD_PRIME || partial.response >= FIELD_PRIME
+        {
+            return false;
+        }
+        let (Ok((g, _)), Ok(h)) = (vss_generators(), Self::hash_to_group(message)) else {
+            return false;
+        };
+
+        // A = g^z·X_i^(-c)，B = H(m)^z·σ_i^(-c)，群的阶为 p，取负即 p - c
+        let verification_key = public.share_commitment(partial.signer);
+        let negated = field_sub(0, partial.challenge);
+        let a = group_mul(group_pow(g, partial.response), group_pow(verification_key, negated));
+        let b = group_mul(group_pow(h, partial.response), group_pow(partial.value, negated));
+        challenge(partial.signer, verification_key, h, partial.value, a, b) == partial.challenge
+    }
+
+    /// 把部分签名聚合为完整签名
+    ///
+    /// # 参数
+    /// - `public`: 公开的 Feldman 承诺
+    /// - `message`: 被签名的消息
+    /// - `partials`: 收到的部分签名，按顺序取前 t 个
+    ///
+    /// # 返回值
+    /// 部分签名无效、重复或不足 t 个时返回错误
+    pub fn aggregate(public: &VssCommitments, message: &[u8], partials: &[BlsPartialSignature]) -> Result<BlsSignature> {
+        if !is_valid_public(public) {
+            return Err(MpcError::ProtocolError("Threshold signatures require Feldman commitments".to_string()));
+        }
+        let mut signers = HashSet::new();
+        for partial in partials {
+            if !signers.insert(partial.signer) {
+                return Err(MpcError::ProtocolError(format!(
+                    "Duplicate partial signature from signer {}", partial.signer
+                )));
+            }
+            if !Self::verify_partial(public, message, partial) {
+                return Err(MpcError::AuthenticationError(format!(
+                    "Partial signature from signer {} is invalid", partial.signer
+                )));
+            }
+        }
+        if partials.len() < public.threshold() {
+            return Err(MpcError::InsufficientShares);
+        }
+
+        let partials = partials[..public.threshold()].to_vec();
+        Ok(BlsSignature { value: interpolate(&partials)?, partials })
+    }
+
+    /// 验证聚合签名
+    pub fn verify(public: &VssCommitments, message: &[u8], signature: &BlsSignature) -> bool {
+        let signers: HashSet<u64> = signature.partials.iter().map(|partial| partial.signer).collect();
+        if signature.partials.len() != public.threshold() || signers.len() != signature.partials.len() {
+            return false;
+        }
+        signature.partials.iter().all(|partial| Self::verify_partial(public, message, partial))
+            && interpolate(&signature.partials).is_ok_and(|value| value == signature.value)
+    }
+}
+
+/// 只接受系数承诺都在群中的 Feldman 承诺
+fn is_valid_public(public: &VssCommitments) -> bool {
+    public.scheme == VssScheme::Feldman
+        && !public.commitments.is_empty()
+        && public.commitments.iter().all(|&commitment| in_subgroup(commitment))
+}
+
+/// 在指数上插值：∏ σ_i^(λ_i)
+fn interpolate(partials: &[BlsPartialSignature]) -> Result<u128> {
+    let xs: Vec<u64> = partials.iter().map(|partial| partial.signer).collect();
+    let lagrange = ShamirSecretSharing::new().precompute_lagrange_coefficients(&xs)?;
+    Ok(partials.iter()
+        .zip(lagrange)
+        .fold(1, |acc, (partial, coefficient)| group_mul(acc, group_pow(partial.value, coefficient))))
+}
+
+/// Chaum-Pedersen 证明的 Fiat-Shamir 挑战
+fn challenge(signer: u64, verification_key: u128, h: u128, value: u128, a: u128, b: u128) -> u64 {
+    let mut transcript = Transcript::new(CHALLENGE_DOMAIN);
+    transcript.append_u64(b"signer", signer);
+    let elements: [(&[u8], u128); 5] =
+        [(b"verification-key", verification_key), (b"h", h), (b"value", value), (b"a", a), (b"b", b)];
+    for (label, element) in elements {
+        transcript.append_message(label, &element.to_le_bytes());
+    }
+    transcript.challenge_field(b"challenge")
+}
@@ -0,0 +1,428 @@
+//! # 可验证秘密分享 (Verifiable Secret Sharing)
+//!
+//! `verify_shares` 只能检查一组份额是否落在同一多项式上，需要收集多方份额。
+//! 可验证秘密分享中，分发者公开对分享多项式系数的承诺，每个持有者只用自己的份额
+//! 就能非交互地验证份额是否正确：
+//!
+//! - **Feldman**: 公开 C_j = g^(a_j)，检查 g^(f(i)) = ∏ C_j^(i^j)。
+//!   C_0 = g^s 公开了秘密的群元素，秘密只在离散对数意义下隐藏
+//! - **Pedersen**: 另取随机多项式 b(x)，公开 C_j = g^(a_j)·h^(b_j)，份额附带 b(i)，
+//!   检查 g^(f(i))·h^(b(i)) = ∏ C_j^(i^j)。承诺完美隐藏秘密
+//!
+//! 份额就是普通的 Shamir 份额，可以直接交给 `ShamirSecretSharing::reconstruct`。
+//!
+//! ## 承诺群
+//!
+//! 指数运算要与份额的域一致，承诺群必须是 p 阶群（p 为 Goldilocks 素数）。
+//! 本模块使用 Z_P* 中的 p 阶子群，P = (2^63 - 2)·p + 1 为 127 位素数；
+//! g = 2^((P-1)/p)，h 由公开标签哈希到子群得到，没有人知道 log_g(h)。
+//!
+//! ## 投诉处理
+//!
+//! 1. 份额验证失败的持有者公开投诉（`VssComplaint`）
+//! 2. 分发者公开被投诉者的份额（`VssDealer::answer_complaints`）
+//! 3. 所有人用承诺检查公开的份额（`VssCommitments::resolve_complaints`）：
+//!    投诉达到门限（公开的份额足以重构秘密）、有投诉未被回应或公开的份额验证失败时，
+//!    分发者被取消资格；否则投诉者改用公开的份额
+//!
+//! ## 乘法关系证明
+//!
+//! 对三个 Pedersen 分享，`VssDealer::prove_product` 证明它们的常数项满足 c = a·b，
+//! `VssCommitments::verify_product` 只用公开承诺验证。承诺群的阶就是份额的域，
+//! 等式直接在 GF(p) 中成立，不需要范围证明。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::secret_sharing::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let dealer = VssDealer::new(VssScheme::Pedersen, 42, 2, 3)?;
+//! let commitments = dealer.commitments()?;
+//! let mut shares = dealer.shares();
+//! assert!(shares.iter().all(|share| commitments.verify_share(share)));
+//!
+//! // 发给第 3 方的份额在传输中被篡改
+//! shares[2].share.y = field_add(shares[2].share.y, 1);
+//! let complaints: Vec<VssComplaint> = shares.iter()
+//!     .filter_map(|share| commitments.check_share(share))
+//!     .collect();
+//! let answers = dealer.answer_complaints(&complaints);
+//! match commitments.resolve_complaints(&complaints, &answers) {
+//!     ComplaintOutcome::Resolved(revealed) => shares[2] = revealed[0].clone(),
+//!     ComplaintOutcome::Disqualified(reason) => panic!("{}", reason),
+//! }
+//!
+//! let points: Vec<Share> = shares.iter().map(|share| share.share.clone()).collect();
+//! assert_eq!(ShamirSecretSharing::reconstruct(&points[1..], 2)?, 42);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{field_add, field_mul, field_sub, Share, FIELD_PRIME};
+use crate::{MpcError, Result};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// 承诺群的模数 P = `VSS_GROUP_COFACTOR`·p + 1
+pub const VSS_GROUP_MODULUS: u128 = 0x7fff_ffff_7fff_fffe_8000_0001_ffff_ffff;
+
+/// (P - 1)/p，把 Z_P* 中的元素映射到 p 阶子群
+pub const VSS_GROUP_COFACTOR: u128 = (1 << 63) - 2;
+
+/// 第二个生成元 h 的公开标签
+const VSS_GENERATOR_DOMAIN: &[u8] = b"mpc_api/vss/pedersen-h";
+
+/// 乘法关系证明挑战的域分隔标签
+const VSS_PRODUCT_DOMAIN: &[u8] = b"mpc_api/vss/product";
+
+/// 承诺方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VssScheme {
+    /// C_j = g^(a_j)，计算隐藏
+    Feldman,
+    /// C_j = g^(a_j)·h^(b_j)，完美隐藏
+    Pedersen,
+}
+
+/// 一个持有者的份额
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VssShare {
+    /// 秘密多项式在持有者 x 坐标处的值
+    pub share: Share,
+    /// 盲化多项式的值 b(x)；Feldman 方案中为 0
+    pub blinding: u64,
+}
+
+/// 分发者公开的系数承诺
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VssCommitments {
+    /// 承诺方式
+    pub scheme: VssScheme,
+    /// 每个系数的承诺，`commitments[0]` 对应秘密
+    pub commitments: Vec<u128>,
+}
+
+/// 持有者对自己份额的投诉
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VssComplaint {
+    /// 投诉者的 x 坐标
+    pub accuser: u64,
+}
+
+/// 投诉处理结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComplaintOutcome {
+    /// 分发者公开的份额全部通过验证，按投诉顺序给出
+    Resolved(Vec<VssShare>),
+    /// 分发者被取消资格
+    Disqualified(String),
+}
+
+/// 三个 Pedersen 分享的常数项满足 c = a·b 的非交互证明
+///
+/// C_a = g^a·h^(r_a)，C_c = C_b^a·h^(r_c − a·r_b)。证明者取随机 x、s₁、s₂，
+/// 公开 T₁ = g^x·h^(s₁) 和 T₂ = C_b^x·h^(s₂)，挑战 e 由承诺、T₁、T₂ 和上下文哈希得到，
+/// 回应 z = x + e·a、z₁ = s₁ + e·r_a、z₂ = s₂ + e·(r_c − a·r_b)。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VssProductProof {
+    /// T₁ = g^x·h^(s₁)
+    pub t1: u128,
+    /// T₂ = C_b^x·h^(s₂)
+    pub t2: u128,
+    /// z = x + e·a
+    pub z: u64,
+    /// z₁ = s₁ + e·r_a
+    pub z1: u64,
+    /// z₂ = s₂ + e·(r_c − a·r_b)
+    pub z2: u64,
+}
+
+/// 分发者：持有分享多项式，可以回应投诉
+#[derive(Debug, Clone)]
+pub struct VssDealer {
+    scheme: VssScheme,
+    coefficients: Vec<u64>,
+    blinding: Vec<u64>,
+    party_count: usize,
+}
+
+impl VssDealer {
+    /// 为秘密生成随机的 `threshold - 1` 次分享多项式
+    ///
+    /// # 参数
+    /// - `scheme`: 承诺方式
+    /// - `secret`: 秘密
+    /// - `threshold`: 重构门限
+    /// - `party_count`: 持有者数量，持有者的 x 坐标为 1..=party_count
+    pub fn new(scheme: VssScheme, secret: u64, threshold: usize, party_count: usize) -> Result<Self> {
+        if threshold == 0 || threshold > party_count {
+            return Err(MpcError::InvalidThreshold);
+        }
+        let mut rng = rand::thread_rng();
+        let mut random_polynomial = || (0..threshold).map(|_| rng.gen_range(0..FIELD_PRIME)).collect::<Vec<u64>>();
+        let mut coefficients = random_polynomial();
+        coefficients[0] = secret % FIELD_PRIME;
+        let blinding = match scheme {
+            VssScheme::Feldman => vec![0; threshold],
+            VssScheme::Pedersen => random_polynomial(),
+        };
+        Ok(Self { scheme, coefficients, blinding, party_count })
+    }
+
+    /// 公开的系数承诺
+    pub fn commitments(&self) -> Result<VssCommitments> {
+        let (g, h) = vss_generators()?;
+        let commitments = self.coefficients.iter()
+            .zip(&self.blinding)
+            .map(|(&a, &b)| match self.scheme {
+                VssScheme::Feldman => group_pow(g, a),
+                VssScheme::Pedersen => group_mul(group_pow(g, a), group_pow(h, b)),
+            })
+            .collect();
+        Ok(VssCommitments { scheme: self.scheme, commitments })
+    }
+
+    /// x 坐标为 `party_x` 的持有者的份额
+    pub fn share(&self, party_x: u64) -> VssShare {
+        VssShare {
+            share: Share::new(party_x, evaluate(&self.coefficients, party_x)),
+            blinding: evaluate(&self.blinding, party_x),
+        }
+    }
+
+    /// 所有持有者的份额
+    pub fn shares(&self) -> Vec<VssShare> {
+        (1..=self.party_count as u64).map(|x| self.share(x)).collect()
+    }
+
+    /// 公开被投诉者的份额
+    pub fn answer_complaints(&self, complaints: &[VssComplaint]) -> Vec<VssShare> {
+        complaints.iter().map(|complaint| self.share(complaint.accuser)).collect()
+    }
+
+    /// 证明自己的秘密与 `b` 的秘密之积等于 `c` 的秘密
+    ///
+    /// # 参数
+    /// - `b`, `c`: 另外两个分享的分发者
+    /// - `context`: 绑定到挑战中的上下文（例如三元组 ID），证明不能挪用到其他上下文
+    ///
+    /// # 返回值
+    /// 任一分享不是 Pedersen 方案或 c ≠ a·b 时返回错误
+    pub fn prove_product(&self, b: &VssDealer, c: &VssDealer, context: &[u8]) -> Result<VssProductProof> {
+        if [self.scheme, b.scheme, c.scheme].iter().any(|&scheme| scheme != VssScheme::Pedersen) {
+            return Err(MpcError::CryptographicError("Product proofs require Pedersen commitments".to_string()));
+        }
+        let (a_value, r_a) = (self.coefficients[0], self.blinding[0]);
+        if c.coefficients[0] != field_mul(a_value, b.coefficients[0]) {
+            return Err(MpcError::CryptographicError("Cannot prove a product that does not hold".to_string()));
+        }
+        let (g, h) = vss_generators()?;
+        let commitments = [self.commitments()?, b.commitments()?, c.commitments()?];
+        let c_b = commitments[1].commitments[0];
+
+        let mut rng = rand::thread_rng();
+        let (x, s1, s2) = (rng.gen_range(0..FIELD_PRIME), rng.gen_range(0..FIELD_PRIME), rng.gen_range(0..FIELD_PRIME));
+        let t1 = group_mul(group_pow(g, x), group_pow(h, s1));
+        let t2 = group_mul(group_pow(c_b, x), group_pow(h, s2));
+        let e = product_challenge(&commitments[0], &commitments[1], &commitments[2], t1, t2, context);
+
+        let r_c = field_sub(c.blinding[0], field_mul(a_value, b.blinding[0]));
+        Ok(VssProductProof {
+            t1,
+            t2,
+            z: field_add(x, field_mul(e, a_value)),
+            z1: field_add(s1, field_mul(e, r_a)),
+            z2: field_add(s2, field_mul(e, r_c)),
+        })
+    }
+}
+
+impl VssCommitments {
+    /// 重构门限（多项式系数的数量）
+    pub fn threshold(&self) -> usize {
+        self.commitments.len()
+    }
+
+    /// 只用一个份额检查其是否与承诺一致
+    pub fn verify_share(&self, share: &VssShare) -> bool {
+        let Ok((g, h)) = vss_generators() else {
+            return false;
+        };
+        if self.commitments.is_empty() || share.share.x == 0 || !self.commitments.iter().all(|&c| in_subgroup(c)) {
+            return false;
+        }
+        if self.scheme == VssScheme::Feldman && share.blinding != 0 {
+            return false;
+        }
+
+        let expected = self.share_commitment(share.share.x);
+        let actual = match self.scheme {
+            VssScheme::Feldman => group_pow(g, share.share.y),
+            VssScheme::Pedersen => group_mul(group_pow(g, share.share.y), group_pow(h, share.blinding)),
+        };
+        actual == expected
+    }
+
+    /// x 坐标为 `party_x` 的份额的承诺 ∏ C_j^(x^j)
+    ///
+    /// Feldman 方案中即 g^(f(x))，可以作为该持有者的公开验证密钥
+    pub fn share_commitment(&self, party_x: u64) -> u128 {
+        let mut power = 1;
+        let mut expected = 1;
+        for &commitment in &self.commitments {
+            expected = group_mul(expected, group_pow(commitment, power));
+            power = field_mul(power, party_x % FIELD_PRIME);
+        }
+        expected
+    }
+
+    /// 检查自己的份额，失败时返回要公开的投诉
+    pub fn check_share(&self, share: &VssShare) -> Option<VssComplaint> {
+        (!self.verify_share(share)).then_some(VssComplaint { accuser: share.share.x })
+    }
+
+    /// 根据分发者公开的份额处理投诉
+    ///
+    /// # 参数
+    /// - `complaints`: 所有持有者公开的投诉
+    /// - `answers`: 分发者按投诉顺序公开的份额
+    ///
+    /// # 返回值
+    /// 所有投诉都得到有效回应时返回公开的份额，否则分发者被取消资格
+    pub fn resolve_complaints(&self, complaints: &[VssComplaint], answers: &[VssShare]) -> ComplaintOutcome {
+        let mut accusers: Vec<u64> = complaints.iter().map(|complaint| complaint.accuser).collect();
+        accusers.sort_unstable();
+        accusers.dedup();
+        if accusers.len() >= self.threshold() {
+            return ComplaintOutcome::Disqualified(format!(
+                "{} complaints reach the threshold {}", accusers.len(), self.threshold()
+            ));
+        }
+        if answers.len() != complaints.len() {
+            return ComplaintOutcome::Disqualified(format!(
+                "Dealer answered {} of {} complaints", answers.len(), complaints.len()
+            ));
+        }
+        for (complaint, answer) in complaints.iter().zip(answers) {
+            if answer.share.x != complaint.accuser || !self.verify_share(answer) {
+                return ComplaintOutcome::Disqualified(format!(
+                    "Revealed share for party {} does not match the commitments", complaint.accuser
+                ));
+            }
+        }
+        ComplaintOutcome::Resolved(answers.to_vec())
+    }
+
+    /// 验证 `VssDealer::prove_product` 生成的证明：本承诺与 `b` 的常数项之积等于 `c` 的常数项
+    pub fn verify_product(&self, b: &VssCommitments, c: &VssCommitments, proof: &VssProductProof, context: &[u8]) -> bool {
+        let Ok((g, h)) = vss_generators() else {
+            return false;
+        };
+        let all = [self, b, c];
+        if all.iter().any(|commitments| {
+            commitments.scheme != VssScheme::Pedersen
+                || commitments.commitments.is_empty()
+                || !commitments.commitments.iter().all(|&c| in_subgroup(c))
+        }) {
+            return false;
+        }
+        if !in_subgroup(proof.t1) || !in_subgroup(proof.t2)
+            || [proof.z, proof.z1, proof.z2].iter().any(|&z| z >= FIELD_PRIME) {
+            return false;
+        }
+
+        let (c_a, c_b, c_c) = (self.commitments[0], b.commitments[0], c.commitments[0]);
+        let e = product_challenge(self, b, c, proof.t1, proof.t2, context);
+        group_mul(group_pow(g, proof.z), group_pow(h, proof.z1)) == group_mul(proof.t1, group_pow(c_a, e))
+            && group_mul(group_pow(c_b, proof.z), group_pow(h, proof.z2)) == group_mul(proof.t2, group_pow(c_c, e))
+    }
+}
+
+/// 乘法关系证明的 Fiat–Shamir 挑战
+fn product_challenge(
+    a: &VssCommitments,
+    b: &VssCommitments,
+    c: &VssCommitments,
+    t1: u128,
+    t2: u128,
+    context: &[u8],
+) -> u64 {
+    let mut hasher = Sha256::new()
+        .chain_update(VSS_PRODUCT_DOMAIN)
+        .chain_update((context.len() as u64).to_le_bytes())
+        .chain_update(context);
+    for commitments in [a, b, c] {
+        hasher.update((commitments.commitments.len() as u64).to_le_bytes());
+        for commitment in &commitments.commitments {
+            hasher.update(commitment.to_le_bytes());
+        }
+    }
+    let digest = hasher.chain_update(t1.to_le_bytes()).chain_update(t2.to_le_bytes()).finalize();
+    u64::from_le_bytes(digest[..8].try_into().expect("8-byte prefix")) % FIELD_PRIME
+}
+
+/// 承诺群的两个生成元 (g, h)
+pub fn vss_generators() -> Result<(u128, u128)> {
+    let g = group_pow_wide(2, VSS_GROUP_COFACTOR);
+    for counter in 0u32.. {
+        let digest = Sha256::new()
+            .chain_update(VSS_GENERATOR_DOMAIN)
+            .chain_update(counter.to_le_bytes())
+            .finalize();
+        let candidate = u128::from_le_bytes(digest[..16].try_into().expect("16-byte prefix")) % VSS_GROUP_MODULUS;
+        let h = group_pow_wide(candidate, VSS_GROUP_COFACTOR);
+        if h > 1 && h != g {
+            return Ok((g, h));
+        }
+    }
+    Err(MpcError::CryptographicError("Failed to derive the VSS generator".to_string()))
+}
+
+/// Horner 法求多项式在 x 处的值
+fn evaluate(coefficients: &[u64], x: u64) -> u64 {
+    coefficients.iter().rev().fold(0, |acc, &c| field_add(field_mul(acc, x % FIELD_PRIME), c))
+}
+
+pub(super) fn in_subgroup(element: u128) -> bool {
+    element != 0 && element < VSS_GROUP_MODULUS && group_pow(element, FIELD_PRIME) == 1
+}
+
+/// 模 P 加法，P < 2^127 保证不溢出
+fn group_add(a: u128, b: u128) -> u128 {
+    let sum = a + b;
+    if sum >= VSS_GROUP_MODULUS { sum - VSS_GROUP_MODULUS } else { sum }
+}
+
+/// 模 P 乘法：逐位移位相加，中间值不超过 2P
+pub(super) fn group_mul(a: u128, b: u128) -> u128 {
+    let mut result = 0;
+    let mut base = a % VSS_GROUP_MODULUS;
+    let mut b = b % VSS_GROUP_MODULUS;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = group_add(result, base);
+        }
+        base = group_add(base, base);
+        b >>= 1;
+    }
+    result
+}
+
+pub(super) fn group_pow(base: u128, exponent: u64) -> u128 {
+    group_pow_wide(base, exponent as u128)
+}
+
+pub(super) fn group_pow_wide(base: u128, mut exponent: u128) -> u128 {
+    let mut result = 1;
+    let mut base = base % VSS_GROUP_MODULUS;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = group_mul(result, base);
+        }
+        base = group_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
@@ -0,0 +1,335 @@
+//! # 审计日志导出与查询 (Audit Log Export and Query)
+//!
+//! `AuditLogger` 在内存（以及可选的 `StorageBackend`）中保存事件；本模块补充：
+//!
+//! - **AuditSink**: 每条事件记录后依次写入已注册的导出端
+//!   - `JsonlFileSink`: 只追加的 JSON Lines 文件，每行一个 `SecurityEvent`
+//!   - `SyslogSink`: 以 RFC 5424 格式经 UDP 发给 syslog 收集器，消息体为 CEF
+//!     （ArcSight Common Event Format），SIEM 可以直接解析
+//! - **AuditQuery**: 按时间范围、威胁类型、严重级别和参与方过滤事件
+//! - **防篡改**: 事件的 `audit_bytes` 依次进入审计日志的 Merkle 累加器，
+//!   `AuditLogger::current_root` 给出当前的根。从导出文件读回事件后用 `audit_root`
+//!   重新计算，与发布过的根比较即可发现删改、插入或重排
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::security::*;
+//! use std::sync::Arc;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let path = std::env::temp_dir().join(format!("mpc_audit_doc_{}.jsonl", std::process::id()));
+//! # let _ = std::fs::remove_file(&path);
+//! let logger = AuditLogger::new(SecurityPolicy::default());
+//! logger.add_sink(Arc::new(JsonlFileSink::open(&path)?));
+//!
+//! logger.log_event(SecurityEvent::new(ThreatType::ReplayAttack, SecurityLevel::High, "duplicate nonce".to_string()))?;
+//! logger.log_event(SecurityEvent::new(ThreatType::TimingAttack, SecurityLevel::Low, "slow round".to_string()))?;
+//!
+//! let high = logger.query(&AuditQuery::new().min_severity(SecurityLevel::High))?;
+//! assert_eq!(high.len(), 1);
+//!
+//! // 导出文件与内存中的日志对应同一个 Merkle 根
+//! let exported = JsonlFileSink::read_events(&path)?;
+//! assert_eq!(audit_root(&exported)?, logger.current_root());
+//! # std::fs::remove_file(&path).ok();
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{SecurityEvent, SecurityLevel, ThreatType};
+use crate::commitment::MerkleAccumulator;
+use crate::{MpcError, Result};
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 审计事件的导出端
+pub trait AuditSink: fmt::Debug + Send + Sync {
+    /// 写出一条已记录的事件
+    fn write(&self, event: &SecurityEvent) -> Result<()>;
+
+    /// 把缓冲的数据写到底层介质
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// 只追加的 JSON Lines 文件
+///
+/// 每条事件写完即刷新到文件，进程崩溃时最多丢失正在写的一行。
+#[derive(Debug)]
+pub struct JsonlFileSink {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl JsonlFileSink {
+    /// 以追加模式打开文件，文件不存在时创建
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| MpcError::StorageError(format!("Cannot open audit log {}: {}", path.display(), e)))?;
+        Ok(Self { path, file: Mutex::new(file) })
+    }
+
+    /// 文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 按写入顺序读回文件中的全部事件
+    pub fn read_events(path: impl AsRef<Path>) -> Result<Vec<SecurityEvent>> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .map_err(|e| MpcError::StorageError(format!("Cannot open audit log {}: {}", path.display(), e)))?;
+        BufReader::new(file)
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.as_ref().map_or(true, |line| !line.trim().is_empty()))
+            .map(|(number, line)| {
+                let line = line.map_err(|e| MpcError::StorageError(e.to_string()))?;
+                serde_json::from_str(&line).map_err(|e| {
+                    MpcError::SerializationError(format!("{}:{}: {}", path.display(), number + 1, e))
+                })
+            })
+            .collect()
+    }
+}
+
+impl AuditSink for JsonlFileSink {
+    fn write(&self, event: &SecurityEvent) -> Result<()> {
+        let mut line = serde_json::to_vec(event).map_err(|e| MpcError::SerializationError(e.to_string()))?;
+        line.push(b'\n');
+        let mut file = self.file.lock().unwrap();
+        // 一次 write_all 写出整行，多个写入方不会交错
+        file.write_all(&line)
+            .and_then(|_| file.flush())
+            .map_err(|e| MpcError::StorageError(format!("Cannot append to audit log {}: {}", self.path.display(), e)))
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.file.lock().unwrap().sync_data().map_err(|e| MpcError::StorageError(e.to_string()))
+    }
+}
+
+/// syslog 的 facility：security/authorization messages (10)
+const SYSLOG_FACILITY: u8 = 10;
+
+/// 经 UDP 发送 RFC 5424 syslog 消息，消息体为 CEF
+#[derive(Debug)]
+pub struct SyslogSink {
+    socket: UdpSocket,
+    target: SocketAddr,
+    hostname: String,
+    app_name: String,
+}
+
+impl SyslogSink {
+    /// 连接到 syslog 收集器
+    pub fn connect(target: SocketAddr, app_name: &str) -> Result<Self> {
+        let bind: SocketAddr = if target.is_ipv4() { ([0, 0, 0, 0], 0).into() } else { ([0u16; 8], 0).into() };
+        let socket = UdpSocket::bind(bind).map_err(|e| MpcError::NetworkError(e.to_string()))?;
+        let hostname = std::env::var("HOSTNAME").unwrap_or_else(|_| "-".to_string());
+        Ok(Self { socket, target, hostname, app_name: app_name.to_string() })
+    }
+
+    /// 事件对应的 syslog 消息
+    pub fn format_message(&self, event: &SecurityEvent) -> String {
+        let priority = SYSLOG_FACILITY * 8 + syslog_severity(event.severity);
+        let timestamp = chrono::DateTime::<chrono::Utc>::from(event.timestamp)
+            .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        format!(
+            "<{}>1 {} {} {} {} {} - {}",
+            priority, timestamp, self.hostname, self.app_name, std::process::id(), event.threat_type.cef_signature(), event.to_cef()
+        )
+    }
+}
+
+impl AuditSink for SyslogSink {
+    fn write(&self, event: &SecurityEvent) -> Result<()> {
+        self.socket
+            .send_to(self.format_message(event).as_bytes(), self.target)
+            .map(|_| ())
+            .map_err(|e| MpcError::NetworkError(format!("Cannot send audit event to {}: {}", self.target, e)))
+    }
+}
+
+/// syslog 严重级别（0 最严重）
+fn syslog_severity(level: SecurityLevel) -> u8 {
+    match level {
+        SecurityLevel::Low => 6,      // informational
+        SecurityLevel::Medium => 4,   // warning
+        SecurityLevel::High => 3,     // error
+        SecurityLevel::Critical => 2, // critical
+    }
+}
+
+impl ThreatType {
+    /// CEF 的 Signature ID
+    pub fn cef_signature(&self) -> &'static str {
+        match self {
+            ThreatType::SideChannelAttack => "side-channel",
+            ThreatType::TimingAttack => "timing",
+            ThreatType::MemoryAttack => "memory",
+            ThreatType::ProtocolAttack => "protocol",
+            ThreatType::ReplayAttack => "replay",
+            ThreatType::DenialOfService => "dos",
+            ThreatType::MaliciousParty => "malicious-party",
+            ThreatType::NetworkAttack => "network",
+            ThreatType::CryptographicAttack => "cryptographic",
+            ThreatType::PhysicalAttack => "physical",
+            ThreatType::SecretDisclosure => "secret-disclosure",
+            ThreatType::StaleMaterial => "stale-material",
+            ThreatType::KeyLifecycle => "key-lifecycle",
+        }
+    }
+}
+
+impl SecurityEvent {
+    /// 以 CEF（Common Event Format）表示事件
+    ///
+    /// 严重级别映射到 CEF 的 0-10；事件 ID、时间、描述、参与方、缓解措施和序列号
+    /// 分别写入 `externalId`、`rt`、`msg`、`suser`、`act` 和 `cs1`。
+    pub fn to_cef(&self) -> String {
+        let severity = match self.severity {
+            SecurityLevel::Low => 3,
+            SecurityLevel::Medium => 5,
+            SecurityLevel::High => 8,
+            SecurityLevel::Critical => 10,
+        };
+        let millis = self.timestamp.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+
+        let mut extension = vec![
+            ("externalId", self.id.clone()),
+            ("rt", millis.to_string()),
+            ("msg", self.description.clone()),
+        ];
+        if let Some(peer_id) = &self.peer_id {
+            extension.push(("suser", peer_id.clone()));
+        }
+        if let Some(mitigation) = &self.mitigation {
+            extension.push(("act", mitigation.clone()));
+        }
+        if let Some(sequence) = self.context.get("sequence") {
+            extension.push(("cs1Label", "sequence".to_string()));
+            extension.push(("cs1", sequence.clone()));
+        }
+
+        format!(
+            "CEF:0|mpc_api|mpc_api|{}|{}|{}|{}|{}",
+            escape_cef_header(env!("CARGO_PKG_VERSION")),
+            escape_cef_header(self.threat_type.cef_signature()),
+            escape_cef_header(self.threat_type.name()),
+            severity,
+            extension.iter()
+                .map(|(key, value)| format!("{}={}", key, escape_cef_extension(value)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
+fn escape_cef_header(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn escape_cef_extension(value: &str) -> String {
+    value.replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+/// 审计事件过滤条件
+///
+/// 所有条件同时满足的事件才会被选中；未设置的条件不限制。
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+    threat_types: Vec<ThreatType>,
+    min_severity: Option<SecurityLevel>,
+    peer_id: Option<String>,
+    limit: Option<usize>,
+}
+
+impl AuditQuery {
+    /// 不限制任何条件的查询
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 只选择不早于 `time` 的事件
+    pub fn since(mut self, time: SystemTime) -> Self {
+        self.since = Some(time);
+        self
+    }
+
+    /// 只选择早于 `time` 的事件
+    pub fn until(mut self, time: SystemTime) -> Self {
+        self.until = Some(time);
+        self
+    }
+
+    /// 只选择给定类型的事件，可以多次调用以选择多种类型
+    pub fn threat_type(mut self, threat_type: ThreatType) -> Self {
+        self.threat_types.push(threat_type);
+        self
+    }
+
+    /// 只选择严重级别不低于 `level` 的事件
+    pub fn min_severity(mut self, level: SecurityLevel) -> Self {
+        self.min_severity = Some(level);
+        self
+    }
+
+    /// 只选择归属于给定参与方的事件
+    pub fn peer(mut self, peer_id: &str) -> Self {
+        self.peer_id = Some(peer_id.to_string());
+        self
+    }
+
+    /// 最多返回最早的 `limit` 个事件
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// 事件是否满足条件（不考虑 `limit`）
+    pub fn matches(&self, event: &SecurityEvent) -> bool {
+        self.since.is_none_or(|since| event.timestamp >= since)
+            && self.until.is_none_or(|until| event.timestamp < until)
+            && (self.threat_types.is_empty() || self.threat_types.contains(&event.threat_type))
+            && self.min_severity.is_none_or(|level| event.severity >= level)
+            && self.peer_id.as_ref().is_none_or(|peer| event.peer_id.as_ref() == Some(peer))
+    }
+
+    /// 按顺序过滤事件
+    pub fn apply<'a>(&self, events: impl IntoIterator<Item = &'a SecurityEvent>) -> Vec<SecurityEvent> {
+        events.into_iter()
+            .filter(|event| self.matches(event))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+}
+
+/// 按顺序由事件计算审计日志的 Merkle 根
+///
+/// 与 `AuditLogger::current_root` 比较，用于验证导出的事件没有被删改、插入或重排。
+/// 事件必须从日志的第一条开始、包含全部事件。
+pub fn audit_root(events: &[SecurityEvent]) -> Result<[u8; 32]> {
+    let mut tree = MerkleAccumulator::new();
+    for event in events {
+        tree.append(&event.audit_bytes()?);
+    }
+    Ok(tree.root())
+}
@@ -0,0 +1,388 @@
+//! # 协议事故与自动响应 (Protocol Incidents)
+//!
+//! 把协议执行中的异常（轮次超时、整体超时、重复的 MAC 校验失败、重连风暴）
+//! 表示为带有参与方归属的类型化事故，并根据 `SecurityPolicy` 中的
+//! `IncidentResponsePolicy` 自动采取响应：隔离参与方、中止会话或暂停并等待人工恢复。
+//!
+//! 事故通过 `SecurityManager::report_incident`（或携带事故的
+//! `SecurityEvent` 交给 `SecurityManager::report_security_event`）上报。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::security::*;
+//! use std::time::Duration;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let manager = SecurityManager::with_policy(SecurityPolicy::strict())?;
+//!
+//! let incident = ProtocolIncident::MacCheckFailure { session_id: "s1".to_string() };
+//! let response = manager.report_incident("party-2", incident)?;
+//! assert_eq!(response, AutomaticResponse::QuarantinePeer);
+//! assert!(manager.is_peer_quarantined("party-2"));
+//!
+//! let mut deadline = LayeredDeadline::new("s2", Duration::from_secs(60), Duration::from_secs(5));
+//! deadline.begin_round(1);
+//! assert!(deadline.check().is_none());
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{SecurityEvent, SecurityLevel, ThreatType};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+/// 协议事故
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProtocolIncident {
+    /// 某一轮在轮次时限内没有完成
+    RoundTimeout {
+        /// 会话标识
+        session_id: String,
+        /// 超时的轮次
+        round: usize,
+        /// 已经过的时间
+        elapsed: Duration,
+    },
+    /// 整个协议在总时限内没有完成
+    ProtocolTimeout {
+        /// 会话标识
+        session_id: String,
+        /// 已经过的时间
+        elapsed: Duration,
+    },
+    /// MAC 校验失败
+    MacCheckFailure {
+        /// 会话标识
+        session_id: String,
+    },
+    /// 参与方重新建立连接
+    Reconnect,
+}
+
+impl ProtocolIncident {
+    /// 事故所属的会话
+    pub fn session_id(&self) -> Option<&str> {
+        match self {
+            ProtocolIncident::RoundTimeout { session_id, .. }
+            | ProtocolIncident::ProtocolTimeout { session_id, .. }
+            | ProtocolIncident::MacCheckFailure { session_id } => Some(session_id),
+            ProtocolIncident::Reconnect => None,
+        }
+    }
+
+    /// 对应的威胁类型
+    pub fn threat_type(&self) -> ThreatType {
+        match self {
+            ProtocolIncident::RoundTimeout { .. } | ProtocolIncident::ProtocolTimeout { .. } => {
+                ThreatType::DenialOfService
+            }
+            ProtocolIncident::MacCheckFailure { .. } => ThreatType::MaliciousParty,
+            ProtocolIncident::Reconnect => ThreatType::NetworkAttack,
+        }
+    }
+
+    /// 单次事故的严重级别
+    pub fn severity(&self) -> SecurityLevel {
+        match self {
+            ProtocolIncident::MacCheckFailure { .. } => SecurityLevel::High,
+            ProtocolIncident::RoundTimeout { .. } | ProtocolIncident::ProtocolTimeout { .. } => {
+                SecurityLevel::Medium
+            }
+            ProtocolIncident::Reconnect => SecurityLevel::Low,
+        }
+    }
+
+    /// 事故的描述
+    pub fn describe(&self) -> String {
+        match self {
+            ProtocolIncident::RoundTimeout { session_id, round, elapsed } => {
+                format!("会话 {} 第 {} 轮超时 ({:?})", session_id, round, elapsed)
+            }
+            ProtocolIncident::ProtocolTimeout { session_id, elapsed } => {
+                format!("会话 {} 协议超时 ({:?})", session_id, elapsed)
+            }
+            ProtocolIncident::MacCheckFailure { session_id } => {
+                format!("会话 {} MAC 校验失败", session_id)
+            }
+            ProtocolIncident::Reconnect => "参与方重新连接".to_string(),
+        }
+    }
+}
+
+/// 策略驱动的自动响应，按严厉程度排序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum AutomaticResponse {
+    /// 仅记录
+    LogOnly,
+    /// 中止事故所属的会话
+    AbortSession,
+    /// 隔离参与方，拒绝其后续消息
+    QuarantinePeer,
+    /// 暂停所有协议，等待人工恢复
+    RequireManualResume,
+}
+
+impl AutomaticResponse {
+    /// 响应的描述
+    pub fn describe(&self) -> &'static str {
+        match self {
+            AutomaticResponse::LogOnly => "仅记录",
+            AutomaticResponse::AbortSession => "中止会话",
+            AutomaticResponse::QuarantinePeer => "隔离参与方",
+            AutomaticResponse::RequireManualResume => "暂停并等待人工恢复",
+        }
+    }
+}
+
+/// 事故响应策略
+///
+/// 同一参与方的某类事故累计达到上限时触发对应的响应；
+/// 重连只统计 `reconnect_window_secs` 时间窗口内的次数。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncidentResponsePolicy {
+    /// 触发超时响应所需的超时次数
+    pub timeout_limit: u32,
+    /// 触发 MAC 失败响应所需的失败次数
+    pub mac_failure_limit: u32,
+    /// 时间窗口内判定为重连风暴的重连次数
+    pub reconnect_limit: u32,
+    /// 重连统计窗口 (秒)
+    pub reconnect_window_secs: u64,
+    /// 超时达到上限时的响应
+    pub on_timeout: AutomaticResponse,
+    /// MAC 失败达到上限时的响应
+    pub on_mac_failure: AutomaticResponse,
+    /// 重连风暴时的响应
+    pub on_reconnect_storm: AutomaticResponse,
+}
+
+impl IncidentResponsePolicy {
+    /// 只记录、不采取任何自动响应（开发环境）
+    pub fn log_only() -> Self {
+        Self {
+            on_timeout: AutomaticResponse::LogOnly,
+            on_mac_failure: AutomaticResponse::LogOnly,
+            on_reconnect_storm: AutomaticResponse::LogOnly,
+            ..Self::default()
+        }
+    }
+
+    /// 严格策略：一次 MAC 失败即隔离参与方，重连风暴需要人工恢复
+    pub fn strict() -> Self {
+        Self {
+            timeout_limit: 1,
+            mac_failure_limit: 1,
+            reconnect_limit: 3,
+            reconnect_window_secs: 60,
+            on_timeout: AutomaticResponse::AbortSession,
+            on_mac_failure: AutomaticResponse::QuarantinePeer,
+            on_reconnect_storm: AutomaticResponse::RequireManualResume,
+        }
+    }
+
+    /// 事故达到上限时的响应
+    fn response_for(&self, incident: &ProtocolIncident) -> AutomaticResponse {
+        match incident {
+            ProtocolIncident::RoundTimeout { .. } | ProtocolIncident::ProtocolTimeout { .. } => self.on_timeout,
+            ProtocolIncident::MacCheckFailure { .. } => self.on_mac_failure,
+            ProtocolIncident::Reconnect => self.on_reconnect_storm,
+        }
+    }
+}
+
+impl Default for IncidentResponsePolicy {
+    fn default() -> Self {
+        Self {
+            timeout_limit: 3,
+            mac_failure_limit: 2,
+            reconnect_limit: 5,
+            reconnect_window_secs: 60,
+            on_timeout: AutomaticResponse::AbortSession,
+            on_mac_failure: AutomaticResponse::QuarantinePeer,
+            on_reconnect_storm: AutomaticResponse::QuarantinePeer,
+        }
+    }
+}
+
+/// 分层超时：整个协议的总时限和每一轮的时限
+#[derive(Debug, Clone)]
+pub struct LayeredDeadline {
+    /// 会话标识
+    session_id: String,
+    /// 协议总时限
+    protocol_timeout: Duration,
+    /// 单轮时限
+    round_timeout: Duration,
+    /// 协议开始时间
+    started: Instant,
+    /// 当前轮次及其开始时间
+    current_round: Option<(usize, Instant)>,
+    /// 判定超时前额外给予的时钟偏差余量
+    skew_allowance: Duration,
+}
+
+impl LayeredDeadline {
+    /// 创建分层超时并开始计时
+    pub fn new(session_id: &str, protocol_timeout: Duration, round_timeout: Duration) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            protocol_timeout,
+            round_timeout,
+            started: Instant::now(),
+            current_round: None,
+            skew_allowance: Duration::ZERO,
+        }
+    }
+
+    /// 在两层时限上都加上时钟偏差余量
+    ///
+    /// 轮次的开始时间由各方的本地时钟决定，时钟偏差会让诚实但较慢的参与方
+    /// 看起来超时。余量通常取各参与方 `ClockSkewTracker::deadline_allowance` 的最大值。
+    pub fn with_skew_allowance(mut self, allowance: Duration) -> Self {
+        self.skew_allowance = allowance;
+        self
+    }
+
+    /// 开始新的一轮
+    pub fn begin_round(&mut self, round: usize) {
+        self.current_round = Some((round, Instant::now()));
+    }
+
+    /// 检查当前是否超时
+    pub fn check(&self) -> Option<ProtocolIncident> {
+        self.check_at(Instant::now())
+    }
+
+    /// 检查在给定时刻是否超时；协议总时限优先于单轮时限
+    pub fn check_at(&self, now: Instant) -> Option<ProtocolIncident> {
+        let elapsed = now.saturating_duration_since(self.started);
+        if elapsed > self.protocol_timeout + self.skew_allowance {
+            return Some(ProtocolIncident::ProtocolTimeout {
+                session_id: self.session_id.clone(),
+                elapsed,
+            });
+        }
+
+        let (round, round_started) = self.current_round?;
+        let round_elapsed = now.saturating_duration_since(round_started);
+        if round_elapsed > self.round_timeout + self.skew_allowance {
+            return Some(ProtocolIncident::RoundTimeout {
+                session_id: self.session_id.clone(),
+                round,
+                elapsed: round_elapsed,
+            });
+        }
+        None
+    }
+}
+
+/// 单个参与方的事故计数
+#[derive(Debug, Default)]
+struct PeerIncidents {
+    timeouts: u32,
+    mac_failures: u32,
+    reconnects: VecDeque<Instant>,
+}
+
+/// 事故跟踪器，维护计数和已经采取的响应
+#[derive(Debug, Default)]
+pub(crate) struct IncidentTracker {
+    peers: HashMap<String, PeerIncidents>,
+    quarantined: HashSet<String>,
+    aborted_sessions: HashSet<String>,
+    manual_resume_required: bool,
+}
+
+impl IncidentTracker {
+    /// 记录事故，返回根据策略采取的响应
+    pub(crate) fn record(
+        &mut self,
+        peer_id: &str,
+        incident: &ProtocolIncident,
+        policy: &IncidentResponsePolicy,
+    ) -> AutomaticResponse {
+        let counters = self.peers.entry(peer_id.to_string()).or_default();
+        let triggered = match incident {
+            ProtocolIncident::RoundTimeout { .. } | ProtocolIncident::ProtocolTimeout { .. } => {
+                counters.timeouts += 1;
+                counters.timeouts >= policy.timeout_limit
+            }
+            ProtocolIncident::MacCheckFailure { .. } => {
+                counters.mac_failures += 1;
+                counters.mac_failures >= policy.mac_failure_limit
+            }
+            ProtocolIncident::Reconnect => {
+                let now = Instant::now();
+                let window = Duration::from_secs(policy.reconnect_window_secs);
+                counters.reconnects.push_back(now);
+                while counters.reconnects.front()
+                    .is_some_and(|&t| now.saturating_duration_since(t) > window)
+                {
+                    counters.reconnects.pop_front();
+                }
+                counters.reconnects.len() as u32 >= policy.reconnect_limit
+            }
+        };
+
+        if !triggered {
+            return AutomaticResponse::LogOnly;
+        }
+
+        let response = policy.response_for(incident);
+        match response {
+            AutomaticResponse::LogOnly => {}
+            AutomaticResponse::AbortSession => {
+                if let Some(session_id) = incident.session_id() {
+                    self.aborted_sessions.insert(session_id.to_string());
+                }
+            }
+            AutomaticResponse::QuarantinePeer => {
+                self.quarantined.insert(peer_id.to_string());
+            }
+            AutomaticResponse::RequireManualResume => {
+                self.manual_resume_required = true;
+            }
+        }
+        response
+    }
+
+    pub(crate) fn is_quarantined(&self, peer_id: &str) -> bool {
+        self.quarantined.contains(peer_id)
+    }
+
+    pub(crate) fn is_session_aborted(&self, session_id: &str) -> bool {
+        self.aborted_sessions.contains(session_id)
+    }
+
+    pub(crate) fn manual_resume_required(&self) -> bool {
+        self.manual_resume_required
+    }
+
+    /// 解除隔离并清零该参与方的计数
+    pub(crate) fn release(&mut self, peer_id: &str) -> bool {
+        self.peers.remove(peer_id);
+        self.quarantined.remove(peer_id)
+    }
+
+    pub(crate) fn resume(&mut self) {
+        self.manual_resume_required = false;
+    }
+}
+
+impl SecurityEvent {
+    /// 由协议事故创建安全事件
+    pub fn from_incident(peer_id: &str, incident: ProtocolIncident) -> Self {
+        let mut event = SecurityEvent::new(
+            incident.threat_type(),
+            incident.severity(),
+            incident.describe(),
+        ).with_peer(peer_id);
+        if let Some(session_id) = incident.session_id() {
+            event.context.insert("session_id".to_string(), session_id.to_string());
+        }
+        event.incident = Some(incident);
+        event
+    }
+}
@@ -0,0 +1,410 @@
+//! # 密钥派生与密钥层次 (Key Derivation and Key Hierarchy)
+//!
+//! `KeyStore` 从一个根密钥按路径派生整棵密钥树，例如 `spdz/mac`、`network/tls`：
+//!
+//! - **HKDF 派生**: 根密钥经 HKDF-Extract 得到伪随机密钥，路径的每一段再经 HKDF-Expand
+//!   从父节点的密钥派生（RFC 5869，HMAC-SHA256）。只需保存根密钥，子密钥随用随派生
+//! - **版本与轮换**: 每个节点有当前版本号，`rotate` 使版本号加一；节点的版本参与派生，
+//!   因此轮换一个节点会同时轮换它的所有后代。旧版本在 `retire_before` 之前仍可派生，
+//!   用于解密轮换前的数据
+//! - **清零**: `SecretKey` 以 `SecretValue` 保存，释放时清除内存，`Debug` 输出不包含密钥
+//! - **加密导出**: `export` 用口令加密根密钥和版本表。口令经 `HMAC::stretch_key` 拉伸，
+//!   再派生 AES-128 计数器模式的加密密钥和 HMAC 认证密钥（先加密后认证），
+//!   口令错误或数据被篡改时 `import` 返回 `AuthenticationError`
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::security::keys::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let mut store = KeyStore::generate();
+//! let mac_key = store.derive("spdz/mac")?;
+//! assert_eq!(mac_key.version(), 0);
+//!
+//! // 轮换后得到新的密钥，旧版本仍可派生
+//! assert_eq!(store.rotate("spdz/mac")?, 1);
+//! assert_ne!(store.derive("spdz/mac")?.key(), mac_key.key());
+//! assert_eq!(store.derive_version("spdz/mac", 0)?.key(), mac_key.key());
+//!
+//! // 用口令加密导出，再导入得到相同的密钥树
+//! let exported = store.export_with_iterations(b"correct horse", MIN_EXPORT_ITERATIONS)?;
+//! let restored = KeyStore::import(&exported, b"correct horse")?;
+//! assert_eq!(restored.derive("spdz/mac")?.key(), store.derive("spdz/mac")?.key());
+//! assert!(KeyStore::import(&exported, b"wrong").is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::authentication::HMAC;
+use crate::utils::crypto::{AesCtrPrg, SeedableRng};
+use crate::utils::memory::{secure_zero, SecretValue};
+use crate::{MpcError, Result};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// HKDF-Extract 使用的盐
+const KEY_DOMAIN: &[u8] = b"mpc_api/security/keys/v1";
+
+/// 导出格式版本
+const EXPORT_FORMAT_VERSION: u8 = 1;
+
+/// 导出时默认的口令拉伸迭代次数
+pub const DEFAULT_EXPORT_ITERATIONS: u32 = 100_000;
+
+/// 导出和导入接受的最小迭代次数
+pub const MIN_EXPORT_ITERATIONS: u32 = 1_000;
+
+/// HKDF-Expand 单次最多输出 255 个 HMAC 块
+const HKDF_MAX_OUTPUT: usize = 255 * 32;
+
+/// 32 字节的秘密密钥，释放时清零
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretKey(SecretValue<[u8; 32]>);
+
+impl SecretKey {
+    /// 由原始字节创建
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(SecretValue::new(bytes))
+    }
+
+    /// 生成随机密钥
+    pub fn generate() -> Self {
+        let mut key = Self::from_bytes([0u8; 32]);
+        rand::thread_rng().fill_bytes(&mut *key.0);
+        key
+    }
+
+    /// 原始字节
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(<redacted>)")
+    }
+}
+
+/// HKDF-Extract：由输入密钥材料和盐得到伪随机密钥
+pub fn hkdf_extract(salt: &[u8], ikm: &[u8]) -> SecretKey {
+    SecretKey::from_bytes(HMAC::compute_hmac(salt, ikm))
+}
+
+/// HKDF-Expand：由伪随机密钥和上下文信息填满 `okm`
+///
+/// # 返回值
+///
+/// `okm` 超过 255 × 32 字节时返回错误
+pub fn hkdf_expand(prk: &SecretKey, info: &[u8], okm: &mut [u8]) -> Result<()> {
+    if okm.len() > HKDF_MAX_OUTPUT {
+        return Err(MpcError::CryptographicError(format!(
+            "HKDF cannot expand to {} bytes (at most {})", okm.len(), HKDF_MAX_OUTPUT
+        )));
+    }
+    let mut block: Vec<u8> = Vec::with_capacity(32 + info.len() + 1);
+    for (counter, chunk) in okm.chunks_mut(32).enumerate() {
+        // T(i) = HMAC(PRK, T(i-1) ‖ info ‖ i)
+        block.extend_from_slice(info);
+        block.push(counter as u8 + 1);
+        let mut output = HMAC::compute_hmac(prk.as_bytes(), &block);
+        chunk.copy_from_slice(&output[..chunk.len()]);
+        secure_zero(block.as_mut_ptr(), block.len());
+        block.clear();
+        block.extend_from_slice(&output);
+        secure_zero(output.as_mut_ptr(), output.len());
+    }
+    secure_zero(block.as_mut_ptr(), block.len());
+    Ok(())
+}
+
+/// 由密钥树派生出的密钥
+#[derive(Debug, Clone)]
+pub struct DerivedKey {
+    path: String,
+    version: u32,
+    key: SecretKey,
+}
+
+impl DerivedKey {
+    /// 节点路径
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// 节点版本
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// 密钥
+    pub fn key(&self) -> &SecretKey {
+        &self.key
+    }
+}
+
+/// 节点的版本范围
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct KeyVersions {
+    /// 当前版本
+    current: u32,
+    /// 仍可派生的最旧版本
+    oldest: u32,
+}
+
+/// 导出时加密的内容
+#[derive(Serialize, Deserialize)]
+struct KeyStoreState {
+    #[serde(with = "crate::utils::memory::expose_secret")]
+    root: SecretValue<[u8; 32]>,
+    versions: BTreeMap<String, KeyVersions>,
+}
+
+/// 加密导出的密钥树
+#[derive(Serialize, Deserialize)]
+struct ExportedKeyStore {
+    format: u8,
+    iterations: u32,
+    salt: [u8; 16],
+    nonce: [u8; 16],
+    ciphertext: Vec<u8>,
+    tag: [u8; 32],
+}
+
+impl ExportedKeyStore {
+    /// 被认证的字节：除标签外的所有字段
+    fn authenticated_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.format];
+        bytes.extend_from_slice(&self.iterations.to_le_bytes());
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&self.nonce);
+        bytes.extend_from_slice(&self.ciphertext);
+        bytes
+    }
+}
+
+/// 以根密钥为起点的密钥树
+pub struct KeyStore {
+    /// HKDF-Extract(KEY_DOMAIN, root)
+    prk: SecretKey,
+    /// 根密钥，仅用于导出
+    root: SecretKey,
+    /// 轮换过或退役过版本的节点
+    versions: BTreeMap<String, KeyVersions>,
+}
+
+impl KeyStore {
+    /// 以给定根密钥创建密钥树
+    pub fn new(root: SecretKey) -> Self {
+        Self {
+            prk: hkdf_extract(KEY_DOMAIN, root.as_bytes()),
+            root,
+            versions: BTreeMap::new(),
+        }
+    }
+
+    /// 以随机根密钥创建密钥树
+    pub fn generate() -> Self {
+        Self::new(SecretKey::generate())
+    }
+
+    /// 节点的当前版本，从未轮换的节点为 0
+    pub fn current_version(&self, path: &str) -> Result<u32> {
+        check_path(path)?;
+        Ok(self.versions(path).current)
+    }
+
+    /// 派生节点当前版本的密钥
+    pub fn derive(&self, path: &str) -> Result<DerivedKey> {
+        let version = self.current_version(path)?;
+        self.derive_version(path, version)
+    }
+
+    /// 派生节点指定版本的密钥，祖先节点取当前版本
+    ///
+    /// # 返回值
+    ///
+    /// 版本尚未生成或已经退役时返回错误
+    pub fn derive_version(&self, path: &str, version: u32) -> Result<DerivedKey> {
+        check_path(path)?;
+        let versions = self.versions(path);
+        if version > versions.current || version < versions.oldest {
+            return Err(MpcError::CryptographicError(format!(
+                "Key {} has no version {} (available {}..={})",
+                path, version, versions.oldest, versions.current
+            )));
+        }
+
+        let mut key = self.prk.clone();
+        let mut prefix_end = 0;
+        for segment in path.split('/') {
+            prefix_end += if prefix_end == 0 { segment.len() } else { segment.len() + 1 };
+            let segment_version = if prefix_end == path.len() {
+                version
+            } else {
+                self.versions(&path[..prefix_end]).current
+            };
+            let mut info = Vec::with_capacity(segment.len() + 12);
+            info.extend_from_slice(&(segment.len() as u64).to_le_bytes());
+            info.extend_from_slice(segment.as_bytes());
+            info.extend_from_slice(&segment_version.to_le_bytes());
+            let mut child = [0u8; 32];
+            hkdf_expand(&key, &info, &mut child)?;
+            key = SecretKey::from_bytes(child);
+            secure_zero(child.as_mut_ptr(), child.len());
+        }
+
+        Ok(DerivedKey { path: path.to_string(), version, key })
+    }
+
+    /// 轮换节点，返回新的版本号
+    ///
+    /// 节点及其所有后代的当前密钥随之改变。
+    pub fn rotate(&mut self, path: &str) -> Result<u32> {
+        check_path(path)?;
+        let versions = self.versions.entry(path.to_string()).or_default();
+        versions.current = versions.current.checked_add(1).ok_or_else(|| {
+            MpcError::CryptographicError(format!("Key {} cannot be rotated further", path))
+        })?;
+        Ok(versions.current)
+    }
+
+    /// 退役节点早于 `version` 的所有版本，之后它们不能再被派生
+    pub fn retire_before(&mut self, path: &str, version: u32) -> Result<()> {
+        check_path(path)?;
+        let current = self.versions(path).current;
+        if version > current {
+            return Err(MpcError::CryptographicError(format!(
+                "Cannot retire key {} up to version {} beyond the current version {}", path, version, current
+            )));
+        }
+        let versions = self.versions.entry(path.to_string()).or_default();
+        versions.oldest = versions.oldest.max(version);
+        Ok(())
+    }
+
+    /// 用口令加密导出根密钥和版本表，使用默认迭代次数
+    pub fn export(&self, passphrase: &[u8]) -> Result<Vec<u8>> {
+        self.export_with_iterations(passphrase, DEFAULT_EXPORT_ITERATIONS)
+    }
+
+    /// 用口令加密导出根密钥和版本表
+    ///
+    /// # 参数
+    ///
+    /// * `passphrase` - 口令
+    /// * `iterations` - 口令拉伸的迭代次数，至少为 `MIN_EXPORT_ITERATIONS`
+    pub fn export_with_iterations(&self, passphrase: &[u8], iterations: u32) -> Result<Vec<u8>> {
+        check_iterations(iterations)?;
+        let state = KeyStoreState { root: self.root.0.clone(), versions: self.versions.clone() };
+        let mut plaintext = bincode::serialize(&state).map_err(|e| MpcError::SerializationError(e.to_string()))?;
+
+        let mut rng = rand::thread_rng();
+        let mut exported = ExportedKeyStore {
+            format: EXPORT_FORMAT_VERSION,
+            iterations,
+            salt: rng.gen(),
+            nonce: rng.gen(),
+            ciphertext: Vec::new(),
+            tag: [0u8; 32],
+        };
+        let (encryption_key, mac_key) = export_keys(passphrase, &exported.salt, iterations)?;
+        apply_keystream(&encryption_key, &exported.nonce, &mut plaintext);
+        exported.ciphertext = plaintext;
+        exported.tag = HMAC::compute_hmac(mac_key.as_bytes(), &exported.authenticated_bytes());
+
+        bincode::serialize(&exported).map_err(|e| MpcError::SerializationError(e.to_string()))
+    }
+
+    /// 用口令解密 `export` 的输出
+    ///
+    /// # 返回值
+    ///
+    /// 口令错误或数据被篡改时返回 `AuthenticationError`
+    pub fn import(bytes: &[u8], passphrase: &[u8]) -> Result<Self> {
+        let exported: ExportedKeyStore = bincode::deserialize(bytes)
+            .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+        if exported.format != EXPORT_FORMAT_VERSION {
+            return Err(MpcError::SerializationError(format!(
+                "Unsupported key store export format {}", exported.format
+            )));
+        }
+        check_iterations(exported.iterations)?;
+
+        let (encryption_key, mac_key) = export_keys(passphrase, &exported.salt, exported.iterations)?;
+        let tag = HMAC::compute_hmac(mac_key.as_bytes(), &exported.authenticated_bytes());
+        if !HMAC::secure_compare(&tag, &exported.tag) {
+            return Err(MpcError::AuthenticationError(
+                "Key store export failed authentication (wrong passphrase or corrupted data)".to_string(),
+            ));
+        }
+
+        let mut plaintext = exported.ciphertext;
+        apply_keystream(&encryption_key, &exported.nonce, &mut plaintext);
+        let state: std::result::Result<KeyStoreState, _> = bincode::deserialize(&plaintext);
+        secure_zero(plaintext.as_mut_ptr(), plaintext.len());
+        let state = state.map_err(|e| MpcError::SerializationError(e.to_string()))?;
+
+        let mut store = Self::new(SecretKey(state.root));
+        store.versions = state.versions;
+        Ok(store)
+    }
+
+    fn versions(&self, path: &str) -> KeyVersions {
+        self.versions.get(path).copied().unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for KeyStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyStore")
+            .field("versions", &self.versions)
+            .finish_non_exhaustive()
+    }
+}
+
+/// 路径由 `/` 分隔的非空段组成
+fn check_path(path: &str) -> Result<()> {
+    if path.is_empty() || path.split('/').any(str::is_empty) {
+        return Err(MpcError::CryptographicError(format!("Invalid key path {:?}", path)));
+    }
+    Ok(())
+}
+
+fn check_iterations(iterations: u32) -> Result<()> {
+    if iterations < MIN_EXPORT_ITERATIONS {
+        return Err(MpcError::CryptographicError(format!(
+            "Key store export needs at least {} iterations, got {}", MIN_EXPORT_ITERATIONS, iterations
+        )));
+    }
+    Ok(())
+}
+
+/// 由口令派生导出使用的加密密钥和认证密钥
+fn export_keys(passphrase: &[u8], salt: &[u8; 16], iterations: u32) -> Result<(SecretKey, SecretKey)> {
+    let stretched = SecretKey(HMAC::stretch_key(passphrase, salt, iterations).key);
+    let mut encryption_key = [0u8; 32];
+    let mut mac_key = [0u8; 32];
+    hkdf_expand(&stretched, b"mpc_api/keys/export/encrypt", &mut encryption_key)?;
+    hkdf_expand(&stretched, b"mpc_api/keys/export/mac", &mut mac_key)?;
+    let keys = (SecretKey::from_bytes(encryption_key), SecretKey::from_bytes(mac_key));
+    secure_zero(encryption_key.as_mut_ptr(), encryption_key.len());
+    secure_zero(mac_key.as_mut_ptr(), mac_key.len());
+    Ok(keys)
+}
+
+/// AES-128 计数器模式加密或解密：密钥取前 16 字节，初始计数器为随机数
+fn apply_keystream(key: &SecretKey, nonce: &[u8; 16], data: &mut [u8]) {
+    let mut seed = [0u8; 32];
+    seed[..16].copy_from_slice(&key.as_bytes()[..16]);
+    seed[16..].copy_from_slice(nonce);
+    let mut prg = AesCtrPrg::from_seed(seed);
+    secure_zero(seed.as_mut_ptr(), seed.len());
+    let mut keystream = vec![0u8; data.len()];
+    prg.fill_bytes(&mut keystream);
+    data.iter_mut().zip(&keystream).for_each(|(byte, k)| *byte ^= k);
+    secure_zero(keystream.as_mut_ptr(), keystream.len());
+}
@@ -11,6 +11,8 @@
 //! 2. **内存安全**: 缓冲区溢出、悬空指针、内存泄露检测
 //! 3. **协议攻击检测**: 恶意参与方、协议偏离、重放攻击检测
 //! 4. **拒绝服务防护**: 资源耗尽、计算炸弹、网络洪流防护
+//! 5. **协议事故响应**: 轮次/协议超时、重复 MAC 失败、重连风暴按参与方归属，
+//!    并根据策略自动隔离参与方、中止会话或等待人工恢复
 //! 
 //! ### 安全审计系统
 //! 
@@ -18,10 +20,13 @@
 //! 2. **异常检测**: 实时监控异常行为模式
 //! 3. **合规检查**: 确保操作符合安全策略
 //! 4. **取证支持**: 提供安全事件的详细取证信息
+//! 5. **导出与查询**: `audit` 子模块把事件写入只追加的 JSONL 文件或 syslog（CEF 格式），
+//!    按时间、类型、严重级别过滤事件，并用 Merkle 根验证导出的日志未被篡改
 //! 
 //! ### 密钥管理
 //! 
-//! 1. **密钥生命周期**: 生成、分发、轮换、销毁管理
+//! 1. **密钥生命周期**: 生成、分发、轮换、销毁管理，`keys` 子模块提供 HKDF 密钥树、
+//!    版本化轮换、释放时清零以及口令加密的导出与导入
 //! 2. **安全存储**: 硬件安全模块（HSM）集成
 //! 3. **访问控制**: 基于角色的密钥访问控制
 //! 4. **密钥托管**: 安全的密钥备份和恢复
@@ -89,14 +94,26 @@
 //! ```
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fmt::{self, Display},
     sync::{Arc, Mutex, RwLock},
     thread,
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use serde::{Deserialize, Serialize};
-use crate::{Result, utils::memory::StackProtector};
+use crate::{MpcError, Result, utils::memory::StackProtector};
+use crate::storage::StorageBackend;
+use crate::commitment::{ConsistencyProof, InclusionProof, MerkleAccumulator};
+use crate::protocols::clock::ClockSkewTracker;
+use crate::utils::metrics::{MetricSample, MetricsRegistry};
+
+pub mod audit;
+pub mod incidents;
+pub mod keys;
+
+pub use audit::*;
+pub use incidents::*;
+pub use keys::*;
 
 /// 安全错误类型
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -172,6 +189,12 @@ pub enum ThreatType {
     CryptographicAttack,
     /// 物理攻击
     PhysicalAttack,
+    /// 秘密公开（记录每一次重构请求）
+    SecretDisclosure,
+    /// 过期或来源不可信的预处理材料（记录每一次清理）
+    StaleMaterial,
+    /// 密钥生命周期（记录每一次轮换和退役）
+    KeyLifecycle,
 }
 
 /// 安全事件记录
@@ -193,6 +216,12 @@ pub struct SecurityEvent {
     pub mitigation: Option<String>,
     /// 是否已处理
     pub is_handled: bool,
+    /// 事件归属的参与方
+    #[serde(default)]
+    pub peer_id: Option<String>,
+    /// 触发事件的协议事故
+    #[serde(default)]
+    pub incident: Option<ProtocolIncident>,
 }
 
 impl SecurityEvent {
@@ -227,9 +256,17 @@ impl SecurityEvent {
             context: HashMap::new(),
             mitigation: None,
             is_handled: false,
+            peer_id: None,
+            incident: None,
         }
     }
 
+    /// 设置事件归属的参与方
+    pub fn with_peer(mut self, peer_id: &str) -> Self {
+        self.peer_id = Some(peer_id.to_string());
+        self
+    }
+
     /// 添加上下文信息
     pub fn with_context(mut self, key: String, value: String) -> Self {
         self.context.insert(key, value);
@@ -246,6 +283,37 @@ impl SecurityEvent {
     pub fn mark_handled(&mut self) {
         self.is_handled = true;
     }
+
+    /// 把其他参与方上报的事件时间戳换算到本地时钟
+    ///
+    /// 原始时间戳保存在上下文的 `remote_timestamp` 中（自 UNIX 纪元的毫秒数）。
+    pub fn normalize_timestamp(&mut self, clock: &ClockSkewTracker, peer_id: &str) {
+        let remote_millis = self.timestamp.duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        self.context.insert("remote_timestamp".to_string(), remote_millis.to_string());
+        self.timestamp = clock.to_local_time(peer_id, self.timestamp);
+    }
+
+    /// 事件在审计日志 Merkle 累加器中的叶子编码
+    ///
+    /// 与 `serde_json::to_vec` 相同，但上下文按键排序，同一事件总是得到相同的字节。
+    pub fn audit_bytes(&self) -> Result<Vec<u8>> {
+        let context: BTreeMap<&String, &String> = self.context.iter().collect();
+        serde_json::to_vec(&(
+            &self.id,
+            &self.timestamp,
+            &self.threat_type,
+            &self.severity,
+            &self.description,
+            &context,
+            &self.mitigation,
+            self.is_handled,
+            &self.peer_id,
+            &self.incident,
+        ))
+        .map_err(|e| MpcError::SerializationError(e.to_string()))
+    }
 }
 
 /// 安全策略配置
@@ -273,6 +341,64 @@ pub struct SecurityPolicy {
     pub max_memory_usage_mb: u64,
     /// 网络超时设置 (秒)
     pub network_timeout_seconds: u64,
+    /// 协议事故的自动响应策略
+    #[serde(default)]
+    pub incident_response: IncidentResponsePolicy,
+    /// 网络服务的限流与配额
+    #[serde(default)]
+    pub rate_limit: RateLimitPolicy,
+}
+
+/// 网络服务的限流与配额策略
+///
+/// 每个对端分别有消息数和字节数两个令牌桶：令牌按每秒速率补充，最多积累到突发上限，
+/// 处理一条消息消耗一个消息令牌和与消息长度相同的字节令牌。速率为 0 表示不限制。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitPolicy {
+    /// 每个对端每秒允许的消息数
+    pub messages_per_second: u32,
+    /// 每个对端可以突发的消息数
+    pub message_burst: u32,
+    /// 每个对端每秒允许的字节数
+    pub bytes_per_second: u64,
+    /// 每个对端可以突发的字节数
+    pub byte_burst: u64,
+    /// 同时跟踪配额的对端数量上限，超出时淘汰最久未活动的对端
+    pub max_tracked_peers: usize,
+}
+
+impl RateLimitPolicy {
+    /// 不限制任何对端（开发环境）
+    pub fn unlimited() -> Self {
+        Self {
+            messages_per_second: 0,
+            bytes_per_second: 0,
+            ..Self::default()
+        }
+    }
+
+    /// 严格策略：较低的速率和突发上限
+    pub fn strict() -> Self {
+        Self {
+            messages_per_second: 100,
+            message_burst: 200,
+            bytes_per_second: 4 << 20,
+            byte_burst: 8 << 20,
+            max_tracked_peers: 1024,
+        }
+    }
+}
+
+impl Default for RateLimitPolicy {
+    fn default() -> Self {
+        Self {
+            messages_per_second: 1000,
+            message_burst: 2000,
+            bytes_per_second: 64 << 20,
+            byte_burst: 128 << 20,
+            max_tracked_peers: 4096,
+        }
+    }
 }
 
 impl SecurityPolicy {
@@ -290,6 +416,8 @@ impl SecurityPolicy {
             log_retention_days: 7,
             max_memory_usage_mb: 1024,
             network_timeout_seconds: 30,
+            incident_response: IncidentResponsePolicy::log_only(),
+            rate_limit: RateLimitPolicy::unlimited(),
         }
     }
 
@@ -307,6 +435,8 @@ impl SecurityPolicy {
             log_retention_days: 30,
             max_memory_usage_mb: 2048,
             network_timeout_seconds: 15,
+            incident_response: IncidentResponsePolicy::default(),
+            rate_limit: RateLimitPolicy::default(),
         }
     }
 
@@ -324,6 +454,8 @@ impl SecurityPolicy {
             log_retention_days: 90,
             max_memory_usage_mb: 4096,
             network_timeout_seconds: 10,
+            incident_response: IncidentResponsePolicy::default(),
+            rate_limit: RateLimitPolicy::default(),
         }
     }
 
@@ -341,6 +473,8 @@ impl SecurityPolicy {
             log_retention_days: 365,
             max_memory_usage_mb: 8192,
             network_timeout_seconds: 5,
+            incident_response: IncidentResponsePolicy::strict(),
+            rate_limit: RateLimitPolicy::strict(),
         }
     }
 
@@ -361,6 +495,18 @@ impl SecurityPolicy {
         self.enable_attack_mitigation = enabled;
         self
     }
+
+    /// 链式配置协议事故响应策略
+    pub fn with_incident_response(mut self, incident_response: IncidentResponsePolicy) -> Self {
+        self.incident_response = incident_response;
+        self
+    }
+
+    /// 链式配置限流策略
+    pub fn with_rate_limit(mut self, rate_limit: RateLimitPolicy) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
 }
 
 impl Default for SecurityPolicy {
@@ -397,8 +543,11 @@ impl ThreatDetector {
     }
 
     /// 启动威胁监控
+    ///
+    /// 后台监控线程需要 `security-monitor` 特性；未启用该特性时不启动线程，
+    /// 时序和内存记录仍然可用。
     pub fn start_monitoring(&self) -> Result<()> {
-        if !self.policy.enable_threat_detection {
+        if !self.policy.enable_threat_detection || !cfg!(feature = "security-monitor") {
             return Ok(());
         }
 
@@ -548,8 +697,17 @@ pub struct AuditLogger {
     events: Arc<RwLock<Vec<SecurityEvent>>>,
     /// 日志计数器
     event_counter: Arc<Mutex<u64>>,
+    /// 持久化存储，事件写入内存的同时按序列号写入此后端
+    storage: Option<Arc<dyn StorageBackend>>,
+    /// 按记录顺序累加所有事件的 Merkle 累加器，不受内存上限和过期清理的影响
+    log_tree: Arc<RwLock<MerkleAccumulator>>,
+    /// 事件记录后依次写入的导出端
+    sinks: Arc<RwLock<Vec<Arc<dyn AuditSink>>>>,
 }
 
+/// 审计事件所在的存储命名空间
+const AUDIT_NAMESPACE: &str = "audit";
+
 impl AuditLogger {
     /// 创建新的审计日志记录器
     pub fn new(policy: SecurityPolicy) -> Self {
@@ -557,21 +715,83 @@ impl AuditLogger {
             policy,
             events: Arc::new(RwLock::new(Vec::new())),
             event_counter: Arc::new(Mutex::new(0)),
+            storage: None,
+            log_tree: Arc::new(RwLock::new(MerkleAccumulator::new())),
+            sinks: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /// 创建把事件持久化到存储后端的审计日志记录器
+    ///
+    /// 后端中已有的事件会被载入内存并按序列号加入审计日志的 Merkle 累加器，
+    /// 序列号从最大的已有序列号继续。
+    pub fn with_storage(policy: SecurityPolicy, storage: Arc<dyn StorageBackend>) -> Result<Self> {
+        let persisted = Self::read_persisted(storage.as_ref())?;
+        let last_sequence = persisted.last().map(|(sequence, _)| *sequence).unwrap_or(0);
+        let mut log_tree = MerkleAccumulator::new();
+        for (_, event) in &persisted {
+            log_tree.append(&event.audit_bytes()?);
         }
+        Ok(AuditLogger {
+            policy,
+            events: Arc::new(RwLock::new(persisted.into_iter().map(|(_, event)| event).collect())),
+            event_counter: Arc::new(Mutex::new(last_sequence)),
+            storage: Some(storage),
+            log_tree: Arc::new(RwLock::new(log_tree)),
+            sinks: Arc::new(RwLock::new(Vec::new())),
+        })
+    }
+
+    /// 注册导出端，之后记录的每条事件都会写入其中
+    pub fn add_sink(&self, sink: Arc<dyn AuditSink>) {
+        self.sinks.write().unwrap().push(sink);
+    }
+
+    /// 刷新所有导出端
+    pub fn flush_sinks(&self) -> Result<()> {
+        self.sinks.read().unwrap().iter().try_for_each(|sink| sink.flush())
+    }
+
+    /// 读取存储后端中的全部事件（按序列号排序），不受内存中事件数量上限的影响
+    pub fn persisted_events(&self) -> Result<Vec<SecurityEvent>> {
+        match &self.storage {
+            Some(storage) => Ok(Self::read_persisted(storage.as_ref())?
+                .into_iter()
+                .map(|(_, event)| event)
+                .collect()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn read_persisted(storage: &dyn StorageBackend) -> Result<Vec<(u64, SecurityEvent)>> {
+        storage.iterate(AUDIT_NAMESPACE)?
+            .into_iter()
+            .map(|(key, value)| {
+                let sequence: [u8; 8] = key.as_slice().try_into()
+                    .map_err(|_| MpcError::StorageError(format!("Invalid audit key length {}", key.len())))?;
+                let event = serde_json::from_slice(&value)
+                    .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+                Ok((u64::from_be_bytes(sequence), event))
+            })
+            .collect()
     }
 
     /// 记录安全事件
+    ///
+    /// 事件写入持久化存储失败时不记录；记录之后再写入各导出端，
+    /// 导出失败时事件仍然保留在日志中，返回第一个导出错误。
     pub fn log_event(&self, mut event: SecurityEvent) -> Result<()> {
         if !self.policy.enable_audit_logging {
             return Ok(());
         }
 
         // 分配序列号
-        {
+        let sequence = {
             let mut counter = self.event_counter.lock().unwrap();
             *counter += 1;
             event.context.insert("sequence".to_string(), counter.to_string());
-        }
+            *counter
+        };
 
         // 添加系统上下文
         event.context.insert("thread_id".to_string(), 
@@ -579,6 +799,14 @@ impl AuditLogger {
         event.context.insert("process_id".to_string(), 
                             std::process::id().to_string());
 
+        // 先写持久化存储，写入失败时不记录到内存
+        if let Some(storage) = &self.storage {
+            let bytes = serde_json::to_vec(&event)
+                .map_err(|e| MpcError::SerializationError(e.to_string()))?;
+            storage.put(AUDIT_NAMESPACE, &sequence.to_be_bytes(), &bytes)?;
+        }
+        self.log_tree.write().unwrap().append(&event.audit_bytes()?);
+
         // 存储事件
         {
             let mut events = self.events.write().unwrap();
@@ -595,7 +823,14 @@ impl AuditLogger {
             eprintln!("🔥 严重安全事件: {} - {}", event.threat_type.name(), event.description);
         }
 
-        Ok(())
+        // 写入所有导出端，单个导出端失败不影响其他导出端
+        let mut export_error = None;
+        for sink in self.sinks.read().unwrap().iter() {
+            if let Err(e) = sink.write(&event) {
+                export_error.get_or_insert(e);
+            }
+        }
+        export_error.map_or(Ok(()), Err)
     }
 
     /// 获取所有事件
@@ -621,7 +856,45 @@ impl AuditLogger {
             .collect()
     }
 
+    /// 按条件查询事件
+    ///
+    /// 配置了持久化存储时查询存储中的全部事件，否则查询内存中的事件。
+    pub fn query(&self, query: &AuditQuery) -> Result<Vec<SecurityEvent>> {
+        if self.storage.is_some() {
+            Ok(query.apply(&self.persisted_events()?))
+        } else {
+            Ok(query.apply(self.events.read().unwrap().iter()))
+        }
+    }
+
+    /// 审计日志当前的 Merkle 根
+    ///
+    /// 由按记录顺序的全部事件计算，见 `audit_root`。
+    pub fn current_root(&self) -> [u8; 32] {
+        self.log_tree.read().unwrap().root()
+    }
+
+    /// 审计日志的大小和 Merkle 根，可以定期发布给外部监督方
+    pub fn log_root(&self) -> (usize, [u8; 32]) {
+        let log_tree = self.log_tree.read().unwrap();
+        (log_tree.len(), log_tree.root())
+    }
+
+    /// 证明按记录顺序的第 `index` 个事件（从 0 开始）在大小为 `tree_size` 的审计日志中
+    ///
+    /// 验证方用 `SecurityEvent::audit_bytes` 得到叶子数据。
+    pub fn prove_event(&self, index: usize, tree_size: usize) -> Result<InclusionProof> {
+        self.log_tree.read().unwrap().prove_inclusion(index, tree_size)
+    }
+
+    /// 证明大小为 `old_size` 的审计日志是大小为 `new_size` 的审计日志的前缀，即期间只追加了事件
+    pub fn prove_log_consistency(&self, old_size: usize, new_size: usize) -> Result<ConsistencyProof> {
+        self.log_tree.read().unwrap().prove_consistency(old_size, new_size)
+    }
+
     /// 清理过期日志
+    ///
+    /// 只删除事件本身，审计日志的 Merkle 根不变。
     pub fn cleanup_expired_logs(&self) -> Result<usize> {
         let retention_duration = Duration::from_secs(
             self.policy.log_retention_days as u64 * 24 * 3600
@@ -634,6 +907,15 @@ impl AuditLogger {
         events.retain(|event| event.timestamp > cutoff_time);
         
         let removed_count = initial_count - events.len();
+
+        if let Some(storage) = &self.storage {
+            for (sequence, event) in Self::read_persisted(storage.as_ref())? {
+                if event.timestamp <= cutoff_time {
+                    storage.delete(AUDIT_NAMESPACE, &sequence.to_be_bytes())?;
+                }
+            }
+        }
+
         Ok(removed_count)
     }
 }
@@ -703,6 +985,9 @@ impl AttackMitigation {
             ThreatType::NetworkAttack => self.mitigate_network_attack(event),
             ThreatType::CryptographicAttack => self.mitigate_crypto_attack(event),
             ThreatType::PhysicalAttack => self.mitigate_physical_attack(event),
+            ThreatType::SecretDisclosure => self.mitigate_secret_disclosure(event),
+            ThreatType::StaleMaterial => self.mitigate_stale_material(event),
+            ThreatType::KeyLifecycle => self.mitigate_key_lifecycle(event),
         };
 
         // 记录缓解措施
@@ -758,8 +1043,12 @@ impl AttackMitigation {
 
     /// 缓解拒绝服务攻击
     fn mitigate_dos_attack(&self, _event: &SecurityEvent) -> MitigationResult {
-        // 实现资源限制和请求节流
-        MitigationResult::Success
+        // 节流由 network::security::RateLimiter 在处理消息之前执行
+        if self.policy.rate_limit.messages_per_second == 0 && self.policy.rate_limit.bytes_per_second == 0 {
+            MitigationResult::Partial("限流策略未启用".to_string())
+        } else {
+            MitigationResult::Success
+        }
     }
 
     /// 缓解恶意参与方
@@ -786,6 +1075,23 @@ impl AttackMitigation {
         MitigationResult::Failed("物理攻击需要硬件级别的防护".to_string())
     }
 
+    /// 缓解秘密公开
+    fn mitigate_secret_disclosure(&self, _event: &SecurityEvent) -> MitigationResult {
+        // 违规的公开请求已被 RevealGate 拒绝
+        MitigationResult::Success
+    }
+
+    fn mitigate_stale_material(&self, _event: &SecurityEvent) -> MitigationResult {
+        // 过期的预处理材料在记录事件前已从池中删除
+        MitigationResult::Success
+    }
+
+    /// 处理密钥生命周期事件
+    fn mitigate_key_lifecycle(&self, _event: &SecurityEvent) -> MitigationResult {
+        // 轮换和退役本身就是响应，事件只用于审计
+        MitigationResult::Success
+    }
+
     /// 获取缓解历史
     pub fn get_mitigation_history(&self) -> Vec<MitigationAction> {
         self.mitigation_history.read().unwrap().clone()
@@ -803,6 +1109,10 @@ pub struct SecurityManager {
     audit_logger: AuditLogger,
     /// 攻击缓解系统
     attack_mitigation: AttackMitigation,
+    /// 协议事故跟踪
+    incidents: Mutex<IncidentTracker>,
+    /// 密钥树
+    keys: RwLock<KeyStore>,
 }
 
 impl SecurityManager {
@@ -818,6 +1128,8 @@ impl SecurityManager {
             threat_detector: ThreatDetector::new(policy.clone()),
             audit_logger: AuditLogger::new(policy.clone()),
             attack_mitigation: AttackMitigation::new(policy.clone()),
+            incidents: Mutex::new(IncidentTracker::default()),
+            keys: RwLock::new(KeyStore::generate()),
             policy,
         })
     }
@@ -879,7 +1191,36 @@ impl SecurityManager {
     }
 
     /// 报告安全事件
+    /// 
+    /// 携带协议事故和参与方归属的事件会按照策略中的
+    /// `incident_response` 累计计数并触发自动响应。
     pub fn report_security_event(&self, event: SecurityEvent) -> Result<()> {
+        self.handle_security_event(event).map(|_| ())
+    }
+
+    /// 报告协议事故
+    /// 
+    /// # 参数
+    /// - `peer_id`: 事故归属的参与方
+    /// - `incident`: 协议事故
+    /// 
+    /// # 返回值
+    /// 返回根据策略采取的自动响应
+    pub fn report_incident(&self, peer_id: &str, incident: ProtocolIncident) -> Result<AutomaticResponse> {
+        self.handle_security_event(SecurityEvent::from_incident(peer_id, incident))
+    }
+
+    /// 记录事件、应用缓解措施并执行事故响应
+    fn handle_security_event(&self, mut event: SecurityEvent) -> Result<AutomaticResponse> {
+        let response = match (&event.peer_id, &event.incident) {
+            (Some(peer_id), Some(incident)) => self.incidents.lock().unwrap()
+                .record(peer_id, incident, &self.policy.incident_response),
+            _ => AutomaticResponse::LogOnly,
+        };
+        if response != AutomaticResponse::LogOnly {
+            event.mitigation = Some(response.describe().to_string());
+        }
+
         // 记录事件
         self.audit_logger.log_event(event.clone())?;
         
@@ -888,9 +1229,98 @@ impl SecurityManager {
             self.attack_mitigation.mitigate_threat(&event)?;
         }
 
+        Ok(response)
+    }
+
+    /// 参与方是否已被隔离
+    pub fn is_peer_quarantined(&self, peer_id: &str) -> bool {
+        self.incidents.lock().unwrap().is_quarantined(peer_id)
+    }
+
+    /// 会话是否已被中止
+    pub fn is_session_aborted(&self, session_id: &str) -> bool {
+        self.incidents.lock().unwrap().is_session_aborted(session_id)
+    }
+
+    /// 是否正在等待人工恢复
+    pub fn requires_manual_resume(&self) -> bool {
+        self.incidents.lock().unwrap().manual_resume_required()
+    }
+
+    /// 检查是否允许继续与参与方通信
+    /// 
+    /// 参与方被隔离或系统等待人工恢复时返回错误
+    pub fn ensure_peer_allowed(&self, peer_id: &str) -> Result<()> {
+        let incidents = self.incidents.lock().unwrap();
+        if incidents.manual_resume_required() {
+            return Err("协议已暂停，等待人工恢复".into());
+        }
+        if incidents.is_quarantined(peer_id) {
+            return Err(format!("参与方 {} 已被隔离", peer_id).into());
+        }
         Ok(())
     }
 
+    /// 解除参与方隔离并清零其事故计数，返回该参与方之前是否被隔离
+    pub fn release_peer(&self, peer_id: &str) -> bool {
+        self.incidents.lock().unwrap().release(peer_id)
+    }
+
+    /// 人工恢复被暂停的协议
+    pub fn resume_operations(&self) {
+        self.incidents.lock().unwrap().resume();
+    }
+
+    /// 注册审计日志导出端
+    pub fn add_audit_sink(&self, sink: Arc<dyn AuditSink>) {
+        self.audit_logger.add_sink(sink);
+    }
+
+    /// 按条件查询审计日志
+    pub fn query_audit_log(&self, query: &AuditQuery) -> Result<Vec<SecurityEvent>> {
+        self.audit_logger.query(query)
+    }
+
+    /// 审计日志当前的 Merkle 根
+    pub fn audit_root(&self) -> [u8; 32] {
+        self.audit_logger.current_root()
+    }
+
+    /// 派生密钥树中节点当前版本的密钥
+    pub fn derive_key(&self, path: &str) -> Result<DerivedKey> {
+        self.keys.read().unwrap().derive(path)
+    }
+
+    /// 轮换密钥并写入审计日志，返回新的版本号
+    pub fn rotate_key(&self, path: &str) -> Result<u32> {
+        let version = self.keys.write().unwrap().rotate(path)?;
+        self.log_key_event(path, format!("rotated key {} to version {}", path, version), version)?;
+        Ok(version)
+    }
+
+    /// 退役密钥早于 `version` 的版本并写入审计日志
+    pub fn retire_keys_before(&self, path: &str, version: u32) -> Result<()> {
+        self.keys.write().unwrap().retire_before(path, version)?;
+        self.log_key_event(path, format!("retired versions of key {} before {}", path, version), version)
+    }
+
+    /// 用口令加密导出密钥树
+    pub fn export_keys(&self, passphrase: &[u8]) -> Result<Vec<u8>> {
+        self.keys.read().unwrap().export(passphrase)
+    }
+
+    /// 替换密钥树，例如换成 `KeyStore::import` 恢复的密钥树
+    pub fn install_key_store(&self, store: KeyStore) {
+        *self.keys.write().unwrap() = store;
+    }
+
+    fn log_key_event(&self, path: &str, description: String, version: u32) -> Result<()> {
+        let event = SecurityEvent::new(ThreatType::KeyLifecycle, SecurityLevel::Low, description)
+            .with_context("key_path".to_string(), path.to_string())
+            .with_context("version".to_string(), version.to_string());
+        self.audit_logger.log_event(event)
+    }
+
     /// 获取安全统计信息
     pub fn get_security_stats(&self) -> SecurityStats {
         let events = self.audit_logger.get_events();
@@ -910,6 +1340,12 @@ impl SecurityManager {
         }
     }
 
+    /// 把安全统计注册为指标采集器，每次导出时重新统计
+    pub fn register_metrics(self: &Arc<Self>, registry: &MetricsRegistry) {
+        let manager = Arc::clone(self);
+        registry.register_collector(move |samples| manager.get_security_stats().write_metrics(samples));
+    }
+
     /// 清理资源
     pub fn cleanup(&self) -> Result<()> {
         let removed = self.audit_logger.cleanup_expired_logs()?;
@@ -939,6 +1375,30 @@ pub struct SecurityStats {
     pub policy_level: SecurityLevel,
 }
 
+impl SecurityStats {
+    /// 把统计写成指标样本，供 `MetricsRegistry` 的采集器使用
+    pub fn write_metrics(&self, samples: &mut Vec<MetricSample>) {
+        samples.push(MetricSample::gauge("mpc_security_events", "审计日志中的安全事件数", self.total_events as f64));
+        samples.push(MetricSample::gauge("mpc_security_unhandled_events", "未处理的安全事件数", self.unhandled_events as f64));
+        samples.push(MetricSample::gauge("mpc_security_mitigation_actions", "已执行的缓解措施数", self.mitigation_actions as f64));
+        let mut threats: Vec<(String, usize)> = self.threat_types
+            .iter()
+            .map(|(threat, count)| (format!("{:?}", threat), *count))
+            .collect();
+        threats.sort();
+        for (threat, count) in threats {
+            samples.push(
+                MetricSample::gauge("mpc_security_threat_events", "按威胁类型统计的安全事件数", count as f64)
+                    .with_label("threat", &threat),
+            );
+        }
+        samples.push(
+            MetricSample::gauge("mpc_security_policy_level", "当前的安全策略级别", 1.0)
+                .with_label("level", &format!("{:?}", self.policy_level)),
+        );
+    }
+}
+
 /// 威胁类型扩展方法
 impl ThreatType {
     /// 获取威胁类型的名称
@@ -954,6 +1414,9 @@ impl ThreatType {
             ThreatType::NetworkAttack => "网络攻击",
             ThreatType::CryptographicAttack => "密码学攻击",
             ThreatType::PhysicalAttack => "物理攻击",
+            ThreatType::SecretDisclosure => "秘密公开",
+            ThreatType::StaleMaterial => "过期预处理材料",
+            ThreatType::KeyLifecycle => "密钥生命周期",
         }
     }
 
@@ -970,6 +1433,9 @@ impl ThreatType {
             ThreatType::NetworkAttack => "针对网络通信的攻击，如中间人攻击、网络窃听等",
             ThreatType::CryptographicAttack => "针对密码学算法或实现的攻击",
             ThreatType::PhysicalAttack => "直接访问硬件进行的物理攻击",
+            ThreatType::SecretDisclosure => "对秘密分享值的重构请求，未经授权的公开会泄露私有数据",
+            ThreatType::StaleMaterial => "超过有效期、来自已吊销生成方或安全模型不符的预处理材料，继续使用会削弱在线阶段的安全性",
+            ThreatType::KeyLifecycle => "密钥的轮换和旧版本退役，用于追溯某一时刻哪个版本的密钥有效",
         }
     }
 }
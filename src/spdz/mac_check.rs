@@ -0,0 +1,139 @@
+//! # 批量 MAC 检查 (Batched MAC Check)
+//!
+//! SPDZ 中秘密 x 的认证分享包含各方的分享值和 MAC 分享 m_i，满足 Σ_i m_i = α·x，
+//! 全局 MAC 密钥 α = Σ_i α_i 以加法分享的形式分布在各方，任何一方都不知道 α。
+//! 打开一批值 x_1..x_m 之后，各方不公开 MAC 分享，而是一起检查：
+//!
+//! 1. **公共系数**: 由会话转录和打开的值派生随机系数 r_k，系数在所有值公开之后才确定
+//! 2. **承诺 σ**: 每方计算 σ_i = Σ_k r_k·(m_ik - α_i·x_k)，先广播 σ_i 的承诺
+//! 3. **打开 σ**: 收齐全部承诺后再打开，检查 Σ_i σ_i = 0
+//!
+//! 若有一方篡改分享值或 MAC，使打开的值变为 x_k + δ_k，要通过检查就必须让
+//! Σ_i σ_i 恰好抵消 α·Σ_k r_k·δ_k，即猜中 α，成功概率约为 1/p。
+//! 承诺保证每方在看到其他方的 σ 之前就已确定自己的 σ，否则最后一个打开的一方
+//! 总可以选择 σ 使和为 0。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::protocols::session::ProtocolSession;
+//! use mpc_api::secret_sharing::{field_add, field_mul, field_sub};
+//! use mpc_api::spdz::MacCheck;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! // 两方：α = 3 + 4，x = 5 的 MAC 分享为 10 和 α·x - 10
+//! let (alpha_shares, x) = ([3u64, 4], 5u64);
+//! let macs = [10u64, field_sub(field_mul(7, x), 10)];
+//!
+//! let session = ProtocolSession::root("spdz", b"doc-example");
+//! let check = MacCheck::new(&session, vec![x]);
+//! let committed = (0..2)
+//!     .map(|i| check.commit(&[macs[i]], alpha_shares[i]))
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//! let commitments: Vec<_> = committed.iter().map(|c| c.commitment().clone()).collect();
+//! let openings: Vec<_> = committed.into_iter().map(|c| c.open()).collect();
+//! check.verify(&commitments, openings)?;
+//!
+//! // 打开的值被篡改时检查失败
+//! let forged = MacCheck::new(&session, vec![field_add(x, 1)]);
+//! let committed = (0..2)
+//!     .map(|i| forged.commit(&[macs[i]], alpha_shares[i]))
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//! let commitments: Vec<_> = committed.iter().map(|c| c.commitment().clone()).collect();
+//! let openings: Vec<_> = committed.into_iter().map(|c| c.open()).collect();
+//! assert!(forged.verify(&commitments, openings).is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::commitment::{CommittedMessage, MessageCommitment, MessageOpening};
+use crate::protocols::session::ProtocolSession;
+use crate::secret_sharing::{field_add, field_mul, field_sub};
+use crate::{MpcError, Result};
+
+/// 一次批量 MAC 检查的公共参数：打开的值、随机系数和承诺上下文
+///
+/// 所有参与方由相同的会话和打开的值得到相同的参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacCheck {
+    opened: Vec<u64>,
+    coefficients: Vec<u64>,
+    context: Vec<u8>,
+}
+
+impl MacCheck {
+    /// 为一批打开的值准备 MAC 检查
+    ///
+    /// # 参数
+    /// - `session`: 所在的协议会话，系数和承诺上下文与它的转录绑定
+    /// - `opened`: 打开的值
+    pub fn new(session: &ProtocolSession, opened: Vec<u64>) -> Self {
+        let mut transcript = session.transcript();
+        let encoded: Vec<u8> = opened.iter().flat_map(|x| x.to_le_bytes()).collect();
+        transcript.append_message(b"spdz/mac_check/opened", &encoded);
+        let coefficients = transcript.challenge_fields(b"spdz/mac_check/coefficient", opened.len());
+        let context = transcript.challenge_bytes::<32>(b"spdz/mac_check/sigma").to_vec();
+        Self { opened, coefficients, context }
+    }
+
+    /// 打开的值
+    pub fn opened(&self) -> &[u64] {
+        &self.opened
+    }
+
+    /// 取出打开的值
+    pub fn into_opened(self) -> Vec<u64> {
+        self.opened
+    }
+
+    /// 计算本方的 σ_i = Σ_k r_k·(m_ik - α_i·x_k)
+    ///
+    /// # 参数
+    /// - `macs`: 本方对每个打开值持有的 MAC 分享
+    /// - `mac_key_share`: 本方的 MAC 密钥分享 α_i
+    pub fn sigma(&self, macs: &[u64], mac_key_share: u64) -> Result<u64> {
+        if macs.len() != self.opened.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} MAC shares, got {}", self.opened.len(), macs.len()
+            )));
+        }
+        Ok(macs.iter()
+            .zip(&self.opened)
+            .zip(&self.coefficients)
+            .fold(0, |acc, ((&mac, &x), &r)| {
+                field_add(acc, field_mul(r, field_sub(mac, field_mul(mac_key_share, x))))
+            }))
+    }
+
+    /// 计算并承诺本方的 σ_i
+    pub fn commit(&self, macs: &[u64], mac_key_share: u64) -> Result<CommittedMessage<u64>> {
+        CommittedMessage::commit(self.sigma(macs, mac_key_share)?, &self.context)
+    }
+
+    /// 验证各方的承诺和打开信息，并检查 Σ_i σ_i = 0
+    ///
+    /// # 参数
+    /// - `commitments`: 按参与方排列的 σ 承诺
+    /// - `openings`: 按参与方排列的打开信息
+    ///
+    /// # 返回值
+    /// 任何打开与承诺不符或和不为 0 时返回认证错误
+    pub fn verify(&self, commitments: &[MessageCommitment], openings: Vec<MessageOpening<u64>>) -> Result<()> {
+        if commitments.len() != openings.len() {
+            return Err(MpcError::ProtocolError(format!(
+                "Got {} MAC check commitments but {} openings", commitments.len(), openings.len()
+            )));
+        }
+        let mut total = 0;
+        for (party, (commitment, opening)) in commitments.iter().zip(openings).enumerate() {
+            let sigma = commitment.open_verified(opening, &self.context).map_err(|_| {
+                MpcError::AuthenticationError(format!("Party {} opened a different MAC check value", party))
+            })?;
+            total = field_add(total, sigma);
+        }
+        if total != 0 {
+            return Err(MpcError::AuthenticationError("SPDZ MAC check failed".to_string()));
+        }
+        Ok(())
+    }
+}
@@ -13,9 +13,9 @@
 //! 
 //! ## 协议阶段
 //! 
-//! 1. **预处理阶段**: 生成 Beaver 三元组和其他预处理材料
-//! 2. **在线阶段**: 使用预处理材料进行实际计算
-//! 3. **验证阶段**: 验证计算结果的正确性
+//! 1. **预处理阶段**: 生成认证三元组、随机比特和输入掩码（`preprocessing` 子模块）
+//! 2. **在线阶段**: 使用预处理材料进行实际计算（`share` 子模块）
+//! 3. **验证阶段**: 打开时以批量 MAC 检查验证结果的正确性（`mac_check` 子模块）
 //! 
 //! ## 安全模型
 //! 
@@ -24,11 +24,15 @@
 //! - **安全性**: 提供隐私性和正确性保证
 
 pub mod share;
+pub mod mac_check;
+pub mod preprocessing;
 
 pub use share::*;
+pub use mac_check::MacCheck;
+pub use preprocessing::*;
 
 use crate::{MpcError, Result};
-use crate::secret_sharing::{FIELD_PRIME, field_add, field_sub, field_mul};
+use crate::secret_sharing::{FIELD_PRIME, field_add, field_sub, field_mul, field_inner_product};
 use crate::authentication::{HMAC, HmacKey};
 use rand::{Rng, thread_rng};
 use serde::{Deserialize, Serialize};
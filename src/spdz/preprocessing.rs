@@ -0,0 +1,295 @@
+//! # SPDZ 预处理阶段 (SPDZ Offline Phase)
+//!
+//! SPDZ 把计算分为与输入无关的离线阶段和在线阶段。离线阶段生成三类认证材料，
+//! 存入 `SpdzPreprocessingPool`，在线阶段按需取用，每份材料只使用一次：
+//!
+//! - **认证三元组**: 任意 `BeaverTripleGenerator`（BFV、OLE、可信方等）生成 Shamir 三元组，
+//!   再经 `SPDZShareProtocol::authenticate` 得到 α·a、α·b、α·c 的 MAC 分享。
+//!   在线乘法 `SPDZShareProtocol::multiply` 每次消耗一个
+//! - **随机比特**: 取随机 [r]，用一个三元组算出 [r²] 并打开，令 s 为 r² 的规范平方根，
+//!   [b] = (s⁻¹·[r] + 1)/2。s⁻¹·r = ±1 的符号由 r 决定且均匀，打开 r² 不泄露 b。
+//!   两次打开都经过 MAC 检查
+//! - **输入掩码**: 随机 [r]，r 只告知掩码的持有方。持有方公开 ε = x - r，
+//!   各方计算 [x] = [r] + ε（`SPDZShareProtocol::input_masked`）
+//!
+//! 三元组只在生成器的安全模型下可信，恶意模型下还需要在使用前验证。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::beaver_triples::OLEBeaverGenerator;
+//! use mpc_api::spdz::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let protocol = SPDZShareProtocol::new(SPDZParams::new(3, 0, 2))?;
+//! let mut offline = SpdzOfflinePhase::new(Box::new(OLEBeaverGenerator::new(3, 2, 0)?));
+//! let mut pool = SpdzPreprocessingPool::new();
+//! offline.fill(&protocol, &mut pool, &PreprocessingDemand::new(1, 1, 1))?;
+//!
+//! // 参与方 0 用掩码输入 6，与另一个输入相乘
+//! let x = protocol.input_masked(6, pool.take_input_mask(0)?)?;
+//! let y = protocol.input(7)?;
+//! let product = protocol.multiply(&x, &y, pool.take_triple()?)?;
+//! assert_eq!(protocol.open(&product)?, 42);
+//!
+//! let bit = protocol.open(&pool.take_bit()?)?;
+//! assert!(bit <= 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::share::{AuthenticatedShare, SPDZShareProtocol};
+use super::PlayerId;
+use crate::beaver_triples::{BeaverTriple, BeaverTripleGenerator, CompleteBeaverTriple};
+use crate::protocols::stats::{ProtocolOutput, ProtocolStats, StatsRecorder};
+use crate::secret_sharing::bits::bit_from_square;
+use crate::secret_sharing::Share;
+use crate::utils::random_field_element;
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// 认证 Beaver 三元组：[a]、[b]、[c = a·b] 都带 MAC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthenticatedTriple {
+    /// a 的认证分享
+    pub a: AuthenticatedShare,
+    /// b 的认证分享
+    pub b: AuthenticatedShare,
+    /// c = a·b 的认证分享
+    pub c: AuthenticatedShare,
+}
+
+/// 输入掩码：随机值 r 的认证分享，r 只有持有方知道
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputMask {
+    /// 持有方（可以用该掩码输入的参与方）
+    pub owner: PlayerId,
+    /// 掩码值 r，只应交给持有方
+    pub mask: u64,
+    /// r 的认证分享
+    pub share: AuthenticatedShare,
+}
+
+/// 一次补充预处理材料的数量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreprocessingDemand {
+    /// 认证三元组数量
+    pub triples: usize,
+    /// 随机比特数量
+    pub bits: usize,
+    /// 每个参与方的输入掩码数量
+    pub input_masks_per_party: usize,
+}
+
+impl PreprocessingDemand {
+    /// 创建补充需求
+    pub fn new(triples: usize, bits: usize, input_masks_per_party: usize) -> Self {
+        Self { triples, bits, input_masks_per_party }
+    }
+}
+
+/// 预处理池：按生成顺序保存离线阶段的认证材料，取出即删除
+#[derive(Debug, Clone, Default)]
+pub struct SpdzPreprocessingPool {
+    triples: VecDeque<AuthenticatedTriple>,
+    bits: VecDeque<AuthenticatedShare>,
+    input_masks: HashMap<PlayerId, VecDeque<InputMask>>,
+}
+
+impl SpdzPreprocessingPool {
+    /// 创建空池
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 加入认证三元组
+    pub fn push_triples(&mut self, triples: Vec<AuthenticatedTriple>) {
+        self.triples.extend(triples);
+    }
+
+    /// 加入随机比特
+    pub fn push_bits(&mut self, bits: Vec<AuthenticatedShare>) {
+        self.bits.extend(bits);
+    }
+
+    /// 加入输入掩码，按持有方分组
+    pub fn push_input_masks(&mut self, masks: Vec<InputMask>) {
+        for mask in masks {
+            self.input_masks.entry(mask.owner).or_default().push_back(mask);
+        }
+    }
+
+    /// 取出一个认证三元组
+    pub fn take_triple(&mut self) -> Result<AuthenticatedTriple> {
+        self.triples.pop_front().ok_or_else(|| exhausted("triples"))
+    }
+
+    /// 取出 `count` 个认证三元组，数量不足时不取出任何三元组
+    pub fn take_triples(&mut self, count: usize) -> Result<Vec<AuthenticatedTriple>> {
+        if self.triples.len() < count {
+            return Err(exhausted("triples"));
+        }
+        Ok(self.triples.drain(..count).collect())
+    }
+
+    /// 取出一个随机比特
+    pub fn take_bit(&mut self) -> Result<AuthenticatedShare> {
+        self.bits.pop_front().ok_or_else(|| exhausted("random bits"))
+    }
+
+    /// 取出参与方 `owner` 的一个输入掩码
+    pub fn take_input_mask(&mut self, owner: PlayerId) -> Result<InputMask> {
+        self.input_masks
+            .get_mut(&owner)
+            .and_then(VecDeque::pop_front)
+            .ok_or_else(|| exhausted(&format!("input masks for party {}", owner)))
+    }
+
+    /// 剩余的三元组数量
+    pub fn triples_available(&self) -> usize {
+        self.triples.len()
+    }
+
+    /// 剩余的随机比特数量
+    pub fn bits_available(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// 参与方 `owner` 剩余的输入掩码数量
+    pub fn input_masks_available(&self, owner: PlayerId) -> usize {
+        self.input_masks.get(&owner).map_or(0, VecDeque::len)
+    }
+}
+
+/// SPDZ 离线阶段：由三元组生成器产生认证三元组、随机比特和输入掩码
+pub struct SpdzOfflinePhase {
+    generator: Box<dyn BeaverTripleGenerator>,
+}
+
+impl SpdzOfflinePhase {
+    /// 创建离线阶段
+    ///
+    /// # 参数
+    /// - `generator`: 三元组生成器，参与方数量和门限必须与 SPDZ 参数一致
+    pub fn new(generator: Box<dyn BeaverTripleGenerator>) -> Self {
+        Self { generator }
+    }
+
+    /// 生成认证三元组
+    ///
+    /// # 返回值
+    /// 返回认证三元组和生成器的执行统计
+    pub fn generate_triples(
+        &mut self,
+        protocol: &SPDZShareProtocol,
+        count: usize,
+    ) -> Result<ProtocolOutput<Vec<AuthenticatedTriple>>> {
+        let params = protocol.params();
+        if self.generator.get_party_count() != params.num_parties || self.generator.get_threshold() != params.threshold {
+            return Err(MpcError::ProtocolError(
+                "Triple generator does not match the SPDZ parameters".to_string()
+            ));
+        }
+
+        let mut recorder = StatsRecorder::start();
+        let (raw, stats) = self.generator.generate_batch_with_stats(count)?.into_parts();
+        recorder.stats_mut().merge(&stats);
+        recorder.stats_mut().record_preprocessing(count);
+
+        let triples = raw.iter()
+            .map(|triple| authenticate_triple(protocol, triple))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(recorder.finish(triples))
+    }
+
+    /// 生成随机比特，每个比特消耗一个三元组
+    ///
+    /// # 返回值
+    /// 返回取值为 0 或 1 的认证分享和执行统计
+    pub fn generate_bits(
+        &mut self,
+        protocol: &SPDZShareProtocol,
+        count: usize,
+    ) -> Result<ProtocolOutput<Vec<AuthenticatedShare>>> {
+        let mut recorder = StatsRecorder::start();
+        let mut bits = Vec::with_capacity(count);
+
+        while bits.len() < count {
+            let needed = count - bits.len();
+            let (triples, stats) = self.generate_triples(protocol, needed)?.into_parts();
+            recorder.stats_mut().merge(&stats);
+
+            let randoms = protocol.random_batch(needed)?;
+            let squares = protocol.multiply_batch(&randoms, &randoms, triples)?;
+            for (random, square) in randoms.iter().zip(protocol.open_batch(&squares)?) {
+                // r = 0 的概率为 1/p，此时重新生成
+                if let Some((scale, offset)) = bit_from_square(square)? {
+                    bits.push(protocol.add_public(&protocol.mul_public(random, scale), offset));
+                }
+            }
+        }
+        Ok(recorder.finish(bits))
+    }
+
+    /// 为参与方 `owner` 生成输入掩码
+    pub fn generate_input_masks(
+        &self,
+        protocol: &SPDZShareProtocol,
+        owner: PlayerId,
+        count: usize,
+    ) -> Result<Vec<InputMask>> {
+        if owner >= protocol.params().num_parties {
+            return Err(MpcError::ProtocolError(format!("Unknown input party {}", owner)));
+        }
+        (0..count)
+            .map(|_| {
+                let mask = random_field_element();
+                Ok(InputMask { owner, mask, share: protocol.input(mask)? })
+            })
+            .collect()
+    }
+
+    /// 按需求生成材料并放入预处理池
+    ///
+    /// # 返回值
+    /// 返回本次补充的执行统计
+    pub fn fill(
+        &mut self,
+        protocol: &SPDZShareProtocol,
+        pool: &mut SpdzPreprocessingPool,
+        demand: &PreprocessingDemand,
+    ) -> Result<ProtocolStats> {
+        let mut stats = ProtocolStats::new();
+
+        let (triples, triple_stats) = self.generate_triples(protocol, demand.triples)?.into_parts();
+        stats.merge(&triple_stats);
+        pool.push_triples(triples);
+
+        let (bits, bit_stats) = self.generate_bits(protocol, demand.bits)?.into_parts();
+        stats.merge(&bit_stats);
+        pool.push_bits(bits);
+
+        for owner in 0..protocol.params().num_parties {
+            pool.push_input_masks(self.generate_input_masks(protocol, owner, demand.input_masks_per_party)?);
+        }
+        Ok(stats)
+    }
+}
+
+/// 认证生成器给出的一个三元组
+fn authenticate_triple(protocol: &SPDZShareProtocol, triple: &CompleteBeaverTriple) -> Result<AuthenticatedTriple> {
+    let component = |select: fn(&BeaverTriple) -> &Share| {
+        let shares: Vec<Share> = triple.shares.values().map(|share| select(share).clone()).collect();
+        protocol.authenticate(&shares)
+    };
+    Ok(AuthenticatedTriple {
+        a: component(|share| &share.a)?,
+        b: component(|share| &share.b)?,
+        c: component(|share| &share.c)?,
+    })
+}
+
+fn exhausted(material: &str) -> MpcError {
+    MpcError::ProtocolError(format!("SPDZ preprocessing pool has no {} left", material))
+}
@@ -7,9 +7,9 @@
 //! 
 //! ### 认证分享结构
 //! 每个认证分享包含：
-//! - **分享值**: 秘密的 Shamir 分享
-//! - **MAC 值**: 对分享值的消息认证码
-//! - **参与方ID**: 持有该分享的参与方标识
+//! - **分享值**: 秘密的 Shamir 分享，参与方 i 的横坐标为 i + 1
+//! - **MAC 值**: α·x 的加法分享，α = Σ_i α_i 为全局 MAC 密钥
+//! - **参与方ID**: 持有该分享的参与方标识（从 0 开始）
 //! - **分享ID**: 分享的唯一标识符
 //! 
 //! ### 安全性质
@@ -19,16 +19,52 @@
 //! 
 //! ## 支持的操作
 //! 
-//! - **线性运算**: 加法、减法、标量乘法
+//! - **线性运算**: 加法、减法、标量乘法、加公开常数
+//! - **乘法**: 消耗离线阶段生成的认证三元组（见 `preprocessing` 模块）
 //! - **分享生成**: 将秘密转换为认证分享
 //! - **秘密重构**: 从分享中恢复原始秘密
-//! - **MAC 验证**: 验证分享的完整性
+//! - **MAC 检查**: 打开时执行批量 MAC 检查，发现被篡改的分享
+//! - **密钥刷新**: 通过零分享刷新 MAC 密钥分享，并轮换通信密钥
+//! 
+//! ## 密钥刷新
+//! 
+//! 长期运行的部署中，各方周期性地执行刷新协议：每方生成一组和为零的随机值，
+//! 将第 j 个值发送给参与方 j；每方把收到的值加到自己的 MAC 密钥分享上。
+//! 全局 MAC 密钥 α 不变，已有分享的 MAC 仍然有效，但旧的密钥分享被清零，
+//! 攻击者在不同纪元窃取的分享无法组合出 α。
+//!
+//! ## 打开与 MAC 检查
+//!
+//! `open` / `open_batch` 需要全部参与方的分享：先用全部 n 个分享插值得到 x，
+//! 任何一个分享值被改动都会改变 x；再执行 [`MacCheck`](super::MacCheck)，
+//! 各方先承诺再打开 σ_i = Σ_k r_k·(m_ik - α_i·x_k)，Σ_i σ_i ≠ 0 时返回认证错误。
+//! 全局 MAC 密钥在打开过程中始终不被重构。
+//!
+//! ## 生成 MAC
+//!
+//! 新分享的 MAC 同样不经过 α：参与方 i 只用自己的 α_i 和 x 的加法分享 x_i，
+//! 本地计算 α_i·x_i，每个交叉项 α_j·x_i 由参与方 j 和 i 执行一次 OLE 得到加法分享，
+//! 见 `authenticate`。
+//!
+//! `SPDZShareProtocol` 在同一个实例中模拟全部参与方的密钥分享和检查消息；
+//! 在网络中执行同样的检查见 `network::session::MpcSession::open_spdz`。
 
 use super::*;
-use crate::secret_sharing::{Share as SecretShare, ShamirSecretSharing, SecretSharing};
+use super::mac_check::MacCheck;
+use super::preprocessing::{AuthenticatedTriple, InputMask};
+use crate::protocols::session::ProtocolSession;
+use crate::secret_sharing::{
+    Share as SecretShare, ShamirSecretSharing, SecretSharing, RevealGate, RevealRequest, validate_field_element,
+};
 use crate::authentication::MessageAuthenticationCode;
+use crate::oblivious_transfer::ole::random_field_ole;
+use crate::secret_sharing::{Field, Goldilocks};
+use crate::utils::memory::{secure_zero_u64s, SecretValue};
 use std::collections::HashMap;
 
+/// 派生 MAC 检查会话使用的协议名称
+const MAC_CHECK_PROTOCOL: &str = "mpc_api/spdz/mac_check";
+
 /// SPDZ 分享结构
 /// 
 /// 表示 SPDZ 协议中的单个认证分享。每个分享包含秘密值的一部分
@@ -53,7 +89,8 @@ pub struct SPDZShare {
 pub struct AuthenticatedShare {
     /// 来自各参与方的分享映射
     pub shares: HashMap<PlayerId, SPDZShare>,
-    /// 全局 MAC 密钥（仅以分布式形式已知）
+    /// 全局 MAC 密钥，仅供 `verify_all_macs` 按单个分享的关系 mac = α·value 检查；
+    /// 打开时不使用，协议中 α 只以分布式形式存在
     pub global_mac_key: Option<u64>,
 }
 
@@ -64,10 +101,12 @@ pub struct AuthenticatedShare {
 pub struct SPDZShareProtocol {
     /// 协议参数
     params: SPDZParams,
-    /// 当前参与方持有的全局 MAC 密钥分享
-    mac_key_share: u64,
+    /// 各参与方持有的全局 MAC 密钥加法分享，按参与方ID排列（模拟全部参与方），释放时清零
+    mac_key_shares: SecretValue<Vec<u64>>,
     /// 与其他参与方通信使用的 HMAC 密钥
     hmac_keys: HashMap<PlayerId, HmacKey>,
+    /// 密钥纪元（每次刷新加一）
+    key_epoch: u64,
 }
 
 impl SPDZShare {
@@ -168,8 +207,9 @@ impl SPDZShare {
     
     /// 验证 MAC 的有效性
     /// 
-    /// 检查分享的 MAC 值是否与预期一致，以验证分享的完整性。
-    /// 需要全局 MAC 密钥才能执行此操作。
+    /// 检查单个分享是否满足 mac = value·α，适用于以完整密钥认证的分享。
+    /// `SPDZShareProtocol` 生成的 MAC 是 α·x 的加法分享，不满足这一关系，
+    /// 应通过 `SPDZShareProtocol::open` 的批量 MAC 检查验证。
     /// 
     /// # 参数
     /// 
@@ -206,23 +246,24 @@ impl AuthenticatedShare {
         self.shares.get(&party_id)
     }
     
-    // Reconstruct the secret (requires shares from all parties)
+    /// 不做 MAC 检查，直接由分享值重构秘密
+    /// 
+    /// 参与方 i 的分享位于横坐标 i + 1。需要认证时使用 `SPDZShareProtocol::open`。
     pub fn reconstruct(&self, threshold: usize) -> Result<u64> {
         if self.shares.len() < threshold {
             return Err(MpcError::InsufficientShares);
         }
         
-        // Convert to secret shares for reconstruction
-        let mut secret_shares = Vec::new();
-        for (party_id, spdz_share) in &self.shares {
-            let share = SecretShare {
-                x: *party_id as u64,
-                y: spdz_share.value,
-            };
-            secret_shares.push(share);
-        }
-        
-        ShamirSecretSharing::reconstruct(&secret_shares, threshold)
+        ShamirSecretSharing::reconstruct(&self.secret_shares(), threshold)
+    }
+    
+    /// 转换为 Shamir 分享（参与方 i 的横坐标为 i + 1），按参与方ID排序
+    fn secret_shares(&self) -> Vec<SecretShare> {
+        let mut secret_shares: Vec<SecretShare> = self.shares.iter()
+            .map(|(party_id, spdz_share)| SecretShare::new(*party_id as u64 + 1, spdz_share.value))
+            .collect();
+        secret_shares.sort_by_key(|share| share.x);
+        secret_shares
     }
     
     // Verify all MACs (requires global MAC key)
@@ -242,9 +283,9 @@ impl SPDZShareProtocol {
             return Err(MpcError::ProtocolError("Invalid SPDZ parameters".to_string()));
         }
         
-        // Generate MAC key share
+        // Generate additive MAC key shares, one per party
         let mut rng = thread_rng();
-        let mac_key_share = rng.gen_range(0..FIELD_PRIME);
+        let mac_key_shares = SecretValue::new((0..params.num_parties).map(|_| rng.gen_range(0..FIELD_PRIME)).collect());
         
         // Generate HMAC keys for communication
         let mut hmac_keys = HashMap::new();
@@ -256,8 +297,9 @@ impl SPDZShareProtocol {
         
         Ok(Self {
             params,
-            mac_key_share,
+            mac_key_shares,
             hmac_keys,
+            key_epoch: 0,
         })
     }
     
@@ -270,29 +312,11 @@ impl SPDZShareProtocol {
             self.params.num_parties 
         )?;
         
-        let mut spdz_shares = Vec::new();
-        let mut rng = thread_rng();
-        let share_id = rng.gen();
-        
-        for share in secret_shares {
-            // Compute MAC: MAC_i = alpha_i * value + r_i
-            // where alpha_i is this party's share of the MAC key
-            let mac = field_add(
-                field_mul(self.mac_key_share, share.y),
-                rng.gen_range(0..FIELD_PRIME)  // Random mask
-            );
-            
-            let spdz_share = SPDZShare::new(
-                share.y,
-                mac,
-                share.x as PlayerId,
-                share_id,
-            );
-            
-            spdz_shares.push(spdz_share);
-        }
-        
-        Ok(spdz_shares)
+        // MAC shares are computed from the alpha shares, see `authenticate`
+        let mut authenticated = self.authenticate(&secret_shares)?;
+        Ok((0..self.params.num_parties)
+            .filter_map(|party_id| authenticated.shares.remove(&party_id))
+            .collect())
     }
     
     // Input a private value (share it among all parties)
@@ -341,7 +365,7 @@ impl SPDZShareProtocol {
         
         for party_id in 0..self.params.num_parties {
             if let Some(spdz_share) = share.get_share(party_id) {
-                let mul_share = spdz_share.mul_public(constant, self.mac_key_share);
+                let mul_share = spdz_share.mul_public(constant, self.get_mac_key_share());
                 result.add_share(party_id, mul_share);
             }
         }
@@ -349,17 +373,237 @@ impl SPDZShareProtocol {
         result
     }
     
-    // Open a shared value (reveal the secret)
-    pub fn open(&self, share: &AuthenticatedShare) -> Result<u64> {
-        // First verify MACs if global MAC key is available
-        if let Some(global_mac_key) = share.global_mac_key {
-            if !share.verify_all_macs(global_mac_key) {
-                return Err(MpcError::AuthenticationError("MAC verification failed".to_string()));
+    /// 加上公开常数
+    /// 
+    /// 每个分享值加 c（Shamir 多项式的常数项随之加 c），参与方 i 的 MAC 分享加 α_i·c，
+    /// 保持 Σ_i m_i = α·(x + c)。
+    pub fn add_public(&self, share: &AuthenticatedShare, constant: u64) -> AuthenticatedShare {
+        let mut result = AuthenticatedShare::new();
+        
+        for (&party_id, spdz_share) in &share.shares {
+            let mac_key_share = self.mac_key_shares.get(party_id).copied().unwrap_or(0);
+            result.add_share(party_id, SPDZShare::new(
+                field_add(spdz_share.value, constant),
+                field_add(spdz_share.mac, field_mul(mac_key_share, constant)),
+                party_id,
+                spdz_share.share_id,
+            ));
+        }
+        
+        result
+    }
+    
+    /// 认证一组没有 MAC 的 Shamir 分享
+    /// 
+    /// 离线阶段由三元组生成器得到的分享还没有 MAC。参与方 i 用分享值 y_i 和拉格朗日系数
+    /// λ_i 得到 x 的加法分享 x_i = λ_i·y_i，各方再用 OLE 计算 [α]·[x] 的加法分享
+    /// （见 `mac_shares`）。整个过程既不重构 x，也不重构 α。本实例模拟全部参与方，
+    /// 每个参与方的计算只读取它自己的 α_i 和 x_i。
+    /// 
+    /// # 参数
+    /// 
+    /// * `shares` - 全部参与方的分享，参与方 i 的横坐标为 i + 1
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回带 MAC 的认证分享
+    pub fn authenticate(&self, shares: &[SecretShare]) -> Result<AuthenticatedShare> {
+        let n = self.params.num_parties;
+        let mut shares = shares.to_vec();
+        shares.sort_by_key(|share| share.x);
+        if shares.len() != n || shares.iter().zip(1..).any(|(share, x)| share.x != x) {
+            return Err(MpcError::ProtocolError(format!(
+                "Authentication needs one share per party at x = 1..={}", n
+            )));
+        }
+        
+        let points: Vec<u64> = (1..=n as u64).collect();
+        let lagrange = ShamirSecretSharing::new().precompute_lagrange_coefficients(&points)?;
+        let mut additive: Vec<u64> = shares.iter().zip(&lagrange)
+            .map(|(share, &lambda)| field_mul(lambda, share.y))
+            .collect();
+        let macs = self.mac_shares(&additive);
+        secure_zero_u64s(&mut additive);
+        let share_id = thread_rng().gen();
+        
+        let mut result = AuthenticatedShare::new();
+        for (party_id, (share, mac)) in shares.iter().zip(macs).enumerate() {
+            result.add_share(party_id, SPDZShare::new(share.y, mac, party_id, share_id));
+        }
+        
+        Ok(result)
+    }
+    
+    /// 由 α 和 x 的加法分享计算 α·x 的加法分享
+    /// 
+    /// α·x = Σ_i α_i·x_i + Σ_{j≠i} α_j·x_i。对角项由参与方 i 本地计算；每个交叉项
+    /// α_j·x_i 执行一次 OLE：参与方 i 作为接收方以 x_i 为输入，参与方 j 作为发送方选随机 r
+    /// 并提供直线 α_j·t - r，i 得到 α_j·x_i - r，j 记 r。OLE 消息被离线阶段的随机 OLE
+    /// 相关性遮蔽，i 看不到 α_j，j 看不到 x_i。
+    fn mac_shares(&self, additive: &[u64]) -> Vec<u64> {
+        let mut rng = thread_rng();
+        let alpha = |party: usize| Goldilocks::from(self.mac_key_shares[party]);
+        let mut macs: Vec<Goldilocks> = additive.iter().enumerate()
+            .map(|(party, &x)| alpha(party) * Goldilocks::from(x))
+            .collect();
+        
+        for receiver in 0..additive.len() {
+            for sender in (0..additive.len()).filter(|&sender| sender != receiver) {
+                let (ole_sender, ole_receiver) = random_field_ole::<Goldilocks>();
+                let input = Goldilocks::from(additive[receiver]);
+                let u = ole_receiver.choose(input);
+                let r = Goldilocks::random(&mut rng);
+                let response = ole_sender.respond(u, alpha(sender), -r);
+                macs[receiver] = macs[receiver] + ole_receiver.finish(input, &response);
+                macs[sender] = macs[sender] + r;
             }
         }
         
-        // Reconstruct the secret
-        share.reconstruct(self.params.threshold)
+        macs.into_iter().map(|mac| mac.to_u128() as u64).collect()
+    }
+    
+    /// 用输入掩码输入私有值
+    /// 
+    /// 掩码的持有方公开 ε = x - r，各方计算 [x] = [r] + ε。
+    /// r 均匀随机且只有持有方知道，ε 不泄露 x。
+    /// 
+    /// # 参数
+    /// 
+    /// * `value` - 持有方的私有输入
+    /// * `mask` - 离线阶段为持有方生成的输入掩码，只能使用一次
+    pub fn input_masked(&self, value: u64, mask: InputMask) -> Result<AuthenticatedShare> {
+        if !validate_field_element(value) {
+            return Err(MpcError::InvalidSecretShare);
+        }
+        Ok(self.add_public(&mask.share, field_sub(value, mask.mask)))
+    }
+    
+    /// 使用认证三元组相乘
+    /// 
+    /// 打开 d = x - a、e = y - b（经过 MAC 检查），计算
+    /// [z] = [c] + d·[b] + e·[a] + d·e。
+    pub fn multiply(
+        &self,
+        x: &AuthenticatedShare,
+        y: &AuthenticatedShare,
+        triple: AuthenticatedTriple,
+    ) -> Result<AuthenticatedShare> {
+        Ok(self.multiply_batch(std::slice::from_ref(x), std::slice::from_ref(y), vec![triple])?.remove(0))
+    }
+    
+    /// 批量相乘，每对输入消耗一个三元组，所有 d、e 共用一次打开和 MAC 检查
+    pub fn multiply_batch(
+        &self,
+        x: &[AuthenticatedShare],
+        y: &[AuthenticatedShare],
+        triples: Vec<AuthenticatedTriple>,
+    ) -> Result<Vec<AuthenticatedShare>> {
+        if x.len() != y.len() || x.len() != triples.len() {
+            return Err(MpcError::ProtocolError("Batch arrays must have same length".to_string()));
+        }
+        
+        let mut masked = Vec::with_capacity(2 * x.len());
+        for ((x, y), triple) in x.iter().zip(y).zip(&triples) {
+            masked.push(self.sub(x, &triple.a)?);
+            masked.push(self.sub(y, &triple.b)?);
+        }
+        let opened = self.open_batch(&masked)?;
+        
+        triples.iter()
+            .zip(opened.chunks_exact(2))
+            .map(|(triple, de)| {
+                let (d, e) = (de[0], de[1]);
+                let z = self.add(&triple.c, &self.mul_public(&triple.b, d))?;
+                let z = self.add(&z, &self.mul_public(&triple.a, e))?;
+                Ok(self.add_public(&z, field_mul(d, e)))
+            })
+            .collect()
+    }
+    
+    /// 打开共享值并执行 MAC 检查
+    /// 
+    /// # 返回值
+    /// 
+    /// 成功时返回秘密值；缺少分享时返回 `InsufficientShares`，
+    /// 任何分享值或 MAC 被篡改时返回认证错误
+    pub fn open(&self, share: &AuthenticatedShare) -> Result<u64> {
+        Ok(self.open_batch(std::slice::from_ref(share))?.remove(0))
+    }
+    
+    /// 批量打开共享值，所有值共用一次 MAC 检查
+    /// 
+    /// 每个值用全部 n 个分享插值，然后各方对整批值承诺并打开各自的 σ_i，
+    /// 检查 Σ_i σ_i = 0。检查的随机系数和承诺上下文由每次打开新建的会话派生。
+    /// 
+    /// # 参数
+    /// 
+    /// * `shares` - 要打开的认证分享，每个都必须包含全部参与方的分享
+    /// 
+    /// # 返回值
+    /// 
+    /// 成功时返回各秘密值，MAC 检查失败时返回认证错误
+    pub fn open_batch(&self, shares: &[AuthenticatedShare]) -> Result<Vec<u64>> {
+        let n = self.params.num_parties;
+        let points: Vec<u64> = (1..=n as u64).collect();
+        let lagrange = ShamirSecretSharing::new().precompute_lagrange_coefficients(&points)?;
+        
+        // macs[i][k]: party i's MAC share of the k-th value
+        let mut macs = vec![Vec::with_capacity(shares.len()); n];
+        let opened = shares.iter()
+            .map(|share| {
+                let parties = (0..n)
+                    .map(|party_id| share.get_share(party_id))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(MpcError::InsufficientShares)?;
+                let values: Vec<u64> = parties.iter().map(|share| share.value).collect();
+                for (party_macs, share) in macs.iter_mut().zip(&parties) {
+                    party_macs.push(share.mac);
+                }
+                Ok(field_inner_product(&values, &lagrange))
+            })
+            .collect::<Result<Vec<u64>>>()?;
+        
+        let mut nonce = [0u8; 32];
+        thread_rng().fill(&mut nonce);
+        let check = MacCheck::new(&ProtocolSession::root(MAC_CHECK_PROTOCOL, &nonce), opened);
+        
+        // Every party commits to its sigma before any sigma is revealed
+        let committed = macs.iter()
+            .zip(self.mac_key_shares.iter())
+            .map(|(party_macs, &mac_key_share)| check.commit(party_macs, mac_key_share))
+            .collect::<Result<Vec<_>>>()?;
+        let commitments: Vec<_> = committed.iter().map(|c| c.commitment().clone()).collect();
+        let openings = committed.into_iter().map(|c| c.open()).collect();
+        check.verify(&commitments, openings)?;
+        
+        Ok(check.into_opened())
+    }
+    
+    /// 通过公开守门人打开共享值
+    /// 
+    /// 先执行批量 MAC 检查并将结果写入请求，之后由守门人执行门限、
+    /// 法定人数和策略检查并记录审计事件。缺少分享或分享被篡改时
+    /// MAC 检查视为未通过，是否仍然公开由守门人的策略决定。
+    /// 
+    /// # 参数
+    /// 
+    /// * `share` - 要打开的认证分享
+    /// * `gate` - 公开守门人
+    /// * `request` - 公开请求
+    /// 
+    /// # 返回值
+    /// 
+    /// 检查通过时返回秘密值，否则返回错误
+    pub fn open_gated(
+        &self,
+        share: &AuthenticatedShare,
+        gate: &RevealGate,
+        request: RevealRequest,
+    ) -> Result<u64> {
+        let mac_check_passed = self.open(share).is_ok();
+        let request = request.with_mac_check_result(mac_check_passed);
+        
+        gate.open(&request, &share.secret_shares())
     }
     
     // Generate a random shared value
@@ -431,24 +675,131 @@ impl SPDZShareProtocol {
             return Err(MpcError::ProtocolError("Invalid linear combination parameters".to_string()));
         }
         
-        let mut result = self.mul_public(&shares[0], coefficients[0]);
-        
-        for (share, &coeff) in shares.iter().zip(coefficients.iter()).skip(1) {
-            let term = self.mul_public(share, coeff);
-            result = self.add(&result, &term)?;
+        Ok(self.linear_combination_batch(shares, &[coefficients.to_vec()])?.remove(0))
+    }
+    
+    /// 批量计算公开系数的线性组合
+    /// 
+    /// 每一行系数与同一组输入分享组合出一个结果。每个参与方的分享值和 MAC
+    /// 先按列收集，再分别用 `field_inner_product` 一次算出，MAC 随分享值同步更新，
+    /// 结果与逐项调用 `mul_public` / `add` 相同。
+    /// 
+    /// # 参数
+    /// 
+    /// * `shares` - 输入的认证分享
+    /// * `coefficient_rows` - 每个结果的系数，每行长度必须等于输入数量
+    /// 
+    /// # 返回值
+    /// 
+    /// 返回每行系数对应的认证分享
+    pub fn linear_combination_batch(
+        &self,
+        shares: &[AuthenticatedShare],
+        coefficient_rows: &[Vec<u64>],
+    ) -> Result<Vec<AuthenticatedShare>> {
+        if shares.is_empty() || coefficient_rows.iter().any(|row| row.len() != shares.len()) {
+            return Err(MpcError::ProtocolError("Invalid linear combination parameters".to_string()));
         }
         
-        Ok(result)
+        // Only parties holding a share of every input take part, as with `add`
+        let columns: Vec<(PlayerId, Vec<u64>, Vec<u64>, ShareId)> = (0..self.params.num_parties)
+            .filter_map(|party_id| {
+                let party_shares = shares.iter()
+                    .map(|share| share.get_share(party_id))
+                    .collect::<Option<Vec<_>>>()?;
+                let values = party_shares.iter().map(|share| share.value).collect();
+                let macs = party_shares.iter().map(|share| share.mac).collect();
+                let share_id = party_shares.iter().fold(0 as ShareId, |id, share| id.wrapping_add(share.share_id));
+                Some((party_id, values, macs, share_id))
+            })
+            .collect();
+        
+        Ok(coefficient_rows.iter()
+            .map(|row| {
+                let mut result = AuthenticatedShare::new();
+                for (party_id, values, macs, share_id) in &columns {
+                    result.add_share(*party_id, SPDZShare::new(
+                        field_inner_product(values, row),
+                        field_inner_product(macs, row),
+                        *party_id,
+                        *share_id,
+                    ));
+                }
+                result
+            })
+            .collect())
+    }
+    
+    /// 协议参数
+    pub fn params(&self) -> &SPDZParams {
+        &self.params
     }
     
     // Get this party's MAC key share
     pub fn get_mac_key_share(&self) -> u64 {
-        self.mac_key_share
+        self.mac_key_shares[self.params.party_id]
     }
     
     // Get communication keys
     pub fn get_hmac_key(&self, party_id: PlayerId) -> Option<&HmacKey> {
         self.hmac_keys.get(&party_id)
     }
+    
+    /// 当前密钥纪元
+    pub fn key_epoch(&self) -> u64 {
+        self.key_epoch
+    }
+    
+    /// 生成本方在密钥刷新协议中的贡献
+    /// 
+    /// 返回和为零的 `num_parties` 个随机值，第 j 个值应发送给参与方 j。
+    pub fn mac_key_refresh_contribution(&self) -> Vec<u64> {
+        let mut rng = thread_rng();
+        let n = self.params.num_parties;
+        let mut contribution: Vec<u64> = (0..n - 1).map(|_| rng.gen_range(0..FIELD_PRIME)).collect();
+        let sum = contribution.iter().fold(0u64, |acc, &v| field_add(acc, v));
+        contribution.push(field_sub(0, sum));
+        contribution
+    }
+    
+    /// 应用密钥刷新
+    /// 
+    /// 将从各参与方收到的零分享值加到本方 MAC 密钥分享上，
+    /// 在原位置覆盖旧的密钥分享（不留副本），并单向轮换所有 HMAC 通信密钥。
+    /// 
+    /// 其余参与方收到的刷新值之和为本方增量的相反数；本实例模拟的
+    /// 下一个参与方的密钥分享减去该增量，使本实例中的 α 保持不变，
+    /// 已生成分享的 MAC 仍然有效。
+    /// 
+    /// # 参数
+    /// 
+    /// * `received` - 每个参与方发给本方的刷新值（每方一个）
+    /// 
+    /// # 返回值
+    /// 
+    /// 成功时返回新的密钥纪元
+    pub fn apply_mac_key_refresh(&mut self, received: &[u64]) -> Result<u64> {
+        if received.len() != self.params.num_parties {
+            return Err(MpcError::ProtocolError(format!(
+                "Expected {} refresh values, got {}", self.params.num_parties, received.len()
+            )));
+        }
+        
+        let delta = received.iter().fold(0u64, |acc, &v| field_add(acc, v));
+        let own = self.params.party_id;
+        self.mac_key_shares[own] = field_add(self.mac_key_shares[own], delta);
+        
+        let next = (own + 1) % self.params.num_parties;
+        self.mac_key_shares[next] = field_sub(self.mac_key_shares[next], delta);
+        
+        let context = format!("mpc_api 2024 spdz hmac rekey epoch {}", self.key_epoch + 1);
+        for hmac_key in self.hmac_keys.values_mut() {
+            // 旧密钥在替换时清零
+            hmac_key.key = SecretValue::new(blake3::derive_key(&context, &hmac_key.key[..]));
+        }
+        
+        self.key_epoch += 1;
+        Ok(self.key_epoch)
+    }
 }
 
@@ -0,0 +1,117 @@
+//! 基于文件系统的存储后端
+//!
+//! 每个命名空间对应根目录下的一个子目录，每个键对应一个文件。
+//! 目录名和文件名都是十六进制编码，因此命名空间和键可以包含任意字节。
+//! 写入先写临时文件再重命名，单个 `put` 在崩溃时不会留下半个值。
+
+use super::StorageBackend;
+use crate::{MpcError, Result};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// 临时文件的后缀
+const TEMP_SUFFIX: &str = ".tmp";
+
+/// 基于文件系统的存储
+#[derive(Debug, Clone)]
+pub struct FileBackend {
+    root: PathBuf,
+}
+
+impl FileBackend {
+    /// 打开（或创建）指定目录下的存储
+    pub fn open(root: impl AsRef<Path>) -> Result<Self> {
+        let root = root.as_ref().to_path_buf();
+        fs::create_dir_all(&root).map_err(storage_error)?;
+        Ok(Self { root })
+    }
+
+    /// 存储根目录
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn namespace_dir(&self, namespace: &str) -> PathBuf {
+        self.root.join(encode_hex(namespace.as_bytes()))
+    }
+
+    fn key_path(&self, namespace: &str, key: &[u8]) -> PathBuf {
+        let name = if key.is_empty() { "_".to_string() } else { encode_hex(key) };
+        self.namespace_dir(namespace).join(name)
+    }
+}
+
+impl StorageBackend for FileBackend {
+    fn get(&self, namespace: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        match fs::read(self.key_path(namespace, key)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(storage_error(e)),
+        }
+    }
+
+    fn put(&self, namespace: &str, key: &[u8], value: &[u8]) -> Result<()> {
+        fs::create_dir_all(self.namespace_dir(namespace)).map_err(storage_error)?;
+        let path = self.key_path(namespace, key);
+        let mut temp = path.clone().into_os_string();
+        temp.push(TEMP_SUFFIX);
+        fs::write(&temp, value).map_err(storage_error)?;
+        fs::rename(&temp, &path).map_err(storage_error)
+    }
+
+    fn delete(&self, namespace: &str, key: &[u8]) -> Result<bool> {
+        match fs::remove_file(self.key_path(namespace, key)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(storage_error(e)),
+        }
+    }
+
+    fn iterate(&self, namespace: &str) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let entries = match fs::read_dir(self.namespace_dir(namespace)) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(storage_error(e)),
+        };
+
+        let mut items = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(storage_error)?;
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.ends_with(TEMP_SUFFIX) {
+                continue;
+            }
+            let key = if name == "_" {
+                Vec::new()
+            } else {
+                match decode_hex(&name) {
+                    Some(key) => key,
+                    None => continue,
+                }
+            };
+            items.push((key, fs::read(entry.path()).map_err(storage_error)?));
+        }
+        items.sort();
+        Ok(items)
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn storage_error(error: std::io::Error) -> MpcError {
+    MpcError::StorageError(error.to_string())
+}
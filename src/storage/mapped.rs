@@ -0,0 +1,531 @@
+//! 内存映射的大型预处理文件
+//!
+//! 混淆表和三元组文件可能有数 GB，全部读入内存对内存较小的节点并不现实。
+//! 本模块把定长记录按页对齐写入单个文件，读取时用 `mmap` 映射整个文件，
+//! 由操作系统按需换入页面：
+//!
+//! - 第一个对齐块是文件头，记录区从下一个对齐块开始
+//! - 记录长度不超过对齐块时，每个块放入整数条记录，任何记录都不会跨页；
+//!   更长的记录各自占用整数个对齐块
+//! - 未加密的文件按记录返回映射区的切片，不发生复制
+//! - 加密的文件每条记录单独加密并带认证标签，只在访问时解密该条记录
+//!
+//! 写入按顺序进行，只缓冲当前的对齐块；文件头在 `finish` 时最后写入，
+//! 中途崩溃留下的文件无法被打开。不支持 `mmap` 的平台退化为把整个文件读入内存。
+
+use crate::authentication::HMAC;
+use crate::utils::get_page_size;
+use crate::{MpcError, Result};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// 文件头的魔数
+pub const ARTIFACT_MAGIC: [u8; 8] = *b"MPCARTF1";
+
+/// 当前的文件格式版本
+pub const ARTIFACT_VERSION: u8 = 1;
+
+/// 加密密钥的最小长度（字节）
+pub const MIN_ARTIFACT_KEY_SIZE: usize = 16;
+
+/// 每条加密记录附带的认证标签长度（字节）
+pub const RECORD_TAG_SIZE: usize = 16;
+
+/// 文件头的实际长度，不超过一个对齐块
+const HEADER_SIZE: usize = 72;
+
+/// 文件头中标志位：记录已加密
+const FLAG_ENCRYPTED: u8 = 1;
+
+/// 文件中保存的预处理材料类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactKind {
+    /// 未指定类型的定长记录
+    Raw,
+    /// 单个参与方的 Beaver 三元组分享
+    BeaverTriples,
+    /// 混淆电路的混淆表
+    GarbledTables,
+}
+
+impl ArtifactKind {
+    fn code(self) -> u8 {
+        match self {
+            ArtifactKind::Raw => 0,
+            ArtifactKind::BeaverTriples => 1,
+            ArtifactKind::GarbledTables => 2,
+        }
+    }
+
+    fn from_code(code: u8) -> Result<Self> {
+        match code {
+            0 => Ok(ArtifactKind::Raw),
+            1 => Ok(ArtifactKind::BeaverTriples),
+            2 => Ok(ArtifactKind::GarbledTables),
+            _ => Err(MpcError::StorageError(format!("Unknown artifact kind {}", code))),
+        }
+    }
+}
+
+/// 记录在文件中的排布
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArtifactLayout {
+    /// 明文记录长度（字节）
+    pub record_size: usize,
+    /// 对齐块大小，写入时默认使用系统页大小
+    pub alignment: usize,
+    /// 记录是否加密
+    pub encrypted: bool,
+}
+
+impl ArtifactLayout {
+    /// 创建排布并检查参数
+    ///
+    /// # 参数
+    /// - `record_size`: 明文记录长度，必须大于 0
+    /// - `alignment`: 对齐块大小，必须是 2 的幂且能容纳文件头
+    /// - `encrypted`: 记录是否加密
+    pub fn new(record_size: usize, alignment: usize, encrypted: bool) -> Result<Self> {
+        if record_size == 0 {
+            return Err(MpcError::StorageError("Record size must be positive".to_string()));
+        }
+        if !alignment.is_power_of_two() || alignment < HEADER_SIZE {
+            return Err(MpcError::StorageError(format!("Invalid alignment {}", alignment)));
+        }
+        Ok(Self { record_size, alignment, encrypted })
+    }
+
+    /// 每条记录在文件中占用的字节数（加密时包含认证标签）
+    pub fn stride(&self) -> usize {
+        if self.encrypted {
+            self.record_size + RECORD_TAG_SIZE
+        } else {
+            self.record_size
+        }
+    }
+
+    /// 记录区中每个块的大小，是对齐块大小的整数倍
+    pub fn block_size(&self) -> usize {
+        self.stride().div_ceil(self.alignment) * self.alignment
+    }
+
+    /// 每个块中的记录数量
+    pub fn records_per_block(&self) -> usize {
+        self.block_size() / self.stride()
+    }
+
+    /// 第 `index` 条记录在文件中的偏移
+    pub fn record_offset(&self, index: u64) -> u64 {
+        let per_block = self.records_per_block() as u64;
+        self.alignment as u64
+            + (index / per_block) * self.block_size() as u64
+            + (index % per_block) * self.stride() as u64
+    }
+
+    /// 保存 `count` 条记录的文件大小
+    pub fn file_size(&self, count: u64) -> u64 {
+        let blocks = count.div_ceil(self.records_per_block() as u64);
+        self.alignment as u64 + blocks * self.block_size() as u64
+    }
+}
+
+/// 逐条记录的加密：SHA-256 计数器模式的密钥流加上截断的 HMAC-SHA256 标签
+///
+/// 密钥流和标签都绑定记录序号，记录之间不能互相替换。
+#[derive(Clone)]
+struct RecordCipher {
+    encryption_key: Vec<u8>,
+    mac_key: Vec<u8>,
+}
+
+impl RecordCipher {
+    fn new(key: &[u8], salt: &[u8; 16]) -> Result<Self> {
+        if key.len() < MIN_ARTIFACT_KEY_SIZE {
+            return Err(MpcError::CryptographicError(format!(
+                "Artifact key must be at least {} bytes", MIN_ARTIFACT_KEY_SIZE
+            )));
+        }
+        let derive = |label: &[u8]| HMAC::derive_key(key, &[label, salt.as_slice()].concat(), 32);
+        Ok(Self {
+            encryption_key: derive(b"mpc-artifact-encryption"),
+            mac_key: derive(b"mpc-artifact-mac"),
+        })
+    }
+
+    /// 写入文件头的密钥校验值，打开时用于尽早发现错误的密钥
+    fn key_check(&self) -> [u8; 16] {
+        let mut check = [0u8; 16];
+        check.copy_from_slice(&HMAC::compute_hmac(&self.mac_key, b"key-check")[..16]);
+        check
+    }
+
+    fn apply_keystream(&self, index: u64, data: &mut [u8]) {
+        for (block, chunk) in data.chunks_mut(32).enumerate() {
+            let mut hasher = Sha256::new();
+            hasher.update(&self.encryption_key);
+            hasher.update(index.to_be_bytes());
+            hasher.update((block as u64).to_be_bytes());
+            let pad = hasher.finalize();
+            chunk.iter_mut().zip(pad.iter()).for_each(|(byte, pad)| *byte ^= pad);
+        }
+    }
+
+    fn tag(&self, index: u64, ciphertext: &[u8]) -> [u8; RECORD_TAG_SIZE] {
+        let message = [index.to_be_bytes().as_slice(), ciphertext].concat();
+        let mut tag = [0u8; RECORD_TAG_SIZE];
+        tag.copy_from_slice(&HMAC::compute_hmac(&self.mac_key, &message)[..RECORD_TAG_SIZE]);
+        tag
+    }
+
+    /// 把明文记录加密为 `密文 || 标签`
+    fn seal(&self, index: u64, record: &[u8]) -> Vec<u8> {
+        let mut sealed = record.to_vec();
+        self.apply_keystream(index, &mut sealed);
+        let tag = self.tag(index, &sealed);
+        sealed.extend_from_slice(&tag);
+        sealed
+    }
+
+    fn open(&self, index: u64, sealed: &[u8]) -> Result<Vec<u8>> {
+        let (ciphertext, tag) = sealed.split_at(sealed.len() - RECORD_TAG_SIZE);
+        if !HMAC::secure_compare(&self.tag(index, ciphertext), tag) {
+            return Err(MpcError::AuthenticationError(format!("Record {} failed authentication", index)));
+        }
+        let mut plaintext = ciphertext.to_vec();
+        self.apply_keystream(index, &mut plaintext);
+        Ok(plaintext)
+    }
+}
+
+impl std::fmt::Debug for RecordCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecordCipher").finish_non_exhaustive()
+    }
+}
+
+/// 按页对齐顺序写入定长记录
+#[derive(Debug)]
+pub struct MappedArtifactWriter {
+    file: BufWriter<File>,
+    path: PathBuf,
+    kind: ArtifactKind,
+    layout: ArtifactLayout,
+    salt: [u8; 16],
+    cipher: Option<RecordCipher>,
+    count: u64,
+    position: u64,
+}
+
+impl MappedArtifactWriter {
+    /// 创建文件，对齐块大小使用系统页大小
+    ///
+    /// # 参数
+    /// - `path`: 文件路径，已存在时覆盖
+    /// - `kind`: 记录类型
+    /// - `record_size`: 明文记录长度
+    /// - `key`: 加密密钥，为空时不加密
+    pub fn create(path: impl AsRef<Path>, kind: ArtifactKind, record_size: usize, key: Option<&[u8]>) -> Result<Self> {
+        Self::with_alignment(path, kind, record_size, get_page_size(), key)
+    }
+
+    /// 使用指定的对齐块大小创建文件
+    pub fn with_alignment(
+        path: impl AsRef<Path>,
+        kind: ArtifactKind,
+        record_size: usize,
+        alignment: usize,
+        key: Option<&[u8]>,
+    ) -> Result<Self> {
+        let layout = ArtifactLayout::new(record_size, alignment, key.is_some())?;
+        let salt: [u8; 16] = rand::random();
+        let cipher = key.map(|key| RecordCipher::new(key, &salt)).transpose()?;
+
+        let path = path.as_ref().to_path_buf();
+        let mut file = BufWriter::new(File::create(&path).map_err(|e| io_error(&path, e))?);
+        // 文件头在 finish 时写入，此前以全零占位
+        file.write_all(&vec![0u8; alignment]).map_err(|e| io_error(&path, e))?;
+
+        Ok(Self {
+            file,
+            path,
+            kind,
+            layout,
+            salt,
+            cipher,
+            count: 0,
+            position: alignment as u64,
+        })
+    }
+
+    /// 文件排布
+    pub fn layout(&self) -> ArtifactLayout {
+        self.layout
+    }
+
+    /// 已写入的记录数量
+    pub fn len(&self) -> u64 {
+        self.count
+    }
+
+    /// 是否尚未写入记录
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// 追加一条记录
+    ///
+    /// # 返回值
+    /// 记录的序号
+    pub fn append(&mut self, record: &[u8]) -> Result<u64> {
+        if record.len() != self.layout.record_size {
+            return Err(MpcError::StorageError(format!(
+                "Record has {} bytes, expected {}", record.len(), self.layout.record_size
+            )));
+        }
+        let index = self.count;
+        self.pad_to(self.layout.record_offset(index))?;
+        let sealed = match &self.cipher {
+            Some(cipher) => Cow::Owned(cipher.seal(index, record)),
+            None => Cow::Borrowed(record),
+        };
+        self.file.write_all(&sealed).map_err(|e| io_error(&self.path, e))?;
+        self.position += sealed.len() as u64;
+        self.count += 1;
+        Ok(index)
+    }
+
+    /// 补齐最后一个块、写入文件头并同步到磁盘
+    ///
+    /// # 返回值
+    /// 写入的记录数量
+    pub fn finish(mut self) -> Result<u64> {
+        self.pad_to(self.layout.file_size(self.count))?;
+
+        let mut header = [0u8; HEADER_SIZE];
+        header[..8].copy_from_slice(&ARTIFACT_MAGIC);
+        header[8] = ARTIFACT_VERSION;
+        header[9] = self.kind.code();
+        header[10] = if self.layout.encrypted { FLAG_ENCRYPTED } else { 0 };
+        header[16..24].copy_from_slice(&(self.layout.record_size as u64).to_be_bytes());
+        header[24..32].copy_from_slice(&(self.layout.alignment as u64).to_be_bytes());
+        header[32..40].copy_from_slice(&self.count.to_be_bytes());
+        header[40..56].copy_from_slice(&self.salt);
+        if let Some(cipher) = &self.cipher {
+            header[56..72].copy_from_slice(&cipher.key_check());
+        }
+
+        let path = self.path.clone();
+        let mut file = self.file.into_inner().map_err(|e| io_error(&path, e.into_error()))?;
+        file.seek(SeekFrom::Start(0)).map_err(|e| io_error(&path, e))?;
+        file.write_all(&header).map_err(|e| io_error(&path, e))?;
+        file.sync_all().map_err(|e| io_error(&path, e))?;
+        Ok(self.count)
+    }
+
+    fn pad_to(&mut self, offset: u64) -> Result<()> {
+        let padding = (offset - self.position) as usize;
+        if padding > 0 {
+            self.file.write_all(&vec![0u8; padding]).map_err(|e| io_error(&self.path, e))?;
+            self.position = offset;
+        }
+        Ok(())
+    }
+}
+
+/// 只读映射的预处理文件
+#[derive(Debug)]
+pub struct MappedArtifact {
+    map: Mapping,
+    kind: ArtifactKind,
+    layout: ArtifactLayout,
+    count: u64,
+    cipher: Option<RecordCipher>,
+}
+
+impl MappedArtifact {
+    /// 打开并映射文件
+    ///
+    /// # 参数
+    /// - `path`: 由 `MappedArtifactWriter` 写入的文件
+    /// - `key`: 加密文件的密钥；未加密的文件必须为空
+    pub fn open(path: impl AsRef<Path>, key: Option<&[u8]>) -> Result<Self> {
+        let path = path.as_ref();
+        let map = Mapping::open(path)?;
+        let bytes = map.as_slice();
+        if bytes.len() < HEADER_SIZE || bytes[..8] != ARTIFACT_MAGIC {
+            return Err(MpcError::StorageError(format!("{} is not a finished artifact file", path.display())));
+        }
+        if bytes[8] != ARTIFACT_VERSION {
+            return Err(MpcError::StorageError(format!("Unsupported artifact version {}", bytes[8])));
+        }
+
+        let kind = ArtifactKind::from_code(bytes[9])?;
+        let encrypted = bytes[10] & FLAG_ENCRYPTED != 0;
+        let read_u64 = |range: Range<usize>| u64::from_be_bytes(bytes[range].try_into().unwrap());
+        let layout = ArtifactLayout::new(read_u64(16..24) as usize, read_u64(24..32) as usize, encrypted)?;
+        let count = read_u64(32..40);
+        if (bytes.len() as u64) < layout.file_size(count) {
+            return Err(MpcError::StorageError(format!("{} is truncated", path.display())));
+        }
+
+        let cipher = match (encrypted, key) {
+            (true, Some(key)) => {
+                let salt: [u8; 16] = bytes[40..56].try_into().unwrap();
+                let cipher = RecordCipher::new(key, &salt)?;
+                if !HMAC::secure_compare(&cipher.key_check(), &bytes[56..72]) {
+                    return Err(MpcError::AuthenticationError("Wrong artifact key".to_string()));
+                }
+                Some(cipher)
+            }
+            (true, None) => return Err(MpcError::AuthenticationError("Artifact is encrypted".to_string())),
+            (false, Some(_)) => return Err(MpcError::StorageError("Artifact is not encrypted".to_string())),
+            (false, None) => None,
+        };
+
+        Ok(Self { map, kind, layout, count, cipher })
+    }
+
+    /// 记录类型
+    pub fn kind(&self) -> ArtifactKind {
+        self.kind
+    }
+
+    /// 文件排布
+    pub fn layout(&self) -> ArtifactLayout {
+        self.layout
+    }
+
+    /// 记录数量
+    pub fn len(&self) -> u64 {
+        self.count
+    }
+
+    /// 是否没有记录
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// 读取第 `index` 条记录
+    ///
+    /// 未加密时直接返回映射区中的切片；加密时校验标签并解密这一条记录。
+    pub fn record(&self, index: u64) -> Result<Cow<'_, [u8]>> {
+        if index >= self.count {
+            return Err(MpcError::StorageError(format!(
+                "Record {} out of range ({} records)", index, self.count
+            )));
+        }
+        let offset = self.layout.record_offset(index) as usize;
+        let stored = &self.map.as_slice()[offset..offset + self.layout.stride()];
+        match &self.cipher {
+            Some(cipher) => cipher.open(index, stored).map(Cow::Owned),
+            None => Ok(Cow::Borrowed(stored)),
+        }
+    }
+
+    /// 按顺序遍历全部记录
+    pub fn records(&self) -> impl Iterator<Item = Result<Cow<'_, [u8]>>> + '_ {
+        (0..self.count).map(move |index| self.record(index))
+    }
+
+    /// 提示操作系统可以回收指定记录所在的页面
+    ///
+    /// 顺序处理大文件时，对已经用过的记录调用本方法可以降低常驻内存。
+    /// 之后再次访问这些记录会重新从文件读取，因此只影响性能不影响结果。
+    pub fn release(&self, records: Range<u64>) {
+        let end = records.end.min(self.count);
+        if records.start >= end {
+            return;
+        }
+        let start = self.layout.record_offset(records.start) as usize;
+        let end = self.layout.record_offset(end - 1) as usize + self.layout.stride();
+        self.map.release(start..end);
+    }
+}
+
+/// 整个文件的只读映射
+#[cfg(unix)]
+#[derive(Debug)]
+struct Mapping {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+// 映射区只读，且在 Drop 之前一直有效
+#[cfg(unix)]
+unsafe impl Send for Mapping {}
+#[cfg(unix)]
+unsafe impl Sync for Mapping {}
+
+#[cfg(unix)]
+impl Mapping {
+    fn open(path: &Path) -> Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let file = File::open(path).map_err(|e| io_error(path, e))?;
+        let len = file.metadata().map_err(|e| io_error(path, e))?.len() as usize;
+        if len == 0 {
+            return Err(MpcError::StorageError(format!("{} is empty", path.display())));
+        }
+        let ptr = unsafe {
+            libc::mmap(std::ptr::null_mut(), len, libc::PROT_READ, libc::MAP_PRIVATE, file.as_raw_fd(), 0)
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io_error(path, std::io::Error::last_os_error()));
+        }
+        Ok(Self { ptr, len })
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+
+    fn release(&self, range: Range<usize>) {
+        // 只回收完全落在范围内的页面，映射起点本身是页对齐的
+        let page = get_page_size();
+        let start = range.start.div_ceil(page) * page;
+        let end = range.end.min(self.len) / page * page;
+        if start < end {
+            unsafe {
+                libc::madvise((self.ptr as *mut u8).add(start) as *mut libc::c_void, end - start, libc::MADV_DONTNEED);
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.ptr, self.len);
+        }
+    }
+}
+
+/// 不支持 `mmap` 的平台：把整个文件读入内存
+#[cfg(not(unix))]
+#[derive(Debug)]
+struct Mapping {
+    bytes: Vec<u8>,
+}
+
+#[cfg(not(unix))]
+impl Mapping {
+    fn open(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path).map_err(|e| io_error(path, e))?;
+        Ok(Self { bytes })
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn release(&self, _range: Range<usize>) {}
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> MpcError {
+    MpcError::StorageError(format!("{}: {}", path.display(), error))
+}
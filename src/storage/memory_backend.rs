@@ -0,0 +1,46 @@
+//! 进程内存储后端
+
+use super::StorageBackend;
+use crate::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
+
+type Namespace = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// 进程内存储，进程退出后数据丢失
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    namespaces: RwLock<HashMap<String, Namespace>>,
+}
+
+impl MemoryBackend {
+    /// 创建空的存储
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn get(&self, namespace: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let namespaces = self.namespaces.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        Ok(namespaces.get(namespace).and_then(|entries| entries.get(key)).cloned())
+    }
+
+    fn put(&self, namespace: &str, key: &[u8], value: &[u8]) -> Result<()> {
+        let mut namespaces = self.namespaces.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        namespaces.entry(namespace.to_string()).or_default().insert(key.to_vec(), value.to_vec());
+        Ok(())
+    }
+
+    fn delete(&self, namespace: &str, key: &[u8]) -> Result<bool> {
+        let mut namespaces = self.namespaces.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        Ok(namespaces.get_mut(namespace).and_then(|entries| entries.remove(key)).is_some())
+    }
+
+    fn iterate(&self, namespace: &str) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        let namespaces = self.namespaces.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        Ok(namespaces.get(namespace)
+            .map(|entries| entries.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default())
+    }
+}
@@ -0,0 +1,96 @@
+//! # 存储后端 (Storage Backends)
+//!
+//! 预处理池、会话存储和审计日志都需要持久化数据。本模块定义统一的
+//! `StorageBackend` 接口（按命名空间划分的键值存储），嵌入方可以提供自己的
+//! 加密或多副本存储实现。内置实现：
+//!
+//! - `MemoryBackend`: 进程内存储，用于测试和不需要持久化的场景
+//! - `FileBackend`: 每个命名空间一个目录、每个键一个文件
+//! - `SledBackend`: 基于 sled 数据库（需要 `sled` 特性）
+//!
+//! 数 GB 级的三元组文件和混淆表不适合放进键值存储，`mapped` 子模块提供按页对齐、
+//! 内存映射、按需解密的定长记录文件（`MappedArtifactWriter` / `MappedArtifact`）。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::storage::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let backend = MemoryBackend::new();
+//! backend.put("sessions", b"alpha", b"state")?;
+//! assert_eq!(backend.get("sessions", b"alpha")?, Some(b"state".to_vec()));
+//!
+//! // 带类型的读写使用 bincode 编码
+//! store_value(&backend, "counters", b"rounds", &7u64)?;
+//! assert_eq!(load_value::<u64>(&backend, "counters", b"rounds")?, Some(7));
+//!
+//! assert!(backend.delete("sessions", b"alpha")?);
+//! assert!(backend.iterate("sessions")?.is_empty());
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod file_backend;
+pub mod memory_backend;
+pub mod mapped;
+#[cfg(feature = "sled")]
+pub mod sled_backend;
+
+pub use file_backend::*;
+pub use memory_backend::*;
+pub use mapped::*;
+#[cfg(feature = "sled")]
+pub use sled_backend::*;
+
+use crate::{MpcError, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt;
+
+/// 按命名空间划分的键值存储
+///
+/// 不同命名空间中的键互不影响。实现需要保证单个 `put` / `delete` 是原子的，
+/// 多个操作之间的原子性由调用方自行加锁保证。
+pub trait StorageBackend: Send + Sync + fmt::Debug {
+    /// 读取键对应的值
+    fn get(&self, namespace: &str, key: &[u8]) -> Result<Option<Vec<u8>>>;
+
+    /// 写入键值，已存在时覆盖
+    fn put(&self, namespace: &str, key: &[u8], value: &[u8]) -> Result<()>;
+
+    /// 删除键
+    ///
+    /// # 返回值
+    /// 键存在并被删除时返回 `true`
+    fn delete(&self, namespace: &str, key: &[u8]) -> Result<bool>;
+
+    /// 按键的字节序列出命名空间中的全部键值
+    fn iterate(&self, namespace: &str) -> Result<Vec<(Vec<u8>, Vec<u8>)>>;
+
+    /// 把缓冲的写入刷到持久存储
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// 以 bincode 编码写入值
+pub fn store_value<T: Serialize + ?Sized>(
+    backend: &dyn StorageBackend,
+    namespace: &str,
+    key: &[u8],
+    value: &T,
+) -> Result<()> {
+    let bytes = bincode::serialize(value).map_err(|e| MpcError::SerializationError(e.to_string()))?;
+    backend.put(namespace, key, &bytes)
+}
+
+/// 读取以 bincode 编码的值
+pub fn load_value<T: DeserializeOwned>(
+    backend: &dyn StorageBackend,
+    namespace: &str,
+    key: &[u8],
+) -> Result<Option<T>> {
+    backend.get(namespace, key)?
+        .map(|bytes| bincode::deserialize(&bytes).map_err(|e| MpcError::SerializationError(e.to_string())))
+        .transpose()
+}
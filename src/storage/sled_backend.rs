@@ -0,0 +1,67 @@
+//! 基于 sled 的存储后端
+//!
+//! 每个命名空间对应一个 sled 树。
+
+use super::StorageBackend;
+use crate::{MpcError, Result};
+use std::path::Path;
+
+/// 基于 sled 数据库的存储
+#[derive(Debug, Clone)]
+pub struct SledBackend {
+    db: sled::Db,
+}
+
+impl SledBackend {
+    /// 打开（或创建）指定目录下的存储
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self { db: sled::open(path).map_err(storage_error)? })
+    }
+
+    /// 创建进程退出后即删除的临时存储
+    pub fn temporary() -> Result<Self> {
+        Ok(Self { db: sled::Config::new().temporary(true).open().map_err(storage_error)? })
+    }
+
+    /// 使用已打开的数据库
+    pub fn from_db(db: sled::Db) -> Self {
+        Self { db }
+    }
+
+    fn tree(&self, namespace: &str) -> Result<sled::Tree> {
+        self.db.open_tree(namespace).map_err(storage_error)
+    }
+}
+
+impl StorageBackend for SledBackend {
+    fn get(&self, namespace: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self.tree(namespace)?.get(key).map_err(storage_error)?.map(|value| value.to_vec()))
+    }
+
+    fn put(&self, namespace: &str, key: &[u8], value: &[u8]) -> Result<()> {
+        self.tree(namespace)?.insert(key, value).map_err(storage_error)?;
+        Ok(())
+    }
+
+    fn delete(&self, namespace: &str, key: &[u8]) -> Result<bool> {
+        Ok(self.tree(namespace)?.remove(key).map_err(storage_error)?.is_some())
+    }
+
+    fn iterate(&self, namespace: &str) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+        self.tree(namespace)?
+            .iter()
+            .map(|entry| {
+                let (key, value) = entry.map_err(storage_error)?;
+                Ok((key.to_vec(), value.to_vec()))
+            })
+            .collect()
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.db.flush().map(|_| ()).map_err(storage_error)
+    }
+}
+
+fn storage_error(error: sled::Error) -> MpcError {
+    MpcError::StorageError(error.to_string())
+}
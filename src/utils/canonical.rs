@@ -0,0 +1,457 @@
+//! # 规范结构化哈希 (Canonical Structured Hashing)
+//!
+//! 承诺、MAC 和转录摘要需要对结构化的值（分享、协议头、消息）计算哈希。
+//! 直接拼接 `to_le_bytes` 的写法很容易出错：`usize` 的宽度随平台变化，
+//! 缺少长度前缀会产生歧义，`HashMap` 的遍历顺序每次运行都不同，
+//! 字段调整后旧版本计算的哈希也会悄悄失效。
+//!
+//! 本模块为任何实现了 `Serialize` 的值定义一种确定的规范编码：
+//!
+//! - 所有整数按固定宽度小端序编码，`usize`/`isize` 统一为 64 位
+//! - 字符串、字节串和序列带 64 位长度前缀
+//! - 映射的条目按键的编码排序
+//! - 结构体按字段名排序后编码为 (字段名, 值) 对，与字段声明顺序无关
+//! - 枚举以变体序号（32 位）作为标签
+//!
+//! `HashSet` 以序列形式编码，遍历顺序不确定，需要哈希集合时应使用 `BTreeSet`。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::utils::{canonical_encode, hash_struct};
+//! use std::collections::HashMap;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let mut first = HashMap::new();
+//! let mut second = HashMap::new();
+//! for i in 0..16u64 {
+//!     first.insert(i, i * i);
+//!     second.insert(15 - i, (15 - i) * (15 - i));
+//! }
+//! assert_eq!(hash_struct(&first)?, hash_struct(&second)?);
+//!
+//! // usize 与 u64 的编码相同，与平台无关
+//! assert_eq!(canonical_encode(&7usize)?, canonical_encode(&7u64)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{MpcError, Result};
+use serde::ser::{self, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// 结构化哈希的默认域分隔标签
+pub const CANONICAL_HASH_DOMAIN: &[u8] = b"mpc_api/canonical/v1";
+
+/// 计算值的规范编码
+///
+/// # 参数
+/// * `value` - 需要编码的值
+///
+/// # 返回值
+/// * `Result<Vec<u8>>` - 规范编码；值无法序列化时返回序列化错误
+pub fn canonical_encode<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    let mut encoder = CanonicalEncoder::default();
+    value.serialize(&mut encoder)
+        .map_err(|e| MpcError::SerializationError(e.0))?;
+    Ok(encoder.out)
+}
+
+/// 计算值的规范哈希（SHA-256）
+///
+/// # 参数
+/// * `value` - 需要哈希的值
+///
+/// # 返回值
+/// * `Result<[u8; 32]>` - 使用默认域分隔标签的哈希
+pub fn hash_struct<T: Serialize + ?Sized>(value: &T) -> Result<[u8; 32]> {
+    hash_struct_with_domain(CANONICAL_HASH_DOMAIN, value)
+}
+
+/// 在指定域中计算值的规范哈希
+///
+/// # 参数
+/// * `domain` - 域分隔标签，不同用途应使用不同的标签
+/// * `value` - 需要哈希的值
+///
+/// # 返回值
+/// * `Result<[u8; 32]>` - 绑定域标签的哈希
+pub fn hash_struct_with_domain<T: Serialize + ?Sized>(domain: &[u8], value: &T) -> Result<[u8; 32]> {
+    let encoded = canonical_encode(value)?;
+    let mut hasher = Sha256::new();
+    hasher.update((domain.len() as u64).to_le_bytes());
+    hasher.update(domain);
+    hasher.update(&encoded);
+    Ok(hasher.finalize().into())
+}
+
+/// 规范编码错误
+#[derive(Debug)]
+struct CanonicalError(String);
+
+impl fmt::Display for CanonicalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for CanonicalError {}
+
+impl ser::Error for CanonicalError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        CanonicalError(msg.to_string())
+    }
+}
+
+type EncodeResult = std::result::Result<(), CanonicalError>;
+
+/// 规范编码器
+#[derive(Default)]
+struct CanonicalEncoder {
+    out: Vec<u8>,
+}
+
+impl CanonicalEncoder {
+    fn write_len(&mut self, len: usize) {
+        self.out.extend_from_slice(&(len as u64).to_le_bytes());
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_len(bytes.len());
+        self.out.extend_from_slice(bytes);
+    }
+
+    fn write_tag(&mut self, variant_index: u32) {
+        self.out.extend_from_slice(&variant_index.to_le_bytes());
+    }
+
+    /// 单独编码一个值，用于需要排序的键、字段和序列元素
+    fn encode_detached<T: Serialize + ?Sized>(value: &T) -> std::result::Result<Vec<u8>, CanonicalError> {
+        let mut encoder = CanonicalEncoder::default();
+        value.serialize(&mut encoder)?;
+        Ok(encoder.out)
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut CanonicalEncoder {
+    type Ok = ();
+    type Error = CanonicalError;
+    type SerializeSeq = SeqEncoder<'a>;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = MapEncoder<'a>;
+    type SerializeStruct = StructEncoder<'a>;
+    type SerializeStructVariant = StructEncoder<'a>;
+
+    fn serialize_bool(self, v: bool) -> EncodeResult {
+        self.out.push(v as u8);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> EncodeResult {
+        self.out.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> EncodeResult {
+        self.out.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> EncodeResult {
+        self.out.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> EncodeResult {
+        self.out.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> EncodeResult {
+        self.out.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> EncodeResult {
+        self.out.push(v);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> EncodeResult {
+        self.out.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> EncodeResult {
+        self.out.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> EncodeResult {
+        self.out.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> EncodeResult {
+        self.out.extend_from_slice(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> EncodeResult {
+        self.out.extend_from_slice(&v.to_bits().to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> EncodeResult {
+        self.out.extend_from_slice(&v.to_bits().to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> EncodeResult {
+        self.out.extend_from_slice(&(v as u32).to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> EncodeResult {
+        self.write_bytes(v.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> EncodeResult {
+        self.write_bytes(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> EncodeResult {
+        self.out.push(0);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> EncodeResult {
+        self.out.push(1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> EncodeResult {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> EncodeResult {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str) -> EncodeResult {
+        self.write_tag(variant_index);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> EncodeResult {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> EncodeResult {
+        self.write_tag(variant_index);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> std::result::Result<SeqEncoder<'a>, CanonicalError> {
+        Ok(SeqEncoder { parent: self, count: 0, body: Vec::new() })
+    }
+
+    fn serialize_tuple(self, _len: usize) -> std::result::Result<Self, CanonicalError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> std::result::Result<Self, CanonicalError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self, CanonicalError> {
+        self.write_tag(variant_index);
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> std::result::Result<MapEncoder<'a>, CanonicalError> {
+        Ok(MapEncoder { parent: self, entries: Vec::new(), pending_key: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> std::result::Result<StructEncoder<'a>, CanonicalError> {
+        Ok(StructEncoder { parent: self, fields: Vec::new() })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> std::result::Result<StructEncoder<'a>, CanonicalError> {
+        self.write_tag(variant_index);
+        Ok(StructEncoder { parent: self, fields: Vec::new() })
+    }
+}
+
+/// 序列编码：元素数量事先未知时也能写出长度前缀
+struct SeqEncoder<'a> {
+    parent: &'a mut CanonicalEncoder,
+    count: usize,
+    body: Vec<u8>,
+}
+
+impl ser::SerializeSeq for SeqEncoder<'_> {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> EncodeResult {
+        self.body.extend(CanonicalEncoder::encode_detached(value)?);
+        self.count += 1;
+        Ok(())
+    }
+
+    fn end(self) -> EncodeResult {
+        self.parent.write_len(self.count);
+        self.parent.out.extend(self.body);
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut CanonicalEncoder {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> EncodeResult {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> EncodeResult {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut CanonicalEncoder {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> EncodeResult {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> EncodeResult {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut CanonicalEncoder {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> EncodeResult {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> EncodeResult {
+        Ok(())
+    }
+}
+
+/// 映射编码：条目按键的编码排序
+struct MapEncoder<'a> {
+    parent: &'a mut CanonicalEncoder,
+    entries: Vec<(Vec<u8>, Vec<u8>)>,
+    pending_key: Option<Vec<u8>>,
+}
+
+impl ser::SerializeMap for MapEncoder<'_> {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> EncodeResult {
+        self.pending_key = Some(CanonicalEncoder::encode_detached(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> EncodeResult {
+        let key = self.pending_key.take()
+            .ok_or_else(|| CanonicalError("Map value serialized before its key".to_string()))?;
+        self.entries.push((key, CanonicalEncoder::encode_detached(value)?));
+        Ok(())
+    }
+
+    fn end(mut self) -> EncodeResult {
+        self.entries.sort();
+        self.parent.write_len(self.entries.len());
+        for (key, value) in self.entries {
+            self.parent.out.extend(key);
+            self.parent.out.extend(value);
+        }
+        Ok(())
+    }
+}
+
+/// 结构体编码：字段按名称排序
+struct StructEncoder<'a> {
+    parent: &'a mut CanonicalEncoder,
+    fields: Vec<(&'static str, Vec<u8>)>,
+}
+
+impl StructEncoder<'_> {
+    fn push_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> EncodeResult {
+        self.fields.push((key, CanonicalEncoder::encode_detached(value)?));
+        Ok(())
+    }
+
+    fn finish(mut self) -> EncodeResult {
+        self.fields.sort_by(|a, b| a.0.cmp(b.0));
+        self.parent.write_len(self.fields.len());
+        for (key, value) in self.fields {
+            self.parent.write_bytes(key.as_bytes());
+            self.parent.out.extend(value);
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for StructEncoder<'_> {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> EncodeResult {
+        self.push_field(key, value)
+    }
+
+    fn skip_field(&mut self, _key: &'static str) -> EncodeResult {
+        Ok(())
+    }
+
+    fn end(self) -> EncodeResult {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for StructEncoder<'_> {
+    type Ok = ();
+    type Error = CanonicalError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> EncodeResult {
+        self.push_field(key, value)
+    }
+
+    fn end(self) -> EncodeResult {
+        self.finish()
+    }
+}
@@ -0,0 +1,412 @@
+//! # 结构化并发 (Structured Concurrency)
+//!
+//! 并行执行的协议子任务（例如 16 批并行的 OT 扩展）通常互相依赖：任何一批失败，
+//! 其余批次的结果都没有用处。本模块提供：
+//!
+//! - **取消令牌 (`CancellationToken`)**: 可克隆的取消标志，支持父子层级，父令牌取消时
+//!   所有子令牌一同取消；阻塞等待的任务可以用 `wait_timeout` 及时醒来
+//! - **作用域任务组 (`task_scope`)**: 子任务可以借用调用方的数据，函数返回前所有子任务
+//!   都已结束
+//! - **后台任务组 (`TaskGroup`)**: 在后台线程中运行的任务组，被丢弃时取消并等待全部任务
+//!
+//! 两种任务组的失败语义相同：第一个失败（返回错误或 panic）的子任务取消任务组的令牌，
+//! 其余子任务在下一次检查令牌时退出；汇合时返回第一个错误，即失败的根本原因，
+//! 而不是兄弟任务因取消而产生的错误。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::utils::concurrency::*;
+//! use mpc_api::MpcError;
+//! use std::time::Duration;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let batches: Vec<u64> = (0..16).collect();
+//! let root = CancellationToken::new();
+//!
+//! // 全部成功时按创建顺序返回结果
+//! let doubled = task_scope(&root, |group| {
+//!     for batch in &batches {
+//!         group.spawn(move |_| Ok(batch * 2));
+//!     }
+//!     Ok(())
+//! })?;
+//! assert_eq!(doubled[3], 6);
+//!
+//! // 一个子任务失败时，其余子任务被取消，返回的是失败的根本原因
+//! let result: mpc_api::Result<Vec<u64>> = task_scope(&root, |group| {
+//!     for &batch in &batches {
+//!         group.spawn(move |token| {
+//!             if batch == 5 {
+//!                 return Err(MpcError::NetworkError("peer disconnected".to_string()));
+//!             }
+//!             token.wait_timeout(Duration::from_secs(30));
+//!             token.check()?;
+//!             Ok(batch)
+//!         });
+//!     }
+//!     Ok(())
+//! });
+//! assert!(matches!(result, Err(MpcError::NetworkError(_))));
+//! assert!(!root.is_cancelled());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{MpcError, Result};
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, Weak};
+use std::thread::{self, JoinHandle, Scope, ScopedJoinHandle};
+use std::time::{Duration, Instant};
+
+/// 取消原因：子任务失败
+const SIBLING_FAILED: &str = "a sibling task failed";
+
+/// 取消原因：任务组被丢弃
+const GROUP_DROPPED: &str = "task group dropped";
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[derive(Debug, Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    reason: Mutex<Option<String>>,
+    changed: Condvar,
+    children: Mutex<Vec<Weak<TokenState>>>,
+}
+
+impl TokenState {
+    fn cancel(&self, reason: &str) {
+        {
+            let mut current = lock(&self.reason);
+            if current.is_some() {
+                return;
+            }
+            *current = Some(reason.to_string());
+            self.cancelled.store(true, Ordering::Release);
+        }
+        self.changed.notify_all();
+
+        let children = std::mem::take(&mut *lock(&self.children));
+        for child in children.iter().filter_map(Weak::upgrade) {
+            child.cancel(reason);
+        }
+    }
+}
+
+/// 协作式取消令牌
+///
+/// 克隆的令牌共享同一状态；`child_token` 创建的子令牌在父令牌取消时一同取消，
+/// 取消子令牌不影响父令牌。令牌只能取消一次，取消原因以第一次为准。
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<TokenState>,
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("reason", &self.reason())
+            .finish()
+    }
+}
+
+impl CancellationToken {
+    /// 创建未取消的根令牌
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建子令牌
+    ///
+    /// 父令牌已经取消时，子令牌创建后立即处于取消状态。
+    pub fn child_token(&self) -> Self {
+        let child = Self::new();
+        {
+            let mut children = lock(&self.state.children);
+            if !self.is_cancelled() {
+                children.retain(|weak| weak.strong_count() > 0);
+                children.push(Arc::downgrade(&child.state));
+                return child;
+            }
+        }
+        if let Some(reason) = self.reason() {
+            child.cancel(&reason);
+        }
+        child
+    }
+
+    /// 取消令牌及其所有子令牌
+    ///
+    /// # 参数
+    /// - `reason`: 取消原因，出现在 `check` 返回的错误中
+    pub fn cancel(&self, reason: &str) {
+        self.state.cancel(reason);
+    }
+
+    /// 是否已取消
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Acquire)
+    }
+
+    /// 取消原因，未取消时为 `None`
+    pub fn reason(&self) -> Option<String> {
+        lock(&self.state.reason).clone()
+    }
+
+    /// 已取消时返回 `ProtocolError`
+    pub fn check(&self) -> Result<()> {
+        match self.reason() {
+            Some(reason) => Err(MpcError::ProtocolError(format!("Cancelled: {}", reason))),
+            None => Ok(()),
+        }
+    }
+
+    /// 阻塞等待，直到令牌被取消或超时
+    ///
+    /// # 返回值
+    /// 令牌已取消时返回 `true`
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut reason = lock(&self.state.reason);
+        while reason.is_none() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return false;
+            }
+            reason = self.state.changed
+                .wait_timeout(reason, remaining)
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0;
+        }
+        true
+    }
+}
+
+/// 任务组的共享状态：组令牌和第一个失败
+#[derive(Debug)]
+struct GroupState {
+    token: CancellationToken,
+    failure: Mutex<Option<MpcError>>,
+}
+
+impl GroupState {
+    fn new(parent: &CancellationToken) -> Arc<Self> {
+        Arc::new(Self {
+            token: parent.child_token(),
+            failure: Mutex::new(None),
+        })
+    }
+
+    /// 记录失败（只保留第一个）并取消其余子任务
+    fn fail(&self, error: MpcError) {
+        lock(&self.failure).get_or_insert(error);
+        self.token.cancel(SIBLING_FAILED);
+    }
+
+    /// 在当前线程中运行子任务；失败或 panic 时取消任务组
+    fn run<T>(&self, task: impl FnOnce(&CancellationToken) -> Result<T>) -> Option<T> {
+        struct PanicGuard<'a>(&'a GroupState);
+        impl Drop for PanicGuard<'_> {
+            fn drop(&mut self) {
+                if thread::panicking() {
+                    self.0.fail(MpcError::ProtocolError("Task panicked".to_string()));
+                }
+            }
+        }
+
+        let _guard = PanicGuard(self);
+        match task(&self.token) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.fail(e);
+                None
+            }
+        }
+    }
+
+    /// 汇合全部子任务的结果
+    fn collect<T>(&self, outcomes: Vec<thread::Result<Option<T>>>) -> Result<Vec<T>> {
+        let mut values = Vec::with_capacity(outcomes.len());
+        for outcome in outcomes {
+            match outcome {
+                Ok(Some(value)) => values.push(value),
+                Ok(None) => {}
+                Err(_) => self.fail(MpcError::ProtocolError("Task panicked".to_string())),
+            }
+        }
+        match lock(&self.failure).take() {
+            Some(error) => Err(error),
+            None => Ok(values),
+        }
+    }
+}
+
+/// `task_scope` 中的任务组
+///
+/// 子任务可以借用作用域外的数据。
+pub struct TaskScope<'scope, 'env: 'scope, T> {
+    scope: &'scope Scope<'scope, 'env>,
+    state: Arc<GroupState>,
+    handles: RefCell<Vec<ScopedJoinHandle<'scope, Option<T>>>>,
+}
+
+impl<'scope, 'env, T: Send + 'scope> TaskScope<'scope, 'env, T> {
+    /// 启动子任务
+    ///
+    /// 子任务收到任务组的令牌，应在阻塞操作之间检查它。任务组已取消时不再启动新任务，
+    /// 任务组以取消错误结束。
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: FnOnce(&CancellationToken) -> Result<T> + Send + 'scope,
+    {
+        if let Err(e) = self.state.token.check() {
+            self.state.fail(e);
+            return;
+        }
+        let state = Arc::clone(&self.state);
+        let handle = self.scope.spawn(move || state.run(task));
+        self.handles.borrow_mut().push(handle);
+    }
+
+    /// 任务组的令牌
+    pub fn token(&self) -> &CancellationToken {
+        &self.state.token
+    }
+}
+
+/// 在作用域内并行运行一组子任务
+///
+/// `body` 通过 `TaskScope::spawn` 启动子任务；函数在所有子任务结束后返回。
+/// 任务组的令牌是 `parent` 的子令牌：取消 `parent` 会取消全部子任务，
+/// 子任务失败只取消本任务组。
+///
+/// # 参数
+/// - `parent`: 父令牌
+/// - `body`: 启动子任务的闭包，返回错误时同样取消已启动的子任务
+///
+/// # 返回值
+/// 全部成功时按启动顺序返回结果，否则返回第一个失败
+pub fn task_scope<'env, T, F>(parent: &CancellationToken, body: F) -> Result<Vec<T>>
+where
+    T: Send + 'env,
+    F: for<'scope> FnOnce(&TaskScope<'scope, 'env, T>) -> Result<()>,
+{
+    parent.check()?;
+    let state = GroupState::new(parent);
+    let outcomes = thread::scope(|scope| {
+        let group = TaskScope {
+            scope,
+            state: Arc::clone(&state),
+            handles: RefCell::new(Vec::new()),
+        };
+        if let Err(e) = body(&group) {
+            state.fail(e);
+        }
+        group.handles.into_inner().into_iter().map(ScopedJoinHandle::join).collect()
+    });
+    state.collect(outcomes)
+}
+
+/// 在后台线程中运行的任务组
+///
+/// 与 `task_scope` 的失败语义相同，但子任务必须拥有自己的数据。任务组被丢弃时
+/// 取消全部子任务并等待它们结束，不会留下无人等待的线程。
+pub struct TaskGroup<T> {
+    name: String,
+    state: Arc<GroupState>,
+    handles: Vec<JoinHandle<Option<T>>>,
+}
+
+impl<T> fmt::Debug for TaskGroup<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskGroup")
+            .field("name", &self.name)
+            .field("tasks", &self.handles.len())
+            .field("token", &self.state.token)
+            .finish()
+    }
+}
+
+impl<T: Send + 'static> TaskGroup<T> {
+    /// 创建任务组
+    ///
+    /// # 参数
+    /// - `name`: 线程名前缀，子任务线程命名为 `<name>-<序号>`
+    /// - `parent`: 父令牌
+    pub fn new(name: impl Into<String>, parent: &CancellationToken) -> Self {
+        Self {
+            name: name.into(),
+            state: GroupState::new(parent),
+            handles: Vec::new(),
+        }
+    }
+
+    /// 启动子任务
+    pub fn spawn<F>(&mut self, task: F) -> Result<()>
+    where
+        F: FnOnce(&CancellationToken) -> Result<T> + Send + 'static,
+    {
+        let state = Arc::clone(&self.state);
+        let handle = thread::Builder::new()
+            .name(format!("{}-{}", self.name, self.handles.len()))
+            .spawn(move || state.run(task))
+            .map_err(|e| {
+                let message = format!("Failed to spawn {} task: {}", self.name, e);
+                self.state.fail(MpcError::ProtocolError(message.clone()));
+                MpcError::ProtocolError(message)
+            })?;
+        self.handles.push(handle);
+        Ok(())
+    }
+
+    /// 任务组的令牌
+    pub fn token(&self) -> &CancellationToken {
+        &self.state.token
+    }
+
+    /// 子任务数量
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// 是否没有子任务
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// 所有子任务是否已结束
+    pub fn is_finished(&self) -> bool {
+        self.handles.iter().all(JoinHandle::is_finished)
+    }
+
+    /// 取消全部子任务
+    pub fn cancel(&self, reason: &str) {
+        self.state.token.cancel(reason);
+    }
+
+    /// 等待全部子任务结束
+    ///
+    /// # 返回值
+    /// 全部成功时按启动顺序返回结果，否则返回第一个失败
+    pub fn join(mut self) -> Result<Vec<T>> {
+        let outcomes = std::mem::take(&mut self.handles).into_iter().map(JoinHandle::join).collect();
+        self.state.collect(outcomes)
+    }
+}
+
+impl<T> Drop for TaskGroup<T> {
+    fn drop(&mut self) {
+        if self.handles.is_empty() {
+            return;
+        }
+        self.state.token.cancel(GROUP_DROPPED);
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! # 可替换的哈希与伪随机数生成器 (Pluggable Hash and PRG Backends)
+//!
+//! 协议中的随机性过去直接来自 `rand::thread_rng`，哈希固定为 SHA-256。
+//! 本模块把两者抽象出来：
+//!
+//! - **Hash**: 32 字节输出的哈希函数，`Sha256Hash` 和 `Blake3Hash` 两种实现，
+//!   BLAKE3 在长输入上快数倍
+//! - **Prg**: 由 32 字节种子确定的密码学伪随机数生成器，实现 `RngCore + CryptoRng`，
+//!   可以直接传给任何接受 `Rng` 的接口
+//!   - `AesCtrPrg`: AES-128 计数器模式，CPU 支持 AES-NI 时每次批量加密 8 个分组
+//!   - `Blake3Prg`: BLAKE3 的可扩展输出，不依赖硬件指令
+//! - **derive_seed**: 由主种子和用途标签派生相互独立的子种子
+//!
+//! 混淆、OT 扩展和份额生成都提供接受显式随机数生成器的接口，
+//! 给定种子时协议的输出完全确定，便于复现、测试和审计。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::utils::crypto::*;
+//! use rand::Rng;
+//!
+//! let seed = [7u8; 32];
+//! let mut first = AesCtrPrg::from_seed(seed);
+//! let mut second = AesCtrPrg::from_seed(seed);
+//! assert_eq!(first.gen::<[u64; 4]>(), second.gen::<[u64; 4]>());
+//!
+//! // 同一主种子派生出的子种子互不相关
+//! let garbling = Blake3Prg::from_seed(derive_seed(&seed, b"garbling"));
+//! let sharing = Blake3Prg::from_seed(derive_seed(&seed, b"sharing"));
+//! # let _ = (garbling, sharing);
+//!
+//! assert_eq!(Sha256Hash::digest(b"abc"), Sha256Hash::new().chain_update(b"ab").chain_update(b"c").finalize());
+//! ```
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use aes::{Aes128, Block};
+use rand::{CryptoRng, RngCore};
+use sha2::Digest;
+
+pub use rand::SeedableRng;
+
+/// 32 字节输出的哈希函数
+pub trait Hash: Clone {
+    /// 算法名称
+    const NAME: &'static str;
+
+    /// 创建空状态
+    fn new() -> Self;
+
+    /// 吸收数据
+    fn update(&mut self, data: &[u8]);
+
+    /// 输出摘要
+    fn finalize(self) -> [u8; 32];
+
+    /// 吸收数据并返回自身，便于链式调用
+    fn chain_update(mut self, data: &[u8]) -> Self {
+        self.update(data);
+        self
+    }
+
+    /// 一次性计算摘要
+    fn digest(data: &[u8]) -> [u8; 32] {
+        Self::new().chain_update(data).finalize()
+    }
+
+    /// 计算多个部分的摘要，每个部分带 64 位长度前缀，不同划分不会得到相同的输入
+    fn digest_parts(parts: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = Self::new();
+        for part in parts {
+            hasher.update(&(part.len() as u64).to_le_bytes());
+            hasher.update(part);
+        }
+        hasher.finalize()
+    }
+}
+
+/// SHA-256
+#[derive(Debug, Clone, Default)]
+pub struct Sha256Hash(sha2::Sha256);
+
+impl Hash for Sha256Hash {
+    const NAME: &'static str = "SHA-256";
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+/// BLAKE3
+#[derive(Debug, Clone, Default)]
+pub struct Blake3Hash(blake3::Hasher);
+
+impl Hash for Blake3Hash {
+    const NAME: &'static str = "BLAKE3";
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+/// 由 32 字节种子确定的密码学伪随机数生成器
+pub trait Prg: RngCore + CryptoRng + SeedableRng<Seed = [u8; 32]> + Clone {
+    /// 由主种子和用途标签派生的生成器，见 `derive_seed`
+    fn derive(seed: &[u8; 32], label: &[u8]) -> Self {
+        Self::from_seed(derive_seed(seed, label))
+    }
+}
+
+/// 由主种子和用途标签派生子种子（以主种子为密钥的 BLAKE3）
+///
+/// 一个协议执行只需要保存一个主种子，各子协议用不同的标签得到各自独立的种子。
+pub fn derive_seed(seed: &[u8; 32], label: &[u8]) -> [u8; 32] {
+    blake3::Hasher::new_keyed(seed)
+        .update(b"mpc_api/derive_seed")
+        .update(label)
+        .finalize()
+        .into()
+}
+
+/// 每次批量加密的分组数
+const AES_PRG_BATCH: usize = 8;
+
+/// AES-128 计数器模式伪随机数生成器
+///
+/// 种子的前 16 字节为 AES 密钥，后 16 字节为初始计数器（小端序）。
+#[derive(Clone)]
+pub struct AesCtrPrg {
+    cipher: Aes128,
+    counter: u128,
+    buffer: [u8; 16 * AES_PRG_BATCH],
+    position: usize,
+}
+
+impl AesCtrPrg {
+    /// 以 AES 密钥和初始计数器创建生成器
+    pub fn new(key: &[u8; 16], counter: u128) -> Self {
+        Self {
+            cipher: Aes128::new(GenericArray::from_slice(key)),
+            counter,
+            buffer: [0u8; 16 * AES_PRG_BATCH],
+            position: 16 * AES_PRG_BATCH,
+        }
+    }
+
+    fn refill(&mut self) {
+        let mut blocks = [Block::default(); AES_PRG_BATCH];
+        for block in blocks.iter_mut() {
+            block.copy_from_slice(&self.counter.to_le_bytes());
+            self.counter = self.counter.wrapping_add(1);
+        }
+        self.cipher.encrypt_blocks(&mut blocks);
+        for (chunk, block) in self.buffer.chunks_mut(16).zip(&blocks) {
+            chunk.copy_from_slice(block);
+        }
+        self.position = 0;
+    }
+}
+
+impl SeedableRng for AesCtrPrg {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: [u8; 32]) -> Self {
+        let key: [u8; 16] = seed[..16].try_into().expect("16-byte key");
+        let counter = u128::from_le_bytes(seed[16..].try_into().expect("16-byte counter"));
+        Self::new(&key, counter)
+    }
+}
+
+impl RngCore for AesCtrPrg {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut written = 0;
+        while written < dest.len() {
+            if self.position == self.buffer.len() {
+                self.refill();
+            }
+            let count = (dest.len() - written).min(self.buffer.len() - self.position);
+            dest[written..written + count].copy_from_slice(&self.buffer[self.position..self.position + count]);
+            self.position += count;
+            written += count;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> std::result::Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for AesCtrPrg {}
+
+impl Prg for AesCtrPrg {}
+
+impl std::fmt::Debug for AesCtrPrg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AesCtrPrg").finish_non_exhaustive()
+    }
+}
+
+/// BLAKE3 可扩展输出伪随机数生成器
+#[derive(Clone)]
+pub struct Blake3Prg {
+    reader: blake3::OutputReader,
+}
+
+impl SeedableRng for Blake3Prg {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: [u8; 32]) -> Self {
+        let reader = blake3::Hasher::new_keyed(&seed).update(b"mpc_api/prg").finalize_xof();
+        Self { reader }
+    }
+}
+
+impl RngCore for Blake3Prg {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.reader.fill(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> std::result::Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for Blake3Prg {}
+
+impl Prg for Blake3Prg {}
+
+impl std::fmt::Debug for Blake3Prg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Blake3Prg").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,192 @@
+//! # 纠删码 (Erasure Coding)
+//!
+//! 基于有限域 GF(p) 上多项式求值的系统 Reed–Solomon 纠删码。
+//!
+//! 数据被切分为 7 字节的符号（保证小于域模数），每 `k` 个符号组成一个条带，
+//! 视为多项式在 x = 1..k 处的取值；编码时在 x = 1..n 处求值得到 `n` 个分片。
+//! 前 `k` 个分片就是原始数据（系统码），任意 `k` 个分片都可以通过
+//! 拉格朗日插值恢复原始数据。
+
+use crate::secret_sharing::{field_add, field_inv, field_mul, field_sub};
+use crate::{MpcError, Result};
+
+/// 每个符号承载的字节数（7 字节 < 2^56 < 域模数）
+pub const SYMBOL_BYTES: usize = 7;
+
+/// Reed–Solomon 纠删编码器
+#[derive(Debug, Clone)]
+pub struct ReedSolomon {
+    /// 数据分片数量 k
+    data_shards: usize,
+    /// 总分片数量 n
+    total_shards: usize,
+    /// 编码矩阵：encoding[j][i] 为数据点 i 对分片 j 的拉格朗日权重
+    encoding: Vec<Vec<u64>>,
+}
+
+impl ReedSolomon {
+    /// 创建新的编码器
+    ///
+    /// # 参数
+    ///
+    /// * `data_shards` - 恢复数据所需的分片数量 k
+    /// * `total_shards` - 生成的分片总数 n
+    ///
+    /// # 返回值
+    ///
+    /// 参数满足 1 <= k <= n 时返回编码器，否则返回错误
+    pub fn new(data_shards: usize, total_shards: usize) -> Result<Self> {
+        if data_shards == 0 || data_shards > total_shards {
+            return Err(MpcError::ProtocolError(format!(
+                "Invalid erasure code parameters: k = {}, n = {}", data_shards, total_shards
+            )));
+        }
+
+        let data_points: Vec<u64> = (1..=data_shards as u64).collect();
+        let encoding = (1..=total_shards as u64)
+            .map(|x| lagrange_weights(&data_points, x))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            data_shards,
+            total_shards,
+            encoding,
+        })
+    }
+
+    /// 获取数据分片数量 k
+    pub fn data_shards(&self) -> usize {
+        self.data_shards
+    }
+
+    /// 获取分片总数 n
+    pub fn total_shards(&self) -> usize {
+        self.total_shards
+    }
+
+    /// 编码数据
+    ///
+    /// # 参数
+    ///
+    /// * `data` - 原始数据
+    ///
+    /// # 返回值
+    ///
+    /// 返回 `n` 个分片，每个分片是一组域元素
+    pub fn encode(&self, data: &[u8]) -> Vec<Vec<u64>> {
+        let mut symbols = bytes_to_symbols(data);
+        let stripes = symbols.len().div_ceil(self.data_shards).max(1);
+        symbols.resize(stripes * self.data_shards, 0);
+
+        let mut shards = vec![Vec::with_capacity(stripes); self.total_shards];
+        for stripe in symbols.chunks(self.data_shards) {
+            for (shard, weights) in shards.iter_mut().zip(&self.encoding) {
+                shard.push(dot(weights, stripe));
+            }
+        }
+        shards
+    }
+
+    /// 从任意 `k` 个分片恢复数据
+    ///
+    /// # 参数
+    ///
+    /// * `shards` - (分片索引, 分片内容) 列表，索引从 0 开始
+    /// * `original_len` - 原始数据长度（字节）
+    ///
+    /// # 返回值
+    ///
+    /// 成功时返回原始数据；分片不足、索引重复或长度不一致时返回错误
+    pub fn decode(&self, shards: &[(usize, Vec<u64>)], original_len: usize) -> Result<Vec<u8>> {
+        let mut selected: Vec<&(usize, Vec<u64>)> = Vec::with_capacity(self.data_shards);
+        for shard in shards {
+            if shard.0 >= self.total_shards {
+                return Err(MpcError::ProtocolError(format!("Shard index {} out of range", shard.0)));
+            }
+            if selected.iter().any(|s| s.0 == shard.0) {
+                continue;
+            }
+            selected.push(shard);
+            if selected.len() == self.data_shards {
+                break;
+            }
+        }
+        if selected.len() < self.data_shards {
+            return Err(MpcError::InsufficientShares);
+        }
+
+        let stripes = selected[0].1.len();
+        if selected.iter().any(|s| s.1.len() != stripes) {
+            return Err(MpcError::ProtocolError("Shards have inconsistent lengths".to_string()));
+        }
+
+        let points: Vec<u64> = selected.iter().map(|s| s.0 as u64 + 1).collect();
+        let decoding = (1..=self.data_shards as u64)
+            .map(|x| lagrange_weights(&points, x))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut symbols = Vec::with_capacity(stripes * self.data_shards);
+        let mut column = vec![0u64; self.data_shards];
+        for stripe in 0..stripes {
+            for (value, shard) in column.iter_mut().zip(&selected) {
+                *value = shard.1[stripe];
+            }
+            for weights in &decoding {
+                symbols.push(dot(weights, &column));
+            }
+        }
+
+        let mut data = symbols_to_bytes(&symbols);
+        if original_len > data.len() {
+            return Err(MpcError::ProtocolError("Original length exceeds decoded data".to_string()));
+        }
+        data.truncate(original_len);
+        Ok(data)
+    }
+}
+
+/// 将字节切分为 7 字节符号
+fn bytes_to_symbols(data: &[u8]) -> Vec<u64> {
+    data.chunks(SYMBOL_BYTES)
+        .map(|chunk| {
+            let mut bytes = [0u8; 8];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            u64::from_le_bytes(bytes)
+        })
+        .collect()
+}
+
+/// 将符号还原为字节
+fn symbols_to_bytes(symbols: &[u64]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(symbols.len() * SYMBOL_BYTES);
+    for symbol in symbols {
+        data.extend_from_slice(&symbol.to_le_bytes()[..SYMBOL_BYTES]);
+    }
+    data
+}
+
+/// 计算在点 `x` 处求值时各插值点的拉格朗日权重
+fn lagrange_weights(points: &[u64], x: u64) -> Result<Vec<u64>> {
+    let mut weights = Vec::with_capacity(points.len());
+    for (i, &xi) in points.iter().enumerate() {
+        let mut numerator = 1u64;
+        let mut denominator = 1u64;
+        for (j, &xj) in points.iter().enumerate() {
+            if i != j {
+                numerator = field_mul(numerator, field_sub(x, xj));
+                denominator = field_mul(denominator, field_sub(xi, xj));
+            }
+        }
+        let inverse = field_inv(denominator)
+            .ok_or_else(|| MpcError::CryptographicError("Duplicate interpolation point".to_string()))?;
+        weights.push(field_mul(numerator, inverse));
+    }
+    Ok(weights)
+}
+
+/// 域上的内积
+fn dot(weights: &[u64], values: &[u64]) -> u64 {
+    weights.iter()
+        .zip(values)
+        .fold(0u64, |acc, (&w, &v)| field_add(acc, field_mul(w, v)))
+}
@@ -4,11 +4,25 @@
 //! - 基础数论函数（最大公约数、最小公倍数）
 //! - 素数检测算法
 //! - 有限域运算支持
+//! - 常数时间工具（条件选择、条件交换、模幂和模逆）
+//! - 二元扩域 GF(2^128) / GF(2^64) 运算（`gf2k` 子模块）
+//! - 任意 NTT 友好素数上的多项式快速乘法和多点求值（`ntt` 子模块）
+//! - 大整数素性检测和随机素数生成（`bigint` 子模块）
 //! 
 //! 这些函数为密码学协议的数学基础提供支持。
+//! 
+//! ## 常数时间运算
+//! 
+//! 处理私钥、随机数等秘密数据时，执行路径和访存模式不能依赖秘密的取值。
+//! `constant_time_*` 系列函数用掩码代替分支：选择值 `choice` 必须是 0 或 1，
+//! 模幂用蒙哥马利阶梯固定处理 64 个指数位，模逆由费马小定理化为模幂。
 
 // use crate::secret_sharing::FIELD_PRIME; // 未使用的导入
 
+pub mod bigint;
+pub mod gf2k;
+pub mod ntt;
+
 /// 计算两个数的最大公约数 (Greatest Common Divisor)
 /// 
 /// 使用欧几里得算法递归计算两个正整数的最大公约数。
@@ -398,6 +412,90 @@ pub fn verify_field_prime() -> bool {
     final_result
 }
 
+// === 常数时间工具 ===
+
+/// 常数时间选择：`choice` 为 1 时返回 `b`，为 0 时返回 `a`
+#[inline]
+pub fn constant_time_select(a: u64, b: u64, choice: u64) -> u64 {
+    let mask = 0u64.wrapping_sub(choice);
+    a ^ ((a ^ b) & mask)
+}
+
+/// 常数时间条件交换：`choice` 为 1 时交换 `a` 和 `b`
+#[inline]
+pub fn constant_time_swap(a: &mut u64, b: &mut u64, choice: u64) {
+    let t = (*a ^ *b) & 0u64.wrapping_sub(choice);
+    *a ^= t;
+    *b ^= t;
+}
+
+/// 常数时间判断是否为零，是零时返回 1，否则返回 0
+#[inline]
+pub fn constant_time_is_zero(a: u64) -> u64 {
+    ((a | a.wrapping_neg()) >> 63) ^ 1
+}
+
+/// 常数时间判断 a < b，成立时返回 1，否则返回 0
+#[inline]
+pub fn constant_time_less_than(a: u64, b: u64) -> u64 {
+    a.overflowing_sub(b).1 as u64
+}
+
+/// 常数时间模幂：计算 (base^exp) mod m
+/// 
+/// 蒙哥马利阶梯：每个指数位都执行一次乘法和一次平方，运算序列与指数无关，
+/// 适用于私钥等秘密指数。
+/// 
+/// # 参数
+/// * `base` - 底数
+/// * `exp` - 指数，可以是秘密
+/// * `m` - 模数，必须大于 0
+/// 
+/// # 返回值
+/// 返回 (base^exp) mod m 的结果
+/// 
+/// # 示例
+/// ```rust
+/// use mpc_api::utils::constant_time_pow_mod;
+/// 
+/// assert_eq!(constant_time_pow_mod(3, 4, 7), 4); // 81 mod 7
+/// ```
+pub fn constant_time_pow_mod(base: u64, exp: u64, m: u64) -> u64 {
+    let mut r0 = 1 % m;
+    let mut r1 = base % m;
+    for bit in (0..u64::BITS).rev() {
+        let choice = (exp >> bit) & 1;
+        constant_time_swap(&mut r0, &mut r1, choice);
+        r1 = mod_mul(r0, r1, m);
+        r0 = mod_mul(r0, r0, m);
+        constant_time_swap(&mut r0, &mut r1, choice);
+    }
+    r0
+}
+
+/// 常数时间模逆：由费马小定理 a^(p-2) ≡ a^(-1) (mod p) 计算
+/// 
+/// 与扩展欧几里得算法不同，运算序列与 `a` 无关。
+/// 
+/// # 参数
+/// * `a` - 要求逆的元素
+/// * `prime` - 奇素数模数
+/// 
+/// # 返回值
+/// `a` 不是 `prime` 的倍数时返回 Some(逆元)，否则返回 None
+/// 
+/// # 示例
+/// ```rust
+/// use mpc_api::utils::constant_time_mod_inverse;
+/// 
+/// assert_eq!(constant_time_mod_inverse(3, 7), Some(5));
+/// assert_eq!(constant_time_mod_inverse(14, 7), None);
+/// ```
+pub fn constant_time_mod_inverse(a: u64, prime: u64) -> Option<u64> {
+    let inverse = constant_time_pow_mod(a, prime - 2, prime);
+    (inverse != 0).then_some(inverse)
+}
+
 // === 辅助函数 ===
 
 /// 模指数运算：计算 (base^exp) mod m
@@ -0,0 +1,125 @@
+//! # 大整数素数 (Big Integer Primes)
+//!
+//! Paillier 等基于因数分解的方案需要 1024 位以上的素数，超出了 `u64` 版本的
+//! `miller_rabin_test` 的范围。本模块在 `num_bigint::BigUint` 上提供概率素性检测和随机素数生成。
+//!
+//! ## 实现
+//!
+//! 候选数先用 2000 以内的小素数筛选：只计算一次候选数对每个小素数的余数，之后按步长 2
+//! 递增时只更新余数，不做大整数除法。通过筛选的候选数先做一轮以 2 为底的 Miller-Rabin 检测，
+//! 幸存者再做 `rounds` 轮随机底数检测。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::utils::math::bigint::*;
+//! use num_bigint::BigUint;
+//!
+//! let p = generate_prime(256).unwrap();
+//! assert_eq!(p.bits(), 256);
+//! assert!(is_probable_prime(&p, PRIME_TEST_ROUNDS));
+//! assert!(!is_probable_prime(&(&p * 3u32), PRIME_TEST_ROUNDS));
+//! assert!(is_probable_prime(&BigUint::from(65537u32), PRIME_TEST_ROUNDS));
+//! ```
+
+use crate::{MpcError, Result};
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive};
+use std::sync::OnceLock;
+
+/// 默认的 Miller-Rabin 随机底数轮数，合数通过检测的概率不超过 4^-40
+pub const PRIME_TEST_ROUNDS: u32 = 40;
+
+/// 小素数筛选的上界
+const SIEVE_LIMIT: u64 = 2000;
+
+/// 3 到 `SIEVE_LIMIT` 之间的奇素数
+fn small_primes() -> &'static [u64] {
+    static PRIMES: OnceLock<Vec<u64>> = OnceLock::new();
+    PRIMES.get_or_init(|| (3..SIEVE_LIMIT).step_by(2).filter(|&p| super::is_prime(p)).collect())
+}
+
+/// 大整数的 Miller-Rabin 素性检测
+///
+/// # 参数
+/// * `n` - 待检测的整数
+/// * `rounds` - 随机底数的轮数
+///
+/// # 返回值
+/// n 很可能是素数时返回 true，确定是合数时返回 false
+pub fn is_probable_prime(n: &BigUint, rounds: u32) -> bool {
+    if let Some(small) = n.to_u64() {
+        if small < SIEVE_LIMIT * SIEVE_LIMIT {
+            return super::is_prime(small);
+        }
+    }
+    if n.is_even() || small_primes().iter().any(|&p| (n % p).to_u64() == Some(0)) {
+        return false;
+    }
+    miller_rabin_round(n, &BigUint::from(2u32)) && miller_rabin_rounds(n, rounds)
+}
+
+/// 生成恰好 `bits` 位的随机素数
+///
+/// 最高两位固定为 1，两个这样的素数的乘积恰好有 2·bits 位。
+///
+/// # 参数
+/// * `bits` - 素数的位数，至少 16
+///
+/// # 返回值
+/// 返回生成的素数；位数过小时返回错误
+pub fn generate_prime(bits: u64) -> Result<BigUint> {
+    if bits < 16 {
+        return Err(MpcError::CryptographicError(format!("Cannot generate a {}-bit prime", bits)));
+    }
+    let mut rng = rand::thread_rng();
+    let top_bits = BigUint::from(3u32) << (bits - 2);
+    // 在一个窗口内按步长 2 搜索，窗口内没有素数时重新取随机起点
+    let window = 64 * bits;
+    loop {
+        let start = rng.gen_biguint(bits) | &top_bits | BigUint::one();
+        let residues: Vec<u64> = small_primes()
+            .iter()
+            .map(|&p| (&start % p).to_u64().expect("reduced below p"))
+            .collect();
+        for offset in (0..window).step_by(2) {
+            if small_primes().iter().zip(&residues).any(|(&p, &r)| (r + offset) % p == 0) {
+                continue;
+            }
+            let candidate = &start + offset;
+            if candidate.bits() != bits {
+                break;
+            }
+            if miller_rabin_round(&candidate, &BigUint::from(2u32)) && miller_rabin_rounds(&candidate, PRIME_TEST_ROUNDS) {
+                return Ok(candidate);
+            }
+        }
+    }
+}
+
+/// `rounds` 轮随机底数 a ∈ [2, n-2] 的检测
+fn miller_rabin_rounds(n: &BigUint, rounds: u32) -> bool {
+    let mut rng = rand::thread_rng();
+    let upper = n - 1u32;
+    (0..rounds).all(|_| miller_rabin_round(n, &rng.gen_biguint_range(&BigUint::from(2u32), &upper)))
+}
+
+/// 以 a 为底的一轮检测，n 为大于 3 的奇数
+fn miller_rabin_round(n: &BigUint, a: &BigUint) -> bool {
+    let n_minus_one = n - 1u32;
+    let r = n_minus_one.trailing_zeros().expect("n - 1 is non-zero");
+    let d = &n_minus_one >> r;
+
+    let mut x = a.modpow(&d, n);
+    if x.is_one() || x == n_minus_one {
+        return true;
+    }
+    for _ in 1..r {
+        x = &x * &x % n;
+        if x == n_minus_one {
+            return true;
+        }
+    }
+    false
+}
@@ -0,0 +1,147 @@
+//! # 二元扩域运算 (Binary Extension Fields)
+//!
+//! GMAC、混淆电路的半门哈希和 OT 扩展的一致性检查都在 GF(2^128) 上计算，
+//! 本模块提供它们共用的无进位乘法、多项式约简和求逆。
+//!
+//! ## 表示
+//!
+//! 元素用整数的比特表示多项式系数：第 i 位是 x^i 的系数，加法即按位异或。
+//!
+//! - GF(2^128): 模 x^128 + x^7 + x^2 + x + 1，元素为 `u128`
+//! - GF(2^64): 模 x^64 + x^4 + x^3 + x + 1，元素为 `u64`
+//!
+//! ## 实现
+//!
+//! 64 位无进位乘法在支持 PCLMULQDQ 的 x86_64 处理器上使用硬件指令（运行时检测），
+//! 否则使用逐位的可移植实现；128 位乘积由 Karatsuba 拆成三次 64 位乘法。
+//! 所有运算都不含依赖操作数取值的分支，可以处理密钥等秘密数据。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::utils::math::gf2k::*;
+//!
+//! let a = 0x1234_5678_9abc_def0_0fed_cba9_8765_4321u128;
+//! let b = gf128_mul(a, 3);
+//! assert_eq!(b, a ^ gf128_double(a)); // a·(x + 1)
+//! assert_eq!(gf128_mul(a, gf128_inv(a).unwrap()), 1);
+//! assert_eq!(gf128_inv(0), None);
+//! ```
+
+/// GF(2^128) 模多项式去掉 x^128 后的低位部分：x^7 + x^2 + x + 1
+pub const GF128_MODULUS_LOW: u128 = 0x87;
+
+/// GF(2^64) 模多项式去掉 x^64 后的低位部分：x^4 + x^3 + x + 1
+pub const GF64_MODULUS_LOW: u64 = 0x1b;
+
+/// 64 位无进位乘法，返回 128 位乘积
+pub fn clmul64(a: u64, b: u64) -> u128 {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::is_x86_feature_detected!("pclmulqdq") {
+            return unsafe { clmul64_pclmul(a, b) };
+        }
+    }
+    clmul64_portable(a, b)
+}
+
+/// 128 位无进位乘法，返回 256 位乘积的 (低 128 位, 高 128 位)
+pub fn clmul128(a: u128, b: u128) -> (u128, u128) {
+    let (a0, a1) = (a as u64, (a >> 64) as u64);
+    let (b0, b1) = (b as u64, (b >> 64) as u64);
+    let low = clmul64(a0, b0);
+    let high = clmul64(a1, b1);
+    // Karatsuba：(a0 + a1)(b0 + b1) - a0·b0 - a1·b1 = a0·b1 + a1·b0
+    let middle = clmul64(a0 ^ a1, b0 ^ b1) ^ low ^ high;
+    (low ^ (middle << 64), high ^ (middle >> 64))
+}
+
+/// 把 256 位多项式 low + high·x^128 约简到 GF(2^128)
+pub fn reduce_gf128(low: u128, high: u128) -> u128 {
+    // high·x^128 ≡ high·(x^7 + x^2 + x + 1)；移出 128 位的部分不超过 7 位，再折叠一次
+    let overflow = (high >> 127) ^ (high >> 126) ^ (high >> 121);
+    let folded = high ^ overflow;
+    low ^ folded ^ (folded << 1) ^ (folded << 2) ^ (folded << 7)
+}
+
+/// GF(2^128) 加法（按位异或），也是 Free-XOR 中标签的组合方式
+pub fn gf128_add(a: u128, b: u128) -> u128 {
+    a ^ b
+}
+
+/// GF(2^128) 乘法
+pub fn gf128_mul(a: u128, b: u128) -> u128 {
+    let (low, high) = clmul128(a, b);
+    reduce_gf128(low, high)
+}
+
+/// GF(2^128) 中乘以 x
+pub fn gf128_double(a: u128) -> u128 {
+    (a << 1) ^ ((a >> 127) * GF128_MODULUS_LOW)
+}
+
+/// GF(2^128) 乘法逆元 a^(2^128 - 2)，零没有逆元
+pub fn gf128_inv(a: u128) -> Option<u128> {
+    // 逐步得到 a^(2^k - 1)：a^(2^(k+1) - 1) = (a^(2^k - 1))²·a
+    let mut power = a;
+    for _ in 1..127 {
+        power = gf128_mul(gf128_mul(power, power), a);
+    }
+    let inverse = gf128_mul(power, power);
+    (inverse != 0).then_some(inverse)
+}
+
+/// 把 128 位多项式约简到 GF(2^64)
+pub fn reduce_gf64(product: u128) -> u64 {
+    let (low, high) = (product as u64, (product >> 64) as u64);
+    let overflow = (high >> 63) ^ (high >> 61) ^ (high >> 60);
+    let folded = high ^ overflow;
+    low ^ folded ^ (folded << 1) ^ (folded << 3) ^ (folded << 4)
+}
+
+/// GF(2^64) 乘法
+pub fn gf64_mul(a: u64, b: u64) -> u64 {
+    reduce_gf64(clmul64(a, b))
+}
+
+/// GF(2^64) 乘法逆元 a^(2^64 - 2)，零没有逆元
+pub fn gf64_inv(a: u64) -> Option<u64> {
+    let mut power = a;
+    for _ in 1..63 {
+        power = gf64_mul(gf64_mul(power, power), a);
+    }
+    let inverse = gf64_mul(power, power);
+    (inverse != 0).then_some(inverse)
+}
+
+/// 逐位无进位乘法，每一位都执行同样的运算
+fn clmul64_portable(a: u64, b: u64) -> u128 {
+    (0..u64::BITS).fold(0u128, |acc, bit| {
+        let mask = 0u128.wrapping_sub(((b >> bit) & 1) as u128);
+        acc ^ (((a as u128) << bit) & mask)
+    })
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn clmul64_pclmul(a: u64, b: u64) -> u128 {
+    use std::arch::x86_64::{__m128i, _mm_clmulepi64_si128, _mm_set_epi64x};
+
+    let product = _mm_clmulepi64_si128(_mm_set_epi64x(0, a as i64), _mm_set_epi64x(0, b as i64), 0x00);
+    std::mem::transmute::<__m128i, u128>(product)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pclmul_matches_portable() {
+        let samples = [0u64, 1, 0x87, u64::MAX, 0x8000_0000_0000_0001, 0x0123_4567_89ab_cdef];
+        for &a in &samples {
+            for &b in &samples {
+                assert_eq!(clmul64(a, b), clmul64_portable(a, b));
+            }
+        }
+    }
+}
@@ -0,0 +1,438 @@
+//! # 数论变换 (Number-Theoretic Transform)
+//!
+//! 任意 NTT 友好素数上的多项式快速乘法，是库中唯一的 NTT 实现：BFV 的密文模数、
+//! CRT 辅助素数以及 `secret_sharing::ntt` 的 Goldilocks 定义域都使用本模块的变换。
+//!
+//! - `NttPlan`: 预先计算长度 n 的单位根幂，提供正/逆变换、模 x^n - 1 的循环卷积
+//!   和模 x^n + 1 的负循环卷积（格密码的环 Z_q[x]/(x^n + 1)）
+//! - `polynomial_multiply_mod`: 普通多项式乘法，短输入走教科书算法，其余走 NTT
+//! - `multipoint_evaluate`: 子积树 + 余数树的多点求值，用于 Shamir 份额的批量计算
+//! - `ntt_primes`: 查找指定位数、支持给定变换长度的素数，用于构造 RNS 模数链
+//!
+//! ## 实现说明
+//!
+//! 变换是迭代的基 2 Cooley–Tukey 算法，长度 n 的计划要求模数 p 为素数且 2n | p - 1，
+//! 此时存在 2n 次本原单位根 ψ，ω = ψ² 用于变换，ψ 的幂用于负循环卷积的预处理。
+//! 模数为 `FIELD_PRIME` 时乘法使用 Goldilocks 的专用约减，其他模数经 u128 取模。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::utils::math::ntt::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let q = 12289; // 12289 - 1 = 3 · 2^12
+//! let plan = NttPlan::new(q, 4)?;
+//! // x^3 · x = x^4 ≡ -1 (mod x^4 + 1)
+//! assert_eq!(plan.negacyclic_multiply(&[0, 0, 0, 1], &[0, 1])?, vec![q - 1, 0, 0, 0]);
+//!
+//! let product = polynomial_multiply_mod(&[1, 2], &[3, 4], q)?;
+//! assert_eq!(product, vec![3, 10, 8]);
+//! assert_eq!(multipoint_evaluate(&[1, 2, 3], &[0, 1, 2], q)?, vec![1, 6, 17]);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{miller_rabin_test, mod_mul, mod_pow};
+use crate::secret_sharing::{field_mul, FIELD_PRIME};
+use crate::{MpcError, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// 较短的一方不超过该长度时，乘法和除法直接用教科书算法
+pub const SCHOOLBOOK_THRESHOLD: usize = 32;
+
+/// (模数, 长度) → 计划
+type PlanCache = HashMap<(u64, usize), Arc<NttPlan>>;
+
+/// 长度为 n 的 NTT 计划
+#[derive(Debug, Clone)]
+pub struct NttPlan {
+    modulus: u64,
+    log_size: usize,
+    /// ω^0 .. ω^(n/2 - 1)
+    twiddles: Vec<u64>,
+    /// ω^0 .. ω^-(n/2 - 1)
+    inverse_twiddles: Vec<u64>,
+    /// ψ^0 .. ψ^(n - 1)
+    twists: Vec<u64>,
+    /// ψ^0 .. ψ^-(n - 1)，已乘上 n^-1
+    inverse_twists: Vec<u64>,
+    /// n^-1
+    size_inverse: u64,
+}
+
+impl NttPlan {
+    /// 创建模数 `modulus` 上长度为 `size` 的计划
+    ///
+    /// # 参数
+    /// - `modulus`: 素数模数，要求 2·size | modulus - 1
+    /// - `size`: 变换长度，必须是 2 的幂
+    ///
+    /// # 返回值
+    /// 模数不是素数或不含 2·size 次单位根时返回错误
+    pub fn new(modulus: u64, size: usize) -> Result<Self> {
+        if !size.is_power_of_two() {
+            return Err(MpcError::CryptographicError(format!("NTT size {} is not a power of two", size)));
+        }
+        let order = 2 * size as u64;
+        if modulus < 3 || !(modulus - 1).is_multiple_of(order) || !miller_rabin_test(modulus, 32) {
+            return Err(MpcError::CryptographicError(format!(
+                "Modulus {} is not a prime with 2^{} dividing p - 1", modulus, order.trailing_zeros()
+            )));
+        }
+
+        // ψ = g^((p-1)/2n) 的阶整除 2n；ψ^n = -1 说明阶恰好为 2n
+        let psi = (2..modulus)
+            .map(|g| mod_pow(g, (modulus - 1) / order, modulus))
+            .find(|&candidate| mod_pow(candidate, size as u64, modulus) == modulus - 1)
+            .expect("a prime field contains a primitive root");
+        Ok(Self::from_root(modulus, size, psi))
+    }
+
+    /// 用给定的 2n 次本原单位根 ψ 创建计划，变换使用 ω = ψ²
+    ///
+    /// 调用方保证 `modulus` 是素数、`size` 是 2 的幂且 ψ 的阶恰好为 2·size。
+    pub(crate) fn from_root(modulus: u64, size: usize, psi: u64) -> Self {
+        let psi_inverse = inverse(psi, modulus);
+        let size_inverse = inverse(size as u64 % modulus, modulus);
+        let powers = |base: u64, scale: u64, count: usize| {
+            std::iter::successors(Some(scale), move |&power| Some(mul(power, base, modulus)))
+                .take(count)
+                .collect::<Vec<_>>()
+        };
+        Self {
+            modulus,
+            log_size: size.trailing_zeros() as usize,
+            twiddles: powers(mul(psi, psi, modulus), 1, size / 2),
+            inverse_twiddles: powers(mul(psi_inverse, psi_inverse, modulus), 1, size / 2),
+            twists: powers(psi, 1, size),
+            inverse_twists: powers(psi_inverse, size_inverse, size),
+            size_inverse,
+        }
+    }
+
+    /// 进程内共享的计划：同一 (模数, 长度) 只做一次素性检测和单位根预计算
+    pub fn shared(modulus: u64, size: usize) -> Result<Arc<Self>> {
+        static PLANS: OnceLock<Mutex<PlanCache>> = OnceLock::new();
+        let plans = PLANS.get_or_init(Default::default);
+        if let Some(plan) = plans.lock().unwrap_or_else(|e| e.into_inner()).get(&(modulus, size)) {
+            return Ok(Arc::clone(plan));
+        }
+        let plan = Arc::new(Self::new(modulus, size)?);
+        plans.lock().unwrap_or_else(|e| e.into_inner()).insert((modulus, size), Arc::clone(&plan));
+        Ok(plan)
+    }
+
+    /// 模数
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// 变换长度
+    pub fn size(&self) -> usize {
+        1 << self.log_size
+    }
+
+    /// 变换使用的 n 次本原单位根 ω
+    pub fn root(&self) -> u64 {
+        self.twiddles.get(1).copied().unwrap_or(1)
+    }
+
+    /// 系数 → 取值：values[i] 变为多项式在 ω^i 处的取值
+    pub fn forward(&self, values: &mut [u64]) -> Result<()> {
+        self.check_len(values.len())?;
+        transform(values, &self.twiddles, self.modulus);
+        Ok(())
+    }
+
+    /// 取值 → 系数，`forward` 的逆变换
+    pub fn inverse(&self, values: &mut [u64]) -> Result<()> {
+        self.check_len(values.len())?;
+        transform(values, &self.inverse_twiddles, self.modulus);
+        for value in values.iter_mut() {
+            *value = mul(*value, self.size_inverse, self.modulus);
+        }
+        Ok(())
+    }
+
+    /// 模 x^n - 1 的乘积，输入长度不超过 n
+    pub fn cyclic_multiply(&self, a: &[u64], b: &[u64]) -> Result<Vec<u64>> {
+        let mut left = self.pad(a)?;
+        let mut right = self.pad(b)?;
+        self.forward(&mut left)?;
+        self.forward(&mut right)?;
+        for (l, &r) in left.iter_mut().zip(&right) {
+            *l = mul(*l, r, self.modulus);
+        }
+        self.inverse(&mut left)?;
+        Ok(left)
+    }
+
+    /// 模 x^n + 1 的乘积，输入长度不超过 n
+    ///
+    /// a(ψx)·b(ψx) 模 x^n - 1 的循环卷积恰好是 a·b 模 x^n + 1 在 ψx 处的值，
+    /// 因此先乘 ψ^i、做循环卷积、再乘 ψ^-i。
+    pub fn negacyclic_multiply(&self, a: &[u64], b: &[u64]) -> Result<Vec<u64>> {
+        let twist = |poly: &[u64]| -> Result<Vec<u64>> {
+            let mut values = self.pad(poly)?;
+            for (value, &power) in values.iter_mut().zip(&self.twists) {
+                *value = mul(*value, power, self.modulus);
+            }
+            transform(&mut values, &self.twiddles, self.modulus);
+            Ok(values)
+        };
+        let mut product = twist(a)?;
+        for (l, r) in product.iter_mut().zip(twist(b)?) {
+            *l = mul(*l, r, self.modulus);
+        }
+        transform(&mut product, &self.inverse_twiddles, self.modulus);
+        for (value, &power) in product.iter_mut().zip(&self.inverse_twists) {
+            *value = mul(*value, power, self.modulus);
+        }
+        Ok(product)
+    }
+
+    /// 约简系数并补零到变换长度
+    fn pad(&self, poly: &[u64]) -> Result<Vec<u64>> {
+        if poly.len() > self.size() {
+            return Err(MpcError::CryptographicError(format!(
+                "Polynomial with {} coefficients does not fit an NTT of size {}", poly.len(), self.size()
+            )));
+        }
+        let mut values: Vec<u64> = poly.iter().map(|&c| c % self.modulus).collect();
+        values.resize(self.size(), 0);
+        Ok(values)
+    }
+
+    fn check_len(&self, len: usize) -> Result<()> {
+        if len != self.size() {
+            return Err(MpcError::CryptographicError(format!(
+                "NTT expects {} values, got {}", self.size(), len
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 小于 2^bits 的最大的 `count` 个素数 p，满足 2·size | p - 1，按从大到小排列
+///
+/// # 参数
+/// - `bits`: 素数的位数上限，不超过 64
+/// - `size`: 要支持的负循环 NTT 长度
+/// - `count`: 需要的素数个数
+///
+/// # 返回值
+/// 该范围内满足条件的素数不足时返回错误
+pub fn ntt_primes(bits: u32, size: usize, count: usize) -> Result<Vec<u64>> {
+    let order = 2 * size as u64;
+    let limit = if bits >= 64 { u64::MAX } else { (1u64 << bits) - 1 };
+    let primes: Vec<u64> = (1..=limit / order)
+        .rev()
+        .map(|k| k * order + 1)
+        .filter(|&candidate| miller_rabin_test(candidate, 32))
+        .take(count)
+        .collect();
+    if primes.len() < count {
+        return Err(MpcError::CryptographicError(format!(
+            "Only {} primes below 2^{} support NTT size {}", primes.len(), bits, size
+        )));
+    }
+    Ok(primes)
+}
+
+/// 教科书多项式乘法 O(|a|·|b|)
+///
+/// # 返回值
+/// 返回长度为 a.len() + b.len() - 1 的乘积系数；任一输入为空时返回空向量
+pub fn schoolbook_multiply(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut result = vec![0u64; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            result[i + j] = add(result[i + j], mul(x % modulus, y % modulus, modulus), modulus);
+        }
+    }
+    result
+}
+
+/// 模 `modulus` 的多项式乘法（系数从低次到高次）
+///
+/// # 参数
+/// - `a`, `b`: 两个多项式
+/// - `modulus`: 素数模数；输入较长时要求 2^k | modulus - 1，2^k 不小于乘积长度的两倍
+///
+/// # 返回值
+/// 返回长度为 a.len() + b.len() - 1 的乘积系数；任一输入为空时返回空向量
+pub fn polynomial_multiply_mod(a: &[u64], b: &[u64], modulus: u64) -> Result<Vec<u64>> {
+    if a.len().min(b.len()) <= SCHOOLBOOK_THRESHOLD {
+        return Ok(schoolbook_multiply(a, b, modulus));
+    }
+    let len = a.len() + b.len() - 1;
+    let mut product = NttPlan::shared(modulus, len.next_power_of_two())?.cyclic_multiply(a, b)?;
+    product.truncate(len);
+    Ok(product)
+}
+
+/// 多项式在多个点上的取值
+///
+/// 用子积树 ∏(x - x_i) 和自顶向下的余数树，复杂度 O(n log² n)，与多项式次数基本无关；
+/// 点数或系数个数不超过 `SCHOOLBOOK_THRESHOLD` 时逐点使用霍纳法。
+/// 逐点霍纳法的代价是 O(n·d)，次数较低时它更快，由调用方决定是否使用本函数。
+///
+/// # 参数
+/// - `coefficients`: 多项式系数，从常数项开始
+/// - `points`: 求值点
+/// - `modulus`: 素数模数，要求同 `polynomial_multiply_mod`
+///
+/// # 返回值
+/// 按 `points` 的顺序返回取值
+pub fn multipoint_evaluate(coefficients: &[u64], points: &[u64], modulus: u64) -> Result<Vec<u64>> {
+    let coefficients: Vec<u64> = coefficients.iter().map(|&c| c % modulus).collect();
+    if points.len() <= SCHOOLBOOK_THRESHOLD || coefficients.len() <= SCHOOLBOOK_THRESHOLD {
+        return Ok(points.iter().map(|&x| horner(&coefficients, x % modulus, modulus)).collect());
+    }
+
+    // 子积树：第 0 层是叶子 x - x_i，上一层是下一层相邻两项之积
+    let mut tree = vec![points.iter().map(|&x| vec![sub(0, x % modulus, modulus), 1]).collect::<Vec<_>>()];
+    while tree.last().is_some_and(|level| level.len() > 1) {
+        let level = tree.last().expect("tree is non-empty")
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => polynomial_multiply_mod(left, right, modulus),
+                _ => Ok(pair[0].clone()),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        tree.push(level);
+    }
+
+    // 余数树：f mod ∏(x - x_i) 逐层下推，到叶子时余数即 f(x_i)
+    let root = &tree.last().expect("tree is non-empty")[0];
+    let mut remainders = vec![polynomial_remainder(&coefficients, root, modulus)?];
+    for level in tree.iter().rev().skip(1) {
+        remainders = level.chunks(2)
+            .zip(&remainders)
+            .flat_map(|(children, remainder)| {
+                children.iter().map(move |child| polynomial_remainder(remainder, child, modulus))
+            })
+            .collect::<Result<Vec<_>>>()?;
+    }
+    Ok(remainders.into_iter().map(|remainder| remainder.first().copied().unwrap_or(0)).collect())
+}
+
+/// f mod g，g 为首一多项式
+fn polynomial_remainder(f: &[u64], g: &[u64], modulus: u64) -> Result<Vec<u64>> {
+    let divisor_degree = g.len() - 1;
+    if f.len() <= divisor_degree {
+        return Ok(f.to_vec());
+    }
+    let quotient_len = f.len() - divisor_degree;
+
+    let quotient = if quotient_len.min(divisor_degree) <= SCHOOLBOOK_THRESHOLD {
+        let mut remainder = f.to_vec();
+        let mut quotient = vec![0; quotient_len];
+        for i in (0..quotient_len).rev() {
+            let lead = remainder[i + divisor_degree];
+            quotient[i] = lead;
+            for (j, &coefficient) in g.iter().enumerate() {
+                remainder[i + j] = sub(remainder[i + j], mul(lead, coefficient, modulus), modulus);
+            }
+        }
+        quotient
+    } else {
+        // 反转后 rev(q) = rev(f)·rev(g)^-1 mod x^quotient_len
+        let reversed_f: Vec<u64> = f.iter().rev().take(quotient_len).copied().collect();
+        let reversed_g: Vec<u64> = g.iter().rev().copied().collect();
+        let mut quotient = polynomial_multiply_mod(&reversed_f, &series_inverse(&reversed_g, quotient_len, modulus)?, modulus)?;
+        quotient.truncate(quotient_len);
+        quotient.reverse();
+        quotient
+    };
+
+    let product = polynomial_multiply_mod(&quotient, g, modulus)?;
+    Ok(f[..divisor_degree].iter().zip(&product).map(|(&a, &b)| sub(a, b, modulus)).collect())
+}
+
+/// 牛顿迭代求幂级数的逆 g^-1 mod x^len，要求 g(0) 可逆
+fn series_inverse(g: &[u64], len: usize, modulus: u64) -> Result<Vec<u64>> {
+    let mut inverse_series = vec![inverse(g[0], modulus)];
+    while inverse_series.len() < len {
+        // h ← h·(2 - g·h) mod x^(2k)
+        let next_len = (2 * inverse_series.len()).min(len);
+        let mut correction = polynomial_multiply_mod(&g[..next_len.min(g.len())], &inverse_series, modulus)?;
+        correction.resize(next_len, 0);
+        for value in correction.iter_mut() {
+            *value = sub(0, *value, modulus);
+        }
+        correction[0] = add(correction[0], 2, modulus);
+        inverse_series = polynomial_multiply_mod(&inverse_series, &correction, modulus)?;
+        inverse_series.truncate(next_len);
+    }
+    Ok(inverse_series)
+}
+
+/// 原地迭代的基 2 Cooley–Tukey 变换，输入输出均为自然顺序
+fn transform(values: &mut [u64], twiddles: &[u64], modulus: u64) {
+    let n = values.len();
+    if n <= 1 {
+        return;
+    }
+    let log_n = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let stride = n / len;
+        for chunk in values.chunks_exact_mut(len) {
+            let (low, high) = chunk.split_at_mut(len / 2);
+            for (k, (u, v)) in low.iter_mut().zip(high.iter_mut()).enumerate() {
+                let t = mul(*v, twiddles[k * stride], modulus);
+                *v = sub(*u, t, modulus);
+                *u = add(*u, t, modulus);
+            }
+        }
+        len *= 2;
+    }
+}
+
+fn horner(coefficients: &[u64], x: u64, modulus: u64) -> u64 {
+    coefficients.iter().rev().fold(0, |acc, &c| add(mul(acc, x, modulus), c, modulus))
+}
+
+fn add(a: u64, b: u64, modulus: u64) -> u64 {
+    let (sum, overflow) = a.overflowing_add(b);
+    if overflow || sum >= modulus {
+        sum.wrapping_sub(modulus)
+    } else {
+        sum
+    }
+}
+
+fn sub(a: u64, b: u64, modulus: u64) -> u64 {
+    let (difference, borrow) = a.overflowing_sub(b);
+    if borrow {
+        difference.wrapping_add(modulus)
+    } else {
+        difference
+    }
+}
+
+fn mul(a: u64, b: u64, modulus: u64) -> u64 {
+    if modulus == FIELD_PRIME {
+        field_mul(a, b)
+    } else {
+        mod_mul(a, b, modulus)
+    }
+}
+
+/// 素数模数下的逆元，调用方保证 a 非零
+fn inverse(a: u64, modulus: u64) -> u64 {
+    mod_pow(a, modulus - 2, modulus)
+}
@@ -28,6 +28,11 @@
 //! 
 //! 提供自动安全清除的内存缓冲区，确保敏感数据在生命周期结束时被安全清除。
 //! 
+//! ### SecretValue - 释放时清零的秘密值
+//! 
+//! 包装份额、密钥、线标签等以普通值保存的秘密，释放时安全清零；
+//! `Debug` 和默认的序列化都不输出内容，需要传输时用 `expose_secret` 显式序列化。
+//! 
 //! ### MemoryLock - 内存锁定
 //! 
 //! 防止敏感内存页被操作系统交换到磁盘，避免敏感数据的持久化。
@@ -69,6 +74,8 @@
 use std::{
     alloc::{self, Layout},
     ffi::c_void,
+    fmt,
+    ops::{Deref, DerefMut},
     ptr::{self, NonNull},
     slice,
     sync::atomic::{AtomicUsize, Ordering},
@@ -723,6 +730,163 @@ pub fn secure_zero(ptr: *mut u8, len: usize) {
     }
 }
 
+/// 安全清零域元素切片
+/// 
+/// 用于清除以 u64 形式保存的敏感数据，例如轮换后的旧 MAC 密钥分享
+/// 或 OT 扩展的旧基础相关性。
+/// 
+/// # 参数
+/// - `values`: 要清零的切片
+pub fn secure_zero_u64s(values: &mut [u64]) {
+    secure_zero(values.as_mut_ptr() as *mut u8, std::mem::size_of_val(values));
+}
+
+/// 可以安全清零的值
+/// 
+/// 清零使用 volatile 写入，不会被编译器优化掉。
+pub trait Zeroize {
+    /// 把值清零
+    fn zeroize(&mut self);
+}
+
+macro_rules! impl_zeroize_for_integers {
+    ($($ty:ty),*) => {$(
+        impl Zeroize for $ty {
+            fn zeroize(&mut self) {
+                // SAFETY: self 是有效且对齐的可变引用
+                unsafe { ptr::write_volatile(self, 0) };
+            }
+        }
+    )*};
+}
+
+impl_zeroize_for_integers!(u8, u16, u32, u64, u128, usize, i64);
+
+impl Zeroize for bool {
+    fn zeroize(&mut self) {
+        // SAFETY: self 是有效且对齐的可变引用
+        unsafe { ptr::write_volatile(self, false) };
+    }
+}
+
+impl<T: Zeroize, const N: usize> Zeroize for [T; N] {
+    fn zeroize(&mut self) {
+        self.iter_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+impl<T: Zeroize> Zeroize for Vec<T> {
+    fn zeroize(&mut self) {
+        self.iter_mut().for_each(Zeroize::zeroize);
+        self.clear();
+    }
+}
+
+impl<A: Zeroize, B: Zeroize> Zeroize for (A, B) {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+        self.1.zeroize();
+    }
+}
+
+/// 释放时清零的秘密值
+/// 
+/// 通过 `Deref` 使用内部的值。`Debug` 输出 `<redacted>`；序列化默认也只写出 `"<redacted>"`，
+/// 确实需要传输或持久化的字段用 `#[serde(with = "mpc_api::utils::memory::expose_secret")]` 标注。
+/// 
+/// # 示例
+/// ```rust
+/// use mpc_api::utils::memory::SecretValue;
+/// 
+/// let key = SecretValue::new([7u8; 16]);
+/// assert_eq!(key[0], 7);
+/// assert_eq!(format!("{:?}", key), "SecretValue(<redacted>)");
+/// assert_eq!(serde_json::to_string(&key).unwrap(), "\"<redacted>\"");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretValue<T: Zeroize>(T);
+
+impl<T: Zeroize> SecretValue<T> {
+    /// 包装秘密值
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Zeroize> From<T> for SecretValue<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Zeroize> Deref for SecretValue<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Zeroize> DerefMut for SecretValue<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Zeroize> Drop for SecretValue<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize> fmt::Debug for SecretValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretValue(<redacted>)")
+    }
+}
+
+impl<T: Zeroize> Serialize for SecretValue<T> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str("<redacted>")
+    }
+}
+
+impl<'de, T: Zeroize + Deserialize<'de>> Deserialize<'de> for SecretValue<T> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(Self)
+    }
+}
+
+/// 显式序列化秘密值内容，用于 `#[serde(with = "...")]`
+pub mod expose_secret {
+    use super::{SecretValue, Zeroize};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// 写出内部的值
+    pub fn serialize<T, S>(value: &SecretValue<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Zeroize + Serialize,
+        S: Serializer,
+    {
+        value.0.serialize(serializer)
+    }
+
+    /// 读取内部的值
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<SecretValue<T>, D::Error>
+    where
+        T: Zeroize + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(SecretValue)
+    }
+}
+
 /// 安全比较函数
 /// 
 /// 使用恒定时间算法比较两个内存区域，防止时序攻击。
@@ -0,0 +1,474 @@
+//! # 运行指标 (Metrics)
+//!
+//! 进程内的指标注册表，输出 Prometheus 文本格式（exposition format 0.0.4）：
+//!
+//! - **计数器 (`Counter`)**: 只增不减的整数，例如收发字节数、通信轮数、生成的三元组数量
+//! - **仪表 (`Gauge`)**: 可增可减的数值，例如当前连接数
+//! - **直方图 (`Histogram`)**: 按固定桶统计观测值的分布，例如协议耗时
+//! - **采集器**: 在每次导出时调用的回调，用于导出 `ConnectionStats`、`SecurityStats`
+//!   这类以快照形式维护的统计信息
+//!
+//! 各模块通过 `MetricsRegistry::global()` 注册到同一个进程级注册表，
+//! 也可以创建独立的注册表（例如在测试中）。同名同标签的指标重复注册时返回同一个句柄，
+//! 因此调用方不需要自己保存句柄。三元组生成速率等速率类指标由 Prometheus
+//! 对计数器求 `rate()` 得到。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::utils::metrics::*;
+//! use std::time::Duration;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let registry = MetricsRegistry::new();
+//! let sent = registry.counter_with_labels("mpc_bytes_sent_total", "发送的字节数", &[("protocol", "spdz")])?;
+//! sent.inc_by(1024);
+//!
+//! let latency = registry.histogram("mpc_protocol_duration_seconds", "协议耗时", DEFAULT_LATENCY_BUCKETS)?;
+//! latency.observe_duration(Duration::from_millis(30));
+//!
+//! registry.register_collector(|samples| {
+//!     samples.push(MetricSample::gauge("mpc_active_sessions", "活跃会话数", 3.0));
+//! });
+//!
+//! let text = registry.render();
+//! assert!(text.contains("mpc_bytes_sent_total{protocol=\"spdz\"} 1024"));
+//! assert!(text.contains("mpc_protocol_duration_seconds_bucket{le=\"0.05\"} 1"));
+//! assert!(text.contains("mpc_active_sessions 3"));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::Duration,
+};
+use crate::{MpcError, Result};
+
+/// 默认的耗时直方图桶（秒），覆盖 1 毫秒到 1 分钟
+pub const DEFAULT_LATENCY_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+/// Prometheus 文本格式的 Content-Type
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// 指标类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    /// 计数器
+    Counter,
+    /// 仪表
+    Gauge,
+    /// 直方图
+    Histogram,
+}
+
+impl MetricKind {
+    /// Prometheus `# TYPE` 行中的类型名
+    pub fn name(&self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+            MetricKind::Histogram => "histogram",
+        }
+    }
+}
+
+/// 计数器，克隆后共享同一个值
+#[derive(Debug, Clone, Default)]
+pub struct Counter(Arc<AtomicU64>);
+
+impl Counter {
+    /// 加 1
+    pub fn inc(&self) {
+        self.inc_by(1);
+    }
+
+    /// 加上 `value`
+    pub fn inc_by(&self, value: u64) {
+        self.0.fetch_add(value, Ordering::Relaxed);
+    }
+
+    /// 当前值
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// 仪表，克隆后共享同一个值
+#[derive(Debug, Clone, Default)]
+pub struct Gauge(Arc<AtomicU64>);
+
+impl Gauge {
+    /// 设置为 `value`
+    pub fn set(&self, value: f64) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    /// 加上 `delta`，可以为负
+    pub fn add(&self, delta: f64) {
+        add_f64(&self.0, delta);
+    }
+
+    /// 当前值
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+}
+
+/// 直方图，克隆后共享同一组桶
+#[derive(Debug, Clone)]
+pub struct Histogram(Arc<HistogramState>);
+
+#[derive(Debug)]
+struct HistogramState {
+    /// 各桶的上界，严格递增
+    bounds: Vec<f64>,
+    /// 落入各桶的观测次数（不累计），最后一个是 +Inf 桶
+    buckets: Vec<AtomicU64>,
+    /// 观测值之和（f64 位模式）
+    sum: AtomicU64,
+    /// 观测次数
+    count: AtomicU64,
+}
+
+impl Histogram {
+    fn new(bounds: &[f64]) -> Result<Self> {
+        if bounds.iter().any(|bound| !bound.is_finite()) || bounds.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(MpcError::ProtocolError("直方图的桶上界必须是严格递增的有限数".to_string()));
+        }
+        Ok(Histogram(Arc::new(HistogramState {
+            bounds: bounds.to_vec(),
+            buckets: (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum: AtomicU64::new(0f64.to_bits()),
+            count: AtomicU64::new(0),
+        })))
+    }
+
+    /// 记录一个观测值
+    pub fn observe(&self, value: f64) {
+        let state = &self.0;
+        let bucket = state.bounds.partition_point(|&bound| bound < value);
+        state.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        add_f64(&state.sum, value);
+        state.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 以秒为单位记录一段耗时
+    pub fn observe_duration(&self, duration: Duration) {
+        self.observe(duration.as_secs_f64());
+    }
+
+    /// 观测次数
+    pub fn count(&self) -> u64 {
+        self.0.count.load(Ordering::Relaxed)
+    }
+
+    /// 观测值之和
+    pub fn sum(&self) -> f64 {
+        f64::from_bits(self.0.sum.load(Ordering::Relaxed))
+    }
+
+    /// 各桶上界及小于等于该上界的累计观测次数，不含 +Inf 桶
+    pub fn cumulative_buckets(&self) -> Vec<(f64, u64)> {
+        let mut total = 0;
+        self.0.bounds
+            .iter()
+            .zip(&self.0.buckets)
+            .map(|(&bound, bucket)| {
+                total += bucket.load(Ordering::Relaxed);
+                (bound, total)
+            })
+            .collect()
+    }
+}
+
+/// 对以 f64 位模式保存的原子值做加法
+fn add_f64(cell: &AtomicU64, delta: f64) {
+    let _ = cell.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+        Some((f64::from_bits(bits) + delta).to_bits())
+    });
+}
+
+/// 采集器在导出时产生的一个样本
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSample {
+    /// 指标名
+    pub name: String,
+    /// 说明
+    pub help: String,
+    /// 指标类型，只能是计数器或仪表
+    pub kind: MetricKind,
+    /// 标签
+    pub labels: Vec<(String, String)>,
+    /// 数值
+    pub value: f64,
+}
+
+impl MetricSample {
+    /// 计数器样本
+    pub fn counter(name: &str, help: &str, value: f64) -> Self {
+        MetricSample { name: name.to_string(), help: help.to_string(), kind: MetricKind::Counter, labels: Vec::new(), value }
+    }
+
+    /// 仪表样本
+    pub fn gauge(name: &str, help: &str, value: f64) -> Self {
+        MetricSample { name: name.to_string(), help: help.to_string(), kind: MetricKind::Gauge, labels: Vec::new(), value }
+    }
+
+    /// 添加标签
+    pub fn with_label(mut self, key: &str, value: &str) -> Self {
+        self.labels.push((key.to_string(), value.to_string()));
+        self
+    }
+}
+
+type Labels = Vec<(String, String)>;
+type Collector = Arc<dyn Fn(&mut Vec<MetricSample>) + Send + Sync>;
+
+#[derive(Debug, Clone)]
+enum Series {
+    Counter(Counter),
+    Gauge(Gauge),
+    Histogram(Histogram),
+}
+
+#[derive(Debug)]
+struct Family {
+    help: String,
+    kind: MetricKind,
+    series: BTreeMap<Labels, Series>,
+}
+
+#[derive(Default)]
+struct RegistryState {
+    families: Mutex<BTreeMap<String, Family>>,
+    collectors: Mutex<Vec<Collector>>,
+}
+
+/// 指标注册表，克隆后共享同一组指标
+#[derive(Clone, Default)]
+pub struct MetricsRegistry {
+    state: Arc<RegistryState>,
+}
+
+impl std::fmt::Debug for MetricsRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetricsRegistry")
+            .field("families", &self.state.families.lock().map(|families| families.len()).unwrap_or(0))
+            .field("collectors", &self.state.collectors.lock().map(|collectors| collectors.len()).unwrap_or(0))
+            .finish()
+    }
+}
+
+impl MetricsRegistry {
+    /// 创建空的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 进程级注册表，库内各模块默认把指标注册到这里
+    pub fn global() -> &'static MetricsRegistry {
+        static GLOBAL: OnceLock<MetricsRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(MetricsRegistry::new)
+    }
+
+    /// 注册或获取计数器
+    pub fn counter(&self, name: &str, help: &str) -> Result<Counter> {
+        self.counter_with_labels(name, help, &[])
+    }
+
+    /// 注册或获取带标签的计数器
+    pub fn counter_with_labels(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Result<Counter> {
+        match self.series(name, help, MetricKind::Counter, labels, || Ok(Series::Counter(Counter::default())))? {
+            Series::Counter(counter) => Ok(counter),
+            _ => unreachable!("指标类型已在注册时检查"),
+        }
+    }
+
+    /// 注册或获取仪表
+    pub fn gauge(&self, name: &str, help: &str) -> Result<Gauge> {
+        self.gauge_with_labels(name, help, &[])
+    }
+
+    /// 注册或获取带标签的仪表
+    pub fn gauge_with_labels(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Result<Gauge> {
+        match self.series(name, help, MetricKind::Gauge, labels, || Ok(Series::Gauge(Gauge::default())))? {
+            Series::Gauge(gauge) => Ok(gauge),
+            _ => unreachable!("指标类型已在注册时检查"),
+        }
+    }
+
+    /// 注册或获取直方图；已注册时沿用原来的桶
+    pub fn histogram(&self, name: &str, help: &str, buckets: &[f64]) -> Result<Histogram> {
+        self.histogram_with_labels(name, help, buckets, &[])
+    }
+
+    /// 注册或获取带标签的直方图；已注册时沿用原来的桶
+    pub fn histogram_with_labels(
+        &self,
+        name: &str,
+        help: &str,
+        buckets: &[f64],
+        labels: &[(&str, &str)],
+    ) -> Result<Histogram> {
+        match self.series(name, help, MetricKind::Histogram, labels, || Histogram::new(buckets).map(Series::Histogram))? {
+            Series::Histogram(histogram) => Ok(histogram),
+            _ => unreachable!("指标类型已在注册时检查"),
+        }
+    }
+
+    /// 注册采集器，每次导出时调用，把当前值追加到样本列表
+    pub fn register_collector<F>(&self, collector: F)
+    where
+        F: Fn(&mut Vec<MetricSample>) + Send + Sync + 'static,
+    {
+        self.state.collectors.lock().unwrap().push(Arc::new(collector));
+    }
+
+    /// 以 Prometheus 文本格式导出全部指标
+    ///
+    /// 采集器产生的名字不合法的样本，或与已注册指标类型冲突的样本会被跳过。
+    pub fn render(&self) -> String {
+        let mut families: BTreeMap<String, (String, MetricKind, Vec<String>)> = BTreeMap::new();
+
+        for (name, family) in self.state.families.lock().unwrap().iter() {
+            let mut lines = Vec::new();
+            for (labels, series) in &family.series {
+                match series {
+                    Series::Counter(counter) => lines.push(sample_line(name, labels, None, counter.get() as f64)),
+                    Series::Gauge(gauge) => lines.push(sample_line(name, labels, None, gauge.get())),
+                    Series::Histogram(histogram) => {
+                        let bucket = format!("{}_bucket", name);
+                        for (bound, count) in histogram.cumulative_buckets() {
+                            lines.push(sample_line(&bucket, labels, Some(&format_value(bound)), count as f64));
+                        }
+                        lines.push(sample_line(&bucket, labels, Some("+Inf"), histogram.count() as f64));
+                        lines.push(sample_line(&format!("{}_sum", name), labels, None, histogram.sum()));
+                        lines.push(sample_line(&format!("{}_count", name), labels, None, histogram.count() as f64));
+                    }
+                }
+            }
+            families.insert(name.clone(), (family.help.clone(), family.kind, lines));
+        }
+
+        let collectors: Vec<Collector> = self.state.collectors.lock().unwrap().clone();
+        let mut samples = Vec::new();
+        for collector in collectors {
+            collector(&mut samples);
+        }
+        for sample in samples {
+            if validate_name(&sample.name).is_err() || sample.kind == MetricKind::Histogram {
+                continue;
+            }
+            let (_, kind, lines) = families
+                .entry(sample.name.clone())
+                .or_insert_with(|| (sample.help.clone(), sample.kind, Vec::new()));
+            if *kind == sample.kind {
+                lines.push(sample_line(&sample.name, &sample.labels, None, sample.value));
+            }
+        }
+
+        let mut text = String::new();
+        for (name, (help, kind, lines)) in families {
+            let _ = writeln!(text, "# HELP {} {}", name, escape_help(&help));
+            let _ = writeln!(text, "# TYPE {} {}", name, kind.name());
+            for line in lines {
+                text.push_str(&line);
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    fn series(
+        &self,
+        name: &str,
+        help: &str,
+        kind: MetricKind,
+        labels: &[(&str, &str)],
+        create: impl FnOnce() -> Result<Series>,
+    ) -> Result<Series> {
+        validate_name(name)?;
+        for (key, _) in labels {
+            validate_name(key)?;
+            if key.starts_with("__") || (kind == MetricKind::Histogram && *key == "le") {
+                return Err(MpcError::ProtocolError(format!("保留的标签名: {}", key)));
+            }
+        }
+        let mut labels: Labels = labels.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect();
+        labels.sort();
+
+        let mut families = self.state.families.lock().unwrap();
+        if let Some(family) = families.get(name) {
+            if family.kind != kind {
+                return Err(MpcError::ProtocolError(format!(
+                    "指标 {} 已注册为 {}，不能再注册为 {}", name, family.kind.name(), kind.name()
+                )));
+            }
+            if let Some(series) = family.series.get(&labels) {
+                return Ok(series.clone());
+            }
+        }
+
+        // 创建失败（例如无效的桶）时不留下空的指标族
+        let series = create()?;
+        families
+            .entry(name.to_string())
+            .or_insert_with(|| Family { help: help.to_string(), kind, series: BTreeMap::new() })
+            .series
+            .insert(labels, series.clone());
+        Ok(series)
+    }
+}
+
+/// 检查指标名或标签名是否符合 `[a-zA-Z_:][a-zA-Z0-9_:]*`
+fn validate_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':');
+    if valid {
+        Ok(())
+    } else {
+        Err(MpcError::ProtocolError(format!("无效的指标名或标签名: {:?}", name)))
+    }
+}
+
+fn sample_line(name: &str, labels: &[(String, String)], le: Option<&str>, value: f64) -> String {
+    let mut pairs: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{}\"", le));
+    }
+    if pairs.is_empty() {
+        format!("{} {}", name, format_value(value))
+    } else {
+        format!("{}{{{}}} {}", name, pairs.join(","), format_value(value))
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
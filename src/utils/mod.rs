@@ -7,7 +7,13 @@
 //! 
 //! - **数学工具 (math)**: 提供数学运算、有限域操作、多项式计算等功能
 //! - **随机数生成 (random)**: 提供密码学安全的随机数生成功能
-//! - **序列化工具 (serialization)**: 提供数据序列化和反序列化功能
+//! - **序列化工具 (serialization)**: 提供数据序列化和反序列化功能，以及带版本的规范线格式
+//! - **规范哈希 (canonical)**: 提供与平台和字段顺序无关的结构化值编码与哈希
+//! - **纠删码 (erasure)**: 提供有限域上的 Reed–Solomon 纠删编码
+//! - **结构化并发 (concurrency)**: 提供取消令牌和失败时取消兄弟任务的任务组
+//! - **协议转录 (transcript)**: 提供域分隔、带标签消息的转录，挑战值由协议至此的全部消息派生
+//! - **哈希与伪随机数后端 (crypto)**: 提供可替换的哈希函数以及由显式种子确定的 AES/BLAKE3 伪随机数生成器
+//! - **运行指标 (metrics)**: 提供计数器、仪表和直方图的注册表，以 Prometheus 文本格式导出
 //! 
 //! ## 主要功能
 //! 
@@ -46,8 +52,19 @@ pub mod math;
 pub mod random;
 pub mod serialization;
 pub mod memory;
+pub mod erasure;
+pub mod canonical;
+pub mod concurrency;
+pub mod transcript;
+pub mod crypto;
+pub mod metrics;
 
 pub use math::*;
 pub use random::*;
 pub use serialization::*;
-pub use memory::*;
\ No newline at end of file
+pub use memory::*;
+pub use canonical::*;
+pub use concurrency::*;
+pub use transcript::*;
+pub use crypto::*;
+pub use metrics::*;
\ No newline at end of file
@@ -9,9 +9,60 @@
 //! - 错误处理和类型安全保证
 //! 
 //! 这些函数为 MPC 协议中的数据交换提供了基础支持。
+//!
+//! ## 规范线格式 (Canonical Wire Format)
+//!
+//! bincode 和 serde 派生的编码依赖字段声明顺序和库的版本，字段调整后旧数据无法读取。
+//! 协议消息在节点之间传输时使用 `WireSerialize`/`WireDeserialize` 定义的线格式：
+//!
+//! - **消息头**: 1 字节格式版本（`WIRE_VERSION`）和 2 字节小端序类型标签
+//! - **字段**: 每个字段为 1 字节字段标签、4 字节小端序长度和字段内容，按标签严格递增排列
+//! - **字段内容**: 整数按固定宽度小端序编码，`usize` 统一为 64 位；字符串和字节串不带额外前缀；
+//!   序列以 4 字节元素个数开头，非定长元素各带 4 字节长度前缀；映射的条目按键的编码排序
+//!
+//! 同一个值只有一种编码。新版本增加字段时使用新的标签，旧版本读取时跳过不认识的标签；
+//! 旧数据缺少的字段由 `WireReader::field_or` 给出默认值。删除字段或改变字段含义时
+//! 必须提升格式版本，读取方拒绝不支持的版本。
+//!
+//! | 类型 | 类型标签 |
+//! |------|----------|
+//! | `Share` | `0x0001` |
+//! | `AdditiveShare` | `0x0002` |
+//! | `SPDZShare` | `0x0003` |
+//! | `BeaverTriple` | `0x0004` |
+//! | `MessageHeader` | `0x0010` |
+//! | `MessageCommitment` | `0x0011` |
+//! | `NetworkMessage` | `0x0020` |
+//! | `BroadcastMessage` | `0x0021` |
+//! | `GarbledGate` | `0x0030` |
+//! | `GarbledCircuit` | `0x0031` |
+//!
+//! ```rust
+//! use mpc_api::secret_sharing::Share;
+//! use mpc_api::utils::serialization::{WireDeserialize, WireSerialize};
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let share = Share::new(1, 42);
+//! let bytes = share.to_wire();
+//! // 版本 1，类型 0x0001，字段 1 (x) 和字段 2 (y)
+//! assert_eq!(&bytes[..3], &[1, 0x01, 0x00]);
+//! assert_eq!(bytes.len(), 3 + 2 * (1 + 4 + 8));
+//! assert_eq!(Share::from_wire(&bytes)?, share);
+//! # Ok(())
+//! # }
+//! ```
 
 use serde::{Serialize, Deserialize};
-use crate::Result;
+use crate::beaver_triples::BeaverTriple;
+use crate::commitment::MessageCommitment;
+use crate::elliptic_curve::Secp256k1Point;
+use crate::protocols::clock::MessageHeader;
+use crate::protocols::session::SessionId;
+use crate::secret_sharing::{AdditiveShare, Share};
+use crate::spdz::SPDZShare;
+use crate::{MpcError, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// 将数据序列化为字节序列
 /// 
@@ -59,4 +110,753 @@ pub fn serialize_to_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>> {
 pub fn deserialize_from_bytes<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Result<T> {
     bincode::deserialize(bytes)
         .map_err(|e| crate::MpcError::SerializationError(e.to_string()))
-}
\ No newline at end of file
+}
+
+/// 当前的线格式版本
+pub const WIRE_VERSION: u8 = 1;
+
+/// 消息头长度：版本和类型标签
+const WIRE_HEADER_LEN: usize = 3;
+
+fn wire_error(message: String) -> MpcError {
+    MpcError::SerializationError(message)
+}
+
+fn put_len(out: &mut Vec<u8>, len: usize) {
+    let len = u32::try_from(len).expect("wire field exceeds 4 GiB");
+    out.extend_from_slice(&len.to_le_bytes());
+}
+
+/// 从 `bytes` 的 `offset` 处读取 4 字节长度前缀和随后的内容
+fn take_prefixed<'a>(bytes: &'a [u8], offset: &mut usize) -> Result<&'a [u8]> {
+    let len = take_fixed(bytes, offset, 4)?;
+    let len = u32::from_le_bytes(len.try_into().expect("length prefix is 4 bytes")) as usize;
+    take_fixed(bytes, offset, len)
+}
+
+fn take_fixed<'a>(bytes: &'a [u8], offset: &mut usize, len: usize) -> Result<&'a [u8]> {
+    let end = offset.checked_add(len).filter(|&end| end <= bytes.len())
+        .ok_or_else(|| wire_error(format!("Wire data truncated at byte {}", offset)))?;
+    let taken = &bytes[*offset..end];
+    *offset = end;
+    Ok(taken)
+}
+
+/// 可以作为线格式字段内容的值
+pub trait WireValue: Sized {
+    /// 定长编码的字节数；变长编码为 `None`
+    const FIXED_LEN: Option<usize> = None;
+
+    /// 追加值的编码
+    fn encode_value(&self, out: &mut Vec<u8>);
+
+    /// 从恰好包含一个值的字节中解码
+    fn decode_value(bytes: &[u8]) -> Result<Self>;
+}
+
+fn fixed<const N: usize>(bytes: &[u8], name: &str) -> Result<[u8; N]> {
+    bytes.try_into().map_err(|_| wire_error(format!("Expected {} bytes for {}, found {}", N, name, bytes.len())))
+}
+
+macro_rules! wire_integer {
+    ($($ty:ty),*) => {$(
+        impl WireValue for $ty {
+            const FIXED_LEN: Option<usize> = Some(std::mem::size_of::<$ty>());
+
+            fn encode_value(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn decode_value(bytes: &[u8]) -> Result<Self> {
+                Ok(<$ty>::from_le_bytes(fixed(bytes, stringify!($ty))?))
+            }
+        }
+    )*};
+}
+
+wire_integer!(u16, u32, u64);
+
+impl WireValue for usize {
+    const FIXED_LEN: Option<usize> = Some(8);
+
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        (*self as u64).encode_value(out);
+    }
+
+    fn decode_value(bytes: &[u8]) -> Result<Self> {
+        usize::try_from(u64::decode_value(bytes)?).map_err(|e| wire_error(e.to_string()))
+    }
+}
+
+impl WireValue for bool {
+    const FIXED_LEN: Option<usize> = Some(1);
+
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        out.push(u8::from(*self));
+    }
+
+    fn decode_value(bytes: &[u8]) -> Result<Self> {
+        match fixed::<1>(bytes, "bool")? {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            [other] => Err(wire_error(format!("Invalid bool byte {}", other))),
+        }
+    }
+}
+
+impl<const N: usize> WireValue for [u8; N] {
+    const FIXED_LEN: Option<usize> = Some(N);
+
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    fn decode_value(bytes: &[u8]) -> Result<Self> {
+        fixed(bytes, "byte array")
+    }
+}
+
+impl WireValue for Vec<u8> {
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    fn decode_value(bytes: &[u8]) -> Result<Self> {
+        Ok(bytes.to_vec())
+    }
+}
+
+impl WireValue for String {
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode_value(bytes: &[u8]) -> Result<Self> {
+        String::from_utf8(bytes.to_vec()).map_err(|e| wire_error(e.to_string()))
+    }
+}
+
+/// 时间编码为 UNIX 纪元以来的秒数（8 字节）和纳秒数（4 字节）
+impl WireValue for SystemTime {
+    const FIXED_LEN: Option<usize> = Some(12);
+
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        let since_epoch = self.duration_since(UNIX_EPOCH).unwrap_or_default();
+        since_epoch.as_secs().encode_value(out);
+        since_epoch.subsec_nanos().encode_value(out);
+    }
+
+    fn decode_value(bytes: &[u8]) -> Result<Self> {
+        let bytes: [u8; 12] = fixed(bytes, "timestamp")?;
+        let secs = u64::decode_value(&bytes[..8])?;
+        let nanos = u32::decode_value(&bytes[8..])?;
+        if nanos >= 1_000_000_000 {
+            return Err(wire_error(format!("Invalid timestamp nanoseconds {}", nanos)));
+        }
+        UNIX_EPOCH.checked_add(Duration::new(secs, nanos))
+            .ok_or_else(|| wire_error("Timestamp out of range".to_string()))
+    }
+}
+
+impl WireValue for SessionId {
+    const FIXED_LEN: Option<usize> = Some(32);
+
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        self.0.encode_value(out);
+    }
+
+    fn decode_value(bytes: &[u8]) -> Result<Self> {
+        Ok(SessionId(fixed(bytes, "session ID")?))
+    }
+}
+
+/// 序列中的一个元素：定长元素直接拼接，变长元素带长度前缀
+fn encode_element<T: WireValue>(value: &T, out: &mut Vec<u8>) {
+    if T::FIXED_LEN.is_some() {
+        value.encode_value(out);
+    } else {
+        let mut element = Vec::new();
+        value.encode_value(&mut element);
+        put_len(out, element.len());
+        out.extend_from_slice(&element);
+    }
+}
+
+fn decode_element<T: WireValue>(bytes: &[u8], offset: &mut usize) -> Result<T> {
+    match T::FIXED_LEN {
+        Some(len) => T::decode_value(take_fixed(bytes, offset, len)?),
+        None => T::decode_value(take_prefixed(bytes, offset)?),
+    }
+}
+
+/// 读取元素个数，并按最短元素长度检查个数是否可信，避免恶意的个数触发超大分配
+fn decode_count(bytes: &[u8], offset: &mut usize, min_element_len: usize) -> Result<usize> {
+    let count = u32::decode_value(take_fixed(bytes, offset, 4)?)? as usize;
+    if count.saturating_mul(min_element_len.max(1)) > bytes.len() - *offset {
+        return Err(wire_error(format!("Sequence of {} elements exceeds the available data", count)));
+    }
+    Ok(count)
+}
+
+fn check_consumed(bytes: &[u8], offset: usize) -> Result<()> {
+    if offset != bytes.len() {
+        return Err(wire_error(format!("{} trailing bytes after wire value", bytes.len() - offset)));
+    }
+    Ok(())
+}
+
+impl<T: WireValue> WireValue for Vec<T> {
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        put_len(out, self.len());
+        for value in self {
+            encode_element(value, out);
+        }
+    }
+
+    fn decode_value(bytes: &[u8]) -> Result<Self> {
+        let mut offset = 0;
+        let count = decode_count(bytes, &mut offset, T::FIXED_LEN.unwrap_or(4))?;
+        let values = (0..count).map(|_| decode_element(bytes, &mut offset)).collect::<Result<_>>()?;
+        check_consumed(bytes, offset)?;
+        Ok(values)
+    }
+}
+
+impl<A: WireValue, B: WireValue> WireValue for (A, B) {
+    const FIXED_LEN: Option<usize> = match (A::FIXED_LEN, B::FIXED_LEN) {
+        (Some(a), Some(b)) => Some(a + b),
+        _ => None,
+    };
+
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        encode_element(&self.0, out);
+        encode_element(&self.1, out);
+    }
+
+    fn decode_value(bytes: &[u8]) -> Result<Self> {
+        let mut offset = 0;
+        let pair = (decode_element(bytes, &mut offset)?, decode_element(bytes, &mut offset)?);
+        check_consumed(bytes, offset)?;
+        Ok(pair)
+    }
+}
+
+/// 按键的编码排序后编码映射，解码时要求键严格递增
+fn encode_entries<'a, K: WireValue + 'a, V: WireValue + 'a>(
+    entries: impl ExactSizeIterator<Item = (&'a K, &'a V)>,
+    out: &mut Vec<u8>,
+) {
+    put_len(out, entries.len());
+    let mut encoded: Vec<(Vec<u8>, &V)> = entries
+        .map(|(key, value)| {
+            let mut key_bytes = Vec::new();
+            encode_element(key, &mut key_bytes);
+            (key_bytes, value)
+        })
+        .collect();
+    encoded.sort_by(|a, b| a.0.cmp(&b.0));
+    for (key, value) in encoded {
+        out.extend_from_slice(&key);
+        encode_element(value, out);
+    }
+}
+
+fn decode_entries<K: WireValue, V: WireValue>(bytes: &[u8]) -> Result<Vec<(K, V)>> {
+    let mut offset = 0;
+    let min_len = K::FIXED_LEN.unwrap_or(4) + V::FIXED_LEN.unwrap_or(4);
+    let count = decode_count(bytes, &mut offset, min_len)?;
+    let mut entries = Vec::with_capacity(count);
+    let mut previous_key: Option<&[u8]> = None;
+    for _ in 0..count {
+        let start = offset;
+        let key = decode_element(bytes, &mut offset)?;
+        let key_bytes = &bytes[start..offset];
+        if previous_key.is_some_and(|previous| previous >= key_bytes) {
+            return Err(wire_error("Map keys are not in canonical order".to_string()));
+        }
+        previous_key = Some(key_bytes);
+        entries.push((key, decode_element(bytes, &mut offset)?));
+    }
+    check_consumed(bytes, offset)?;
+    Ok(entries)
+}
+
+impl<K: WireValue + Eq + std::hash::Hash, V: WireValue> WireValue for HashMap<K, V> {
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        encode_entries(self.iter(), out);
+    }
+
+    fn decode_value(bytes: &[u8]) -> Result<Self> {
+        Ok(decode_entries(bytes)?.into_iter().collect())
+    }
+}
+
+impl<K: WireValue + Ord, V: WireValue> WireValue for BTreeMap<K, V> {
+    fn encode_value(&self, out: &mut Vec<u8>) {
+        encode_entries(self.iter(), out);
+    }
+
+    fn decode_value(bytes: &[u8]) -> Result<Self> {
+        Ok(decode_entries(bytes)?.into_iter().collect())
+    }
+}
+
+/// 字段写入器
+///
+/// 字段必须按标签严格递增的顺序写入，否则视为编程错误并 panic。
+#[derive(Debug, Default)]
+pub struct WireWriter {
+    out: Vec<u8>,
+    last_tag: Option<u8>,
+}
+
+impl WireWriter {
+    /// 创建空的字段写入器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn raw(&mut self, tag: u8, bytes: &[u8]) -> &mut Self {
+        assert!(self.last_tag.is_none_or(|last| last < tag), "wire field tags must be strictly increasing");
+        self.last_tag = Some(tag);
+        self.out.push(tag);
+        put_len(&mut self.out, bytes.len());
+        self.out.extend_from_slice(bytes);
+        self
+    }
+
+    /// 写入字段
+    pub fn field<V: WireValue>(&mut self, tag: u8, value: &V) -> &mut Self {
+        let mut bytes = Vec::new();
+        value.encode_value(&mut bytes);
+        self.raw(tag, &bytes)
+    }
+
+    /// 写入可选字段，`None` 时不写入
+    pub fn optional<V: WireValue>(&mut self, tag: u8, value: Option<&V>) -> &mut Self {
+        match value {
+            Some(value) => self.field(tag, value),
+            None => self,
+        }
+    }
+
+    /// 写入嵌套消息（只写字段，不带消息头）
+    pub fn message<M: WireSerialize>(&mut self, tag: u8, value: &M) -> &mut Self {
+        let bytes = value.wire_fields();
+        self.raw(tag, &bytes)
+    }
+
+    /// 写入嵌套消息序列
+    pub fn messages<M: WireSerialize>(&mut self, tag: u8, values: &[M]) -> &mut Self {
+        let mut bytes = Vec::new();
+        put_len(&mut bytes, values.len());
+        for value in values {
+            let fields = value.wire_fields();
+            put_len(&mut bytes, fields.len());
+            bytes.extend_from_slice(&fields);
+        }
+        self.raw(tag, &bytes)
+    }
+
+    /// 已写入的字段
+    pub fn finish(self) -> Vec<u8> {
+        self.out
+    }
+}
+
+/// 字段读取器
+///
+/// 解析时检查字段标签严格递增、长度不越界；不认识的标签被忽略。
+#[derive(Debug)]
+pub struct WireReader<'a> {
+    version: u8,
+    fields: BTreeMap<u8, &'a [u8]>,
+}
+
+impl<'a> WireReader<'a> {
+    /// 解析字段
+    ///
+    /// # 参数
+    /// * `version` - 数据的格式版本，供按版本解码的类型使用
+    /// * `bytes` - 字段部分的字节
+    pub fn parse(version: u8, bytes: &'a [u8]) -> Result<Self> {
+        let mut fields = BTreeMap::new();
+        let mut offset = 0;
+        let mut last_tag: Option<u8> = None;
+        while offset < bytes.len() {
+            let tag = bytes[offset];
+            offset += 1;
+            if last_tag.is_some_and(|last| last >= tag) {
+                return Err(wire_error(format!("Wire field {} is out of order", tag)));
+            }
+            last_tag = Some(tag);
+            fields.insert(tag, take_prefixed(bytes, &mut offset)?);
+        }
+        Ok(WireReader { version, fields })
+    }
+
+    /// 数据的格式版本
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn raw(&self, tag: u8) -> Result<&'a [u8]> {
+        self.fields.get(&tag).copied()
+            .ok_or_else(|| wire_error(format!("Missing wire field {}", tag)))
+    }
+
+    /// 读取必需字段
+    pub fn field<V: WireValue>(&self, tag: u8) -> Result<V> {
+        V::decode_value(self.raw(tag)?)
+    }
+
+    /// 读取可选字段
+    pub fn optional<V: WireValue>(&self, tag: u8) -> Result<Option<V>> {
+        self.fields.get(&tag).map(|bytes| V::decode_value(bytes)).transpose()
+    }
+
+    /// 读取后来增加的字段，旧数据中没有该字段时返回 `default`
+    pub fn field_or<V: WireValue>(&self, tag: u8, default: V) -> Result<V> {
+        Ok(self.optional(tag)?.unwrap_or(default))
+    }
+
+    /// 读取嵌套消息
+    pub fn message<M: WireDeserialize>(&self, tag: u8) -> Result<M> {
+        M::read_fields(&WireReader::parse(self.version, self.raw(tag)?)?)
+    }
+
+    /// 读取嵌套消息序列
+    pub fn messages<M: WireDeserialize>(&self, tag: u8) -> Result<Vec<M>> {
+        let bytes = self.raw(tag)?;
+        let mut offset = 0;
+        let count = decode_count(bytes, &mut offset, 4)?;
+        let values = (0..count)
+            .map(|_| M::read_fields(&WireReader::parse(self.version, take_prefixed(bytes, &mut offset)?)?))
+            .collect::<Result<_>>()?;
+        check_consumed(bytes, offset)?;
+        Ok(values)
+    }
+}
+
+/// 可以按线格式编码的协议消息
+pub trait WireSerialize {
+    /// 类型标签，同一个库中各类型互不相同
+    const WIRE_TYPE: u16;
+
+    /// 写入各字段
+    fn write_fields(&self, writer: &mut WireWriter);
+
+    /// 只包含字段、不带消息头的编码，用于嵌套
+    fn wire_fields(&self) -> Vec<u8> {
+        let mut writer = WireWriter::new();
+        self.write_fields(&mut writer);
+        writer.finish()
+    }
+
+    /// 带版本和类型标签的完整编码
+    fn to_wire(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(WIRE_HEADER_LEN);
+        out.push(WIRE_VERSION);
+        out.extend_from_slice(&Self::WIRE_TYPE.to_le_bytes());
+        out.extend_from_slice(&self.wire_fields());
+        out
+    }
+}
+
+/// 可以从线格式解码的协议消息
+pub trait WireDeserialize: WireSerialize + Sized {
+    /// 从字段读取器构造值
+    fn read_fields(reader: &WireReader<'_>) -> Result<Self>;
+
+    /// 解码带消息头的完整编码
+    ///
+    /// 版本不受支持或类型标签不符时返回序列化错误。
+    fn from_wire(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < WIRE_HEADER_LEN {
+            return Err(wire_error("Wire data is shorter than the header".to_string()));
+        }
+        let version = bytes[0];
+        if version == 0 || version > WIRE_VERSION {
+            return Err(wire_error(format!("Unsupported wire format version {}", version)));
+        }
+        let wire_type = u16::from_le_bytes([bytes[1], bytes[2]]);
+        if wire_type != Self::WIRE_TYPE {
+            return Err(wire_error(format!(
+                "Expected wire type {:#06x}, found {:#06x}", Self::WIRE_TYPE, wire_type
+            )));
+        }
+        Self::read_fields(&WireReader::parse(version, &bytes[WIRE_HEADER_LEN..])?)
+    }
+}
+
+// ============================================================================
+// 协议消息的线格式
+// ============================================================================
+
+impl WireSerialize for Share {
+    const WIRE_TYPE: u16 = 0x0001;
+
+    fn write_fields(&self, writer: &mut WireWriter) {
+        writer.field(1, &self.x).field(2, &self.y);
+    }
+}
+
+impl WireDeserialize for Share {
+    fn read_fields(reader: &WireReader<'_>) -> Result<Self> {
+        Ok(Share { x: reader.field(1)?, y: reader.field(2)? })
+    }
+}
+
+impl WireSerialize for AdditiveShare {
+    const WIRE_TYPE: u16 = 0x0002;
+
+    fn write_fields(&self, writer: &mut WireWriter) {
+        writer.field(1, &self.party_id).field(2, &self.value);
+    }
+}
+
+impl WireDeserialize for AdditiveShare {
+    fn read_fields(reader: &WireReader<'_>) -> Result<Self> {
+        Ok(AdditiveShare { party_id: reader.field(1)?, value: reader.field(2)? })
+    }
+}
+
+impl WireSerialize for SPDZShare {
+    const WIRE_TYPE: u16 = 0x0003;
+
+    fn write_fields(&self, writer: &mut WireWriter) {
+        writer.field(1, &self.value)
+            .field(2, &self.mac)
+            .field(3, &self.party_id)
+            .field(4, &self.share_id);
+    }
+}
+
+impl WireDeserialize for SPDZShare {
+    fn read_fields(reader: &WireReader<'_>) -> Result<Self> {
+        Ok(SPDZShare {
+            value: reader.field(1)?,
+            mac: reader.field(2)?,
+            party_id: reader.field(3)?,
+            share_id: reader.field(4)?,
+        })
+    }
+}
+
+impl WireSerialize for BeaverTriple {
+    const WIRE_TYPE: u16 = 0x0004;
+
+    fn write_fields(&self, writer: &mut WireWriter) {
+        writer.message(1, &self.a)
+            .message(2, &self.b)
+            .message(3, &self.c)
+            .field(4, &self.id);
+    }
+}
+
+impl WireDeserialize for BeaverTriple {
+    fn read_fields(reader: &WireReader<'_>) -> Result<Self> {
+        Ok(BeaverTriple {
+            a: reader.message(1)?,
+            b: reader.message(2)?,
+            c: reader.message(3)?,
+            id: reader.field(4)?,
+        })
+    }
+}
+
+impl WireSerialize for MessageHeader {
+    const WIRE_TYPE: u16 = 0x0010;
+
+    fn write_fields(&self, writer: &mut WireWriter) {
+        writer.field(1, &self.session)
+            .field(2, &self.round)
+            .field(3, &self.sent_at);
+    }
+}
+
+impl WireDeserialize for MessageHeader {
+    fn read_fields(reader: &WireReader<'_>) -> Result<Self> {
+        Ok(MessageHeader {
+            session: reader.field(1)?,
+            round: reader.field(2)?,
+            sent_at: reader.field(3)?,
+        })
+    }
+}
+
+/// 哈希承诺写入字段 1，Pedersen 承诺的压缩 SEC 1 编码写入字段 2
+impl WireSerialize for MessageCommitment {
+    const WIRE_TYPE: u16 = 0x0011;
+
+    fn write_fields(&self, writer: &mut WireWriter) {
+        match self {
+            MessageCommitment::Hash(digest) => {
+                writer.field(1, digest);
+            }
+            MessageCommitment::Pedersen(point) => {
+                writer.field(2, &point.to_sec1(true));
+            }
+        }
+    }
+}
+
+impl WireDeserialize for MessageCommitment {
+    fn read_fields(reader: &WireReader<'_>) -> Result<Self> {
+        match reader.optional(1)? {
+            Some(digest) => Ok(MessageCommitment::Hash(digest)),
+            None => {
+                let encoded: Vec<u8> = reader.field(2)?;
+                Ok(MessageCommitment::Pedersen(Secp256k1Point::from_sec1(&encoded)?))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+mod network_messages {
+    use super::*;
+    use crate::network::protocol::{BroadcastMessage, BroadcastPhase, NetworkMessage};
+
+    impl WireSerialize for NetworkMessage {
+        const WIRE_TYPE: u16 = 0x0020;
+
+        fn write_fields(&self, writer: &mut WireWriter) {
+            writer.field(1, &self.id)
+                .field(2, &self.message_type)
+                .field(3, &self.version)
+                .field(4, &self.timestamp)
+                .optional(5, self.sender_id.as_ref())
+                .optional(6, self.receiver_id.as_ref())
+                .field(7, &self.payload)
+                .field(8, &self.headers)
+                .optional(9, self.signature.as_ref());
+        }
+    }
+
+    impl WireDeserialize for NetworkMessage {
+        fn read_fields(reader: &WireReader<'_>) -> Result<Self> {
+            Ok(NetworkMessage {
+                id: reader.field(1)?,
+                message_type: reader.field(2)?,
+                version: reader.field(3)?,
+                timestamp: reader.field(4)?,
+                sender_id: reader.optional(5)?,
+                receiver_id: reader.optional(6)?,
+                payload: reader.field(7)?,
+                headers: reader.field(8)?,
+                signature: reader.optional(9)?,
+            })
+        }
+    }
+
+    impl WireValue for BroadcastPhase {
+        const FIXED_LEN: Option<usize> = Some(1);
+
+        fn encode_value(&self, out: &mut Vec<u8>) {
+            out.push(match self {
+                BroadcastPhase::Send => 0,
+                BroadcastPhase::Echo => 1,
+                BroadcastPhase::Ready => 2,
+            });
+        }
+
+        fn decode_value(bytes: &[u8]) -> Result<Self> {
+            match fixed::<1>(bytes, "broadcast phase")? {
+                [0] => Ok(BroadcastPhase::Send),
+                [1] => Ok(BroadcastPhase::Echo),
+                [2] => Ok(BroadcastPhase::Ready),
+                [other] => Err(wire_error(format!("Unknown broadcast phase {}", other))),
+            }
+        }
+    }
+
+    impl WireSerialize for BroadcastMessage {
+        const WIRE_TYPE: u16 = 0x0021;
+
+        fn write_fields(&self, writer: &mut WireWriter) {
+            writer.field(1, &self.origin)
+                .field(2, &self.tag)
+                .field(3, &self.phase)
+                .field(4, &self.value);
+        }
+    }
+
+    impl WireDeserialize for BroadcastMessage {
+        fn read_fields(reader: &WireReader<'_>) -> Result<Self> {
+            Ok(BroadcastMessage {
+                origin: reader.field(1)?,
+                tag: reader.field(2)?,
+                phase: reader.field(3)?,
+                value: reader.field(4)?,
+            })
+        }
+    }
+}
+
+#[cfg(feature = "garbled-circuits")]
+mod garbled_messages {
+    use super::*;
+    use crate::garbled_circuits::table_file::{gate_type_code, gate_type_from_code};
+    use crate::garbled_circuits::{GarbledCircuit, GarbledGate, GateType};
+
+    /// 门类型编码为 1 字节，与混淆表文件的编码相同
+    impl WireValue for GateType {
+        const FIXED_LEN: Option<usize> = Some(1);
+
+        fn encode_value(&self, out: &mut Vec<u8>) {
+            out.push(gate_type_code(self));
+        }
+
+        fn decode_value(bytes: &[u8]) -> Result<Self> {
+            let [code] = fixed::<1>(bytes, "gate type")?;
+            gate_type_from_code(code)
+        }
+    }
+
+    impl WireSerialize for GarbledGate {
+        const WIRE_TYPE: u16 = 0x0030;
+
+        fn write_fields(&self, writer: &mut WireWriter) {
+            writer.field(1, &self.id)
+                .field(2, &self.gate_type)
+                .field(3, &self.input_wires)
+                .field(4, &self.output_wire)
+                .optional(5, self.garbled_table.as_ref());
+        }
+    }
+
+    impl WireDeserialize for GarbledGate {
+        fn read_fields(reader: &WireReader<'_>) -> Result<Self> {
+            Ok(GarbledGate {
+                id: reader.field(1)?,
+                gate_type: reader.field(2)?,
+                input_wires: reader.field(3)?,
+                output_wire: reader.field(4)?,
+                garbled_table: reader.optional(5)?,
+            })
+        }
+    }
+
+    impl WireSerialize for GarbledCircuit {
+        const WIRE_TYPE: u16 = 0x0031;
+
+        fn write_fields(&self, writer: &mut WireWriter) {
+            writer.messages(1, &self.gates)
+                .field(2, &self.input_wires)
+                .field(3, &self.output_wires)
+                .field(4, &self.wire_labels);
+        }
+    }
+
+    impl WireDeserialize for GarbledCircuit {
+        fn read_fields(reader: &WireReader<'_>) -> Result<Self> {
+            Ok(GarbledCircuit {
+                gates: reader.messages(1)?,
+                input_wires: reader.field(2)?,
+                output_wires: reader.field(3)?,
+                wire_labels: reader.field(4)?,
+            })
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! # 协议转录 (Protocol Transcripts)
+//!
+//! 硬币抛掷、零知识证明、SPDZ 打开检查等协议中的挑战值都应由协议至此的全部公开消息派生。
+//! `Transcript` 按顺序吸收带标签的消息，任何时刻都可以从当前状态导出挑战；
+//! 导出后状态随之更新，之后的挑战与之前的不同。
+//!
+//! 每条消息以 `标签长度 ‖ 标签 ‖ 消息长度 ‖ 消息` 的形式进入 SHA-256 状态，
+//! 不同的消息划分不会得到相同的输入。转录以协议的域分隔标签开始，不同协议的挑战互不相关。
+//! 双方必须以相同的顺序写入相同的消息；其他模块可以先写入自己的上下文
+//! （会话标识、参与方编号等，`ProtocolSession::transcript` 会写好这些），
+//! 再把转录交给子协议使用，使挑战与上下文绑定、无法在别处重放。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::utils::Transcript;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let mut prover = Transcript::new(b"example-protocol");
+//! prover.append_message(b"session", b"42");
+//! prover.append_serialized(b"opened", &vec![3u64, 5, 8])?;
+//! let mut verifier = prover.clone();
+//!
+//! assert_eq!(prover.challenge_scalar(b"c"), verifier.challenge_scalar(b"c"));
+//! assert_eq!(prover.challenge_fields(b"r", 4), verifier.challenge_fields(b"r", 4));
+//! // 导出挑战后状态改变，再次导出得到不同的值
+//! assert_ne!(prover.challenge_bytes::<32>(b"c"), prover.challenge_bytes::<32>(b"c"));
+//! # Ok(())
+//! # }
+//! ```
+
+use super::canonical::canonical_encode;
+use crate::elliptic_curve::{Secp256k1Point, Secp256k1Scalar};
+use crate::secret_sharing::FIELD_PRIME;
+use crate::Result;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// 基于 SHA-256 的协议转录
+#[derive(Clone)]
+pub struct Transcript {
+    hasher: Sha256,
+}
+
+impl Transcript {
+    /// 以协议的域分隔标签创建转录
+    pub fn new(domain: &[u8]) -> Self {
+        let mut transcript = Self { hasher: Sha256::new() };
+        transcript.append_message(b"mpc_api/transcript", domain);
+        transcript
+    }
+
+    /// 写入一条带标签的消息
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        self.hasher.update((label.len() as u64).to_be_bytes());
+        self.hasher.update(label);
+        self.hasher.update((message.len() as u64).to_be_bytes());
+        self.hasher.update(message);
+    }
+
+    /// 写入一个整数
+    pub fn append_u64(&mut self, label: &[u8], value: u64) {
+        self.append_message(label, &value.to_be_bytes());
+    }
+
+    /// 写入任意可序列化的值（`canonical_encode` 规范编码）
+    pub fn append_serialized<T: Serialize + ?Sized>(&mut self, label: &[u8], value: &T) -> Result<()> {
+        self.append_message(label, &canonical_encode(value)?);
+        Ok(())
+    }
+
+    /// 写入一个 secp256k1 点（SEC 1 压缩编码）
+    pub fn append_point(&mut self, label: &[u8], point: &Secp256k1Point) {
+        self.append_message(label, &point.to_sec1(true));
+    }
+
+    /// 写入一个 secp256k1 标量（32 字节大端编码）
+    pub fn append_scalar(&mut self, label: &[u8], scalar: &Secp256k1Scalar) {
+        self.append_message(label, &scalar.to_bytes());
+    }
+
+    /// 导出 N 字节挑战，并把挑战写回转录
+    pub fn challenge_bytes<const N: usize>(&mut self, label: &[u8]) -> [u8; N] {
+        self.append_message(b"challenge", label);
+        let seed = self.hasher.clone().finalize();
+
+        let mut output = [0u8; N];
+        for (counter, chunk) in output.chunks_mut(32).enumerate() {
+            let block = Sha256::new()
+                .chain_update(seed)
+                .chain_update((counter as u64).to_be_bytes())
+                .finalize();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        self.append_message(b"challenge-output", &output);
+        output
+    }
+
+    /// 导出 secp256k1 标量挑战
+    ///
+    /// 512 位输出模 n 约简，与均匀分布的统计距离可以忽略。
+    pub fn challenge_scalar(&mut self, label: &[u8]) -> Secp256k1Scalar {
+        let bytes: [u8; 64] = self.challenge_bytes(label);
+        let high = Secp256k1Scalar::from_bytes_reduced(bytes[..32].try_into().unwrap());
+        let low = Secp256k1Scalar::from_bytes_reduced(bytes[32..].try_into().unwrap());
+        // high·2^256 + low，其中 2^256 ≡ 2^256 - n (mod n)
+        let two_256 = Secp256k1Scalar::from_bytes_reduced(&[0xff; 32]) + Secp256k1Scalar::ONE;
+        high * two_256 + low
+    }
+
+    /// 导出 `FIELD_PRIME` 域中的挑战
+    ///
+    /// 128 位输出模 p 约简，与均匀分布的统计距离约为 2^-64。
+    pub fn challenge_field(&mut self, label: &[u8]) -> u64 {
+        let bytes: [u8; 16] = self.challenge_bytes(label);
+        (u128::from_le_bytes(bytes) % FIELD_PRIME as u128) as u64
+    }
+
+    /// 导出 `count` 个 `FIELD_PRIME` 域中的挑战，例如随机线性组合的系数
+    pub fn challenge_fields(&mut self, label: &[u8], count: usize) -> Vec<u64> {
+        self.append_u64(b"challenge-count", count as u64);
+        (0..count).map(|_| self.challenge_field(label)).collect()
+    }
+}
+
+impl std::fmt::Debug for Transcript {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transcript").finish_non_exhaustive()
+    }
+}
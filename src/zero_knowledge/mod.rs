@@ -1,5 +1,53 @@
-//! Zero-Knowledge Proofs (零知识证明)
-//! 
-//! This module will implement various zero-knowledge proof systems
+//! # 零知识证明 (Zero-Knowledge Proofs)
+//!
+//! 本模块提供 secp256k1 上的 Schnorr 式 Sigma 协议和 Fiat-Shamir 转录，
+//! 供承诺、硬币抛掷、SPDZ 输入等协议为自己的消息附加证明。
+//!
+//! ## 组成
+//!
+//! - **Transcript**: 基于 SHA-256 的 Fiat-Shamir 转录（即 `utils::transcript::Transcript`），按顺序吸收带标签的消息并导出挑战
+//! - **SigmaProtocol**: 三步公开币证明的通用接口，可以交互执行，也可以经
+//!   `SigmaProof` 变成非交互证明
+//! - **DlogKnowledge**: 离散对数知识证明，P = x·B
+//! - **DlogEquality**: 离散对数相等证明，A = x·G 且 C = x·H
+//! - **ProductProof**: Pedersen 承诺值的乘法关系证明，`prove_product` / `verify_product`
+//! - **RangeProof**: Pedersen 承诺值的范围证明（按位分解），`prove_range` / `verify_range`
+//! - **ShuffleProof**: 指数 ElGamal 密文的可验证重加密洗牌（混合网络），需要 `he` 特性
+//!
+//! ## 与上下文绑定
+//!
+//! 证明只对生成它时的转录状态有效。调用方先写入会话标识、参与方编号和协议消息，
+//! 再生成或验证证明，这样证明无法被复制到其他会话或其他参与方名下。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::elliptic_curve::Secp256k1Scalar;
+//! use mpc_api::zero_knowledge::*;
+//!
+//! let x = Secp256k1Scalar::random();
+//! let statement = DlogStatement::new(&x);
+//!
+//! let mut transcript = Transcript::new(b"spdz-input");
+//! transcript.append_u64(b"party", 3);
+//! let proof = DlogProof::prove(&mut transcript.clone(), &statement, &x);
+//!
+//! assert!(proof.verify(&mut transcript.clone(), &statement));
+//! // 换一个参与方编号，证明不再有效
+//! let mut other = Transcript::new(b"spdz-input");
+//! other.append_u64(b"party", 4);
+//! assert!(!proof.verify(&mut other, &statement));
+//! ```
 
-// Placeholder for future implementation
\ No newline at end of file
+pub mod product;
+pub mod range;
+#[cfg(feature = "he")]
+pub mod shuffle;
+pub mod sigma;
+
+pub use product::*;
+pub use range::*;
+#[cfg(feature = "he")]
+pub use shuffle::*;
+pub use sigma::*;
+pub use crate::utils::transcript::Transcript;
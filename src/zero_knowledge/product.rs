@@ -0,0 +1,207 @@
+//! # 承诺值的乘法关系证明 (Product Proofs for Committed Values)
+//!
+//! 给定三个 Pedersen 承诺 A = a·G + r_a·H、B = b·G + r_b·H、C = c·G + r_c·H，
+//! 证明 c = a·b（secp256k1 标量域中），而不泄露 a、b、c。
+//! 可信方用它证明分发的 Beaver 三元组满足乘法关系，也可以用于 SPDZ 等协议中检查乘法门。
+//!
+//! ## 构造
+//!
+//! c = a·b 时 C = a·B + (r_c - a·r_b)·H，即 C 是以 B 和 H 为底的承诺，并且其中 B 的系数
+//! 与 A 中 G 的系数相同。Sigma 协议同时证明三件事，并让 a 的两次出现共用一个响应：
+//!
+//! - 知道 A 的开启 (a, r_a)
+//! - 知道 B 的开启 (b, r_b)
+//! - 知道 (a, r) 使 C = a·B + r·H
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::elliptic_curve::Secp256k1Scalar;
+//! use mpc_api::zero_knowledge::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let generators = PedersenGenerators::default();
+//! let witness = ProductWitness {
+//!     a: Secp256k1Scalar::from_u64(6),
+//!     r_a: Secp256k1Scalar::random(),
+//!     b: Secp256k1Scalar::from_u64(7),
+//!     r_b: Secp256k1Scalar::random(),
+//!     r_c: Secp256k1Scalar::random(),
+//! };
+//! let a = generators.commit(6, &witness.r_a);
+//! let b = generators.commit(7, &witness.r_b);
+//! let c = generators.commit(42, &witness.r_c);
+//!
+//! let proof = prove_product(&a, &b, &c, &witness)?;
+//! assert!(verify_product(&a, &b, &c, &proof));
+//! assert!(!verify_product(&a, &c, &b, &proof));
+//! # Ok(())
+//! # }
+//! ```
+
+use super::range::PedersenGenerators;
+use super::sigma::{SigmaProof, SigmaProtocol};
+use crate::utils::transcript::Transcript;
+use crate::elliptic_curve::{Secp256k1Point, Secp256k1Scalar};
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+
+/// 乘法关系证明的公开陈述
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProductStatement {
+    /// 承诺使用的生成元
+    pub generators: PedersenGenerators,
+    /// A = a·G + r_a·H
+    pub a: Secp256k1Point,
+    /// B = b·G + r_b·H
+    pub b: Secp256k1Point,
+    /// C = a·b·G + r_c·H
+    pub c: Secp256k1Point,
+}
+
+/// 乘法关系证明的证据，c = a·b 由 a 和 b 确定
+#[derive(Debug, Clone, Copy)]
+pub struct ProductWitness {
+    /// 第一个因子
+    pub a: Secp256k1Scalar,
+    /// A 的随机数
+    pub r_a: Secp256k1Scalar,
+    /// 第二个因子
+    pub b: Secp256k1Scalar,
+    /// B 的随机数
+    pub r_b: Secp256k1Scalar,
+    /// C 的随机数
+    pub r_c: Secp256k1Scalar,
+}
+
+/// 乘法关系证明的第一步消息
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProductCommitment {
+    /// k_a·G + k_ra·H
+    pub t_a: Secp256k1Point,
+    /// k_b·G + k_rb·H
+    pub t_b: Secp256k1Point,
+    /// k_a·B + k_r·H
+    pub t_c: Secp256k1Point,
+}
+
+/// 乘法关系证明的响应
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProductResponse {
+    /// a 的响应，同时用于 A 和 C 的检查
+    pub z_a: Secp256k1Scalar,
+    /// r_a 的响应
+    pub z_ra: Secp256k1Scalar,
+    /// b 的响应
+    pub z_b: Secp256k1Scalar,
+    /// r_b 的响应
+    pub z_rb: Secp256k1Scalar,
+    /// r_c - a·r_b 的响应
+    pub z_r: Secp256k1Scalar,
+}
+
+/// Pedersen 承诺值的乘法关系证明
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProductRelation;
+
+impl SigmaProtocol for ProductRelation {
+    type Statement = ProductStatement;
+    type Witness = ProductWitness;
+    type Commitment = ProductCommitment;
+    type Nonce = [Secp256k1Scalar; 5];
+    type Response = ProductResponse;
+
+    const LABEL: &'static [u8] = b"pedersen-product";
+
+    fn commit(statement: &ProductStatement, _witness: &ProductWitness) -> ([Secp256k1Scalar; 5], ProductCommitment) {
+        let PedersenGenerators { g, h } = statement.generators;
+        let [k_a, k_ra, k_b, k_rb, k_r] = std::array::from_fn(|_| Secp256k1Scalar::random());
+        let commitment = ProductCommitment {
+            t_a: g * k_a + h * k_ra,
+            t_b: g * k_b + h * k_rb,
+            t_c: statement.b * k_a + h * k_r,
+        };
+        ([k_a, k_ra, k_b, k_rb, k_r], commitment)
+    }
+
+    fn respond(witness: &ProductWitness, nonce: [Secp256k1Scalar; 5], challenge: &Secp256k1Scalar) -> ProductResponse {
+        let [k_a, k_ra, k_b, k_rb, k_r] = nonce;
+        let e = *challenge;
+        ProductResponse {
+            z_a: k_a + e * witness.a,
+            z_ra: k_ra + e * witness.r_a,
+            z_b: k_b + e * witness.b,
+            z_rb: k_rb + e * witness.r_b,
+            z_r: k_r + e * (witness.r_c - witness.a * witness.r_b),
+        }
+    }
+
+    fn check(
+        statement: &ProductStatement,
+        commitment: &ProductCommitment,
+        challenge: &Secp256k1Scalar,
+        response: &ProductResponse,
+    ) -> bool {
+        let PedersenGenerators { g, h } = statement.generators;
+        let e = *challenge;
+        // z_a·G + z_ra·H = T_a + e·A，z_b·G + z_rb·H = T_b + e·B，z_a·B + z_r·H = T_c + e·C
+        g * response.z_a + h * response.z_ra == commitment.t_a + statement.a * e
+            && g * response.z_b + h * response.z_rb == commitment.t_b + statement.b * e
+            && statement.b * response.z_a + h * response.z_r == commitment.t_c + statement.c * e
+    }
+
+    fn append_statement(transcript: &mut Transcript, statement: &ProductStatement) {
+        transcript.append_point(b"g", &statement.generators.g);
+        transcript.append_point(b"h", &statement.generators.h);
+        transcript.append_point(b"a", &statement.a);
+        transcript.append_point(b"b", &statement.b);
+        transcript.append_point(b"c", &statement.c);
+    }
+
+    fn append_commitment(transcript: &mut Transcript, commitment: &ProductCommitment) {
+        transcript.append_point(b"t-a", &commitment.t_a);
+        transcript.append_point(b"t-b", &commitment.t_b);
+        transcript.append_point(b"t-c", &commitment.t_c);
+    }
+}
+
+/// 非交互乘法关系证明
+pub type ProductProof = SigmaProof<ProductRelation>;
+
+impl ProductProof {
+    /// 在调用方的转录上生成乘法关系证明
+    ///
+    /// # 返回值
+    /// 证据不能打开三个承诺（包括 c ≠ a·b）时返回错误
+    pub fn prove_product(transcript: &mut Transcript, statement: &ProductStatement, witness: &ProductWitness) -> Result<Self> {
+        let generators = &statement.generators;
+        if generators.commit_scalar(&witness.a, &witness.r_a) != statement.a
+            || generators.commit_scalar(&witness.b, &witness.r_b) != statement.b
+            || generators.commit_scalar(&(witness.a * witness.b), &witness.r_c) != statement.c
+        {
+            return Err(MpcError::ProtocolError("Witness does not open the commitments to a, b and a·b".to_string()));
+        }
+        Ok(Self::prove(transcript, statement, witness))
+    }
+}
+
+/// 证明默认生成元下的承诺 `c` 中的值是 `a`、`b` 中的值之积
+///
+/// # 参数
+/// - `a`, `b`, `c`: 三个承诺
+/// - `witness`: 两个因子和三个承诺的随机数
+pub fn prove_product(
+    a: &Secp256k1Point,
+    b: &Secp256k1Point,
+    c: &Secp256k1Point,
+    witness: &ProductWitness,
+) -> Result<ProductProof> {
+    let statement = ProductStatement { generators: PedersenGenerators::default(), a: *a, b: *b, c: *c };
+    ProductProof::prove_product(&mut Transcript::new(b"mpc_api/product-proof"), &statement, witness)
+}
+
+/// 验证 `prove_product` 生成的证明
+pub fn verify_product(a: &Secp256k1Point, b: &Secp256k1Point, c: &Secp256k1Point, proof: &ProductProof) -> bool {
+    let statement = ProductStatement { generators: PedersenGenerators::default(), a: *a, b: *b, c: *c };
+    proof.verify(&mut Transcript::new(b"mpc_api/product-proof"), &statement)
+}
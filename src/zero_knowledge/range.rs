@@ -0,0 +1,262 @@
+//! # 承诺值的范围证明 (Range Proofs for Committed Values)
+//!
+//! 证明 secp256k1 上的 Pedersen 承诺 C = v·G + r·H 中的 v 落在 [0, 2^n) 内，而不泄露 v。
+//! 拍卖出价、SPDZ 的有界输入等场合用它防止参与方提交负数或越界的值
+//! （负数在标量域中是接近阶 n 的大数）。
+//!
+//! ## 构造（按位分解）
+//!
+//! 1. 把 v 写成二进制 v = Σ 2^i·b_i，对每一位承诺 C_i = b_i·G + r_i·H，
+//!    并选取 r_i 使 Σ 2^i·r_i = r，验证方检查 Σ 2^i·C_i = C
+//! 2. 每一位给出 Cramer–Damgård–Schoenmakers 或证明：C_i 或 C_i - G 是 H 的已知倍数，
+//!    未选中的分支用模拟的挑战和响应填充
+//! 3. 所有位共用一个 Fiat-Shamir 挑战 e，每一位的两个分支挑战满足 c0 + c1 = e
+//!
+//! 证明大小与 n 成线性（每位 3 个点和 3 个标量）。更一般的区间 [a, b] 可以对
+//! v - a 和 b - v 的承诺（由 C 同态得到）分别证明。
+//!
+//! ## 生成元
+//!
+//! G 是标准生成元，H 由固定标签哈希到曲线（try-and-increment），没有人知道 H 对 G 的离散对数，
+//! 承诺因此是计算绑定的。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::elliptic_curve::Secp256k1Scalar;
+//! use mpc_api::zero_knowledge::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let randomness = Secp256k1Scalar::random();
+//! let bid = PedersenGenerators::default().commit(1500, &randomness);
+//!
+//! let proof = prove_range(&bid, 1500, &randomness, 16)?;
+//! assert!(verify_range(&bid, &proof, 16));
+//! assert!(!verify_range(&bid, &proof, 8));
+//! assert!(prove_range(&bid, 1500, &randomness, 8).is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+use super::sigma::{SigmaProof, SigmaProtocol};
+use crate::utils::transcript::Transcript;
+pub use crate::commitment::PedersenGenerators;
+use crate::elliptic_curve::{Secp256k1Point, Secp256k1Scalar};
+use crate::{MpcError, Result};
+use serde::{Deserialize, Serialize};
+
+/// 范围证明的公开陈述
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeStatement {
+    /// 承诺使用的生成元
+    pub generators: PedersenGenerators,
+    /// 承诺 C = v·G + r·H
+    pub commitment: Secp256k1Point,
+    /// 位数 n，证明 v < 2^n
+    pub n_bits: usize,
+}
+
+/// 范围证明的证据
+#[derive(Debug, Clone, Copy)]
+pub struct RangeWitness {
+    /// 承诺的值
+    pub value: u64,
+    /// 承诺的随机数
+    pub randomness: Secp256k1Scalar,
+}
+
+/// 一位的承诺 C_i 和或证明两个分支的第一步消息
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BitCommitment {
+    /// C_i = b_i·G + r_i·H
+    pub commitment: Secp256k1Point,
+    /// b_i = 0 分支的承诺
+    pub a0: Secp256k1Point,
+    /// b_i = 1 分支的承诺
+    pub a1: Secp256k1Point,
+}
+
+/// 一位的或证明响应，b_i = 1 分支的挑战为 e - c0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BitResponse {
+    /// b_i = 0 分支的挑战
+    pub c0: Secp256k1Scalar,
+    /// b_i = 0 分支的响应
+    pub s0: Secp256k1Scalar,
+    /// b_i = 1 分支的响应
+    pub s1: Secp256k1Scalar,
+}
+
+/// 证明方在一位上的随机数：真实分支的 k 和模拟分支的 (挑战, 响应)
+#[derive(Debug, Clone, Copy)]
+pub struct BitNonce {
+    bit: bool,
+    blinding: Secp256k1Scalar,
+    k: Secp256k1Scalar,
+    simulated_challenge: Secp256k1Scalar,
+    simulated_response: Secp256k1Scalar,
+}
+
+/// 按位分解的范围证明
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitDecomposition;
+
+impl BitDecomposition {
+    /// 证明支持的最大位数（值为 u64）
+    pub const MAX_BITS: usize = 64;
+
+    fn power_of_two(i: usize) -> Secp256k1Scalar {
+        Secp256k1Scalar::from_u64(1u64 << i)
+    }
+}
+
+impl SigmaProtocol for BitDecomposition {
+    type Statement = RangeStatement;
+    type Witness = RangeWitness;
+    type Commitment = Vec<BitCommitment>;
+    type Nonce = Vec<BitNonce>;
+    type Response = Vec<BitResponse>;
+
+    const LABEL: &'static [u8] = b"range-bit-decomposition";
+
+    fn commit(statement: &RangeStatement, witness: &RangeWitness) -> (Vec<BitNonce>, Vec<BitCommitment>) {
+        let PedersenGenerators { g, h } = statement.generators;
+        // 位数越界的陈述得到的证明不会通过 `check`
+        let n = statement.n_bits.clamp(1, Self::MAX_BITS);
+
+        // r_i 随机，最高位的 r_(n-1) 使 Σ 2^i·r_i = r
+        let mut blindings: Vec<Secp256k1Scalar> = (0..n.saturating_sub(1)).map(|_| Secp256k1Scalar::random()).collect();
+        let partial = blindings.iter().enumerate()
+            .fold(Secp256k1Scalar::ZERO, |acc, (i, r_i)| acc + Self::power_of_two(i) * *r_i);
+        let top = Self::power_of_two(n - 1).invert().expect("powers of two are invertible");
+        blindings.push((witness.randomness - partial) * top);
+
+        blindings.into_iter().enumerate()
+            .map(|(i, blinding)| {
+                let bit = (witness.value >> i) & 1 == 1;
+                let commitment = if bit { g + h * blinding } else { h * blinding };
+                let nonce = BitNonce {
+                    bit,
+                    blinding,
+                    k: Secp256k1Scalar::random(),
+                    simulated_challenge: Secp256k1Scalar::random(),
+                    simulated_response: Secp256k1Scalar::random(),
+                };
+                // 真实分支 A = k·H，模拟分支 A = s·H - c·Y
+                let real = h * nonce.k;
+                let simulated = |y: Secp256k1Point| h * nonce.simulated_response + -(y * nonce.simulated_challenge);
+                let (a0, a1) = if bit {
+                    (simulated(commitment), real)
+                } else {
+                    (real, simulated(commitment + -g))
+                };
+                (nonce, BitCommitment { commitment, a0, a1 })
+            })
+            .unzip()
+    }
+
+    fn respond(_witness: &RangeWitness, nonces: Vec<BitNonce>, challenge: &Secp256k1Scalar) -> Vec<BitResponse> {
+        nonces.into_iter()
+            .map(|nonce| {
+                let real_challenge = *challenge - nonce.simulated_challenge;
+                let real_response = nonce.k + real_challenge * nonce.blinding;
+                if nonce.bit {
+                    BitResponse { c0: nonce.simulated_challenge, s0: nonce.simulated_response, s1: real_response }
+                } else {
+                    BitResponse { c0: real_challenge, s0: real_response, s1: nonce.simulated_response }
+                }
+            })
+            .collect()
+    }
+
+    fn check(
+        statement: &RangeStatement,
+        commitments: &Vec<BitCommitment>,
+        challenge: &Secp256k1Scalar,
+        responses: &Vec<BitResponse>,
+    ) -> bool {
+        let PedersenGenerators { g, h } = statement.generators;
+        let n = statement.n_bits;
+        if n == 0 || n > Self::MAX_BITS || commitments.len() != n || responses.len() != n {
+            return false;
+        }
+
+        // Σ 2^i·C_i = C
+        let recombined = commitments.iter().enumerate()
+            .fold(Secp256k1Point::identity(), |acc, (i, bit)| acc + bit.commitment * Self::power_of_two(i));
+        if recombined != statement.commitment {
+            return false;
+        }
+
+        // s0·H = A0 + c0·C_i 且 s1·H = A1 + c1·(C_i - G)
+        commitments.iter().zip(responses).all(|(bit, response)| {
+            let c1 = *challenge - response.c0;
+            h * response.s0 == bit.a0 + bit.commitment * response.c0
+                && h * response.s1 == bit.a1 + (bit.commitment + -g) * c1
+        })
+    }
+
+    fn append_statement(transcript: &mut Transcript, statement: &RangeStatement) {
+        transcript.append_point(b"g", &statement.generators.g);
+        transcript.append_point(b"h", &statement.generators.h);
+        transcript.append_point(b"commitment", &statement.commitment);
+        transcript.append_u64(b"n-bits", statement.n_bits as u64);
+    }
+
+    fn append_commitment(transcript: &mut Transcript, commitments: &Vec<BitCommitment>) {
+        for bit in commitments {
+            transcript.append_point(b"bit-commitment", &bit.commitment);
+            transcript.append_point(b"a0", &bit.a0);
+            transcript.append_point(b"a1", &bit.a1);
+        }
+    }
+}
+
+/// 非交互范围证明
+pub type RangeProof = SigmaProof<BitDecomposition>;
+
+impl RangeProof {
+    /// 在调用方的转录上生成范围证明
+    ///
+    /// # 返回值
+    /// 位数不在 1..=64 内、值不小于 2^n 或承诺与 (值, 随机数) 不符时返回错误
+    pub fn prove_range(transcript: &mut Transcript, statement: &RangeStatement, witness: &RangeWitness) -> Result<Self> {
+        let n = statement.n_bits;
+        if n == 0 || n > BitDecomposition::MAX_BITS {
+            return Err(MpcError::ProtocolError(format!(
+                "Range proofs support 1 to {} bits, got {}", BitDecomposition::MAX_BITS, n
+            )));
+        }
+        if n < 64 && witness.value >> n != 0 {
+            return Err(MpcError::ProtocolError(format!("Value does not fit in {} bits", n)));
+        }
+        if statement.generators.commit(witness.value, &witness.randomness) != statement.commitment {
+            return Err(MpcError::ProtocolError("Witness does not open the commitment".to_string()));
+        }
+        Ok(Self::prove(transcript, statement, witness))
+    }
+}
+
+/// 证明默认生成元下的承诺 `commitment` 打开为小于 2^n_bits 的 `value`
+///
+/// # 参数
+/// - `commitment`: C = value·G + randomness·H
+/// - `value`: 承诺的值
+/// - `randomness`: 承诺的随机数
+/// - `n_bits`: 位数，1 到 64
+pub fn prove_range(
+    commitment: &Secp256k1Point,
+    value: u64,
+    randomness: &Secp256k1Scalar,
+    n_bits: usize,
+) -> Result<RangeProof> {
+    let statement = RangeStatement { generators: PedersenGenerators::default(), commitment: *commitment, n_bits };
+    let witness = RangeWitness { value, randomness: *randomness };
+    RangeProof::prove_range(&mut Transcript::new(b"mpc_api/range-proof"), &statement, &witness)
+}
+
+/// 验证 `prove_range` 生成的证明
+pub fn verify_range(commitment: &Secp256k1Point, proof: &RangeProof, n_bits: usize) -> bool {
+    let statement = RangeStatement { generators: PedersenGenerators::default(), commitment: *commitment, n_bits };
+    proof.verify(&mut Transcript::new(b"mpc_api/range-proof"), &statement)
+}
@@ -0,0 +1,370 @@
+//! # 可验证洗牌 (Verifiable Re-encryption Shuffle)
+//!
+//! 混合网络中的每个混合者把一组指数 ElGamal 密文打乱顺序并逐个重加密后转发，
+//! 输出与输入之间的对应关系因此被隐藏。`ShuffleProof` 证明输出确实是输入的某个置换再加上
+//! 0 的加密，而不泄露置换和重加密随机数，使不诚实的混合者无法替换、复制或丢弃密文。
+//! 匿名投票、匿名聚合等场景在同态计数或解密之前先经过若干个混合者。
+//!
+//! ## 构造（Terelius–Wikström）
+//!
+//! 记输入为 e_j、输出为 e'_i = e_(π(i)) + Enc(0; r'_i)，证明按如下步骤进行：
+//!
+//! 1. 用独立生成元 h_1..h_N 承诺置换矩阵：c_(π(i)) = r_(π(i))·G + h_i
+//! 2. 从转录导出挑战 u_1..u_N，令 u'_i = u_(π(i))
+//! 3. 承诺链 ĉ_i = r̂_i·G + u'_i·ĉ_(i-1)（ĉ_0 = h）证明 Π u'_i = Π u_i
+//! 4. 一个 Sigma 协议同时证明：
+//!    - Σ c_j 打开为 Σ h_i（置换矩阵每列和为 1）
+//!    - ĉ_N 打开为 (Π u_i)·h
+//!    - Σ u_j·c_j 打开为 Σ u'_i·h_i
+//!    - Σ u'_i·e'_i = Σ u_j·e_j + Enc(0; Σ u'_i·r'_i)
+//!
+//! 前三条说明 u' 是 u 的一个置换（否则 Schwartz–Zippel 引理使其以可忽略的概率成立），
+//! 第四条把这个置换与密文联系起来。证明大小与 N 成线性（约 3N 个点和 2N 个标量）。
+//!
+//! h、h_1..h_N 由固定标签哈希到曲线，没有人知道它们之间的离散对数关系。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::homomorphic_encryption::*;
+//! use mpc_api::zero_knowledge::*;
+//!
+//! # fn main() -> mpc_api::Result<()> {
+//! let (pk, sk) = ExponentialElGamal::keygen()?;
+//! let votes = [1u64, 0, 1, 1]
+//!     .iter()
+//!     .map(|v| ExponentialElGamal::encrypt(&pk, v))
+//!     .collect::<mpc_api::Result<Vec<_>>>()?;
+//!
+//! let (mixed, witness) = shuffle_ciphertexts(&pk, &votes);
+//! let proof = prove_shuffle(&pk, &votes, &mixed, &witness)?;
+//! assert!(verify_shuffle(&pk, &votes, &mixed, &proof));
+//!
+//! // 顺序被打乱，但计票结果不变
+//! let tally = ExponentialElGamal::sum_ciphertexts(&pk, &mixed)?;
+//! assert_eq!(ExponentialElGamal::decrypt(&sk, &tally)?, 3);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::range::PedersenGenerators;
+use crate::utils::transcript::Transcript;
+use crate::elliptic_curve::{Secp256k1Point, Secp256k1Scalar};
+use crate::homomorphic_encryption::{ExponentialElGamalCiphertext, ExponentialElGamalPublicKey};
+use crate::{MpcError, Result};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+/// 洗牌的证据：outputs[i] = inputs[permutation[i]] + Enc(0; randomness[i])
+#[derive(Debug, Clone)]
+pub struct ShuffleWitness {
+    /// 置换 π，输出 i 来自输入 π(i)
+    pub permutation: Vec<usize>,
+    /// 每个输出的重加密随机数
+    pub randomness: Vec<Secp256k1Scalar>,
+}
+
+/// Sigma 协议部分的第一步消息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShuffleCommitment {
+    /// 对应 Σ c_j - Σ h_i 的承诺
+    pub t1: Secp256k1Point,
+    /// 对应 ĉ_N - (Π u_i)·h 的承诺
+    pub t2: Secp256k1Point,
+    /// 对应 Σ u_j·c_j 的承诺
+    pub t3: Secp256k1Point,
+    /// 对应密文第一分量的承诺
+    pub t4_c1: Secp256k1Point,
+    /// 对应密文第二分量的承诺
+    pub t4_c2: Secp256k1Point,
+    /// 对应承诺链每一环的承诺
+    pub t_hat: Vec<Secp256k1Point>,
+}
+
+/// Sigma 协议部分的响应
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShuffleResponse {
+    /// Σ r_j 的响应
+    pub s1: Secp256k1Scalar,
+    /// 承诺链开启值的响应
+    pub s2: Secp256k1Scalar,
+    /// Σ u_j·r_j 的响应
+    pub s3: Secp256k1Scalar,
+    /// 合并重加密随机数 Σ u'_i·r'_i 的响应
+    pub s4: Secp256k1Scalar,
+    /// 承诺链随机数 r̂_i 的响应
+    pub s_hat: Vec<Secp256k1Scalar>,
+    /// 置换后挑战 u'_i 的响应
+    pub s_prime: Vec<Secp256k1Scalar>,
+}
+
+/// 非交互重加密洗牌证明
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShuffleProof {
+    /// 置换矩阵的承诺 c_1..c_N
+    pub permutation_commitments: Vec<Secp256k1Point>,
+    /// 承诺链 ĉ_1..ĉ_N
+    pub chain_commitments: Vec<Secp256k1Point>,
+    /// Sigma 协议的承诺
+    pub commitment: ShuffleCommitment,
+    /// Sigma 协议的响应
+    pub response: ShuffleResponse,
+}
+
+impl ShuffleProof {
+    /// 在调用方的转录上生成洗牌证明
+    ///
+    /// # 返回值
+    /// 密文为空、长度不一致、`permutation` 不是置换或输出与证据不符时返回错误
+    pub fn prove(
+        transcript: &mut Transcript,
+        pk: &ExponentialElGamalPublicKey,
+        inputs: &[ExponentialElGamalCiphertext],
+        outputs: &[ExponentialElGamalCiphertext],
+        witness: &ShuffleWitness,
+    ) -> Result<Self> {
+        let n = inputs.len();
+        Self::check_witness(pk, inputs, outputs, witness)?;
+        let g = Secp256k1Point::generator();
+        let (h, hs) = Self::generators(n);
+        let permutation = &witness.permutation;
+
+        // c_(π(i)) = r_(π(i))·G + h_i
+        let r: Vec<Secp256k1Scalar> = (0..n).map(|_| Secp256k1Scalar::random()).collect();
+        let mut permutation_commitments = vec![Secp256k1Point::identity(); n];
+        for (i, &j) in permutation.iter().enumerate() {
+            permutation_commitments[j] = g * r[j] + hs[i];
+        }
+
+        let u = Self::permutation_challenges(transcript, pk, inputs, outputs, &permutation_commitments);
+        let u_prime: Vec<Secp256k1Scalar> = permutation.iter().map(|&j| u[j]).collect();
+
+        // ĉ_i = r̂_i·G + u'_i·ĉ_(i-1)
+        let r_hat: Vec<Secp256k1Scalar> = (0..n).map(|_| Secp256k1Scalar::random()).collect();
+        let mut chain_commitments = Vec::with_capacity(n);
+        let mut previous = h;
+        for (r_hat_i, u_prime_i) in r_hat.iter().zip(&u_prime) {
+            previous = g * *r_hat_i + previous * *u_prime_i;
+            chain_commitments.push(previous);
+        }
+
+        let omega: [Secp256k1Scalar; 4] = std::array::from_fn(|_| Secp256k1Scalar::random());
+        let omega_hat: Vec<Secp256k1Scalar> = (0..n).map(|_| Secp256k1Scalar::random()).collect();
+        let omega_prime: Vec<Secp256k1Scalar> = (0..n).map(|_| Secp256k1Scalar::random()).collect();
+
+        let chain_previous = std::iter::once(h).chain(chain_commitments[..n - 1].iter().copied());
+        let commitment = ShuffleCommitment {
+            t1: g * omega[0],
+            t2: g * omega[1],
+            t3: g * omega[2] + Self::linear_combination(&omega_prime, hs.iter().copied()),
+            t4_c1: Self::linear_combination(&omega_prime, outputs.iter().map(|c| c.c1)) + -(g * omega[3]),
+            t4_c2: Self::linear_combination(&omega_prime, outputs.iter().map(|c| c.c2)) + -(pk.point * omega[3]),
+            t_hat: chain_previous
+                .zip(omega_hat.iter().zip(&omega_prime))
+                .map(|(previous, (w_hat, w_prime))| g * *w_hat + previous * *w_prime)
+                .collect(),
+        };
+        let e = Self::challenge(transcript, &chain_commitments, &commitment);
+
+        // ĉ_N - (Π u_i)·h 的开启值 Σ r̂_i·v_i，其中 v_i = Π_(k>i) u'_k
+        let mut chain_opening = Secp256k1Scalar::ZERO;
+        let mut v = Secp256k1Scalar::ONE;
+        for i in (0..n).rev() {
+            chain_opening = chain_opening + r_hat[i] * v;
+            v = v * u_prime[i];
+        }
+        let r_bar = Self::sum(r.iter().copied());
+        let r_tilde = Self::sum(r.iter().zip(&u).map(|(r_j, u_j)| *r_j * *u_j));
+        let r_prime = Self::sum(witness.randomness.iter().zip(&u_prime).map(|(r_i, u_i)| *r_i * *u_i));
+
+        let response = ShuffleResponse {
+            s1: omega[0] + e * r_bar,
+            s2: omega[1] + e * chain_opening,
+            s3: omega[2] + e * r_tilde,
+            s4: omega[3] + e * r_prime,
+            s_hat: omega_hat.iter().zip(&r_hat).map(|(w, r_hat_i)| *w + e * *r_hat_i).collect(),
+            s_prime: omega_prime.iter().zip(&u_prime).map(|(w, u_i)| *w + e * *u_i).collect(),
+        };
+        Ok(Self { permutation_commitments, chain_commitments, commitment, response })
+    }
+
+    /// 验证 `outputs` 是 `inputs` 的置换和重加密，转录必须与生成证明时的状态相同
+    pub fn verify(
+        &self,
+        transcript: &mut Transcript,
+        pk: &ExponentialElGamalPublicKey,
+        inputs: &[ExponentialElGamalCiphertext],
+        outputs: &[ExponentialElGamalCiphertext],
+    ) -> bool {
+        let n = inputs.len();
+        let (t, s) = (&self.commitment, &self.response);
+        if n == 0
+            || outputs.len() != n
+            || self.permutation_commitments.len() != n
+            || self.chain_commitments.len() != n
+            || t.t_hat.len() != n
+            || s.s_hat.len() != n
+            || s.s_prime.len() != n
+        {
+            return false;
+        }
+        let g = Secp256k1Point::generator();
+        let (h, hs) = Self::generators(n);
+
+        let u = Self::permutation_challenges(transcript, pk, inputs, outputs, &self.permutation_commitments);
+        let e = Self::challenge(transcript, &self.chain_commitments, t);
+
+        let sum_points = |points: &[Secp256k1Point]| points.iter().fold(Secp256k1Point::identity(), |acc, p| acc + *p);
+        let c_bar = sum_points(&self.permutation_commitments) + -sum_points(&hs);
+        let u_product = u.iter().fold(Secp256k1Scalar::ONE, |acc, u_i| acc * *u_i);
+        let c_hat = self.chain_commitments[n - 1] + -(h * u_product);
+        let c_tilde = Self::linear_combination(&u, self.permutation_commitments.iter().copied());
+        let a_c1 = Self::linear_combination(&u, inputs.iter().map(|c| c.c1));
+        let a_c2 = Self::linear_combination(&u, inputs.iter().map(|c| c.c2));
+
+        let chain_previous = std::iter::once(h).chain(self.chain_commitments[..n - 1].iter().copied());
+        g * s.s1 == t.t1 + c_bar * e
+            && g * s.s2 == t.t2 + c_hat * e
+            && g * s.s3 + Self::linear_combination(&s.s_prime, hs.iter().copied()) == t.t3 + c_tilde * e
+            && Self::linear_combination(&s.s_prime, outputs.iter().map(|c| c.c1)) + -(g * s.s4) == t.t4_c1 + a_c1 * e
+            && Self::linear_combination(&s.s_prime, outputs.iter().map(|c| c.c2)) + -(pk.point * s.s4) == t.t4_c2 + a_c2 * e
+            && chain_previous
+                .zip(&self.chain_commitments)
+                .enumerate()
+                .all(|(i, (previous, current))| {
+                    g * s.s_hat[i] + previous * s.s_prime[i] == t.t_hat[i] + *current * e
+                })
+    }
+
+    fn check_witness(
+        pk: &ExponentialElGamalPublicKey,
+        inputs: &[ExponentialElGamalCiphertext],
+        outputs: &[ExponentialElGamalCiphertext],
+        witness: &ShuffleWitness,
+    ) -> Result<()> {
+        let n = inputs.len();
+        if n == 0 {
+            return Err(MpcError::ProtocolError("Cannot shuffle an empty list of ciphertexts".to_string()));
+        }
+        if outputs.len() != n || witness.permutation.len() != n || witness.randomness.len() != n {
+            return Err(MpcError::ProtocolError(format!(
+                "Shuffle of {} ciphertexts needs {} outputs, permutation entries and randomness values", n, n
+            )));
+        }
+        let mut seen = vec![false; n];
+        for &j in &witness.permutation {
+            if j >= n || std::mem::replace(&mut seen[j], true) {
+                return Err(MpcError::ProtocolError("Shuffle witness is not a permutation".to_string()));
+            }
+        }
+        let consistent = outputs.iter().zip(&witness.permutation).zip(&witness.randomness)
+            .all(|((output, &j), r)| *output == reencrypt(pk, &inputs[j], r));
+        if !consistent {
+            return Err(MpcError::ProtocolError("Outputs do not match the shuffle witness".to_string()));
+        }
+        Ok(())
+    }
+
+    /// h 和 h_1..h_N
+    fn generators(n: usize) -> (Secp256k1Point, Vec<Secp256k1Point>) {
+        let h = PedersenGenerators::hash_to_point(b"mpc_api/shuffle/h");
+        let hs = (0..n as u64)
+            .map(|i| PedersenGenerators::hash_to_point(&[&b"mpc_api/shuffle/h_i"[..], &i.to_be_bytes()].concat()))
+            .collect();
+        (h, hs)
+    }
+
+    /// Σ k_i·P_i
+    fn linear_combination(scalars: &[Secp256k1Scalar], points: impl IntoIterator<Item = Secp256k1Point>) -> Secp256k1Point {
+        scalars.iter().zip(points).fold(Secp256k1Point::identity(), |acc, (k, p)| acc + p * *k)
+    }
+
+    fn sum(scalars: impl Iterator<Item = Secp256k1Scalar>) -> Secp256k1Scalar {
+        scalars.fold(Secp256k1Scalar::ZERO, |acc, x| acc + x)
+    }
+
+    /// 写入陈述和置换承诺，导出 u_1..u_N
+    fn permutation_challenges(
+        transcript: &mut Transcript,
+        pk: &ExponentialElGamalPublicKey,
+        inputs: &[ExponentialElGamalCiphertext],
+        outputs: &[ExponentialElGamalCiphertext],
+        permutation_commitments: &[Secp256k1Point],
+    ) -> Vec<Secp256k1Scalar> {
+        transcript.append_message(b"sigma-protocol", b"reencryption-shuffle");
+        transcript.append_point(b"public-key", &pk.point);
+        transcript.append_u64(b"size", inputs.len() as u64);
+        for (label, ciphertexts) in [(&b"input"[..], inputs), (&b"output"[..], outputs)] {
+            for ciphertext in ciphertexts {
+                transcript.append_point(label, &ciphertext.c1);
+                transcript.append_point(label, &ciphertext.c2);
+            }
+        }
+        for commitment in permutation_commitments {
+            transcript.append_point(b"permutation-commitment", commitment);
+        }
+        (0..inputs.len()).map(|_| transcript.challenge_scalar(b"shuffle-u")).collect()
+    }
+
+    /// 写入承诺链和 Sigma 协议的承诺，导出挑战 e
+    fn challenge(transcript: &mut Transcript, chain_commitments: &[Secp256k1Point], commitment: &ShuffleCommitment) -> Secp256k1Scalar {
+        for chain_commitment in chain_commitments {
+            transcript.append_point(b"chain-commitment", chain_commitment);
+        }
+        transcript.append_point(b"t1", &commitment.t1);
+        transcript.append_point(b"t2", &commitment.t2);
+        transcript.append_point(b"t3", &commitment.t3);
+        transcript.append_point(b"t4-c1", &commitment.t4_c1);
+        transcript.append_point(b"t4-c2", &commitment.t4_c2);
+        for t_hat in &commitment.t_hat {
+            transcript.append_point(b"t-hat", t_hat);
+        }
+        transcript.challenge_scalar(b"sigma-challenge")
+    }
+}
+
+/// (c1 + r·G, c2 + r·H)，即加上 Enc(0; r)
+fn reencrypt(
+    pk: &ExponentialElGamalPublicKey,
+    ciphertext: &ExponentialElGamalCiphertext,
+    randomness: &Secp256k1Scalar,
+) -> ExponentialElGamalCiphertext {
+    ExponentialElGamalCiphertext {
+        c1: ciphertext.c1 + Secp256k1Point::generator() * *randomness,
+        c2: ciphertext.c2 + pk.point * *randomness,
+    }
+}
+
+/// 混合者的一步：随机置换并重加密 `inputs`，返回输出和生成证明所需的证据
+pub fn shuffle_ciphertexts(
+    pk: &ExponentialElGamalPublicKey,
+    inputs: &[ExponentialElGamalCiphertext],
+) -> (Vec<ExponentialElGamalCiphertext>, ShuffleWitness) {
+    let mut permutation: Vec<usize> = (0..inputs.len()).collect();
+    permutation.shuffle(&mut rand::thread_rng());
+    let randomness: Vec<Secp256k1Scalar> = permutation.iter().map(|_| Secp256k1Scalar::random()).collect();
+    let outputs = permutation.iter().zip(&randomness)
+        .map(|(&j, r)| reencrypt(pk, &inputs[j], r))
+        .collect();
+    (outputs, ShuffleWitness { permutation, randomness })
+}
+
+/// 证明 `outputs` 是 `inputs` 按 `witness` 置换并重加密的结果
+pub fn prove_shuffle(
+    pk: &ExponentialElGamalPublicKey,
+    inputs: &[ExponentialElGamalCiphertext],
+    outputs: &[ExponentialElGamalCiphertext],
+    witness: &ShuffleWitness,
+) -> Result<ShuffleProof> {
+    ShuffleProof::prove(&mut Transcript::new(b"mpc_api/shuffle-proof"), pk, inputs, outputs, witness)
+}
+
+/// 验证 `prove_shuffle` 生成的证明
+pub fn verify_shuffle(
+    pk: &ExponentialElGamalPublicKey,
+    inputs: &[ExponentialElGamalCiphertext],
+    outputs: &[ExponentialElGamalCiphertext],
+    proof: &ShuffleProof,
+) -> bool {
+    proof.verify(&mut Transcript::new(b"mpc_api/shuffle-proof"), pk, inputs, outputs)
+}
@@ -0,0 +1,233 @@
+//! # Schnorr 式 Sigma 协议 (Schnorr Sigma Protocols)
+//!
+//! Sigma 协议是三步的公开币证明：证明方发送承诺，验证方返回随机挑战，证明方给出响应。
+//! `SigmaProtocol` 把三步拆成 `commit` / `respond` / `check`，可以直接用于交互式执行；
+//! `SigmaProof` 用 `Transcript` 派生挑战（Fiat-Shamir 变换），得到非交互证明。
+//!
+//! 本模块在 secp256k1 上实现两个协议：
+//!
+//! - **DlogKnowledge**: 知道 x 使 P = x·B（Schnorr 证明）
+//! - **DlogEquality**: 同一个 x 满足 A = x·G 且 C = x·H（Chaum-Pedersen 证明），
+//!   用于证明解密份额、VRF 输出等与公钥使用了同一私钥
+//!
+//! 两个协议都是特殊可靠且诚实验证者零知识的：同一承诺下两组不同挑战的响应可以提取出 x，
+//! 而给定挑战时可以不用 x 模拟出同分布的记录。
+//!
+//! ## 使用示例
+//!
+//! ```rust
+//! use mpc_api::elliptic_curve::{Secp256k1Point, Secp256k1Scalar};
+//! use mpc_api::zero_knowledge::*;
+//!
+//! let x = Secp256k1Scalar::random();
+//! let h = Secp256k1Point::generator() * Secp256k1Scalar::from_u64(7);
+//! let statement = DlogEqualityStatement::new(Secp256k1Point::generator(), h, &x);
+//!
+//! let proof = SigmaProof::<DlogEquality>::prove(&mut Transcript::new(b"demo"), &statement, &x);
+//! assert!(proof.verify(&mut Transcript::new(b"demo"), &statement));
+//! assert!(!proof.verify(&mut Transcript::new(b"other"), &statement));
+//! ```
+
+use crate::utils::transcript::Transcript;
+use crate::elliptic_curve::{Secp256k1Point, Secp256k1Scalar};
+use serde::{Deserialize, Serialize};
+
+/// 三步公开币证明，挑战为 secp256k1 标量
+pub trait SigmaProtocol {
+    /// 公开陈述
+    type Statement;
+    /// 证明方的秘密证据
+    type Witness;
+    /// 第一步消息
+    type Commitment: Clone + std::fmt::Debug + PartialEq + Eq;
+    /// 证明方在承诺和响应之间保存的随机数
+    type Nonce;
+    /// 第三步消息
+    type Response: Clone + std::fmt::Debug + PartialEq + Eq;
+
+    /// 写入转录时使用的协议标签
+    const LABEL: &'static [u8];
+
+    /// 第一步：生成随机数和承诺
+    fn commit(statement: &Self::Statement, witness: &Self::Witness) -> (Self::Nonce, Self::Commitment);
+
+    /// 第三步：根据挑战计算响应，随机数只能使用一次
+    fn respond(witness: &Self::Witness, nonce: Self::Nonce, challenge: &Secp256k1Scalar) -> Self::Response;
+
+    /// 验证方检查 (承诺, 挑战, 响应) 是否被接受
+    fn check(
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Secp256k1Scalar,
+        response: &Self::Response,
+    ) -> bool;
+
+    /// 把陈述写入转录
+    fn append_statement(transcript: &mut Transcript, statement: &Self::Statement);
+
+    /// 把承诺写入转录
+    fn append_commitment(transcript: &mut Transcript, commitment: &Self::Commitment);
+}
+
+/// Fiat-Shamir 变换得到的非交互证明
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "P::Commitment: Serialize, P::Response: Serialize",
+    deserialize = "P::Commitment: Deserialize<'de>, P::Response: Deserialize<'de>"
+))]
+pub struct SigmaProof<P: SigmaProtocol> {
+    /// 证明方的承诺
+    pub commitment: P::Commitment,
+    /// 对转录挑战的响应
+    pub response: P::Response,
+}
+
+impl<P: SigmaProtocol> SigmaProof<P> {
+    /// 生成证明
+    ///
+    /// # 参数
+    /// - `transcript`: 已写入调用方上下文的转录，验证时必须以相同的状态开始
+    /// - `statement`: 公开陈述
+    /// - `witness`: 证据
+    pub fn prove(transcript: &mut Transcript, statement: &P::Statement, witness: &P::Witness) -> Self {
+        let (nonce, commitment) = P::commit(statement, witness);
+        let challenge = Self::challenge(transcript, statement, &commitment);
+        let response = P::respond(witness, nonce, &challenge);
+        Self { commitment, response }
+    }
+
+    /// 验证证明
+    pub fn verify(&self, transcript: &mut Transcript, statement: &P::Statement) -> bool {
+        let challenge = Self::challenge(transcript, statement, &self.commitment);
+        P::check(statement, &self.commitment, &challenge, &self.response)
+    }
+
+    fn challenge(transcript: &mut Transcript, statement: &P::Statement, commitment: &P::Commitment) -> Secp256k1Scalar {
+        transcript.append_message(b"sigma-protocol", P::LABEL);
+        P::append_statement(transcript, statement);
+        P::append_commitment(transcript, commitment);
+        transcript.challenge_scalar(b"sigma-challenge")
+    }
+}
+
+/// 离散对数陈述：P = x·B
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DlogStatement {
+    /// 底点 B
+    pub base: Secp256k1Point,
+    /// 公开点 P
+    pub public: Secp256k1Point,
+}
+
+impl DlogStatement {
+    /// 以生成元 G 为底的陈述 P = x·G
+    pub fn new(witness: &Secp256k1Scalar) -> Self {
+        let base = Secp256k1Point::generator();
+        Self { base, public: base * *witness }
+    }
+}
+
+/// 离散对数知识证明（Schnorr）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DlogKnowledge;
+
+impl SigmaProtocol for DlogKnowledge {
+    type Statement = DlogStatement;
+    type Witness = Secp256k1Scalar;
+    type Commitment = Secp256k1Point;
+    type Nonce = Secp256k1Scalar;
+    type Response = Secp256k1Scalar;
+
+    const LABEL: &'static [u8] = b"dlog-knowledge";
+
+    fn commit(statement: &DlogStatement, _witness: &Secp256k1Scalar) -> (Secp256k1Scalar, Secp256k1Point) {
+        let k = Secp256k1Scalar::random();
+        (k, statement.base * k)
+    }
+
+    fn respond(witness: &Secp256k1Scalar, nonce: Secp256k1Scalar, challenge: &Secp256k1Scalar) -> Secp256k1Scalar {
+        nonce + *challenge * *witness
+    }
+
+    fn check(statement: &DlogStatement, commitment: &Secp256k1Point, challenge: &Secp256k1Scalar, response: &Secp256k1Scalar) -> bool {
+        // s·B = R + c·P
+        statement.base * *response == *commitment + statement.public * *challenge
+    }
+
+    fn append_statement(transcript: &mut Transcript, statement: &DlogStatement) {
+        transcript.append_point(b"base", &statement.base);
+        transcript.append_point(b"public", &statement.public);
+    }
+
+    fn append_commitment(transcript: &mut Transcript, commitment: &Secp256k1Point) {
+        transcript.append_point(b"commitment", commitment);
+    }
+}
+
+/// 离散对数相等陈述：A = x·G 且 C = x·H
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DlogEqualityStatement {
+    /// 第一个底点 G
+    pub g: Secp256k1Point,
+    /// 第二个底点 H
+    pub h: Secp256k1Point,
+    /// A = x·G
+    pub a: Secp256k1Point,
+    /// C = x·H
+    pub c: Secp256k1Point,
+}
+
+impl DlogEqualityStatement {
+    /// 由两个底点和证据计算陈述
+    pub fn new(g: Secp256k1Point, h: Secp256k1Point, witness: &Secp256k1Scalar) -> Self {
+        Self { g, h, a: g * *witness, c: h * *witness }
+    }
+}
+
+/// 离散对数相等证明（Chaum-Pedersen）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DlogEquality;
+
+impl SigmaProtocol for DlogEquality {
+    type Statement = DlogEqualityStatement;
+    type Witness = Secp256k1Scalar;
+    type Commitment = (Secp256k1Point, Secp256k1Point);
+    type Nonce = Secp256k1Scalar;
+    type Response = Secp256k1Scalar;
+
+    const LABEL: &'static [u8] = b"dlog-equality";
+
+    fn commit(statement: &DlogEqualityStatement, _witness: &Secp256k1Scalar) -> (Secp256k1Scalar, Self::Commitment) {
+        let k = Secp256k1Scalar::random();
+        (k, (statement.g * k, statement.h * k))
+    }
+
+    fn respond(witness: &Secp256k1Scalar, nonce: Secp256k1Scalar, challenge: &Secp256k1Scalar) -> Secp256k1Scalar {
+        nonce + *challenge * *witness
+    }
+
+    fn check(statement: &DlogEqualityStatement, commitment: &Self::Commitment, challenge: &Secp256k1Scalar, response: &Secp256k1Scalar) -> bool {
+        // s·G = R1 + c·A 且 s·H = R2 + c·C
+        let (r1, r2) = commitment;
+        statement.g * *response == *r1 + statement.a * *challenge
+            && statement.h * *response == *r2 + statement.c * *challenge
+    }
+
+    fn append_statement(transcript: &mut Transcript, statement: &DlogEqualityStatement) {
+        transcript.append_point(b"g", &statement.g);
+        transcript.append_point(b"h", &statement.h);
+        transcript.append_point(b"a", &statement.a);
+        transcript.append_point(b"c", &statement.c);
+    }
+
+    fn append_commitment(transcript: &mut Transcript, commitment: &Self::Commitment) {
+        transcript.append_point(b"commitment-g", &commitment.0);
+        transcript.append_point(b"commitment-h", &commitment.1);
+    }
+}
+
+/// 非交互离散对数知识证明
+pub type DlogProof = SigmaProof<DlogKnowledge>;
+
+/// 非交互离散对数相等证明
+pub type DlogEqualityProof = SigmaProof<DlogEquality>;
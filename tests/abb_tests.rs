@@ -0,0 +1,100 @@
+//! 算术黑盒测试
+//!
+//! 同一组应用层协议分别在模拟、Shamir 和 SPDZ 后端上运行
+
+use mpc_api::protocols::abb::*;
+use mpc_api::spdz::SPDZParams;
+use mpc_api::MpcError;
+
+async fn input_all<A: ArithmeticBlackBox>(abb: &A, values: &[u64]) -> Vec<A::Value> {
+    let mut shared = Vec::new();
+    for (i, value) in values.iter().enumerate() {
+        shared.push(abb.input(i % abb.parties(), *value).await.unwrap());
+    }
+    shared
+}
+
+/// 针对 trait 编写一次的检查：算术、内积和样本矩
+async fn check_backend<A: ArithmeticBlackBox>(abb: &A) {
+    let x = abb.input(0, 6).await.unwrap();
+    let y = abb.input(1, 7).await.unwrap();
+    let seven = abb.constant(7).await.unwrap();
+
+    let product = abb.mul(&x, &y).await.unwrap();
+    assert_eq!(abb.open(&product).await.unwrap(), 42);
+    let diff = abb.sub(&y, &seven).await.unwrap();
+    assert_eq!(abb.open(&diff).await.unwrap(), 0);
+    let scaled = abb.mul_public(&x, 5).await.unwrap();
+    let sum = abb.add(&scaled, &y).await.unwrap();
+    assert_eq!(abb.open(&sum).await.unwrap(), 37);
+
+    // 随机值与自身相减为零
+    let r = abb.random().await.unwrap();
+    let zero = abb.sub(&r, &r).await.unwrap();
+    assert_eq!(abb.open(&zero).await.unwrap(), 0);
+
+    let weights = input_all(abb, &[1, 2, 3]).await;
+    let features = input_all(abb, &[4, 5, 6]).await;
+    let score = abb_inner_product(abb, &weights, &features).await.unwrap();
+    assert_eq!(abb.open(&score).await.unwrap(), 32);
+
+    let samples = input_all(abb, &[2, 4, 4, 4, 5, 5, 7, 9]).await;
+    let moments = abb_moments(abb, &samples).await.unwrap();
+    assert_eq!((moments.count, moments.sum, moments.sum_of_squares), (8, 40, 232));
+    assert_eq!(moments.mean(), 5.0);
+    assert_eq!(moments.variance(), 4.0);
+
+    let empty = abb_sum(abb, &[]).await.unwrap();
+    assert_eq!(abb.open(&empty).await.unwrap(), 0);
+    assert!(abb.input(abb.parties(), 1).await.is_err());
+    assert!(abb.mul_batch(&weights, &features[..2]).await.is_err());
+}
+
+// ===== Backend Tests =====
+
+#[tokio::test]
+async fn test_simulated_backend() {
+    let abb = SimulatedBlackBox::new(3);
+    check_backend(&abb).await;
+
+    let stats = abb.stats();
+    assert_eq!(stats.total_bytes(), 0);
+    assert!(stats.rounds > 0);
+}
+
+#[tokio::test]
+async fn test_shamir_backend() {
+    let abb = ShamirBlackBox::new(5, 3).unwrap();
+    check_backend(&abb).await;
+    assert_eq!(abb.stats().preprocessing_consumed, 0);
+    assert!(abb.stats().bytes_sent > 0);
+
+    // 诚实多数要求 n ≥ 2t - 1
+    assert!(matches!(ShamirBlackBox::new(4, 3), Err(MpcError::InvalidThreshold)));
+    assert!(ShamirBlackBox::new(3, 0).is_err());
+}
+
+#[tokio::test]
+async fn test_spdz_backend() {
+    let abb = SpdzBlackBox::new(SPDZParams::new(3, 0, 2)).unwrap();
+    check_backend(&abb).await;
+    assert!(abb.stats().preprocessing_consumed > 0);
+}
+
+#[tokio::test]
+async fn test_batched_multiplication_rounds() {
+    // 模拟后端与 Shamir 后端记录的轮数一致，可以用模拟后端估算开销
+    let simulated = SimulatedBlackBox::new(3);
+    let shamir = ShamirBlackBox::new(3, 2).unwrap();
+
+    let a = input_all(&simulated, &[1, 2, 3, 4]).await;
+    let before = simulated.stats().rounds;
+    abb_inner_product(&simulated, &a, &a).await.unwrap();
+    assert_eq!(simulated.stats().rounds - before, 1);
+
+    let b = input_all(&shamir, &[1, 2, 3, 4]).await;
+    let before = shamir.stats().rounds;
+    let result = abb_inner_product(&shamir, &b, &b).await.unwrap();
+    assert_eq!(shamir.stats().rounds - before, 1);
+    assert_eq!(shamir.open(&result).await.unwrap(), 30);
+}
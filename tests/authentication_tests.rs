@@ -5,10 +5,14 @@
 //! - CMAC (Cipher-based Message Authentication Code) - 基于密码的消息认证码  
 //! - GMAC (Galois Message Authentication Code) - 伽罗华消息认证码
 //! - Poly1305 - 高性能消息认证码
+//! - Nonce 管理 - 一次性密钥的 nonce 序列、重放检测和 SIV 认证加密
 //! 
 //! 每个算法都测试了密钥生成、认证、验证、错误检测等核心功能。
 
 use mpc_api::authentication::{MessageAuthenticationCode, HMAC, HmacTag, CMAC, GMAC, Poly1305};
+use mpc_api::authentication::{NonceManagedMac, NonceSequence, SivAead};
+use mpc_api::storage::MemoryBackend;
+use std::sync::Arc;
 
 // ===== HMAC Tests =====
 // HMAC是基于哈希函数的消息认证码，广泛用于验证消息完整性和真实性
@@ -92,7 +96,7 @@ fn test_hmac_u64() {
     let value = 12345u64;
     
     // 计算u64值的HMAC标签
-    let tag_bytes = HMAC::compute_hmac_u64(&key.key, value);
+    let tag_bytes = HMAC::compute_hmac_u64(&key.key[..], value);
     let tag = HmacTag { tag: tag_bytes };
     // 验证u64值的HMAC标签
     let verification = HMAC::verify_u64(&key, value, &tag);
@@ -800,4 +804,84 @@ fn test_poly1305_large_message() {
     let verification = Poly1305::verify(&key, &large_message, &tag);
     
     assert!(verification);
-}
\ No newline at end of file
+}
+
+// ===== Nonce Management Tests =====
+// 一次性 MAC 的密钥由主密钥和 nonce 派生，nonce 必须唯一
+
+/// 测试持久化 nonce 序列在重新打开后继续计数
+///
+/// 目的：验证进程重启后不会重新发出已用过的 nonce
+/// 预期：重新打开的序列沿用同一前缀，计数器从上次的位置继续
+#[test]
+fn test_nonce_sequence_persists_counter() {
+    let backend = Arc::new(MemoryBackend::new());
+
+    let mut first = NonceSequence::persistent(backend.clone(), b"poly-key").unwrap();
+    let issued: Vec<_> = (0..3).map(|_| first.next_nonce().unwrap()).collect();
+    drop(first);
+
+    let mut reopened = NonceSequence::persistent(backend.clone(), b"poly-key").unwrap();
+    assert_eq!(reopened.issued(), 3);
+    let next = reopened.next_nonce().unwrap();
+    assert!(!issued.contains(&next));
+    assert_eq!(next[..8], issued[0][..8]);
+
+    // 不同密钥使用独立的序列
+    let other = NonceSequence::persistent(backend, b"gmac-key").unwrap();
+    assert_eq!(other.issued(), 0);
+}
+
+/// 测试 nonce 托管的 Poly1305/GMAC 拒绝重复使用和重放
+///
+/// 目的：验证同一 nonce 不会被用于认证两条消息，也不会被接受两次
+/// 预期：正常消息通过验证；重复 nonce 和重放返回错误；篡改的消息验证失败且不占用 nonce
+#[test]
+fn test_nonce_managed_mac_rejects_reuse_and_replay() {
+    let master_key = [42u8; 32];
+
+    let mut sender = NonceManagedMac::<Poly1305>::new(&master_key, NonceSequence::new()).unwrap();
+    let mut receiver = NonceManagedMac::<Poly1305>::new(&master_key, NonceSequence::new()).unwrap();
+    let (nonce1, tag1) = sender.authenticate(b"first").unwrap();
+    let (nonce2, tag2) = sender.authenticate(b"second").unwrap();
+    assert_ne!(nonce1, nonce2);
+
+    assert!(!receiver.verify(&nonce1, b"tampered", &tag1).unwrap());
+    assert!(receiver.verify(&nonce1, b"first", &tag1).unwrap());
+    assert!(receiver.verify(&nonce1, b"first", &tag1).is_err());
+    assert!(receiver.verify(&nonce2, b"second", &tag2).unwrap());
+    assert!(sender.authenticate_with_nonce(&nonce1, b"other").is_err());
+
+    let mut gmac = NonceManagedMac::<GMAC>::new(&master_key, NonceSequence::new()).unwrap();
+    let mut gmac_receiver = NonceManagedMac::<GMAC>::new(&master_key, NonceSequence::new()).unwrap();
+    let (nonce, tag) = gmac.authenticate(b"gmac message").unwrap();
+    assert!(gmac_receiver.verify(&nonce, b"gmac message", &tag).unwrap());
+
+    assert!(NonceManagedMac::<Poly1305>::new(b"short", NonceSequence::new()).is_err());
+}
+
+/// 测试 SIV 认证加密在 nonce 重复时的行为
+///
+/// 目的：验证加解密正确、篡改被检测，以及 nonce 重复时不同明文仍得到不同密文
+/// 预期：相同输入得到相同密文，不同明文的合成 IV 不同；修改密文、关联数据或 nonce 都无法解密
+#[test]
+fn test_siv_aead_misuse_resistance() {
+    let aead = SivAead::new(&[9u8; 32]).unwrap();
+    let nonce = [1u8; 16];
+
+    let sealed = aead.seal(&nonce, b"aad", b"attack at dawn");
+    assert_ne!(sealed.ciphertext, b"attack at dawn");
+    assert_eq!(aead.open(&nonce, b"aad", &sealed).unwrap(), b"attack at dawn");
+
+    assert_eq!(aead.seal(&nonce, b"aad", b"attack at dawn"), sealed);
+    let other = aead.seal(&nonce, b"aad", b"attack at dusk");
+    assert_ne!(other.siv, sealed.siv);
+
+    let mut tampered = sealed.clone();
+    tampered.ciphertext[0] ^= 1;
+    assert!(aead.open(&nonce, b"aad", &tampered).is_err());
+    assert!(aead.open(&nonce, b"other", &sealed).is_err());
+    assert!(aead.open(&[2u8; 16], b"aad", &sealed).is_err());
+
+    assert!(SivAead::new(&[0u8; 16]).is_err());
+}
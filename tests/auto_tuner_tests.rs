@@ -0,0 +1,129 @@
+use mpc_api::beaver_triples::auto_tuner::*;
+use mpc_api::network::ConnectionStats;
+use std::time::Duration;
+
+fn benchmark(kind: TripleGeneratorKind, micros_per_triple: u64, rounds: usize) -> GeneratorBenchmark {
+    GeneratorBenchmark {
+        kind,
+        samples: 4,
+        time_per_triple: Duration::from_micros(micros_per_triple),
+        bytes_per_triple: 1_000,
+        rounds_per_batch: rounds,
+    }
+}
+
+#[test]
+fn test_plan_prefers_fewest_workers_that_meet_deadline() {
+    let tuner = PreprocessingAutoTuner::new(3, 2).unwrap().with_max_parallelism(8);
+    let benchmarks = vec![
+        benchmark(TripleGeneratorKind::Bfv, 5_000, 1),
+        benchmark(TripleGeneratorKind::Ole, 1_000, 2),
+    ];
+
+    // 10000 个三元组：OLE 单线程 10 秒，BFV 需要 50 秒
+    let target = PreprocessingTarget::new(10_000, Duration::from_secs(20));
+    let decision = tuner.plan(&target, &benchmarks).unwrap();
+    assert_eq!(decision.generator, TripleGeneratorKind::Ole);
+    assert_eq!(decision.parallelism, 1);
+    assert!(decision.meets_deadline);
+    assert_eq!(decision.benchmarks.len(), 2);
+
+    // 截止时间更紧时增加并行度
+    let target = PreprocessingTarget::new(10_000, Duration::from_secs(3));
+    let decision = tuner.plan(&target, &benchmarks).unwrap();
+    assert_eq!(decision.generator, TripleGeneratorKind::Ole);
+    assert_eq!(decision.parallelism, 4);
+    assert!(decision.estimated_time <= target.deadline);
+
+    // 无法满足时选择最快的方案并如实标记
+    let target = PreprocessingTarget::new(10_000, Duration::from_millis(100));
+    let decision = tuner.plan(&target, &benchmarks).unwrap();
+    assert_eq!(decision.parallelism, 8);
+    assert!(!decision.meets_deadline);
+}
+
+#[test]
+fn test_plan_amortizes_network_latency() {
+    let benchmarks = vec![benchmark(TripleGeneratorKind::Ole, 100, 2)];
+    let target = PreprocessingTarget::new(100_000, Duration::from_secs(600));
+
+    let local = PreprocessingAutoTuner::new(3, 2).unwrap().with_max_parallelism(1);
+    assert_eq!(local.plan(&target, &benchmarks).unwrap().batch_size, MIN_TUNED_BATCH);
+
+    // 50ms 延迟、每批 2 轮：批计算时间至少为 9 × 100ms
+    let wan = NetworkProfile::new(Duration::from_millis(50), 100_000_000).unwrap();
+    let tuner = local.clone().with_network(wan);
+    let decision = tuner.plan(&target, &benchmarks).unwrap();
+    assert!(decision.batch_size > MIN_TUNED_BATCH);
+    assert!(decision.batch_size <= MAX_TUNED_BATCH);
+
+    assert!(NetworkProfile::new(Duration::from_millis(1), 0).is_err());
+    assert!(tuner.plan(&PreprocessingTarget::new(0, Duration::from_secs(1)), &benchmarks).is_err());
+    assert!(tuner.plan(&target, &[]).is_err());
+}
+
+#[test]
+fn test_benchmark_skips_unsupported_generators() {
+    let tuner = PreprocessingAutoTuner::new(3, 2).unwrap()
+        .with_candidates(vec![TripleGeneratorKind::Ole, TripleGeneratorKind::OtGilboa])
+        .with_benchmark_samples(2);
+    let benchmarks = tuner.benchmark().unwrap();
+    assert_eq!(benchmarks.len(), 1);
+    assert_eq!(benchmarks[0].kind, TripleGeneratorKind::Ole);
+
+    let only_ot = tuner.with_candidates(vec![TripleGeneratorKind::OtGilboa]);
+    assert!(only_ot.benchmark().is_err());
+}
+
+#[test]
+fn test_scheduled_generation_and_metrics() {
+    let tuner = PreprocessingAutoTuner::new(3, 2).unwrap()
+        .with_candidates(vec![TripleGeneratorKind::TrustedParty])
+        .with_max_parallelism(3);
+    let target = PreprocessingTarget::new(25, Duration::from_secs(60));
+    let mut decision = tuner.tune(&target).unwrap();
+    assert_eq!(decision.generator, TripleGeneratorKind::TrustedParty);
+
+    // 强制使用多个工作线程和较小的批
+    decision.parallelism = 3;
+    decision.batch_size = 4;
+    let job = tuner.schedule(&decision).unwrap();
+    assert_eq!(job.decision().parallelism, 3);
+    let output = job.join().unwrap();
+
+    assert_eq!(output.result.len(), 25);
+    let mut ids: Vec<u64> = output.result.iter()
+        .map(|triple| triple.shares.values().next().unwrap().id)
+        .collect();
+    ids.dedup();
+    assert_eq!(ids, (0..25).collect::<Vec<_>>());
+    assert!(output.result.iter().all(|triple| triple.verify(2).unwrap()));
+
+    let mut stats = ConnectionStats::default();
+    stats.record_preprocessing_plan(&decision);
+    stats.record_protocol_stats(&output.stats);
+    assert_eq!(stats.preprocessing_plans, 1);
+    assert_eq!(stats.last_preprocessing_plan.as_ref().map(|plan| plan.generator), Some(TripleGeneratorKind::TrustedParty));
+    assert_eq!(stats.protocol_runs, 1);
+}
+
+#[test]
+fn test_scheduled_generation_cancellation() {
+    use mpc_api::utils::concurrency::CancellationToken;
+
+    let tuner = PreprocessingAutoTuner::new(3, 2).unwrap()
+        .with_candidates(vec![TripleGeneratorKind::TrustedParty]);
+    let mut decision = tuner.tune(&PreprocessingTarget::new(10_000, Duration::from_secs(60))).unwrap();
+    decision.parallelism = 2;
+    decision.batch_size = 8;
+
+    let parent = CancellationToken::new();
+    parent.cancel("session aborted");
+    let job = tuner.schedule_with_cancellation(&decision, &parent).unwrap();
+    let error = job.join().unwrap_err();
+    assert!(error.to_string().contains("session aborted"));
+
+    let job = tuner.schedule(&decision).unwrap();
+    job.cancel();
+    assert!(job.join().is_err());
+}
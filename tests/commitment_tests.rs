@@ -4,6 +4,9 @@
 //! - Hash承诺 (Hash Commitment) - 基于密码学哈希函数的承诺
 //! - Pedersen承诺 (Pedersen Commitment) - 基于椭圆曲线的同态承诺
 //! - Merkle树承诺 (Merkle Tree Commitment) - 基于Merkle树的批量承诺
+//! - 向量承诺 (Vector Commitment) - 位置绑定、可批量选择性打开的向量承诺
+//! - Merkle累加器 (Merkle Accumulator) - 只追加的 Merkle 树，支持历史包含证明和一致性证明
+//! - KZG多项式承诺 (KZG Polynomial Commitment) - 常数大小的求值证明和 eVSS（需要 `zk` 特性）
 //! 
 //! 承诺方案是密码学的基础原语，提供隐藏性和绑定性保证：
 //! - 隐藏性：承诺不会泄露承诺值的信息
@@ -522,4 +525,293 @@ fn test_merkle_tree_single_leaf() {
     let proof = tree.generate_proof(0).unwrap();
     let verification = MerkleTree::verify_proof(tree.get_root(), &data[0], &proof).unwrap();
     assert!(verification);
-}
\ No newline at end of file
+}
+// ===== Committed Message Tests =====
+
+#[test]
+fn test_committed_message_hash_roundtrip() {
+    let context = b"session-1";
+    let committed = CommittedMessage::commit((42u64, "payload".to_string()), context).unwrap();
+    let commitment = committed.commitment().clone();
+    assert_eq!(commitment.kind(), CommitmentKind::Hash);
+
+    let opening = committed.open();
+    let value = commitment.open_verified(opening, context).unwrap();
+    assert_eq!(value, (42u64, "payload".to_string()));
+}
+
+#[test]
+fn test_committed_message_pedersen_roundtrip() {
+    let context = b"session-2";
+    let committed = CommittedMessage::commit_with(CommitmentKind::Pedersen, vec![7u64, 8, 9], context).unwrap();
+    let commitment = committed.commitment().clone();
+    assert_eq!(commitment.kind(), CommitmentKind::Pedersen);
+
+    let opening = committed.open();
+    assert!(commitment.verify(&opening, context).unwrap());
+    assert_eq!(opening.randomness.len(), 32);
+
+    // 值、随机数或上下文不符都会被拒绝
+    let mut wrong_value = opening.clone();
+    wrong_value.value[0] = 8;
+    assert!(!commitment.verify(&wrong_value, context).unwrap());
+    let mut wrong_randomness = opening.clone();
+    wrong_randomness.randomness[31] ^= 1;
+    assert!(!commitment.verify(&wrong_randomness, context).unwrap());
+    let mut truncated = opening.clone();
+    truncated.randomness.truncate(8);
+    assert!(!commitment.verify(&truncated, context).unwrap());
+    assert!(!commitment.verify(&opening, b"session-3").unwrap());
+}
+
+#[test]
+fn test_committed_message_rejects_tampered_value() {
+    let context = b"session-3";
+    let committed = CommittedMessage::commit(100u64, context).unwrap();
+    let commitment = committed.commitment().clone();
+
+    let mut opening = committed.open();
+    opening.value = 101;
+    assert!(!commitment.verify(&opening, context).unwrap());
+    assert!(commitment.open_verified(opening, context).is_err());
+}
+
+#[test]
+fn test_committed_message_bound_to_context() {
+    let committed = CommittedMessage::commit(true, b"session-a").unwrap();
+    let commitment = committed.commitment().clone();
+    let opening = committed.open();
+
+    assert!(!commitment.verify(&opening, b"session-b").unwrap());
+    assert!(commitment.verify(&opening, b"session-a").unwrap());
+}
+
+// ===== Vector Commitment Tests =====
+
+#[test]
+fn test_vector_commitment_open_every_subset() {
+    // 覆盖长度为 2 的幂、非 2 的幂和长度 1 的情况
+    for length in [1usize, 2, 5, 8] {
+        let values: Vec<u64> = (0..length as u64).map(|i| i * 1000 + 7).collect();
+        let committer = VectorCommitter::commit_vector(&values).unwrap();
+        let commitment = committer.commitment();
+        assert_eq!(commitment.length, length);
+        assert_eq!(committer.values(), values.as_slice());
+
+        for mask in 1u32..(1 << length) {
+            let indices: Vec<usize> = (0..length).filter(|i| mask >> i & 1 == 1).collect();
+            let opening = committer.open_at(&indices).unwrap();
+            assert!(commitment.verify_opening(&opening), "length {} indices {:?}", length, indices);
+            assert_eq!(opening.values(), indices.iter().map(|&i| (i, values[i])).collect::<Vec<_>>());
+        }
+    }
+}
+
+#[test]
+fn test_vector_commitment_batch_opening() {
+    let values: Vec<u64> = (0..64).collect();
+    let committer = VectorCommitter::commit_vector(&values).unwrap();
+    let commitment = committer.commitment();
+
+    // 无序、重复的下标会被排序去重
+    let opening = committer.open_at(&[9, 3, 9, 4]).unwrap();
+    assert_eq!(opening.values(), vec![(3, 3), (4, 4), (9, 9)]);
+    assert!(commitment.verify_opening(&opening));
+
+    // 相邻位置共享认证路径：一整棵子树只需要到根的路径
+    let block = committer.open_at(&(16..32).collect::<Vec<_>>()).unwrap();
+    assert!(commitment.verify_opening(&block));
+    assert_eq!(block.siblings.len(), 2);
+    let single = committer.open_at(&[16]).unwrap();
+    assert_eq!(single.siblings.len(), 6);
+    let everything = committer.open_at(&values.iter().map(|&v| v as usize).collect::<Vec<_>>()).unwrap();
+    assert!(everything.siblings.is_empty());
+    assert!(commitment.verify_opening(&everything));
+
+    assert!(committer.open_at(&[]).is_err());
+    assert!(committer.open_at(&[64]).is_err());
+    assert!(VectorCommitter::commit_vector(&[]).is_err());
+}
+
+#[test]
+fn test_vector_commitment_binding() {
+    let committer = VectorCommitter::commit_vector(&[5, 6, 7, 8, 9]).unwrap();
+    let commitment = committer.commitment();
+    let opening = committer.open_at(&[1, 2]).unwrap();
+
+    // 修改值、盐或位置都会使验证失败
+    let mut tampered = opening.clone();
+    tampered.positions[0].value = 60;
+    assert!(!commitment.verify_opening(&tampered));
+    let mut tampered = opening.clone();
+    tampered.positions[1].salt[0] ^= 1;
+    assert!(!commitment.verify_opening(&tampered));
+    let mut moved = opening.clone();
+    moved.positions[0].index = 0;
+    assert!(!commitment.verify_opening(&moved));
+    let mut reordered = opening.clone();
+    reordered.positions.swap(0, 1);
+    assert!(!commitment.verify_opening(&reordered));
+
+    // 兄弟节点多一个或少一个都不接受
+    let mut extra = opening.clone();
+    extra.siblings.push([0u8; 32]);
+    assert!(!commitment.verify_opening(&extra));
+    let mut missing = opening.clone();
+    missing.siblings.pop();
+    assert!(!commitment.verify_opening(&missing));
+
+    // 承诺同时绑定长度：同一打开不能针对不同长度的承诺验证
+    let resized = VectorCommitment { length: 8, ..commitment };
+    assert!(!resized.verify_opening(&opening));
+
+    // 同一向量的两次承诺使用不同的盐，承诺不泄露值
+    let again = VectorCommitter::commit_vector(&[5, 6, 7, 8, 9]).unwrap();
+    assert_ne!(again.commitment(), commitment);
+    assert!(!again.commitment().verify_opening(&opening));
+
+    let bytes = bincode::serialize(&opening).unwrap();
+    let decoded: VectorOpening = bincode::deserialize(&bytes).unwrap();
+    assert!(commitment.verify_opening(&decoded));
+    assert_eq!(decoded.value_at(2), Some(7));
+    assert_eq!(decoded.value_at(3), None);
+}
+
+#[cfg(feature = "zk")]
+#[test]
+fn test_kzg_commit_open_verify() {
+    let setup = KzgSetup::generate(8).unwrap();
+    assert_eq!(setup.max_degree(), 8);
+
+    // f(x) = 3 + 2x + x^2
+    let coefficients: Vec<KzgScalar> = [3u64, 2, 1].iter().map(|&c| KzgScalar::from(c)).collect();
+    let commitment = setup.commit(&coefficients).unwrap();
+    let point = KzgScalar::from(5u64);
+    let opening = setup.open(&coefficients, &point).unwrap();
+    assert_eq!(opening.value, KzgScalar::from(38u64));
+    assert!(setup.verify(&commitment, &point, &opening));
+
+    // 修改值、求值点或承诺都会使验证失败
+    assert!(!setup.verify(&commitment, &point, &KzgOpening { value: KzgScalar::from(39u64), ..opening }));
+    assert!(!setup.verify(&commitment, &point, &KzgOpening { point: KzgScalar::from(6u64), ..opening }));
+    let other = setup.commit(&coefficients[..2]).unwrap();
+    assert!(!setup.verify(&other, &point, &opening));
+
+    // 在其他点上的有效打开不能冒充期望点上的打开
+    let elsewhere = setup.open(&coefficients, &KzgScalar::from(6u64)).unwrap();
+    assert!(setup.verify(&commitment, &KzgScalar::from(6u64), &elsewhere));
+    assert!(!setup.verify(&commitment, &point, &elsewhere));
+
+    // 超过设置次数的多项式被拒绝
+    let too_long = vec![KzgScalar::from(1u64); 10];
+    assert!(setup.commit(&too_long).is_err());
+    assert!(setup.open(&too_long, &KzgScalar::from(1u64)).is_err());
+
+    let bytes = bincode::serialize(&(setup.clone(), commitment, opening)).unwrap();
+    let decoded: (KzgSetup, KzgCommitment, KzgOpening) = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(decoded, (setup.clone(), commitment, opening));
+    assert!(decoded.0.verify(&decoded.1, &point, &decoded.2));
+}
+
+#[cfg(feature = "zk")]
+#[test]
+fn test_kzg_verifiable_secret_sharing() {
+    let setup = KzgSetup::generate(4).unwrap();
+    let secret = KzgScalar::from(123_456_789u64);
+    let (commitment, shares) = setup.share_secret(&secret, 3, 5).unwrap();
+    assert_eq!(shares.len(), 5);
+
+    // 所有份额一次批量验证；篡改任一份额后批量验证失败
+    let batch: Vec<_> = shares.iter()
+        .enumerate()
+        .map(|(i, share)| (commitment, KzgScalar::from(i as u64 + 1), *share))
+        .collect();
+    assert!(setup.verify_batch(&batch));
+    let mut tampered = batch.clone();
+    tampered[3].2.value += KzgScalar::from(1u64);
+    assert!(!setup.verify_batch(&tampered));
+
+    // 把第 1 方的有效份额当作第 2 方的份额出示会被拒绝；空批次不算验证通过
+    let mut swapped = batch.clone();
+    swapped[1].2 = shares[0];
+    assert!(!setup.verify_batch(&swapped));
+    assert!(!setup.verify_batch(&[]));
+
+    // 任意门限个份额都能重构秘密，重复的求值点被拒绝
+    let zero = KzgScalar::from(0u64);
+    assert_eq!(KzgOpening::interpolate(&shares[..3], &zero).unwrap(), secret);
+    assert_eq!(KzgOpening::interpolate(&[shares[0], shares[2], shares[4]], &zero).unwrap(), secret);
+    assert!(KzgOpening::interpolate(&[shares[0], shares[0]], &zero).is_err());
+    assert!(KzgOpening::interpolate(&[], &zero).is_err());
+
+    assert!(setup.share_secret(&secret, 0, 5).is_err());
+    assert!(setup.share_secret(&secret, 6, 5).is_err());
+    assert!(setup.share_secret(&secret, 6, 8).is_err());
+}
+
+#[test]
+fn test_merkle_accumulator_proofs_for_every_size() {
+    let leaves: Vec<Vec<u8>> = (0..20u32).map(|i| format!("leaf-{}", i).into_bytes()).collect();
+    let mut accumulator = MerkleAccumulator::new();
+    let mut frontier = MerkleFrontier::new();
+    let mut roots = vec![accumulator.root()];
+    for leaf in &leaves {
+        accumulator.append(leaf);
+        frontier.append(leaf);
+        assert_eq!(frontier.root(), accumulator.root());
+        assert_eq!(accumulator.frontier(), frontier);
+        roots.push(accumulator.root());
+    }
+
+    for new_size in 1..=leaves.len() {
+        assert_eq!(accumulator.root_at(new_size).unwrap(), roots[new_size]);
+        for index in 0..new_size {
+            let proof = accumulator.prove_inclusion(index, new_size).unwrap();
+            assert!(proof.verify(&roots[new_size], &leaves[index]));
+            assert!(!proof.verify(&roots[new_size], &leaves[(index + 1) % leaves.len()]));
+        }
+        for old_size in 0..=new_size {
+            let proof = accumulator.prove_consistency(old_size, new_size).unwrap();
+            assert!(proof.verify(&roots[old_size], &roots[new_size]));
+            if old_size > 0 && old_size < new_size {
+                assert!(!proof.verify(&roots[old_size - 1], &roots[new_size]));
+                assert!(!proof.verify(&roots[old_size], &roots[new_size - 1]));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_merkle_accumulator_rfc6962_hashing() {
+    // 空树的根是 SHA-256("")，单叶子树的根是 SHA-256(0x00 || data)
+    use sha2::{Digest, Sha256};
+    let mut accumulator = MerkleAccumulator::new();
+    let empty: [u8; 32] = Sha256::digest([]).into();
+    assert_eq!(accumulator.root(), empty);
+    accumulator.append(b"entry");
+    let leaf: [u8; 32] = Sha256::digest(b"\x00entry").into();
+    assert_eq!(accumulator.root(), leaf);
+
+    for i in 1..7u8 {
+        accumulator.append(&[i]);
+    }
+    let mut proof = accumulator.prove_inclusion(2, 7).unwrap();
+    let root = accumulator.root();
+    assert!(proof.verify(&root, &[2]));
+
+    // 修改树大小、下标或路径都会使验证失败
+    assert!(!InclusionProof { tree_size: 4, ..proof.clone() }.verify(&root, &[2]));
+    assert!(!InclusionProof { leaf_index: 3, ..proof.clone() }.verify(&root, &[2]));
+    proof.path.push([0u8; 32]);
+    assert!(!proof.verify(&root, &[2]));
+
+    let consistency = accumulator.prove_consistency(3, 7).unwrap();
+    let old_root = accumulator.root_at(3).unwrap();
+    assert!(!ConsistencyProof { new_size: 4, ..consistency.clone() }.verify(&old_root, &root));
+    assert!(accumulator.prove_consistency(5, 4).is_err());
+    assert!(accumulator.prove_inclusion(0, 8).is_err());
+
+    let bytes = bincode::serialize(&consistency).unwrap();
+    let decoded: ConsistencyProof = bincode::deserialize(&bytes).unwrap();
+    assert!(decoded.verify(&old_root, &root));
+}
@@ -0,0 +1,324 @@
+use mpc_api::coordination::*;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+fn exercise_backend(backend: &dyn CoordinationBackend) {
+    assert_eq!(backend.get("a/1").unwrap(), None);
+    backend.put("a/2", b"two", None).unwrap();
+    backend.put("a/1", b"one", None).unwrap();
+    backend.put("b/1", b"other", None).unwrap();
+    assert_eq!(backend.get("a/1").unwrap(), Some(b"one".to_vec()));
+
+    assert!(!backend.compare_and_swap("a/1", None, b"x").unwrap());
+    assert!(!backend.compare_and_swap("a/1", Some(b"wrong"), b"x").unwrap());
+    assert!(backend.compare_and_swap("a/1", Some(b"one"), b"uno").unwrap());
+    assert!(backend.compare_and_swap("a/3", None, b"three").unwrap());
+    assert_eq!(
+        backend.list("a/").unwrap(),
+        vec![
+            ("a/1".to_string(), b"uno".to_vec()),
+            ("a/2".to_string(), b"two".to_vec()),
+            ("a/3".to_string(), b"three".to_vec()),
+        ]
+    );
+
+    assert!(backend.delete("a/2").unwrap());
+    assert!(!backend.delete("a/2").unwrap());
+    assert_eq!(backend.list("a/").unwrap().len(), 2);
+    assert!(backend.list("missing/").unwrap().is_empty());
+}
+
+// ===== Backend Tests =====
+
+#[test]
+fn test_memory_coordination() {
+    let backend = MemoryCoordination::new();
+    exercise_backend(&backend);
+
+    backend.put("ttl", b"short", Some(Duration::from_millis(20))).unwrap();
+    assert!(backend.get("ttl").unwrap().is_some());
+    thread::sleep(Duration::from_millis(40));
+    assert_eq!(backend.get("ttl").unwrap(), None);
+    assert!(backend.compare_and_swap("ttl", None, b"again").unwrap());
+}
+
+#[cfg(feature = "coordination")]
+mod fake_servers {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::collections::BTreeMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Mutex;
+
+    fn read_command(reader: &mut impl BufRead) -> Option<Vec<Vec<u8>>> {
+        let mut line = String::new();
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+        let count: usize = line.trim()[1..].parse().ok()?;
+        (0..count)
+            .map(|_| {
+                let mut header = String::new();
+                reader.read_line(&mut header).ok()?;
+                let length: usize = header.trim()[1..].parse().ok()?;
+                let mut data = vec![0u8; length + 2];
+                reader.read_exact(&mut data).ok()?;
+                data.truncate(length);
+                Some(data)
+            })
+            .collect()
+    }
+
+    fn bulk(value: Option<&Vec<u8>>) -> Vec<u8> {
+        match value {
+            Some(value) => [format!("${}\r\n", value.len()).into_bytes(), value.clone(), b"\r\n".to_vec()].concat(),
+            None => b"$-1\r\n".to_vec(),
+        }
+    }
+
+    /// 支持 GET/SET/DEL/EVAL/SCAN 的最小 Redis 服务，TTL 被忽略
+    fn spawn_redis() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut data: BTreeMap<Vec<u8>, Vec<u8>> = BTreeMap::new();
+            while let Some(args) = read_command(&mut reader) {
+                let reply = match args[0].as_slice() {
+                    b"GET" => bulk(data.get(&args[1])),
+                    b"SET" if args.get(3).map(Vec::as_slice) == Some(b"NX") => {
+                        if data.contains_key(&args[1]) {
+                            bulk(None)
+                        } else {
+                            data.insert(args[1].clone(), args[2].clone());
+                            b"+OK\r\n".to_vec()
+                        }
+                    }
+                    b"SET" => {
+                        data.insert(args[1].clone(), args[2].clone());
+                        b"+OK\r\n".to_vec()
+                    }
+                    b"DEL" => format!(":{}\r\n", data.remove(&args[1]).is_some() as i64).into_bytes(),
+                    b"EVAL" => {
+                        let swapped = data.get(&args[3]) == Some(&args[4]);
+                        if swapped {
+                            data.insert(args[3].clone(), args[5].clone());
+                        }
+                        format!(":{}\r\n", swapped as i64).into_bytes()
+                    }
+                    b"SCAN" => {
+                        let prefix = &args[3][..args[3].len() - 1];
+                        let keys: Vec<u8> = data.keys()
+                            .filter(|key| key.starts_with(prefix))
+                            .flat_map(|key| bulk(Some(key)))
+                            .collect();
+                        let count = data.keys().filter(|key| key.starts_with(prefix)).count();
+                        [b"*2\r\n$1\r\n0\r\n".to_vec(), format!("*{}\r\n", count).into_bytes(), keys].concat()
+                    }
+                    _ => b"-ERR unknown command\r\n".to_vec(),
+                };
+                writer.write_all(&reply).unwrap();
+            }
+        });
+        address
+    }
+
+    fn http_reply(writer: &mut impl Write, body: &Value) {
+        // 分块传输，覆盖客户端的 chunked 解码
+        let body = body.to_string();
+        let (first, second) = body.split_at(body.len() / 2);
+        write!(
+            writer,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+            first.len(), first, second.len(), second
+        ).unwrap();
+    }
+
+    /// 支持 range/put/txn/deleterange/lease 的最小 etcd 网关，租约被忽略
+    fn spawn_etcd() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let data: Arc<Mutex<BTreeMap<String, String>>> = Arc::default();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = header.to_ascii_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0u8; length];
+                reader.read_exact(&mut body).unwrap();
+                let request: Value = serde_json::from_slice(&body).unwrap();
+                let path = request_line.split_whitespace().nth(1).unwrap().to_string();
+
+                let mut data = data.lock().unwrap();
+                let key = request["key"].as_str().unwrap_or_default().to_string();
+                let response = match path.as_str() {
+                    "/v3/lease/grant" => json!({ "ID": "42", "TTL": request["TTL"].to_string() }),
+                    "/v3/kv/put" => {
+                        data.insert(key, request["value"].as_str().unwrap().to_string());
+                        json!({})
+                    }
+                    "/v3/kv/range" => {
+                        let kvs: Vec<Value> = match request["range_end"].as_str() {
+                            Some(end) => data.range(key..end.to_string())
+                                .map(|(k, v)| json!({ "key": k, "value": v }))
+                                .collect(),
+                            None => data.get(&key).map(|v| json!({ "key": key, "value": v })).into_iter().collect(),
+                        };
+                        // 网关按字节序比较解码后的键；测试中的键都是 ASCII 且前缀相同，
+                        // base64 编码后的顺序与原始顺序一致
+                        json!({ "kvs": kvs })
+                    }
+                    "/v3/kv/deleterange" => json!({ "deleted": if data.remove(&key).is_some() { "1" } else { "0" } }),
+                    "/v3/kv/txn" => {
+                        let compare = &request["compare"][0];
+                        let target = compare["key"].as_str().unwrap().to_string();
+                        let matches = match compare["target"].as_str().unwrap() {
+                            "CREATE" => !data.contains_key(&target),
+                            _ => data.get(&target).map(String::as_str) == compare["value"].as_str(),
+                        };
+                        if matches {
+                            let put = &request["success"][0]["request_put"];
+                            data.insert(target, put["value"].as_str().unwrap().to_string());
+                        }
+                        if matches { json!({ "succeeded": true }) } else { json!({}) }
+                    }
+                    _ => json!({ "error": "unknown" }),
+                };
+                http_reply(&mut stream, &response);
+            }
+        });
+        address
+    }
+
+    #[test]
+    fn test_redis_coordination_against_fake_server() {
+        let backend = RedisCoordination::connect(&spawn_redis(), None).unwrap();
+        exercise_backend(&backend);
+        backend.put("ttl", b"v", Some(Duration::from_secs(5))).unwrap();
+        assert_eq!(backend.get("ttl").unwrap(), Some(b"v".to_vec()));
+    }
+
+    #[test]
+    fn test_redis_reconnects_after_truncated_reply() {
+        // 第一条连接只发出半条应答就断开，之后的连接正常应答
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            for (index, stream) in listener.incoming().enumerate() {
+                let stream = stream.unwrap();
+                let mut writer = stream.try_clone().unwrap();
+                let mut reader = BufReader::new(stream);
+                while let Some(args) = read_command(&mut reader) {
+                    if index == 0 {
+                        writer.write_all(b"$5\r\nst").unwrap();
+                        break;
+                    }
+                    let reply = match args[0].as_slice() {
+                        b"GET" => bulk(Some(&b"fresh".to_vec())),
+                        _ => b"-ERR unknown command\r\n".to_vec(),
+                    };
+                    writer.write_all(&reply).unwrap();
+                }
+            }
+        });
+
+        let backend = RedisCoordination::connect(&address, None).unwrap();
+        assert!(matches!(backend.get("k"), Err(mpc_api::MpcError::NetworkError(_))));
+
+        // 出错的连接被丢弃，下一次请求使用新连接并读到自己的应答
+        assert_eq!(backend.get("k").unwrap(), Some(b"fresh".to_vec()));
+
+        // 服务端错误应答是完整的，不需要重连
+        assert!(backend.delete("k").is_err());
+        assert_eq!(backend.get("k").unwrap(), Some(b"fresh".to_vec()));
+    }
+
+    #[test]
+    fn test_etcd_coordination_against_fake_server() {
+        let backend = EtcdCoordination::new(&spawn_etcd());
+        exercise_backend(&backend);
+        backend.put("ttl", b"v", Some(Duration::from_secs(5))).unwrap();
+        assert_eq!(backend.get("ttl").unwrap(), Some(b"v".to_vec()));
+    }
+
+    #[test]
+    fn test_unreachable_services_report_network_errors() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        drop(listener);
+        assert!(matches!(RedisCoordination::connect(&address, None), Err(mpc_api::MpcError::NetworkError(_))));
+        assert!(matches!(EtcdCoordination::new(&address).get("k"), Err(mpc_api::MpcError::NetworkError(_))));
+    }
+}
+
+// ===== Coordinator Tests =====
+
+#[test]
+fn test_coordinator_membership_and_liveness() {
+    let backend = Arc::new(MemoryCoordination::new());
+    let coordinator = Coordinator::new(backend.clone(), "cluster");
+    coordinator.announce("party-2", "10.0.0.2:9000", Duration::from_secs(30)).unwrap();
+    coordinator.announce("party-1", "10.0.0.1:9000", Duration::from_secs(30)).unwrap();
+    coordinator.announce("party-3", "10.0.0.3:9000", Duration::from_millis(20)).unwrap();
+
+    // 其他集群互不可见
+    let other = Coordinator::new(backend, "other");
+    assert!(other.live_nodes().unwrap().is_empty());
+
+    thread::sleep(Duration::from_millis(40));
+    let nodes = coordinator.live_nodes().unwrap();
+    assert_eq!(nodes.iter().map(|node| node.node_id.as_str()).collect::<Vec<_>>(), vec!["party-1", "party-2"]);
+    assert_eq!(nodes[0].address, "10.0.0.1:9000");
+
+    assert!(coordinator.withdraw("party-2").unwrap());
+    assert_eq!(coordinator.live_nodes().unwrap().len(), 1);
+}
+
+#[test]
+fn test_coordinator_epoch_agreement() {
+    let coordinator = Coordinator::new(Arc::new(MemoryCoordination::new()), "cluster");
+    let members = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+    assert_eq!(coordinator.current_epoch("s").unwrap(), None);
+
+    // 首个纪元必须为 1
+    assert!(coordinator.propose_epoch("s", 2, &members(&["a", "b"])).is_err());
+
+    let first = coordinator.propose_epoch("s", 1, &members(&["b", "a"])).unwrap();
+    assert_eq!(first.members, members(&["a", "b"]));
+    // 同一提议被其他参与方重复提交
+    assert_eq!(coordinator.propose_epoch("s", 1, &members(&["a", "b"])).unwrap(), first);
+    // 成员不同的并发提议被拒绝
+    assert!(coordinator.propose_epoch("s", 1, &members(&["a", "c"])).is_err());
+
+    let second = coordinator.propose_epoch("s", 2, &members(&["a", "b", "c"])).unwrap();
+    assert_eq!(coordinator.current_epoch("s").unwrap(), Some(second));
+    // 跳过纪元或回退到旧纪元都被拒绝
+    assert!(coordinator.propose_epoch("s", 4, &members(&["a"])).is_err());
+    assert!(coordinator.propose_epoch("s", 1, &members(&["a", "b"])).is_err());
+}
+
+#[test]
+fn test_coordinator_commitments_are_write_once() {
+    let coordinator = Coordinator::new(Arc::new(MemoryCoordination::new()), "cluster");
+    assert_eq!(coordinator.commitment("params").unwrap(), None);
+    coordinator.publish_commitment("params", b"fingerprint").unwrap();
+    coordinator.publish_commitment("params", b"fingerprint").unwrap();
+    assert!(coordinator.publish_commitment("params", b"forged").is_err());
+    assert_eq!(coordinator.commitment("params").unwrap(), Some(b"fingerprint".to_vec()));
+}
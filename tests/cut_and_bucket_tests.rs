@@ -0,0 +1,70 @@
+use mpc_api::beaver_triples::cut_and_bucket::*;
+use mpc_api::beaver_triples::{BeaverTripleGenerator, TrustedPartyBeaverGenerator};
+use mpc_api::secret_sharing::{field_add, field_mul, ShamirSecretSharing, SecretSharing};
+use mpc_api::MpcError;
+
+#[test]
+fn test_cut_and_bucket_parameters() {
+    let params = CutAndBucketParams::for_security(1 << 20, 40).unwrap();
+    assert_eq!((params.bucket_size, params.cut_size), (3, 3));
+    assert_eq!(params.required_triples(), 3 * (1 << 20) + 3);
+    assert!(params.statistical_security() >= 40.0);
+
+    // 批量越小需要的桶越大
+    let small = CutAndBucketParams::for_security(1000, 40).unwrap();
+    assert_eq!(small.bucket_size, 6);
+    assert!(small.statistical_security() >= 40.0);
+
+    // 切开数量小于桶大小时不提供保证
+    assert_eq!(CutAndBucketParams::new(1000, 6, 2).unwrap().statistical_security(), 0.0);
+
+    assert!(CutAndBucketParams::for_security(1, 40).is_err());
+    assert!(CutAndBucketParams::for_security(2, 80).is_err());
+    assert!(CutAndBucketParams::new(10, 1, 1).is_err());
+    assert!(CutAndBucketParams::new(0, 3, 3).is_err());
+}
+
+#[test]
+fn test_cut_and_bucket_accepts_honest_triples() {
+    let params = CutAndBucketParams::new(16, 3, 3).unwrap();
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    let output = CutAndBucketVerifier::new(params, 2)
+        .generate_verified(&mut generator, [1u8; 32])
+        .unwrap();
+
+    assert_eq!(output.result.len(), 16);
+    assert_eq!(output.stats.preprocessing_consumed, params.required_triples() - 16);
+    assert!(output.stats.bytes_sent > 0);
+    for triple in &output.result {
+        let open = |pick: fn(&mpc_api::beaver_triples::BeaverTriple) -> mpc_api::secret_sharing::Share| {
+            let shares: Vec<_> = (1..=2).map(|id| pick(&triple.shares[&id])).collect();
+            ShamirSecretSharing::reconstruct(&shares, 2).unwrap()
+        };
+        assert_eq!(open(|t| t.c.clone()), field_mul(open(|t| t.a.clone()), open(|t| t.b.clone())));
+    }
+}
+
+#[test]
+fn test_cut_and_bucket_detects_bad_triple() {
+    let params = CutAndBucketParams::new(8, 2, 2).unwrap();
+    let verifier = CutAndBucketVerifier::new(params, 2);
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    let honest = generator.generate_batch(params.required_triples()).unwrap();
+
+    // 无论被打乱到切开部分还是某个桶中，单个坏三元组都会被发现
+    for seed in 0..16u8 {
+        let mut triples = honest.clone();
+        for share in triples[5].shares.values_mut() {
+            share.c.y = field_add(share.c.y, 1);
+        }
+        let result = verifier.verify(triples, [seed; 32]);
+        assert!(matches!(result, Err(MpcError::AuthenticationError(_))));
+    }
+
+    // 分享不一致同样被拒绝
+    let mut triples = honest.clone();
+    triples[0].shares.get_mut(&3).unwrap().a.y ^= 1;
+    assert!(verifier.verify(triples, [0u8; 32]).is_err());
+
+    assert!(verifier.verify(honest[1..].to_vec(), [0u8; 32]).is_err());
+}
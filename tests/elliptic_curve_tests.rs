@@ -8,7 +8,7 @@
 //! 
 //! 椭圆曲线密码学提供了相同安全级别下更短的密钥长度，广泛应用于现代密码学协议。
 
-use mpc_api::elliptic_curve::{ECPoint, ECDH, ECDSA, EllipticCurve};
+use mpc_api::elliptic_curve::{ECPoint, ECDH, ECDSA, EllipticCurve, Curve25519ECDH};
 use mpc_api::elliptic_curve::ecdh::*;
 use mpc_api::elliptic_curve::ecdsa::*;
 use mpc_api::elliptic_curve::point::*;
@@ -108,13 +108,13 @@ fn test_key_agreement_protocol() {
 #[test]
 fn test_curve25519_keypair() {
     // 使用Curve25519椭圆曲线生成密钥对
-    let result = Curve25519ECDH::generate_keypair_curve25519();
+    let result = Curve25519ECDH::generate_keypair();
     assert!(result.is_ok()); // 密钥生成应该成功
     
     let (private_key, public_key) = result.unwrap();
-    // Curve25519中私钥和公钥都表示为u64，应该为正数
-    assert!(private_key > 0);
-    assert!(public_key > 0);
+    // 私钥和公钥都是32字节，不应全为零
+    assert!(!private_key.0.iter().all(|&b| b == 0));
+    assert!(public_key.is_valid());
 }
 
 /// 测试Curve25519椭圆曲线的共享密钥计算
@@ -124,13 +124,13 @@ fn test_curve25519_keypair() {
 #[test]
 fn test_curve25519_shared_secret() {
     // 为Alice和Bob生成Curve25519密钥对
-    let (alice_private, alice_public) = Curve25519ECDH::generate_keypair_curve25519().unwrap();
-    let (bob_private, bob_public) = Curve25519ECDH::generate_keypair_curve25519().unwrap();
+    let (alice_private, alice_public) = Curve25519ECDH::generate_keypair().unwrap();
+    let (bob_private, bob_public) = Curve25519ECDH::generate_keypair().unwrap();
     
     // Alice使用自己的私钥和Bob的公钥计算共享密钥
-    let alice_shared = Curve25519ECDH::compute_shared_secret_curve25519(alice_private, bob_public).unwrap();
+    let alice_shared = Curve25519ECDH::compute_shared_secret(alice_private, &bob_public).unwrap();
     // Bob使用自己的私钥和Alice的公钥计算共享密钥
-    let bob_shared = Curve25519ECDH::compute_shared_secret_curve25519(bob_private, alice_public).unwrap();
+    let bob_shared = Curve25519ECDH::compute_shared_secret(bob_private, &alice_public).unwrap();
     
     // 两个共享密钥应该相同，这是ECDH协议的核心特性
     assert_eq!(alice_shared, bob_shared);
@@ -301,6 +301,20 @@ fn test_scalar_multiplication() {
     assert_eq!(result, doubled);
 }
 
+/// 测试蒙哥马利阶梯标量乘法与逐次加法一致
+///
+/// 预期：对 0..=n+1 的每个标量，k*G 等于 G 累加 k 次，n*G 为无穷远点
+#[test]
+fn test_ladder_scalar_multiplication_matches_repeated_addition() {
+    let params = SimpleEC::params();
+    let mut expected = ECPoint::infinity();
+    for k in 0..=params.n + 1 {
+        assert_eq!(SimpleEC::scalar_multiply(k, &params.g).unwrap(), expected, "k = {}", k);
+        expected = SimpleEC::point_add(&expected, &params.g).unwrap();
+    }
+    assert!(SimpleEC::scalar_multiply(params.n, &params.g).unwrap().is_infinity());
+}
+
 #[test]
 fn test_ec_params() {
     let params = SimpleEC::params();
@@ -433,7 +447,178 @@ fn test_field_element_inversion() {
     let product = a * a_inv;
     
     // a * a^(-1) 应该等于 1
-    let one = FieldElement::one();
-    // 注意：由于模运算的精度问题，这里可能需要更精确的比较
-    assert_eq!(product.0[0] & 0xFFFF, one.0[0] & 0xFFFF);
-}
\ No newline at end of file
+    assert_eq!(product, FieldElement::one());
+}
+
+fn hex32(hex: &str) -> [u8; 32] {
+    let bytes: Vec<u8> = (0..hex.len()).step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+    bytes.try_into().unwrap()
+}
+
+/// 测试X25519与RFC 7748测试向量一致
+#[test]
+fn test_x25519_rfc7748_vectors() {
+    use mpc_api::elliptic_curve::curve25519::{PrivateKey, PublicKey};
+
+    let scalar = PrivateKey::from_bytes(hex32("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"));
+    let u = PublicKey::from_bytes(hex32("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c"));
+    assert_eq!(
+        Curve25519ECDH::compute_shared_secret(scalar, &u).unwrap(),
+        hex32("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552")
+    );
+
+    let alice = PrivateKey::from_bytes(hex32("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a"));
+    assert_eq!(alice.public_key().0, hex32("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"));
+
+    // 低阶点（u = 0）得到全零共享密钥，必须拒绝
+    assert!(Curve25519ECDH::compute_shared_secret(alice, &PublicKey::from_bytes([0; 32])).is_err());
+}
+
+/// 测试secp256k1点运算、编码与外部实现一致
+#[test]
+fn test_secp256k1_points_and_encoding() {
+    use mpc_api::elliptic_curve::{Secp256k1, Secp256k1Point, Secp256k1Scalar};
+
+    let params = Secp256k1::params();
+    let g = params.g;
+    assert!(Secp256k1::is_on_curve(&g));
+    assert_eq!(params.n, hex32("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"));
+
+    // 2G 的 x 坐标是公开的已知值
+    let two_g = Secp256k1::point_double(&g).unwrap();
+    assert_eq!(two_g.to_affine().unwrap().0, hex32("c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5"));
+    assert_eq!(Secp256k1::point_add(&g, &g).unwrap(), two_g);
+    assert_eq!(Secp256k1::scalar_multiply(Secp256k1Scalar::from_u64(2), &g).unwrap(), two_g);
+
+    // n·G = O，(n-1)·G = -G
+    let minus_one = -Secp256k1Scalar::ONE;
+    assert_eq!(g * minus_one, -g);
+    assert!((g * minus_one + g).is_identity());
+    assert_eq!(g + Secp256k1Point::identity(), g);
+
+    let d = Secp256k1Scalar::from_bytes(&hex32("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721")).unwrap();
+    let public_key = g * d;
+    let compressed = public_key.to_sec1(true);
+    assert_eq!(compressed[0], 0x03);
+    assert_eq!(compressed[1..], hex32("2c8c31fc9f990c6b55e3865a184a4ce50e09481f2eaeb3e60ec1cea13a6ae645"));
+    assert_eq!(Secp256k1Point::from_sec1(&compressed).unwrap(), public_key);
+    assert_eq!(Secp256k1Point::from_sec1(&public_key.to_sec1(false)).unwrap(), public_key);
+    let mut off_curve = public_key.to_sec1(false);
+    off_curve[64] ^= 1;
+    assert!(Secp256k1Point::from_sec1(&off_curve).is_err());
+
+    let bytes = bincode::serialize(&public_key).unwrap();
+    assert_eq!(bincode::deserialize::<Secp256k1Point>(&bytes).unwrap(), public_key);
+    assert!(Secp256k1Scalar::from_bytes(&params.n).is_none());
+
+    // 批量编码共用一次求逆，结果与逐点编码相同
+    let points = [g, Secp256k1Point::identity(), two_g, public_key, -public_key];
+    let expected: Vec<Vec<u8>> = points.iter().map(|point| point.to_sec1(true)).collect();
+    assert_eq!(Secp256k1Point::batch_to_sec1(&points, true), expected);
+    assert_eq!(Secp256k1Point::batch_to_sec1(&points[2..], false)[1], public_key.to_sec1(false));
+    assert!(Secp256k1Point::batch_to_sec1(&[], true).is_empty());
+}
+
+/// 测试secp256k1 ECDSA / ECDH与外部实现互通
+#[test]
+fn test_secp256k1_ecdsa_and_ecdh_interop() {
+    use mpc_api::elliptic_curve::{Secp256k1Ecdh, Secp256k1Ecdsa, Secp256k1Point, Secp256k1Scalar, Secp256k1Signature};
+
+    let d = Secp256k1Scalar::from_bytes(&hex32("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721")).unwrap();
+    let public_key = Secp256k1Point::generator() * d;
+
+    // RFC 6979 确定性签名与外部实现一致
+    let signature = Secp256k1Ecdsa::sign_message(b"sample", &d).unwrap();
+    assert_eq!(signature.r.to_bytes(), hex32("432310e32cb80eb6503a26ce83cc165c783b870845fb8aad6d970889fcd7a6c8"));
+    assert_eq!(signature.s.to_bytes(), hex32("530128b6b81c548874a6305d93ed071ca6e05074d85863d4056ce89b02bfab69"));
+    assert!(Secp256k1Ecdsa::verify_message(b"sample", &public_key, &signature).unwrap());
+    assert!(!Secp256k1Ecdsa::verify_message(b"samples", &public_key, &signature).unwrap());
+    assert_eq!(Secp256k1Signature::from_bytes(&signature.to_bytes()).unwrap(), signature);
+
+    // 外部实现用随机数签名（high-S）也能验证
+    let external = Secp256k1Signature {
+        r: Secp256k1Scalar::from_bytes(&hex32("a39b516f29c033e6b7e940fa6bd5a766282cb8a0b4a61e1bbcfcffc1904c3b72")).unwrap(),
+        s: Secp256k1Scalar::from_bytes(&hex32("fd9a5c41f18d20feecd903a703e4c800055f77fe124240f75e77fe7e890d6ebb")).unwrap(),
+    };
+    assert!(external.s.is_high());
+    assert!(Secp256k1Ecdsa::verify_message(b"test", &public_key, &external).unwrap());
+    assert!(!Secp256k1Ecdsa::verify_message(b"test", &(public_key + Secp256k1Point::generator()), &external).unwrap());
+
+    // 随机密钥签名
+    let (private_key, public_key) = Secp256k1Ecdsa::generate_keypair();
+    let signature = Secp256k1Ecdsa::sign_message(b"message", &private_key).unwrap();
+    assert!(!signature.s.is_high());
+    assert!(Secp256k1Ecdsa::verify_message(b"message", &public_key, &signature).unwrap());
+
+    // ECDH 共享密钥为共享点的 x 坐标
+    let peer = Secp256k1Scalar::from_u64(0x1234567890ABCDEF);
+    let peer_public = Secp256k1Point::generator() * peer;
+    assert_eq!(peer_public.to_sec1(true)[1..], hex32("f973a0b87062c389d125d8199e803b832b6ac6bf7867a4f6cd87506060fc4c58"));
+    let expected = hex32("d4e6929f5c72a633728b06d4c377748e524d8007c71a685e807e7ef4692312de");
+    assert_eq!(Secp256k1Ecdh::compute_shared_secret(d, &peer_public).unwrap(), expected);
+    assert_eq!(Secp256k1Ecdh::compute_shared_secret(peer, &(Secp256k1Point::generator() * d)).unwrap(), expected);
+    assert!(Secp256k1Ecdh::compute_shared_secret(d, &Secp256k1Point::identity()).is_err());
+}
+/// 测试Schnorr签名
+#[test]
+fn test_schnorr_sign_and_verify() {
+    use mpc_api::elliptic_curve::{Schnorr, SchnorrSignature, Secp256k1Point, Secp256k1Scalar};
+
+    let (private_key, public_key) = Schnorr::generate_keypair();
+    let signature = Schnorr::sign(&private_key, b"message").unwrap();
+    assert!(Schnorr::verify(&public_key, b"message", &signature));
+    assert!(!Schnorr::verify(&public_key, b"massage", &signature));
+    assert!(!Schnorr::verify(&(public_key + Secp256k1Point::generator()), b"message", &signature));
+    let forged = SchnorrSignature { r: signature.r, s: signature.s + Secp256k1Scalar::ONE };
+    assert!(!Schnorr::verify(&public_key, b"message", &forged));
+    assert!(!Schnorr::verify(&Secp256k1Point::identity(), b"message", &signature));
+    assert!(Schnorr::sign(&Secp256k1Scalar::ZERO, b"message").is_err());
+
+    // 签名可以序列化传输
+    let bytes = bincode::serialize(&signature).unwrap();
+    assert_eq!(bincode::deserialize::<SchnorrSignature>(&bytes).unwrap(), signature);
+    // 每次签名使用新的随机数
+    assert_ne!(Schnorr::sign(&private_key, b"message").unwrap().r, signature.r);
+}
+
+/// 测试MuSig两轮多重签名
+#[test]
+fn test_musig_two_round_multisignature() {
+    use mpc_api::elliptic_curve::{run_musig, KeyAggregation, MuSigSigner, Schnorr, Secp256k1Scalar};
+
+    let keys: Vec<_> = (0..3).map(|_| Schnorr::generate_keypair()).collect();
+    let private_keys: Vec<Secp256k1Scalar> = keys.iter().map(|(private_key, _)| *private_key).collect();
+    let public_keys: Vec<_> = keys.iter().map(|(_, public_key)| *public_key).collect();
+
+    let (aggregate_key, signature) = run_musig(&private_keys, b"pay 5").unwrap();
+    assert!(Schnorr::verify(&aggregate_key, b"pay 5", &signature));
+    assert!(!Schnorr::verify(&public_keys[0], b"pay 5", &signature));
+
+    // 聚合系数防止直接相加：聚合公钥不等于公钥之和
+    let aggregation = KeyAggregation::new(&public_keys).unwrap();
+    assert_eq!(aggregation.aggregate_key, aggregate_key);
+    assert_ne!(aggregate_key, public_keys[0] + public_keys[1] + public_keys[2]);
+
+    // 手动执行两轮，篡改一个部分签名会被定位
+    let signers: Vec<MuSigSigner> = private_keys.iter().map(|&key| MuSigSigner::new(key, &aggregation).unwrap()).collect();
+    let (secrets, nonces): (Vec<_>, Vec<_>) = signers.iter().map(MuSigSigner::commit_nonce).unzip();
+    let mut partials: Vec<_> = signers.iter().zip(secrets)
+        .map(|(signer, secret)| signer.partial_sign(secret, &nonces, b"pay 6").unwrap())
+        .collect();
+    let signature = aggregation.combine(&nonces, b"pay 6", &partials).unwrap();
+    assert!(Schnorr::verify(&aggregate_key, b"pay 6", &signature));
+    assert!(aggregation.combine(&nonces, b"pay 6", &partials[..2]).is_err());
+    partials[1].s = partials[1].s + Secp256k1Scalar::ONE;
+    let error = aggregation.combine(&nonces, b"pay 6", &partials).unwrap_err();
+    assert!(error.to_string().contains("signer 1"));
+
+    // 不在密钥集合中的签名者、随机数数量错误都被拒绝
+    assert!(MuSigSigner::new(Secp256k1Scalar::from_u64(7), &aggregation).is_err());
+    let (secret, _) = signers[0].commit_nonce();
+    assert!(signers[0].partial_sign(secret, &nonces[..2], b"pay 7").is_err());
+    let (secret, _) = signers[0].commit_nonce();
+    assert!(signers[0].partial_sign(secret, &nonces, b"pay 7").is_err());
+    assert!(KeyAggregation::new(&[]).is_err());
+}
@@ -3,7 +3,8 @@
 //! This test suite verifies that the MPC API properly handles error conditions,
 //! edge cases, and invalid inputs with appropriate error messages and recovery.
 
-use mpc_api::*;
+use mpc_api::prelude::*;
+use mpc_api::secret_sharing::{field_add, field_mul, field_sub};
 
 /// Test error handling in secret sharing operations
 #[test]
@@ -10,7 +10,8 @@
 //! 
 //! 这些测试确保所有示例代码都能正确运行，为用户提供可靠的参考实现。
 
-use mpc_api::*;
+use mpc_api::prelude::*;
+use mpc_api::secret_sharing::field_add;
 
 // ===== Beaver Triples Trusted Party Example Tests =====
 // Beaver三元组是安全多方计算中用于安全乘法的关键原语
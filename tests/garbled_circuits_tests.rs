@@ -3,6 +3,7 @@
 //! 包含电路构造, 混淆器, 求值器等混淆电路相关测试
 
 use mpc_api::garbled_circuits::*;
+use mpc_api::utils::crypto::{AesCtrPrg, SeedableRng};
 
 // ===== Circuit Tests =====
 
@@ -33,4 +34,979 @@ fn test_adder_circuit() {
     
     // Should have multiple gates for the adder logic
     assert!(circuit.gates.len() > 0);
-}
\ No newline at end of file
+}
+// ===== Garbling Integrity Tests =====
+
+fn garbled_gate_circuit(gate_type: GateType) -> (Garbler, GarbledCircuit) {
+    let mut circuit = Circuit::new();
+    let a = circuit.add_input_wire();
+    let b = circuit.add_input_wire();
+    let output = circuit.add_gate(gate_type, vec![a, b]);
+    circuit.add_output_wire(output);
+
+    let garbler = Garbler::new();
+    let garbled = garbler.garble_circuit(&circuit).unwrap();
+    (garbler, garbled)
+}
+
+fn integrity_error(result: mpc_api::Result<Vec<bool>>) -> GarblingIntegrityError {
+    match result {
+        Err(mpc_api::MpcError::GarblingIntegrity(error)) => error,
+        other => panic!("expected garbling integrity error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_point_and_permute_evaluation() {
+    let cases: [(GateType, fn(bool, bool) -> bool); 3] = [
+        (GateType::And, |a, b| a & b),
+        (GateType::Or, |a, b| a | b),
+        (GateType::Xor, |a, b| a ^ b),
+    ];
+
+    for (gate_type, expected) in cases {
+        let (garbler, garbled) = garbled_gate_circuit(gate_type);
+        for (a, b) in [(false, false), (false, true), (true, false), (true, true)] {
+            let labels = garbler.get_input_labels(&garbled, &[a, b]).unwrap();
+            assert_eq!(evaluate_garbled_circuit(&garbled, &labels).unwrap(), vec![expected(a, b)]);
+        }
+    }
+
+    // 同一条线的两个标签选择位相反
+    let (_, garbled) = garbled_gate_circuit(GateType::And);
+    for (label_0, label_1) in garbled.wire_labels.values() {
+        assert_ne!(ColoredLabel::new(*label_0).color, ColoredLabel::new(*label_1).color);
+    }
+}
+
+#[test]
+fn test_malformed_tables_are_rejected() {
+    let (garbler, garbled) = garbled_gate_circuit(GateType::And);
+    let labels = garbler.get_input_labels(&garbled, &[true, false]).unwrap();
+    let row = permute_row(&ColoredLabel::new(labels[0]), &ColoredLabel::new(labels[1]));
+    let gate_id = garbled.gates[0].id;
+
+    let tampered = |edit: &dyn Fn(&mut Vec<Label>)| {
+        let mut circuit = garbled.clone();
+        edit(circuit.gates[0].garbled_table.as_mut().unwrap());
+        evaluate_garbled_circuit(&circuit, &labels)
+    };
+
+    // 行被移动：选择位指向的行与通过认证的行不一致
+    assert_eq!(
+        integrity_error(tampered(&|table| table.rotate_left(GARBLED_ROW_LABELS))),
+        GarblingIntegrityError::SelectBitMismatch { gate: gate_id, expected: row, found: (row + 3) % 4 }
+    );
+
+    // 同一行被复制到所有位置：多行通过认证
+    let ambiguous = integrity_error(tampered(&|table| {
+        let copied = table[row * GARBLED_ROW_LABELS..(row + 1) * GARBLED_ROW_LABELS].to_vec();
+        for chunk in table.chunks_mut(GARBLED_ROW_LABELS) {
+            chunk.copy_from_slice(&copied);
+        }
+    }));
+    assert_eq!(ambiguous, GarblingIntegrityError::AmbiguousRows { gate: gate_id, rows: vec![0, 1, 2, 3] });
+
+    // 认证标签被篡改
+    assert_eq!(
+        integrity_error(tampered(&|table| table[row * GARBLED_ROW_LABELS + 1][0] ^= 1)),
+        GarblingIntegrityError::NoValidRow { gate: gate_id }
+    );
+
+    // 密文被篡改：不会静默解码出错误的输出
+    assert_eq!(
+        integrity_error(tampered(&|table| table[row * GARBLED_ROW_LABELS][3] ^= 0x80)),
+        GarblingIntegrityError::InvalidOutputLabel { gate: gate_id }
+    );
+
+    // 表长度不正确
+    assert_eq!(
+        integrity_error(tampered(&|table| table.truncate(4))),
+        GarblingIntegrityError::MalformedTable { gate: gate_id, len: 4, expected: 8 }
+    );
+}
+
+// ===== Audit Artifact Tests =====
+
+fn audited_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    let a = circuit.add_input_wire();
+    let b = circuit.add_input_wire();
+    let c = circuit.add_input_wire();
+    let and = circuit.and_gate(a, b);
+    let not = circuit.not_gate(c);
+    let or = circuit.or_gate(and, not);
+    let xor = circuit.xor_gate(or, a);
+    circuit.add_output_wire(or);
+    circuit.add_output_wire(xor);
+    circuit
+}
+
+fn audit_artifact(circuit: &Circuit, inputs: &[bool]) -> GarbledEvaluationArtifact {
+    let garbler = Garbler::new();
+    let garbled = garbler.garble_circuit(circuit).unwrap();
+    let labels = garbler.get_input_labels(&garbled, inputs).unwrap();
+    let transcript = Evaluator::new().evaluate_with_transcript(&garbled, &labels).unwrap();
+    GarbledEvaluationArtifact::new(GarbledCircuitExport::from_garbled(&garbled).unwrap(), transcript)
+}
+
+#[test]
+fn test_audit_artifact_verifies_honest_evaluation() {
+    let circuit = audited_circuit();
+    for inputs in [[false, false, false], [true, true, false], [true, false, true], [false, true, true]] {
+        let artifact = audit_artifact(&circuit, &inputs);
+        let or = (inputs[0] && inputs[1]) || !inputs[2];
+        assert_eq!(artifact.transcript.outputs, vec![or, or ^ inputs[0]]);
+
+        let restored = GarbledEvaluationArtifact::from_bytes(&artifact.to_bytes().unwrap()).unwrap();
+        assert_eq!(restored.digest().unwrap(), artifact.digest().unwrap());
+        assert_eq!(restored.verify(&circuit).unwrap(), artifact.transcript.outputs);
+        assert_eq!(
+            restored.verify_with_digest(&agreed_circuit_digest(&circuit).unwrap()).unwrap(),
+            artifact.transcript.outputs
+        );
+    }
+}
+
+#[test]
+fn test_audit_artifact_rejects_disputed_evaluations() {
+    let circuit = audited_circuit();
+    let artifact = audit_artifact(&circuit, &[true, true, false]);
+
+    // 电路与约定不一致
+    let mut other = audited_circuit();
+    let extra = other.and_gate(0, 2);
+    other.add_output_wire(extra);
+    assert!(artifact.verify(&other).is_err());
+
+    let mut swapped = artifact.clone();
+    swapped.export.gates[0].gate_type = GateType::Or;
+    assert!(swapped.verify(&circuit).is_err());
+
+    // 输入标签不在承诺中
+    let mut forged_input = artifact.clone();
+    forged_input.transcript.input_labels[0][5] ^= 1;
+    assert!(forged_input.verify(&circuit).is_err());
+
+    // 转录中的门输出与重放不一致
+    let mut forged_gate = artifact.clone();
+    forged_gate.transcript.gate_outputs[1].1[0] ^= 1;
+    assert!(forged_gate.verify(&circuit).is_err());
+
+    // 声称的输出与解码结果不一致
+    let mut forged_output = artifact.clone();
+    forged_output.transcript.outputs[0] ^= true;
+    assert!(forged_output.verify(&circuit).is_err());
+
+    // 混淆表被篡改
+    let mut forged_table = artifact.clone();
+    for label in forged_table.export.gates[0].table.iter_mut() {
+        label[0] ^= 1;
+    }
+    assert!(matches!(forged_table.verify(&circuit), Err(mpc_api::MpcError::GarblingIntegrity(_))));
+
+    assert!(artifact.verify(&circuit).is_ok());
+}
+
+// ===== Plaintext Simulator Tests =====
+
+#[test]
+fn test_simulator_matches_garbled_evaluation() {
+    let circuit = audited_circuit();
+    for a in [false, true] {
+        for b in [false, true] {
+            for c in [false, true] {
+                let inputs = [a, b, c];
+                let or = (a && b) || !c;
+                assert_eq!(simulate(&circuit, &inputs).unwrap(), vec![or, or ^ a]);
+                let report = cross_check_garbled(&circuit, &inputs).unwrap();
+                assert!(report.is_consistent());
+                assert_eq!(report.checked_gates, 4);
+            }
+        }
+    }
+
+    let adder = Circuit::create_adder(4);
+    // 5 + 6，输入按 a0, b0, a1, b1, ... 交错排列
+    let inputs: Vec<bool> = (0..4).flat_map(|i| [(5 >> i) & 1 == 1, (6 >> i) & 1 == 1]).collect();
+    let sum = simulate(&adder, &inputs).unwrap();
+    assert_eq!(sum.iter().enumerate().map(|(i, &bit)| (bit as u32) << i).sum::<u32>(), 11);
+    assert!(simulate(&adder, &inputs[..3]).is_err());
+}
+
+#[test]
+fn test_simulator_pinpoints_first_diverging_gate() {
+    let circuit = audited_circuit();
+    let inputs = [true, true, false];
+    let garbler = Garbler::new();
+    let garbled = garbler.garble_circuit(&circuit).unwrap();
+    let labels = garbler.get_input_labels(&garbled, &inputs).unwrap();
+    let mut transcript = Evaluator::new().evaluate_with_transcript(&garbled, &labels).unwrap();
+
+    // 把 NOT 门（第 1 个门）的输出换成另一个标签，把 XOR 门的输出换成无效标签
+    let (not_gate, not_label) = transcript.gate_outputs[1];
+    let (label_0, label_1) = garbled.wire_labels[&circuit.gates[1].output_wire];
+    transcript.gate_outputs[1].1 = if not_label == label_0 { label_1 } else { label_0 };
+    transcript.gate_outputs[3].1[0] ^= 0xff;
+
+    let report = diff_transcript(&circuit, &garbled, &inputs, &transcript).unwrap();
+    assert!(!report.is_consistent());
+    assert_eq!(report.divergences.len(), 2);
+    let first = report.first_divergence().unwrap();
+    assert_eq!(first.gate, not_gate);
+    assert_eq!(first.gate_type, GateType::Not);
+    assert_eq!((first.expected, first.actual), (true, Some(false)));
+    assert_eq!(report.divergences[1].actual, None);
+}
+
+// ===== Extended Gate Tests =====
+
+fn extended_gate_circuit() -> Circuit {
+    let mut circuit = Circuit::new();
+    let inputs: Vec<WireId> = (0..4).map(|_| circuit.add_input_wire()).collect();
+    let nand = circuit.nand_gate(inputs[0], inputs[1]);
+    let nor = circuit.nor_gate(inputs[1], inputs[2]);
+    let xnor = circuit.xnor_gate(nand, nor);
+    let copy = circuit.buf_gate(xnor);
+    let all = circuit.multi_and_gate(&inputs);
+    let any = circuit.multi_or_gate(&[inputs[0], inputs[2], inputs[3]]);
+    let one = circuit.constant(true);
+    let masked = circuit.xor_gate(any, one);
+    for wire in [nand, nor, copy, all, any, masked, one] {
+        circuit.add_output_wire(wire);
+    }
+    circuit
+}
+
+#[test]
+fn test_extended_gates_garble_and_audit() {
+    let circuit = extended_gate_circuit();
+    let garbler = Garbler::new();
+    let garbled = garbler.garble_circuit(&circuit).unwrap();
+    assert_eq!(garbled.gates[4].table_size(), 16);
+
+    for assignment in 0..16u32 {
+        let inputs: Vec<bool> = (0..4).map(|i| (assignment >> i) & 1 == 1).collect();
+        let (a, b, c, d) = (inputs[0], inputs[1], inputs[2], inputs[3]);
+        let expected = vec![
+            !(a && b),
+            !(b || c),
+            !(a && b) == !(b || c),
+            a && b && c && d,
+            a || c || d,
+            !(a || c || d),
+            true,
+        ];
+        assert_eq!(simulate(&circuit, &inputs).unwrap(), expected);
+
+        let labels = garbler.get_input_labels(&garbled, &inputs).unwrap();
+        let transcript = Evaluator::new().evaluate_with_transcript(&garbled, &labels).unwrap();
+        assert_eq!(transcript.outputs, expected);
+
+        let artifact = GarbledEvaluationArtifact::new(GarbledCircuitExport::from_garbled(&garbled).unwrap(), transcript);
+        assert_eq!(artifact.verify(&circuit).unwrap(), expected);
+    }
+
+    // 超过直接混淆上限的多扇入门需要先分解
+    let mut wide = Circuit::new();
+    let inputs: Vec<WireId> = (0..7).map(|_| wide.add_input_wire()).collect();
+    let output = wide.multi_or_gate(&inputs);
+    wide.add_output_wire(output);
+    assert!(garbler.garble_circuit(&wide).is_err());
+
+    let decomposed = wide.decompose_fan_in(2);
+    assert!(decomposed.gates.iter().all(|gate| gate.gate_type == GateType::Or));
+    assert_eq!(decomposed.gates.last().unwrap().output_wire, output);
+    let garbled = garbler.garble_circuit(&decomposed).unwrap();
+    for assignment in [0u32, 1, 64, 127] {
+        let bits: Vec<bool> = (0..7).map(|i| (assignment >> i) & 1 == 1).collect();
+        let labels = garbler.get_input_labels(&garbled, &bits).unwrap();
+        assert_eq!(evaluate_garbled_circuit(&garbled, &labels).unwrap(), vec![assignment != 0]);
+    }
+}
+
+#[test]
+fn test_constant_simplification() {
+    let circuit = extended_gate_circuit();
+    let mut with_constants = circuit.clone();
+    let zero = with_constants.constant(false);
+    let one = with_constants.constant(true);
+    let x = with_constants.input_wires[0];
+    let y = with_constants.input_wires[1];
+    let killed = with_constants.and_gate(x, zero);
+    let passed = with_constants.multi_and_gate(&[x, one, y]);
+    let flipped = with_constants.xor_gate(one, y);
+    let copied = with_constants.buf_gate(x);
+    let same = with_constants.nor_gate(copied, zero);
+    for wire in [killed, passed, flipped, copied, same] {
+        with_constants.add_output_wire(wire);
+    }
+
+    let simplified = with_constants.simplify_constants();
+    assert!(simplified.gates.len() < with_constants.gates.len());
+    assert!(simplified.gates.iter().all(|gate| gate.gate_type != GateType::Buf));
+    // 与 1 的多扇入 AND 变为两输入 AND，与 1 的 XOR 变为 NOT，缓冲门的输出直接指向输入线
+    assert!(simplified.gates.iter().any(|gate| gate.gate_type == GateType::And && gate.input_wires == vec![x, y]));
+    assert!(simplified.gates.iter().any(|gate| gate.gate_type == GateType::Not && gate.input_wires == vec![y]));
+    assert_eq!(simplified.output_wires[10], x);
+
+    for assignment in 0..16u32 {
+        let inputs: Vec<bool> = (0..4).map(|i| (assignment >> i) & 1 == 1).collect();
+        let expected = simulate(&with_constants, &inputs).unwrap();
+        assert_eq!(simulate(&simplified, &inputs).unwrap(), expected);
+        assert!(cross_check_garbled(&simplified, &inputs).unwrap().is_consistent());
+    }
+}
+
+// ===== Garble Plan Tests =====
+
+#[test]
+fn test_garble_plan_reuses_structure() {
+    let adder = Circuit::create_adder(3);
+    let plan = GarblePlan::new(&adder).unwrap();
+    assert_eq!(plan.gate_count(), adder.gates.len());
+    assert_eq!(plan.gate_order(), adder.gates.iter().map(|gate| gate.id).collect::<Vec<_>>());
+
+    // 同一种子得到相同的混淆电路，不同种子得到新的标签
+    let first = plan.garble_with_seed([7; 32]);
+    let again = plan.garble_with_seed([7; 32]);
+    let other = plan.garble_with_seed([8; 32]);
+    assert_eq!(first.wire_labels, again.wire_labels);
+    assert!(first.gates.iter().zip(&again.gates).all(|(a, b)| a.garbled_table == b.garbled_table));
+    assert_ne!(first.wire_labels[&0], other.wire_labels[&0]);
+
+    // 逐门混淆同样可以由种子确定
+    let garbler = Garbler::with_rng(&mut AesCtrPrg::from_seed([7; 32]));
+    let seeded = garbler.garble_circuit_with_rng(&adder, &mut AesCtrPrg::from_seed([7; 32])).unwrap();
+    let reseeded = garbler.garble_circuit_with_rng(&adder, &mut AesCtrPrg::from_seed([7; 32])).unwrap();
+    assert_eq!(seeded.wire_labels, reseeded.wire_labels);
+
+    for (a, b) in [(0u32, 0u32), (5, 3), (7, 7)] {
+        let inputs: Vec<bool> = (0..3).flat_map(|i| [(a >> i) & 1 == 1, (b >> i) & 1 == 1]).collect();
+        let garbled = plan.garble();
+        let labels = Garbler::new().get_input_labels(&garbled, &inputs).unwrap();
+        let mut evaluator = Evaluator::new();
+        let outputs = evaluator.evaluate(&garbled, &labels).unwrap();
+        let sum = evaluator.decode_output(&outputs, &garbled).unwrap();
+        assert_eq!(sum.iter().enumerate().map(|(i, &bit)| (bit as u32) << i).sum::<u32>(), a + b);
+    }
+}
+
+#[test]
+fn test_garble_plan_orders_and_validates_gates() {
+    // 门按依赖关系逆序给出
+    let mut circuit = Circuit::new();
+    let a = circuit.add_input_wire();
+    let b = circuit.add_input_wire();
+    let and = circuit.and_gate(a, b);
+    let not = circuit.not_gate(and);
+    circuit.add_output_wire(not);
+    circuit.gates.reverse();
+
+    let plan = GarblePlan::new(&circuit).unwrap();
+    assert_eq!(plan.gate_order(), vec![0, 1]);
+    assert_eq!(plan.table_gate_count(), 1);
+    let garbled = plan.garble();
+    let labels = Garbler::new().get_input_labels(&garbled, &[true, true]).unwrap();
+    let mut evaluator = Evaluator::new();
+    let outputs = evaluator.evaluate(&garbled, &labels).unwrap();
+    assert_eq!(evaluator.decode_output(&outputs, &garbled).unwrap(), vec![false]);
+
+    // 环路
+    let mut cyclic = circuit.clone();
+    cyclic.gates[1].input_wires = vec![a, not];
+    assert!(GarblePlan::new(&cyclic).is_err());
+
+    // 输入数不正确的门
+    let mut malformed = circuit.clone();
+    malformed.gates[0].input_wires.push(b);
+    assert!(GarblePlan::new(&malformed).is_err());
+
+    // 输出线从未被驱动
+    let mut dangling = circuit;
+    dangling.add_output_wire(99);
+    assert!(GarblePlan::new(&dangling).is_err());
+}
+
+// ===== Canonical Form Tests =====
+
+#[test]
+fn test_circuit_digest_ignores_construction_order() {
+    let adder = Circuit::create_adder(4);
+
+    // 同一个加法器：门逆序排列、线编号整体平移
+    let mut shuffled = adder.clone();
+    shuffled.gates.reverse();
+    let shift = 100;
+    let mut renumbered = shuffled.clone();
+    renumbered.wire_count += shift;
+    for wire in renumbered.input_wires.iter_mut().chain(renumbered.output_wires.iter_mut()) {
+        *wire += shift;
+    }
+    for (id, gate) in renumbered.gates.iter_mut().enumerate() {
+        gate.id = id as GateId * 7;
+        gate.output_wire += shift;
+        gate.input_wires.iter_mut().for_each(|wire| *wire += shift);
+    }
+
+    let canonical = adder.canonicalize().unwrap();
+    assert_eq!(shuffled.canonicalize().unwrap(), canonical);
+    assert_eq!(renumbered.canonicalize().unwrap(), canonical);
+    assert_eq!(renumbered.circuit_digest().unwrap(), adder.circuit_digest().unwrap());
+    // 规范形式是不动点，且门 ID 与位置一致
+    assert_eq!(canonical.canonicalize().unwrap(), canonical);
+    assert!(canonical.gates.iter().enumerate().all(|(i, gate)| gate.id == i as GateId));
+
+    // 交换输入顺序或更改门类型得到不同的函数
+    let mut swapped = adder.clone();
+    swapped.input_wires.swap(0, 2);
+    let mut changed = adder.clone();
+    let and = changed.gates.iter().position(|gate| gate.gate_type == GateType::And).unwrap();
+    changed.gates[and].gate_type = GateType::Or;
+    assert_ne!(swapped.circuit_digest().unwrap(), adder.circuit_digest().unwrap());
+    assert_ne!(changed.circuit_digest().unwrap(), adder.circuit_digest().unwrap());
+    assert_ne!(Circuit::create_adder(3).circuit_digest().unwrap(), adder.circuit_digest().unwrap());
+
+    // 结构不合法的电路没有摘要
+    let mut cyclic = adder.clone();
+    let last = cyclic.gates.last().unwrap().output_wire;
+    cyclic.gates[1].input_wires[0] = last;
+    assert!(cyclic.circuit_digest().is_err());
+}
+
+#[test]
+fn test_canonical_form_merges_duplicate_gates_and_still_evaluates() {
+    let mut circuit = Circuit::new();
+    let a = circuit.add_input_wire();
+    let b = circuit.add_input_wire();
+    let first = circuit.and_gate(a, b);
+    let second = circuit.and_gate(a, b);
+    let out = circuit.xor_gate(first, second);
+    circuit.add_output_wire(out);
+    circuit.add_output_wire(second);
+
+    let canonical = circuit.canonicalize().unwrap();
+    assert_eq!(canonical.gates.len(), 2);
+    assert_eq!(canonical.output_wires[1], canonical.gates[0].output_wire);
+
+    for inputs in [[false, false], [true, false], [true, true]] {
+        let garbler = Garbler::new();
+        let garbled = garbler.garble_circuit(&canonical).unwrap();
+        let labels = garbler.get_input_labels(&garbled, &inputs).unwrap();
+        let mut evaluator = Evaluator::new();
+        let outputs = evaluator.evaluate(&garbled, &labels).unwrap();
+        assert_eq!(evaluator.decode_output(&outputs, &garbled).unwrap(), vec![false, inputs[0] && inputs[1]]);
+    }
+}
+
+#[test]
+fn test_garbled_circuit_serde_and_verification() {
+    let circuit = Circuit::create_mux(2);
+    let json = serde_json::to_string(&circuit).unwrap();
+    let decoded: Circuit = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, circuit);
+
+    let garbled = Garbler::new().garble_circuit(&circuit).unwrap();
+    let bytes = bincode::serialize(&garbled).unwrap();
+    let restored: GarbledCircuit = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(restored, garbled);
+
+    // 混淆电路的摘要不受标签影响，与明文电路一致
+    let digest = circuit.circuit_digest().unwrap();
+    assert_eq!(restored.circuit_digest().unwrap(), digest);
+    assert_eq!(Garbler::new().garble_circuit(&circuit).unwrap().circuit_digest().unwrap(), digest);
+    restored.verify_circuit(&digest).unwrap();
+
+    // 混淆方替换了一个门
+    let mut tampered = restored.clone();
+    let index = tampered.gates.iter().position(|gate| gate.gate_type == GateType::And).unwrap();
+    tampered.gates[index].gate_type = GateType::Nand;
+    assert!(matches!(
+        tampered.verify_circuit(&digest),
+        Err(mpc_api::MpcError::AuthenticationError(_))
+    ));
+}
+
+// ===== Garbled Table File Tests =====
+
+#[test]
+fn test_garbled_table_file_roundtrip() {
+    let dir = std::env::temp_dir().join(format!(
+        "mpc_api_garbled_tables_{}_{}",
+        std::process::id(),
+        rand::random::<u64>()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    // 包含两输入表、多扇入表、常量门和无表的门
+    let mut circuit = Circuit::create_adder(4);
+    let inputs = circuit.input_wires.clone();
+    let wide = circuit.multi_and_gate(&inputs[..4]);
+    let constant = circuit.constant(true);
+    let combined = circuit.xor_gate(wide, constant);
+    circuit.add_output_wire(combined);
+    let garbled = Garbler::new().garble_circuit(&circuit).unwrap();
+
+    for key in [None, Some(&[6u8; 32][..])] {
+        let path = dir.join(format!("tables-{}", key.is_some()));
+        assert_eq!(write_garbled_tables(&path, &garbled, key).unwrap(), garbled.gates.len() as u64);
+
+        let file = GarbledTableFile::open(&path, key).unwrap();
+        assert_eq!(file.max_table_labels(), (1 << 4) * GARBLED_ROW_LABELS);
+        for (read, gate) in file.gates().zip(&garbled.gates) {
+            let read = read.unwrap();
+            assert_eq!(read.id, gate.id);
+            assert_eq!(read.gate_type, gate.gate_type);
+            assert_eq!(read.input_wires, gate.input_wires);
+            assert_eq!(read.output_wire, gate.output_wire);
+            assert_eq!(read.garbled_table, gate.garbled_table);
+        }
+
+        // 把读回的门放回电路后求值结果不变
+        let mut restored = garbled.clone();
+        restored.gates = file.gates().collect::<mpc_api::Result<Vec<_>>>().unwrap();
+        let bits: Vec<bool> = (0..8).map(|i| i % 3 == 0).collect();
+        let labels = Garbler::new().get_input_labels(&restored, &bits).unwrap();
+        let mut evaluator = Evaluator::new();
+        let outputs = evaluator.evaluate(&restored, &labels).unwrap();
+        let expected = {
+            let labels = Garbler::new().get_input_labels(&garbled, &bits).unwrap();
+            Evaluator::new().evaluate(&garbled, &labels).unwrap()
+        };
+        assert_eq!(outputs, expected);
+    }
+
+    // 表超过声明的容量时拒绝写入
+    let mut writer = GarbledTableWriter::create(dir.join("small"), 2, None).unwrap();
+    let table_gate = garbled.gates.iter().find(|gate| gate.garbled_table.as_ref().is_some_and(|t| t.len() > 2)).unwrap();
+    assert!(writer.append(table_gate).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// ===== Bristol Format Tests =====
+
+#[test]
+fn test_bristol_parse_and_roundtrip() {
+    // 输入 a (2 位)、b (1 位)，输出 (a0 AND b, a1 AND b) 的反、常量 1 以及 a0 XOR a1 的副本
+    let text = "\
+6 10
+2 2 1
+1 4
+
+4 2 0 1 2 2 3 4 MAND
+1 1 3 5 INV
+2 1 0 1 7 XOR
+1 1 1 9 EQ
+1 1 7 8 EQW
+1 1 4 6 INV
+";
+    let bristol = BristolCircuit::parse(text).unwrap();
+    assert_eq!(bristol.input_widths, vec![2, 1]);
+    assert_eq!(bristol.output_widths, vec![4]);
+    assert_eq!(bristol.input_wires(1).unwrap(), &[2]);
+    assert_eq!(bristol.output_wires(0).unwrap(), &[6, 7, 8, 9]);
+    assert_eq!(bristol.and_count(), 2);
+
+    let reparsed = BristolCircuit::parse(&bristol.to_bristol().unwrap()).unwrap();
+    for assignment in 0..8u32 {
+        let inputs: Vec<bool> = (0..3).map(|i| (assignment >> i) & 1 == 1).collect();
+        let (a0, a1, b) = (inputs[0], inputs[1], inputs[2]);
+        let expected = vec![!(a1 && b), a0 ^ a1, a0 ^ a1, true];
+        assert_eq!(simulate(&bristol.circuit, &inputs).unwrap(), expected);
+        assert_eq!(simulate(&reparsed.circuit, &inputs).unwrap(), expected);
+    }
+
+    // 输出线直接是输入线或重复出现时写出 EQW
+    let mut circuit = Circuit::new();
+    let a = circuit.add_input_wire();
+    let b = circuit.add_input_wire();
+    let nand = circuit.nand_gate(a, b);
+    for wire in [a, nand, nand] {
+        circuit.add_output_wire(wire);
+    }
+    let bristol = BristolCircuit::new(circuit, vec![1, 1], vec![3]).unwrap();
+    let reparsed = BristolCircuit::parse(&bristol.to_bristol().unwrap()).unwrap();
+    assert_eq!(simulate(&reparsed.circuit, &[true, true]).unwrap(), vec![true, false, false]);
+
+    // 没有 Bristol 编码的门、格式错误的文件
+    assert!(BristolCircuit::new(extended_gate_circuit(), vec![4], vec![7]).unwrap().to_bristol().is_err());
+    assert!(BristolCircuit::new(Circuit::create_adder(2), vec![4], vec![2]).is_err());
+    for malformed in [
+        "1 3\n2 1 1\n1 1\n\n2 1 0 5 2 AND\n",
+        "1 3\n2 1 1\n1 1\n\n2 1 0 2 2 AND\n",
+        "2 3\n2 1 1\n1 1\n\n2 1 0 1 2 AND\n",
+        "1 3\n2 1 1\n1 1\n\n2 1 0 1 2 OR\n",
+        "1 3\n2 1 1\n1 1\n\n1 1 2 2 EQ\n",
+        "1 3\n3 1 1\n1 1\n\n2 1 0 1 2 AND\n",
+    ] {
+        assert!(BristolCircuit::parse(malformed).is_err(), "{:?}", malformed);
+    }
+}
+
+// ===== Half-Gates Tests =====
+
+#[test]
+fn test_half_gates_match_simulation() {
+    let circuit = extended_gate_circuit();
+    let compiled = HalfGatesCircuit::compile(&circuit).unwrap();
+    assert_eq!((compiled.input_count(), compiled.output_count()), (4, 7));
+
+    let mut rng = rand::thread_rng();
+    for assignment in 0..16u32 {
+        let inputs: Vec<bool> = (0..4).map(|i| (assignment >> i) & 1 == 1).collect();
+        let (garbled, encoding) = compiled.garble(&mut rng);
+        assert_eq!(garbled.tables.len(), compiled.and_count());
+
+        let labels = encoding.encode(&inputs).unwrap();
+        let outputs = compiled.evaluate(&garbled, &labels).unwrap();
+        assert_eq!(garbled.decode(&outputs).unwrap(), simulate(&circuit, &inputs).unwrap());
+    }
+
+    // 两个标签的选择位相反，且只有一个是求值方持有的
+    let (garbled, encoding) = compiled.garble(&mut rng);
+    let (zero, one) = encoding.label_pair(0).unwrap();
+    assert_ne!(zero[0] & 1, one[0] & 1);
+    assert_eq!(encoding.label(0, true), Some(one));
+    assert!(encoding.label_pair(4).is_none());
+
+    // 混淆表数量或输入数量不匹配时拒绝求值
+    let labels = encoding.encode(&[true, false, true, true]).unwrap();
+    let mut truncated = garbled.clone();
+    truncated.tables.pop();
+    assert!(compiled.evaluate(&truncated, &labels).is_err());
+    assert!(compiled.evaluate(&garbled, &labels[..3]).is_err());
+    assert!(encoding.encode(&[true]).is_err());
+}
+
+// ===== AES Circuit Tests =====
+
+#[test]
+fn test_aes_circuit_matches_software_aes() {
+    let aes = AesCircuit::builtin();
+    assert_eq!(aes.and_count(), 6800);
+
+    // FIPS-197 附录 C.1
+    let key: AesBlock = std::array::from_fn(|i| i as u8);
+    let plaintext: AesBlock = std::array::from_fn(|i| (i as u8) * 0x11);
+    let expected = [
+        0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4, 0xc5, 0x5a,
+    ];
+    assert_eq!(aes128_encrypt(&key, &plaintext), expected);
+    assert_eq!(aes.evaluate_plain(&key, &plaintext).unwrap(), expected);
+
+    let compiled = HalfGatesCircuit::compile(aes.circuit()).unwrap();
+    let mut rng = rand::thread_rng();
+    for _ in 0..3 {
+        let key: AesBlock = rand::random();
+        let plaintext: AesBlock = rand::random();
+        let expected = aes128_encrypt(&key, &plaintext);
+        assert_eq!(aes.evaluate_plain(&key, &plaintext).unwrap(), expected);
+
+        let (garbled, encoding) = compiled.garble(&mut rng);
+        let labels = encoding.encode(&aes.encode_inputs(&key, &plaintext)).unwrap();
+        let outputs = compiled.evaluate(&garbled, &labels).unwrap();
+        assert_eq!(aes.decode_ciphertext(&garbled.decode(&outputs).unwrap()).unwrap(), expected);
+    }
+}
+
+/// 按给定布局重新排列内置电路的输入输出线，模拟其他来源的电路文件
+fn relayout_builtin(plaintext_first: bool, bit_order: AesBitOrder) -> BristolCircuit {
+    let mut bristol = aes128_circuit();
+    let reorder = |wires: &[WireId]| -> Vec<WireId> {
+        match bit_order {
+            AesBitOrder::MsbFirst => wires.to_vec(),
+            AesBitOrder::LsbFirst => wires.chunks(8).flat_map(|byte| byte.iter().rev().copied()).collect(),
+            AesBitOrder::Reversed => wires.iter().rev().copied().collect(),
+        }
+    };
+    let (key, plaintext) = bristol.circuit.input_wires.split_at(128);
+    let (first, second) = if plaintext_first { (plaintext, key) } else { (key, plaintext) };
+    bristol.circuit.input_wires = [reorder(first), reorder(second)].concat();
+    bristol.circuit.output_wires = reorder(&bristol.circuit.output_wires);
+    bristol
+}
+
+#[test]
+fn test_aes_circuit_layout_detection() {
+    let dir = std::env::temp_dir().join(format!("mpc_api_aes_{}_{}", std::process::id(), rand::random::<u64>()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let key: AesBlock = rand::random();
+    let plaintext: AesBlock = rand::random();
+
+    for (plaintext_first, bit_order) in [
+        (false, AesBitOrder::MsbFirst),
+        (true, AesBitOrder::LsbFirst),
+        (false, AesBitOrder::Reversed),
+    ] {
+        // 经过 Bristol 文件往返后仍能识别布局
+        let path = dir.join(format!("aes_{}_{:?}.txt", plaintext_first, bit_order));
+        relayout_builtin(plaintext_first, bit_order).save(&path).unwrap();
+        let aes = AesCircuit::load(&path).unwrap();
+        assert_eq!(aes.bit_order(), bit_order);
+        assert_eq!(aes.key_positions().start, if plaintext_first { 128 } else { 0 });
+        assert_eq!(aes.evaluate_plain(&key, &plaintext).unwrap(), aes128_encrypt(&key, &plaintext));
+    }
+
+    // 不是 AES 的电路
+    let mut broken = aes128_circuit();
+    broken.circuit.output_wires.swap(0, 1);
+    assert!(AesCircuit::from_bristol(broken).is_err());
+    let adder = BristolCircuit::new(Circuit::create_adder(128), vec![128, 128], vec![129]).unwrap();
+    assert!(AesCircuit::from_bristol(adder).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// ===== Two-Party AES Tests =====
+
+#[test]
+fn test_two_party_aes_gc() {
+    use mpc_api::protocols::aes_gc::*;
+
+    let circuit = AesGcCircuit::builtin().unwrap();
+    let key: AesBlock = rand::random();
+    let plaintext: AesBlock = rand::random();
+
+    let (ciphertext, stats) = execute_aes_gc(&circuit, &key, &plaintext).unwrap().into_parts();
+    assert_eq!(ciphertext, aes128_encrypt(&key, &plaintext));
+    assert_eq!(stats.rounds, 3);
+    assert!(stats.bytes_sent > (circuit.and_count() * 32) as u64);
+
+    // 求值方拒绝按另一个电路混淆的消息
+    let (_, mut offer) = AesKeyHolder::start(&circuit, &key).unwrap();
+    offer.circuit_digest[0] ^= 1;
+    assert!(matches!(
+        AesPlaintextHolder::respond(&circuit, &plaintext, offer),
+        Err(mpc_api::MpcError::AuthenticationError(_))
+    ));
+
+    // 以加载的标准布局电路执行时结果不变
+    let reversed = AesGcCircuit::new(AesCircuit::from_bristol(relayout_builtin(true, AesBitOrder::Reversed)).unwrap()).unwrap();
+    assert_ne!(reversed.digest(), circuit.digest());
+    assert_eq!(execute_aes_gc(&reversed, &key, &plaintext).unwrap().result, aes128_encrypt(&key, &plaintext));
+
+    // 吞吐量测量同时检查每个分组的结果
+    let report = benchmark_aes_gc(&circuit, 2).unwrap();
+    assert_eq!(report.and_gates, 6800);
+    assert_eq!(report.garbled_bytes_per_block, 6800 * 32 + 128);
+    assert!(report.blocks_per_second > 0.0 && report.end_to_end_blocks_per_second > 0.0);
+    assert_eq!(report.protocol_stats.rounds, 3);
+    assert!(benchmark_aes_gc(&circuit, 0).is_err());
+}
+
+// ===== Compact Encoding Tests =====
+
+#[test]
+fn test_garbled_circuit_compact_roundtrip() {
+    // 覆盖有混淆表、无混淆表和常量门
+    let mut circuit = Circuit::new();
+    let inputs: Vec<_> = (0..4).map(|_| circuit.add_input_wire()).collect();
+    let and = circuit.and_gate(inputs[0], inputs[1]);
+    let not = circuit.not_gate(and);
+    let wide = circuit.multi_or_gate(&inputs);
+    let constant = circuit.add_gate(GateType::Const(true), Vec::new());
+    let out = circuit.xnor_gate(not, wide);
+    circuit.add_output_wire(out);
+    circuit.add_output_wire(constant);
+
+    let estimate = estimate_garbled_size(&circuit).unwrap();
+    let garbler = Garbler::new();
+    let garbled = garbler.garble_circuit(&circuit).unwrap();
+    let bytes = garbled.to_compact_bytes();
+    assert_eq!(garbled.compact_size(), estimate);
+    assert_eq!(bytes.len(), estimate.total());
+    assert_eq!(estimate.tables, (8 + 32 + 1 + 8) * 16);
+
+    let decoded = GarbledCircuit::from_compact_bytes(&bytes).unwrap();
+    assert_eq!(decoded, garbled);
+    let labels = garbler.get_input_labels(&decoded, &[true, true, false, false]).unwrap();
+    assert_eq!(evaluate_garbled_circuit(&decoded, &labels).unwrap(), vec![false, true]);
+
+    // 截断、多余字节和未知版本都被拒绝
+    assert!(GarbledCircuit::from_compact_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(GarbledCircuit::from_compact_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+    let mut wrong_version = bytes.clone();
+    wrong_version[0] = COMPACT_FORMAT_VERSION + 1;
+    assert!(GarbledCircuit::from_compact_bytes(&wrong_version).is_err());
+    assert!(GarbledCircuit::from_compact_bytes(&[COMPACT_FORMAT_VERSION, 0xff, 0xff, 0xff, 0xff, 0x0f]).is_err());
+}
+
+#[test]
+fn test_garbled_circuit_compact_size() {
+    let circuit = Circuit::create_adder(32);
+    let garbled = Garbler::new().garble_circuit(&circuit).unwrap();
+    let compact = garbled.to_compact_bytes();
+    let size = garbled.compact_size();
+    assert_eq!(compact.len(), size.total());
+    assert_eq!(size.wire_labels, 2 + circuit.wire_count as usize * 33);
+
+    // 比 serde 的通用编码更小，差距主要来自线 ID 和标签的长度前缀
+    let bincode_len = bincode::serialize(&garbled).unwrap().len();
+    assert!(compact.len() < bincode_len, "{} >= {}", compact.len(), bincode_len);
+
+    // 作为 serde 字段嵌入其它消息
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Offer {
+        #[serde(with = "mpc_api::garbled_circuits::compact::serde_compact")]
+        garbled: GarbledCircuit,
+    }
+    let offer = bincode::serialize(&Offer { garbled: garbled.clone() }).unwrap();
+    assert_eq!(offer.len(), compact.len() + 8);
+    let decoded: Offer = bincode::deserialize(&offer).unwrap();
+    assert_eq!(decoded.garbled, garbled);
+
+    // 无法混淆的门没有估计值
+    let mut invalid = Circuit::new();
+    let wires: Vec<_> = (0..MAX_NATIVE_FAN_IN + 1).map(|_| invalid.add_input_wire()).collect();
+    invalid.multi_and_gate(&wires);
+    assert!(estimate_garbled_size(&invalid).is_err());
+}
+
+// ===== Yao Two-Party Tests =====
+
+#[test]
+fn test_yao_two_party_adder() {
+    let circuit = Circuit::create_adder(4);
+    // 输入按 a0, b0, a1, b1, ... 交错排列，混淆方持有 a
+    let protocol = Yao2PC::new(&circuit, (0..8).step_by(2)).unwrap();
+    assert_eq!(protocol.garbler_input_count(), 4);
+    assert_eq!(protocol.evaluator_input_count(), 4);
+
+    let bits = |value: u8, width: usize| (0..width).map(|i| (value >> i) & 1 == 1).collect::<Vec<_>>();
+    for (a, b) in [(0u8, 0u8), (5, 9), (15, 15), (7, 1)] {
+        let (output, stats) = protocol.execute(&bits(a, 4), &bits(b, 4)).unwrap().into_parts();
+        assert_eq!(output, bits(a + b, 5));
+        assert_eq!(stats.rounds, 3);
+    }
+
+    // 另一种输入划分得到不同的摘要，求值方拒绝
+    let swapped = Yao2PC::new(&circuit, (1..8).step_by(2)).unwrap();
+    assert_ne!(swapped.digest(), protocol.digest());
+    let (_, offer) = YaoGarbler::start(&swapped, &bits(3, 4)).unwrap();
+    assert!(matches!(
+        YaoEvaluator::respond(&protocol, &bits(3, 4), offer),
+        Err(mpc_api::MpcError::AuthenticationError(_))
+    ));
+
+    // 输入数量、位置或 OT 消息数量不匹配
+    assert!(Yao2PC::new(&circuit, [0, 0]).is_err());
+    assert!(Yao2PC::new(&circuit, [8]).is_err());
+    assert!(protocol.execute(&bits(1, 3), &bits(1, 4)).is_err());
+    assert!(protocol.execute(&bits(1, 4), &bits(1, 5)).is_err());
+    let (garbler, offer) = YaoGarbler::start(&protocol, &bits(1, 4)).unwrap();
+    let (_, mut request) = YaoEvaluator::respond(&protocol, &bits(2, 4), offer).unwrap();
+    request.ot_responses.pop();
+    assert!(garbler.transfer(&request).is_err());
+}
+
+#[test]
+fn test_yao_two_party_one_sided_inputs() {
+    // 全部输入属于求值方或混淆方时协议仍然完整
+    let mut circuit = Circuit::new();
+    let x = circuit.add_input_wire();
+    let y = circuit.add_input_wire();
+    let out = circuit.and_gate(x, y);
+    circuit.add_output_wire(out);
+
+    let evaluator_only = Yao2PC::new(&circuit, []).unwrap();
+    assert_eq!(evaluator_only.execute(&[], &[true, true]).unwrap().result, vec![true]);
+    let garbler_only = Yao2PC::new(&circuit, [1, 0]).unwrap();
+    assert_eq!(garbler_only.execute(&[true, false], &[]).unwrap().result, vec![false]);
+}
+
+// ===== Circuit Builder Tests =====
+
+#[test]
+fn test_circuit_builder_arithmetic() {
+    let width = 8;
+    let mut builder = CircuitBuilder::new();
+    let a = builder.input_bits(width);
+    let b = builder.input_bits(width);
+    let (ripple, ripple_carry) = builder.ripple_carry_add(&a, &b);
+    let (kogge, kogge_carry) = builder.kogge_stone_add(&a, &b);
+    let (difference, borrow) = builder.subtract(&a, &b);
+    for wires in [&ripple, &kogge, &difference] {
+        builder.output_bits(wires);
+    }
+    for wire in [ripple_carry, kogge_carry, borrow] {
+        builder.output(wire);
+    }
+    let circuit = builder.finish();
+
+    let values = [0u64, 1, 2, 77, 128, 200, 254, 255];
+    for &x in &values {
+        for &y in &values {
+            let mut inputs = CircuitBuilder::encode(x, width);
+            inputs.extend(CircuitBuilder::encode(y, width));
+            let outputs = simulate(&circuit, &inputs).unwrap();
+            let sum = (x + y) & 0xff;
+            assert_eq!(CircuitBuilder::decode(&outputs[..8]), sum);
+            assert_eq!(CircuitBuilder::decode(&outputs[8..16]), sum);
+            assert_eq!(CircuitBuilder::decode(&outputs[16..24]), x.wrapping_sub(y) & 0xff);
+            assert_eq!(outputs[24..], [x + y > 0xff, x + y > 0xff, x < y]);
+        }
+    }
+
+    // Kogge-Stone 用更多 AND 门换取对数深度，两种加法器在混淆后结果一致
+    let and_count = |circuit: &Circuit| circuit.gates.iter().filter(|gate| gate.gate_type == GateType::And).count();
+    let mut ripple_only = CircuitBuilder::new();
+    let (x, y) = (ripple_only.input_bits(width), ripple_only.input_bits(width));
+    let (sum, _) = ripple_only.ripple_carry_add(&x, &y);
+    ripple_only.output_bits(&sum);
+    assert_eq!(and_count(ripple_only.circuit()), width);
+
+    let protocol = Yao2PC::new(&circuit, 0..width).unwrap();
+    let output = protocol.execute(&CircuitBuilder::encode(200, width), &CircuitBuilder::encode(99, width)).unwrap().result;
+    assert_eq!(CircuitBuilder::decode(&output[8..16]), (200 + 99) & 0xff);
+}
+
+#[test]
+fn test_circuit_builder_comparisons() {
+    let width = 64;
+    let mut builder = CircuitBuilder::new();
+    let a = builder.input_bits(width);
+    let b = builder.input_bits(width);
+    let selector = builder.input();
+    let outputs = [
+        builder.less_than(&a, &b),
+        builder.greater_than(&a, &b),
+        builder.less_or_equal(&a, &b),
+        builder.greater_or_equal(&a, &b),
+        builder.signed_less_than(&a, &b),
+        builder.equal(&a, &b),
+    ];
+    builder.output_bits(&outputs);
+    let chosen = builder.mux(selector, &a, &b);
+    builder.output_bits(&chosen);
+    let circuit = builder.finish();
+
+    let values = [0u64, 1, 42, u64::MAX / 2, 1 << 63, u64::MAX - 1, u64::MAX];
+    for &x in &values {
+        for &y in &values {
+            for select in [false, true] {
+                let mut inputs = CircuitBuilder::encode(x, width);
+                inputs.extend(CircuitBuilder::encode(y, width));
+                inputs.push(select);
+                let outputs = simulate(&circuit, &inputs).unwrap();
+                assert_eq!(outputs[..6], [x < y, x > y, x <= y, x >= y, (x as i64) < (y as i64), x == y]);
+                assert_eq!(CircuitBuilder::decode(&outputs[6..]), if select { y } else { x });
+            }
+        }
+    }
+
+    // 64 位比较只需 64 个 AND 门
+    let mut comparator = CircuitBuilder::new();
+    let (x, y) = (comparator.input_bits(width), comparator.input_bits(width));
+    let less = comparator.less_than(&x, &y);
+    comparator.output(less);
+    let compiled = HalfGatesCircuit::compile(comparator.circuit()).unwrap();
+    assert_eq!(compiled.and_count(), width);
+}
+
+#[test]
+#[should_panic(expected = "same width")]
+fn test_circuit_builder_rejects_mismatched_widths() {
+    let mut builder = CircuitBuilder::new();
+    let a = builder.input_bits(4);
+    let b = builder.input_bits(5);
+    builder.less_than(&a, &b);
+}
@@ -6,8 +6,12 @@ use mpc_api::homomorphic_encryption::{HomomorphicEncryption, AdditivelyHomomorph
 use mpc_api::homomorphic_encryption::bfv::*;
 use mpc_api::homomorphic_encryption::rsa::*;
 use mpc_api::homomorphic_encryption::elgamal::*;
+use mpc_api::homomorphic_encryption::exponential_elgamal::*;
 use mpc_api::homomorphic_encryption::paillier::*;
 use mpc_api::secret_sharing::{FIELD_PRIME, field_mul};
+use num_bigint::BigUint;
+use num_traits::Zero;
+use std::sync::OnceLock;
 
 // ===== BFV Tests =====
 
@@ -32,9 +36,8 @@ fn test_bfv_encrypt_decrypt() {
     let ciphertext = BFV::encrypt(&pk, &message).unwrap();
     let decrypted = BFV::decrypt(&sk, &ciphertext).unwrap();
     
-    // Due to noise, exact equality might not hold
-    // In practice, we'd check if decrypted is close to message
-    assert!(decrypted < pk.t);
+    assert_eq!(decrypted, message);
+    assert_eq!(ciphertext.c0.len(), pk.n * pk.rns_moduli.len());
 }
 
 #[test]
@@ -49,8 +52,89 @@ fn test_bfv_homomorphic_addition() {
     let c_sum = BFV::add_ciphertexts(&pk, &c1, &c2).unwrap();
     let decrypted_sum = BFV::decrypt(&sk, &c_sum).unwrap();
     
-    // Check if result is reasonable (noise might affect exact equality)
-    assert!(decrypted_sum < pk.t);
+    assert_eq!(decrypted_sum, m1 + m2);
+    let scaled = BFV::scalar_multiply(&pk, &c1, &(pk.t + 10)).unwrap();
+    assert_eq!(BFV::decrypt(&sk, &scaled).unwrap(), 30);
+}
+
+#[test]
+fn test_bfv_homomorphic_multiplication() {
+    let (pk, sk) = BFV::keygen().unwrap();
+    assert!(!pk.relin_key.is_empty());
+
+    for (m1, m2) in [(3u64, 5u64), (7, 9), (0, 11), (15, 15)] {
+        let c1 = BFV::encrypt(&pk, &m1).unwrap();
+        let c2 = BFV::encrypt(&pk, &m2).unwrap();
+        assert_eq!(BFV::decrypt(&sk, &c1).unwrap(), m1);
+
+        let product = BFV::multiply_ciphertexts(&pk, &c1, &c2).unwrap();
+        assert_eq!(product.c0.len(), pk.n * pk.rns_moduli.len());
+        assert_eq!(BFV::decrypt(&sk, &product).unwrap(), m1 * m2 % pk.t);
+
+        // 乘积可以继续参与加法和乘法
+        let sum = BFV::add_ciphertexts(&pk, &product, &c1).unwrap();
+        assert_eq!(BFV::decrypt(&sk, &sum).unwrap(), (m1 * m2 + m1) % pk.t);
+        let cube = BFV::multiply_ciphertexts(&pk, &product, &c2).unwrap();
+        assert_eq!(BFV::decrypt(&sk, &cube).unwrap(), m1 * m2 * m2 % pk.t);
+    }
+
+    let mut no_relin = pk.clone();
+    no_relin.relin_key.clear();
+    let c = BFV::encrypt(&pk, &2).unwrap();
+    assert!(BFV::multiply_ciphertexts(&no_relin, &c, &c).is_err());
+}
+
+#[test]
+fn test_bfv_modulus_switching() {
+    let (pk, sk) = BFV::keygen().unwrap();
+    let levels = pk.rns_moduli.len();
+    assert!(levels > 1);
+
+    let c1 = BFV::encrypt(&pk, &6).unwrap();
+    let c2 = BFV::encrypt(&pk, &7).unwrap();
+    let product = BFV::multiply_ciphertexts(&pk, &c1, &c2).unwrap();
+    assert_eq!(BFV::level(&pk, &product).unwrap(), levels - 1);
+
+    // 每次切换去掉一个素数，明文不变
+    let switched = BFV::modulus_switch(&pk, &product).unwrap();
+    assert_eq!(switched.c0.len(), pk.n * (levels - 1));
+    assert_eq!(BFV::level(&pk, &switched).unwrap(), levels - 2);
+    assert_eq!(BFV::decrypt(&sk, &switched).unwrap(), 42);
+
+    // 低层级的密文仍可相乘，但不能与高层级的密文混合
+    let c3 = BFV::modulus_switch(&pk, &BFV::encrypt(&pk, &3).unwrap()).unwrap();
+    let lower = BFV::multiply_ciphertexts(&pk, &switched, &c3).unwrap();
+    assert_eq!(BFV::decrypt(&sk, &lower).unwrap(), 126);
+    assert!(BFV::add_ciphertexts(&pk, &switched, &c1).is_err());
+    assert!(BFV::multiply_ciphertexts(&pk, &switched, &c1).is_err());
+
+    let mut bottom = switched;
+    while BFV::level(&pk, &bottom).unwrap() > 0 {
+        bottom = BFV::modulus_switch(&pk, &bottom).unwrap();
+    }
+    assert!(BFV::modulus_switch(&pk, &bottom).is_err());
+}
+
+#[test]
+fn test_bfv_power_and_circuit() {
+    use mpc_api::homomorphic_encryption::FullyHomomorphic;
+
+    let params = BFVParameters { n: 1024, t: 257, moduli: mpc_api::utils::math::ntt::ntt_primes(40, 1024, 3).unwrap() };
+    assert!(params.validate().is_ok());
+    assert!(BFVParameters { n: 1000, ..params.clone() }.validate().is_err());
+    let (pk, sk) = BFV::keygen_with_params(&params).unwrap();
+
+    let c = BFV::encrypt(&pk, &3).unwrap();
+    assert_eq!(BFV::decrypt(&sk, &BFV::power(&pk, &c, 0).unwrap()).unwrap(), 1);
+    assert_eq!(BFV::decrypt(&sk, &BFV::power(&pk, &c, 5).unwrap()).unwrap(), 243 % 257);
+
+    // (x + y)·y
+    let inputs = [BFV::encrypt(&pk, &4).unwrap(), BFV::encrypt(&pk, &9).unwrap()];
+    let result = BFV::evaluate_circuit(&pk, |cts| {
+        let sum = BFV::add_ciphertexts(&pk, &cts[0], &cts[1])?;
+        BFV::multiply_ciphertexts(&pk, &sum, &cts[1])
+    }, &inputs).unwrap();
+    assert_eq!(BFV::decrypt(&sk, &result).unwrap(), 117);
 }
 
 // ===== RSA Tests =====
@@ -120,6 +204,21 @@ fn test_elgamal_encrypt_decrypt() {
     assert_eq!(message, decrypted);
 }
 
+#[test]
+fn test_elgamal_keygen_with_joint_generator() {
+    use mpc_api::protocols::generator_setup::JointGenerators;
+
+    let generators = JointGenerators::derive([3u8; 32]).unwrap();
+    let (pk, sk) = ElGamal::keygen_with_generator(generators.elgamal_generator.value).unwrap();
+    assert_eq!(pk.generator, generators.elgamal_generator.value);
+
+    let ciphertext = ElGamal::encrypt(&pk, &42).unwrap();
+    assert_eq!(ElGamal::decrypt(&sk, &ciphertext).unwrap(), 42);
+
+    assert!(ElGamal::keygen_with_generator(1).is_err());
+    assert!(ElGamal::keygen_with_generator(FIELD_PRIME).is_err());
+}
+
 #[test]
 fn test_elgamal_homomorphic_multiplication() {
     let (pk, sk) = ElGamal::keygen().unwrap();
@@ -203,38 +302,117 @@ fn test_elgamal_multiple_multiplications() {
     assert_eq!(decrypted_product, expected_product);
 }
 
+// ===== Exponential ElGamal Tests =====
+
+#[test]
+fn test_exponential_elgamal_encrypt_decrypt() {
+    let (pk, sk) = ExponentialElGamal::keygen().unwrap();
+    for message in [0u64, 1, 42, 65_535, 1 << 20, ExponentialElGamal::DEFAULT_MESSAGE_BOUND] {
+        let ciphertext = ExponentialElGamal::encrypt(&pk, &message).unwrap();
+        assert_eq!(ExponentialElGamal::decrypt(&sk, &ciphertext).unwrap(), message);
+    }
+
+    // 同一明文的两次加密不同，重新随机化后仍解密为原值
+    let c1 = ExponentialElGamal::encrypt(&pk, &7).unwrap();
+    let c2 = ExponentialElGamal::encrypt(&pk, &7).unwrap();
+    assert_ne!(c1, c2);
+    let randomized = ExponentialElGamal::randomize_ciphertext(&pk, &c1).unwrap();
+    assert_ne!(randomized, c1);
+    assert_eq!(ExponentialElGamal::decrypt(&sk, &randomized).unwrap(), 7);
+}
+
+#[test]
+fn test_exponential_elgamal_tally() {
+    let (pk, sk) = ExponentialElGamal::keygen().unwrap();
+
+    // 100 张 0/1 选票的加密计票
+    let votes: Vec<u64> = (0..100).map(|i| (i % 3 == 0) as u64).collect();
+    let ballots: Vec<_> = votes.iter().map(|vote| ExponentialElGamal::encrypt(&pk, vote).unwrap()).collect();
+    let tally = ExponentialElGamal::sum_ciphertexts(&pk, &ballots).unwrap();
+    assert_eq!(ExponentialElGamal::decrypt(&sk, &tally).unwrap(), votes.iter().sum::<u64>());
+
+    let weighted = ExponentialElGamal::scalar_multiply(&pk, &tally, &1000).unwrap();
+    assert_eq!(ExponentialElGamal::decrypt(&sk, &weighted).unwrap(), 34_000);
+    let sum = ExponentialElGamal::add_ciphertexts(&pk, &weighted, &ballots[0]).unwrap();
+    assert_eq!(ExponentialElGamal::decrypt(&sk, &sum).unwrap(), 34_001);
+    let empty = ExponentialElGamal::sum_ciphertexts(&pk, &[]).unwrap();
+    assert_eq!(ExponentialElGamal::decrypt(&sk, &empty).unwrap(), 0);
+}
+
+#[test]
+fn test_exponential_elgamal_discrete_log_table() {
+    let (pk, sk) = ExponentialElGamal::keygen().unwrap();
+
+    // 较小的表：更多大步，但同样覆盖 [0, max_message]
+    let table = DiscreteLogTable::with_baby_steps(10_000, 16).unwrap();
+    assert_eq!(table.baby_steps(), 16);
+    assert_eq!(table.max_message(), 10_000);
+    for message in [0u64, 15, 16, 9_999, 10_000] {
+        let ciphertext = ExponentialElGamal::encrypt(&pk, &message).unwrap();
+        assert_eq!(ExponentialElGamal::decrypt_with_table(&sk, &ciphertext, &table).unwrap(), message);
+    }
+
+    // 超出表范围的明文无法解码
+    let ciphertext = ExponentialElGamal::encrypt(&pk, &10_001).unwrap();
+    assert!(ExponentialElGamal::decrypt_with_table(&sk, &ciphertext, &table).is_err());
+    assert!(ExponentialElGamal::decrypt_with_table(&sk, &ciphertext, &DiscreteLogTable::new(20_000).unwrap()).is_ok());
+    assert!(DiscreteLogTable::with_baby_steps(100, 0).is_err());
+    assert_eq!(DiscreteLogTable::with_baby_steps(10, 1000).unwrap().baby_steps(), 11);
+
+    // 错误的私钥得到的是随机点
+    let (_, other_sk) = ExponentialElGamal::keygen().unwrap();
+    let ciphertext = ExponentialElGamal::encrypt(&pk, &5).unwrap();
+    assert!(ExponentialElGamal::decrypt_with_table(&other_sk, &ciphertext, &table).is_err());
+}
+
 // ===== Paillier Tests =====
 
+/// 2048 位密钥生成较慢，各测试共用一对密钥
+fn paillier_keys() -> (PaillierPublicKey, PaillierPrivateKey) {
+    static KEYS: OnceLock<(PaillierPublicKey, PaillierPrivateKey)> = OnceLock::new();
+    KEYS.get_or_init(|| Paillier::keygen().unwrap()).clone()
+}
+
 #[test]
 fn test_paillier_keygen() {
-    let result = Paillier::keygen();
-    assert!(result.is_ok());
-    
-    let (pk, sk) = result.unwrap();
-    assert!(pk.n > 0);
-    assert!(pk.n_squared > pk.n);
-    assert!(pk.g > 0);
-    assert!(sk.lambda > 0);
-    assert!(sk.mu > 0);
+    let (pk, sk) = paillier_keys();
+    assert_eq!(pk.n.bits(), PAILLIER_DEFAULT_KEY_BITS);
+    assert_eq!(pk.n_squared, &pk.n * &pk.n);
+    assert_eq!(pk.g, &pk.n + 1u32);
+    assert_eq!(&sk.p * &sk.q, sk.n);
+    assert!(sk.lambda > BigUint::zero());
+    assert!(sk.mu > BigUint::zero());
     assert_eq!(pk.n, sk.n);
+
+    let (pk, _) = Paillier::keygen_with_bits(1024).unwrap();
+    assert_eq!(pk.n.bits(), 1024);
+    assert!(Paillier::keygen_with_bits(512).is_err());
+    assert!(Paillier::keygen_with_bits(1025).is_err());
 }
 
 #[test]
 fn test_paillier_encrypt_decrypt() {
-    let (pk, sk) = Paillier::keygen().unwrap();
-    let message = 42u64;
+    let (pk, sk) = paillier_keys();
+    let message = BigUint::from(42u64);
     
     let ciphertext = Paillier::encrypt(&pk, &message).unwrap();
     let decrypted = Paillier::decrypt(&sk, &ciphertext).unwrap();
     
     assert_eq!(message, decrypted);
+
+    // 明文空间是完整的 Z_n
+    let largest = &pk.n - 1u32;
+    let ciphertext = Paillier::encrypt(&pk, &largest).unwrap();
+    assert_eq!(Paillier::decrypt(&sk, &ciphertext).unwrap(), largest);
+    assert!(Paillier::encrypt(&pk, &pk.n).is_err());
+    assert!(Paillier::decrypt(&sk, &PaillierCiphertext { value: pk.n_squared.clone() }).is_err());
 }
 
 #[test]
 fn test_paillier_homomorphic_addition() {
-    let (pk, sk) = Paillier::keygen().unwrap();
-    let m1 = 10u64;
-    let m2 = 20u64;
+    let (pk, sk) = paillier_keys();
+    let m1 = BigUint::from(10u64);
+    let m2 = BigUint::from(20u64);
     
     let c1 = Paillier::encrypt(&pk, &m1).unwrap();
     let c2 = Paillier::encrypt(&pk, &m2).unwrap();
@@ -243,37 +421,37 @@ fn test_paillier_homomorphic_addition() {
     let c_sum = Paillier::add_ciphertexts(&pk, &c1, &c2).unwrap();
     let decrypted_sum = Paillier::decrypt(&sk, &c_sum).unwrap();
     
-    assert_eq!(decrypted_sum, (m1 + m2) % pk.n);
+    assert_eq!(decrypted_sum, (m1 + m2) % &pk.n);
 }
 
 #[test]
 fn test_paillier_scalar_multiplication() {
-    let (pk, sk) = Paillier::keygen().unwrap();
-    let message = 7u64;
-    let scalar = 3u64;
+    let (pk, sk) = paillier_keys();
+    let message = BigUint::from(7u64);
+    let scalar = BigUint::from(3u64);
     
     let ciphertext = Paillier::encrypt(&pk, &message).unwrap();
     let scaled_ciphertext = Paillier::scalar_multiply(&pk, &ciphertext, &scalar).unwrap();
     let decrypted = Paillier::decrypt(&sk, &scaled_ciphertext).unwrap();
     
-    assert_eq!(decrypted, (message * scalar) % pk.n);
+    assert_eq!(decrypted, (message * scalar) % &pk.n);
 }
 
 #[test]
 fn test_paillier_encrypt_zero() {
-    let (pk, sk) = Paillier::keygen().unwrap();
+    let (pk, sk) = paillier_keys();
     
     let zero_encryption = Paillier::encrypt_zero(&pk).unwrap();
     let decrypted = Paillier::decrypt(&sk, &zero_encryption).unwrap();
     
-    assert_eq!(decrypted, 0);
+    assert_eq!(decrypted, BigUint::zero());
 }
 
 #[test]
 fn test_paillier_subtraction() {
-    let (pk, sk) = Paillier::keygen().unwrap();
-    let m1 = 30u64;
-    let m2 = 12u64;
+    let (pk, sk) = paillier_keys();
+    let m1 = BigUint::from(30u64);
+    let m2 = BigUint::from(12u64);
     
     let c1 = Paillier::encrypt(&pk, &m1).unwrap();
     let c2 = Paillier::encrypt(&pk, &m2).unwrap();
@@ -281,19 +459,20 @@ fn test_paillier_subtraction() {
     let c_diff = Paillier::subtract_ciphertexts(&pk, &c1, &c2).unwrap();
     let decrypted_diff = Paillier::decrypt(&sk, &c_diff).unwrap();
     
-    let expected = if m1 >= m2 { m1 - m2 } else { pk.n + m1 - m2 };
-    assert_eq!(decrypted_diff, expected % pk.n);
+    assert_eq!(decrypted_diff, &m1 - &m2);
+
+    // 负数结果在 Z_n 中回绕
+    let c_neg = Paillier::subtract_ciphertexts(&pk, &c2, &c1).unwrap();
+    assert_eq!(Paillier::decrypt(&sk, &c_neg).unwrap(), &pk.n + m2 - m1);
 }
 
 #[test]
 fn test_paillier_multiple_additions() {
-    let (pk, sk) = Paillier::keygen().unwrap();
-    let messages = vec![5u64, 10u64, 15u64, 20u64];
+    let (pk, sk) = paillier_keys();
+    let messages: Vec<BigUint> = [5u64, 10, 15, 20].into_iter().map(BigUint::from).collect();
     
-    let mut ciphertexts = Vec::new();
-    for &msg in &messages {
-        ciphertexts.push(Paillier::encrypt(&pk, &msg).unwrap());
-    }
+    let ciphertexts = Paillier::encrypt_batch(&pk, &messages).unwrap();
+    assert_eq!(Paillier::decrypt_batch(&sk, &ciphertexts).unwrap(), messages);
     
     // Add all ciphertexts together
     let mut sum_ciphertext = ciphertexts[0].clone();
@@ -303,14 +482,14 @@ fn test_paillier_multiple_additions() {
     
     let decrypted_sum = Paillier::decrypt(&sk, &sum_ciphertext).unwrap();
     
-    let expected_sum: u64 = messages.iter().sum();
-    assert_eq!(decrypted_sum, expected_sum % pk.n);
+    let expected_sum: BigUint = messages.iter().sum();
+    assert_eq!(decrypted_sum, expected_sum % &pk.n);
 }
 
 #[test]
 fn test_paillier_randomization() {
-    let (pk, sk) = Paillier::keygen().unwrap();
-    let message = 123u64;
+    let (pk, sk) = paillier_keys();
+    let message = BigUint::from(123u64);
     
     let original_ciphertext = Paillier::encrypt(&pk, &message).unwrap();
     let randomized_ciphertext = Paillier::randomize_ciphertext(&pk, &original_ciphertext).unwrap();
@@ -3,7 +3,8 @@
 //! These tests verify that different components of the MPC API work together correctly
 //! and provide comprehensive test coverage for real-world usage scenarios.
 
-use mpc_api::*;
+use mpc_api::prelude::*;
+use mpc_api::secret_sharing::{field_add, field_mul};
 
 /// Test integration between secret sharing and Beaver triples
 #[test]
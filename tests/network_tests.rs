@@ -164,6 +164,113 @@ mod p2p_tests {
             }
         });
     }
+
+    fn discovery(node_id: &str, port: u16) -> mpc_api::network::p2p::PeerDiscovery {
+        use mpc_api::network::p2p::{GossipConfig, PeerDiscovery};
+
+        PeerDiscovery::new(PeerConfig {
+            node_id: Some(node_id.to_string()),
+            port,
+            gossip: GossipConfig { member_timeout: 200, ..GossipConfig::default() },
+            ..PeerConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_gossip_membership_join_and_expiry() {
+        let bootstrap = discovery("bootstrap", 9000);
+        let alice = discovery("alice", 9001);
+        let bob = discovery("bob", 9002);
+
+        // 通过引导节点加入，引导节点回复完整的成员表
+        let reply = bootstrap.handle_message(&alice.join_request().await.unwrap()).await.unwrap().unwrap();
+        assert!(alice.handle_message(&reply).await.unwrap().is_none());
+        let reply = bootstrap.handle_message(&bob.join_request().await.unwrap()).await.unwrap().unwrap();
+        bob.handle_message(&reply).await.unwrap();
+        assert_eq!(bootstrap.party_count(), 3);
+        assert_eq!(bob.party_count(), 3);
+        assert_eq!(alice.party_count(), 2);
+
+        // alice 通过 gossip 得知 bob
+        alice.handle_message(&bootstrap.gossip_message().await.unwrap()).await.unwrap();
+        let members: Vec<String> = alice.members().await.into_iter().map(|m| m.node_id).collect();
+        assert_eq!(members, vec!["bob".to_string(), "bootstrap".to_string()]);
+
+        // 其他网络的摘要被拒绝
+        let outsider = mpc_api::network::p2p::PeerDiscovery::new(PeerConfig {
+            network_id: "other".to_string(),
+            ..PeerConfig::default()
+        })
+        .unwrap();
+        assert!(bootstrap.handle_message(&outsider.gossip_message().await.unwrap()).await.is_err());
+
+        // bob 主动离开，旧摘要不能让它重新加入
+        let stale = bob.gossip_message().await.unwrap();
+        bootstrap.handle_message(&stale).await.unwrap();
+        bootstrap.handle_message(&bob.leave_message().await.unwrap()).await.unwrap();
+        assert_eq!(bootstrap.party_count(), 2);
+        bootstrap.handle_message(&stale).await.unwrap();
+        assert_eq!(bootstrap.party_count(), 2);
+        bootstrap.handle_message(&bob.gossip_message().await.unwrap()).await.unwrap();
+        assert_eq!(bootstrap.party_count(), 3);
+
+        // 心跳不再增长的成员超时后被移除
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        bootstrap.handle_message(&alice.gossip_message().await.unwrap()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let events = bootstrap.expire_members().await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(bootstrap.members().await[0].node_id, "alice");
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_parties_and_monitor_events() {
+        use mpc_api::network::{MonitorConfig, NetworkEvent, NetworkEventListener, NetworkMonitor};
+        use std::sync::{Arc, Mutex};
+
+        struct Recorder(Arc<Mutex<Vec<String>>>);
+
+        impl NetworkEventListener for Recorder {
+            fn on_event(&self, event: NetworkEvent) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send + '_>> {
+                let entry = match event {
+                    NetworkEvent::PeerJoined { peer_id, .. } => format!("joined:{}", peer_id),
+                    NetworkEvent::PeerLeft { peer_id, .. } => format!("left:{}", peer_id),
+                    _ => String::new(),
+                };
+                self.0.lock().unwrap().push(entry);
+                Box::pin(async {})
+            }
+        }
+
+        let node = P2PNode::new(PeerConfig { node_id: Some("party-0".to_string()), ..PeerConfig::default() })
+            .await
+            .unwrap();
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let monitor = Arc::new(NetworkMonitor::new(MonitorConfig::default()));
+        monitor.add_listener(Box::new(Recorder(Arc::clone(&recorded)))).await;
+        node.set_monitor(monitor).await;
+
+        assert!(matches!(
+            node.wait_for_parties(2, Duration::from_millis(50)).await,
+            Err(NetworkError::Timeout)
+        ));
+
+        let party_1 = discovery("party-1", 9101);
+        let party_2 = discovery("party-2", 9102);
+        let waiter = node.wait_for_parties(3, Duration::from_secs(5));
+        let joins = async {
+            node.handle_incoming("party-1", &party_1.join_request().await.unwrap()).await.unwrap();
+            node.handle_incoming("party-2", &party_2.gossip_message().await.unwrap()).await.unwrap();
+        };
+        let (parties, _) = tokio::join!(waiter, joins);
+        let parties: Vec<String> = parties.unwrap().into_iter().map(|p| p.node_id).collect();
+        assert_eq!(parties, vec!["party-0", "party-1", "party-2"]);
+
+        node.handle_incoming("party-1", &party_1.leave_message().await.unwrap()).await.unwrap();
+        assert_eq!(node.members().await.len(), 1);
+        assert_eq!(*recorded.lock().unwrap(), vec!["joined:party-1", "joined:party-2", "left:party-1"]);
+    }
 }
 
 /// HTTP API 测试
@@ -329,6 +436,173 @@ mod http_tests {
         assert_eq!(HttpMethod::PUT.name(), "PUT");
         assert_eq!(HttpMethod::DELETE.name(), "DELETE");
     }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        use mpc_api::network::http::{HttpRequest, MetricsHandler, RouteHandler};
+        use mpc_api::protocols::ProtocolStats;
+        use mpc_api::security::SecurityManager;
+        use mpc_api::utils::metrics::{MetricsRegistry, PROMETHEUS_CONTENT_TYPE};
+        use std::sync::Arc;
+
+        let registry = MetricsRegistry::new();
+        let stats = ProtocolStats {
+            rounds: 3,
+            bytes_sent: 100,
+            bytes_received: 50,
+            preprocessing_consumed: 2,
+            wall_time: Duration::from_millis(20),
+            ..Default::default()
+        };
+        stats.record_metrics(&registry, "spdz").unwrap();
+        stats.record_metrics(&registry, "spdz").unwrap();
+
+        let manager = NetworkManager::new(NetworkConfig::default());
+        manager.record_protocol_stats(&stats).await;
+        manager.register_metrics(&registry);
+        Arc::new(SecurityManager::new().unwrap()).register_metrics(&registry);
+
+        let handler = MetricsHandler::new(registry.clone());
+        let request = |method: HttpMethod| HttpRequest {
+            method,
+            path: "/metrics".to_string(),
+            query_params: std::collections::HashMap::new(),
+            headers: std::collections::HashMap::new(),
+            body: Vec::new(),
+            client_ip: "127.0.0.1".to_string(),
+            timestamp: std::time::SystemTime::now(),
+            request_id: "test".to_string(),
+        };
+
+        let response = handler.handle_request(&request(HttpMethod::GET)).await.unwrap();
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.headers["Content-Type"], PROMETHEUS_CONTENT_TYPE);
+        let text = String::from_utf8(response.body).unwrap();
+        for line in [
+            "# TYPE mpc_protocol_rounds_total counter",
+            "mpc_protocol_rounds_total{protocol=\"spdz\"} 6",
+            "mpc_bytes_sent_total{protocol=\"spdz\"} 200",
+            "mpc_protocol_duration_seconds_bucket{protocol=\"spdz\",le=\"0.025\"} 2",
+            "mpc_protocol_duration_seconds_count{protocol=\"spdz\"} 2",
+            "mpc_network_protocol_rounds_total 3",
+            "mpc_network_bytes_received_total 50",
+            "# TYPE mpc_security_events gauge",
+        ] {
+            assert!(text.lines().any(|l| l == line), "缺少指标行: {}\n{}", line, text);
+        }
+
+        // 之后的统计在下一次导出时可见
+        manager.record_protocol_stats(&stats).await;
+        let response = handler.handle_request(&request(HttpMethod::GET)).await.unwrap();
+        assert!(String::from_utf8(response.body).unwrap().contains("mpc_network_protocol_rounds_total 6\n"));
+        assert_eq!(handler.handle_request(&request(HttpMethod::POST)).await.unwrap().status_code, 405);
+    }
+
+    fn websocket_config() -> mpc_api::network::http::WebSocketConfig {
+        mpc_api::network::http::WebSocketConfig { port: 0, ..Default::default() }
+    }
+
+    /// 连接一个完成握手后不再读取的客户端
+    async fn silent_client(
+        addr: std::net::SocketAddr,
+        client_id: &str,
+    ) -> tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>> {
+        use futures::{SinkExt, StreamExt};
+        use mpc_api::network::http::WebSocketHello;
+
+        let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{}", addr)).await.unwrap();
+        let hello = serde_json::to_string(&WebSocketHello::observer(client_id).to_message().unwrap()).unwrap();
+        ws.send(tokio_tungstenite::tungstenite::Message::Text(hello)).await.unwrap();
+        assert!(ws.next().await.unwrap().unwrap().is_text());
+        ws
+    }
+
+    #[tokio::test]
+    async fn test_websocket_roles_and_routing() {
+        use mpc_api::network::http::{WebSocketClient, WebSocketConfig, WebSocketHello, WebSocketServer};
+
+        assert!(WebSocketServer::new(WebSocketConfig { send_buffer: 0, ..websocket_config() }).is_err());
+        let config = websocket_config();
+        let mut server = WebSocketServer::new(config.clone()).unwrap();
+        let url = format!("ws://{}", server.start().await.unwrap());
+
+        let mut dashboard = WebSocketClient::connect(&url, WebSocketHello::observer("dashboard"), &config).await.unwrap();
+        let mut input = WebSocketClient::connect(&url, WebSocketHello::input_provider("input"), &config).await.unwrap();
+        assert!(WebSocketClient::connect(&url, WebSocketHello::observer("dashboard"), &config).await.is_err());
+        assert_eq!(
+            server.clients().await.iter().map(|c| c.client_id.as_str()).collect::<Vec<_>>(),
+            vec!["dashboard", "input"]
+        );
+
+        // 输入提供方的消息带上服务器认定的发送者，观察者不能提交消息
+        input.send(NetworkMessage::new("data", b"x").with_sender("p0".to_string())).await.unwrap();
+        let (from, message) = timeout(Duration::from_secs(5), server.recv()).await.unwrap().unwrap();
+        assert_eq!(from, "input");
+        assert_eq!(message.sender_id.as_deref(), Some("input"));
+        assert!(matches!(
+            dashboard.send(NetworkMessage::new("data", b"y")).await,
+            Err(NetworkError::AuthorizationFailed(_))
+        ));
+
+        assert_eq!(server.broadcast(NetworkMessage::new("data", b"result")).await.unwrap(), 2);
+        server.send_to("dashboard", NetworkMessage::new("data", b"only")).await.unwrap();
+        assert!(server.send_to("nobody", NetworkMessage::new("data", b"z")).await.is_err());
+        assert_eq!(dashboard.recv().await.unwrap().payload, b"result");
+        assert_eq!(dashboard.recv().await.unwrap().payload, b"only");
+        assert_eq!(input.recv().await.unwrap().payload, b"result");
+
+        // 服务器断开观察者
+        server.disconnect("dashboard").await.unwrap();
+        assert!(timeout(Duration::from_secs(5), dashboard.recv()).await.unwrap().is_none());
+        input.close().await.unwrap();
+        timeout(Duration::from_secs(5), async {
+            while !server.clients().await.is_empty() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await.unwrap();
+
+        let stats = server.get_stats().await;
+        assert_eq!(stats.total_connections, 2);
+        assert_eq!(stats.messages_received, 1);
+        assert_eq!(stats.messages_sent, 3);
+        server.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_websocket_keepalive_and_slow_consumers() {
+        use mpc_api::network::http::{WebSocketClient, WebSocketConfig, WebSocketHello, WebSocketServer};
+
+        let config = WebSocketConfig { ping_interval: 50, pong_timeout: 50, send_buffer: 4, ..websocket_config() };
+        let mut server = WebSocketServer::new(config.clone()).unwrap();
+        let addr = server.start().await.unwrap();
+
+        // 正常的客户端自动回复 Ping，不读取的客户端被判定为保活超时
+        let client = WebSocketClient::connect(&format!("ws://{}", addr), WebSocketHello::observer("live"), &config)
+            .await
+            .unwrap();
+        let _silent = silent_client(addr, "silent").await;
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(client.is_connected());
+        let clients = server.clients().await;
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].client_id, "live");
+        assert_eq!(server.get_stats().await.keepalive_timeouts, 1);
+        client.close().await.unwrap();
+
+        // 发送队列已满的慢速客户端在广播时被断开
+        let mut server = WebSocketServer::new(WebSocketConfig { send_buffer: 4, ..websocket_config() }).unwrap();
+        let addr = server.start().await.unwrap();
+        let _slow = silent_client(addr, "slow").await;
+        let payload = vec![7u8; 64 * 1024];
+        for _ in 0..10_000 {
+            if server.broadcast(NetworkMessage::new("data", &payload)).await.unwrap() == 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        assert!(server.clients().await.is_empty());
+        assert_eq!(server.get_stats().await.slow_consumer_disconnects, 1);
+    }
 }
 
 /// 网络通用功能测试
@@ -597,6 +871,1128 @@ mod security_tests {
         assert_eq!(TlsVersion::V1_3, TlsVersion::V1_3);
         assert_ne!(TlsVersion::V1_2, TlsVersion::V1_3);
     }
+
+    #[test]
+    fn test_channel_key_rotation() {
+        let policy = RekeyPolicy { max_messages: 3, max_bytes: 1000 };
+        let mut alice = NetworkSecurity::new(None).unwrap().with_rekey_policy(policy);
+        let mut bob = NetworkSecurity::new(None).unwrap().with_rekey_policy(policy);
+        alice.establish_channel_key("bob", [7u8; 32]);
+        bob.establish_channel_key("alice", [7u8; 32]);
+
+        // 按消息数量轮换，双方保持同步
+        let mut keys = Vec::new();
+        for _ in 0..4 {
+            let sent = alice.channel_key_for_message("bob", 10).unwrap();
+            let received = bob.channel_key_for_message("alice", 10).unwrap();
+            assert_eq!(sent, received);
+            keys.push(sent);
+        }
+        assert_eq!(keys[2].0, 0);
+        assert_eq!(keys[3].0, 1);
+        assert_ne!(keys[2].1, keys[3].1);
+
+        // 按字节数轮换
+        let mut schedule = ChannelKeySchedule::new([1u8; 32], policy);
+        schedule.key_for_message(1000);
+        assert!(schedule.needs_rekey());
+        let (epoch, _) = schedule.key_for_message(1);
+        assert_eq!(epoch, 1);
+
+        assert_eq!(alice.rotate_channel_key("bob"), Some(2));
+        alice.close_channel("bob");
+        assert!(alice.channel_key_for_message("bob", 1).is_none());
+    }
+
+    /// 完成三条消息的握手，返回（发起方信道, 响应方信道）
+    fn run_handshake(mut initiator: Handshake, mut responder: Handshake) -> NetworkResult<(SecureChannel, SecureChannel)> {
+        responder.read_message(&initiator.write_message()?)?;
+        initiator.read_message(&responder.write_message()?)?;
+        responder.read_message(&initiator.write_message()?)?;
+        Ok((initiator.into_channel()?, responder.into_channel()?))
+    }
+
+    #[test]
+    fn test_handshake_mutual_authentication_and_rekey() {
+        let alice = PartyIdentity::generate("party-0");
+        let bob = PartyIdentity::generate("party-1");
+        let policy = RekeyPolicy { max_messages: 2, max_bytes: 1 << 20 };
+        let mut alice_security = NetworkSecurity::new(None).unwrap().with_rekey_policy(policy);
+        let mut bob_security = NetworkSecurity::new(None).unwrap().with_rekey_policy(policy);
+        for security in [&mut alice_security, &mut bob_security] {
+            security.register_party_key("party-0", alice.public_key());
+            security.register_party_key("party-1", bob.public_key());
+        }
+
+        let (mut to_bob, mut to_alice) = run_handshake(
+            alice_security.initiate_handshake(alice.clone()),
+            bob_security.accept_handshake(bob.clone()),
+        ).unwrap();
+        assert_eq!(to_bob.peer_party(), "party-1");
+        assert_eq!(to_alice.peer_party(), "party-0");
+        assert_eq!(to_bob.peer_public_key(), &bob.public_key());
+        assert_eq!(to_bob.handshake_hash(), to_alice.handshake_hash());
+
+        // 两个方向独立加密，达到消息上限后轮换密钥
+        for i in 0..5u8 {
+            let frame = to_bob.seal(&[i; 8]);
+            assert_ne!(&frame[16..], &[i; 8]);
+            assert_eq!(to_alice.open(&frame).unwrap(), vec![i; 8]);
+            assert_eq!(to_bob.open(&to_alice.seal(b"ack")).unwrap(), b"ack");
+        }
+        assert_eq!((to_bob.send_epoch(), to_alice.recv_epoch()), (2, 2));
+
+        // 篡改或重放的消息被拒绝，之后信道失效
+        let frame = to_bob.seal(b"share");
+        let mut tampered = frame.clone();
+        tampered[20] ^= 1;
+        assert!(matches!(to_alice.open(&tampered), Err(NetworkError::AuthenticationFailed(_))));
+        assert!(to_alice.open(&frame).is_err());
+
+        // 新的握手得到不同的密钥
+        let (mut second, _) = run_handshake(
+            alice_security.initiate_handshake(alice.clone()),
+            bob_security.accept_handshake(bob.clone()),
+        ).unwrap();
+        assert_ne!(second.handshake_hash(), to_bob.handshake_hash());
+        assert!(to_alice.open(&second.seal(b"x")).is_err());
+    }
+
+    #[test]
+    fn test_handshake_rejects_unregistered_identity() {
+        let alice = PartyIdentity::generate("party-0");
+        let bob = PartyIdentity::generate("party-1");
+        let mallory = PartyIdentity::generate("party-0");
+        let mut directory = PartyDirectory::new();
+        directory.insert("party-0", alice.public_key());
+        directory.insert("party-1", bob.public_key());
+
+        // 冒充 party-0 的发起方在第三条消息被拒绝
+        let result = run_handshake(Handshake::initiator(mallory.clone(), directory.clone()), Handshake::responder(bob.clone(), directory.clone()));
+        assert!(matches!(result, Err(NetworkError::AuthenticationFailed(_))));
+
+        // 冒充 party-1 的响应方在第二条消息被拒绝
+        let impostor = PartyIdentity::generate("party-1");
+        let result = run_handshake(Handshake::initiator(alice.clone(), directory.clone()), Handshake::responder(impostor, directory.clone()));
+        assert!(matches!(result, Err(NetworkError::AuthenticationFailed(_))));
+
+        // 篡改握手消息、调用顺序错误和未完成的握手都会失败
+        let mut initiator = Handshake::initiator(alice.clone(), directory.clone());
+        assert!(initiator.read_message(&[0u8; 32]).is_err());
+        let mut initiator = Handshake::initiator(alice, directory.clone());
+        let mut responder = Handshake::responder(bob, directory);
+        responder.read_message(&initiator.write_message().unwrap()).unwrap();
+        let mut second = responder.write_message().unwrap();
+        let last = second.len() - 1;
+        second[last] ^= 1;
+        assert!(initiator.read_message(&second).is_err());
+        assert!(!initiator.is_complete());
+        assert!(initiator.into_channel().is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter_quotas_and_events() {
+        use mpc_api::security::{AuditLogger, RateLimitPolicy, SecurityPolicy, ThreatType};
+        use std::sync::Arc;
+
+        let policy = RateLimitPolicy {
+            messages_per_second: 1,
+            message_burst: 3,
+            bytes_per_second: 1,
+            byte_burst: 100,
+            max_tracked_peers: 2,
+        };
+        let security = NetworkSecurity::new(None).unwrap().with_rate_limit_policy(policy);
+        let audit_logger = Arc::new(AuditLogger::new(SecurityPolicy::medium()));
+        security.rate_limiter().set_audit_logger(Arc::clone(&audit_logger));
+
+        // 突发上限内的消息被接受，之后的消息被拒绝
+        for _ in 0..3 {
+            security.check_rate_limit("alice", 10).unwrap();
+        }
+        for _ in 0..2 {
+            assert!(matches!(security.check_rate_limit("alice", 10), Err(NetworkError::RateLimited(_))));
+        }
+        let usage = security.rate_limiter().usage("alice").unwrap();
+        assert_eq!((usage.accepted_messages, usage.accepted_bytes), (3, 30));
+        assert_eq!((usage.rejected_messages, usage.rejected_bytes), (2, 20));
+
+        // 字节配额独立计算，其他对端不受影响
+        security.check_rate_limit("bob", 60).unwrap();
+        assert!(security.check_rate_limit("bob", 60).is_err());
+
+        // 连续的拒绝只记录一次
+        let events = audit_logger.get_events_by_type(&ThreatType::DenialOfService);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].peer_id.as_deref(), Some("alice"));
+        assert_eq!(events[0].context.get("limit").map(String::as_str), Some("messages"));
+        assert_eq!(events[1].context.get("limit").map(String::as_str), Some("bytes"));
+
+        // 超过跟踪上限时淘汰最久未活动的对端
+        security.check_rate_limit("carol", 1).unwrap();
+        assert_eq!(security.rate_limiter().tracked_peers(), 2);
+        assert!(security.rate_limiter().usage("alice").is_none());
+
+        // 不限速的策略接受所有消息
+        security.rate_limiter().set_policy(RateLimitPolicy::unlimited());
+        for _ in 0..10 {
+            security.check_rate_limit("bob", 1000).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_p2p_node_enforces_rate_limit() {
+        use mpc_api::network::p2p::DefaultMessageHandler;
+        use mpc_api::security::RateLimitPolicy;
+
+        let node = P2PNode::new(PeerConfig::default()).await.unwrap();
+        node.register_handler("ping".to_string(), Box::new(DefaultMessageHandler)).await;
+        node.rate_limiter().set_policy(RateLimitPolicy {
+            messages_per_second: 1,
+            message_burst: 1,
+            ..RateLimitPolicy::default()
+        });
+
+        let ping = NetworkMessage::new("ping", b"ping");
+        let reply = node.handle_incoming("peer_1", &ping).await.unwrap();
+        assert_eq!(reply.unwrap().message_type, "pong");
+        assert!(matches!(node.handle_incoming("peer_1", &ping).await, Err(NetworkError::RateLimited(_))));
+        assert!(node.handle_incoming("peer_2", &ping).await.is_ok());
+        assert_eq!(node.get_stats().await.messages_received, 2);
+    }
+
+    #[tokio::test]
+    async fn test_p2p_node_reassembles_large_messages() {
+        use mpc_api::network::p2p::DefaultMessageHandler;
+        use mpc_api::network::protocol::{Compression, FrameCodec, FramingConfig, FRAME_MESSAGE_TYPE};
+
+        let framing = FramingConfig { max_frame_size: 4096, compression: Compression::None, ..FramingConfig::default() };
+        let node = P2PNode::new(PeerConfig::default()).await.unwrap().with_framing(framing.clone()).unwrap();
+        node.register_handler("ping".to_string(), Box::new(DefaultMessageHandler)).await;
+        assert_eq!(node.framing().max_frame_size, 4096);
+
+        // 未启动的节点不能发送
+        let ping = NetworkMessage::new("ping", &[9u8; 20_000]);
+        assert!(matches!(node.send_large("peer_1", ping.clone()).await, Err(NetworkError::NotInitialized)));
+
+        let frames = FrameCodec::new(framing).unwrap().encode(&ping).unwrap();
+        assert!(frames.len() > 1);
+        let (last, rest) = frames.split_last().unwrap();
+        for frame in rest {
+            let chunk = NetworkMessage::new(FRAME_MESSAGE_TYPE, frame);
+            assert!(node.handle_incoming("peer_1", &chunk).await.unwrap().is_none());
+        }
+        let reply = node.handle_incoming("peer_1", &NetworkMessage::new(FRAME_MESSAGE_TYPE, last)).await.unwrap();
+        assert_eq!(reply.unwrap().message_type, "pong");
+
+        // 损坏的分片被拒绝
+        let garbage = NetworkMessage::new(FRAME_MESSAGE_TYPE, &[0xff; 8]);
+        assert!(matches!(node.handle_incoming("peer_2", &garbage).await, Err(NetworkError::ProtocolError(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_p2p_nodes_handshake_and_seal_traffic() {
+        use mpc_api::network::p2p::MessageHandler;
+        use mpc_api::network::security::{PartyDirectory, PartyIdentity};
+        use tokio::sync::mpsc;
+
+        struct Recorder(mpsc::UnboundedSender<(String, Vec<u8>)>);
+        impl MessageHandler for Recorder {
+            fn handle_message(
+                &self,
+                from_peer: &str,
+                message: &NetworkMessage,
+            ) -> std::pin::Pin<Box<dyn std::future::Future<Output = NetworkResult<Option<NetworkMessage>>> + Send + '_>> {
+                let _ = self.0.send((from_peer.to_string(), message.payload.clone()));
+                Box::pin(async { Ok(None) })
+            }
+        }
+
+        let free_port = || std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let config = |name: &str, port: u16| PeerConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            node_id: Some(name.to_string()),
+            enable_discovery: false,
+            ..PeerConfig::default()
+        };
+        let alice_identity = PartyIdentity::generate("alice");
+        let bob_identity = PartyIdentity::generate("bob");
+        let mut directory = PartyDirectory::new();
+        directory.insert("alice", alice_identity.public_key());
+        directory.insert("bob", bob_identity.public_key());
+
+        let alice = P2PNode::new(config("alice", free_port())).await.unwrap()
+            .with_identity(alice_identity).unwrap()
+            .with_party_directory(directory.clone()).unwrap();
+        let bob_port = free_port();
+        let bob = P2PNode::new(config("bob", bob_port)).await.unwrap()
+            .with_identity(bob_identity).unwrap()
+            .with_party_directory(directory.clone()).unwrap();
+        assert!(P2PNode::new(config("bob", bob_port)).await.unwrap()
+            .with_identity(PartyIdentity::generate("carol")).is_err());
+
+        let (alice_tx, mut alice_rx) = mpsc::unbounded_channel();
+        let (bob_tx, mut bob_rx) = mpsc::unbounded_channel();
+        alice.register_handler("share".to_string(), Box::new(Recorder(alice_tx))).await;
+        bob.register_handler("share".to_string(), Box::new(Recorder(bob_tx))).await;
+        for node in [&alice, &bob] {
+            let mut node = node.clone();
+            tokio::spawn(async move { node.start().await });
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        // 握手认证出对端的节点 ID，之后消息经加密连接往返
+        let bob_addr = format!("127.0.0.1:{}", bob_port);
+        assert_eq!(alice.connect_to_peer(&bob_addr).await.unwrap(), "bob");
+        alice.send_to_peer("bob", NetworkMessage::new("share", b"to-bob")).await.unwrap();
+        let received = timeout(Duration::from_secs(5), bob_rx.recv()).await.unwrap().unwrap();
+        assert_eq!(received, ("alice".to_string(), b"to-bob".to_vec()));
+        bob.send_to_peer("alice", NetworkMessage::new("share", b"to-alice")).await.unwrap();
+        let received = timeout(Duration::from_secs(5), alice_rx.recv()).await.unwrap().unwrap();
+        assert_eq!(received, ("bob".to_string(), b"to-alice".to_vec()));
+        assert!(alice.get_stats().await.bytes_sent > 0);
+
+        // 公钥未登记的节点不会被接受；不认识对端公钥的发起方直接失败
+        let mallory = P2PNode::new(config("mallory", free_port())).await.unwrap()
+            .with_party_directory(directory).unwrap();
+        let _ = mallory.connect_to_peer(&bob_addr).await;
+        let stranger = P2PNode::new(config("carol", free_port())).await.unwrap();
+        assert!(stranger.connect_to_peer(&bob_addr).await.is_err());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(bob.get_peer_info("mallory").await.is_none());
+        assert!(bob.get_peer_info("carol").await.is_none());
+        assert_eq!(bob.get_peers().await, vec!["alice".to_string()]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_p2p_node_reports_reconnects_and_rejects_quarantined_peers() {
+        use mpc_api::network::security::{PartyDirectory, PartyIdentity};
+        use mpc_api::security::{ProtocolIncident, SecurityManager, SecurityPolicy};
+        use std::sync::Arc;
+
+        let free_port = || std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let config = |name: &str, port: u16| PeerConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            node_id: Some(name.to_string()),
+            enable_discovery: false,
+            ..PeerConfig::default()
+        };
+        let alice_identity = PartyIdentity::generate("alice");
+        let bob_identity = PartyIdentity::generate("bob");
+        let mut directory = PartyDirectory::new();
+        directory.insert("alice", alice_identity.public_key());
+        directory.insert("bob", bob_identity.public_key());
+
+        let manager = Arc::new(SecurityManager::with_policy(SecurityPolicy::strict()).unwrap());
+        let alice = P2PNode::new(config("alice", free_port())).await.unwrap()
+            .with_identity(alice_identity).unwrap()
+            .with_party_directory(directory.clone()).unwrap()
+            .with_security_manager(Arc::clone(&manager));
+        let bob_port = free_port();
+        let bob = P2PNode::new(config("bob", bob_port)).await.unwrap()
+            .with_identity(bob_identity).unwrap()
+            .with_party_directory(directory).unwrap();
+        for node in [&alice, &bob] {
+            let mut node = node.clone();
+            tokio::spawn(async move { node.start().await });
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        // 严格策略下 60 秒内第 3 次重连触发暂停，之后的握手被拒绝
+        let bob_addr = format!("127.0.0.1:{}", bob_port);
+        for _ in 0..3 {
+            assert_eq!(alice.connect_to_peer(&bob_addr).await.unwrap(), "bob");
+        }
+        assert!(!manager.requires_manual_resume());
+        assert!(matches!(alice.connect_to_peer(&bob_addr).await, Err(NetworkError::AuthorizationFailed(_))));
+        assert!(manager.requires_manual_resume());
+
+        manager.resume_operations();
+        manager.release_peer("bob");
+        assert_eq!(alice.connect_to_peer(&bob_addr).await.unwrap(), "bob");
+        alice.send_to_peer("bob", NetworkMessage::new("share", b"ok")).await.unwrap();
+
+        // 被隔离的对端既不能收到消息，也不能送达消息
+        let incident = ProtocolIncident::MacCheckFailure { session_id: "s".to_string() };
+        manager.report_incident("bob", incident).unwrap();
+        assert!(matches!(
+            alice.send_to_peer("bob", NetworkMessage::new("share", b"blocked")).await,
+            Err(NetworkError::AuthorizationFailed(_))
+        ));
+        assert!(matches!(
+            alice.handle_incoming("bob", &NetworkMessage::new("share", b"blocked")).await,
+            Err(NetworkError::AuthorizationFailed(_))
+        ));
+    }
+}
+
+/// 节点入网测试
+#[cfg(test)]
+mod onboarding_tests {
+    use mpc_api::elliptic_curve::Ed25519;
+    use mpc_api::network::http::{HttpMethod, HttpRequest, OnboardingHandler, RouteHandler};
+    use mpc_api::network::onboarding::*;
+    use mpc_api::network::security::NetworkSecurity;
+    use mpc_api::protocols::output_certification::OutputCertifier;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::SystemTime;
+
+    fn new_committee(id: &str) -> CertificateCommittee {
+        let members = (0..3).map(|i| OutputCertifier::new(i).unwrap()).collect();
+        CertificateCommittee::new(id, members, 2).unwrap()
+    }
+
+    fn request(node_id: &str) -> NodeCertificateRequest {
+        let (signing_key, public_key) = Ed25519::generate_keypair();
+        NodeCertificateRequest::new(node_id, "127.0.0.1:9000", &signing_key, public_key).unwrap()
+    }
+
+    #[test]
+    fn test_committee_issued_certificate_trusted() {
+        let committee = new_committee("committee-a");
+        let certificate = committee.issue(&request("node-1")).unwrap();
+        assert_eq!(certificate.signer_count(), 3);
+        assert_eq!(certificate.body().unwrap().node_id, "node-1");
+
+        let cert = certificate.to_certificate().unwrap();
+        let mut security = NetworkSecurity::new(None).unwrap();
+        assert!(!security.verify_certificate(&cert).unwrap());
+
+        security.trust_committee(committee.trust_anchor());
+        assert!(security.verify_certificate(&cert).unwrap());
+
+        // 篡改证书字段
+        let mut forged = cert.clone();
+        forged.subject = "node-2".to_string();
+        assert!(!security.verify_certificate(&forged).unwrap());
+
+        // 其他委员会签发的证书不受信任
+        let other = new_committee("committee-b").issue(&request("node-1")).unwrap().to_certificate().unwrap();
+        assert!(!security.verify_certificate(&other).unwrap());
+
+        security.revoke_certificate(&cert.id);
+        assert!(!security.verify_certificate(&cert).unwrap());
+    }
+
+    #[test]
+    fn test_threshold_issuance() {
+        let committee = new_committee("committee-a");
+        let anchor = committee.trust_anchor();
+
+        let certificate = committee.issue_with(&request("node-1"), &[0, 2]).unwrap();
+        assert_eq!(certificate.signer_count(), 2);
+        assert!(anchor.verify(&certificate, SystemTime::now()).is_ok());
+
+        assert!(matches!(
+            committee.issue_with(&request("node-1"), &[1]),
+            Err(mpc_api::MpcError::InsufficientShares)
+        ));
+
+        // 去掉签名后低于法定数量
+        let mut weakened = certificate.clone();
+        weakened.certified.signatures.truncate(1);
+        assert!(anchor.verify(&weakened, SystemTime::now()).is_err());
+
+        assert!(CertificateCommittee::new("c", Vec::new(), 1).is_err());
+    }
+
+    #[test]
+    fn test_proof_of_possession_required() {
+        let committee = new_committee("committee-a");
+        let mut csr = request("node-1");
+        csr.public_key = request("node-2").public_key;
+        assert!(matches!(committee.issue(&csr), Err(mpc_api::MpcError::AuthenticationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_onboarding_http_api() {
+        let committee = Arc::new(new_committee("committee-a"));
+        let handler = OnboardingHandler::new(Arc::clone(&committee));
+        let http_request = |method: HttpMethod, body: Vec<u8>| HttpRequest {
+            method,
+            path: "/api/v1/onboarding".to_string(),
+            query_params: HashMap::new(),
+            headers: HashMap::new(),
+            body,
+            client_ip: "127.0.0.1".to_string(),
+            timestamp: SystemTime::now(),
+            request_id: "test".to_string(),
+        };
+
+        let response = handler.handle_request(&http_request(HttpMethod::GET, Vec::new())).await.unwrap();
+        let anchor: CommitteeTrustAnchor = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(anchor, committee.trust_anchor());
+
+        let body = serde_json::to_vec(&request("node-1")).unwrap();
+        let response = handler.handle_request(&http_request(HttpMethod::POST, body)).await.unwrap();
+        assert_eq!(response.status_code, 200);
+        let certificate: NodeCertificate = serde_json::from_slice(&response.body).unwrap();
+        let mut security = NetworkSecurity::new(None).unwrap();
+        security.trust_committee(anchor);
+        assert!(security.verify_certificate(&certificate.to_certificate().unwrap()).unwrap());
+
+        let bad = handler.handle_request(&http_request(HttpMethod::POST, b"{}".to_vec())).await.unwrap();
+        assert_eq!(bad.status_code, 400);
+        let mut csr = request("node-2");
+        csr.address = "10.0.0.1:1".to_string();
+        let body = serde_json::to_vec(&csr).unwrap();
+        let forged = handler.handle_request(&http_request(HttpMethod::POST, body)).await.unwrap();
+        assert_eq!(forged.status_code, 403);
+    }
+}
+
+/// 节点能力自测测试
+#[cfg(test)]
+mod capability_tests {
+    use mpc_api::network::capability::*;
+    use mpc_api::network::http::{CapabilityHandler, HttpMethod, HttpRequest, RouteHandler};
+    use std::collections::HashMap;
+    use std::time::{Duration, SystemTime};
+
+    fn benchmark() -> CapabilityBenchmark {
+        CapabilityBenchmark::new("node-1").with_budget(Duration::from_millis(5)).unwrap()
+    }
+
+    fn report(node_id: &str, triple_verify_per_sec: f64, available_parallelism: usize) -> CapabilityReport {
+        CapabilityReport {
+            node_id: node_id.to_string(),
+            measured_at: SystemTime::now(),
+            budget: Duration::from_millis(5),
+            available_parallelism,
+            field_mul_per_sec: 1.0e8,
+            garble_gates_per_sec: None,
+            ot_per_sec: 1.0e6,
+            triple_verify_per_sec,
+            peer_rtts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_measure_local_reports_positive_throughput() {
+        let report = benchmark().measure_local().unwrap();
+        assert_eq!(report.node_id, "node-1");
+        assert!(report.available_parallelism >= 1);
+        assert!(report.field_mul_per_sec > 0.0);
+        assert!(report.ot_per_sec > 0.0);
+        assert!(report.triple_verify_per_sec > 0.0);
+        assert_eq!(report.garble_gates_per_sec.is_some(), cfg!(feature = "garbled-circuits"));
+        assert!(report.peer_rtts.is_empty());
+
+        let json = serde_json::to_vec(&report).unwrap();
+        let decoded: CapabilityReport = serde_json::from_slice(&json).unwrap();
+        assert_eq!(decoded.measured_at, report.measured_at);
+        assert_eq!(decoded.available_parallelism, report.available_parallelism);
+    }
+
+    #[test]
+    fn test_benchmark_rejects_invalid_settings() {
+        assert!(CapabilityBenchmark::new("node-1").with_budget(Duration::ZERO).is_err());
+        assert!(CapabilityBenchmark::new("node-1").with_rtt_probe(0, Duration::from_millis(10)).is_err());
+        assert!(CapabilityBenchmark::new("node-1").with_rtt_probe(1, Duration::ZERO).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_peer_rtt_measurement() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let reachable = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((_stream, _)) = listener.accept().await {}
+        });
+        // 绑定后立即释放的端口，连接会被拒绝
+        let unreachable = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+
+        let report = benchmark()
+            .with_peers(vec![reachable, unreachable])
+            .with_rtt_probe(2, Duration::from_millis(500))
+            .unwrap()
+            .measure()
+            .await
+            .unwrap();
+        assert_eq!(report.peer_rtts.len(), 2);
+        assert_eq!(report.peer_rtts[0].address, reachable);
+        assert!(report.peer_rtts[0].rtt.is_some());
+        assert_eq!(report.peer_rtts[1].rtt, None);
+        assert_eq!(report.round_latency(), report.peer_rtts[0].rtt);
+
+        let profile = report.network_profile(1_000_000).unwrap();
+        assert_eq!(profile.round_latency, report.peer_rtts[0].rtt.unwrap());
+    }
+
+    #[test]
+    fn test_assign_work_proportional_to_capacity() {
+        // 容量分别为 100×4、100×2、200×1
+        let reports = vec![report("a", 100.0, 4), report("b", 100.0, 2), report("c", 200.0, 1)];
+        let shares = assign_work(&reports, Primitive::TripleVerify, 1000).unwrap();
+        assert_eq!(shares, vec![500, 250, 250]);
+
+        let shares = assign_work(&reports, Primitive::TripleVerify, 7).unwrap();
+        assert_eq!(shares.iter().sum::<usize>(), 7);
+        assert!(shares[0] >= shares[1] && shares[0] >= shares[2]);
+
+        // 不支持混淆的节点分不到工作
+        let mut garbler = report("d", 100.0, 1);
+        garbler.garble_gates_per_sec = Some(1.0e6);
+        let shares = assign_work(&[reports[0].clone(), garbler], Primitive::GarbleGate, 10).unwrap();
+        assert_eq!(shares, vec![0, 10]);
+        assert!(assign_work(&reports, Primitive::GarbleGate, 10).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_capability_http_api() {
+        let handler = CapabilityHandler::new(benchmark());
+        let http_request = |method: HttpMethod, refresh: bool| HttpRequest {
+            method,
+            path: "/api/v1/capability".to_string(),
+            query_params: if refresh {
+                HashMap::from([("refresh".to_string(), "true".to_string())])
+            } else {
+                HashMap::new()
+            },
+            headers: HashMap::new(),
+            body: Vec::new(),
+            client_ip: "127.0.0.1".to_string(),
+            timestamp: SystemTime::now(),
+            request_id: "test".to_string(),
+        };
+
+        let response = handler.handle_request(&http_request(HttpMethod::GET, false)).await.unwrap();
+        assert_eq!(response.status_code, 200);
+        let first: CapabilityReport = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(first.node_id, "node-1");
+
+        // 未要求刷新时返回缓存的报告
+        let response = handler.handle_request(&http_request(HttpMethod::GET, false)).await.unwrap();
+        let cached: CapabilityReport = serde_json::from_slice(&response.body).unwrap();
+        assert_eq!(cached.measured_at, first.measured_at);
+
+        let response = handler.handle_request(&http_request(HttpMethod::GET, true)).await.unwrap();
+        let refreshed: CapabilityReport = serde_json::from_slice(&response.body).unwrap();
+        assert!(refreshed.measured_at > first.measured_at);
+
+        let response = handler.handle_request(&http_request(HttpMethod::POST, false)).await.unwrap();
+        assert_eq!(response.status_code, 405);
+    }
+}
+
+/// 多方计算会话测试
+#[cfg(test)]
+mod session_tests {
+    use mpc_api::network::session::*;
+    use mpc_api::protocols::topology::{ProtocolRole, Topology};
+    use mpc_api::secret_sharing::{field_add, field_mul, Share, FIELD_PRIME};
+    use mpc_api::spdz::SPDZShare;
+    use mpc_api::MpcError;
+    use std::thread;
+    use std::time::Duration;
+
+    /// 在线程中运行 n 个参与方，返回按参与方 ID 排列的结果
+    fn run_parties<T: Send>(
+        name: &str,
+        n: usize,
+        t: usize,
+        party: impl Fn(MpcSession) -> mpc_api::Result<T> + Sync,
+    ) -> Vec<mpc_api::Result<T>> {
+        let addresses = reserve_local_addresses(n).unwrap();
+        let configs: Vec<SessionConfig> = (0..n)
+            .map(|party_id| SessionConfig::new(name, party_id, addresses.clone(), t))
+            .collect();
+        run_configs(configs, party)
+    }
+
+    fn run_configs<T: Send>(
+        configs: Vec<SessionConfig>,
+        party: impl Fn(MpcSession) -> mpc_api::Result<T> + Sync,
+    ) -> Vec<mpc_api::Result<T>> {
+        let party = &party;
+        thread::scope(|scope| {
+            let handles: Vec<_> = configs.into_iter()
+                .map(|config| scope.spawn(move || party(MpcSession::join(config)?)))
+                .collect();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        })
+    }
+
+    #[test]
+    fn test_session_input_multiply_open() {
+        let results = run_parties("multiply", 5, 2, |mut session| {
+            let own = [session.party_id() as u64 + 10, FIELD_PRIME - 1];
+            let input = session.input(&own)?;
+            assert_eq!(input.stats.rounds, 1);
+            let inputs = input.result;
+            assert_eq!(inputs.len(), 5);
+
+            let preprocessing = session.preprocess(3)?;
+            assert_eq!(preprocessing.result, 3);
+            assert_eq!(preprocessing.stats.rounds, 2);
+
+            let x: Vec<Share> = inputs[..3].iter().map(|shares| shares[0].clone()).collect();
+            let y: Vec<Share> = inputs[2..].iter().map(|shares| shares[1].clone()).collect();
+            let product = session.multiply(&x, &y)?;
+            assert_eq!(product.stats.rounds, 1);
+            assert_eq!(product.stats.preprocessing_consumed, 3);
+            assert_eq!(session.triples_available(), 0);
+
+            Ok((session.id(), session.open(&product.result)?.result))
+        });
+
+        let minus_one = FIELD_PRIME - 1;
+        let expected: Vec<u64> = (10..13).map(|x| field_mul(x, minus_one)).collect();
+        let results: Vec<_> = results.into_iter().map(Result::unwrap).collect();
+        for (id, opened) in &results {
+            assert_eq!(*id, results[0].0);
+            assert_eq!(opened, &expected);
+        }
+    }
+
+    #[test]
+    fn test_session_rejects_mismatched_configuration() {
+        let addresses = reserve_local_addresses(3).unwrap();
+        let mut configs: Vec<SessionConfig> = (0..3)
+            .map(|party_id| {
+                SessionConfig::new("mismatch", party_id, addresses.clone(), 1).with_timeout(Duration::from_secs(2))
+            })
+            .collect();
+        configs[2].name = "other".to_string();
+
+        let results = run_configs(configs, |_| Ok(()));
+        assert!(results.iter().all(|result| matches!(result, Err(MpcError::NetworkError(_)))));
+
+        assert!(SessionConfig::new("invalid", 0, addresses.clone(), 2).validate().is_err());
+        assert!(SessionConfig::new("invalid", 3, addresses, 1).validate().is_err());
+    }
+
+    #[test]
+    fn test_session_detects_inconsistent_opening() {
+        let results = run_parties("tamper", 3, 1, |mut session| {
+            let inputs = session.input(&[7])?.result;
+            let mut share = inputs[0][0].clone();
+            if session.party_id() == 1 {
+                share.y = field_add(share.y, 1);
+            }
+            session.open(&[share])
+        });
+        assert!(results.iter().all(|result| matches!(result, Err(MpcError::ProtocolError(_)))));
+    }
+
+    #[test]
+    fn test_session_requires_triples_for_multiplication() {
+        let results = run_parties("no-triples", 3, 1, |mut session| {
+            let inputs = session.input(&[2])?.result;
+            session.multiply(&[inputs[0][0].clone()], &[inputs[1][0].clone()])
+        });
+        assert!(results.iter().all(|result| result.is_err()));
+    }
+
+    /// 可信方为 3 个参与方生成 SPDZ 加法分享和 MAC 密钥分享
+    fn deal_spdz(values: &[u64]) -> Vec<(u64, Vec<SPDZShare>)> {
+        let split = |secret: u64| {
+            let a = mpc_api::utils::random_field_element();
+            let b = mpc_api::utils::random_field_element();
+            [a, b, mpc_api::secret_sharing::field_sub(secret, field_add(a, b))]
+        };
+        let alpha = mpc_api::utils::random_field_element();
+        let alpha_shares = split(alpha);
+        let mut parties: Vec<(u64, Vec<SPDZShare>)> = alpha_shares.iter().map(|&a| (a, Vec::new())).collect();
+        for (k, &value) in values.iter().enumerate() {
+            let value_shares = split(value);
+            let mac_shares = split(field_mul(alpha, value));
+            for (party, (_, shares)) in parties.iter_mut().enumerate() {
+                shares.push(SPDZShare::new(value_shares[party], mac_shares[party], party, k as u64));
+            }
+        }
+        parties
+    }
+
+    #[test]
+    fn test_session_spdz_opening_with_mac_check() {
+        let dealt = deal_spdz(&[5, 11]);
+        let results = run_parties("spdz", 3, 1, |mut session| {
+            let (alpha, shares) = &dealt[session.party_id()];
+            // 本地线性运算：x + y
+            let sum = shares[0].add(&shares[1])?;
+            session.open_spdz(&[shares[0].clone(), shares[1].clone(), sum], *alpha)
+        });
+        for result in results {
+            let opened = result.unwrap();
+            assert_eq!(opened.result, vec![5, 11, 16]);
+            assert_eq!(opened.stats.rounds, 3);
+        }
+
+        let results = run_parties("spdz-tamper", 3, 1, |mut session| {
+            let (alpha, shares) = &dealt[session.party_id()];
+            let mut share = shares[0].clone();
+            if session.party_id() == 2 {
+                share.value = field_add(share.value, 1);
+            }
+            session.open_spdz(&[share], *alpha)
+        });
+        assert!(results.iter().all(|result| matches!(result, Err(MpcError::AuthenticationError(_)))));
+    }
+
+    #[test]
+    fn test_session_reports_incidents_to_security_manager() {
+        use mpc_api::security::{SecurityManager, SecurityPolicy};
+        use std::sync::Arc;
+
+        // MAC 检查失败：严格策略隔离所有对等方，之后的轮次不再进行
+        let dealt = deal_spdz(&[5]);
+        let results = run_parties("spdz-incident", 3, 1, |session| {
+            let manager = Arc::new(SecurityManager::with_policy(SecurityPolicy::strict())?);
+            let mut session = session.with_security_manager(Arc::clone(&manager));
+            let (alpha, shares) = &dealt[session.party_id()];
+            let mut share = shares[0].clone();
+            if session.party_id() == 0 {
+                share.value = field_add(share.value, 1);
+            }
+            assert!(matches!(session.open_spdz(&[share], *alpha), Err(MpcError::AuthenticationError(_))));
+            let peers: Vec<String> = (0..3).filter(|&p| p != session.party_id()).map(|p| p.to_string()).collect();
+            assert!(peers.iter().all(|peer| manager.is_peer_quarantined(peer)));
+            assert!(matches!(session.input(&[1]), Err(MpcError::ProtocolError(_))));
+            Ok(())
+        });
+        assert!(results.into_iter().all(|result| result.is_ok()));
+
+        // 轮次超时：严格策略中止会话
+        let addresses = reserve_local_addresses(3).unwrap();
+        let configs: Vec<SessionConfig> = (0..3)
+            .map(|party_id| SessionConfig { timeout_ms: 300, ..SessionConfig::new("timeout", party_id, addresses.clone(), 1) })
+            .collect();
+        let results = run_configs(configs, |session| {
+            if session.party_id() != 0 {
+                thread::sleep(Duration::from_millis(900));
+                return Ok(());
+            }
+            let manager = Arc::new(SecurityManager::with_policy(SecurityPolicy::strict())?);
+            let mut session = session.with_security_manager(Arc::clone(&manager));
+            assert!(matches!(session.input(&[1]), Err(MpcError::NetworkError(_))));
+            assert!(manager.is_session_aborted(&session.id().to_string()));
+            assert!(matches!(session.input(&[1]), Err(MpcError::ProtocolError(_))));
+            Ok(())
+        });
+        for result in results {
+            result.unwrap();
+        }
+    }
+
+    #[test]
+    fn test_session_dealer_triples_and_aggregation() {
+        let results = run_parties("roles", 3, 1, |mut session| {
+            let topology = Topology::with_party_count(3)?
+                .with_role(ProtocolRole::Dealer, 2)?
+                .with_role(ProtocolRole::Aggregator, 1)?;
+            session.set_topology(topology)?;
+
+            let inputs = session.input(&[session.party_id() as u64 + 3])?.result;
+            let dealt = session.preprocess_from_dealer(2)?;
+            assert_eq!(dealt.result, 2);
+            assert_eq!(dealt.stats.rounds, 1);
+
+            let product = session.multiply(
+                &[inputs[0][0].clone(), inputs[1][0].clone()],
+                &[inputs[1][0].clone(), inputs[2][0].clone()],
+            )?.result;
+            Ok(session.aggregate(&product)?.result)
+        });
+        let outputs: Vec<_> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(outputs, vec![None, Some(vec![3 * 4, 4 * 5]), None]);
+    }
+
+    #[test]
+    fn test_session_rejects_misassigned_roles() {
+        // 参与方 0 认为自己是分发者，其余参与方认为分发者是参与方 2
+        let results = run_parties("misassigned-dealer", 3, 1, |mut session| {
+            let dealer = if session.party_id() == 0 { 0 } else { 2 };
+            session.set_topology(Topology::with_party_count(3)?.with_role(ProtocolRole::Dealer, dealer)?)?;
+            session.preprocess_from_dealer(1)
+        });
+        assert!(results.iter().all(|result| matches!(result, Err(MpcError::ProtocolError(_)))));
+
+        // 参与方 2 认为自己是聚合方
+        let results = run_parties("misassigned-aggregator", 3, 1, |mut session| {
+            let aggregator = if session.party_id() == 2 { 2 } else { 0 };
+            session.set_topology(Topology::with_party_count(3)?.with_role(ProtocolRole::Aggregator, aggregator)?)?;
+            let inputs = session.input(&[1])?.result;
+            session.aggregate(&[inputs[0][0].clone()])
+        });
+        assert!(matches!(results[0], Err(MpcError::ProtocolError(_))));
+        assert!(matches!(results[2], Err(MpcError::ProtocolError(_))));
+
+        // 未分配角色，或拓扑与会话名单不一致
+        let results = run_parties("unassigned", 3, 1, |mut session| {
+            assert!(session.set_topology(Topology::with_party_count(4)?).is_err());
+            assert!(session.preprocess_from_dealer(1).is_err());
+            assert!(session.aggregate(&[]).is_err());
+            Ok(())
+        });
+        assert!(results.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn test_session_reliable_broadcast() {
+        let results = run_parties("rbc", 4, 1, |mut session| {
+            let value = format!("value-{}", session.party_id());
+            session.reliable_broadcast("values", &value)
+        });
+        for result in results {
+            let output = result.unwrap();
+            assert_eq!(output.result, vec!["value-0", "value-1", "value-2", "value-3"]);
+            assert_eq!(output.stats.rounds, 4);
+        }
+    }
+}
+
+/// 传输抽象测试
+#[cfg(test)]
+mod transport_tests {
+    use mpc_api::network::common::{NetworkError, Transport};
+    use mpc_api::network::http::{HttpMethod, HttpRequest, LongPollingTransport, MailboxHandler, RouteHandler};
+    use mpc_api::network::p2p::{P2PNode, P2PTransport, PeerConfig, TRANSPORT_MESSAGE_TYPE};
+    use mpc_api::network::protocol::NetworkMessage;
+    use mpc_api::network::session::{MpcSession, SessionConfig};
+    use mpc_api::network::transport::{BlockingTransport, InMemoryTransport};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    #[tokio::test]
+    async fn test_in_memory_transport() {
+        let parties = InMemoryTransport::network(3);
+        assert_eq!(parties[1].parties(), vec![0, 1, 2]);
+        assert_eq!(parties[1].peers(), vec![0, 2]);
+
+        parties[0].send(2, NetworkMessage::new("share", b"to-2")).await.unwrap();
+        parties[1].broadcast(NetworkMessage::new("share", b"from-1")).await.unwrap();
+
+        let (from, message) = parties[2].recv().await.unwrap();
+        assert_eq!((from, message.payload.as_slice()), (0, &b"to-2"[..]));
+        let (from, message) = parties[2].recv().await.unwrap();
+        assert_eq!((from, message.payload.as_slice()), (1, &b"from-1"[..]));
+        assert_eq!(parties[0].recv().await.unwrap().0, 1);
+
+        assert!(matches!(parties[0].send(7, NetworkMessage::new("share", b"")).await, Err(NetworkError::PeerNotFound(_))));
+        assert!(matches!(
+            parties[2].recv_timeout(Duration::from_millis(20)).await,
+            Err(NetworkError::Timeout)
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_session_over_in_memory_transport() {
+        let addresses: Vec<String> = (0..3).map(|party| format!("party-{}", party)).collect();
+        let handle = tokio::runtime::Handle::current();
+
+        // 参与方 ID 与名单不一致的传输被拒绝
+        let mismatched = BlockingTransport::new(InMemoryTransport::network(3).remove(1), handle.clone(), Duration::from_secs(5));
+        assert!(MpcSession::with_transport(SessionConfig::new("memory", 0, addresses.clone(), 1), mismatched).is_err());
+
+        let tasks: Vec<_> = InMemoryTransport::network(3).into_iter()
+            .enumerate()
+            .map(|(party_id, transport)| {
+                let config = SessionConfig::new("memory", party_id, addresses.clone(), 1);
+                let transport = BlockingTransport::new(transport, handle.clone(), Duration::from_secs(5));
+                tokio::task::spawn_blocking(move || -> mpc_api::Result<_> {
+                    let mut session = MpcSession::with_transport(config, transport)?;
+                    let inputs = session.input(&[party_id as u64 + 2])?.result;
+                    session.preprocess(1)?;
+                    let product = session.multiply(&[inputs[0][0].clone()], &[inputs[2][0].clone()])?;
+                    let opened = session.open(&product.result)?;
+                    Ok((session.id(), opened.result[0], opened.stats.rounds))
+                })
+            })
+            .collect();
+
+        let mut ids = Vec::new();
+        for task in tasks {
+            let (id, product, rounds) = task.await.unwrap().unwrap();
+            assert_eq!(product, 2 * 4);
+            assert_eq!(rounds, 1);
+            ids.push(id);
+        }
+        assert!(ids.iter().all(|id| *id == ids[0]));
+    }
+
+    #[tokio::test]
+    async fn test_long_polling_transport() {
+        let mailbox = MailboxHandler::new(2);
+        let endpoint: Arc<dyn RouteHandler> = Arc::new(mailbox.clone());
+        let alice = LongPollingTransport::new(0, 2, endpoint.clone(), Duration::from_millis(500));
+        let bob = LongPollingTransport::new(1, 2, endpoint, Duration::from_millis(500));
+
+        // 邮箱为空时取信请求挂起，消息到达后立即返回
+        let waiting = tokio::spawn(async move {
+            let received = bob.recv().await.unwrap();
+            (bob, received)
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        alice.send(1, NetworkMessage::new("share", b"first")).await.unwrap();
+        alice.send(1, NetworkMessage::new("share", b"second")).await.unwrap();
+        let (bob, (from, message)) = waiting.await.unwrap();
+        assert_eq!((from, message.payload.as_slice()), (0, &b"first"[..]));
+        assert_eq!(message.sender_id.as_deref(), Some("0"));
+        assert_eq!(bob.recv().await.unwrap().1.payload, b"second");
+        assert_eq!(mailbox.pending(1).await, 0);
+
+        bob.send(0, NetworkMessage::new("share", b"reply")).await.unwrap();
+        let (from, message) = alice.recv().await.unwrap();
+        assert_eq!((from, message.payload.as_slice()), (1, &b"reply"[..]));
+
+        // 不存在的参与方和无效的请求被拒绝
+        assert!(alice.send(5, NetworkMessage::new("share", b"")).await.is_err());
+        let request = HttpRequest {
+            method: HttpMethod::POST,
+            path: "/api/v1/mailbox".to_string(),
+            query_params: HashMap::from([("to".to_string(), "1".to_string())]),
+            headers: HashMap::new(),
+            body: NetworkMessage::new("share", b"anonymous").serialize().unwrap(),
+            client_ip: "127.0.0.1".to_string(),
+            timestamp: SystemTime::now(),
+            request_id: "anonymous".to_string(),
+        };
+        assert_eq!(mailbox.handle_request(&request).await.unwrap().status_code, 400);
+        assert!(matches!(
+            alice.recv_timeout(Duration::from_millis(50)).await,
+            Err(NetworkError::Timeout)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_p2p_transport_receive_path() {
+        let members = vec!["alice".to_string(), "bob".to_string()];
+        let config = PeerConfig { node_id: Some("bob".to_string()), ..PeerConfig::default() };
+        let node = Arc::new(P2PNode::new(config).await.unwrap());
+        let transport = P2PTransport::new(node.clone(), members.clone()).await.unwrap();
+        assert_eq!(transport.party_id(), 1);
+        assert_eq!(transport.peers(), vec![0]);
+
+        let inner = NetworkMessage::new("share", b"from-alice").with_round(3);
+        let wrapped = NetworkMessage::new(TRANSPORT_MESSAGE_TYPE, &inner.serialize().unwrap());
+        assert!(node.handle_incoming("alice", &wrapped).await.unwrap().is_none());
+        let (from, message) = transport.recv().await.unwrap();
+        assert_eq!(from, 0);
+        assert_eq!(message.payload, b"from-alice");
+        assert_eq!(message.round().unwrap(), Some(3));
+
+        // 名单之外的节点不能投递消息，未启动的节点不能发送
+        assert!(matches!(node.handle_incoming("mallory", &wrapped).await, Err(NetworkError::AuthorizationFailed(_))));
+        assert!(matches!(transport.send(0, inner).await, Err(NetworkError::NotInitialized)));
+
+        let outsider = Arc::new(P2PNode::new(PeerConfig::default()).await.unwrap());
+        assert!(P2PTransport::new(outsider, members).await.is_err());
+    }
+}
+
+/// 多方协议模拟器测试
+#[cfg(test)]
+mod simulator_tests {
+    use mpc_api::network::simulator::*;
+    use mpc_api::secret_sharing::{field_add, field_mul, field_sub};
+    use mpc_api::spdz::SPDZShare;
+    use mpc_api::MpcError;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::time::Duration;
+
+    /// 每个参与方输入一个随机数，返回 (本方输入, 全部输入之积)
+    fn multiply_inputs(simulator: &Simulator) -> Simulation<(u64, u64)> {
+        simulator.run_sessions(|mut session, mut rng| {
+            let own = rng.gen_range(1..1000);
+            let inputs = session.input(&[own])?.result;
+            session.preprocess(2)?;
+            let product = session.multiply(&[inputs[0][0].clone()], &[inputs[1][0].clone()])?.result;
+            let product = session.multiply(&product, &[inputs[2][0].clone()])?.result;
+            Ok((own, session.open(&product)?.result[0]))
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_simulator_is_deterministic() {
+        let simulator = Simulator::new(3, 1).with_seed(42);
+        let first = multiply_inputs(&simulator);
+        let second = multiply_inputs(&simulator);
+
+        let first = (first.events, first.outputs.into_iter().map(Result::unwrap).collect::<Vec<_>>());
+        let second = (second.events, second.outputs.into_iter().map(Result::unwrap).collect::<Vec<_>>());
+        assert_eq!(first, second);
+
+        let (events, outputs) = first;
+        let expected = outputs.iter().fold(1, |acc, (own, _)| field_mul(acc, *own));
+        assert!(outputs.iter().all(|(_, product)| *product == expected));
+        // 每轮每个参与方向另外两方各发一条消息
+        assert_eq!(events.len() % 6, 0);
+        assert!(events.iter().all(|event| event.fault.is_none()));
+
+        let reseeded = multiply_inputs(&Simulator::new(3, 1).with_seed(43)).unwrap_all().unwrap();
+        assert_ne!(reseeded, outputs);
+    }
+
+    #[test]
+    fn test_simulator_dropped_message_times_out() {
+        let simulator = Simulator::new(3, 1)
+            .with_timeout(Duration::from_millis(200))
+            .with_fault(FaultRule::new(Fault::Drop).from(1).to(0).label("session/input"));
+        let simulation = simulator.run_sessions(|mut session, _| session.input(&[1])).unwrap();
+
+        assert!(matches!(simulation.outputs[0], Err(MpcError::NetworkError(_))));
+        assert!(simulation.outputs[1].is_ok() && simulation.outputs[2].is_ok());
+        let faults: Vec<_> = simulation.faults().collect();
+        assert_eq!(faults.len(), 1);
+        assert_eq!((faults[0].from, faults[0].to, faults[0].fault), (1, 0, Some(FaultKind::Drop)));
+    }
+
+    #[test]
+    fn test_simulator_detects_modified_spdz_shares() {
+        // 用固定种子的可信方为 3 个参与方生成 SPDZ 分享和 MAC 密钥分享
+        let mut dealer = StdRng::seed_from_u64(1);
+        let mut split = |secret: u64| {
+            let a = dealer.gen_range(0..1u64 << 62);
+            let b = dealer.gen_range(0..1u64 << 62);
+            [a, b, field_sub(secret, field_add(a, b))]
+        };
+        let alpha = 123_456_789;
+        let alpha_shares = split(alpha);
+        let (value_shares, mac_shares) = (split(21), split(field_mul(alpha, 21)));
+        let dealt: Vec<(u64, SPDZShare)> = (0..3)
+            .map(|party| (alpha_shares[party], SPDZShare::new(value_shares[party], mac_shares[party], party, 0)))
+            .collect();
+        let open = |simulator: Simulator| {
+            simulator.run_sessions(|mut session, _| {
+                let (alpha, share) = &dealt[session.party_id()];
+                session.open_spdz(std::slice::from_ref(share), *alpha)
+            }).unwrap()
+        };
+
+        for opened in open(Simulator::new(3, 1)).unwrap_all().unwrap() {
+            assert_eq!(opened.result, vec![21]);
+        }
+
+        let simulation = open(Simulator::new(3, 1).with_fault(FaultRule::new(Fault::ShiftValues(5)).from(2).label("session/spdz/open")));
+        assert_eq!(simulation.faults().count(), 2);
+        for output in &simulation.outputs[..2] {
+            assert!(matches!(output, Err(MpcError::AuthenticationError(_))));
+        }
+    }
+
+    #[test]
+    fn test_simulator_delay_and_corruption() {
+        let simulator = Simulator::new(3, 1)
+            .with_seed(9)
+            .with_fault(FaultRule::new(Fault::Delay(Duration::from_millis(100))).from(0).round(1))
+            .with_fault(FaultRule::new(Fault::Corrupt).from(1).to(2).label("session/open"));
+        let simulation = simulator.run_sessions(|mut session, _| {
+            let inputs = session.input(&[session.party_id() as u64 + 1])?.result;
+            session.open(&[inputs[2][0].clone()])
+        }).unwrap();
+
+        // 延迟不超过超时时间，不影响结果；参与方 2 收到被篡改的分享
+        assert_eq!(simulation.outputs[0].as_ref().unwrap().result, vec![3]);
+        assert_eq!(simulation.outputs[1].as_ref().unwrap().result, vec![3]);
+        assert!(simulation.outputs[2].is_err());
+        let kinds: Vec<_> = simulation.faults().map(|event| (event.round, event.fault.unwrap())).collect();
+        assert_eq!(kinds, vec![(1, FaultKind::Delay), (1, FaultKind::Delay), (2, FaultKind::Corrupt)]);
+    }
 }
 
 /// 协议功能测试
@@ -651,6 +2047,24 @@ mod protocol_tests {
         assert_eq!(original_message.payload, restored_message.payload);
     }
 
+    #[test]
+    fn test_message_logical_clock_header() {
+        use mpc_api::protocols::clock::LogicalClock;
+        use mpc_api::protocols::session::ProtocolSession;
+
+        let session = ProtocolSession::root("spdz", b"net").id();
+        let header = LogicalClock::new().stamp(session);
+        let message = NetworkMessage::new("mpc_protocol", b"payload").with_logical_clock(&header);
+
+        let restored = NetworkMessage::deserialize(&message.serialize().unwrap()).unwrap();
+        assert_eq!(restored.logical_clock().unwrap(), Some(header));
+        assert_eq!(NetworkMessage::new("ping", b"").logical_clock().unwrap(), None);
+
+        let mut corrupted = restored.clone();
+        corrupted.headers.insert("mpc-round".to_string(), "x".to_string());
+        assert!(corrupted.logical_clock().is_err());
+    }
+
     #[test]
     fn test_message_validation() {
         // 有效消息
@@ -668,6 +2082,68 @@ mod protocol_tests {
         assert!(large_payload_message.validate().is_err());
     }
 
+    #[test]
+    fn test_replay_window() {
+        use mpc_api::network::protocol::ReplayRejection;
+
+        let mut window = ReplayWindow::new(4);
+        assert_eq!(window.accept(0), Err(ReplayRejection::Stale));
+        assert!(window.accept(1).is_ok());
+        assert!(window.accept(3).is_ok());
+        // 窗口内的乱序消息被接受，重复的被拒绝
+        assert!(window.accept(2).is_ok());
+        assert_eq!(window.accept(3), Err(ReplayRejection::Duplicate));
+        assert!(window.accept(10).is_ok());
+        assert_eq!(window.highest(), 10);
+        assert_eq!(window.accept(6), Err(ReplayRejection::Stale));
+        assert!(window.accept(7).is_ok());
+        assert_eq!(window.accept(7), Err(ReplayRejection::Duplicate));
+    }
+
+    #[test]
+    fn test_message_protocol_rejects_replays() {
+        use mpc_api::security::{AuditLogger, SecurityPolicy, ThreatType};
+        use std::sync::Arc;
+
+        let audit_logger = Arc::new(AuditLogger::new(SecurityPolicy::medium()));
+        let mut alice = MessageProtocol::new();
+        let mut bob = MessageProtocol::new().with_replay_window(8).with_audit_logger(Arc::clone(&audit_logger));
+
+        let send = |protocol: &mut MessageProtocol, payload: &[u8]| {
+            protocol.stamp_outgoing(
+                NetworkMessage::new("mpc_protocol", payload)
+                    .with_sender("alice".to_string())
+                    .with_receiver("bob".to_string()),
+            )
+        };
+        let first = send(&mut alice, b"first");
+        let second = send(&mut alice, b"second");
+        assert_eq!(first.sequence().unwrap(), Some(1));
+        assert_eq!(second.sequence().unwrap(), Some(2));
+
+        // 乱序到达的消息都被接受，重放的消息被拒绝
+        assert!(bob.check_incoming(&second).is_ok());
+        assert!(bob.check_incoming(&first).is_ok());
+        let replayed = NetworkMessage::deserialize(&first.serialize().unwrap()).unwrap();
+        assert!(matches!(bob.check_incoming(&replayed), Err(NetworkError::ProtocolError(_))));
+
+        // 没有序列号的消息被拒绝
+        assert!(bob.check_incoming(&NetworkMessage::new("mpc_protocol", b"raw")).is_err());
+
+        // 不同发送方的序列号互不影响
+        let carol = NetworkMessage::new("mpc_protocol", b"carol").with_sender("carol".to_string()).with_sequence(1);
+        assert!(bob.check_incoming(&carol).is_ok());
+
+        let events = audit_logger.get_events_by_type(&ThreatType::ReplayAttack);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].peer_id.as_deref(), Some("alice"));
+        assert_eq!(events[0].context.get("reason").map(String::as_str), Some("duplicate"));
+
+        // 关闭会话后重新计数
+        bob.close_session("alice", "");
+        assert!(bob.check_incoming(&replayed).is_ok());
+    }
+
     #[test]
     fn test_message_protocol() {
         let protocol = MessageProtocol::new();
@@ -725,6 +2201,278 @@ mod protocol_tests {
             }
         }
     }
+
+    #[test]
+    fn test_frame_codec_chunking_and_compression() {
+        use mpc_api::network::protocol::{Compression, FrameAssembler, FrameCodec, FramingConfig};
+
+        // 伪随机载荷几乎不可压缩，会被切成多个分片
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let payload: Vec<u8> = (0..50_000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect();
+        let codec = FrameCodec::new(FramingConfig { max_frame_size: 8192, ..FramingConfig::default() }).unwrap();
+        let message = NetworkMessage::new("garbled_circuit", &payload).with_sender("alice".to_string());
+        let frames = codec.split(&message).unwrap();
+        assert!(frames.len() > 1);
+        assert!(frames.iter().all(|frame| frame.encode().len() <= 4 + 8192));
+
+        // 分片乱序到达，通过字节流一次交付多帧也能重组
+        let mut assembler = FrameAssembler::new(codec.clone());
+        let mut stream: Vec<u8> = frames.iter().rev().flat_map(|frame| frame.encode()).collect();
+        let tail = stream.split_off(stream.len() - 10);
+        assert!(assembler.push_bytes(&stream).unwrap().is_empty());
+        assert_eq!(assembler.pending_transfers(), 1);
+        let received = assembler.push_bytes(&tail).unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].payload, payload);
+        assert_eq!(received[0].sender_id.as_deref(), Some("alice"));
+        assert_eq!(assembler.pending_transfers(), 0);
+        assert_eq!(assembler.buffered_bytes(), 0);
+
+        // 重复的载荷被压缩成一个分片
+        let shares = NetworkMessage::new("share_batch", &vec![42u8; 200_000]);
+        let compressed = codec.split(&shares).unwrap();
+        assert_eq!(compressed.len(), 1);
+        assert!(compressed[0].compressed);
+        assert_eq!(assembler.push(compressed[0].clone()).unwrap().unwrap().payload, shares.payload);
+
+        let plain = FrameCodec::new(FramingConfig { compression: Compression::None, ..FramingConfig::default() }).unwrap();
+        assert!(plain.split(&shares).unwrap().iter().all(|frame| !frame.compressed));
+    }
+
+    #[test]
+    fn test_frame_assembler_enforces_limits() {
+        use mpc_api::network::protocol::{Compression, FrameAssembler, FrameCodec, FramingConfig};
+
+        assert!(FrameCodec::new(FramingConfig { max_frame_size: 10, ..FramingConfig::default() }).is_err());
+
+        let codec = FrameCodec::new(FramingConfig {
+            max_frame_size: 1024,
+            max_message_size: 16 * 1024,
+            compression: Compression::None,
+            max_pending_transfers: 1,
+            ..FramingConfig::default()
+        })
+        .unwrap();
+
+        // 超过上限的消息不能发送
+        let huge = NetworkMessage::new("garbled_circuit", &vec![1u8; 32 * 1024]);
+        assert!(matches!(codec.split(&huge), Err(NetworkError::ProtocolError(_))));
+
+        // 声明的帧长度超过上限时在读取帧体前拒绝
+        let mut assembler = FrameAssembler::new(codec.clone());
+        assert!(assembler.push_bytes(&(1u32 << 20).to_be_bytes()).is_err());
+        assert_eq!(assembler.buffered_bytes(), 0);
+
+        let message = NetworkMessage::new("share_batch", &[3u8; 1500]);
+        let frames = codec.split(&message).unwrap();
+        assert!(frames.len() > 1);
+
+        // 重复的分片会丢弃整个传输
+        assert!(assembler.push(frames[0].clone()).unwrap().is_none());
+        assert!(assembler.push(frames[0].clone()).is_err());
+        assert_eq!(assembler.pending_transfers(), 0);
+
+        // 分片序号越界、并发传输过多都被拒绝
+        let mut bad = frames[0].clone();
+        bad.index = bad.total;
+        assert!(assembler.push(bad).is_err());
+        assert!(assembler.push(frames[0].clone()).unwrap().is_none());
+        let other = codec.split(&NetworkMessage::new("share_batch", &[4u8; 1500])).unwrap();
+        assert!(assembler.push(other[0].clone()).is_err());
+
+        let mut received = None;
+        for frame in &frames[1..] {
+            received = assembler.push(frame.clone()).unwrap();
+        }
+        assert_eq!(received.unwrap().payload, message.payload);
+        assert_eq!(assembler.pending_transfers(), 0);
+    }
+
+    #[test]
+    fn test_round_coordinator_threshold_and_stragglers() {
+        use mpc_api::network::protocol::{RoundCoordinator, Submission};
+        use std::time::Instant;
+
+        let parties = ["p0", "p1", "p2", "p3"].map(String::from);
+        assert!(RoundCoordinator::new(parties.clone(), 5, Duration::from_secs(1)).is_err());
+        assert!(RoundCoordinator::new(Vec::<String>::new(), 1, Duration::from_secs(1)).is_err());
+
+        let mut rounds = RoundCoordinator::new(parties, 3, Duration::from_millis(100)).unwrap();
+        let share = |round: u64| NetworkMessage::new("mpc_protocol", b"share").with_round(round);
+
+        // 收齐全部消息时立即结束
+        for party in ["p0", "p1", "p2", "p3"] {
+            assert_eq!(rounds.submit(party, share(1)).unwrap(), Submission::Accepted);
+        }
+        let outcome = rounds.poll().unwrap().unwrap();
+        assert!(outcome.is_complete() && !outcome.timed_out);
+        assert_eq!(outcome.round, 1);
+        assert_eq!(rounds.round(), 2);
+
+        // 非参与方、缺少轮次、重复和超前太多的消息被拒绝；下一轮的消息被缓存
+        assert!(rounds.submit("mallory", share(2)).is_err());
+        assert!(rounds.submit("p0", NetworkMessage::new("mpc_protocol", b"share")).is_err());
+        assert_eq!(rounds.submit("p0", share(2)).unwrap(), Submission::Accepted);
+        assert!(rounds.submit("p0", share(2)).is_err());
+        assert!(rounds.submit("p1", share(4)).is_err());
+        assert_eq!(rounds.submit("p1", share(3)).unwrap(), Submission::Buffered);
+        assert_eq!(rounds.submit("p2", share(1)).unwrap(), Submission::Late);
+
+        // 超时时不足门限返回错误并停留在当前轮
+        let deadline = rounds.deadline();
+        assert!(rounds.poll_at(deadline - Duration::from_millis(1)).unwrap().is_none());
+        assert!(matches!(rounds.poll_at(deadline), Err(NetworkError::ProtocolError(_))));
+        assert_eq!(rounds.round(), 2);
+
+        // 达到门限后超时结束本轮并报告缺席的参与方
+        rounds.submit("p1", share(2)).unwrap();
+        rounds.submit("p3", share(2)).unwrap();
+        let outcome = rounds.poll_at(deadline).unwrap().unwrap();
+        assert!(outcome.timed_out);
+        assert_eq!(outcome.missing, vec!["p2".to_string()]);
+        assert_eq!(outcome.messages.keys().cloned().collect::<Vec<_>>(), vec!["p0", "p1", "p3"]);
+        assert_eq!(rounds.missed_rounds("p2"), 1);
+        assert_eq!(rounds.missed_rounds("p0"), 0);
+
+        // 缓存的消息并入新的一轮，新一轮从上一轮结束时开始计时
+        assert_eq!(rounds.round(), 3);
+        assert_eq!(rounds.received_count(), 1);
+        assert_eq!(rounds.missing(), vec!["p0", "p2", "p3"]);
+        assert!(rounds.deadline() > Instant::now() - Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_reliable_broadcast_with_equivocating_sender() {
+        use mpc_api::network::protocol::{BroadcastMessage, BroadcastPhase, ReliableBroadcast};
+
+        let parties: Vec<String> = (0..4).map(|i| format!("p{}", i)).collect();
+        assert!(ReliableBroadcast::new("p0", parties[..3].to_vec(), 1).is_err());
+        assert!(ReliableBroadcast::new("p9", parties.clone(), 1).is_err());
+
+        // p3 是恶意发送方：向 p0、p1 发送 "a"，向 p2 发送 "b"
+        let mut honest: Vec<ReliableBroadcast> = parties[..3]
+            .iter()
+            .map(|id| ReliableBroadcast::new(id, parties.clone(), 1).unwrap())
+            .collect();
+        let message = |phase, value: &[u8]| BroadcastMessage {
+            origin: "p3".to_string(),
+            tag: "coin".to_string(),
+            phase,
+            value: value.to_vec(),
+        };
+
+        let mut queue: Vec<(usize, BroadcastMessage)> = Vec::new();
+        for (to, value) in [(0, b"a"), (1, b"a"), (2, b"b")] {
+            let replies = honest[to].handle("p3", message(BroadcastPhase::Send, value)).unwrap();
+            queue.extend(replies.into_iter().map(|m| (to, m)));
+        }
+        // p3 向所有人回显 "a"，但随后又向 p2 回显 "b"，第二个不同的值让它被记为可疑
+        for to in 0..3 {
+            let replies = honest[to].handle("p3", message(BroadcastPhase::Echo, b"a")).unwrap();
+            queue.extend(replies.into_iter().map(|m| (to, m)));
+        }
+        assert!(honest[2].handle("p3", message(BroadcastPhase::Echo, b"b")).unwrap().is_empty());
+        assert_eq!(honest[2].suspected_parties(), vec!["p3".to_string()]);
+        // 冒充发送方的 SEND 被忽略
+        assert!(honest[0].handle("p1", message(BroadcastPhase::Send, b"b")).unwrap().is_empty());
+
+        while let Some((from, message)) = queue.pop() {
+            for to in (0..3).filter(|&to| to != from) {
+                let replies = honest[to].handle(&parties[from], message.clone()).unwrap();
+                queue.extend(replies.into_iter().map(|m| (to, m)));
+            }
+        }
+
+        // 所有诚实参与方交付同一个值
+        for node in &honest {
+            assert_eq!(node.delivered("p3", "coin"), Some(&b"a"[..]));
+        }
+        let deliveries = honest[0].take_deliveries();
+        assert_eq!(deliveries.len(), 1);
+        assert!(honest[0].take_deliveries().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_channel_delivers_all() {
+        use mpc_api::network::protocol::{BroadcastChannel, ReliableBroadcast};
+        use tokio::sync::mpsc;
+
+        let parties: Vec<String> = (0..4).map(|i| format!("p{}", i)).collect();
+        let mut inboxes = Vec::new();
+        let mut channels = Vec::new();
+        let (router_tx, mut router_rx) = mpsc::unbounded_channel();
+        for id in &parties {
+            let (in_tx, in_rx) = mpsc::unbounded_channel();
+            let (out_tx, mut out_rx) = mpsc::unbounded_channel();
+            inboxes.push(in_tx);
+            let state = ReliableBroadcast::new(id, parties.clone(), 1).unwrap();
+            channels.push(BroadcastChannel::new(state, out_tx, in_rx));
+
+            // 为每个参与方的发出消息打上发送方
+            let router_tx = router_tx.clone();
+            let id = id.clone();
+            tokio::spawn(async move {
+                while let Some(message) = out_rx.recv().await {
+                    let _ = router_tx.send((id.clone(), message));
+                }
+            });
+        }
+        let ids = parties.clone();
+        tokio::spawn(async move {
+            while let Some((from, message)) = router_rx.recv().await {
+                for (id, inbox) in ids.iter().zip(&inboxes) {
+                    if *id != from {
+                        let _ = inbox.send((from.clone(), message.clone()));
+                    }
+                }
+            }
+        });
+
+        let handles: Vec<_> = channels
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut channel)| {
+                tokio::spawn(async move {
+                    channel.broadcast("round-1", format!("v{}", i).as_bytes()).unwrap();
+                    channel.deliver_all("round-1", Duration::from_secs(5)).await
+                })
+            })
+            .collect();
+        for handle in handles {
+            let values = handle.await.unwrap().unwrap();
+            let values: Vec<&[u8]> = values.values().map(|v| v.as_slice()).collect();
+            assert_eq!(values, vec![&b"v0"[..], b"v1", b"v2", b"v3"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_coordinator_collects_from_channel() {
+        use mpc_api::network::protocol::RoundCoordinator;
+
+        let parties = ["alice", "bob", "carol"].map(String::from);
+        let mut rounds = RoundCoordinator::new(parties, 2, Duration::from_millis(200)).unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        for party in ["alice", "bob"] {
+            tx.send((party.to_string(), NetworkMessage::new("mpc_protocol", party.as_bytes()).with_round(1))).unwrap();
+        }
+        tx.send(("mallory".to_string(), NetworkMessage::new("mpc_protocol", b"x").with_round(1))).unwrap();
+
+        let outcome = rounds.collect(&mut rx).await.unwrap();
+        assert_eq!(outcome.missing, vec!["carol".to_string()]);
+        assert!(outcome.elapsed >= Duration::from_millis(200));
+        assert_eq!(outcome.messages["bob"].payload, b"bob");
+
+        drop(tx);
+        assert!(matches!(rounds.collect(&mut rx).await, Err(NetworkError::ChannelError(_))));
+    }
 }
 
 /// 网络管理器测试
@@ -0,0 +1,265 @@
+//! 多进程集成测试
+//!
+//! 在本机启动 N 个真实的 `mpc_node` 进程，每个进程使用生成的配置和签名身份，
+//! 经 TCP 执行完整的流水线（密钥生成 → 预处理 → 在线计算 → 输出认证），
+//! 然后检查各节点报告中的结果、认证输出和统计信息。
+
+use mpc_api::beaver_triples::PreprocessingPool;
+use mpc_api::node::*;
+use mpc_api::secret_sharing::FIELD_PRIME;
+use mpc_api::storage::FileBackend;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 每个阶段的通信轮数
+const EXPECTED_ROUNDS: [(&str, usize); 4] = [
+    (DKG_STAGE, 1),
+    (PREPROCESSING_STAGE, 2),
+    (ONLINE_STAGE, 3),
+    (CERTIFICATION_STAGE, 3),
+];
+
+/// 一组节点进程，测试结束时终止残留进程并删除工作目录
+struct Cluster {
+    dir: PathBuf,
+    children: Vec<Child>,
+}
+
+/// 单个节点进程的结果
+struct NodeOutcome {
+    status: ExitStatus,
+    report: Option<NodeReport>,
+    stderr: String,
+}
+
+impl Cluster {
+    /// 写入配置并启动全部节点进程
+    fn spawn(label: &str, mut configs: Vec<NodeConfig>) -> (Self, Vec<NodeConfig>) {
+        let dir = std::env::temp_dir().join(format!(
+            "mpc_api_cluster_{}_{}_{}",
+            label,
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut children = Vec::with_capacity(configs.len());
+        for config in &mut configs {
+            let node_dir = dir.join(format!("node-{}", config.party_id));
+            fs::create_dir_all(&node_dir).unwrap();
+            config.data_dir = Some(node_dir.join("preprocessing"));
+            config.save(node_dir.join("config.json")).unwrap();
+
+            let child = Command::new(env!("CARGO_BIN_EXE_mpc_node"))
+                .arg(node_dir.join("config.json"))
+                .arg(node_dir.join("report.json"))
+                .stdout(Stdio::null())
+                .stderr(fs::File::create(node_dir.join("stderr.log")).unwrap())
+                .spawn()
+                .expect("failed to start mpc_node");
+            children.push(child);
+        }
+        (Self { dir, children }, configs)
+    }
+
+    /// 等待全部进程退出并读取报告
+    fn wait(&mut self, timeout: Duration) -> Vec<NodeOutcome> {
+        let deadline = Instant::now() + timeout;
+        let mut statuses = vec![None; self.children.len()];
+        while statuses.iter().any(Option::is_none) {
+            for (status, child) in statuses.iter_mut().zip(&mut self.children) {
+                if status.is_none() {
+                    *status = child.try_wait().unwrap();
+                }
+            }
+            assert!(Instant::now() < deadline, "cluster did not finish within {:?}", timeout);
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        statuses.into_iter()
+            .enumerate()
+            .map(|(party_id, status)| {
+                let node_dir = self.dir.join(format!("node-{}", party_id));
+                NodeOutcome {
+                    status: status.unwrap(),
+                    report: fs::read(node_dir.join("report.json"))
+                        .ok()
+                        .map(|data| serde_json::from_slice(&data).unwrap()),
+                    stderr: fs::read_to_string(node_dir.join("stderr.log")).unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+
+    /// 运行到结束，要求所有节点成功
+    fn run_to_completion(&mut self) -> Vec<NodeReport> {
+        let outcomes = self.wait(Duration::from_secs(120));
+        // 一个节点失败通常会导致其他节点连接中断，同时给出所有节点的错误信息
+        let failures: Vec<String> = outcomes.iter()
+            .enumerate()
+            .filter(|(_, outcome)| !outcome.status.success())
+            .map(|(party_id, outcome)| format!("node {}: {}", party_id, outcome.stderr.trim()))
+            .collect();
+        assert!(failures.is_empty(), "cluster failed:\n{}", failures.join("\n"));
+
+        outcomes.into_iter()
+            .map(|outcome| outcome.report.expect("node exited without a report"))
+            .collect()
+    }
+}
+
+impl Drop for Cluster {
+    fn drop(&mut self) {
+        for child in &mut self.children {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// 检查所有节点的结果、认证输出和统计信息
+fn check_reports(reports: &[NodeReport], configs: &[NodeConfig], inputs: &[u64]) {
+    let expected = AggregateOutput::compute(inputs);
+    let roster = configs[0].roster();
+    let n = configs.len();
+
+    for (party_id, report) in reports.iter().enumerate() {
+        assert_eq!(report.party_id, party_id);
+        assert_eq!(report.session_id, reports[0].session_id);
+        assert_eq!(report.public_key_digest, reports[0].public_key_digest);
+        assert_eq!(report.output, expected);
+
+        // 外部验证者只需要公钥名单
+        report.certificate.verify(&roster, n).unwrap();
+        assert_eq!(report.certificate.value(), Some(&expected));
+        assert_eq!(report.certificate.commitment, reports[0].certificate.commitment);
+
+        let stages: Vec<&str> = report.stages.iter().map(|stage| stage.stage.as_str()).collect();
+        assert_eq!(stages, EXPECTED_ROUNDS.map(|(stage, _)| stage));
+        for (stage, rounds) in EXPECTED_ROUNDS {
+            assert_eq!(report.stage(stage).unwrap().rounds, rounds, "rounds of {}", stage);
+        }
+        assert_eq!(report.stage(ONLINE_STAGE).unwrap().preprocessing_consumed, n);
+
+        assert_eq!(report.metrics.p2p_connections, n - 1);
+        assert_eq!(report.metrics.protocol_runs, EXPECTED_ROUNDS.len() as u64);
+        assert_eq!(report.metrics.protocol_rounds, EXPECTED_ROUNDS.iter().map(|&(_, r)| r as u64).sum::<u64>());
+        assert_eq!(report.metrics.preprocessing_consumed, n as u64);
+        assert_eq!(report.triples_remaining, configs[party_id].triples - n);
+    }
+
+    // 每个阶段中整个集群发送的字节数等于接收的字节数
+    for (stage, _) in EXPECTED_ROUNDS {
+        let sent: u64 = reports.iter().map(|report| report.stage(stage).unwrap().bytes_sent).sum();
+        let received: u64 = reports.iter().map(|report| report.stage(stage).unwrap().bytes_received).sum();
+        assert!(sent > 0, "no traffic in {}", stage);
+        assert_eq!(sent, received, "traffic mismatch in {}", stage);
+    }
+}
+
+// ===== Pipeline Tests =====
+
+#[test]
+fn test_three_node_pipeline() {
+    let inputs = [12, 7, 30];
+    let mut configs = generate_cluster("three-node", &inputs, 1).unwrap();
+    for config in &mut configs {
+        config.triples = 5;
+    }
+
+    let (mut cluster, configs) = Cluster::spawn("three", configs);
+    let reports = cluster.run_to_completion();
+    check_reports(&reports, &configs, &inputs);
+
+    // 未使用的三元组保存在各节点的存储目录中，进程退出后仍然可用
+    for config in &configs {
+        let backend = Arc::new(FileBackend::open(config.data_dir.as_ref().unwrap()).unwrap());
+        let pool = PreprocessingPool::new(backend, &config.session);
+        assert_eq!(pool.len().unwrap(), 2);
+    }
+}
+
+#[test]
+fn test_five_node_pipeline_with_two_colluders() {
+    let inputs = [1, 2, 3, 4, FIELD_PRIME - 1];
+    let configs = generate_cluster("five-node", &inputs, 2).unwrap();
+
+    let (mut cluster, configs) = Cluster::spawn("five", configs);
+    let reports = cluster.run_to_completion();
+    check_reports(&reports, &configs, &inputs);
+
+    // 隐去输出后仍可验证，事后公布的打开信息给出相同的结果
+    let redacted = reports[0].certificate.redacted();
+    let opening = reports[0].certificate.opening.clone().unwrap();
+    assert_eq!(
+        redacted.verify_opening(opening, &configs[0].roster(), 5).unwrap(),
+        AggregateOutput::compute(&inputs)
+    );
+}
+
+#[test]
+fn test_session_mismatch_fails_every_node() {
+    let mut configs = generate_cluster("mismatch", &[5, 6, 7], 1).unwrap();
+    for config in &mut configs {
+        config.timeout_ms = 3_000;
+    }
+    configs[2].session = "another-session".to_string();
+
+    let (mut cluster, _) = Cluster::spawn("mismatch", configs);
+    for (party_id, outcome) in cluster.wait(Duration::from_secs(60)).into_iter().enumerate() {
+        assert!(!outcome.status.success(), "node {} should have failed", party_id);
+        assert!(outcome.report.is_none());
+        assert!(outcome.stderr.contains("node failed"), "node {}: {}", party_id, outcome.stderr);
+    }
+}
+
+// ===== Two-Party AES Tests =====
+
+#[cfg(feature = "garbled-circuits")]
+#[test]
+fn test_two_party_aes_over_tcp() {
+    use mpc_api::garbled_circuits::aes128_encrypt;
+    use mpc_api::protocols::aes_gc::*;
+    use mpc_api::protocols::session::ProtocolSession;
+    use mpc_api::protocols::ProtocolStats;
+
+    // 只用前两个成员的地址
+    let mut configs = generate_cluster("aes-gc", &[0, 0, 0], 1).unwrap();
+    configs.truncate(2);
+    for config in &mut configs {
+        config.members.truncate(2);
+    }
+    let circuit = Arc::new(AesGcCircuit::builtin().unwrap());
+    let key: [u8; 16] = rand::random();
+    let plaintext: [u8; 16] = rand::random();
+    let inputs = [AesGcInput::Key(key), AesGcInput::Plaintext(plaintext)];
+
+    let handles: Vec<_> = configs.into_iter()
+        .zip(inputs)
+        .map(|(config, input)| {
+            let circuit = Arc::clone(&circuit);
+            thread::spawn(move || {
+                let session = ProtocolSession::root("aes-gc", config.session.as_bytes());
+                let mut transport = MeshTransport::connect(&config, session.id())?;
+                let mut stats = ProtocolStats::new();
+                let output = run_aes_gc(&mut transport, &circuit, input, &mut stats)?;
+                Ok::<_, mpc_api::MpcError>((output, stats))
+            })
+        })
+        .collect();
+    let results: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap().unwrap()).collect();
+
+    assert_eq!(results[0].0, None);
+    assert_eq!(results[1].0, Some(aes128_encrypt(&key, &plaintext)));
+    for (_, stats) in &results {
+        assert_eq!(stats.rounds, 3);
+    }
+    // 混淆电路由密钥持有方发出
+    assert!(results[0].1.bytes_sent > (circuit.and_count() * 32) as u64);
+    assert_eq!(results[0].1.bytes_sent, results[1].1.bytes_received);
+}
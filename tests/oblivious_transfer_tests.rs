@@ -2,4 +2,413 @@
 //! 
 //! 包含基础 OT, OT 扩展, VOLE, Random OT 等不经意传输协议的测试
 
-// Tests will be moved here from src/oblivious_transfer/
\ No newline at end of file
+// Tests will be moved here from src/oblivious_transfer/
+use mpc_api::oblivious_transfer::*;
+
+#[test]
+fn test_malicious_model_ot_roundtrip() {
+    for choice in [false, true] {
+        let expected = if choice { b"secret1".to_vec() } else { b"secret0".to_vec() };
+
+        let basic = execute_basic_ot_with_model(
+            b"secret0".to_vec(), b"secret1".to_vec(), choice, SecurityModel::Malicious,
+        ).unwrap();
+        assert_eq!(basic, expected);
+
+        let np = execute_naor_pinkas_ot_with_model(
+            b"secret0", b"secret1", choice, SecurityModel::Malicious,
+        ).unwrap();
+        assert_eq!(np, expected);
+    }
+}
+
+#[test]
+fn test_basic_ot_rejects_degenerate_sender_key() {
+    // g^a = 0 would make the receiver's message 0 exactly when choice = 1
+    let commitment = OTCommitment::commit(b"m0", b"m1").0;
+    let mut receiver = BasicOT::new().with_security_model(SecurityModel::Malicious);
+    assert!(receiver.receiver_phase1_committed(true, 0, commitment.clone()).is_err());
+    assert!(receiver.receiver_phase1_committed(false, 1, commitment).is_err());
+
+    // 恶意模型下必须先收到发送方的承诺
+    assert!(receiver.receiver_phase1(false, 5).is_err());
+
+    // 半诚实模型不做检查
+    let mut receiver = BasicOT::new();
+    assert!(receiver.receiver_phase1(true, 0).is_ok());
+}
+
+#[test]
+fn test_basic_ot_detects_tampered_sender_messages() {
+    for choice in [false, true] {
+        let mut sender = BasicOT::new().with_security_model(SecurityModel::Malicious);
+        let mut receiver = BasicOT::new().with_security_model(SecurityModel::Malicious);
+        receiver.setup = sender.setup.clone();
+
+        let a = sender.sender_phase1(vec![1; 16], vec![2; 16]).unwrap();
+        let commitment = sender.sender_commitment().unwrap().clone();
+        let b = receiver.receiver_phase1_committed(choice, a, commitment).unwrap();
+        let ((c0, c1), verification) = sender.sender_phase2_verified(b).unwrap();
+        assert_eq!(
+            receiver.receiver_phase2_verified((c0.clone(), c1.clone()), &verification).unwrap(),
+            vec![if choice { 2 } else { 1 }; 16],
+        );
+
+        // 篡改所选分支的密文：解密结果与承诺不符
+        let (mut u0, mut u1) = (c0.clone(), c1.clone());
+        if choice { u1[0] ^= 0xff } else { u0[0] ^= 0xff }
+        let error = receiver.receiver_phase2_verified((u0, u1), &verification).unwrap_err();
+
+        // 篡改盐值同样被拒绝，且与篡改密文返回相同的错误
+        let mut forged = verification.clone();
+        forged.encrypted_salts[usize::from(choice)][0] ^= 1;
+        let salt_error = receiver.receiver_phase2_verified((c0.clone(), c1.clone()), &forged).unwrap_err();
+        assert_eq!(error.to_string(), salt_error.to_string());
+
+        // 长度不一致的密文被拒绝
+        let mut short = c1.clone();
+        short.pop();
+        assert!(receiver.receiver_phase2_verified((c0.clone(), short), &verification).is_err());
+
+        // 恶意模型下必须使用带验证的接口
+        assert!(receiver.receiver_phase2((c0.clone(), c0)).is_err());
+    }
+}
+
+#[test]
+fn test_basic_ot_rejects_messages_changed_after_commitment() {
+    for choice in [false, true] {
+        let mut sender = BasicOT::new().with_security_model(SecurityModel::Malicious);
+        let mut receiver = BasicOT::new().with_security_model(SecurityModel::Malicious);
+        receiver.setup = sender.setup.clone();
+
+        let a = sender.sender_phase1(vec![1; 16], vec![2; 16]).unwrap();
+        let commitment = sender.sender_commitment().unwrap().clone();
+        let b = receiver.receiver_phase1_committed(choice, a, commitment).unwrap();
+
+        // 看到接收方消息后，发送方换成另一组消息并为其重新生成承诺和打开信息
+        let mut cheater = BasicOT::new().with_security_model(SecurityModel::Malicious);
+        cheater.setup = sender.setup.clone();
+        cheater.sender_phase1(vec![7; 16], vec![8; 16]).unwrap();
+        let (ciphertexts, verification) = cheater.sender_phase2_verified(b).unwrap();
+
+        // 接收方持有的是原来的承诺，无论选择哪个分支都拒绝
+        assert!(receiver.receiver_phase2_verified(ciphertexts, &verification).is_err());
+    }
+}
+
+#[test]
+fn test_typestate_ot_rounds() {
+    for model in [SecurityModel::SemiHonest, SecurityModel::Malicious] {
+        for choice in [false, true] {
+            let sender = OtSender::with_security_model(vec![1; 8], vec![2; 8], model).unwrap();
+            assert_eq!(sender.commitment().is_some(), model == SecurityModel::Malicious);
+            let receiver = OtReceiver::with_security_model(choice, model);
+            let (receiver, response) = match sender.commitment() {
+                Some(commitment) => receiver.receive_committed(sender.public_key(), commitment.clone()).unwrap(),
+                None => receiver.receive(sender.public_key()).unwrap(),
+            };
+            let transfer = sender.receive(response).send().unwrap();
+            assert_eq!(transfer.verification.is_some(), model == SecurityModel::Malicious);
+            assert_eq!(receiver.finish(transfer).unwrap(), vec![if choice { 2 } else { 1 }; 8]);
+        }
+    }
+
+    // 恶意模型的接收方拒绝没有承诺的发送方
+    let sender = OtSender::new(vec![1; 8], vec![2; 8]).unwrap();
+    assert!(OtReceiver::with_security_model(false, SecurityModel::Malicious)
+        .receive(sender.public_key())
+        .is_err());
+}
+
+#[test]
+fn test_naor_pinkas_rejects_tampered_branch_with_recomputed_opening() {
+    for choice in [false, true] {
+        let mut sender = NaorPinkasOT::new().with_security_model(SecurityModel::Malicious);
+        let mut receiver = NaorPinkasOT::new().with_security_model(SecurityModel::Malicious);
+        receiver.setup = sender.setup.clone();
+
+        let commitment = sender.sender_commit(b"left", b"rght");
+        let pk_r = receiver.receiver_round1_committed(choice, commitment).unwrap();
+        let (h, c0, c1, verification) = sender.sender_round1_verified(pk_r).unwrap();
+        assert_eq!(
+            receiver.receiver_round2_verified(h, choice, &c0, &c1, &verification).unwrap(),
+            if choice { b"rght".to_vec() } else { b"left".to_vec() },
+        );
+
+        // 发送方篡改所选分支的密文并重新计算全部打开信息：
+        // 接收方在发出消息前已持有承诺，篡改后的消息无法打开它
+        let (mut u0, mut u1) = (c0.clone(), c1.clone());
+        if choice { u1[0] ^= 1 } else { u0[0] ^= 1 }
+        let (mut m0, mut m1) = (b"left".to_vec(), b"rght".to_vec());
+        if choice { m1[0] ^= 1 } else { m0[0] ^= 1 }
+        let mut resender = NaorPinkasOT::new().with_security_model(SecurityModel::Malicious);
+        resender.setup = sender.setup.clone();
+        resender.sender_commit(&m0, &m1);
+        let (_, r0, r1, recomputed) = resender.sender_round1_verified(pk_r).unwrap();
+        assert_eq!((&r0, &r1), (&u0, &u1));
+        assert!(receiver.receiver_round2_verified(h, choice, &u0, &u1, &recomputed).is_err());
+        assert!(receiver.receiver_round2_verified(h, choice, &u0, &u1, &verification).is_err());
+
+        // 退化的 h 被拒绝；没有承诺的接收方不能进入恶意模型
+        assert!(receiver.receiver_round2_verified(0, choice, &c0, &c1, &verification).is_err());
+        let mut uncommitted = NaorPinkasOT::new().with_security_model(SecurityModel::Malicious);
+        assert!(uncommitted.receiver_round1(choice).is_err());
+        assert!(sender.sender_round1_verified(pk_r).is_err());
+    }
+}
+
+#[test]
+fn test_ot_extension_base_refresh() {
+    let mut extension = OTExtension::new(8);
+    extension.setup_base_ots().unwrap();
+    let old_base = extension.base_ots.clone();
+    let choices = vec![false, true, false, true];
+    let before = extension.extend_ots(4, &choices).unwrap();
+
+    extension.refresh_base_ots().unwrap();
+    assert_eq!(extension.base_ot_epoch(), 1);
+    assert_eq!(extension.base_ots.len(), 8);
+    assert_ne!(extension.base_ots, old_base);
+    assert_ne!(extension.extend_ots(4, &choices).unwrap(), before);
+}
+
+#[test]
+fn test_parallel_ot_extension_batches() {
+    use mpc_api::utils::concurrency::CancellationToken;
+
+    let mut extension = OTExtension::new(8);
+    let choices: Vec<bool> = (0..100).map(|i| i % 3 == 0).collect();
+    let token = CancellationToken::new();
+
+    // 没有基础 OT 时每批都失败，返回的是失败原因
+    let error = extension.parallel_batch_extend_ots(10, &choices, 4, &token).unwrap_err();
+    assert!(!error.to_string().contains("Cancelled"));
+
+    extension.setup_base_ots().unwrap();
+    let sequential = extension.batch_extend_ots(10, &choices).unwrap();
+    let parallel = extension.parallel_batch_extend_ots(10, &choices, 4, &token).unwrap();
+    assert_eq!(parallel.len(), 10);
+    assert_eq!(parallel, sequential);
+
+    assert!(extension.parallel_batch_extend_ots(0, &choices, 4, &token).is_err());
+    token.cancel("aborted");
+    assert!(extension.parallel_batch_extend_ots(10, &choices, 4, &token).is_err());
+}
+
+#[test]
+fn test_iknp_ot_extension() {
+    let (mut sender, mut receiver) = setup_ot_extension(SecurityModel::SemiHonest).unwrap();
+    let choices: Vec<bool> = (0..200).map(|i| i % 3 == 1).collect();
+
+    for _ in 0..2 {
+        let (received, request) = receiver.extend(&choices).unwrap();
+        assert!(request.check.is_none());
+        let pairs = sender.extend(&request).unwrap();
+        assert_eq!(pairs.len(), choices.len());
+        for ((&choice, &message), &(m0, m1)) in choices.iter().zip(&received).zip(&pairs) {
+            assert_ne!(m0, m1);
+            assert_eq!(message, if choice { m1 } else { m0 });
+        }
+    }
+
+    // 批次必须按顺序处理
+    let (_, stale) = receiver.extend(&choices).unwrap();
+    let (_, next) = receiver.extend(&choices).unwrap();
+    assert!(sender.extend(&next).is_err());
+    assert!(sender.extend(&stale).is_ok());
+}
+
+#[test]
+fn test_kos_ot_extension_detects_inconsistent_receiver() {
+    let (mut sender, mut receiver) = setup_ot_extension(SecurityModel::Malicious).unwrap();
+    assert_eq!(sender.security_model(), SecurityModel::Malicious);
+    let choices: Vec<bool> = (0..100).map(|i| i % 2 == 0).collect();
+
+    let (received, request) = receiver.extend(&choices).unwrap();
+    assert!(request.check.is_some());
+    assert_eq!(request.columns.len(), OT_EXTENSION_KAPPA);
+    let pairs = sender.extend(&request).unwrap();
+    for ((&choice, &message), &(m0, m1)) in choices.iter().zip(&received).zip(&pairs) {
+        assert_eq!(message, if choice { m1 } else { m0 });
+    }
+
+    // 恶意接收方只在偶数列中翻转第 5 行，各列使用的选择位不一致：检查失败且发送方中止
+    let (_, mut request) = receiver.extend(&choices).unwrap();
+    for column in request.columns.iter_mut().step_by(2) {
+        column[0] ^= 1 << 5;
+    }
+    assert!(matches!(sender.extend(&request), Err(mpc_api::MpcError::AuthenticationError(_))));
+    let (_, request) = receiver.extend(&choices).unwrap();
+    assert!(sender.extend(&request).is_err());
+
+    // 半诚实模型不检查，同样的篡改不会被发现
+    let (mut sender, mut receiver) = setup_ot_extension(SecurityModel::SemiHonest).unwrap();
+    let (_, mut request) = receiver.extend(&choices).unwrap();
+    for column in request.columns.iter_mut().step_by(2) {
+        column[0] ^= 1 << 5;
+    }
+    assert!(sender.extend(&request).is_ok());
+
+    // 恶意模型的发送方拒绝没有检查值的消息
+    let (mut sender, mut receiver) = setup_ot_extension(SecurityModel::Malicious).unwrap();
+    let (_, mut request) = receiver.extend(&choices).unwrap();
+    request.check = None;
+    assert!(sender.extend(&request).is_err());
+
+    // 篡改检查值同样被发现
+    let (mut sender, mut receiver) = setup_ot_extension(SecurityModel::Malicious).unwrap();
+    let (_, mut request) = receiver.extend(&choices).unwrap();
+    request.check.as_mut().unwrap().x ^= 1;
+    assert!(sender.extend(&request).is_err());
+}
+
+#[test]
+fn test_seeded_ot_extension_is_deterministic() {
+    use mpc_api::utils::crypto::{AesCtrPrg, Prg};
+
+    let run = |seed: [u8; 32]| {
+        let mut rng = AesCtrPrg::derive(&seed, b"ot-extension");
+        let (mut sender, mut receiver) = setup_ot_extension_with_rng(SecurityModel::Malicious, &mut rng).unwrap();
+        let choices: Vec<bool> = (0..70).map(|i| i % 5 == 0).collect();
+        let (received, request) = receiver.extend_with_rng(&choices, &mut rng).unwrap();
+        let pairs = sender.extend(&request).unwrap();
+        for ((&choice, &message), &(m0, m1)) in choices.iter().zip(&received).zip(&pairs) {
+            assert_eq!(message, if choice { m1 } else { m0 });
+        }
+        pairs
+    };
+    assert_eq!(run([1; 32]), run([1; 32]));
+    assert_ne!(run([1; 32]), run([2; 32]));
+}
+
+#[test]
+fn test_silent_vole_expansion() {
+    use mpc_api::secret_sharing::{field_add, field_mul};
+
+    for (output_len, factor) in [(1usize, 1usize), (1000, 4), (1 << 14, 32)] {
+        let params = SilentParams::new(output_len, factor).unwrap();
+        assert_eq!(params.base_len, output_len.div_ceil(factor));
+        assert!(params.noise_weight * params.block_len() >= output_len);
+
+        let (sender_seed, receiver_seed) = deal_silent_vole(&params).unwrap();
+        let sender = sender_seed.expand().unwrap();
+        let receiver = receiver_seed.expand().unwrap();
+        assert_eq!(sender.v.len(), output_len);
+        for i in 0..output_len {
+            assert_eq!(receiver.w[i], field_add(field_mul(receiver.u[i], sender.delta), sender.v[i]));
+        }
+    }
+
+    // 扩展倍数越大，种子相对输出越小
+    let small = SilentParams::new(1 << 14, 4).unwrap();
+    let large = SilentParams::new(1 << 14, 64).unwrap();
+    let small_size = deal_silent_vole(&small).unwrap().1.size_in_bytes().unwrap();
+    let large_size = deal_silent_vole(&large).unwrap().1.size_in_bytes().unwrap();
+    assert!(large_size < small_size);
+    assert!(large_size * 8 < (1 << 14) * 16);
+
+    assert!(SilentParams::new(0, 4).is_err());
+    assert!(SilentParams::new(16, 0).is_err());
+
+    // 穿孔密钥被篡改或与参数不符时展开失败
+    let (_, mut receiver_seed) = deal_silent_vole(&SilentParams::new(1000, 4).unwrap()).unwrap();
+    receiver_seed.keys[0].copath.pop();
+    assert!(receiver_seed.expand().is_err());
+}
+
+#[test]
+fn test_silent_random_ot_expansion() {
+    let params = SilentParams::new(5000, 10).unwrap();
+    let (sender_seed, receiver_seed) = deal_silent_ot(&params).unwrap();
+    let sender = sender_seed.expand().unwrap();
+    let receiver = receiver_seed.expand().unwrap();
+
+    let pairs = sender.random_ots();
+    let received = receiver.random_ots();
+    assert_eq!(pairs.len(), 5000);
+    for ((&choice, &message), &(m0, m1)) in receiver.choices.iter().zip(&received).zip(&pairs) {
+        assert_eq!(message, if choice { m1 } else { m0 });
+        assert_ne!(message, if choice { m0 } else { m1 });
+    }
+
+    // 选择位近似均匀
+    let ones = receiver.choices.iter().filter(|&&choice| choice).count();
+    assert!((2000..3000).contains(&ones));
+}
+
+#[test]
+fn test_silent_two_party_setup() {
+    use mpc_api::secret_sharing::{field_add, field_mul};
+
+    // VOLE：OT 穿孔、基础 VOLE 和单点修正，双方各自展开
+    let params = SilentParams::new(3000, 8).unwrap();
+    let (mut sender, mut receiver) = setup_silent_vole(&params).unwrap();
+    let request = receiver.request().unwrap();
+    let response = sender.respond(&request).unwrap();
+    let check = receiver.check(&response).unwrap();
+    let (sender_seed, corrections) = sender.finish(&check).unwrap();
+    let receiver_seed = receiver.finish(&corrections).unwrap();
+    assert_eq!(receiver_seed.keys.len(), params.noise_weight);
+
+    let sender_output = sender_seed.expand().unwrap();
+    let receiver_output = receiver_seed.expand().unwrap();
+    for i in 0..params.output_len {
+        assert_eq!(
+            receiver_output.w[i],
+            field_add(field_mul(receiver_output.u[i], sender_output.delta), sender_output.v[i])
+        );
+    }
+
+    // 篡改的基础 VOLE 检查响应被发送方拒绝
+    let (mut sender, mut receiver) = setup_silent_vole(&params).unwrap();
+    let request = receiver.request().unwrap();
+    let response = sender.respond(&request).unwrap();
+    let mut check = receiver.check(&response).unwrap();
+    check.v = field_add(check.v, 1);
+    assert!(sender.finish(&check).is_err());
+
+    // 随机 OT
+    let params = SilentParams::new(2000, 10).unwrap();
+    let (sender, mut receiver) = setup_silent_ot(&params).unwrap();
+    let request = receiver.request().unwrap();
+    let (sender_seed, response) = sender.respond(&request).unwrap();
+    let receiver_seed = receiver.finish(&response).unwrap();
+
+    let pairs = sender_seed.expand().unwrap().random_ots();
+    let receiver_output = receiver_seed.expand().unwrap();
+    for ((&choice, &message), &(m0, m1)) in receiver_output.choices.iter().zip(&receiver_output.random_ots()).zip(&pairs) {
+        assert_eq!(message, if choice { m1 } else { m0 });
+    }
+}
+
+#[test]
+fn test_batch_vole_evaluate() {
+    use mpc_api::secret_sharing::{field_add, field_mul, FIELD_PRIME};
+
+    let n = 200;
+    let u: Vec<u64> = (0..n).map(|i| (i * 7919 + 3) % FIELD_PRIME).collect();
+    let v: Vec<u64> = (0..n).map(|i| FIELD_PRIME - 1 - i).collect();
+    for x in [0, 1, 0xdead_beef, FIELD_PRIME - 1] {
+        let w = VectorOLE::new(n as usize).batch_evaluate(&u, &v, x).unwrap();
+        let expected: Vec<u64> = u.iter().zip(&v).map(|(&ui, &vi)| field_add(field_mul(ui, x), vi)).collect();
+        assert_eq!(w, expected);
+    }
+
+    assert!(VectorOLE::new(3).batch_evaluate(&u, &v, 5).is_err());
+    assert!(VectorOLE::new(1).batch_evaluate(&[FIELD_PRIME], &[0], 5).is_err());
+}
+
+#[test]
+fn test_batch_vole_detects_inconsistent_sender() {
+    let u = vec![11, 22, 33, 44];
+    let v = vec![5, 6, 7, 8];
+    // x 的第 0 位为 1，接收方会用到第 0 列
+    let (sender, mut receiver) = setup_batch_vole(&u, &v, 0x1235).unwrap();
+    let mut correction = sender.correction();
+    correction.columns[0][2] = mpc_api::secret_sharing::field_add(correction.columns[0][2], 1);
+
+    let challenges = receiver.receive(&correction).unwrap();
+    let response = sender.respond(&challenges).unwrap();
+    assert!(matches!(receiver.finish(&response), Err(mpc_api::MpcError::AuthenticationError(_))));
+}
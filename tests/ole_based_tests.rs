@@ -109,4 +109,17 @@ fn test_verifier_functionality() {
     let results = verifier.batch_verify(&triples).unwrap();
     assert_eq!(results.len(), 3);
     assert!(results.iter().all(|&x| x));
-}
\ No newline at end of file
+}
+#[test]
+fn test_ole_generate_batch_with_stats() {
+    let mut generator = OLEBeaverGenerator::new(3, 2, 0).unwrap();
+    let output = generator.generate_batch_with_stats(4).unwrap();
+
+    assert_eq!(output.result.len(), 4);
+    assert_eq!(output.stats.rounds, 2);
+    assert!(output.stats.bytes_sent > 0);
+    assert_eq!(output.stats.bytes_sent, output.stats.bytes_received);
+    assert_eq!(output.stats.preprocessing_consumed, 0);
+    // 默认实现只能估计生成器内部的通信量
+    assert!(output.stats.estimated);
+}
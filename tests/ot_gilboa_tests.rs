@@ -0,0 +1,44 @@
+use mpc_api::beaver_triples::ot_gilboa::*;
+use mpc_api::beaver_triples::{secure_multiply, BeaverTripleGenerator};
+use mpc_api::secret_sharing::{ShamirSecretSharing, SecretSharing, field_mul};
+
+#[test]
+fn test_ot_gilboa_generator_requires_two_parties() {
+    assert!(OTGilboaBeaverGenerator::new(2, 2, 0).is_ok());
+    assert!(OTGilboaBeaverGenerator::new(3, 2, 0).is_err());
+    assert!(OTGilboaBeaverGenerator::new(2, 1, 0).is_err());
+    assert!(OTGilboaBeaverGenerator::new(2, 2, 2).is_err());
+}
+
+#[test]
+fn test_ot_gilboa_triple_is_correct() {
+    let mut generator = OTGilboaBeaverGenerator::new(2, 2, 0).unwrap();
+    let triple = generator.generate_single().unwrap();
+    assert!(triple.original_values.is_none());
+    assert!(generator.verify_triple(&triple).unwrap());
+
+    let collect = |pick: fn(&mpc_api::beaver_triples::BeaverTriple) -> _| {
+        let mut shares: Vec<_> = triple.shares.iter().map(|(id, t)| (*id, pick(t))).collect();
+        shares.sort_by_key(|(id, _)| *id);
+        shares.into_iter().map(|(_, s)| s).collect::<Vec<_>>()
+    };
+    let a = ShamirSecretSharing::reconstruct(&collect(|t| t.a.clone()), 2).unwrap();
+    let b = ShamirSecretSharing::reconstruct(&collect(|t| t.b.clone()), 2).unwrap();
+    let c = ShamirSecretSharing::reconstruct(&collect(|t| t.c.clone()), 2).unwrap();
+    assert_eq!(c, field_mul(a, b));
+
+    // 三元组可以直接用于安全乘法
+    let x_shares = ShamirSecretSharing::share(&12, 2, 2).unwrap();
+    let y_shares = ShamirSecretSharing::share(&34, 2, 2).unwrap();
+    let product_shares = secure_multiply(&x_shares, &y_shares, &triple, 2).unwrap();
+    assert_eq!(ShamirSecretSharing::reconstruct(&product_shares, 2).unwrap(), field_mul(12, 34));
+}
+
+#[test]
+fn test_ot_gilboa_batch_stats() {
+    let mut generator = OTGilboaBeaverGenerator::new(2, 2, 1).unwrap();
+    let output = generator.generate_batch_with_stats(2).unwrap();
+    assert_eq!(output.result.len(), 2);
+    assert_eq!(output.stats.rounds, generator.rounds_per_batch());
+    assert!(output.stats.bytes_sent > 0);
+}
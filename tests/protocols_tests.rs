@@ -95,4 +95,1040 @@ fn test_random_field_elements_generation() {
     for element in elements {
         assert!(element < FIELD_PRIME);
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_coin_flip_party_rounds() {
+    let context = b"coin_flip/typestate";
+    let (parties, commitments): (Vec<_>, Vec<_>) = (0..3)
+        .map(|id| CoinFlipParty::new(id, 3, context).unwrap().commit().unwrap())
+        .unzip();
+    let (parties, openings): (Vec<_>, Vec<_>) = parties.into_iter()
+        .map(|party| party.open(commitments.clone()).unwrap())
+        .unzip();
+
+    let results: Vec<bool> = parties.iter().cloned()
+        .map(|party| party.finish(openings.clone()).unwrap())
+        .collect();
+    assert!(results.iter().all(|&r| r == results[0]));
+
+    // 打开信息被替换或缺失时失败
+    let mut forged = openings.clone();
+    forged[1].value = !forged[1].value;
+    assert!(parties[0].clone().finish(forged).is_err());
+    assert!(parties[0].clone().finish(openings[..2].to_vec()).is_err());
+
+    // 承诺数量不对或本方承诺被替换时拒绝打开
+    let (party, _) = CoinFlipParty::new(0, 2, context).unwrap().commit().unwrap();
+    assert!(party.clone().open(commitments[..1].to_vec()).is_err());
+    assert!(party.open(commitments[1..].to_vec()).is_err());
+    assert!(CoinFlipParty::new(2, 2, context).is_err());
+}
+// ===== Topology Tests =====
+
+use mpc_api::protocols::topology::*;
+use mpc_api::secret_sharing::{AdditiveSecretSharingScheme, field_sub, field_mul};
+
+#[test]
+fn test_topology_default_king_is_lowest_party() {
+    let topology = Topology::new(vec![3, 1, 2]).unwrap();
+    assert_eq!(topology.parties(), &[1, 2, 3]);
+    assert_eq!(topology.king(), 1);
+    assert!(topology.dealer().is_none());
+}
+
+#[test]
+fn test_topology_rejects_invalid_parties() {
+    assert!(Topology::new(vec![]).is_err());
+    assert!(Topology::new(vec![0, 1, 1]).is_err());
+    assert!(Topology::with_party_count(3).unwrap().with_role(ProtocolRole::Dealer, 5).is_err());
+}
+
+#[test]
+fn test_topology_routing() {
+    let topology = Topology::with_party_count(4).unwrap()
+        .with_role(ProtocolRole::King, 2).unwrap()
+        .with_role(ProtocolRole::Aggregator, 3).unwrap();
+
+    assert_eq!(topology.route_to_role(0, ProtocolRole::King).unwrap(), vec![2]);
+    assert!(topology.route_to_role(2, ProtocolRole::King).unwrap().is_empty());
+    assert_eq!(topology.route_to_role(1, ProtocolRole::Aggregator).unwrap(), vec![3]);
+    assert!(topology.route_to_role(1, ProtocolRole::Dealer).is_err());
+    assert_eq!(topology.broadcast_from_role(ProtocolRole::King).unwrap(), vec![0, 1, 3]);
+}
+
+#[test]
+fn test_additive_beaver_mul_uses_topology_king() {
+    let topology = Topology::with_party_count(3).unwrap()
+        .with_role(ProtocolRole::King, 2).unwrap();
+    let scheme = AdditiveSecretSharingScheme::with_topology(topology);
+
+    let (x, y) = (12u64, 34u64);
+    let x_shares = scheme.share_additive(&x, 3).unwrap();
+    let y_shares = scheme.share_additive(&y, 3).unwrap();
+    let (a_shares, b_shares, c_shares) = scheme.generate_beaver_triple_additive(3).unwrap();
+
+    let a = scheme.reconstruct_additive(&a_shares).unwrap();
+    let b = scheme.reconstruct_additive(&b_shares).unwrap();
+    let d = field_sub(x, a);
+    let e = field_sub(y, b);
+
+    let z_shares: Vec<_> = (0..3)
+        .map(|i| scheme.beaver_mul_additive(
+            &x_shares[i], &y_shares[i], &a_shares[i], &b_shares[i], &c_shares[i], &d, &e,
+        ).unwrap())
+        .collect();
+
+    assert_eq!(scheme.reconstruct_additive(&z_shares).unwrap(), field_mul(x, y));
+}
+
+// ===== Protocol Stats Tests =====
+
+use mpc_api::protocols::stats::*;
+
+#[test]
+fn test_protocol_stats_merge_and_recorder() {
+    let mut recorder = StatsRecorder::start();
+    recorder.stats_mut().record_rounds(2);
+    recorder.stats_mut().record_sent(100);
+    recorder.stats_mut().record_received(80);
+    recorder.stats_mut().record_preprocessing(3);
+    let output = recorder.finish(42u64);
+    assert_eq!(output.result, 42);
+
+    let mut total = ProtocolStats::new();
+    total.merge(&output.stats);
+    total.merge(&output.stats);
+    assert_eq!(total.rounds, 4);
+    assert_eq!(total.total_bytes(), 360);
+    assert_eq!(total.preprocessing_consumed, 6);
+}
+
+#[test]
+#[cfg(feature = "network")]
+fn test_connection_stats_aggregates_protocol_stats() {
+    use mpc_api::network::ConnectionStats;
+
+    let mut stats = ProtocolStats::new();
+    stats.record_rounds(3);
+    stats.record_sent(10);
+    stats.record_received(20);
+
+    let mut connection_stats = ConnectionStats::default();
+    connection_stats.record_protocol_stats(&stats);
+    connection_stats.record_protocol_stats(&stats);
+
+    assert_eq!(connection_stats.protocol_runs, 2);
+    assert_eq!(connection_stats.protocol_rounds, 6);
+    assert_eq!(connection_stats.bytes_sent, 20);
+    assert_eq!(connection_stats.bytes_received, 40);
+}
+
+use mpc_api::protocols::psi::*;
+use mpc_api::protocols::private_join::*;
+
+#[test]
+fn test_oblivious_prf_matches_direct_evaluation() {
+    let (mut ot_sender, mut ot_receiver) =
+        mpc_api::oblivious_transfer::setup_ot_extension(mpc_api::oblivious_transfer::SecurityModel::SemiHonest).unwrap();
+    let key = OprfKey::generate();
+    let inputs = vec![b"x".to_vec(), b"y".to_vec()];
+
+    // 密钥只在发送方使用，接收方只看到 OT 扩展消息和盲化基
+    let (receiver, request) = OprfReceiver::request(&inputs, &mut ot_receiver).unwrap();
+    assert_eq!(request.extension.count, inputs.len() * PSI_HASH_BITS);
+    let response = key.respond(&request, &mut ot_sender).unwrap();
+    let outputs = receiver.clone().finish(&response).unwrap();
+    assert_eq!(outputs[0], key.evaluate(b"x"));
+    assert_eq!(outputs[1], key.evaluate(b"y"));
+    assert_ne!(outputs[0], outputs[1]);
+
+    // 同一组 OT 扩展可以继续使用；不同密钥得到不同的 PRF 值
+    let (receiver, request) = OprfReceiver::request(&inputs[..1], &mut ot_receiver).unwrap();
+    let other = OprfKey::generate();
+    let outputs = receiver.finish(&other.respond(&request, &mut ot_sender).unwrap()).unwrap();
+    assert_eq!(outputs[0], other.evaluate(b"x"));
+    assert_ne!(outputs[0], key.evaluate(b"x"));
+
+    let mut truncated = response;
+    truncated.blinded_bases.pop();
+    let (receiver, _) = OprfReceiver::request(&inputs, &mut ot_receiver).unwrap();
+    assert!(receiver.finish(&truncated).is_err());
+}
+
+#[test]
+fn test_psi_intersection_indices() {
+    let sender = vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()];
+    let receiver = vec![b"c".to_vec(), b"d".to_vec(), b"a".to_vec()];
+    assert_eq!(execute_psi(&sender, receiver).unwrap(), vec![0, 2]);
+}
+
+#[test]
+fn test_psi_sender_and_receiver_messages() {
+    let (mut ot_sender, mut ot_receiver) =
+        mpc_api::oblivious_transfer::setup_ot_extension(mpc_api::oblivious_transfer::SecurityModel::SemiHonest).unwrap();
+    let sender = PsiSender::new(vec![b"a".to_vec(), b"b".to_vec()]);
+
+    let (pending, request) = PsiReceiver::request(vec![b"b".to_vec(), b"z".to_vec()], &mut ot_receiver).unwrap();
+    assert_eq!(request.oprf.count, request.cuckoo.bin_count);
+    let response = sender.respond_oprf(&request, &mut ot_sender).unwrap();
+    let receiver = pending.finish(&response).unwrap();
+
+    // 接收方通过 OPRF 得到的值与发送方自己计算的一致
+    let (bin, prf) = receiver.element_prf(0).unwrap();
+    assert_eq!(*prf, sender.evaluate_in_bin(bin, b"b"));
+    assert!(receiver.element_prf(2).is_none());
+
+    let values = sender.prf_values(&request.cuckoo).unwrap();
+    assert!(values.len() <= sender.elements().len() * CUCKOO_HASH_COUNT);
+    assert_eq!(receiver.intersection(&values), vec![0]);
+}
+
+#[test]
+fn test_private_join_shares_payloads() {
+    let records = vec![
+        PsiRecord::new(b"id-1".to_vec(), vec![10, 11]),
+        PsiRecord::new(b"id-2".to_vec(), vec![20, 21]),
+        PsiRecord::new(b"id-3".to_vec(), vec![30, 31]),
+    ];
+    let receiver_keys = vec![b"id-3".to_vec(), b"id-9".to_vec(), b"id-1".to_vec()];
+
+    let output = execute_private_join(records, receiver_keys).unwrap();
+    assert_eq!(output.receiver.matched, vec![true, false, true]);
+
+    // 接收方只持有掩码后的分享，看不到关联数据本身
+    assert_ne!(output.receiver.shares[0][0].value, 30);
+    assert_eq!(output.receiver.shares[0][0].party_id, JOIN_RECEIVER_ID);
+    assert_eq!(output.sender_shares[0][0].party_id, JOIN_SENDER_ID);
+
+    assert_eq!(output.reconstruct(0), Some(vec![30, 31]));
+    assert_eq!(output.reconstruct(1), None);
+    assert_eq!(output.reconstruct(2), Some(vec![10, 11]));
+
+    // 分享可以直接用于后续计算：交集关联数据第一列之和（发送方分享按桶排列）
+    let sum = [0usize, 2].iter().fold(0u64, |acc, &i| {
+        let sender_share = &output.sender_shares[output.receiver.bins[i]];
+        let s = mpc_api::secret_sharing::field_add(sender_share[0].value, output.receiver.shares[i][0].value);
+        mpc_api::secret_sharing::field_add(acc, s)
+    });
+    assert_eq!(sum, 40);
+
+    let mismatched = vec![PsiRecord::new(b"k".to_vec(), vec![1]), PsiRecord::new(b"l".to_vec(), vec![])];
+    assert!(JoinSender::new(mismatched).is_err());
+}
+
+#[test]
+fn test_psi_cuckoo_bins_keep_hints_linear() {
+    let elements: Vec<Vec<u8>> = (0..64u32).map(|i| i.to_le_bytes().to_vec()).collect();
+    let table = CuckooTable::build(&elements).unwrap();
+    let params = table.params();
+    assert_eq!(params.bin_count, 96);
+
+    // 每个元素恰好位于它的某个候选桶中
+    let bins = table.element_bins();
+    assert_eq!(bins.len(), elements.len());
+    for (element, &bin) in elements.iter().zip(&bins) {
+        assert!(params.candidate_bins(element).contains(&bin));
+        assert!(params.candidate_bins(element).len() <= CUCKOO_HASH_COUNT);
+    }
+
+    // 提示表按桶填充到相同条数，总量远小于逐对配对的 64 × 64
+    let records: Vec<PsiRecord> = elements.iter().map(|key| PsiRecord::new(key.clone(), vec![1])).collect();
+    let sender = JoinSender::new(records).unwrap();
+    let psi_sender = PsiSender::new(elements.clone());
+    let (hints, sender_shares) = sender.join_hints(&params).unwrap();
+    assert_eq!(hints.hints.len(), params.bin_count);
+    assert_eq!(sender_shares.len(), params.bin_count);
+    let load = hints.hints[0].len();
+    assert!(hints.hints.iter().all(|row| row.len() == load));
+    assert!(hints.entry_count() < elements.len() * elements.len());
+
+    // 重复元素无法放入布谷鸟哈希表
+    let duplicates = vec![b"same".to_vec(); 8];
+    assert!(CuckooTable::build(&duplicates).is_err());
+
+    // 接收方发来的桶数为 0 或过大时发送方拒绝请求，而不是 panic 或分配过大的表
+    for bin_count in [0, CUCKOO_MAX_BINS + 1] {
+        let params = CuckooParams { seed: 1, bin_count };
+        assert!(sender.join_hints(&params).is_err());
+        assert!(psi_sender.prf_values(&params).is_err());
+        assert!(params.candidate_bins(b"x").len() <= CUCKOO_HASH_COUNT);
+    }
+
+    let (mut ot_sender, mut ot_receiver) =
+        mpc_api::oblivious_transfer::setup_ot_extension(mpc_api::oblivious_transfer::SecurityModel::SemiHonest).unwrap();
+    let (_, oprf) = OprfReceiver::request(&[], &mut ot_receiver).unwrap();
+    let empty = PsiRequest { cuckoo: CuckooParams { seed: 1, bin_count: 0 }, oprf };
+    assert!(sender.respond_oprf(&empty, &mut ot_sender).is_err());
+    assert!(psi_sender.respond_oprf(&empty, &mut ot_sender).is_err());
+}
+
+// ===== Dot Product Tests =====
+
+use mpc_api::protocols::dot_product::*;
+
+#[test]
+fn test_dot_product_strategy_selection() {
+    assert_eq!(select_dot_product_strategy(1), DotProductStrategy::OtGadget);
+    assert_eq!(select_dot_product_strategy(OT_DOT_PRODUCT_MAX_LEN), DotProductStrategy::OtGadget);
+    assert_eq!(select_dot_product_strategy(OT_DOT_PRODUCT_MAX_LEN + 1), DotProductStrategy::Beaver);
+}
+
+#[test]
+fn test_secure_dot_product_2pc_both_strategies() {
+    let x = vec![3, FIELD_PRIME - 1, 1 << 40, 0];
+    let y = vec![7, 2, 1 << 20, 99];
+    let expected = mpc_api::secret_sharing::field_add(
+        21,
+        mpc_api::secret_sharing::field_add(FIELD_PRIME - 2, 1 << 60),
+    );
+
+    let ot = secure_dot_product_2pc(&x, &y).unwrap();
+    assert_eq!(ot.result.strategy, DotProductStrategy::OtGadget);
+    assert_eq!(ot.result.reconstruct(), expected);
+    assert_eq!(ot.result.x_holder.party_id, DOT_PRODUCT_X_HOLDER);
+    assert_eq!(ot.result.y_holder.party_id, DOT_PRODUCT_Y_HOLDER);
+    assert_eq!(ot.stats.rounds, 2);
+    assert_eq!(ot.stats.preprocessing_consumed, 0);
+    // y 持有方发送 OT 扩展消息，x 持有方发送修正值，两者大小不同
+    let (ot_x, ot_y) = (ot.stats.party(DOT_PRODUCT_X_HOLDER), ot.stats.party(DOT_PRODUCT_Y_HOLDER));
+    assert_eq!(ot_x.bytes_sent, ot_y.bytes_received);
+    assert_eq!(ot_y.bytes_sent, ot_x.bytes_received);
+    assert_ne!(ot_x.bytes_sent, ot_y.bytes_sent);
+    assert_eq!(ot.stats.bytes_sent, ot_x.bytes_sent + ot_y.bytes_sent);
+
+    let beaver = secure_dot_product_2pc_with(DotProductStrategy::Beaver, &x, &y).unwrap();
+    assert_eq!(beaver.result.reconstruct(), expected);
+    assert_eq!(beaver.stats.preprocessing_consumed, x.len());
+    assert_eq!(beaver.stats.rounds, 2);
+    let beaver_x = beaver.stats.party(DOT_PRODUCT_X_HOLDER);
+    assert_eq!(beaver_x, beaver.stats.party(DOT_PRODUCT_Y_HOLDER));
+    // 每方发送 len 个输入分享和 2·len 个公开值
+    assert!(beaver_x.bytes_sent >= 3 * x.len() as u64 * 8);
+    assert_eq!(beaver.stats.bytes_sent, 2 * beaver_x.bytes_sent);
+
+    assert!(secure_dot_product_2pc(&x, &y[..2]).is_err());
+}
+
+#[test]
+#[cfg(feature = "network")]
+fn test_ot_dot_product_over_network_messages() {
+    use mpc_api::oblivious_transfer::{setup_ot_extension, SecurityModel};
+
+    // 每一方只持有自己的 OT 扩展一端
+    let (mut x_ot, mut y_ot) = setup_ot_extension(SecurityModel::SemiHonest).unwrap();
+    let mut sender = OtDotProductSender::new(vec![5, 6]);
+    let mut receiver = OtDotProductReceiver::new(vec![10, 20]);
+
+    let wire1 = receiver.round1(&mut y_ot).unwrap().to_network_message().unwrap();
+    let wire2 = sender.round2(DotProductMessage::from_network_message(&wire1).unwrap(), &mut x_ot)
+        .unwrap().to_network_message().unwrap();
+    let y_share = receiver.finish(DotProductMessage::from_network_message(&wire2).unwrap()).unwrap();
+
+    let result = mpc_api::secret_sharing::field_add(sender.share().value, y_share.value);
+    assert_eq!(result, 170);
+
+    // 同一对扩展可以继续用于下一次内积
+    let output = ot_dot_product_2pc(&mut x_ot, &mut y_ot, &[FIELD_PRIME - 1, 3], &[2, 4]).unwrap();
+    assert_eq!(output.result.reconstruct(), 10);
+    assert_eq!(output.stats.rounds, 2);
+
+    // 乱序或长度不符的消息被拒绝
+    let mut other = OtDotProductReceiver::new(vec![1]);
+    assert!(other.finish(DotProductMessage::from_network_message(&wire2).unwrap()).is_err());
+    let mut short = OtDotProductSender::new(vec![1]);
+    assert!(short.round2(DotProductMessage::from_network_message(&wire1).unwrap(), &mut x_ot).is_err());
+}
+
+#[test]
+fn test_beaver_dot_product_per_party_rounds() {
+    let (x_triples, y_triples) = deal_dot_product_triples(3).unwrap();
+    let mut x_party = BeaverDotProductParty::new(DOT_PRODUCT_X_HOLDER, vec![1, 2, 3], x_triples.clone()).unwrap();
+    let mut y_party = BeaverDotProductParty::new(DOT_PRODUCT_Y_HOLDER, vec![4, 5, 6], y_triples).unwrap();
+
+    let x_inputs = x_party.round1().unwrap();
+    let y_inputs = y_party.round1().unwrap();
+    // 公开值不能早于输入分享
+    assert!(x_party.finish(y_inputs.clone()).is_err());
+    let x_openings = x_party.round2(y_inputs).unwrap();
+    let y_openings = y_party.round2(x_inputs).unwrap();
+    let x_share = x_party.finish(y_openings).unwrap();
+    let y_share = y_party.finish(x_openings).unwrap();
+    assert_eq!(mpc_api::secret_sharing::field_add(x_share.value, y_share.value), 32);
+
+    // 三元组属于另一方或数量不足时拒绝
+    assert!(BeaverDotProductParty::new(DOT_PRODUCT_Y_HOLDER, vec![1, 2, 3], x_triples.clone()).is_err());
+    assert!(BeaverDotProductParty::new(DOT_PRODUCT_X_HOLDER, vec![1, 2], x_triples).is_err());
+}
+
+// ===== Output Certification Tests =====
+
+use mpc_api::protocols::output_certification::*;
+
+fn certifiers_and_roster(count: usize) -> (Vec<OutputCertifier>, std::collections::HashMap<usize, mpc_api::elliptic_curve::Ed25519PublicKey>) {
+    let certifiers: Vec<_> = (0..count).map(|id| OutputCertifier::new(id).unwrap()).collect();
+    let roster = certifiers.iter().map(|c| (c.party_id(), *c.public_key())).collect();
+    (certifiers, roster)
+}
+
+#[test]
+fn test_certified_output_external_verification() {
+    let (certifiers, roster) = certifiers_and_roster(3);
+    let tally = vec![12u64, 7, 30];
+
+    let certified = certify_output("vote-2024", &tally, &certifiers).unwrap();
+    assert_eq!(certified.signatures.len(), 3);
+    certified.verify(&roster, 3).unwrap();
+
+    // 外部验证者只拿到序列化后的证书
+    let bytes = bincode::serialize(&certified).unwrap();
+    let received: CertifiedOutput<Vec<u64>> = bincode::deserialize(&bytes).unwrap();
+    received.verify(&roster, 2).unwrap();
+    assert_eq!(received.value(), Some(&tally));
+
+    // 篡改输出值
+    let mut forged = received.clone();
+    forged.opening.as_mut().unwrap().value[0] = 13;
+    assert!(forged.verify(&roster, 2).is_err());
+
+    // 签名不足、重复签名、未知签名方
+    let mut partial = received.clone();
+    partial.signatures.truncate(1);
+    assert!(partial.verify(&roster, 2).is_err());
+    let mut duplicated = received.clone();
+    duplicated.signatures[1] = duplicated.signatures[0].clone();
+    assert!(duplicated.verify(&roster, 2).is_err());
+    let mut unknown_roster = roster.clone();
+    unknown_roster.remove(&2);
+    assert!(received.verify(&unknown_roster, 2).is_err());
+
+    // 签名绑定会话 ID
+    let mut replayed = received.clone();
+    replayed.session_id = "vote-2025".to_string();
+    assert!(replayed.verify(&roster, 2).is_err());
+}
+
+#[test]
+fn test_certified_output_rejects_single_byte_changes() {
+    let (certifiers, roster) = certifiers_and_roster(2);
+    let certified = certify_output("auction-3", &9_001u64, &certifiers).unwrap().redacted();
+    certified.verify(&roster, 2).unwrap();
+
+    // 签名或承诺中任意一个字节被改动，证书都不再有效
+    let signature = bincode::serialize(&certified.signatures[0]).unwrap();
+    for position in 0..signature.len() {
+        let mut bytes = signature.clone();
+        bytes[position] ^= 0x01;
+        if let Ok(altered) = bincode::deserialize::<OutputSignature>(&bytes) {
+            let mut forged = certified.clone();
+            forged.signatures[0] = altered;
+            assert!(forged.verify(&roster, 2).is_err(), "signature byte {} accepted", position);
+        }
+    }
+    let commitment = bincode::serialize(&certified.commitment).unwrap();
+    for position in 0..commitment.len() {
+        let mut bytes = commitment.clone();
+        bytes[position] ^= 0x01;
+        if let Ok(altered) = bincode::deserialize(&bytes) {
+            let mut forged = certified.clone();
+            forged.commitment = altered;
+            assert!(forged.verify(&roster, 2).is_err(), "commitment byte {} accepted", position);
+        }
+    }
+}
+
+#[test]
+fn test_redacted_output_opened_later() {
+    let (certifiers, roster) = certifiers_and_roster(2);
+    let certified = certify_output("auction-1", &4_200u64, &certifiers).unwrap();
+
+    let redacted = certified.redacted();
+    assert_eq!(redacted.value(), None);
+    redacted.verify(&roster, 2).unwrap();
+
+    let opening = certified.opening.clone().unwrap();
+    assert_eq!(redacted.verify_opening(opening.clone(), &roster, 2).unwrap(), 4_200);
+
+    let mut wrong = opening;
+    wrong.value = 4_100;
+    assert!(redacted.verify_opening(wrong, &roster, 2).is_err());
+}
+
+#[test]
+fn test_output_certification_rejects_inconsistent_parties() {
+    let (certifiers, _) = certifiers_and_roster(2);
+    let session = "auction-2";
+
+    let contributions: Vec<_> = certifiers.iter().map(|c| {
+        let committed = c.contribute_randomness(session).unwrap();
+        (c.party_id(), committed.commitment().clone(), committed.open())
+    }).collect();
+    let randomness = combine_randomness(session, &contributions).unwrap();
+
+    // 打开信息被替换
+    let mut tampered = contributions.clone();
+    tampered[0].2.value[0] ^= 1;
+    assert!(combine_randomness(session, &tampered).is_err());
+
+    // 对不同输出值计算的承诺不同，签名覆盖的是各自的承诺
+    let (honest, _) = certifiers[0].sign_output(session, &10u64, &randomness).unwrap();
+    let (cheating, _) = certifiers[1].sign_output(session, &11u64, &randomness).unwrap();
+    assert_ne!(honest, cheating);
+    let (agreed, _) = certifiers[1].sign_output(session, &10u64, &randomness).unwrap();
+    assert_eq!(honest, agreed);
+}
+
+// ===== Session Composition Tests =====
+
+use mpc_api::protocols::session::*;
+
+#[test]
+fn test_child_sessions_are_distinct_and_bound() {
+    let mut spdz = ProtocolSession::root("spdz", b"parties=3");
+    let other_root = ProtocolSession::root("spdz", b"parties=4");
+    assert_ne!(spdz.id(), other_root.id());
+
+    let first = spdz.spawn_child("sacrifice");
+    let second = spdz.spawn_child("sacrifice");
+    assert_ne!(first.id(), second.id());
+    assert_eq!(second.path(), "spdz/sacrifice#1");
+    assert_ne!(first.challenge_field("r"), second.challenge_field("r"));
+
+    // 相同的子协议路径在不同父上下文中派生出不同的会话
+    let mut other_root = other_root;
+    let foreign = other_root.spawn_child("sacrifice");
+    assert_eq!(foreign.path(), first.path());
+    assert_ne!(foreign.id(), first.id());
+    assert_ne!(foreign.challenge("r"), first.challenge("r"));
+
+    // 挑战值随转录变化
+    let mut child = first.clone();
+    let before = child.challenge("r");
+    assert_eq!(child.transcript().challenge_bytes::<32>(b"r"), before);
+    child.absorb("message", b"hello");
+    assert_ne!(before, child.challenge("r"));
+
+    // 只接受由本会话派生且尚未结束的子会话
+    assert!(spdz.finish_child(foreign).is_err());
+    let digest = spdz.transcript_digest();
+    spdz.finish_child(child.clone()).unwrap();
+    assert_ne!(digest, spdz.transcript_digest());
+    assert!(spdz.finish_child(child).is_err());
+}
+
+#[test]
+fn test_nested_coin_flip_rejects_spliced_commitments() {
+    use mpc_api::commitment::CommittedMessage;
+
+    let mut spdz = ProtocolSession::root("spdz", b"instance-1");
+    let mut sacrifice = spdz.spawn_child("sacrifice");
+    let mut coin_flip = sacrifice.spawn_child("coin_flip");
+
+    BlumCoinFlip::multi_party_coin_flip_in_session(&mut coin_flip, 3).unwrap();
+    assert!(BlumCoinFlip::multi_party_coin_flip_in_session(&mut coin_flip, 0).is_err());
+
+    // 父会话还有未结束的子会话时不能结束
+    let mut early = sacrifice.clone();
+    early.spawn_child("coin_flip");
+    assert!(spdz.finish_child(early).is_err());
+
+    // 一个兄弟实例中的承诺不能在另一个实例中打开
+    let sibling = sacrifice.spawn_child("coin_flip");
+    let committed = CommittedMessage::commit(true, &coin_flip.commitment_context("coin_flip")).unwrap();
+    let commitment = committed.commitment().clone();
+    assert!(commitment.open_verified(committed.open(), &sibling.commitment_context("coin_flip")).is_err());
+
+    sacrifice.finish_child(coin_flip).unwrap();
+    sacrifice.finish_child(sibling).unwrap();
+    spdz.finish_child(sacrifice).unwrap();
+}
+
+// ===== Secure Aggregation Tests =====
+
+use mpc_api::protocols::secure_aggregation::*;
+use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+
+#[test]
+fn test_weighted_aggregation_clips_large_updates() {
+    let clipping = ClippingBound::new(6, 40).unwrap();
+    let aggregation = WeightedSecureAggregation::new(3, 2, 3, clipping).unwrap();
+    let submissions = vec![
+        ClientSubmission::new(7, &[0, 40, 63], 3, clipping, 3, 2).unwrap(),
+        ClientSubmission::new(8, &[41, 39, 1], 1, clipping, 3, 2).unwrap(),
+    ];
+
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    let (result, stats) = aggregation.aggregate(&submissions, &mut generator).unwrap().into_parts();
+
+    assert_eq!(result.reconstruct(2).unwrap(), vec![40, 3 * 40 + 39, 3 * 40 + 1]);
+    assert_eq!(result.accepted, vec![7, 8]);
+    assert!(result.rejected.is_empty());
+    assert_eq!(result.total_weight, 4);
+    // 输入 + 范围证明乘法 + 公开检查 + 每位一次比较 + 多路选择
+    assert_eq!(stats.rounds, 1 + 1 + 1 + 6 + 1);
+    assert_eq!(stats.preprocessing_consumed, 2 * 3 * (6 + 6 + 1));
+
+    // 客户端的输入只计入服务器的接收量，服务器之间的消息两端各计一次
+    assert!(stats.bytes_received > stats.bytes_sent);
+    let servers: Vec<_> = (1..=3).map(|party| stats.party(party)).collect();
+    assert_eq!(servers.iter().map(|traffic| traffic.bytes_sent).sum::<u64>(), stats.bytes_sent);
+    assert_eq!(servers.iter().map(|traffic| traffic.bytes_received).sum::<u64>(), stats.bytes_received);
+    for traffic in &servers {
+        assert!(traffic.bytes_received > traffic.bytes_sent);
+        assert_eq!(traffic, &servers[0]);
+    }
+}
+
+#[test]
+fn test_weighted_aggregation_rejects_non_boolean_bits() {
+    use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+
+    let clipping = ClippingBound::new(4, 5).unwrap();
+    let aggregation = WeightedSecureAggregation::new(3, 2, 1, clipping).unwrap();
+    let honest = ClientSubmission::new(1, &[3], 1, clipping, 3, 2).unwrap();
+    // 恶意客户端把最高位分享为 1000，绕过位长限制
+    let mut poisoned = ClientSubmission::new(2, &[0], 1, clipping, 3, 2).unwrap();
+    poisoned.bit_shares[0][3] = ShamirSecretSharing::share(&1000, 2, 3).unwrap();
+
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    let result = aggregation.aggregate(&[honest, poisoned], &mut generator).unwrap().result;
+    assert_eq!(result.accepted, vec![1]);
+    assert_eq!(result.rejected, vec![2]);
+    assert_eq!(result.reconstruct(2).unwrap(), vec![3]);
+
+    assert!(ClippingBound::new(4, 16).is_err());
+    assert!(ClientSubmission::new(3, &[16], 1, clipping, 3, 2).is_err());
+    let wrong_dimension = ClientSubmission::new(4, &[1, 2], 1, clipping, 3, 2).unwrap();
+    assert!(aggregation.aggregate(&[wrong_dimension], &mut generator).is_err());
+}
+
+// ===== Logical Clock Tests =====
+
+#[test]
+fn test_logical_clock_rounds() {
+    use mpc_api::protocols::clock::*;
+    use mpc_api::protocols::session::ProtocolSession;
+
+    let session = ProtocolSession::root("spdz", b"clock").id();
+    let other = ProtocolSession::root("spdz", b"other").id();
+    let mut sender = LogicalClock::new();
+    let mut receiver = LogicalClock::new();
+
+    let first = sender.stamp(session);
+    let second = sender.stamp(session);
+    assert_eq!((first.round, second.round), (1, 2));
+    assert_eq!(sender.stamp(other).round, 1);
+
+    // 接收方追上发送方的轮次，重放的旧轮次被拒绝
+    assert_eq!(receiver.observe(&second).unwrap(), 2);
+    assert!(receiver.observe(&first).is_err());
+    assert!(receiver.observe(&second).is_err());
+    assert_eq!(receiver.stamp(session).round, 3);
+
+    receiver.forget(session);
+    assert_eq!(receiver.current(session), 0);
+}
+
+#[test]
+fn test_clock_skew_handshake() {
+    use mpc_api::protocols::clock::*;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let nanos = |secs: u64, millis: u64| secs * 1_000_000_000 + millis * 1_000_000;
+    let at = |secs: u64, millis: u64| UNIX_EPOCH + Duration::from_nanos(nanos(secs, millis));
+
+    // 对方时钟快 3 秒，单向时延 50ms
+    let mut tracker = ClockSkewTracker::new(Duration::from_secs(5));
+    let reply = SkewReply {
+        probe_sent_at: nanos(1000, 0),
+        received_at: nanos(1003, 50),
+        replied_at: nanos(1003, 60),
+    };
+    let estimate = tracker.complete_at("slow-clock", &reply, at(1000, 110)).unwrap();
+    assert_eq!(estimate.offset_nanos, 3_000_000_000);
+    assert_eq!(estimate.round_trip, Duration::from_millis(100));
+    assert_eq!(tracker.deadline_allowance("slow-clock"), Duration::from_millis(50));
+    assert_eq!(tracker.deadline_allowance("unknown"), Duration::from_secs(5));
+
+    // 换算后的时间戳被接受，同一时间戳对未握手的参与方会被拒绝
+    let now = at(2000, 0);
+    assert!(tracker.check_timestamp_at("slow-clock", at(2007, 0), now).is_ok());
+    assert!(tracker.check_timestamp_at("unknown", at(2007, 0), now).is_err());
+    assert!(tracker.check_timestamp_at("unknown", at(2004, 0), now).is_ok());
+    assert!(tracker.check_timestamp_at("slow-clock", at(1996, 0), now).is_err());
+
+    // 截止时间按对方时钟换算
+    assert!(tracker.within_deadline("slow-clock", at(2003, 0), now));
+    assert!(!tracker.within_deadline("slow-clock", at(2004, 0), now));
+
+    // 超出上限的偏差在握手时即被拒绝
+    let far = SkewReply { probe_sent_at: nanos(1000, 0), received_at: nanos(1010, 0), replied_at: nanos(1010, 0) };
+    assert!(tracker.complete_at("far", &far, at(1000, 10)).is_err());
+    assert!(tracker.estimate("far").is_none());
+
+    let probe = tracker.probe();
+    let reply = SkewReply::respond(&probe);
+    assert!(tracker.complete("local", &reply).is_ok());
+    assert!(tracker.check_timestamp("local", SystemTime::now()).is_ok());
+}
+
+// ===== Oblivious Array Tests =====
+
+#[test]
+fn test_oblivious_array_read_write() {
+    use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+    use mpc_api::protocols::oblivious_array::*;
+    use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+
+    let share = |value: u64| ShamirSecretSharing::share(&value, 2, 3).unwrap();
+    let open = |shares: &[mpc_api::secret_sharing::Share]| ShamirSecretSharing::reconstruct(&shares[1..], 2).unwrap();
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+
+    let table: Vec<u64> = vec![7, 11, 13, 17, 19, 23];
+    let mut array = LinearScanArray::new(table.iter().map(|&v| share(v)).collect(), 2).unwrap();
+    for (i, &expected) in table.iter().enumerate() {
+        let output = array.read(&share(i as u64), &mut generator).unwrap();
+        assert_eq!(open(&output.result), expected);
+        // 幂需要 ⌈log₂ 5⌉ = 3 轮，组合需要 1 轮，与下标无关
+        assert_eq!(output.stats.rounds, 4);
+        assert_eq!(output.stats.preprocessing_consumed, 4 + 5);
+    }
+
+    let output = array.write(&share(3), &share(100), &mut generator).unwrap();
+    assert_eq!(output.stats.preprocessing_consumed, 4 + 6);
+    let values: Vec<u64> = array.elements().iter().map(|shares| open(shares)).collect();
+    assert_eq!(values, vec![7, 11, 13, 100, 19, 23]);
+
+    // 单元素数组和函数形式
+    let single = vec![share(42)];
+    assert_eq!(open(&oblivious_read(&single, &share(0), 2, &mut generator).unwrap().result), 42);
+    let written = oblivious_write(&single, &share(0), &share(5), 2, &mut generator).unwrap();
+    assert_eq!(open(&written.result[0]), 5);
+
+    assert!(LinearScanArray::new(Vec::new(), 2).is_err());
+    assert!(array.read(&share(0)[..2], &mut generator).is_err());
+}
+
+// ===== Path ORAM Tests =====
+
+#[test]
+fn test_path_oram_matches_plain_array() {
+    use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+    use mpc_api::protocols::oblivious_array::ObliviousArray;
+    use mpc_api::protocols::oram::*;
+    use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+    use rand::Rng;
+
+    let share = |value: u64| ShamirSecretSharing::share(&value, 2, 3).unwrap();
+    let open = |shares: &[mpc_api::secret_sharing::Share]| ShamirSecretSharing::reconstruct(&shares[1..], 2).unwrap();
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+
+    // 8 个位置、每级打包 2 个条目，得到数据树加两级位置图
+    let config = PathOramConfig { bucket_size: 4, stash_size: 8, packing_bits: 1, linear_cutoff_bits: 1 };
+    let mut oram = PathOram::new(8, 2, 3, config).unwrap();
+    assert_eq!(oram.len(), 8);
+    assert_eq!(oram.address_bits(), 3);
+    assert_eq!(oram.recursion_depth(), 3);
+
+    // 未写入的位置读出 0
+    assert_eq!(open(&oram_read(&mut oram, &share(5), &mut generator).unwrap().result), 0);
+
+    let mut rng = rand::thread_rng();
+    let mut plain = [0u64; 8];
+    let mut stats = None;
+    for _ in 0..24 {
+        let index = rng.gen_range(0..8);
+        let output = if rng.gen_bool(0.5) {
+            let value = rng.gen_range(0..1000);
+            plain[index] = value;
+            oram_write(&mut oram, &share(index as u64), &share(value), &mut generator).unwrap().stats
+        } else {
+            let output = oram_read(&mut oram, &share(index as u64), &mut generator).unwrap();
+            assert_eq!(open(&output.result), plain[index]);
+            output.stats
+        };
+
+        // 读写执行相同的操作序列，开销与下标无关
+        let cost = (output.rounds, output.bytes_sent, output.preprocessing_consumed);
+        assert_eq!(*stats.get_or_insert(cost), cost);
+    }
+    for (index, &expected) in plain.iter().enumerate() {
+        assert_eq!(open(&oram.read(&share(index as u64), &mut generator).unwrap().result), expected);
+    }
+}
+
+#[test]
+fn test_path_oram_rejects_invalid_parameters() {
+    use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+    use mpc_api::protocols::oram::*;
+    use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+
+    let config = PathOramConfig::default();
+    assert!(PathOram::new(0, 2, 3, config).is_err());
+    assert!(PathOram::new(16, 4, 3, config).is_err());
+    assert!(PathOram::new(1 << (MAX_ORAM_ADDRESS_BITS + 1), 2, 3, config).is_err());
+    assert!(PathOram::new(16, 2, 3, PathOramConfig { stash_size: 0, ..config }).is_err());
+
+    // 小容量只需要线性位置图
+    let mut oram = PathOram::new(16, 2, 3, config).unwrap();
+    assert_eq!(oram.recursion_depth(), 1);
+
+    let index = ShamirSecretSharing::share(&3, 2, 3).unwrap();
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    assert!(oram_read(&mut oram, &index[..2], &mut generator).is_err());
+    assert!(oram_write(&mut oram, &index, &index[..1], &mut generator).is_err());
+    let mut mismatched = TrustedPartyBeaverGenerator::new(4, 2, 0, None).unwrap();
+    assert!(oram_read(&mut oram, &index, &mut mismatched).is_err());
+}
+
+// ===== String Equality Tests =====
+
+#[test]
+fn test_string_equality_and_keyword_search() {
+    use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+    use mpc_api::protocols::string_equality::*;
+    use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+
+    let open = |shares: &[mpc_api::secret_sharing::Share]| ShamirSecretSharing::reconstruct(&shares[1..], 2).unwrap();
+    let shared = |text: &str| SharedString::share(text.as_bytes(), 2, 3).unwrap();
+    let protocol = StringEquality::new(3, 2).unwrap();
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+
+    let output = protocol.equal(&shared("hello"), &shared("hello"), &mut generator).unwrap();
+    assert_eq!(open(&output.result), 1);
+    assert_eq!(output.stats.rounds, ZERO_TEST_MULTIPLICATIONS);
+    // 空串、前缀和长字符串都按哈希编码比较
+    let long = "x".repeat(10_000);
+    for (a, b) in [("hello", "hellO"), ("", "a"), ("abc", "abcd"), (long.as_str(), "x")] {
+        assert_eq!(open(&protocol.equal(&shared(a), &shared(b), &mut generator).unwrap().result), 0);
+    }
+
+    let records: Vec<SharedString> = ["carol", "dave", "carol", "erin", ""].iter().map(|text| shared(text)).collect();
+    let output = protocol.keyword_search(&records, &shared("carol"), &mut generator).unwrap();
+    let matches: Vec<u64> = output.result.iter().map(|bit| open(bit)).collect();
+    assert_eq!(matches, vec![1, 0, 1, 0, 0]);
+    // 轮数与记录数量无关
+    assert_eq!(output.stats.rounds, ZERO_TEST_MULTIPLICATIONS);
+    assert_eq!(output.stats.preprocessing_consumed, records.len() * ZERO_TEST_MULTIPLICATIONS);
+    assert!(protocol.keyword_search(&[], &shared("carol"), &mut generator).unwrap().result.is_empty());
+
+    // 参数不一致时拒绝
+    let other_parties = SharedString::share(b"carol", 2, 4).unwrap();
+    assert!(protocol.equal(&records[0], &other_parties, &mut generator).is_err());
+    let mut mismatched = TrustedPartyBeaverGenerator::new(4, 2, 0, None).unwrap();
+    assert!(protocol.equal(&records[0], &records[1], &mut mismatched).is_err());
+    assert!(SharedString::from_limb_shares(records[0].limbs()[..1].to_vec()).is_err());
+    assert_eq!(SharedString::from_limb_shares(records[0].limbs().to_vec()).unwrap(), records[0]);
+    assert!(StringEquality::new(3, 4).is_err());
+}
+
+// ===== Secure Comparison Tests =====
+
+#[test]
+fn test_secure_comparison_less_than_and_equal() {
+    use mpc_api::protocols::comparison::*;
+    use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+
+    let open = |shares: &[mpc_api::secret_sharing::Share]| ShamirSecretSharing::reconstruct(&shares[1..], 2).unwrap();
+    let shared = |value: u64| ShamirSecretSharing::share(&value, 2, 3).unwrap();
+    let protocol = SecureComparison::new(3, 2, 8).unwrap();
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+
+    let values = [(0, 0), (0, 1), (1, 0), (17, 200), (200, 17), (255, 255), (254, 255), (255, 0)];
+    let shares: Vec<_> = values.iter().map(|&(a, b)| (shared(a), shared(b))).collect();
+    let pairs: Vec<ComparisonPair> = shares.iter().map(|(a, b)| (a.as_slice(), b.as_slice())).collect();
+
+    let less = protocol.less_than_batch(&pairs, &mut generator).unwrap();
+    let equal = protocol.equal_batch(&pairs, &mut generator).unwrap();
+    for (i, &(a, b)) in values.iter().enumerate() {
+        assert_eq!(open(&less.result[i]), (a < b) as u64, "{} < {}", a, b);
+        assert_eq!(open(&equal.result[i]), (a == b) as u64, "{} == {}", a, b);
+    }
+
+    // 轮数与对数无关
+    let single = protocol.less_than(&shares[3].0, &shares[3].1, &mut generator).unwrap();
+    assert_eq!(open(&single.result), 1);
+    assert_eq!(single.stats.rounds, less.stats.rounds);
+    assert!(protocol.equal_batch(&[], &mut generator).unwrap().result.is_empty());
+
+    // 参数不一致时拒绝
+    assert!(SecureComparison::new(3, 2, 0).is_err());
+    assert!(SecureComparison::new(3, 2, MAX_COMPARISON_BITS + 1).is_err());
+    assert!(SecureComparison::new(3, 4, 8).is_err());
+    let wide = SecureComparison::new(3, 2, MAX_COMPARISON_BITS).unwrap();
+    let max = (1u64 << MAX_COMPARISON_BITS) - 1;
+    assert_eq!(open(&wide.less_than(&shared(max - 1), &shared(max), &mut generator).unwrap().result), 1);
+    let other_parties = ShamirSecretSharing::share(&1, 2, 4).unwrap();
+    assert!(protocol.equal(&shares[0].0, &other_parties, &mut generator).is_err());
+    let mut mismatched = TrustedPartyBeaverGenerator::new(4, 2, 0, None).unwrap();
+    assert!(protocol.equal(&shares[0].0, &shares[0].1, &mut mismatched).is_err());
+}
+
+#[test]
+fn test_sealed_bid_auction_reveals_only_winner() {
+    use mpc_api::protocols::auction::*;
+    use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+
+    let open = |shares: &[mpc_api::secret_sharing::Share]| ShamirSecretSharing::reconstruct(&shares[1..], 2).unwrap();
+    let shared = |bids: &[u64]| -> Vec<Vec<mpc_api::secret_sharing::Share>> {
+        bids.iter().map(|bid| ShamirSecretSharing::share(bid, 2, 3).unwrap()).collect()
+    };
+    let auction = SealedBidAuction::new(3, 2, 12).unwrap();
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+
+    for bids in [vec![7], vec![3, 9], vec![1000, 1500, 1200], vec![5, 4095, 0, 17, 4095, 8, 300]] {
+        let expected_max = *bids.iter().max().unwrap();
+        let expected_winner = bids.iter().position(|&bid| bid == expected_max).unwrap();
+
+        let outcome = auction.run(&shared(&bids), true, &mut generator).unwrap().result;
+        assert_eq!(outcome, AuctionOutcome { winner: expected_winner, price: Some(expected_max) });
+
+        let hidden = auction.run(&shared(&bids), false, &mut generator).unwrap().result;
+        assert_eq!(hidden, AuctionOutcome { winner: expected_winner, price: None });
+
+        let argmax = auction.argmax(&shared(&bids), &mut generator).unwrap().result;
+        assert_eq!(open(&argmax.max), expected_max);
+        assert_eq!(open(&argmax.index), expected_winner as u64);
+    }
+
+    assert!(auction.run(&[], true, &mut generator).is_err());
+    let mut mismatched = TrustedPartyBeaverGenerator::new(4, 2, 0, None).unwrap();
+    assert!(auction.run(&shared(&[1, 2]), true, &mut mismatched).is_err());
+    assert!(SealedBidAuction::new(3, 2, 0).is_err());
+}
+
+// ===== Generator Setup Tests =====
+
+#[test]
+fn test_generator_setup_agreement_and_verification() {
+    use mpc_api::commitment::PedersenParams;
+    use mpc_api::elliptic_curve::{EllipticCurve, SimpleEC};
+    use mpc_api::protocols::generator_setup::*;
+
+    let context = b"test/generator-setup";
+    let (committed, commitments): (Vec<_>, Vec<_>) = (0..3)
+        .map(|id| GeneratorSetupParty::new(id, 3, context).unwrap().commit().unwrap())
+        .unzip();
+    let (opened, openings): (Vec<_>, Vec<_>) = committed.into_iter()
+        .map(|party| party.open(commitments.clone()).unwrap())
+        .unzip();
+    let transcripts: Vec<_> = opened.into_iter()
+        .map(|party| party.finish(openings.clone()).unwrap())
+        .collect();
+    assert!(transcripts.iter().all(|t| t.generators == transcripts[0].generators));
+
+    let generators = transcripts[0].verify().unwrap();
+    assert!(SimpleEC::is_on_curve(&generators.pedersen_h.value));
+    assert!(is_primitive_root(generators.elgamal_generator.value));
+    let params = generators.pedersen_params().unwrap();
+    assert_ne!(params.g, params.h);
+
+    // 篡改打开信息、种子或生成元都会被发现
+    let mut tampered = transcripts[0].clone();
+    tampered.openings.swap(0, 1);
+    assert!(tampered.verify().is_err());
+
+    let mut tampered = transcripts[0].clone();
+    tampered.generators.seed[0] ^= 1;
+    assert!(tampered.verify().is_err());
+
+    let mut tampered = transcripts[0].clone();
+    tampered.generators.elgamal_generator.counter += 1;
+    assert!(tampered.verify().is_err());
+
+    // 默认 Pedersen 参数是确定的，不依赖本地随机数
+    assert_eq!(PedersenParams::new().unwrap(), PedersenParams::new().unwrap());
+    assert!(PedersenParams::from_generators(params.g.clone(), params.g.clone()).is_err());
+}
+
+#[test]
+fn test_generator_setup_rejects_bad_rounds() {
+    use mpc_api::protocols::generator_setup::*;
+
+    assert!(GeneratorSetupParty::new(2, 2, b"ctx").is_err());
+
+    let (alice, alice_commitment) = GeneratorSetupParty::new(0, 2, b"ctx").unwrap().commit().unwrap();
+    let (bob, bob_commitment) = GeneratorSetupParty::new(1, 2, b"ctx").unwrap().commit().unwrap();
+    assert!(alice.clone().open(vec![alice_commitment.clone()]).is_err());
+    assert!(alice.clone().open(vec![bob_commitment.clone(), bob_commitment.clone()]).is_err());
+
+    let commitments = vec![alice_commitment, bob_commitment];
+    let (alice, alice_opening) = alice.open(commitments.clone()).unwrap();
+    let (_, bob_opening) = bob.open(commitments).unwrap();
+    // 打开信息顺序错误时与承诺不一致
+    assert!(alice.clone().finish(vec![bob_opening.clone(), alice_opening.clone()]).is_err());
+    assert!(alice.finish(vec![alice_opening, bob_opening]).is_ok());
+
+    assert!(is_primitive_root(7));
+    assert!(!is_primitive_root(1));
+    assert!(!is_primitive_root(FIELD_PRIME - 1));
+}
+
+#[test]
+fn test_arithmetic_circuit_matches_plain_evaluation() {
+    use mpc_api::protocols::mpc_circuit::*;
+    use mpc_api::secret_sharing::{diff_program, open_results, SecretSharing, ShamirSecretSharing};
+
+    // (a·b + c)·(a - 4) + 2·c，以及独立输出 b·c
+    let mut circuit = ArithmeticCircuit::new(3);
+    let (a, b, c) = (circuit.input(0), circuit.input(1), circuit.input(2));
+    let ab = circuit.mul(a, b);
+    let bc = circuit.mul(b, c);
+    let sum = circuit.add(ab, c);
+    let shifted = circuit.add_constant(a, FIELD_PRIME - 4);
+    let product = circuit.mul(sum, shifted);
+    let doubled = circuit.scalar(c, 2);
+    let result = circuit.add(product, doubled);
+    circuit.output(result);
+    circuit.output(bc);
+    assert_eq!(circuit.multiplication_count(), 3);
+    assert_eq!(circuit.multiplicative_depth(), 2);
+
+    let values = [9u64, 11, 13];
+    let inputs: Vec<_> = values.iter().map(|v| ShamirSecretSharing::share(v, 2, 3).unwrap()).collect();
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    let evaluator = CircuitEvaluator::new(3, 2).unwrap();
+
+    let output = evaluator.evaluate(&circuit, &inputs, &mut generator).unwrap();
+    let opened = open_results(&output.result, 2).unwrap();
+    assert_eq!(opened, circuit.evaluate_plain(&values).unwrap());
+    assert_eq!(opened, vec![(9 * 11 + 13) * 5 + 26, 11 * 13]);
+    // 同一深度的乘法共用一轮
+    assert_eq!(output.stats.rounds, 2);
+    assert_eq!(output.stats.preprocessing_consumed, 3);
+
+    let trace = evaluator.evaluate_trace(&circuit, &inputs, &mut generator).unwrap().result;
+    assert_eq!(trace.len(), circuit.gate_count());
+    let results = open_results(&trace, 2).unwrap();
+    assert!(diff_program(circuit.program(), &values, &results).unwrap().is_consistent());
+}
+
+#[test]
+fn test_arithmetic_circuit_rejects_bad_inputs() {
+    use mpc_api::protocols::mpc_circuit::*;
+    use mpc_api::secret_sharing::{ArithmeticInstruction, ArithmeticProgram, SecretSharing, ShamirSecretSharing};
+
+    assert!(CircuitEvaluator::new(3, 0).is_err());
+    assert!(CircuitEvaluator::new(3, 4).is_err());
+
+    let mut program = ArithmeticProgram::new(1);
+    program.push(ArithmeticInstruction::Mul(0, 1));
+    assert!(ArithmeticCircuit::from_program(program).is_err());
+    let mut program = ArithmeticProgram::new(1);
+    program.push(ArithmeticInstruction::Const(1));
+    program.output(5);
+    assert!(ArithmeticCircuit::from_program(program).is_err());
+
+    let mut circuit = ArithmeticCircuit::new(2);
+    let product = circuit.mul(0, 1);
+    circuit.output(product);
+    let evaluator = CircuitEvaluator::new(3, 2).unwrap();
+    let share = ShamirSecretSharing::share(&5, 2, 3).unwrap();
+
+    // 输入数量、分享数量或生成器参数不匹配
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    assert!(evaluator.evaluate(&circuit, &[share.clone()], &mut generator).is_err());
+    assert!(evaluator.evaluate(&circuit, &[share.clone(), share[..2].to_vec()], &mut generator).is_err());
+    let mut mismatched = TrustedPartyBeaverGenerator::new(4, 2, 0, None).unwrap();
+    assert!(evaluator.evaluate(&circuit, &[share.clone(), share], &mut mismatched).is_err());
+}
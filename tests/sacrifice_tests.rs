@@ -0,0 +1,68 @@
+use mpc_api::beaver_triples::sacrifice::*;
+use mpc_api::beaver_triples::{BeaverTripleGenerator, OLEBeaverGenerator, TrustedPartyBeaverGenerator};
+use mpc_api::secret_sharing::{field_add, field_mul, ShamirSecretSharing, SecretSharing};
+
+#[test]
+fn test_sacrifice_accepts_honest_triples() {
+    let mut generator = OLEBeaverGenerator::new(3, 2, 0).unwrap();
+    let output = TripleSacrifice::new(2)
+        .generate_verified(&mut generator, 10, [3u8; 32])
+        .unwrap();
+
+    let outcome = output.result;
+    assert!(outcome.is_clean());
+    assert_eq!(outcome.safe.len(), 10);
+    assert_eq!(outcome.safe_indices, (0..20).step_by(2).collect::<Vec<_>>());
+    assert_eq!(output.stats.preprocessing_consumed, 10);
+    assert!(output.stats.bytes_sent > 0);
+    for triple in &outcome.safe {
+        let open = |pick: fn(&mpc_api::beaver_triples::BeaverTriple) -> mpc_api::secret_sharing::Share| {
+            let shares: Vec<_> = (1..=2).map(|id| pick(&triple.shares[&id])).collect();
+            ShamirSecretSharing::reconstruct(&shares, 2).unwrap()
+        };
+        assert_eq!(open(|t| t.c.clone()), field_mul(open(|t| t.a.clone()), open(|t| t.b.clone())));
+    }
+}
+
+#[test]
+fn test_sacrifice_rejects_bad_pairs_only() {
+    let sacrifice = TripleSacrifice::new(2);
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    let honest = generator.generate_batch(12).unwrap();
+
+    // 被检查的和被牺牲的三元组出错都会被发现，其余的对不受影响
+    for seed in 0..8u8 {
+        let mut triples = honest.clone();
+        for share in triples[2].shares.values_mut() {
+            share.c.y = field_add(share.c.y, 1);
+        }
+        for share in triples[7].shares.values_mut() {
+            share.c.y = field_add(share.c.y, 5);
+        }
+        let outcome = sacrifice.verify(triples, [seed; 32]).unwrap().result;
+        assert!(!outcome.is_clean());
+        assert_eq!(outcome.rejected, vec![2, 3, 6, 7]);
+        assert_eq!(outcome.safe_indices, vec![0, 4, 8, 10]);
+    }
+
+    // 两个三元组以相同的偏差出错也无法抵消，因为挑战 t 在生成之后才确定
+    let mut triples = honest.clone();
+    for index in [0, 1] {
+        for share in triples[index].shares.values_mut() {
+            share.c.y = field_add(share.c.y, 1);
+        }
+    }
+    assert_eq!(sacrifice.verify(triples, [0u8; 32]).unwrap().result.rejected, vec![0, 1]);
+
+    // 分享不在同一多项式上
+    let mut triples = honest.clone();
+    triples[4].shares.get_mut(&3).unwrap().a.y ^= 1;
+    assert_eq!(sacrifice.verify(triples, [0u8; 32]).unwrap().result.rejected, vec![4, 5]);
+
+    assert!(sacrifice.verify(honest[1..].to_vec(), [0u8; 32]).is_err());
+
+    // 公开 ρ/σ、承诺检查值、打开检查值
+    let stats = sacrifice.verify(honest, [0u8; 32]).unwrap().stats;
+    assert_eq!(stats.rounds, 3);
+    assert_eq!(stats.preprocessing_consumed, 6);
+}
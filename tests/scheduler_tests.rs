@@ -0,0 +1,246 @@
+use mpc_api::beaver_triples::TripleGeneratorKind;
+use mpc_api::scheduler::*;
+use mpc_api::MpcError;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn custom(name: &str) -> MpcTask {
+    MpcTask::Custom { name: name.to_string(), payload: Vec::new() }
+}
+
+fn temporary_scheduler() -> Arc<JobScheduler> {
+    Arc::new(JobScheduler::new(JobStore::temporary().unwrap()).unwrap())
+}
+
+// ===== Queue Tests =====
+
+#[test]
+fn test_priority_and_triple_dependencies() {
+    let scheduler = temporary_scheduler();
+    let low = scheduler.submit(JobRequest::new(custom("record")).with_priority(JobPriority::Low)).unwrap();
+    let needs_triples = scheduler.submit(
+        JobRequest::new(custom("record")).with_priority(JobPriority::Critical).requires_triples(500),
+    ).unwrap();
+    let high = scheduler.submit(JobRequest::new(custom("record")).with_priority(JobPriority::High)).unwrap();
+
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&order);
+    let pool = WorkerPool::new(Arc::clone(&scheduler)).with_handler(
+        "record",
+        Arc::new(move |job: &Job| {
+            recorded.lock().unwrap().push(job.id);
+            Ok(Vec::new())
+        }),
+    );
+
+    // 三元组不足时跳过该作业
+    assert_eq!(pool.run_until_idle().unwrap(), 2);
+    assert_eq!(*order.lock().unwrap(), vec![high, low]);
+    assert_eq!(scheduler.job(needs_triples).unwrap().unwrap().state, JobState::Pending);
+
+    scheduler.add_triples(499).unwrap();
+    assert_eq!(pool.run_until_idle().unwrap(), 0);
+    scheduler.add_triples(1).unwrap();
+    assert_eq!(pool.run_until_idle().unwrap(), 1);
+    assert_eq!(scheduler.available_triples().unwrap(), 0);
+    assert_eq!(scheduler.job(needs_triples).unwrap().unwrap().state, JobState::Succeeded);
+}
+
+#[test]
+fn test_triple_generation_feeds_pool() {
+    let scheduler = temporary_scheduler();
+    let generate = scheduler.submit(JobRequest::new(MpcTask::GenerateTriples {
+        generator: TripleGeneratorKind::TrustedParty,
+        count: 6,
+        party_count: 3,
+        threshold: 2,
+    })).unwrap();
+    let consume = scheduler.submit(JobRequest::new(custom("online")).depends_on(generate).requires_triples(4)).unwrap();
+
+    let generated = mpc_api::utils::MetricsRegistry::global()
+        .counter_with_labels("mpc_triples_generated_total", "", &[("generator", "trusted_party")])
+        .unwrap();
+    let generated_before = generated.get();
+
+    let pool = WorkerPool::new(Arc::clone(&scheduler))
+        .with_handler("online", Arc::new(|_job: &Job| Ok(b"ok".to_vec())));
+    assert_eq!(pool.run_until_idle().unwrap(), 2);
+    // 其他测试可能同时生成三元组，全局计数器至少增加本作业的数量
+    assert!(generated.get() >= generated_before + 6);
+
+    let triples: Vec<mpc_api::beaver_triples::CompleteBeaverTriple> =
+        bincode::deserialize(scheduler.job(generate).unwrap().unwrap().output.as_ref().unwrap()).unwrap();
+    assert_eq!(triples.len(), 6);
+    assert_eq!(scheduler.job(consume).unwrap().unwrap().output, Some(b"ok".to_vec()));
+    assert_eq!(scheduler.available_triples().unwrap(), 2);
+
+    assert!(scheduler.submit(JobRequest::new(custom("online")).depends_on(JobId(u64::MAX))).is_err());
+}
+
+#[test]
+fn test_retry_policy_and_failed_dependencies() {
+    let policy = RetryPolicy::new(4, Duration::from_millis(10)).with_multiplier(3);
+    assert_eq!(policy.backoff(1), Duration::from_millis(10));
+    assert_eq!(policy.backoff(3), Duration::from_millis(90));
+
+    let scheduler = temporary_scheduler();
+    scheduler.add_triples(5).unwrap();
+    let flaky = scheduler.submit(
+        JobRequest::new(custom("flaky")).requires_triples(5).with_retry(RetryPolicy::new(2, Duration::ZERO)),
+    ).unwrap();
+    let doomed = scheduler.submit(JobRequest::new(custom("never")).with_retry(RetryPolicy::none())).unwrap();
+    let dependent = scheduler.submit(JobRequest::new(custom("flaky")).depends_on(doomed)).unwrap();
+    let cancelled = scheduler.submit(JobRequest::new(custom("flaky"))).unwrap();
+    assert!(scheduler.cancel(cancelled).unwrap());
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    let pool = WorkerPool::new(Arc::clone(&scheduler)).with_handler(
+        "flaky",
+        Arc::new(move |_job: &Job| {
+            if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(MpcError::NetworkError("peer unavailable".to_string()))
+            } else {
+                Ok(Vec::new())
+            }
+        }),
+    );
+    pool.run_until_idle().unwrap();
+
+    let flaky = scheduler.job(flaky).unwrap().unwrap();
+    assert_eq!(flaky.state, JobState::Succeeded);
+    assert_eq!(flaky.attempts, 2);
+    assert_eq!(scheduler.available_triples().unwrap(), 0);
+
+    // 没有注册处理器的作业失败，依赖它的作业随之失败
+    assert_eq!(scheduler.job(doomed).unwrap().unwrap().state, JobState::Failed);
+    assert_eq!(scheduler.job(dependent).unwrap().unwrap().state, JobState::Failed);
+    assert_eq!(scheduler.job(cancelled).unwrap().unwrap().state, JobState::Cancelled);
+    assert!(!scheduler.cancel(cancelled).unwrap());
+}
+
+// ===== Persistence Tests =====
+
+#[test]
+fn test_jobs_survive_restart() {
+    let path = std::env::temp_dir().join(format!("mpc_scheduler_{}_{}", std::process::id(), rand::random::<u64>()));
+
+    let (pending, interrupted) = {
+        let scheduler = JobScheduler::open(&path).unwrap();
+        scheduler.add_triples(3).unwrap();
+        let interrupted = scheduler.submit(JobRequest::new(custom("work")).requires_triples(3)).unwrap();
+        let pending = scheduler.submit(JobRequest::new(custom("work"))).unwrap();
+        // 领取后进程"崩溃"
+        assert_eq!(scheduler.claim_next().unwrap().unwrap().id, interrupted);
+        assert_eq!(scheduler.available_triples().unwrap(), 0);
+        scheduler.flush().unwrap();
+        (pending, interrupted)
+    };
+
+    let scheduler = Arc::new(JobScheduler::open(&path).unwrap());
+    assert_eq!(scheduler.job(interrupted).unwrap().unwrap().state, JobState::Pending);
+    assert_eq!(scheduler.available_triples().unwrap(), 3);
+
+    let pool = WorkerPool::new(Arc::clone(&scheduler)).with_handler("work", Arc::new(|_job: &Job| Ok(Vec::new())));
+    assert_eq!(pool.run_until_idle().unwrap(), 2);
+    assert_eq!(scheduler.job(pending).unwrap().unwrap().state, JobState::Succeeded);
+    assert!(scheduler.submit(JobRequest::new(custom("work"))).unwrap() > pending);
+
+    drop(pool);
+    drop(scheduler);
+    let _ = std::fs::remove_dir_all(&path);
+}
+
+// ===== Worker Pool and HTTP Tests =====
+
+#[tokio::test]
+#[cfg(feature = "http")]
+async fn test_background_workers_and_http_api() {
+    use mpc_api::network::http::{HttpMethod, HttpRequest, JobsHandler, RouteHandler};
+    use std::collections::HashMap;
+
+    let scheduler = temporary_scheduler();
+    let handler = JobsHandler::new(Arc::clone(&scheduler));
+    let request = |method: HttpMethod, id: Option<u64>, body: Vec<u8>| HttpRequest {
+        method,
+        path: "/api/v1/jobs".to_string(),
+        query_params: id.map(|id| HashMap::from([("id".to_string(), id.to_string())])).unwrap_or_default(),
+        headers: HashMap::new(),
+        body,
+        client_ip: "127.0.0.1".to_string(),
+        timestamp: std::time::SystemTime::now(),
+        request_id: "test".to_string(),
+    };
+
+    let body = serde_json::to_vec(&JobRequest::new(custom("work"))).unwrap();
+    let response = handler.handle_request(&request(HttpMethod::POST, None, body)).await.unwrap();
+    assert_eq!(response.status_code, 200);
+    let job_id = serde_json::from_slice::<serde_json::Value>(&response.body).unwrap()["job_id"].as_u64().unwrap();
+
+    let bad = handler.handle_request(&request(HttpMethod::POST, None, b"{}".to_vec())).await.unwrap();
+    assert_eq!(bad.status_code, 400);
+
+    let workers = WorkerPool::new(Arc::clone(&scheduler))
+        .with_handler("work", Arc::new(|_job: &Job| Ok(Vec::new())))
+        .with_workers(2)
+        .with_poll_interval(Duration::from_millis(5))
+        .spawn();
+    assert_eq!(workers.workers(), 2);
+    for _ in 0..200 {
+        if scheduler.job(JobId(job_id)).unwrap().unwrap().state.is_terminal() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    workers.shutdown().await;
+
+    let response = handler.handle_request(&request(HttpMethod::GET, Some(job_id), Vec::new())).await.unwrap();
+    let job: Job = serde_json::from_slice(&response.body).unwrap();
+    assert_eq!(job.state, JobState::Succeeded);
+
+    let response = handler.handle_request(&request(HttpMethod::GET, None, Vec::new())).await.unwrap();
+    let listing: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+    assert_eq!(listing["jobs"].as_array().unwrap().len(), 1);
+    let missing = handler.handle_request(&request(HttpMethod::GET, Some(u64::MAX), Vec::new())).await.unwrap();
+    assert_eq!(missing.status_code, 404);
+}
+
+#[test]
+fn test_worker_pool_cancels_running_job() {
+    use mpc_api::utils::concurrency::CancellationToken;
+
+    struct WaitForCancellation;
+    impl JobHandler for WaitForCancellation {
+        fn execute(&self, _job: &Job) -> mpc_api::Result<Vec<u8>> {
+            Ok(Vec::new())
+        }
+
+        fn execute_with_cancellation(&self, _job: &Job, token: &CancellationToken) -> mpc_api::Result<Vec<u8>> {
+            token.wait_timeout(Duration::from_secs(30));
+            token.check()?;
+            Ok(Vec::new())
+        }
+    }
+
+    let scheduler = temporary_scheduler();
+    let id = scheduler.submit(JobRequest::new(custom("wait"))).unwrap();
+    let root = CancellationToken::new();
+    let pool = WorkerPool::new(Arc::clone(&scheduler))
+        .with_handler("wait", Arc::new(WaitForCancellation))
+        .with_cancellation(&root);
+    let worker = std::thread::spawn(move || (pool.run_once(), pool));
+
+    std::thread::sleep(Duration::from_millis(20));
+    root.cancel("node shutting down");
+    let (outcome, pool) = worker.join().unwrap();
+    let (job_id, state) = outcome.unwrap().unwrap();
+    assert_eq!(job_id, id);
+    assert_ne!(state, JobState::Succeeded);
+    let last_error = scheduler.job(id).unwrap().unwrap().last_error.unwrap();
+    assert!(last_error.contains("node shutting down"));
+
+    // 令牌取消后不再领取新作业
+    scheduler.submit(JobRequest::new(custom("wait"))).unwrap();
+    assert!(pool.run_once().unwrap().is_none());
+}
@@ -1,4 +1,5 @@
 use mpc_api::secret_sharing::{ShamirSecretSharing, SecretSharing, AdditiveSecretSharing, AdditiveSecretSharingScheme, field_add, field_mul};
+use mpc_api::secret_sharing::{field_inner_product, field_pow, polynomial_multiply, root_of_unity, NttDomain, FIELD_PRIME, TWO_ADICITY};
 
 #[test]
 fn test_shamir_secret_sharing() {
@@ -88,4 +89,882 @@ fn test_additive_operations() {
     
     let scaled_result = scheme.reconstruct_additive(&scaled_shares).unwrap();
     assert_eq!(scaled_result, field_mul(secret1, scalar));
-}
\ No newline at end of file
+}
+#[test]
+fn test_shamir_beaver_mul() {
+    use mpc_api::secret_sharing::{MultiplicationSecretSharing, field_sub};
+
+    let (x, y) = (7u64, 9u64);
+    let x_shares = ShamirSecretSharing::share(&x, 2, 3).unwrap();
+    let y_shares = ShamirSecretSharing::share(&y, 2, 3).unwrap();
+    let (a_shares, b_shares, c_shares) = ShamirSecretSharing::generate_beaver_triple(2, 3).unwrap();
+
+    let a = ShamirSecretSharing::reconstruct(&a_shares[..2], 2).unwrap();
+    let b = ShamirSecretSharing::reconstruct(&b_shares[..2], 2).unwrap();
+    let d = field_sub(x, a);
+    let e = field_sub(y, b);
+
+    let z_shares: Vec<_> = (0..3)
+        .map(|i| ShamirSecretSharing::beaver_mul(
+            &x_shares[i], &y_shares[i], &a_shares[i], &b_shares[i], &c_shares[i], &d, &e,
+        ).unwrap())
+        .collect();
+
+    assert_eq!(ShamirSecretSharing::reconstruct(&z_shares[1..], 2).unwrap(), field_mul(x, y));
+}
+
+#[test]
+fn test_reveal_gate_policies() {
+    use mpc_api::elliptic_curve::Ed25519;
+    use mpc_api::secret_sharing::{RevealApproval, RevealGate, RevealPolicy, RevealRequest};
+
+    let shares = ShamirSecretSharing::share(&1234, 2, 4).unwrap();
+    let policy = RevealPolicy::All(vec![
+        RevealPolicy::RequiresApprovals { approvers: vec![1, 2, 3], required: 2 },
+        RevealPolicy::OnlyTo(vec![4]),
+    ]);
+    let keys: Vec<_> = (0..4).map(|_| Ed25519::generate_keypair()).collect();
+    let gate = (1..4).fold(RevealGate::new(policy, 2), |gate, party| gate.with_approver_key(party, keys[party].1));
+    let approval = |party: usize, value_id: &str, requester: usize| {
+        RevealApproval::sign(party, &keys[party].0, value_id, requester)
+    };
+
+    let request = RevealRequest::new("bid", 4).with_approval(approval(1, "bid", 4));
+    assert!(gate.open(&request, &shares[..2]).is_err());
+
+    // 冒充参与方 3 的签名、签给别的值的批准都不计数
+    let forged = RevealApproval::sign(3, &keys[0].0, "bid", 4);
+    assert!(gate.open(&request.clone().with_approval(forged), &shares[..2]).is_err());
+    assert!(gate.open(&request.clone().with_approval(approval(3, "ask", 4)), &shares[..2]).is_err());
+
+    let request = request.with_approval(approval(3, "bid", 4));
+    assert_eq!(request.approvals().len(), 2);
+    assert_eq!(gate.open(&request, &shares[..2]).unwrap(), 1234);
+
+    let wrong_recipient = RevealRequest::new("bid", 2)
+        .with_approval(approval(1, "bid", 2))
+        .with_approval(approval(3, "bid", 2));
+    assert!(gate.open(&wrong_recipient, &shares[..2]).is_err());
+
+    // 每一次请求都有审计记录
+    let events = gate.audit_logger().get_events();
+    assert_eq!(events.len(), 5);
+    assert_eq!(events.iter().filter(|e| e.is_handled).count(), 1);
+}
+
+#[test]
+fn test_reveal_gate_release_share_and_mac_status() {
+    use mpc_api::secret_sharing::{RevealGate, RevealPolicy, RevealRequest};
+
+    let shares = ShamirSecretSharing::share(&55, 2, 3).unwrap();
+
+    // 持有方只在策略满足时交出分享
+    let gate = RevealGate::new(RevealPolicy::OnlyTo(vec![1]), 2).with_allowed_holders(&[1, 2, 3]);
+    let request = RevealRequest::new("v", 1);
+    let released: Vec<_> = shares[..2].iter().map(|share| gate.release_share(&request, share).unwrap()).collect();
+    assert_eq!(gate.open(&request, &released).unwrap(), 55);
+    assert!(gate.release_share(&RevealRequest::new("v", 2), &shares[0]).is_err());
+
+    // MAC 检查状态不能由请求方声明，反序列化也不能伪造
+    let mac_gate = RevealGate::new(RevealPolicy::RequiresMacCheck, 2);
+    assert!(!request.mac_check_passed());
+    let decoded: RevealRequest = bincode::deserialize(&bincode::serialize(&request).unwrap()).unwrap();
+    assert!(!decoded.mac_check_passed());
+    assert!(mac_gate.release_share(&decoded, &shares[0]).is_err());
+}
+
+#[test]
+fn test_reveal_gate_quorum_checks() {
+    use mpc_api::secret_sharing::{RevealGate, RevealPolicy, RevealRequest, Share};
+
+    let shares = ShamirSecretSharing::share(&99, 3, 5).unwrap();
+    let gate = RevealGate::new(RevealPolicy::Public, 3).with_allowed_holders(&[1, 2, 3, 4, 5]);
+    let request = RevealRequest::new("v", 0);
+
+    // 低于门限
+    assert!(gate.open(&request, &shares[..2]).is_err());
+    // 重复的持有方
+    let duplicated = vec![shares[0].clone(), shares[0].clone(), shares[1].clone()];
+    assert!(gate.open(&request, &duplicated).is_err());
+    // 非成员持有方
+    let outsider = vec![shares[0].clone(), shares[1].clone(), Share::new(9, 1)];
+    assert!(gate.open(&request, &outsider).is_err());
+
+    assert_eq!(gate.open(&request, &shares[..3]).unwrap(), 99);
+}
+
+#[test]
+fn test_chunked_sharing_with_missing_parties() {
+    use mpc_api::secret_sharing::{ChunkedSecretSharing, ChunkedSharingParams};
+
+    let params = ChunkedSharingParams::new(5, 3, 3).unwrap().with_chunk_size(256);
+    let scheme = ChunkedSecretSharing::new(params).unwrap();
+    let data: Vec<u8> = (0..2000u32).map(|i| (i * 31 % 251) as u8).collect();
+
+    let (manifest, pieces) = scheme.share("blob", &data).unwrap();
+    assert_eq!(manifest.chunk_count(), 8);
+    assert_eq!(pieces.len(), 5);
+
+    // 参与方 0 和 3 离线
+    let available: Vec<_> = [1, 2, 4].iter().flat_map(|&p| pieces[p].clone()).collect();
+    assert_eq!(scheme.reconstruct(&manifest, &available).unwrap(), data);
+
+    // 少于密钥门限无法恢复
+    let too_few: Vec<_> = [1, 2].iter().flat_map(|&p| pieces[p].clone()).collect();
+    assert!(scheme.reconstruct(&manifest, &too_few).is_err());
+}
+
+#[test]
+#[cfg(feature = "network")]
+fn test_chunked_collection_resume_and_tampering() {
+    use mpc_api::secret_sharing::{
+        ChunkPiece, ChunkedSecretSharing, ChunkedSharingParams, CollectionSession, ResumeRequest,
+    };
+
+    let params = ChunkedSharingParams::new(4, 2, 2).unwrap().with_chunk_size(100);
+    let scheme = ChunkedSecretSharing::new(params).unwrap();
+    let data = vec![0xabu8; 450];
+    let (manifest, pieces) = scheme.share("stream", &data).unwrap();
+
+    // 第一次传输只收到前两块
+    let mut session = CollectionSession::new(manifest.clone()).unwrap();
+    for party in 0..2 {
+        for piece in pieces[party].iter().filter(|p| p.chunk_index < 2) {
+            let message = piece.to_network_message().unwrap();
+            assert!(session.accept(ChunkPiece::from_network_message(&message).unwrap()).unwrap());
+        }
+    }
+    assert!(!session.is_complete());
+
+    // 会话可以持久化后恢复
+    let saved = bincode::serialize(&session).unwrap();
+    let mut session: CollectionSession = bincode::deserialize(&saved).unwrap();
+
+    let request = session.resume_request();
+    assert_eq!(request.missing_chunks, vec![2, 3, 4]);
+    let message = request.to_network_message().unwrap();
+    let request = ResumeRequest::from_network_message(&message).unwrap();
+
+    for party in 0..2 {
+        for piece in request.select(&pieces[party]) {
+            session.accept(piece.clone()).unwrap();
+        }
+    }
+    // 重复分片被忽略
+    assert!(!session.accept(pieces[0][0].clone()).unwrap());
+    assert!(session.is_complete());
+    assert_eq!(session.finish().unwrap(), data);
+
+    // 篡改的分片无法通过 Merkle 验证
+    let mut tampered = pieces[2][0].clone();
+    tampered.fragment[0] ^= 1;
+    assert!(session.accept(tampered).is_err());
+
+    // 根的数量多于块数的清单被拒绝，不会在接收分片时越界
+    let mut extra_root = manifest.clone();
+    extra_root.chunk_roots.push(manifest.chunk_roots[0]);
+    assert!(CollectionSession::new(extra_root.clone()).is_err());
+
+    // 反序列化绕过 `new` 的会话也只返回错误
+    let received = vec![std::collections::BTreeMap::<usize, ChunkPiece>::new(); extra_root.chunk_count()];
+    let mut forged: CollectionSession =
+        bincode::deserialize(&bincode::serialize(&(&extra_root, &received)).unwrap()).unwrap();
+    // 额外的根与第 0 块相同，分片能通过 Merkle 验证，但块索引超出块数
+    let mut stray = pieces[0][0].clone();
+    stray.chunk_index = manifest.chunk_count();
+    assert!(forged.accept(stray).is_err());
+    assert!(forged.finish().is_err());
+
+    // 声明的总长度与块长度不符的清单被拒绝，收集方不会按声明的长度分配内存
+    let mut oversized = manifest.clone();
+    oversized.total_len = u64::MAX;
+    assert!(CollectionSession::new(oversized).is_err());
+    let mut long_chunk = manifest;
+    long_chunk.chunk_lens[0] = 1 << 40;
+    long_chunk.total_len += (1 << 40) - 100;
+    assert!(CollectionSession::new(long_chunk).is_err());
+}
+
+#[test]
+fn test_diagnose_shares_blames_bad_party() {
+    use mpc_api::secret_sharing::{SuspectReason, field_add};
+
+    let scheme = ShamirSecretSharing::new();
+    let mut shares = ShamirSecretSharing::share(&777, 3, 7).unwrap();
+    let clean = scheme.diagnose_shares(&shares, 3);
+    assert!(clean.is_consistent());
+    assert_eq!(clean.secret, Some(777));
+
+    // 两方发送了错误数据
+    shares[1].y = field_add(shares[1].y, 5);
+    shares[5].y = field_add(shares[5].y, 9);
+    assert!(!scheme.verify_shares(&shares, 3));
+
+    let report = scheme.diagnose_shares(&shares, 3);
+    assert!(report.unique);
+    assert_eq!(report.secret, Some(777));
+    let mut blamed = report.suspect_indices();
+    blamed.sort();
+    assert_eq!(blamed, vec![shares[1].x, shares[5].x]);
+    assert!(report.suspects.iter().all(|s| s.reason == SuspectReason::InconsistentWithMajority));
+}
+
+#[test]
+fn test_diagnose_shares_ambiguous_and_malformed() {
+    use mpc_api::secret_sharing::{Share, SuspectReason, field_add};
+
+    let scheme = ShamirSecretSharing::new();
+    let mut shares = ShamirSecretSharing::share(&5, 2, 3).unwrap();
+    shares[0].y = field_add(shares[0].y, 1);
+
+    // 只多一个份额时可以检测到错误，但无法定位
+    let report = scheme.diagnose_shares(&shares, 2);
+    assert!(!report.unique);
+    assert!(!report.is_consistent());
+    assert_eq!(report.secret, None);
+
+    let mut malformed = ShamirSecretSharing::share(&5, 2, 4).unwrap();
+    malformed.push(Share::new(0, 5));
+    malformed.push(Share::new(malformed[0].x, field_add(malformed[0].y, 1)));
+    let report = scheme.diagnose_shares(&malformed, 2);
+    assert_eq!(report.secret, Some(5));
+    assert!(report.suspects.contains(&mpc_api::secret_sharing::SuspectShare { x: 0, reason: SuspectReason::ZeroIndex }));
+    assert!(report.suspects.iter().any(|s| s.reason == SuspectReason::ConflictingDuplicate));
+}
+
+#[test]
+fn test_threshold_conversion_raise_and_lower() {
+    use mpc_api::secret_sharing::{convert_threshold, ThresholdConverter, ConversionKind};
+
+    let shares = ShamirSecretSharing::share(&1234, 2, 5).unwrap();
+
+    let raised = convert_threshold(&shares, 2, 4).unwrap();
+    assert_eq!(ShamirSecretSharing::reconstruct(&raised[1..5], 4).unwrap(), 1234);
+    // 新份额与原份额不同，且两个份额不再足以恢复秘密
+    assert_ne!(raised[0].y, shares[0].y);
+    assert_ne!(ShamirSecretSharing::reconstruct(&raised[..2], 2).unwrap(), 1234);
+
+    let lowered = convert_threshold(&raised, 4, 2).unwrap();
+    assert_eq!(ShamirSecretSharing::reconstruct(&lowered[3..5], 2).unwrap(), 1234);
+
+    let xs: Vec<u64> = shares.iter().map(|s| s.x).collect();
+    assert_eq!(ThresholdConverter::new(1, &xs, 2, 4).unwrap().kind(), ConversionKind::ZeroSharing);
+    assert_eq!(ThresholdConverter::new(1, &xs, 4, 2).unwrap().kind(), ConversionKind::Resharing);
+    assert!(ThresholdConverter::new(1, &xs, 2, 6).is_err());
+    assert!(ThresholdConverter::new(9, &xs, 2, 3).is_err());
+}
+
+#[test]
+fn test_threshold_conversion_rejects_bad_messages() {
+    use mpc_api::secret_sharing::ThresholdConverter;
+    use mpc_api::MpcError;
+
+    let shares = ShamirSecretSharing::share(&7, 2, 3).unwrap();
+    let xs: Vec<u64> = shares.iter().map(|s| s.x).collect();
+    let converters: Vec<_> = xs.iter()
+        .map(|&x| ThresholdConverter::new(x, &xs, 2, 3).unwrap())
+        .collect();
+    let outgoing: Vec<_> = converters.iter().zip(&shares)
+        .map(|(c, s)| c.contribute(s).unwrap())
+        .collect();
+    let inbox: Vec<_> = outgoing.iter().map(|messages| messages[0]).collect();
+
+    assert!(converters[0].combine(&shares[0], &inbox).is_ok());
+    assert!(matches!(converters[0].combine(&shares[0], &inbox[..2]), Err(MpcError::InsufficientShares)));
+
+    let duplicated = vec![inbox[0], inbox[1], inbox[1]];
+    assert!(converters[0].combine(&shares[0], &duplicated).is_err());
+
+    let misaddressed = vec![inbox[0], inbox[1], outgoing[2][1]];
+    assert!(converters[0].combine(&shares[0], &misaddressed).is_err());
+    assert!(converters[0].contribute(&shares[1]).is_err());
+}
+
+#[test]
+fn test_arithmetic_program_cross_check_with_shares() {
+    use mpc_api::beaver_triples::{secure_multiply, BeaverTripleGenerator, TrustedPartyBeaverGenerator};
+    use mpc_api::secret_sharing::{
+        diff_program, open_results, simulate_program, ArithmeticInstruction, ArithmeticProgram, Share,
+    };
+
+    // x·y - 2x + 9
+    let mut program = ArithmeticProgram::new(2);
+    let product = program.push(ArithmeticInstruction::Mul(0, 1));
+    let doubled = program.push(ArithmeticInstruction::MulConst(0, 2));
+    let difference = program.push(ArithmeticInstruction::Sub(product, doubled));
+    let result = program.push(ArithmeticInstruction::AddConst(difference, 9));
+    program.output(result);
+    assert_eq!(program.multiplication_count(), 1);
+
+    let inputs = [6, 7];
+    assert_eq!(simulate_program(&program, &inputs).unwrap().outputs, vec![6 * 7 - 12 + 9]);
+    assert!(simulate_program(&program, &[1]).is_err());
+
+    // 用 Shamir 分享和 Beaver 三元组执行同一个程序
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    let mut registers: Vec<Vec<Share>> = inputs.iter()
+        .map(|x| ShamirSecretSharing::share(x, 2, 3).unwrap())
+        .collect();
+    let affine = |shares: &[Share], scale: u64, offset: u64| -> Vec<Share> {
+        shares.iter().map(|s| Share::new(s.x, field_add(field_mul(s.y, scale), offset))).collect()
+    };
+    for instruction in &program.instructions {
+        let value = match *instruction {
+            ArithmeticInstruction::Mul(a, b) => {
+                let triple = generator.generate_single().unwrap();
+                secure_multiply(&registers[a], &registers[b], &triple, 2).unwrap()
+            }
+            ArithmeticInstruction::MulConst(a, c) => affine(&registers[a], c, 0),
+            ArithmeticInstruction::AddConst(a, c) => affine(&registers[a], 1, c),
+            ArithmeticInstruction::Sub(a, b) => registers[a].iter().zip(&registers[b])
+                .map(|(x, y)| Share::new(x.x, mpc_api::secret_sharing::field_sub(x.y, y.y)))
+                .collect(),
+            _ => unreachable!(),
+        };
+        registers.push(value);
+    }
+
+    let mut results = open_results(&registers[2..], 2).unwrap();
+    assert!(diff_program(&program, &inputs, &results).unwrap().is_consistent());
+
+    // 模拟 MulConst 实现错误
+    results[1] = field_add(results[1], 1);
+    let report = diff_program(&program, &inputs, &results).unwrap();
+    let first = report.first_divergence().unwrap();
+    assert_eq!((first.index, first.register), (1, doubled));
+    assert_eq!(first.instruction, ArithmeticInstruction::MulConst(0, 2));
+    assert_eq!(report.divergences.len(), 1);
+}
+
+#[test]
+fn test_linear_combination_batch() {
+    use mpc_api::secret_sharing::{field_inner_product, linear_combination_batch, Share, FIELD_PRIME};
+
+    // 接近模数的取值覆盖高位累加和各种尾部长度
+    for len in [0usize, 1, 3, 4, 5, 9, 33] {
+        let values: Vec<u64> = (0..len as u64).map(|i| FIELD_PRIME - 1 - i * 7919).collect();
+        let coefficients: Vec<u64> = (0..len as u64).map(|i| FIELD_PRIME - 2 - i).collect();
+        let expected = values.iter().zip(&coefficients).fold(0, |acc, (&v, &c)| field_add(acc, field_mul(v, c)));
+        assert_eq!(field_inner_product(&values, &coefficients), expected);
+    }
+
+    let terms: Vec<Vec<Share>> = [11u64, 22, 33, 44, 55]
+        .iter()
+        .map(|value| ShamirSecretSharing::share(value, 3, 5).unwrap())
+        .collect();
+    let coefficients = [1, 2, 3, 4, FIELD_PRIME - 1];
+    let combined = linear_combination_batch(&terms, &coefficients).unwrap();
+    let expected = field_add(11 + 44 + 99 + 176, FIELD_PRIME - 55);
+    assert_eq!(ShamirSecretSharing::reconstruct(&combined[2..5], 3).unwrap(), expected);
+
+    // 与逐项 scalar_mul / add_shares 一致
+    let naive: Vec<Share> = (0..5)
+        .map(|party| {
+            terms.iter().zip(&coefficients).skip(1).fold(
+                ShamirSecretSharing::scalar_mul(&terms[0][party], &coefficients[0]).unwrap(),
+                |sum, (term, c)| {
+                    ShamirSecretSharing::add_shares(&sum, &ShamirSecretSharing::scalar_mul(&term[party], c).unwrap()).unwrap()
+                },
+            )
+        })
+        .collect();
+    assert_eq!(combined, naive);
+
+    assert!(linear_combination_batch(&terms, &coefficients[..4]).is_err());
+    assert!(linear_combination_batch(&[], &[]).is_err());
+    let mut mismatched = terms.clone();
+    mismatched[1].swap(0, 1);
+    assert!(linear_combination_batch(&mismatched, &coefficients).is_err());
+}
+
+#[test]
+fn test_goldilocks_reduction_matches_generic_modulo() {
+    let reference = |a: u64, b: u64| ((a as u128 * b as u128) % FIELD_PRIME as u128) as u64;
+    let edges = [0, 1, 2, (1 << 32) - 1, 1 << 32, (1 << 32) + 1, FIELD_PRIME - 2, FIELD_PRIME - 1];
+    for &a in &edges {
+        for &b in &edges {
+            assert_eq!(field_mul(a, b), reference(a, b), "{} * {}", a, b);
+        }
+    }
+    let values: Vec<u64> = (0..1000).map(|_| rand::random::<u64>() % FIELD_PRIME).collect();
+    for pair in values.chunks_exact(2) {
+        assert_eq!(field_mul(pair[0], pair[1]), reference(pair[0], pair[1]));
+    }
+
+    let expected = values.iter().zip(values.iter().rev())
+        .fold(0, |acc, (&v, &c)| field_add(acc, reference(v, c)));
+    let reversed: Vec<u64> = values.iter().rev().copied().collect();
+    assert_eq!(field_inner_product(&values, &reversed), expected);
+}
+
+#[test]
+fn test_ntt_roundtrip_and_polynomial_multiply() {
+    // 2^32 次本原单位根
+    let root = root_of_unity(TWO_ADICITY).unwrap();
+    assert_eq!(field_pow(root, 1 << 31), FIELD_PRIME - 1);
+    assert!(root_of_unity(TWO_ADICITY + 1).is_err());
+
+    let domain = NttDomain::new(4).unwrap();
+    assert_eq!(domain.root(), root_of_unity(4).unwrap());
+    assert!(NttDomain::new(TWO_ADICITY).is_err());
+    let coefficients: Vec<u64> = (0..16).map(|_| rand::random::<u64>() % FIELD_PRIME).collect();
+    let mut values = coefficients.clone();
+    domain.forward(&mut values).unwrap();
+    for (i, &value) in values.iter().enumerate() {
+        let point = field_pow(domain.root(), i as u64);
+        let expected = coefficients.iter().rev().fold(0, |acc, &c| field_add(field_mul(acc, point), c));
+        assert_eq!(value, expected);
+    }
+    domain.inverse(&mut values).unwrap();
+    assert_eq!(values, coefficients);
+    assert!(domain.forward(&mut values[..8]).is_err());
+
+    let a: Vec<u64> = (0..13).map(|_| rand::random::<u64>() % FIELD_PRIME).collect();
+    let b: Vec<u64> = (0..6).map(|_| rand::random::<u64>() % FIELD_PRIME).collect();
+    let mut naive = vec![0u64; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            naive[i + j] = field_add(naive[i + j], field_mul(x, y));
+        }
+    }
+    assert_eq!(polynomial_multiply(&a, &b).unwrap(), naive);
+    assert!(polynomial_multiply(&a, &[]).unwrap().is_empty());
+}
+
+#[test]
+fn test_shamir_batch_evaluation() {
+    let scheme = ShamirSecretSharing::new();
+    assert_eq!(scheme.evaluate_polynomial_batch(&[5, 3, 2], &[0, 1, 2, 3]), vec![5, 10, 19, 32]);
+
+    // 与逐点霍纳法一致
+    let coefficients: Vec<u64> = (0..400).map(|_| rand::random::<u64>() % FIELD_PRIME).collect();
+    let xs: Vec<u64> = (1..=1000).collect();
+    let expected: Vec<u64> = xs.iter().map(|&x| scheme.evaluate_polynomial(&coefficients, x)).collect();
+    assert_eq!(scheme.evaluate_polynomial_batch(&coefficients, &xs), expected);
+
+    let shares = ShamirSecretSharing::share(&777, 300, 600).unwrap();
+    assert_eq!(ShamirSecretSharing::reconstruct(&shares[200..500], 300).unwrap(), 777);
+}
+
+#[test]
+fn test_field_sqrt() {
+    use mpc_api::secret_sharing::field_sqrt;
+
+    assert_eq!(field_sqrt(0), Some(0));
+    assert_eq!(field_sqrt(4), Some(2));
+    // 7 是乘法群生成元，不是二次剩余
+    assert_eq!(field_sqrt(7), None);
+    for _ in 0..50 {
+        let x = rand::random::<u64>() % FIELD_PRIME;
+        let root = field_sqrt(field_mul(x, x)).unwrap();
+        assert!(root == x || root == FIELD_PRIME - x);
+        assert!(root <= FIELD_PRIME - root);
+    }
+}
+
+#[test]
+fn test_shared_bits_and_bit_decomposition() {
+    use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+    use mpc_api::secret_sharing::bits::*;
+    use mpc_api::secret_sharing::Share;
+
+    let open = |shares: &[Share]| ShamirSecretSharing::reconstruct(&shares[1..], 2).unwrap();
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+
+    let output = generate_random_shared_bits(64, &mut generator).unwrap();
+    let bits: Vec<u64> = output.result.iter().map(|bit| open(bit)).collect();
+    assert!(bits.iter().all(|&bit| bit <= 1));
+    // 64 个随机位全部相同的概率为 2^-63
+    assert!(bits.contains(&0) && bits.contains(&1));
+    assert_eq!(output.stats.rounds, 3);
+    assert_eq!(output.stats.preprocessing_consumed, 64);
+    // 每一方发出的消息被其余两方各接收一次，三方收发对称
+    for party in 1..=3 {
+        let traffic = output.stats.party(party);
+        assert!(traffic.bytes_sent > 64 * 8);
+        assert_eq!(traffic.bytes_sent, traffic.bytes_received);
+    }
+    assert_eq!(output.stats.bytes_sent, 3 * output.stats.party(1).bytes_sent);
+
+    let values = [0u64, 1, 0b1011, 4_000_000, (1 << MAX_DECOMPOSITION_BITS) - 1];
+    let shares: Vec<Vec<Share>> = values.iter().map(|v| ShamirSecretSharing::share(v, 2, 3).unwrap()).collect();
+    let output = bit_decompose_batch(&shares, MAX_DECOMPOSITION_BITS, &mut generator).unwrap();
+    for (value, bits) in values.iter().zip(&output.result) {
+        let opened: Vec<u64> = bits.iter().map(|bit| open(bit)).collect();
+        let expected: Vec<u64> = (0..MAX_DECOMPOSITION_BITS).map(|k| (value >> k) & 1).collect();
+        assert_eq!(opened, expected);
+        assert_eq!(open(&bits_to_share(bits).unwrap()), *value);
+    }
+    // 随机位 3 轮、公开 1 轮、每一位 1 轮
+    assert_eq!(output.stats.rounds, 4 + MAX_DECOMPOSITION_BITS);
+
+    let single = bit_decompose(&shares[2], 4, &mut generator).unwrap().result;
+    assert_eq!(single.iter().map(|bit| open(bit)).collect::<Vec<_>>(), vec![1, 1, 0, 1]);
+
+    assert!(bit_decompose(&shares[0], 0, &mut generator).is_err());
+    assert!(bit_decompose(&shares[0], MAX_DECOMPOSITION_BITS + 1, &mut generator).is_err());
+    assert!(bit_decompose(&shares[0][..2], 4, &mut generator).is_err());
+    assert!(bit_decompose_batch(&[], 4, &mut generator).unwrap().result.is_empty());
+    assert!(bits_to_share(&[]).is_err());
+    assert!(bits_to_share(&[shares[0].clone(), shares[1][..2].to_vec()]).is_err());
+}
+
+#[test]
+fn test_secure_dot_product_and_matmul() {
+    use mpc_api::beaver_triples::{BeaverTripleGenerator, TrustedPartyBeaverGenerator};
+    use mpc_api::secret_sharing::linear_algebra::*;
+    use mpc_api::secret_sharing::Share;
+
+    let open = |shares: &[Share]| ShamirSecretSharing::reconstruct(&shares[1..], 2).unwrap();
+    let share = |values: &[u64]| -> Vec<Vec<Share>> {
+        values.iter().map(|value| ShamirSecretSharing::share(value, 2, 3).unwrap()).collect()
+    };
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+
+    let x: Vec<u64> = (1..=100).collect();
+    let y: Vec<u64> = (0..100).map(|i| FIELD_PRIME - 1 - i).collect();
+    let expected = x.iter().zip(&y).fold(0, |sum, (&a, &b)| field_add(sum, field_mul(a, b)));
+    let triples = generator.generate_batch(x.len()).unwrap();
+    let output = secure_dot_product(&share(&x), &share(&y), &triples, 2).unwrap();
+    assert_eq!(open(&output.result), expected);
+    assert_eq!(output.stats.rounds, 1);
+    assert_eq!(output.stats.preprocessing_consumed, 100);
+
+    // [[1, 2, 3], [4, 5, 6]]·[[7, 8], [9, 10], [11, 12]] = [[58, 64], [139, 154]]
+    let a: Vec<Vec<Vec<Share>>> = [[1u64, 2, 3], [4, 5, 6]].iter().map(|row| share(row)).collect();
+    let b: Vec<Vec<Vec<Share>>> = [[7u64, 8], [9, 10], [11, 12]].iter().map(|row| share(row)).collect();
+    let triples = generator.generate_batch(12).unwrap();
+    let output = secure_matmul(&a, &b, &triples, 2).unwrap();
+    let product: Vec<Vec<u64>> = output.result.iter().map(|row| row.iter().map(|entry| open(entry)).collect()).collect();
+    assert_eq!(product, vec![vec![58, 64], vec![139, 154]]);
+    assert_eq!(output.stats.rounds, 1);
+
+    assert!(secure_matmul(&a, &a, &triples, 2).is_err());
+    assert!(secure_matmul(&a, &b, &triples[..11], 2).is_err());
+    assert!(secure_dot_product(&share(&[1, 2]), &share(&[3]), &triples[..2], 2).is_err());
+    assert!(secure_dot_product(&[], &[], &[], 2).is_err());
+    let mut mixed = share(&[1, 2]);
+    mixed[1].reverse();
+    assert!(secure_dot_product(&mixed, &share(&[3, 4]), &triples[..2], 2).is_err());
+}
+
+#[test]
+fn test_feldman_and_pedersen_vss() {
+    use mpc_api::secret_sharing::vss::*;
+    use mpc_api::secret_sharing::Share;
+
+    let p = FIELD_PRIME as u128;
+    assert_eq!(VSS_GROUP_MODULUS, VSS_GROUP_COFACTOR * p + 1);
+    let (g, h) = vss_generators().unwrap();
+    assert!(g > 1 && h > 1 && g != h);
+
+    for scheme in [VssScheme::Feldman, VssScheme::Pedersen] {
+        let dealer = VssDealer::new(scheme, 123_456_789, 3, 5).unwrap();
+        let commitments = dealer.commitments().unwrap();
+        assert_eq!(commitments.threshold(), 3);
+        let shares = dealer.shares();
+        assert!(shares.iter().all(|share| commitments.verify_share(share)));
+        assert!(shares.iter().all(|share| commitments.check_share(share).is_none()));
+        let points: Vec<Share> = shares.iter().map(|share| share.share.clone()).collect();
+        assert_eq!(ShamirSecretSharing::reconstruct(&points[2..], 3).unwrap(), 123_456_789);
+
+        // 错误的份额和错误的 x 坐标都无法通过验证
+        let mut bad = shares[1].clone();
+        bad.share.y = field_add(bad.share.y, 1);
+        assert_eq!(commitments.check_share(&bad), Some(VssComplaint { accuser: 2 }));
+        let mut moved = shares[1].clone();
+        moved.share.x = 3;
+        assert!(!commitments.verify_share(&moved));
+    }
+
+    // Pedersen 承诺不随秘密确定：两次分享同一秘密得到不同的 C_0
+    let first = VssDealer::new(VssScheme::Pedersen, 7, 2, 3).unwrap().commitments().unwrap();
+    let second = VssDealer::new(VssScheme::Pedersen, 7, 2, 3).unwrap().commitments().unwrap();
+    assert_ne!(first.commitments[0], second.commitments[0]);
+    assert!(VssDealer::new(VssScheme::Feldman, 7, 4, 3).is_err());
+
+    // 常数项乘法关系证明绑定上下文，只接受 Pedersen 承诺
+    let [a, b, c] = [6, 7, 42].map(|secret| VssDealer::new(VssScheme::Pedersen, secret, 2, 3).unwrap());
+    let proof = a.prove_product(&b, &c, b"ctx").unwrap();
+    let [ca, cb, cc] = [&a, &b, &c].map(|dealer| dealer.commitments().unwrap());
+    assert!(ca.verify_product(&cb, &cc, &proof, b"ctx"));
+    assert!(!ca.verify_product(&cb, &cc, &proof, b"other"));
+    assert!(!cb.verify_product(&ca, &first, &proof, b"ctx"));
+    assert!(a.prove_product(&b, &a, b"ctx").is_err());
+    let feldman = VssDealer::new(VssScheme::Feldman, 42, 2, 3).unwrap();
+    assert!(a.prove_product(&b, &feldman, b"ctx").is_err());
+}
+
+#[test]
+fn test_vss_complaint_handling() {
+    use mpc_api::secret_sharing::vss::*;
+
+    let dealer = VssDealer::new(VssScheme::Pedersen, 99, 3, 5).unwrap();
+    let commitments = dealer.commitments().unwrap();
+
+    // 诚实分发者回应投诉，公开的份额通过验证
+    let complaints = vec![VssComplaint { accuser: 4 }];
+    let answers = dealer.answer_complaints(&complaints);
+    assert_eq!(commitments.resolve_complaints(&complaints, &answers), ComplaintOutcome::Resolved(vec![dealer.share(4)]));
+
+    // 回应错误、缺失或投诉达到门限时取消分发者资格
+    let mut wrong = answers.clone();
+    wrong[0].blinding = field_add(wrong[0].blinding, 1);
+    assert!(matches!(commitments.resolve_complaints(&complaints, &wrong), ComplaintOutcome::Disqualified(_)));
+    assert!(matches!(commitments.resolve_complaints(&complaints, &[]), ComplaintOutcome::Disqualified(_)));
+    let mut misattributed = answers.clone();
+    misattributed[0] = dealer.share(5);
+    assert!(matches!(commitments.resolve_complaints(&complaints, &misattributed), ComplaintOutcome::Disqualified(_)));
+    let many: Vec<VssComplaint> = (1..=3).map(|accuser| VssComplaint { accuser }).collect();
+    let answers = dealer.answer_complaints(&many);
+    assert!(matches!(commitments.resolve_complaints(&many, &answers), ComplaintOutcome::Disqualified(_)));
+
+    // 分发者使用不一致的承诺：所有持有者都会投诉
+    let mut forged = commitments.clone();
+    forged.commitments[1] = commitments.commitments[2];
+    let complaints: Vec<VssComplaint> = dealer.shares().iter().filter_map(|share| forged.check_share(share)).collect();
+    assert_eq!(complaints.len(), 5);
+    // 不在子群中的承诺被拒绝
+    let mut outside = commitments.clone();
+    outside.commitments[0] = 2;
+    assert!(!outside.verify_share(&dealer.share(1)));
+}
+
+#[test]
+fn test_proactive_refresh_epochs() {
+    use mpc_api::secret_sharing::proactive::*;
+    use mpc_api::secret_sharing::Share;
+    use std::time::{Duration, SystemTime};
+
+    let shares = ShamirSecretSharing::share(&2024, 3, 5).unwrap();
+    let mut parties: Vec<ProactiveParty> = shares.iter()
+        .map(|share| ProactiveParty::new(share.clone(), 3, 5).unwrap())
+        .collect();
+
+    for epoch in 1..=3 {
+        run_refresh_epoch(&mut parties).unwrap();
+        let refreshed: Vec<Share> = parties.iter().map(|party| party.share().clone()).collect();
+        assert!(parties.iter().all(|party| party.epoch() == epoch));
+        assert_ne!(refreshed, shares);
+        assert_eq!(ShamirSecretSharing::reconstruct(&refreshed[..3], 3).unwrap(), 2024);
+        assert_eq!(ShamirSecretSharing::reconstruct(&refreshed[2..], 3).unwrap(), 2024);
+    }
+
+    // 消息经过序列化后仍可使用
+    let message = parties[0].contribute().unwrap()[1];
+    let bytes = bincode::serialize(&message).unwrap();
+    assert_eq!(bincode::deserialize::<RefreshMessage>(&bytes).unwrap(), message);
+    assert_eq!(message.epoch, 3);
+
+    let start = SystemTime::UNIX_EPOCH;
+    let schedule = RefreshSchedule::new(start, Duration::from_secs(60)).unwrap();
+    assert_eq!(schedule.epoch_at(start + Duration::from_secs(59)), 0);
+    assert_eq!(schedule.epoch_at(start + Duration::from_secs(240)), 4);
+    assert!(parties[0].is_refresh_due(&schedule, start + Duration::from_secs(240)));
+    assert!(!parties[0].is_refresh_due(&schedule, start + Duration::from_secs(200)));
+    assert!(RefreshSchedule::new(start, Duration::ZERO).is_err());
+    assert!(ProactiveParty::new(Share::new(6, 1), 3, 5).is_err());
+}
+
+#[test]
+fn test_proactive_refresh_rejects_bad_messages() {
+    use mpc_api::secret_sharing::proactive::*;
+
+    let shares = ShamirSecretSharing::share(&7, 2, 3).unwrap();
+    let parties: Vec<ProactiveParty> = shares.iter()
+        .map(|share| ProactiveParty::new(share.clone(), 2, 3).unwrap())
+        .collect();
+    let inbox: Vec<RefreshMessage> = parties.iter().map(|party| party.contribute().unwrap()[0]).collect();
+
+    let mut receiver = parties[0].clone();
+    assert!(receiver.complete(&inbox[..2]).is_err());
+    let mut stale = inbox.clone();
+    stale[1].epoch = 1;
+    assert!(receiver.complete(&stale).is_err());
+    let mut duplicate = inbox.clone();
+    duplicate[2].from = 1;
+    assert!(receiver.complete(&duplicate).is_err());
+    let mut misaddressed = inbox.clone();
+    misaddressed[0].to = 2;
+    assert!(receiver.complete(&misaddressed).is_err());
+    // 失败的刷新不改变份额和纪元
+    assert_eq!(receiver.epoch(), 0);
+    assert_eq!(receiver.share(), &shares[0]);
+
+    receiver.complete(&inbox).unwrap();
+    assert_eq!(receiver.epoch(), 1);
+    assert!(receiver.complete(&inbox).is_err());
+}
+
+#[test]
+fn test_threshold_bls_signature() {
+    use mpc_api::secret_sharing::*;
+
+    let dealer = VssDealer::new(VssScheme::Feldman, 123_456, 3, 5).unwrap();
+    let public = dealer.commitments().unwrap();
+    let shares: Vec<Share> = dealer.shares().into_iter().map(|share| share.share).collect();
+    let message = b"threshold message";
+
+    let partials: Vec<BlsPartialSignature> = shares.iter()
+        .map(|share| ThresholdBls::partial_sign(share, message).unwrap())
+        .collect();
+    assert!(partials.iter().all(|partial| ThresholdBls::verify_partial(&public, message, partial)));
+
+    // 任意 t 方聚合出同一个签名 H(m)^x
+    let expected = ThresholdBls::partial_sign(&Share::new(1, 123_456), message).unwrap().value;
+    let first = ThresholdBls::aggregate(&public, message, &partials[..3]).unwrap();
+    let second = ThresholdBls::aggregate(&public, message, &[partials[4], partials[1], partials[3]]).unwrap();
+    assert_eq!(first.value, expected);
+    assert_eq!(second.value, expected);
+    assert!(ThresholdBls::verify(&public, message, &first));
+    assert!(ThresholdBls::verify(&public, message, &second));
+    assert!(!ThresholdBls::verify(&public, b"other message", &first));
+
+    let (other_public, _) = ThresholdBls::generate_keys(3, 5).unwrap();
+    assert!(!ThresholdBls::verify(&other_public, message, &first));
+}
+
+#[test]
+fn test_threshold_bls_rejects_bad_partials() {
+    use mpc_api::secret_sharing::*;
+    use mpc_api::MpcError;
+
+    let (public, shares) = ThresholdBls::generate_keys(2, 3).unwrap();
+    let message = b"threshold message";
+    let partials: Vec<BlsPartialSignature> = shares.iter()
+        .map(|share| ThresholdBls::partial_sign(share, message).unwrap())
+        .collect();
+
+    assert!(matches!(ThresholdBls::aggregate(&public, message, &partials[..1]), Err(MpcError::InsufficientShares)));
+    assert!(ThresholdBls::aggregate(&public, message, &[partials[0], partials[0]]).is_err());
+
+    // 用错误份额签名或篡改签名值都无法通过证明
+    let mut forged = ThresholdBls::partial_sign(&Share::new(2, field_add(shares[1].y, 1)), message).unwrap();
+    assert!(!ThresholdBls::verify_partial(&public, message, &forged));
+    assert!(matches!(
+        ThresholdBls::aggregate(&public, message, &[partials[0], forged]),
+        Err(MpcError::AuthenticationError(_))
+    ));
+    forged = partials[1];
+    forged.value = partials[2].value;
+    assert!(!ThresholdBls::verify_partial(&public, message, &forged));
+
+    let mut signature = ThresholdBls::aggregate(&public, message, &partials[1..]).unwrap();
+    signature.value = partials[0].value;
+    assert!(!ThresholdBls::verify(&public, message, &signature));
+    signature.partials.pop();
+    assert!(!ThresholdBls::verify(&public, message, &signature));
+
+    // Pedersen 承诺不能作为验证密钥
+    let pedersen = VssDealer::new(VssScheme::Pedersen, 5, 2, 3).unwrap();
+    let pedersen_shares = pedersen.shares();
+    let partial = ThresholdBls::partial_sign(&pedersen_shares[0].share, message).unwrap();
+    assert!(!ThresholdBls::verify_partial(&pedersen.commitments().unwrap(), message, &partial));
+}
+
+#[test]
+fn test_constant_time_field_operations() {
+    use mpc_api::secret_sharing::{field_inv, field_pow_ct, field_sub};
+
+    // 进位、借位和约减边界
+    assert_eq!(field_add(FIELD_PRIME - 1, FIELD_PRIME - 1), FIELD_PRIME - 2);
+    assert_eq!(field_add(FIELD_PRIME - 1, 1), 0);
+    assert_eq!(field_sub(0, 1), FIELD_PRIME - 1);
+    assert_eq!(field_sub(5, 5), 0);
+    assert_eq!(field_mul(FIELD_PRIME - 1, FIELD_PRIME - 1), 1);
+    assert_eq!(field_mul(1 << 32, 1 << 32), (1 << 32) - 1);
+
+    for (base, exponent) in [(3, 0), (3, 1), (7, FIELD_PRIME - 2), (FIELD_PRIME - 1, u64::MAX), (0, 5)] {
+        assert_eq!(field_pow_ct(base, exponent), field_pow(base, exponent));
+    }
+
+    assert_eq!(field_inv(0), None);
+    for a in [1, 2, 12345, FIELD_PRIME - 1] {
+        assert_eq!(field_mul(a, field_inv(a).unwrap()), 1);
+    }
+}
+
+#[test]
+fn test_generic_prime_fields() {
+    use mpc_api::secret_sharing::*;
+
+    // Goldilocks 实例与原始域运算一致
+    let (a, b) = (FIELD_PRIME - 5, 123_456_789u64);
+    assert_eq!((Goldilocks::from(a) * Goldilocks::from(b)).to_u128(), field_mul(a, b) as u128);
+    assert_eq!((Goldilocks::from(a) + Goldilocks::from(b)).to_u128(), field_add(a, b) as u128);
+    assert_eq!(-Goldilocks::ONE, Goldilocks::from(FIELD_PRIME - 1));
+
+    // 2^127 - 1 上的乘法：2^126·4 = 2^128 ≡ 2
+    let p127 = Mersenne127::MODULUS;
+    assert_eq!(Mersenne127::from_u128(1 << 126) * Mersenne127::from_u128(4), Mersenne127::from_u128(2));
+    let large = Mersenne127::from_u128(p127 - 3);
+    assert_eq!(large * large, Mersenne127::from_u128(9));
+    assert_eq!(large * large.inverse().unwrap(), Mersenne127::ONE);
+    assert_eq!(Mersenne127::ZERO.inverse(), None);
+    assert_eq!(Fp65537::from_u128(65537 + 7), Fp65537::from_u128(7));
+
+    let secret = Mersenne127::from_u128(p127 - 1);
+    let scheme = Shamir::<Mersenne127>::new();
+    let shares = Shamir::<Mersenne127>::share(&secret, 3, 5).unwrap();
+    assert!(scheme.verify_shares(&shares, 3));
+    assert_eq!(Shamir::reconstruct(&shares[2..], 3).unwrap(), secret);
+    let doubled: Vec<_> = shares.iter()
+        .map(|share| Shamir::add_shares(share, share).unwrap())
+        .collect();
+    assert_eq!(Shamir::reconstruct(&doubled[..3], 3).unwrap(), secret + secret);
+
+    let mut tampered = shares.clone();
+    tampered[4].y = tampered[4].y + Mersenne127::ONE;
+    assert!(!scheme.verify_shares(&tampered, 3));
+    assert!(Shamir::<Fp65537>::share(&Fp65537::ONE, 2, 70_000).is_err());
+
+    let json = serde_json::to_string(&shares[0]).unwrap();
+    assert_eq!(serde_json::from_str::<Share<Mersenne127>>(&json).unwrap(), shares[0]);
+
+    // ShamirSecretSharing 就是 u64 上的同一实现
+    let shares = ShamirSecretSharing::share(&(FIELD_PRIME - 1), 3, 5).unwrap();
+    assert_eq!(Shamir::<u64>::reconstruct(&shares[1..4], 3).unwrap(), FIELD_PRIME - 1);
+    assert!(ShamirSecretSharing::share(&FIELD_PRIME, 3, 5).is_err());
+    assert!(serde_json::from_str::<Fp65537>("65537").is_err());
+}
+
+#[test]
+fn test_generic_field_beaver_and_ole() {
+    use mpc_api::beaver_triples::{open_value, secure_multiply, CompleteBeaverTriple};
+    use mpc_api::oblivious_transfer::{random_field_ole, FieldOleResponse};
+    use mpc_api::secret_sharing::{Field, Fp65537, Mersenne127, Shamir};
+
+    let (x, y) = (Fp65537::from_u128(40_000), Fp65537::from_u128(50_000));
+    let x_shares = Shamir::share(&x, 2, 4).unwrap();
+    let y_shares = Shamir::share(&y, 2, 4).unwrap();
+    let triple = CompleteBeaverTriple::<Fp65537>::deal(2, 4, 7).unwrap();
+    assert!(triple.verify(2).unwrap());
+    let product = secure_multiply(&x_shares, &y_shares, &triple, 2).unwrap();
+    assert_eq!(open_value(&product, 2).unwrap(), x * y);
+    let mut partial = triple.clone();
+    partial.shares.retain(|&party, _| party == 1);
+    assert!(secure_multiply(&x_shares, &y_shares, &partial, 2).is_err());
+
+    // 随机 OLE 关联去随机化为发送方选定的直线和接收方选定的输入
+    let (sender, receiver) = random_field_ole::<Mersenne127>();
+    let (a, b, input) = (Mersenne127::from_u128(3 << 120), Mersenne127::from_u128(11), Mersenne127::from_u128(1 << 100));
+    let response: FieldOleResponse<Mersenne127> = sender.respond(receiver.choose(input), a, b);
+    assert_eq!(receiver.finish(input, &response), a * input + b);
+}
+
+#[test]
+fn test_seeded_share_generation() {
+    use mpc_api::utils::crypto::{AesCtrPrg, Blake3Prg, SeedableRng};
+
+    let scheme = ShamirSecretSharing::new();
+    let shares = scheme.share_with_rng(&4242, 3, 5, &mut AesCtrPrg::from_seed([9; 32])).unwrap();
+    assert_eq!(shares, scheme.share_with_rng(&4242, 3, 5, &mut AesCtrPrg::from_seed([9; 32])).unwrap());
+    assert_ne!(shares, scheme.share_with_rng(&4242, 3, 5, &mut AesCtrPrg::from_seed([10; 32])).unwrap());
+    assert_eq!(ShamirSecretSharing::reconstruct(&shares[1..4], 3).unwrap(), 4242);
+    assert!(scheme.share_with_rng(&4242, 6, 5, &mut AesCtrPrg::from_seed([9; 32])).is_err());
+
+    let additive = AdditiveSecretSharingScheme::new();
+    let shares = additive.share_additive_with_rng(&77, 4, &mut Blake3Prg::from_seed([9; 32])).unwrap();
+    assert_eq!(shares, additive.share_additive_with_rng(&77, 4, &mut Blake3Prg::from_seed([9; 32])).unwrap());
+    assert_eq!(additive.reconstruct_additive(&shares).unwrap(), 77);
+}
@@ -47,6 +47,94 @@ fn test_audit_logger() {
     assert!(!events.is_empty());
 }
 
+#[test]
+fn test_audit_log_merkle_proofs() {
+    let logger = AuditLogger::new(SecurityPolicy::medium());
+    let log = |description: &str| {
+        logger.log_event(SecurityEvent::new(ThreatType::ProtocolAttack, SecurityLevel::Low, description.to_string()))
+    };
+    log("first").unwrap();
+    log("second").unwrap();
+    let (old_size, old_root) = logger.log_root();
+    log("third").unwrap();
+    let (new_size, new_root) = logger.log_root();
+    assert_eq!((old_size, new_size), (2, 3));
+
+    // 历史事件在新根下可证明，旧根是新根的前缀
+    let events = logger.get_events();
+    let proof = logger.prove_event(1, new_size).unwrap();
+    assert!(proof.verify(&new_root, &events[1].audit_bytes().unwrap()));
+    assert!(!proof.verify(&new_root, &events[0].audit_bytes().unwrap()));
+    assert!(logger.prove_log_consistency(old_size, new_size).unwrap().verify(&old_root, &new_root));
+    assert!(logger.prove_event(3, new_size).is_err());
+
+    // 过期清理不改变审计日志的根
+    logger.cleanup_expired_logs().unwrap();
+    assert_eq!(logger.log_root(), (new_size, new_root));
+}
+
+#[test]
+fn test_audit_jsonl_export_and_query() {
+    let path = std::env::temp_dir().join(format!("mpc_audit_test_{}_{}.jsonl", std::process::id(), rand::random::<u64>()));
+    let logger = AuditLogger::new(SecurityPolicy::medium());
+    logger.add_sink(std::sync::Arc::new(JsonlFileSink::open(&path).unwrap()));
+
+    let start = std::time::SystemTime::now();
+    let mut replay = SecurityEvent::new(ThreatType::ReplayAttack, SecurityLevel::High, "duplicate nonce".to_string());
+    replay.peer_id = Some("party-2".to_string());
+    logger.log_event(replay).unwrap();
+    logger.log_event(SecurityEvent::new(ThreatType::TimingAttack, SecurityLevel::Low, "slow round".to_string())).unwrap();
+    logger.log_event(SecurityEvent::new(ThreatType::ReplayAttack, SecurityLevel::Medium, "stale message".to_string())).unwrap();
+    logger.flush_sinks().unwrap();
+
+    // 导出文件与日志的 Merkle 根一致
+    let exported = JsonlFileSink::read_events(&path).unwrap();
+    assert_eq!(exported.len(), 3);
+    assert_eq!(audit_root(&exported).unwrap(), logger.current_root());
+    assert_eq!(logger.current_root(), logger.log_root().1);
+
+    // 删除或修改任何一条事件都会改变根
+    assert_ne!(audit_root(&exported[1..]).unwrap(), logger.current_root());
+    let mut tampered = exported.clone();
+    tampered[1].severity = SecurityLevel::Critical;
+    assert_ne!(audit_root(&tampered).unwrap(), logger.current_root());
+
+    let query = |q: AuditQuery| logger.query(&q).unwrap().into_iter().map(|e| e.description).collect::<Vec<_>>();
+    assert_eq!(query(AuditQuery::new().threat_type(ThreatType::ReplayAttack)), ["duplicate nonce", "stale message"]);
+    assert_eq!(query(AuditQuery::new().min_severity(SecurityLevel::Medium)), ["duplicate nonce", "stale message"]);
+    assert_eq!(query(AuditQuery::new().peer("party-2")), ["duplicate nonce"]);
+    assert_eq!(query(AuditQuery::new().threat_type(ThreatType::ReplayAttack).limit(1)), ["duplicate nonce"]);
+    assert_eq!(query(AuditQuery::new().since(start)).len(), 3);
+    assert!(query(AuditQuery::new().until(start)).is_empty());
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_audit_cef_and_syslog_export() {
+    let mut event = SecurityEvent::new(ThreatType::MaliciousParty, SecurityLevel::Critical, "a=b\nc".to_string());
+    event.peer_id = Some("party|3".to_string());
+    let cef = event.to_cef();
+    assert!(cef.starts_with("CEF:0|mpc_api|mpc_api|"));
+    assert!(cef.contains("|malicious-party|"));
+    assert!(cef.contains("|10|"));
+    assert!(cef.contains("msg=a\\=b\\nc"));
+    assert!(cef.contains("suser=party|3"));
+
+    let receiver = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    receiver.set_read_timeout(Some(std::time::Duration::from_secs(5))).unwrap();
+    let sink = SyslogSink::connect(receiver.local_addr().unwrap(), "mpc-node").unwrap();
+    sink.write(&event).unwrap();
+
+    let mut buffer = [0u8; 2048];
+    let len = receiver.recv(&mut buffer).unwrap();
+    let message = std::str::from_utf8(&buffer[..len]).unwrap();
+    // facility 10 (authpriv) × 8 + severity 2 (critical)
+    assert!(message.starts_with("<82>1 "));
+    assert!(message.contains(" mpc-node "));
+    assert!(message.ends_with(&cef));
+}
+
 #[test]
 fn test_security_manager() {
     let mgr = SecurityManager::with_policy(SecurityPolicy::low()).unwrap();
@@ -54,4 +142,160 @@ fn test_security_manager() {
     assert!(result.is_ok());
     
     mgr.stop();
-}
\ No newline at end of file
+}
+#[test]
+fn test_repeated_mac_failures_quarantine_peer() {
+    let manager = SecurityManager::with_policy(SecurityPolicy::high()).unwrap();
+    let failure = || ProtocolIncident::MacCheckFailure { session_id: "s1".to_string() };
+
+    assert_eq!(manager.report_incident("p1", failure()).unwrap(), AutomaticResponse::LogOnly);
+    assert!(manager.ensure_peer_allowed("p1").is_ok());
+    assert_eq!(manager.report_incident("p1", failure()).unwrap(), AutomaticResponse::QuarantinePeer);
+    assert!(manager.is_peer_quarantined("p1"));
+    assert!(!manager.is_peer_quarantined("p2"));
+    assert!(manager.ensure_peer_allowed("p1").is_err());
+
+    assert!(manager.release_peer("p1"));
+    assert!(manager.ensure_peer_allowed("p1").is_ok());
+
+    // 通过 report_security_event 上报的事件同样带有归属并计入统计
+    let event = SecurityEvent::from_incident("p3", failure());
+    assert_eq!(event.peer_id.as_deref(), Some("p3"));
+    assert_eq!(event.threat_type, ThreatType::MaliciousParty);
+    manager.report_security_event(event.clone()).unwrap();
+    manager.report_security_event(event).unwrap();
+    assert!(manager.is_peer_quarantined("p3"));
+}
+
+#[test]
+fn test_layered_timeouts_abort_session() {
+    use std::time::{Duration, Instant};
+
+    let mut deadline = LayeredDeadline::new("session-7", Duration::from_secs(60), Duration::from_secs(5));
+    deadline.begin_round(2);
+    let now = Instant::now();
+    assert!(deadline.check_at(now).is_none());
+
+    let incident = deadline.check_at(now + Duration::from_secs(10)).unwrap();
+    assert!(matches!(incident, ProtocolIncident::RoundTimeout { round: 2, .. }));
+    assert!(matches!(
+        deadline.check_at(now + Duration::from_secs(120)),
+        Some(ProtocolIncident::ProtocolTimeout { .. })
+    ));
+
+    let manager = SecurityManager::with_policy(SecurityPolicy::strict()).unwrap();
+    assert_eq!(manager.report_incident("p1", incident).unwrap(), AutomaticResponse::AbortSession);
+    assert!(manager.is_session_aborted("session-7"));
+    assert!(!manager.is_peer_quarantined("p1"));
+
+    // 时钟偏差余量推迟超时判定
+    let mut lenient = LayeredDeadline::new("session-8", Duration::from_secs(60), Duration::from_secs(5))
+        .with_skew_allowance(Duration::from_secs(2));
+    lenient.begin_round(1);
+    let now = Instant::now();
+    assert!(lenient.check_at(now + Duration::from_secs(6)).is_none());
+    assert!(lenient.check_at(now + Duration::from_secs(8)).is_some());
+}
+
+#[test]
+fn test_reconnect_storm_requires_manual_resume() {
+    let policy = SecurityPolicy::medium().with_incident_response(IncidentResponsePolicy {
+        reconnect_limit: 3,
+        on_reconnect_storm: AutomaticResponse::RequireManualResume,
+        ..IncidentResponsePolicy::default()
+    });
+    let manager = SecurityManager::with_policy(policy).unwrap();
+
+    for _ in 0..2 {
+        assert_eq!(manager.report_incident("p4", ProtocolIncident::Reconnect).unwrap(), AutomaticResponse::LogOnly);
+    }
+    assert_eq!(
+        manager.report_incident("p4", ProtocolIncident::Reconnect).unwrap(),
+        AutomaticResponse::RequireManualResume
+    );
+    assert!(manager.requires_manual_resume());
+    assert!(manager.ensure_peer_allowed("p5").is_err());
+
+    manager.resume_operations();
+    assert!(manager.ensure_peer_allowed("p5").is_ok());
+
+    // 开发环境策略只记录
+    let lenient = SecurityManager::with_policy(SecurityPolicy::low()).unwrap();
+    for _ in 0..10 {
+        assert_eq!(lenient.report_incident("p4", ProtocolIncident::Reconnect).unwrap(), AutomaticResponse::LogOnly);
+    }
+}
+
+#[test]
+fn test_hkdf_rfc5869_vector() {
+    // RFC 5869 附录 A.1
+    let salt: Vec<u8> = (0x00..=0x0c).collect();
+    let info: Vec<u8> = (0xf0..=0xf9).collect();
+    let prk = hkdf_extract(&salt, &[0x0b; 22]);
+    let parse = |hex: &str| (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect::<Vec<u8>>();
+    assert_eq!(prk.as_bytes().to_vec(), parse("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5"));
+
+    let mut okm = [0u8; 42];
+    hkdf_expand(&prk, &info, &mut okm).unwrap();
+    assert_eq!(okm.to_vec(), parse("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"));
+    assert!(hkdf_expand(&prk, &info, &mut vec![0u8; 255 * 32 + 1]).is_err());
+}
+
+#[test]
+fn test_key_store_hierarchy_and_rotation() {
+    let mut store = KeyStore::new(SecretKey::from_bytes([5; 32]));
+    let mac = store.derive("spdz/mac").unwrap();
+    let tls = store.derive("network/tls").unwrap();
+    assert_eq!(mac.path(), "spdz/mac");
+    assert_ne!(mac.key(), tls.key());
+    assert_eq!(KeyStore::new(SecretKey::from_bytes([5; 32])).derive("spdz/mac").unwrap().key(), mac.key());
+    assert!(format!("{:?}", mac).contains("redacted"));
+    assert!(store.derive("spdz//mac").is_err());
+    assert!(store.derive("").is_err());
+
+    // 轮换父节点同时轮换后代，兄弟节点不受影响
+    assert_eq!(store.rotate("spdz").unwrap(), 1);
+    assert_ne!(store.derive("spdz/mac").unwrap().key(), mac.key());
+    assert_eq!(store.derive("network/tls").unwrap().key(), tls.key());
+
+    // 旧版本在退役之前可以派生
+    let before = store.derive("network/tls").unwrap();
+    assert_eq!(store.rotate("network/tls").unwrap(), 1);
+    assert_eq!(store.rotate("network/tls").unwrap(), 2);
+    assert_eq!(store.derive_version("network/tls", 0).unwrap().key(), before.key());
+    assert!(store.derive_version("network/tls", 3).is_err());
+    store.retire_before("network/tls", 2).unwrap();
+    assert!(store.derive_version("network/tls", 1).is_err());
+    assert_eq!(store.derive("network/tls").unwrap().version(), 2);
+    assert!(store.retire_before("network/tls", 3).is_err());
+}
+
+#[test]
+fn test_key_store_encrypted_export() {
+    let mut store = KeyStore::generate();
+    store.rotate("spdz/mac").unwrap();
+    store.retire_before("spdz/mac", 1).unwrap();
+
+    let exported = store.export_with_iterations(b"passphrase", MIN_EXPORT_ITERATIONS).unwrap();
+    let restored = KeyStore::import(&exported, b"passphrase").unwrap();
+    assert_eq!(restored.derive("spdz/mac").unwrap().key(), store.derive("spdz/mac").unwrap().key());
+    assert_eq!(restored.current_version("spdz/mac").unwrap(), 1);
+    assert!(restored.derive_version("spdz/mac", 0).is_err());
+
+    // 口令错误或任一字节被篡改都无法导入
+    assert!(matches!(KeyStore::import(&exported, b"passphrasf"), Err(mpc_api::MpcError::AuthenticationError(_))));
+    for index in [0, exported.len() / 2, exported.len() - 1] {
+        let mut tampered = exported.clone();
+        tampered[index] ^= 1;
+        assert!(KeyStore::import(&tampered, b"passphrase").is_err());
+    }
+    assert!(store.export_with_iterations(b"passphrase", MIN_EXPORT_ITERATIONS - 1).is_err());
+
+    // 安全管理器的轮换写入审计日志
+    let manager = SecurityManager::new().unwrap();
+    manager.install_key_store(restored);
+    assert_eq!(manager.derive_key("spdz/mac").unwrap().key(), store.derive("spdz/mac").unwrap().key());
+    assert_eq!(manager.rotate_key("spdz/mac").unwrap(), 2);
+    manager.retire_keys_before("spdz/mac", 2).unwrap();
+    assert_eq!(manager.get_security_stats().threat_types.get(&ThreatType::KeyLifecycle), Some(&2));
+}
@@ -71,11 +71,78 @@ fn test_input_and_reconstruction() {
     let secret = 42u64;
     let auth_share = protocol.input(secret).unwrap();
     
-    // In a real scenario, MAC verification would be done
-    // For this test, we'll skip it
-    let reconstructed = auth_share.reconstruct(2).unwrap();
-    // Check that reconstruction works - the value might be equivalent in the field
-    assert!(reconstructed == secret || reconstructed == field_add(secret, 0));
+    assert_eq!(protocol.open(&auth_share).unwrap(), secret);
+    assert_eq!(auth_share.reconstruct(2).unwrap(), secret);
+    for party_id in 0..3 {
+        assert_eq!(auth_share.get_share(party_id).unwrap().party_id, party_id);
+    }
+}
+
+#[test]
+fn test_authenticate_shamir_shares() {
+    use mpc_api::secret_sharing::{SecretSharing, ShamirSecretSharing};
+
+    let protocol = SPDZShareProtocol::new(SPDZParams::new(3, 0, 2)).unwrap();
+    let shares = ShamirSecretSharing::share(&17, 2, 3).unwrap();
+
+    // 各方由自己的 α_i 通过 OLE 得到 MAC 分享，打开时通过 MAC 检查
+    let first = protocol.authenticate(&shares).unwrap();
+    let second = protocol.authenticate(&shares).unwrap();
+    assert_eq!(protocol.open(&first).unwrap(), 17);
+    assert_eq!(protocol.open(&second).unwrap(), 17);
+    assert!((0..3).any(|party| first.shares[&party].mac != second.shares[&party].mac));
+    assert!(protocol.authenticate(&shares[..2]).is_err());
+}
+
+#[test]
+fn test_open_detects_tampering() {
+    use mpc_api::MpcError;
+
+    let protocol = SPDZShareProtocol::new(SPDZParams::new(3, 0, 2)).unwrap();
+    let shared = protocol.input(42).unwrap();
+    let sum = protocol.add(&shared, &protocol.input(8).unwrap()).unwrap();
+    assert_eq!(protocol.open(&sum).unwrap(), 50);
+
+    // 恶意参与方改动分享值：插值结果变化，MAC 检查失败
+    let mut tampered_value = shared.clone();
+    tampered_value.shares.get_mut(&1).unwrap().value = field_add(tampered_value.shares[&1].value, 1);
+    assert!(matches!(protocol.open(&tampered_value), Err(MpcError::AuthenticationError(_))));
+
+    // 同时改动分享值和 MAC 也无法通过检查
+    let mut tampered_both = tampered_value.clone();
+    tampered_both.shares.get_mut(&1).unwrap().mac = field_add(tampered_both.shares[&1].mac, 1);
+    assert!(matches!(protocol.open(&tampered_both), Err(MpcError::AuthenticationError(_))));
+
+    let mut tampered_mac = shared.clone();
+    tampered_mac.shares.get_mut(&2).unwrap().mac = field_add(tampered_mac.shares[&2].mac, 1);
+    assert!(matches!(protocol.open(&tampered_mac), Err(MpcError::AuthenticationError(_))));
+
+    // 批量打开共用一次检查，其中任何一个值被篡改都会失败
+    let batch = vec![shared.clone(), sum.clone()];
+    assert_eq!(protocol.open_batch(&batch).unwrap(), vec![42, 50]);
+    assert!(protocol.open_batch(&[sum, tampered_mac]).is_err());
+
+    let mut missing = shared;
+    missing.shares.remove(&0);
+    assert!(matches!(protocol.open(&missing), Err(MpcError::InsufficientShares)));
+}
+
+#[test]
+fn test_open_after_mac_key_refresh() {
+    let n = 3;
+    let mut parties: Vec<SPDZShareProtocol> = (0..n)
+        .map(|id| SPDZShareProtocol::new(SPDZParams::new(n, id, 1)).unwrap())
+        .collect();
+    let shared = parties[0].input(9).unwrap();
+
+    let contributions: Vec<Vec<u64>> = parties.iter().map(|p| p.mac_key_refresh_contribution()).collect();
+    for (j, party) in parties.iter_mut().enumerate() {
+        let received: Vec<u64> = contributions.iter().map(|c| c[j]).collect();
+        party.apply_mac_key_refresh(&received).unwrap();
+    }
+
+    // 刷新不改变 α，刷新前生成的分享仍能通过 MAC 检查
+    assert_eq!(parties[0].open(&shared).unwrap(), 9);
 }
 
 #[test]
@@ -151,4 +218,146 @@ fn test_random_generation() {
     let value = random_share.reconstruct(2).unwrap();
     
     assert!(value < FIELD_PRIME);
-}
\ No newline at end of file
+}
+#[test]
+fn test_spdz_open_gated() {
+    use mpc_api::secret_sharing::{RevealGate, RevealPolicy, RevealRequest};
+    use mpc_api::security::ThreatType;
+
+    let protocol = SPDZShareProtocol::new(SPDZParams::new(3, 0, 2)).unwrap();
+    let shared = protocol.input(77).unwrap();
+
+    let public_gate = RevealGate::new(RevealPolicy::Public, 2);
+    let opened = protocol.open_gated(&shared, &public_gate, RevealRequest::new("x", 0)).unwrap();
+    assert_eq!(opened, 77);
+
+    // 诚实的分享通过 MAC 检查
+    let mac_gate = RevealGate::new(RevealPolicy::RequiresMacCheck, 2);
+    assert_eq!(protocol.open_gated(&shared, &mac_gate, RevealRequest::new("x", 0)).unwrap(), 77);
+
+    // 被篡改的分享无法通过 MAC 检查，守门人拒绝公开；两次请求都记录审计事件
+    let mut tampered = shared.clone();
+    tampered.shares.get_mut(&0).unwrap().value = field_add(tampered.shares[&0].value, 1);
+    assert!(protocol.open_gated(&tampered, &mac_gate, RevealRequest::new("x", 0)).is_err());
+    assert_eq!(mac_gate.audit_logger().get_events_by_type(&ThreatType::SecretDisclosure).len(), 2);
+}
+
+#[test]
+fn test_spdz_mac_key_refresh() {
+    let n = 3;
+    let mut parties: Vec<SPDZShareProtocol> = (0..n)
+        .map(|id| SPDZShareProtocol::new(SPDZParams::new(n, id, 1)).unwrap())
+        .collect();
+
+    let global_key = parties.iter()
+        .fold(0u64, |acc, p| field_add(acc, p.get_mac_key_share()));
+    let old_shares: Vec<u64> = parties.iter().map(|p| p.get_mac_key_share()).collect();
+    let old_hmac = parties[0].get_hmac_key(1).unwrap().key.clone();
+
+    // 每方生成零分享，第 j 个值发给参与方 j
+    let contributions: Vec<Vec<u64>> = parties.iter().map(|p| p.mac_key_refresh_contribution()).collect();
+    for (j, party) in parties.iter_mut().enumerate() {
+        let received: Vec<u64> = contributions.iter().map(|c| c[j]).collect();
+        assert_eq!(party.apply_mac_key_refresh(&received).unwrap(), 1);
+    }
+
+    // 全局密钥不变，但每一方的分享都已更新
+    let new_key = parties.iter()
+        .fold(0u64, |acc, p| field_add(acc, p.get_mac_key_share()));
+    assert_eq!(new_key, global_key);
+    for (party, old) in parties.iter().zip(&old_shares) {
+        assert_ne!(party.get_mac_key_share(), *old);
+    }
+    assert_ne!(parties[0].get_hmac_key(1).unwrap().key, old_hmac);
+
+    assert!(parties[0].apply_mac_key_refresh(&[1, 2]).is_err());
+}
+
+#[test]
+fn test_linear_combination_batch_updates_macs() {
+    let params = SPDZParams::new(3, 0, 2);
+    let protocol = SPDZShareProtocol::new(params).unwrap();
+    let mac_key = 987_654_321;
+
+    // 带有效 MAC 的输入：MAC = α·value
+    let shares: Vec<AuthenticatedShare> = [10u64, 20, 30, 40, 50]
+        .iter()
+        .map(|&secret| {
+            let mut share = AuthenticatedShare::new();
+            for (party_id, value) in [(0, secret), (1, secret + 1), (2, secret + 2)] {
+                share.add_share(party_id, SPDZShare::new(value, field_mul(value, mac_key), party_id, 1));
+            }
+            share
+        })
+        .collect();
+    let rows = vec![vec![1, 2, 3, 4, 5], vec![FIELD_PRIME - 1, 0, 0, 0, 1]];
+
+    let results = protocol.linear_combination_batch(&shares, &rows).unwrap();
+    assert_eq!(results.len(), 2);
+    for (result, row) in results.iter().zip(&rows) {
+        assert!(result.verify_all_macs(mac_key));
+        let naive = protocol.linear_combination(&shares, row).unwrap();
+        for party_id in 0..3 {
+            let (fast, slow) = (result.get_share(party_id).unwrap(), naive.get_share(party_id).unwrap());
+            assert_eq!((fast.value, fast.mac), (slow.value, slow.mac));
+        }
+    }
+    assert_eq!(results[0].get_share(0).unwrap().value, 10 + 40 + 90 + 160 + 250);
+    assert_eq!(results[1].get_share(1).unwrap().value, field_sub(51, 11));
+
+    assert!(protocol.linear_combination_batch(&shares, &[vec![1, 2]]).is_err());
+    assert!(protocol.linear_combination_batch(&[], &rows).is_err());
+}
+
+#[test]
+fn test_offline_phase_triples_and_multiplication() {
+    use mpc_api::beaver_triples::OLEBeaverGenerator;
+    use mpc_api::spdz::{PreprocessingDemand, SpdzOfflinePhase, SpdzPreprocessingPool};
+
+    let protocol = SPDZShareProtocol::new(SPDZParams::new(3, 0, 2)).unwrap();
+    let mut offline = SpdzOfflinePhase::new(Box::new(OLEBeaverGenerator::new(3, 2, 0).unwrap()));
+    let mut pool = SpdzPreprocessingPool::new();
+    let stats = offline.fill(&protocol, &mut pool, &PreprocessingDemand::new(4, 0, 2)).unwrap();
+    assert_eq!(stats.preprocessing_consumed, 4);
+    assert_eq!(pool.triples_available(), 4);
+    assert_eq!(pool.input_masks_available(1), 2);
+
+    // 三元组的三个分量都带有效 MAC，且 c = a·b
+    let triple = pool.take_triple().unwrap();
+    let opened = protocol.open_batch(&[triple.a.clone(), triple.b.clone(), triple.c.clone()]).unwrap();
+    assert_eq!(field_mul(opened[0], opened[1]), opened[2]);
+
+    let x = protocol.input_masked(12, pool.take_input_mask(1).unwrap()).unwrap();
+    let y = protocol.input_masked(5, pool.take_input_mask(2).unwrap()).unwrap();
+    let products = protocol.multiply_batch(&[x.clone(), y.clone()], &[y, x], pool.take_triples(2).unwrap()).unwrap();
+    assert_eq!(protocol.open_batch(&products).unwrap(), vec![60, 60]);
+    assert_eq!(pool.triples_available(), 1);
+
+    // 材料用完后返回错误
+    assert!(pool.take_triples(2).is_err());
+    assert_eq!(pool.triples_available(), 1);
+    assert!(pool.take_input_mask(0).is_ok());
+    assert!(pool.take_input_mask(0).is_ok());
+    assert!(pool.take_input_mask(0).is_err());
+    assert!(pool.take_bit().is_err());
+}
+
+#[test]
+fn test_offline_phase_random_bits() {
+    use mpc_api::beaver_triples::TrustedPartyBeaverGenerator;
+    use mpc_api::spdz::SpdzOfflinePhase;
+
+    let protocol = SPDZShareProtocol::new(SPDZParams::new(3, 0, 2)).unwrap();
+    let mut offline = SpdzOfflinePhase::new(Box::new(TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap()));
+    let bits = offline.generate_bits(&protocol, 40).unwrap().result;
+    let values = protocol.open_batch(&bits).unwrap();
+    assert_eq!(values.len(), 40);
+    assert!(values.iter().all(|&bit| bit <= 1));
+    // 全部相同的概率为 2^-39
+    assert!(values.contains(&0) && values.contains(&1));
+
+    // 生成器参数与 SPDZ 参数不一致
+    let mut mismatched = SpdzOfflinePhase::new(Box::new(TrustedPartyBeaverGenerator::new(4, 2, 0, None).unwrap()));
+    assert!(mismatched.generate_triples(&protocol, 1).is_err());
+    assert!(offline.generate_input_masks(&protocol, 3, 1).is_err());
+}
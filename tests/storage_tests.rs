@@ -0,0 +1,316 @@
+use mpc_api::beaver_triples::{
+    BeaverTripleGenerator, FreshnessPolicy, MaterialStamp, PreprocessingPool, StaleReason, TripleFile, TripleFileWriter,
+    TrustedPartyBeaverGenerator,
+};
+use mpc_api::oblivious_transfer::SecurityModel;
+use mpc_api::protocols::session::{ProtocolSession, SessionStore};
+use mpc_api::security::{AuditLogger, SecurityEvent, SecurityLevel, SecurityPolicy, ThreatType};
+use mpc_api::storage::*;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+fn temp_dir(label: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "mpc_api_storage_{}_{}_{}",
+        label,
+        std::process::id(),
+        rand::random::<u64>()
+    ))
+}
+
+fn exercise_backend(backend: &dyn StorageBackend) {
+    assert_eq!(backend.get("a", b"k1").unwrap(), None);
+    backend.put("a", b"k2", b"two").unwrap();
+    backend.put("a", b"k1", b"one").unwrap();
+    backend.put("a", b"", b"empty").unwrap();
+    backend.put("b", b"k1", b"other").unwrap();
+    backend.put("a", b"k1", b"uno").unwrap();
+
+    assert_eq!(backend.get("a", b"k1").unwrap(), Some(b"uno".to_vec()));
+    assert_eq!(backend.get("b", b"k1").unwrap(), Some(b"other".to_vec()));
+    assert_eq!(
+        backend.iterate("a").unwrap(),
+        vec![
+            (b"".to_vec(), b"empty".to_vec()),
+            (b"k1".to_vec(), b"uno".to_vec()),
+            (b"k2".to_vec(), b"two".to_vec()),
+        ]
+    );
+
+    assert!(backend.delete("a", b"k1").unwrap());
+    assert!(!backend.delete("a", b"k1").unwrap());
+    assert_eq!(backend.iterate("a").unwrap().len(), 2);
+    assert!(backend.iterate("missing").unwrap().is_empty());
+    backend.flush().unwrap();
+}
+
+// ===== Backend Tests =====
+
+#[test]
+fn test_memory_backend() {
+    exercise_backend(&MemoryBackend::new());
+}
+
+#[test]
+fn test_file_backend_persists_across_reopen() {
+    let dir = temp_dir("file");
+    exercise_backend(&FileBackend::open(&dir).unwrap());
+
+    let reopened = FileBackend::open(&dir).unwrap();
+    assert_eq!(reopened.get("b", b"k1").unwrap(), Some(b"other".to_vec()));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "sled")]
+#[test]
+fn test_sled_backend() {
+    exercise_backend(&SledBackend::temporary().unwrap());
+}
+
+// ===== Consumer Tests =====
+
+#[test]
+fn test_preprocessing_pool_is_fifo_and_persistent() {
+    let dir = temp_dir("pool");
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    let triples = generator.generate_batch(5).unwrap();
+
+    {
+        let pool = PreprocessingPool::new(Arc::new(FileBackend::open(&dir).unwrap()), "offline");
+        pool.push(&triples, &MaterialStamp::new("dealer", SecurityModel::SemiHonest, 0)).unwrap();
+        let first = pool.take(2).unwrap();
+        assert_eq!(first[0].original_values, triples[0].original_values);
+        assert_eq!(first[1].original_values, triples[1].original_values);
+        assert!(pool.take(4).is_err());
+        assert_eq!(pool.len().unwrap(), 3);
+    }
+
+    let backend = Arc::new(FileBackend::open(&dir).unwrap());
+    let pool = PreprocessingPool::new(backend.clone(), "offline");
+    assert_eq!(pool.len().unwrap(), 3);
+    assert_eq!(pool.take(1).unwrap()[0].original_values, triples[2].original_values);
+    assert!(PreprocessingPool::new(backend, "other").is_empty().unwrap());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_preprocessing_pool_enforces_freshness_policy() {
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    let day = Duration::from_secs(24 * 3600);
+    let old = MaterialStamp::new("dealer-a", SecurityModel::Malicious, 1).with_created_at(SystemTime::now() - 10 * day);
+    let revoked = MaterialStamp::new("dealer-b", SecurityModel::Malicious, 1);
+    let fresh = MaterialStamp::new("dealer-c", SecurityModel::Malicious, 1);
+
+    let logger = Arc::new(AuditLogger::new(SecurityPolicy::medium()));
+    let pool = PreprocessingPool::new(Arc::new(MemoryBackend::new()), "fresh")
+        .with_audit_logger(logger.clone())
+        .with_freshness_policy(FreshnessPolicy::new().with_max_age(7 * day).revoke_generator("dealer-b"));
+    let old_triples = generator.generate_batch(2).unwrap();
+    let fresh_triples = generator.generate_batch(3).unwrap();
+    pool.push(&old_triples, &old).unwrap();
+    pool.push(&generator.generate_batch(2).unwrap(), &revoked).unwrap();
+    pool.push(&fresh_triples, &fresh).unwrap();
+    assert_eq!(pool.len().unwrap(), 7);
+
+    // 过期和已吊销的三元组被跳过并删除
+    let taken = pool.take(1).unwrap();
+    assert_eq!(taken[0].original_values, fresh_triples[0].original_values);
+    assert_eq!(pool.len().unwrap(), 2);
+    let events = logger.get_events_by_type(&ThreatType::StaleMaterial);
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|event| event.is_handled && event.context["count"] == "2"));
+    assert_eq!(events[0].context["generator_id"], "dealer-a");
+
+    // 收紧策略后清理剩余的三元组，清理不影响先进先出顺序
+    pool.push(&generator.generate_batch(1).unwrap(), &MaterialStamp::new("dealer-c", SecurityModel::SemiHonest, 1)).unwrap();
+    pool.push(&generator.generate_batch(1).unwrap(), &MaterialStamp::new("dealer-c", SecurityModel::Malicious, 0)).unwrap();
+    pool.set_freshness_policy(pool.freshness_policy().with_min_epoch(1).with_minimum_model(SecurityModel::Malicious));
+    assert_eq!(pool.purge_stale().unwrap(), 2);
+    assert_eq!(pool.len().unwrap(), 2);
+    assert_eq!(pool.take(2).unwrap()[1].original_values, fresh_triples[2].original_values);
+    assert!(pool.is_empty().unwrap());
+
+    // 新鲜的三元组不足时不取出任何三元组，遇到的过期三元组仍被清理
+    pool.push(&generator.generate_batch(1).unwrap(), &revoked).unwrap();
+    pool.push(&generator.generate_batch(1).unwrap(), &fresh).unwrap();
+    assert!(pool.take(2).is_err());
+    assert_eq!(pool.len().unwrap(), 1);
+    assert_eq!(pool.take(1).unwrap().len(), 1);
+    assert_eq!(logger.get_events_by_type(&ThreatType::StaleMaterial).len(), 5);
+
+    let policy = FreshnessPolicy::new().with_max_age(day);
+    assert_eq!(policy.check(&old, SystemTime::now()), Err(StaleReason::Expired));
+    assert_eq!(policy.check(&fresh, SystemTime::now()), Ok(()));
+}
+
+#[test]
+fn test_session_store_roundtrip() {
+    let store = SessionStore::new(Arc::new(MemoryBackend::new()));
+    let mut root = ProtocolSession::root("spdz", b"parties=3");
+    let child = root.spawn_child("sacrifice");
+
+    store.save(&root).unwrap();
+    store.save(&child).unwrap();
+    assert_eq!(store.load(root.id()).unwrap(), Some(root.clone()));
+    assert_eq!(store.list().unwrap().len(), 2);
+
+    assert!(store.remove(child.id()).unwrap());
+    assert_eq!(store.load(child.id()).unwrap(), None);
+    assert_eq!(store.list().unwrap(), vec![root.id()]);
+}
+
+#[test]
+fn test_audit_logger_persists_events() {
+    let backend: Arc<dyn StorageBackend> = Arc::new(MemoryBackend::new());
+    let logger = AuditLogger::with_storage(SecurityPolicy::medium(), backend.clone()).unwrap();
+    for description in ["first", "second"] {
+        logger.log_event(SecurityEvent::new(
+            ThreatType::ProtocolAttack,
+            SecurityLevel::Low,
+            description.to_string(),
+        )).unwrap();
+    }
+    assert_eq!(logger.persisted_events().unwrap().len(), 2);
+    let (size, root) = logger.log_root();
+
+    // 重新打开后恢复事件和审计日志的根，序列号继续递增
+    let reopened = AuditLogger::with_storage(SecurityPolicy::medium(), backend).unwrap();
+    assert_eq!(reopened.get_events().len(), 2);
+    assert_eq!(reopened.log_root(), (size, root));
+    reopened.log_event(SecurityEvent::new(
+        ThreatType::ProtocolAttack,
+        SecurityLevel::Low,
+        "third".to_string(),
+    )).unwrap();
+    let events = reopened.persisted_events().unwrap();
+    assert_eq!(events[2].description, "third");
+    assert_eq!(events[2].context["sequence"], "3");
+}
+
+// ===== Mapped Artifact Tests =====
+
+#[test]
+fn test_mapped_artifact_layout_never_splits_records() {
+    // 100 字节的记录：每 4096 字节的块放 40 条，剩余 96 字节补零
+    let layout = ArtifactLayout::new(100, 4096, false).unwrap();
+    assert_eq!(layout.records_per_block(), 40);
+    assert_eq!(layout.record_offset(0), 4096);
+    assert_eq!(layout.record_offset(39), 4096 + 39 * 100);
+    assert_eq!(layout.record_offset(40), 2 * 4096);
+    assert_eq!(layout.file_size(41), 3 * 4096);
+
+    // 超过一个块的记录各自占用整数个块
+    let large = ArtifactLayout::new(5000, 4096, true).unwrap();
+    assert_eq!(large.stride(), 5000 + RECORD_TAG_SIZE);
+    assert_eq!(large.block_size(), 2 * 4096);
+    assert_eq!(large.record_offset(3), 4096 + 3 * 2 * 4096);
+
+    assert!(ArtifactLayout::new(0, 4096, false).is_err());
+    assert!(ArtifactLayout::new(8, 3000, false).is_err());
+}
+
+#[test]
+fn test_mapped_artifact_roundtrip_and_encryption() {
+    let dir = temp_dir("mapped");
+    std::fs::create_dir_all(&dir).unwrap();
+    let records: Vec<Vec<u8>> = (0..300u32).map(|i| i.to_be_bytes().repeat(6)).collect();
+
+    for key in [None, Some(&[3u8; 32][..])] {
+        let path = dir.join(format!("artifact-{}", key.is_some()));
+        let mut writer = MappedArtifactWriter::with_alignment(&path, ArtifactKind::Raw, 24, 512, key).unwrap();
+        for record in &records {
+            writer.append(record).unwrap();
+        }
+        assert!(writer.append(&[0u8; 23]).is_err());
+        assert_eq!(writer.finish().unwrap(), 300);
+
+        let artifact = MappedArtifact::open(&path, key).unwrap();
+        assert_eq!(artifact.kind(), ArtifactKind::Raw);
+        assert_eq!(artifact.layout().alignment, 512);
+        assert_eq!(artifact.len(), 300);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), artifact.layout().file_size(300));
+        // 未加密时直接借用映射区，加密时逐条解密
+        assert_eq!(matches!(artifact.record(7).unwrap(), std::borrow::Cow::Borrowed(_)), key.is_none());
+        let read: Vec<Vec<u8>> = artifact.records().map(|record| record.unwrap().into_owned()).collect();
+        assert_eq!(read, records);
+        assert!(artifact.record(300).is_err());
+
+        // 回收页面之后仍可重新读取
+        artifact.release(0..300);
+        assert_eq!(artifact.record(150).unwrap().as_ref(), records[150].as_slice());
+    }
+
+    // 密钥必须与写入时一致
+    let encrypted = dir.join("artifact-true");
+    assert!(MappedArtifact::open(&encrypted, None).is_err());
+    assert!(MappedArtifact::open(&encrypted, Some(&[4u8; 32])).is_err());
+    assert!(MappedArtifact::open(dir.join("artifact-false"), Some(&[3u8; 32])).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_mapped_artifact_detects_tampering_and_unfinished_files() {
+    let dir = temp_dir("mapped_tamper");
+    std::fs::create_dir_all(&dir).unwrap();
+    let key = [5u8; 16];
+
+    let path = dir.join("sealed");
+    let mut writer = MappedArtifactWriter::with_alignment(&path, ArtifactKind::Raw, 32, 256, Some(&key)).unwrap();
+    for i in 0..10u8 {
+        writer.append(&[i; 32]).unwrap();
+    }
+    let layout = writer.layout();
+    writer.finish().unwrap();
+
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes[layout.record_offset(4) as usize] ^= 1;
+    std::fs::write(&path, &bytes).unwrap();
+    let artifact = MappedArtifact::open(&path, Some(&key)).unwrap();
+    assert_eq!(artifact.record(3).unwrap().as_ref(), &[3u8; 32]);
+    assert!(artifact.record(4).is_err());
+
+    // 没有调用 finish 的文件没有文件头
+    let unfinished = dir.join("unfinished");
+    let mut writer = MappedArtifactWriter::create(&unfinished, ArtifactKind::Raw, 8, None).unwrap();
+    writer.append(&[1u8; 8]).unwrap();
+    drop(writer);
+    assert!(MappedArtifact::open(&unfinished, None).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_triple_file_streams_party_shares() {
+    let dir = temp_dir("triple_file");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("party-2.triples");
+    let key = [8u8; 32];
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 1, None).unwrap();
+    let triples = generator.generate_batch(200).unwrap();
+
+    let mut writer = TripleFileWriter::create(&path, Some(&key)).unwrap();
+    for triple in &triples {
+        writer.append(&triple.shares[&2]).unwrap();
+    }
+    assert_eq!(writer.finish().unwrap(), 200);
+
+    let file = TripleFile::open(&path, Some(&key)).unwrap();
+    assert_eq!(file.len(), 200);
+    for (read, triple) in file.iter().zip(&triples) {
+        let read = read.unwrap();
+        let expected = &triple.shares[&2];
+        assert_eq!((&read.a, &read.b, &read.c, read.id), (&expected.a, &expected.b, &expected.c, expected.id));
+    }
+    let middle = file.range(50..53).unwrap();
+    assert_eq!(middle[2].c, triples[52].shares[&2].c);
+    file.release(0..100);
+    assert_eq!(file.get(10).unwrap().a, triples[10].shares[&2].a);
+
+    // 其他类型的文件不能当作三元组文件打开
+    let raw = dir.join("raw");
+    let mut writer = MappedArtifactWriter::create(&raw, ArtifactKind::Raw, 56, None).unwrap();
+    writer.append(&[0u8; 56]).unwrap();
+    writer.finish().unwrap();
+    assert!(TripleFile::open(&raw, None).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -135,4 +135,296 @@ fn test_triple_verification() {
     for beaver_share in triple.shares.values() {
         assert!(beaver_share.is_consistent());
     }
-}
\ No newline at end of file
+}
+#[test]
+fn test_batch_secure_multiply_with_stats() {
+    use mpc_api::beaver_triples::batch_secure_multiply_with_stats;
+
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    let generated = generator.generate_batch_with_stats(3).unwrap();
+    assert_eq!(generated.stats.rounds, 1);
+    assert!(generated.stats.estimated);
+    assert_eq!(generated.stats.preprocessing_consumed, 0);
+
+    let x_batch = vec![
+        ShamirSecretSharing::share(&3, 2, 3).unwrap(),
+        ShamirSecretSharing::share(&5, 2, 3).unwrap(),
+    ];
+    let y_batch = vec![
+        ShamirSecretSharing::share(&4, 2, 3).unwrap(),
+        ShamirSecretSharing::share(&6, 2, 3).unwrap(),
+    ];
+
+    let output = batch_secure_multiply_with_stats(&x_batch, &y_batch, &generated.result, 2).unwrap();
+    assert_eq!(output.stats.rounds, 1);
+    // 只有两个三元组被使用
+    assert_eq!(output.stats.preprocessing_consumed, 2);
+    assert!(!output.stats.estimated);
+    // 3 方各向另外 2 方广播一条消息：长度前缀 8 字节 + 2 次乘法的 (d, e) 分享各 16 字节
+    let message = 8 + 2 * 2 * 16;
+    assert_eq!(output.stats.bytes_sent, 3 * 2 * message);
+    assert_eq!(output.stats.bytes_received, output.stats.bytes_sent);
+    for party in 1..=3 {
+        assert_eq!(output.stats.party(party).bytes_sent, 2 * message);
+        assert_eq!(output.stats.party(party).bytes_received, 2 * message);
+    }
+
+    let product = ShamirSecretSharing::reconstruct(&output.result[1][..2], 2).unwrap();
+    assert_eq!(product, field_mul(5, 6));
+}
+
+#[test]
+fn test_per_party_beaver_multiplication() {
+    use mpc_api::beaver_triples::open_value;
+    use mpc_api::secret_sharing::{field_add, Share};
+
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    let triple = generator.generate_single().unwrap();
+    let x_shares = ShamirSecretSharing::share(&7, 2, 3).unwrap();
+    let y_shares = ShamirSecretSharing::share(&9, 2, 3).unwrap();
+
+    // 每方只用自己的 x_i、y_i 和三元组分享计算 d_i、e_i
+    let masked: Vec<(Share, Share)> = x_shares.iter().zip(&y_shares)
+        .map(|(x, y)| triple.shares[&(x.x as usize)].mask(x, y).unwrap())
+        .collect();
+    let d_shares: Vec<Share> = masked.iter().map(|(d, _)| d.clone()).collect();
+    let e_shares: Vec<Share> = masked.iter().map(|(_, e)| e.clone()).collect();
+    let (d, e) = (open_value(&d_shares, 2).unwrap(), open_value(&e_shares, 2).unwrap());
+
+    let products: Vec<Share> = x_shares.iter()
+        .map(|x| triple.shares[&(x.x as usize)].combine(d, e))
+        .collect();
+    assert_eq!(ShamirSecretSharing::reconstruct(&products[1..], 2).unwrap(), 63);
+    assert_eq!(products, secure_multiply(&x_shares, &y_shares, &triple, 2).unwrap());
+
+    // 分享与三元组不属于同一参与方
+    assert!(triple.shares[&1].mask(&x_shares[1], &y_shares[1]).is_err());
+
+    // 公开时发现不一致的分享
+    let mut tampered = d_shares.clone();
+    tampered[2].y = field_add(tampered[2].y, 1);
+    assert!(open_value(&tampered, 2).is_err());
+    assert!(open_value(&d_shares[..1], 2).is_err());
+}
+
+#[test]
+fn test_dealer_committee_config_requires_honest_majority() {
+    use mpc_api::beaver_triples::distributed_dealer::*;
+
+    assert!(DealerCommitteeConfig::new(3, 1).is_ok());
+    assert!(DealerCommitteeConfig::new(5, 2).is_ok());
+    assert!(DealerCommitteeConfig::new(4, 2).is_err());
+    assert!(DealerCommitteeConfig::new(3, 0).is_err());
+
+    let invalid = DealerCommitteeConfig { committee_size: 2, collusion_threshold: 1 };
+    assert!(TrustedPartyBeaverGenerator::with_dealer_committee(3, 2, 0, None, invalid).is_err());
+}
+
+#[test]
+fn test_distributed_dealer_triples() {
+    use mpc_api::beaver_triples::distributed_dealer::*;
+
+    let committee = DealerCommitteeConfig::new(5, 2).unwrap();
+    let config = TrustedPartyConfig {
+        pool_size: 4,
+        ..TrustedPartyConfig::default()
+    };
+    let mut generator = TrustedPartyBeaverGenerator::with_dealer_committee(3, 2, 0, Some(config), committee).unwrap();
+    assert_eq!(generator.dealer_committee().unwrap().config(), &committee);
+
+    let triples = generator.generate_batch(6).unwrap();
+    for triple in &triples {
+        // 委员会模式下没有任何一方掌握原始值
+        assert!(triple.original_values.is_none());
+        assert!(generator.verify_triple(triple).unwrap());
+
+        let mut shares: Vec<_> = triple.shares.values().cloned().collect();
+        shares.sort_by_key(|t| t.a.x);
+        let a: Vec<_> = shares.iter().map(|t| t.a.clone()).collect();
+        let b: Vec<_> = shares.iter().map(|t| t.b.clone()).collect();
+        let c: Vec<_> = shares.iter().map(|t| t.c.clone()).collect();
+
+        let a_value = ShamirSecretSharing::reconstruct(&a[..2], 2).unwrap();
+        let b_value = ShamirSecretSharing::reconstruct(&b[..2], 2).unwrap();
+        assert_eq!(ShamirSecretSharing::reconstruct(&c[1..], 2).unwrap(), field_mul(a_value, b_value));
+        // 消费方的分享是门限 2 的一致分享
+        assert_eq!(ShamirSecretSharing::reconstruct(&a[1..], 2).unwrap(), a_value);
+    }
+
+    // 消费方使用方式与单一可信方相同
+    let x_shares = ShamirSecretSharing::share(&11, 2, 3).unwrap();
+    let y_shares = ShamirSecretSharing::share(&13, 2, 3).unwrap();
+    let product_shares = secure_multiply(&x_shares, &y_shares, &triples[0], 2).unwrap();
+    assert_eq!(ShamirSecretSharing::reconstruct(&product_shares[..2], 2).unwrap(), 143);
+}
+
+#[test]
+fn test_dealer_member_rejects_bad_messages() {
+    use mpc_api::beaver_triples::distributed_dealer::*;
+
+    let config = DealerCommitteeConfig::new(3, 1).unwrap();
+    assert!(DealerMember::new(config, 0).is_err());
+    assert!(DealerMember::new(config, 4).is_err());
+    let mut members: Vec<_> = (1..=3).map(|member| DealerMember::new(config, member).unwrap()).collect();
+
+    // 没有进行中的三元组时不能重新分享
+    assert!(members[0].reshare(&[]).is_err());
+
+    let outgoing: Vec<_> = members.iter_mut().map(|member| member.contribute(9).unwrap()).collect();
+    assert!(outgoing.iter().all(|messages| messages.len() == 3));
+    let inbox = |recipient: usize| -> Vec<DealerMessage> {
+        outgoing.iter().map(|messages| messages[&recipient].clone()).collect()
+    };
+
+    // 缺少、重复或三元组 ID 不符的消息都被拒绝
+    let mut missing = inbox(1);
+    missing.pop();
+    assert!(members[0].reshare(&missing).is_err());
+    let mut duplicated = inbox(1);
+    duplicated[2] = duplicated[0].clone();
+    assert!(members[0].reshare(&duplicated).is_err());
+    let mut other_triple = members[1].clone();
+    other_triple.contribute(10).unwrap();
+    assert!(other_triple.reshare(&inbox(2)).is_err());
+    // 重新分享之前不能转交
+    assert!(members[2].hand_off(&inbox(3), 2, 3).is_err());
+}
+
+#[cfg(feature = "network")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_dealer_members_over_transport() {
+    use mpc_api::beaver_triples::distributed_dealer::*;
+    use mpc_api::network::{InMemoryTransport, NetworkMessage, Transport};
+    use std::collections::BTreeMap;
+
+    // 参与方 0..3 为委员会成员（x 坐标 1..=3），3..6 为消费方（x 坐标 1..=3）
+    const MEMBERS: usize = 3;
+    const CONSUMERS: usize = 3;
+    let config = DealerCommitteeConfig::new(MEMBERS, 1).unwrap();
+
+    /// 收齐 `count` 条 `wanted` 类型的委员会消息，提前到达的其他轮次消息留在 `later` 中
+    async fn receive(
+        transport: &InMemoryTransport,
+        later: &mut Vec<DealerMessage>,
+        count: usize,
+        wanted: fn(&DealerMessage) -> bool,
+    ) -> Vec<DealerMessage> {
+        let mut received: Vec<DealerMessage> = Vec::new();
+        later.retain(|message| if wanted(message) { received.push(message.clone()); false } else { true });
+        while received.len() < count {
+            let (_, message) = transport.recv().await.unwrap();
+            let message = DealerMessage::from_network_message(&message).unwrap();
+            if wanted(&message) { received.push(message) } else { later.push(message) }
+        }
+        received
+    }
+
+    async fn send_all(transport: &InMemoryTransport, outgoing: BTreeMap<usize, DealerMessage>, offset: usize) -> Vec<DealerMessage> {
+        let mut own = Vec::new();
+        for (x, message) in outgoing {
+            let to = x - 1 + offset;
+            if to == transport.party_id() {
+                own.push(message);
+            } else {
+                let wire: NetworkMessage = message.to_network_message().unwrap();
+                transport.send(to, wire).await.unwrap();
+            }
+        }
+        own
+    }
+
+    let mut transports = InMemoryTransport::network(MEMBERS + CONSUMERS);
+    let consumers: Vec<_> = transports.split_off(MEMBERS).into_iter()
+        .map(|transport| tokio::spawn(async move {
+            let mut later = Vec::new();
+            let hand_offs = receive(&transport, &mut later, MEMBERS, |m| matches!(m, DealerMessage::HandOff { .. })).await;
+            config.combine_hand_offs(&hand_offs).unwrap()
+        }))
+        .collect();
+
+    // 每个成员在自己的任务中只持有自己的状态
+    let members: Vec<_> = transports.into_iter()
+        .enumerate()
+        .map(|(index, transport)| tokio::spawn(async move {
+            let mut member = DealerMember::new(config, index + 1).unwrap();
+            let mut later = Vec::new();
+            let mut inbox = send_all(&transport, member.contribute(42).unwrap(), 0).await;
+            inbox.extend(receive(&transport, &mut later, MEMBERS - 1, |m| matches!(m, DealerMessage::Contribute { .. })).await);
+            let mut inbox_2 = send_all(&transport, member.reshare(&inbox).unwrap(), 0).await;
+            inbox_2.extend(receive(&transport, &mut later, MEMBERS - 1, |m| matches!(m, DealerMessage::Reshare { .. })).await);
+            send_all(&transport, member.hand_off(&inbox_2, 2, CONSUMERS).unwrap(), MEMBERS).await;
+        }))
+        .collect();
+    for member in members {
+        member.await.unwrap();
+    }
+
+    let mut triples = Vec::new();
+    for consumer in consumers {
+        triples.push(consumer.await.unwrap());
+    }
+    assert!(triples.iter().all(|triple| triple.id == 42));
+    let a: Vec<_> = triples.iter().map(|t| t.a.clone()).collect();
+    let b: Vec<_> = triples.iter().map(|t| t.b.clone()).collect();
+    let c: Vec<_> = triples.iter().map(|t| t.c.clone()).collect();
+    let a_value = ShamirSecretSharing::reconstruct(&a[..2], 2).unwrap();
+    let b_value = ShamirSecretSharing::reconstruct(&b[1..], 2).unwrap();
+    assert_eq!(ShamirSecretSharing::reconstruct(&c[..2], 2).unwrap(), field_mul(a_value, b_value));
+}
+
+#[test]
+fn test_triple_correctness_proofs() {
+    use mpc_api::beaver_triples::distributed_dealer::*;
+
+    let mut generator = TrustedPartyBeaverGenerator::new(3, 2, 0, None).unwrap();
+    let (triple, first, blindings) = generator.generate_single_with_proof().unwrap();
+    assert!(first.verify());
+    assert!(triple.shares.values().all(|share| share.id == first.triple_id));
+
+    // 每一方只用自己的份额和盲化值检查份额与承诺一致
+    for (party, share) in &triple.shares {
+        assert!(first.verify_share(share, &blindings[party]));
+    }
+    let shares: Vec<_> = triple.shares.values().cloned().collect();
+    let a: Vec<_> = shares.iter().map(|t| t.a.clone()).collect();
+    let b: Vec<_> = shares.iter().map(|t| t.b.clone()).collect();
+    let c: Vec<_> = shares.iter().map(|t| t.c.clone()).collect();
+    let (a_value, b_value) = (
+        ShamirSecretSharing::reconstruct(&a[..2], 2).unwrap(),
+        ShamirSecretSharing::reconstruct(&b[..2], 2).unwrap(),
+    );
+    assert_eq!(ShamirSecretSharing::reconstruct(&c[1..], 2).unwrap(), field_mul(a_value, b_value));
+
+    // 错误的三元组无法生成证明
+    assert!(TripleProof::deal(7, (3, 5, 16), 2, 3).is_err());
+    let (second_shares, second, second_blindings) = TripleProof::deal(7, (3, 5, field_mul(3, 5)), 2, 3).unwrap();
+    assert!(second.verify());
+
+    // 证明与三元组 ID 和承诺绑定
+    let mut moved = first.clone();
+    moved.triple_id = second.triple_id;
+    assert!(!moved.verify());
+    let mut swapped = first.clone();
+    swapped.c = second.c.clone();
+    assert!(!swapped.verify());
+    let mut truncated = first.clone();
+    truncated.b.commitments.pop();
+    assert!(!truncated.verify());
+
+    // 份额与另一个三元组的证明不符：可信方不能给出畸形份额配上别的三元组的有效证明
+    assert!(!first.verify_share(&second_shares.shares[&1], &second_blindings[&1]));
+    let mut tampered = triple.shares[&1].clone();
+    tampered.c.y = field_mul(tampered.c.y, 2);
+    assert!(!first.verify_share(&tampered, &blindings[&1]));
+
+    let bytes = bincode::serialize(&first).unwrap();
+    let decoded: TripleProof = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(decoded, first);
+    assert!(generator.generate_batch_with_proofs(0).unwrap().is_empty());
+
+    // 委员会模式下没有单一可信方知道三元组
+    let config = TrustedPartyConfig { enable_precomputation: false, ..TrustedPartyConfig::default() };
+    let committee = DealerCommitteeConfig::new(3, 1).unwrap();
+    let mut generator = TrustedPartyBeaverGenerator::with_dealer_committee(3, 2, 0, Some(config), committee).unwrap();
+    assert!(generator.generate_single_with_proof().is_err());
+}
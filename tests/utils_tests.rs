@@ -69,4 +69,591 @@ fn test_memory_stats() {
     assert!(stats.secure_buffers > 0);
     assert!(stats.secure_bytes >= 1024);
     assert!(stats.page_size > 0);
-}
\ No newline at end of file
+}
+#[test]
+fn test_reed_solomon_any_k_shards() {
+    use mpc_api::utils::erasure::ReedSolomon;
+
+    let coder = ReedSolomon::new(3, 5).unwrap();
+    let data: Vec<u8> = (0..100u8).collect();
+    let shards = coder.encode(&data);
+    assert_eq!(shards.len(), 5);
+
+    // 任意 3 个分片都可以恢复
+    let subset: Vec<(usize, Vec<u64>)> = vec![
+        (4, shards[4].clone()),
+        (1, shards[1].clone()),
+        (3, shards[3].clone()),
+    ];
+    assert_eq!(coder.decode(&subset, data.len()).unwrap(), data);
+
+    // 分片不足
+    assert!(coder.decode(&subset[..2], data.len()).is_err());
+    assert!(ReedSolomon::new(4, 3).is_err());
+}
+
+#[test]
+fn test_canonical_hash_is_layout_stable() {
+    use mpc_api::utils::canonical::*;
+    use serde::Serialize;
+    use std::collections::HashMap;
+
+    #[derive(Serialize)]
+    struct Header { session: String, round: u32, party: usize }
+
+    #[derive(Serialize)]
+    struct Reordered { party: u64, round: u32, session: String }
+
+    let header = Header { session: "s".to_string(), round: 2, party: 1 };
+    let reordered = Reordered { party: 1, round: 2, session: "s".to_string() };
+    assert_eq!(hash_struct(&header).unwrap(), hash_struct(&reordered).unwrap());
+
+    // 映射的编码与插入顺序无关
+    let forward: HashMap<u64, u64> = (0..32).map(|i| (i, i + 1)).collect();
+    let backward: HashMap<u64, u64> = (0..32).rev().map(|i| (i, i + 1)).collect();
+    assert_eq!(canonical_encode(&forward).unwrap(), canonical_encode(&backward).unwrap());
+
+    // 长度前缀消除拼接歧义，域标签区分用途
+    assert_ne!(hash_struct(&("ab", "c")).unwrap(), hash_struct(&("a", "bc")).unwrap());
+    assert_ne!(hash_struct(&header).unwrap(), hash_struct_with_domain(b"other", &header).unwrap());
+
+    // 固定宽度小端序编码
+    assert_eq!(canonical_encode(&(1u64, 7usize)).unwrap(), [1u64.to_le_bytes(), 7u64.to_le_bytes()].concat());
+}
+
+#[test]
+fn test_transcript_field_challenges() {
+    use mpc_api::secret_sharing::FIELD_PRIME;
+    use mpc_api::utils::Transcript;
+
+    let mut prover = Transcript::new(b"mac-check");
+    prover.append_serialized(b"opened", &vec![1u64, 2, 3]).unwrap();
+    let mut verifier = prover.clone();
+    let coefficients = prover.challenge_fields(b"r", 16);
+    assert_eq!(coefficients, verifier.challenge_fields(b"r", 16));
+    assert!(coefficients.iter().all(|&r| r < FIELD_PRIME));
+    assert_eq!(coefficients.len(), 16);
+
+    // 不同的已打开值、数量或后续状态得到不同的挑战
+    let mut other = Transcript::new(b"mac-check");
+    other.append_serialized(b"opened", &vec![1u64, 2, 4]).unwrap();
+    assert_ne!(other.challenge_fields(b"r", 16), coefficients);
+    let mut fresh = Transcript::new(b"mac-check");
+    fresh.append_serialized(b"opened", &vec![1u64, 2, 3]).unwrap();
+    assert_ne!(fresh.challenge_fields(b"r", 15)[..], coefficients[..15]);
+    assert_ne!(prover.challenge_field(b"r"), verifier.challenge_fields(b"r", 1)[0]);
+}
+
+#[test]
+fn test_secret_value_redaction() {
+    use mpc_api::authentication::{MessageAuthenticationCode, HMAC};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct Persisted {
+        #[serde(with = "mpc_api::utils::memory::expose_secret")]
+        labels: SecretValue<Vec<[u8; 16]>>,
+    }
+
+    let secret = SecretValue::new(vec![0x5au64; 4]);
+    assert_eq!(secret.iter().sum::<u64>(), 4 * 0x5a);
+    assert!(!format!("{:?}", secret).contains("90"));
+    assert_eq!(serde_json::to_string(&secret).unwrap(), "\"<redacted>\"");
+
+    // 显式序列化可以往返，默认的脱敏输出不能被当作秘密读回
+    let persisted = Persisted { labels: SecretValue::new(vec![[1; 16], [2; 16]]) };
+    let bytes = bincode::serialize(&persisted).unwrap();
+    let restored: Persisted = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(restored.labels, persisted.labels);
+    let redacted = bincode::serialize(&persisted.labels).unwrap();
+    assert!(bincode::deserialize::<SecretValue<[u8; 32]>>(&redacted).is_err());
+
+    // HMAC 密钥默认不会被序列化出去
+    let key = HMAC::generate_key();
+    assert!(serde_json::to_string(&key).unwrap().contains("<redacted>"));
+
+    let mut values = (vec![7u8; 8], [9u64; 2]);
+    values.zeroize();
+    assert!(values.0.is_empty());
+    assert_eq!(values.1, [0, 0]);
+}
+
+#[test]
+fn test_crypto_backends() {
+    use aes::cipher::{BlockEncrypt, KeyInit};
+    use mpc_api::utils::crypto::*;
+    use rand::{Rng, RngCore};
+
+    // AES 计数器模式的输出就是依次加密计数器 IV, IV + 1, ...
+    let mut seed = [0u8; 32];
+    seed[..16].copy_from_slice(&[0x2b; 16]);
+    seed[16..].copy_from_slice(&u128::MAX.to_le_bytes());
+    let cipher = aes::Aes128::new(&[0x2b; 16].into());
+    let mut expected = Vec::new();
+    for counter in [u128::MAX, 0, 1] {
+        let mut block = counter.to_le_bytes().into();
+        cipher.encrypt_block(&mut block);
+        expected.extend_from_slice(&block);
+    }
+    let mut output = vec![0u8; 48];
+    AesCtrPrg::from_seed(seed).fill_bytes(&mut output);
+    assert_eq!(output, expected);
+
+    // 分段读取与一次读取的结果相同，跨越批量加密的边界也一样
+    let mut whole = vec![0u8; 300];
+    AesCtrPrg::from_seed([3; 32]).fill_bytes(&mut whole);
+    let mut prg = AesCtrPrg::from_seed([3; 32]);
+    let mut pieces = Vec::new();
+    for len in [1, 15, 17, 100, 127, 40] {
+        let mut piece = vec![0u8; len];
+        prg.fill_bytes(&mut piece);
+        pieces.extend(piece);
+    }
+    assert_eq!(pieces, whole);
+
+    let mut first = Blake3Prg::from_seed([3; 32]);
+    let mut second = first.clone();
+    assert_eq!(first.gen::<[u64; 8]>(), second.gen::<[u64; 8]>());
+    assert_ne!(Blake3Prg::from_seed([4; 32]).next_u64(), Blake3Prg::from_seed([3; 32]).next_u64());
+
+    // 不同标签派生的子种子互不相同
+    assert_ne!(derive_seed(&[3; 32], b"garbling"), derive_seed(&[3; 32], b"sharing"));
+    assert_eq!(AesCtrPrg::derive(&[3; 32], b"ot").next_u64(), AesCtrPrg::from_seed(derive_seed(&[3; 32], b"ot")).next_u64());
+
+    // 哈希与标准实现一致，长度前缀区分不同的划分
+    use sha2::Digest;
+    assert_eq!(Sha256Hash::digest(b"abc"), <[u8; 32]>::from(sha2::Sha256::digest(b"abc")));
+    assert_eq!(Blake3Hash::digest(b"abc"), *blake3::hash(b"abc").as_bytes());
+    assert_ne!(Blake3Hash::digest_parts(&[b"ab", b"c"]), Blake3Hash::digest_parts(&[b"a", b"bc"]));
+}
+
+#[test]
+fn test_cancellation_token_hierarchy() {
+    use mpc_api::utils::concurrency::CancellationToken;
+    use std::time::Duration;
+
+    let root = CancellationToken::new();
+    let child = root.child_token();
+    let grandchild = child.child_token();
+    let sibling = root.child_token();
+
+    child.cancel("first");
+    child.cancel("second");
+    assert!(child.is_cancelled() && grandchild.is_cancelled());
+    assert!(!root.is_cancelled() && !sibling.is_cancelled());
+    assert_eq!(grandchild.reason().as_deref(), Some("first"));
+    assert!(grandchild.check().unwrap_err().to_string().contains("first"));
+    assert!(grandchild.wait_timeout(Duration::from_secs(30)));
+    assert!(!sibling.wait_timeout(Duration::from_millis(1)));
+
+    // 等待中的线程在取消时及时醒来
+    let waiter = {
+        let sibling = sibling.clone();
+        std::thread::spawn(move || sibling.wait_timeout(Duration::from_secs(30)))
+    };
+    root.cancel("shutdown");
+    assert!(waiter.join().unwrap());
+    assert!(root.child_token().is_cancelled());
+}
+
+#[test]
+fn test_task_scope_cancels_siblings_on_failure() {
+    use mpc_api::utils::concurrency::{task_scope, CancellationToken, TaskGroup};
+    use mpc_api::MpcError;
+    use std::time::{Duration, Instant};
+
+    let root = CancellationToken::new();
+    let inputs: Vec<u64> = (0..16).collect();
+    let squares = task_scope(&root, |group| {
+        for value in &inputs {
+            group.spawn(move |_| Ok(value * value));
+        }
+        Ok(())
+    }).unwrap();
+    assert_eq!(squares, inputs.iter().map(|v| v * v).collect::<Vec<_>>());
+
+    // 一批失败时，其余 15 批不会等满 30 秒，返回的是根本原因而不是取消错误
+    let started = Instant::now();
+    let result: mpc_api::Result<Vec<u64>> = task_scope(&root, |group| {
+        for &value in &inputs {
+            group.spawn(move |token| {
+                if value == 7 {
+                    std::thread::sleep(Duration::from_millis(20));
+                    return Err(MpcError::NetworkError("peer 2 disconnected".to_string()));
+                }
+                token.wait_timeout(Duration::from_secs(30));
+                token.check()?;
+                Ok(value)
+            });
+        }
+        Ok(())
+    });
+    assert!(matches!(result, Err(MpcError::NetworkError(_))));
+    assert!(started.elapsed() < Duration::from_secs(10));
+    assert!(!root.is_cancelled());
+
+    // panic 同样取消兄弟任务
+    let result: mpc_api::Result<Vec<()>> = task_scope(&root, |group| {
+        group.spawn(|_| panic!("boom"));
+        group.spawn(|token| {
+            token.wait_timeout(Duration::from_secs(30));
+            token.check()
+        });
+        Ok(())
+    });
+    assert!(result.unwrap_err().to_string().contains("panicked"));
+
+    // 父令牌已取消时不启动任务
+    let cancelled = root.child_token();
+    cancelled.cancel("stopped");
+    assert!(task_scope(&cancelled, |group| {
+        group.spawn(|_| Ok(1));
+        Ok(())
+    }).is_err());
+
+    // 后台任务组被丢弃时取消并等待子任务
+    let mut group = TaskGroup::new("test-group", &root);
+    let observed = group.token().clone();
+    group.spawn(|token| {
+        token.wait_timeout(Duration::from_secs(30));
+        token.check()
+    }).unwrap();
+    assert_eq!(group.len(), 1);
+    drop(group);
+    assert!(observed.is_cancelled());
+    assert!(!root.is_cancelled());
+}
+
+#[test]
+fn test_constant_time_math() {
+    use mpc_api::utils::{
+        constant_time_is_zero, constant_time_less_than, constant_time_mod_inverse, constant_time_pow_mod,
+        constant_time_select, constant_time_swap,
+    };
+
+    assert_eq!(constant_time_select(3, 9, 0), 3);
+    assert_eq!(constant_time_select(3, 9, 1), 9);
+    let (mut a, mut b) = (u64::MAX, 7);
+    constant_time_swap(&mut a, &mut b, 0);
+    assert_eq!((a, b), (u64::MAX, 7));
+    constant_time_swap(&mut a, &mut b, 1);
+    assert_eq!((a, b), (7, u64::MAX));
+    assert_eq!(constant_time_is_zero(0), 1);
+    assert_eq!(constant_time_is_zero(1 << 63), 0);
+    assert_eq!(constant_time_less_than(1, u64::MAX), 1);
+    assert_eq!(constant_time_less_than(u64::MAX, u64::MAX), 0);
+
+    let prime = 18446744069414584321u64;
+    assert_eq!(constant_time_pow_mod(2, 0, prime), 1);
+    assert_eq!(constant_time_pow_mod(2, 64, prime), (1 << 32) - 1);
+    assert_eq!(constant_time_pow_mod(5, 3, 1), 0);
+    for a in [1u64, 2, 97, prime - 1] {
+        let inverse = constant_time_mod_inverse(a, prime).unwrap();
+        assert_eq!((a as u128 * inverse as u128 % prime as u128) as u64, 1);
+    }
+    assert_eq!(constant_time_mod_inverse(0, 97), None);
+    assert_eq!(constant_time_mod_inverse(194, 97), None);
+}
+
+#[test]
+fn test_binary_extension_fields() {
+    use mpc_api::authentication::GMAC;
+    use mpc_api::utils::math::gf2k::*;
+    use rand::Rng;
+
+    // 逐位移位相加的参考实现
+    fn reference_mul(mut a: u128, mut b: u128) -> u128 {
+        let mut result = 0;
+        while b != 0 {
+            if b & 1 == 1 {
+                result ^= a;
+            }
+            a = (a << 1) ^ if a >> 127 == 1 { 0x87 } else { 0 };
+            b >>= 1;
+        }
+        result
+    }
+
+    let mut rng = rand::thread_rng();
+    for _ in 0..50 {
+        let (a, b, c): (u128, u128, u128) = (rng.gen(), rng.gen(), rng.gen());
+        assert_eq!(gf128_mul(a, b), reference_mul(a, b));
+        assert_eq!(GMAC::gf128_mul(a, b), reference_mul(a, b));
+        assert_eq!(gf128_mul(a, gf128_add(b, c)), gf128_add(gf128_mul(a, b), gf128_mul(a, c)));
+        assert_eq!(gf128_double(a), gf128_mul(a, 2));
+        if a != 0 {
+            assert_eq!(gf128_mul(a, gf128_inv(a).unwrap()), 1);
+        }
+
+        let (x, y, z): (u64, u64, u64) = (rng.gen(), rng.gen(), rng.gen());
+        assert_eq!(gf64_mul(x, y), gf64_mul(y, x));
+        assert_eq!(gf64_mul(gf64_mul(x, y), z), gf64_mul(x, gf64_mul(y, z)));
+        if x != 0 {
+            assert_eq!(gf64_mul(x, gf64_inv(x).unwrap()), 1);
+        }
+    }
+
+    // x^127·x = x^128 ≡ x^7 + x^2 + x + 1，x^63·x = x^64 ≡ x^4 + x^3 + x + 1
+    assert_eq!(gf128_mul(1 << 127, 2), GF128_MODULUS_LOW);
+    assert_eq!(gf64_mul(1 << 63, 2), GF64_MODULUS_LOW);
+    assert_eq!(clmul128(u128::MAX, 1), (u128::MAX, 0));
+    assert_eq!(gf128_inv(0), None);
+    assert_eq!(gf64_inv(0), None);
+}
+
+#[test]
+fn test_ntt_polynomial_arithmetic() {
+    use mpc_api::secret_sharing::FIELD_PRIME;
+    use mpc_api::utils::math::ntt::*;
+    use rand::Rng;
+
+    let mut rng = rand::thread_rng();
+    for modulus in [12289, 0xff_ffe8_0001, FIELD_PRIME] {
+        let a: Vec<u64> = (0..300).map(|_| rng.gen_range(0..modulus)).collect();
+        let b: Vec<u64> = (0..200).map(|_| rng.gen_range(0..modulus)).collect();
+        assert_eq!(polynomial_multiply_mod(&a, &b, modulus).unwrap(), schoolbook_multiply(&a, &b, modulus));
+
+        // 负循环卷积：把线性乘积的 x^(n+i) 项以 -1 折回 x^i
+        let plan = NttPlan::new(modulus, 512).unwrap();
+        let linear = schoolbook_multiply(&a, &b, modulus);
+        let mut expected = vec![0u64; 512];
+        for (i, &c) in linear.iter().enumerate() {
+            expected[i % 512] = if i < 512 { (expected[i] + c) % modulus } else { (expected[i - 512] + modulus - c) % modulus };
+        }
+        assert_eq!(plan.negacyclic_multiply(&a, &b).unwrap(), expected);
+
+        let mut values = a.clone();
+        values.resize(512, 0);
+        let original = values.clone();
+        plan.forward(&mut values).unwrap();
+        plan.inverse(&mut values).unwrap();
+        assert_eq!(values, original);
+
+        // 子积树求值与逐点霍纳法一致
+        let points: Vec<u64> = (0..600).map(|_| rng.gen_range(0..modulus)).collect();
+        let horner: Vec<u64> = points.iter()
+            .map(|&x| a.iter().rev().fold(0u64, |acc, &c| ((acc as u128 * x as u128 + c as u128) % modulus as u128) as u64))
+            .collect();
+        assert_eq!(multipoint_evaluate(&a, &points, modulus).unwrap(), horner);
+    }
+
+    // 1024 不是素数，12289 - 1 只含 2^12
+    assert!(NttPlan::new(1024, 8).is_err());
+    assert!(NttPlan::new(12289, 1 << 12).is_err());
+    assert!(NttPlan::new(12289, 6).is_err());
+}
+
+#[test]
+fn test_metrics_registry_exposition() {
+    use mpc_api::utils::metrics::*;
+    use std::time::Duration;
+
+    let registry = MetricsRegistry::new();
+    let rounds = registry.counter_with_labels("mpc_rounds_total", "rounds", &[("protocol", "spdz")]).unwrap();
+    rounds.inc_by(3);
+    // 同名同标签返回同一个计数器，标签顺序无关
+    registry.counter_with_labels("mpc_rounds_total", "rounds", &[("protocol", "spdz")]).unwrap().inc();
+    assert_eq!(rounds.get(), 4);
+    assert!(registry.gauge("mpc_rounds_total", "rounds").is_err());
+    assert!(registry.counter("2bad-name", "").is_err());
+    assert!(registry.histogram("mpc_bad_buckets", "", &[1.0, 0.5]).is_err());
+    assert!(registry.histogram_with_labels("mpc_latency", "", DEFAULT_LATENCY_BUCKETS, &[("le", "1")]).is_err());
+
+    let connections = registry.gauge("mpc_connections", "open \"connections\"\nnow").unwrap();
+    connections.set(5.0);
+    connections.add(-2.0);
+    assert_eq!(connections.get(), 3.0);
+
+    let latency = registry.histogram("mpc_latency_seconds", "latency", &[0.25, 1.0]).unwrap();
+    latency.observe_duration(Duration::from_millis(250));
+    latency.observe(0.125);
+    latency.observe(0.5);
+    latency.observe(7.0);
+    assert_eq!(latency.count(), 4);
+    assert_eq!(latency.cumulative_buckets(), vec![(0.25, 2), (1.0, 3)]);
+
+    registry.register_collector(|samples| {
+        samples.push(MetricSample::gauge("mpc_collected", "collected", 1.5).with_label("party", "p\"0"));
+        // 与已注册指标类型冲突的样本被跳过
+        samples.push(MetricSample::gauge("mpc_rounds_total", "rounds", 99.0));
+    });
+
+    let text = registry.render();
+    let expected = [
+        "# HELP mpc_collected collected",
+        "# TYPE mpc_collected gauge",
+        "mpc_collected{party=\"p\\\"0\"} 1.5",
+        "# HELP mpc_connections open \"connections\"\\nnow",
+        "# TYPE mpc_connections gauge",
+        "mpc_connections 3",
+        "# HELP mpc_latency_seconds latency",
+        "# TYPE mpc_latency_seconds histogram",
+        "mpc_latency_seconds_bucket{le=\"0.25\"} 2",
+        "mpc_latency_seconds_bucket{le=\"1\"} 3",
+        "mpc_latency_seconds_bucket{le=\"+Inf\"} 4",
+        "mpc_latency_seconds_sum 7.875",
+        "mpc_latency_seconds_count 4",
+        "# HELP mpc_rounds_total rounds",
+        "# TYPE mpc_rounds_total counter",
+        "mpc_rounds_total{protocol=\"spdz\"} 4",
+    ];
+    assert_eq!(text, expected.join("\n") + "\n");
+}
+
+#[test]
+fn test_wire_format_golden_vectors() {
+    use mpc_api::beaver_triples::BeaverTriple;
+    use mpc_api::secret_sharing::Share;
+    use mpc_api::utils::serialization::*;
+
+    // 版本 1 的编码一经发布就不能改变
+    let share = Share::new(3, 0x0102);
+    let golden: &[u8] = &[
+        0x01, 0x01, 0x00,
+        0x01, 0x08, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x08, 0x00, 0x00, 0x00, 0x02, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+    assert_eq!(share.to_wire(), golden);
+    assert_eq!(Share::from_wire(golden).unwrap(), share);
+
+    let triple = BeaverTriple { a: Share::new(1, 2), b: Share::new(1, 3), c: Share::new(1, 6), id: 9 };
+    let bytes = triple.to_wire();
+    assert_eq!(&bytes[..3], &[0x01, 0x04, 0x00]);
+    // 嵌套的分享只编码字段，不带消息头
+    assert_eq!(&bytes[3..8], &[0x01, 26, 0, 0, 0]);
+    assert_eq!(&bytes[8..34], &Share::new(1, 2).to_wire()[3..]);
+    let decoded = BeaverTriple::from_wire(&bytes).unwrap();
+    assert_eq!((decoded.a, decoded.b, decoded.c, decoded.id), (triple.a, triple.b, triple.c, 9));
+}
+
+#[test]
+fn test_wire_format_protocol_messages() {
+    use mpc_api::commitment::MessageCommitment;
+    use mpc_api::elliptic_curve::Secp256k1Point;
+    use mpc_api::garbled_circuits::{Circuit, Garbler, GarbledCircuit};
+    use mpc_api::network::protocol::{BroadcastMessage, BroadcastPhase, NetworkMessage};
+    use mpc_api::protocols::clock::MessageHeader;
+    use mpc_api::protocols::session::SessionId;
+    use mpc_api::secret_sharing::AdditiveShare;
+    use mpc_api::spdz::SPDZShare;
+    use mpc_api::utils::serialization::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn roundtrip<T: WireDeserialize>(value: &T) -> T {
+        let bytes = value.to_wire();
+        let decoded = T::from_wire(&bytes).unwrap();
+        assert_eq!(decoded.to_wire(), bytes);
+        decoded
+    }
+
+    assert_eq!(roundtrip(&AdditiveShare::new(2, 77)), AdditiveShare::new(2, 77));
+    let spdz = roundtrip(&SPDZShare::new(5, 6, 1, 42));
+    assert_eq!((spdz.value, spdz.mac, spdz.party_id, spdz.share_id), (5, 6, 1, 42));
+
+    let header = MessageHeader {
+        session: SessionId([7; 32]),
+        round: 4,
+        sent_at: UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789),
+    };
+    assert_eq!(roundtrip(&header), header);
+    for commitment in [MessageCommitment::Hash([9; 32]), MessageCommitment::Pedersen(Secp256k1Point::generator())] {
+        assert_eq!(roundtrip(&commitment), commitment);
+    }
+
+    let broadcast = BroadcastMessage {
+        origin: "p0".to_string(),
+        tag: "votes".to_string(),
+        phase: BroadcastPhase::Ready,
+        value: vec![1, 2, 3],
+    };
+    assert_eq!(roundtrip(&broadcast), broadcast);
+
+    // 消息头的插入顺序不影响编码
+    let message = (0..8).fold(
+        NetworkMessage::new("share", b"payload").with_sender("0".to_string()).with_round(3),
+        |message, i| message.with_header(format!("h{}", i), i.to_string()),
+    );
+    let mut reordered = message.clone();
+    let mut headers: Vec<_> = message.headers.clone().into_iter().collect();
+    headers.sort();
+    reordered.headers = headers.into_iter().rev().collect();
+    assert_eq!(message.to_wire(), reordered.to_wire());
+    let decoded = roundtrip(&message);
+    assert_eq!(decoded.round().unwrap(), Some(3));
+    assert_eq!(decoded.timestamp, message.timestamp);
+    assert_eq!((decoded.id, decoded.sender_id, decoded.receiver_id), (message.id, Some("0".to_string()), None));
+
+    let mut circuit = Circuit::new();
+    let (a, b) = (circuit.add_input_wire(), circuit.add_input_wire());
+    let and = circuit.and_gate(a, b);
+    let out = circuit.xor_gate(and, a);
+    circuit.add_output_wire(out);
+    let garbled = Garbler::new().garble_circuit(&circuit).unwrap();
+    assert_eq!(roundtrip(&garbled), garbled);
+    assert!(GarbledCircuit::from_wire(&header.to_wire()).is_err());
+}
+
+#[test]
+fn test_wire_format_compatibility() {
+    use mpc_api::secret_sharing::Share;
+    use mpc_api::utils::serialization::*;
+
+    /// 版本演进的模拟：第二版增加了字段 2
+    #[derive(Debug, PartialEq)]
+    struct ReceiptV1 {
+        id: u64,
+    }
+    #[derive(Debug, PartialEq)]
+    struct ReceiptV2 {
+        id: u64,
+        retries: u32,
+    }
+    impl WireSerialize for ReceiptV1 {
+        const WIRE_TYPE: u16 = 0x7f00;
+        fn write_fields(&self, writer: &mut WireWriter) {
+            writer.field(1, &self.id);
+        }
+    }
+    impl WireDeserialize for ReceiptV1 {
+        fn read_fields(reader: &WireReader<'_>) -> mpc_api::Result<Self> {
+            Ok(ReceiptV1 { id: reader.field(1)? })
+        }
+    }
+    impl WireSerialize for ReceiptV2 {
+        const WIRE_TYPE: u16 = 0x7f00;
+        fn write_fields(&self, writer: &mut WireWriter) {
+            writer.field(1, &self.id).field(2, &self.retries);
+        }
+    }
+    impl WireDeserialize for ReceiptV2 {
+        fn read_fields(reader: &WireReader<'_>) -> mpc_api::Result<Self> {
+            Ok(ReceiptV2 { id: reader.field(1)?, retries: reader.field_or(2, 0)? })
+        }
+    }
+
+    // 新代码读取旧数据时使用默认值，旧代码读取新数据时跳过不认识的字段
+    let old = ReceiptV1 { id: 5 }.to_wire();
+    assert_eq!(ReceiptV2::from_wire(&old).unwrap(), ReceiptV2 { id: 5, retries: 0 });
+    let new = ReceiptV2 { id: 5, retries: 3 }.to_wire();
+    assert_eq!(ReceiptV1::from_wire(&new).unwrap(), ReceiptV1 { id: 5 });
+
+    let bytes = Share::new(1, 2).to_wire();
+    // 不支持的版本和错误的类型被拒绝
+    let mut future = bytes.clone();
+    future[0] = WIRE_VERSION + 1;
+    assert!(Share::from_wire(&future).is_err());
+    assert!(ReceiptV1::from_wire(&bytes).is_err());
+    // 截断、缺少字段、字段乱序都被拒绝
+    assert!(Share::from_wire(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Share::from_wire(&bytes[..16]).is_err());
+    let mut swapped = bytes[..3].to_vec();
+    swapped.extend_from_slice(&bytes[16..]);
+    swapped.extend_from_slice(&bytes[3..16]);
+    assert!(Share::from_wire(&swapped).is_err());
+    // 定长字段的长度必须准确
+    let mut long_field = bytes[..3].to_vec();
+    long_field.extend_from_slice(&[0x01, 0x09, 0, 0, 0]);
+    long_field.extend_from_slice(&[0; 9]);
+    long_field.extend_from_slice(&bytes[16..]);
+    assert!(Share::from_wire(&long_field).is_err());
+}
@@ -0,0 +1,313 @@
+//! 零知识证明测试
+//! 
+//! 覆盖 Fiat-Shamir 转录、离散对数知识证明、离散对数相等证明、范围证明和洗牌证明：
+//! - 完备性：诚实证明总能通过验证
+//! - 可靠性：错误的证据、篡改的证明、不同的陈述或上下文都无法通过验证
+//! - 特殊可靠性：同一承诺下两个挑战的响应可以提取出证据
+
+use mpc_api::elliptic_curve::{Secp256k1Point, Secp256k1Scalar};
+use mpc_api::zero_knowledge::*;
+
+#[test]
+fn test_transcript_determinism_and_separation() {
+    let mut a = Transcript::new(b"test");
+    let mut b = Transcript::new(b"test");
+    a.append_message(b"m", b"hello");
+    b.append_message(b"m", b"hello");
+    assert_eq!(a.challenge_bytes::<48>(b"c"), b.challenge_bytes::<48>(b"c"));
+    assert_eq!(a.challenge_scalar(b"c"), b.challenge_scalar(b"c"));
+
+    // 域标签、消息划分和挑战标签都会改变挑战
+    let challenge = |transcript: &Transcript, label: &[u8]| transcript.clone().challenge_bytes::<32>(label);
+    let mut fresh = Transcript::new(b"test");
+    fresh.append_message(b"m", b"hello");
+    let mut other_domain = Transcript::new(b"test2");
+    other_domain.append_message(b"m", b"hello");
+    assert_ne!(challenge(&fresh, b"c"), challenge(&other_domain, b"c"));
+    assert_ne!(challenge(&fresh, b"c"), challenge(&fresh, b"d"));
+
+    let mut split_one = Transcript::new(b"test");
+    split_one.append_message(b"ab", b"c");
+    let mut split_two = Transcript::new(b"test");
+    split_two.append_message(b"a", b"bc");
+    assert_ne!(challenge(&split_one, b"c"), challenge(&split_two, b"c"));
+}
+
+#[test]
+fn test_dlog_proof() {
+    let x = Secp256k1Scalar::random();
+    let statement = DlogStatement::new(&x);
+    assert_eq!(statement.public, Secp256k1Point::generator() * x);
+
+    let proof = DlogProof::prove(&mut Transcript::new(b"dlog"), &statement, &x);
+    assert!(proof.verify(&mut Transcript::new(b"dlog"), &statement));
+
+    // 错误的证据
+    let wrong = DlogProof::prove(&mut Transcript::new(b"dlog"), &statement, &(x + Secp256k1Scalar::ONE));
+    assert!(!wrong.verify(&mut Transcript::new(b"dlog"), &statement));
+
+    // 篡改的响应、不同的陈述、不同的上下文
+    let mut tampered = proof.clone();
+    tampered.response = tampered.response + Secp256k1Scalar::ONE;
+    assert!(!tampered.verify(&mut Transcript::new(b"dlog"), &statement));
+    let other = DlogStatement::new(&Secp256k1Scalar::random());
+    assert!(!proof.verify(&mut Transcript::new(b"dlog"), &other));
+    assert!(!proof.verify(&mut Transcript::new(b"other"), &statement));
+
+    // 任意底点
+    let base = Secp256k1Point::generator() * Secp256k1Scalar::from_u64(5);
+    let statement = DlogStatement { base, public: base * x };
+    let proof = DlogProof::prove(&mut Transcript::new(b"dlog"), &statement, &x);
+    assert!(proof.verify(&mut Transcript::new(b"dlog"), &statement));
+
+    // 序列化往返
+    let bytes = bincode::serialize(&proof).unwrap();
+    let decoded: DlogProof = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(decoded, proof);
+    assert!(decoded.verify(&mut Transcript::new(b"dlog"), &statement));
+}
+
+#[test]
+fn test_dlog_equality_proof() {
+    let x = Secp256k1Scalar::random();
+    let g = Secp256k1Point::generator();
+    let h = g * Secp256k1Scalar::random();
+    let statement = DlogEqualityStatement::new(g, h, &x);
+
+    let mut transcript = Transcript::new(b"decryption-share");
+    transcript.append_u64(b"party", 1);
+    let proof = DlogEqualityProof::prove(&mut transcript.clone(), &statement, &x);
+    assert!(proof.verify(&mut transcript.clone(), &statement));
+
+    // 两个点的离散对数不同
+    let mismatched = DlogEqualityStatement { c: h * (x + Secp256k1Scalar::ONE), ..statement };
+    let proof = DlogEqualityProof::prove(&mut transcript.clone(), &mismatched, &x);
+    assert!(!proof.verify(&mut transcript.clone(), &mismatched));
+}
+
+#[test]
+fn test_interactive_execution_and_extraction() {
+    let x = Secp256k1Scalar::random();
+    let statement = DlogStatement::new(&x);
+
+    // 交互执行：验证方选择挑战
+    let (nonce, commitment) = DlogKnowledge::commit(&statement, &x);
+    let challenge = Secp256k1Scalar::random();
+    let response = DlogKnowledge::respond(&x, nonce, &challenge);
+    assert!(DlogKnowledge::check(&statement, &commitment, &challenge, &response));
+    assert!(!DlogKnowledge::check(&statement, &commitment, &(challenge + Secp256k1Scalar::ONE), &response));
+
+    // 特殊可靠性：同一随机数回答两个挑战会泄露 x = (s1 - s2) / (c1 - c2)
+    let h = Secp256k1Point::generator() * Secp256k1Scalar::from_u64(11);
+    let statement = DlogEqualityStatement::new(Secp256k1Point::generator(), h, &x);
+    let (nonce, commitment) = DlogEquality::commit(&statement, &x);
+    let (c1, c2) = (Secp256k1Scalar::random(), Secp256k1Scalar::random());
+    let s1 = DlogEquality::respond(&x, nonce, &c1);
+    let s2 = DlogEquality::respond(&x, nonce, &c2);
+    assert!(DlogEquality::check(&statement, &commitment, &c1, &s1));
+    assert!(DlogEquality::check(&statement, &commitment, &c2, &s2));
+    let extracted = (s1 - s2) * (c1 - c2).invert().unwrap();
+    assert_eq!(extracted, x);
+}
+
+#[test]
+fn test_range_proof() {
+    let generators = PedersenGenerators::default();
+    assert_eq!(generators, PedersenGenerators::default());
+    assert_ne!(generators.h, generators.g);
+
+    for (value, n_bits) in [(0u64, 1usize), (1, 1), (255, 8), (1 << 20, 32), (u64::MAX, 64)] {
+        let randomness = Secp256k1Scalar::random();
+        let commitment = generators.commit(value, &randomness);
+        let proof = prove_range(&commitment, value, &randomness, n_bits).unwrap();
+        assert_eq!(proof.commitment.len(), n_bits);
+        assert!(verify_range(&commitment, &proof, n_bits));
+    }
+
+    // 越界的值、错误的随机数、不支持的位数都无法生成证明
+    let randomness = Secp256k1Scalar::random();
+    let commitment = generators.commit(256, &randomness);
+    assert!(prove_range(&commitment, 256, &randomness, 8).is_err());
+    assert!(prove_range(&commitment, 256, &Secp256k1Scalar::random(), 16).is_err());
+    assert!(prove_range(&commitment, 256, &randomness, 0).is_err());
+    assert!(prove_range(&commitment, 256, &randomness, 65).is_err());
+
+    // 证明只对原承诺和原位数有效
+    let proof = prove_range(&commitment, 256, &randomness, 16).unwrap();
+    assert!(verify_range(&commitment, &proof, 16));
+    assert!(!verify_range(&commitment, &proof, 15));
+    assert!(!verify_range(&generators.commit(257, &randomness), &proof, 16));
+
+    // 篡改任意一位的承诺或响应都会被发现
+    let mut tampered = proof.clone();
+    tampered.commitment[3].commitment = tampered.commitment[3].commitment + generators.g;
+    assert!(!verify_range(&commitment, &tampered, 16));
+    let mut tampered = proof.clone();
+    tampered.response[0].c0 = tampered.response[0].c0 + Secp256k1Scalar::ONE;
+    assert!(!verify_range(&commitment, &tampered, 16));
+    let mut truncated = proof.clone();
+    truncated.commitment.pop();
+    truncated.response.pop();
+    assert!(!verify_range(&commitment, &truncated, 16));
+
+    let bytes = bincode::serialize(&proof).unwrap();
+    let decoded: RangeProof = bincode::deserialize(&bytes).unwrap();
+    assert!(verify_range(&commitment, &decoded, 16));
+}
+
+#[test]
+fn test_range_proof_for_bounded_bid() {
+    // 出价 v ∈ [100, 1000]：分别证明 v - 100 和 1000 - v 都小于 2^10
+    let generators = PedersenGenerators::default();
+    let (bid, randomness) = (730u64, Secp256k1Scalar::random());
+    let commitment = generators.commit(bid, &randomness);
+
+    let lower = commitment + -(generators.g * Secp256k1Scalar::from_u64(100));
+    let upper = generators.g * Secp256k1Scalar::from_u64(1000) + -commitment;
+    let mut transcript = Transcript::new(b"auction");
+    transcript.append_u64(b"bidder", 2);
+
+    let statement = |commitment| RangeStatement { generators, commitment, n_bits: 10 };
+    let lower_proof = RangeProof::prove_range(
+        &mut transcript.clone(), &statement(lower), &RangeWitness { value: bid - 100, randomness },
+    ).unwrap();
+    let upper_proof = RangeProof::prove_range(
+        &mut transcript.clone(), &statement(upper), &RangeWitness { value: 1000 - bid, randomness: -randomness },
+    ).unwrap();
+    assert!(lower_proof.verify(&mut transcript.clone(), &statement(lower)));
+    assert!(upper_proof.verify(&mut transcript.clone(), &statement(upper)));
+    assert!(!lower_proof.verify(&mut Transcript::new(b"auction"), &statement(lower)));
+
+    // 超出上界的出价：1000 - v 是标量域中的"负数"，无法用 u64 证据打开
+    let commitment = generators.commit(1001, &randomness);
+    let upper = generators.g * Secp256k1Scalar::from_u64(1000) + -commitment;
+    for value in [0u64, 1, u64::MAX] {
+        assert!(RangeProof::prove_range(
+            &mut transcript.clone(), &statement(upper), &RangeWitness { value, randomness: -randomness },
+        ).is_err());
+    }
+}
+
+#[cfg(feature = "he")]
+#[test]
+fn test_shuffle_proof() {
+    use mpc_api::homomorphic_encryption::*;
+
+    let (pk, sk) = ExponentialElGamal::keygen().unwrap();
+    let decrypt_sorted = |ciphertexts: &[ExponentialElGamalCiphertext]| {
+        let mut values: Vec<u64> = ciphertexts.iter().map(|c| ExponentialElGamal::decrypt(&sk, c).unwrap()).collect();
+        values.sort_unstable();
+        values
+    };
+    let inputs: Vec<_> = [5u64, 3, 9, 3, 0, 7].iter().map(|m| ExponentialElGamal::encrypt(&pk, m).unwrap()).collect();
+
+    // 两个混合者依次洗牌，每一步都可以单独验证
+    let (first, witness) = shuffle_ciphertexts(&pk, &inputs);
+    let first_proof = prove_shuffle(&pk, &inputs, &first, &witness).unwrap();
+    let (second, witness) = shuffle_ciphertexts(&pk, &first);
+    let second_proof = prove_shuffle(&pk, &first, &second, &witness).unwrap();
+    assert!(verify_shuffle(&pk, &inputs, &first, &first_proof));
+    assert!(verify_shuffle(&pk, &first, &second, &second_proof));
+    assert!(!verify_shuffle(&pk, &inputs, &second, &second_proof));
+    assert_eq!(decrypt_sorted(&second), vec![0, 3, 3, 5, 7, 9]);
+    assert!(second.iter().all(|c| !inputs.contains(c)));
+
+    // 单个密文也是合法的洗牌
+    let (single, witness) = shuffle_ciphertexts(&pk, &inputs[..1]);
+    assert!(verify_shuffle(&pk, &inputs[..1], &single, &prove_shuffle(&pk, &inputs[..1], &single, &witness).unwrap()));
+
+    let bytes = bincode::serialize(&first_proof).unwrap();
+    let decoded: ShuffleProof = bincode::deserialize(&bytes).unwrap();
+    assert!(verify_shuffle(&pk, &inputs, &first, &decoded));
+
+    // 绑定调用方的转录
+    let mut transcript = Transcript::new(b"election");
+    transcript.append_u64(b"mixer", 1);
+    let (outputs, witness) = shuffle_ciphertexts(&pk, &inputs);
+    let proof = ShuffleProof::prove(&mut transcript.clone(), &pk, &inputs, &outputs, &witness).unwrap();
+    assert!(proof.verify(&mut transcript.clone(), &pk, &inputs, &outputs));
+    assert!(!proof.verify(&mut Transcript::new(b"election"), &pk, &inputs, &outputs));
+}
+
+#[cfg(feature = "he")]
+#[test]
+fn test_shuffle_proof_rejects_cheating_mixer() {
+    use mpc_api::homomorphic_encryption::*;
+
+    let (pk, _) = ExponentialElGamal::keygen().unwrap();
+    let inputs: Vec<_> = (0..5u64).map(|m| ExponentialElGamal::encrypt(&pk, &m).unwrap()).collect();
+    let (outputs, witness) = shuffle_ciphertexts(&pk, &inputs);
+    let proof = prove_shuffle(&pk, &inputs, &outputs, &witness).unwrap();
+
+    // 替换一个输出、调换输出顺序、丢弃一个输出都会使验证失败
+    let mut replaced = outputs.clone();
+    replaced[2] = ExponentialElGamal::encrypt(&pk, &1).unwrap();
+    assert!(!verify_shuffle(&pk, &inputs, &replaced, &proof));
+    let mut swapped = outputs.clone();
+    swapped.swap(0, 1);
+    assert!(!verify_shuffle(&pk, &inputs, &swapped, &proof));
+    assert!(!verify_shuffle(&pk, &inputs, &outputs[..4], &proof));
+    let (other_pk, _) = ExponentialElGamal::keygen().unwrap();
+    assert!(!verify_shuffle(&other_pk, &inputs, &outputs, &proof));
+
+    // 篡改证明
+    let mut tampered = proof.clone();
+    tampered.response.s_prime[1] = tampered.response.s_prime[1] + Secp256k1Scalar::ONE;
+    assert!(!verify_shuffle(&pk, &inputs, &outputs, &tampered));
+    let mut tampered = proof.clone();
+    tampered.permutation_commitments.swap(0, 1);
+    assert!(!verify_shuffle(&pk, &inputs, &outputs, &tampered));
+
+    // 复制一个输入（不是置换）或证据与输出不符时无法生成证明
+    let mut duplicated = witness.clone();
+    duplicated.permutation[0] = duplicated.permutation[1];
+    assert!(prove_shuffle(&pk, &inputs, &outputs, &duplicated).is_err());
+    let mut wrong_randomness = witness.clone();
+    wrong_randomness.randomness[3] = Secp256k1Scalar::random();
+    assert!(prove_shuffle(&pk, &inputs, &outputs, &wrong_randomness).is_err());
+    assert!(prove_shuffle(&pk, &replaced, &outputs, &witness).is_err());
+    assert!(prove_shuffle(&pk, &[], &[], &ShuffleWitness { permutation: vec![], randomness: vec![] }).is_err());
+}
+
+#[test]
+fn test_product_proof() {
+    let generators = PedersenGenerators::default();
+    let (a, b) = (Secp256k1Scalar::random(), Secp256k1Scalar::random());
+    let witness = ProductWitness {
+        a,
+        r_a: Secp256k1Scalar::random(),
+        b,
+        r_b: Secp256k1Scalar::random(),
+        r_c: Secp256k1Scalar::random(),
+    };
+    let a_commitment = generators.commit_scalar(&a, &witness.r_a);
+    let b_commitment = generators.commit_scalar(&b, &witness.r_b);
+    let c_commitment = generators.commit_scalar(&(a * b), &witness.r_c);
+
+    let proof = prove_product(&a_commitment, &b_commitment, &c_commitment, &witness).unwrap();
+    assert!(verify_product(&a_commitment, &b_commitment, &c_commitment, &proof));
+    assert!(!verify_product(&b_commitment, &a_commitment, &c_commitment, &proof));
+    assert!(!verify_product(&a_commitment, &b_commitment, &(c_commitment + generators.g), &proof));
+
+    let mut tampered = proof.clone();
+    tampered.response.z_r = tampered.response.z_r + Secp256k1Scalar::ONE;
+    assert!(!verify_product(&a_commitment, &b_commitment, &c_commitment, &tampered));
+
+    // c ≠ a·b 或随机数不符时无法生成证明
+    let wrong_c = generators.commit_scalar(&(a * b + Secp256k1Scalar::ONE), &witness.r_c);
+    assert!(prove_product(&a_commitment, &b_commitment, &wrong_c, &witness).is_err());
+    let wrong_randomness = ProductWitness { r_b: Secp256k1Scalar::random(), ..witness };
+    assert!(prove_product(&a_commitment, &b_commitment, &c_commitment, &wrong_randomness).is_err());
+
+    // 绑定调用方的转录
+    let statement = ProductStatement { generators, a: a_commitment, b: b_commitment, c: c_commitment };
+    let mut transcript = Transcript::new(b"spdz-mac-check");
+    transcript.append_u64(b"gate", 17);
+    let proof = ProductProof::prove_product(&mut transcript.clone(), &statement, &witness).unwrap();
+    assert!(proof.verify(&mut transcript.clone(), &statement));
+    assert!(!proof.verify(&mut Transcript::new(b"spdz-mac-check"), &statement));
+
+    let bytes = bincode::serialize(&proof).unwrap();
+    let decoded: ProductProof = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(decoded, proof);
+}